@@ -0,0 +1,73 @@
+const SWAP_VECTORS_HEADER = "base_liquidity,quote_liquidity,swap_amount,providers_fee_rate_basis_points,protocol_fee_rate_basis_points,is_in_out,amount_out,providers_fee_amount,protocol_fee_amount,price_impact_basis_points";
+
+type SwapOutcome = {
+    amountOut: bigint;
+    providersFeeAmount: bigint;
+    protocolFeeAmount: bigint;
+    priceImpactBasisPoints: bigint;
+};
+
+type SwapVector = {
+    baseLiquidity: bigint;
+    quoteLiquidity: bigint;
+    swapAmount: bigint;
+    providersFeeRateBasisPoints: number;
+    protocolFeeRateBasisPoints: number;
+    isInOut: boolean;
+    expected: SwapOutcome | null;
+};
+
+/**
+ * Parses the golden swap vectors published in `crates/cpmm-math/golden/swap_vectors.csv`.
+ *
+ * The expected outcomes are produced by the on-chain swap math, so quoting every vector proves
+ * bit-exact agreement of a client with the program.
+ * @param {string} csv - The content of the vectors table.
+ * @returns {SwapVector[]} - The vectors, with a null outcome for swaps the program rejects.
+ */
+export const parseSwapVectors = (csv: string): SwapVector[] => {
+    const lines = csv.split("\n").map((line) => line.trim()).filter((line) => line != "" && !line.startsWith("#"));
+    if (lines[0] != SWAP_VECTORS_HEADER) {
+        throw new Error("Malformed swap vectors header");
+    }
+    return lines.slice(1).map((line) => {
+        const columns = line.split(",");
+        if (columns.length != 10 || !["true", "false"].includes(columns[5])) {
+            throw new Error(`Malformed swap vector ${line}`);
+        }
+        const outcome = columns.slice(6);
+        return {
+            baseLiquidity: BigInt(columns[0]),
+            quoteLiquidity: BigInt(columns[1]),
+            swapAmount: BigInt(columns[2]),
+            providersFeeRateBasisPoints: Number(columns[3]),
+            protocolFeeRateBasisPoints: Number(columns[4]),
+            isInOut: columns[5] == "true",
+            expected: outcome.every((column) => column == "-") ? null : {
+                amountOut: BigInt(outcome[0]),
+                providersFeeAmount: BigInt(outcome[1]),
+                protocolFeeAmount: BigInt(outcome[2]),
+                priceImpactBasisPoints: BigInt(outcome[3]),
+            },
+        };
+    });
+};
+
+/**
+ * Returns the golden swap vectors a swap quoting function disagrees with.
+ * @param {SwapVector[]} vectors - The parsed vectors.
+ * @param {(vector: SwapVector) => SwapOutcome | null} quoteSwap - Quotes the swap of a vector in a pool freshly launched with its liquidity, null if the swap fails.
+ * @returns {SwapVector[]} - The vectors whose quote differs from the expected outcome.
+ */
+export const findSwapVectorMismatches = (vectors: SwapVector[], quoteSwap: (vector: SwapVector) => SwapOutcome | null): SwapVector[] => {
+    return vectors.filter((vector) => {
+        const quote = quoteSwap(vector);
+        if (quote == null || vector.expected == null) {
+            return quote != vector.expected;
+        }
+        return quote.amountOut != vector.expected.amountOut ||
+            quote.providersFeeAmount != vector.expected.providersFeeAmount ||
+            quote.protocolFeeAmount != vector.expected.protocolFeeAmount ||
+            quote.priceImpactBasisPoints != vector.expected.priceImpactBasisPoints;
+    });
+};
\ No newline at end of file
@@ -0,0 +1,69 @@
+import {
+    Address,
+    AddressesByLookupTableAddress,
+    getAddressEncoder,
+    getProgramDerivedAddress,
+    ProgramDerivedAddress,
+} from "@solana/web3.js";
+import {SYSTEM_PROGRAM_ADDRESS} from "@solana-program/system";
+import {ASSOCIATED_TOKEN_PROGRAM_ADDRESS} from "@solana-program/token";
+import {CpAmm, CPMM_PROGRAM_ADDRESS} from "./generated";
+
+type CpAmmTokenPrograms = {
+    baseTokenProgram: Address;
+    quoteTokenProgram: Address;
+    lpTokenProgram: Address;
+};
+
+/**
+ * Retrieves the PDA registering the Address Lookup Table of a constant product AMM.
+ * @param {Address} cpAmm - The address of the constant product AMM.
+ * @returns {Promise<ProgramDerivedAddress>} - The derived address for the pool lookup table registry entry.
+ */
+export const getPoolLookupTablePDA = async (cpAmm: Address): Promise<ProgramDerivedAddress> => {
+    return await getProgramDerivedAddress({
+        programAddress: CPMM_PROGRAM_ADDRESS,
+        seeds: ["pool_lookup_table", getAddressEncoder().encode(cpAmm)]
+    });
+};
+
+/**
+ * Returns the addresses to store in the Address Lookup Table of a constant product AMM.
+ *
+ * The pool, its config, mints and vaults come first, they are required by `register_cp_amm_lookup_table`.
+ * The programs referenced by the pool instructions follow, duplicates are skipped.
+ * @param {Address} cpAmm - The address of the constant product AMM.
+ * @param {CpAmm} cpAmmData - The decoded constant product AMM account.
+ * @param {CpAmmTokenPrograms} tokenPrograms - The token programs of the base, quote and LP mints.
+ * @returns {Address[]} - The addresses to extend the lookup table with.
+ */
+export const getCpAmmLookupTableAddresses = (cpAmm: Address, cpAmmData: CpAmm, tokenPrograms: CpAmmTokenPrograms): Address[] => {
+    const addresses = [
+        cpAmm,
+        cpAmmData.ammsConfig,
+        cpAmmData.baseMint,
+        cpAmmData.quoteMint,
+        cpAmmData.lpMint,
+        cpAmmData.baseVault,
+        cpAmmData.quoteVault,
+        cpAmmData.lockedLpVault,
+        tokenPrograms.baseTokenProgram,
+        tokenPrograms.quoteTokenProgram,
+        tokenPrograms.lpTokenProgram,
+        ASSOCIATED_TOKEN_PROGRAM_ADDRESS,
+        SYSTEM_PROGRAM_ADDRESS,
+        CPMM_PROGRAM_ADDRESS,
+    ];
+    return [...new Set(addresses)];
+};
+
+/**
+ * Maps a lookup table to its addresses, as expected by `compressTransactionMessageUsingAddressLookupTables`
+ * to build v0 transactions referencing the pool accounts through the table.
+ * @param {Address} lookupTable - The address of the Address Lookup Table.
+ * @param {Address[]} addresses - The addresses stored in the table, in their order within the table.
+ * @returns {AddressesByLookupTableAddress} - The lookup table addresses mapping.
+ */
+export const getAddressesByLookupTable = (lookupTable: Address, addresses: Address[]): AddressesByLookupTableAddress => {
+    return {[lookupTable]: addresses};
+};
\ No newline at end of file
@@ -0,0 +1,75 @@
+import {isSome} from "@solana/web3.js";
+import {Extension, Mint as Token22Mint} from "@solana-program/token-2022";
+
+const ONE_IN_BASIS_POINTS = BigInt(10_000);
+
+type TransferFee = {
+    epoch: number | bigint;
+    maximumFee: number | bigint;
+    transferFeeBasisPoints: number;
+};
+
+/**
+ * Returns the transfer fee of a Token-2022 mint effective in the given epoch.
+ * @param {Token22Mint} mint - The Token-2022 mint.
+ * @param {bigint} epoch - The epoch of the transfer.
+ * @returns {TransferFee | null} - The effective transfer fee, or null if the mint has no TransferFeeConfig extension.
+ */
+export const getEpochTransferFee = (mint: Token22Mint, epoch: bigint): TransferFee | null => {
+    if (!isSome(mint.extensions)) {
+        return null;
+    }
+    const config = mint.extensions.value.find(
+        (extension): extension is Extract<Extension, { __kind: "TransferFeeConfig" }> => extension.__kind == "TransferFeeConfig"
+    );
+    if (!config) {
+        return null;
+    }
+    return epoch >= BigInt(config.newerTransferFee.epoch) ? config.newerTransferFee : config.olderTransferFee;
+};
+
+/**
+ * Calculates the transfer fee charged on a gross amount, mirroring the Token-2022 program.
+ * @param {TransferFee} transferFee - The effective transfer fee.
+ * @param {bigint} grossAmount - The transferred amount.
+ * @returns {bigint} - The fee withheld from the transfer.
+ */
+export const calculateTransferFee = (transferFee: TransferFee, grossAmount: bigint): bigint => {
+    const basisPoints = BigInt(transferFee.transferFeeBasisPoints);
+    if (basisPoints == BigInt(0) || grossAmount == BigInt(0)) {
+        return BigInt(0);
+    }
+    const rawFee = (grossAmount * basisPoints + ONE_IN_BASIS_POINTS - BigInt(1)) / ONE_IN_BASIS_POINTS;
+    const maximumFee = BigInt(transferFee.maximumFee);
+    return rawFee < maximumFee ? rawFee : maximumFee;
+};
+
+/**
+ * Calculates the gross wallet amount to pass as a swap amount so that the pool receives
+ * exactly `netAmount` tokens. Matches the on-chain `quote_gross_swap_input` instruction.
+ * @param {Token22Mint} mint - The input Token-2022 mint. For SPL Token mints the gross amount equals the net amount.
+ * @param {bigint} netAmount - The desired net input into the pool.
+ * @param {bigint} epoch - The epoch of the swap.
+ * @returns {bigint} - The gross amount to transfer.
+ * @throws If no gross amount yields exactly `netAmount`.
+ */
+export const calculateGrossTransferAmount = (mint: Token22Mint, netAmount: bigint, epoch: bigint): bigint => {
+    const transferFee = getEpochTransferFee(mint, epoch);
+    if (!transferFee || transferFee.transferFeeBasisPoints == 0 || netAmount == BigInt(0)) {
+        return netAmount;
+    }
+    const basisPoints = BigInt(transferFee.transferFeeBasisPoints);
+    const maximumFee = BigInt(transferFee.maximumFee);
+    let grossAmount: bigint;
+    if (basisPoints == ONE_IN_BASIS_POINTS) {
+        grossAmount = netAmount + maximumFee;
+    } else {
+        const denominator = ONE_IN_BASIS_POINTS - basisPoints;
+        const rawGrossAmount = (netAmount * ONE_IN_BASIS_POINTS + denominator - BigInt(1)) / denominator;
+        grossAmount = rawGrossAmount - netAmount >= maximumFee ? netAmount + maximumFee : rawGrossAmount;
+    }
+    if (grossAmount - calculateTransferFee(transferFee, grossAmount) != netAmount) {
+        throw new Error("No gross amount yields the requested net amount");
+    }
+    return grossAmount;
+};
\ No newline at end of file
@@ -0,0 +1,63 @@
+//! Measures the CPU cost of the `Q64_64` and `Q64_128` operations on the constant product hot path.
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use cpmm_bench::analysis::{calculate_swap_result, calculate_withdraw_result, sample_swap, sample_withdraw, FixedPoint, SplitMix64, SCENARIOS};
+use cpmm_bench::Q64_64;
+use cpmm_math::Q64_128;
+
+/// The number of pregenerated operations each benchmark iterates over.
+const OPERATIONS: usize = 1024;
+
+fn bench_type<T: FixedPoint>(criterion: &mut Criterion) {
+    let mut group = criterion.benchmark_group(T::NAME);
+    for scenario in SCENARIOS.iter() {
+        let mut rng = SplitMix64::new(42);
+        let swaps: Vec<_> = (0..OPERATIONS).map(|_| sample_swap(&mut rng, scenario)).collect();
+        group.bench_with_input(BenchmarkId::new("sqrt_from_u128", scenario.name), &swaps, |bencher, swaps| {
+            bencher.iter(|| {
+                for (base_liquidity, quote_liquidity, _) in swaps {
+                    black_box(T::sqrt_from_u128(*base_liquidity as u128 * *quote_liquidity as u128));
+                }
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("square_as_u128", scenario.name), &swaps, |bencher, swaps| {
+            let roots: Vec<T> = swaps.iter().map(|(base_liquidity, quote_liquidity, _)| T::sqrt_from_u128(*base_liquidity as u128 * *quote_liquidity as u128)).collect();
+            bencher.iter(|| {
+                for root in &roots {
+                    black_box(black_box(*root).square_as_u128());
+                }
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("checked_div", scenario.name), &swaps, |bencher, swaps| {
+            bencher.iter(|| {
+                for (base_liquidity, quote_liquidity, swap_amount) in swaps {
+                    let constant_product = T::from_u128(*base_liquidity as u128 * *quote_liquidity as u128);
+                    black_box(constant_product.checked_div(T::from_u128((base_liquidity + swap_amount) as u128)));
+                }
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("swap", scenario.name), &swaps, |bencher, swaps| {
+            bencher.iter(|| {
+                for (base_liquidity, quote_liquidity, swap_amount) in swaps {
+                    black_box(calculate_swap_result::<T>(*base_liquidity, *quote_liquidity, *swap_amount));
+                }
+            })
+        });
+        let withdrawals: Vec<_> = (0..OPERATIONS).map(|_| sample_withdraw(&mut rng, scenario)).collect();
+        group.bench_with_input(BenchmarkId::new("withdraw", scenario.name), &withdrawals, |bencher, withdrawals| {
+            bencher.iter(|| {
+                for (base_liquidity, quote_liquidity, lp_tokens_supply, lp_tokens) in withdrawals {
+                    black_box(calculate_withdraw_result::<T>(*base_liquidity, *quote_liquidity, *lp_tokens_supply, *lp_tokens));
+                }
+            })
+        });
+    }
+    group.finish();
+}
+
+fn bench_fixed_point(criterion: &mut Criterion) {
+    bench_type::<Q64_64>(criterion);
+    bench_type::<Q64_128>(criterion);
+}
+
+criterion_group!(benches, bench_fixed_point);
+criterion_main!(benches);
\ No newline at end of file
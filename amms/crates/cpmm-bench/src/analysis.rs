@@ -0,0 +1,440 @@
+//! Precision analysis of the constant product hot path under both fixed-point types.
+//!
+//! Every sample draws a pool of the scenario magnitudes and a swap or a withdrawal of a log-uniform
+//! share of its liquidity, then runs the math of `cpmm_math::cp_amm` with each type and compares
+//! the resulting amounts against the exact integer results. Samples come from a seeded `SplitMix64`,
+//! so the same seed always produces the same report.
+use cpmm_math::Q64_128;
+use crate::Q64_64;
+
+/// The operations of the constant product hot path, implemented by both fixed-point types.
+pub trait FixedPoint: Copy {
+    /// The name of the type in reports.
+    const NAME: &'static str;
+
+    /// Creates a value from an integer.
+    fn from_u64(value: u64) -> Self;
+
+    /// Computes the square root of a `u128` value.
+    fn sqrt_from_u128(value: u128) -> Self;
+
+    /// Computes the square root of the division of two values, returning `None` on division by zero.
+    fn checked_div_sqrt(q1: Self, q2: Self) -> Option<Self>;
+
+    /// Computes the square of the value rounded to a `u128`.
+    fn square_as_u128(self) -> u128;
+
+    /// Creates a value from the raw Q64.64 bits of a `u128`.
+    fn from_u128(value: u128) -> Self;
+
+    /// Multiplies two values, returning `None` on overflow.
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+
+    /// Divides two values, returning `None` on division by zero or overflow.
+    fn checked_div(self, rhs: Self) -> Option<Self>;
+
+    /// Converts the value to a `u64` with rounding.
+    fn as_u64_round(self) -> u64;
+}
+
+impl FixedPoint for Q64_128 {
+    const NAME: &'static str = "Q64_128";
+
+    #[inline]
+    fn from_u64(value: u64) -> Self {
+        Q64_128::from_u64(value)
+    }
+
+    #[inline]
+    fn sqrt_from_u128(value: u128) -> Self {
+        Q64_128::sqrt_from_u128(value)
+    }
+
+    #[inline]
+    fn checked_div_sqrt(q1: Self, q2: Self) -> Option<Self> {
+        Q64_128::checked_div_sqrt(q1, q2)
+    }
+
+    #[inline]
+    fn square_as_u128(self) -> u128 {
+        Q64_128::square_as_u128(self)
+    }
+
+    #[inline]
+    fn from_u128(value: u128) -> Self {
+        Q64_128::from_u128(value)
+    }
+
+    #[inline]
+    fn checked_mul(self, rhs: Self) -> Option<Self> {
+        Q64_128::checked_mul(self, rhs)
+    }
+
+    #[inline]
+    fn checked_div(self, rhs: Self) -> Option<Self> {
+        Q64_128::checked_div(self, rhs)
+    }
+
+    #[inline]
+    fn as_u64_round(self) -> u64 {
+        Q64_128::as_u64_round(&self)
+    }
+}
+
+impl FixedPoint for Q64_64 {
+    const NAME: &'static str = "Q64_64";
+
+    #[inline]
+    fn from_u64(value: u64) -> Self {
+        Q64_64::from_u64(value)
+    }
+
+    #[inline]
+    fn sqrt_from_u128(value: u128) -> Self {
+        Q64_64::sqrt_from_u128(value)
+    }
+
+    #[inline]
+    fn checked_div_sqrt(q1: Self, q2: Self) -> Option<Self> {
+        Q64_64::checked_div_sqrt(q1, q2)
+    }
+
+    #[inline]
+    fn square_as_u128(self) -> u128 {
+        Q64_64::square_as_u128(self)
+    }
+
+    #[inline]
+    fn from_u128(value: u128) -> Self {
+        Q64_64::from_u128(value)
+    }
+
+    #[inline]
+    fn checked_mul(self, rhs: Self) -> Option<Self> {
+        Q64_64::checked_mul(self, rhs)
+    }
+
+    #[inline]
+    fn checked_div(self, rhs: Self) -> Option<Self> {
+        Q64_64::checked_div(self, rhs)
+    }
+
+    #[inline]
+    fn as_u64_round(self) -> u64 {
+        Q64_64::as_u64_round(&self)
+    }
+}
+
+/// Calculates the output amount of a swap of base tokens in, as `cpmm_math::cp_amm` does.
+///
+/// The constant product square root is recalculated from the liquidity, and the new quote
+/// liquidity is the rounded division of the squared constant product by the new base liquidity.
+///
+/// # Parameters
+/// - `base_liquidity`: The base liquidity.
+/// - `quote_liquidity`: The quote liquidity.
+/// - `swap_amount`: The base tokens swapped in.
+///
+/// # Returns
+/// - `Some(u64)` with the quote tokens swapped out.
+/// - `None` if the calculation fails.
+#[inline]
+pub fn calculate_swap_result<T: FixedPoint>(base_liquidity: u64, quote_liquidity: u64, swap_amount: u64) -> Option<u64> {
+    let constant_product_sqrt = T::sqrt_from_u128(base_liquidity as u128 * quote_liquidity as u128);
+    let new_base_liquidity = base_liquidity.checked_add(swap_amount)?;
+    let constant_product = constant_product_sqrt.square_as_u128();
+    let new_quote_liquidity = T::from_u128(constant_product).checked_div(T::from_u128(new_base_liquidity as u128))?.as_u64_round();
+    quote_liquidity.checked_sub(new_quote_liquidity)
+}
+
+/// Calculates the exact output amount of a swap of base tokens in, with the new quote liquidity
+/// rounded half up as the fixed-point calculation does.
+///
+/// # Parameters
+/// - `base_liquidity`: The base liquidity.
+/// - `quote_liquidity`: The quote liquidity.
+/// - `swap_amount`: The base tokens swapped in.
+///
+/// # Returns
+/// - `Some(u64)` with the quote tokens swapped out.
+/// - `None` if the new base liquidity overflows.
+pub fn calculate_exact_swap_result(base_liquidity: u64, quote_liquidity: u64, swap_amount: u64) -> Option<u64> {
+    let new_base_liquidity = base_liquidity.checked_add(swap_amount)?;
+    let new_quote_liquidity = div_round(base_liquidity as u128 * quote_liquidity as u128, new_base_liquidity as u128);
+    quote_liquidity.checked_sub(new_quote_liquidity as u64)
+}
+
+/// Calculates the base and quote tokens withdrawn for LP tokens, as `cpmm_math::cp_amm` does.
+///
+/// The share of the constant product square root is converted back to the liquidity of each
+/// side through the square root of the base-to-quote liquidity ratio.
+///
+/// # Parameters
+/// - `base_liquidity`: The base liquidity.
+/// - `quote_liquidity`: The quote liquidity.
+/// - `lp_tokens_supply`: The LP tokens supply.
+/// - `lp_tokens`: The LP tokens redeemed.
+///
+/// # Returns
+/// - `Some((u64, u64))` with the base and quote tokens withdrawn.
+/// - `None` if the calculation fails.
+#[inline]
+pub fn calculate_withdraw_result<T: FixedPoint>(base_liquidity: u64, quote_liquidity: u64, lp_tokens_supply: u64, lp_tokens: u64) -> Option<(u64, u64)> {
+    let constant_product_sqrt = T::sqrt_from_u128(base_liquidity as u128 * quote_liquidity as u128);
+    let base_quote_ratio_sqrt = T::checked_div_sqrt(T::from_u64(base_liquidity), T::from_u64(quote_liquidity))?;
+    let liquidity_share = T::from_u64(lp_tokens).checked_div(T::from_u64(lp_tokens_supply))?;
+    let constant_product_sqrt_share = constant_product_sqrt.checked_mul(liquidity_share)?;
+    let base_withdraw = constant_product_sqrt_share.checked_mul(base_quote_ratio_sqrt)?.as_u64_round();
+    let quote_withdraw = constant_product_sqrt_share.checked_div(base_quote_ratio_sqrt)?.as_u64_round();
+    Some((base_withdraw, quote_withdraw))
+}
+
+/// Calculates the exact base and quote tokens withdrawn for LP tokens, rounded half up.
+///
+/// # Parameters
+/// - `base_liquidity`: The base liquidity.
+/// - `quote_liquidity`: The quote liquidity.
+/// - `lp_tokens_supply`: The LP tokens supply.
+/// - `lp_tokens`: The LP tokens redeemed.
+pub fn calculate_exact_withdraw_result(base_liquidity: u64, quote_liquidity: u64, lp_tokens_supply: u64, lp_tokens: u64) -> (u64, u64) {
+    (
+        div_round(base_liquidity as u128 * lp_tokens as u128, lp_tokens_supply as u128) as u64,
+        div_round(quote_liquidity as u128 * lp_tokens as u128, lp_tokens_supply as u128) as u64,
+    )
+}
+
+/// Divides two integers with rounding half up.
+fn div_round(numerator: u128, denominator: u128) -> u128 {
+    let remainder = numerator % denominator;
+    numerator / denominator + (remainder >= denominator - remainder) as u128
+}
+
+/// An operation of the hot path the analysis measures.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operation {
+    /// A swap of base tokens in, compared on the quote tokens out.
+    Swap,
+    /// A withdrawal of LP tokens, compared on the worse of the base and quote tokens out.
+    Withdraw,
+}
+
+impl Operation {
+    /// All measured operations.
+    pub const ALL: [Operation; 2] = [Operation::Swap, Operation::Withdraw];
+
+    /// Returns the name of the operation in reports.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Swap => "swap",
+            Self::Withdraw => "withdraw",
+        }
+    }
+}
+
+/// A range of pool magnitudes the analysis samples from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Scenario {
+    /// The name of the scenario in reports.
+    pub name: &'static str,
+    /// The decimal exponent of the base liquidity, drawn from `[10^e, 10^(e + 1))`.
+    pub base_exponent: u32,
+    /// The decimal exponent of the quote liquidity, drawn from `[10^e, 10^(e + 1))`.
+    pub quote_exponent: u32,
+}
+
+/// The pool magnitudes seen on mainnet, from small pools of 6 decimals tokens to
+/// large pools of 9 decimals tokens, and strongly imbalanced pools.
+pub const SCENARIOS: [Scenario; 7] = [
+    Scenario { name: "small", base_exponent: 6, quote_exponent: 6 },
+    Scenario { name: "medium", base_exponent: 10, quote_exponent: 10 },
+    Scenario { name: "large", base_exponent: 14, quote_exponent: 14 },
+    Scenario { name: "huge", base_exponent: 18, quote_exponent: 18 },
+    Scenario { name: "memecoin", base_exponent: 17, quote_exponent: 9 },
+    Scenario { name: "stable-wrapped", base_exponent: 9, quote_exponent: 15 },
+    Scenario { name: "dust-quote", base_exponent: 15, quote_exponent: 3 },
+];
+
+/// The error distribution of the results of one operation with one type in one scenario.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ErrorDistribution {
+    /// The number of samples.
+    pub samples: usize,
+    /// The number of samples the calculation failed for.
+    pub failures: usize,
+    /// The number of samples matching the exact result.
+    pub exact: usize,
+    /// The mean absolute error in tokens.
+    pub mean_error: f64,
+    /// The median absolute error in tokens.
+    pub median_error: u64,
+    /// The 99th percentile of the absolute error in tokens.
+    pub p99_error: u64,
+    /// The maximal absolute error in tokens.
+    pub max_error: u64,
+    /// The maximal error relative to the exact result, in parts per million.
+    pub max_relative_error_ppm: f64,
+}
+
+impl ErrorDistribution {
+    /// Builds the distribution from the absolute and relative errors of the successful samples.
+    fn from_errors(mut errors: Vec<(u64, f64)>, failures: usize) -> Self {
+        errors.sort_unstable_by_key(|(error, _)| *error);
+        let percentile = |share: usize| errors.get((errors.len().saturating_sub(1)) * share / 100).map_or(0, |(error, _)| *error);
+        Self {
+            samples: errors.len() + failures,
+            failures,
+            exact: errors.iter().filter(|(error, _)| *error == 0).count(),
+            mean_error: errors.iter().map(|(error, _)| *error as f64).sum::<f64>() / errors.len().max(1) as f64,
+            median_error: percentile(50),
+            p99_error: percentile(99),
+            max_error: errors.last().map_or(0, |(error, _)| *error),
+            max_relative_error_ppm: errors.iter().map(|(_, relative)| *relative).fold(0.0, f64::max) * 1e6,
+        }
+    }
+}
+
+/// Measures the error distribution of the results of an operation with a type in a scenario.
+///
+/// # Parameters
+/// - `operation`: The measured operation.
+/// - `scenario`: The pool magnitudes to sample.
+/// - `samples`: The number of sampled operations.
+/// - `seed`: The seed of the sampling.
+pub fn measure<T: FixedPoint>(operation: Operation, scenario: &Scenario, samples: usize, seed: u64) -> ErrorDistribution {
+    let mut rng = SplitMix64::new(seed);
+    let mut errors = Vec::with_capacity(samples);
+    let mut failures = 0;
+    for _ in 0..samples {
+        let error = match operation {
+            Operation::Swap => {
+                let (base_liquidity, quote_liquidity, swap_amount) = sample_swap(&mut rng, scenario);
+                calculate_swap_result::<T>(base_liquidity, quote_liquidity, swap_amount)
+                    .zip(calculate_exact_swap_result(base_liquidity, quote_liquidity, swap_amount))
+                    .map(|(result, exact)| relative_error(result, exact))
+            }
+            Operation::Withdraw => {
+                let (base_liquidity, quote_liquidity, lp_tokens_supply, lp_tokens) = sample_withdraw(&mut rng, scenario);
+                let (exact_base, exact_quote) = calculate_exact_withdraw_result(base_liquidity, quote_liquidity, lp_tokens_supply, lp_tokens);
+                calculate_withdraw_result::<T>(base_liquidity, quote_liquidity, lp_tokens_supply, lp_tokens)
+                    .map(|(base, quote)| {
+                        let ((base_error, base_relative_error), (quote_error, quote_relative_error)) = (relative_error(base, exact_base), relative_error(quote, exact_quote));
+                        (base_error.max(quote_error), base_relative_error.max(quote_relative_error))
+                    })
+            }
+        };
+        match error {
+            Some(error) => errors.push(error),
+            None => failures += 1,
+        }
+    }
+    ErrorDistribution::from_errors(errors, failures)
+}
+
+/// Returns the absolute error of a result and the error relative to the exact result.
+fn relative_error(result: u64, exact: u64) -> (u64, f64) {
+    let error = result.abs_diff(exact);
+    (error, error as f64 / exact.max(1) as f64)
+}
+
+/// Draws a pool of the scenario magnitudes and a swap of 0.0001% to 10% of its base liquidity.
+pub fn sample_swap(rng: &mut SplitMix64, scenario: &Scenario) -> (u64, u64, u64) {
+    let base_liquidity = rng.magnitude(scenario.base_exponent);
+    let quote_liquidity = rng.magnitude(scenario.quote_exponent);
+    let swap_amount = rng.share(base_liquidity);
+    (base_liquidity, quote_liquidity, swap_amount)
+}
+
+/// Draws a pool of the scenario magnitudes, its LP tokens supply minted at launch, and a withdrawal
+/// of 0.0001% to 10% of the supply.
+pub fn sample_withdraw(rng: &mut SplitMix64, scenario: &Scenario) -> (u64, u64, u64, u64) {
+    let base_liquidity = rng.magnitude(scenario.base_exponent);
+    let quote_liquidity = rng.magnitude(scenario.quote_exponent);
+    let lp_tokens_supply = (base_liquidity as u128 * quote_liquidity as u128).isqrt() as u64;
+    let lp_tokens = rng.share(lp_tokens_supply);
+    (base_liquidity, quote_liquidity, lp_tokens_supply, lp_tokens)
+}
+
+/// A small deterministic pseudo-random generator, the SplitMix64 of the `cpmm` simulation.
+#[derive(Debug, Clone)]
+pub struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    /// Creates a generator from a seed.
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// Returns the next pseudo-random `u64`.
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a value in `[0, 1)`.
+    pub fn unit(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Returns a log-uniform share of 0.0001% to 10% of a value, at least `1`.
+    pub fn share(&mut self, value: u64) -> u64 {
+        let share = 10f64.powf(-6.0 + 5.0 * self.unit());
+        ((value as f64 * share) as u64).max(1)
+    }
+
+    /// Returns a value in `[10^exponent, 10^(exponent + 1))`, capped to `u64::MAX`.
+    pub fn magnitude(&mut self, exponent: u32) -> u64 {
+        let low = 10u64.pow(exponent);
+        let high = 10u64.checked_pow(exponent + 1).unwrap_or(u64::MAX);
+        low + self.next_u64() % (high - low)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tests that both types reproduce the swap math of `cpmm_math::cp_amm`.
+    #[test]
+    fn test_calculate_swap_result() {
+        let (base_liquidity, quote_liquidity, swap_amount) = (1_000_000_000u64, 2_000_000_000u64, 10_000_000u64);
+        let constant_product_sqrt = cpmm_math::cp_amm::calculate_constant_product_sqrt(base_liquidity, quote_liquidity).unwrap();
+        let (_, new_quote_liquidity) = cpmm_math::cp_amm::calculate_afterswap_liquidity(constant_product_sqrt, base_liquidity, quote_liquidity, swap_amount, true).unwrap();
+
+        assert_eq!(calculate_swap_result::<Q64_128>(base_liquidity, quote_liquidity, swap_amount), Some(quote_liquidity - new_quote_liquidity));
+        assert_eq!(calculate_exact_swap_result(base_liquidity, quote_liquidity, swap_amount), Some(19_801_980));
+        assert_eq!(calculate_swap_result::<Q64_64>(base_liquidity, quote_liquidity, swap_amount), Some(19_801_980));
+        assert_eq!(calculate_swap_result::<Q64_64>(u64::MAX, 1, 1), None);
+    }
+
+    /// Tests that `Q64_128` reproduces the withdrawal math of `cpmm_math::cp_amm`.
+    #[test]
+    fn test_calculate_withdraw_result() {
+        let (base_liquidity, quote_liquidity, lp_tokens_supply, lp_tokens) = (1_000_000_000u64, 4_000_000_000u64, 2_000_000_000u64, 30_000_000u64);
+        let constant_product_sqrt = cpmm_math::cp_amm::calculate_constant_product_sqrt(base_liquidity, quote_liquidity).unwrap();
+        let base_quote_ratio_sqrt = cpmm_math::cp_amm::calculate_base_quote_ratio_sqrt(base_liquidity, quote_liquidity).unwrap();
+        let expected = cpmm_math::cp_amm::calculate_liquidity_from_share(constant_product_sqrt, base_quote_ratio_sqrt, lp_tokens_supply, lp_tokens);
+
+        assert_eq!(calculate_withdraw_result::<Q64_128>(base_liquidity, quote_liquidity, lp_tokens_supply, lp_tokens), expected);
+        assert_eq!(calculate_exact_withdraw_result(base_liquidity, quote_liquidity, lp_tokens_supply, lp_tokens), (15_000_000, 60_000_000));
+        assert_eq!(calculate_withdraw_result::<Q64_64>(base_liquidity, quote_liquidity, lp_tokens_supply, lp_tokens), Some((15_000_000, 60_000_000)));
+    }
+
+    /// Tests that the measurement is deterministic and that `Q64_128` is at least as precise as `Q64_64`.
+    #[test]
+    fn test_measure() {
+        for scenario in SCENARIOS.iter() {
+            for operation in Operation::ALL {
+                let q64_128 = measure::<Q64_128>(operation, scenario, 200, 42);
+                let q64_64 = measure::<Q64_64>(operation, scenario, 200, 42);
+                assert_eq!(q64_128, measure::<Q64_128>(operation, scenario, 200, 42));
+                assert_eq!(q64_128.samples, 200);
+                assert!(q64_128.max_error <= q64_64.max_error, "{} {}", operation.name(), scenario.name);
+            }
+        }
+    }
+}
\ No newline at end of file
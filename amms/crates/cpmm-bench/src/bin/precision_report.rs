@@ -0,0 +1,58 @@
+//! Prints the error distributions of the swap and withdrawal results of both fixed-point types as CSV.
+//!
+//! Usage: `cargo run -p cpmm-bench --release --bin precision-report -- [--seed N] [--samples N]`
+use std::process::ExitCode;
+use cpmm_bench::analysis::{measure, ErrorDistribution, FixedPoint, Operation, Scenario, SCENARIOS};
+use cpmm_bench::Q64_64;
+use cpmm_math::Q64_128;
+
+fn parse_args(args: impl Iterator<Item = String>) -> Result<(u64, usize), String> {
+    let (mut seed, mut samples) = (42, 100_000);
+    let mut args = args.peekable();
+    while let Some(flag) = args.next() {
+        let value = args.next().ok_or_else(|| format!("missing value for {}", flag))?;
+        let invalid = |_| format!("invalid value for {}: {}", flag, value);
+        match flag.as_str() {
+            "--seed" => seed = value.parse().map_err(invalid)?,
+            "--samples" => samples = value.parse().map_err(invalid)?,
+            _ => return Err(format!("unknown argument: {}", flag)),
+        }
+    }
+    Ok((seed, samples))
+}
+
+fn print_row<T: FixedPoint>(operation: Operation, scenario: &Scenario, distribution: &ErrorDistribution) {
+    println!(
+        "{},{},{},{},{},{},{},{:.6},{},{},{},{:.6}",
+        operation.name(),
+        scenario.name,
+        scenario.base_exponent,
+        scenario.quote_exponent,
+        T::NAME,
+        distribution.samples,
+        distribution.failures,
+        distribution.exact as f64 / distribution.samples.max(1) as f64,
+        distribution.median_error,
+        distribution.p99_error,
+        distribution.max_error,
+        distribution.max_relative_error_ppm,
+    );
+}
+
+fn main() -> ExitCode {
+    let (seed, samples) = match parse_args(std::env::args().skip(1)) {
+        Ok(args) => args,
+        Err(error) => {
+            eprintln!("{}", error);
+            return ExitCode::FAILURE;
+        }
+    };
+    println!("operation,scenario,base_exponent,quote_exponent,type,samples,failures,exact_share,median_error,p99_error,max_error,max_relative_error_ppm");
+    for operation in Operation::ALL {
+        for scenario in SCENARIOS.iter() {
+            print_row::<Q64_64>(operation, scenario, &measure::<Q64_64>(operation, scenario, samples, seed));
+            print_row::<Q64_128>(operation, scenario, &measure::<Q64_128>(operation, scenario, samples, seed));
+        }
+    }
+    ExitCode::SUCCESS
+}
\ No newline at end of file
@@ -0,0 +1,15 @@
+//! Precision and performance comparison of the `Q64_64` and `Q64_128` fixed-point types.
+//!
+//! The pool math moved from the 128-bit `Q64_64` to the 192-bit `Q64_128` for precision. The
+//! crate keeps a reference `Q64_64` implementing the constant product hot path with the same
+//! rounding rules, and measures how far each type's swap results drift from the exact integer
+//! results across realistic pool magnitudes, to tell whether a hot path could safely use the
+//! cheaper type.
+//!
+//! - `cargo run -p cpmm-bench --release --bin precision-report` prints the error distributions.
+//! - `cargo bench -p cpmm-bench` measures the CPU cost of the operations and of a whole swap.
+mod q64_64;
+
+pub mod analysis;
+
+pub use q64_64::Q64_64;
\ No newline at end of file
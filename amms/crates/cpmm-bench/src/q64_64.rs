@@ -0,0 +1,194 @@
+use std::ops::{Div, Mul};
+use uint::construct_uint;
+
+// Define a 256-bit unsigned integer for the intermediate results of `Q64_64`.
+construct_uint! {
+    pub(crate) struct U256(4);
+}
+
+/// Represents a fixed-point number with 64 integer bits and 64 fractional bits.
+///
+/// The legacy fixed-point type of the pool math, stored in a native `u128` instead of the `U192`
+/// of `Q64_128`. It implements the operations of the constant product hot path with the same
+/// rounding rules as `Q64_128`, so the comparison measures only the precision lost to the
+/// 64 fewer fractional bits and the cost saved by the narrower integers.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+pub struct Q64_64 {
+    /// The internal representation of the fixed-point value as a 128-bit unsigned integer.
+    value: u128,
+}
+
+impl Q64_64 {
+    /// The number of fractional bits (64).
+    pub const FRACTIONAL_BITS: u32 = 64;
+
+    /// The minimal fraction required for rounding.
+    const ROUND_FRACTION: u128 = 1 << 63;
+
+    /// The maximum representable value for `Q64_64`.
+    pub const MAX: Self = Self { value: u128::MAX };
+
+    /// A constant representing the value 1 in `Q64_64` format.
+    pub const ONE: Self = Self::from_u64(1);
+
+    /// Creates a `Q64_64` instance from an unsigned 64-bit integer.
+    ///
+    /// # Parameters
+    /// - `value`: The integer to be converted to a fixed-point value.
+    pub const fn from_u64(value: u64) -> Self {
+        Self { value: (value as u128) << Self::FRACTIONAL_BITS }
+    }
+
+    /// Creates a `Q64_64` instance from the raw bits of a `u128`.
+    ///
+    /// Matches `Q64_128::from_u128`, which also reads the `u128` as a Q64.64 value.
+    ///
+    /// # Parameters
+    /// - `value`: The raw 128-bit value.
+    pub const fn from_u128(value: u128) -> Self {
+        Self { value }
+    }
+
+    /// Returns the raw 128-bit value.
+    #[inline]
+    pub fn raw_value(&self) -> u128 {
+        self.value
+    }
+
+    /// Returns whether the value is zero.
+    #[inline]
+    pub fn is_zero(&self) -> bool {
+        self.value == 0
+    }
+
+    /// Converts the fixed-point value to a 64-bit unsigned integer, discarding the fraction.
+    pub fn as_u64(&self) -> u64 {
+        (self.value >> Self::FRACTIONAL_BITS) as u64
+    }
+
+    /// Converts the fixed-point value to a 64-bit unsigned integer with rounding half up.
+    pub fn as_u64_round(&self) -> u64 {
+        let mut integer = self.as_u64();
+        if integer < u64::MAX && (self.value & u64::MAX as u128) >= Self::ROUND_FRACTION {
+            integer += 1;
+        }
+        integer
+    }
+
+    /// Multiplies two `Q64_64` values.
+    ///
+    /// # Returns
+    /// - `Some(Q64_64)` with the truncated product.
+    /// - `None` if the product overflows.
+    pub fn checked_mul(self, rhs: Self) -> Option<Self> {
+        let result = (U256::from(self.value) * U256::from(rhs.value)) >> Self::FRACTIONAL_BITS;
+        (result.bits() <= 128).then(|| Self { value: result.as_u128() })
+    }
+
+    /// Divides two `Q64_64` values.
+    ///
+    /// # Returns
+    /// - `Some(Q64_64)` with the truncated quotient.
+    /// - `None` if `rhs` is zero or the quotient overflows.
+    pub fn checked_div(self, rhs: Self) -> Option<Self> {
+        if rhs.is_zero() {
+            return None;
+        }
+        let result = (U256::from(self.value) << Self::FRACTIONAL_BITS) / U256::from(rhs.value);
+        (result.bits() <= 128).then(|| Self { value: result.as_u128() })
+    }
+
+    /// Computes the square root of a `u128` value and scales it to `Q64_64`.
+    ///
+    /// # Parameters
+    /// - `value`: A `u128` value for which the square root is to be computed.
+    pub fn sqrt_from_u128(value: u128) -> Self {
+        let scaled_value = U256::from(value) << (2 * Self::FRACTIONAL_BITS);
+        Self { value: scaled_value.integer_sqrt().as_u128() }
+    }
+
+    /// Computes the square root of the division of two `Q64_64` values.
+    ///
+    /// # Returns
+    /// - `Some(Q64_64)` with the square root of the truncated quotient.
+    /// - `None` if `q2` is zero or the quotient overflows.
+    pub fn checked_div_sqrt(q1: Self, q2: Self) -> Option<Self> {
+        if q2.is_zero() {
+            return None;
+        } else if q1 == q2 {
+            return Some(Self::ONE);
+        }
+        let division_result = U256::from(q1.checked_div(q2)?.value);
+        Some(Self { value: (division_result << Self::FRACTIONAL_BITS).integer_sqrt().as_u128() })
+    }
+
+    /// Computes the square of the `Q64_64` value rounded to a `u128`.
+    ///
+    /// Rounds up when the 8 most significant fractional bits exceed one half, as `Q64_128` does.
+    pub fn square_as_u128(self) -> u128 {
+        let square = U256::from(self.value) * U256::from(self.value);
+        let mut rounded_value = (square >> (2 * Self::FRACTIONAL_BITS)).as_u128();
+        if rounded_value == 0 || rounded_value == u128::MAX {
+            return rounded_value;
+        }
+        if (square >> (2 * Self::FRACTIONAL_BITS - 8)).low_u32() & 0xFF > 128 {
+            rounded_value += 1;
+        }
+        rounded_value
+    }
+}
+
+/// Implements multiplication for `Q64_64`.
+///
+/// # Panic
+/// - Panics if the product overflows.
+impl Mul for Q64_64 {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        self.checked_mul(rhs).expect("Q64_64 multiplication overflow")
+    }
+}
+
+/// Implements division for `Q64_64`.
+///
+/// # Panic
+/// - Panics if the divisor is zero or the quotient overflows.
+impl Div for Q64_64 {
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        self.checked_div(rhs).expect("Q64_64 division by zero or overflow")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cpmm_math::Q64_128;
+    use super::*;
+
+    /// Tests the conversions and the rounding of `Q64_64`.
+    #[test]
+    fn test_q64_64_conversions() {
+        assert_eq!(Q64_64::from_u64(7).as_u64(), 7);
+        assert_eq!(Q64_64::from_u128((7 << 64) + (1 << 63)).as_u64_round(), 8);
+        assert_eq!(Q64_64::from_u128((7 << 64) + (1 << 63) - 1).as_u64_round(), 7);
+        assert_eq!(Q64_64::MAX.as_u64_round(), u64::MAX);
+        assert!(Q64_64::default().is_zero());
+    }
+
+    /// Tests the arithmetic of `Q64_64` against `Q64_128` on exactly representable values.
+    #[test]
+    fn test_q64_64_matches_q64_128() {
+        let (a, b) = (1_500_000_000u64, 3u64);
+        assert_eq!((Q64_64::from_u64(a) * Q64_64::from_u64(b)).as_u64(), Q64_128::from_u64(a).checked_mul(Q64_128::from_u64(b)).unwrap().as_u64());
+        assert_eq!((Q64_64::from_u64(a) / Q64_64::from_u64(b)).as_u64_round(), Q64_128::from_u64(a).checked_div(Q64_128::from_u64(b)).unwrap().as_u64_round());
+        for value in [1u128, 4, 1_000_000_007 * 1_000_000_007, 1 << 100] {
+            assert_eq!(Q64_64::sqrt_from_u128(value).as_u64(), Q64_128::sqrt_from_u128(value).as_u64());
+            assert_eq!(Q64_64::sqrt_from_u128(value).square_as_u128(), value);
+        }
+        assert_eq!(Q64_64::sqrt_from_u128(u128::MAX).as_u64(), u64::MAX);
+        assert_eq!(Q64_64::MAX.checked_mul(Q64_64::from_u64(2)), None);
+        assert_eq!(Q64_64::ONE.checked_div(Q64_64::default()), None);
+    }
+}
\ No newline at end of file
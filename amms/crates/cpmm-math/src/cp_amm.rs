@@ -0,0 +1,369 @@
+//! Constant product calculations shared by the program and off-chain quoting.
+use crate::{Q64_128, SignedQ64_128};
+
+/// The maximum allowable fee rate, expressed in basis points.
+///
+/// - 1 basis point = 0.01%.
+/// - `FEE_MAX_BASIS_POINTS = 10000` corresponds to a maximum fee rate of 100%.
+pub const FEE_MAX_BASIS_POINTS: u128 = 10000;
+
+/// The tolerance for changes in the constant product square root during swaps.
+///
+/// - This value is used to ensure the AMM adheres to the constant product rule with minimal deviation.
+/// - Defined as a `Q64_128` value representing a tolerance of `0.0001%`.
+pub const SWAP_CONSTANT_PRODUCT_SQRT_TOLERANCE: Q64_128 = Q64_128::from_bits(0, 340282366920938463463374607431768);
+
+/// The tolerance for liquidity ratio sqrt after adjusting.
+///
+/// - Similar to `SWAP_CONSTANT_PRODUCT_SQRT_TOLERANCE`, this constant defines the allowable deviation
+///   when recalculating the liquidity ratio of the pool.
+/// - Defined as a `Q64_128` value representing a tolerance of `0.00001%`.
+pub const ADJUST_LIQUIDITY_RATIO_SQRT_TOLERANCE: Q64_128 = Q64_128::from_bits(0, 34028236692093846346337460743176);
+
+/// The minimal base and quote liquidity a withdraw may leave in a pool.
+///
+/// - Keeps drained pools from trading at dust reserves, where rounding dominates the price.
+/// - Only a withdraw leaving nothing but the locked LP tokens in supply may go below it,
+///   the locked liquidity being the backstop of such pools.
+pub const MIN_LIQUIDITY: u64 = 1_000;
+
+/// Calculates the amount of LP tokens to mint based on the provided liquidity.
+///
+/// # Parameters
+/// - `constant_product_sqrt`: The square root of the current constant product.
+/// - `lp_tokens_supply`: The current LP tokens supply.
+/// - `new_constant_product_sqrt`: The square root of the new constant product after providing liquidity.
+///
+/// # Returns
+/// - `Some(u64)` with the amount of LP tokens to mint if the calculation is valid.
+/// - `None` if the calculation fails (e.g., due to underflow or zero tokens).
+pub fn calculate_lp_mint_for_provided_liquidity(constant_product_sqrt: Q64_128, lp_tokens_supply: u64, new_constant_product_sqrt: Q64_128) -> Option<u64> {
+    let provided_liquidity = new_constant_product_sqrt.checked_sub(constant_product_sqrt)?;
+
+    let share_from_current_liquidity = provided_liquidity.checked_div(constant_product_sqrt)?;
+    let tokens_to_mint = share_from_current_liquidity.checked_mul(Q64_128::from_u64(lp_tokens_supply))?.as_u64();
+    if tokens_to_mint == 0{
+        return None;
+    }
+    Some(tokens_to_mint)
+}
+
+/// Calculates the amount of base and quote liquidity to withdraw for a given share of LP tokens.
+///
+/// # Parameters
+/// - `constant_product_sqrt`: The square root of the current constant product.
+/// - `base_quote_ratio_sqrt`: The square root of the current base-to-quote liquidity ratio.
+/// - `lp_tokens_supply`: The current LP tokens supply.
+/// - `lp_tokens`: The number of LP tokens being redeemed.
+///
+/// # Returns
+/// - `Some((u64, u64))` with the base and quote liquidity amounts.
+/// - `None` if the calculation fails (e.g., due to zero tokens).
+pub fn calculate_liquidity_from_share(constant_product_sqrt: Q64_128, base_quote_ratio_sqrt: Q64_128, lp_tokens_supply: u64, lp_tokens: u64) -> Option<(u64, u64)>{
+    let liquidity_share = Q64_128::from_u64(lp_tokens).checked_div(Q64_128::from_u64(lp_tokens_supply))?;
+    let constant_product_sqrt_share = constant_product_sqrt.checked_mul(liquidity_share)?;
+    let base_withdraw = constant_product_sqrt_share.saturating_mul(base_quote_ratio_sqrt).as_u64_round();
+    let quote_withdraw = constant_product_sqrt_share.saturating_checked_div(base_quote_ratio_sqrt)?.as_u64_round();
+
+    if base_withdraw == 0 || quote_withdraw == 0{
+        return None;
+    }
+    Some((base_withdraw, quote_withdraw))
+}
+
+/// Calculates the new base and quote liquidity after a swap.
+///
+/// # Parameters
+/// - `constant_product_sqrt`: The square root of the current constant product.
+/// - `base_liquidity`: The current base liquidity.
+/// - `quote_liquidity`: The current quote liquidity.
+/// - `swap_amount`: The amount being swapped.
+/// - `is_in_out`: Whether the swap is "in" (true) or "out" (false).
+///
+/// # Returns
+/// - `Some((u64, u64))` with the new base and quote liquidity values.
+/// - `None` if the calculation fails.
+pub fn calculate_afterswap_liquidity(constant_product_sqrt: Q64_128, base_liquidity: u64, quote_liquidity: u64, swap_amount: u64, is_in_out: bool) -> Option<(u64, u64)>{
+    let new_base_liquidity;
+    let new_quote_liquidity;
+    if is_in_out {
+        new_base_liquidity = base_liquidity.checked_add(swap_amount)?;
+        new_quote_liquidity = calculate_opposite_liquidity(constant_product_sqrt, new_base_liquidity)?;
+    }
+    else{
+        new_quote_liquidity = quote_liquidity.checked_add(swap_amount)?;
+        new_base_liquidity = calculate_opposite_liquidity(constant_product_sqrt, new_quote_liquidity)?;
+    }
+    Some((new_base_liquidity, new_quote_liquidity))
+}
+
+/// Calculates the opposite liquidity value based on the constant product formula.
+///
+/// # Parameters
+/// - `constant_product_sqrt`: The square root of the current constant product.
+/// - `x_liquidity`: The current liquidity for one side (base or quote).
+///
+/// # Returns
+/// - `Some(u64)` with the opposite liquidity value.
+/// - `None` if the result is zero or the division overflows.
+#[inline]
+pub fn calculate_opposite_liquidity(constant_product_sqrt: Q64_128, x_liquidity: u64) -> Option<u64> {
+    let constant_product = constant_product_sqrt.square_as_u128();
+    let opposite_liquidity = Q64_128::from_u128(constant_product).checked_div(Q64_128::from_u128(x_liquidity as u128))?.as_u64_round();
+    if opposite_liquidity == 0 {
+        return None;
+    }
+    Some(opposite_liquidity)
+}
+
+/// Calculates the fee for a given swap amount based on the provided fee rate.
+///
+/// # Parameters
+/// - `swap_amount`: The amount of tokens being swapped.
+/// - `fee_basis_points`: The fee rate expressed in **basis points** (1 basis point = 0.01%).
+///
+/// # Returns
+/// - A `u64` representing the **calculated fee amount**, zero without any math for a zero fee rate.
+#[inline]
+pub fn calculate_fee_amount(swap_amount: u64, fee_basis_points: u16) -> u64 {
+    if fee_basis_points == 0 {
+        return 0;
+    }
+    ((swap_amount as u128) * (fee_basis_points as u128) / FEE_MAX_BASIS_POINTS) as u64
+}
+
+/// Calculates the swap fee for a given swap amount based on the provided fee rate, rounded up.
+///
+/// Swap fees are rounded up, so the pool is never undercharged by the truncated fraction of a fee,
+/// which would otherwise make splitting a swap into small swaps cheaper.
+///
+/// # Parameters
+/// - `swap_amount`: The amount of tokens being swapped.
+/// - `fee_basis_points`: The fee rate expressed in **basis points** (1 basis point = 0.01%).
+///
+/// # Returns
+/// - A `u64` representing the **calculated fee amount**, never exceeding `swap_amount` for rates up to 100%.
+#[inline]
+pub fn calculate_swap_fee_amount(swap_amount: u64, fee_basis_points: u16) -> u64 {
+    if fee_basis_points == 0 {
+        return 0;
+    }
+    ((swap_amount as u128) * (fee_basis_points as u128)).div_ceil(FEE_MAX_BASIS_POINTS) as u64
+}
+
+/// Calculates the base-to-quote liquidity ratio square root.
+///
+/// # Parameters
+/// - `base_liquidity`: The base liquidity.
+/// - `quote_liquidity`: The quote liquidity.
+///
+/// # Returns
+/// - `Some(Q64_128)` with the ratio square root.
+/// - `None` if the ratio is zero.
+#[inline]
+pub fn calculate_base_quote_ratio_sqrt(base_liquidity: u64, quote_liquidity: u64) -> Option<Q64_128> {
+    let ratio = Q64_128::checked_div_sqrt(Q64_128::from_u64(base_liquidity), Q64_128::from_u64(quote_liquidity))?;
+    if ratio.is_zero() {
+        return None;
+    }
+    Some(ratio)
+}
+
+/// Calculates the price impact of moving the pool to the new liquidity.
+///
+/// The impact is the relative change of the base-to-quote liquidity ratio.
+///
+/// # Parameters
+/// - `base_quote_ratio_sqrt`: The square root of the current base-to-quote liquidity ratio.
+/// - `new_base_liquidity`: The base liquidity after the operation.
+/// - `new_quote_liquidity`: The quote liquidity after the operation.
+///
+/// # Returns
+/// - `Some(u64)` with the price impact in basis points.
+/// - `None` if the new ratio can't be calculated.
+#[inline]
+pub fn calculate_price_impact_basis_points(base_quote_ratio_sqrt: Q64_128, new_base_liquidity: u64, new_quote_liquidity: u64) -> Option<u64> {
+    let new_base_quote_ratio_sqrt = calculate_base_quote_ratio_sqrt(new_base_liquidity, new_quote_liquidity)?;
+    calculate_price_impact_from_ratio_sqrt(base_quote_ratio_sqrt, new_base_quote_ratio_sqrt)
+}
+
+/// Calculates the price impact of moving the pool to an already calculated liquidity ratio.
+///
+/// # Parameters
+/// - `base_quote_ratio_sqrt`: The square root of the current base-to-quote liquidity ratio.
+/// - `new_base_quote_ratio_sqrt`: The square root of the base-to-quote liquidity ratio after the operation.
+///
+/// # Returns
+/// - `Some(u64)` with the price impact in basis points.
+/// - `None` if the current ratio is zero.
+#[inline]
+pub fn calculate_price_impact_from_ratio_sqrt(base_quote_ratio_sqrt: Q64_128, new_base_quote_ratio_sqrt: Q64_128) -> Option<u64> {
+    let ratio_change_sqrt = new_base_quote_ratio_sqrt.checked_div(base_quote_ratio_sqrt)?;
+    let ratio_change = ratio_change_sqrt.saturating_mul(ratio_change_sqrt);
+    Some(SignedQ64_128::delta(Q64_128::ONE, ratio_change).magnitude().saturating_mul(Q64_128::from_u64(FEE_MAX_BASIS_POINTS as u64)).as_u64_round())
+}
+
+/// Calculates the square root of the constant product.
+///
+/// # Parameters
+/// - `base_liquidity`: The base liquidity.
+/// - `quote_liquidity`: The quote liquidity.
+///
+/// # Returns
+/// - `Some(Q64_128)` with the square root of the constant product.
+/// - `None` if the product is zero.
+#[inline]
+pub fn calculate_constant_product_sqrt(base_liquidity: u64, quote_liquidity: u64) -> Option<Q64_128> {
+    let constant_product_sqrt = Q64_128::sqrt_from_u128(base_liquidity as u128 * quote_liquidity as u128);
+    if constant_product_sqrt.is_zero() {
+        return None;
+    }
+    Some(constant_product_sqrt)
+}
+
+/// Derives the square root of the constant product from the base-to-quote liquidity ratio square root.
+///
+/// `sqrt(base * quote) = sqrt(base / quote) * quote`, so a known ratio gives the constant product
+/// with a multiplication instead of a square root. The result only deviates from
+/// `calculate_constant_product_sqrt` by the truncation of the ratio, far below any tolerance.
+///
+/// # Parameters
+/// - `base_quote_ratio_sqrt`: The square root of the base-to-quote liquidity ratio.
+/// - `quote_liquidity`: The quote liquidity.
+///
+/// # Returns
+/// - `Some(Q64_128)` with the square root of the constant product.
+/// - `None` if the product is zero or overflows.
+#[inline]
+pub fn calculate_constant_product_sqrt_from_ratio_sqrt(base_quote_ratio_sqrt: Q64_128, quote_liquidity: u64) -> Option<Q64_128> {
+    let constant_product_sqrt = base_quote_ratio_sqrt.checked_mul(Q64_128::from_u64(quote_liquidity))?;
+    if constant_product_sqrt.is_zero() {
+        return None;
+    }
+    Some(constant_product_sqrt)
+}
+
+/// Checks that a recalculated value deviates from the current one within a relative tolerance.
+///
+/// # Parameters
+/// - `current`: The current value.
+/// - `new`: The recalculated value.
+/// - `tolerance`: The allowed relative deviation.
+///
+/// # Returns
+/// - `Some(bool)` with whether the deviation is within the tolerance.
+/// - `None` if the allowed deviation overflows.
+#[inline]
+pub fn is_within_tolerance(current: Q64_128, new: Q64_128, tolerance: Q64_128) -> Option<bool> {
+    let allowed_difference = current.checked_mul(tolerance)?;
+    Some(SignedQ64_128::delta(current, new).is_within(allowed_difference))
+}
+
+/// Calculates a withdrawal burning only the LP tokens its payout is worth.
+///
+/// Withdrawn amounts are rounded to whole tokens, so the side with the coarser token units usually
+/// pays out less than the redeemed share. The LP tokens are reduced to the minimal amount covering
+/// that side, and the other side is recalculated from the reduced amount, rounding down.
+/// Both payouts stay covered by the burned share, and the unburned remainder stays with the user.
+///
+/// # Parameters
+/// - `base_liquidity`: The current base liquidity.
+/// - `quote_liquidity`: The current quote liquidity.
+/// - `lp_tokens_supply`: The current LP tokens supply.
+/// - `lp_tokens`: The LP tokens requested to be redeemed.
+/// - `base_amount`: The base liquidity paid out for `lp_tokens`.
+/// - `quote_amount`: The quote liquidity paid out for `lp_tokens`.
+///
+/// # Returns
+/// - `Some((u64, u64, u64))` with the LP tokens to burn and the base and quote liquidity to pay out.
+/// - `None` if any of the liquidity is zero or the payout rounds to zero.
+pub fn calculate_burn_exact_withdraw(base_liquidity: u64, quote_liquidity: u64, lp_tokens_supply: u64, lp_tokens: u64, base_amount: u64, quote_amount: u64) -> Option<(u64, u64, u64)> {
+    if base_liquidity == 0 || quote_liquidity == 0 {
+        return None;
+    }
+    let lp_tokens_supply = lp_tokens_supply as u128;
+    let lp_tokens_for = |amount: u64, liquidity: u64| (amount as u128 * lp_tokens_supply).div_ceil(liquidity as u128);
+    let liquidity_for = |lp_tokens: u128, liquidity: u64| (lp_tokens * liquidity as u128 / lp_tokens_supply) as u64;
+
+    let lp_tokens_to_burn = lp_tokens_for(base_amount, base_liquidity).min(lp_tokens_for(quote_amount, quote_liquidity));
+    if lp_tokens_to_burn >= lp_tokens as u128 {
+        return Some((lp_tokens, base_amount, quote_amount));
+    }
+
+    let base_amount = base_amount.min(liquidity_for(lp_tokens_to_burn, base_liquidity));
+    let quote_amount = quote_amount.min(liquidity_for(lp_tokens_to_burn, quote_liquidity));
+    if base_amount == 0 || quote_amount == 0 {
+        return None;
+    }
+    Some((lp_tokens_to_burn as u64, base_amount, quote_amount))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_calculate_swap_fee_amount() {
+        assert_eq!(calculate_swap_fee_amount(10_000, 30), 30);
+        // The truncated fraction of a fee is charged in full
+        assert_eq!(calculate_swap_fee_amount(10_001, 30), 31);
+        assert_eq!(calculate_swap_fee_amount(1, 1), 1);
+        assert_eq!(calculate_swap_fee_amount(0, 30), 0);
+        assert_eq!(calculate_swap_fee_amount(u64::MAX, 0), 0);
+        assert_eq!(calculate_swap_fee_amount(u64::MAX, 10_000), u64::MAX);
+
+        // Swap fees are never undercounted and exceed the truncated fees by at most one unit
+        for swap_amount in (1..100_000u64).step_by(997) {
+            for (providers_fee_basis_points, protocol_fee_basis_points) in [(1, 1), (25, 5), (30, 0), (5_000, 5_000), (9_999, 1)] {
+                let providers_fee_amount = calculate_swap_fee_amount(swap_amount, providers_fee_basis_points);
+                let protocol_fee_amount = calculate_swap_fee_amount(swap_amount, protocol_fee_basis_points);
+                assert!(providers_fee_amount as u128 * FEE_MAX_BASIS_POINTS >= swap_amount as u128 * providers_fee_basis_points as u128);
+                assert!(protocol_fee_amount as u128 * FEE_MAX_BASIS_POINTS >= swap_amount as u128 * protocol_fee_basis_points as u128);
+                assert!(providers_fee_amount >= calculate_fee_amount(swap_amount, providers_fee_basis_points));
+                assert!(providers_fee_amount - calculate_fee_amount(swap_amount, providers_fee_basis_points) <= 1);
+            }
+        }
+    }
+
+    #[test]
+    fn test_calculate_burn_exact_withdraw() {
+        // Exact shares burn everything
+        assert_eq!(calculate_burn_exact_withdraw(6_000, 1_500, 3_000, 300, 600, 150), Some((300, 600, 150)));
+        // 1 base token is worth 1000 LP tokens, so the base rounding remainder is kept
+        assert_eq!(
+            calculate_burn_exact_withdraw(10_000, 10_000_000_000, 10_000_000, 1_500, 1, 1_500_000),
+            Some((1_000, 1, 1_000_000))
+        );
+        // Rounding up burns the requested amount
+        assert_eq!(
+            calculate_burn_exact_withdraw(10_000, 10_000_000_000, 10_000_000, 1_500, 2, 1_500_000),
+            Some((1_500, 2, 1_500_000))
+        );
+        assert_eq!(calculate_burn_exact_withdraw(0, 1_500, 3_000, 300, 600, 150), None);
+    }
+
+    #[test]
+    fn test_calculate_burn_exact_withdraw_covers_payout() {
+        let (base_liquidity, quote_liquidity) = (10_000_003, 10_000_000_000_007);
+        let constant_product_sqrt = calculate_constant_product_sqrt(base_liquidity, quote_liquidity).unwrap();
+        let base_quote_ratio_sqrt = calculate_base_quote_ratio_sqrt(base_liquidity, quote_liquidity).unwrap();
+        let lp_tokens_supply = constant_product_sqrt.as_u64();
+        let mut is_remainder_kept = false;
+        for lp_tokens in (1_234_567_800..1_234_578_800).step_by(777) {
+            let (base_amount, quote_amount) = calculate_liquidity_from_share(constant_product_sqrt, base_quote_ratio_sqrt, lp_tokens_supply, lp_tokens).unwrap();
+            let (lp_tokens_to_burn, exact_base_amount, exact_quote_amount) = calculate_burn_exact_withdraw(
+                base_liquidity, quote_liquidity, lp_tokens_supply, lp_tokens, base_amount, quote_amount
+            ).unwrap();
+
+            assert!(lp_tokens_to_burn <= lp_tokens);
+            assert!(exact_base_amount <= base_amount && exact_quote_amount <= quote_amount);
+            if lp_tokens_to_burn < lp_tokens {
+                is_remainder_kept = true;
+                let burned = lp_tokens_to_burn as u128;
+                assert!(burned * base_liquidity as u128 >= exact_base_amount as u128 * lp_tokens_supply as u128);
+                assert!(burned * quote_liquidity as u128 >= exact_quote_amount as u128 * lp_tokens_supply as u128);
+            }
+        }
+        assert!(is_remainder_kept);
+    }
+}
\ No newline at end of file
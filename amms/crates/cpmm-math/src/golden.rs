@@ -0,0 +1,134 @@
+//! Golden test vectors of the swap math, shared by the program and its clients.
+//!
+//! `golden/swap_vectors.csv` lists swaps in freshly launched pools with the outcome produced by
+//! `CpAmm::get_swap_payload` of the program, whose tests validate every row. The quotes of this
+//! crate and of its `wasm` bindings are tested against the same table, and SDKs in other languages
+//! can read the file to prove bit-exact agreement with the program.
+//!
+//! Lines starting with `#` are comments, the first other line is the header. Swaps the program
+//! rejects have `-` in every outcome column.
+use std::fmt;
+use crate::quote::SwapQuote;
+
+/// The CSV table of swap vectors.
+pub const SWAP_VECTORS_CSV: &str = include_str!("../golden/swap_vectors.csv");
+
+/// The header of the swap vectors table.
+pub const SWAP_VECTORS_HEADER: &str = "base_liquidity,quote_liquidity,swap_amount,providers_fee_rate_basis_points,protocol_fee_rate_basis_points,is_in_out,amount_out,providers_fee_amount,protocol_fee_amount,price_impact_basis_points";
+
+/// A swap in a freshly launched pool with its expected outcome.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SwapVector {
+    /// The base liquidity the pool is launched with.
+    pub base_liquidity: u64,
+    /// The quote liquidity the pool is launched with.
+    pub quote_liquidity: u64,
+    /// The amount of tokens swapped in.
+    pub swap_amount: u64,
+    /// The providers fee rate, measured in basis points.
+    pub providers_fee_rate_basis_points: u16,
+    /// The protocol fee rate, measured in basis points.
+    pub protocol_fee_rate_basis_points: u16,
+    /// Whether base tokens are swapped in for quote tokens.
+    pub is_in_out: bool,
+    /// The outcome of the swap, `None` if the program rejects it.
+    pub expected: Option<SwapQuote>,
+}
+
+impl SwapVector {
+    /// Parses a row of the table.
+    ///
+    /// # Returns
+    /// - `Some(SwapVector)` if the row has every column.
+    /// - `None` if a column is missing or malformed.
+    pub fn parse(line: &str) -> Option<Self> {
+        let mut columns = line.split(',').map(str::trim);
+        let mut next = || columns.next();
+        let base_liquidity = next()?.parse().ok()?;
+        let quote_liquidity = next()?.parse().ok()?;
+        let swap_amount = next()?.parse().ok()?;
+        let providers_fee_rate_basis_points = next()?.parse().ok()?;
+        let protocol_fee_rate_basis_points = next()?.parse().ok()?;
+        let is_in_out = next()?.parse().ok()?;
+        let outcome = [next()?, next()?, next()?, next()?];
+        if next().is_some() {
+            return None;
+        }
+        let expected = if outcome.iter().all(|column| *column == "-") {
+            None
+        } else {
+            let [amount_out, providers_fee_amount, protocol_fee_amount, price_impact_basis_points] = outcome;
+            Some(SwapQuote {
+                amount_out: amount_out.parse().ok()?,
+                providers_fee_amount: providers_fee_amount.parse().ok()?,
+                protocol_fee_amount: protocol_fee_amount.parse().ok()?,
+                price_impact_basis_points: price_impact_basis_points.parse().ok()?,
+            })
+        };
+        Some(Self {
+            base_liquidity,
+            quote_liquidity,
+            swap_amount,
+            providers_fee_rate_basis_points,
+            protocol_fee_rate_basis_points,
+            is_in_out,
+            expected,
+        })
+    }
+}
+
+/// Formats the vector as a row of the table.
+impl fmt::Display for SwapVector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f, "{},{},{},{},{},{},", self.base_liquidity, self.quote_liquidity, self.swap_amount,
+            self.providers_fee_rate_basis_points, self.protocol_fee_rate_basis_points, self.is_in_out
+        )?;
+        match &self.expected {
+            Some(quote) => write!(f, "{},{},{},{}", quote.amount_out, quote.providers_fee_amount, quote.protocol_fee_amount, quote.price_impact_basis_points),
+            None => write!(f, "-,-,-,-"),
+        }
+    }
+}
+
+/// Returns the rows of a swap vectors table, skipping comments, blank lines and the header.
+///
+/// # Panics
+/// - Panics if the header or a row is malformed.
+pub fn parse_swap_vectors(csv: &str) -> Vec<SwapVector> {
+    let mut lines = csv.lines().filter(|line| !line.trim().is_empty() && !line.starts_with('#'));
+    assert_eq!(lines.next(), Some(SWAP_VECTORS_HEADER), "Malformed swap vectors header");
+    lines.map(|line| SwapVector::parse(line).unwrap_or_else(|| panic!("Malformed swap vector {}", line))).collect()
+}
+
+/// Returns the committed swap vectors.
+pub fn swap_vectors() -> Vec<SwapVector> {
+    parse_swap_vectors(SWAP_VECTORS_CSV)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::quote::CpAmmState;
+
+    #[test]
+    fn test_swap_vectors_roundtrip() {
+        for vector in swap_vectors() {
+            assert_eq!(SwapVector::parse(&vector.to_string()), Some(vector));
+        }
+        assert!(SwapVector::parse("1,1,1,0,0,true,-,-,-").is_none());
+        assert!(SwapVector::parse("1,1,1,0,0,true,-,-,-,-,-").is_none());
+        assert!(SwapVector::parse("1,1,1,0,0,yes,-,-,-,-").is_none());
+    }
+
+    #[test]
+    fn test_quote_swap_matches_swap_vectors() {
+        let vectors = swap_vectors();
+        assert!(vectors.iter().any(|vector| vector.expected.is_some()) && vectors.iter().any(|vector| vector.expected.is_none()));
+        for vector in vectors {
+            let state = CpAmmState::launched(vector.base_liquidity, vector.quote_liquidity).unwrap();
+            let quote = state.quote_swap(vector.swap_amount, vector.providers_fee_rate_basis_points, vector.protocol_fee_rate_basis_points, vector.is_in_out);
+            assert_eq!(quote, vector.expected, "Swap vector {}", vector);
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,27 @@
+//! Fixed-point math and quoting core of the `cpmm` program.
+//!
+//! The program uses this crate for all of its constant product calculations, so quotes produced
+//! here match the on-chain results exactly. Without features the crate has no Anchor or Solana
+//! dependencies and compiles to `wasm32-unknown-unknown`.
+//!
+//! The `golden` module publishes swap vectors produced by the program, so clients in any language
+//! can check their math against it bit for bit.
+//!
+//! Features:
+//! - `anchor`: Borsh serialization and `Space` for storing the fixed-point types in accounts.
+//! - `float`: Lossy conversions between the fixed-point types and `f64`, for display and tests.
+//! - `strict-math`: Compiles out the panicking `Q64_128` operators, leaving only the checked methods.
+//! - `wasm`: A `wasm-bindgen` API for quoting swaps, provides and withdrawals from web UIs.
+mod q64_128;
+mod signed_q64_128;
+mod uint_types;
+
+pub mod cp_amm;
+pub mod golden;
+pub mod quote;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+pub use q64_128::*;
+pub use signed_q64_128::*;
+use uint_types::*;
\ No newline at end of file
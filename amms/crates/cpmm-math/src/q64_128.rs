@@ -1,6 +1,21 @@
+#[cfg(feature = "anchor")]
 use anchor_lang::{AnchorDeserialize, AnchorSerialize, prelude::borsh, InitSpace};
+use std::fmt;
+#[cfg(any(test, not(feature = "strict-math")))]
 use std::ops::{Add, Div, Mul, Sub};
-use super::{U384, U192};
+use crate::{U384, U192};
+
+/// Error of a conversion into `Q64_128` of a value exceeding its 192 bits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Q64_128OverflowError;
+
+impl fmt::Display for Q64_128OverflowError {
+	fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+		formatter.write_str("value does not fit into Q64_128")
+	}
+}
+
+impl std::error::Error for Q64_128OverflowError {}
 
 /// Represents a fixed-point number with 64 integer bits and 128 fractional bits.
 ///
@@ -11,7 +26,8 @@ use super::{U384, U192};
 ///
 /// This type provides utilities for fixed-point arithmetic, conversions from primitive types,
 /// and accessing the integer and fractional components of the value.
-#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default, AnchorSerialize, AnchorDeserialize, InitSpace)]
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+#[cfg_attr(feature = "anchor", derive(AnchorSerialize, AnchorDeserialize, InitSpace))]
 pub struct Q64_128 {
 	/// The internal representation of the fixed-point value as a 192-bit unsigned integer.
 	value: U192,
@@ -32,7 +48,7 @@ impl Q64_128 {
 	/*	const ROUND_FRACTION: U192 = U192([0,  18446744073709551360, 0]);*/
 	
 	/// The scaling factor used for converting floating-point values to fixed-point.
-	#[cfg(test)]
+	#[cfg(any(test, feature = "float"))]
 	const FRACTIONAL_SCALE: f64 = 340282366920938463463374607431768211456.0;
 
 	/// The maximum representable value for `Q64_128`.
@@ -48,10 +64,21 @@ impl Q64_128 {
 	///
 	/// # Returns
 	/// A new `Q64_128` instance.
-	pub(super) const fn new(value: U192) -> Self {
+	pub(crate) const fn new(value: U192) -> Self {
 		Q64_128 { value }
 	}
 
+	/// Creates a `Q64_128` instance from the integer square root of a `U384` value.
+	///
+	/// The square root of any `U384` value fits into 192 bits, so the conversion never overflows.
+	///
+	/// # Parameters
+	/// - `root`: The integer square root of a `U384` value.
+	fn from_u384_root(root: U384) -> Self {
+		debug_assert!(root.leading_zeros() >= 192);
+		Self::new(U192::from_big_endian(&root.to_big_endian()[24..]))
+	}
+
 	/// Creates a `Q64_128` instance from an unsigned 64-bit integer.
 	///
 	/// # Parameters
@@ -133,7 +160,7 @@ impl Q64_128 {
 	///
 	/// # Returns
 	/// The raw `U192` value.
-	pub(super) fn raw_value(&self) -> U192 {
+	pub(crate) fn raw_value(&self) -> U192 {
 		self.value
 	}
 
@@ -181,7 +208,7 @@ impl Q64_128 {
 	///
 	/// # Valid Range
 	/// - The value must be within `[0.0, 2^64)`.
-	#[cfg(test)]
+	#[cfg(any(test, feature = "float"))]
 	pub fn from_f64(value: f64) -> Option<Self> {
 		if !(0.0..18446744073709551616.0).contains(&value) {
 			return None;
@@ -192,24 +219,23 @@ impl Q64_128 {
 	}
 }
 
-/// Implements conversion from `U384` to `Q64_128`.
-///
-/// # Panic
-/// - Panics if the `U384` value cannot fit into 192 bits. This occurs when
-///   the number of leading zeros in the `U384` value is less than 192.
+/// Implements the checked conversion from `U384` to `Q64_128`.
 ///
 /// # Parameters
 /// - `value`: A `U384` value to be converted into `Q64_128`.
 ///
 /// # Returns
-/// A `Q64_128` instance representing the input `U384` value.
-impl From<U384> for Q64_128 {
-	fn from(value: U384) -> Self {
+/// A `Result<Q64_128, Q64_128OverflowError>`:
+/// - `Ok(Q64_128)` representing the input `U384` value.
+/// - `Err(Q64_128OverflowError)` if the value doesn't fit into 192 bits.
+impl TryFrom<U384> for Q64_128 {
+	type Error = Q64_128OverflowError;
+
+	fn try_from(value: U384) -> Result<Self, Self::Error> {
 		if value.leading_zeros() < 192 {
-			panic!("Cannot convert U384 to Q64_128");
+			return Err(Q64_128OverflowError);
 		}
-		let u192 = U192::from_big_endian(&(value.to_big_endian()[24..]));
-		Self::new(u192)
+		Ok(Self::new(U192::from_big_endian(&(value.to_big_endian()[24..]))))
 	}
 }
 
@@ -225,7 +251,7 @@ impl From<U384> for Q64_128 {
 ///
 /// # Returns
 /// A `f64` representation of the input `Q64_128`.
-#[cfg(test)]
+#[cfg(any(test, feature = "float"))]
 impl From<Q64_128> for f64 {
 	fn from(value: Q64_128) -> Self {
 		let (high_bits, low_bits) = value.split();
@@ -240,8 +266,13 @@ impl From<Q64_128> for f64 {
 /// Adds two `Q64_128` values together by performing an addition on their raw
 /// `U192` representations.
 ///
+/// # Panic
+/// - Panics if the sum overflows. With `strict-math` the operator is compiled out outside of the crate
+///   tests, so code reachable from instructions uses `checked_add`.
+///
 /// # Returns
 /// A new `Q64_128` instance containing the sum of the two inputs.
+#[cfg(any(test, not(feature = "strict-math")))]
 impl Add for Q64_128 {
 	type Output = Self;
 
@@ -257,8 +288,13 @@ impl Add for Q64_128 {
 /// Subtracts one `Q64_128` value from another by performing a subtraction on their
 /// raw `U192` representations.
 ///
+/// # Panic
+/// - Panics if the difference underflows. With `strict-math` the operator is compiled out outside of the
+///   crate tests, so code reachable from instructions uses `checked_sub`.
+///
 /// # Returns
 /// A new `Q64_128` instance containing the result of the subtraction.
+#[cfg(any(test, not(feature = "strict-math")))]
 impl Sub for Q64_128 {
 	type Output = Self;
 
@@ -275,14 +311,18 @@ impl Sub for Q64_128 {
 /// Multiplies two `Q64_128` values by converting them to `U384`, performing the
 /// multiplication, and then shifting the result to adjust for the fractional bits.
 ///
+/// # Panic
+/// - Panics if the product overflows, code reachable from instructions must use `checked_mul`. With
+///   `strict-math` the operator is compiled out outside of the crate tests.
+///
 /// # Returns
 /// A new `Q64_128` instance containing the product of the two inputs.
+#[cfg(any(test, not(feature = "strict-math")))]
 impl Mul for Q64_128 {
 	type Output = Self;
 
 	fn mul(self, rhs: Self) -> Self::Output {
-		let result = (U384::from(self) * U384::from(rhs)) >> Q64_128::FRACTIONAL_BITS;
-		result.into()
+		self.checked_mul(rhs).expect("Q64_128 multiplication overflow")
 	}
 }
 
@@ -294,9 +334,12 @@ impl Mul for Q64_128 {
 ///
 /// # Panic
 /// - Panics if the divisor (`rhs`) is zero to avoid division by zero.
+/// - Panics if the quotient overflows, code reachable from instructions must use `checked_div`. With
+///   `strict-math` the operator is compiled out outside of the crate tests.
 ///
 /// # Returns
 /// A new `Q64_128` instance containing the result of the division.
+#[cfg(any(test, not(feature = "strict-math")))]
 impl Div for Q64_128 {
 	type Output = Self;
 
@@ -304,8 +347,7 @@ impl Div for Q64_128 {
 		if rhs.is_zero() {
 			panic!("Division by zero!");
 		}
-		let result = (U384::from(self) << Self::FRACTIONAL_BITS) / U384::from(rhs);
-		result.into()
+		self.checked_div(rhs).expect("Q64_128 division overflow")
 	}
 }
 
@@ -495,6 +537,7 @@ impl Q64_128 {
 	///
 	/// # Returns
 	/// A `u64` representing the square of the value.
+	#[deprecated(note = "panics on overflow, use `checked_square_as_u64`")]
 	pub fn square_as_u64(self) -> u64 {
 		self.checked_square_as_u64().unwrap()
 	}
@@ -508,7 +551,7 @@ impl Q64_128 {
 	/// A `Q64_128` instance representing the square root of the input value.
 	pub fn sqrt_from_u128(value: u128) -> Self {
 		let scaled_value = U384::from(value) << (2 * Self::FRACTIONAL_BITS);
-		Q64_128::from(scaled_value.integer_sqrt())
+		Q64_128::from_u384_root(scaled_value.integer_sqrt())
 	}
 
 	/// Computes the square root of the `Q64_128` value.
@@ -520,7 +563,7 @@ impl Q64_128 {
 	/// A `Q64_128` instance representing the square root of the value.
 	pub fn sqrt(self) -> Self {
 		let scaled_value = U384::from(self) << Self::FRACTIONAL_BITS;
-		Q64_128::from(scaled_value.integer_sqrt())
+		Q64_128::from_u384_root(scaled_value.integer_sqrt())
 	}
 
 	/// Computes the square root of the division of two `Q64_128` values.
@@ -545,7 +588,7 @@ impl Q64_128 {
 			return Some(Q64_128::ONE);
 		}
 		let division_result = U384::from(q1.checked_div(q2)?);
-		let result = Q64_128::from((division_result << Self::FRACTIONAL_BITS).integer_sqrt());
+		let result = Q64_128::from_u384_root((division_result << Self::FRACTIONAL_BITS).integer_sqrt());
 		Some(result)
 	}
 }
@@ -611,6 +654,28 @@ mod tests {
 				);
 			}
 
+			/// Tests the checked conversion of `U384` values around the 192 bits boundary to `Q64_128`.
+			#[test]
+			fn test_try_from_u384() {
+				let max_value = U192::MAX.into_u384();
+				assert_eq!(
+					Q64_128::try_from(max_value),
+					Ok(Q64_128::new(U192::MAX)),
+					"The largest 192 bits value should be convertible to `Q64_128`."
+				);
+				assert_eq!(
+					Q64_128::try_from(max_value + 1),
+					Err(Q64_128OverflowError),
+					"Values exceeding 192 bits should not be convertible to `Q64_128`."
+				);
+				assert_eq!(
+					Q64_128::try_from(U384::MAX),
+					Err(Q64_128OverflowError),
+					"Values exceeding 192 bits should not be convertible to `Q64_128`."
+				);
+				assert_eq!(Q64_128::try_from(U384::zero()), Ok(Q64_128::new(U192::zero())));
+			}
+
 			/// Tests the conversion of `Q64_128` to `f64`.
 			#[test]
 			fn test_to_f64() {
@@ -925,6 +990,7 @@ mod tests {
 
 			/// Tests various square operations for integer numbers.
 			#[test]
+			#[allow(deprecated)]
 			fn test_square_operations() {
 				let value = Q64_128::from_u64(3);
 				assert_eq!(
@@ -1010,6 +1076,20 @@ mod tests {
 				let denominator = Q64_128::from_u64(0);
 				let _ = numerator / denominator;
 			}
+
+			/// Tests that an overflowing multiplication results in a panic.
+			#[test]
+			#[should_panic(expected = "Q64_128 multiplication overflow")]
+			fn test_mul_overflow_panic() {
+				let _ = Q64_128::new(U192::MAX) * Q64_128::from_u64(2);
+			}
+
+			/// Tests that an overflowing division results in a panic.
+			#[test]
+			#[should_panic(expected = "Q64_128 division overflow")]
+			fn test_div_overflow_panic() {
+				let _ = Q64_128::new(U192::MAX) / Q64_128::from_f64(0.5).unwrap();
+			}
 		}
 	}
 
@@ -1327,7 +1407,7 @@ mod tests {
 			    let result = q1.checked_mul(q2);
 			
 			    if let Some(product) = result {
-			        let expected_product: Q64_128 = ((value1.into_u384() * value2.into_u384()) >> Q64_128::FRACTIONAL_BITS).into();
+			        let expected_product: Q64_128 = Q64_128::try_from((value1.into_u384() * value2.into_u384()) >> Q64_128::FRACTIONAL_BITS).unwrap();
 			        prop_assert_eq!(
 			            product,
 			            expected_product,
@@ -1364,7 +1444,7 @@ mod tests {
 			    if value2 == U192::zero() {
 			        prop_assert!(result.is_none(), "Division by zero should return None");
 			    } else if let Some(quotient) = result {
-			        let expected_quotient: Q64_128 = ((value1.into_u384() << Q64_128::FRACTIONAL_BITS) / value2.into_u384()).into();
+			        let expected_quotient: Q64_128 = Q64_128::try_from((value1.into_u384() << Q64_128::FRACTIONAL_BITS) / value2.into_u384()).unwrap();
 			        prop_assert_eq!(
 			            quotient,
 			            expected_quotient,
@@ -1385,6 +1465,50 @@ mod tests {
 			    }
 			}
 
+			/// Tests the checked conversion of `U384` values on both sides of the 192 bits boundary.
+			///
+			/// Values up to `U192::MAX` must convert losslessly, larger values must return an error.
+			#[test]
+			fn test_try_from_u384_overflow_boundary(offset in arbitrary_u128(), is_overflowing in any::<bool>()) {
+			    let boundary = U192::MAX.into_u384() + 1;
+			    let value = if is_overflowing {
+			        boundary + U384::from(offset)
+			    } else {
+			        boundary - 1 - U384::from(offset)
+			    };
+			
+			    match Q64_128::try_from(value) {
+			        Ok(q64_128) => {
+			            prop_assert!(!is_overflowing, "Conversion should fail for overflowing value {}", value);
+			            prop_assert_eq!(q64_128.raw_value().into_u384(), value);
+			        }
+			        Err(error) => {
+			            prop_assert!(is_overflowing, "Conversion should succeed for value {}", value);
+			            prop_assert_eq!(error, Q64_128OverflowError);
+			        }
+			    }
+			}
+
+			/// Tests `checked_mul` and `checked_div` on both sides of the overflow boundary.
+			///
+			/// Multiplying by `2^shift` or dividing by `2^-shift` shifts the raw value, which must
+			/// return `None` exactly when the shifted value exceeds 192 bits.
+			#[test]
+			fn test_checked_mul_and_div_overflow_boundary(value1 in arbitrary_u128(), value2 in arbitrary_u64(), shift in 0usize..=128) {
+			    let value = U192::from(value1) * U192::from(value2);
+			    let q64_128 = Q64_128::new(value);
+			    let expected = if (value.leading_zeros() as usize) < shift {
+			        None
+			    } else {
+			        Some(Q64_128::new(value << shift))
+			    };
+			
+			    if shift < 64 {
+			        prop_assert_eq!(q64_128.checked_mul(Q64_128::from_u64(1 << shift)), expected);
+			    }
+			    prop_assert_eq!(q64_128.checked_div(Q64_128::new(U192::one() << (128 - shift))), expected);
+			}
+
         }
 	}
 }
\ No newline at end of file
@@ -0,0 +1,320 @@
+//! Off-chain quotes of the operations of a constant product AMM.
+//!
+//! The quotes run the same calculations and tolerance checks as the program, so a quote succeeds
+//! exactly when the matching instruction would, given the same pool state and fee rates.
+use crate::cp_amm::{
+    calculate_afterswap_liquidity, calculate_base_quote_ratio_sqrt, calculate_constant_product_sqrt, calculate_swap_fee_amount,
+    calculate_burn_exact_withdraw, calculate_liquidity_from_share, calculate_lp_mint_for_provided_liquidity, calculate_price_impact_basis_points,
+    is_within_tolerance, ADJUST_LIQUIDITY_RATIO_SQRT_TOLERANCE, FEE_MAX_BASIS_POINTS, MIN_LIQUIDITY, SWAP_CONSTANT_PRODUCT_SQRT_TOLERANCE,
+};
+use crate::Q64_128;
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::wasm_bindgen;
+
+/// The liquidity state of a constant product AMM, as stored in the `CpAmm` account.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CpAmmState {
+    base_liquidity: u64,
+    quote_liquidity: u64,
+    lp_tokens_supply: u64,
+    constant_product_sqrt: Q64_128,
+    base_quote_ratio_sqrt: Q64_128,
+}
+
+/// The result of a swap quote.
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SwapQuote {
+    /// The amount of tokens the swap returns.
+    pub amount_out: u64,
+    /// The part of the swap amount paid to the liquidity providers.
+    pub providers_fee_amount: u64,
+    /// The part of the swap amount paid to the protocol.
+    pub protocol_fee_amount: u64,
+    /// The change of the pool price caused by the swap, measured in basis points.
+    pub price_impact_basis_points: u64,
+}
+
+/// The result of a provide quote.
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ProvideQuote {
+    /// The amount of LP tokens the provide mints.
+    pub lp_tokens_to_mint: u64,
+}
+
+/// The result of a withdraw quote.
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WithdrawQuote {
+    /// The amount of LP tokens the withdraw burns.
+    pub lp_tokens_to_burn: u64,
+    /// The amount of base tokens the withdraw returns.
+    pub base_withdraw_amount: u64,
+    /// The amount of quote tokens the withdraw returns.
+    pub quote_withdraw_amount: u64,
+}
+
+impl CpAmmState {
+    /// Creates the state of a launched AMM.
+    ///
+    /// Pools with decimals normalization store their constant product and ratio in a virtual decimal basis,
+    /// so their liquidity must be passed scaled by `10^normalization_exponent` and the quoted amounts scaled back.
+    ///
+    /// # Parameters
+    /// - `base_liquidity`: The base liquidity.
+    /// - `quote_liquidity`: The quote liquidity.
+    /// - `lp_tokens_supply`: The LP tokens supply.
+    /// - `constant_product_sqrt`: The square root of the constant product.
+    /// - `base_quote_ratio_sqrt`: The square root of the base-to-quote liquidity ratio.
+    ///
+    /// # Returns
+    /// - `Some(CpAmmState)` if the state is launched.
+    /// - `None` if any of the values is zero.
+    pub fn new(base_liquidity: u64, quote_liquidity: u64, lp_tokens_supply: u64, constant_product_sqrt: Q64_128, base_quote_ratio_sqrt: Q64_128) -> Option<Self> {
+        if base_liquidity == 0 || quote_liquidity == 0 || lp_tokens_supply == 0 || constant_product_sqrt.is_zero() || base_quote_ratio_sqrt.is_zero() {
+            return None;
+        }
+        Some(Self {
+            base_liquidity,
+            quote_liquidity,
+            lp_tokens_supply,
+            constant_product_sqrt,
+            base_quote_ratio_sqrt,
+        })
+    }
+
+    /// Creates the state a pool has right after launching with the given liquidity.
+    ///
+    /// # Parameters
+    /// - `base_liquidity`: The launch base liquidity.
+    /// - `quote_liquidity`: The launch quote liquidity.
+    ///
+    /// # Returns
+    /// - `Some(CpAmmState)` with the launched state.
+    /// - `None` if any of the liquidity amounts is zero.
+    pub fn launched(base_liquidity: u64, quote_liquidity: u64) -> Option<Self> {
+        let constant_product_sqrt = calculate_constant_product_sqrt(base_liquidity, quote_liquidity)?;
+        let base_quote_ratio_sqrt = calculate_base_quote_ratio_sqrt(base_liquidity, quote_liquidity)?;
+        Self::new(base_liquidity, quote_liquidity, constant_product_sqrt.as_u64(), constant_product_sqrt, base_quote_ratio_sqrt)
+    }
+
+    #[inline]
+    pub fn base_liquidity(&self) -> u64 {
+        self.base_liquidity
+    }
+    #[inline]
+    pub fn quote_liquidity(&self) -> u64 {
+        self.quote_liquidity
+    }
+    #[inline]
+    pub fn lp_tokens_supply(&self) -> u64 {
+        self.lp_tokens_supply
+    }
+    #[inline]
+    pub fn constant_product_sqrt(&self) -> Q64_128 {
+        self.constant_product_sqrt
+    }
+    #[inline]
+    pub fn base_quote_ratio_sqrt(&self) -> Q64_128 {
+        self.base_quote_ratio_sqrt
+    }
+
+    /// Quotes a swap, mirroring `CpAmm::get_swap_payload`.
+    ///
+    /// # Parameters
+    /// - `swap_amount`: The amount of tokens to swap, before fees.
+    /// - `providers_fee_rate_basis_points`: The providers fee rate, measured in basis points.
+    /// - `protocol_fee_rate_basis_points`: The protocol fee rate, measured in basis points.
+    /// - `is_in_out`: Whether base tokens are swapped for quote tokens (true) or the reverse (false).
+    ///
+    /// # Returns
+    /// - `Some(SwapQuote)` if the program would accept the swap.
+    /// - `None` if the swap would fail.
+    pub fn quote_swap(&self, swap_amount: u64, providers_fee_rate_basis_points: u16, protocol_fee_rate_basis_points: u16, is_in_out: bool) -> Option<SwapQuote> {
+        if swap_amount == 0 || (providers_fee_rate_basis_points as u128 + protocol_fee_rate_basis_points as u128) > FEE_MAX_BASIS_POINTS {
+            return None;
+        }
+        let providers_fee_amount = calculate_swap_fee_amount(swap_amount, providers_fee_rate_basis_points);
+        let protocol_fee_amount = calculate_swap_fee_amount(swap_amount, protocol_fee_rate_basis_points);
+        if (providers_fee_amount == 0 && providers_fee_rate_basis_points > 0) || (protocol_fee_amount == 0 && protocol_fee_rate_basis_points > 0) {
+            return None;
+        }
+
+        let amount_after_fees = swap_amount.checked_sub(providers_fee_amount)?.checked_sub(protocol_fee_amount)?;
+        let (new_base_liquidity, new_quote_liquidity) = calculate_afterswap_liquidity(
+            self.constant_product_sqrt, self.base_liquidity, self.quote_liquidity, amount_after_fees, is_in_out
+        )?;
+        let amount_out = if is_in_out {
+            self.quote_liquidity.checked_sub(new_quote_liquidity)?
+        } else {
+            self.base_liquidity.checked_sub(new_base_liquidity)?
+        };
+        if amount_out == 0 {
+            return None;
+        }
+
+        let new_constant_product_sqrt = calculate_constant_product_sqrt(new_base_liquidity, new_quote_liquidity)?;
+        if !is_within_tolerance(self.constant_product_sqrt, new_constant_product_sqrt, SWAP_CONSTANT_PRODUCT_SQRT_TOLERANCE)? {
+            return None;
+        }
+
+        let (final_base_liquidity, final_quote_liquidity) = if is_in_out {
+            (new_base_liquidity.checked_add(providers_fee_amount)?, new_quote_liquidity)
+        } else {
+            (new_base_liquidity, new_quote_liquidity.checked_add(providers_fee_amount)?)
+        };
+        let price_impact_basis_points = calculate_price_impact_basis_points(self.base_quote_ratio_sqrt, final_base_liquidity, final_quote_liquidity)?;
+
+        Some(SwapQuote {
+            amount_out,
+            providers_fee_amount,
+            protocol_fee_amount,
+            price_impact_basis_points,
+        })
+    }
+
+    /// Quotes a provide, mirroring `CpAmm::get_provide_payload`.
+    ///
+    /// # Parameters
+    /// - `base_liquidity`: The amount of base liquidity to provide.
+    /// - `quote_liquidity`: The amount of quote liquidity to provide.
+    ///
+    /// # Returns
+    /// - `Some(ProvideQuote)` if the program would accept the provide.
+    /// - `None` if the provide would fail.
+    pub fn quote_provide(&self, base_liquidity: u64, quote_liquidity: u64) -> Option<ProvideQuote> {
+        if base_liquidity == 0 || quote_liquidity == 0 {
+            return None;
+        }
+        let new_base_liquidity = self.base_liquidity.checked_add(base_liquidity)?;
+        let new_quote_liquidity = self.quote_liquidity.checked_add(quote_liquidity)?;
+        self.validate_liquidity_ratio(new_base_liquidity, new_quote_liquidity)?;
+
+        let new_constant_product_sqrt = calculate_constant_product_sqrt(new_base_liquidity, new_quote_liquidity)?;
+        let lp_tokens_to_mint = calculate_lp_mint_for_provided_liquidity(self.constant_product_sqrt, self.lp_tokens_supply, new_constant_product_sqrt)?;
+        self.lp_tokens_supply.checked_add(lp_tokens_to_mint)?;
+
+        Some(ProvideQuote { lp_tokens_to_mint })
+    }
+
+    /// Quotes a withdraw, mirroring `CpAmm::get_withdraw_payload`.
+    ///
+    /// The state doesn't track the locked LP tokens, so withdraws taking the liquidity from at least `MIN_LIQUIDITY`
+    /// below it are quoted as failing, even the ones the program accepts because only the locked LP tokens remain.
+    ///
+    /// # Parameters
+    /// - `lp_tokens`: The number of LP tokens to redeem.
+    /// - `is_burn_exact`: Whether only the LP tokens the rounded payout is worth are burned.
+    ///
+    /// # Returns
+    /// - `Some(WithdrawQuote)` if the program would accept the withdraw.
+    /// - `None` if the withdraw would fail.
+    pub fn quote_withdraw(&self, lp_tokens: u64, is_burn_exact: bool) -> Option<WithdrawQuote> {
+        if lp_tokens == 0 {
+            return None;
+        }
+        self.lp_tokens_supply.checked_sub(lp_tokens)?;
+        let (base_withdraw_amount, quote_withdraw_amount) = calculate_liquidity_from_share(
+            self.constant_product_sqrt, self.base_quote_ratio_sqrt, self.lp_tokens_supply, lp_tokens
+        )?;
+        let (lp_tokens_to_burn, base_withdraw_amount, quote_withdraw_amount) = if is_burn_exact {
+            calculate_burn_exact_withdraw(self.base_liquidity, self.quote_liquidity, self.lp_tokens_supply, lp_tokens, base_withdraw_amount, quote_withdraw_amount)?
+        } else {
+            (lp_tokens, base_withdraw_amount, quote_withdraw_amount)
+        };
+        let new_base_liquidity = self.base_liquidity.checked_sub(base_withdraw_amount)?;
+        let new_quote_liquidity = self.quote_liquidity.checked_sub(quote_withdraw_amount)?;
+        let drops_below_minimum = |liquidity: u64, new_liquidity: u64| liquidity >= MIN_LIQUIDITY && new_liquidity < MIN_LIQUIDITY;
+        if drops_below_minimum(self.base_liquidity, new_base_liquidity) || drops_below_minimum(self.quote_liquidity, new_quote_liquidity) {
+            return None;
+        }
+        self.validate_liquidity_ratio(new_base_liquidity, new_quote_liquidity)?;
+
+        Some(WithdrawQuote {
+            lp_tokens_to_burn,
+            base_withdraw_amount,
+            quote_withdraw_amount,
+        })
+    }
+
+    /// Checks the liquidity ratio after a provide or withdraw stays within `ADJUST_LIQUIDITY_RATIO_SQRT_TOLERANCE`.
+    fn validate_liquidity_ratio(&self, new_base_liquidity: u64, new_quote_liquidity: u64) -> Option<()> {
+        let new_base_quote_ratio_sqrt = calculate_base_quote_ratio_sqrt(new_base_liquidity, new_quote_liquidity)?;
+        is_within_tolerance(self.base_quote_ratio_sqrt, new_base_quote_ratio_sqrt, ADJUST_LIQUIDITY_RATIO_SQRT_TOLERANCE)?
+            .then_some(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cp_amm_state_new_rejects_zero_values() {
+        assert!(CpAmmState::new(0, 1, 1, Q64_128::ONE, Q64_128::ONE).is_none());
+        assert!(CpAmmState::new(1, 1, 0, Q64_128::ONE, Q64_128::ONE).is_none());
+        assert!(CpAmmState::new(1, 1, 1, Q64_128::default(), Q64_128::ONE).is_none());
+        assert!(CpAmmState::new(1, 1, 1, Q64_128::ONE, Q64_128::ONE).is_some());
+    }
+
+    #[test]
+    fn test_quote_swap() {
+        let state = CpAmmState::launched(1_000_000_000, 4_000_000_000).unwrap();
+        let quote = state.quote_swap(1_000_000, 25, 5, true).unwrap();
+
+        assert_eq!(quote.providers_fee_amount, 2_500);
+        assert_eq!(quote.protocol_fee_amount, 500);
+        // 997_000 base tokens in at a price of 4 quote per base, minus the curve slippage
+        assert_eq!(quote.amount_out, 3_984_028);
+        assert_eq!(quote.price_impact_basis_points, 20);
+
+        let reverse_quote = state.quote_swap(4_000_000, 0, 0, false).unwrap();
+        assert_eq!(reverse_quote.amount_out, 999_001);
+
+        // The fee of a small swap is rounded up to a full token instead of down to zero
+        let small_quote = state.quote_swap(10, 25, 0, true).unwrap();
+        assert_eq!(small_quote.providers_fee_amount, 1);
+        assert_eq!(small_quote.amount_out, 36);
+    }
+
+    #[test]
+    fn test_quote_swap_failures() {
+        let state = CpAmmState::launched(1_000_000_000, 4_000_000_000).unwrap();
+        assert!(state.quote_swap(0, 25, 5, true).is_none());
+        assert!(state.quote_swap(1_000_000, 9_000, 1_001, true).is_none());
+        assert!(state.quote_swap(u64::MAX, 0, 0, true).is_none());
+    }
+
+    #[test]
+    fn test_quote_provide() {
+        let state = CpAmmState::launched(1_000_000_000, 4_000_000_000).unwrap();
+        let quote = state.quote_provide(100_000_000, 400_000_000).unwrap();
+        // Minted LP tokens are rounded down
+        assert_eq!(quote.lp_tokens_to_mint, state.lp_tokens_supply() / 10 - 1);
+
+        assert!(state.quote_provide(0, 400_000_000).is_none());
+        // The ratio deviates from the pool ratio
+        assert!(state.quote_provide(100_000_000, 100_000_000).is_none());
+    }
+
+    #[test]
+    fn test_quote_withdraw() {
+        let state = CpAmmState::launched(1_000_000_000, 4_000_000_000).unwrap();
+        let quote = state.quote_withdraw(state.lp_tokens_supply() / 4, false).unwrap();
+        assert_eq!(quote.lp_tokens_to_burn, state.lp_tokens_supply() / 4);
+        assert_eq!(quote.base_withdraw_amount, 250_000_000);
+        assert_eq!(quote.quote_withdraw_amount, 1_000_000_000);
+
+        assert!(state.quote_withdraw(0, false).is_none());
+        assert!(state.quote_withdraw(state.lp_tokens_supply() + 1, false).is_none());
+        // Draining the pool fails
+        assert!(state.quote_withdraw(state.lp_tokens_supply(), false).is_none());
+        // So does leaving dust reserves
+        assert_eq!(state.quote_withdraw(state.lp_tokens_supply() - 4_000, false).unwrap().base_withdraw_amount, 1_000_000_000 - 2_000);
+        assert!(state.quote_withdraw(state.lp_tokens_supply() - 1_000, false).is_none());
+        // Reserves already below the minimum don't lock the LPs out
+        let state = CpAmmState::launched(400, 1_600_000).unwrap();
+        assert_eq!(state.quote_withdraw(state.lp_tokens_supply() / 2, false).unwrap().base_withdraw_amount, 200);
+    }
+}
\ No newline at end of file
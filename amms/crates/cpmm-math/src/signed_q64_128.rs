@@ -0,0 +1,271 @@
+use std::ops::Neg;
+use crate::Q64_128;
+
+/// Represents a signed fixed-point number as a sign and a `Q64_128` magnitude.
+///
+/// The `SignedQ64_128` type is used for deltas between `Q64_128` values, such as price or
+/// constant product changes, where the direction of the change matters. Zero is always
+/// stored as non-negative, so equal values have a single representation.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct SignedQ64_128 {
+	/// The absolute value.
+	magnitude: Q64_128,
+	/// Whether the value is below zero.
+	is_negative: bool,
+}
+
+impl SignedQ64_128 {
+	/// The zero value.
+	pub const ZERO: Self = Self::from_q64_128(Q64_128::from_u64(0));
+
+	/// Creates a new `SignedQ64_128` from a magnitude and a sign.
+	///
+	/// # Parameters
+	/// - `magnitude`: The absolute value.
+	/// - `is_negative`: Whether the value is below zero, ignored for zero magnitude.
+	///
+	/// # Returns
+	/// A new `SignedQ64_128` instance.
+	pub fn new(magnitude: Q64_128, is_negative: bool) -> Self {
+		Self {
+			magnitude,
+			is_negative: is_negative && !magnitude.is_zero(),
+		}
+	}
+
+	/// Creates a non-negative `SignedQ64_128` from a `Q64_128` value.
+	///
+	/// # Parameters
+	/// - `value`: The value to be converted.
+	///
+	/// # Returns
+	/// A new `SignedQ64_128` instance equal to `value`.
+	pub const fn from_q64_128(value: Q64_128) -> Self {
+		Self {
+			magnitude: value,
+			is_negative: false,
+		}
+	}
+
+	/// Calculates the signed change from one `Q64_128` value to another.
+	///
+	/// # Parameters
+	/// - `from`: The initial value.
+	/// - `to`: The new value.
+	///
+	/// # Returns
+	/// A `SignedQ64_128` equal to `to - from`.
+	pub fn delta(from: Q64_128, to: Q64_128) -> Self {
+		Self::new(from.abs_diff(to), to < from)
+	}
+
+	/// Returns the absolute value.
+	#[inline]
+	pub fn magnitude(&self) -> Q64_128 {
+		self.magnitude
+	}
+
+	/// Checks if the value is below zero.
+	#[inline]
+	pub fn is_negative(&self) -> bool {
+		self.is_negative
+	}
+
+	/// Checks if the value is zero.
+	#[inline]
+	pub fn is_zero(&self) -> bool {
+		self.magnitude.is_zero()
+	}
+
+	/// Checks if the absolute value doesn't exceed the tolerance.
+	///
+	/// # Parameters
+	/// - `tolerance`: The maximal allowed absolute value.
+	///
+	/// # Returns
+	/// `true` if `|self| <= tolerance`, `false` otherwise.
+	#[inline]
+	pub fn is_within(&self, tolerance: Q64_128) -> bool {
+		self.magnitude <= tolerance
+	}
+
+	/// Performs a checked addition of two `SignedQ64_128` values.
+	///
+	/// # Parameters
+	/// - `self`: The first `SignedQ64_128` value.
+	/// - `rhs`: The second `SignedQ64_128` value to add to `self`.
+	///
+	/// # Returns
+	/// An `Option<SignedQ64_128>`:
+	/// - `Some(SignedQ64_128)` containing the result if the magnitude does not overflow.
+	/// - `None` if the magnitude overflows.
+	pub fn checked_add(self, rhs: Self) -> Option<Self> {
+		if self.is_negative == rhs.is_negative {
+			return Some(Self::new(self.magnitude.checked_add(rhs.magnitude)?, self.is_negative));
+		}
+		if self.magnitude >= rhs.magnitude {
+			Some(Self::new(self.magnitude.abs_diff(rhs.magnitude), self.is_negative))
+		} else {
+			Some(Self::new(self.magnitude.abs_diff(rhs.magnitude), rhs.is_negative))
+		}
+	}
+
+	/// Performs a checked subtraction of two `SignedQ64_128` values.
+	///
+	/// # Parameters
+	/// - `self`: The first `SignedQ64_128` value.
+	/// - `rhs`: The second `SignedQ64_128` value to subtract from `self`.
+	///
+	/// # Returns
+	/// An `Option<SignedQ64_128>`:
+	/// - `Some(SignedQ64_128)` containing the result if the magnitude does not overflow.
+	/// - `None` if the magnitude overflows.
+	pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+		self.checked_add(-rhs)
+	}
+
+	/// Performs a checked multiplication of two `SignedQ64_128` values.
+	///
+	/// # Parameters
+	/// - `self`: The first `SignedQ64_128` value.
+	/// - `rhs`: The second `SignedQ64_128` value to multiply with `self`.
+	///
+	/// # Returns
+	/// An `Option<SignedQ64_128>`:
+	/// - `Some(SignedQ64_128)` containing the result if the magnitude does not overflow.
+	/// - `None` if the magnitude overflows.
+	pub fn checked_mul(self, rhs: Self) -> Option<Self> {
+		Some(Self::new(self.magnitude.checked_mul(rhs.magnitude)?, self.is_negative != rhs.is_negative))
+	}
+
+	/// Performs a checked division of two `SignedQ64_128` values.
+	///
+	/// # Parameters
+	/// - `self`: The numerator `SignedQ64_128` value.
+	/// - `rhs`: The denominator `SignedQ64_128` value.
+	///
+	/// # Returns
+	/// An `Option<SignedQ64_128>`:
+	/// - `Some(SignedQ64_128)` containing the result if the division is valid.
+	/// - `None` if the denominator is zero or the magnitude overflows.
+	pub fn checked_div(self, rhs: Self) -> Option<Self> {
+		Some(Self::new(self.magnitude.checked_div(rhs.magnitude)?, self.is_negative != rhs.is_negative))
+	}
+}
+
+/// Implements negation for `SignedQ64_128`.
+///
+/// # Returns
+/// A new `SignedQ64_128` with the same magnitude and the opposite sign.
+impl Neg for SignedQ64_128 {
+	type Output = Self;
+
+	fn neg(self) -> Self::Output {
+		Self::new(self.magnitude, !self.is_negative)
+	}
+}
+
+/// Converts a `SignedQ64_128` into a `f64` value.
+///
+/// # Returns
+/// A `f64` value representing the signed fixed-point number.
+#[cfg(any(test, feature = "float"))]
+impl From<SignedQ64_128> for f64 {
+	fn from(value: SignedQ64_128) -> Self {
+		let magnitude: f64 = value.magnitude.into();
+		if value.is_negative {
+			-magnitude
+		} else {
+			magnitude
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	/// Unit tests for the `SignedQ64_128`.
+	mod unit_tests {
+		use super::*;
+
+		/// Tests that zero has a single non-negative representation.
+		#[test]
+		fn test_zero_is_not_negative() {
+			let negative_zero = SignedQ64_128::new(Q64_128::from_u64(0), true);
+			assert_eq!(negative_zero, SignedQ64_128::ZERO);
+			assert!(!negative_zero.is_negative());
+			assert_eq!(-SignedQ64_128::ZERO, SignedQ64_128::ZERO);
+		}
+
+		/// Tests the direction and magnitude of `delta`.
+		#[test]
+		fn test_delta() {
+			let increase = SignedQ64_128::delta(Q64_128::from_u64(100), Q64_128::from_u64(150));
+			let decrease = SignedQ64_128::delta(Q64_128::from_u64(150), Q64_128::from_u64(100));
+
+			assert!(!increase.is_negative());
+			assert!(decrease.is_negative());
+			assert_eq!(increase.magnitude(), Q64_128::from_u64(50));
+			assert_eq!(decrease, -increase);
+			assert!(SignedQ64_128::delta(Q64_128::from_u64(7), Q64_128::from_u64(7)).is_zero());
+		}
+
+		/// Tests `is_within` for both signs.
+		#[test]
+		fn test_is_within() {
+			let tolerance = Q64_128::from_u64(10);
+			assert!(SignedQ64_128::delta(Q64_128::from_u64(100), Q64_128::from_u64(110)).is_within(tolerance));
+			assert!(SignedQ64_128::delta(Q64_128::from_u64(100), Q64_128::from_u64(90)).is_within(tolerance));
+			assert!(!SignedQ64_128::delta(Q64_128::from_u64(100), Q64_128::from_u64(89)).is_within(tolerance));
+		}
+
+		/// Tests checked arithmetic with mixed signs and overflow handling.
+		#[test]
+		fn test_checked_operations() {
+			let five = SignedQ64_128::from_q64_128(Q64_128::from_u64(5));
+			let minus_three = SignedQ64_128::new(Q64_128::from_u64(3), true);
+
+			let sum: f64 = five.checked_add(minus_three).unwrap().into();
+			let difference: f64 = minus_three.checked_sub(five).unwrap().into();
+			let product: f64 = five.checked_mul(minus_three).unwrap().into();
+			let quotient: f64 = minus_three.checked_div(minus_three).unwrap().into();
+
+			assert_eq!(sum, 2.0);
+			assert_eq!(difference, -8.0);
+			assert_eq!(product, -15.0);
+			assert_eq!(quotient, 1.0);
+
+			let max = SignedQ64_128::from_q64_128(Q64_128::MAX);
+			assert!(max.checked_add(five).is_none());
+			assert!(max.checked_sub(minus_three).is_none());
+			assert_eq!(max.checked_add(-max), Some(SignedQ64_128::ZERO));
+			assert!(five.checked_div(SignedQ64_128::ZERO).is_none());
+		}
+	}
+
+	/// Fuzz tests for the `SignedQ64_128`.
+	mod fuzz_tests {
+		use super::*;
+		use proptest::prelude::*;
+
+		/// Converts an `i64` into a `SignedQ64_128`.
+		fn from_i64(value: i64) -> SignedQ64_128 {
+			SignedQ64_128::new(Q64_128::from_u64(value.unsigned_abs()), value < 0)
+		}
+
+		proptest! {
+			#![proptest_config(ProptestConfig::with_cases(10000))]
+
+			/// Tests that checked addition and subtraction match `i128` arithmetic.
+			#[test]
+			fn test_checked_add_sub_match_i128(a in any::<i64>(), b in any::<i64>()) {
+				let sum: f64 = from_i64(a).checked_add(from_i64(b)).unwrap().into();
+				let difference: f64 = from_i64(a).checked_sub(from_i64(b)).unwrap().into();
+
+				prop_assert_eq!(sum, (a as i128 + b as i128) as f64);
+				prop_assert_eq!(difference, (a as i128 - b as i128) as f64);
+			}
+		}
+	}
+}
\ No newline at end of file
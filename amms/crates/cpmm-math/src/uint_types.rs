@@ -1,13 +1,15 @@
+#[cfg(feature = "anchor")]
 use anchor_lang::{AnchorDeserialize, AnchorSerialize, prelude::borsh, Space};
 use uint::construct_uint;
-use super::{Q64_128};
+use crate::Q64_128;
 
 // Define a 192-bit unsigned integer with Anchor serialization/deserialization.
 construct_uint! {
-    #[derive(AnchorSerialize, AnchorDeserialize)]
-    pub(super) struct U192(3);
+    #[cfg_attr(feature = "anchor", derive(AnchorSerialize, AnchorDeserialize))]
+    pub(crate) struct U192(3);
 }
 
+#[cfg(feature = "anchor")]
 impl Space for U192 {
 	/// Space required for `U192` initialization in bytes.
 	const INIT_SPACE: usize = 24;
@@ -43,7 +45,7 @@ impl U192 {
 
 // Define a 384-bit unsigned integer.
 construct_uint! {
-    pub(super) struct U384(6);
+    pub(crate) struct U384(6);
 }
 impl From<Q64_128> for U384 {
 	/// Converts a `Q64_128` value into a `U384` by expanding its internal representation.
@@ -79,10 +81,7 @@ impl U384 {
 	/// - `Some(Q64_128)` if the value fits within 192 bits.
 	/// - `None` if the value exceeds 192 bits.
 	pub fn checked_as_q64_128(&self) -> Option<Q64_128> {
-		if self.leading_zeros() < 192 {
-			return None;
-		}
-		Some((*self).into())
+		Q64_128::try_from(*self).ok()
 	}
 
 	/// Rounds and converts a `Q128.256` value to a `u128`.
@@ -108,10 +107,7 @@ impl U384 {
 	/// - `None` if the value overflows.
 	#[cfg(test)]
 	pub fn q128_256_to_q64_128_round(&self) -> Option<Q64_128> {
-		if self.leading_zeros() < 64 {
-			return None;
-		}
-		let mut rounded_value = Q64_128::from(self >> 128);
+		let mut rounded_value = Q64_128::try_from(self >> 128).ok()?;
 		if rounded_value == Q64_128::MAX || rounded_value.is_zero() {
 			return Some(rounded_value);
 		}
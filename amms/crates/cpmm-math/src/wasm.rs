@@ -0,0 +1,69 @@
+//! `wasm-bindgen` bindings of the quoting API for web front-ends.
+//!
+//! Amounts are passed as `bigint`. Fixed-point values are passed as the 24 bytes they occupy in
+//! the `CpAmm` account, so front-ends can forward account data without decoding it.
+use wasm_bindgen::prelude::*;
+use crate::quote::{CpAmmState, ProvideQuote, SwapQuote, WithdrawQuote};
+use crate::Q64_128;
+
+/// The size of a serialized `Q64_128`.
+const Q64_128_BYTES: usize = 24;
+
+/// Decodes a `Q64_128` from its little-endian account representation.
+fn q64_128_from_le_bytes(bytes: &[u8]) -> Result<Q64_128, JsError> {
+    if bytes.len() != Q64_128_BYTES {
+        return Err(JsError::new("Q64_128 must be 24 bytes long"));
+    }
+    let low_bits = u128::from_le_bytes(bytes[..16].try_into().unwrap());
+    let high_bits = u64::from_le_bytes(bytes[16..].try_into().unwrap());
+    Ok(Q64_128::from_bits(high_bits, low_bits))
+}
+
+/// Quotes swaps, provides and withdrawals of a single pool state.
+#[wasm_bindgen]
+pub struct CpAmmQuoter {
+    state: CpAmmState,
+}
+
+#[wasm_bindgen]
+impl CpAmmQuoter {
+    /// Creates a quoter from the state stored in a `CpAmm` account.
+    #[wasm_bindgen(constructor)]
+    pub fn new(base_liquidity: u64, quote_liquidity: u64, lp_tokens_supply: u64, constant_product_sqrt: &[u8], base_quote_ratio_sqrt: &[u8]) -> Result<CpAmmQuoter, JsError> {
+        let state = CpAmmState::new(
+            base_liquidity,
+            quote_liquidity,
+            lp_tokens_supply,
+            q64_128_from_le_bytes(constant_product_sqrt)?,
+            q64_128_from_le_bytes(base_quote_ratio_sqrt)?,
+        ).ok_or_else(|| JsError::new("Pool is not launched"))?;
+        Ok(Self { state })
+    }
+
+    /// Creates a quoter for a pool right after launching with the given liquidity.
+    pub fn launched(base_liquidity: u64, quote_liquidity: u64) -> Result<CpAmmQuoter, JsError> {
+        let state = CpAmmState::launched(base_liquidity, quote_liquidity).ok_or_else(|| JsError::new("Launch liquidity is zero"))?;
+        Ok(Self { state })
+    }
+
+    #[wasm_bindgen(js_name = quoteSwap)]
+    pub fn quote_swap(&self, swap_amount: u64, providers_fee_rate_basis_points: u16, protocol_fee_rate_basis_points: u16, is_in_out: bool) -> Result<SwapQuote, JsError> {
+        self.state
+            .quote_swap(swap_amount, providers_fee_rate_basis_points, protocol_fee_rate_basis_points, is_in_out)
+            .ok_or_else(|| JsError::new("Swap would fail"))
+    }
+
+    #[wasm_bindgen(js_name = quoteProvide)]
+    pub fn quote_provide(&self, base_liquidity: u64, quote_liquidity: u64) -> Result<ProvideQuote, JsError> {
+        self.state
+            .quote_provide(base_liquidity, quote_liquidity)
+            .ok_or_else(|| JsError::new("Provide would fail"))
+    }
+
+    #[wasm_bindgen(js_name = quoteWithdraw)]
+    pub fn quote_withdraw(&self, lp_tokens: u64, is_burn_exact: bool) -> Result<WithdrawQuote, JsError> {
+        self.state
+            .quote_withdraw(lp_tokens, is_burn_exact)
+            .ok_or_else(|| JsError::new("Withdraw would fail"))
+    }
+}
\ No newline at end of file
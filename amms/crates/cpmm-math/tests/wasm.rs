@@ -0,0 +1,45 @@
+//! Runs the quoting API through its `wasm-bindgen` bindings.
+//!
+//! Run with `wasm-pack test --node crates/cpmm-math --features wasm`.
+#![cfg(all(target_arch = "wasm32", feature = "wasm"))]
+use cpmm_math::golden::swap_vectors;
+use cpmm_math::wasm::CpAmmQuoter;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+/// `Q64_128` value of 1, as stored in an account.
+const ONE: [u8; 24] = [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0];
+
+#[wasm_bindgen_test]
+fn test_quote_swap() {
+    let quoter = CpAmmQuoter::launched(1_000_000_000, 4_000_000_000).unwrap();
+    let quote = quoter.quote_swap(1_000_000, 25, 5, true).unwrap();
+    assert_eq!(quote.amount_out, 3_984_028);
+    assert_eq!(quote.providers_fee_amount, 2_500);
+    assert_eq!(quote.protocol_fee_amount, 500);
+    assert!(quoter.quote_swap(0, 25, 5, true).is_err());
+}
+
+#[wasm_bindgen_test]
+fn test_quote_swap_matches_swap_vectors() {
+    for vector in swap_vectors() {
+        let quoter = CpAmmQuoter::launched(vector.base_liquidity, vector.quote_liquidity).unwrap();
+        let quote = quoter.quote_swap(vector.swap_amount, vector.providers_fee_rate_basis_points, vector.protocol_fee_rate_basis_points, vector.is_in_out).ok();
+        assert_eq!(quote, vector.expected, "Swap vector {}", vector);
+    }
+}
+
+#[wasm_bindgen_test]
+fn test_quote_provide_and_withdraw() {
+    let quoter = CpAmmQuoter::new(1_000_000, 1_000_000, 1_000_000, &[0u8; 24], &ONE);
+    assert!(quoter.is_err());
+
+    let mut constant_product_sqrt = [0u8; 24];
+    constant_product_sqrt[16..].copy_from_slice(&1_000_000u64.to_le_bytes());
+    let quoter = CpAmmQuoter::new(1_000_000, 1_000_000, 1_000_000, &constant_product_sqrt, &ONE).unwrap();
+
+    assert_eq!(quoter.quote_provide(100_000, 100_000).unwrap().lp_tokens_to_mint, 100_000);
+    let withdraw_quote = quoter.quote_withdraw(500_000, false).unwrap();
+    assert_eq!(withdraw_quote.base_withdraw_amount, 500_000);
+    assert_eq!(withdraw_quote.quote_withdraw_amount, 500_000);
+    assert!(CpAmmQuoter::new(1, 1, 1, &ONE[..16], &ONE).is_err());
+}
\ No newline at end of file
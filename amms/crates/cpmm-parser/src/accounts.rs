@@ -0,0 +1,1965 @@
+use crate::error::ParseError;
+use crate::reader::Reader;
+use crate::types::{CurveKind, DisplayData, EventRecord, FeeBounds, FeeCheckpoint, FeeRamp, FeeSettlement, LoyaltyBoost, MintEquivalence, Pubkey, PoolCreationPricing, PoolStats, PoolStatus, Q64_128, SwapOriginPolicy, TraderFeeTier, TradingSchedule, VirtualPriceCheckpoint, WithdrawRateLimiter, EVENT_RING_CAPACITY, FEE_CHECKPOINTS_CAPACITY, MINT_EQUIVALENCES_CAPACITY, POOL_INDEX_CAPACITY, ROUTER_REGISTRY_CAPACITY, TRADER_FEE_TIERS_CAPACITY, VIRTUAL_PRICE_CHECKPOINTS_CAPACITY, WP_AMM_TOKENS_COUNT};
+
+/// Mirror of the on-chain `AmmsConfigsManager` account.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AmmsConfigsManager {
+    pub authority: Pubkey,
+    pub head_authority: Pubkey,
+    pub configs_count: u64,
+    pub bump: u8,
+    pub pool_creation_window_slots: u64,
+    pub max_pools_per_window: u16,
+    pub layout_version: u8,
+}
+
+impl AmmsConfigsManager {
+    pub const DISCRIMINATOR: [u8; 8] = [139, 250, 201, 66, 232, 224, 136, 144];
+
+    /// Account data size in bytes, including the discriminator.
+    pub const LEN: usize = 8 + 84;
+
+    /// Parses raw account data, trailing bytes are ignored.
+    pub fn parse(data: &[u8]) -> Result<Self, ParseError> {
+        let mut reader = Reader::new(data);
+        reader.read_discriminator(&Self::DISCRIMINATOR)?;
+        Ok(Self {
+            authority: reader.read_pubkey()?,
+            head_authority: reader.read_pubkey()?,
+            configs_count: reader.read_u64()?,
+            bump: reader.read_u8()?,
+            pool_creation_window_slots: reader.read_u64()?,
+            max_pools_per_window: reader.read_u16()?,
+            layout_version: reader.read_u8()?,
+        })
+    }
+}
+
+/// Mirror of the on-chain `AmmsConfig` account.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AmmsConfig {
+    pub bump: u8,
+    pub id: u64,
+    pub fee_authority: Pubkey,
+    pub providers_fee_rate_basis_points: u16,
+    pub protocol_fee_rate_basis_points: u16,
+    pub incident_withdraw_limit_basis_points: u16,
+    pub keeper_bounty_basis_points: u16,
+    pub min_provide_lp_tokens: u64,
+    pub min_withdraw_lp_tokens: u64,
+    pub min_mint_decimals: u8,
+    pub max_mint_decimals: u8,
+    pub is_protocol_rebalance_enabled: bool,
+    pub fee_bounds: Option<FeeBounds>,
+    pub providers_fee_ramp: Option<FeeRamp>,
+    pub lending_adapter: Option<Pubkey>,
+    pub max_lend_out_basis_points: u16,
+    pub lend_out_interest_rate_basis_points: u16,
+    pub pools_count: u64,
+    pub loyalty_boost: Option<LoyaltyBoost>,
+    pub max_launch_price_orders: u8,
+    pub swap_constant_product_tolerance_ppb: u32,
+    pub adjust_liquidity_ratio_tolerance_ppb: u32,
+    pub fee_settlement: Option<FeeSettlement>,
+    pub max_creator_fee_share_basis_points: u16,
+    pub stale_pool_close_epochs: u16,
+    pub stale_pool_closer_share_basis_points: u16,
+    pub screening_program: Option<Pubkey>,
+    pub has_mint_equivalences: bool,
+    pub pool_creation_pricing: Option<PoolCreationPricing>,
+    pub protocol_fees_cap_basis_points: u16,
+    pub is_pool_creation_allow_listed: bool,
+    pub lp_concentration_share_basis_points: u16,
+    pub lp_concentration_guard_epochs: u16,
+    pub swap_origin_policy: SwapOriginPolicy,
+    pub swap_origin_policy_until_slot: u64,
+    pub layout_version: u8,
+}
+
+impl AmmsConfig {
+    pub const DISCRIMINATOR: [u8; 8] = [14, 184, 126, 68, 173, 213, 150, 0];
+
+    /// Account data size in bytes, including the discriminator.
+    pub const LEN: usize = 8 + 281;
+
+    /// Parses raw account data, trailing bytes are ignored.
+    pub fn parse(data: &[u8]) -> Result<Self, ParseError> {
+        let mut reader = Reader::new(data);
+        reader.read_discriminator(&Self::DISCRIMINATOR)?;
+        Ok(Self {
+            bump: reader.read_u8()?,
+            id: reader.read_u64()?,
+            fee_authority: reader.read_pubkey()?,
+            providers_fee_rate_basis_points: reader.read_u16()?,
+            protocol_fee_rate_basis_points: reader.read_u16()?,
+            incident_withdraw_limit_basis_points: reader.read_u16()?,
+            keeper_bounty_basis_points: reader.read_u16()?,
+            min_provide_lp_tokens: reader.read_u64()?,
+            min_withdraw_lp_tokens: reader.read_u64()?,
+            min_mint_decimals: reader.read_u8()?,
+            max_mint_decimals: reader.read_u8()?,
+            is_protocol_rebalance_enabled: reader.read_bool()?,
+            fee_bounds: reader.read_option(FeeBounds::read)?,
+            providers_fee_ramp: reader.read_option(FeeRamp::read)?,
+            lending_adapter: reader.read_option(Reader::read_pubkey)?,
+            max_lend_out_basis_points: reader.read_u16()?,
+            lend_out_interest_rate_basis_points: reader.read_u16()?,
+            pools_count: reader.read_u64()?,
+            loyalty_boost: reader.read_option(LoyaltyBoost::read)?,
+            max_launch_price_orders: reader.read_u8()?,
+            swap_constant_product_tolerance_ppb: reader.read_u32()?,
+            adjust_liquidity_ratio_tolerance_ppb: reader.read_u32()?,
+            fee_settlement: reader.read_option(FeeSettlement::read)?,
+            max_creator_fee_share_basis_points: reader.read_u16()?,
+            stale_pool_close_epochs: reader.read_u16()?,
+            stale_pool_closer_share_basis_points: reader.read_u16()?,
+            screening_program: reader.read_option(Reader::read_pubkey)?,
+            has_mint_equivalences: reader.read_bool()?,
+            pool_creation_pricing: reader.read_option(PoolCreationPricing::read)?,
+            protocol_fees_cap_basis_points: reader.read_u16()?,
+            is_pool_creation_allow_listed: reader.read_bool()?,
+            lp_concentration_share_basis_points: reader.read_u16()?,
+            lp_concentration_guard_epochs: reader.read_u16()?,
+            swap_origin_policy: SwapOriginPolicy::read(&mut reader)?,
+            swap_origin_policy_until_slot: reader.read_u64()?,
+            layout_version: reader.read_u8()?,
+        })
+    }
+}
+
+/// Mirror of the on-chain `PoolCreationLimiter` account.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PoolCreationLimiter {
+    pub bump: u8,
+    pub creator: Pubkey,
+    pub window_start_slot: u64,
+    pub pools_created: u16,
+}
+
+impl PoolCreationLimiter {
+    pub const DISCRIMINATOR: [u8; 8] = [236, 2, 169, 102, 237, 105, 128, 76];
+
+    /// Account data size in bytes, including the discriminator.
+    pub const LEN: usize = 8 + 43;
+
+    /// Parses raw account data, trailing bytes are ignored.
+    pub fn parse(data: &[u8]) -> Result<Self, ParseError> {
+        let mut reader = Reader::new(data);
+        reader.read_discriminator(&Self::DISCRIMINATOR)?;
+        Ok(Self {
+            bump: reader.read_u8()?,
+            creator: reader.read_pubkey()?,
+            window_start_slot: reader.read_u64()?,
+            pools_created: reader.read_u16()?,
+        })
+    }
+}
+
+/// Mirror of the on-chain `PoolCreatorPermit` account.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PoolCreatorPermit {
+    pub bump: u8,
+    pub amms_config: Pubkey,
+    pub creator: Pubkey,
+}
+
+impl PoolCreatorPermit {
+    pub const DISCRIMINATOR: [u8; 8] = [110, 212, 136, 1, 232, 10, 162, 142];
+
+    /// Account data size in bytes, including the discriminator.
+    pub const LEN: usize = 8 + 65;
+
+    /// Parses raw account data, trailing bytes are ignored.
+    pub fn parse(data: &[u8]) -> Result<Self, ParseError> {
+        let mut reader = Reader::new(data);
+        reader.read_discriminator(&Self::DISCRIMINATOR)?;
+        Ok(Self {
+            bump: reader.read_u8()?,
+            amms_config: reader.read_pubkey()?,
+            creator: reader.read_pubkey()?,
+        })
+    }
+}
+
+/// Mirror of the on-chain `PoolIndex` account.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PoolIndex {
+    pub bump: u8,
+    pub amms_config: Pubkey,
+    pub bucket: u64,
+    pub pools_count: u16,
+    pub pools: [Pubkey; POOL_INDEX_CAPACITY],
+}
+
+impl PoolIndex {
+    pub const DISCRIMINATOR: [u8; 8] = [52, 134, 192, 185, 67, 48, 14, 96];
+
+    /// Account data size in bytes, including the discriminator.
+    pub const LEN: usize = 8 + 43 + POOL_INDEX_CAPACITY * 32;
+
+    /// Parses raw account data, trailing bytes are ignored.
+    pub fn parse(data: &[u8]) -> Result<Self, ParseError> {
+        let mut reader = Reader::new(data);
+        reader.read_discriminator(&Self::DISCRIMINATOR)?;
+        Ok(Self {
+            bump: reader.read_u8()?,
+            amms_config: reader.read_pubkey()?,
+            bucket: reader.read_u64()?,
+            pools_count: reader.read_u16()?,
+            pools: reader.read_pubkey_array()?,
+        })
+    }
+
+    /// Returns the pools stored in the bucket in creation order.
+    pub fn pools(&self) -> &[Pubkey] {
+        &self.pools[..(self.pools_count as usize).min(POOL_INDEX_CAPACITY)]
+    }
+}
+
+/// Mirror of the on-chain `MintEquivalences` account.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MintEquivalences {
+    pub bump: u8,
+    pub amms_config: Pubkey,
+    pub mints_count: u8,
+    pub mints: [MintEquivalence; MINT_EQUIVALENCES_CAPACITY],
+}
+
+impl MintEquivalences {
+    pub const DISCRIMINATOR: [u8; 8] = [163, 36, 99, 251, 135, 180, 215, 201];
+
+    /// Account data size in bytes, including the discriminator.
+    pub const LEN: usize = 8 + 34 + MINT_EQUIVALENCES_CAPACITY * MintEquivalence::LEN;
+
+    /// Parses raw account data, trailing bytes are ignored.
+    pub fn parse(data: &[u8]) -> Result<Self, ParseError> {
+        let mut reader = Reader::new(data);
+        reader.read_discriminator(&Self::DISCRIMINATOR)?;
+        let bump = reader.read_u8()?;
+        let amms_config = reader.read_pubkey()?;
+        let mints_count = reader.read_u8()?;
+        let mut mints = [MintEquivalence::default(); MINT_EQUIVALENCES_CAPACITY];
+        for mint in mints.iter_mut() {
+            *mint = MintEquivalence::read(&mut reader)?;
+        }
+        Ok(Self {
+            bump,
+            amms_config,
+            mints_count,
+            mints,
+        })
+    }
+
+    /// Returns the mints assigned to groups.
+    pub fn mints(&self) -> &[MintEquivalence] {
+        &self.mints[..(self.mints_count as usize).min(MINT_EQUIVALENCES_CAPACITY)]
+    }
+}
+
+/// Mirror of the on-chain `TraderFeeTiers` account.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TraderFeeTiers {
+    pub bump: u8,
+    pub amms_config: Pubkey,
+    pub tiers_count: u8,
+    pub tiers: [TraderFeeTier; TRADER_FEE_TIERS_CAPACITY],
+}
+
+impl TraderFeeTiers {
+    pub const DISCRIMINATOR: [u8; 8] = [117, 62, 209, 1, 44, 205, 178, 11];
+
+    /// Account data size in bytes, including the discriminator.
+    pub const LEN: usize = 8 + 34 + TRADER_FEE_TIERS_CAPACITY * TraderFeeTier::LEN;
+
+    /// Parses raw account data, trailing bytes are ignored.
+    pub fn parse(data: &[u8]) -> Result<Self, ParseError> {
+        let mut reader = Reader::new(data);
+        reader.read_discriminator(&Self::DISCRIMINATOR)?;
+        let bump = reader.read_u8()?;
+        let amms_config = reader.read_pubkey()?;
+        let tiers_count = reader.read_u8()?;
+        let mut tiers = [TraderFeeTier::default(); TRADER_FEE_TIERS_CAPACITY];
+        for tier in tiers.iter_mut() {
+            *tier = TraderFeeTier::read(&mut reader)?;
+        }
+        Ok(Self {
+            bump,
+            amms_config,
+            tiers_count,
+            tiers,
+        })
+    }
+
+    /// Returns the configured tiers by ascending volume.
+    pub fn tiers(&self) -> &[TraderFeeTier] {
+        &self.tiers[..(self.tiers_count as usize).min(TRADER_FEE_TIERS_CAPACITY)]
+    }
+}
+
+/// Mirror of the on-chain `TraderVolume` account.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TraderVolume {
+    pub bump: u8,
+    pub amms_config: Pubkey,
+    pub trader: Pubkey,
+    pub volume_usd: u64,
+    pub last_update_timestamp: i64,
+}
+
+impl TraderVolume {
+    pub const DISCRIMINATOR: [u8; 8] = [53, 98, 98, 45, 195, 3, 43, 35];
+
+    /// Account data size in bytes, including the discriminator.
+    pub const LEN: usize = 8 + 81;
+
+    /// Parses raw account data, trailing bytes are ignored.
+    pub fn parse(data: &[u8]) -> Result<Self, ParseError> {
+        let mut reader = Reader::new(data);
+        reader.read_discriminator(&Self::DISCRIMINATOR)?;
+        Ok(Self {
+            bump: reader.read_u8()?,
+            amms_config: reader.read_pubkey()?,
+            trader: reader.read_pubkey()?,
+            volume_usd: reader.read_u64()?,
+            last_update_timestamp: reader.read_i64()?,
+        })
+    }
+}
+
+/// Mirror of the on-chain `RouterRegistry` account.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RouterRegistry {
+    pub bump: u8,
+    pub routers_count: u8,
+    pub routers: [Pubkey; ROUTER_REGISTRY_CAPACITY],
+}
+
+impl RouterRegistry {
+    pub const DISCRIMINATOR: [u8; 8] = [189, 40, 209, 121, 190, 170, 25, 122];
+
+    /// Account data size in bytes, including the discriminator.
+    pub const LEN: usize = 8 + 2 + ROUTER_REGISTRY_CAPACITY * 32;
+
+    /// Parses raw account data, trailing bytes are ignored.
+    pub fn parse(data: &[u8]) -> Result<Self, ParseError> {
+        let mut reader = Reader::new(data);
+        reader.read_discriminator(&Self::DISCRIMINATOR)?;
+        Ok(Self {
+            bump: reader.read_u8()?,
+            routers_count: reader.read_u8()?,
+            routers: reader.read_pubkey_array()?,
+        })
+    }
+
+    /// Returns the approved router program ids.
+    pub fn routers(&self) -> &[Pubkey] {
+        &self.routers[..(self.routers_count as usize).min(ROUTER_REGISTRY_CAPACITY)]
+    }
+}
+
+/// Mirror of the on-chain `CpAmm` account.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CpAmm {
+    pub layout_version: u8,
+    pub status: PoolStatus,
+    pub bump: u8,
+    pub base_vault_bump: u8,
+    pub quote_vault_bump: u8,
+    pub locked_lp_vault_bump: u8,
+    pub initial_locked_liquidity: u64,
+    pub constant_product_sqrt: Q64_128,
+    pub base_quote_ratio_sqrt: Q64_128,
+    pub base_liquidity: u64,
+    pub quote_liquidity: u64,
+    pub lp_tokens_supply: u64,
+    pub protocol_base_fees_to_redeem: u64,
+    pub protocol_quote_fees_to_redeem: u64,
+    pub base_mint: Pubkey,
+    pub quote_mint: Pubkey,
+    pub lp_mint: Pubkey,
+    pub base_vault: Pubkey,
+    pub quote_vault: Pubkey,
+    pub locked_lp_vault: Pubkey,
+    pub amms_config: Pubkey,
+    pub creator: Pubkey,
+    pub is_incident_limited: bool,
+    pub withdraw_rate_limiter: WithdrawRateLimiter,
+    pub usd_oracle: Option<Pubkey>,
+    pub stats: PoolStats,
+    pub trading_schedule: Option<TradingSchedule>,
+    pub is_lend_out_enabled: bool,
+    pub base_lent_out: u64,
+    pub quote_lent_out: u64,
+    pub liquidity_subscriber: Option<Pubkey>,
+    pub protocol_fee_override_basis_points: Option<u16>,
+    pub swaps_paused: bool,
+    pub provides_paused: bool,
+    pub withdraws_paused: bool,
+    pub creator_fee_share_basis_points: u16,
+    pub creator_base_fees_to_redeem: u64,
+    pub creator_quote_fees_to_redeem: u64,
+    pub initialized_epoch: u64,
+    pub is_fee_parameters_locked: bool,
+    pub base_normalization_exponent: u8,
+    pub quote_normalization_exponent: u8,
+    pub trading_open_slot: u64,
+    pub curve_kind: CurveKind,
+    pub max_protocol_fee_basis_points_at_creation: Option<u16>,
+    pub protected_until_slot: u64,
+    pub display_data: DisplayData,
+    pub largest_provide_share_basis_points: u16,
+    pub concentration_flag: bool,
+    pub slot_start_price: u64,
+    pub slot_start_price_slot: u64,
+}
+
+impl CpAmm {
+    pub const DISCRIMINATOR: [u8; 8] = [105, 219, 233, 13, 147, 109, 73, 100];
+
+    /// Account data size in bytes, including the discriminator.
+    pub const LEN: usize = 8 + 834;
+
+    /// Offset of `layout_version` in the account data, including the discriminator.
+    pub const LAYOUT_VERSION_OFFSET: usize = 8;
+
+    /// Offset of `base_mint` in the account data, including the discriminator.
+    pub const BASE_MINT_OFFSET: usize = 110;
+
+    /// Offset of `quote_mint` in the account data, including the discriminator.
+    pub const QUOTE_MINT_OFFSET: usize = 142;
+
+    /// Offset of `lp_mint` in the account data, including the discriminator.
+    pub const LP_MINT_OFFSET: usize = 174;
+
+    /// Offset of `amms_config` in the account data, including the discriminator.
+    pub const AMMS_CONFIG_OFFSET: usize = 302;
+
+    /// Offset of `creator` in the account data, including the discriminator.
+    pub const CREATOR_OFFSET: usize = 334;
+
+    /// Parses raw account data, trailing bytes are ignored.
+    pub fn parse(data: &[u8]) -> Result<Self, ParseError> {
+        let mut reader = Reader::new(data);
+        reader.read_discriminator(&Self::DISCRIMINATOR)?;
+        Ok(Self {
+            layout_version: reader.read_u8()?,
+            status: PoolStatus::read(&mut reader)?,
+            bump: reader.read_u8()?,
+            base_vault_bump: reader.read_u8()?,
+            quote_vault_bump: reader.read_u8()?,
+            locked_lp_vault_bump: reader.read_u8()?,
+            initial_locked_liquidity: reader.read_u64()?,
+            constant_product_sqrt: Q64_128::read(&mut reader)?,
+            base_quote_ratio_sqrt: Q64_128::read(&mut reader)?,
+            base_liquidity: reader.read_u64()?,
+            quote_liquidity: reader.read_u64()?,
+            lp_tokens_supply: reader.read_u64()?,
+            protocol_base_fees_to_redeem: reader.read_u64()?,
+            protocol_quote_fees_to_redeem: reader.read_u64()?,
+            base_mint: reader.read_pubkey()?,
+            quote_mint: reader.read_pubkey()?,
+            lp_mint: reader.read_pubkey()?,
+            base_vault: reader.read_pubkey()?,
+            quote_vault: reader.read_pubkey()?,
+            locked_lp_vault: reader.read_pubkey()?,
+            amms_config: reader.read_pubkey()?,
+            creator: reader.read_pubkey()?,
+            is_incident_limited: reader.read_bool()?,
+            withdraw_rate_limiter: WithdrawRateLimiter::read(&mut reader)?,
+            usd_oracle: reader.read_option(Reader::read_pubkey)?,
+            stats: PoolStats::read(&mut reader)?,
+            trading_schedule: reader.read_option(TradingSchedule::read)?,
+            is_lend_out_enabled: reader.read_bool()?,
+            base_lent_out: reader.read_u64()?,
+            quote_lent_out: reader.read_u64()?,
+            liquidity_subscriber: reader.read_option(Reader::read_pubkey)?,
+            protocol_fee_override_basis_points: reader.read_option(Reader::read_u16)?,
+            swaps_paused: reader.read_bool()?,
+            provides_paused: reader.read_bool()?,
+            withdraws_paused: reader.read_bool()?,
+            creator_fee_share_basis_points: reader.read_u16()?,
+            creator_base_fees_to_redeem: reader.read_u64()?,
+            creator_quote_fees_to_redeem: reader.read_u64()?,
+            initialized_epoch: reader.read_u64()?,
+            is_fee_parameters_locked: reader.read_bool()?,
+            base_normalization_exponent: reader.read_u8()?,
+            quote_normalization_exponent: reader.read_u8()?,
+            trading_open_slot: reader.read_u64()?,
+            curve_kind: CurveKind::read(&mut reader)?,
+            max_protocol_fee_basis_points_at_creation: reader.read_option(Reader::read_u16)?,
+            protected_until_slot: reader.read_u64()?,
+            display_data: DisplayData::read(&mut reader)?,
+            largest_provide_share_basis_points: reader.read_u16()?,
+            concentration_flag: reader.read_bool()?,
+            slot_start_price: reader.read_u64()?,
+            slot_start_price_slot: reader.read_u64()?,
+        })
+    }
+}
+
+/// Mirror of the on-chain `LendOutLoan` account.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LendOutLoan {
+    pub bump: u8,
+    pub cp_amm: Pubkey,
+    pub mint: Pubkey,
+    pub principal: u64,
+    pub accrued_interest: u64,
+    pub last_accrual_slot: u64,
+    pub is_recalled: bool,
+}
+
+impl LendOutLoan {
+    pub const DISCRIMINATOR: [u8; 8] = [0, 217, 199, 5, 96, 142, 68, 193];
+
+    /// Account data size in bytes, including the discriminator.
+    pub const LEN: usize = 8 + 90;
+
+    /// Parses raw account data, trailing bytes are ignored.
+    pub fn parse(data: &[u8]) -> Result<Self, ParseError> {
+        let mut reader = Reader::new(data);
+        reader.read_discriminator(&Self::DISCRIMINATOR)?;
+        Ok(Self {
+            bump: reader.read_u8()?,
+            cp_amm: reader.read_pubkey()?,
+            mint: reader.read_pubkey()?,
+            principal: reader.read_u64()?,
+            accrued_interest: reader.read_u64()?,
+            last_accrual_slot: reader.read_u64()?,
+            is_recalled: reader.read_bool()?,
+        })
+    }
+}
+
+/// Mirror of the on-chain `LiquidityPosition` account.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LiquidityPosition {
+    pub bump: u8,
+    pub cp_amm: Pubkey,
+    pub owner: Pubkey,
+    pub lp_tokens: u64,
+    pub hold_start_timestamp: i64,
+}
+
+impl LiquidityPosition {
+    pub const DISCRIMINATOR: [u8; 8] = [153, 56, 106, 34, 55, 42, 113, 176];
+
+    /// Account data size in bytes, including the discriminator.
+    pub const LEN: usize = 8 + 81;
+
+    /// Parses raw account data, trailing bytes are ignored.
+    pub fn parse(data: &[u8]) -> Result<Self, ParseError> {
+        let mut reader = Reader::new(data);
+        reader.read_discriminator(&Self::DISCRIMINATOR)?;
+        Ok(Self {
+            bump: reader.read_u8()?,
+            cp_amm: reader.read_pubkey()?,
+            owner: reader.read_pubkey()?,
+            lp_tokens: reader.read_u64()?,
+            hold_start_timestamp: reader.read_i64()?,
+        })
+    }
+}
+
+/// Mirror of the on-chain `TradingSession` account.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TradingSession {
+    pub bump: u8,
+    pub cp_amm: Pubkey,
+    pub owner: Pubkey,
+    pub session_key: Pubkey,
+    pub max_swap_quote_amount: u64,
+    pub max_total_quote_volume: u64,
+    pub total_quote_volume: u64,
+    pub expires_at_slot: u64,
+}
+
+impl TradingSession {
+    pub const DISCRIMINATOR: [u8; 8] = [162, 169, 196, 184, 73, 28, 8, 191];
+
+    /// Account data size in bytes, including the discriminator.
+    pub const LEN: usize = 8 + 129;
+
+    /// Parses raw account data, trailing bytes are ignored.
+    pub fn parse(data: &[u8]) -> Result<Self, ParseError> {
+        let mut reader = Reader::new(data);
+        reader.read_discriminator(&Self::DISCRIMINATOR)?;
+        Ok(Self {
+            bump: reader.read_u8()?,
+            cp_amm: reader.read_pubkey()?,
+            owner: reader.read_pubkey()?,
+            session_key: reader.read_pubkey()?,
+            max_swap_quote_amount: reader.read_u64()?,
+            max_total_quote_volume: reader.read_u64()?,
+            total_quote_volume: reader.read_u64()?,
+            expires_at_slot: reader.read_u64()?,
+        })
+    }
+}
+
+/// Mirror of the on-chain `PoolStatsShard` account.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PoolStatsShard {
+    pub bump: u8,
+    pub cp_amm: Pubkey,
+    pub shard_index: u8,
+    pub stats: PoolStats,
+}
+
+impl PoolStatsShard {
+    pub const DISCRIMINATOR: [u8; 8] = [91, 143, 125, 29, 133, 208, 152, 201];
+
+    /// Account data size in bytes, including the discriminator.
+    pub const LEN: usize = 8 + 34 + PoolStats::LEN;
+
+    /// Parses raw account data, trailing bytes are ignored.
+    pub fn parse(data: &[u8]) -> Result<Self, ParseError> {
+        let mut reader = Reader::new(data);
+        reader.read_discriminator(&Self::DISCRIMINATOR)?;
+        Ok(Self {
+            bump: reader.read_u8()?,
+            cp_amm: reader.read_pubkey()?,
+            shard_index: reader.read_u8()?,
+            stats: PoolStats::read(&mut reader)?,
+        })
+    }
+}
+
+/// Mirror of the on-chain `PoolLookupTable` account.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PoolLookupTable {
+    pub bump: u8,
+    pub cp_amm: Pubkey,
+    pub lookup_table: Pubkey,
+}
+
+impl PoolLookupTable {
+    pub const DISCRIMINATOR: [u8; 8] = [235, 65, 14, 172, 255, 227, 160, 105];
+
+    /// Account data size in bytes, including the discriminator.
+    pub const LEN: usize = 8 + 65;
+
+    /// Parses raw account data, trailing bytes are ignored.
+    pub fn parse(data: &[u8]) -> Result<Self, ParseError> {
+        let mut reader = Reader::new(data);
+        reader.read_discriminator(&Self::DISCRIMINATOR)?;
+        Ok(Self {
+            bump: reader.read_u8()?,
+            cp_amm: reader.read_pubkey()?,
+            lookup_table: reader.read_pubkey()?,
+        })
+    }
+}
+
+/// Mirror of the on-chain `Snapshot` account.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Snapshot {
+    pub bump: u8,
+    pub cp_amm: Pubkey,
+    pub slot: u64,
+    pub merkle_root: [u8; 32],
+    pub holders_count: u32,
+    pub total_lp_tokens: u64,
+}
+
+impl Snapshot {
+    pub const DISCRIMINATOR: [u8; 8] = [137, 213, 28, 133, 224, 161, 48, 108];
+
+    /// Account data size in bytes, including the discriminator.
+    pub const LEN: usize = 8 + 85;
+
+    /// Parses raw account data, trailing bytes are ignored.
+    pub fn parse(data: &[u8]) -> Result<Self, ParseError> {
+        let mut reader = Reader::new(data);
+        reader.read_discriminator(&Self::DISCRIMINATOR)?;
+        Ok(Self {
+            bump: reader.read_u8()?,
+            cp_amm: reader.read_pubkey()?,
+            slot: reader.read_u64()?,
+            merkle_root: reader.read_array()?,
+            holders_count: reader.read_u32()?,
+            total_lp_tokens: reader.read_u64()?,
+        })
+    }
+}
+
+/// Mirror of the on-chain `FeeCheckpoints` account.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FeeCheckpoints {
+    pub bump: u8,
+    pub cp_amm: Pubkey,
+    pub head: u16,
+    pub count: u16,
+    pub checkpoints: [FeeCheckpoint; FEE_CHECKPOINTS_CAPACITY],
+}
+
+impl FeeCheckpoints {
+    pub const DISCRIMINATOR: [u8; 8] = [224, 156, 2, 253, 58, 249, 232, 46];
+
+    /// Account data size in bytes, including the discriminator.
+    pub const LEN: usize = 8 + 37 + FEE_CHECKPOINTS_CAPACITY * FeeCheckpoint::LEN;
+
+    /// Parses raw account data, trailing bytes are ignored.
+    pub fn parse(data: &[u8]) -> Result<Self, ParseError> {
+        let mut reader = Reader::new(data);
+        reader.read_discriminator(&Self::DISCRIMINATOR)?;
+        let bump = reader.read_u8()?;
+        let cp_amm = reader.read_pubkey()?;
+        let head = reader.read_u16()?;
+        let count = reader.read_u16()?;
+        let mut checkpoints = [FeeCheckpoint::default(); FEE_CHECKPOINTS_CAPACITY];
+        for checkpoint in checkpoints.iter_mut() {
+            *checkpoint = FeeCheckpoint::read(&mut reader)?;
+        }
+        Ok(Self {
+            bump,
+            cp_amm,
+            head,
+            count,
+            checkpoints,
+        })
+    }
+}
+
+/// Mirror of the on-chain zero-copy `EventRing` account.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EventRing {
+    pub cp_amm: Pubkey,
+    pub sequence: u64,
+    pub bump: u8,
+    pub records: [EventRecord; EVENT_RING_CAPACITY],
+}
+
+impl EventRing {
+    pub const DISCRIMINATOR: [u8; 8] = [104, 15, 10, 174, 112, 252, 253, 227];
+
+    /// Account data size in bytes, including the discriminator.
+    pub const LEN: usize = 8 + 48 + EVENT_RING_CAPACITY * EventRecord::LEN;
+
+    /// Parses raw account data, trailing bytes are ignored.
+    pub fn parse(data: &[u8]) -> Result<Self, ParseError> {
+        let mut reader = Reader::new(data);
+        reader.read_discriminator(&Self::DISCRIMINATOR)?;
+        let cp_amm = reader.read_pubkey()?;
+        let sequence = reader.read_u64()?;
+        let bump = reader.read_u8()?;
+        reader.read_array::<7>()?;
+        let mut records = [EventRecord::default(); EVENT_RING_CAPACITY];
+        for record in records.iter_mut() {
+            *record = EventRecord::read(&mut reader)?;
+        }
+        Ok(Self {
+            cp_amm,
+            sequence,
+            bump,
+            records,
+        })
+    }
+
+    /// Returns the record of a sequence number, or `None` if it isn't written yet or was overwritten.
+    pub fn record(&self, sequence: u64) -> Option<&EventRecord> {
+        if sequence >= self.sequence || self.sequence - sequence > EVENT_RING_CAPACITY as u64 {
+            return None;
+        }
+        Some(&self.records[(sequence % EVENT_RING_CAPACITY as u64) as usize])
+    }
+}
+
+/// Mirror of the on-chain `VirtualPriceCheckpoints` account.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VirtualPriceCheckpoints {
+    pub bump: u8,
+    pub cp_amm: Pubkey,
+    pub head: u16,
+    pub count: u16,
+    pub checkpoints: [VirtualPriceCheckpoint; VIRTUAL_PRICE_CHECKPOINTS_CAPACITY],
+}
+
+impl VirtualPriceCheckpoints {
+    pub const DISCRIMINATOR: [u8; 8] = [76, 94, 212, 15, 151, 79, 188, 237];
+
+    /// Account data size in bytes, including the discriminator.
+    pub const LEN: usize = 8 + 37 + VIRTUAL_PRICE_CHECKPOINTS_CAPACITY * VirtualPriceCheckpoint::LEN;
+
+    /// Parses raw account data, trailing bytes are ignored.
+    pub fn parse(data: &[u8]) -> Result<Self, ParseError> {
+        let mut reader = Reader::new(data);
+        reader.read_discriminator(&Self::DISCRIMINATOR)?;
+        let bump = reader.read_u8()?;
+        let cp_amm = reader.read_pubkey()?;
+        let head = reader.read_u16()?;
+        let count = reader.read_u16()?;
+        let mut checkpoints = [VirtualPriceCheckpoint::default(); VIRTUAL_PRICE_CHECKPOINTS_CAPACITY];
+        for checkpoint in checkpoints.iter_mut() {
+            *checkpoint = VirtualPriceCheckpoint::read(&mut reader)?;
+        }
+        Ok(Self {
+            bump,
+            cp_amm,
+            head,
+            count,
+            checkpoints,
+        })
+    }
+}
+
+/// Mirror of the on-chain `WpAmm` account.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WpAmm {
+    pub is_initialized: bool,
+    pub is_launched: bool,
+    pub bump: u8,
+    pub vaults_bumps: [u8; WP_AMM_TOKENS_COUNT],
+    pub locked_lp_vault_bump: u8,
+    pub weights_basis_points: [u16; WP_AMM_TOKENS_COUNT],
+    pub initial_locked_liquidity: u64,
+    pub liquidity: [u64; WP_AMM_TOKENS_COUNT],
+    pub lp_tokens_supply: u64,
+    pub mints: [Pubkey; WP_AMM_TOKENS_COUNT],
+    pub lp_mint: Pubkey,
+    pub vaults: [Pubkey; WP_AMM_TOKENS_COUNT],
+    pub locked_lp_vault: Pubkey,
+    pub amms_config: Pubkey,
+    pub creator: Pubkey,
+    pub protocol_fees_to_redeem: [u64; WP_AMM_TOKENS_COUNT],
+}
+
+impl WpAmm {
+    pub const DISCRIMINATOR: [u8; 8] = [228, 92, 52, 198, 135, 43, 50, 60];
+
+    /// Account data size in bytes, including the discriminator.
+    pub const LEN: usize = 8 + 397;
+
+    /// Offset of the first of `mints` in the account data, including the discriminator.
+    pub const MINTS_OFFSET: usize = 61;
+
+    /// Offset of `lp_mint` in the account data, including the discriminator.
+    pub const LP_MINT_OFFSET: usize = 157;
+
+    /// Offset of `amms_config` in the account data, including the discriminator.
+    pub const AMMS_CONFIG_OFFSET: usize = 317;
+
+    /// Offset of `creator` in the account data, including the discriminator.
+    pub const CREATOR_OFFSET: usize = 349;
+
+    /// Parses raw account data, trailing bytes are ignored.
+    pub fn parse(data: &[u8]) -> Result<Self, ParseError> {
+        let mut reader = Reader::new(data);
+        reader.read_discriminator(&Self::DISCRIMINATOR)?;
+        Ok(Self {
+            is_initialized: reader.read_bool()?,
+            is_launched: reader.read_bool()?,
+            bump: reader.read_u8()?,
+            vaults_bumps: reader.read_array()?,
+            locked_lp_vault_bump: reader.read_u8()?,
+            weights_basis_points: reader.read_u16_array()?,
+            initial_locked_liquidity: reader.read_u64()?,
+            liquidity: reader.read_u64_array()?,
+            lp_tokens_supply: reader.read_u64()?,
+            mints: reader.read_pubkey_array()?,
+            lp_mint: reader.read_pubkey()?,
+            vaults: reader.read_pubkey_array()?,
+            locked_lp_vault: reader.read_pubkey()?,
+            amms_config: reader.read_pubkey()?,
+            creator: reader.read_pubkey()?,
+            protocol_fees_to_redeem: reader.read_u64_array()?,
+        })
+    }
+}
+
+/// Any account owned by the program.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProgramAccount {
+    AmmsConfigsManager(AmmsConfigsManager),
+    AmmsConfig(AmmsConfig),
+    PoolCreationLimiter(PoolCreationLimiter),
+    PoolCreatorPermit(PoolCreatorPermit),
+    PoolIndex(Box<PoolIndex>),
+    MintEquivalences(Box<MintEquivalences>),
+    TraderFeeTiers(TraderFeeTiers),
+    TraderVolume(TraderVolume),
+    RouterRegistry(Box<RouterRegistry>),
+    CpAmm(Box<CpAmm>),
+    LendOutLoan(LendOutLoan),
+    LiquidityPosition(LiquidityPosition),
+    TradingSession(TradingSession),
+    PoolStatsShard(PoolStatsShard),
+    PoolLookupTable(PoolLookupTable),
+    Snapshot(Snapshot),
+    FeeCheckpoints(Box<FeeCheckpoints>),
+    VirtualPriceCheckpoints(Box<VirtualPriceCheckpoints>),
+    EventRing(Box<EventRing>),
+    WpAmm(Box<WpAmm>),
+}
+
+/// Parses raw data of any program account, selecting the type by its discriminator.
+pub fn parse_account(data: &[u8]) -> Result<ProgramAccount, ParseError> {
+    let discriminator = Reader::new(data).read_array::<8>()?;
+    match discriminator {
+        AmmsConfigsManager::DISCRIMINATOR => Ok(ProgramAccount::AmmsConfigsManager(AmmsConfigsManager::parse(data)?)),
+        AmmsConfig::DISCRIMINATOR => Ok(ProgramAccount::AmmsConfig(AmmsConfig::parse(data)?)),
+        PoolCreationLimiter::DISCRIMINATOR => Ok(ProgramAccount::PoolCreationLimiter(PoolCreationLimiter::parse(data)?)),
+        PoolCreatorPermit::DISCRIMINATOR => Ok(ProgramAccount::PoolCreatorPermit(PoolCreatorPermit::parse(data)?)),
+        PoolIndex::DISCRIMINATOR => Ok(ProgramAccount::PoolIndex(Box::new(PoolIndex::parse(data)?))),
+        MintEquivalences::DISCRIMINATOR => Ok(ProgramAccount::MintEquivalences(Box::new(MintEquivalences::parse(data)?))),
+        TraderFeeTiers::DISCRIMINATOR => Ok(ProgramAccount::TraderFeeTiers(TraderFeeTiers::parse(data)?)),
+        TraderVolume::DISCRIMINATOR => Ok(ProgramAccount::TraderVolume(TraderVolume::parse(data)?)),
+        RouterRegistry::DISCRIMINATOR => Ok(ProgramAccount::RouterRegistry(Box::new(RouterRegistry::parse(data)?))),
+        CpAmm::DISCRIMINATOR => Ok(ProgramAccount::CpAmm(Box::new(CpAmm::parse(data)?))),
+        LendOutLoan::DISCRIMINATOR => Ok(ProgramAccount::LendOutLoan(LendOutLoan::parse(data)?)),
+        LiquidityPosition::DISCRIMINATOR => Ok(ProgramAccount::LiquidityPosition(LiquidityPosition::parse(data)?)),
+        TradingSession::DISCRIMINATOR => Ok(ProgramAccount::TradingSession(TradingSession::parse(data)?)),
+        PoolStatsShard::DISCRIMINATOR => Ok(ProgramAccount::PoolStatsShard(PoolStatsShard::parse(data)?)),
+        PoolLookupTable::DISCRIMINATOR => Ok(ProgramAccount::PoolLookupTable(PoolLookupTable::parse(data)?)),
+        Snapshot::DISCRIMINATOR => Ok(ProgramAccount::Snapshot(Snapshot::parse(data)?)),
+        FeeCheckpoints::DISCRIMINATOR => Ok(ProgramAccount::FeeCheckpoints(Box::new(FeeCheckpoints::parse(data)?))),
+        VirtualPriceCheckpoints::DISCRIMINATOR => Ok(ProgramAccount::VirtualPriceCheckpoints(Box::new(VirtualPriceCheckpoints::parse(data)?))),
+        EventRing::DISCRIMINATOR => Ok(ProgramAccount::EventRing(Box::new(EventRing::parse(data)?))),
+        WpAmm::DISCRIMINATOR => Ok(ProgramAccount::WpAmm(Box::new(WpAmm::parse(data)?))),
+        _ => Err(ParseError::UnknownDiscriminator(discriminator)),
+    }
+}
+
+#[cfg(test)]
+mod accounts_tests {
+    use super::*;
+    use anchor_lang::{AccountDeserialize, AccountSerialize, Discriminator, Space};
+    use cpmm::state::VersionedAccount;
+    use anchor_lang::prelude::Pubkey as OnChainPubkey;
+
+    /// Serializes values in the Borsh layout of the on-chain structs.
+    #[derive(Default)]
+    struct Writer(Vec<u8>);
+
+    impl Writer {
+        fn bytes(mut self, bytes: &[u8]) -> Self {
+            self.0.extend_from_slice(bytes);
+            self
+        }
+        fn u8(self, value: u8) -> Self {
+            self.bytes(&[value])
+        }
+        fn u16(self, value: u16) -> Self {
+            self.bytes(&value.to_le_bytes())
+        }
+        fn u32(self, value: u32) -> Self {
+            self.bytes(&value.to_le_bytes())
+        }
+        fn u64(self, value: u64) -> Self {
+            self.bytes(&value.to_le_bytes())
+        }
+        fn u128(self, value: u128) -> Self {
+            self.bytes(&value.to_le_bytes())
+        }
+    }
+
+    fn key(seed: u8) -> Pubkey {
+        [seed; 32]
+    }
+
+    fn on_chain_key(seed: u8) -> OnChainPubkey {
+        OnChainPubkey::new_from_array(key(seed))
+    }
+
+    /// Checks that `data` is a valid on-chain encoding of `T` and returns the decoded account.
+    fn assert_on_chain_layout<T: AccountSerialize + AccountDeserialize + Discriminator + Space>(data: &[u8], len: usize) -> T {
+        assert_eq!(data.len(), len);
+        assert_eq!(len, 8 + T::INIT_SPACE);
+        let account = T::try_deserialize(&mut &data[..]).unwrap();
+        let mut serialized = Vec::new();
+        account.try_serialize(&mut serialized).unwrap();
+        assert_eq!(serialized.as_slice(), data);
+        account
+    }
+
+    /// Tests the `AmmsConfigsManager` layout against the on-chain struct.
+    #[test]
+    fn test_amms_configs_manager_layout() {
+        assert_eq!(AmmsConfigsManager::DISCRIMINATOR, cpmm::state::AmmsConfigsManager::DISCRIMINATOR);
+        let data = Writer::default()
+            .bytes(&AmmsConfigsManager::DISCRIMINATOR)
+            .bytes(&key(1))
+            .bytes(&key(2))
+            .u64(17)
+            .u8(254)
+            .u64(1_000)
+            .u16(5)
+            .u8(1)
+            .0;
+
+        let on_chain = assert_on_chain_layout::<cpmm::state::AmmsConfigsManager>(&data, AmmsConfigsManager::LEN);
+        let parsed = AmmsConfigsManager::parse(&data).unwrap();
+        assert_eq!(parsed, AmmsConfigsManager {
+            authority: key(1),
+            head_authority: key(2),
+            configs_count: 17,
+            bump: 254,
+            pool_creation_window_slots: 1_000,
+            max_pools_per_window: 5,
+            layout_version: 1,
+        });
+        assert_eq!(on_chain.authority(), &on_chain_key(1));
+        assert_eq!(on_chain.head_authority(), &on_chain_key(2));
+        assert_eq!(on_chain.configs_count(), parsed.configs_count);
+        assert_eq!(on_chain.bump(), parsed.bump);
+        assert_eq!(on_chain.pool_creation_window_slots(), parsed.pool_creation_window_slots);
+        assert_eq!(on_chain.max_pools_per_window(), parsed.max_pools_per_window);
+        assert_eq!(on_chain.layout_version(), parsed.layout_version);
+        assert_eq!(data[cpmm::state::AmmsConfigsManager::LAYOUT_VERSION_OFFSET], parsed.layout_version);
+        assert_eq!(parse_account(&data), Ok(ProgramAccount::AmmsConfigsManager(parsed)));
+    }
+
+    /// Tests the `AmmsConfig` layout against the on-chain struct.
+    #[test]
+    fn test_amms_config_layout() {
+        assert_eq!(AmmsConfig::DISCRIMINATOR, cpmm::state::AmmsConfig::DISCRIMINATOR);
+        let data = Writer::default()
+            .bytes(&AmmsConfig::DISCRIMINATOR)
+            .u8(253)
+            .u64(3)
+            .bytes(&key(4))
+            .u16(25)
+            .u16(5)
+            .u16(500)
+            .u16(100)
+            .u64(1_000)
+            .u64(2_000)
+            .u8(6)
+            .u8(9)
+            .u8(1)
+            .u8(1).u16(10).u16(50).u16(20)
+            .u8(1).u64(7_000).u64(8_000).u16(40)
+            .u8(1).bytes(&key(6))
+            .u16(2_000)
+            .u16(300)
+            .u64(12)
+            .u8(1).u32(3_600).u32(7_200).u16(15_000)
+            .u8(9)
+            .u32(2_000).u32(300)
+            .u8(1).u8(1).bytes(&key(7)).u16(150)
+            .u16(2_500)
+            .u16(30).u16(1_000)
+            .u8(1).bytes(&key(8))
+            .u8(1)
+            .u8(1).u64(100_000_000).u32(50).u64(10_000_000).u64(500_000_000)
+            .u16(1_500)
+            .u8(1)
+            .u16(2_000).u16(7)
+            .u8(2).u64(12_000)
+            .u8(1)
+            .0;
+
+        let on_chain = assert_on_chain_layout::<cpmm::state::AmmsConfig>(&data, AmmsConfig::LEN);
+        let parsed = AmmsConfig::parse(&data).unwrap();
+        assert_eq!(parsed, AmmsConfig {
+            bump: 253,
+            id: 3,
+            fee_authority: key(4),
+            providers_fee_rate_basis_points: 25,
+            protocol_fee_rate_basis_points: 5,
+            incident_withdraw_limit_basis_points: 500,
+            keeper_bounty_basis_points: 100,
+            min_provide_lp_tokens: 1_000,
+            min_withdraw_lp_tokens: 2_000,
+            min_mint_decimals: 6,
+            max_mint_decimals: 9,
+            is_protocol_rebalance_enabled: true,
+            fee_bounds: Some(FeeBounds {
+                min_providers_fee_rate_basis_points: 10,
+                max_providers_fee_rate_basis_points: 50,
+                max_protocol_fee_rate_basis_points: 20,
+            }),
+            providers_fee_ramp: Some(FeeRamp {
+                start_slot: 7_000,
+                end_slot: 8_000,
+                start_fee_rate_basis_points: 40,
+            }),
+            lending_adapter: Some(key(6)),
+            max_lend_out_basis_points: 2_000,
+            lend_out_interest_rate_basis_points: 300,
+            pools_count: 12,
+            loyalty_boost: Some(LoyaltyBoost {
+                min_hold_seconds: 3_600,
+                full_boost_hold_seconds: 7_200,
+                max_multiplier_basis_points: 15_000,
+            }),
+            max_launch_price_orders: 9,
+            swap_constant_product_tolerance_ppb: 2_000,
+            adjust_liquidity_ratio_tolerance_ppb: 300,
+            fee_settlement: Some(FeeSettlement {
+                settlement_mint: Some(key(7)),
+                max_impact_basis_points: 150,
+            }),
+            max_creator_fee_share_basis_points: 2_500,
+            stale_pool_close_epochs: 30,
+            stale_pool_closer_share_basis_points: 1_000,
+            screening_program: Some(key(8)),
+            has_mint_equivalences: true,
+            pool_creation_pricing: Some(PoolCreationPricing {
+                base_price_in_lamports: 100_000_000,
+                pools_per_step: 50,
+                step_price_in_lamports: 10_000_000,
+                max_price_in_lamports: 500_000_000,
+            }),
+            protocol_fees_cap_basis_points: 1_500,
+            is_pool_creation_allow_listed: true,
+            lp_concentration_share_basis_points: 2_000,
+            lp_concentration_guard_epochs: 7,
+            swap_origin_policy: SwapOriginPolicy::CpiOnly,
+            swap_origin_policy_until_slot: 12_000,
+            layout_version: 1,
+        });
+        assert_eq!(on_chain.id, parsed.id);
+        assert_eq!(on_chain.bump(), parsed.bump);
+        assert_eq!(on_chain.fee_authority(), &on_chain_key(4));
+        assert_eq!(on_chain.providers_fee_rate_basis_points(), parsed.providers_fee_rate_basis_points);
+        assert_eq!(on_chain.protocol_fee_rate_basis_points(), parsed.protocol_fee_rate_basis_points);
+        assert_eq!(on_chain.incident_withdraw_limit_basis_points(), parsed.incident_withdraw_limit_basis_points);
+        assert_eq!(on_chain.keeper_bounty_basis_points(), parsed.keeper_bounty_basis_points);
+        assert_eq!(on_chain.min_provide_lp_tokens(), parsed.min_provide_lp_tokens);
+        assert_eq!(on_chain.min_withdraw_lp_tokens(), parsed.min_withdraw_lp_tokens);
+        assert_eq!(on_chain.min_mint_decimals(), parsed.min_mint_decimals);
+        assert_eq!(on_chain.max_mint_decimals(), parsed.max_mint_decimals);
+        assert_eq!(on_chain.is_protocol_rebalance_enabled(), parsed.is_protocol_rebalance_enabled);
+        let on_chain_fee_bounds = on_chain.fee_bounds().unwrap();
+        assert_eq!(on_chain_fee_bounds.min_providers_fee_rate_basis_points(), 10);
+        assert_eq!(on_chain_fee_bounds.max_providers_fee_rate_basis_points(), 50);
+        assert_eq!(on_chain_fee_bounds.max_protocol_fee_rate_basis_points(), 20);
+        let on_chain_providers_fee_ramp = on_chain.providers_fee_ramp().unwrap();
+        assert_eq!(on_chain_providers_fee_ramp.start_slot(), 7_000);
+        assert_eq!(on_chain_providers_fee_ramp.end_slot(), 8_000);
+        assert_eq!(on_chain_providers_fee_ramp.start_fee_rate_basis_points(), 40);
+        assert_eq!(on_chain.providers_fee_rate_basis_points_at(7_500), 33);
+        assert_eq!(on_chain.lending_adapter(), Some(&on_chain_key(6)));
+        assert_eq!(on_chain.max_lend_out_basis_points(), parsed.max_lend_out_basis_points);
+        assert_eq!(on_chain.lend_out_interest_rate_basis_points(), parsed.lend_out_interest_rate_basis_points);
+        assert_eq!(on_chain.pools_count(), parsed.pools_count);
+        let on_chain_loyalty_boost = on_chain.loyalty_boost().unwrap();
+        assert_eq!(on_chain_loyalty_boost.min_hold_seconds(), 3_600);
+        assert_eq!(on_chain_loyalty_boost.full_boost_hold_seconds(), 7_200);
+        assert_eq!(on_chain_loyalty_boost.max_multiplier_basis_points(), 15_000);
+        assert_eq!(on_chain.max_launch_price_orders(), parsed.max_launch_price_orders);
+        assert_eq!(on_chain.swap_constant_product_tolerance_ppb(), parsed.swap_constant_product_tolerance_ppb);
+        assert_eq!(on_chain.adjust_liquidity_ratio_tolerance_ppb(), parsed.adjust_liquidity_ratio_tolerance_ppb);
+        let on_chain_fee_settlement = on_chain.fee_settlement().unwrap();
+        assert_eq!(on_chain_fee_settlement.settlement_mint(), Some(&on_chain_key(7)));
+        assert_eq!(on_chain_fee_settlement.max_impact_basis_points(), 150);
+        assert_eq!(on_chain.max_creator_fee_share_basis_points(), parsed.max_creator_fee_share_basis_points);
+        assert_eq!(on_chain.stale_pool_close_epochs(), parsed.stale_pool_close_epochs);
+        assert_eq!(on_chain.stale_pool_closer_share_basis_points(), parsed.stale_pool_closer_share_basis_points);
+        assert_eq!(on_chain.screening_program(), Some(&on_chain_key(8)));
+        assert_eq!(on_chain.has_mint_equivalences(), parsed.has_mint_equivalences);
+        let on_chain_pool_creation_pricing = on_chain.pool_creation_pricing().unwrap();
+        assert_eq!(on_chain_pool_creation_pricing.base_price_in_lamports(), 100_000_000);
+        assert_eq!(on_chain_pool_creation_pricing.pools_per_step(), 50);
+        assert_eq!(on_chain_pool_creation_pricing.step_price_in_lamports(), 10_000_000);
+        assert_eq!(on_chain_pool_creation_pricing.max_price_in_lamports(), 500_000_000);
+        assert_eq!(on_chain.cp_amm_initialize_price_in_lamports(), 100_000_000);
+        assert_eq!(on_chain.protocol_fees_cap_basis_points(), parsed.protocol_fees_cap_basis_points);
+        assert_eq!(on_chain.is_pool_creation_allow_listed(), parsed.is_pool_creation_allow_listed);
+        assert_eq!(on_chain.lp_concentration_share_basis_points(), parsed.lp_concentration_share_basis_points);
+        assert_eq!(on_chain.lp_concentration_guard_epochs(), parsed.lp_concentration_guard_epochs);
+        assert_eq!(on_chain.swap_origin_policy_at(11_999), cpmm::state::SwapOriginPolicy::CpiOnly);
+        assert_eq!(on_chain.swap_origin_policy_until_slot(), parsed.swap_origin_policy_until_slot);
+        assert_eq!(on_chain.layout_version(), parsed.layout_version);
+        assert_eq!(parse_account(&data), Ok(ProgramAccount::AmmsConfig(parsed)));
+    }
+
+    /// Tests the `PoolCreationLimiter` layout against the on-chain struct.
+    #[test]
+    fn test_pool_creation_limiter_layout() {
+        assert_eq!(PoolCreationLimiter::DISCRIMINATOR, cpmm::state::PoolCreationLimiter::DISCRIMINATOR);
+        let data = Writer::default()
+            .bytes(&PoolCreationLimiter::DISCRIMINATOR)
+            .u8(252)
+            .bytes(&key(5))
+            .u64(777)
+            .u16(2)
+            .0;
+
+        let on_chain = assert_on_chain_layout::<cpmm::state::PoolCreationLimiter>(&data, PoolCreationLimiter::LEN);
+        let parsed = PoolCreationLimiter::parse(&data).unwrap();
+        assert_eq!(parsed, PoolCreationLimiter {
+            bump: 252,
+            creator: key(5),
+            window_start_slot: 777,
+            pools_created: 2,
+        });
+        assert_eq!(on_chain.bump(), parsed.bump);
+        assert_eq!(on_chain.creator(), &on_chain_key(5));
+        assert_eq!(on_chain.window_start_slot(), parsed.window_start_slot);
+        assert_eq!(on_chain.pools_created(), parsed.pools_created);
+        assert_eq!(parse_account(&data), Ok(ProgramAccount::PoolCreationLimiter(parsed)));
+    }
+
+    /// Tests the `PoolCreatorPermit` layout against the on-chain struct.
+    #[test]
+    fn test_pool_creator_permit_layout() {
+        assert_eq!(PoolCreatorPermit::DISCRIMINATOR, cpmm::state::PoolCreatorPermit::DISCRIMINATOR);
+        let data = Writer::default()
+            .bytes(&PoolCreatorPermit::DISCRIMINATOR)
+            .u8(250)
+            .bytes(&key(62))
+            .bytes(&key(63))
+            .0;
+
+        let on_chain = assert_on_chain_layout::<cpmm::state::PoolCreatorPermit>(&data, PoolCreatorPermit::LEN);
+        let parsed = PoolCreatorPermit::parse(&data).unwrap();
+        assert_eq!(parsed, PoolCreatorPermit {
+            bump: 250,
+            amms_config: key(62),
+            creator: key(63),
+        });
+        assert_eq!(on_chain.bump(), parsed.bump);
+        assert_eq!(on_chain.amms_config(), &on_chain_key(62));
+        assert_eq!(on_chain.creator(), &on_chain_key(63));
+        assert_eq!(parse_account(&data), Ok(ProgramAccount::PoolCreatorPermit(parsed)));
+    }
+
+    /// Tests the `PoolIndex` layout against the on-chain struct.
+    #[test]
+    fn test_pool_index_layout() {
+        assert_eq!(PoolIndex::DISCRIMINATOR, cpmm::state::PoolIndex::DISCRIMINATOR);
+        let mut writer = Writer::default()
+            .bytes(&PoolIndex::DISCRIMINATOR)
+            .u8(251)
+            .bytes(&key(7))
+            .u64(3)
+            .u16(2);
+        for i in 0..POOL_INDEX_CAPACITY as u8 {
+            writer = writer.bytes(&if i < 2 { key(40 + i) } else { [0; 32] });
+        }
+        let data = writer.0;
+
+        let on_chain = assert_on_chain_layout::<cpmm::state::PoolIndex>(&data, PoolIndex::LEN);
+        let parsed = PoolIndex::parse(&data).unwrap();
+        assert_eq!(parsed.bump, 251);
+        assert_eq!(parsed.amms_config, key(7));
+        assert_eq!(parsed.bucket, 3);
+        assert_eq!(parsed.pools(), &[key(40), key(41)]);
+        assert_eq!(on_chain.bump(), parsed.bump);
+        assert_eq!(on_chain.amms_config(), &on_chain_key(7));
+        assert_eq!(on_chain.bucket(), parsed.bucket);
+        assert_eq!(on_chain.pools(), &[on_chain_key(40), on_chain_key(41)]);
+        assert_eq!(parse_account(&data), Ok(ProgramAccount::PoolIndex(Box::new(parsed))));
+    }
+
+    /// Tests the `MintEquivalences` layout against the on-chain struct.
+    #[test]
+    fn test_mint_equivalences_layout() {
+        assert_eq!(MintEquivalences::DISCRIMINATOR, cpmm::state::MintEquivalences::DISCRIMINATOR);
+        let mut writer = Writer::default()
+            .bytes(&MintEquivalences::DISCRIMINATOR)
+            .u8(249)
+            .bytes(&key(7))
+            .u8(2);
+        for i in 0..MINT_EQUIVALENCES_CAPACITY as u8 {
+            writer = if i < 2 { writer.bytes(&key(50 + i)).u8(1) } else { writer.bytes(&[0; 32]).u8(0) };
+        }
+        let data = writer.0;
+
+        let on_chain = assert_on_chain_layout::<cpmm::state::MintEquivalences>(&data, MintEquivalences::LEN);
+        let parsed = MintEquivalences::parse(&data).unwrap();
+        assert_eq!(parsed.bump, 249);
+        assert_eq!(parsed.amms_config, key(7));
+        assert_eq!(parsed.mints(), &[
+            MintEquivalence { mint: key(50), group: 1 },
+            MintEquivalence { mint: key(51), group: 1 },
+        ]);
+        assert_eq!(on_chain.bump(), parsed.bump);
+        assert_eq!(on_chain.amms_config(), &on_chain_key(7));
+        assert_eq!(on_chain.group(&on_chain_key(51)), Some(1));
+        assert_eq!(on_chain.mints().len(), parsed.mints().len());
+        assert_eq!(parse_account(&data), Ok(ProgramAccount::MintEquivalences(Box::new(parsed))));
+    }
+
+    /// Tests the `TraderFeeTiers` layout against the on-chain struct.
+    #[test]
+    fn test_trader_fee_tiers_layout() {
+        assert_eq!(TraderFeeTiers::DISCRIMINATOR, cpmm::state::TraderFeeTiers::DISCRIMINATOR);
+        let mut writer = Writer::default()
+            .bytes(&TraderFeeTiers::DISCRIMINATOR)
+            .u8(248)
+            .bytes(&key(7))
+            .u8(2);
+        for i in 0..TRADER_FEE_TIERS_CAPACITY as u64 {
+            writer = if i < 2 { writer.u64(1_000_000 * (i + 1)).u16(500 * (i as u16 + 1)) } else { writer.u64(0).u16(0) };
+        }
+        let data = writer.0;
+
+        let on_chain = assert_on_chain_layout::<cpmm::state::TraderFeeTiers>(&data, TraderFeeTiers::LEN);
+        let parsed = TraderFeeTiers::parse(&data).unwrap();
+        assert_eq!(parsed.bump, 248);
+        assert_eq!(parsed.amms_config, key(7));
+        assert_eq!(parsed.tiers(), &[
+            TraderFeeTier { min_volume_usd: 1_000_000, discount_basis_points: 500 },
+            TraderFeeTier { min_volume_usd: 2_000_000, discount_basis_points: 1000 },
+        ]);
+        assert_eq!(on_chain.bump(), parsed.bump);
+        assert_eq!(on_chain.amms_config(), &on_chain_key(7));
+        assert_eq!(on_chain.tier(2_500_000).map(|(index, tier)| (index, tier.discount_basis_points)), Some((1, 1000)));
+        assert_eq!(on_chain.tiers().len(), parsed.tiers().len());
+        assert_eq!(parse_account(&data), Ok(ProgramAccount::TraderFeeTiers(parsed)));
+    }
+
+    /// Tests the `TraderVolume` layout against the on-chain struct.
+    #[test]
+    fn test_trader_volume_layout() {
+        assert_eq!(TraderVolume::DISCRIMINATOR, cpmm::state::TraderVolume::DISCRIMINATOR);
+        let data = Writer::default()
+            .bytes(&TraderVolume::DISCRIMINATOR)
+            .u8(247)
+            .bytes(&key(7))
+            .bytes(&key(35))
+            .u64(12_000_000)
+            .bytes(&1_700_000_000i64.to_le_bytes())
+            .0;
+
+        let on_chain = assert_on_chain_layout::<cpmm::state::TraderVolume>(&data, TraderVolume::LEN);
+        let parsed = TraderVolume::parse(&data).unwrap();
+        assert_eq!(parsed, TraderVolume {
+            bump: 247,
+            amms_config: key(7),
+            trader: key(35),
+            volume_usd: 12_000_000,
+            last_update_timestamp: 1_700_000_000,
+        });
+        assert_eq!(on_chain.bump(), parsed.bump);
+        assert_eq!(on_chain.amms_config(), &on_chain_key(7));
+        assert_eq!(on_chain.trader(), &on_chain_key(35));
+        assert_eq!(on_chain.volume_usd(), parsed.volume_usd);
+        assert_eq!(on_chain.last_update_timestamp(), parsed.last_update_timestamp);
+        assert_eq!(parse_account(&data), Ok(ProgramAccount::TraderVolume(parsed)));
+    }
+
+    /// Tests the `RouterRegistry` layout against the on-chain struct.
+    #[test]
+    fn test_router_registry_layout() {
+        assert_eq!(RouterRegistry::DISCRIMINATOR, cpmm::state::RouterRegistry::DISCRIMINATOR);
+        let mut writer = Writer::default()
+            .bytes(&RouterRegistry::DISCRIMINATOR)
+            .u8(246)
+            .u8(2);
+        for i in 0..ROUTER_REGISTRY_CAPACITY as u8 {
+            writer = writer.bytes(&if i < 2 { key(60 + i) } else { [0; 32] });
+        }
+        let data = writer.0;
+
+        let on_chain = assert_on_chain_layout::<cpmm::state::RouterRegistry>(&data, RouterRegistry::LEN);
+        let parsed = RouterRegistry::parse(&data).unwrap();
+        assert_eq!(parsed.bump, 246);
+        assert_eq!(parsed.routers(), &[key(60), key(61)]);
+        assert_eq!(on_chain.bump(), parsed.bump);
+        assert_eq!(on_chain.routers(), &[on_chain_key(60), on_chain_key(61)]);
+        assert!(on_chain.is_approved(&on_chain_key(61)));
+        assert_eq!(parse_account(&data), Ok(ProgramAccount::RouterRegistry(Box::new(parsed))));
+    }
+
+    /// Tests the `CpAmm` layout against the on-chain struct.
+    #[test]
+    fn test_cp_amm_layout() {
+        assert_eq!(CpAmm::DISCRIMINATOR, cpmm::state::cp_amm::CpAmm::DISCRIMINATOR);
+        let open_hours = [0x5Au8; 21];
+        let mut display_data = [0u8; 160];
+        display_data[..12].copy_from_slice(&[6, 0, 8, 0, 0, 32, 0, 0, 0, 0, 0, 0]);
+        let data = Writer::default()
+            .bytes(&CpAmm::DISCRIMINATOR)
+            .u8(2)
+            .u8(2)
+            .u8(255)
+            .u8(254)
+            .u8(253)
+            .u8(252)
+            .u64(1_000)
+            .u128(1u128 << 127).u64(3_000)
+            .u128(1u128 << 126).u64(2)
+            .u64(6_000)
+            .u64(1_500)
+            .u64(3_000)
+            .u64(11)
+            .u64(12)
+            .bytes(&key(10))
+            .bytes(&key(11))
+            .bytes(&key(12))
+            .bytes(&key(13))
+            .bytes(&key(14))
+            .bytes(&key(15))
+            .bytes(&key(16))
+            .bytes(&key(17))
+            .u8(1)
+            .u64(100).u64(200).u64(10)
+            .u8(1).bytes(&key(18))
+            .u128(21).u128(22).u128(23).u128(24).u128(25).u128(26).u128(27)
+            .u8(1).bytes(&open_hours)
+            .u8(1)
+            .u64(400)
+            .u64(100)
+            .u8(1).bytes(&key(19))
+            .u8(1).u16(5)
+            .u8(1).u8(0).u8(1)
+            .u16(2_500)
+            .u64(30)
+            .u64(15)
+            .u64(512)
+            .u8(1)
+            .u8(9).u8(0)
+            .u64(4_096)
+            .u8(0)
+            .u8(1).u16(20)
+            .u64(8_192)
+            .bytes(&display_data)
+            .u16(3_000)
+            .u8(1)
+            .u64(250_000_000).u64(8_000)
+            .0;
+
+        let on_chain = assert_on_chain_layout::<cpmm::state::cp_amm::CpAmm>(&data, CpAmm::LEN);
+        let parsed = CpAmm::parse(&data).unwrap();
+        assert_eq!(parsed, CpAmm {
+            layout_version: 2,
+            status: PoolStatus::Launched,
+            bump: 255,
+            base_vault_bump: 254,
+            quote_vault_bump: 253,
+            locked_lp_vault_bump: 252,
+            initial_locked_liquidity: 1_000,
+            constant_product_sqrt: Q64_128 { fractional_bits: 1u128 << 127, integer_bits: 3_000 },
+            base_quote_ratio_sqrt: Q64_128 { fractional_bits: 1u128 << 126, integer_bits: 2 },
+            base_liquidity: 6_000,
+            quote_liquidity: 1_500,
+            lp_tokens_supply: 3_000,
+            protocol_base_fees_to_redeem: 11,
+            protocol_quote_fees_to_redeem: 12,
+            base_mint: key(10),
+            quote_mint: key(11),
+            lp_mint: key(12),
+            base_vault: key(13),
+            quote_vault: key(14),
+            locked_lp_vault: key(15),
+            amms_config: key(16),
+            creator: key(17),
+            is_incident_limited: true,
+            withdraw_rate_limiter: WithdrawRateLimiter { slot: 100, slot_lp_tokens_supply: 200, withdrawn_lp_tokens: 10 },
+            usd_oracle: Some(key(18)),
+            stats: PoolStats {
+                cumulative_base_volume: 21,
+                cumulative_quote_volume: 22,
+                cumulative_usd_volume: 23,
+                cumulative_base_providers_fees: 24,
+                cumulative_quote_providers_fees: 25,
+                cumulative_base_protocol_fees: 26,
+                cumulative_quote_protocol_fees: 27,
+            },
+            trading_schedule: Some(TradingSchedule { open_hours }),
+            is_lend_out_enabled: true,
+            base_lent_out: 400,
+            quote_lent_out: 100,
+            liquidity_subscriber: Some(key(19)),
+            protocol_fee_override_basis_points: Some(5),
+            swaps_paused: true,
+            provides_paused: false,
+            withdraws_paused: true,
+            creator_fee_share_basis_points: 2_500,
+            creator_base_fees_to_redeem: 30,
+            creator_quote_fees_to_redeem: 15,
+            initialized_epoch: 512,
+            is_fee_parameters_locked: true,
+            base_normalization_exponent: 9,
+            quote_normalization_exponent: 0,
+            trading_open_slot: 4_096,
+            curve_kind: CurveKind::ConstantProduct,
+            max_protocol_fee_basis_points_at_creation: Some(20),
+            protected_until_slot: 8_192,
+            display_data: DisplayData { data: display_data },
+            largest_provide_share_basis_points: 3_000,
+            concentration_flag: true,
+            slot_start_price: 250_000_000,
+            slot_start_price_slot: 8_000,
+        });
+        assert!(on_chain.is_launched());
+        assert_eq!(on_chain.layout_version(), parsed.layout_version);
+        assert_eq!(on_chain.bump(), parsed.bump);
+        assert_eq!(on_chain.base_vault_bump(), parsed.base_vault_bump);
+        assert_eq!(on_chain.quote_vault_bump(), parsed.quote_vault_bump);
+        assert_eq!(on_chain.locked_lp_vault_bump(), parsed.locked_lp_vault_bump);
+        assert_eq!(on_chain.base_mint(), &on_chain_key(10));
+        assert_eq!(on_chain.quote_mint(), &on_chain_key(11));
+        assert_eq!(on_chain.lp_mint, on_chain_key(12));
+        assert_eq!(on_chain.base_vault(), &on_chain_key(13));
+        assert_eq!(on_chain.quote_vault(), &on_chain_key(14));
+        assert_eq!(on_chain.locked_lp_vault(), &on_chain_key(15));
+        assert_eq!(on_chain.amms_config(), &on_chain_key(16));
+        assert_eq!(on_chain.creator(), &on_chain_key(17));
+        assert_eq!(data[CpAmm::LAYOUT_VERSION_OFFSET], parsed.layout_version);
+        assert_eq!(data[CpAmm::BASE_MINT_OFFSET..CpAmm::BASE_MINT_OFFSET + 32], key(10));
+        assert_eq!(data[CpAmm::QUOTE_MINT_OFFSET..CpAmm::QUOTE_MINT_OFFSET + 32], key(11));
+        assert_eq!(data[CpAmm::LP_MINT_OFFSET..CpAmm::LP_MINT_OFFSET + 32], key(12));
+        assert_eq!(data[CpAmm::AMMS_CONFIG_OFFSET..CpAmm::AMMS_CONFIG_OFFSET + 32], key(16));
+        assert_eq!(data[CpAmm::CREATOR_OFFSET..CpAmm::CREATOR_OFFSET + 32], key(17));
+        assert_eq!(on_chain.is_incident_limited(), parsed.is_incident_limited);
+        assert_eq!(on_chain.withdraw_rate_limiter().slot(), parsed.withdraw_rate_limiter.slot);
+        assert_eq!(on_chain.withdraw_rate_limiter().slot_lp_tokens_supply(), parsed.withdraw_rate_limiter.slot_lp_tokens_supply);
+        assert_eq!(on_chain.withdraw_rate_limiter().withdrawn_lp_tokens(), parsed.withdraw_rate_limiter.withdrawn_lp_tokens);
+        assert_eq!(on_chain.usd_oracle(), Some(&on_chain_key(18)));
+        assert_eq!(on_chain.stats().cumulative_base_volume(), parsed.stats.cumulative_base_volume);
+        assert_eq!(on_chain.stats().cumulative_quote_volume(), parsed.stats.cumulative_quote_volume);
+        assert_eq!(on_chain.stats().cumulative_usd_volume(), parsed.stats.cumulative_usd_volume);
+        assert_eq!(on_chain.stats().cumulative_base_providers_fees(), parsed.stats.cumulative_base_providers_fees);
+        assert_eq!(on_chain.stats().cumulative_quote_providers_fees(), parsed.stats.cumulative_quote_providers_fees);
+        assert_eq!(on_chain.stats().cumulative_base_protocol_fees(), parsed.stats.cumulative_base_protocol_fees);
+        assert_eq!(on_chain.stats().cumulative_quote_protocol_fees(), parsed.stats.cumulative_quote_protocol_fees);
+        assert_eq!(on_chain.trading_schedule().map(|schedule| *schedule.open_hours()), Some(open_hours));
+        assert_eq!(on_chain.is_lend_out_enabled(), parsed.is_lend_out_enabled);
+        assert_eq!(on_chain.base_lent_out(), parsed.base_lent_out);
+        assert_eq!(on_chain.quote_lent_out(), parsed.quote_lent_out);
+        assert_eq!(on_chain.liquidity_subscriber(), Some(&on_chain_key(19)));
+        assert_eq!(on_chain.protocol_fee_override_basis_points(), parsed.protocol_fee_override_basis_points);
+        assert_eq!(on_chain.swaps_paused(), parsed.swaps_paused);
+        assert_eq!(on_chain.provides_paused(), parsed.provides_paused);
+        assert_eq!(on_chain.withdraws_paused(), parsed.withdraws_paused);
+        assert_eq!(on_chain.creator_fee_share_basis_points(), parsed.creator_fee_share_basis_points);
+        assert_eq!(on_chain.creator_base_fees_to_redeem(), parsed.creator_base_fees_to_redeem);
+        assert_eq!(on_chain.creator_quote_fees_to_redeem(), parsed.creator_quote_fees_to_redeem);
+        assert_eq!(on_chain.initialized_epoch(), parsed.initialized_epoch);
+        assert_eq!(on_chain.is_fee_parameters_locked(), parsed.is_fee_parameters_locked);
+        assert_eq!(on_chain.base_normalization_exponent(), parsed.base_normalization_exponent);
+        assert_eq!(on_chain.quote_normalization_exponent(), parsed.quote_normalization_exponent);
+        assert_eq!(on_chain.trading_open_slot(), parsed.trading_open_slot);
+        assert_eq!(on_chain.curve_kind(), cpmm::state::cp_amm::CurveKind::ConstantProduct);
+        assert_eq!(on_chain.max_protocol_fee_basis_points_at_creation(), parsed.max_protocol_fee_basis_points_at_creation);
+        assert_eq!(on_chain.protected_until_slot(), parsed.protected_until_slot);
+        assert!(on_chain.is_protected(8_191));
+        assert_eq!(on_chain.display_data().data(), &parsed.display_data.data);
+        assert_eq!(on_chain.largest_provide_share_basis_points(), parsed.largest_provide_share_basis_points);
+        assert!(on_chain.concentration_flag());
+        assert_eq!(on_chain.slot_start_price(), parsed.slot_start_price);
+        assert_eq!(on_chain.slot_start_price_slot(), parsed.slot_start_price_slot);
+        assert_eq!(parsed.display_data.entry(DisplayData::LAST_UPDATE_SLOT), Some(8_192u64.to_le_bytes().as_ref()));
+        assert_eq!(parse_account(&data), Ok(ProgramAccount::CpAmm(Box::new(parsed))));
+    }
+
+    /// Tests the `CpAmm` layout with empty optional fields.
+    #[test]
+    fn test_cp_amm_layout_without_options() {
+        let mut data = Writer::default()
+            .bytes(&CpAmm::DISCRIMINATOR)
+            .bytes(&[0; 6 + 8 + 24 + 24 + 5 * 8 + 8 * 32 + 1 + 24])
+            .u8(0)
+            .bytes(&[0; 7 * 16])
+            .u8(0)
+            .0;
+        // Accounts are allocated for the maximal size, so empty options leave zeroed tail bytes
+        data.resize(CpAmm::LEN, 0);
+
+        let parsed = CpAmm::parse(&data).unwrap();
+        assert_eq!(parsed.usd_oracle, None);
+        assert_eq!(parsed.trading_schedule, None);
+        assert_eq!(parsed.stats, PoolStats::default());
+        assert!(!parsed.is_lend_out_enabled);
+        assert_eq!(parsed.liquidity_subscriber, None);
+        assert_eq!(parsed.protocol_fee_override_basis_points, None);
+        assert_eq!(parsed.max_protocol_fee_basis_points_at_creation, None);
+    }
+
+    /// Tests the `LendOutLoan` layout against the on-chain struct.
+    #[test]
+    fn test_lend_out_loan_layout() {
+        assert_eq!(LendOutLoan::DISCRIMINATOR, cpmm::state::cp_amm::LendOutLoan::DISCRIMINATOR);
+        let data = Writer::default()
+            .bytes(&LendOutLoan::DISCRIMINATOR)
+            .u8(251)
+            .bytes(&key(30))
+            .bytes(&key(31))
+            .u64(5_000)
+            .u64(25)
+            .u64(900)
+            .u8(1)
+            .0;
+
+        let on_chain = assert_on_chain_layout::<cpmm::state::cp_amm::LendOutLoan>(&data, LendOutLoan::LEN);
+        let parsed = LendOutLoan::parse(&data).unwrap();
+        assert_eq!(parsed, LendOutLoan {
+            bump: 251,
+            cp_amm: key(30),
+            mint: key(31),
+            principal: 5_000,
+            accrued_interest: 25,
+            last_accrual_slot: 900,
+            is_recalled: true,
+        });
+        assert_eq!(on_chain.bump(), parsed.bump);
+        assert_eq!(on_chain.cp_amm(), &on_chain_key(30));
+        assert_eq!(on_chain.mint(), &on_chain_key(31));
+        assert_eq!(on_chain.principal(), parsed.principal);
+        assert_eq!(on_chain.accrued_interest(), parsed.accrued_interest);
+        assert_eq!(on_chain.last_accrual_slot(), parsed.last_accrual_slot);
+        assert_eq!(on_chain.is_recalled(), parsed.is_recalled);
+        assert_eq!(parse_account(&data), Ok(ProgramAccount::LendOutLoan(parsed)));
+    }
+
+    /// Tests the `LiquidityPosition` layout against the on-chain struct.
+    #[test]
+    fn test_liquidity_position_layout() {
+        assert_eq!(LiquidityPosition::DISCRIMINATOR, cpmm::state::cp_amm::LiquidityPosition::DISCRIMINATOR);
+        let data = Writer::default()
+            .bytes(&LiquidityPosition::DISCRIMINATOR)
+            .u8(249)
+            .bytes(&key(33))
+            .bytes(&key(34))
+            .u64(5_000)
+            .bytes(&1_700_000_000i64.to_le_bytes())
+            .0;
+
+        let on_chain = assert_on_chain_layout::<cpmm::state::cp_amm::LiquidityPosition>(&data, LiquidityPosition::LEN);
+        let parsed = LiquidityPosition::parse(&data).unwrap();
+        assert_eq!(parsed, LiquidityPosition {
+            bump: 249,
+            cp_amm: key(33),
+            owner: key(34),
+            lp_tokens: 5_000,
+            hold_start_timestamp: 1_700_000_000,
+        });
+        assert_eq!(on_chain.bump(), parsed.bump);
+        assert_eq!(on_chain.cp_amm(), &on_chain_key(33));
+        assert_eq!(on_chain.owner(), &on_chain_key(34));
+        assert_eq!(on_chain.lp_tokens(), parsed.lp_tokens);
+        assert_eq!(on_chain.hold_start_timestamp(), parsed.hold_start_timestamp);
+        assert_eq!(parse_account(&data), Ok(ProgramAccount::LiquidityPosition(parsed)));
+    }
+
+    /// Tests the `TradingSession` layout against the on-chain struct.
+    #[test]
+    fn test_trading_session_layout() {
+        assert_eq!(TradingSession::DISCRIMINATOR, cpmm::state::cp_amm::TradingSession::DISCRIMINATOR);
+        let data = Writer::default()
+            .bytes(&TradingSession::DISCRIMINATOR)
+            .u8(249)
+            .bytes(&key(33))
+            .bytes(&key(34))
+            .bytes(&key(35))
+            .u64(1_000)
+            .u64(10_000)
+            .u64(2_500)
+            .u64(123_456)
+            .0;
+
+        let on_chain = assert_on_chain_layout::<cpmm::state::cp_amm::TradingSession>(&data, TradingSession::LEN);
+        let parsed = TradingSession::parse(&data).unwrap();
+        assert_eq!(parsed, TradingSession {
+            bump: 249,
+            cp_amm: key(33),
+            owner: key(34),
+            session_key: key(35),
+            max_swap_quote_amount: 1_000,
+            max_total_quote_volume: 10_000,
+            total_quote_volume: 2_500,
+            expires_at_slot: 123_456,
+        });
+        assert_eq!(on_chain.bump(), parsed.bump);
+        assert_eq!(on_chain.cp_amm(), &on_chain_key(33));
+        assert_eq!(on_chain.owner(), &on_chain_key(34));
+        assert_eq!(on_chain.session_key(), &on_chain_key(35));
+        assert_eq!(on_chain.max_swap_quote_amount(), parsed.max_swap_quote_amount);
+        assert_eq!(on_chain.max_total_quote_volume(), parsed.max_total_quote_volume);
+        assert_eq!(on_chain.total_quote_volume(), parsed.total_quote_volume);
+        assert_eq!(on_chain.expires_at_slot(), parsed.expires_at_slot);
+        assert_eq!(parse_account(&data), Ok(ProgramAccount::TradingSession(parsed)));
+    }
+
+    /// Tests the `PoolStatsShard` layout against the on-chain struct.
+    #[test]
+    fn test_pool_stats_shard_layout() {
+        assert_eq!(PoolStatsShard::DISCRIMINATOR, cpmm::state::cp_amm::PoolStatsShard::DISCRIMINATOR);
+        let data = Writer::default()
+            .bytes(&PoolStatsShard::DISCRIMINATOR)
+            .u8(248)
+            .bytes(&key(35))
+            .u8(1)
+            .u128(31).u128(32).u128(33).u128(34).u128(35).u128(36).u128(37)
+            .0;
+
+        let on_chain = assert_on_chain_layout::<cpmm::state::cp_amm::PoolStatsShard>(&data, PoolStatsShard::LEN);
+        let parsed = PoolStatsShard::parse(&data).unwrap();
+        assert_eq!(parsed, PoolStatsShard {
+            bump: 248,
+            cp_amm: key(35),
+            shard_index: 1,
+            stats: PoolStats {
+                cumulative_base_volume: 31,
+                cumulative_quote_volume: 32,
+                cumulative_usd_volume: 33,
+                cumulative_base_providers_fees: 34,
+                cumulative_quote_providers_fees: 35,
+                cumulative_base_protocol_fees: 36,
+                cumulative_quote_protocol_fees: 37,
+            },
+        });
+        assert_eq!(on_chain.bump(), parsed.bump);
+        assert_eq!(on_chain.cp_amm(), &on_chain_key(35));
+        assert_eq!(on_chain.shard_index(), parsed.shard_index);
+        assert_eq!(on_chain.stats().cumulative_base_volume(), parsed.stats.cumulative_base_volume);
+        assert_eq!(on_chain.stats().cumulative_quote_protocol_fees(), parsed.stats.cumulative_quote_protocol_fees);
+        assert_eq!(parse_account(&data), Ok(ProgramAccount::PoolStatsShard(parsed)));
+    }
+
+    /// Tests the `PoolLookupTable` layout against the on-chain struct.
+    #[test]
+    fn test_pool_lookup_table_layout() {
+        assert_eq!(PoolLookupTable::DISCRIMINATOR, cpmm::state::cp_amm::PoolLookupTable::DISCRIMINATOR);
+        let data = Writer::default()
+            .bytes(&PoolLookupTable::DISCRIMINATOR)
+            .u8(246)
+            .bytes(&key(42))
+            .bytes(&key(43))
+            .0;
+
+        let on_chain = assert_on_chain_layout::<cpmm::state::cp_amm::PoolLookupTable>(&data, PoolLookupTable::LEN);
+        let parsed = PoolLookupTable::parse(&data).unwrap();
+        assert_eq!(parsed, PoolLookupTable {
+            bump: 246,
+            cp_amm: key(42),
+            lookup_table: key(43),
+        });
+        assert_eq!(on_chain.bump(), parsed.bump);
+        assert_eq!(on_chain.cp_amm(), &on_chain_key(42));
+        assert_eq!(on_chain.lookup_table(), &on_chain_key(43));
+        assert_eq!(parse_account(&data), Ok(ProgramAccount::PoolLookupTable(parsed)));
+    }
+
+    /// Tests the `Snapshot` layout against the on-chain struct.
+    #[test]
+    fn test_snapshot_layout() {
+        assert_eq!(Snapshot::DISCRIMINATOR, cpmm::state::cp_amm::Snapshot::DISCRIMINATOR);
+        let data = Writer::default()
+            .bytes(&Snapshot::DISCRIMINATOR)
+            .u8(247)
+            .bytes(&key(36))
+            .u64(1_234)
+            .bytes(&key(37))
+            .u32(56)
+            .u64(7_890)
+            .0;
+
+        let on_chain = assert_on_chain_layout::<cpmm::state::cp_amm::Snapshot>(&data, Snapshot::LEN);
+        let parsed = Snapshot::parse(&data).unwrap();
+        assert_eq!(parsed, Snapshot {
+            bump: 247,
+            cp_amm: key(36),
+            slot: 1_234,
+            merkle_root: key(37),
+            holders_count: 56,
+            total_lp_tokens: 7_890,
+        });
+        assert_eq!(on_chain.bump(), parsed.bump);
+        assert_eq!(on_chain.cp_amm(), &on_chain_key(36));
+        assert_eq!(on_chain.slot(), parsed.slot);
+        assert_eq!(on_chain.merkle_root(), &parsed.merkle_root);
+        assert_eq!(on_chain.holders_count(), parsed.holders_count);
+        assert_eq!(on_chain.total_lp_tokens(), parsed.total_lp_tokens);
+        assert_eq!(parse_account(&data), Ok(ProgramAccount::Snapshot(parsed)));
+    }
+
+    /// Tests the `FeeCheckpoints` layout against the on-chain struct.
+    #[test]
+    fn test_fee_checkpoints_layout() {
+        assert_eq!(FeeCheckpoints::DISCRIMINATOR, cpmm::state::cp_amm::FeeCheckpoints::DISCRIMINATOR);
+        let mut writer = Writer::default()
+            .bytes(&FeeCheckpoints::DISCRIMINATOR)
+            .u8(250)
+            .bytes(&key(32))
+            .u16(2)
+            .u16(2);
+        for i in 0..FEE_CHECKPOINTS_CAPACITY as u64 {
+            let timestamp = if i < 2 { 1_000 + i * 10_800 } else { 0 };
+            writer = writer.u64(timestamp).u128(i as u128 * 10).u128(i as u128 * 20);
+        }
+        let data = writer.0;
+
+        let on_chain = assert_on_chain_layout::<cpmm::state::cp_amm::FeeCheckpoints>(&data, FeeCheckpoints::LEN);
+        let parsed = FeeCheckpoints::parse(&data).unwrap();
+        assert_eq!(parsed.bump, 250);
+        assert_eq!(parsed.cp_amm, key(32));
+        assert_eq!(parsed.head, 2);
+        assert_eq!(parsed.count, 2);
+        assert_eq!(parsed.checkpoints[1], FeeCheckpoint {
+            timestamp: 11_800,
+            cumulative_base_providers_fees: 10,
+            cumulative_quote_providers_fees: 20,
+        });
+        assert_eq!(parsed.checkpoints[63].cumulative_quote_providers_fees, 1_260);
+        assert_eq!(on_chain.bump(), parsed.bump);
+        assert_eq!(on_chain.cp_amm(), &on_chain_key(32));
+        assert_eq!(on_chain.count(), parsed.count);
+        let on_chain_latest = on_chain.latest().unwrap();
+        assert_eq!(on_chain_latest.timestamp(), parsed.checkpoints[1].timestamp);
+        assert_eq!(on_chain_latest.cumulative_base_providers_fees(), parsed.checkpoints[1].cumulative_base_providers_fees);
+        assert_eq!(on_chain_latest.cumulative_quote_providers_fees(), parsed.checkpoints[1].cumulative_quote_providers_fees);
+        assert_eq!(parse_account(&data), Ok(ProgramAccount::FeeCheckpoints(Box::new(parsed))));
+    }
+
+    /// Tests the `VirtualPriceCheckpoints` layout against the on-chain struct.
+    #[test]
+    fn test_virtual_price_checkpoints_layout() {
+        assert_eq!(VirtualPriceCheckpoints::DISCRIMINATOR, cpmm::state::cp_amm::VirtualPriceCheckpoints::DISCRIMINATOR);
+        let mut writer = Writer::default()
+            .bytes(&VirtualPriceCheckpoints::DISCRIMINATOR)
+            .u8(250)
+            .bytes(&key(33))
+            .u16(2)
+            .u16(2);
+        for i in 0..VIRTUAL_PRICE_CHECKPOINTS_CAPACITY as u64 {
+            let timestamp = if i < 2 { 1_000 + i * 86_400 } else { 0 };
+            writer = writer.u64(timestamp).u64(1_000_000_000 + i);
+        }
+        let data = writer.0;
+
+        let on_chain = assert_on_chain_layout::<cpmm::state::cp_amm::VirtualPriceCheckpoints>(&data, VirtualPriceCheckpoints::LEN);
+        let parsed = VirtualPriceCheckpoints::parse(&data).unwrap();
+        assert_eq!(parsed.bump, 250);
+        assert_eq!(parsed.cp_amm, key(33));
+        assert_eq!(parsed.head, 2);
+        assert_eq!(parsed.count, 2);
+        assert_eq!(parsed.checkpoints[1], VirtualPriceCheckpoint {
+            timestamp: 87_400,
+            virtual_price: 1_000_000_001,
+        });
+        assert_eq!(parsed.checkpoints[29].virtual_price, 1_000_000_029);
+        assert_eq!(on_chain.bump(), parsed.bump);
+        assert_eq!(on_chain.cp_amm(), &on_chain_key(33));
+        assert_eq!(on_chain.count(), parsed.count);
+        let on_chain_latest = on_chain.latest().unwrap();
+        assert_eq!(on_chain_latest.timestamp(), parsed.checkpoints[1].timestamp);
+        assert_eq!(on_chain_latest.virtual_price(), parsed.checkpoints[1].virtual_price);
+        assert_eq!(parse_account(&data), Ok(ProgramAccount::VirtualPriceCheckpoints(Box::new(parsed))));
+    }
+
+    /// Tests the zero-copy `EventRing` layout against the on-chain struct.
+    #[test]
+    fn test_event_ring_layout() {
+        assert_eq!(EventRing::DISCRIMINATOR, cpmm::state::cp_amm::EventRing::DISCRIMINATOR);
+        let mut writer = Writer::default()
+            .bytes(&EventRing::DISCRIMINATOR)
+            .bytes(&key(32))
+            .u64(66)
+            .u8(251)
+            .bytes(&[0; 7]);
+        for i in 0..EVENT_RING_CAPACITY as u64 {
+            let sequence = if i < 2 { 64 + i } else { i };
+            writer = writer
+                .u64(sequence)
+                .u64(1_000 + sequence)
+                .bytes(&key(40))
+                .u64(sequence * 10)
+                .u64(sequence * 20)
+                .u64(0)
+                .u8(1)
+                .bytes(&[0; 7]);
+        }
+        let data = writer.0;
+
+        assert_eq!(data.len(), EventRing::LEN);
+        assert_eq!(EventRing::LEN, 8 + cpmm::state::cp_amm::EventRing::INIT_SPACE);
+        let on_chain = bytemuck::pod_read_unaligned::<cpmm::state::cp_amm::EventRing>(&data[8..]);
+        let parsed = EventRing::parse(&data).unwrap();
+        assert_eq!(parsed.cp_amm, key(32));
+        assert_eq!(parsed.sequence, 66);
+        assert_eq!(parsed.bump, 251);
+        assert_eq!(parsed.record(65), Some(&EventRecord {
+            sequence: 65,
+            slot: 1_065,
+            signer: key(40),
+            base_amount: 650,
+            quote_amount: 1_300,
+            lp_tokens: 0,
+            kind: 1,
+        }));
+        assert_eq!(parsed.record(1), None);
+        assert_eq!(on_chain.cp_amm(), &on_chain_key(32));
+        assert_eq!(on_chain.sequence(), parsed.sequence);
+        assert_eq!(on_chain.bump(), parsed.bump);
+        for sequence in 2..66 {
+            let (on_chain_record, parsed_record) = (on_chain.record(sequence).unwrap(), parsed.record(sequence).unwrap());
+            assert_eq!(on_chain_record.sequence(), parsed_record.sequence);
+            assert_eq!(on_chain_record.slot(), parsed_record.slot);
+            assert_eq!(on_chain_record.signer(), &on_chain_key(40));
+            assert_eq!(on_chain_record.base_amount(), parsed_record.base_amount);
+            assert_eq!(on_chain_record.quote_amount(), parsed_record.quote_amount);
+            assert_eq!(on_chain_record.lp_tokens(), parsed_record.lp_tokens);
+            assert_eq!(on_chain_record.kind(), Some(cpmm::state::cp_amm::EventRecordKind::SwapBaseToQuote));
+        }
+        assert_eq!(parse_account(&data), Ok(ProgramAccount::EventRing(Box::new(parsed))));
+    }
+
+    /// Tests the `WpAmm` layout against the on-chain struct.
+    #[test]
+    fn test_wp_amm_layout() {
+        assert_eq!(WpAmm::DISCRIMINATOR, cpmm::state::wp_amm::WpAmm::DISCRIMINATOR);
+        let data = Writer::default()
+            .bytes(&WpAmm::DISCRIMINATOR)
+            .u8(1)
+            .u8(0)
+            .u8(255)
+            .bytes(&[254, 253, 252])
+            .u8(251)
+            .u16(5_000).u16(3_000).u16(2_000)
+            .u64(1_000)
+            .u64(10).u64(20).u64(30)
+            .u64(5_000)
+            .bytes(&key(20)).bytes(&key(21)).bytes(&key(22))
+            .bytes(&key(23))
+            .bytes(&key(24)).bytes(&key(25)).bytes(&key(26))
+            .bytes(&key(27))
+            .bytes(&key(28))
+            .bytes(&key(29))
+            .u64(40).u64(50).u64(60)
+            .0;
+
+        let on_chain = assert_on_chain_layout::<cpmm::state::wp_amm::WpAmm>(&data, WpAmm::LEN);
+        let parsed = WpAmm::parse(&data).unwrap();
+        assert_eq!(parsed, WpAmm {
+            is_initialized: true,
+            is_launched: false,
+            bump: 255,
+            vaults_bumps: [254, 253, 252],
+            locked_lp_vault_bump: 251,
+            weights_basis_points: [5_000, 3_000, 2_000],
+            initial_locked_liquidity: 1_000,
+            liquidity: [10, 20, 30],
+            lp_tokens_supply: 5_000,
+            mints: [key(20), key(21), key(22)],
+            lp_mint: key(23),
+            vaults: [key(24), key(25), key(26)],
+            locked_lp_vault: key(27),
+            amms_config: key(28),
+            creator: key(29),
+            protocol_fees_to_redeem: [40, 50, 60],
+        });
+        assert_eq!(on_chain.is_initialized(), parsed.is_initialized);
+        assert_eq!(on_chain.is_launched(), parsed.is_launched);
+        assert_eq!(on_chain.bump(), parsed.bump);
+        assert_eq!(on_chain.vault_bump(2), parsed.vaults_bumps[2]);
+        assert_eq!(on_chain.locked_lp_vault_bump(), parsed.locked_lp_vault_bump);
+        assert_eq!(on_chain.mints(), &[on_chain_key(20), on_chain_key(21), on_chain_key(22)]);
+        assert_eq!(on_chain.lp_mint, on_chain_key(23));
+        assert_eq!(on_chain.vaults(), &[on_chain_key(24), on_chain_key(25), on_chain_key(26)]);
+        assert_eq!(on_chain.locked_lp_vault(), &on_chain_key(27));
+        assert_eq!(on_chain.amms_config(), &on_chain_key(28));
+        assert_eq!(on_chain.creator(), &on_chain_key(29));
+        assert_eq!(on_chain.protocol_fees_to_redeem(), parsed.protocol_fees_to_redeem);
+        assert_eq!(data[WpAmm::MINTS_OFFSET + 64..WpAmm::MINTS_OFFSET + 96], key(22));
+        assert_eq!(data[WpAmm::LP_MINT_OFFSET..WpAmm::LP_MINT_OFFSET + 32], key(23));
+        assert_eq!(data[WpAmm::AMMS_CONFIG_OFFSET..WpAmm::AMMS_CONFIG_OFFSET + 32], key(28));
+        assert_eq!(data[WpAmm::CREATOR_OFFSET..WpAmm::CREATOR_OFFSET + 32], key(29));
+        assert_eq!(parse_account(&data), Ok(ProgramAccount::WpAmm(Box::new(parsed))));
+    }
+
+    /// Tests errors on unknown, mismatched and truncated account data.
+    #[test]
+    fn test_parse_account_errors() {
+        assert_eq!(parse_account(&[7; 16]), Err(ParseError::UnknownDiscriminator([7; 8])));
+        assert_eq!(parse_account(&[0; 4]), Err(ParseError::UnexpectedEnd));
+        assert_eq!(AmmsConfig::parse(&AmmsConfigsManager::DISCRIMINATOR), Err(ParseError::InvalidDiscriminator));
+        assert_eq!(AmmsConfig::parse(&AmmsConfig::DISCRIMINATOR), Err(ParseError::UnexpectedEnd));
+    }
+}
\ No newline at end of file
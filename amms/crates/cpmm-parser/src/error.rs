@@ -0,0 +1,38 @@
+use std::fmt;
+
+/// Errors returned when raw account or event data can not be parsed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseError {
+    /// The data ended before the whole layout was read.
+    UnexpectedEnd,
+
+    /// The leading 8 bytes do not match the discriminator of the requested type.
+    InvalidDiscriminator,
+
+    /// The leading 8 bytes do not match the discriminator of any known type.
+    UnknownDiscriminator([u8; 8]),
+
+    /// A `bool` field holds a byte other than `0` or `1`.
+    InvalidBool(u8),
+
+    /// An `Option` field holds a tag other than `0` or `1`.
+    InvalidOptionTag(u8),
+
+    /// An enum field holds a variant index that does not exist.
+    InvalidEnumVariant(u8),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::UnexpectedEnd => write!(f, "Unexpected end of data"),
+            ParseError::InvalidDiscriminator => write!(f, "Discriminator does not match the requested type"),
+            ParseError::UnknownDiscriminator(discriminator) => write!(f, "Unknown discriminator {:?}", discriminator),
+            ParseError::InvalidBool(value) => write!(f, "Invalid bool value {}", value),
+            ParseError::InvalidOptionTag(tag) => write!(f, "Invalid option tag {}", tag),
+            ParseError::InvalidEnumVariant(variant) => write!(f, "Invalid enum variant {}", variant),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
\ No newline at end of file
@@ -0,0 +1,487 @@
+use crate::error::ParseError;
+use crate::reader::Reader;
+use crate::types::Pubkey;
+
+/// Mirror of the on-chain `SwapEvent`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SwapEvent {
+    pub cp_amm: Pubkey,
+    pub signer: Pubkey,
+    pub is_in_out: bool,
+    pub swap_amount: u64,
+    pub swap_result: u64,
+    pub price_impact_basis_points: u64,
+    pub providers_fee_rate_basis_points: u16,
+    pub protocol_fee_rate_basis_points: u16,
+    pub providers_fee_amount: u64,
+    pub protocol_fee_amount: u64,
+    pub creator_fee_amount: u64,
+}
+
+impl SwapEvent {
+    pub const DISCRIMINATOR: [u8; 8] = [64, 198, 205, 232, 38, 8, 113, 226];
+
+    /// Parses decoded event data: the discriminator followed by the Borsh encoded fields.
+    pub fn parse(data: &[u8]) -> Result<Self, ParseError> {
+        let mut reader = Reader::new(data);
+        reader.read_discriminator(&Self::DISCRIMINATOR)?;
+        Ok(Self {
+            cp_amm: reader.read_pubkey()?,
+            signer: reader.read_pubkey()?,
+            is_in_out: reader.read_bool()?,
+            swap_amount: reader.read_u64()?,
+            swap_result: reader.read_u64()?,
+            price_impact_basis_points: reader.read_u64()?,
+            providers_fee_rate_basis_points: reader.read_u16()?,
+            protocol_fee_rate_basis_points: reader.read_u16()?,
+            providers_fee_amount: reader.read_u64()?,
+            protocol_fee_amount: reader.read_u64()?,
+            creator_fee_amount: reader.read_u64()?,
+        })
+    }
+}
+
+/// Mirror of the on-chain `TraderFeeTierAppliedEvent`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TraderFeeTierAppliedEvent {
+    pub cp_amm: Pubkey,
+    pub trader: Pubkey,
+    pub tier_index: u8,
+    pub discount_basis_points: u16,
+    pub volume_usd: u64,
+    pub undiscounted_providers_fee_rate_basis_points: u16,
+}
+
+impl TraderFeeTierAppliedEvent {
+    pub const DISCRIMINATOR: [u8; 8] = [229, 3, 216, 168, 31, 226, 206, 193];
+
+    /// Parses decoded event data: the discriminator followed by the Borsh encoded fields.
+    pub fn parse(data: &[u8]) -> Result<Self, ParseError> {
+        let mut reader = Reader::new(data);
+        reader.read_discriminator(&Self::DISCRIMINATOR)?;
+        Ok(Self {
+            cp_amm: reader.read_pubkey()?,
+            trader: reader.read_pubkey()?,
+            tier_index: reader.read_u8()?,
+            discount_basis_points: reader.read_u16()?,
+            volume_usd: reader.read_u64()?,
+            undiscounted_providers_fee_rate_basis_points: reader.read_u16()?,
+        })
+    }
+}
+
+/// Mirror of the on-chain `RebalanceEvent`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RebalanceEvent {
+    pub cp_amm: Pubkey,
+    pub fee_authority: Pubkey,
+    pub is_in_out: bool,
+    pub swap_amount: u64,
+    pub swap_result: u64,
+    pub price_impact_basis_points: u64,
+    pub providers_fee_rate_basis_points: u16,
+    pub protocol_fee_rate_basis_points: u16,
+    pub providers_fee_amount: u64,
+    pub protocol_fee_amount: u64,
+    pub creator_fee_amount: u64,
+}
+
+impl RebalanceEvent {
+    pub const DISCRIMINATOR: [u8; 8] = [120, 27, 117, 235, 104, 42, 132, 75];
+
+    /// Parses decoded event data: the discriminator followed by the Borsh encoded fields.
+    pub fn parse(data: &[u8]) -> Result<Self, ParseError> {
+        let mut reader = Reader::new(data);
+        reader.read_discriminator(&Self::DISCRIMINATOR)?;
+        Ok(Self {
+            cp_amm: reader.read_pubkey()?,
+            fee_authority: reader.read_pubkey()?,
+            is_in_out: reader.read_bool()?,
+            swap_amount: reader.read_u64()?,
+            swap_result: reader.read_u64()?,
+            price_impact_basis_points: reader.read_u64()?,
+            providers_fee_rate_basis_points: reader.read_u16()?,
+            protocol_fee_rate_basis_points: reader.read_u16()?,
+            providers_fee_amount: reader.read_u64()?,
+            protocol_fee_amount: reader.read_u64()?,
+            creator_fee_amount: reader.read_u64()?,
+        })
+    }
+}
+
+/// Mirror of the on-chain `FeeRateDisabledEvent`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FeeRateDisabledEvent {
+    pub amms_config: Pubkey,
+    pub cp_amm: Option<Pubkey>,
+    pub is_protocol_fee: bool,
+}
+
+impl FeeRateDisabledEvent {
+    pub const DISCRIMINATOR: [u8; 8] = [74, 113, 142, 249, 221, 55, 170, 235];
+
+    /// Parses decoded event data: the discriminator followed by the Borsh encoded fields.
+    pub fn parse(data: &[u8]) -> Result<Self, ParseError> {
+        let mut reader = Reader::new(data);
+        reader.read_discriminator(&Self::DISCRIMINATOR)?;
+        Ok(Self {
+            amms_config: reader.read_pubkey()?,
+            cp_amm: reader.read_option(Reader::read_pubkey)?,
+            is_protocol_fee: reader.read_bool()?,
+        })
+    }
+}
+
+/// Mirror of the on-chain `CollectFeesEvent`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CollectFeesEvent {
+    pub cp_amm: Pubkey,
+    pub fee_authority: Pubkey,
+    pub signer: Pubkey,
+    pub base_fees_amount: u64,
+    pub quote_fees_amount: u64,
+    pub base_keeper_bounty: u64,
+    pub quote_keeper_bounty: u64,
+}
+
+impl CollectFeesEvent {
+    pub const DISCRIMINATOR: [u8; 8] = [226, 2, 53, 67, 132, 158, 125, 173];
+
+    /// Parses decoded event data: the discriminator followed by the Borsh encoded fields.
+    pub fn parse(data: &[u8]) -> Result<Self, ParseError> {
+        let mut reader = Reader::new(data);
+        reader.read_discriminator(&Self::DISCRIMINATOR)?;
+        Ok(Self {
+            cp_amm: reader.read_pubkey()?,
+            fee_authority: reader.read_pubkey()?,
+            signer: reader.read_pubkey()?,
+            base_fees_amount: reader.read_u64()?,
+            quote_fees_amount: reader.read_u64()?,
+            base_keeper_bounty: reader.read_u64()?,
+            quote_keeper_bounty: reader.read_u64()?,
+        })
+    }
+}
+
+/// Mirror of the on-chain `NothingToCollectEvent`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NothingToCollectEvent {
+    pub cp_amm: Pubkey,
+    pub signer: Pubkey,
+}
+
+impl NothingToCollectEvent {
+    pub const DISCRIMINATOR: [u8; 8] = [207, 116, 29, 180, 77, 178, 239, 11];
+
+    /// Parses decoded event data: the discriminator followed by the Borsh encoded fields.
+    pub fn parse(data: &[u8]) -> Result<Self, ParseError> {
+        let mut reader = Reader::new(data);
+        reader.read_discriminator(&Self::DISCRIMINATOR)?;
+        Ok(Self {
+            cp_amm: reader.read_pubkey()?,
+            signer: reader.read_pubkey()?,
+        })
+    }
+}
+
+/// Mirror of the on-chain `CollectCreatorFeesEvent`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CollectCreatorFeesEvent {
+    pub cp_amm: Pubkey,
+    pub creator: Pubkey,
+    pub base_fees_amount: u64,
+    pub quote_fees_amount: u64,
+}
+
+impl CollectCreatorFeesEvent {
+    pub const DISCRIMINATOR: [u8; 8] = [151, 199, 191, 245, 169, 155, 159, 24];
+
+    /// Parses decoded event data: the discriminator followed by the Borsh encoded fields.
+    pub fn parse(data: &[u8]) -> Result<Self, ParseError> {
+        let mut reader = Reader::new(data);
+        reader.read_discriminator(&Self::DISCRIMINATOR)?;
+        Ok(Self {
+            cp_amm: reader.read_pubkey()?,
+            creator: reader.read_pubkey()?,
+            base_fees_amount: reader.read_u64()?,
+            quote_fees_amount: reader.read_u64()?,
+        })
+    }
+}
+
+/// Mirror of the on-chain `VaultBalanceDriftEvent`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VaultBalanceDriftEvent {
+    pub cp_amm: Pubkey,
+    pub signer: Pubkey,
+    pub base_vault_balance: u64,
+    pub quote_vault_balance: u64,
+    pub recorded_base_balance: u128,
+    pub recorded_quote_balance: u128,
+}
+
+impl VaultBalanceDriftEvent {
+    pub const DISCRIMINATOR: [u8; 8] = [228, 236, 35, 190, 116, 151, 198, 198];
+
+    /// Parses decoded event data: the discriminator followed by the Borsh encoded fields.
+    pub fn parse(data: &[u8]) -> Result<Self, ParseError> {
+        let mut reader = Reader::new(data);
+        reader.read_discriminator(&Self::DISCRIMINATOR)?;
+        Ok(Self {
+            cp_amm: reader.read_pubkey()?,
+            signer: reader.read_pubkey()?,
+            base_vault_balance: reader.read_u64()?,
+            quote_vault_balance: reader.read_u64()?,
+            recorded_base_balance: reader.read_u128()?,
+            recorded_quote_balance: reader.read_u128()?,
+        })
+    }
+}
+
+/// Any event emitted by the program.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProgramEvent {
+    Swap(SwapEvent),
+    TraderFeeTierApplied(TraderFeeTierAppliedEvent),
+    Rebalance(RebalanceEvent),
+    FeeRateDisabled(FeeRateDisabledEvent),
+    CollectFees(CollectFeesEvent),
+    NothingToCollect(NothingToCollectEvent),
+    CollectCreatorFees(CollectCreatorFeesEvent),
+    VaultBalanceDrift(VaultBalanceDriftEvent),
+}
+
+/// Parses decoded event data of any program event, selecting the type by its discriminator.
+///
+/// `data` is the base64-decoded payload of a `Program data:` log line.
+pub fn parse_event(data: &[u8]) -> Result<ProgramEvent, ParseError> {
+    let discriminator = Reader::new(data).read_array::<8>()?;
+    match discriminator {
+        SwapEvent::DISCRIMINATOR => Ok(ProgramEvent::Swap(SwapEvent::parse(data)?)),
+        TraderFeeTierAppliedEvent::DISCRIMINATOR => Ok(ProgramEvent::TraderFeeTierApplied(TraderFeeTierAppliedEvent::parse(data)?)),
+        RebalanceEvent::DISCRIMINATOR => Ok(ProgramEvent::Rebalance(RebalanceEvent::parse(data)?)),
+        FeeRateDisabledEvent::DISCRIMINATOR => Ok(ProgramEvent::FeeRateDisabled(FeeRateDisabledEvent::parse(data)?)),
+        CollectFeesEvent::DISCRIMINATOR => Ok(ProgramEvent::CollectFees(CollectFeesEvent::parse(data)?)),
+        NothingToCollectEvent::DISCRIMINATOR => Ok(ProgramEvent::NothingToCollect(NothingToCollectEvent::parse(data)?)),
+        CollectCreatorFeesEvent::DISCRIMINATOR => Ok(ProgramEvent::CollectCreatorFees(CollectCreatorFeesEvent::parse(data)?)),
+        VaultBalanceDriftEvent::DISCRIMINATOR => Ok(ProgramEvent::VaultBalanceDrift(VaultBalanceDriftEvent::parse(data)?)),
+        _ => Err(ParseError::UnknownDiscriminator(discriminator)),
+    }
+}
+
+#[cfg(test)]
+mod events_tests {
+    use super::*;
+    use anchor_lang::{Discriminator, Event};
+    use anchor_lang::prelude::Pubkey as OnChainPubkey;
+
+    /// Tests parsing of a `SwapEvent` emitted by the program.
+    #[test]
+    fn test_swap_event_layout() {
+        assert_eq!(SwapEvent::DISCRIMINATOR, cpmm::events::SwapEvent::DISCRIMINATOR);
+        let data = cpmm::events::SwapEvent {
+            cp_amm: OnChainPubkey::new_from_array([1; 32]),
+            signer: OnChainPubkey::new_from_array([2; 32]),
+            is_in_out: true,
+            swap_amount: 1_000,
+            swap_result: 990,
+            price_impact_basis_points: 12,
+            providers_fee_rate_basis_points: 25,
+            protocol_fee_rate_basis_points: 5,
+            providers_fee_amount: 2,
+            protocol_fee_amount: 1,
+            creator_fee_amount: 0,
+        }.data();
+
+        let expected = SwapEvent {
+            cp_amm: [1; 32],
+            signer: [2; 32],
+            is_in_out: true,
+            swap_amount: 1_000,
+            swap_result: 990,
+            price_impact_basis_points: 12,
+            providers_fee_rate_basis_points: 25,
+            protocol_fee_rate_basis_points: 5,
+            providers_fee_amount: 2,
+            protocol_fee_amount: 1,
+            creator_fee_amount: 0,
+        };
+        assert_eq!(SwapEvent::parse(&data), Ok(expected));
+        assert_eq!(parse_event(&data), Ok(ProgramEvent::Swap(expected)));
+        assert_eq!(SwapEvent::parse(&data[..data.len() - 1]), Err(ParseError::UnexpectedEnd));
+    }
+
+    /// Tests parsing of a `RebalanceEvent` emitted by the program.
+    #[test]
+    fn test_rebalance_event_layout() {
+        assert_eq!(RebalanceEvent::DISCRIMINATOR, cpmm::events::RebalanceEvent::DISCRIMINATOR);
+        let data = cpmm::events::RebalanceEvent {
+            cp_amm: OnChainPubkey::new_from_array([3; 32]),
+            fee_authority: OnChainPubkey::new_from_array([4; 32]),
+            is_in_out: false,
+            swap_amount: 2_000,
+            swap_result: 1_950,
+            price_impact_basis_points: 40,
+            providers_fee_rate_basis_points: 25,
+            protocol_fee_rate_basis_points: 0,
+            providers_fee_amount: 4,
+            protocol_fee_amount: 0,
+            creator_fee_amount: 1,
+        }.data();
+
+        let expected = RebalanceEvent {
+            cp_amm: [3; 32],
+            fee_authority: [4; 32],
+            is_in_out: false,
+            swap_amount: 2_000,
+            swap_result: 1_950,
+            price_impact_basis_points: 40,
+            providers_fee_rate_basis_points: 25,
+            protocol_fee_rate_basis_points: 0,
+            providers_fee_amount: 4,
+            protocol_fee_amount: 0,
+            creator_fee_amount: 1,
+        };
+        assert_eq!(RebalanceEvent::parse(&data), Ok(expected));
+        assert_eq!(parse_event(&data), Ok(ProgramEvent::Rebalance(expected)));
+        assert_eq!(parse_event(&[9; 8]), Err(ParseError::UnknownDiscriminator([9; 8])));
+    }
+    /// Tests parsing of a `FeeRateDisabledEvent` emitted by the program.
+    #[test]
+    fn test_fee_rate_disabled_event_layout() {
+        assert_eq!(FeeRateDisabledEvent::DISCRIMINATOR, cpmm::events::FeeRateDisabledEvent::DISCRIMINATOR);
+        let data = cpmm::events::FeeRateDisabledEvent {
+            amms_config: OnChainPubkey::new_from_array([5; 32]),
+            cp_amm: Some(OnChainPubkey::new_from_array([6; 32])),
+            is_protocol_fee: true,
+        }.data();
+
+        let expected = FeeRateDisabledEvent {
+            amms_config: [5; 32],
+            cp_amm: Some([6; 32]),
+            is_protocol_fee: true,
+        };
+        assert_eq!(FeeRateDisabledEvent::parse(&data), Ok(expected));
+        assert_eq!(parse_event(&data), Ok(ProgramEvent::FeeRateDisabled(expected)));
+
+        let data = cpmm::events::FeeRateDisabledEvent {
+            amms_config: OnChainPubkey::new_from_array([5; 32]),
+            cp_amm: None,
+            is_protocol_fee: false,
+        }.data();
+        assert_eq!(FeeRateDisabledEvent::parse(&data), Ok(FeeRateDisabledEvent { amms_config: [5; 32], cp_amm: None, is_protocol_fee: false }));
+    }
+    /// Tests parsing of a `CollectFeesEvent` emitted by the program.
+    #[test]
+    fn test_collect_fees_event_layout() {
+        assert_eq!(CollectFeesEvent::DISCRIMINATOR, cpmm::events::CollectFeesEvent::DISCRIMINATOR);
+        let data = cpmm::events::CollectFeesEvent {
+            cp_amm: OnChainPubkey::new_from_array([7; 32]),
+            fee_authority: OnChainPubkey::new_from_array([8; 32]),
+            signer: OnChainPubkey::new_from_array([9; 32]),
+            base_fees_amount: 900,
+            quote_fees_amount: 450,
+            base_keeper_bounty: 100,
+            quote_keeper_bounty: 50,
+        }.data();
+
+        let expected = CollectFeesEvent {
+            cp_amm: [7; 32],
+            fee_authority: [8; 32],
+            signer: [9; 32],
+            base_fees_amount: 900,
+            quote_fees_amount: 450,
+            base_keeper_bounty: 100,
+            quote_keeper_bounty: 50,
+        };
+        assert_eq!(CollectFeesEvent::parse(&data), Ok(expected));
+        assert_eq!(parse_event(&data), Ok(ProgramEvent::CollectFees(expected)));
+    }
+
+    /// Tests parsing of a `TraderFeeTierAppliedEvent` emitted by the program.
+    #[test]
+    fn test_trader_fee_tier_applied_event_layout() {
+        assert_eq!(TraderFeeTierAppliedEvent::DISCRIMINATOR, cpmm::events::TraderFeeTierAppliedEvent::DISCRIMINATOR);
+        let data = cpmm::events::TraderFeeTierAppliedEvent {
+            cp_amm: OnChainPubkey::new_from_array([14; 32]),
+            trader: OnChainPubkey::new_from_array([15; 32]),
+            tier_index: 2,
+            discount_basis_points: 1500,
+            volume_usd: 250_000_000_000,
+            undiscounted_providers_fee_rate_basis_points: 30,
+        }.data();
+
+        let expected = TraderFeeTierAppliedEvent {
+            cp_amm: [14; 32],
+            trader: [15; 32],
+            tier_index: 2,
+            discount_basis_points: 1500,
+            volume_usd: 250_000_000_000,
+            undiscounted_providers_fee_rate_basis_points: 30,
+        };
+        assert_eq!(TraderFeeTierAppliedEvent::parse(&data), Ok(expected));
+        assert_eq!(parse_event(&data), Ok(ProgramEvent::TraderFeeTierApplied(expected)));
+    }
+
+    /// Tests parsing of a `NothingToCollectEvent` emitted by the program.
+    #[test]
+    fn test_nothing_to_collect_event_layout() {
+        assert_eq!(NothingToCollectEvent::DISCRIMINATOR, cpmm::events::NothingToCollectEvent::DISCRIMINATOR);
+        let data = cpmm::events::NothingToCollectEvent {
+            cp_amm: OnChainPubkey::new_from_array([12; 32]),
+            signer: OnChainPubkey::new_from_array([13; 32]),
+        }.data();
+
+        let expected = NothingToCollectEvent {
+            cp_amm: [12; 32],
+            signer: [13; 32],
+        };
+        assert_eq!(NothingToCollectEvent::parse(&data), Ok(expected));
+        assert_eq!(parse_event(&data), Ok(ProgramEvent::NothingToCollect(expected)));
+    }
+
+    /// Tests parsing of a `CollectCreatorFeesEvent` emitted by the program.
+    #[test]
+    fn test_collect_creator_fees_event_layout() {
+        assert_eq!(CollectCreatorFeesEvent::DISCRIMINATOR, cpmm::events::CollectCreatorFeesEvent::DISCRIMINATOR);
+        let data = cpmm::events::CollectCreatorFeesEvent {
+            cp_amm: OnChainPubkey::new_from_array([10; 32]),
+            creator: OnChainPubkey::new_from_array([11; 32]),
+            base_fees_amount: 30,
+            quote_fees_amount: 0,
+        }.data();
+
+        let expected = CollectCreatorFeesEvent {
+            cp_amm: [10; 32],
+            creator: [11; 32],
+            base_fees_amount: 30,
+            quote_fees_amount: 0,
+        };
+        assert_eq!(CollectCreatorFeesEvent::parse(&data), Ok(expected));
+        assert_eq!(parse_event(&data), Ok(ProgramEvent::CollectCreatorFees(expected)));
+    }
+    /// Tests parsing of a `VaultBalanceDriftEvent` emitted by the program.
+    #[test]
+    fn test_vault_balance_drift_event_layout() {
+        assert_eq!(VaultBalanceDriftEvent::DISCRIMINATOR, cpmm::events::VaultBalanceDriftEvent::DISCRIMINATOR);
+        let data = cpmm::events::VaultBalanceDriftEvent {
+            cp_amm: OnChainPubkey::new_from_array([14; 32]),
+            signer: OnChainPubkey::new_from_array([15; 32]),
+            base_vault_balance: 900_499,
+            quote_vault_balance: 4_001_000,
+            recorded_base_balance: 900_500,
+            recorded_quote_balance: u64::MAX as u128 + 1,
+        }.data();
+
+        let expected = VaultBalanceDriftEvent {
+            cp_amm: [14; 32],
+            signer: [15; 32],
+            base_vault_balance: 900_499,
+            quote_vault_balance: 4_001_000,
+            recorded_base_balance: 900_500,
+            recorded_quote_balance: u64::MAX as u128 + 1,
+        };
+        assert_eq!(VaultBalanceDriftEvent::parse(&data), Ok(expected));
+        assert_eq!(parse_event(&data), Ok(ProgramEvent::VaultBalanceDrift(expected)));
+    }
+}
\ No newline at end of file
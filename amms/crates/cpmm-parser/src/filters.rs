@@ -0,0 +1,115 @@
+use crate::accounts::{CpAmm, WpAmm};
+use crate::types::Pubkey;
+
+/// A `memcmp` filter of `getProgramAccounts`: the account data must hold `bytes` at `offset`.
+///
+/// The offsets include the 8 bytes discriminator and are stable across layout versions, as new
+/// fields are only appended to the pool accounts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemcmpFilter {
+    pub offset: usize,
+    pub bytes: Vec<u8>,
+}
+
+impl MemcmpFilter {
+    pub fn new(offset: usize, bytes: &[u8]) -> Self {
+        Self { offset, bytes: bytes.to_vec() }
+    }
+
+    /// Returns whether raw account data passes the filter, like the RPC node would evaluate it.
+    pub fn matches(&self, data: &[u8]) -> bool {
+        data.get(self.offset..self.offset + self.bytes.len()) == Some(self.bytes.as_slice())
+    }
+}
+
+impl CpAmm {
+    /// Filter of all `CpAmm` accounts.
+    pub fn discriminator_filter() -> MemcmpFilter {
+        MemcmpFilter::new(0, &Self::DISCRIMINATOR)
+    }
+
+    /// Filters of all `CpAmm` accounts of an `AmmsConfig`.
+    pub fn amms_config_filters(amms_config: &Pubkey) -> [MemcmpFilter; 2] {
+        [Self::discriminator_filter(), MemcmpFilter::new(Self::AMMS_CONFIG_OFFSET, amms_config)]
+    }
+
+    /// Filters of all `CpAmm` accounts with a base mint.
+    pub fn base_mint_filters(base_mint: &Pubkey) -> [MemcmpFilter; 2] {
+        [Self::discriminator_filter(), MemcmpFilter::new(Self::BASE_MINT_OFFSET, base_mint)]
+    }
+
+    /// Filters of all `CpAmm` accounts with a quote mint.
+    pub fn quote_mint_filters(quote_mint: &Pubkey) -> [MemcmpFilter; 2] {
+        [Self::discriminator_filter(), MemcmpFilter::new(Self::QUOTE_MINT_OFFSET, quote_mint)]
+    }
+
+    /// Filters of all `CpAmm` accounts created by a signer.
+    pub fn creator_filters(creator: &Pubkey) -> [MemcmpFilter; 2] {
+        [Self::discriminator_filter(), MemcmpFilter::new(Self::CREATOR_OFFSET, creator)]
+    }
+}
+
+impl WpAmm {
+    /// Filter of all `WpAmm` accounts.
+    pub fn discriminator_filter() -> MemcmpFilter {
+        MemcmpFilter::new(0, &Self::DISCRIMINATOR)
+    }
+
+    /// Filters of all `WpAmm` accounts of an `AmmsConfig`.
+    pub fn amms_config_filters(amms_config: &Pubkey) -> [MemcmpFilter; 2] {
+        [Self::discriminator_filter(), MemcmpFilter::new(Self::AMMS_CONFIG_OFFSET, amms_config)]
+    }
+
+    /// Filters of all `WpAmm` accounts with a mint at the `index` position.
+    pub fn mint_filters(index: usize, mint: &Pubkey) -> [MemcmpFilter; 2] {
+        [Self::discriminator_filter(), MemcmpFilter::new(Self::MINTS_OFFSET + index * 32, mint)]
+    }
+
+    /// Filters of all `WpAmm` accounts created by a signer.
+    pub fn creator_filters(creator: &Pubkey) -> [MemcmpFilter; 2] {
+        [Self::discriminator_filter(), MemcmpFilter::new(Self::CREATOR_OFFSET, creator)]
+    }
+}
+
+#[cfg(test)]
+mod filters_tests {
+    use super::*;
+
+    /// Tests that the offsets match the ones of the on-chain structs.
+    #[test]
+    fn test_offsets_match_on_chain() {
+        use cpmm::state::cp_amm::CpAmm as OnChainCpAmm;
+        use cpmm::state::wp_amm::WpAmm as OnChainWpAmm;
+        assert_eq!(CpAmm::LAYOUT_VERSION_OFFSET, OnChainCpAmm::LAYOUT_VERSION_OFFSET);
+        assert_eq!(CpAmm::BASE_MINT_OFFSET, OnChainCpAmm::BASE_MINT_OFFSET);
+        assert_eq!(CpAmm::QUOTE_MINT_OFFSET, OnChainCpAmm::QUOTE_MINT_OFFSET);
+        assert_eq!(CpAmm::LP_MINT_OFFSET, OnChainCpAmm::LP_MINT_OFFSET);
+        assert_eq!(CpAmm::AMMS_CONFIG_OFFSET, OnChainCpAmm::AMMS_CONFIG_OFFSET);
+        assert_eq!(CpAmm::CREATOR_OFFSET, OnChainCpAmm::CREATOR_OFFSET);
+        assert_eq!(WpAmm::MINTS_OFFSET, OnChainWpAmm::MINTS_OFFSET);
+        assert_eq!(WpAmm::LP_MINT_OFFSET, OnChainWpAmm::LP_MINT_OFFSET);
+        assert_eq!(WpAmm::AMMS_CONFIG_OFFSET, OnChainWpAmm::AMMS_CONFIG_OFFSET);
+        assert_eq!(WpAmm::CREATOR_OFFSET, OnChainWpAmm::CREATOR_OFFSET);
+    }
+
+    /// Tests that the config filters select pools by the discriminator and the config key.
+    #[test]
+    fn test_amms_config_filters() {
+        let amms_config = [7u8; 32];
+        let mut data = vec![0u8; CpAmm::LEN];
+        data[..8].copy_from_slice(&CpAmm::DISCRIMINATOR);
+        data[CpAmm::AMMS_CONFIG_OFFSET..CpAmm::AMMS_CONFIG_OFFSET + 32].copy_from_slice(&amms_config);
+        assert!(CpAmm::amms_config_filters(&amms_config).iter().all(|filter| filter.matches(&data)));
+        assert!(!CpAmm::amms_config_filters(&[8u8; 32]).iter().all(|filter| filter.matches(&data)));
+        assert!(!WpAmm::amms_config_filters(&amms_config).iter().all(|filter| filter.matches(&data)));
+
+        let mut data = vec![0u8; WpAmm::LEN];
+        data[..8].copy_from_slice(&WpAmm::DISCRIMINATOR);
+        data[WpAmm::AMMS_CONFIG_OFFSET..WpAmm::AMMS_CONFIG_OFFSET + 32].copy_from_slice(&amms_config);
+        data[WpAmm::MINTS_OFFSET + 32..WpAmm::MINTS_OFFSET + 64].copy_from_slice(&[9u8; 32]);
+        assert!(WpAmm::amms_config_filters(&amms_config).iter().all(|filter| filter.matches(&data)));
+        assert!(WpAmm::mint_filters(1, &[9u8; 32]).iter().all(|filter| filter.matches(&data)));
+        assert!(!WpAmm::mint_filters(0, &[9u8; 32]).iter().all(|filter| filter.matches(&data)));
+        assert!(!MemcmpFilter::new(WpAmm::LEN - 1, &[0, 0]).matches(&data));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,20 @@
+//! Parsers for the raw accounts and events of the `cpmm` program.
+//!
+//! The crate has no dependencies and does not require Anchor: accounts are decoded from the
+//! raw account data, including the 8 bytes discriminator, and events from the base64-decoded
+//! payload of `Program data:` log lines. This makes it suitable for indexers ingesting account
+//! updates directly, e.g. from a Geyser plugin. The `filters` module builds the `memcmp` filters of
+//! `getProgramAccounts`, e.g. to fetch all pools of a config.
+mod reader;
+
+pub mod accounts;
+pub mod error;
+pub mod events;
+pub mod filters;
+pub mod types;
+
+pub use accounts::*;
+pub use error::ParseError;
+pub use events::*;
+pub use filters::*;
+pub use types::*;
\ No newline at end of file
@@ -0,0 +1,125 @@
+use crate::error::ParseError;
+use crate::types::Pubkey;
+
+/// Sequential little-endian Borsh reader over a byte slice.
+pub(crate) struct Reader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    pub(crate) fn new(data: &'a [u8]) -> Self {
+        Self { data }
+    }
+
+    /// Reads the discriminator and checks it against the `expected` one.
+    pub(crate) fn read_discriminator(&mut self, expected: &[u8; 8]) -> Result<(), ParseError> {
+        if &self.read_array::<8>()? != expected {
+            return Err(ParseError::InvalidDiscriminator);
+        }
+        Ok(())
+    }
+
+    pub(crate) fn read_array<const N: usize>(&mut self) -> Result<[u8; N], ParseError> {
+        if self.data.len() < N {
+            return Err(ParseError::UnexpectedEnd);
+        }
+        let (bytes, rest) = self.data.split_at(N);
+        self.data = rest;
+        let mut array = [0u8; N];
+        array.copy_from_slice(bytes);
+        Ok(array)
+    }
+
+    pub(crate) fn read_u8(&mut self) -> Result<u8, ParseError> {
+        Ok(self.read_array::<1>()?[0])
+    }
+
+    pub(crate) fn read_bool(&mut self) -> Result<bool, ParseError> {
+        match self.read_u8()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            value => Err(ParseError::InvalidBool(value)),
+        }
+    }
+
+    pub(crate) fn read_u16(&mut self) -> Result<u16, ParseError> {
+        Ok(u16::from_le_bytes(self.read_array()?))
+    }
+
+    pub(crate) fn read_u32(&mut self) -> Result<u32, ParseError> {
+        Ok(u32::from_le_bytes(self.read_array()?))
+    }
+
+    pub(crate) fn read_u64(&mut self) -> Result<u64, ParseError> {
+        Ok(u64::from_le_bytes(self.read_array()?))
+    }
+
+    pub(crate) fn read_i64(&mut self) -> Result<i64, ParseError> {
+        Ok(i64::from_le_bytes(self.read_array()?))
+    }
+
+    pub(crate) fn read_u128(&mut self) -> Result<u128, ParseError> {
+        Ok(u128::from_le_bytes(self.read_array()?))
+    }
+
+    pub(crate) fn read_pubkey(&mut self) -> Result<Pubkey, ParseError> {
+        self.read_array()
+    }
+
+    /// Reads a Borsh `Option`: a `0` tag for `None`, or a `1` tag followed by the value.
+    pub(crate) fn read_option<T>(&mut self, read: impl FnOnce(&mut Self) -> Result<T, ParseError>) -> Result<Option<T>, ParseError> {
+        match self.read_u8()? {
+            0 => Ok(None),
+            1 => Ok(Some(read(self)?)),
+            tag => Err(ParseError::InvalidOptionTag(tag)),
+        }
+    }
+
+    pub(crate) fn read_u64_array<const N: usize>(&mut self) -> Result<[u64; N], ParseError> {
+        let mut array = [0u64; N];
+        for value in array.iter_mut() {
+            *value = self.read_u64()?;
+        }
+        Ok(array)
+    }
+
+    pub(crate) fn read_u16_array<const N: usize>(&mut self) -> Result<[u16; N], ParseError> {
+        let mut array = [0u16; N];
+        for value in array.iter_mut() {
+            *value = self.read_u16()?;
+        }
+        Ok(array)
+    }
+
+    pub(crate) fn read_pubkey_array<const N: usize>(&mut self) -> Result<[Pubkey; N], ParseError> {
+        let mut array = [[0u8; 32]; N];
+        for value in array.iter_mut() {
+            *value = self.read_pubkey()?;
+        }
+        Ok(array)
+    }
+}
+
+#[cfg(test)]
+mod reader_tests {
+    use super::*;
+
+    /// Tests primitive reads and the errors on malformed input.
+    #[test]
+    fn test_reader() {
+        let data = [1u8, 0x34, 0x12, 1, 7, 0, 2];
+        let mut reader = Reader::new(&data);
+        assert_eq!(reader.read_bool(), Ok(true));
+        assert_eq!(reader.read_u16(), Ok(0x1234));
+        assert_eq!(reader.read_option(|reader| reader.read_u8()), Ok(Some(7)));
+        assert_eq!(reader.read_option(|reader| reader.read_u8()), Ok(None));
+        assert_eq!(reader.read_bool(), Err(ParseError::InvalidBool(2)));
+        assert_eq!(reader.read_u8(), Err(ParseError::UnexpectedEnd));
+
+        assert_eq!(Reader::new(&[2]).read_option(|reader| reader.read_u8()), Err(ParseError::InvalidOptionTag(2)));
+        assert_eq!(Reader::new(&[0; 7]).read_u64(), Err(ParseError::UnexpectedEnd));
+        assert_eq!(Reader::new(&(-5i64).to_le_bytes()).read_i64(), Ok(-5));
+        assert_eq!(Reader::new(&0x12345678u32.to_le_bytes()).read_u32(), Ok(0x12345678));
+        assert_eq!(Reader::new(&[0; 8]).read_discriminator(&[1; 8]), Err(ParseError::InvalidDiscriminator));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,507 @@
+use crate::error::ParseError;
+use crate::reader::Reader;
+
+/// Raw 32 bytes of a Solana public key.
+pub type Pubkey = [u8; 32];
+
+/// Number of tokens in a `WpAmm` pool.
+pub const WP_AMM_TOKENS_COUNT: usize = 3;
+
+/// Number of checkpoints in a `FeeCheckpoints` ring buffer.
+pub const FEE_CHECKPOINTS_CAPACITY: usize = 64;
+
+/// Number of checkpoints in a `VirtualPriceCheckpoints` ring buffer.
+pub const VIRTUAL_PRICE_CHECKPOINTS_CAPACITY: usize = 30;
+
+/// Number of pools in a `PoolIndex` bucket.
+pub const POOL_INDEX_CAPACITY: usize = 32;
+
+/// Number of mints in a `MintEquivalences` account.
+pub const MINT_EQUIVALENCES_CAPACITY: usize = 32;
+
+/// Number of tiers in a `TraderFeeTiers` account.
+pub const TRADER_FEE_TIERS_CAPACITY: usize = 8;
+
+/// Number of routers in a `RouterRegistry` account.
+pub const ROUTER_REGISTRY_CAPACITY: usize = 16;
+
+/// Number of records in an `EventRing` ring buffer.
+pub const EVENT_RING_CAPACITY: usize = 64;
+
+/// Raw bits of a Q64.128 fixed-point number.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Q64_128 {
+    /// The lower 128 fractional bits.
+    pub fractional_bits: u128,
+
+    /// The upper 64 integer bits.
+    pub integer_bits: u64,
+}
+
+impl Q64_128 {
+    /// Serialized size in bytes.
+    pub const LEN: usize = 24;
+
+    /// Returns an approximate floating point value, for display and analytics only.
+    pub fn to_f64(&self) -> f64 {
+        self.integer_bits as f64 + self.fractional_bits as f64 / 2f64.powi(128)
+    }
+
+    pub(crate) fn read(reader: &mut Reader) -> Result<Self, ParseError> {
+        // The on-chain value is a 192-bit integer serialized as little-endian 64-bit words
+        let fractional_bits = reader.read_u128()?;
+        let integer_bits = reader.read_u64()?;
+        Ok(Self { fractional_bits, integer_bits })
+    }
+}
+
+/// Bounds on the fee rates of an `AmmsConfig`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct FeeBounds {
+    pub min_providers_fee_rate_basis_points: u16,
+    pub max_providers_fee_rate_basis_points: u16,
+    pub max_protocol_fee_rate_basis_points: u16,
+}
+
+impl FeeBounds {
+    /// Serialized size in bytes.
+    pub const LEN: usize = 6;
+
+    pub(crate) fn read(reader: &mut Reader) -> Result<Self, ParseError> {
+        Ok(Self {
+            min_providers_fee_rate_basis_points: reader.read_u16()?,
+            max_providers_fee_rate_basis_points: reader.read_u16()?,
+            max_protocol_fee_rate_basis_points: reader.read_u16()?,
+        })
+    }
+}
+
+/// Linear ramp of an `AmmsConfig` providers fee rate towards the stored rate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct FeeRamp {
+    pub start_slot: u64,
+    pub end_slot: u64,
+    pub start_fee_rate_basis_points: u16,
+}
+
+impl FeeRamp {
+    /// Serialized size in bytes.
+    pub const LEN: usize = 18;
+
+    pub(crate) fn read(reader: &mut Reader) -> Result<Self, ParseError> {
+        Ok(Self {
+            start_slot: reader.read_u64()?,
+            end_slot: reader.read_u64()?,
+            start_fee_rate_basis_points: reader.read_u16()?,
+        })
+    }
+}
+
+/// Loyalty multiplier of an `AmmsConfig` for liquidity held beyond configured durations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct LoyaltyBoost {
+    pub min_hold_seconds: u32,
+    pub full_boost_hold_seconds: u32,
+    pub max_multiplier_basis_points: u16,
+}
+
+impl LoyaltyBoost {
+    /// Serialized size in bytes.
+    pub const LEN: usize = 10;
+
+    pub(crate) fn read(reader: &mut Reader) -> Result<Self, ParseError> {
+        Ok(Self {
+            min_hold_seconds: reader.read_u32()?,
+            full_boost_hold_seconds: reader.read_u32()?,
+            max_multiplier_basis_points: reader.read_u16()?,
+        })
+    }
+}
+
+/// Pricing of pool creation in an `AmmsConfig`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct PoolCreationPricing {
+    pub base_price_in_lamports: u64,
+    pub pools_per_step: u32,
+    pub step_price_in_lamports: u64,
+    pub max_price_in_lamports: u64,
+}
+
+impl PoolCreationPricing {
+    /// Serialized size in bytes.
+    pub const LEN: usize = 28;
+
+    pub(crate) fn read(reader: &mut Reader) -> Result<Self, ParseError> {
+        Ok(Self {
+            base_price_in_lamports: reader.read_u64()?,
+            pools_per_step: reader.read_u32()?,
+            step_price_in_lamports: reader.read_u64()?,
+            max_price_in_lamports: reader.read_u64()?,
+        })
+    }
+}
+
+/// A mint assigned to a group of equivalent mints of an `AmmsConfig`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct MintEquivalence {
+    pub mint: Pubkey,
+    pub group: u8,
+}
+
+impl MintEquivalence {
+    /// Serialized size in bytes.
+    pub const LEN: usize = 33;
+
+    pub(crate) fn read(reader: &mut Reader) -> Result<Self, ParseError> {
+        Ok(Self {
+            mint: reader.read_pubkey()?,
+            group: reader.read_u8()?,
+        })
+    }
+}
+
+/// A providers fee discount of the `TraderFeeTiers` of an `AmmsConfig`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TraderFeeTier {
+    pub min_volume_usd: u64,
+    pub discount_basis_points: u16,
+}
+
+impl TraderFeeTier {
+    /// Serialized size in bytes.
+    pub const LEN: usize = 10;
+
+    pub(crate) fn read(reader: &mut Reader) -> Result<Self, ParseError> {
+        Ok(Self {
+            min_volume_usd: reader.read_u64()?,
+            discount_basis_points: reader.read_u16()?,
+        })
+    }
+}
+
+/// Compact record of an operation in an `EventRing`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct EventRecord {
+    pub sequence: u64,
+    pub slot: u64,
+    pub signer: Pubkey,
+    pub base_amount: u64,
+    pub quote_amount: u64,
+    pub lp_tokens: u64,
+    /// 1 for a base-to-quote swap, 2 for a quote-to-base swap, 3 for a provide, 4 for a withdrawal, 5 for a fee
+    /// settlement, 6 for the launch, 7 for a loan repayment, 0 for an empty record.
+    pub kind: u8,
+}
+
+impl EventRecord {
+    /// Serialized size in bytes, including the 7 padding bytes.
+    pub const LEN: usize = 80;
+
+    pub(crate) fn read(reader: &mut Reader) -> Result<Self, ParseError> {
+        let record = Self {
+            sequence: reader.read_u64()?,
+            slot: reader.read_u64()?,
+            signer: reader.read_pubkey()?,
+            base_amount: reader.read_u64()?,
+            quote_amount: reader.read_u64()?,
+            lp_tokens: reader.read_u64()?,
+            kind: reader.read_u8()?,
+        };
+        reader.read_array::<7>()?;
+        Ok(record)
+    }
+}
+
+/// Settlement of the protocol fees of an `AmmsConfig` in a single mint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct FeeSettlement {
+    pub settlement_mint: Option<Pubkey>,
+    pub max_impact_basis_points: u16,
+}
+
+impl FeeSettlement {
+    /// Serialized size in bytes.
+    pub const LEN: usize = 35;
+
+    pub(crate) fn read(reader: &mut Reader) -> Result<Self, ParseError> {
+        Ok(Self {
+            settlement_mint: reader.read_option(Reader::read_pubkey)?,
+            max_impact_basis_points: reader.read_u16()?,
+        })
+    }
+}
+
+/// Snapshot of the cumulative providers fees of a `CpAmm`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct FeeCheckpoint {
+    pub timestamp: i64,
+    pub cumulative_base_providers_fees: u128,
+    pub cumulative_quote_providers_fees: u128,
+}
+
+impl FeeCheckpoint {
+    /// Serialized size in bytes.
+    pub const LEN: usize = 40;
+
+    pub(crate) fn read(reader: &mut Reader) -> Result<Self, ParseError> {
+        Ok(Self {
+            timestamp: reader.read_i64()?,
+            cumulative_base_providers_fees: reader.read_u128()?,
+            cumulative_quote_providers_fees: reader.read_u128()?,
+        })
+    }
+}
+
+/// Snapshot of the virtual price of a `CpAmm`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct VirtualPriceCheckpoint {
+    pub timestamp: i64,
+    pub virtual_price: u64,
+}
+
+impl VirtualPriceCheckpoint {
+    /// Serialized size in bytes.
+    pub const LEN: usize = 16;
+
+    pub(crate) fn read(reader: &mut Reader) -> Result<Self, ParseError> {
+        Ok(Self {
+            timestamp: reader.read_i64()?,
+            virtual_price: reader.read_u64()?,
+        })
+    }
+}
+
+/// Per-slot withdrawals accounting of a `CpAmm`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct WithdrawRateLimiter {
+    pub slot: u64,
+    pub slot_lp_tokens_supply: u64,
+    pub withdrawn_lp_tokens: u64,
+}
+
+impl WithdrawRateLimiter {
+    /// Serialized size in bytes.
+    pub const LEN: usize = 24;
+
+    pub(crate) fn read(reader: &mut Reader) -> Result<Self, ParseError> {
+        Ok(Self {
+            slot: reader.read_u64()?,
+            slot_lp_tokens_supply: reader.read_u64()?,
+            withdrawn_lp_tokens: reader.read_u64()?,
+        })
+    }
+}
+
+/// Cumulative trading statistics of a `CpAmm`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct PoolStats {
+    pub cumulative_base_volume: u128,
+    pub cumulative_quote_volume: u128,
+    pub cumulative_usd_volume: u128,
+    pub cumulative_base_providers_fees: u128,
+    pub cumulative_quote_providers_fees: u128,
+    pub cumulative_base_protocol_fees: u128,
+    pub cumulative_quote_protocol_fees: u128,
+}
+
+impl PoolStats {
+    /// Serialized size in bytes.
+    pub const LEN: usize = 112;
+
+    pub(crate) fn read(reader: &mut Reader) -> Result<Self, ParseError> {
+        Ok(Self {
+            cumulative_base_volume: reader.read_u128()?,
+            cumulative_quote_volume: reader.read_u128()?,
+            cumulative_usd_volume: reader.read_u128()?,
+            cumulative_base_providers_fees: reader.read_u128()?,
+            cumulative_quote_providers_fees: reader.read_u128()?,
+            cumulative_base_protocol_fees: reader.read_u128()?,
+            cumulative_quote_protocol_fees: reader.read_u128()?,
+        })
+    }
+}
+
+/// Weekly UTC trading hours of a `CpAmm`, one bit per hour starting on Monday 00:00.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TradingSchedule {
+    pub open_hours: [u8; 21],
+}
+
+impl TradingSchedule {
+    /// Serialized size in bytes.
+    pub const LEN: usize = 21;
+
+    pub(crate) fn read(reader: &mut Reader) -> Result<Self, ParseError> {
+        Ok(Self { open_hours: reader.read_array()? })
+    }
+}
+
+/// Lifecycle status of a `CpAmm`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PoolStatus {
+    #[default]
+    Uninitialized,
+    Initialized,
+    Launched,
+    Paused,
+    Retired,
+}
+
+impl PoolStatus {
+    pub(crate) fn read(reader: &mut Reader) -> Result<Self, ParseError> {
+        match reader.read_u8()? {
+            0 => Ok(Self::Uninitialized),
+            1 => Ok(Self::Initialized),
+            2 => Ok(Self::Launched),
+            3 => Ok(Self::Paused),
+            4 => Ok(Self::Retired),
+            variant => Err(ParseError::InvalidEnumVariant(variant)),
+        }
+    }
+}
+
+/// The curve pricing the liquidity of a `CpAmm`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CurveKind {
+    #[default]
+    ConstantProduct,
+}
+
+impl CurveKind {
+    pub(crate) fn read(reader: &mut Reader) -> Result<Self, ParseError> {
+        match reader.read_u8()? {
+            0 => Ok(Self::ConstantProduct),
+            variant => Err(ParseError::InvalidEnumVariant(variant)),
+        }
+    }
+}
+
+/// Origin of the swaps accepted in the pools of an `AmmsConfig` while its swap origin policy is in force.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SwapOriginPolicy {
+    #[default]
+    Unrestricted,
+    DirectOnly,
+    CpiOnly,
+}
+
+impl SwapOriginPolicy {
+    pub(crate) fn read(reader: &mut Reader) -> Result<Self, ParseError> {
+        match reader.read_u8()? {
+            0 => Ok(Self::Unrestricted),
+            1 => Ok(Self::DirectOnly),
+            2 => Ok(Self::CpiOnly),
+            variant => Err(ParseError::InvalidEnumVariant(variant)),
+        }
+    }
+}
+
+/// Self-describing summary of a `CpAmm` for wallets, encoded as type-length-value entries.
+///
+/// Each entry is a `u16` type, a `u16` length and the value, all little-endian. The entries end
+/// with a zero type or the end of the region. The known types are `DisplayData::MINTS` to
+/// `DisplayData::LAST_UPDATE_SLOT`, unknown ones should be skipped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DisplayData {
+    pub data: [u8; 160],
+}
+
+impl DisplayData {
+    /// Serialized size in bytes.
+    pub const LEN: usize = 160;
+
+    /// The base and quote mints, 32 bytes each.
+    pub const MINTS: u16 = 1;
+    /// The base and quote liquidity in raw token units, `u64` each.
+    pub const RESERVES: u16 = 2;
+    /// The price of a raw base unit in raw quote units, a `u64` with 9 decimals.
+    pub const PRICE: u16 = 3;
+    /// The providers and protocol fee rates in basis points, `u16` each.
+    pub const FEE_RATES: u16 = 4;
+    /// The supply of LP tokens, a `u64`.
+    pub const LP_TOKENS_SUPPLY: u16 = 5;
+    /// The slot of the last refresh of the entries, a `u64`.
+    pub const LAST_UPDATE_SLOT: u16 = 6;
+
+    pub(crate) fn read(reader: &mut Reader) -> Result<Self, ParseError> {
+        Ok(Self { data: reader.read_array()? })
+    }
+
+    /// Returns the entries as their types and values, a truncated entry ends the entries.
+    pub fn entries(&self) -> impl Iterator<Item = (u16, &[u8])> {
+        let mut offset = 0;
+        std::iter::from_fn(move || {
+            let header = self.data.get(offset..offset + 4)?;
+            let entry_type = u16::from_le_bytes([header[0], header[1]]);
+            let length = u16::from_le_bytes([header[2], header[3]]) as usize;
+            if entry_type == 0 {
+                return None;
+            }
+            let value = self.data.get(offset + 4..offset + 4 + length)?;
+            offset += 4 + length;
+            Some((entry_type, value))
+        })
+    }
+
+    /// Returns the value of the first entry of a type.
+    pub fn entry(&self, entry_type: u16) -> Option<&[u8]> {
+        self.entries().find(|(raw_type, _)| *raw_type == entry_type).map(|(_, value)| value)
+    }
+}
+
+#[cfg(test)]
+mod types_tests {
+    use super::*;
+
+    /// Tests that Q64.128 bits are read in the on-chain word order.
+    #[test]
+    fn test_q64_128_read() {
+        let mut data = Vec::new();
+        data.extend_from_slice(&(1u128 << 127).to_le_bytes());
+        data.extend_from_slice(&5u64.to_le_bytes());
+
+        let value = Q64_128::read(&mut Reader::new(&data)).unwrap();
+        assert_eq!(value, Q64_128 { fractional_bits: 1u128 << 127, integer_bits: 5 });
+        assert_eq!(value.to_f64(), 5.5);
+    }
+
+    /// Tests that pool statuses are read by their on-chain variant index.
+    #[test]
+    fn test_pool_status_read() {
+        use cpmm::state::cp_amm::PoolStatus as OnChainPoolStatus;
+        let statuses = [
+            (OnChainPoolStatus::Uninitialized, PoolStatus::Uninitialized),
+            (OnChainPoolStatus::Initialized, PoolStatus::Initialized),
+            (OnChainPoolStatus::Launched, PoolStatus::Launched),
+            (OnChainPoolStatus::Paused, PoolStatus::Paused),
+            (OnChainPoolStatus::Retired, PoolStatus::Retired),
+        ];
+        for (on_chain, expected) in statuses {
+            let data = anchor_lang::AnchorSerialize::try_to_vec(&on_chain).unwrap();
+            assert_eq!(PoolStatus::read(&mut Reader::new(&data)), Ok(expected));
+        }
+        assert_eq!(PoolStatus::read(&mut Reader::new(&[5])), Err(ParseError::InvalidEnumVariant(5)));
+    }
+
+    /// Tests that curve kinds are read by their on-chain variant index.
+    #[test]
+    fn test_curve_kind_read() {
+        use cpmm::state::cp_amm::CurveKind as OnChainCurveKind;
+        let data = anchor_lang::AnchorSerialize::try_to_vec(&OnChainCurveKind::ConstantProduct).unwrap();
+        assert_eq!(CurveKind::read(&mut Reader::new(&data)), Ok(CurveKind::ConstantProduct));
+        assert_eq!(CurveKind::read(&mut Reader::new(&[1])), Err(ParseError::InvalidEnumVariant(1)));
+    }
+
+    /// Tests reading the entries of the display data, skipping unknown types.
+    #[test]
+    fn test_display_data_entries() {
+        let mut data = [0u8; 160];
+        data[..6].copy_from_slice(&[99, 0, 2, 0, 1, 2]);
+        data[6..18].copy_from_slice(&[4, 0, 4, 0, 25, 0, 5, 0, 6, 0, 200, 0]);
+        let display_data = DisplayData::read(&mut Reader::new(&data)).unwrap();
+        assert_eq!(display_data.entry(DisplayData::FEE_RATES), Some([25, 0, 5, 0].as_ref()));
+        // The truncated last entry ends the entries
+        assert_eq!(display_data.entries().count(), 2);
+        assert_eq!(display_data.entry(DisplayData::LAST_UPDATE_SLOT), None);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,46 @@
+//! Runs a deterministic `CpAmm` simulation and prints the CSV report to stdout.
+//!
+//! Usage: `cargo run -p cpmm --features sim --bin sim -- [--seed N] [--steps N]
+//! [--base N] [--quote N] [--providers-fee BPS] [--protocol-fee BPS] [--max-swap BPS]
+//! [--max-liquidity-change BPS] [--liquidity-operations BPS]`
+use std::io::{self, BufWriter};
+use std::process::ExitCode;
+use cpmm::sim::{run, SimConfig};
+
+fn parse_args(args: impl Iterator<Item = String>) -> Result<SimConfig, String> {
+    let mut config = SimConfig::default();
+    let mut args = args.peekable();
+    while let Some(flag) = args.next() {
+        let value = args.next().ok_or_else(|| format!("missing value for {}", flag))?;
+        let invalid = |_| format!("invalid value for {}: {}", flag, value);
+        match flag.as_str() {
+            "--seed" => config.seed = value.parse().map_err(invalid)?,
+            "--steps" => config.steps = value.parse().map_err(invalid)?,
+            "--base" => config.initial_base_liquidity = value.parse().map_err(invalid)?,
+            "--quote" => config.initial_quote_liquidity = value.parse().map_err(invalid)?,
+            "--providers-fee" => config.providers_fee_rate_basis_points = value.parse().map_err(invalid)?,
+            "--protocol-fee" => config.protocol_fee_rate_basis_points = value.parse().map_err(invalid)?,
+            "--max-swap" => config.max_swap_basis_points = value.parse().map_err(invalid)?,
+            "--max-liquidity-change" => config.max_liquidity_change_basis_points = value.parse().map_err(invalid)?,
+            "--liquidity-operations" => config.liquidity_operation_basis_points = value.parse().map_err(invalid)?,
+            _ => return Err(format!("unknown argument: {}", flag)),
+        }
+    }
+    Ok(config)
+}
+
+fn main() -> ExitCode {
+    let config = match parse_args(std::env::args().skip(1)) {
+        Ok(config) => config,
+        Err(error) => {
+            eprintln!("{}", error);
+            return ExitCode::FAILURE;
+        }
+    };
+    let mut output = BufWriter::new(io::stdout().lock());
+    if let Err(error) = run(&config, &mut output) {
+        eprintln!("simulation failed: {}", error);
+        return ExitCode::FAILURE;
+    }
+    ExitCode::SUCCESS
+}
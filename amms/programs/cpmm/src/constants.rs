@@ -1,3 +1,5 @@
 pub const ANCHOR_DISCRIMINATOR: usize = 8;
+pub const TEST_MINT_SEED: &[u8] = b"test_mint";
 
-pub const CP_AMM_INITIALIZE_PRICE_IN_LAMPORTS: u64 = 100_000_000;
\ No newline at end of file
+pub const CP_AMM_INITIALIZE_PRICE_IN_LAMPORTS: u64 = 100_000_000;
+pub const WP_AMM_INITIALIZE_PRICE_IN_LAMPORTS: u64 = 100_000_000;
\ No newline at end of file
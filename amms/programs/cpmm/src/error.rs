@@ -9,11 +9,145 @@ pub enum ErrorCode {
 
     #[msg("CpAmm vault owner mismatch: the vault is not owned by the expected program.")]
     InvalidCpAmmVaultOwner,
+
+    #[msg("CpAmm USD oracle account is missing or does not match the configured oracle.")]
+    InvalidCpAmmUsdOracle,
+
+    #[msg("CpAmm USD oracle owner mismatch: the oracle is not owned by the Pyth receiver program.")]
+    InvalidCpAmmUsdOracleOwner,
+
+    #[msg("Tokens of the CpAmm base, quote or LP mint and the CpAmm vaults can't be recovered.")]
+    RecoverPoolAssetMint,
+
+    #[msg("No stranded tokens to recover.")]
+    RecoverAmountIsZero,
+
+    #[msg("Token account is not owned by the provided owner or the signer is not its approved delegate.")]
+    InvalidDelegatedTokenAccount,
+
+    #[msg("Fee authority can't be the default address, an executable account or a token program account.")]
+    InvalidFeeAuthority,
+
+    #[msg("Account data layout version does not match the version expected by the program.")]
+    AccountVersionMismatch,
+
+    #[msg("Account already has the current data layout.")]
+    CpAmmLayoutUpToDate,
+
+    #[msg("Legacy instruction version is disabled in this deployment, use the latest version.")]
+    LegacyInstructionDisabled,
+
+    #[msg("Instruction is only available in devnet builds.")]
+    DevnetOnlyInstruction,
+
+    #[msg("Instruction is only available in builds with weighted pools enabled.")]
+    WpAmmBuildOnlyInstruction,
     
+    // AmmsConfigsManager
+    #[msg("Pool creation limit must allow at least one pool per window when the window is set.")]
+    ManagerPoolCreationLimitInvalid,
+
+    #[msg("Creator exceeded the number of pools allowed to be created within the current window.")]
+    PoolCreationRateLimitExceeded,
+
+    #[msg("Pool creation limiter account is required while the pool creation limit is active.")]
+    PoolCreationLimiterMissing,
+
+    #[msg("AmmsConfigsManager account already has the current data layout.")]
+    ManagerLayoutUpToDate,
+
+    #[msg("Swap origin policy exceeds the maximal number of slots.")]
+    SwapOriginPolicySlotsExceeded,
+
+    #[msg("Swaps from this origin are temporarily blocked by the swap origin policy.")]
+    SwapOriginRestricted,
+
     // AmmsConfig
     #[msg("The provided fee rate for AmmsConfig exceeds the maximum allowed value of 10000 basis points (100%).")]
     ConfigFeeRateExceeded,
 
+    #[msg("The provided fee bounds for AmmsConfig are invalid: the minimum exceeds the maximum or a maximum exceeds 10000 basis points (100%).")]
+    ConfigFeeBoundsInvalid,
+
+    #[msg("The fee rate is outside of the AmmsConfig fee bounds.")]
+    ConfigFeeRateOutOfBounds,
+
+    #[msg("The provided fee ramp duration for AmmsConfig is zero.")]
+    ConfigFeeRampDurationIsZero,
+
+    #[msg("The provided lend-out limit for AmmsConfig exceeds the maximum allowed value of 5000 basis points (50%).")]
+    ConfigLendOutLimitExceeded,
+
+    #[msg("The provided lend-out interest rate for AmmsConfig exceeds the maximum allowed value of 10000 basis points (100%).")]
+    ConfigLendOutInterestRateExceeded,
+
+    #[msg("The provided incident withdraw limit for AmmsConfig exceeds the maximum allowed value of 10000 basis points (100%).")]
+    ConfigIncidentWithdrawLimitExceeded,
+
+    #[msg("The provided keeper bounty for AmmsConfig exceeds the maximum allowed value of 1000 basis points (10%).")]
+    ConfigKeeperBountyExceeded,
+
+    #[msg("The provided protocol fees cap for AmmsConfig exceeds the maximum allowed value of 10000 basis points (100%).")]
+    ConfigProtocolFeesCapExceeded,
+
+    #[msg("The provided minimal mint decimals for AmmsConfig exceed the maximal mint decimals.")]
+    ConfigMintDecimalsRangeInvalid,
+
+    #[msg("Tradable mint decimals are outside of the range allowed by AmmsConfig.")]
+    MintDecimalsOutOfRange,
+
+    #[msg("Launch price of the CpAmm is outside of the bound allowed by AmmsConfig.")]
+    LaunchPriceOutOfBounds,
+
+    #[msg("AmmsConfig invariant tolerances must be within 100..=100000 parts per billion for swaps and 10..=10000 for liquidity adjustments.")]
+    ConfigTolerancesOutOfBounds,
+
+    #[msg("The provided fee settlement for AmmsConfig is invalid: the maximal price impact must be within 1..=10000 basis points.")]
+    ConfigFeeSettlementInvalid,
+
+    #[msg("The provided maximal creator fee share for AmmsConfig exceeds 5000 basis points.")]
+    ConfigCreatorFeeShareExceeded,
+
+    #[msg("The provided stale pool closer share for AmmsConfig exceeds 10000 basis points.")]
+    ConfigStalePoolCloserShareExceeded,
+
+    #[msg("The provided LP concentration share for AmmsConfig exceeds 10000 basis points.")]
+    ConfigLpConcentrationShareExceeded,
+
+    #[msg("The provided loyalty boost for AmmsConfig is invalid: the full boost duration must exceed the minimal one and the maximal multiplier must be within 10000..=30000 basis points.")]
+    ConfigLoyaltyBoostInvalid,
+
+    #[msg("The provided pool creation pricing for AmmsConfig is invalid: the maximal price must be within the base price and 100 SOL, and a step must set both its pools and price.")]
+    ConfigPoolCreationPricingInvalid,
+
+    #[msg("Fee-free protocol rebalancing is disabled in AmmsConfig.")]
+    ProtocolRebalanceDisabled,
+
+    #[msg("AmmsConfig account already has the current data layout.")]
+    ConfigLayoutUpToDate,
+
+    // PoolIndex
+    #[msg("The pool index bucket already holds the maximum number of pools.")]
+    PoolIndexBucketFull,
+
+    // MintEquivalences
+    #[msg("The mint equivalences already hold the maximum number of mints.")]
+    MintEquivalencesFull,
+
+    #[msg("Base and quote mints are configured as equivalent and can't form a pool.")]
+    EquivalentMintsPair,
+
+    #[msg("Mint equivalences of the AmmsConfig must be provided.")]
+    MintEquivalencesMissing,
+
+    // RouterRegistry
+    #[msg("The router registry already holds the maximum number of routers.")]
+    RouterRegistryFull,
+
+    // PoolCreatorPermit
+    #[msg("Pool creation in the AmmsConfig is allow-listed and requires the creator's PoolCreatorPermit.")]
+    PoolCreatorPermitMissing,
+
     // CpAmm state errors
     #[msg("Quote liquidity is zero.")]
     QuoteLiquidityIsZero,
@@ -42,6 +176,51 @@ pub enum ErrorCode {
     #[msg("CpAmm is already launched.")]
     CpAmmAlreadyLaunched,
 
+    #[msg("CpAmm status doesn't allow this transition.")]
+    CpAmmStatusTransitionInvalid,
+
+    #[msg("Closing of stale CpAmms is disabled in AmmsConfig.")]
+    StaleCpAmmCloseDisabled,
+
+    #[msg("CpAmm was initialized too recently to be closed as stale.")]
+    CpAmmNotStale,
+
+    #[msg("CpAmm vault holds tokens and the creator token account to sweep them to is missing.")]
+    StaleCpAmmSweepAccountMissing,
+
+    #[msg("CpAmm market is closed according to its trading schedule.")]
+    MarketClosed,
+
+    #[msg("CpAmm is in its provide-only warm-up and trading is not open yet.")]
+    TradingNotOpenYet,
+
+    #[msg("CpAmm warm-up exceeds the maximal number of slots.")]
+    WarmUpSlotsExceeded,
+
+    #[msg("CpAmm is in protected mode and only accepts swaps routed through an approved router.")]
+    ProtectedModeRouterRequired,
+
+    #[msg("CpAmm protected mode exceeds the maximal number of slots.")]
+    ProtectedSlotsExceeded,
+
+    #[msg("Swaps in the CpAmm are paused.")]
+    CpAmmSwapsPaused,
+
+    #[msg("Liquidity provisions to the CpAmm are paused.")]
+    CpAmmProvidesPaused,
+
+    #[msg("Liquidity withdrawals from the CpAmm are paused.")]
+    CpAmmWithdrawsPaused,
+
+    #[msg("Only the AmmsConfigsManager authorities can pause or resume CpAmm withdrawals.")]
+    WithdrawsPauseUnauthorized,
+
+    #[msg("Fee parameters of the CpAmm are locked.")]
+    ParametersLocked,
+
+    #[msg("Rent shortfall of the CpAmm accounts exceeds the maximal top-up.")]
+    RentTopUpExceeded,
+
     // CpAmm operations inputs errors
     #[msg("Provided quote liquidity is zero.")]
     ProvidedQuoteLiquidityIsZero,
@@ -49,15 +228,30 @@ pub enum ErrorCode {
     #[msg("Provided base liquidity is zero.")]
     ProvidedBaseLiquidityIsZero,
 
+    #[msg("Launch base-to-quote ratio deviates from the expected ratio beyond the tolerance.")]
+    LaunchRatioToleranceExceeded,
+
     #[msg("Provided liquidity tokens are zero.")]
     ProvidedLpTokensIsZero,
 
+    #[msg("Provided liquidity tokens exceed the liquidity tokens supply.")]
+    LpTokensExceedSupply,
+
     #[msg("Swap amount cannot be zero.")]
     SwapAmountIsZero,
 
     #[msg("Estimated swap result cannot be zero.")]
     EstimatedResultIsZero,
 
+    #[msg("Swap slippage must not exceed 10000 basis points.")]
+    InvalidSlippageBasisPoints,
+
+    #[msg("Swap deadline has passed.")]
+    SwapDeadlineExceeded,
+
+    #[msg("Liquidity position recipient must differ from the signer.")]
+    LiquidityPositionTransferToSelf,
+
     // CpAmm operations errors
     #[msg("Launch liquidity must be at least 4 times greater than the initial locked liquidity.")]
     LaunchLiquidityTooSmall,
@@ -71,6 +265,9 @@ pub enum ErrorCode {
     #[msg("Failed to calculate withdraw liquidity due to invalid input or overflow.")]
     WithdrawLiquidityCalculationFailed,
 
+    #[msg("Failed to calculate the virtual price due to overflow.")]
+    VirtualPriceCalculationFailed,
+
     #[msg("Swap result is zero.")]
     SwapResultIsZero,
     
@@ -79,6 +276,45 @@ pub enum ErrorCode {
     
     #[msg("Calculated slippage exceeds allowed tolerance.")]
     SwapSlippageExceeded,
+
+    #[msg("Tokens actually received from the swap are less than the required minimum.")]
+    SwapReceivedAmountBelowMinimum,
+
+    #[msg("Arbitrage between the CpAmms requires two distinct pools of the same pair of mints.")]
+    ArbPoolsMismatch,
+
+    #[msg("Arbitrage profit is less than the required minimum.")]
+    ArbProfitBelowMinimum,
+
+    #[msg("Split provide requires one non-zero weight per pool, for up to 4 pools.")]
+    ProvideSplitInvalidWeights,
+
+    #[msg("Split provide requires distinct launched CpAmms of the provided mints with their accounts.")]
+    ProvideSplitPoolsMismatch,
+
+    #[msg("Split provide does not support CpAmms with a liquidity subscriber.")]
+    ProvideSplitLiquiditySubscriberUnsupported,
+
+    #[msg("Split provide does not support AmmsConfigs with a screening program.")]
+    ProvideSplitScreeningUnsupported,
+
+    #[msg("Bulk quote requires one swap request per pool, for up to 10 pools.")]
+    QuoteManyInvalidRequests,
+
+    #[msg("Bulk quote requires CpAmms of the quoted mints, each preceded by its AmmsConfig.")]
+    QuoteManyPoolsMismatch,
+
+    #[msg("Withdrawals in the current slot exceed the incident rate limit.")]
+    WithdrawRateLimitExceeded,
+
+    #[msg("Liquidity tokens to mint are less than the minimal amount allowed per provide.")]
+    ProvideLpTokensBelowMinimum,
+
+    #[msg("Withdrawn liquidity tokens are less than the minimal amount allowed per withdraw.")]
+    WithdrawLpTokensBelowMinimum,
+
+    #[msg("Withdraw would leave the base or quote liquidity of the CpAmm below the minimum.")]
+    WithdrawLiquidityBelowMinimum,
     
     #[msg("Overflow error when providing liquidity.")]
     ProvideOverflowError,
@@ -89,6 +325,27 @@ pub enum ErrorCode {
     
     #[msg("Protocol fees to redeem is zero")]
     ProvidersFeesIsZero,
+
+    #[msg("Protocol fee rate of the CpAmm is zero, so there are no protocol fees to collect.")]
+    ProtocolFeeDisabled,
+
+    #[msg("Protocol fees of the CpAmm can't be settled within the AmmsConfig maximal price impact yet.")]
+    FeeSettlementImpactExceeded,
+
+    #[msg("Requested protocol fees exceed the protocol fees accrued by the CpAmm.")]
+    CollectFeesAmountExceeded,
+
+    #[msg("Protocol fees accrued by the CpAmm exceed the AmmsConfig cap and must be collected first.")]
+    ProtocolFeesCapExceeded,
+
+    #[msg("The provide would give a single LP more than the AmmsConfig share of the young CpAmm's LP tokens.")]
+    LpConcentrationExceeded,
+
+    #[msg("Creator fees to redeem is zero")]
+    CreatorFeesIsZero,
+
+    #[msg("Creator fee share of the CpAmm exceeds the maximum allowed by AmmsConfig.")]
+    CreatorFeeShareExceeded,
     
     // CpAmm integrity errors
     #[msg("Failed to calculate base-to-quote liquidity ratio due to invalid input or overflow.")]
@@ -100,8 +357,132 @@ pub enum ErrorCode {
     #[msg("Constant product tolerance exceeded.")]
     ConstantProductToleranceExceeded,
 
-    #[msg("Liquidity ratio tolerance exceeded.")]
-    LiquidityRatioToleranceExceeded,
+    #[msg("Liquidity ratio tolerance exceeded above the pool ratio, too much base liquidity.")]
+    LiquidityRatioAboveTolerance,
+
+    #[msg("Liquidity ratio tolerance exceeded below the pool ratio, too much quote liquidity.")]
+    LiquidityRatioBelowTolerance,
+
+    #[msg("Arithmetic overflow detected in strict math mode.")]
+    StrictMathOverflow,
+
+    #[msg("LP mint supply exceeds the CpAmm LP tokens supply.")]
+    LpTokensSupplyDesync,
+
+    #[msg("LP mint supply can't be used to resync the CpAmm LP tokens supply.")]
+    LpTokensSupplyResyncInvalid,
+
+    #[msg("CpAmm vault balance is below the recorded liquidity and fees.")]
+    VaultBalanceDrift,
+
+    #[msg("CpAmm vault balances cover the recorded liquidity and fees.")]
+    VaultBalanceNotDrifted,
+
+    // CpAmm lending errors
+    #[msg("Signer is not the lending adapter configured in AmmsConfig.")]
+    InvalidLendingAdapter,
+
+    #[msg("Lending out liquidity is disabled for the CpAmm.")]
+    CpAmmLendOutDisabled,
+
+    #[msg("Lend-out amount is zero.")]
+    LendOutAmountIsZero,
+
+    #[msg("Lent out liquidity would exceed the AmmsConfig lend-out limit.")]
+    LendOutLimitExceeded,
+
+    #[msg("Requested liquidity is lent out and not available in the vault.")]
+    LentOutLiquidityUnavailable,
+
+    #[msg("Lend-out loan is recalled and must be repaid before borrowing again.")]
+    LendOutLoanRecalled,
+
+    #[msg("Repaid amount exceeds the lend-out loan debt.")]
+    LendOutRepayExceedsDebt,
+
+    // CpAmm liquidity subscriber errors
+    #[msg("Liquidity subscriber must be an executable program other than the AMM program.")]
+    InvalidLiquiditySubscriber,
+
+    #[msg("Liquidity subscriber program account is missing from the remaining accounts.")]
+    LiquiditySubscriberMissing,
+
+    #[msg("Accounts forwarded to the liquidity subscriber must not include the CpAmm.")]
+    LiquiditySubscriberReentrancy,
+
+    // AmmsConfig screening errors
+    #[msg("Screening program must be an executable program other than the AMM program.")]
+    InvalidScreeningProgram,
+
+    #[msg("Screening program account is missing from the remaining accounts.")]
+    ScreeningProgramMissing,
+
+    #[msg("Accounts forwarded to the screening program must not include the CpAmm.")]
+    ScreeningProgramReentrancy,
+
+    // CpAmm checkpoints errors
+    #[msg("Fee checkpoint interval has not elapsed since the latest checkpoint.")]
+    FeeCheckpointTooEarly,
+
+    #[msg("Virtual price checkpoint interval has not elapsed since the latest checkpoint.")]
+    VirtualPriceCheckpointTooEarly,
+
+    // CpAmm pool stats shards errors
+    #[msg("Pool stats shard index must be lower than the number of shards.")]
+    InvalidPoolStatsShardIndex,
+
+    // CpAmm lookup table errors
+    #[msg("Lookup table must be an active address lookup table holding the pool's accounts.")]
+    LookupTableInvalid,
+
+    // CpAmm snapshots errors
+    #[msg("Snapshot must have a non-zero merkle root and holders, a total of LP tokens within the supply and a past slot.")]
+    SnapshotInvalid,
+
+    #[msg("Snapshot merkle proof is invalid.")]
+    SnapshotProofInvalid,
+
+    // CpAmm trading sessions errors
+    #[msg("Trading session must expire at a future slot and have a non-zero swap limit not exceeding its total volume limit.")]
+    TradingSessionInvalid,
+
+    #[msg("Trading session has expired.")]
+    TradingSessionExpired,
+
+    #[msg("Swap exceeds the quote amount allowed per swap by the trading session.")]
+    TradingSessionSwapLimitExceeded,
+
+    #[msg("Swap exceeds the remaining quote volume of the trading session.")]
+    TradingSessionVolumeLimitExceeded,
+
+    // Trader fee tiers errors
+    #[msg("Trader fee tiers must hold at most 8 tiers with strictly ascending volumes and discounts within 1..=5000 basis points.")]
+    TraderFeeTiersInvalid,
+
+    // WpAmm errors
+    #[msg("WpAmm token weights must be at least 500 basis points each and sum up to 10000 basis points.")]
+    WpAmmInvalidWeights,
+
+    #[msg("WpAmm is not launched.")]
+    WpAmmNotLaunched,
+
+    #[msg("WpAmm is not initialized.")]
+    WpAmmNotInitialized,
+
+    #[msg("WpAmm is already initialized.")]
+    WpAmmAlreadyInitialized,
+
+    #[msg("WpAmm is already launched.")]
+    WpAmmAlreadyLaunched,
+
+    #[msg("Swap input and output mints must be different tokens of the WpAmm.")]
+    WpAmmInvalidSwapMints,
+
+    #[msg("Failed to calculate WpAmm invariant due to invalid input or overflow.")]
+    WpAmmInvariantCalculationFailed,
+
+    #[msg("WpAmm invariant tolerance exceeded.")]
+    WpAmmInvariantToleranceExceeded,
     
     
     
@@ -115,12 +496,24 @@ pub enum ErrorCode {
     #[msg("Tradable mint for CpAmm hase unsupported token extension.")]
     UnsupportedMintTokenExtension,
 
+    #[msg("Provided LP mint must be empty, have the LP decimals and no authorities other than the signer or the CpAmm.")]
+    LpMintInvalid,
+
+    #[msg("LP mint supply must be zero at the CpAmm launch.")]
+    LaunchLpMintSupplyNotZero,
+
+    #[msg("LP mint freeze authority must be unset or the CpAmm at the CpAmm launch.")]
+    LaunchLpMintFreezeAuthorityInvalid,
+
     #[msg("Mint with TransferFee extension failed to calculate fee")]
     MintTransferFeeCalculationFailed,
     
     #[msg("Insufficient balance in the token account to complete the transfer.")]
     InsufficientBalanceForTransfer,
 
+    #[msg("Token account can't be closed while it holds tokens.")]
+    TokenAccountNotEmpty,
+
     #[msg("Mint and Token Program mismatch")]
     MintAndTokenProgramMismatch,
     
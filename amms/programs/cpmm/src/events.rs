@@ -0,0 +1,184 @@
+use anchor_lang::prelude::*;
+
+/// Emitted after a successful swap in a `CpAmm`.
+#[event]
+pub struct SwapEvent {
+    /// The swapped `CpAmm` account.
+    pub cp_amm: Pubkey,
+
+    /// The account performing the swap.
+    pub signer: Pubkey,
+
+    /// `true` for a base-to-quote swap, `false` for a quote-to-base swap.
+    pub is_in_out: bool,
+
+    /// The amount of input tokens received by the pool, after transfer fees.
+    pub swap_amount: u64,
+
+    /// The amount of output tokens sent from the pool.
+    pub swap_result: u64,
+
+    /// The change of the pool base-to-quote ratio caused by the swap, in basis points.
+    pub price_impact_basis_points: u64,
+
+    /// The providers fee rate applied to the swap, in basis points.
+    pub providers_fee_rate_basis_points: u16,
+
+    /// The protocol fee rate applied to the swap, in basis points.
+    pub protocol_fee_rate_basis_points: u16,
+
+    /// The providers fee added to the input side liquidity, net of the creator's share.
+    pub providers_fee_amount: u64,
+
+    /// The protocol fee accrued for the fee authority, in input tokens.
+    pub protocol_fee_amount: u64,
+
+    /// The creator's share of the providers fee accrued for the pool creator, in input tokens.
+    pub creator_fee_amount: u64,
+}
+
+/// Emitted with `SwapEvent` when a swap is discounted by a tier of the `AmmsConfig`'s `TraderFeeTiers`.
+#[event]
+pub struct TraderFeeTierAppliedEvent {
+    /// The swapped `CpAmm` account.
+    pub cp_amm: Pubkey,
+
+    /// The trader performing the swap.
+    pub trader: Pubkey,
+
+    /// The index of the applied tier in the `TraderFeeTiers`.
+    pub tier_index: u8,
+
+    /// The discount of the providers fee rate granted by the tier, in basis points of the rate.
+    pub discount_basis_points: u16,
+
+    /// The decayed volume of the trader before the swap, in USD.
+    pub volume_usd: u64,
+
+    /// The providers fee rate before the discount, in basis points.
+    pub undiscounted_providers_fee_rate_basis_points: u16,
+}
+
+/// Emitted after a fee-free protocol rebalancing swap in a `CpAmm`.
+#[event]
+pub struct RebalanceEvent {
+    /// The swapped `CpAmm` account.
+    pub cp_amm: Pubkey,
+
+    /// The fee authority performing the rebalancing.
+    pub fee_authority: Pubkey,
+
+    /// `true` for a base-to-quote swap, `false` for a quote-to-base swap.
+    pub is_in_out: bool,
+
+    /// The amount of input tokens received by the pool, after transfer fees.
+    pub swap_amount: u64,
+
+    /// The amount of output tokens sent from the pool.
+    pub swap_result: u64,
+
+    /// The change of the pool base-to-quote ratio caused by the swap, in basis points.
+    pub price_impact_basis_points: u64,
+
+    /// The providers fee rate applied to the swap, in basis points.
+    pub providers_fee_rate_basis_points: u16,
+
+    /// The protocol fee rate applied to the swap, in basis points.
+    pub protocol_fee_rate_basis_points: u16,
+
+    /// The providers fee added to the input side liquidity, net of the creator's share.
+    pub providers_fee_amount: u64,
+
+    /// The protocol fee accrued for the fee authority, in input tokens.
+    pub protocol_fee_amount: u64,
+
+    /// The creator's share of the providers fee accrued for the pool creator, in input tokens.
+    pub creator_fee_amount: u64,
+}
+
+/// Emitted when a fee rate is set to zero, making the affected swaps free of that fee.
+#[event]
+pub struct FeeRateDisabledEvent {
+    /// The `AmmsConfig` whose fee rate was zeroed, or of the `CpAmm` whose override was zeroed.
+    pub amms_config: Pubkey,
+
+    /// The `CpAmm` whose protocol fee override was set to zero, `None` for `AmmsConfig` rates.
+    pub cp_amm: Option<Pubkey>,
+
+    /// `true` for the protocol fee rate, `false` for the providers fee rate.
+    pub is_protocol_fee: bool,
+}
+
+/// Emitted after protocol fees are collected from a `CpAmm`.
+#[event]
+pub struct CollectFeesEvent {
+    /// The `CpAmm` account the fees are collected from.
+    pub cp_amm: Pubkey,
+
+    /// The fee authority receiving the fees.
+    pub fee_authority: Pubkey,
+
+    /// The account triggering the collection and receiving the keeper bounty.
+    pub signer: Pubkey,
+
+    /// The amount of base tokens sent to the fee authority, including settled fees.
+    pub base_fees_amount: u64,
+
+    /// The amount of quote tokens sent to the fee authority, including settled fees.
+    pub quote_fees_amount: u64,
+
+    /// The amount of base tokens paid to the signer as the keeper bounty.
+    pub base_keeper_bounty: u64,
+
+    /// The amount of quote tokens paid to the signer as the keeper bounty.
+    pub quote_keeper_bounty: u64,
+}
+
+/// Emitted instead of `CollectFeesEvent` when an idempotent collection finds no protocol fees in a `CpAmm`.
+#[event]
+pub struct NothingToCollectEvent {
+    /// The `CpAmm` account the fees were to be collected from.
+    pub cp_amm: Pubkey,
+
+    /// The account triggering the collection.
+    pub signer: Pubkey,
+}
+
+/// Emitted after the pool creator's fees are collected from a `CpAmm`.
+#[event]
+pub struct CollectCreatorFeesEvent {
+    /// The `CpAmm` account the fees are collected from.
+    pub cp_amm: Pubkey,
+
+    /// The pool creator receiving the fees.
+    pub creator: Pubkey,
+
+    /// The amount of base tokens sent to the creator.
+    pub base_fees_amount: u64,
+
+    /// The amount of quote tokens sent to the creator.
+    pub quote_fees_amount: u64,
+}
+
+/// Emitted when `report_vault_drift` finds a `CpAmm` vault holding less than the recorded liquidity and fees,
+/// after suspending the swaps and provides of the pool.
+#[event]
+pub struct VaultBalanceDriftEvent {
+    /// The frozen `CpAmm` account.
+    pub cp_amm: Pubkey,
+
+    /// The account that reported the drift.
+    pub signer: Pubkey,
+
+    /// The balance of the base vault.
+    pub base_vault_balance: u64,
+
+    /// The balance of the quote vault.
+    pub quote_vault_balance: u64,
+
+    /// The base balance recorded by the pool: the liquidity not lent out and the fees to redeem.
+    pub recorded_base_balance: u128,
+
+    /// The quote balance recorded by the pool: the liquidity not lent out and the fees to redeem.
+    pub recorded_quote_balance: u128,
+}
\ No newline at end of file
@@ -0,0 +1,584 @@
+use anchor_lang::prelude::*;
+use proptest::prelude::*;
+use proptest::sample::Index;
+use crate::instructions::*;
+use super::{validator, InstructionSpec, Mutation, MutationNoise, PoolFixture, TestAccount, ABSENT};
+use super::Mutation::*;
+
+/// Mutations rejected for a mint only checked to be a mint.
+const MINT: &[Mutation] = &[Owner, Data];
+
+/// Mutations rejected for a program.
+const PROGRAM: &[Mutation] = &[Address, Substitute("signer")];
+
+/// The authority of the `AmmsConfigsManager`, rejected for anyone else.
+const AUTHORITY: (&str, &[Mutation]) = ("authority", &[Signer, Writable, Substitute("signer")]);
+
+/// The head authority of the `AmmsConfigsManager`, rejected for the authority.
+const HEAD_AUTHORITY: (&str, &[Mutation]) = ("head_authority", &[Signer, Writable, Substitute("authority")]);
+
+/// The creator of the pool, rejected for anyone else.
+const CREATOR: (&str, &[Mutation]) = ("signer", &[Signer, Substitute("keeper")]);
+
+/// The `AmmsConfigsManager` read by the admin instructions.
+const AMMS_CONFIGS_MANAGER: (&str, &[Mutation]) = ("amms_configs_manager", &[Owner, Address, Data, Substitute("amms_config")]);
+
+/// The `AmmsConfig` updated by an admin instruction.
+const UPDATED_AMMS_CONFIG: (&str, &[Mutation]) = ("amms_config", &[Owner, Address, Data, Writable, Substitute("cp_amm")]);
+
+/// The `CpAmm` updated by an admin instruction.
+const UPDATED_CP_AMM: (&str, &[Mutation]) = ("cp_amm", &[Owner, Address, Data, Writable, Substitute("amms_config")]);
+
+/// Returns the constraint matrix of an admin instruction updating an `AmmsConfig`.
+macro_rules! amms_config_update {
+    ($name:literal, $accounts:ident, $authority:ident) => {
+        InstructionSpec { name: $name, validate: validator!($accounts), accounts: &[$authority, AMMS_CONFIGS_MANAGER, UPDATED_AMMS_CONFIG] }
+    };
+}
+
+/// Returns the constraint matrix of an admin instruction updating a `CpAmm`.
+macro_rules! cp_amm_update {
+    ($name:literal, $accounts:ident) => {
+        InstructionSpec { name: $name, validate: validator!($accounts), accounts: &[AUTHORITY, AMMS_CONFIGS_MANAGER, UPDATED_CP_AMM] }
+    };
+}
+
+const SWAP_IN_CP_AMM: InstructionSpec = InstructionSpec {
+    name: "swap_in_cp_amm",
+    validate: validator!(SwapInCpAmm),
+    accounts: &[
+        ("signer", &[Signer, Writable]),
+        ("base_mint", &[Owner, Address, Data, Substitute("quote_mint")]),
+        ("quote_mint", &[Owner, Address, Data, Substitute("base_mint")]),
+        ("signer_base_account", &[Owner, Address, Data, Substitute("signer_quote_account")]),
+        ("signer_quote_account", &[Owner, Address, Data, Substitute("signer_base_account")]),
+        ("amms_config", &[Owner, Address, Data, Substitute("other_amms_config")]),
+        ("cp_amm", &[Owner, Address, Data, Writable]),
+        ("cp_amm_base_vault", &[Owner, Address, Data, Writable, Substitute("cp_amm_quote_vault")]),
+        ("cp_amm_quote_vault", &[Owner, Address, Data, Writable, Substitute("cp_amm_base_vault")]),
+        (ABSENT, &[Substitute("signer")]),
+        ("associated_token_program", PROGRAM),
+        ("token_program", PROGRAM),
+        ("token_program", PROGRAM),
+        ("system_program", PROGRAM),
+        (ABSENT, &[Substitute("amms_config")]),
+        (ABSENT, &[Substitute("cp_amm")]),
+        (ABSENT, &[Substitute("amms_config")]),
+        (ABSENT, &[Substitute("cp_amm")]),
+        (ABSENT, &[Substitute("amms_config")]),
+        (ABSENT, &[Substitute("cp_amm")]),
+    ],
+};
+
+const PROVIDE_TO_CP_AMM: InstructionSpec = InstructionSpec {
+    name: "provide_to_cp_amm",
+    validate: validator!(ProvideToCpAmm),
+    accounts: &[
+        ("signer", &[Signer, Writable]),
+        ("base_mint", &[Owner, Address, Data, Substitute("quote_mint")]),
+        ("quote_mint", &[Owner, Address, Data, Substitute("base_mint")]),
+        ("lp_mint", &[Owner, Address, Data, Writable, Substitute("base_mint")]),
+        // The token program checks the mints and authorities of the source accounts
+        ("signer_base_account", &[Owner, Data, Writable]),
+        ("signer_quote_account", &[Owner, Data, Writable]),
+        ("signer_lp_account", &[Owner, Address, Data, Substitute("signer_base_account")]),
+        ("amms_config", &[Owner, Address, Data, Substitute("other_amms_config")]),
+        ("cp_amm", &[Owner, Address, Data, Writable]),
+        ("cp_amm_base_vault", &[Owner, Address, Data, Writable, Substitute("cp_amm_quote_vault")]),
+        ("cp_amm_quote_vault", &[Owner, Address, Data, Writable, Substitute("cp_amm_base_vault")]),
+        ("associated_token_program", PROGRAM),
+        ("token_program", PROGRAM),
+        ("token_program", PROGRAM),
+        ("token_program", PROGRAM),
+        ("system_program", PROGRAM),
+        (ABSENT, &[Substitute("amms_config")]),
+        (ABSENT, &[Substitute("cp_amm")]),
+    ],
+};
+
+const WITHDRAW_FROM_CP_AMM: InstructionSpec = InstructionSpec {
+    name: "withdraw_from_cp_amm",
+    validate: validator!(WithdrawFromCpAmm),
+    accounts: &[
+        ("signer", &[Signer, Writable]),
+        ("base_mint", &[Owner, Address, Data, Substitute("quote_mint")]),
+        ("quote_mint", &[Owner, Address, Data, Substitute("base_mint")]),
+        ("lp_mint", &[Owner, Address, Data, Writable, Substitute("base_mint")]),
+        ("signer_base_account", &[Owner, Address, Data, Substitute("signer_quote_account")]),
+        ("signer_quote_account", &[Owner, Address, Data, Substitute("signer_base_account")]),
+        // The token program checks the mint and authority of the burned LP tokens account
+        ("signer_lp_account", &[Owner, Data, Writable]),
+        ("amms_config", &[Owner, Address, Data, Substitute("other_amms_config")]),
+        ("cp_amm", &[Owner, Address, Data, Writable]),
+        ("cp_amm_base_vault", &[Owner, Address, Data, Writable, Substitute("cp_amm_quote_vault")]),
+        ("cp_amm_quote_vault", &[Owner, Address, Data, Writable, Substitute("cp_amm_base_vault")]),
+        ("associated_token_program", PROGRAM),
+        ("token_program", PROGRAM),
+        ("token_program", PROGRAM),
+        ("token_program", PROGRAM),
+        ("system_program", PROGRAM),
+        (ABSENT, &[Substitute("amms_config")]),
+        (ABSENT, &[Substitute("cp_amm")]),
+    ],
+};
+
+const QUOTE_GROSS_SWAP_INPUT: InstructionSpec = InstructionSpec {
+    name: "quote_gross_swap_input",
+    validate: validator!(QuoteGrossSwapInput),
+    accounts: &[
+        ("base_mint", &[Owner, Address, Data, Substitute("quote_mint")]),
+        ("quote_mint", &[Owner, Address, Data, Substitute("base_mint")]),
+        ("cp_amm", &[Owner, Address, Data]),
+    ],
+};
+
+const GET_LP_VALUE: InstructionSpec = InstructionSpec {
+    name: "get_lp_value",
+    validate: validator!(GetLpValue),
+    accounts: &[
+        ("cp_amm", &[Owner, Address, Data, Substitute("amms_config")]),
+    ],
+};
+
+const ENSURE_RENT_EXEMPT: InstructionSpec = InstructionSpec {
+    name: "ensure_rent_exempt",
+    validate: validator!(EnsureRentExempt),
+    accounts: &[
+        ("signer", &[Signer, Writable]),
+        ("cp_amm", &[Owner, Address, Data, Writable]),
+        ("cp_amm_base_vault", &[Owner, Address, Data, Writable, Substitute("cp_amm_quote_vault")]),
+        ("cp_amm_quote_vault", &[Owner, Address, Data, Writable, Substitute("cp_amm_locked_lp_vault")]),
+        ("cp_amm_locked_lp_vault", &[Owner, Address, Data, Writable, Substitute("cp_amm_base_vault")]),
+        ("system_program", PROGRAM),
+    ],
+};
+
+const QUOTE_MANY: InstructionSpec = InstructionSpec {
+    name: "quote_many",
+    validate: validator!(QuoteMany),
+    accounts: &[
+        // The mints are matched against the pools passed in the remaining accounts by the handler
+        ("base_mint", MINT),
+        ("quote_mint", MINT),
+    ],
+};
+
+const SWAP_IN_CP_AMM_WITH_SESSION: InstructionSpec = InstructionSpec {
+    name: "swap_in_cp_amm_with_session",
+    validate: validator!(SwapInCpAmmWithSession),
+    accounts: &[
+        ("session_key", &[Signer, Substitute("manager")]),
+        ("owner", &[Address, Substitute("signer")]),
+        ("base_mint", &[Owner, Address, Data, Substitute("quote_mint")]),
+        ("quote_mint", &[Owner, Address, Data, Substitute("base_mint")]),
+        ("owner_base_account", &[Owner, Address, Data, Writable, Substitute("signer_base_account")]),
+        ("owner_quote_account", &[Owner, Address, Data, Writable, Substitute("owner_base_account")]),
+        ("amms_config", &[Owner, Address, Data, Substitute("other_amms_config")]),
+        ("cp_amm", &[Owner, Address, Data, Writable, Substitute("second_cp_amm")]),
+        ("trading_session", &[Owner, Address, Data, Writable, Substitute("owner_liquidity_position")]),
+        ("cp_amm_base_vault", &[Owner, Address, Data, Writable, Substitute("cp_amm_quote_vault")]),
+        ("cp_amm_quote_vault", &[Owner, Address, Data, Writable, Substitute("cp_amm_base_vault")]),
+        (ABSENT, &[Substitute("signer")]),
+        ("token_program", PROGRAM),
+        ("token_program", PROGRAM),
+        (ABSENT, &[Substitute("amms_config")]),
+        (ABSENT, &[Substitute("cp_amm")]),
+        (ABSENT, &[Substitute("cp_amm")]),
+        (ABSENT, &[Substitute("amms_config")]),
+        (ABSENT, &[Substitute("cp_amm")]),
+        (ABSENT, &[Substitute("amms_config")]),
+    ],
+};
+
+const ARB_TWO_POOLS: InstructionSpec = InstructionSpec {
+    name: "arb_two_pools",
+    validate: validator!(ArbTwoPools),
+    accounts: &[
+        ("signer", &[Signer, Writable]),
+        ("base_mint", &[Owner, Address, Data, Substitute("quote_mint"), Substitute("stranded_mint")]),
+        ("quote_mint", &[Owner, Address, Data, Substitute("base_mint"), Substitute("stranded_mint")]),
+        ("signer_base_account", &[Owner, Address, Data, Writable, Substitute("signer_quote_account")]),
+        ("signer_quote_account", &[Owner, Address, Data, Substitute("signer_base_account")]),
+        ("amms_config", &[Owner, Address, Data, Substitute("other_amms_config")]),
+        ("cp_amm", &[Owner, Address, Data, Writable, Substitute("second_cp_amm")]),
+        ("cp_amm_base_vault", &[Owner, Address, Data, Writable, Substitute("second_cp_amm_base_vault")]),
+        ("cp_amm_quote_vault", &[Owner, Address, Data, Writable, Substitute("second_cp_amm_quote_vault")]),
+        ("other_amms_config", &[Owner, Address, Data, Substitute("amms_config")]),
+        ("second_cp_amm", &[Owner, Address, Data, Writable, Substitute("cp_amm")]),
+        ("second_cp_amm_base_vault", &[Owner, Address, Data, Writable, Substitute("cp_amm_base_vault")]),
+        ("second_cp_amm_quote_vault", &[Owner, Address, Data, Writable, Substitute("cp_amm_quote_vault")]),
+        (ABSENT, &[Substitute("signer")]),
+        (ABSENT, &[Substitute("signer")]),
+        ("associated_token_program", PROGRAM),
+        ("token_program", PROGRAM),
+        ("token_program", PROGRAM),
+        ("system_program", PROGRAM),
+        (ABSENT, &[Substitute("amms_config")]),
+        (ABSENT, &[Substitute("cp_amm")]),
+        (ABSENT, &[Substitute("second_cp_amm")]),
+    ],
+};
+
+const REBALANCE_IN_CP_AMM: InstructionSpec = InstructionSpec {
+    name: "rebalance_in_cp_amm",
+    validate: validator!(RebalanceInCpAmm),
+    accounts: &[
+        ("fee_authority", &[Signer, Writable, Substitute("signer")]),
+        ("base_mint", &[Owner, Address, Data, Substitute("quote_mint")]),
+        ("quote_mint", &[Owner, Address, Data, Substitute("base_mint")]),
+        ("fee_authority_base_account", &[Owner, Address, Data, Substitute("signer_base_account")]),
+        ("fee_authority_quote_account", &[Owner, Address, Data, Substitute("fee_authority_base_account")]),
+        ("amms_config", &[Owner, Address, Data, Substitute("other_amms_config")]),
+        ("cp_amm", &[Owner, Address, Data, Writable, Substitute("second_cp_amm")]),
+        ("cp_amm_base_vault", &[Owner, Address, Data, Writable, Substitute("cp_amm_quote_vault")]),
+        ("cp_amm_quote_vault", &[Owner, Address, Data, Writable, Substitute("cp_amm_base_vault")]),
+        (ABSENT, &[Substitute("signer")]),
+        ("associated_token_program", PROGRAM),
+        ("token_program", PROGRAM),
+        ("token_program", PROGRAM),
+        ("system_program", PROGRAM),
+        (ABSENT, &[Substitute("amms_config")]),
+        (ABSENT, &[Substitute("cp_amm")]),
+    ],
+};
+
+const PROVIDE_TO_CP_AMM_AS_DELEGATE: InstructionSpec = InstructionSpec {
+    name: "provide_to_cp_amm_as_delegate",
+    validate: validator!(ProvideToCpAmmAsDelegate),
+    accounts: &[
+        ("manager", &[Signer, Writable, Substitute("signer")]),
+        ("owner", &[Address, Substitute("signer")]),
+        ("base_mint", &[Owner, Address, Data, Substitute("quote_mint")]),
+        ("quote_mint", &[Owner, Address, Data, Substitute("base_mint")]),
+        ("lp_mint", &[Owner, Address, Data, Writable, Substitute("second_lp_mint")]),
+        // The token program checks the mints and delegated amounts of the source accounts
+        ("owner_base_account", &[Owner, Data, Writable, Substitute("signer_base_account")]),
+        ("owner_quote_account", &[Owner, Data, Writable, Substitute("signer_quote_account")]),
+        ("owner_lp_account", &[Owner, Address, Data, Substitute("signer_lp_account")]),
+        ("amms_config", &[Owner, Address, Data, Substitute("other_amms_config")]),
+        ("cp_amm", &[Owner, Address, Data, Writable, Substitute("second_cp_amm")]),
+        ("cp_amm_base_vault", &[Owner, Address, Data, Writable, Substitute("cp_amm_quote_vault")]),
+        ("cp_amm_quote_vault", &[Owner, Address, Data, Writable, Substitute("cp_amm_base_vault")]),
+        ("associated_token_program", PROGRAM),
+        ("token_program", PROGRAM),
+        ("token_program", PROGRAM),
+        ("token_program", PROGRAM),
+        ("system_program", PROGRAM),
+        ("owner_liquidity_position", &[Owner, Address, Data, Writable, Substitute("signer_liquidity_position")]),
+        (ABSENT, &[Substitute("cp_amm")]),
+    ],
+};
+
+const WITHDRAW_FROM_CP_AMM_AS_DELEGATE: InstructionSpec = InstructionSpec {
+    name: "withdraw_from_cp_amm_as_delegate",
+    validate: validator!(WithdrawFromCpAmmAsDelegate),
+    accounts: &[
+        ("manager", &[Signer, Writable, Substitute("signer")]),
+        ("owner", &[Address, Substitute("signer")]),
+        ("base_mint", &[Owner, Address, Data, Substitute("quote_mint")]),
+        ("quote_mint", &[Owner, Address, Data, Substitute("base_mint")]),
+        ("lp_mint", &[Owner, Address, Data, Writable, Substitute("second_lp_mint")]),
+        ("owner_base_account", &[Owner, Address, Data, Substitute("signer_base_account")]),
+        ("owner_quote_account", &[Owner, Address, Data, Substitute("owner_base_account")]),
+        // The token program checks the mint and delegated amount of the burned LP tokens account
+        ("owner_lp_account", &[Owner, Data, Writable, Substitute("signer_lp_account")]),
+        ("amms_config", &[Owner, Address, Data, Substitute("other_amms_config")]),
+        ("cp_amm", &[Owner, Address, Data, Writable, Substitute("second_cp_amm")]),
+        ("cp_amm_base_vault", &[Owner, Address, Data, Writable, Substitute("cp_amm_quote_vault")]),
+        ("cp_amm_quote_vault", &[Owner, Address, Data, Writable, Substitute("cp_amm_base_vault")]),
+        ("associated_token_program", PROGRAM),
+        ("token_program", PROGRAM),
+        ("token_program", PROGRAM),
+        ("token_program", PROGRAM),
+        ("system_program", PROGRAM),
+        ("owner_liquidity_position", &[Owner, Address, Data, Writable, Substitute("signer_liquidity_position")]),
+        (ABSENT, &[Substitute("cp_amm")]),
+    ],
+};
+
+const COLLECT_FEES_FROM_CP_AMM: InstructionSpec = InstructionSpec {
+    name: "collect_fees_from_cp_amm",
+    validate: validator!(CollectFeesFromCpAmm),
+    accounts: &[
+        ("signer", &[Signer, Writable]),
+        ("fee_authority", &[Address, Substitute("signer")]),
+        ("base_mint", &[Owner, Address, Data, Substitute("quote_mint")]),
+        ("quote_mint", &[Owner, Address, Data, Substitute("base_mint")]),
+        ("fee_authority_base_account", &[Owner, Address, Data, Substitute("signer_base_account")]),
+        ("fee_authority_quote_account", &[Owner, Address, Data, Substitute("fee_authority_base_account")]),
+        ("signer_base_account", &[Owner, Address, Data, Substitute("fee_authority_base_account")]),
+        ("signer_quote_account", &[Owner, Address, Data, Substitute("signer_base_account")]),
+        ("amms_config", &[Owner, Address, Data, Substitute("other_amms_config")]),
+        ("cp_amm", &[Owner, Address, Data, Writable, Substitute("second_cp_amm")]),
+        ("cp_amm_base_vault", &[Owner, Address, Data, Writable, Substitute("cp_amm_quote_vault")]),
+        ("cp_amm_quote_vault", &[Owner, Address, Data, Writable, Substitute("cp_amm_base_vault")]),
+        ("associated_token_program", PROGRAM),
+        ("token_program", PROGRAM),
+        ("token_program", PROGRAM),
+        ("system_program", PROGRAM),
+        (ABSENT, &[Substitute("cp_amm")]),
+    ],
+};
+
+const BORROW_FROM_CP_AMM: InstructionSpec = InstructionSpec {
+    name: "borrow_from_cp_amm",
+    validate: validator!(BorrowFromCpAmm),
+    accounts: &[
+        ("lending_adapter", &[Signer, Writable, Substitute("signer")]),
+        ("base_mint", &[Owner, Address, Data, Substitute("quote_mint")]),
+        // The adapter may receive the borrowed tokens in any account of the mint
+        ("lending_adapter_account", &[Owner, Data, Writable, Substitute("signer_quote_account")]),
+        ("amms_config", &[Owner, Address, Data, Substitute("other_amms_config")]),
+        ("cp_amm", &[Owner, Address, Data, Writable, Substitute("second_cp_amm")]),
+        ("cp_amm_base_vault", &[Owner, Address, Data, Writable, Substitute("cp_amm_quote_vault")]),
+        ("lend_out_loan", &[Owner, Address, Data, Substitute("cp_amm")]),
+        ("token_program", PROGRAM),
+        ("system_program", PROGRAM),
+    ],
+};
+
+const REPAY_TO_CP_AMM: InstructionSpec = InstructionSpec {
+    name: "repay_to_cp_amm",
+    validate: validator!(RepayToCpAmm),
+    accounts: &[
+        ("signer", &[Signer, Substitute("lending_adapter")]),
+        ("base_mint", &[Owner, Address, Data, Substitute("quote_mint")]),
+        ("signer_base_account", &[Owner, Data, Writable, Substitute("signer_quote_account"), Substitute("owner_base_account")]),
+        ("amms_config", &[Owner, Address, Data, Substitute("other_amms_config")]),
+        ("cp_amm", &[Owner, Address, Data, Writable, Substitute("second_cp_amm")]),
+        ("cp_amm_base_vault", &[Owner, Address, Data, Writable, Substitute("cp_amm_quote_vault")]),
+        ("lend_out_loan", &[Owner, Address, Data, Writable, Substitute("cp_amm")]),
+        ("token_program", PROGRAM),
+        (ABSENT, &[Substitute("cp_amm")]),
+    ],
+};
+
+const TRANSFER_LP_POSITION: InstructionSpec = InstructionSpec {
+    name: "transfer_lp_position",
+    validate: validator!(TransferLpPosition),
+    accounts: &[
+        ("signer", &[Signer, Writable]),
+        ("owner", &[Address, Substitute("signer")]),
+        ("cp_amm", &[Owner, Address, Data, Substitute("second_cp_amm")]),
+        ("lp_mint", &[Owner, Address, Data, Substitute("second_lp_mint")]),
+        // The token program checks the authority of the transferred LP tokens account
+        ("signer_lp_account", &[Owner, Data, Writable, Substitute("signer_base_account")]),
+        ("owner_lp_account", &[Owner, Data, Writable, Substitute("signer_lp_account")]),
+        ("signer_liquidity_position", &[Owner, Address, Data, Writable, Substitute("owner_liquidity_position")]),
+        ("owner_liquidity_position", &[Owner, Address, Data, Substitute("signer_liquidity_position")]),
+        ("token_program", PROGRAM),
+        ("system_program", PROGRAM),
+    ],
+};
+
+const CLOSE_STALE_CP_AMM: InstructionSpec = InstructionSpec {
+    name: "close_stale_cp_amm",
+    validate: validator!(CloseStaleCpAmm),
+    accounts: &[
+        ("keeper", &[Signer, Writable]),
+        // The pool creator receives the refunded rent
+        ("signer", &[Address, Writable, Substitute("keeper")]),
+        ("amms_config", &[Owner, Address, Data, Substitute("other_amms_config")]),
+        ("cp_amm", &[Owner, Address, Data, Writable, Substitute("second_cp_amm")]),
+        ("cp_amm_base_vault", &[Owner, Address, Data, Writable, Substitute("cp_amm_quote_vault")]),
+        ("cp_amm_quote_vault", &[Owner, Address, Data, Writable, Substitute("cp_amm_locked_lp_vault")]),
+        ("cp_amm_locked_lp_vault", &[Owner, Address, Data, Writable, Substitute("cp_amm_base_vault")]),
+        ("token_program", PROGRAM),
+        ("token_program", PROGRAM),
+        ("token_program", PROGRAM),
+        ("base_mint", &[Owner, Address, Data, Substitute("quote_mint")]),
+        ("quote_mint", &[Owner, Address, Data, Substitute("base_mint")]),
+        ("signer_base_account", &[Owner, Address, Data, Writable, Substitute("signer_quote_account")]),
+        ("signer_quote_account", &[Owner, Address, Data, Writable, Substitute("owner_quote_account")]),
+    ],
+};
+
+const RECOVER_STRANDED_TOKENS: InstructionSpec = InstructionSpec {
+    name: "recover_stranded_tokens",
+    validate: validator!(RecoverStrandedTokens),
+    accounts: &[
+        AUTHORITY,
+        AMMS_CONFIGS_MANAGER,
+        ("cp_amm", &[Owner, Address, Data, Substitute("second_cp_amm")]),
+        ("stranded_mint", &[Owner, Address, Data, Substitute("base_mint"), Substitute("lp_mint")]),
+        // The stranded tokens may sit in any account of the pool, except its vaults
+        ("stranded_account", &[Owner, Data, Writable, Substitute("cp_amm_base_vault"), Substitute("recipient_account")]),
+        ("recipient_account", &[Owner, Data, Writable, Substitute("signer_base_account")]),
+        ("token_program", PROGRAM),
+    ],
+};
+
+/// The instructions covered by the constraint matrices.
+const INSTRUCTIONS: &[InstructionSpec] = &[
+    SWAP_IN_CP_AMM,
+    PROVIDE_TO_CP_AMM,
+    WITHDRAW_FROM_CP_AMM,
+    QUOTE_GROSS_SWAP_INPUT,
+    GET_LP_VALUE,
+    ENSURE_RENT_EXEMPT,
+    QUOTE_MANY,
+    SWAP_IN_CP_AMM_WITH_SESSION,
+    ARB_TWO_POOLS,
+    REBALANCE_IN_CP_AMM,
+    PROVIDE_TO_CP_AMM_AS_DELEGATE,
+    WITHDRAW_FROM_CP_AMM_AS_DELEGATE,
+    COLLECT_FEES_FROM_CP_AMM,
+    BORROW_FROM_CP_AMM,
+    REPAY_TO_CP_AMM,
+    TRANSFER_LP_POSITION,
+    CLOSE_STALE_CP_AMM,
+    amms_config_update!("update_amms_config_creator_fee_share_limit", UpdateAmmsConfigCreatorFeeShareLimit, AUTHORITY),
+    amms_config_update!("update_amms_config_fee_settlement", UpdateAmmsConfigFeeSettlement, AUTHORITY),
+    amms_config_update!("update_amms_config_incident_withdraw_limit", UpdateAmmsConfigIncidentWithdrawLimit, AUTHORITY),
+    amms_config_update!("update_amms_config_keeper_bounty", UpdateAmmsConfigKeeperBounty, AUTHORITY),
+    amms_config_update!("update_amms_config_launch_price_bound", UpdateAmmsConfigLaunchPriceBound, AUTHORITY),
+    amms_config_update!("update_amms_config_loyalty_boost", UpdateAmmsConfigLoyaltyBoost, AUTHORITY),
+    amms_config_update!("update_amms_config_lp_concentration", UpdateAmmsConfigLpConcentration, AUTHORITY),
+    amms_config_update!("update_amms_config_lp_tokens_limits", UpdateAmmsConfigLpTokensLimits, AUTHORITY),
+    amms_config_update!("update_amms_config_mint_decimals_range", UpdateAmmsConfigMintDecimalsRange, AUTHORITY),
+    amms_config_update!("update_amms_config_pool_creation_allow_list", UpdateAmmsConfigPoolCreationAllowList, AUTHORITY),
+    amms_config_update!("update_amms_config_pool_creation_pricing", UpdateAmmsConfigPoolCreationPricing, AUTHORITY),
+    amms_config_update!("update_amms_config_protocol_fee_rate", UpdateAmmsConfigProtocolFeeRate, AUTHORITY),
+    amms_config_update!("update_amms_config_protocol_fees_cap", UpdateAmmsConfigProtocolFeesCap, AUTHORITY),
+    amms_config_update!("update_amms_config_protocol_rebalance", UpdateAmmsConfigProtocolRebalance, AUTHORITY),
+    amms_config_update!("update_amms_config_providers_fee_rate", UpdateAmmsConfigProvidersFeeRate, AUTHORITY),
+    amms_config_update!("update_amms_config_stale_pool_close", UpdateAmmsConfigStalePoolClose, AUTHORITY),
+    amms_config_update!("update_amms_config_tolerances", UpdateAmmsConfigTolerances, AUTHORITY),
+    amms_config_update!("schedule_amms_config_providers_fee_ramp", ScheduleAmmsConfigProvidersFeeRamp, AUTHORITY),
+    amms_config_update!("update_amms_config_fee_bounds", UpdateAmmsConfigFeeBounds, HEAD_AUTHORITY),
+    amms_config_update!("update_amms_config_lending", UpdateAmmsConfigLending, HEAD_AUTHORITY),
+    InstructionSpec {
+        name: "update_amms_config_fee_authority",
+        validate: validator!(UpdateAmmsConfigFeeAuthority),
+        // The handler validates the new fee authority
+        accounts: &[AUTHORITY, AMMS_CONFIGS_MANAGER, UPDATED_AMMS_CONFIG, ("owner", &[])],
+    },
+    InstructionSpec {
+        name: "update_amms_config_screening_program",
+        validate: validator!(UpdateAmmsConfigScreeningProgram),
+        accounts: &[HEAD_AUTHORITY, AMMS_CONFIGS_MANAGER, UPDATED_AMMS_CONFIG, (ABSENT, &[Substitute("signer")])],
+    },
+    InstructionSpec {
+        name: "update_amms_configs_manager_authority",
+        validate: validator!(UpdateAmmsConfigsManagerAuthority),
+        accounts: &[AUTHORITY, ("amms_configs_manager", &[Owner, Address, Data, Writable, Substitute("amms_config")]), ("owner", &[])],
+    },
+    InstructionSpec {
+        name: "update_amms_configs_manager_head_authority",
+        validate: validator!(UpdateAmmsConfigsManagerHeadAuthority),
+        accounts: &[HEAD_AUTHORITY, ("amms_configs_manager", &[Owner, Address, Data, Writable, Substitute("amms_config")]), ("owner", &[])],
+    },
+    InstructionSpec {
+        name: "update_amms_configs_manager_pool_creation_limit",
+        validate: validator!(UpdateAmmsConfigsManagerPoolCreationLimit),
+        accounts: &[AUTHORITY, ("amms_configs_manager", &[Owner, Address, Data, Writable, Substitute("amms_config")])],
+    },
+    InstructionSpec {
+        name: "update_amms_configs_manager_swap_origin_policy",
+        validate: validator!(UpdateAmmsConfigsManagerSwapOriginPolicy),
+        accounts: &[("head_authority", &[Signer, Substitute("authority")]), AMMS_CONFIGS_MANAGER],
+    },
+    InstructionSpec {
+        name: "update_router_registry",
+        validate: validator!(UpdateRouterRegistry),
+        accounts: &[
+            ("head_authority", &[Signer, Substitute("authority")]),
+            AMMS_CONFIGS_MANAGER,
+            ("router_registry", &[Owner, Address, Data, Writable, Substitute("amms_configs_manager")]),
+        ],
+    },
+    cp_amm_update!("update_cp_amm_incident_mode", UpdateCpAmmIncidentMode),
+    cp_amm_update!("update_cp_amm_lend_out", UpdateCpAmmLendOut),
+    cp_amm_update!("update_cp_amm_protected_mode", UpdateCpAmmProtectedMode),
+    InstructionSpec {
+        name: "update_cp_amm_liquidity_subscriber",
+        validate: validator!(UpdateCpAmmLiquiditySubscriber),
+        accounts: &[AUTHORITY, AMMS_CONFIGS_MANAGER, UPDATED_CP_AMM, (ABSENT, &[Substitute("signer")])],
+    },
+    InstructionSpec {
+        name: "update_cp_amm_usd_oracle",
+        validate: validator!(UpdateCpAmmUsdOracle),
+        // Any price update account may become the oracle, its feed is checked by the handler
+        accounts: &[AUTHORITY, AMMS_CONFIGS_MANAGER, UPDATED_CP_AMM, (ABSENT, &[])],
+    },
+    InstructionSpec {
+        name: "update_cp_amm_protocol_fee_override",
+        validate: validator!(UpdateCpAmmProtocolFeeOverride),
+        accounts: &[
+            HEAD_AUTHORITY,
+            AMMS_CONFIGS_MANAGER,
+            ("amms_config", &[Owner, Address, Data, Substitute("other_amms_config")]),
+            UPDATED_CP_AMM,
+        ],
+    },
+    InstructionSpec {
+        name: "recall_cp_amm_lend_out_loan",
+        validate: validator!(RecallCpAmmLendOutLoan),
+        accounts: &[
+            AUTHORITY,
+            AMMS_CONFIGS_MANAGER,
+            ("cp_amm", &[Owner, Address, Data, Writable, Substitute("second_cp_amm")]),
+            ("lend_out_loan", &[Owner, Address, Data, Writable, Substitute("cp_amm")]),
+        ],
+    },
+    InstructionSpec {
+        name: "resync_cp_amm_lp_tokens_supply",
+        validate: validator!(ResyncCpAmmLpTokensSupply),
+        accounts: &[
+            AUTHORITY,
+            AMMS_CONFIGS_MANAGER,
+            UPDATED_CP_AMM,
+            ("lp_mint", &[Owner, Address, Data, Substitute("second_lp_mint")]),
+        ],
+    },
+    RECOVER_STRANDED_TOKENS,
+    InstructionSpec {
+        name: "update_cp_amm_creator_fee_share",
+        validate: validator!(UpdateCpAmmCreatorFeeShare),
+        accounts: &[CREATOR, ("amms_config", &[Owner, Address, Data, Substitute("other_amms_config")]), UPDATED_CP_AMM],
+    },
+    InstructionSpec {
+        name: "update_cp_amm_trading_schedule",
+        validate: validator!(UpdateCpAmmTradingSchedule),
+        accounts: &[CREATOR, UPDATED_CP_AMM],
+    },
+    InstructionSpec {
+        name: "update_cp_amm_paused_operations",
+        validate: validator!(UpdateCpAmmPausedOperations),
+        accounts: &[CREATOR, AMMS_CONFIGS_MANAGER, UPDATED_CP_AMM],
+    },
+    InstructionSpec {
+        name: "lock_fee_parameters",
+        validate: validator!(LockFeeParameters),
+        accounts: &[CREATOR, UPDATED_CP_AMM],
+    },
+];
+
+/// Tests the constraint matrices of all covered instructions.
+#[test]
+fn test_constraint_matrices() {
+    let fixture = PoolFixture::new();
+    for instruction in INSTRUCTIONS {
+        instruction.assert_matrix(&fixture);
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(2000))]
+
+    /// Fuzz-test of the constraint matrices with random keys and truncated data lengths.
+    #[test]
+    fn test_fuzz_constraint_matrices(instruction in any::<Index>(), account in any::<Index>(), mutation in any::<Index>(), key in any::<[u8; 32]>(), data_len in any::<usize>()) {
+        let fixture = PoolFixture::new();
+        let instruction = instruction.get(INSTRUCTIONS);
+        let rows: Vec<usize> = (0..instruction.accounts.len()).filter(|&index| !instruction.accounts[index].1.is_empty()).collect();
+        let index = *account.get(&rows);
+        let noise = MutationNoise { key: Pubkey::new_from_array(key), data_len };
+        let mutation = *mutation.get(instruction.accounts[index].1);
+        // A random key may hit the original owner or address, leaving the account unchanged
+        let mut mutated = instruction.accounts(&fixture)[index].clone();
+        mutation.apply(&mut mutated, &fixture, &noise);
+        prop_assume!(mutated != instruction.accounts(&fixture)[index]);
+        instruction.assert_rejected(&fixture, index, mutation, &noise);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,256 @@
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use anchor_lang::solana_program::{bpf_loader, entrypoint::SUCCESS, program_option::COption, program_pack::Pack, program_stubs, system_program};
+use anchor_spl::associated_token::{self, get_associated_token_address_with_program_id};
+use anchor_spl::token::{self, spl_token};
+use anchor_spl::token_interface;
+use crate::state::{AmmsConfig, AmmsConfigsManager, RouterRegistry};
+use crate::state::cp_amm::{CpAmm, LendOutLoan, LiquidityPosition, TradingSession};
+use super::TestAccount;
+
+/// The name of the placeholder passed for absent optional accounts.
+pub(crate) const ABSENT: &str = "absent";
+
+/// Base liquidity the fixture pool is launched with.
+const BASE_LIQUIDITY: u64 = 1_000_000_000;
+
+/// Quote liquidity the fixture pool is launched with.
+const QUOTE_LIQUIDITY: u64 = 4_000_000_000;
+
+/// A launched `CpAmm` with its `AmmsConfig`, mints, vaults, the token accounts of a trader and
+/// the programs its instructions take, all valid for the account constraints of the program.
+///
+/// Around it, the fixture holds what the other instructions validate: a second pool of the same
+/// mints in another config for arbitrage, the `AmmsConfigsManager` and its authorities, the fee
+/// authority, the lending adapter with a loan, a position owner with token accounts delegated to a
+/// manager and a trading session, and tokens of another mint stranded in the pool.
+///
+/// The pool state is produced by `AmmsConfig::initialize`, `CpAmm::initialize` and `CpAmm::launch`
+/// rather than by hand-written bytes, so the fixture follows the account layouts. Keys are fixed,
+/// so every run validates the same accounts.
+pub(crate) struct PoolFixture {
+    accounts: Vec<(&'static str, TestAccount)>,
+}
+
+/// Syscall stubs providing the `Rent` sysvar, read by `init_if_needed` constraints even for
+/// existing accounts.
+struct RentSyscallStubs;
+
+impl program_stubs::SyscallStubs for RentSyscallStubs {
+    fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
+        // SAFETY: `Rent::get` passes a pointer to a `Rent` value
+        unsafe { *(var_addr as *mut Rent) = Rent::default() };
+        SUCCESS
+    }
+}
+
+impl PoolFixture {
+    pub(crate) fn new() -> Self {
+        static SYSCALL_STUBS: std::sync::Once = std::sync::Once::new();
+        SYSCALL_STUBS.call_once(|| {
+            program_stubs::set_syscall_stubs(Box::new(RentSyscallStubs));
+        });
+
+        let signer = Pubkey::new_from_array([1; 32]);
+        let base_mint = Pubkey::new_from_array([2; 32]);
+        let quote_mint = Pubkey::new_from_array([3; 32]);
+        let lp_mint = Pubkey::new_from_array([4; 32]);
+        let fee_authority = Pubkey::new_from_array([5; 32]);
+        let owner = Pubkey::new_from_array([6; 32]);
+        let second_lp_mint = Pubkey::new_from_array([7; 32]);
+        let lending_adapter = Pubkey::new_from_array([8; 32]);
+        let authority = Pubkey::new_from_array([9; 32]);
+        let head_authority = Pubkey::new_from_array([10; 32]);
+        let stranded_mint = Pubkey::new_from_array([11; 32]);
+        let manager = Pubkey::new_from_array([12; 32]);
+        let session_key = Pubkey::new_from_array([13; 32]);
+        let keeper = Pubkey::new_from_array([14; 32]);
+
+        let (amms_config, amms_config_bump) = Pubkey::find_program_address(&[AmmsConfig::SEED, 0u64.to_le_bytes().as_ref()], &crate::ID);
+        let (other_amms_config, other_amms_config_bump) = Pubkey::find_program_address(&[AmmsConfig::SEED, 1u64.to_le_bytes().as_ref()], &crate::ID);
+        let (amms_configs_manager, amms_configs_manager_bump) = Pubkey::find_program_address(&[AmmsConfigsManager::SEED], &crate::ID);
+        let (router_registry, router_registry_bump) = Pubkey::find_program_address(&[RouterRegistry::SEED], &crate::ID);
+
+        let mut fixture = Self { accounts: Vec::new() };
+        fixture.insert("signer", TestAccount::new(signer, system_program::ID, Vec::new()).signer().writable());
+        fixture.insert("fee_authority", TestAccount::new(fee_authority, system_program::ID, Vec::new()).signer().writable());
+        fixture.insert("owner", TestAccount::new(owner, system_program::ID, Vec::new()));
+        fixture.insert("lending_adapter", TestAccount::new(lending_adapter, system_program::ID, Vec::new()).signer().writable());
+        fixture.insert("authority", TestAccount::new(authority, system_program::ID, Vec::new()).signer().writable());
+        fixture.insert("head_authority", TestAccount::new(head_authority, system_program::ID, Vec::new()).signer().writable());
+        fixture.insert("manager", TestAccount::new(manager, system_program::ID, Vec::new()).signer().writable());
+        fixture.insert("session_key", TestAccount::new(session_key, system_program::ID, Vec::new()).signer());
+        fixture.insert("keeper", TestAccount::new(keeper, system_program::ID, Vec::new()).signer().writable());
+        fixture.insert("base_mint", mint_account(base_mint, signer, 6, 0));
+        fixture.insert("quote_mint", mint_account(quote_mint, signer, 9, 0));
+        fixture.insert("stranded_mint", mint_account(stranded_mint, signer, 6, 0));
+        fixture.insert("amms_config", amms_config_account(amms_config, fee_authority, lending_adapter, 0, amms_config_bump).writable());
+        fixture.insert("other_amms_config", amms_config_account(other_amms_config, fee_authority, lending_adapter, 1, other_amms_config_bump));
+        fixture.insert("associated_token_program", program_account(associated_token::ID, bpf_loader::ID));
+        fixture.insert("token_program", program_account(token::ID, bpf_loader::ID));
+        fixture.insert("system_program", program_account(system_program::ID, bpf_loader::ID));
+        fixture.insert(ABSENT, program_account(crate::ID, bpf_loader::ID));
+
+        let mut manager_account = zeroed_account::<AmmsConfigsManager>(8 + AmmsConfigsManager::INIT_SPACE);
+        manager_account.initialize(authority, head_authority, amms_configs_manager_bump);
+        fixture.insert("amms_configs_manager", TestAccount::new(amms_configs_manager, crate::ID, serialize_account(&manager_account, 8 + AmmsConfigsManager::INIT_SPACE)).writable());
+        let mut router_registry_account = zeroed_account::<RouterRegistry>(8 + RouterRegistry::INIT_SPACE);
+        router_registry_account.initialize(router_registry_bump);
+        fixture.insert("router_registry", TestAccount::new(router_registry, crate::ID, serialize_account(&router_registry_account, 8 + RouterRegistry::INIT_SPACE)).writable());
+
+        let cp_amm = fixture.insert_pool(lp_mint, "amms_config", ["lp_mint", "cp_amm", "cp_amm_base_vault", "cp_amm_quote_vault", "cp_amm_locked_lp_vault"]);
+        fixture.insert_pool(second_lp_mint, "other_amms_config", ["second_lp_mint", "second_cp_amm", "second_cp_amm_base_vault", "second_cp_amm_quote_vault", "second_cp_amm_locked_lp_vault"]);
+
+        let associated_token_account = |authority: &Pubkey, mint: &Pubkey| get_associated_token_address_with_program_id(authority, mint, &token::ID);
+        for (name, mint) in [("signer_base_account", base_mint), ("signer_quote_account", quote_mint), ("signer_lp_account", lp_mint)] {
+            fixture.insert(name, token_account(associated_token_account(&signer, &mint), mint, signer, 1_000_000).writable());
+        }
+        for (name, mint) in [("fee_authority_base_account", base_mint), ("fee_authority_quote_account", quote_mint)] {
+            fixture.insert(name, token_account(associated_token_account(&fee_authority, &mint), mint, fee_authority, 0).writable());
+        }
+        // The owner accounts are delegated to the manager, as for liquidity managed on behalf of the owner
+        for (name, mint) in [("owner_base_account", base_mint), ("owner_quote_account", quote_mint), ("owner_lp_account", lp_mint)] {
+            let account = token_account(associated_token_account(&owner, &mint), mint, owner, 1_000_000);
+            fixture.insert(name, delegated(account, manager).writable());
+        }
+        fixture.insert("lending_adapter_account", token_account(Pubkey::new_from_array([15; 32]), base_mint, lending_adapter, 0).writable());
+        fixture.insert("stranded_account", token_account(Pubkey::new_from_array([16; 32]), stranded_mint, cp_amm, 1_000).writable());
+        fixture.insert("recipient_account", token_account(Pubkey::new_from_array([17; 32]), stranded_mint, signer, 0).writable());
+
+        let (lend_out_loan, lend_out_loan_bump) = Pubkey::find_program_address(&[LendOutLoan::SEED, cp_amm.as_ref(), base_mint.as_ref()], &crate::ID);
+        let mut lend_out_loan_account = zeroed_account::<LendOutLoan>(8 + LendOutLoan::INIT_SPACE);
+        lend_out_loan_account.initialize(cp_amm, base_mint, lend_out_loan_bump);
+        fixture.insert("lend_out_loan", TestAccount::new(lend_out_loan, crate::ID, serialize_account(&lend_out_loan_account, 8 + LendOutLoan::INIT_SPACE)).writable());
+
+        for (name, position_owner) in [("signer_liquidity_position", signer), ("owner_liquidity_position", owner)] {
+            let (liquidity_position, bump) = Pubkey::find_program_address(&[LiquidityPosition::SEED, cp_amm.as_ref(), position_owner.as_ref()], &crate::ID);
+            let mut liquidity_position_account = zeroed_account::<LiquidityPosition>(8 + LiquidityPosition::INIT_SPACE);
+            liquidity_position_account.initialize(cp_amm, position_owner, bump);
+            fixture.insert(name, TestAccount::new(liquidity_position, crate::ID, serialize_account(&liquidity_position_account, 8 + LiquidityPosition::INIT_SPACE)).writable());
+        }
+
+        let (trading_session, trading_session_bump) = Pubkey::find_program_address(&[TradingSession::SEED, cp_amm.as_ref(), owner.as_ref(), session_key.as_ref()], &crate::ID);
+        let mut trading_session_account = zeroed_account::<TradingSession>(8 + TradingSession::INIT_SPACE);
+        trading_session_account.initialize(cp_amm, owner, session_key, 1_000_000, 10_000_000, 1_000, 0, trading_session_bump).unwrap();
+        fixture.insert("trading_session", TestAccount::new(trading_session, crate::ID, serialize_account(&trading_session_account, 8 + TradingSession::INIT_SPACE)).writable());
+        fixture
+    }
+
+    /// Returns a copy of the named account.
+    ///
+    /// # Panics
+    /// - Panics if the fixture has no account of that name.
+    pub(crate) fn account(&self, name: &str) -> TestAccount {
+        self.accounts.iter()
+            .find(|(account_name, _)| *account_name == name)
+            .map(|(_, account)| account.clone())
+            .unwrap_or_else(|| panic!("Unknown fixture account {}", name))
+    }
+
+    fn insert(&mut self, name: &'static str, account: TestAccount) {
+        self.accounts.retain(|(account_name, _)| *account_name != name);
+        self.accounts.push((name, account));
+    }
+
+    /// Inserts a pool of the fixture mints launched in the named `AmmsConfig`, with its LP mint, `CpAmm`
+    /// and vaults named by `names` in this order, returning the `CpAmm` key.
+    fn insert_pool(&mut self, lp_mint: Pubkey, amms_config: &str, names: [&'static str; 5]) -> Pubkey {
+        let [lp_mint_name, cp_amm_name, base_vault_name, quote_vault_name, locked_lp_vault_name] = names;
+        let (base_mint, quote_mint) = (self.account("base_mint").key, self.account("quote_mint").key);
+        let (cp_amm, cp_amm_bump) = Pubkey::find_program_address(&[CpAmm::SEED, lp_mint.as_ref()], &crate::ID);
+        let vault = |mint: &Pubkey| Pubkey::find_program_address(&[CpAmm::VAULT_SEED, cp_amm.as_ref(), mint.as_ref()], &crate::ID);
+        let ((base_vault, base_vault_bump), (quote_vault, quote_vault_bump), (locked_lp_vault, locked_lp_vault_bump)) =
+            (vault(&base_mint), vault(&quote_mint), vault(&lp_mint));
+
+        self.insert(lp_mint_name, mint_account(lp_mint, cp_amm, 9, 0).writable());
+        self.insert(base_vault_name, token_account(base_vault, base_mint, cp_amm, BASE_LIQUIDITY).writable());
+        self.insert(quote_vault_name, token_account(quote_vault, quote_mint, cp_amm, QUOTE_LIQUIDITY).writable());
+        self.insert(locked_lp_vault_name, token_account(locked_lp_vault, lp_mint, cp_amm, 0).writable());
+
+        let cp_amm_data = self.launched_cp_amm_data(
+            ["base_mint", "quote_mint", lp_mint_name, amms_config, "signer", base_vault_name, quote_vault_name, locked_lp_vault_name],
+            cp_amm_bump, base_vault_bump, quote_vault_bump, locked_lp_vault_bump
+        );
+        self.insert(cp_amm_name, TestAccount::new(cp_amm, crate::ID, cp_amm_data).writable());
+        cp_amm
+    }
+
+    /// Initializes and launches a `CpAmm` of the named mints, config, creator and vaults, returning its account data.
+    fn launched_cp_amm_data(&self, names: [&str; 8], bump: u8, base_vault_bump: u8, quote_vault_bump: u8, locked_lp_vault_bump: u8) -> Vec<u8> {
+        let mut accounts = names.map(|name| self.account(name));
+        let infos: Vec<AccountInfo> = accounts.iter_mut().map(TestAccount::info).collect();
+        let base_mint = InterfaceAccount::<token_interface::Mint>::try_from(&infos[0]).unwrap();
+        let quote_mint = InterfaceAccount::<token_interface::Mint>::try_from(&infos[1]).unwrap();
+        let lp_mint = Account::<token::Mint>::try_from(&infos[2]).unwrap();
+        let amms_config = Account::<AmmsConfig>::try_from(&infos[3]).unwrap();
+
+        let mut cp_amm = zeroed_account::<CpAmm>(8 + CpAmm::INIT_SPACE);
+        cp_amm.initialize(
+            &base_mint, &quote_mint, &lp_mint, &amms_config, &infos[4], &infos[5], &infos[6], &infos[7],
+            bump, base_vault_bump, quote_vault_bump, locked_lp_vault_bump, 0
+        ).unwrap();
+        let launch_payload = cp_amm.get_launch_payload(BASE_LIQUIDITY, QUOTE_LIQUIDITY, base_mint.decimals, quote_mint.decimals, lp_mint.decimals, None).unwrap();
+        cp_amm.launch(launch_payload, 0);
+        serialize_account(&cp_amm, 8 + CpAmm::INIT_SPACE)
+    }
+}
+
+/// Deserializes an account of zeroed data, as allocated by the runtime.
+fn zeroed_account<T: AccountDeserialize + Discriminator>(space: usize) -> T {
+    let mut data = vec![0u8; space];
+    data[..8].copy_from_slice(&T::DISCRIMINATOR);
+    T::try_deserialize(&mut data.as_slice()).unwrap()
+}
+
+fn serialize_account<T: AccountSerialize>(account: &T, space: usize) -> Vec<u8> {
+    let mut data = Vec::with_capacity(space);
+    account.try_serialize(&mut data).unwrap();
+    data.resize(space, 0);
+    data
+}
+
+/// Returns an `AmmsConfig` enabling the protocol rebalance and lending to `lending_adapter`.
+fn amms_config_account(key: Pubkey, fee_authority: Pubkey, lending_adapter: Pubkey, id: u64, bump: u8) -> TestAccount {
+    let mut amms_config = zeroed_account::<AmmsConfig>(8 + AmmsConfig::INIT_SPACE);
+    amms_config.initialize(fee_authority, 5, 25, id, bump).unwrap();
+    amms_config.update_protocol_rebalance(true);
+    amms_config.update_lending(Some(lending_adapter), 1_000, 100).unwrap();
+    TestAccount::new(key, crate::ID, serialize_account(&amms_config, 8 + AmmsConfig::INIT_SPACE))
+}
+
+fn mint_account(key: Pubkey, mint_authority: Pubkey, decimals: u8, supply: u64) -> TestAccount {
+    let mut data = vec![0u8; spl_token::state::Mint::LEN];
+    spl_token::state::Mint {
+        mint_authority: COption::Some(mint_authority),
+        supply,
+        decimals,
+        is_initialized: true,
+        freeze_authority: COption::None,
+    }.pack_into_slice(&mut data);
+    TestAccount::new(key, token::ID, data)
+}
+
+fn token_account(key: Pubkey, mint: Pubkey, owner: Pubkey, amount: u64) -> TestAccount {
+    let mut data = vec![0u8; spl_token::state::Account::LEN];
+    spl_token::state::Account {
+        mint,
+        owner,
+        amount,
+        state: spl_token::state::AccountState::Initialized,
+        ..Default::default()
+    }.pack_into_slice(&mut data);
+    TestAccount::new(key, token::ID, data)
+}
+
+/// Delegates the tokens of a token account to `delegate`.
+fn delegated(mut account: TestAccount, delegate: Pubkey) -> TestAccount {
+    let mut token_account = spl_token::state::Account::unpack(&account.data).unwrap();
+    token_account.delegate = COption::Some(delegate);
+    token_account.delegated_amount = token_account.amount;
+    token_account.pack_into_slice(&mut account.data);
+    account
+}
+
+fn program_account(key: Pubkey, loader: Pubkey) -> TestAccount {
+    TestAccount { executable: true, ..TestAccount::new(key, loader, Vec::new()) }
+}
\ No newline at end of file
@@ -0,0 +1,179 @@
+//! Account constraint fuzzing of the program instructions.
+//!
+//! Every covered instruction declares a constraint matrix: its accounts in the field order, each
+//! with the mutations of its account metas that the account validation must reject, e.g. another
+//! owner, a copy at another address or the other mint of the pool. The harness runs the Anchor
+//! account validation of the instruction natively on the accounts of a `PoolFixture`, checks
+//! that the unmutated accounts pass and that every mutation of the matrix is rejected with the
+//! same error on every run. A proptest then applies the mutations with random keys and data.
+//!
+//! Accounts left to the token program by design, such as the source token accounts of a provide,
+//! only list the mutations their account types reject, so the matrix documents those gaps too.
+//!
+//! Matrices cover the trading, liquidity, fee, lending, delegate, session and admin instructions.
+//! Instructions reusing the accounts of a covered one, e.g. `swap_in_cp_amm_v2`, share its matrix.
+//! The fixture has no `WpAmm`, so the `*_wp_amm` instructions are not covered, nor are those whose
+//! validation creates accounts (`initialize_*`, `open_*`, `launch_*`, `realloc_*`, `record_*`,
+//! `issue_pool_creator_permit`, `create_test_pool` and the `init_if_needed` tables of
+//! `update_amms_config_mint_equivalence` and `update_amms_config_trader_fee_tiers`) or reads
+//! accounts the fixture lacks (`close_trading_session`, `collect_creator_fees`,
+//! `enable_cp_amm_decimals_normalization`, `get_loyalty_multiplier`, `get_safe_spot_price`,
+//! `get_trailing_apr`, `get_virtual_price`, `provide_split`, `refresh_cp_amm_display_data`,
+//! `register_cp_amm_lookup_table`, `report_vault_drift`, `revoke_pool_creator_permit`,
+//! `swap_in_cp_amm_compact` and `verify_cp_amm_lp_snapshot_leaf`).
+mod constraints;
+mod fixture;
+
+use anchor_lang::prelude::*;
+pub(crate) use fixture::{PoolFixture, ABSENT};
+
+/// An account owning its lamports and data, so its metas can be mutated before validation.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct TestAccount {
+    pub key: Pubkey,
+    pub owner: Pubkey,
+    pub lamports: u64,
+    pub data: Vec<u8>,
+    pub is_signer: bool,
+    pub is_writable: bool,
+    pub executable: bool,
+}
+
+impl TestAccount {
+    /// Creates a read-only, non-signer account funded with one SOL.
+    pub(crate) fn new(key: Pubkey, owner: Pubkey, data: Vec<u8>) -> Self {
+        Self {
+            key,
+            owner,
+            lamports: 1_000_000_000,
+            data,
+            is_signer: false,
+            is_writable: false,
+            executable: false,
+        }
+    }
+
+    pub(crate) fn signer(mut self) -> Self {
+        self.is_signer = true;
+        self
+    }
+
+    pub(crate) fn writable(mut self) -> Self {
+        self.is_writable = true;
+        self
+    }
+
+    /// Returns the `AccountInfo` of the account, as passed to the program by the runtime.
+    pub(crate) fn info(&mut self) -> AccountInfo<'_> {
+        AccountInfo::new(&self.key, self.is_signer, self.is_writable, &mut self.lamports, &mut self.data, &self.owner, self.executable, 0)
+    }
+}
+
+/// A mutation of the metas of an account passed to an instruction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Mutation {
+    /// The account is owned by another program.
+    Owner,
+    /// A copy of the account is passed from another address.
+    Address,
+    /// The account data is truncated to less than 8 bytes, cutting the discriminator of Anchor accounts.
+    Data,
+    /// The account doesn't sign the transaction.
+    Signer,
+    /// The account is passed read-only.
+    Writable,
+    /// Another account of the fixture is passed instead, keeping the signer and writable flags.
+    Substitute(&'static str),
+}
+
+/// Random values mutated accounts are built from.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct MutationNoise {
+    /// The key used for the `Owner` and `Address` mutations.
+    pub key: Pubkey,
+    /// The length the data is truncated to by the `Data` mutation, modulo 8.
+    pub data_len: usize,
+}
+
+impl Default for MutationNoise {
+    fn default() -> Self {
+        Self { key: Pubkey::new_from_array([0xA5; 32]), data_len: 0 }
+    }
+}
+
+impl Mutation {
+    /// Applies the mutation to an account.
+    pub(crate) fn apply(self, account: &mut TestAccount, fixture: &PoolFixture, noise: &MutationNoise) {
+        match self {
+            Mutation::Owner => account.owner = noise.key,
+            Mutation::Address => account.key = noise.key,
+            Mutation::Data => account.data.truncate(noise.data_len % 8),
+            Mutation::Signer => account.is_signer = false,
+            Mutation::Writable => account.is_writable = false,
+            Mutation::Substitute(name) => {
+                *account = TestAccount { is_signer: account.is_signer, is_writable: account.is_writable, ..fixture.account(name) };
+            }
+        }
+    }
+}
+
+/// The constraint matrix of an instruction.
+pub(crate) struct InstructionSpec {
+    /// The name of the instruction, for failure messages.
+    pub name: &'static str,
+    /// Runs the Anchor account validation of the instruction.
+    pub validate: fn(&mut [TestAccount]) -> Result<()>,
+    /// The fixture accounts in the field order, each with the mutations the validation must reject.
+    pub accounts: &'static [(&'static str, &'static [Mutation])],
+}
+
+impl InstructionSpec {
+    /// Returns the valid accounts of the instruction.
+    pub(crate) fn accounts(&self, fixture: &PoolFixture) -> Vec<TestAccount> {
+        self.accounts.iter().map(|(name, _)| fixture.account(name)).collect()
+    }
+
+    /// Runs the validation with a mutation applied to the account at `index`.
+    pub(crate) fn validate_mutated(&self, fixture: &PoolFixture, index: usize, mutation: Mutation, noise: &MutationNoise) -> Result<()> {
+        let mut accounts = self.accounts(fixture);
+        mutation.apply(&mut accounts[index], fixture, noise);
+        (self.validate)(&mut accounts)
+    }
+
+    /// Asserts that a mutation is rejected, with the same error on a second run.
+    pub(crate) fn assert_rejected(&self, fixture: &PoolFixture, index: usize, mutation: Mutation, noise: &MutationNoise) {
+        let name = self.accounts[index].0;
+        let error = self.validate_mutated(fixture, index, mutation, noise).err()
+            .unwrap_or_else(|| panic!("{}: {:?} of `{}` was accepted", self.name, mutation, name));
+        assert_eq!(
+            self.validate_mutated(fixture, index, mutation, noise).err(),
+            Some(error),
+            "{}: {:?} of `{}` was rejected nondeterministically", self.name, mutation, name
+        );
+    }
+
+    /// Asserts that the valid accounts pass and every mutation of the matrix is rejected.
+    pub(crate) fn assert_matrix(&self, fixture: &PoolFixture) {
+        let result = (self.validate)(&mut self.accounts(fixture));
+        assert!(result.is_ok(), "{}: valid accounts were rejected: {:?}", self.name, result);
+        for (index, (_, mutations)) in self.accounts.iter().enumerate() {
+            for mutation in mutations.iter() {
+                self.assert_rejected(fixture, index, *mutation, &MutationNoise::default());
+            }
+        }
+    }
+}
+
+/// Returns a function running the Anchor account validation of an `Accounts` struct.
+macro_rules! validator {
+    ($accounts:ident) => {{
+        fn validate(test_accounts: &mut [TestAccount]) -> Result<()> {
+            let infos: Vec<AccountInfo> = test_accounts.iter_mut().map(TestAccount::info).collect();
+            let mut remaining_accounts = infos.as_slice();
+            let mut bumps = <$accounts as anchor_lang::Bumps>::Bumps::default();
+            $accounts::try_accounts(&crate::ID, &mut remaining_accounts, &[], &mut bumps, &mut std::collections::BTreeSet::new()).map(|_| ())
+        }
+        validate
+    }};
+}
+pub(crate) use validator;
\ No newline at end of file
@@ -1,6 +1,7 @@
 use anchor_lang::prelude::*;
 use crate::state::{AmmsConfig, AmmsConfigsManager};
 use crate::constants::ANCHOR_DISCRIMINATOR;
+use crate::utils::validate_fee_authority;
 
 #[derive(Accounts)]
 pub struct InitializeAmmsConfig<'info> {
@@ -23,13 +24,14 @@ pub struct InitializeAmmsConfig<'info> {
         bump
     )]
     amms_config: Account<'info, AmmsConfig>,
-    /// CHECK: Amms config's fee authority can be arbitrary
+    /// CHECK: Amms config's fee authority can be a wallet or a PDA of another program, validated in the handler
     fee_authority: UncheckedAccount<'info>,
     rent: Sysvar<'info, Rent>,
     system_program: Program<'info, System>,
 }
 
 pub(crate) fn handler(ctx: Context<InitializeAmmsConfig>, protocol_fee_rate_basis_points: u16, providers_fee_rate_basis_points: u16) -> Result<()> {
+    validate_fee_authority(&ctx.accounts.fee_authority)?;
     ctx.accounts.amms_config.initialize(
         ctx.accounts.fee_authority.key(),
         protocol_fee_rate_basis_points,
@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+use crate::state::{AmmsConfigsManager, RouterRegistry};
+use crate::constants::ANCHOR_DISCRIMINATOR;
+
+#[derive(Accounts)]
+pub struct InitializeRouterRegistry<'info> {
+    #[account(
+        mut,
+        constraint = head_authority.key() == amms_configs_manager.head_authority().key()
+    )]
+    head_authority: Signer<'info>,
+    #[account(
+        seeds = [AmmsConfigsManager::SEED],
+        bump = amms_configs_manager.bump()
+    )]
+    amms_configs_manager: Account<'info, AmmsConfigsManager>,
+    #[account(
+        init,
+        payer = head_authority,
+        space = ANCHOR_DISCRIMINATOR + RouterRegistry::INIT_SPACE,
+        seeds = [RouterRegistry::SEED],
+        bump
+    )]
+    router_registry: Box<Account<'info, RouterRegistry>>,
+    system_program: Program<'info, System>,
+}
+
+pub(crate) fn handler(ctx: Context<InitializeRouterRegistry>) -> Result<()> {
+    ctx.accounts.router_registry.initialize(ctx.bumps.router_registry);
+    Ok(())
+}
\ No newline at end of file
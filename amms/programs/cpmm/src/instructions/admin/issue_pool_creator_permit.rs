@@ -0,0 +1,40 @@
+use anchor_lang::Accounts;
+use anchor_lang::prelude::*;
+use crate::state::{AmmsConfig, AmmsConfigsManager, PoolCreatorPermit};
+
+#[derive(Accounts)]
+pub struct IssuePoolCreatorPermit<'info> {
+    #[account(
+        mut,
+        constraint = (authority.key() == amms_configs_manager.authority().key() || authority.key() == amms_configs_manager.head_authority().key())
+    )]
+    authority: Signer<'info>,
+    #[account(
+        seeds = [AmmsConfigsManager::SEED],
+        bump = amms_configs_manager.bump()
+    )]
+    amms_configs_manager: Account<'info, AmmsConfigsManager>,
+    #[account(
+        seeds = [AmmsConfig::SEED, amms_config.id.to_le_bytes().as_ref()],
+        bump = amms_config.bump()
+    )]
+    amms_config: Account<'info, AmmsConfig>,
+    /// CHECK: The permitted creator can be any address, e.g. a PDA of a launchpad program
+    creator: AccountInfo<'info>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + PoolCreatorPermit::INIT_SPACE,
+        seeds = [PoolCreatorPermit::SEED, amms_config.key().as_ref(), creator.key().as_ref()],
+        bump
+    )]
+    pool_creator_permit: Account<'info, PoolCreatorPermit>,
+    system_program: Program<'info, System>,
+}
+
+/// Issues a `PoolCreatorPermit` allowing a creator to initialize pools in an allow-listed config.
+pub(crate) fn handler(ctx: Context<IssuePoolCreatorPermit>) -> Result<()> {
+    let (amms_config_key, creator_key) = (ctx.accounts.amms_config.key(), ctx.accounts.creator.key());
+    ctx.accounts.pool_creator_permit.initialize(amms_config_key, creator_key, ctx.bumps.pool_creator_permit);
+    Ok(())
+}
\ No newline at end of file
@@ -2,15 +2,95 @@
 pub mod initialize_amms_configs_manager;
 pub mod update_amms_configs_manager_authority;
 pub mod update_amms_configs_manager_head_authority;
+pub mod update_amms_configs_manager_pool_creation_limit;
+pub mod update_amms_configs_manager_swap_origin_policy;
 pub mod initialize_amms_config;
 pub mod update_amms_config_fee_authority;
 pub mod update_amms_config_providers_fee_rate;
+pub mod schedule_amms_config_providers_fee_ramp;
 pub mod update_amms_config_protocol_fee_rate;
+pub mod update_amms_config_incident_withdraw_limit;
+pub mod update_amms_config_keeper_bounty;
+pub mod update_amms_config_protocol_fees_cap;
+pub mod update_amms_config_lp_tokens_limits;
+pub mod update_amms_config_mint_decimals_range;
+pub mod update_amms_config_protocol_rebalance;
+pub mod update_amms_config_fee_bounds;
+pub mod update_amms_config_lending;
+pub mod update_amms_config_loyalty_boost;
+pub mod update_amms_config_pool_creation_pricing;
+pub mod update_amms_config_launch_price_bound;
+pub mod update_amms_config_tolerances;
+pub mod update_amms_config_fee_settlement;
+pub mod update_amms_config_creator_fee_share_limit;
+pub mod update_amms_config_stale_pool_close;
+pub mod update_amms_config_screening_program;
+pub mod update_amms_config_mint_equivalence;
+pub mod update_amms_config_trader_fee_tiers;
+pub mod update_amms_config_pool_creation_allow_list;
+pub mod update_amms_config_lp_concentration;
+pub mod issue_pool_creator_permit;
+pub mod revoke_pool_creator_permit;
+pub mod initialize_router_registry;
+pub mod update_router_registry;
+pub mod update_cp_amm_incident_mode;
+pub mod update_cp_amm_protected_mode;
+pub mod update_cp_amm_usd_oracle;
+pub mod update_cp_amm_liquidity_subscriber;
+pub mod update_cp_amm_lend_out;
+pub mod update_cp_amm_protocol_fee_override;
+pub mod recall_cp_amm_lend_out_loan;
+pub mod resync_cp_amm_lp_tokens_supply;
+pub mod record_cp_amm_lp_snapshot;
+pub mod recover_stranded_tokens;
+pub mod realloc_pool_account;
+pub mod realloc_amms_configs_manager;
+pub mod realloc_amms_config;
 
 pub use initialize_amms_configs_manager::*;
 pub use update_amms_configs_manager_authority::*;
 pub use update_amms_configs_manager_head_authority::*;
+pub use update_amms_configs_manager_pool_creation_limit::*;
+pub use update_amms_configs_manager_swap_origin_policy::*;
 pub use initialize_amms_config::*;
 pub use update_amms_config_fee_authority::*;
 pub use update_amms_config_providers_fee_rate::*;
-pub use update_amms_config_protocol_fee_rate::*;
\ No newline at end of file
+pub use schedule_amms_config_providers_fee_ramp::*;
+pub use update_amms_config_protocol_fee_rate::*;
+pub use update_amms_config_incident_withdraw_limit::*;
+pub use update_amms_config_keeper_bounty::*;
+pub use update_amms_config_protocol_fees_cap::*;
+pub use update_amms_config_lp_tokens_limits::*;
+pub use update_amms_config_mint_decimals_range::*;
+pub use update_amms_config_protocol_rebalance::*;
+pub use update_amms_config_fee_bounds::*;
+pub use update_amms_config_lending::*;
+pub use update_amms_config_loyalty_boost::*;
+pub use update_amms_config_pool_creation_pricing::*;
+pub use update_amms_config_launch_price_bound::*;
+pub use update_amms_config_tolerances::*;
+pub use update_amms_config_fee_settlement::*;
+pub use update_amms_config_creator_fee_share_limit::*;
+pub use update_amms_config_stale_pool_close::*;
+pub use update_amms_config_screening_program::*;
+pub use update_amms_config_mint_equivalence::*;
+pub use update_amms_config_trader_fee_tiers::*;
+pub use update_amms_config_pool_creation_allow_list::*;
+pub use update_amms_config_lp_concentration::*;
+pub use issue_pool_creator_permit::*;
+pub use revoke_pool_creator_permit::*;
+pub use initialize_router_registry::*;
+pub use update_router_registry::*;
+pub use update_cp_amm_incident_mode::*;
+pub use update_cp_amm_protected_mode::*;
+pub use update_cp_amm_usd_oracle::*;
+pub use update_cp_amm_liquidity_subscriber::*;
+pub use update_cp_amm_lend_out::*;
+pub use update_cp_amm_protocol_fee_override::*;
+pub use recall_cp_amm_lend_out_loan::*;
+pub use resync_cp_amm_lp_tokens_supply::*;
+pub use record_cp_amm_lp_snapshot::*;
+pub use recover_stranded_tokens::*;
+pub use realloc_pool_account::*;
+pub use realloc_amms_configs_manager::*;
+pub use realloc_amms_config::*;
\ No newline at end of file
@@ -0,0 +1,60 @@
+use anchor_lang::Accounts;
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use crate::state::{AmmsConfig, AmmsConfigsManager};
+
+#[derive(Accounts)]
+pub struct ReallocAmmsConfig<'info> {
+    #[account(
+        constraint = (authority.key() == amms_configs_manager.authority().key() || authority.key() == amms_configs_manager.head_authority().key())
+    )]
+    authority: Signer<'info>,
+    #[account(mut)]
+    payer: Signer<'info>,
+    #[account(
+        seeds = [AmmsConfigsManager::SEED],
+        bump = amms_configs_manager.bump()
+    )]
+    amms_configs_manager: Account<'info, AmmsConfigsManager>,
+    /// CHECK: An outdated `AmmsConfig` can't be deserialized into the current layout, its discriminator and
+    /// version are validated in the handler
+    #[account(
+        mut,
+        owner = crate::ID
+    )]
+    amms_config: UncheckedAccount<'info>,
+    system_program: Program<'info, System>,
+}
+
+/// Upgrades an `AmmsConfig` written by an older program build to the current layout.
+///
+/// The account is grown to the current size, with the payer funding the additional rent, the new
+/// bytes are zero-initialized and the fields whose zero value would reject every pool are set to the
+/// defaults of a new config. The `AmmsConfigsManager` has to be upgraded first.
+pub(crate) fn handler(ctx: Context<ReallocAmmsConfig>) -> Result<()> {
+    let amms_config = ctx.accounts.amms_config.to_account_info();
+    let original_len = amms_config.data_len();
+    let space = AmmsConfig::validate_layout_upgrade(&amms_config.try_borrow_data()?)?;
+
+    let rent_deficit = Rent::get()?.minimum_balance(space).saturating_sub(amms_config.lamports());
+    if rent_deficit > 0 {
+        transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.payer.to_account_info(),
+                    to: amms_config.clone(),
+                }
+            ),
+            rent_deficit
+        )?;
+    }
+    if amms_config.data_len() < space {
+        amms_config.realloc(space, true)?;
+    }
+
+    let mut data = amms_config.try_borrow_mut_data()?;
+    let mut upgraded_amms_config = AmmsConfig::try_deserialize(&mut &data[..])?;
+    upgraded_amms_config.upgrade_layout(original_len);
+    upgraded_amms_config.try_serialize(&mut &mut data[..])
+}
\ No newline at end of file
@@ -0,0 +1,51 @@
+use anchor_lang::Accounts;
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use crate::state::AmmsConfigsManager;
+
+#[derive(Accounts)]
+pub struct ReallocAmmsConfigsManager<'info> {
+    authority: Signer<'info>,
+    #[account(mut)]
+    payer: Signer<'info>,
+    /// CHECK: An outdated `AmmsConfigsManager` can't be deserialized into the current layout, its discriminator,
+    /// size and authorities are validated in the handler
+    #[account(
+        mut,
+        owner = crate::ID,
+        seeds = [AmmsConfigsManager::SEED],
+        bump
+    )]
+    amms_configs_manager: UncheckedAccount<'info>,
+    system_program: Program<'info, System>,
+}
+
+/// Upgrades the `AmmsConfigsManager` written by an older program build to the current layout.
+///
+/// The account is grown to the current size, with the payer funding the additional rent, the new
+/// bytes are zero-initialized, which leaves the pool creation limit disabled and the swaps unrestricted,
+/// and the current layout version is stored.
+pub(crate) fn handler(ctx: Context<ReallocAmmsConfigsManager>) -> Result<()> {
+    let amms_configs_manager = ctx.accounts.amms_configs_manager.to_account_info();
+    let space = AmmsConfigsManager::validate_layout_upgrade(&amms_configs_manager.try_borrow_data()?, ctx.accounts.authority.key)?;
+
+    let rent_deficit = Rent::get()?.minimum_balance(space).saturating_sub(amms_configs_manager.lamports());
+    if rent_deficit > 0 {
+        transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.payer.to_account_info(),
+                    to: amms_configs_manager.clone(),
+                }
+            ),
+            rent_deficit
+        )?;
+    }
+    if amms_configs_manager.data_len() < space {
+        amms_configs_manager.realloc(space, true)?;
+    }
+
+    AmmsConfigsManager::upgrade_layout(&mut amms_configs_manager.try_borrow_mut_data()?);
+    Ok(())
+}
\ No newline at end of file
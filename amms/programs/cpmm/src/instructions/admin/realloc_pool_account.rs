@@ -0,0 +1,64 @@
+use anchor_lang::Accounts;
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_spl::token_interface::Mint;
+use crate::state::{AmmsConfigsManager, cp_amm::CpAmm};
+
+#[derive(Accounts)]
+pub struct ReallocPoolAccount<'info> {
+    #[account(
+        constraint = (authority.key() == amms_configs_manager.authority().key() || authority.key() == amms_configs_manager.head_authority().key())
+    )]
+    authority: Signer<'info>,
+    #[account(mut)]
+    payer: Signer<'info>,
+    #[account(
+        seeds = [AmmsConfigsManager::SEED],
+        bump = amms_configs_manager.bump()
+    )]
+    amms_configs_manager: Account<'info, AmmsConfigsManager>,
+    lp_mint: Box<InterfaceAccount<'info, Mint>>,
+    /// CHECK: An outdated `CpAmm` can't be deserialized into the current layout, its discriminator and
+    /// version are validated in the handler
+    #[account(
+        mut,
+        owner = crate::ID,
+        seeds = [CpAmm::SEED, lp_mint.key().as_ref()],
+        bump
+    )]
+    cp_amm: UncheckedAccount<'info>,
+    system_program: Program<'info, System>,
+}
+
+/// Upgrades a `CpAmm` written by an older program build to the current layout, opting the pool into
+/// the features added since.
+///
+/// The account is grown to the current size, with the payer funding the additional rent, the new
+/// bytes are zero-initialized and the current layout version is stored. The lifecycle flags of pools written
+/// before the layout was versioned are translated into their status, and never launched pools written before
+/// the initialization epoch was recorded start their stale period at the upgrade.
+pub(crate) fn handler(ctx: Context<ReallocPoolAccount>) -> Result<()> {
+    let cp_amm = ctx.accounts.cp_amm.to_account_info();
+    let original_len = cp_amm.data_len();
+    let space = CpAmm::validate_layout_upgrade(&cp_amm.try_borrow_data()?)?;
+
+    let rent_deficit = Rent::get()?.minimum_balance(space).saturating_sub(cp_amm.lamports());
+    if rent_deficit > 0 {
+        transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.payer.to_account_info(),
+                    to: cp_amm.clone(),
+                }
+            ),
+            rent_deficit
+        )?;
+    }
+    if cp_amm.data_len() < space {
+        cp_amm.realloc(space, true)?;
+    }
+
+    CpAmm::upgrade_layout(&mut cp_amm.try_borrow_mut_data()?, original_len, Clock::get()?.epoch)?;
+    Ok(())
+}
\ No newline at end of file
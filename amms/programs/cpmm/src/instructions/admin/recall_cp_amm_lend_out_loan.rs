@@ -0,0 +1,40 @@
+use anchor_lang::Accounts;
+use anchor_lang::prelude::*;
+use crate::state::VersionedAccount;
+use crate::state::{AmmsConfigsManager, cp_amm::{CpAmm, LendOutLoan}};
+
+#[derive(Accounts)]
+pub struct RecallCpAmmLendOutLoan<'info> {
+    #[account(
+        mut,
+        constraint = (authority.key() == amms_configs_manager.authority().key() || authority.key() == amms_configs_manager.head_authority().key())
+    )]
+    authority: Signer<'info>,
+    #[account(
+        seeds = [AmmsConfigsManager::SEED],
+        bump = amms_configs_manager.bump()
+    )]
+    amms_configs_manager: Account<'info, AmmsConfigsManager>,
+    #[account(
+        mut,
+        seeds = [CpAmm::SEED, cp_amm.lp_mint.as_ref()],
+        bump = cp_amm.bump()
+    )]
+    cp_amm: Box<Account<'info, CpAmm>>,
+    #[account(
+        mut,
+        constraint = lend_out_loan.cp_amm() == &cp_amm.key(),
+        seeds = [LendOutLoan::SEED, cp_amm.key().as_ref(), lend_out_loan.mint().as_ref()],
+        bump = lend_out_loan.bump()
+    )]
+    lend_out_loan: Box<Account<'info, LendOutLoan>>,
+}
+
+/// Emergency recall of a loan: disables lending out the pool liquidity and blocks
+/// further borrowing on the loan until the lending adapter repays it in full.
+pub(crate) fn handler(ctx: Context<RecallCpAmmLendOutLoan>) -> Result<()> {
+    ctx.accounts.cp_amm.assert_layout_version()?;
+    ctx.accounts.cp_amm.update_lend_out(false);
+    ctx.accounts.lend_out_loan.recall();
+    Ok(())
+}
\ No newline at end of file
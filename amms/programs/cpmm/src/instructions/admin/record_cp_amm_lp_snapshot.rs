@@ -0,0 +1,51 @@
+use anchor_lang::Accounts;
+use anchor_lang::prelude::*;
+use crate::state::{AmmsConfigsManager, cp_amm::{CpAmm, CpAmmCore, Snapshot}, VersionedAccount};
+
+#[derive(Accounts)]
+#[instruction(slot: u64)]
+pub struct RecordCpAmmLpSnapshot<'info> {
+    #[account(
+        mut,
+        constraint = (authority.key() == amms_configs_manager.authority().key() || authority.key() == amms_configs_manager.head_authority().key())
+    )]
+    authority: Signer<'info>,
+    #[account(
+        seeds = [AmmsConfigsManager::SEED],
+        bump = amms_configs_manager.bump()
+    )]
+    amms_configs_manager: Account<'info, AmmsConfigsManager>,
+    #[account(
+        seeds = [CpAmm::SEED, cp_amm.lp_mint.as_ref()],
+        bump = cp_amm.bump()
+    )]
+    cp_amm: Box<Account<'info, CpAmm>>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Snapshot::INIT_SPACE,
+        seeds = [Snapshot::SEED, cp_amm.key().as_ref(), slot.to_le_bytes().as_ref()],
+        bump
+    )]
+    snapshot: Box<Account<'info, Snapshot>>,
+    system_program: Program<'info, System>,
+}
+
+/// Records the merkle root of the LP holder balances of a pool at a past slot.
+///
+/// The tree is computed off-chain, so only its format is verified on-chain.
+pub(crate) fn handler(ctx: Context<RecordCpAmmLpSnapshot>, slot: u64, merkle_root: [u8; 32], holders_count: u32, total_lp_tokens: u64) -> Result<()> {
+    ctx.accounts.cp_amm.assert_layout_version()?;
+    let cp_amm_key = ctx.accounts.cp_amm.key();
+    let lp_tokens_supply = ctx.accounts.cp_amm.lp_tokens_supply();
+    ctx.accounts.snapshot.initialize(
+        cp_amm_key,
+        slot,
+        merkle_root,
+        holders_count,
+        total_lp_tokens,
+        Clock::get()?.slot,
+        lp_tokens_supply,
+        ctx.bumps.snapshot
+    )
+}
\ No newline at end of file
@@ -0,0 +1,76 @@
+use anchor_lang::Accounts;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::error::ErrorCode;
+use crate::state::{AmmsConfigsManager, cp_amm::CpAmm, VersionedAccount};
+use crate::utils::token_instructions::TransferTokensInstruction;
+
+#[derive(Accounts)]
+pub struct RecoverStrandedTokens<'info> {
+    #[account(
+        mut,
+        constraint = (authority.key() == amms_configs_manager.authority().key() || authority.key() == amms_configs_manager.head_authority().key())
+    )]
+    authority: Signer<'info>,
+    #[account(
+        seeds = [AmmsConfigsManager::SEED],
+        bump = amms_configs_manager.bump()
+    )]
+    amms_configs_manager: Box<Account<'info, AmmsConfigsManager>>,
+    #[account(
+        seeds = [CpAmm::SEED, cp_amm.lp_mint.as_ref()],
+        bump = cp_amm.bump()
+    )]
+    cp_amm: Box<Account<'info, CpAmm>>,
+    #[account(
+        constraint = mint.key() != cp_amm.base_mint().key() @ ErrorCode::RecoverPoolAssetMint,
+        constraint = mint.key() != cp_amm.quote_mint().key() @ ErrorCode::RecoverPoolAssetMint,
+        constraint = mint.key() != cp_amm.lp_mint.key() @ ErrorCode::RecoverPoolAssetMint,
+        mint::token_program = token_program,
+    )]
+    mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        constraint = stranded_account.key() != cp_amm.base_vault().key() @ ErrorCode::RecoverPoolAssetMint,
+        constraint = stranded_account.key() != cp_amm.quote_vault().key() @ ErrorCode::RecoverPoolAssetMint,
+        constraint = stranded_account.key() != cp_amm.locked_lp_vault().key() @ ErrorCode::RecoverPoolAssetMint,
+        token::mint = mint,
+        token::authority = cp_amm,
+        token::token_program = token_program,
+    )]
+    stranded_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        token::mint = mint,
+        token::token_program = token_program,
+    )]
+    recipient_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    token_program: Interface<'info, TokenInterface>,
+}
+
+pub(crate) fn handler(ctx: Context<RecoverStrandedTokens>) -> Result<()> {
+    ctx.accounts.cp_amm.assert_layout_version()?;
+    let amount = ctx.accounts.stranded_account.amount;
+    require!(amount > 0, ErrorCode::RecoverAmountIsZero);
+
+    let transfer_instruction = ctx.accounts.get_recover_transfer_instruction(amount)?;
+    let cp_amm_seeds = ctx.accounts.cp_amm.seeds();
+    let instruction_seeds: &[&[&[u8]]] = &[&cp_amm_seeds];
+    transfer_instruction.execute(Some(instruction_seeds))?;
+
+    Ok(())
+}
+
+impl<'info> RecoverStrandedTokens<'info> {
+    #[inline(never)]
+    fn get_recover_transfer_instruction(&self, amount: u64) -> Result<TransferTokensInstruction<'_, '_, '_, 'info>> {
+        TransferTokensInstruction::try_new(
+            amount,
+            &self.mint,
+            &self.stranded_account,
+            self.cp_amm.to_account_info(),
+            &self.recipient_account,
+            &self.token_program
+        )
+    }
+}
\ No newline at end of file
@@ -0,0 +1,32 @@
+use anchor_lang::Accounts;
+use anchor_lang::prelude::*;
+use anchor_spl::token::Mint;
+use crate::state::{AmmsConfigsManager, cp_amm::CpAmm, VersionedAccount};
+
+#[derive(Accounts)]
+pub struct ResyncCpAmmLpTokensSupply<'info> {
+    #[account(
+        mut,
+        constraint = (authority.key() == amms_configs_manager.authority().key() || authority.key() == amms_configs_manager.head_authority().key())
+    )]
+    authority: Signer<'info>,
+    #[account(
+        seeds = [AmmsConfigsManager::SEED],
+        bump = amms_configs_manager.bump()
+    )]
+    amms_configs_manager: Account<'info, AmmsConfigsManager>,
+    #[account(
+        mut,
+        constraint = lp_mint.key() == cp_amm.lp_mint,
+        seeds = [CpAmm::SEED, cp_amm.lp_mint.as_ref()],
+        bump = cp_amm.bump()
+    )]
+    cp_amm: Box<Account<'info, CpAmm>>,
+    lp_mint: Box<Account<'info, Mint>>,
+}
+
+pub(crate) fn handler(ctx: Context<ResyncCpAmmLpTokensSupply>) -> Result<()> {
+    ctx.accounts.cp_amm.assert_layout_version()?;
+    let lp_mint_supply = ctx.accounts.lp_mint.supply;
+    ctx.accounts.cp_amm.resync_lp_tokens_supply(lp_mint_supply)
+}
\ No newline at end of file
@@ -0,0 +1,29 @@
+use anchor_lang::Accounts;
+use anchor_lang::prelude::*;
+use crate::state::{AmmsConfigsManager, PoolCreatorPermit};
+
+#[derive(Accounts)]
+pub struct RevokePoolCreatorPermit<'info> {
+    #[account(
+        mut,
+        constraint = (authority.key() == amms_configs_manager.authority().key() || authority.key() == amms_configs_manager.head_authority().key())
+    )]
+    authority: Signer<'info>,
+    #[account(
+        seeds = [AmmsConfigsManager::SEED],
+        bump = amms_configs_manager.bump()
+    )]
+    amms_configs_manager: Account<'info, AmmsConfigsManager>,
+    #[account(
+        mut,
+        close = authority,
+        seeds = [PoolCreatorPermit::SEED, pool_creator_permit.amms_config().as_ref(), pool_creator_permit.creator().as_ref()],
+        bump = pool_creator_permit.bump()
+    )]
+    pool_creator_permit: Account<'info, PoolCreatorPermit>,
+}
+
+/// Revokes a `PoolCreatorPermit`, closing it to the authority. Pools already created by the creator stay untouched.
+pub(crate) fn handler(_ctx: Context<RevokePoolCreatorPermit>) -> Result<()> {
+    Ok(())
+}
\ No newline at end of file
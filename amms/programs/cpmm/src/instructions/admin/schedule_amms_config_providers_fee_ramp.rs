@@ -0,0 +1,27 @@
+use anchor_lang::Accounts;
+use anchor_lang::prelude::*;
+use crate::state::{AmmsConfig, AmmsConfigsManager};
+
+#[derive(Accounts)]
+pub struct ScheduleAmmsConfigProvidersFeeRamp<'info> {
+    #[account(
+        mut,
+        constraint = (authority.key() == amms_configs_manager.authority().key() || authority.key() == amms_configs_manager.head_authority().key())
+    )]
+    authority: Signer<'info>,
+    #[account(
+        seeds = [AmmsConfigsManager::SEED],
+        bump = amms_configs_manager.bump()
+    )]
+    amms_configs_manager: Account<'info, AmmsConfigsManager>,
+    #[account(
+        mut,
+        seeds = [AmmsConfig::SEED, amms_config.id.to_le_bytes().as_ref()],
+        bump = amms_config.bump()
+    )]
+    amms_config: Account<'info, AmmsConfig>,
+}
+
+pub(crate) fn handler(ctx: Context<ScheduleAmmsConfigProvidersFeeRamp>, target_providers_fee_rate_basis_points: u16, duration_slots: u64) -> Result<()> {
+    ctx.accounts.amms_config.schedule_providers_fee_ramp(Clock::get()?.slot, target_providers_fee_rate_basis_points, duration_slots)
+}
\ No newline at end of file
@@ -1,6 +1,7 @@
 use anchor_lang::Accounts;
 use anchor_lang::prelude::*;
 use crate::state::{AmmsConfig, AmmsConfigsManager};
+use crate::utils::validate_fee_authority;
 
 #[derive(Accounts)]
 pub struct UpdateAmmsConfigFeeAuthority<'info> {
@@ -20,10 +21,11 @@ pub struct UpdateAmmsConfigFeeAuthority<'info> {
         bump = amms_config.bump()
     )]
     amms_config: Account<'info, AmmsConfig>,
-    /// CHECK: New fee authority can be arbitrary
+    /// CHECK: New fee authority can be a wallet or a PDA of another program, validated in the handler
     new_fee_authority: UncheckedAccount<'info>,
 }
 pub(crate) fn handler(ctx: Context<UpdateAmmsConfigFeeAuthority>) -> Result<()> {
+    validate_fee_authority(&ctx.accounts.new_fee_authority)?;
     ctx.accounts.amms_config.update_fee_authority(
         ctx.accounts.new_fee_authority.key()
     );
@@ -0,0 +1,33 @@
+use anchor_lang::Accounts;
+use anchor_lang::prelude::*;
+use crate::state::{AmmsConfig, AmmsConfigsManager, FeeBounds};
+
+#[derive(Accounts)]
+pub struct UpdateAmmsConfigFeeBounds<'info> {
+    // Bounds constrain the fee rates set by the authority, so only the head authority may change them
+    #[account(
+        mut,
+        constraint = head_authority.key() == amms_configs_manager.head_authority().key()
+    )]
+    head_authority: Signer<'info>,
+    #[account(
+        seeds = [AmmsConfigsManager::SEED],
+        bump = amms_configs_manager.bump()
+    )]
+    amms_configs_manager: Account<'info, AmmsConfigsManager>,
+    #[account(
+        mut,
+        seeds = [AmmsConfig::SEED, amms_config.id.to_le_bytes().as_ref()],
+        bump = amms_config.bump()
+    )]
+    amms_config: Account<'info, AmmsConfig>,
+}
+
+pub(crate) fn handler(ctx: Context<UpdateAmmsConfigFeeBounds>, fee_bounds: Option<FeeBounds>) -> Result<()> {
+    let fee_bounds = fee_bounds.map(|fee_bounds| FeeBounds::try_new(
+        fee_bounds.min_providers_fee_rate_basis_points(),
+        fee_bounds.max_providers_fee_rate_basis_points(),
+        fee_bounds.max_protocol_fee_rate_basis_points()
+    )).transpose()?;
+    ctx.accounts.amms_config.update_fee_bounds(fee_bounds)
+}
\ No newline at end of file
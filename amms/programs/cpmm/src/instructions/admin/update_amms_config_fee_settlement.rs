@@ -0,0 +1,32 @@
+use anchor_lang::Accounts;
+use anchor_lang::prelude::*;
+use crate::state::{AmmsConfig, AmmsConfigsManager, FeeSettlement};
+
+#[derive(Accounts)]
+pub struct UpdateAmmsConfigFeeSettlement<'info> {
+    #[account(
+        mut,
+        constraint = (authority.key() == amms_configs_manager.authority().key() || authority.key() == amms_configs_manager.head_authority().key())
+    )]
+    authority: Signer<'info>,
+    #[account(
+        seeds = [AmmsConfigsManager::SEED],
+        bump = amms_configs_manager.bump()
+    )]
+    amms_configs_manager: Account<'info, AmmsConfigsManager>,
+    #[account(
+        mut,
+        seeds = [AmmsConfig::SEED, amms_config.id.to_le_bytes().as_ref()],
+        bump = amms_config.bump()
+    )]
+    amms_config: Account<'info, AmmsConfig>,
+}
+
+pub(crate) fn handler(ctx: Context<UpdateAmmsConfigFeeSettlement>, fee_settlement: Option<FeeSettlement>) -> Result<()> {
+    let fee_settlement = fee_settlement.map(|fee_settlement| FeeSettlement::try_new(
+        fee_settlement.settlement_mint().copied(),
+        fee_settlement.max_impact_basis_points()
+    )).transpose()?;
+    ctx.accounts.amms_config.update_fee_settlement(fee_settlement);
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,28 @@
+use anchor_lang::Accounts;
+use anchor_lang::prelude::*;
+use crate::state::{AmmsConfig, AmmsConfigsManager};
+
+#[derive(Accounts)]
+pub struct UpdateAmmsConfigLending<'info> {
+    // The lending adapter takes custody of pools liquidity, so only the head authority may change it
+    #[account(
+        mut,
+        constraint = head_authority.key() == amms_configs_manager.head_authority().key()
+    )]
+    head_authority: Signer<'info>,
+    #[account(
+        seeds = [AmmsConfigsManager::SEED],
+        bump = amms_configs_manager.bump()
+    )]
+    amms_configs_manager: Account<'info, AmmsConfigsManager>,
+    #[account(
+        mut,
+        seeds = [AmmsConfig::SEED, amms_config.id.to_le_bytes().as_ref()],
+        bump = amms_config.bump()
+    )]
+    amms_config: Account<'info, AmmsConfig>,
+}
+
+pub(crate) fn handler(ctx: Context<UpdateAmmsConfigLending>, lending_adapter: Option<Pubkey>, max_lend_out_basis_points: u16, lend_out_interest_rate_basis_points: u16) -> Result<()> {
+    ctx.accounts.amms_config.update_lending(lending_adapter, max_lend_out_basis_points, lend_out_interest_rate_basis_points)
+}
\ No newline at end of file
@@ -0,0 +1,33 @@
+use anchor_lang::Accounts;
+use anchor_lang::prelude::*;
+use crate::state::{AmmsConfig, AmmsConfigsManager, LoyaltyBoost};
+
+#[derive(Accounts)]
+pub struct UpdateAmmsConfigLoyaltyBoost<'info> {
+    #[account(
+        mut,
+        constraint = (authority.key() == amms_configs_manager.authority().key() || authority.key() == amms_configs_manager.head_authority().key())
+    )]
+    authority: Signer<'info>,
+    #[account(
+        seeds = [AmmsConfigsManager::SEED],
+        bump = amms_configs_manager.bump()
+    )]
+    amms_configs_manager: Account<'info, AmmsConfigsManager>,
+    #[account(
+        mut,
+        seeds = [AmmsConfig::SEED, amms_config.id.to_le_bytes().as_ref()],
+        bump = amms_config.bump()
+    )]
+    amms_config: Account<'info, AmmsConfig>,
+}
+
+pub(crate) fn handler(ctx: Context<UpdateAmmsConfigLoyaltyBoost>, loyalty_boost: Option<LoyaltyBoost>) -> Result<()> {
+    let loyalty_boost = loyalty_boost.map(|loyalty_boost| LoyaltyBoost::try_new(
+        loyalty_boost.min_hold_seconds(),
+        loyalty_boost.full_boost_hold_seconds(),
+        loyalty_boost.max_multiplier_basis_points()
+    )).transpose()?;
+    ctx.accounts.amms_config.update_loyalty_boost(loyalty_boost);
+    Ok(())
+}
\ No newline at end of file
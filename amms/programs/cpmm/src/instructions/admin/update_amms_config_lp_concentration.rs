@@ -0,0 +1,29 @@
+use anchor_lang::Accounts;
+use anchor_lang::prelude::*;
+use crate::state::{AmmsConfig, AmmsConfigsManager};
+
+#[derive(Accounts)]
+pub struct UpdateAmmsConfigLpConcentration<'info> {
+    #[account(
+        mut,
+        constraint = (authority.key() == amms_configs_manager.authority().key() || authority.key() == amms_configs_manager.head_authority().key())
+    )]
+    authority: Signer<'info>,
+    #[account(
+        seeds = [AmmsConfigsManager::SEED],
+        bump = amms_configs_manager.bump()
+    )]
+    amms_configs_manager: Account<'info, AmmsConfigsManager>,
+    #[account(
+        mut,
+        seeds = [AmmsConfig::SEED, amms_config.id.to_le_bytes().as_ref()],
+        bump = amms_config.bump()
+    )]
+    amms_config: Account<'info, AmmsConfig>,
+}
+
+/// Updates the share of a pool's LP tokens a single provide may mint before the pool raises its concentration
+/// flag, and the number of epochs after the pool's initialization during which such provides are rejected.
+pub(crate) fn handler(ctx: Context<UpdateAmmsConfigLpConcentration>, lp_concentration_share_basis_points: u16, lp_concentration_guard_epochs: u16) -> Result<()> {
+    ctx.accounts.amms_config.update_lp_concentration(lp_concentration_share_basis_points, lp_concentration_guard_epochs)
+}
\ No newline at end of file
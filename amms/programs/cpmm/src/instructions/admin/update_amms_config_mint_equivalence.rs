@@ -0,0 +1,45 @@
+use anchor_lang::Accounts;
+use anchor_lang::prelude::*;
+use crate::state::{AmmsConfig, AmmsConfigsManager, MintEquivalences};
+
+#[derive(Accounts)]
+pub struct UpdateAmmsConfigMintEquivalence<'info> {
+    #[account(
+        mut,
+        constraint = (authority.key() == amms_configs_manager.authority().key() || authority.key() == amms_configs_manager.head_authority().key())
+    )]
+    authority: Signer<'info>,
+    #[account(
+        seeds = [AmmsConfigsManager::SEED],
+        bump = amms_configs_manager.bump()
+    )]
+    amms_configs_manager: Account<'info, AmmsConfigsManager>,
+    #[account(
+        mut,
+        seeds = [AmmsConfig::SEED, amms_config.id.to_le_bytes().as_ref()],
+        bump = amms_config.bump()
+    )]
+    amms_config: Account<'info, AmmsConfig>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + MintEquivalences::INIT_SPACE,
+        seeds = [MintEquivalences::SEED, amms_config.key().as_ref()],
+        bump
+    )]
+    mint_equivalences: Box<Account<'info, MintEquivalences>>,
+    system_program: Program<'info, System>,
+}
+
+/// Assigns a mint to a group of equivalent mints of the config, or removes it with `group` of `None`.
+///
+/// The `MintEquivalences` of the config are created on the first update, after which every pool
+/// creation in the config must provide them and pairs of mints of the same group are rejected.
+pub(crate) fn handler(ctx: Context<UpdateAmmsConfigMintEquivalence>, mint: Pubkey, group: Option<u8>) -> Result<()> {
+    if !ctx.accounts.amms_config.has_mint_equivalences() {
+        let amms_config_key = ctx.accounts.amms_config.key();
+        ctx.accounts.mint_equivalences.initialize(amms_config_key, ctx.bumps.mint_equivalences);
+        ctx.accounts.amms_config.enable_mint_equivalences();
+    }
+    ctx.accounts.mint_equivalences.update(mint, group)
+}
\ No newline at end of file
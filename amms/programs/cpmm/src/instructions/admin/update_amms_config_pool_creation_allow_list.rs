@@ -0,0 +1,29 @@
+use anchor_lang::Accounts;
+use anchor_lang::prelude::*;
+use crate::state::{AmmsConfig, AmmsConfigsManager};
+
+#[derive(Accounts)]
+pub struct UpdateAmmsConfigPoolCreationAllowList<'info> {
+    #[account(
+        mut,
+        constraint = (authority.key() == amms_configs_manager.authority().key() || authority.key() == amms_configs_manager.head_authority().key())
+    )]
+    authority: Signer<'info>,
+    #[account(
+        seeds = [AmmsConfigsManager::SEED],
+        bump = amms_configs_manager.bump()
+    )]
+    amms_configs_manager: Account<'info, AmmsConfigsManager>,
+    #[account(
+        mut,
+        seeds = [AmmsConfig::SEED, amms_config.id.to_le_bytes().as_ref()],
+        bump = amms_config.bump()
+    )]
+    amms_config: Account<'info, AmmsConfig>,
+}
+
+/// Restricts pool creation in the config to creators holding a `PoolCreatorPermit`, or lifts the restriction.
+pub(crate) fn handler(ctx: Context<UpdateAmmsConfigPoolCreationAllowList>, is_pool_creation_allow_listed: bool) -> Result<()> {
+    ctx.accounts.amms_config.update_pool_creation_allow_list(is_pool_creation_allow_listed);
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,34 @@
+use anchor_lang::Accounts;
+use anchor_lang::prelude::*;
+use crate::state::{AmmsConfig, AmmsConfigsManager, PoolCreationPricing};
+
+#[derive(Accounts)]
+pub struct UpdateAmmsConfigPoolCreationPricing<'info> {
+    #[account(
+        mut,
+        constraint = (authority.key() == amms_configs_manager.authority().key() || authority.key() == amms_configs_manager.head_authority().key())
+    )]
+    authority: Signer<'info>,
+    #[account(
+        seeds = [AmmsConfigsManager::SEED],
+        bump = amms_configs_manager.bump()
+    )]
+    amms_configs_manager: Account<'info, AmmsConfigsManager>,
+    #[account(
+        mut,
+        seeds = [AmmsConfig::SEED, amms_config.id.to_le_bytes().as_ref()],
+        bump = amms_config.bump()
+    )]
+    amms_config: Account<'info, AmmsConfig>,
+}
+
+pub(crate) fn handler(ctx: Context<UpdateAmmsConfigPoolCreationPricing>, pool_creation_pricing: Option<PoolCreationPricing>) -> Result<()> {
+    let pool_creation_pricing = pool_creation_pricing.map(|pool_creation_pricing| PoolCreationPricing::try_new(
+        pool_creation_pricing.base_price_in_lamports(),
+        pool_creation_pricing.pools_per_step(),
+        pool_creation_pricing.step_price_in_lamports(),
+        pool_creation_pricing.max_price_in_lamports()
+    )).transpose()?;
+    ctx.accounts.amms_config.update_pool_creation_pricing(pool_creation_pricing);
+    Ok(())
+}
\ No newline at end of file
@@ -1,5 +1,6 @@
 use anchor_lang::Accounts;
 use anchor_lang::prelude::*;
+use crate::events::FeeRateDisabledEvent;
 use crate::state::{AmmsConfig, AmmsConfigsManager};
 
 #[derive(Accounts)]
@@ -23,5 +24,13 @@ pub struct UpdateAmmsConfigProtocolFeeRate<'info> {
 }
 
 pub(crate) fn handler(ctx: Context<UpdateAmmsConfigProtocolFeeRate>, new_protocol_fee_rate_basis_points: u16) -> Result<()> {
-    ctx.accounts.amms_config.update_protocol_fee_rate(new_protocol_fee_rate_basis_points)
+    ctx.accounts.amms_config.update_protocol_fee_rate(new_protocol_fee_rate_basis_points)?;
+    if new_protocol_fee_rate_basis_points == 0 {
+        emit!(FeeRateDisabledEvent {
+            amms_config: ctx.accounts.amms_config.key(),
+            cp_amm: None,
+            is_protocol_fee: true,
+        });
+    }
+    Ok(())
 }
\ No newline at end of file
@@ -0,0 +1,36 @@
+use anchor_lang::Accounts;
+use anchor_lang::prelude::*;
+use crate::error::ErrorCode;
+use crate::state::{AmmsConfig, AmmsConfigsManager};
+
+#[derive(Accounts)]
+pub struct UpdateAmmsConfigScreeningProgram<'info> {
+    // The screening program may reject withdrawals from the config's pools, so only the head authority may change it
+    #[account(
+        mut,
+        constraint = head_authority.key() == amms_configs_manager.head_authority().key()
+    )]
+    head_authority: Signer<'info>,
+    #[account(
+        seeds = [AmmsConfigsManager::SEED],
+        bump = amms_configs_manager.bump()
+    )]
+    amms_configs_manager: Account<'info, AmmsConfigsManager>,
+    #[account(
+        mut,
+        seeds = [AmmsConfig::SEED, amms_config.id.to_le_bytes().as_ref()],
+        bump = amms_config.bump()
+    )]
+    amms_config: Account<'info, AmmsConfig>,
+    #[account(
+        constraint = screening_program.executable && screening_program.key() != crate::ID @ ErrorCode::InvalidScreeningProgram
+    )]
+    /// CHECK: Program screening provides and withdrawals in the config's pools, omitted to stop screening
+    screening_program: Option<UncheckedAccount<'info>>,
+}
+
+pub(crate) fn handler(ctx: Context<UpdateAmmsConfigScreeningProgram>) -> Result<()> {
+    let screening_program = ctx.accounts.screening_program.as_ref().map(|screening_program| screening_program.key());
+    ctx.accounts.amms_config.update_screening_program(screening_program);
+    Ok(())
+}
\ No newline at end of file
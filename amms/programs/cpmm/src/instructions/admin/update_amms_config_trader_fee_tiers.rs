@@ -0,0 +1,42 @@
+use anchor_lang::Accounts;
+use anchor_lang::prelude::*;
+use crate::state::{AmmsConfig, AmmsConfigsManager, TraderFeeTier, TraderFeeTiers};
+
+#[derive(Accounts)]
+pub struct UpdateAmmsConfigTraderFeeTiers<'info> {
+    #[account(
+        mut,
+        constraint = (authority.key() == amms_configs_manager.authority().key() || authority.key() == amms_configs_manager.head_authority().key())
+    )]
+    authority: Signer<'info>,
+    #[account(
+        seeds = [AmmsConfigsManager::SEED],
+        bump = amms_configs_manager.bump()
+    )]
+    amms_configs_manager: Account<'info, AmmsConfigsManager>,
+    #[account(
+        seeds = [AmmsConfig::SEED, amms_config.id.to_le_bytes().as_ref()],
+        bump = amms_config.bump()
+    )]
+    amms_config: Account<'info, AmmsConfig>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + TraderFeeTiers::INIT_SPACE,
+        seeds = [TraderFeeTiers::SEED, amms_config.key().as_ref()],
+        bump
+    )]
+    trader_fee_tiers: Box<Account<'info, TraderFeeTiers>>,
+    system_program: Program<'info, System>,
+}
+
+/// Replaces the volume-based providers fee discount tiers of the config, an empty table disables them.
+///
+/// The `TraderFeeTiers` of the config are created on the first update.
+pub(crate) fn handler(ctx: Context<UpdateAmmsConfigTraderFeeTiers>, tiers: Vec<TraderFeeTier>) -> Result<()> {
+    if ctx.accounts.trader_fee_tiers.amms_config() == &Pubkey::default() {
+        let amms_config_key = ctx.accounts.amms_config.key();
+        ctx.accounts.trader_fee_tiers.initialize(amms_config_key, ctx.bumps.trader_fee_tiers);
+    }
+    ctx.accounts.trader_fee_tiers.update(&tiers)
+}
\ No newline at end of file
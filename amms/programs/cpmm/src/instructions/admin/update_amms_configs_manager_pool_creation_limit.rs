@@ -0,0 +1,20 @@
+use anchor_lang::prelude::*;
+use crate::state::AmmsConfigsManager;
+
+#[derive(Accounts)]
+pub struct UpdateAmmsConfigsManagerPoolCreationLimit<'info> {
+    #[account(
+        mut,
+        constraint = (authority.key() == amms_configs_manager.authority().key() || authority.key() == amms_configs_manager.head_authority().key())
+    )]
+    authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [AmmsConfigsManager::SEED],
+        bump = amms_configs_manager.bump()
+    )]
+    amms_configs_manager: Account<'info, AmmsConfigsManager>,
+}
+pub(crate) fn handler(ctx: Context<UpdateAmmsConfigsManagerPoolCreationLimit>, pool_creation_window_slots: u64, max_pools_per_window: u16) -> Result<()> {
+    ctx.accounts.amms_configs_manager.update_pool_creation_limit(pool_creation_window_slots, max_pools_per_window)
+}
\ No newline at end of file
@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+use crate::state::{AmmsConfig, AmmsConfigsManager, SwapOriginPolicy, VersionedAccount};
+
+#[derive(Accounts)]
+pub struct UpdateAmmsConfigsManagerSwapOriginPolicy<'info> {
+    #[account(
+        constraint = head_authority.key() == amms_configs_manager.head_authority().key()
+    )]
+    head_authority: Signer<'info>,
+    #[account(
+        seeds = [AmmsConfigsManager::SEED],
+        bump = amms_configs_manager.bump()
+    )]
+    amms_configs_manager: Account<'info, AmmsConfigsManager>,
+}
+
+/// Restricts the swaps in the pools of every `AmmsConfig` passed as writable remaining accounts to direct or CPI
+/// invocations for `policy_slots` from the current slot, e.g. to stop an exploit running through a malicious program
+/// without pausing every pool.
+/// An `Unrestricted` policy or zero slots lift the restriction.
+///
+/// The policy is stored on the configs, since every swap already requires its pool's config.
+pub(crate) fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, UpdateAmmsConfigsManagerSwapOriginPolicy<'info>>, swap_origin_policy: SwapOriginPolicy, policy_slots: u64) -> Result<()> {
+    let slot = Clock::get()?.slot;
+    for amms_config_info in ctx.remaining_accounts {
+        require!(amms_config_info.is_writable, ErrorCode::AccountNotMutable);
+        let mut amms_config = Account::<AmmsConfig>::try_from(amms_config_info)?;
+        amms_config.assert_layout_version()?;
+        amms_config.update_swap_origin_policy(swap_origin_policy, policy_slots, slot)?;
+        amms_config.exit(&crate::ID)?;
+    }
+    Ok(())
+}
\ No newline at end of file
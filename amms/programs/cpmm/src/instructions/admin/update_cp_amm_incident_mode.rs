@@ -0,0 +1,29 @@
+use anchor_lang::Accounts;
+use anchor_lang::prelude::*;
+use crate::state::{AmmsConfigsManager, cp_amm::CpAmm, VersionedAccount};
+
+#[derive(Accounts)]
+pub struct UpdateCpAmmIncidentMode<'info> {
+    #[account(
+        mut,
+        constraint = (authority.key() == amms_configs_manager.authority().key() || authority.key() == amms_configs_manager.head_authority().key())
+    )]
+    authority: Signer<'info>,
+    #[account(
+        seeds = [AmmsConfigsManager::SEED],
+        bump = amms_configs_manager.bump()
+    )]
+    amms_configs_manager: Account<'info, AmmsConfigsManager>,
+    #[account(
+        mut,
+        seeds = [CpAmm::SEED, cp_amm.lp_mint.as_ref()],
+        bump = cp_amm.bump()
+    )]
+    cp_amm: Box<Account<'info, CpAmm>>,
+}
+
+pub(crate) fn handler(ctx: Context<UpdateCpAmmIncidentMode>, is_incident_limited: bool) -> Result<()> {
+    ctx.accounts.cp_amm.assert_layout_version()?;
+    ctx.accounts.cp_amm.update_incident_mode(is_incident_limited);
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,36 @@
+use anchor_lang::Accounts;
+use anchor_lang::prelude::*;
+use crate::error::ErrorCode;
+use crate::state::{AmmsConfigsManager, cp_amm::CpAmm, VersionedAccount};
+
+#[derive(Accounts)]
+pub struct UpdateCpAmmLiquiditySubscriber<'info> {
+    #[account(
+        mut,
+        constraint = (authority.key() == amms_configs_manager.authority().key() || authority.key() == amms_configs_manager.head_authority().key())
+    )]
+    authority: Signer<'info>,
+    #[account(
+        seeds = [AmmsConfigsManager::SEED],
+        bump = amms_configs_manager.bump()
+    )]
+    amms_configs_manager: Account<'info, AmmsConfigsManager>,
+    #[account(
+        mut,
+        seeds = [CpAmm::SEED, cp_amm.lp_mint.as_ref()],
+        bump = cp_amm.bump()
+    )]
+    cp_amm: Box<Account<'info, CpAmm>>,
+    #[account(
+        constraint = liquidity_subscriber.executable && liquidity_subscriber.key() != crate::ID @ ErrorCode::InvalidLiquiditySubscriber
+    )]
+    /// CHECK: Program notified about liquidity changes of the CpAmm, omitted to clear the subscriber
+    liquidity_subscriber: Option<UncheckedAccount<'info>>,
+}
+
+pub(crate) fn handler(ctx: Context<UpdateCpAmmLiquiditySubscriber>) -> Result<()> {
+    ctx.accounts.cp_amm.assert_layout_version()?;
+    let liquidity_subscriber = ctx.accounts.liquidity_subscriber.as_ref().map(|liquidity_subscriber| liquidity_subscriber.key());
+    ctx.accounts.cp_amm.update_liquidity_subscriber(liquidity_subscriber);
+    Ok(())
+}
\ No newline at end of file
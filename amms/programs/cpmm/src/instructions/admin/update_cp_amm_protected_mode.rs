@@ -0,0 +1,31 @@
+use anchor_lang::Accounts;
+use anchor_lang::prelude::*;
+use crate::state::{AmmsConfigsManager, cp_amm::CpAmm, VersionedAccount};
+
+#[derive(Accounts)]
+pub struct UpdateCpAmmProtectedMode<'info> {
+    #[account(
+        mut,
+        constraint = (authority.key() == amms_configs_manager.authority().key() || authority.key() == amms_configs_manager.head_authority().key())
+    )]
+    authority: Signer<'info>,
+    #[account(
+        seeds = [AmmsConfigsManager::SEED],
+        bump = amms_configs_manager.bump()
+    )]
+    amms_configs_manager: Account<'info, AmmsConfigsManager>,
+    #[account(
+        mut,
+        seeds = [CpAmm::SEED, cp_amm.lp_mint.as_ref()],
+        bump = cp_amm.bump()
+    )]
+    cp_amm: Box<Account<'info, CpAmm>>,
+}
+
+/// Puts the pool in protected mode for `protected_slots` from the current slot, in which swaps are only
+/// accepted from routers approved in the `RouterRegistry`, e.g. to shield a launch from toxic order flow.
+/// Zero slots end the protected mode.
+pub(crate) fn handler(ctx: Context<UpdateCpAmmProtectedMode>, protected_slots: u64) -> Result<()> {
+    ctx.accounts.cp_amm.assert_layout_version()?;
+    ctx.accounts.cp_amm.update_protected_mode(protected_slots, Clock::get()?.slot)
+}
\ No newline at end of file
@@ -0,0 +1,45 @@
+use anchor_lang::Accounts;
+use anchor_lang::prelude::*;
+use crate::events::FeeRateDisabledEvent;
+use crate::state::{AmmsConfig, AmmsConfigsManager, cp_amm::CpAmm, VersionedAccount};
+
+#[derive(Accounts)]
+pub struct UpdateCpAmmProtocolFeeOverride<'info> {
+    // Overrides are negotiated per pool outside of the config fee bounds, so only the head authority may set them
+    #[account(
+        mut,
+        constraint = head_authority.key() == amms_configs_manager.head_authority().key()
+    )]
+    head_authority: Signer<'info>,
+    #[account(
+        seeds = [AmmsConfigsManager::SEED],
+        bump = amms_configs_manager.bump()
+    )]
+    amms_configs_manager: Account<'info, AmmsConfigsManager>,
+    #[account(
+        seeds = [AmmsConfig::SEED, amms_config.id.to_le_bytes().as_ref()],
+        bump = amms_config.bump()
+    )]
+    amms_config: Box<Account<'info, AmmsConfig>>,
+    #[account(
+        mut,
+        constraint = amms_config.key() == cp_amm.amms_config().key(),
+        seeds = [CpAmm::SEED, cp_amm.lp_mint.as_ref()],
+        bump = cp_amm.bump()
+    )]
+    cp_amm: Box<Account<'info, CpAmm>>,
+}
+
+pub(crate) fn handler(ctx: Context<UpdateCpAmmProtocolFeeOverride>, protocol_fee_override_basis_points: Option<u16>) -> Result<()> {
+    ctx.accounts.cp_amm.assert_layout_version()?;
+    let providers_fee_rate_basis_points = ctx.accounts.amms_config.providers_fee_rate_basis_points_at(Clock::get()?.slot);
+    ctx.accounts.cp_amm.update_protocol_fee_override(protocol_fee_override_basis_points, providers_fee_rate_basis_points)?;
+    if protocol_fee_override_basis_points == Some(0) {
+        emit!(FeeRateDisabledEvent {
+            amms_config: ctx.accounts.amms_config.key(),
+            cp_amm: Some(ctx.accounts.cp_amm.key()),
+            is_protocol_fee: true,
+        });
+    }
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,37 @@
+use anchor_lang::Accounts;
+use anchor_lang::prelude::*;
+use crate::error::ErrorCode;
+use crate::state::{AmmsConfigsManager, cp_amm::CpAmm, VersionedAccount};
+use crate::utils::oracle::PYTH_RECEIVER_PROGRAM_ID;
+
+#[derive(Accounts)]
+pub struct UpdateCpAmmUsdOracle<'info> {
+    #[account(
+        mut,
+        constraint = (authority.key() == amms_configs_manager.authority().key() || authority.key() == amms_configs_manager.head_authority().key())
+    )]
+    authority: Signer<'info>,
+    #[account(
+        seeds = [AmmsConfigsManager::SEED],
+        bump = amms_configs_manager.bump()
+    )]
+    amms_configs_manager: Account<'info, AmmsConfigsManager>,
+    #[account(
+        mut,
+        seeds = [CpAmm::SEED, cp_amm.lp_mint.as_ref()],
+        bump = cp_amm.bump()
+    )]
+    cp_amm: Box<Account<'info, CpAmm>>,
+    #[account(
+        owner = PYTH_RECEIVER_PROGRAM_ID @ ErrorCode::InvalidCpAmmUsdOracleOwner
+    )]
+    /// CHECK: Pyth price update account providing the USD price of the quote token, omitted to clear the oracle
+    usd_oracle: Option<UncheckedAccount<'info>>,
+}
+
+pub(crate) fn handler(ctx: Context<UpdateCpAmmUsdOracle>) -> Result<()> {
+    ctx.accounts.cp_amm.assert_layout_version()?;
+    let usd_oracle = ctx.accounts.usd_oracle.as_ref().map(|usd_oracle| usd_oracle.key());
+    ctx.accounts.cp_amm.update_usd_oracle(usd_oracle);
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,28 @@
+use anchor_lang::Accounts;
+use anchor_lang::prelude::*;
+use crate::state::{AmmsConfigsManager, RouterRegistry};
+
+#[derive(Accounts)]
+pub struct UpdateRouterRegistry<'info> {
+    // Approved routers bypass the protected mode of every pool, so only the head authority may change them
+    #[account(
+        constraint = head_authority.key() == amms_configs_manager.head_authority().key()
+    )]
+    head_authority: Signer<'info>,
+    #[account(
+        seeds = [AmmsConfigsManager::SEED],
+        bump = amms_configs_manager.bump()
+    )]
+    amms_configs_manager: Account<'info, AmmsConfigsManager>,
+    #[account(
+        mut,
+        seeds = [RouterRegistry::SEED],
+        bump = router_registry.bump()
+    )]
+    router_registry: Box<Account<'info, RouterRegistry>>,
+}
+
+/// Approves a router program for swaps in pools in protected mode, or revokes its approval.
+pub(crate) fn handler(ctx: Context<UpdateRouterRegistry>, router: Pubkey, is_approved: bool) -> Result<()> {
+    ctx.accounts.router_registry.update(router, is_approved)
+}
\ No newline at end of file
@@ -0,0 +1,326 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions as sysvar_instructions;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::error::ErrorCode;
+use crate::events::SwapEvent;
+use crate::liquidity_hook::{LiquidityDeltaKind, LiquiditySnapshot};
+use crate::state::{AmmsConfig, cp_amm::{CpAmm, EventRing, SwapPayload}, VersionedAccount};
+use crate::utils::oracle::read_configured_usd_price;
+use crate::utils::{record_liquidity_change, validate_swap_origin, LiquidityChange};
+use crate::utils::liquidity_hook_instructions::notify_liquidity_subscriber;
+use crate::utils::token_instructions::TransferTokensInstruction;
+
+#[derive(Accounts)]
+pub struct ArbTwoPools<'info>{
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(
+        constraint = mint_a.key() != mint_b.key()
+    )]
+    pub mint_a: Box<InterfaceAccount<'info, Mint>>,
+    pub mint_b: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_a,
+        associated_token::authority = signer,
+        associated_token::token_program = a_token_program
+    )]
+    pub signer_a_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = signer,
+        associated_token::mint = mint_b,
+        associated_token::authority = signer,
+        associated_token::token_program = b_token_program
+    )]
+    pub signer_b_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        seeds = [AmmsConfig::SEED, first_amms_config.id.to_le_bytes().as_ref()],
+        bump = first_amms_config.bump()
+    )]
+    pub first_amms_config: Box<Account<'info, AmmsConfig>>,
+
+    #[account(
+        mut,
+        constraint = first_cp_amm.is_launched(),
+        constraint = first_amms_config.key() == first_cp_amm.amms_config().key(),
+        constraint = is_pool_of_mints(&first_cp_amm, &mint_a.key(), &mint_b.key()) @ ErrorCode::ArbPoolsMismatch,
+        constraint = first_cp_amm_base_vault.key() == first_cp_amm.base_vault().key(),
+        constraint = first_cp_amm_quote_vault.key() == first_cp_amm.quote_vault().key(),
+        seeds = [CpAmm::SEED, first_cp_amm.lp_mint.as_ref()],
+        bump = first_cp_amm.bump()
+    )]
+    pub first_cp_amm: Box<Account<'info, CpAmm>>,
+
+    #[account(
+        mut,
+        seeds = [CpAmm::VAULT_SEED, first_cp_amm.key().as_ref(), first_cp_amm.base_mint().as_ref()],
+        bump = first_cp_amm.base_vault_bump()
+    )]
+    pub first_cp_amm_base_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [CpAmm::VAULT_SEED, first_cp_amm.key().as_ref(), first_cp_amm.quote_mint().as_ref()],
+        bump = first_cp_amm.quote_vault_bump()
+    )]
+    pub first_cp_amm_quote_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        seeds = [AmmsConfig::SEED, second_amms_config.id.to_le_bytes().as_ref()],
+        bump = second_amms_config.bump()
+    )]
+    pub second_amms_config: Box<Account<'info, AmmsConfig>>,
+
+    #[account(
+        mut,
+        constraint = second_cp_amm.is_launched(),
+        constraint = second_cp_amm.key() != first_cp_amm.key() @ ErrorCode::ArbPoolsMismatch,
+        constraint = second_amms_config.key() == second_cp_amm.amms_config().key(),
+        constraint = is_pool_of_mints(&second_cp_amm, &mint_a.key(), &mint_b.key()) @ ErrorCode::ArbPoolsMismatch,
+        constraint = second_cp_amm_base_vault.key() == second_cp_amm.base_vault().key(),
+        constraint = second_cp_amm_quote_vault.key() == second_cp_amm.quote_vault().key(),
+        seeds = [CpAmm::SEED, second_cp_amm.lp_mint.as_ref()],
+        bump = second_cp_amm.bump()
+    )]
+    pub second_cp_amm: Box<Account<'info, CpAmm>>,
+
+    #[account(
+        mut,
+        seeds = [CpAmm::VAULT_SEED, second_cp_amm.key().as_ref(), second_cp_amm.base_mint().as_ref()],
+        bump = second_cp_amm.base_vault_bump()
+    )]
+    pub second_cp_amm_base_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [CpAmm::VAULT_SEED, second_cp_amm.key().as_ref(), second_cp_amm.quote_mint().as_ref()],
+        bump = second_cp_amm.quote_vault_bump()
+    )]
+    pub second_cp_amm_quote_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        constraint = first_cp_amm.usd_oracle() == Some(&first_usd_oracle.key()) @ ErrorCode::InvalidCpAmmUsdOracle
+    )]
+    /// CHECK: Price update account matching the first CpAmm USD oracle, required only if the oracle is set
+    pub first_usd_oracle: Option<UncheckedAccount<'info>>,
+
+    #[account(
+        constraint = second_cp_amm.usd_oracle() == Some(&second_usd_oracle.key()) @ ErrorCode::InvalidCpAmmUsdOracle
+    )]
+    /// CHECK: Price update account matching the second CpAmm USD oracle, required only if the oracle is set
+    pub second_usd_oracle: Option<UncheckedAccount<'info>>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub a_token_program: Interface<'info, TokenInterface>,
+    pub b_token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+
+    #[account(address = sysvar_instructions::ID)]
+    /// CHECK: The instructions sysvar, identifying the origin of the swap while a swap origin policy is in force
+    pub instructions_sysvar: Option<UncheckedAccount<'info>>,
+
+    #[account(
+        mut,
+        constraint = first_event_ring.load()?.cp_amm() == &first_cp_amm.key()
+    )]
+    pub first_event_ring: Option<AccountLoader<'info, EventRing>>,
+
+    #[account(
+        mut,
+        constraint = second_event_ring.load()?.cp_amm() == &second_cp_amm.key()
+    )]
+    pub second_event_ring: Option<AccountLoader<'info, EventRing>>,
+}
+
+/// Arbitrage outcome returned to the caller as instruction return data.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct ArbResult {
+    /// The amount of B tokens received from the first pool and swapped in the second one.
+    pub intermediate_amount: u64,
+
+    /// The increase of the signer's A token balance.
+    pub profit: u64,
+}
+
+/// Checks if the `CpAmm` trades exactly the given pair of mints, in any orientation.
+fn is_pool_of_mints(cp_amm: &CpAmm, mint_a: &Pubkey, mint_b: &Pubkey) -> bool {
+    (cp_amm.base_mint() == mint_a && cp_amm.quote_mint() == mint_b) || (cp_amm.base_mint() == mint_b && cp_amm.quote_mint() == mint_a)
+}
+
+/// Swaps `swap_amount` A tokens to B in the first pool and all received B tokens back to A in the second pool.
+///
+/// No per-leg slippage is checked; instead the signer's A balance must grow by at least `min_profit`,
+/// otherwise the whole transaction fails. Both pools charge their regular fees, so price misalignments
+/// between fee tiers are closed for the benefit of their providers.
+///
+/// Both liquidity subscribers, if any, are notified with the same remaining accounts. The swap origin
+/// policy applies to the arbitrage as a whole, see `validate_swap_origin`.
+///
+/// With `first_event_ring` or `second_event_ring` passed, a record of the swap is appended to the event ring of the pool.
+pub(crate) fn handler<'info>(ctx: Context<'_, '_, '_, 'info, ArbTwoPools<'info>>, swap_amount: u64, min_profit: u64) -> Result<ArbResult> {
+    ctx.accounts.first_cp_amm.assert_layout_version()?;
+    ctx.accounts.second_cp_amm.assert_layout_version()?;
+    ctx.accounts.first_cp_amm.check_vault_balances(ctx.accounts.first_cp_amm_base_vault.amount, ctx.accounts.first_cp_amm_quote_vault.amount)?;
+    ctx.accounts.second_cp_amm.check_vault_balances(ctx.accounts.second_cp_amm_base_vault.amount, ctx.accounts.second_cp_amm_quote_vault.amount)?;
+    let clock = Clock::get()?;
+    require!(
+        ctx.accounts.first_cp_amm.is_market_open(clock.unix_timestamp) && ctx.accounts.second_cp_amm.is_market_open(clock.unix_timestamp),
+        ErrorCode::MarketClosed
+    );
+    validate_swap_origin(&ctx.accounts.first_amms_config, ctx.accounts.instructions_sysvar.as_deref(), clock.slot)?;
+    validate_swap_origin(&ctx.accounts.second_amms_config, ctx.accounts.instructions_sysvar.as_deref(), clock.slot)?;
+    let first_liquidity_snapshot = LiquiditySnapshot::of(&ctx.accounts.first_cp_amm);
+    let second_liquidity_snapshot = LiquiditySnapshot::of(&ctx.accounts.second_cp_amm);
+    let first_is_in_out = ctx.accounts.first_cp_amm.base_mint() == &ctx.accounts.mint_a.key();
+    let second_is_in_out = ctx.accounts.second_cp_amm.base_mint() == &ctx.accounts.mint_b.key();
+    let a_balance_before = ctx.accounts.signer_a_account.amount;
+
+    // A -> B in the first pool
+    let first_in_transfer_instruction = Box::new(ctx.accounts.get_first_in_transfer_instruction(swap_amount, first_is_in_out)?);
+    let first_in_amount = first_in_transfer_instruction.get_amount_after_fee();
+    let (first_swap_payload, first_fee_rates) = ctx.accounts.get_first_swap_payload(first_in_amount, first_is_in_out, clock.slot)?;
+    let first_out_transfer_instruction = Box::new(ctx.accounts.get_first_out_transfer_instruction(first_swap_payload.amount_to_withdraw(), first_is_in_out)?);
+    let intermediate_amount = first_out_transfer_instruction.get_amount_after_fee();
+
+    first_in_transfer_instruction.execute(None)?;
+    let first_cp_amm_seeds = ctx.accounts.first_cp_amm.seeds();
+    first_out_transfer_instruction.execute(Some(&[&first_cp_amm_seeds]))?;
+    ctx.accounts.signer_b_account.reload()?;
+
+    // B -> A in the second pool
+    let second_in_transfer_instruction = Box::new(ctx.accounts.get_second_in_transfer_instruction(intermediate_amount, second_is_in_out)?);
+    let second_in_amount = second_in_transfer_instruction.get_amount_after_fee();
+    let (second_swap_payload, second_fee_rates) = ctx.accounts.get_second_swap_payload(second_in_amount, second_is_in_out, clock.slot)?;
+    let second_out_transfer_instruction = Box::new(ctx.accounts.get_second_out_transfer_instruction(second_swap_payload.amount_to_withdraw(), second_is_in_out)?);
+
+    second_in_transfer_instruction.execute(None)?;
+    let second_cp_amm_seeds = ctx.accounts.second_cp_amm.seeds();
+    second_out_transfer_instruction.execute(Some(&[&second_cp_amm_seeds]))?;
+
+    ctx.accounts.signer_a_account.reload()?;
+    let profit = ctx.accounts.signer_a_account.amount.saturating_sub(a_balance_before);
+    require!(profit > 0 && profit >= min_profit, ErrorCode::ArbProfitBelowMinimum);
+
+    let signer = ctx.accounts.signer.key();
+    let first_liquidity_change = LiquidityChange::swap(first_is_in_out, signer, first_in_amount, first_swap_payload.amount_to_withdraw());
+    let second_liquidity_change = LiquidityChange::swap(second_is_in_out, signer, second_in_amount, second_swap_payload.amount_to_withdraw());
+    emit!(SwapEvent {
+        cp_amm: ctx.accounts.first_cp_amm.key(),
+        signer,
+        is_in_out: first_is_in_out,
+        swap_amount: first_in_amount,
+        swap_result: first_swap_payload.amount_to_withdraw(),
+        price_impact_basis_points: first_swap_payload.price_impact_basis_points(),
+        providers_fee_rate_basis_points: first_fee_rates.0,
+        protocol_fee_rate_basis_points: first_fee_rates.1,
+        providers_fee_amount: first_swap_payload.providers_fee_amount(),
+        protocol_fee_amount: first_swap_payload.protocol_fee_amount(),
+        creator_fee_amount: first_swap_payload.creator_fee_amount(),
+    });
+    emit!(SwapEvent {
+        cp_amm: ctx.accounts.second_cp_amm.key(),
+        signer,
+        is_in_out: second_is_in_out,
+        swap_amount: second_in_amount,
+        swap_result: second_swap_payload.amount_to_withdraw(),
+        price_impact_basis_points: second_swap_payload.price_impact_basis_points(),
+        providers_fee_rate_basis_points: second_fee_rates.0,
+        protocol_fee_rate_basis_points: second_fee_rates.1,
+        providers_fee_amount: second_swap_payload.providers_fee_amount(),
+        protocol_fee_amount: second_swap_payload.protocol_fee_amount(),
+        creator_fee_amount: second_swap_payload.creator_fee_amount(),
+    });
+
+    ctx.accounts.first_cp_amm.record_slot_start_price(clock.slot);
+    ctx.accounts.second_cp_amm.record_slot_start_price(clock.slot);
+    ctx.accounts.first_cp_amm.swap(first_swap_payload)?;
+    ctx.accounts.second_cp_amm.swap(second_swap_payload)?;
+    record_liquidity_change(&mut ctx.accounts.first_cp_amm, &ctx.accounts.first_amms_config, ctx.accounts.first_event_ring.as_ref(), first_liquidity_change, clock.slot)?;
+    record_liquidity_change(&mut ctx.accounts.second_cp_amm, &ctx.accounts.second_amms_config, ctx.accounts.second_event_ring.as_ref(), second_liquidity_change, clock.slot)?;
+    notify_liquidity_subscriber(&ctx.accounts.first_cp_amm, ctx.remaining_accounts, LiquidityDeltaKind::Swap, first_liquidity_snapshot)?;
+    notify_liquidity_subscriber(&ctx.accounts.second_cp_amm, ctx.remaining_accounts, LiquidityDeltaKind::Swap, second_liquidity_snapshot)?;
+
+    Ok(ArbResult {
+        intermediate_amount,
+        profit,
+    })
+}
+
+impl<'info> ArbTwoPools<'info>{
+    /// Returns the swap payload of the first leg with the applied providers and protocol fee rates.
+    #[inline(never)]
+    fn get_first_swap_payload(&self, in_amount: u64, is_in_out: bool, slot: u64) -> Result<(SwapPayload, (u16, u16))>{
+        let quote_decimals = if is_in_out { self.mint_b.decimals } else { self.mint_a.decimals };
+        let quote_usd_price = read_configured_usd_price(self.first_cp_amm.usd_oracle(), self.first_usd_oracle.as_deref(), quote_decimals)?;
+        require!(!self.first_cp_amm.is_protected(slot), ErrorCode::ProtectedModeRouterRequired);
+        let fee_rates = (
+            self.first_amms_config.providers_fee_rate_basis_points_at(slot),
+            self.first_cp_amm.protocol_fee_rate_basis_points(self.first_amms_config.protocol_fee_rate_basis_points())
+        );
+        // The profit assertion replaces the per-leg slippage check
+        let swap_payload = self.first_cp_amm.get_swap_payload(in_amount, 1, u64::MAX, fee_rates.0, fee_rates.1, is_in_out, quote_usd_price, self.first_amms_config.swap_constant_product_tolerance(), slot)?;
+        Ok((swap_payload, fee_rates))
+    }
+    /// Returns the swap payload of the second leg with the applied providers and protocol fee rates.
+    #[inline(never)]
+    fn get_second_swap_payload(&self, in_amount: u64, is_in_out: bool, slot: u64) -> Result<(SwapPayload, (u16, u16))>{
+        let quote_decimals = if is_in_out { self.mint_a.decimals } else { self.mint_b.decimals };
+        let quote_usd_price = read_configured_usd_price(self.second_cp_amm.usd_oracle(), self.second_usd_oracle.as_deref(), quote_decimals)?;
+        require!(!self.second_cp_amm.is_protected(slot), ErrorCode::ProtectedModeRouterRequired);
+        let fee_rates = (
+            self.second_amms_config.providers_fee_rate_basis_points_at(slot),
+            self.second_cp_amm.protocol_fee_rate_basis_points(self.second_amms_config.protocol_fee_rate_basis_points())
+        );
+        let swap_payload = self.second_cp_amm.get_swap_payload(in_amount, 1, u64::MAX, fee_rates.0, fee_rates.1, is_in_out, quote_usd_price, self.second_amms_config.swap_constant_product_tolerance(), slot)?;
+        Ok((swap_payload, fee_rates))
+    }
+    #[inline(never)]
+    fn get_first_in_transfer_instruction(&self, amount: u64, is_in_out: bool) -> Result<TransferTokensInstruction<'_, '_, '_, 'info>>{
+        TransferTokensInstruction::try_new(
+            amount,
+            &self.mint_a,
+            &self.signer_a_account,
+            self.signer.to_account_info(),
+            if is_in_out { &self.first_cp_amm_base_vault } else { &self.first_cp_amm_quote_vault },
+            &self.a_token_program
+        )
+    }
+    #[inline(never)]
+    fn get_first_out_transfer_instruction(&self, amount: u64, is_in_out: bool) -> Result<TransferTokensInstruction<'_, '_, '_, 'info>>{
+        TransferTokensInstruction::try_new(
+            amount,
+            &self.mint_b,
+            if is_in_out { &self.first_cp_amm_quote_vault } else { &self.first_cp_amm_base_vault },
+            self.first_cp_amm.to_account_info(),
+            &self.signer_b_account,
+            &self.b_token_program
+        )
+    }
+    #[inline(never)]
+    fn get_second_in_transfer_instruction(&self, amount: u64, is_in_out: bool) -> Result<TransferTokensInstruction<'_, '_, '_, 'info>>{
+        TransferTokensInstruction::try_new(
+            amount,
+            &self.mint_b,
+            &self.signer_b_account,
+            self.signer.to_account_info(),
+            if is_in_out { &self.second_cp_amm_base_vault } else { &self.second_cp_amm_quote_vault },
+            &self.b_token_program
+        )
+    }
+    #[inline(never)]
+    fn get_second_out_transfer_instruction(&self, amount: u64, is_in_out: bool) -> Result<TransferTokensInstruction<'_, '_, '_, 'info>>{
+        TransferTokensInstruction::try_new(
+            amount,
+            &self.mint_a,
+            if is_in_out { &self.second_cp_amm_quote_vault } else { &self.second_cp_amm_base_vault },
+            self.second_cp_amm.to_account_info(),
+            &self.signer_a_account,
+            &self.a_token_program
+        )
+    }
+}
\ No newline at end of file
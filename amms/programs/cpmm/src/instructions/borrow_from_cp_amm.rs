@@ -0,0 +1,94 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::error::ErrorCode;
+use crate::state::{AmmsConfig, VersionedAccount};
+use crate::state::cp_amm::{CpAmm, LendOutLoan};
+use crate::utils::token_instructions::TransferTokensInstruction;
+
+#[derive(Accounts)]
+pub struct BorrowFromCpAmm<'info> {
+    #[account(
+        mut,
+        constraint = amms_config.lending_adapter() == Some(&lending_adapter.key()) @ ErrorCode::InvalidLendingAdapter
+    )]
+    pub lending_adapter: Signer<'info>,
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::token_program = token_program
+    )]
+    pub lending_adapter_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        seeds = [AmmsConfig::SEED, amms_config.id.to_le_bytes().as_ref()],
+        bump = amms_config.bump()
+    )]
+    pub amms_config: Box<Account<'info, AmmsConfig>>,
+
+    #[account(
+        mut,
+        constraint = cp_amm.is_launched(),
+        constraint = amms_config.key() == cp_amm.amms_config().key(),
+        constraint = (mint.key() == cp_amm.base_mint().key() && cp_amm_vault.key() == cp_amm.base_vault().key()) ||
+            (mint.key() == cp_amm.quote_mint().key() && cp_amm_vault.key() == cp_amm.quote_vault().key()),
+        seeds = [CpAmm::SEED, cp_amm.lp_mint.as_ref()],
+        bump = cp_amm.bump()
+    )]
+    pub cp_amm: Box<Account<'info, CpAmm>>,
+
+    #[account(mut)]
+    pub cp_amm_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = lending_adapter,
+        space = 8 + LendOutLoan::INIT_SPACE,
+        seeds = [LendOutLoan::SEED, cp_amm.key().as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub lend_out_loan: Box<Account<'info, LendOutLoan>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Lends out idle pool liquidity to the configured lending adapter against its `LendOutLoan`.
+pub(crate) fn handler(ctx: Context<BorrowFromCpAmm>, amount: u64) -> Result<()> {
+    ctx.accounts.cp_amm.assert_layout_version()?;
+    ctx.accounts.accrue_interest(ctx.bumps.lend_out_loan)?;
+    let is_base = ctx.accounts.mint.key() == ctx.accounts.cp_amm.base_mint().key();
+    let lend_out_payload = ctx.accounts.cp_amm.get_borrow_payload(amount, is_base, ctx.accounts.amms_config.max_lend_out_basis_points())?;
+    ctx.accounts.lend_out_loan.borrow(amount)?;
+
+    let transfer_instruction = Box::new(ctx.accounts.get_transfer_instruction(amount)?);
+    let cp_amm_seeds = ctx.accounts.cp_amm.seeds();
+    let transfer_instruction_seeds: &[&[&[u8]]] = &[&cp_amm_seeds];
+    transfer_instruction.execute(Some(transfer_instruction_seeds))?;
+
+    ctx.accounts.cp_amm.record_slot_start_price(Clock::get()?.slot);
+    ctx.accounts.cp_amm.lend_out(lend_out_payload);
+    Ok(())
+}
+
+impl<'info> BorrowFromCpAmm<'info> {
+    fn accrue_interest(&mut self, lend_out_loan_bump: u8) -> Result<()> {
+        if !self.lend_out_loan.is_initialized() {
+            self.lend_out_loan.initialize(self.cp_amm.key(), self.mint.key(), lend_out_loan_bump);
+        }
+        self.lend_out_loan.accrue_interest(Clock::get()?.slot, self.amms_config.lend_out_interest_rate_basis_points());
+        Ok(())
+    }
+    #[inline(never)]
+    fn get_transfer_instruction(&self, amount: u64) -> Result<TransferTokensInstruction<'_, '_, '_, 'info>> {
+        TransferTokensInstruction::try_new(
+            amount,
+            &self.mint,
+            &self.cp_amm_vault,
+            self.cp_amm.to_account_info(),
+            &self.lending_adapter_account,
+            &self.token_program
+        )
+    }
+}
\ No newline at end of file
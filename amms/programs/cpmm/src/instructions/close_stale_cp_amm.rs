@@ -0,0 +1,183 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::error::ErrorCode;
+use crate::state::{AmmsConfig, VersionedAccount};
+use crate::state::cp_amm::CpAmm;
+use crate::utils::token_instructions::{CloseTokenAccountInstruction, TransferTokensInstruction};
+
+#[derive(Accounts)]
+pub struct CloseStaleCpAmm<'info> {
+    #[account(mut)]
+    pub closer: Signer<'info>,
+    /// CHECK: The pool creator only receives the refunded rent
+    #[account(
+        mut,
+        constraint = creator.key() == cp_amm.creator().key()
+    )]
+    pub creator: AccountInfo<'info>,
+
+    #[account(
+        seeds = [AmmsConfig::SEED, amms_config.id.to_le_bytes().as_ref()],
+        bump = amms_config.bump()
+    )]
+    pub amms_config: Box<Account<'info, AmmsConfig>>,
+
+    #[account(
+        mut,
+        constraint = amms_config.key() == cp_amm.amms_config().key(),
+        seeds = [CpAmm::SEED, cp_amm.lp_mint.as_ref()],
+        bump = cp_amm.bump()
+    )]
+    pub cp_amm: Box<Account<'info, CpAmm>>,
+
+    #[account(
+        mut,
+        seeds = [CpAmm::VAULT_SEED, cp_amm.key().as_ref(), cp_amm.base_mint().as_ref()],
+        bump = cp_amm.base_vault_bump()
+    )]
+    pub cp_amm_base_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [CpAmm::VAULT_SEED, cp_amm.key().as_ref(), cp_amm.quote_mint().as_ref()],
+        bump = cp_amm.quote_vault_bump()
+    )]
+    pub cp_amm_quote_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [CpAmm::VAULT_SEED, cp_amm.key().as_ref(), cp_amm.lp_mint.as_ref()],
+        bump = cp_amm.locked_lp_vault_bump()
+    )]
+    pub cp_amm_locked_lp_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub base_token_program: Interface<'info, TokenInterface>,
+    pub quote_token_program: Interface<'info, TokenInterface>,
+    pub lp_token_program: Interface<'info, TokenInterface>,
+
+    #[account(
+        constraint = base_mint.key() == cp_amm.base_mint().key()
+    )]
+    pub base_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        constraint = quote_mint.key() == cp_amm.quote_mint().key()
+    )]
+    pub quote_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Required only if the base vault holds tokens.
+    #[account(
+        mut,
+        associated_token::mint = base_mint,
+        associated_token::authority = creator,
+        associated_token::token_program = base_token_program
+    )]
+    pub creator_base_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    /// Required only if the quote vault holds tokens.
+    #[account(
+        mut,
+        associated_token::mint = quote_mint,
+        associated_token::authority = creator,
+        associated_token::token_program = quote_token_program
+    )]
+    pub creator_quote_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+}
+
+/// Closes a pool that was never launched within the `AmmsConfig` stale period, along with its vaults.
+///
+/// The rent of the vaults is gathered in the pool account, the closer receives the `AmmsConfig` share
+/// of it and the rest is refunded to the creator. The LP mint and the pool's `PoolIndex` entry are kept.
+///
+/// A launch never happened, so any vault balance was donated; it is swept to the creator token accounts
+/// first, as a token account holding tokens can't be closed. The vaults of Token-2022 mints holding
+/// withheld transfer fees still need the fees harvested before the pool can be closed.
+pub(crate) fn handler(ctx: Context<CloseStaleCpAmm>) -> Result<()> {
+    ctx.accounts.cp_amm.assert_layout_version()?;
+    ctx.accounts.cp_amm.validate_stale_close(Clock::get()?.epoch, ctx.accounts.amms_config.stale_pool_close_epochs())?;
+
+    let cp_amm_seeds = ctx.accounts.cp_amm.seeds();
+    let close_vaults_instruction_seeds: &[&[&[u8]]] = &[&cp_amm_seeds];
+
+    if let Some(sweep_base_vault_instruction) = ctx.accounts.get_sweep_base_vault_instruction()? {
+        sweep_base_vault_instruction.execute(Some(close_vaults_instruction_seeds))?;
+    }
+    if let Some(sweep_quote_vault_instruction) = ctx.accounts.get_sweep_quote_vault_instruction()? {
+        sweep_quote_vault_instruction.execute(Some(close_vaults_instruction_seeds))?;
+    }
+
+    ctx.accounts.get_close_base_vault_instruction()?.execute(Some(close_vaults_instruction_seeds))?;
+    ctx.accounts.get_close_quote_vault_instruction()?.execute(Some(close_vaults_instruction_seeds))?;
+    ctx.accounts.get_close_locked_lp_vault_instruction()?.execute(Some(close_vaults_instruction_seeds))?;
+
+    ctx.accounts.pay_closer_share()?;
+    ctx.accounts.cp_amm.close(ctx.accounts.creator.to_account_info())
+}
+
+impl<'info> CloseStaleCpAmm<'info> {
+    #[inline(never)]
+    fn get_sweep_base_vault_instruction(&self) -> Result<Option<TransferTokensInstruction<'_, '_, '_, 'info>>>{
+        if self.cp_amm_base_vault.amount == 0 {
+            return Ok(None);
+        }
+        let creator_base_account = self.creator_base_account.as_ref().ok_or(ErrorCode::StaleCpAmmSweepAccountMissing)?;
+        TransferTokensInstruction::try_new(
+            self.cp_amm_base_vault.amount,
+            &self.base_mint,
+            &self.cp_amm_base_vault,
+            self.cp_amm.to_account_info(),
+            creator_base_account,
+            &self.base_token_program
+        ).map(Some)
+    }
+    #[inline(never)]
+    fn get_sweep_quote_vault_instruction(&self) -> Result<Option<TransferTokensInstruction<'_, '_, '_, 'info>>>{
+        if self.cp_amm_quote_vault.amount == 0 {
+            return Ok(None);
+        }
+        let creator_quote_account = self.creator_quote_account.as_ref().ok_or(ErrorCode::StaleCpAmmSweepAccountMissing)?;
+        TransferTokensInstruction::try_new(
+            self.cp_amm_quote_vault.amount,
+            &self.quote_mint,
+            &self.cp_amm_quote_vault,
+            self.cp_amm.to_account_info(),
+            creator_quote_account,
+            &self.quote_token_program
+        ).map(Some)
+    }
+    #[inline(never)]
+    fn get_close_base_vault_instruction(&self) -> Result<CloseTokenAccountInstruction<'_, '_, '_, 'info>>{
+        CloseTokenAccountInstruction::try_new(
+            &self.cp_amm_base_vault,
+            self.cp_amm.to_account_info(),
+            self.cp_amm.to_account_info(),
+            &self.base_token_program
+        )
+    }
+    #[inline(never)]
+    fn get_close_quote_vault_instruction(&self) -> Result<CloseTokenAccountInstruction<'_, '_, '_, 'info>>{
+        CloseTokenAccountInstruction::try_new(
+            &self.cp_amm_quote_vault,
+            self.cp_amm.to_account_info(),
+            self.cp_amm.to_account_info(),
+            &self.quote_token_program
+        )
+    }
+    #[inline(never)]
+    fn get_close_locked_lp_vault_instruction(&self) -> Result<CloseTokenAccountInstruction<'_, '_, '_, 'info>>{
+        CloseTokenAccountInstruction::try_new(
+            &self.cp_amm_locked_lp_vault,
+            self.cp_amm.to_account_info(),
+            self.cp_amm.to_account_info(),
+            &self.lp_token_program
+        )
+    }
+    fn pay_closer_share(&self) -> Result<()> {
+        let closer_share = (self.cp_amm.get_lamports() as u128
+            * self.amms_config.stale_pool_closer_share_basis_points() as u128
+            / 10000) as u64;
+        if closer_share > 0 {
+            self.cp_amm.sub_lamports(closer_share)?;
+            self.closer.add_lamports(closer_share)?;
+        }
+        Ok(())
+    }
+}
\ No newline at end of file
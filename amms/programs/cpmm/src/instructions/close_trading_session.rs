@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+use crate::state::cp_amm::TradingSession;
+
+#[derive(Accounts)]
+pub struct CloseTradingSession<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        close = owner,
+        constraint = trading_session.owner().key() == owner.key(),
+        seeds = [TradingSession::SEED, trading_session.cp_amm().as_ref(), owner.key().as_ref(), trading_session.session_key().as_ref()],
+        bump = trading_session.bump()
+    )]
+    pub trading_session: Box<Account<'info, TradingSession>>,
+}
+
+/// Closes a trading session of the signer, revoking its session key and refunding the rent.
+///
+/// Token delegations approved to the session PDA are left in place but can't be used without
+/// the session, the owner may revoke them separately.
+pub(crate) fn handler(_ctx: Context<CloseTradingSession>) -> Result<()> {
+    Ok(())
+}
\ No newline at end of file
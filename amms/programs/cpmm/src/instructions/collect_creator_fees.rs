@@ -0,0 +1,118 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::events::CollectCreatorFeesEvent;
+use crate::state::VersionedAccount;
+use crate::state::cp_amm::CpAmm;
+use crate::utils::token_instructions::TransferTokensInstruction;
+
+#[derive(Accounts)]
+pub struct CollectCreatorFees<'info> {
+    #[account(
+        mut,
+        constraint = creator.key() == cp_amm.creator().key()
+    )]
+    pub creator: Signer<'info>,
+    pub base_mint: Box<InterfaceAccount<'info, Mint>>,
+    pub quote_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        init_if_needed,
+        payer = creator,
+        associated_token::mint = base_mint,
+        associated_token::authority = creator,
+        associated_token::token_program = base_token_program
+    )]
+    pub creator_base_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = creator,
+        associated_token::mint = quote_mint,
+        associated_token::authority = creator,
+        associated_token::token_program = quote_token_program
+    )]
+    pub creator_quote_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = base_mint.key() == cp_amm.base_mint().key(),
+        constraint = quote_mint.key() == cp_amm.quote_mint().key(),
+        constraint = cp_amm_base_vault.key() == cp_amm.base_vault().key(),
+        constraint = cp_amm_quote_vault.key() == cp_amm.quote_vault().key(),
+        seeds = [CpAmm::SEED, cp_amm.lp_mint.as_ref()],
+        bump = cp_amm.bump()
+    )]
+    pub cp_amm: Box<Account<'info, CpAmm>>,
+
+    #[account(
+        mut,
+        seeds = [CpAmm::VAULT_SEED, cp_amm.key().as_ref(), cp_amm.base_mint().as_ref()],
+        bump = cp_amm.base_vault_bump()
+    )]
+    pub cp_amm_base_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [CpAmm::VAULT_SEED, cp_amm.key().as_ref(), cp_amm.quote_mint().as_ref()],
+        bump = cp_amm.quote_vault_bump()
+    )]
+    pub cp_amm_quote_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub base_token_program: Interface<'info, TokenInterface>,
+    pub quote_token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Transfers the creator's share of the providers fees accumulated by the pool to the creator.
+pub(crate) fn handler(ctx: Context<CollectCreatorFees>) -> Result<()> {
+    ctx.accounts.cp_amm.assert_layout_version()?;
+    let collect_creator_fees_payload = ctx.accounts.cp_amm.get_collect_creator_fees_payload()?;
+    let (creator_base_fees_to_redeem, creator_quote_fees_to_redeem) = (collect_creator_fees_payload.creator_base_fees_to_redeem(), collect_creator_fees_payload.creator_quote_fees_to_redeem());
+
+    let cp_amm_seeds = ctx.accounts.cp_amm.seeds();
+    let collect_creator_fees_instruction_seeds: &[&[&[u8]]] = &[&cp_amm_seeds];
+
+    if creator_base_fees_to_redeem > 0{
+        ctx.accounts.get_collect_base_fees_transfer_instruction(creator_base_fees_to_redeem)?.execute(Some(collect_creator_fees_instruction_seeds))?;
+    }
+    if creator_quote_fees_to_redeem > 0{
+        ctx.accounts.get_collect_quote_fees_transfer_instruction(creator_quote_fees_to_redeem)?.execute(Some(collect_creator_fees_instruction_seeds))?;
+    }
+
+    ctx.accounts.cp_amm.collect_creator_fees(collect_creator_fees_payload);
+
+    emit!(CollectCreatorFeesEvent {
+        cp_amm: ctx.accounts.cp_amm.key(),
+        creator: ctx.accounts.creator.key(),
+        base_fees_amount: creator_base_fees_to_redeem,
+        quote_fees_amount: creator_quote_fees_to_redeem,
+    });
+    Ok(())
+}
+
+impl<'info> CollectCreatorFees<'info> {
+    #[inline(never)]
+    fn get_collect_base_fees_transfer_instruction(&self, base_fees: u64) -> Result<TransferTokensInstruction<'_, '_, '_, 'info>>{
+        TransferTokensInstruction::try_new(
+            base_fees,
+            &self.base_mint,
+            &self.cp_amm_base_vault,
+            self.cp_amm.to_account_info(),
+            &self.creator_base_account,
+            &self.base_token_program
+        )
+    }
+    #[inline(never)]
+    fn get_collect_quote_fees_transfer_instruction(&self, quote_fees: u64) -> Result<TransferTokensInstruction<'_, '_, '_, 'info>>{
+        TransferTokensInstruction::try_new(
+            quote_fees,
+            &self.quote_mint,
+            &self.cp_amm_quote_vault,
+            self.cp_amm.to_account_info(),
+            &self.creator_quote_account,
+            &self.quote_token_program
+        )
+    }
+}
\ No newline at end of file
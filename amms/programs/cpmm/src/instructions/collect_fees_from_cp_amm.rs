@@ -1,16 +1,20 @@
 use anchor_lang::prelude::*;
 use anchor_spl::associated_token::AssociatedToken;
 use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
-use crate::state::AmmsConfig;
-use crate::state::cp_amm::CpAmm;
+use crate::events::{CollectFeesEvent, NothingToCollectEvent};
+use crate::liquidity_hook::{LiquidityDeltaKind, LiquiditySnapshot};
+use crate::state::{AmmsConfig, VersionedAccount};
+use crate::state::cp_amm::{CpAmm, CpAmmCore, EventRecordKind, EventRing};
+use crate::utils::{record_liquidity_change, LiquidityChange};
+use crate::utils::liquidity_hook_instructions::notify_liquidity_subscriber;
 use crate::utils::token_instructions::TransferTokensInstruction;
 
 #[derive(Accounts)]
 pub struct CollectFeesFromCpAmm<'info> {
     #[account(mut)]
     pub signer: Signer<'info>,
-    #[account(mut)]
-    /// CHECK: Amms config's fee authority can be arbitrary type
+    /// CHECK: Amms config's fee authority can be arbitrary type. It never signs here, so it may be a PDA
+    /// of another program (e.g. a DAO treasury): its ATAs below are derived on-chain and paid for by the signer
     pub fee_authority: AccountInfo<'info>,
     pub base_mint: Box<InterfaceAccount<'info, Mint>>,
     pub quote_mint: Box<InterfaceAccount<'info, Mint>>,
@@ -32,13 +36,31 @@ pub struct CollectFeesFromCpAmm<'info> {
         associated_token::token_program = quote_token_program
     )]
     pub fee_authority_quote_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = signer,
+        associated_token::mint = base_mint,
+        associated_token::authority = signer,
+        associated_token::token_program = base_token_program
+    )]
+    pub signer_base_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = signer,
+        associated_token::mint = quote_mint,
+        associated_token::authority = signer,
+        associated_token::token_program = quote_token_program
+    )]
+    pub signer_quote_account: Box<InterfaceAccount<'info, TokenAccount>>,
     
     #[account(
         constraint = amms_config.fee_authority().key() == fee_authority.key(),
         seeds = [AmmsConfig::SEED, amms_config.id.to_le_bytes().as_ref()],
         bump = amms_config.bump()
     )]
-    pub amms_config: Account<'info, AmmsConfig>,
+    pub amms_config: Box<Account<'info, AmmsConfig>>,
 
     #[account(
         mut,
@@ -71,11 +93,56 @@ pub struct CollectFeesFromCpAmm<'info> {
     pub base_token_program: Interface<'info, TokenInterface>,
     pub quote_token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
+
+    #[account(
+        mut,
+        constraint = event_ring.load()?.cp_amm() == &cp_amm.key()
+    )]
+    pub event_ring: Option<AccountLoader<'info, EventRing>>,
 }
 
-pub(crate) fn handler(ctx: Context<CollectFeesFromCpAmm>) -> Result<()> {
-    let collect_fees_payload = ctx.accounts.cp_amm.get_collect_fees_payload()?;
+/// Collects the protocol fees of a `CpAmm` into the fee authority accounts, paying the keeper bounty to the signer.
+///
+/// Anyone can collect, which lifts the `AmmsConfig` protocol fees cap rejecting the provides of a pool
+/// whose accrued fees exceed it, see `CpAmm::check_protocol_fees_cap`.
+/// The in-pool conversion of a fee settlement only runs while the spot price agrees with the price the last
+/// slot ended at, so a caller can't settle the fees at a price skewed earlier in the same slot.
+///
+/// A settlement converting the fees through the reserves changes the liquidity of the pool: it is then
+/// recorded like any liquidity change, see `record_liquidity_change`, appended to the pool's event ring with
+/// `event_ring` passed, and the liquidity subscriber of the pool, if any, is notified with the remaining accounts.
+///
+/// - `idempotent`: Whether a `CpAmm` without protocol fees is a successful no-op emitting `NothingToCollectEvent`
+///   instead of an error, so batched fee sweeps don't fail on pools with nothing accrued.
+/// - `requested_fees`: The base and quote protocol fees to collect, including the keeper bounty, or `None`
+///   to collect all the accrued fees. The rest of the fees stays accrued in the `CpAmm`.
+pub(crate) fn handler<'info>(ctx: Context<'_, '_, '_, 'info, CollectFeesFromCpAmm<'info>>, idempotent: bool, requested_fees: Option<(u64, u64)>) -> Result<()> {
+    ctx.accounts.cp_amm.assert_layout_version()?;
+    if idempotent && !ctx.accounts.cp_amm.has_protocol_fees_to_redeem() {
+        emit!(NothingToCollectEvent {
+            cp_amm: ctx.accounts.cp_amm.key(),
+            signer: ctx.accounts.signer.key(),
+        });
+        return Ok(());
+    }
+    let liquidity_snapshot = LiquiditySnapshot::of(&ctx.accounts.cp_amm);
+    let (base_liquidity_before, quote_liquidity_before) = (ctx.accounts.cp_amm.base_liquidity(), ctx.accounts.cp_amm.quote_liquidity());
+    let protocol_fee_rate_basis_points = ctx.accounts.cp_amm.protocol_fee_rate_basis_points(ctx.accounts.amms_config.protocol_fee_rate_basis_points());
+    let (requested_base_fees, requested_quote_fees) = requested_fees.unwrap_or((
+        ctx.accounts.cp_amm.protocol_base_fees_to_redeem(),
+        ctx.accounts.cp_amm.protocol_quote_fees_to_redeem()
+    ));
+    let collect_fees_payload = ctx.accounts.cp_amm.get_collect_fees_payload(
+        requested_base_fees,
+        requested_quote_fees,
+        ctx.accounts.amms_config.keeper_bounty_basis_points(),
+        protocol_fee_rate_basis_points,
+        ctx.accounts.amms_config.fee_settlement(),
+        ctx.accounts.amms_config.swap_constant_product_tolerance(),
+        Clock::get()?.slot
+    )?;
     let (protocol_base_fees_to_redeem, protocol_quote_fees_to_redeem) = (collect_fees_payload.protocol_base_fees_to_redeem(), collect_fees_payload.protocol_quote_fees_to_redeem());
+    let (base_keeper_bounty, quote_keeper_bounty) = (collect_fees_payload.base_keeper_bounty(), collect_fees_payload.quote_keeper_bounty());
 
     let cp_amm_seeds = ctx.accounts.cp_amm.seeds();
     let collect_fees_instruction_seeds: &[&[&[u8]]] = &[&cp_amm_seeds];
@@ -86,12 +153,40 @@ pub(crate) fn handler(ctx: Context<CollectFeesFromCpAmm>) -> Result<()> {
     if protocol_quote_fees_to_redeem > 0{
         ctx.accounts.get_collect_quote_fees_transfer_instruction(protocol_quote_fees_to_redeem)?.execute(Some(collect_fees_instruction_seeds))?;
     }
+    if base_keeper_bounty > 0{
+        ctx.accounts.get_base_keeper_bounty_transfer_instruction(base_keeper_bounty)?.execute(Some(collect_fees_instruction_seeds))?;
+    }
+    if quote_keeper_bounty > 0{
+        ctx.accounts.get_quote_keeper_bounty_transfer_instruction(quote_keeper_bounty)?.execute(Some(collect_fees_instruction_seeds))?;
+    }
+
+    ctx.accounts.cp_amm.record_slot_start_price(Clock::get()?.slot);
+    if ctx.accounts.cp_amm.collect_fees(collect_fees_payload) {
+        let liquidity_change = LiquidityChange {
+            kind: EventRecordKind::FeeSettlement,
+            signer: ctx.accounts.signer.key(),
+            base_amount: ctx.accounts.cp_amm.base_liquidity().abs_diff(base_liquidity_before),
+            quote_amount: ctx.accounts.cp_amm.quote_liquidity().abs_diff(quote_liquidity_before),
+            lp_tokens: 0,
+        };
+        record_liquidity_change(&mut ctx.accounts.cp_amm, &ctx.accounts.amms_config, ctx.accounts.event_ring.as_ref(), liquidity_change, Clock::get()?.slot)?;
+        notify_liquidity_subscriber(&ctx.accounts.cp_amm, ctx.remaining_accounts, LiquidityDeltaKind::FeeSettlement, liquidity_snapshot)?;
+    }
 
-    ctx.accounts.cp_amm.collect_fees(collect_fees_payload);
+    emit!(CollectFeesEvent {
+        cp_amm: ctx.accounts.cp_amm.key(),
+        fee_authority: ctx.accounts.fee_authority.key(),
+        signer: ctx.accounts.signer.key(),
+        base_fees_amount: protocol_base_fees_to_redeem,
+        quote_fees_amount: protocol_quote_fees_to_redeem,
+        base_keeper_bounty,
+        quote_keeper_bounty,
+    });
     Ok(())
 }
 
 impl<'info> CollectFeesFromCpAmm<'info> {
+    #[inline(never)]
     fn get_collect_base_fees_transfer_instruction(&self, base_fees: u64) -> Result<TransferTokensInstruction<'_, '_, '_, 'info>>{
         TransferTokensInstruction::try_new(
             base_fees,
@@ -102,6 +197,7 @@ impl<'info> CollectFeesFromCpAmm<'info> {
             &self.base_token_program
         )
     }
+    #[inline(never)]
     fn get_collect_quote_fees_transfer_instruction(&self, quote_fees: u64) -> Result<TransferTokensInstruction<'_, '_, '_, 'info>>{
         TransferTokensInstruction::try_new(
             quote_fees,
@@ -112,5 +208,27 @@ impl<'info> CollectFeesFromCpAmm<'info> {
             &self.quote_token_program
         )
     }
+    #[inline(never)]
+    fn get_base_keeper_bounty_transfer_instruction(&self, base_bounty: u64) -> Result<TransferTokensInstruction<'_, '_, '_, 'info>>{
+        TransferTokensInstruction::try_new(
+            base_bounty,
+            &self.base_mint,
+            &self.cp_amm_base_vault,
+            self.cp_amm.to_account_info(),
+            &self.signer_base_account,
+            &self.base_token_program
+        )
+    }
+    #[inline(never)]
+    fn get_quote_keeper_bounty_transfer_instruction(&self, quote_bounty: u64) -> Result<TransferTokensInstruction<'_, '_, '_, 'info>>{
+        TransferTokensInstruction::try_new(
+            quote_bounty,
+            &self.quote_mint,
+            &self.cp_amm_quote_vault,
+            self.cp_amm.to_account_info(),
+            &self.signer_quote_account,
+            &self.quote_token_program
+        )
+    }
 }
 
@@ -0,0 +1,243 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+use anchor_spl::token_interface::{self, MintTo, TokenInterface};
+use crate::constants::TEST_MINT_SEED;
+use crate::state::{AmmsConfig, PoolIndex, cp_amm::{CpAmm, CpAmmCalculate}};
+use crate::utils::assert_devnet_build;
+use crate::utils::token_instructions::MintTokensInstructions;
+
+#[derive(Accounts)]
+#[instruction(base_decimals: u8, quote_decimals: u8)]
+pub struct CreateTestPool<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        init,
+        payer = signer,
+        mint::decimals = CpAmm::calculate_lp_mint_decimals(base_decimals, quote_decimals),
+        mint::authority = cp_amm,
+        mint::token_program = lp_token_program
+    )]
+    pub lp_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        init,
+        payer = signer,
+        mint::decimals = base_decimals,
+        mint::authority = signer,
+        mint::token_program = token_program,
+        seeds = [TEST_MINT_SEED, lp_mint.key().as_ref(), &[0]],
+        bump
+    )]
+    pub base_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+
+    #[account(
+        init,
+        payer = signer,
+        mint::decimals = quote_decimals,
+        mint::authority = signer,
+        mint::token_program = token_program,
+        seeds = [TEST_MINT_SEED, lp_mint.key().as_ref(), &[1]],
+        bump
+    )]
+    pub quote_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+
+    #[account(
+        init,
+        payer = signer,
+        associated_token::mint = base_mint,
+        associated_token::authority = signer,
+        associated_token::token_program = token_program
+    )]
+    pub signer_base_account: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    #[account(
+        init,
+        payer = signer,
+        associated_token::mint = quote_mint,
+        associated_token::authority = signer,
+        associated_token::token_program = token_program
+    )]
+    pub signer_quote_account: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    #[account(
+        init,
+        payer = signer,
+        associated_token::mint = lp_mint,
+        associated_token::authority = signer,
+        associated_token::token_program = lp_token_program
+    )]
+    pub signer_lp_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [AmmsConfig::SEED, amms_config.id.to_le_bytes().as_ref()],
+        bump = amms_config.bump()
+    )]
+    pub amms_config: Box<Account<'info, AmmsConfig>>,
+
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = 8 + PoolIndex::INIT_SPACE,
+        seeds = [PoolIndex::SEED, amms_config.key().as_ref(), amms_config.pool_index_bucket().to_le_bytes().as_ref()],
+        bump
+    )]
+    pub pool_index: Box<Account<'info, PoolIndex>>,
+
+    #[account(
+        init,
+        payer = signer,
+        space = 8 + CpAmm::INIT_SPACE,
+        seeds = [CpAmm::SEED, lp_mint.key().as_ref()],
+        bump
+    )]
+    pub cp_amm: Box<Account<'info, CpAmm>>,
+
+    #[account(
+        init,
+        payer = signer,
+        token::mint = base_mint,
+        token::authority = cp_amm,
+        token::token_program = token_program,
+        seeds = [CpAmm::VAULT_SEED, cp_amm.key().as_ref(), base_mint.key().as_ref()],
+        bump
+    )]
+    pub cp_amm_base_vault: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    #[account(
+        init,
+        payer = signer,
+        token::mint = quote_mint,
+        token::authority = cp_amm,
+        token::token_program = token_program,
+        seeds = [CpAmm::VAULT_SEED, cp_amm.key().as_ref(), quote_mint.key().as_ref()],
+        bump
+    )]
+    pub cp_amm_quote_vault: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    #[account(
+        init,
+        payer = signer,
+        token::mint = lp_mint,
+        token::authority = cp_amm,
+        token::token_program = lp_token_program,
+        seeds = [CpAmm::VAULT_SEED, cp_amm.key().as_ref(), lp_mint.key().as_ref()],
+        bump
+    )]
+    pub cp_amm_locked_lp_vault: Box<Account<'info, TokenAccount>>,
+
+    pub system_program: Program<'info, System>,
+    pub lp_token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+/// Creates two test mints, funds the signer with them and initializes and launches a pool of the pair
+/// in a single transaction, for hackathons and CI on devnet. Only available in `devnet` builds.
+///
+/// The signer stays the mint authority of both test mints, so it may mint more tokens later. The launch
+/// liquidity is minted directly into the pool vaults and trading opens immediately. Test pools don't pay
+/// the pool creation fee and don't count against the creator's pool creation limit.
+///
+/// - `base_decimals`, `quote_decimals`: The decimals of the test mints.
+/// - `base_amount`, `quote_amount`: The test tokens minted to the signer.
+/// - `base_liquidity`, `quote_liquidity`: The launch liquidity of the pool.
+pub(crate) fn handler(
+    ctx: Context<CreateTestPool>,
+    base_decimals: u8,
+    quote_decimals: u8,
+    base_amount: u64,
+    quote_amount: u64,
+    base_liquidity: u64,
+    quote_liquidity: u64
+) -> Result<()> {
+    assert_devnet_build()?;
+    ctx.accounts.amms_config.validate_mint_decimals(base_decimals)?;
+    ctx.accounts.amms_config.validate_mint_decimals(quote_decimals)?;
+    ctx.accounts.amms_config.validate_launch_price(base_liquidity, quote_liquidity, base_decimals, quote_decimals)?;
+    ctx.accounts.register_in_pool_index(ctx.bumps.pool_index)?;
+
+    let accounts = ctx.accounts;
+    accounts.cp_amm.initialize(
+        &accounts.base_mint,
+        &accounts.quote_mint,
+        &accounts.lp_mint,
+        &accounts.amms_config,
+        &accounts.signer.to_account_info(),
+        &accounts.cp_amm_base_vault.to_account_info(),
+        &accounts.cp_amm_quote_vault.to_account_info(),
+        &accounts.cp_amm_locked_lp_vault.to_account_info(),
+        ctx.bumps.cp_amm,
+        ctx.bumps.cp_amm_base_vault,
+        ctx.bumps.cp_amm_quote_vault,
+        ctx.bumps.cp_amm_locked_lp_vault,
+        Clock::get()?.epoch
+    )?;
+
+    accounts.mint_test_tokens(&accounts.base_mint, accounts.signer_base_account.to_account_info(), base_amount)?;
+    accounts.mint_test_tokens(&accounts.quote_mint, accounts.signer_quote_account.to_account_info(), quote_amount)?;
+    accounts.mint_test_tokens(&accounts.base_mint, accounts.cp_amm_base_vault.to_account_info(), base_liquidity)?;
+    accounts.mint_test_tokens(&accounts.quote_mint, accounts.cp_amm_quote_vault.to_account_info(), quote_liquidity)?;
+
+    let launch_payload = Box::new(accounts.cp_amm.get_launch_payload(
+        base_liquidity,
+        quote_liquidity,
+        base_decimals,
+        quote_decimals,
+        accounts.lp_mint.decimals,
+        None
+    )?);
+
+    let cp_amm_seeds = accounts.cp_amm.seeds();
+    let mint_instruction_seeds: &[&[&[u8]]] = &[&cp_amm_seeds];
+    accounts.get_lp_tokens_mint_instruction(launch_payload.launch_liquidity(), accounts.signer_lp_account.to_account_info())
+        .execute(Some(mint_instruction_seeds))?;
+    accounts.get_lp_tokens_mint_instruction(launch_payload.initial_locked_liquidity(), accounts.cp_amm_locked_lp_vault.to_account_info())
+        .execute(Some(mint_instruction_seeds))?;
+
+    accounts.cp_amm.launch(*launch_payload, Clock::get()?.slot);
+    Ok(())
+}
+
+impl<'info> CreateTestPool<'info> {
+    fn register_in_pool_index(&mut self, pool_index_bump: u8) -> Result<()> {
+        if !self.pool_index.is_initialized() {
+            self.pool_index.initialize(self.amms_config.key(), self.amms_config.pool_index_bucket(), pool_index_bump);
+        }
+        self.pool_index.push(self.cp_amm.key())?;
+        self.amms_config.increment_pools_count();
+        Ok(())
+    }
+
+    #[inline(never)]
+    fn mint_test_tokens(&self, mint: &InterfaceAccount<'info, token_interface::Mint>, to: AccountInfo<'info>, amount: u64) -> Result<()> {
+        if amount == 0 {
+            return Ok(());
+        }
+        token_interface::mint_to(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                MintTo {
+                    mint: mint.to_account_info(),
+                    to,
+                    authority: self.signer.to_account_info(),
+                }
+            ),
+            amount
+        )
+    }
+
+    #[inline(never)]
+    fn get_lp_tokens_mint_instruction(&self, amount: u64, to: AccountInfo<'info>) -> MintTokensInstructions<'_, '_, '_, 'info> {
+        MintTokensInstructions::new(
+            amount,
+            &self.lp_mint,
+            self.cp_amm.to_account_info(),
+            to,
+            &self.lp_token_program
+        )
+    }
+}
\ No newline at end of file
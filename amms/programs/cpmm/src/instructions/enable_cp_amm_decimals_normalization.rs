@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+use crate::state::{cp_amm::CpAmm, VersionedAccount};
+
+#[derive(Accounts)]
+pub struct EnableCpAmmDecimalsNormalization<'info> {
+    #[account(
+        constraint = creator.key() == cp_amm.creator().key()
+    )]
+    pub creator: Signer<'info>,
+    pub base_mint: Box<InterfaceAccount<'info, Mint>>,
+    pub quote_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        constraint = base_mint.key() == cp_amm.base_mint().key(),
+        constraint = quote_mint.key() == cp_amm.quote_mint().key(),
+        seeds = [CpAmm::SEED, cp_amm.lp_mint.as_ref()],
+        bump = cp_amm.bump()
+    )]
+    pub cp_amm: Box<Account<'info, CpAmm>>,
+}
+
+/// Opts the pool into decimals-normalized constant product math before its launch,
+/// improving the precision of LP mint and withdraw calculations for pairs of extreme decimals.
+pub(crate) fn handler(ctx: Context<EnableCpAmmDecimalsNormalization>) -> Result<()> {
+    ctx.accounts.cp_amm.assert_layout_version()?;
+    let base_decimals = ctx.accounts.base_mint.decimals;
+    let quote_decimals = ctx.accounts.quote_mint.decimals;
+    ctx.accounts.cp_amm.enable_decimals_normalization(base_decimals, quote_decimals)
+}
\ No newline at end of file
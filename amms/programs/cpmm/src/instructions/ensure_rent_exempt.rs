@@ -0,0 +1,112 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::set_return_data;
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_spl::token_interface::TokenAccount;
+use crate::error::ErrorCode;
+use crate::state::cp_amm::CpAmm;
+
+#[derive(Accounts)]
+pub struct EnsureRentExempt<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [CpAmm::SEED, cp_amm.lp_mint.as_ref()],
+        bump = cp_amm.bump()
+    )]
+    pub cp_amm: Box<Account<'info, CpAmm>>,
+
+    #[account(
+        mut,
+        seeds = [CpAmm::VAULT_SEED, cp_amm.key().as_ref(), cp_amm.base_mint().as_ref()],
+        bump = cp_amm.base_vault_bump()
+    )]
+    pub cp_amm_base_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [CpAmm::VAULT_SEED, cp_amm.key().as_ref(), cp_amm.quote_mint().as_ref()],
+        bump = cp_amm.quote_vault_bump()
+    )]
+    pub cp_amm_quote_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [CpAmm::VAULT_SEED, cp_amm.key().as_ref(), cp_amm.lp_mint.as_ref()],
+        bump = cp_amm.locked_lp_vault_bump()
+    )]
+    pub cp_amm_locked_lp_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Lamports missing for the rent exemption of the pool accounts, returned to the caller as
+/// instruction return data.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct RentShortfall {
+    /// The lamports missing on the `CpAmm` account.
+    pub cp_amm: u64,
+
+    /// The lamports missing on the base vault.
+    pub base_vault: u64,
+
+    /// The lamports missing on the quote vault.
+    pub quote_vault: u64,
+
+    /// The lamports missing on the locked LP tokens vault.
+    pub locked_lp_vault: u64,
+}
+
+impl RentShortfall {
+    /// Returns the lamports missing on all pool accounts.
+    pub fn total(&self) -> u64 {
+        self.cp_amm + self.base_vault + self.quote_vault + self.locked_lp_vault
+    }
+}
+
+/// Audits the rent exemption of the pool and its vaults, and tops up the missing lamports from the payer.
+///
+/// Account reallocs may leave pools below the rent-exempt minimum of their current size. Anyone may
+/// fund the shortfall: it is transferred from the payer if it doesn't exceed `max_top_up`. Otherwise
+/// the instruction sets the shortfall as return data and fails with `RentTopUpExceeded`, so a
+/// simulation with `max_top_up` of zero audits the pool without paying. The shortfall is returned
+/// on success too.
+pub(crate) fn handler(ctx: Context<EnsureRentExempt>, max_top_up: u64) -> Result<RentShortfall> {
+    let rent = Rent::get()?;
+    let shortfall_of = |account: &AccountInfo| rent.minimum_balance(account.data_len()).saturating_sub(account.lamports());
+    let accounts = [
+        ctx.accounts.cp_amm.to_account_info(),
+        ctx.accounts.cp_amm_base_vault.to_account_info(),
+        ctx.accounts.cp_amm_quote_vault.to_account_info(),
+        ctx.accounts.cp_amm_locked_lp_vault.to_account_info(),
+    ];
+    let shortfall = RentShortfall {
+        cp_amm: shortfall_of(&accounts[0]),
+        base_vault: shortfall_of(&accounts[1]),
+        quote_vault: shortfall_of(&accounts[2]),
+        locked_lp_vault: shortfall_of(&accounts[3]),
+    };
+
+    if shortfall.total() > max_top_up {
+        msg!("Rent shortfall of {} lamports", shortfall.total());
+        set_return_data(&shortfall.try_to_vec()?);
+        return err!(ErrorCode::RentTopUpExceeded);
+    }
+    let amounts = [shortfall.cp_amm, shortfall.base_vault, shortfall.quote_vault, shortfall.locked_lp_vault];
+    for (account, amount) in accounts.into_iter().zip(amounts) {
+        if amount > 0 {
+            transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.payer.to_account_info(),
+                        to: account,
+                    }
+                ),
+                amount
+            )?;
+        }
+    }
+    Ok(shortfall)
+}
\ No newline at end of file
@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token;
+use crate::state::{AmmsConfig, VersionedAccount};
+use crate::state::cp_amm::{CpAmm, LiquidityPosition};
+
+#[derive(Accounts)]
+pub struct GetLoyaltyMultiplier<'info> {
+    #[account(
+        seeds = [AmmsConfig::SEED, amms_config.id.to_le_bytes().as_ref()],
+        bump = amms_config.bump()
+    )]
+    pub amms_config: Box<Account<'info, AmmsConfig>>,
+
+    #[account(
+        constraint = amms_config.key() == cp_amm.amms_config().key(),
+        seeds = [CpAmm::SEED, cp_amm.lp_mint.as_ref()],
+        bump = cp_amm.bump()
+    )]
+    pub cp_amm: Box<Account<'info, CpAmm>>,
+
+    #[account(
+        seeds = [LiquidityPosition::SEED, cp_amm.key().as_ref(), liquidity_position.owner().as_ref()],
+        bump = liquidity_position.bump()
+    )]
+    pub liquidity_position: Box<Account<'info, LiquidityPosition>>,
+
+    #[account(
+        constraint = owner_lp_account.mint == cp_amm.lp_mint,
+        constraint = owner_lp_account.owner == liquidity_position.owner().key(),
+    )]
+    pub owner_lp_account: Box<Account<'info, token::TokenAccount>>,
+}
+
+/// Loyalty multiplier of a liquidity position returned to the caller as instruction return data.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct LoyaltyMultiplier {
+    /// The multiplier of the position, in basis points (10000 = 1x).
+    pub multiplier_basis_points: u16,
+
+    /// The duration the position's LP tokens have been held for, in seconds.
+    pub hold_seconds: u64,
+
+    /// The LP tokens the multiplier applies to: the tracked LP tokens still held by the owner.
+    pub boosted_lp_tokens: u64,
+}
+
+pub(crate) fn handler(ctx: Context<GetLoyaltyMultiplier>) -> Result<LoyaltyMultiplier> {
+    ctx.accounts.cp_amm.assert_layout_version()?;
+    let current_timestamp = Clock::get()?.unix_timestamp;
+    let liquidity_position = &ctx.accounts.liquidity_position;
+    Ok(LoyaltyMultiplier {
+        multiplier_basis_points: liquidity_position.loyalty_multiplier_basis_points(current_timestamp, ctx.accounts.amms_config.loyalty_boost()),
+        hold_seconds: liquidity_position.hold_seconds(current_timestamp),
+        boosted_lp_tokens: liquidity_position.lp_tokens().min(ctx.accounts.owner_lp_account.amount),
+    })
+}
\ No newline at end of file
@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+use crate::state::VersionedAccount;
+use crate::state::cp_amm::CpAmm;
+
+#[derive(Accounts)]
+pub struct GetLpValue<'info> {
+    #[account(
+        seeds = [CpAmm::SEED, cp_amm.lp_mint.as_ref()],
+        bump = cp_amm.bump()
+    )]
+    pub cp_amm: Box<Account<'info, CpAmm>>,
+}
+
+/// Redeemable value of LP tokens returned to the caller as instruction return data.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct LpValue {
+    /// The amount of base tokens the LP tokens can be redeemed for.
+    pub base_amount: u64,
+
+    /// The amount of quote tokens the LP tokens can be redeemed for.
+    pub quote_amount: u64,
+}
+
+pub(crate) fn handler(ctx: Context<GetLpValue>, lp_tokens: u64) -> Result<LpValue> {
+    ctx.accounts.cp_amm.assert_layout_version()?;
+    let (base_amount, quote_amount) = ctx.accounts.cp_amm.get_lp_value(lp_tokens)?;
+    Ok(LpValue {
+        base_amount,
+        quote_amount,
+    })
+}
\ No newline at end of file
@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+use crate::error::ErrorCode;
+use crate::state::VersionedAccount;
+use crate::state::cp_amm::CpAmm;
+
+#[derive(Accounts)]
+pub struct GetSafeSpotPrice<'info> {
+    #[account(
+        seeds = [CpAmm::SEED, cp_amm.lp_mint.as_ref()],
+        bump = cp_amm.bump()
+    )]
+    pub cp_amm: Box<Account<'info, CpAmm>>,
+}
+
+/// Spot prices of a pool returned to the caller as instruction return data.
+///
+/// Consumers requiring both prices to agree within a bound reject prices moved within the current slot.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct SafeSpotPrice {
+    /// The instantaneous quote per base price, scaled by `CpAmm::VIRTUAL_PRICE_SCALE`.
+    pub spot_price: u64,
+    /// The price the last slot before the current one ended at, scaled by `CpAmm::VIRTUAL_PRICE_SCALE`.
+    pub last_slot_price: u64,
+}
+
+pub(crate) fn handler(ctx: Context<GetSafeSpotPrice>) -> Result<SafeSpotPrice> {
+    ctx.accounts.cp_amm.assert_layout_version()?;
+    require!(ctx.accounts.cp_amm.is_launched(), ErrorCode::CpAmmNotLaunched);
+    Ok(SafeSpotPrice {
+        spot_price: ctx.accounts.cp_amm.spot_price(),
+        last_slot_price: ctx.accounts.cp_amm.last_slot_price(Clock::get()?.slot),
+    })
+}
\ No newline at end of file
@@ -0,0 +1,86 @@
+use anchor_lang::prelude::*;
+use crate::state::VersionedAccount;
+use crate::state::cp_amm::{CpAmm, CpAmmCore, FeeCheckpoints, PoolStats, PoolStatsShard};
+
+#[derive(Accounts)]
+pub struct GetTrailingApr<'info> {
+    #[account(
+        seeds = [CpAmm::SEED, cp_amm.lp_mint.as_ref()],
+        bump = cp_amm.bump()
+    )]
+    pub cp_amm: Box<Account<'info, CpAmm>>,
+
+    #[account(
+        seeds = [FeeCheckpoints::SEED, cp_amm.key().as_ref()],
+        bump = fee_checkpoints.bump()
+    )]
+    pub fee_checkpoints: Box<Account<'info, FeeCheckpoints>>,
+
+    #[account(
+        seeds = [PoolStatsShard::SEED, cp_amm.key().as_ref(), [0u8].as_ref()],
+        bump
+    )]
+    /// CHECK: First `PoolStatsShard` PDA of the pool, its statistics are included only if initialized
+    pub pool_stats_shard_0: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [PoolStatsShard::SEED, cp_amm.key().as_ref(), [1u8].as_ref()],
+        bump
+    )]
+    /// CHECK: Second `PoolStatsShard` PDA of the pool, its statistics are included only if initialized
+    pub pool_stats_shard_1: UncheckedAccount<'info>,
+}
+
+/// Trailing providers fee APR of a pool returned to the caller as instruction return data.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct TrailingApr {
+    /// The providers fee APR over the trailing 24 hours, in basis points.
+    pub day_apr_basis_points: u64,
+
+    /// The number of seconds actually covered by the 24 hours APR.
+    pub day_covered_seconds: u64,
+
+    /// The providers fee APR over the trailing 7 days, in basis points.
+    pub week_apr_basis_points: u64,
+
+    /// The number of seconds actually covered by the 7 days APR.
+    pub week_covered_seconds: u64,
+}
+
+/// The length of the daily trailing window, in seconds.
+const DAY_SECONDS: i64 = 24 * 60 * 60;
+
+/// The length of the weekly trailing window, in seconds.
+const WEEK_SECONDS: i64 = 7 * DAY_SECONDS;
+
+pub(crate) fn handler(ctx: Context<GetTrailingApr>) -> Result<TrailingApr> {
+    ctx.accounts.cp_amm.assert_layout_version()?;
+    let current_timestamp = Clock::get()?.unix_timestamp;
+    let cp_amm = &ctx.accounts.cp_amm;
+    let stats = ctx.accounts.get_stats()?;
+    let get_trailing_apr = |window_seconds| ctx.accounts.fee_checkpoints.get_trailing_apr(
+        current_timestamp,
+        window_seconds,
+        &stats,
+        cp_amm.base_liquidity(),
+        cp_amm.quote_liquidity()
+    );
+
+    let (day_apr_basis_points, day_covered_seconds) = get_trailing_apr(DAY_SECONDS);
+    let (week_apr_basis_points, week_covered_seconds) = get_trailing_apr(WEEK_SECONDS);
+    Ok(TrailingApr {
+        day_apr_basis_points,
+        day_covered_seconds,
+        week_apr_basis_points,
+        week_covered_seconds,
+    })
+}
+
+impl<'info> GetTrailingApr<'info> {
+    fn get_stats(&self) -> Result<PoolStats> {
+        PoolStatsShard::aggregate_stats(
+            self.cp_amm.stats(),
+            &[self.pool_stats_shard_0.as_ref(), self.pool_stats_shard_1.as_ref()]
+        )
+    }
+}
\ No newline at end of file
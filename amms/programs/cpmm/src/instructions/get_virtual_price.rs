@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+use crate::state::VersionedAccount;
+use crate::state::cp_amm::CpAmm;
+
+#[derive(Accounts)]
+pub struct GetVirtualPrice<'info> {
+    #[account(
+        seeds = [CpAmm::SEED, cp_amm.lp_mint.as_ref()],
+        bump = cp_amm.bump()
+    )]
+    pub cp_amm: Box<Account<'info, CpAmm>>,
+}
+
+/// Virtual price of a pool returned to the caller as instruction return data.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct VirtualPrice {
+    /// The value of one LP token in quote tokens, scaled by `CpAmm::VIRTUAL_PRICE_SCALE`.
+    pub virtual_price: u64,
+}
+
+pub(crate) fn handler(ctx: Context<GetVirtualPrice>) -> Result<VirtualPrice> {
+    ctx.accounts.cp_amm.assert_layout_version()?;
+    Ok(VirtualPrice {
+        virtual_price: ctx.accounts.cp_amm.get_virtual_price()?,
+    })
+}
\ No newline at end of file
@@ -1,13 +1,14 @@
 use anchor_lang::prelude::*;
 use anchor_spl::{token::{Mint, Token}, token_interface};
 use anchor_spl::token_interface::TokenInterface;
-use crate::constants::CP_AMM_INITIALIZE_PRICE_IN_LAMPORTS;
-use crate::state::{AmmsConfig, cp_amm::{
+use crate::error::ErrorCode;
+use crate::state::{AmmsConfig, AmmsConfigsManager, MintEquivalences, PoolCreationLimiter, PoolCreatorPermit, PoolIndex, cp_amm::{
     CpAmm, 
     CpAmmCalculate
 }};
 use crate::utils::system_instructions::TransferLamportsInstruction;
 use crate::utils::token_accounts_instructions::CreatePdaTokenAccountInstruction;
+use crate::utils::token_metadata::log_invalid_mint;
 use crate::utils::validate_tradable_mint;
 
 #[derive(Accounts)]
@@ -26,18 +27,43 @@ pub struct InitializeCpAmm<'info> {
     #[account(
         init,
         payer = signer,
-        mint::decimals = CpAmm::LP_MINT_INITIAL_DECIMALS,
+        mint::decimals = CpAmm::calculate_lp_mint_decimals(base_mint.decimals, quote_mint.decimals),
         mint::authority = cp_amm,
         mint::token_program = lp_token_program
     )]
     pub lp_mint: Box<Account<'info, Mint>>,
     
     #[account(
+        mut,
         constraint = amms_config.fee_authority().key() == fee_authority.key(),
         seeds = [AmmsConfig::SEED, amms_config.id.to_le_bytes().as_ref()],
         bump = amms_config.bump()
     )]
     pub amms_config: Box<Account<'info, AmmsConfig>>,
+
+    #[account(
+        seeds = [AmmsConfigsManager::SEED],
+        bump = amms_configs_manager.bump()
+    )]
+    pub amms_configs_manager: Box<Account<'info, AmmsConfigsManager>>,
+
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = 8 + PoolCreationLimiter::INIT_SPACE,
+        seeds = [PoolCreationLimiter::SEED, signer.key().as_ref()],
+        bump
+    )]
+    pub pool_creation_limiter: Option<Box<Account<'info, PoolCreationLimiter>>>,
+
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = 8 + PoolIndex::INIT_SPACE,
+        seeds = [PoolIndex::SEED, amms_config.key().as_ref(), amms_config.pool_index_bucket().to_le_bytes().as_ref()],
+        bump
+    )]
+    pub pool_index: Box<Account<'info, PoolIndex>>,
     
     #[account(
         init,
@@ -74,11 +100,34 @@ pub struct InitializeCpAmm<'info> {
     pub lp_token_program: Program<'info, Token>,
     pub base_token_program: Interface<'info, TokenInterface>,
     pub quote_token_program: Interface<'info, TokenInterface>,
+
+    #[account(
+        seeds = [MintEquivalences::SEED, amms_config.key().as_ref()],
+        bump = mint_equivalences.bump()
+    )]
+    pub mint_equivalences: Option<Box<Account<'info, MintEquivalences>>>,
+
+    /// CHECK: Metaplex metadata of the base mint, only read for the symbol logged on failed validation
+    pub base_mint_metadata: Option<UncheckedAccount<'info>>,
+    /// CHECK: Metaplex metadata of the quote mint, only read for the symbol logged on failed validation
+    pub quote_mint_metadata: Option<UncheckedAccount<'info>>,
+
+    #[account(
+        seeds = [PoolCreatorPermit::SEED, amms_config.key().as_ref(), signer.key().as_ref()],
+        bump = pool_creator_permit.bump()
+    )]
+    pub pool_creator_permit: Option<Box<Account<'info, PoolCreatorPermit>>>,
 }
 
 pub(crate) fn handler(ctx: Context<InitializeCpAmm>) -> Result<()> {
     ctx.accounts.validate_base_mint()?;
     ctx.accounts.validate_quote_mint()?;
+    ctx.accounts.validate_mints_equivalence()?;
+    ctx.accounts.validate_pool_creator_permit()?;
+    ctx.accounts.register_pool_creation(ctx.bumps.pool_creation_limiter)?;
+    // Priced before the pool is counted, so the step curve prices it by the pools created before
+    let initialize_price_in_lamports = ctx.accounts.amms_config.cp_amm_initialize_price_in_lamports();
+    ctx.accounts.register_in_pool_index(ctx.bumps.pool_index)?;
     {
         let cp_amm_key = ctx.accounts.cp_amm.key();
         {
@@ -102,7 +151,7 @@ pub(crate) fn handler(ctx: Context<InitializeCpAmm>) -> Result<()> {
     }
     let accounts = ctx.accounts;
 
-    let pay_initial_lamports_instruction = Box::new(accounts.get_pay_initial_lamports_instruction(CP_AMM_INITIALIZE_PRICE_IN_LAMPORTS)?);
+    let pay_initial_lamports_instruction = Box::new(accounts.get_pay_initial_lamports_instruction(initialize_price_in_lamports)?);
     pay_initial_lamports_instruction.execute()?;
     
     accounts.cp_amm.initialize(
@@ -117,19 +166,64 @@ pub(crate) fn handler(ctx: Context<InitializeCpAmm>) -> Result<()> {
         ctx.bumps.cp_amm,
         ctx.bumps.cp_amm_base_vault,
         ctx.bumps.cp_amm_quote_vault,
-        ctx.bumps.cp_amm_locked_lp_vault
+        ctx.bumps.cp_amm_locked_lp_vault,
+        Clock::get()?.epoch
     )
 }
 
 impl<'info> InitializeCpAmm<'info>{
     fn validate_base_mint(&self) -> Result<()> {
         let base_mint = self.base_mint.as_ref();
-        validate_tradable_mint(base_mint)
+        self.amms_config.validate_mint_decimals(base_mint.decimals)
+            .and_then(|_| validate_tradable_mint(base_mint))
+            .map_err(|error| log_invalid_mint(&base_mint.to_account_info(), self.base_mint_metadata.as_deref(), error))
     }
     fn validate_quote_mint(&self) -> Result<()> {
         let quote_mint = self.quote_mint.as_ref();
-        validate_tradable_mint(quote_mint)
+        self.amms_config.validate_mint_decimals(quote_mint.decimals)
+            .and_then(|_| validate_tradable_mint(quote_mint))
+            .map_err(|error| log_invalid_mint(&quote_mint.to_account_info(), self.quote_mint_metadata.as_deref(), error))
+    }
+    fn validate_mints_equivalence(&self) -> Result<()> {
+        if !self.amms_config.has_mint_equivalences() {
+            return Ok(());
+        }
+        let mint_equivalences = self.mint_equivalences.as_ref().ok_or(ErrorCode::MintEquivalencesMissing)?;
+        mint_equivalences.validate_pair(&self.base_mint.key(), &self.quote_mint.key())
+    }
+    fn validate_pool_creator_permit(&self) -> Result<()> {
+        require!(
+            !self.amms_config.is_pool_creation_allow_listed() || self.pool_creator_permit.is_some(),
+            ErrorCode::PoolCreatorPermitMissing
+        );
+        Ok(())
     }
+    fn register_pool_creation(&mut self, pool_creation_limiter_bump: Option<u8>) -> Result<()> {
+        let pool_creation_window_slots = self.amms_configs_manager.pool_creation_window_slots();
+        if pool_creation_window_slots == 0 {
+            return Ok(());
+        }
+        let (Some(pool_creation_limiter), Some(pool_creation_limiter_bump)) = (self.pool_creation_limiter.as_mut(), pool_creation_limiter_bump) else {
+            return err!(ErrorCode::PoolCreationLimiterMissing);
+        };
+        if !pool_creation_limiter.is_initialized() {
+            pool_creation_limiter.initialize(self.signer.key(), pool_creation_limiter_bump);
+        }
+        pool_creation_limiter.register_pool_creation(
+            Clock::get()?.slot,
+            pool_creation_window_slots,
+            self.amms_configs_manager.max_pools_per_window()
+        )
+    }
+    fn register_in_pool_index(&mut self, pool_index_bump: u8) -> Result<()> {
+        if !self.pool_index.is_initialized() {
+            self.pool_index.initialize(self.amms_config.key(), self.amms_config.pool_index_bucket(), pool_index_bump);
+        }
+        self.pool_index.push(self.cp_amm.key())?;
+        self.amms_config.increment_pools_count();
+        Ok(())
+    }
+    #[inline(never)]
     fn get_pay_initial_lamports_instruction(&self, lamports: u64) -> Result<TransferLamportsInstruction<'_, '_, '_, 'info>>{
         TransferLamportsInstruction::new(
             lamports,
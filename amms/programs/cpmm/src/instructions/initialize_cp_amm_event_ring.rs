@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+use crate::state::VersionedAccount;
+use crate::state::cp_amm::{CpAmm, EventRing};
+
+#[derive(Accounts)]
+pub struct InitializeCpAmmEventRing<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        seeds = [CpAmm::SEED, cp_amm.lp_mint.as_ref()],
+        bump = cp_amm.bump()
+    )]
+    pub cp_amm: Box<Account<'info, CpAmm>>,
+
+    #[account(
+        init,
+        payer = signer,
+        space = 8 + EventRing::INIT_SPACE,
+        seeds = [EventRing::SEED, cp_amm.key().as_ref()],
+        bump
+    )]
+    pub event_ring: AccountLoader<'info, EventRing>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Permissionlessly creates the event ring of a pool, which swaps, provides and withdrawals passing it
+/// append their records to, for indexers reading account data instead of program logs.
+pub(crate) fn handler(ctx: Context<InitializeCpAmmEventRing>) -> Result<()> {
+    ctx.accounts.cp_amm.assert_layout_version()?;
+    let cp_amm_key = ctx.accounts.cp_amm.key();
+    ctx.accounts.event_ring.load_init()?.initialize(cp_amm_key, ctx.bumps.event_ring);
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+use crate::state::VersionedAccount;
+use crate::state::cp_amm::{CpAmm, FeeCheckpoints, PoolStats, PoolStatsShard};
+
+#[derive(Accounts)]
+pub struct InitializeCpAmmFeeCheckpoints<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        seeds = [CpAmm::SEED, cp_amm.lp_mint.as_ref()],
+        bump = cp_amm.bump()
+    )]
+    pub cp_amm: Box<Account<'info, CpAmm>>,
+
+    #[account(
+        init,
+        payer = signer,
+        space = 8 + FeeCheckpoints::INIT_SPACE,
+        seeds = [FeeCheckpoints::SEED, cp_amm.key().as_ref()],
+        bump
+    )]
+    pub fee_checkpoints: Box<Account<'info, FeeCheckpoints>>,
+
+    #[account(
+        seeds = [PoolStatsShard::SEED, cp_amm.key().as_ref(), [0u8].as_ref()],
+        bump
+    )]
+    /// CHECK: First `PoolStatsShard` PDA of the pool, its statistics are included only if initialized
+    pub pool_stats_shard_0: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [PoolStatsShard::SEED, cp_amm.key().as_ref(), [1u8].as_ref()],
+        bump
+    )]
+    /// CHECK: Second `PoolStatsShard` PDA of the pool, its statistics are included only if initialized
+    pub pool_stats_shard_1: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Creates the providers fee checkpoints ring buffer of a pool, recording its first checkpoint.
+pub(crate) fn handler(ctx: Context<InitializeCpAmmFeeCheckpoints>) -> Result<()> {
+    ctx.accounts.cp_amm.assert_layout_version()?;
+    let cp_amm_key = ctx.accounts.cp_amm.key();
+    let stats = ctx.accounts.get_stats()?;
+    ctx.accounts.fee_checkpoints.initialize(cp_amm_key, ctx.bumps.fee_checkpoints, Clock::get()?.unix_timestamp, &stats);
+    Ok(())
+}
+
+impl<'info> InitializeCpAmmFeeCheckpoints<'info> {
+    fn get_stats(&self) -> Result<PoolStats> {
+        PoolStatsShard::aggregate_stats(
+            self.cp_amm.stats(),
+            &[self.pool_stats_shard_0.as_ref(), self.pool_stats_shard_1.as_ref()]
+        )
+    }
+}
\ No newline at end of file
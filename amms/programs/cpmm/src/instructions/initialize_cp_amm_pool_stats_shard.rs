@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+use crate::error::ErrorCode;
+use crate::state::VersionedAccount;
+use crate::state::cp_amm::{CpAmm, PoolStatsShard};
+
+#[derive(Accounts)]
+#[instruction(shard_index: u8)]
+pub struct InitializeCpAmmPoolStatsShard<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        seeds = [CpAmm::SEED, cp_amm.lp_mint.as_ref()],
+        bump = cp_amm.bump()
+    )]
+    pub cp_amm: Box<Account<'info, CpAmm>>,
+
+    #[account(
+        init,
+        payer = signer,
+        space = 8 + PoolStatsShard::INIT_SPACE,
+        seeds = [PoolStatsShard::SEED, cp_amm.key().as_ref(), [shard_index].as_ref()],
+        bump
+    )]
+    pub pool_stats_shard: Box<Account<'info, PoolStatsShard>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Permissionlessly creates a trading statistics shard of a pool, which swaps may record
+/// their statistics on instead of the pool account.
+pub(crate) fn handler(ctx: Context<InitializeCpAmmPoolStatsShard>, shard_index: u8) -> Result<()> {
+    ctx.accounts.cp_amm.assert_layout_version()?;
+    require!(shard_index < PoolStatsShard::SHARDS_COUNT, ErrorCode::InvalidPoolStatsShardIndex);
+    let cp_amm_key = ctx.accounts.cp_amm.key();
+    ctx.accounts.pool_stats_shard.initialize(cp_amm_key, shard_index, ctx.bumps.pool_stats_shard);
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+use crate::state::VersionedAccount;
+use crate::state::cp_amm::{CpAmm, VirtualPriceCheckpoints};
+
+#[derive(Accounts)]
+pub struct InitializeCpAmmVirtualPriceCheckpoints<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        seeds = [CpAmm::SEED, cp_amm.lp_mint.as_ref()],
+        bump = cp_amm.bump()
+    )]
+    pub cp_amm: Box<Account<'info, CpAmm>>,
+
+    #[account(
+        init,
+        payer = signer,
+        space = 8 + VirtualPriceCheckpoints::INIT_SPACE,
+        seeds = [VirtualPriceCheckpoints::SEED, cp_amm.key().as_ref()],
+        bump
+    )]
+    pub virtual_price_checkpoints: Box<Account<'info, VirtualPriceCheckpoints>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Creates the daily virtual price checkpoints ring buffer of a pool, recording its first checkpoint.
+pub(crate) fn handler(ctx: Context<InitializeCpAmmVirtualPriceCheckpoints>) -> Result<()> {
+    ctx.accounts.cp_amm.assert_layout_version()?;
+    let cp_amm_key = ctx.accounts.cp_amm.key();
+    let virtual_price = ctx.accounts.cp_amm.get_virtual_price()?;
+    ctx.accounts.virtual_price_checkpoints.initialize(cp_amm_key, ctx.bumps.virtual_price_checkpoints, Clock::get()?.unix_timestamp, virtual_price);
+    Ok(())
+}
\ No newline at end of file
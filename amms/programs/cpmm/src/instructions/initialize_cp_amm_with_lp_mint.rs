@@ -0,0 +1,304 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{token::{Mint, Token}, token_interface};
+use anchor_spl::token::spl_token::instruction::AuthorityType;
+use anchor_spl::token_interface::TokenInterface;
+use crate::error::ErrorCode;
+use crate::state::{AmmsConfig, AmmsConfigsManager, MintEquivalences, PoolCreationLimiter, PoolCreatorPermit, PoolIndex, cp_amm::{
+    CpAmm, 
+    CpAmmCalculate
+}};
+use crate::utils::system_instructions::TransferLamportsInstruction;
+use crate::utils::token_accounts_instructions::CreatePdaTokenAccountInstruction;
+use crate::utils::token_instructions::SetMintAuthorityInstruction;
+use crate::utils::token_metadata::log_invalid_mint;
+use crate::utils::validate_tradable_mint;
+
+#[derive(Accounts)]
+pub struct InitializeCpAmmWithLpMint<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(mut)]
+    /// CHECK: Amms config's fee authority can be arbitrary type
+    pub fee_authority: AccountInfo<'info>,
+    pub base_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+    #[account(
+        constraint = base_mint.key() != quote_mint.key()
+    )]
+    pub quote_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+    
+    /// A pre-existing empty LP mint, e.g. at a vanity address. Its authorities are validated
+    /// in `validate_lp_mint` and handed over to the `CpAmm` by the signer if they hold them.
+    #[account(
+        mut,
+        constraint = lp_mint.key() != base_mint.key() && lp_mint.key() != quote_mint.key() @ ErrorCode::LpMintInvalid,
+        constraint = lp_mint.supply == 0 @ ErrorCode::LpMintInvalid,
+        constraint = lp_mint.decimals == CpAmm::calculate_lp_mint_decimals(base_mint.decimals, quote_mint.decimals) @ ErrorCode::LpMintInvalid
+    )]
+    pub lp_mint: Box<Account<'info, Mint>>,
+    
+    #[account(
+        mut,
+        constraint = amms_config.fee_authority().key() == fee_authority.key(),
+        seeds = [AmmsConfig::SEED, amms_config.id.to_le_bytes().as_ref()],
+        bump = amms_config.bump()
+    )]
+    pub amms_config: Box<Account<'info, AmmsConfig>>,
+
+    #[account(
+        seeds = [AmmsConfigsManager::SEED],
+        bump = amms_configs_manager.bump()
+    )]
+    pub amms_configs_manager: Box<Account<'info, AmmsConfigsManager>>,
+
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = 8 + PoolCreationLimiter::INIT_SPACE,
+        seeds = [PoolCreationLimiter::SEED, signer.key().as_ref()],
+        bump
+    )]
+    pub pool_creation_limiter: Option<Box<Account<'info, PoolCreationLimiter>>>,
+
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = 8 + PoolIndex::INIT_SPACE,
+        seeds = [PoolIndex::SEED, amms_config.key().as_ref(), amms_config.pool_index_bucket().to_le_bytes().as_ref()],
+        bump
+    )]
+    pub pool_index: Box<Account<'info, PoolIndex>>,
+    
+    #[account(
+        init,
+        payer = signer,
+        space = 8 + CpAmm::INIT_SPACE,
+        seeds = [CpAmm::SEED, lp_mint.key().as_ref()],
+        bump
+    )]
+    pub cp_amm: Box<Account<'info, CpAmm>>,
+
+    #[account(
+        mut,
+        seeds = [CpAmm::VAULT_SEED, cp_amm.key().as_ref(), base_mint.key().as_ref()],
+        bump
+    )]
+    pub cp_amm_base_vault: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [CpAmm::VAULT_SEED, cp_amm.key().as_ref(), quote_mint.key().as_ref()],
+        bump
+    )]
+    pub cp_amm_quote_vault: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [CpAmm::VAULT_SEED, cp_amm.key().as_ref(), lp_mint.key().as_ref()],
+        bump
+    )]
+    pub cp_amm_locked_lp_vault: AccountInfo<'info>,
+    
+    pub rent: Sysvar<'info, Rent>,
+    pub system_program: Program<'info, System>,
+    pub lp_token_program: Program<'info, Token>,
+    pub base_token_program: Interface<'info, TokenInterface>,
+    pub quote_token_program: Interface<'info, TokenInterface>,
+
+    #[account(
+        seeds = [MintEquivalences::SEED, amms_config.key().as_ref()],
+        bump = mint_equivalences.bump()
+    )]
+    pub mint_equivalences: Option<Box<Account<'info, MintEquivalences>>>,
+
+    /// CHECK: Metaplex metadata of the base mint, only read for the symbol logged on failed validation
+    pub base_mint_metadata: Option<UncheckedAccount<'info>>,
+    /// CHECK: Metaplex metadata of the quote mint, only read for the symbol logged on failed validation
+    pub quote_mint_metadata: Option<UncheckedAccount<'info>>,
+
+    #[account(
+        seeds = [PoolCreatorPermit::SEED, amms_config.key().as_ref(), signer.key().as_ref()],
+        bump = pool_creator_permit.bump()
+    )]
+    pub pool_creator_permit: Option<Box<Account<'info, PoolCreatorPermit>>>,
+}
+
+pub(crate) fn handler(ctx: Context<InitializeCpAmmWithLpMint>) -> Result<()> {
+    ctx.accounts.validate_base_mint()?;
+    ctx.accounts.validate_quote_mint()?;
+    ctx.accounts.validate_mints_equivalence()?;
+    ctx.accounts.validate_pool_creator_permit()?;
+    ctx.accounts.validate_lp_mint()?;
+    ctx.accounts.hand_over_lp_mint_authorities()?;
+    ctx.accounts.register_pool_creation(ctx.bumps.pool_creation_limiter)?;
+    // Priced before the pool is counted, so the step curve prices it by the pools created before
+    let initialize_price_in_lamports = ctx.accounts.amms_config.cp_amm_initialize_price_in_lamports();
+    ctx.accounts.register_in_pool_index(ctx.bumps.pool_index)?;
+    {
+        let cp_amm_key = ctx.accounts.cp_amm.key();
+        {
+            let base_mint_key = ctx.accounts.base_mint.key();
+            let create_cp_amm_base_vault = Box::new(ctx.accounts.get_create_cp_amm_base_vault_instruction()?);
+            let cp_amm_base_vault_seeds = [CpAmm::VAULT_SEED, cp_amm_key.as_ref(), base_mint_key.as_ref(), &[ctx.bumps.cp_amm_base_vault]];
+            create_cp_amm_base_vault.execute(&[&cp_amm_base_vault_seeds])?;
+        }
+        {
+            let quote_mint_key = ctx.accounts.quote_mint.key();
+            let create_cp_amm_quote_vault = Box::new(ctx.accounts.get_create_cp_amm_quote_vault_instruction()?);
+            let cp_amm_quote_vault_seeds = [CpAmm::VAULT_SEED, cp_amm_key.as_ref(),quote_mint_key.as_ref(), &[ctx.bumps.cp_amm_quote_vault]];
+            create_cp_amm_quote_vault.execute(&[&cp_amm_quote_vault_seeds])?;
+        }
+        {
+            let lp_mint_key = ctx.accounts.lp_mint.key();
+            let create_cp_amm_locked_lp_vault = Box::new(ctx.accounts.get_create_cp_amm_locked_lp_vault_instruction()?);
+            let cp_amm_locked_lp_vault_seeds = [CpAmm::VAULT_SEED, cp_amm_key.as_ref(), lp_mint_key.as_ref(), &[ctx.bumps.cp_amm_locked_lp_vault]];
+            create_cp_amm_locked_lp_vault.execute(&[&cp_amm_locked_lp_vault_seeds])?;
+        }
+    }
+    let accounts = ctx.accounts;
+
+    let pay_initial_lamports_instruction = Box::new(accounts.get_pay_initial_lamports_instruction(initialize_price_in_lamports)?);
+    pay_initial_lamports_instruction.execute()?;
+    
+    accounts.cp_amm.initialize(
+        &accounts.base_mint,
+        &accounts.quote_mint,
+        &accounts.lp_mint,
+        &accounts.amms_config,
+        &accounts.signer.to_account_info(),
+        &accounts.cp_amm_base_vault,
+        &accounts.cp_amm_quote_vault,
+        &accounts.cp_amm_locked_lp_vault,
+        ctx.bumps.cp_amm,
+        ctx.bumps.cp_amm_base_vault,
+        ctx.bumps.cp_amm_quote_vault,
+        ctx.bumps.cp_amm_locked_lp_vault,
+        Clock::get()?.epoch
+    )
+}
+
+impl<'info> InitializeCpAmmWithLpMint<'info>{
+    fn validate_base_mint(&self) -> Result<()> {
+        let base_mint = self.base_mint.as_ref();
+        self.amms_config.validate_mint_decimals(base_mint.decimals)
+            .and_then(|_| validate_tradable_mint(base_mint))
+            .map_err(|error| log_invalid_mint(&base_mint.to_account_info(), self.base_mint_metadata.as_deref(), error))
+    }
+    fn validate_quote_mint(&self) -> Result<()> {
+        let quote_mint = self.quote_mint.as_ref();
+        self.amms_config.validate_mint_decimals(quote_mint.decimals)
+            .and_then(|_| validate_tradable_mint(quote_mint))
+            .map_err(|error| log_invalid_mint(&quote_mint.to_account_info(), self.quote_mint_metadata.as_deref(), error))
+    }
+    fn validate_mints_equivalence(&self) -> Result<()> {
+        if !self.amms_config.has_mint_equivalences() {
+            return Ok(());
+        }
+        let mint_equivalences = self.mint_equivalences.as_ref().ok_or(ErrorCode::MintEquivalencesMissing)?;
+        mint_equivalences.validate_pair(&self.base_mint.key(), &self.quote_mint.key())
+    }
+    fn validate_pool_creator_permit(&self) -> Result<()> {
+        require!(
+            !self.amms_config.is_pool_creation_allow_listed() || self.pool_creator_permit.is_some(),
+            ErrorCode::PoolCreatorPermitMissing
+        );
+        Ok(())
+    }
+    fn validate_lp_mint(&self) -> Result<()> {
+        let cp_amm_key = self.cp_amm.key();
+        let signer_key = self.signer.key();
+        // The mint authority must end up with the CpAmm alone, so it can't be missing or held by a third party
+        let mint_authority = Option::<Pubkey>::from(self.lp_mint.mint_authority).ok_or(ErrorCode::LpMintInvalid)?;
+        require!(mint_authority == cp_amm_key || mint_authority == signer_key, ErrorCode::LpMintInvalid);
+        if let Some(freeze_authority) = Option::<Pubkey>::from(self.lp_mint.freeze_authority) {
+            require!(freeze_authority == cp_amm_key || freeze_authority == signer_key, ErrorCode::LpMintInvalid);
+        }
+        Ok(())
+    }
+    fn hand_over_lp_mint_authorities(&self) -> Result<()> {
+        let signer_key = self.signer.key();
+        if Option::<Pubkey>::from(self.lp_mint.mint_authority) == Some(signer_key) {
+            self.get_set_lp_mint_authority_instruction(AuthorityType::MintTokens).execute()?;
+        }
+        if Option::<Pubkey>::from(self.lp_mint.freeze_authority) == Some(signer_key) {
+            self.get_set_lp_mint_authority_instruction(AuthorityType::FreezeAccount).execute()?;
+        }
+        Ok(())
+    }
+    fn register_pool_creation(&mut self, pool_creation_limiter_bump: Option<u8>) -> Result<()> {
+        let pool_creation_window_slots = self.amms_configs_manager.pool_creation_window_slots();
+        if pool_creation_window_slots == 0 {
+            return Ok(());
+        }
+        let (Some(pool_creation_limiter), Some(pool_creation_limiter_bump)) = (self.pool_creation_limiter.as_mut(), pool_creation_limiter_bump) else {
+            return err!(ErrorCode::PoolCreationLimiterMissing);
+        };
+        if !pool_creation_limiter.is_initialized() {
+            pool_creation_limiter.initialize(self.signer.key(), pool_creation_limiter_bump);
+        }
+        pool_creation_limiter.register_pool_creation(
+            Clock::get()?.slot,
+            pool_creation_window_slots,
+            self.amms_configs_manager.max_pools_per_window()
+        )
+    }
+    fn register_in_pool_index(&mut self, pool_index_bump: u8) -> Result<()> {
+        if !self.pool_index.is_initialized() {
+            self.pool_index.initialize(self.amms_config.key(), self.amms_config.pool_index_bucket(), pool_index_bump);
+        }
+        self.pool_index.push(self.cp_amm.key())?;
+        self.amms_config.increment_pools_count();
+        Ok(())
+    }
+    #[inline(never)]
+    fn get_set_lp_mint_authority_instruction(&self, authority_type: AuthorityType) -> SetMintAuthorityInstruction<'_, '_, '_, 'info>{
+        SetMintAuthorityInstruction::new(
+            authority_type,
+            &self.lp_mint,
+            self.signer.to_account_info(),
+            self.cp_amm.key(),
+            &self.lp_token_program
+        )
+    }
+    #[inline(never)]
+    fn get_pay_initial_lamports_instruction(&self, lamports: u64) -> Result<TransferLamportsInstruction<'_, '_, '_, 'info>>{
+        TransferLamportsInstruction::new(
+            lamports,
+            self.signer.to_account_info(),
+            self.fee_authority.to_account_info(),
+            &self.system_program
+        )
+    }
+    #[inline(never)]
+    fn get_create_cp_amm_base_vault_instruction(&self) -> Result<CreatePdaTokenAccountInstruction<'_, '_, '_, 'info>>{
+        CreatePdaTokenAccountInstruction::try_new(
+            self.signer.to_account_info(),
+            self.cp_amm_base_vault.to_account_info(),
+            self.cp_amm.to_account_info(),
+            self.base_mint.to_account_info(),
+            self.base_token_program.to_account_info(),
+            self.system_program.to_account_info()
+        )
+    }
+    #[inline(never)]
+    fn get_create_cp_amm_quote_vault_instruction(&self) -> Result<CreatePdaTokenAccountInstruction<'_, '_, '_, 'info>>{
+        CreatePdaTokenAccountInstruction::try_new(
+            self.signer.to_account_info(),
+            self.cp_amm_quote_vault.to_account_info(),
+            self.cp_amm.to_account_info(),
+            self.quote_mint.to_account_info(),
+            self.quote_token_program.to_account_info(),
+            self.system_program.to_account_info()
+        )
+    }
+    #[inline(never)]
+    fn get_create_cp_amm_locked_lp_vault_instruction(&self) -> Result<CreatePdaTokenAccountInstruction<'_, '_, '_, 'info>>{
+        CreatePdaTokenAccountInstruction::try_new(
+            self.signer.to_account_info(),
+            self.cp_amm_locked_lp_vault.to_account_info(),
+            self.cp_amm.to_account_info(),
+            self.lp_mint.to_account_info(),
+            self.lp_token_program.to_account_info(),
+            self.system_program.to_account_info()
+        )
+    }
+}
\ No newline at end of file
@@ -5,8 +5,12 @@ use anchor_spl::{
     token_interface::{TokenAccount as InterfaceTokenAccount, Mint, TokenInterface}
 };
 use anchor_spl::associated_token::AssociatedToken;
-use crate::state::{AmmsConfig, cp_amm::CpAmm};
+use crate::liquidity_hook::{LiquidityDeltaKind, LiquiditySnapshot};
+use crate::state::{AmmsConfig, cp_amm::{CpAmm, EventRecordKind, EventRing}, VersionedAccount};
 use crate::utils::{
+    record_liquidity_change, LiquidityChange,
+    liquidity_hook_instructions::notify_liquidity_subscriber,
+    math::Q64_128,
     token_instructions::{MintTokensInstructions, TransferTokensInstruction}
 };
 
@@ -82,18 +86,63 @@ pub struct LaunchCpAmm<'info>{
     pub quote_token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
     pub associated_token_program: Program<'info, AssociatedToken>,
+
+    #[account(
+        mut,
+        constraint = event_ring.load()?.cp_amm() == &cp_amm.key()
+    )]
+    pub event_ring: Option<AccountLoader<'info, EventRing>>,
 }
 
-pub(crate) fn handler(ctx: Context<LaunchCpAmm>, base_liquidity: u64, quote_liquidity: u64) -> Result<()> {
-    
+/// Launches the pool with its initial liquidity.
+///
+/// The launch price must fit into the `AmmsConfig` bound. With `expected_base_quote_ratio_sqrt` set,
+/// the launch also fails if the pool ratio deviates from the creator's expectation beyond the tolerance.
+/// With a non-zero `warm_up_slots`, the pool is provide-only for that many slots after the launch,
+/// so liquidity providers can join at the launch ratio before trading opens.
+///
+/// The LP mint must be empty and can't be frozen by anyone but the pool, so no LP tokens
+/// exist besides the ones minted for the launch liquidity.
+///
+/// The launch is recorded like any liquidity change, see `record_liquidity_change`, so the display
+/// data is set from the launch and an event ring initialized beforehand starts with the launch.
+pub(crate) fn handler<'info>(ctx: Context<'_, '_, '_, 'info, LaunchCpAmm<'info>>, base_liquidity: u64, quote_liquidity: u64, expected_base_quote_ratio_sqrt: Option<(Q64_128, u16)>, warm_up_slots: u64) -> Result<()> {
+    ctx.accounts.cp_amm.assert_layout_version()?;
+    CpAmm::validate_launch_lp_mint(
+        &ctx.accounts.cp_amm.key(),
+        ctx.accounts.lp_mint.supply,
+        ctx.accounts.lp_mint.freeze_authority.into()
+    )?;
+    let trading_open_slot = CpAmm::calculate_trading_open_slot(Clock::get()?.slot, warm_up_slots)?;
+    let liquidity_snapshot = LiquiditySnapshot::of(&ctx.accounts.cp_amm);
     let provide_base_liquidity_instruction = Box::new(ctx.accounts.get_provide_base_liquidity_transfer_instruction(base_liquidity)?);
     let provide_quote_liquidity_instruction = Box::new(ctx.accounts.get_provide_quote_liquidity_transfer_instruction(quote_liquidity)?);
 
     let base_liquidity_to_provide = provide_base_liquidity_instruction.get_amount_after_fee();
     let quote_liquidity_to_provide = provide_quote_liquidity_instruction.get_amount_after_fee();
 
-    let launch_payload = Box::new(ctx.accounts.cp_amm.get_launch_payload(base_liquidity_to_provide, quote_liquidity_to_provide)?);
-
+    ctx.accounts.amms_config.validate_launch_price(
+        base_liquidity_to_provide,
+        quote_liquidity_to_provide,
+        ctx.accounts.base_mint.decimals,
+        ctx.accounts.quote_mint.decimals
+    )?;
+    let launch_payload = Box::new(ctx.accounts.cp_amm.get_launch_payload(
+        base_liquidity_to_provide,
+        quote_liquidity_to_provide,
+        ctx.accounts.base_mint.decimals,
+        ctx.accounts.quote_mint.decimals,
+        ctx.accounts.lp_mint.decimals,
+        expected_base_quote_ratio_sqrt
+    )?);
+
+    let liquidity_change = LiquidityChange {
+        kind: EventRecordKind::Launch,
+        signer: ctx.accounts.creator.key(),
+        base_amount: base_liquidity_to_provide,
+        quote_amount: quote_liquidity_to_provide,
+        lp_tokens: launch_payload.launch_liquidity(),
+    };
     let launch_liquidity_mint_instruction = Box::new(ctx.accounts.get_launch_liquidity_mint_instruction(launch_payload.launch_liquidity()));
     let initial_locked_liquidity_mint_instruction = Box::new(ctx.accounts.get_initial_locked_liquidity_mint_instruction(launch_payload.initial_locked_liquidity()));
 
@@ -106,7 +155,9 @@ pub(crate) fn handler(ctx: Context<LaunchCpAmm>, base_liquidity: u64, quote_liqu
     launch_liquidity_mint_instruction.execute(Some(mint_instruction_seeds))?;
     initial_locked_liquidity_mint_instruction.execute(Some(mint_instruction_seeds))?;
 
-    ctx.accounts.cp_amm.launch(*launch_payload);
+    ctx.accounts.cp_amm.launch(*launch_payload, trading_open_slot);
+    record_liquidity_change(&mut ctx.accounts.cp_amm, &ctx.accounts.amms_config, ctx.accounts.event_ring.as_ref(), liquidity_change, Clock::get()?.slot)?;
+    notify_liquidity_subscriber(&ctx.accounts.cp_amm, ctx.remaining_accounts, LiquidityDeltaKind::Launch, liquidity_snapshot)?;
     Ok(())
 }
 
@@ -0,0 +1,24 @@
+use anchor_lang::Accounts;
+use anchor_lang::prelude::*;
+use crate::state::{cp_amm::CpAmm, VersionedAccount};
+
+#[derive(Accounts)]
+pub struct LockFeeParameters<'info> {
+    #[account(
+        constraint = authority.key() == cp_amm.creator().key()
+    )]
+    authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [CpAmm::SEED, cp_amm.lp_mint.as_ref()],
+        bump = cp_amm.bump()
+    )]
+    cp_amm: Box<Account<'info, CpAmm>>,
+}
+
+/// Permanently freezes the creator fee share and the protocol fee override of the pool,
+/// so liquidity providers can rely on its fee parameters never changing.
+pub(crate) fn handler(ctx: Context<LockFeeParameters>) -> Result<()> {
+    ctx.accounts.cp_amm.assert_layout_version()?;
+    ctx.accounts.cp_amm.lock_fee_parameters()
+}
\ No newline at end of file
@@ -1,17 +1,103 @@
 #![allow(ambiguous_glob_reexports)]
 mod admin;
+mod wp_amm;
 pub use admin::*;
+pub use wp_amm::*;
 
 pub mod initialize_cp_amm;
+pub mod initialize_cp_amm_with_lp_mint;
 pub mod launch_cp_amm;
+pub mod close_stale_cp_amm;
+pub mod ensure_rent_exempt;
 pub mod provide_to_cp_amm;
+pub mod provide_split;
 pub mod withdraw_from_cp_amm;
+pub mod provide_to_cp_amm_as_delegate;
+pub mod withdraw_from_cp_amm_as_delegate;
 pub mod swap_in_cp_amm;
+pub mod swap_in_cp_amm_v2;
+pub mod swap_in_cp_amm_compact;
+pub mod swap_in_cp_amm_with_session;
+pub mod open_trading_session;
+pub mod close_trading_session;
+pub mod rebalance_in_cp_amm;
+pub mod arb_two_pools;
+pub mod report_vault_drift;
 pub mod collect_fees_from_cp_amm;
+pub mod collect_creator_fees;
+pub mod update_cp_amm_trading_schedule;
+pub mod update_cp_amm_paused_operations;
+pub mod update_cp_amm_creator_fee_share;
+pub mod lock_fee_parameters;
+pub mod enable_cp_amm_decimals_normalization;
+pub mod get_lp_value;
+pub mod initialize_cp_amm_fee_checkpoints;
+pub mod record_cp_amm_fee_checkpoint;
+pub mod get_trailing_apr;
+pub mod get_virtual_price;
+pub mod get_safe_spot_price;
+pub mod initialize_cp_amm_virtual_price_checkpoints;
+pub mod record_cp_amm_virtual_price_checkpoint;
+pub mod refresh_cp_amm_display_data;
+pub mod initialize_cp_amm_pool_stats_shard;
+pub mod initialize_cp_amm_event_ring;
+pub mod register_cp_amm_lookup_table;
+pub mod open_cp_amm_liquidity_position;
+pub mod open_trader_volume;
+pub mod get_loyalty_multiplier;
+pub mod transfer_lp_position;
+pub mod quote_gross_swap_input;
+pub mod quote_many;
+pub mod verify_cp_amm_lp_snapshot_leaf;
+pub mod borrow_from_cp_amm;
+pub mod repay_to_cp_amm;
+pub mod create_test_pool;
 
 pub use initialize_cp_amm::*;
+pub use initialize_cp_amm_with_lp_mint::*;
 pub use launch_cp_amm::*;
+pub use close_stale_cp_amm::*;
+pub use ensure_rent_exempt::*;
 pub use provide_to_cp_amm::*;
+pub use provide_split::*;
 pub use withdraw_from_cp_amm::*;
+pub use provide_to_cp_amm_as_delegate::*;
+pub use withdraw_from_cp_amm_as_delegate::*;
 pub use swap_in_cp_amm::*;
-pub use collect_fees_from_cp_amm::*;
\ No newline at end of file
+pub use swap_in_cp_amm_v2::*;
+pub use swap_in_cp_amm_compact::*;
+pub use swap_in_cp_amm_with_session::*;
+pub use open_trading_session::*;
+pub use close_trading_session::*;
+pub use rebalance_in_cp_amm::*;
+pub use arb_two_pools::*;
+pub use report_vault_drift::*;
+pub use collect_fees_from_cp_amm::*;
+pub use collect_creator_fees::*;
+pub use update_cp_amm_trading_schedule::*;
+pub use update_cp_amm_paused_operations::*;
+pub use update_cp_amm_creator_fee_share::*;
+pub use lock_fee_parameters::*;
+pub use enable_cp_amm_decimals_normalization::*;
+pub use get_lp_value::*;
+pub use initialize_cp_amm_fee_checkpoints::*;
+pub use record_cp_amm_fee_checkpoint::*;
+pub use get_trailing_apr::*;
+pub use get_virtual_price::*;
+pub use get_safe_spot_price::*;
+pub use initialize_cp_amm_virtual_price_checkpoints::*;
+pub use record_cp_amm_virtual_price_checkpoint::*;
+pub use refresh_cp_amm_display_data::*;
+pub use initialize_cp_amm_pool_stats_shard::*;
+pub use initialize_cp_amm_event_ring::*;
+pub use register_cp_amm_lookup_table::*;
+pub use open_cp_amm_liquidity_position::*;
+pub use open_trader_volume::*;
+pub use get_loyalty_multiplier::*;
+pub use transfer_lp_position::*;
+pub use quote_gross_swap_input::*;
+pub use quote_many::*;
+pub use verify_cp_amm_lp_snapshot_leaf::*;
+pub use borrow_from_cp_amm::*;
+pub use repay_to_cp_amm::*;
+pub use create_test_pool::*;
\ No newline at end of file
@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+use crate::state::VersionedAccount;
+use crate::state::cp_amm::{CpAmm, LiquidityPosition};
+
+#[derive(Accounts)]
+pub struct OpenCpAmmLiquidityPosition<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        seeds = [CpAmm::SEED, cp_amm.lp_mint.as_ref()],
+        bump = cp_amm.bump()
+    )]
+    pub cp_amm: Box<Account<'info, CpAmm>>,
+
+    #[account(
+        init,
+        payer = signer,
+        space = 8 + LiquidityPosition::INIT_SPACE,
+        seeds = [LiquidityPosition::SEED, cp_amm.key().as_ref(), signer.key().as_ref()],
+        bump
+    )]
+    pub liquidity_position: Box<Account<'info, LiquidityPosition>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Creates the signer's liquidity position in a pool, tracking how long the signer holds
+/// liquidity provided from now on.
+pub(crate) fn handler(ctx: Context<OpenCpAmmLiquidityPosition>) -> Result<()> {
+    ctx.accounts.cp_amm.assert_layout_version()?;
+    let cp_amm_key = ctx.accounts.cp_amm.key();
+    let owner = ctx.accounts.signer.key();
+    ctx.accounts.liquidity_position.initialize(cp_amm_key, owner, ctx.bumps.liquidity_position);
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+use crate::state::{AmmsConfig, TraderVolume};
+
+#[derive(Accounts)]
+pub struct OpenTraderVolume<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        seeds = [AmmsConfig::SEED, amms_config.id.to_le_bytes().as_ref()],
+        bump = amms_config.bump()
+    )]
+    pub amms_config: Box<Account<'info, AmmsConfig>>,
+
+    #[account(
+        init,
+        payer = signer,
+        space = 8 + TraderVolume::INIT_SPACE,
+        seeds = [TraderVolume::SEED, amms_config.key().as_ref(), signer.key().as_ref()],
+        bump
+    )]
+    pub trader_volume: Box<Account<'info, TraderVolume>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Creates the signer's volume tracker in the pools of a config, recording the volume of the swaps
+/// passing it from now on for the config's `TraderFeeTiers`.
+pub(crate) fn handler(ctx: Context<OpenTraderVolume>) -> Result<()> {
+    let amms_config_key = ctx.accounts.amms_config.key();
+    let trader = ctx.accounts.signer.key();
+    ctx.accounts.trader_volume.initialize(amms_config_key, trader, ctx.bumps.trader_volume, Clock::get()?.unix_timestamp);
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,55 @@
+use anchor_lang::prelude::*;
+use crate::state::VersionedAccount;
+use crate::state::cp_amm::{CpAmm, TradingSession};
+
+#[derive(Accounts)]
+#[instruction(session_key: Pubkey)]
+pub struct OpenTradingSession<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        seeds = [CpAmm::SEED, cp_amm.lp_mint.as_ref()],
+        bump = cp_amm.bump()
+    )]
+    pub cp_amm: Box<Account<'info, CpAmm>>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + TradingSession::INIT_SPACE,
+        seeds = [TradingSession::SEED, cp_amm.key().as_ref(), owner.key().as_ref(), session_key.as_ref()],
+        bump
+    )]
+    pub trading_session: Box<Account<'info, TradingSession>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Opens a trading session authorizing `session_key` to swap in the pool on behalf of the signer.
+///
+/// The session key only swaps the signer's tokens the signer approves to the session PDA as delegate,
+/// and the output tokens are always sent to the signer's token accounts. Each swap is bounded by
+/// `max_swap_quote_amount` and all swaps by `max_total_quote_volume`, both in quote tokens, until
+/// the session expires at `expires_at_slot`.
+pub(crate) fn handler(
+    ctx: Context<OpenTradingSession>,
+    session_key: Pubkey,
+    max_swap_quote_amount: u64,
+    max_total_quote_volume: u64,
+    expires_at_slot: u64
+) -> Result<()> {
+    ctx.accounts.cp_amm.assert_layout_version()?;
+    let cp_amm_key = ctx.accounts.cp_amm.key();
+    let owner = ctx.accounts.owner.key();
+    ctx.accounts.trading_session.initialize(
+        cp_amm_key,
+        owner,
+        session_key,
+        max_swap_quote_amount,
+        max_total_quote_volume,
+        expires_at_slot,
+        Clock::get()?.slot,
+        ctx.bumps.trading_session
+    )
+}
\ No newline at end of file
@@ -0,0 +1,186 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token;
+use anchor_spl::token::Token;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::error::ErrorCode;
+use crate::state::{AmmsConfig, cp_amm::{CpAmm, CpAmmCalculate, EventRecordKind, EventRing}, VersionedAccount};
+use crate::utils::{record_liquidity_change, LiquidityChange};
+use crate::utils::token_instructions::{MintTokensInstructions, TransferTokensInstruction};
+
+/// The maximal number of pools a deposit can be split across.
+pub const MAX_PROVIDE_SPLIT_POOLS: usize = 4;
+
+/// The number of remaining accounts describing each pool of the split.
+const PROVIDE_SPLIT_POOL_ACCOUNTS: usize = 7;
+
+#[derive(Accounts)]
+pub struct ProvideSplit<'info>{
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    pub base_mint: Box<InterfaceAccount<'info, Mint>>,
+    pub quote_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(mut)]
+    // Token program will check mint and authority via token_instructions instruction
+    pub signer_base_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut)]
+    // Token program will check mint and authority via token_instructions instruction
+    pub signer_quote_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub lp_token_program: Program<'info, Token>,
+    pub base_token_program: Interface<'info, TokenInterface>,
+    pub quote_token_program: Interface<'info, TokenInterface>,
+}
+
+/// Accounts of a single pool of the split, passed in the remaining accounts in the field order.
+struct ProvideSplitPool<'info> {
+    amms_config: Box<Account<'info, AmmsConfig>>,
+    cp_amm: Box<Account<'info, CpAmm>>,
+    lp_mint: Box<Account<'info, token::Mint>>,
+    cp_amm_base_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    cp_amm_quote_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    signer_lp_account: Box<Account<'info, token::TokenAccount>>,
+    event_ring: Option<AccountLoader<'info, EventRing>>,
+}
+
+/// Provides `base_liquidity` and `quote_liquidity` to several pools of the same pair, e.g. across fee tiers,
+/// splitting both amounts proportionally to `weights`.
+///
+/// Each pool is described in the remaining accounts by its `AmmsConfig`, `CpAmm`, LP mint, base and quote vaults
+/// the signer's LP token account and its optional `EventRing`, the program id if absent, in this order and
+/// in the order of `weights`.
+/// Every pool checks its own liquidity ratio and minimal LP tokens, so the pools must trade at close prices.
+/// Pools with a liquidity subscriber or a screening program are not supported, since the remaining accounts are taken by the pools.
+pub(crate) fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, ProvideSplit<'info>>, base_liquidity: u64, quote_liquidity: u64, weights: Vec<u16>) -> Result<()> {
+    require!(
+        !weights.is_empty() && weights.len() <= MAX_PROVIDE_SPLIT_POOLS && weights.iter().all(|weight| *weight > 0),
+        ErrorCode::ProvideSplitInvalidWeights
+    );
+    require!(
+        ctx.remaining_accounts.len() == weights.len() * PROVIDE_SPLIT_POOL_ACCOUNTS,
+        ErrorCode::ProvideSplitPoolsMismatch
+    );
+
+    let base_shares = CpAmm::calculate_split_liquidity(base_liquidity, &weights).ok_or(ErrorCode::ProvideSplitInvalidWeights)?;
+    let quote_shares = CpAmm::calculate_split_liquidity(quote_liquidity, &weights).ok_or(ErrorCode::ProvideSplitInvalidWeights)?;
+
+    let mut provided_cp_amms: Vec<Pubkey> = Vec::with_capacity(weights.len());
+    for ((pool_accounts, base_share), quote_share) in ctx.remaining_accounts.chunks_exact(PROVIDE_SPLIT_POOL_ACCOUNTS).zip(base_shares).zip(quote_shares) {
+        let mut pool = ctx.accounts.get_split_pool(pool_accounts)?;
+        require!(!provided_cp_amms.contains(&pool.cp_amm.key()), ErrorCode::ProvideSplitPoolsMismatch);
+        provided_cp_amms.push(pool.cp_amm.key());
+
+        ctx.accounts.provide_to_pool(&mut pool, base_share, quote_share)?;
+    }
+
+    Ok(())
+}
+
+impl<'info> ProvideSplit<'info> {
+    /// Deserializes and validates the accounts of a pool of the split.
+    #[inline(never)]
+    fn get_split_pool(&self, pool_accounts: &'info [AccountInfo<'info>]) -> Result<ProvideSplitPool<'info>> {
+        let pool = ProvideSplitPool {
+            amms_config: Box::new(Account::try_from(&pool_accounts[0])?),
+            cp_amm: Box::new(Account::try_from(&pool_accounts[1])?),
+            lp_mint: Box::new(Account::try_from(&pool_accounts[2])?),
+            cp_amm_base_vault: Box::new(InterfaceAccount::try_from(&pool_accounts[3])?),
+            cp_amm_quote_vault: Box::new(InterfaceAccount::try_from(&pool_accounts[4])?),
+            signer_lp_account: Box::new(Account::try_from(&pool_accounts[5])?),
+            event_ring: if pool_accounts[6].key() == crate::ID { None } else { Some(AccountLoader::try_from(&pool_accounts[6])?) },
+        };
+        let cp_amm = &pool.cp_amm;
+        require!(
+            pool_accounts[1].is_writable && pool_accounts[2].is_writable && pool_accounts[3].is_writable && pool_accounts[4].is_writable && pool_accounts[5].is_writable,
+            ErrorCode::ProvideSplitPoolsMismatch
+        );
+        require!(
+            cp_amm.is_launched() &&
+                pool.amms_config.key() == cp_amm.amms_config().key() &&
+                pool.lp_mint.key() == cp_amm.lp_mint &&
+                self.base_mint.key() == cp_amm.base_mint().key() &&
+                self.quote_mint.key() == cp_amm.quote_mint().key() &&
+                pool.cp_amm_base_vault.key() == cp_amm.base_vault().key() &&
+                pool.cp_amm_quote_vault.key() == cp_amm.quote_vault().key() &&
+                pool.signer_lp_account.mint == cp_amm.lp_mint &&
+                pool.signer_lp_account.owner == self.signer.key(),
+            ErrorCode::ProvideSplitPoolsMismatch
+        );
+        if let Some(event_ring) = pool.event_ring.as_ref() {
+            require!(pool_accounts[6].is_writable && event_ring.load()?.cp_amm() == &cp_amm.key(), ErrorCode::ProvideSplitPoolsMismatch);
+        }
+        require!(cp_amm.liquidity_subscriber().is_none(), ErrorCode::ProvideSplitLiquiditySubscriberUnsupported);
+        require!(pool.amms_config.screening_program().is_none(), ErrorCode::ProvideSplitScreeningUnsupported);
+        Ok(pool)
+    }
+
+    /// Provides the liquidity shares to a pool of the split and persists its updated state.
+    #[inline(never)]
+    fn provide_to_pool(&self, pool: &mut ProvideSplitPool<'info>, base_liquidity: u64, quote_liquidity: u64) -> Result<()> {
+        pool.cp_amm.assert_layout_version()?;
+        pool.cp_amm.check_vault_balances(pool.cp_amm_base_vault.amount, pool.cp_amm_quote_vault.amount)?;
+        pool.cp_amm.validate_lp_tokens_supply(pool.lp_mint.supply)?;
+        pool.cp_amm.check_protocol_fees_cap(pool.amms_config.protocol_fees_cap_basis_points())?;
+
+        let provide_base_liquidity_instruction = Box::new(TransferTokensInstruction::try_new(
+            base_liquidity,
+            &self.base_mint,
+            &self.signer_base_account,
+            self.signer.to_account_info(),
+            &pool.cp_amm_base_vault,
+            &self.base_token_program
+        )?);
+        let provide_quote_liquidity_instruction = Box::new(TransferTokensInstruction::try_new(
+            quote_liquidity,
+            &self.quote_mint,
+            &self.signer_quote_account,
+            self.signer.to_account_info(),
+            &pool.cp_amm_quote_vault,
+            &self.quote_token_program
+        )?);
+        let base_liquidity_to_provide = provide_base_liquidity_instruction.get_amount_after_fee();
+        let quote_liquidity_to_provide = provide_quote_liquidity_instruction.get_amount_after_fee();
+
+        let provide_payload = pool.cp_amm.get_provide_payload(
+            base_liquidity_to_provide,
+            quote_liquidity_to_provide,
+            pool.amms_config.min_provide_lp_tokens(),
+            pool.amms_config.adjust_liquidity_ratio_tolerance()
+        )?;
+        pool.cp_amm.check_lp_concentration(
+            &provide_payload,
+            pool.signer_lp_account.amount,
+            pool.amms_config.lp_concentration_share_basis_points(),
+            pool.amms_config.lp_concentration_guard_epochs(),
+            Clock::get()?.epoch
+        )?;
+
+        provide_base_liquidity_instruction.execute(None)?;
+        provide_quote_liquidity_instruction.execute(None)?;
+
+        let liquidity_mint_instruction = Box::new(MintTokensInstructions::new(
+            provide_payload.lp_tokens_to_mint(),
+            &pool.lp_mint,
+            pool.cp_amm.to_account_info(),
+            pool.signer_lp_account.to_account_info(),
+            &self.lp_token_program
+        ));
+
+        let cp_amm_seeds = pool.cp_amm.seeds();
+        let mint_instruction_seeds: &[&[&[u8]]] = &[&cp_amm_seeds];
+        liquidity_mint_instruction.execute(Some(mint_instruction_seeds))?;
+
+        pool.cp_amm.record_lp_concentration(&provide_payload, pool.signer_lp_account.amount, pool.amms_config.lp_concentration_share_basis_points());
+        pool.cp_amm.record_slot_start_price(Clock::get()?.slot);
+        let liquidity_change = LiquidityChange {
+            kind: EventRecordKind::Provide,
+            signer: self.signer.key(),
+            base_amount: base_liquidity_to_provide,
+            quote_amount: quote_liquidity_to_provide,
+            lp_tokens: provide_payload.lp_tokens_to_mint(),
+        };
+        pool.cp_amm.provide(provide_payload);
+        record_liquidity_change(&mut pool.cp_amm, &pool.amms_config, pool.event_ring.as_ref(), liquidity_change, Clock::get()?.slot)?;
+        // Remaining accounts are not persisted by Anchor
+        pool.cp_amm.exit(&crate::ID)
+    }
+}
\ No newline at end of file
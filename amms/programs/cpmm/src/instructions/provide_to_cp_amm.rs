@@ -3,9 +3,34 @@ use anchor_spl::associated_token::AssociatedToken;
 use anchor_spl::token;
 use anchor_spl::token::Token;
 use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
-use crate::state::{AmmsConfig, cp_amm::CpAmm};
+use crate::liquidity_hook::{LiquidityDeltaKind, LiquiditySnapshot};
+use crate::screening_hook::{LiquidityOperation, LiquidityOperationKind};
+use crate::state::{AmmsConfig, cp_amm::{CpAmm, EventRecordKind, EventRing, LiquidityPosition}, VersionedAccount};
+use crate::utils::{record_liquidity_change, LiquidityChange};
+use crate::utils::liquidity_hook_instructions::notify_liquidity_subscriber;
+use crate::utils::screening_hook_instructions::screen_liquidity_operation;
 use crate::utils::token_instructions::{MintTokensInstructions, TransferTokensInstruction};
 
+/// Accounts of `provide_to_cp_amm`, expected in the field order:
+///
+/// 0. `signer` - writable signer owning the provided token accounts and paying for the LP token account.
+/// 1. `base_mint`, 2. `quote_mint` - the tradable mints of the pool.
+/// 3. `lp_mint` - writable LP mint of the pool.
+/// 4. `signer_base_account`, 5. `signer_quote_account` - writable token accounts the liquidity is provided from.
+/// 6. `signer_lp_account` - writable LP associated token account of the signer, created if missing.
+/// 7. `amms_config` - the `AmmsConfig` of the pool.
+/// 8. `cp_amm` - writable pool.
+/// 9. `cp_amm_base_vault`, 10. `cp_amm_quote_vault` - writable vaults of the pool.
+/// 11. `associated_token_program`, 12. `lp_token_program`, 13. `base_token_program`, 14. `quote_token_program`, 15. `system_program`.
+/// 16. `liquidity_position` - optional writable `LiquidityPosition` of the signer, the program id if absent.
+/// 17. `event_ring` - optional writable `EventRing` of the pool recording the provide, the program id if absent.
+///
+/// The screening program of the `AmmsConfig`, if any, comes first in the remaining accounts, followed by
+/// the liquidity subscriber program and its forwarded accounts if the pool has one.
+///
+/// The `signer` is only required to sign, so it may be a PDA of another program signing via CPI signer seeds.
+/// Creating the LP token account transfers lamports from the `signer`, so a PDA signer must then be
+/// a system-owned account without data holding enough lamports, otherwise the account must exist beforehand.
 #[derive(Accounts)]
 pub struct ProvideToCpAmm<'info>{
     #[account(mut)]
@@ -69,61 +94,182 @@ pub struct ProvideToCpAmm<'info>{
     pub base_token_program: Interface<'info, TokenInterface>,
     pub quote_token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
+
+    #[account(
+        mut,
+        seeds = [LiquidityPosition::SEED, cp_amm.key().as_ref(), signer.key().as_ref()],
+        bump = liquidity_position.bump()
+    )]
+    pub liquidity_position: Option<Box<Account<'info, LiquidityPosition>>>,
+
+    #[account(
+        mut,
+        constraint = event_ring.load()?.cp_amm() == &cp_amm.key()
+    )]
+    pub event_ring: Option<AccountLoader<'info, EventRing>>,
 }
 
-pub(crate) fn handler(ctx: Context<ProvideToCpAmm>, base_liquidity: u64, quote_liquidity: u64) -> Result<()> {
+/// Provides liquidity to the pool.
+///
+/// Fails with `VaultBalanceDrift` if a vault holds less than the recorded liquidity and fees, anyone can
+/// then suspend the swaps and provides of the pool with `report_vault_drift`.
+pub(crate) fn handler<'info>(ctx: Context<'_, '_, '_, 'info, ProvideToCpAmm<'info>>, base_liquidity: u64, quote_liquidity: u64) -> Result<()> {
+    ProvideCore {
+        signer: ctx.accounts.signer.key(),
+        owner: ctx.accounts.signer.to_account_info(),
+        transfer_authority: ctx.accounts.signer.to_account_info(),
+        base_mint: &ctx.accounts.base_mint,
+        quote_mint: &ctx.accounts.quote_mint,
+        lp_mint: &ctx.accounts.lp_mint,
+        owner_base_account: &ctx.accounts.signer_base_account,
+        owner_quote_account: &ctx.accounts.signer_quote_account,
+        owner_lp_account: &ctx.accounts.signer_lp_account,
+        amms_config: &ctx.accounts.amms_config,
+        cp_amm: &mut ctx.accounts.cp_amm,
+        cp_amm_base_vault: &ctx.accounts.cp_amm_base_vault,
+        cp_amm_quote_vault: &ctx.accounts.cp_amm_quote_vault,
+        lp_token_program: &ctx.accounts.lp_token_program,
+        base_token_program: &ctx.accounts.base_token_program,
+        quote_token_program: &ctx.accounts.quote_token_program,
+        liquidity_position: ctx.accounts.liquidity_position.as_deref_mut(),
+        event_ring: ctx.accounts.event_ring.as_ref(),
+        remaining_accounts: ctx.remaining_accounts,
+    }.execute(base_liquidity, quote_liquidity)
+}
 
-    let provide_base_liquidity_instruction = Box::new(ctx.accounts.get_provide_base_liquidity_transfer_instruction(base_liquidity)?);
-    let provide_quote_liquidity_instruction = Box::new(ctx.accounts.get_provide_quote_liquidity_transfer_instruction(quote_liquidity)?);
+/// The accounts of a provide to a `CpAmm`, shared by `provide_to_cp_amm` and `provide_to_cp_amm_as_delegate`
+/// so both run the same provide.
+///
+/// The `owner` owns the provided token accounts and the LP token account and is recorded as the provider,
+/// while the `transfer_authority` moves the provided tokens out of the owner's accounts, e.g. the owner
+/// itself or a manager approved as their delegate. The `signer` is the key signing the instruction, passed to
+/// the screening program.
+pub(crate) struct ProvideCore<'a, 'info> {
+    pub signer: Pubkey,
+    pub owner: AccountInfo<'info>,
+    pub transfer_authority: AccountInfo<'info>,
+    pub base_mint: &'a InterfaceAccount<'info, Mint>,
+    pub quote_mint: &'a InterfaceAccount<'info, Mint>,
+    pub lp_mint: &'a Account<'info, token::Mint>,
+    pub owner_base_account: &'a InterfaceAccount<'info, TokenAccount>,
+    pub owner_quote_account: &'a InterfaceAccount<'info, TokenAccount>,
+    pub owner_lp_account: &'a Account<'info, token::TokenAccount>,
+    pub amms_config: &'a AmmsConfig,
+    pub cp_amm: &'a mut Account<'info, CpAmm>,
+    pub cp_amm_base_vault: &'a InterfaceAccount<'info, TokenAccount>,
+    pub cp_amm_quote_vault: &'a InterfaceAccount<'info, TokenAccount>,
+    pub lp_token_program: &'a Program<'info, Token>,
+    pub base_token_program: &'a Interface<'info, TokenInterface>,
+    pub quote_token_program: &'a Interface<'info, TokenInterface>,
+    pub liquidity_position: Option<&'a mut Account<'info, LiquidityPosition>>,
+    pub event_ring: Option<&'a AccountLoader<'info, EventRing>>,
+    pub remaining_accounts: &'a [AccountInfo<'info>],
+}
 
-    let base_liquidity_to_provide = provide_base_liquidity_instruction.get_amount_after_fee();
-    let quote_liquidity_to_provide = provide_quote_liquidity_instruction.get_amount_after_fee();
+impl<'a, 'info> ProvideCore<'a, 'info> {
+    /// Executes the provide, see `provide_to_cp_amm` for its behaviour.
+    #[inline(never)]
+    pub(crate) fn execute(mut self, base_liquidity: u64, quote_liquidity: u64) -> Result<()> {
+        self.cp_amm.assert_layout_version()?;
+        self.cp_amm.check_vault_balances(self.cp_amm_base_vault.amount, self.cp_amm_quote_vault.amount)?;
+        let liquidity_snapshot = LiquiditySnapshot::of(self.cp_amm);
+        self.cp_amm.validate_lp_tokens_supply(self.lp_mint.supply)?;
+        self.cp_amm.check_protocol_fees_cap(self.amms_config.protocol_fees_cap_basis_points())?;
 
-    let provide_payload = ctx.accounts.cp_amm.get_provide_payload(base_liquidity_to_provide, quote_liquidity_to_provide)?;
+        let provide_base_liquidity_instruction = Box::new(self.get_provide_base_liquidity_transfer_instruction(base_liquidity)?);
+        let provide_quote_liquidity_instruction = Box::new(self.get_provide_quote_liquidity_transfer_instruction(quote_liquidity)?);
 
-    provide_base_liquidity_instruction.execute(None)?;
-    provide_quote_liquidity_instruction.execute(None)?;
+        let base_liquidity_to_provide = provide_base_liquidity_instruction.get_amount_after_fee();
+        let quote_liquidity_to_provide = provide_quote_liquidity_instruction.get_amount_after_fee();
 
-    let liquidity_mint_instruction = Box::new(ctx.accounts.get_liquidity_mint_instruction(provide_payload.lp_tokens_to_mint()));
+        let provide_payload = self.cp_amm.get_provide_payload(
+            base_liquidity_to_provide,
+            quote_liquidity_to_provide,
+            self.amms_config.min_provide_lp_tokens(),
+            self.amms_config.adjust_liquidity_ratio_tolerance()
+        )?;
+        self.cp_amm.check_lp_concentration(
+            &provide_payload,
+            self.owner_lp_account.amount,
+            self.amms_config.lp_concentration_share_basis_points(),
+            self.amms_config.lp_concentration_guard_epochs(),
+            Clock::get()?.epoch
+        )?;
 
-    let cp_amm_seeds = ctx.accounts.cp_amm.seeds();
-    let mint_instruction_seeds: &[&[&[u8]]] = &[&cp_amm_seeds];
+        let remaining_accounts = screen_liquidity_operation(
+            self.amms_config,
+            self.cp_amm,
+            self.owner.clone(),
+            self.remaining_accounts,
+            &LiquidityOperation {
+                cp_amm: self.cp_amm.key(),
+                kind: LiquidityOperationKind::Provide,
+                signer: self.signer,
+                owner: self.owner.key(),
+                base_amount: base_liquidity_to_provide,
+                quote_amount: quote_liquidity_to_provide,
+                lp_tokens: provide_payload.lp_tokens_to_mint(),
+            }
+        )?;
 
-    liquidity_mint_instruction.execute(Some(mint_instruction_seeds))?;
+        provide_base_liquidity_instruction.execute(None)?;
+        provide_quote_liquidity_instruction.execute(None)?;
 
-    ctx.accounts.cp_amm.provide(provide_payload);
+        let liquidity_mint_instruction = Box::new(self.get_liquidity_mint_instruction(provide_payload.lp_tokens_to_mint()));
 
-    Ok(())
-}
+        let cp_amm_seeds = self.cp_amm.seeds();
+        let mint_instruction_seeds: &[&[&[u8]]] = &[&cp_amm_seeds];
 
-impl<'info> ProvideToCpAmm<'info> {
+        liquidity_mint_instruction.execute(Some(mint_instruction_seeds))?;
+
+        if let Some(liquidity_position) = self.liquidity_position.as_mut() {
+            liquidity_position.record_provide(provide_payload.lp_tokens_to_mint(), Clock::get()?.unix_timestamp);
+        }
+        self.cp_amm.record_lp_concentration(&provide_payload, self.owner_lp_account.amount, self.amms_config.lp_concentration_share_basis_points());
+        self.cp_amm.record_slot_start_price(Clock::get()?.slot);
+        let liquidity_change = LiquidityChange {
+            kind: EventRecordKind::Provide,
+            signer: self.owner.key(),
+            base_amount: base_liquidity_to_provide,
+            quote_amount: quote_liquidity_to_provide,
+            lp_tokens: provide_payload.lp_tokens_to_mint(),
+        };
+        self.cp_amm.provide(provide_payload);
+        record_liquidity_change(self.cp_amm, self.amms_config, self.event_ring, liquidity_change, Clock::get()?.slot)?;
+        notify_liquidity_subscriber(self.cp_amm, remaining_accounts, LiquidityDeltaKind::Provide, liquidity_snapshot)?;
+
+        Ok(())
+    }
+    #[inline(never)]
     fn get_provide_base_liquidity_transfer_instruction(&self, base_liquidity: u64) -> Result<TransferTokensInstruction<'_, '_, '_, 'info>> {
         TransferTokensInstruction::try_new(
             base_liquidity,
-            &self.base_mint,
-            &self.signer_base_account,
-            self.signer.to_account_info(),
-            &self.cp_amm_base_vault,
-            &self.base_token_program
+            self.base_mint,
+            self.owner_base_account,
+            self.transfer_authority.clone(),
+            self.cp_amm_base_vault,
+            self.base_token_program
         )
     }
+    #[inline(never)]
     fn get_provide_quote_liquidity_transfer_instruction(&self, quote_liquidity: u64) -> Result<TransferTokensInstruction<'_, '_, '_, 'info>>{
         TransferTokensInstruction::try_new(
             quote_liquidity,
-            &self.quote_mint,
-            &self.signer_quote_account,
-            self.signer.to_account_info(),
-            &self.cp_amm_quote_vault,
-            &self.quote_token_program
+            self.quote_mint,
+            self.owner_quote_account,
+            self.transfer_authority.clone(),
+            self.cp_amm_quote_vault,
+            self.quote_token_program
         )
     }
+    #[inline(never)]
     fn get_liquidity_mint_instruction(&self, liquidity: u64) -> MintTokensInstructions<'_, '_, '_, 'info> {
         MintTokensInstructions::new(
             liquidity,
-            &self.lp_mint,
+            self.lp_mint,
             self.cp_amm.to_account_info(),
-            self.signer_lp_account.to_account_info(),
-            &self.lp_token_program
+            self.owner_lp_account.to_account_info(),
+            self.lp_token_program
         )
     }
 }
\ No newline at end of file
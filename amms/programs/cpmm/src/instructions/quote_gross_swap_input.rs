@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+use crate::error::ErrorCode;
+use crate::state::VersionedAccount;
+use crate::state::cp_amm::CpAmm;
+use crate::utils::token_instructions::calculate_gross_transfer_amount;
+
+#[derive(Accounts)]
+pub struct QuoteGrossSwapInput<'info> {
+    pub base_mint: Box<InterfaceAccount<'info, Mint>>,
+    pub quote_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        constraint = base_mint.key() == cp_amm.base_mint().key(),
+        constraint = quote_mint.key() == cp_amm.quote_mint().key(),
+        seeds = [CpAmm::SEED, cp_amm.lp_mint.as_ref()],
+        bump = cp_amm.bump()
+    )]
+    pub cp_amm: Box<Account<'info, CpAmm>>,
+}
+
+/// Gross swap input returned to the caller as instruction return data.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct GrossSwapInput {
+    /// The amount to pass as `swap_amount` so the pool receives the desired net input.
+    pub gross_amount: u64,
+
+    /// The input mint transfer fee charged on `gross_amount` in the current epoch.
+    pub transfer_fee: u64,
+}
+
+pub(crate) fn handler(ctx: Context<QuoteGrossSwapInput>, net_amount: u64, is_in_out: bool) -> Result<GrossSwapInput> {
+    ctx.accounts.cp_amm.assert_layout_version()?;
+    require!(net_amount > 0, ErrorCode::SwapAmountIsZero);
+    let in_mint = if is_in_out {
+        &ctx.accounts.base_mint
+    } else {
+        &ctx.accounts.quote_mint
+    };
+    let (gross_amount, transfer_fee) = calculate_gross_transfer_amount(in_mint, net_amount, Clock::get()?.epoch)?;
+    Ok(GrossSwapInput {
+        gross_amount,
+        transfer_fee,
+    })
+}
\ No newline at end of file
@@ -0,0 +1,112 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+use crate::error::ErrorCode;
+use crate::state::{AmmsConfig, cp_amm::CpAmm, VersionedAccount};
+
+/// The maximal number of pools quoted by a single bulk quote.
+pub const MAX_QUOTE_MANY_POOLS: usize = 10;
+
+/// The number of remaining accounts describing each quoted pool.
+const QUOTE_MANY_POOL_ACCOUNTS: usize = 2;
+
+#[derive(Accounts)]
+pub struct QuoteMany<'info> {
+    pub base_mint: Box<InterfaceAccount<'info, Mint>>,
+    pub quote_mint: Box<InterfaceAccount<'info, Mint>>,
+}
+
+/// A swap to quote in one of the pools of a bulk quote.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct SwapQuoteRequest {
+    /// The amount of input tokens received by the pool, net of any input mint transfer fee.
+    pub swap_amount: u64,
+
+    /// `true` to quote a base to quote swap, `false` for a quote to base swap.
+    pub is_in_out: bool,
+}
+
+/// Swap quote of a pool returned to the caller as instruction return data.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct SwapQuote {
+    /// The amount of output tokens the swap would pay out.
+    pub swap_result: u64,
+
+    /// The price impact of the swap, measured in basis points.
+    pub price_impact_basis_points: u16,
+
+    /// The providers fee rate applied to the swap, measured in basis points.
+    pub providers_fee_rate_basis_points: u16,
+
+    /// The protocol fee rate applied to the swap, measured in basis points.
+    pub protocol_fee_rate_basis_points: u16,
+}
+
+/// Quotes swaps in up to `MAX_QUOTE_MANY_POOLS` pools of the same pair at once, so routers compare
+/// fee tiers with a single simulation instead of one RPC round-trip per pool.
+///
+/// Each pool is described in the remaining accounts by its `AmmsConfig` and `CpAmm`, in this order
+/// and in the order of `requests`. The quotes are packed in the same order, a pool which would reject
+/// the swap (paused, closed, outdated layout, insufficient liquidity, ...) is quoted as `None`
+/// instead of failing the whole instruction.
+pub(crate) fn handler(ctx: Context<QuoteMany>, requests: Vec<SwapQuoteRequest>) -> Result<Vec<Option<SwapQuote>>> {
+    require!(
+        !requests.is_empty() && requests.len() <= MAX_QUOTE_MANY_POOLS,
+        ErrorCode::QuoteManyInvalidRequests
+    );
+    require!(
+        ctx.remaining_accounts.len() == requests.len() * QUOTE_MANY_POOL_ACCOUNTS,
+        ErrorCode::QuoteManyPoolsMismatch
+    );
+
+    let clock = Clock::get()?;
+    let mut quotes = Vec::with_capacity(requests.len());
+    for (pool_accounts, request) in ctx.remaining_accounts.chunks_exact(QUOTE_MANY_POOL_ACCOUNTS).zip(requests) {
+        let amms_config: Box<AmmsConfig> = Box::new(load_program_account(&pool_accounts[0])?);
+        let cp_amm: Box<CpAmm> = Box::new(load_program_account(&pool_accounts[1])?);
+        require!(
+            pool_accounts[0].key() == cp_amm.amms_config().key() &&
+                ctx.accounts.base_mint.key() == cp_amm.base_mint().key() &&
+                ctx.accounts.quote_mint.key() == cp_amm.quote_mint().key(),
+            ErrorCode::QuoteManyPoolsMismatch
+        );
+        quotes.push(quote_swap(&amms_config, &cp_amm, &request, &clock));
+    }
+    Ok(quotes)
+}
+
+/// Deserializes a read-only account of the program, checking its owner and discriminator.
+fn load_program_account<T: AccountDeserialize>(account: &AccountInfo) -> Result<T> {
+    require_keys_eq!(*account.owner, crate::ID, anchor_lang::error::ErrorCode::AccountOwnedByWrongProgram);
+    T::try_deserialize(&mut account.try_borrow_data()?.as_ref())
+}
+
+/// Quotes a swap in a pool with the checks of a real swap, except the slippage.
+///
+/// # Returns
+/// - `Some(SwapQuote)` if the pool would accept the swap.
+/// - `None` otherwise.
+#[inline(never)]
+fn quote_swap(amms_config: &AmmsConfig, cp_amm: &CpAmm, request: &SwapQuoteRequest, clock: &Clock) -> Option<SwapQuote> {
+    if cp_amm.assert_layout_version().is_err() || !cp_amm.is_market_open(clock.unix_timestamp) {
+        return None;
+    }
+    let providers_fee_rate_basis_points = amms_config.providers_fee_rate_basis_points_at(clock.slot);
+    let protocol_fee_rate_basis_points = cp_amm.protocol_fee_rate_basis_points(amms_config.protocol_fee_rate_basis_points());
+    let swap_payload = cp_amm.get_swap_payload(
+        request.swap_amount,
+        1,
+        u64::MAX,
+        providers_fee_rate_basis_points,
+        protocol_fee_rate_basis_points,
+        request.is_in_out,
+        None,
+        amms_config.swap_constant_product_tolerance(),
+        clock.slot
+    ).ok()?;
+    Some(SwapQuote {
+        swap_result: swap_payload.amount_to_withdraw(),
+        price_impact_basis_points: swap_payload.price_impact_basis_points().try_into().ok()?,
+        providers_fee_rate_basis_points,
+        protocol_fee_rate_basis_points,
+    })
+}
\ No newline at end of file
@@ -0,0 +1,210 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions as sysvar_instructions;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::error::ErrorCode;
+use crate::events::RebalanceEvent;
+use crate::instructions::SwapResult;
+use crate::liquidity_hook::{LiquidityDeltaKind, LiquiditySnapshot};
+use crate::state::{AmmsConfig, cp_amm::{CpAmm, EventRing}, VersionedAccount};
+use crate::utils::oracle::{read_configured_usd_price, UsdPrice};
+use crate::utils::{record_liquidity_change, validate_swap_origin, LiquidityChange};
+use crate::utils::liquidity_hook_instructions::notify_liquidity_subscriber;
+use crate::utils::token_instructions::{TransferTokensInstruction};
+
+#[derive(Accounts)]
+pub struct RebalanceInCpAmm<'info>{
+    #[account(
+        mut,
+        constraint = signer.key() == amms_config.fee_authority().key()
+    )]
+    pub signer: Signer<'info>,
+    pub base_mint: Box<InterfaceAccount<'info, Mint>>,
+    pub quote_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        init_if_needed,
+        payer = signer,
+        associated_token::mint = base_mint,
+        associated_token::authority = signer,
+        associated_token::token_program = base_token_program
+    )]
+    pub signer_base_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = signer,
+        associated_token::mint = quote_mint,
+        associated_token::authority = signer,
+        associated_token::token_program = quote_token_program
+    )]
+    pub signer_quote_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        constraint = amms_config.is_protocol_rebalance_enabled() @ ErrorCode::ProtocolRebalanceDisabled,
+        seeds = [AmmsConfig::SEED, amms_config.id.to_le_bytes().as_ref()],
+        bump = amms_config.bump()
+    )]
+    pub amms_config: Box<Account<'info, AmmsConfig>>,
+
+    #[account(
+        mut,
+        constraint = cp_amm.is_launched(),
+        constraint = amms_config.key() == cp_amm.amms_config().key(),
+        constraint = base_mint.key() == cp_amm.base_mint().key(),
+        constraint = quote_mint.key() == cp_amm.quote_mint().key(),
+        constraint = cp_amm_base_vault.key() == cp_amm.base_vault().key(),
+        constraint = cp_amm_quote_vault.key() == cp_amm.quote_vault().key(),
+        seeds = [CpAmm::SEED, cp_amm.lp_mint.as_ref()],
+        bump = cp_amm.bump()
+    )]
+    pub cp_amm: Box<Account<'info, CpAmm>>,
+
+    #[account(
+        mut,
+        seeds = [CpAmm::VAULT_SEED, cp_amm.key().as_ref(), cp_amm.base_mint().as_ref()],
+        bump = cp_amm.base_vault_bump()
+    )]
+    pub cp_amm_base_vault:Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [CpAmm::VAULT_SEED, cp_amm.key().as_ref(), cp_amm.quote_mint().as_ref()],
+        bump = cp_amm.quote_vault_bump()
+    )]
+    pub cp_amm_quote_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        constraint = cp_amm.usd_oracle() == Some(&usd_oracle.key()) @ ErrorCode::InvalidCpAmmUsdOracle
+    )]
+    /// CHECK: Price update account matching the CpAmm USD oracle, required only if the oracle is set
+    pub usd_oracle: Option<UncheckedAccount<'info>>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub base_token_program: Interface<'info, TokenInterface>,
+    pub quote_token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+
+    #[account(address = sysvar_instructions::ID)]
+    /// CHECK: The instructions sysvar, identifying the origin of the rebalance while a swap origin policy is in force
+    pub instructions_sysvar: Option<UncheckedAccount<'info>>,
+
+    #[account(
+        mut,
+        constraint = event_ring.load()?.cp_amm() == &cp_amm.key()
+    )]
+    pub event_ring: Option<AccountLoader<'info, EventRing>>,
+}
+
+/// Swaps the protocol-owned tokens of the fee authority in the pool without protocol fees.
+///
+/// With `event_ring` passed, a record of the swap is appended to the pool's event ring.
+pub(crate) fn handler<'info>(ctx: Context<'_, '_, '_, 'info, RebalanceInCpAmm<'info>>, swap_amount: u64, estimated_result: u64, allowed_slippage: u64, is_in_out: bool) -> Result<SwapResult> {
+    ctx.accounts.cp_amm.assert_layout_version()?;
+    ctx.accounts.cp_amm.check_vault_balances(ctx.accounts.cp_amm_base_vault.amount, ctx.accounts.cp_amm_quote_vault.amount)?;
+    let liquidity_snapshot = LiquiditySnapshot::of(&ctx.accounts.cp_amm);
+    require!(ctx.accounts.cp_amm.is_market_open(Clock::get()?.unix_timestamp), ErrorCode::MarketClosed);
+    validate_swap_origin(&ctx.accounts.amms_config, ctx.accounts.instructions_sysvar.as_deref(), Clock::get()?.slot)?;
+    let quote_usd_price = ctx.accounts.get_quote_usd_price()?;
+    let in_transfer_instruction = Box::new(ctx.accounts.get_in_transfer_instruction(swap_amount, is_in_out)?);
+    let in_amount = in_transfer_instruction.get_amount_after_fee();
+    let providers_fee_rate_basis_points = ctx.accounts.amms_config.providers_fee_rate_basis_points_at(Clock::get()?.slot);
+    // Protocol fees are waived for protocol-owned liquidity rebalancing
+    let protocol_fee_rate_basis_points = 0;
+    let swap_payload = ctx.accounts.cp_amm.get_swap_payload(
+        in_amount,
+        estimated_result,
+        allowed_slippage,
+        providers_fee_rate_basis_points,
+        protocol_fee_rate_basis_points,
+        is_in_out,
+        quote_usd_price,
+        ctx.accounts.amms_config.swap_constant_product_tolerance(),
+        Clock::get()?.slot
+    )?;
+
+    let out_transfer_instruction = Box::new(ctx.accounts.get_out_transfer_instruction(swap_payload.amount_to_withdraw(), is_in_out)?);
+    in_transfer_instruction.execute(None)?;
+    let cp_amm_seeds = ctx.accounts.cp_amm.seeds();
+    let out_instruction_seeds: &[&[&[u8]]] = &[&cp_amm_seeds];
+    out_transfer_instruction.execute(Some(out_instruction_seeds))?;
+
+    let swap_result = SwapResult {
+        swap_result: swap_payload.amount_to_withdraw(),
+        price_impact_basis_points: swap_payload.price_impact_basis_points(),
+    };
+    let (providers_fee_amount, protocol_fee_amount, creator_fee_amount) = (swap_payload.providers_fee_amount(), swap_payload.protocol_fee_amount(), swap_payload.creator_fee_amount());
+    ctx.accounts.cp_amm.record_slot_start_price(Clock::get()?.slot);
+    ctx.accounts.cp_amm.swap(swap_payload)?;
+    record_liquidity_change(&mut ctx.accounts.cp_amm, &ctx.accounts.amms_config, ctx.accounts.event_ring.as_ref(), LiquidityChange::swap(is_in_out, ctx.accounts.signer.key(), in_amount, swap_result.swap_result), Clock::get()?.slot)?;
+
+    emit!(RebalanceEvent {
+        cp_amm: ctx.accounts.cp_amm.key(),
+        fee_authority: ctx.accounts.signer.key(),
+        is_in_out,
+        swap_amount: in_amount,
+        swap_result: swap_result.swap_result,
+        price_impact_basis_points: swap_result.price_impact_basis_points,
+        providers_fee_rate_basis_points,
+        protocol_fee_rate_basis_points,
+        providers_fee_amount,
+        protocol_fee_amount,
+        creator_fee_amount,
+    });
+    notify_liquidity_subscriber(&ctx.accounts.cp_amm, ctx.remaining_accounts, LiquidityDeltaKind::Rebalance, liquidity_snapshot)?;
+
+    Ok(swap_result)
+}
+
+impl<'info> RebalanceInCpAmm<'info>{
+    fn get_quote_usd_price(&self) -> Result<Option<UsdPrice>>{
+        read_configured_usd_price(self.cp_amm.usd_oracle(), self.usd_oracle.as_deref(), self.quote_mint.decimals)
+    }
+    #[inline(never)]
+    fn get_in_transfer_instruction(&self, in_amount: u64, is_in_out: bool) -> Result<TransferTokensInstruction<'_, '_, '_, 'info>>{
+        if is_in_out{
+            TransferTokensInstruction::try_new(
+                in_amount,
+                &self.base_mint,
+                &self.signer_base_account,
+                self.signer.to_account_info(),
+                &self.cp_amm_base_vault,
+                &self.base_token_program
+            )
+        }
+        else{
+            TransferTokensInstruction::try_new(
+                in_amount,
+                &self.quote_mint,
+                &self.signer_quote_account,
+                self.signer.to_account_info(),
+                &self.cp_amm_quote_vault,
+                &self.quote_token_program
+            )
+        }
+    }
+    #[inline(never)]
+    fn get_out_transfer_instruction(&self, in_amount: u64, is_in_out: bool) -> Result<TransferTokensInstruction<'_, '_, '_, 'info>>{
+        if is_in_out{
+            TransferTokensInstruction::try_new(
+                in_amount,
+                &self.quote_mint,
+                &self.cp_amm_quote_vault,
+                self.cp_amm.to_account_info(),
+                &self.signer_quote_account,
+                &self.quote_token_program
+            )
+
+        }
+        else{
+            TransferTokensInstruction::try_new(
+                in_amount,
+                &self.base_mint,
+                &self.cp_amm_base_vault,
+                self.cp_amm.to_account_info(),
+                &self.signer_base_account,
+                &self.base_token_program
+            )
+        }
+    }
+}
\ No newline at end of file
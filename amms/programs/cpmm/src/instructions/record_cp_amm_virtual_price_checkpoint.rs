@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+use crate::state::VersionedAccount;
+use crate::state::cp_amm::{CpAmm, VirtualPriceCheckpoints};
+
+#[derive(Accounts)]
+pub struct RecordCpAmmVirtualPriceCheckpoint<'info> {
+    #[account(
+        seeds = [CpAmm::SEED, cp_amm.lp_mint.as_ref()],
+        bump = cp_amm.bump()
+    )]
+    pub cp_amm: Box<Account<'info, CpAmm>>,
+
+    #[account(
+        mut,
+        seeds = [VirtualPriceCheckpoints::SEED, cp_amm.key().as_ref()],
+        bump = virtual_price_checkpoints.bump()
+    )]
+    pub virtual_price_checkpoints: Box<Account<'info, VirtualPriceCheckpoints>>,
+}
+
+/// Permissionlessly records a virtual price checkpoint of a pool once a day.
+pub(crate) fn handler(ctx: Context<RecordCpAmmVirtualPriceCheckpoint>) -> Result<()> {
+    ctx.accounts.cp_amm.assert_layout_version()?;
+    let virtual_price = ctx.accounts.cp_amm.get_virtual_price()?;
+    ctx.accounts.virtual_price_checkpoints.record(Clock::get()?.unix_timestamp, virtual_price)
+}
\ No newline at end of file
@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+use crate::state::{AmmsConfig, VersionedAccount};
+use crate::state::cp_amm::CpAmm;
+
+#[derive(Accounts)]
+pub struct RefreshCpAmmDisplayData<'info> {
+    #[account(
+        seeds = [AmmsConfig::SEED, amms_config.id.to_le_bytes().as_ref()],
+        bump = amms_config.bump()
+    )]
+    pub amms_config: Box<Account<'info, AmmsConfig>>,
+
+    #[account(
+        mut,
+        constraint = amms_config.key() == cp_amm.amms_config().key(),
+        seeds = [CpAmm::SEED, cp_amm.lp_mint.as_ref()],
+        bump = cp_amm.bump()
+    )]
+    pub cp_amm: Box<Account<'info, CpAmm>>,
+}
+
+/// Permissionlessly refreshes the display data of a pool, e.g. after its fee rates were updated
+/// or its layout upgraded. Swaps, provides and withdraws refresh it on their own, wallets read
+/// the `LastUpdateSlot` entry to tell how fresh it is.
+pub(crate) fn handler(ctx: Context<RefreshCpAmmDisplayData>) -> Result<()> {
+    ctx.accounts.cp_amm.assert_layout_version()?;
+    let current_slot = Clock::get()?.slot;
+    ctx.accounts.cp_amm.refresh_display_data(
+        ctx.accounts.amms_config.providers_fee_rate_basis_points_at(current_slot),
+        ctx.accounts.amms_config.protocol_fee_rate_basis_points(),
+        current_slot
+    );
+    Ok(())
+}
\ No newline at end of file
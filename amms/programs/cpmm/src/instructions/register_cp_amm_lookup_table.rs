@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::address_lookup_table::{self, state::AddressLookupTable};
+use crate::error::ErrorCode;
+use crate::state::VersionedAccount;
+use crate::state::cp_amm::{CpAmm, PoolLookupTable};
+
+#[derive(Accounts)]
+pub struct RegisterCpAmmLookupTable<'info> {
+    #[account(
+        mut,
+        constraint = signer.key() == cp_amm.creator().key()
+    )]
+    pub signer: Signer<'info>,
+
+    #[account(
+        seeds = [CpAmm::SEED, cp_amm.lp_mint.as_ref()],
+        bump = cp_amm.bump()
+    )]
+    pub cp_amm: Box<Account<'info, CpAmm>>,
+
+    /// CHECK: Owner is checked by the constraint, the content is validated in the handler
+    #[account(
+        owner = address_lookup_table::program::ID @ ErrorCode::LookupTableInvalid
+    )]
+    pub lookup_table: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = 8 + PoolLookupTable::INIT_SPACE,
+        seeds = [PoolLookupTable::SEED, cp_amm.key().as_ref()],
+        bump
+    )]
+    pub pool_lookup_table: Box<Account<'info, PoolLookupTable>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Registers the Address Lookup Table holding the pool's accounts, replacing a previously registered one.
+///
+/// The table must be active and hold the pool, its config, mints and vaults.
+/// Only the pool creator may register it, usually in the launch transaction.
+pub(crate) fn handler(ctx: Context<RegisterCpAmmLookupTable>) -> Result<()> {
+    ctx.accounts.cp_amm.assert_layout_version()?;
+    let cp_amm_key = ctx.accounts.cp_amm.key();
+    {
+        let lookup_table_data = ctx.accounts.lookup_table.try_borrow_data()?;
+        let lookup_table = AddressLookupTable::deserialize(&lookup_table_data).map_err(|_| ErrorCode::LookupTableInvalid)?;
+        require!(lookup_table.meta.deactivation_slot == u64::MAX, ErrorCode::LookupTableInvalid);
+        PoolLookupTable::validate_addresses(
+            &lookup_table.addresses,
+            &PoolLookupTable::required_addresses(&cp_amm_key, &ctx.accounts.cp_amm)
+        )?;
+    }
+    let lookup_table_key = ctx.accounts.lookup_table.key();
+    ctx.accounts.pool_lookup_table.register(cp_amm_key, lookup_table_key, ctx.bumps.pool_lookup_table);
+    Ok(())
+}
\ No newline at end of file
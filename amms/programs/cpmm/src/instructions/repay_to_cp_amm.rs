@@ -0,0 +1,102 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::liquidity_hook::{LiquidityDeltaKind, LiquiditySnapshot};
+use crate::state::{AmmsConfig, VersionedAccount};
+use crate::state::cp_amm::{CpAmm, EventRecordKind, EventRing, LendOutLoan};
+use crate::utils::{record_liquidity_change, LiquidityChange};
+use crate::utils::liquidity_hook_instructions::notify_liquidity_subscriber;
+use crate::utils::token_instructions::TransferTokensInstruction;
+
+#[derive(Accounts)]
+pub struct RepayToCpAmm<'info> {
+    pub signer: Signer<'info>,
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = signer,
+        token::token_program = token_program
+    )]
+    pub signer_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        seeds = [AmmsConfig::SEED, amms_config.id.to_le_bytes().as_ref()],
+        bump = amms_config.bump()
+    )]
+    pub amms_config: Box<Account<'info, AmmsConfig>>,
+
+    #[account(
+        mut,
+        constraint = amms_config.key() == cp_amm.amms_config().key(),
+        constraint = (mint.key() == cp_amm.base_mint().key() && cp_amm_vault.key() == cp_amm.base_vault().key()) ||
+            (mint.key() == cp_amm.quote_mint().key() && cp_amm_vault.key() == cp_amm.quote_vault().key()),
+        seeds = [CpAmm::SEED, cp_amm.lp_mint.as_ref()],
+        bump = cp_amm.bump()
+    )]
+    pub cp_amm: Box<Account<'info, CpAmm>>,
+
+    #[account(mut)]
+    pub cp_amm_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [LendOutLoan::SEED, cp_amm.key().as_ref(), mint.key().as_ref()],
+        bump = lend_out_loan.bump()
+    )]
+    pub lend_out_loan: Box<Account<'info, LendOutLoan>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+
+    #[account(
+        mut,
+        constraint = event_ring.load()?.cp_amm() == &cp_amm.key()
+    )]
+    pub event_ring: Option<AccountLoader<'info, EventRing>>,
+}
+
+/// Repays a `LendOutLoan`, settling the accrued interest into the pool liquidity first.
+///
+/// Anyone may repay on behalf of the lending adapter. The amount received by the vault
+/// after transfer fees is what gets credited to the loan. The repayment is recorded like any
+/// liquidity change, see `record_liquidity_change`, as the interest grows the pool liquidity.
+pub(crate) fn handler<'info>(ctx: Context<'_, '_, '_, 'info, RepayToCpAmm<'info>>, amount: u64) -> Result<()> {
+    ctx.accounts.cp_amm.assert_layout_version()?;
+    let liquidity_snapshot = LiquiditySnapshot::of(&ctx.accounts.cp_amm);
+    ctx.accounts.lend_out_loan.accrue_interest(Clock::get()?.slot, ctx.accounts.amms_config.lend_out_interest_rate_basis_points());
+    let transfer_instruction = Box::new(ctx.accounts.get_transfer_instruction(amount)?);
+    let repaid_amount = transfer_instruction.get_amount_after_fee();
+    let (interest, principal) = ctx.accounts.lend_out_loan.get_repayment(repaid_amount)?;
+    let is_base = ctx.accounts.mint.key() == ctx.accounts.cp_amm.base_mint().key();
+    let lend_out_payload = ctx.accounts.cp_amm.get_repay_payload(principal, interest, is_base)?;
+    let liquidity_change = LiquidityChange {
+        kind: EventRecordKind::Repay,
+        signer: ctx.accounts.signer.key(),
+        base_amount: if is_base { repaid_amount } else { 0 },
+        quote_amount: if is_base { 0 } else { repaid_amount },
+        lp_tokens: 0,
+    };
+
+    transfer_instruction.execute(None)?;
+
+    ctx.accounts.lend_out_loan.repay(interest, principal);
+    ctx.accounts.cp_amm.record_slot_start_price(Clock::get()?.slot);
+    ctx.accounts.cp_amm.lend_out(lend_out_payload);
+    record_liquidity_change(&mut ctx.accounts.cp_amm, &ctx.accounts.amms_config, ctx.accounts.event_ring.as_ref(), liquidity_change, Clock::get()?.slot)?;
+    notify_liquidity_subscriber(&ctx.accounts.cp_amm, ctx.remaining_accounts, LiquidityDeltaKind::Repay, liquidity_snapshot)?;
+    Ok(())
+}
+
+impl<'info> RepayToCpAmm<'info> {
+    #[inline(never)]
+    fn get_transfer_instruction(&self, amount: u64) -> Result<TransferTokensInstruction<'_, '_, '_, 'info>> {
+        TransferTokensInstruction::try_new(
+            amount,
+            &self.mint,
+            &self.signer_account,
+            self.signer.to_account_info(),
+            &self.cp_amm_vault,
+            &self.token_program
+        )
+    }
+}
\ No newline at end of file
@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+use crate::error::ErrorCode;
+use crate::events::VaultBalanceDriftEvent;
+use crate::state::{cp_amm::CpAmm, VersionedAccount};
+
+#[derive(Accounts)]
+pub struct ReportVaultDrift<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = cp_amm_base_vault.key() == cp_amm.base_vault().key(),
+        constraint = cp_amm_quote_vault.key() == cp_amm.quote_vault().key(),
+        seeds = [CpAmm::SEED, cp_amm.lp_mint.as_ref()],
+        bump = cp_amm.bump()
+    )]
+    pub cp_amm: Box<Account<'info, CpAmm>>,
+
+    pub cp_amm_base_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub cp_amm_quote_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+}
+
+/// Permissionlessly suspends the swaps and provides of a pool whose vaults hold less than its recorded
+/// liquidity and fees, see `CpAmm::check_vault_balances`.
+///
+/// Trading instructions fail with `VaultBalanceDrift` on such a pool, which would revert any freeze they
+/// wrote, so the freeze is persisted by this instruction instead. Withdrawals stay available.
+pub(crate) fn handler(ctx: Context<ReportVaultDrift>) -> Result<()> {
+    ctx.accounts.cp_amm.assert_layout_version()?;
+    let base_vault_balance = ctx.accounts.cp_amm_base_vault.amount;
+    let quote_vault_balance = ctx.accounts.cp_amm_quote_vault.amount;
+    require!(
+        ctx.accounts.cp_amm.check_vault_balances(base_vault_balance, quote_vault_balance).is_err(),
+        ErrorCode::VaultBalanceNotDrifted
+    );
+
+    let (recorded_base_balance, recorded_quote_balance) = ctx.accounts.cp_amm.recorded_vault_balances();
+    ctx.accounts.cp_amm.freeze_on_vault_drift();
+    emit!(VaultBalanceDriftEvent {
+        cp_amm: ctx.accounts.cp_amm.key(),
+        signer: ctx.accounts.signer.key(),
+        base_vault_balance,
+        quote_vault_balance,
+        recorded_base_balance,
+        recorded_quote_balance,
+    });
+    Ok(())
+}
\ No newline at end of file
@@ -1,7 +1,14 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions as sysvar_instructions;
 use anchor_spl::associated_token::AssociatedToken;
 use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
-use crate::state::{AmmsConfig, cp_amm::CpAmm};
+use crate::error::ErrorCode;
+use crate::events::{SwapEvent, TraderFeeTierAppliedEvent};
+use crate::liquidity_hook::{LiquidityDeltaKind, LiquiditySnapshot};
+use crate::state::{AmmsConfig, cp_amm::{CpAmm, EventRing, PoolStatsShard}, RouterRegistry, TraderFeeTier, TraderFeeTiers, TraderVolume, VersionedAccount};
+use crate::utils::oracle::{read_configured_usd_price, UsdPrice};
+use crate::utils::{record_liquidity_change, validate_routed_swap, validate_swap_origin, LiquidityChange};
+use crate::utils::liquidity_hook_instructions::notify_liquidity_subscriber;
 use crate::utils::token_instructions::{TransferTokensInstruction};
 
 #[derive(Accounts)]
@@ -62,77 +69,306 @@ pub struct SwapInCpAmm<'info>{
     )]
     pub cp_amm_quote_vault: Box<InterfaceAccount<'info, TokenAccount>>,
 
+    #[account(
+        constraint = cp_amm.usd_oracle() == Some(&usd_oracle.key()) @ ErrorCode::InvalidCpAmmUsdOracle
+    )]
+    /// CHECK: Price update account matching the CpAmm USD oracle, required only if the oracle is set
+    pub usd_oracle: Option<UncheckedAccount<'info>>,
+
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub base_token_program: Interface<'info, TokenInterface>,
     pub quote_token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
+
+    #[account(
+        mut,
+        seeds = [PoolStatsShard::SEED, cp_amm.key().as_ref(), [pool_stats_shard.shard_index()].as_ref()],
+        bump = pool_stats_shard.bump()
+    )]
+    pub pool_stats_shard: Option<Box<Account<'info, PoolStatsShard>>>,
+
+    #[account(
+        mut,
+        constraint = event_ring.load()?.cp_amm() == &cp_amm.key()
+    )]
+    pub event_ring: Option<AccountLoader<'info, EventRing>>,
+
+    #[account(
+        seeds = [TraderFeeTiers::SEED, amms_config.key().as_ref()],
+        bump = trader_fee_tiers.bump()
+    )]
+    pub trader_fee_tiers: Option<Box<Account<'info, TraderFeeTiers>>>,
+
+    #[account(
+        mut,
+        seeds = [TraderVolume::SEED, amms_config.key().as_ref(), signer.key().as_ref()],
+        bump = trader_volume.bump()
+    )]
+    pub trader_volume: Option<Box<Account<'info, TraderVolume>>>,
+
+    #[account(
+        seeds = [RouterRegistry::SEED],
+        bump = router_registry.bump()
+    )]
+    pub router_registry: Option<Box<Account<'info, RouterRegistry>>>,
+
+    #[account(address = sysvar_instructions::ID)]
+    /// CHECK: The instructions sysvar, identifying the router of swaps in AMMs in protected mode and the
+    /// origin of swaps while a swap origin policy is in force
+    pub instructions_sysvar: Option<UncheckedAccount<'info>>,
+}
+
+/// Swap outcome returned to the caller as instruction return data.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct SwapResult {
+    /// The amount of output tokens sent from the pool.
+    pub swap_result: u64,
+
+    /// The change of the pool base-to-quote ratio caused by the swap, in basis points.
+    pub price_impact_basis_points: u64,
 }
 
-pub(crate) fn handler(ctx: Context<SwapInCpAmm>, swap_amount: u64, estimated_result: u64, allowed_slippage: u64, is_in_out: bool) -> Result<()> {
-    let in_transfer_instruction = Box::new(ctx.accounts.get_in_transfer_instruction(swap_amount, is_in_out)?);
-    let swap_payload = ctx.accounts.cp_amm.get_swap_payload(
-        in_transfer_instruction.get_amount_after_fee(),
-        estimated_result,
-        allowed_slippage,
-        ctx.accounts.amms_config.providers_fee_rate_basis_points(),
-        ctx.accounts.amms_config.protocol_fee_rate_basis_points(),
-        is_in_out
-    )?;
-
-    let out_transfer_instruction = Box::new(ctx.accounts.get_out_transfer_instruction(swap_payload.amount_to_withdraw(), is_in_out)?);
-    in_transfer_instruction.execute(None)?;
-    let cp_amm_seeds = ctx.accounts.cp_amm.seeds();
-    let out_instruction_seeds: &[&[&[u8]]] = &[&cp_amm_seeds];
-    out_transfer_instruction.execute(Some(out_instruction_seeds))?;
-
-    ctx.accounts.cp_amm.swap(swap_payload);
-
-    Ok(())
+/// Swaps tokens in the pool.
+///
+/// With `min_received` set, the swap runs in strict mode: the signer's output account is reloaded
+/// after the transfer and the actually received amount must be at least `min_received`. This guards
+/// against mints whose extensions make the delivered amount differ from the computed one.
+///
+/// With `pool_stats_shard` passed, the swap statistics are recorded on the shard instead of the pool.
+/// With `event_ring` passed, a record of the swap is appended to the pool's event ring.
+/// With `trader_volume` passed, the USD volume of the swap is added to the signer's decayed volume, and with
+/// `trader_fee_tiers` passed as well, the providers fee rate is discounted by the tier the volume reached before the swap.
+///
+/// While the pool is in protected mode, the swap must be invoked via CPI by a router approved in the
+/// `RouterRegistry`, passed along with the instructions sysvar, see `validate_routed_swap`. While a swap
+/// origin policy is in force, the instructions sysvar must be passed as well, see `validate_swap_origin`.
+///
+/// The display data of the pool is refreshed with the state after the swap, see `CpAmm::refresh_display_data`.
+///
+/// Fails with `VaultBalanceDrift` if a vault holds less than the recorded liquidity and fees, anyone can
+/// then suspend the swaps and provides of the pool with `report_vault_drift`.
+pub(crate) fn handler<'info>(ctx: Context<'_, '_, '_, 'info, SwapInCpAmm<'info>>, swap_amount: u64, estimated_result: u64, allowed_slippage: u64, is_in_out: bool, min_received: Option<u64>) -> Result<SwapResult> {
+    SwapCore {
+        trader: ctx.accounts.signer.key(),
+        transfer_authority: ctx.accounts.signer.to_account_info(),
+        base_mint: &ctx.accounts.base_mint,
+        quote_mint: &ctx.accounts.quote_mint,
+        trader_base_account: &mut ctx.accounts.signer_base_account,
+        trader_quote_account: &mut ctx.accounts.signer_quote_account,
+        amms_config: &ctx.accounts.amms_config,
+        cp_amm: &mut ctx.accounts.cp_amm,
+        cp_amm_base_vault: &ctx.accounts.cp_amm_base_vault,
+        cp_amm_quote_vault: &ctx.accounts.cp_amm_quote_vault,
+        usd_oracle: ctx.accounts.usd_oracle.as_deref(),
+        base_token_program: &ctx.accounts.base_token_program,
+        quote_token_program: &ctx.accounts.quote_token_program,
+        pool_stats_shard: ctx.accounts.pool_stats_shard.as_deref_mut(),
+        event_ring: ctx.accounts.event_ring.as_ref(),
+        trader_fee_tiers: ctx.accounts.trader_fee_tiers.as_deref(),
+        trader_volume: ctx.accounts.trader_volume.as_deref_mut(),
+        router_registry: ctx.accounts.router_registry.as_deref(),
+        instructions_sysvar: ctx.accounts.instructions_sysvar.as_deref(),
+        remaining_accounts: ctx.remaining_accounts,
+    }.execute(swap_amount, estimated_result, allowed_slippage, is_in_out, min_received, None)
 }
 
-impl<'info> SwapInCpAmm<'info>{
+/// The accounts of a swap in a `CpAmm`, shared by every swap instruction so they all run the same swap.
+///
+/// The `trader` owns the token accounts the swap is settled with and is recorded as the signer of the swap,
+/// while the `transfer_authority` moves the input tokens out of the trader's account, e.g. the trader itself
+/// or a trading session PDA approved as its delegate. The optional accounts are `None` for the swap
+/// instructions not taking them.
+pub(crate) struct SwapCore<'a, 'info> {
+    pub trader: Pubkey,
+    pub transfer_authority: AccountInfo<'info>,
+    pub base_mint: &'a InterfaceAccount<'info, Mint>,
+    pub quote_mint: &'a InterfaceAccount<'info, Mint>,
+    pub trader_base_account: &'a mut InterfaceAccount<'info, TokenAccount>,
+    pub trader_quote_account: &'a mut InterfaceAccount<'info, TokenAccount>,
+    pub amms_config: &'a AmmsConfig,
+    pub cp_amm: &'a mut Account<'info, CpAmm>,
+    pub cp_amm_base_vault: &'a InterfaceAccount<'info, TokenAccount>,
+    pub cp_amm_quote_vault: &'a InterfaceAccount<'info, TokenAccount>,
+    pub usd_oracle: Option<&'a AccountInfo<'info>>,
+    pub base_token_program: &'a Interface<'info, TokenInterface>,
+    pub quote_token_program: &'a Interface<'info, TokenInterface>,
+    pub pool_stats_shard: Option<&'a mut Account<'info, PoolStatsShard>>,
+    pub event_ring: Option<&'a AccountLoader<'info, EventRing>>,
+    pub trader_fee_tiers: Option<&'a Account<'info, TraderFeeTiers>>,
+    pub trader_volume: Option<&'a mut Account<'info, TraderVolume>>,
+    pub router_registry: Option<&'a Account<'info, RouterRegistry>>,
+    pub instructions_sysvar: Option<&'a AccountInfo<'info>>,
+    pub remaining_accounts: &'a [AccountInfo<'info>],
+}
+
+impl<'a, 'info> SwapCore<'a, 'info>{
+    /// Executes the swap, see `swap_in_cp_amm` for its behaviour.
+    ///
+    /// # Parameters
+    /// - `transfer_authority_seeds`: The signer seeds of the `transfer_authority` if it's a PDA of the program.
+    #[inline(never)]
+    pub(crate) fn execute(mut self, swap_amount: u64, estimated_result: u64, allowed_slippage: u64, is_in_out: bool, min_received: Option<u64>, transfer_authority_seeds: Option<&[&[&[u8]]]>) -> Result<SwapResult> {
+        self.cp_amm.assert_layout_version()?;
+        self.cp_amm.check_vault_balances(self.cp_amm_base_vault.amount, self.cp_amm_quote_vault.amount)?;
+        let liquidity_snapshot = LiquiditySnapshot::of(self.cp_amm);
+        require!(self.cp_amm.is_market_open(Clock::get()?.unix_timestamp), ErrorCode::MarketClosed);
+        validate_swap_origin(self.amms_config, self.instructions_sysvar, Clock::get()?.slot)?;
+        if self.cp_amm.is_protected(Clock::get()?.slot) {
+            validate_routed_swap(
+                self.router_registry.map(|router_registry| &**router_registry),
+                self.instructions_sysvar
+            )?;
+        }
+        let quote_usd_price = self.get_quote_usd_price()?;
+        let in_transfer_instruction = Box::new(self.get_in_transfer_instruction(swap_amount, is_in_out)?);
+        let in_amount = in_transfer_instruction.get_amount_after_fee();
+        let undiscounted_providers_fee_rate_basis_points = self.amms_config.providers_fee_rate_basis_points_at(Clock::get()?.slot);
+        let trader_fee_tier = self.get_trader_fee_tier(Clock::get()?.unix_timestamp);
+        let providers_fee_rate_basis_points = match trader_fee_tier.as_ref() {
+            Some((_, tier, _)) => TraderFeeTiers::discounted_providers_fee_rate(undiscounted_providers_fee_rate_basis_points, tier),
+            None => undiscounted_providers_fee_rate_basis_points,
+        };
+        let protocol_fee_rate_basis_points = self.cp_amm.protocol_fee_rate_basis_points(self.amms_config.protocol_fee_rate_basis_points());
+        let mut swap_payload = self.cp_amm.get_swap_payload(
+            in_amount,
+            estimated_result,
+            allowed_slippage,
+            providers_fee_rate_basis_points,
+            protocol_fee_rate_basis_points,
+            is_in_out,
+            quote_usd_price,
+            self.amms_config.swap_constant_product_tolerance(),
+            Clock::get()?.slot
+        )?;
+
+        let out_transfer_instruction = Box::new(self.get_out_transfer_instruction(swap_payload.amount_to_withdraw(), is_in_out)?);
+        let out_balance_before = self.get_out_account_balance(is_in_out);
+        in_transfer_instruction.execute(transfer_authority_seeds)?;
+        let cp_amm_seeds = self.cp_amm.seeds();
+        let out_instruction_seeds: &[&[&[u8]]] = &[&cp_amm_seeds];
+        out_transfer_instruction.execute(Some(out_instruction_seeds))?;
+
+        if let Some(min_received) = min_received {
+            let received = self.reload_out_account_balance(is_in_out)?.saturating_sub(out_balance_before);
+            require!(received >= min_received, ErrorCode::SwapReceivedAmountBelowMinimum);
+        }
+
+        let swap_result = SwapResult {
+            swap_result: swap_payload.amount_to_withdraw(),
+            price_impact_basis_points: swap_payload.price_impact_basis_points(),
+        };
+        let (providers_fee_amount, protocol_fee_amount, creator_fee_amount) = (swap_payload.providers_fee_amount(), swap_payload.protocol_fee_amount(), swap_payload.creator_fee_amount());
+        if let Some(pool_stats_shard) = self.pool_stats_shard.as_mut() {
+            pool_stats_shard.record(&swap_payload.take_stats());
+        }
+        self.cp_amm.record_slot_start_price(Clock::get()?.slot);
+        self.cp_amm.swap(swap_payload)?;
+        record_liquidity_change(self.cp_amm, self.amms_config, self.event_ring, LiquidityChange::swap(is_in_out, self.trader, in_amount, swap_result.swap_result), Clock::get()?.slot)?;
+        if let Some(trader_volume) = self.trader_volume.as_mut() {
+            let quote_volume = if is_in_out { swap_result.swap_result } else { in_amount };
+            let usd_volume = quote_usd_price.and_then(|price| price.usd_value(quote_volume)).unwrap_or(0);
+            trader_volume.record_swap(usd_volume, Clock::get()?.unix_timestamp);
+        }
+
+        emit!(SwapEvent {
+            cp_amm: self.cp_amm.key(),
+            signer: self.trader,
+            is_in_out,
+            swap_amount: in_amount,
+            swap_result: swap_result.swap_result,
+            price_impact_basis_points: swap_result.price_impact_basis_points,
+            providers_fee_rate_basis_points,
+            protocol_fee_rate_basis_points,
+            providers_fee_amount,
+            protocol_fee_amount,
+            creator_fee_amount,
+        });
+        if let Some((tier_index, tier, volume_usd)) = trader_fee_tier {
+            emit!(TraderFeeTierAppliedEvent {
+                cp_amm: self.cp_amm.key(),
+                trader: self.trader,
+                tier_index,
+                discount_basis_points: tier.discount_basis_points,
+                volume_usd,
+                undiscounted_providers_fee_rate_basis_points,
+            });
+        }
+        notify_liquidity_subscriber(self.cp_amm, self.remaining_accounts, LiquidityDeltaKind::Swap, liquidity_snapshot)?;
+
+        Ok(swap_result)
+    }
+    fn get_quote_usd_price(&self) -> Result<Option<UsdPrice>>{
+        read_configured_usd_price(self.cp_amm.usd_oracle(), self.usd_oracle, self.quote_mint.decimals)
+    }
+    /// Returns the index of the fee tier reached by the trader's decayed volume, the tier and the volume,
+    /// if both the `TraderFeeTiers` and the `TraderVolume` are passed.
+    fn get_trader_fee_tier(&self, current_timestamp: i64) -> Option<(u8, TraderFeeTier, u64)>{
+        let volume_usd = self.trader_volume.as_ref()?.decayed_volume_usd(current_timestamp);
+        let (tier_index, tier) = self.trader_fee_tiers?.tier(volume_usd)?;
+        Some((tier_index, *tier, volume_usd))
+    }
+    fn get_out_account_balance(&self, is_in_out: bool) -> u64{
+        if is_in_out{
+            self.trader_quote_account.amount
+        }
+        else{
+            self.trader_base_account.amount
+        }
+    }
+    fn reload_out_account_balance(&mut self, is_in_out: bool) -> Result<u64>{
+        if is_in_out{
+            self.trader_quote_account.reload()?;
+            Ok(self.trader_quote_account.amount)
+        }
+        else{
+            self.trader_base_account.reload()?;
+            Ok(self.trader_base_account.amount)
+        }
+    }
+    #[inline(never)]
     fn get_in_transfer_instruction(&self, in_amount: u64, is_in_out: bool) -> Result<TransferTokensInstruction<'_, '_, '_, 'info>>{
         if is_in_out{
             TransferTokensInstruction::try_new(
                 in_amount,
-                &self.base_mint,
-                &self.signer_base_account,
-                self.signer.to_account_info(),
-                &self.cp_amm_base_vault,
-                &self.base_token_program
+                self.base_mint,
+                self.trader_base_account,
+                self.transfer_authority.clone(),
+                self.cp_amm_base_vault,
+                self.base_token_program
             )
         }
         else{
             TransferTokensInstruction::try_new(
                 in_amount,
-                &self.quote_mint,
-                &self.signer_quote_account,
-                self.signer.to_account_info(),
-                &self.cp_amm_quote_vault,
-                &self.quote_token_program
+                self.quote_mint,
+                self.trader_quote_account,
+                self.transfer_authority.clone(),
+                self.cp_amm_quote_vault,
+                self.quote_token_program
             )
         }
     }
+    #[inline(never)]
     fn get_out_transfer_instruction(&self, in_amount: u64, is_in_out: bool) -> Result<TransferTokensInstruction<'_, '_, '_, 'info>>{
         if is_in_out{
             TransferTokensInstruction::try_new(
                 in_amount,
-                &self.quote_mint,
-                &self.cp_amm_quote_vault,
+                self.quote_mint,
+                self.cp_amm_quote_vault,
                 self.cp_amm.to_account_info(),
-                &self.signer_quote_account,
-                &self.quote_token_program
+                self.trader_quote_account,
+                self.quote_token_program
             )
 
         }
         else{
             TransferTokensInstruction::try_new(
                 in_amount,
-                &self.base_mint,
-                &self.cp_amm_base_vault,
+                self.base_mint,
+                self.cp_amm_base_vault,
                 self.cp_amm.to_account_info(),
-                &self.signer_base_account,
-                &self.base_token_program
+                self.trader_base_account,
+                self.base_token_program
             )
         }
     }
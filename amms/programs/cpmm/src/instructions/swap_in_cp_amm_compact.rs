@@ -0,0 +1,106 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions as sysvar_instructions;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::error::ErrorCode;
+use crate::instructions::{SwapCore, SwapResult};
+use crate::state::{AmmsConfig, cp_amm::{CpAmm, EventRing}};
+
+/// Reduced account list variant of `SwapInCpAmm`.
+///
+/// The signer's token accounts must already exist, their addresses are derived on-chain from the signer,
+/// the mints and the token programs, so neither the associated token program nor the system program is
+/// passed. The config and the vaults are validated against the keys stored in the `CpAmm` instead of
+/// re-deriving their PDAs, and the statistics are always recorded on the pool.
+#[derive(Accounts)]
+pub struct SwapInCpAmmCompact<'info>{
+    pub signer: Signer<'info>,
+    pub base_mint: Box<InterfaceAccount<'info, Mint>>,
+    pub quote_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = base_mint,
+        associated_token::authority = signer,
+        associated_token::token_program = base_token_program
+    )]
+    pub signer_base_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = quote_mint,
+        associated_token::authority = signer,
+        associated_token::token_program = quote_token_program
+    )]
+    pub signer_quote_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub amms_config: Box<Account<'info, AmmsConfig>>,
+
+    #[account(
+        mut,
+        constraint = cp_amm.is_launched(),
+        constraint = amms_config.key() == cp_amm.amms_config().key(),
+        constraint = base_mint.key() == cp_amm.base_mint().key(),
+        constraint = quote_mint.key() == cp_amm.quote_mint().key(),
+        constraint = cp_amm_base_vault.key() == cp_amm.base_vault().key(),
+        constraint = cp_amm_quote_vault.key() == cp_amm.quote_vault().key(),
+        seeds = [CpAmm::SEED, cp_amm.lp_mint.as_ref()],
+        bump = cp_amm.bump()
+    )]
+    pub cp_amm: Box<Account<'info, CpAmm>>,
+
+    #[account(mut)]
+    pub cp_amm_base_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub cp_amm_quote_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        constraint = cp_amm.usd_oracle() == Some(&usd_oracle.key()) @ ErrorCode::InvalidCpAmmUsdOracle
+    )]
+    /// CHECK: Price update account matching the CpAmm USD oracle, required only if the oracle is set
+    pub usd_oracle: Option<UncheckedAccount<'info>>,
+
+    pub base_token_program: Interface<'info, TokenInterface>,
+    pub quote_token_program: Interface<'info, TokenInterface>,
+
+    #[account(address = sysvar_instructions::ID)]
+    /// CHECK: The instructions sysvar, identifying the origin of the swap while a swap origin policy is in force
+    pub instructions_sysvar: Option<UncheckedAccount<'info>>,
+
+    #[account(
+        mut,
+        constraint = event_ring.load()?.cp_amm() == &cp_amm.key()
+    )]
+    pub event_ring: Option<AccountLoader<'info, EventRing>>,
+}
+
+/// Swaps tokens in the pool with the reduced account list, shrinking the transaction for wallets
+/// with tight size limits. The swap itself runs through the same `SwapCore` as `swap_in_cp_amm`,
+/// without the accounts the reduced list leaves out: AMMs in protected mode reject it, as a routed
+/// swap needs the `RouterRegistry`, and no trader volume is recorded nor fee tier applied.
+///
+/// With `event_ring` passed, a record of the swap is appended to the pool's event ring.
+pub(crate) fn handler<'info>(ctx: Context<'_, '_, '_, 'info, SwapInCpAmmCompact<'info>>, swap_amount: u64, estimated_result: u64, allowed_slippage: u64, is_in_out: bool) -> Result<SwapResult> {
+    SwapCore {
+        trader: ctx.accounts.signer.key(),
+        transfer_authority: ctx.accounts.signer.to_account_info(),
+        base_mint: &ctx.accounts.base_mint,
+        quote_mint: &ctx.accounts.quote_mint,
+        trader_base_account: &mut ctx.accounts.signer_base_account,
+        trader_quote_account: &mut ctx.accounts.signer_quote_account,
+        amms_config: &ctx.accounts.amms_config,
+        cp_amm: &mut ctx.accounts.cp_amm,
+        cp_amm_base_vault: &ctx.accounts.cp_amm_base_vault,
+        cp_amm_quote_vault: &ctx.accounts.cp_amm_quote_vault,
+        usd_oracle: ctx.accounts.usd_oracle.as_deref(),
+        base_token_program: &ctx.accounts.base_token_program,
+        quote_token_program: &ctx.accounts.quote_token_program,
+        pool_stats_shard: None,
+        event_ring: ctx.accounts.event_ring.as_ref(),
+        trader_fee_tiers: None,
+        trader_volume: None,
+        router_registry: None,
+        instructions_sysvar: ctx.accounts.instructions_sysvar.as_deref(),
+        remaining_accounts: ctx.remaining_accounts,
+    }.execute(swap_amount, estimated_result, allowed_slippage, is_in_out, None, None)
+}
\ No newline at end of file
@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+use crate::error::ErrorCode;
+use crate::instructions::{swap_in_cp_amm, SwapInCpAmm, SwapResult};
+
+/// Direction of a swap in the pool.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SwapDirection {
+    /// Base tokens in, quote tokens out.
+    BaseToQuote,
+    /// Quote tokens in, base tokens out.
+    QuoteToBase,
+}
+
+impl SwapDirection {
+    /// Returns the `is_in_out` flag of the v1 swap instructions.
+    #[inline]
+    pub fn is_in_out(&self) -> bool {
+        matches!(self, SwapDirection::BaseToQuote)
+    }
+}
+
+/// Swaps tokens in the pool, version 2 of `swap_in_cp_amm`.
+///
+/// The slippage is given in basis points of `estimated_result` instead of an absolute amount, and the
+/// swap fails once the `deadline` unix timestamp has passed, so a transaction stuck in the mempool
+/// can't be executed at a stale price. `min_received` enables the strict mode of `swap_in_cp_amm_strict`.
+pub(crate) fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, SwapInCpAmm<'info>>,
+    swap_amount: u64,
+    direction: SwapDirection,
+    estimated_result: u64,
+    slippage_basis_points: u16,
+    deadline: i64,
+    min_received: Option<u64>
+) -> Result<SwapResult> {
+    require!(Clock::get()?.unix_timestamp <= deadline, ErrorCode::SwapDeadlineExceeded);
+    require!(slippage_basis_points <= 10_000, ErrorCode::InvalidSlippageBasisPoints);
+    let allowed_slippage = (estimated_result as u128 * slippage_basis_points as u128 / 10_000) as u64;
+    swap_in_cp_amm::handler(ctx, swap_amount, estimated_result, allowed_slippage, direction.is_in_out(), min_received)
+}
\ No newline at end of file
@@ -0,0 +1,160 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions as sysvar_instructions;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::error::ErrorCode;
+use crate::instructions::{SwapCore, SwapResult};
+use crate::state::{AmmsConfig, cp_amm::{CpAmm, EventRing, PoolStatsShard, TradingSession}, RouterRegistry, TraderFeeTiers, TraderVolume};
+
+#[derive(Accounts)]
+pub struct SwapInCpAmmWithSession<'info>{
+    pub session_key: Signer<'info>,
+    /// CHECK: Owner of the swapped tokens, validated via the trading session seeds
+    pub owner: AccountInfo<'info>,
+    pub base_mint: Box<InterfaceAccount<'info, Mint>>,
+    pub quote_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = base_mint,
+        associated_token::authority = owner,
+        associated_token::token_program = base_token_program
+    )]
+    // Token program will check the delegation to the trading session via token_instructions instruction
+    pub owner_base_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = quote_mint,
+        associated_token::authority = owner,
+        associated_token::token_program = quote_token_program
+    )]
+    // Token program will check the delegation to the trading session via token_instructions instruction
+    pub owner_quote_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        seeds = [AmmsConfig::SEED, amms_config.id.to_le_bytes().as_ref()],
+        bump = amms_config.bump()
+    )]
+    pub amms_config: Box<Account<'info, AmmsConfig>>,
+
+    #[account(
+        mut,
+        constraint = cp_amm.is_launched(),
+        constraint = amms_config.key() == cp_amm.amms_config().key(),
+        constraint = base_mint.key() == cp_amm.base_mint().key(),
+        constraint = quote_mint.key() == cp_amm.quote_mint().key(),
+        constraint = cp_amm_base_vault.key() == cp_amm.base_vault().key(),
+        constraint = cp_amm_quote_vault.key() == cp_amm.quote_vault().key(),
+        seeds = [CpAmm::SEED, cp_amm.lp_mint.as_ref()],
+        bump = cp_amm.bump()
+    )]
+    pub cp_amm: Box<Account<'info, CpAmm>>,
+
+    #[account(
+        mut,
+        seeds = [TradingSession::SEED, cp_amm.key().as_ref(), owner.key().as_ref(), session_key.key().as_ref()],
+        bump = trading_session.bump()
+    )]
+    pub trading_session: Box<Account<'info, TradingSession>>,
+
+    #[account(
+        mut,
+        seeds = [CpAmm::VAULT_SEED, cp_amm.key().as_ref(), cp_amm.base_mint().as_ref()],
+        bump = cp_amm.base_vault_bump()
+    )]
+    pub cp_amm_base_vault:Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [CpAmm::VAULT_SEED, cp_amm.key().as_ref(), cp_amm.quote_mint().as_ref()],
+        bump = cp_amm.quote_vault_bump()
+    )]
+    pub cp_amm_quote_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        constraint = cp_amm.usd_oracle() == Some(&usd_oracle.key()) @ ErrorCode::InvalidCpAmmUsdOracle
+    )]
+    /// CHECK: Price update account matching the CpAmm USD oracle, required only if the oracle is set
+    pub usd_oracle: Option<UncheckedAccount<'info>>,
+
+    pub base_token_program: Interface<'info, TokenInterface>,
+    pub quote_token_program: Interface<'info, TokenInterface>,
+
+    #[account(address = sysvar_instructions::ID)]
+    /// CHECK: The instructions sysvar, identifying the router of swaps in AMMs in protected mode and the
+    /// origin of swaps while a swap origin policy is in force
+    pub instructions_sysvar: Option<UncheckedAccount<'info>>,
+
+    #[account(
+        mut,
+        constraint = event_ring.load()?.cp_amm() == &cp_amm.key()
+    )]
+    pub event_ring: Option<AccountLoader<'info, EventRing>>,
+
+    #[account(
+        mut,
+        seeds = [PoolStatsShard::SEED, cp_amm.key().as_ref(), [pool_stats_shard.shard_index()].as_ref()],
+        bump = pool_stats_shard.bump()
+    )]
+    pub pool_stats_shard: Option<Box<Account<'info, PoolStatsShard>>>,
+
+    #[account(
+        seeds = [TraderFeeTiers::SEED, amms_config.key().as_ref()],
+        bump = trader_fee_tiers.bump()
+    )]
+    pub trader_fee_tiers: Option<Box<Account<'info, TraderFeeTiers>>>,
+
+    #[account(
+        mut,
+        seeds = [TraderVolume::SEED, amms_config.key().as_ref(), owner.key().as_ref()],
+        bump = trader_volume.bump()
+    )]
+    pub trader_volume: Option<Box<Account<'info, TraderVolume>>>,
+
+    #[account(
+        seeds = [RouterRegistry::SEED],
+        bump = router_registry.bump()
+    )]
+    pub router_registry: Option<Box<Account<'info, RouterRegistry>>>,
+}
+
+/// Swaps the owner's tokens in the pool, signed by the session key of an open trading session.
+///
+/// The input tokens are transferred from the owner's token account by the trading session PDA, which
+/// must be approved as its delegate, and the output tokens are sent to the owner's token account.
+/// The quote tokens swapped in or out are recorded on the session and bounded by its limits.
+///
+/// The swap itself runs through the same `SwapCore` as `swap_in_cp_amm`, with the owner as the trader:
+/// the optional accounts, the strict mode with `min_received` set and the validations behave the same way,
+/// and the trader volume and fee tier are the owner's.
+pub(crate) fn handler<'info>(ctx: Context<'_, '_, '_, 'info, SwapInCpAmmWithSession<'info>>, swap_amount: u64, estimated_result: u64, allowed_slippage: u64, is_in_out: bool, min_received: Option<u64>) -> Result<SwapResult> {
+    let trading_session_seeds = ctx.accounts.trading_session.seeds();
+    let in_instruction_seeds: &[&[&[u8]]] = &[&trading_session_seeds];
+    let swap_result = SwapCore {
+        trader: ctx.accounts.owner.key(),
+        transfer_authority: ctx.accounts.trading_session.to_account_info(),
+        base_mint: &ctx.accounts.base_mint,
+        quote_mint: &ctx.accounts.quote_mint,
+        trader_base_account: &mut ctx.accounts.owner_base_account,
+        trader_quote_account: &mut ctx.accounts.owner_quote_account,
+        amms_config: &ctx.accounts.amms_config,
+        cp_amm: &mut ctx.accounts.cp_amm,
+        cp_amm_base_vault: &ctx.accounts.cp_amm_base_vault,
+        cp_amm_quote_vault: &ctx.accounts.cp_amm_quote_vault,
+        usd_oracle: ctx.accounts.usd_oracle.as_deref(),
+        base_token_program: &ctx.accounts.base_token_program,
+        quote_token_program: &ctx.accounts.quote_token_program,
+        pool_stats_shard: ctx.accounts.pool_stats_shard.as_deref_mut(),
+        event_ring: ctx.accounts.event_ring.as_ref(),
+        trader_fee_tiers: ctx.accounts.trader_fee_tiers.as_deref(),
+        trader_volume: ctx.accounts.trader_volume.as_deref_mut(),
+        router_registry: ctx.accounts.router_registry.as_deref(),
+        instructions_sysvar: ctx.accounts.instructions_sysvar.as_deref(),
+        remaining_accounts: ctx.remaining_accounts,
+    }.execute(swap_amount, estimated_result, allowed_slippage, is_in_out, min_received, Some(in_instruction_seeds))?;
+
+    let quote_amount = if is_in_out { swap_result.swap_result } else { swap_amount };
+    ctx.accounts.trading_session.record_swap(quote_amount, Clock::get()?.slot)?;
+
+    Ok(swap_result)
+}
\ No newline at end of file
@@ -0,0 +1,98 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::error::ErrorCode;
+use crate::state::VersionedAccount;
+use crate::state::cp_amm::{CpAmm, LiquidityPosition};
+use crate::utils::token_instructions::TransferTokensInstruction;
+
+#[derive(Accounts)]
+pub struct TransferLpPosition<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    /// CHECK: The recipient only owns the receiving LP token account and position
+    #[account(
+        constraint = recipient.key() != signer.key() @ ErrorCode::LiquidityPositionTransferToSelf
+    )]
+    pub recipient: AccountInfo<'info>,
+
+    #[account(
+        seeds = [CpAmm::SEED, cp_amm.lp_mint.as_ref()],
+        bump = cp_amm.bump()
+    )]
+    pub cp_amm: Box<Account<'info, CpAmm>>,
+
+    #[account(
+        constraint = lp_mint.key() == cp_amm.lp_mint,
+    )]
+    pub lp_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        constraint = signer_lp_account.mint == cp_amm.lp_mint,
+    )]
+    // Token program will check authority via token_instructions instruction
+    pub signer_lp_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = recipient_lp_account.mint == cp_amm.lp_mint,
+        constraint = recipient_lp_account.owner == recipient.key(),
+    )]
+    pub recipient_lp_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [LiquidityPosition::SEED, cp_amm.key().as_ref(), signer.key().as_ref()],
+        bump = signer_liquidity_position.bump()
+    )]
+    pub signer_liquidity_position: Box<Account<'info, LiquidityPosition>>,
+
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = 8 + LiquidityPosition::INIT_SPACE,
+        seeds = [LiquidityPosition::SEED, cp_amm.key().as_ref(), recipient.key().as_ref()],
+        bump
+    )]
+    pub recipient_liquidity_position: Box<Account<'info, LiquidityPosition>>,
+
+    pub lp_token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Transfers LP tokens to a recipient along with their liquidity position state, so custodial reshuffles
+/// keep the loyalty attribution of the moved liquidity instead of restarting it.
+///
+/// The recipient's position is opened if missing. No liquidity is provided or withdrawn,
+/// so the transfer is free of slippage and pool fees.
+pub(crate) fn handler(ctx: Context<TransferLpPosition>, lp_tokens: u64) -> Result<()> {
+    ctx.accounts.cp_amm.assert_layout_version()?;
+    require!(lp_tokens > 0, ErrorCode::ProvidedLpTokensIsZero);
+
+    let transfer_lp_tokens_instruction = Box::new(ctx.accounts.get_transfer_lp_tokens_instruction(lp_tokens)?);
+    transfer_lp_tokens_instruction.execute(None)?;
+
+    // A position created by `init_if_needed` has no owner yet
+    if ctx.accounts.recipient_liquidity_position.owner().key() == Pubkey::default() {
+        let cp_amm_key = ctx.accounts.cp_amm.key();
+        let recipient = ctx.accounts.recipient.key();
+        ctx.accounts.recipient_liquidity_position.initialize(cp_amm_key, recipient, ctx.bumps.recipient_liquidity_position);
+    }
+    let accounts = &mut *ctx.accounts;
+    accounts.signer_liquidity_position.transfer_to(&mut accounts.recipient_liquidity_position, lp_tokens);
+    Ok(())
+}
+
+impl<'info> TransferLpPosition<'info> {
+    #[inline(never)]
+    fn get_transfer_lp_tokens_instruction(&self, lp_tokens: u64) -> Result<TransferTokensInstruction<'_, '_, '_, 'info>>{
+        TransferTokensInstruction::try_new(
+            lp_tokens,
+            &self.lp_mint,
+            &self.signer_lp_account,
+            self.signer.to_account_info(),
+            &self.recipient_lp_account,
+            &self.lp_token_program
+        )
+    }
+}
\ No newline at end of file
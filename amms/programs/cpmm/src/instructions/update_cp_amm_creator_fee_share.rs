@@ -0,0 +1,30 @@
+use anchor_lang::Accounts;
+use anchor_lang::prelude::*;
+use crate::state::{AmmsConfig, cp_amm::CpAmm, VersionedAccount};
+
+#[derive(Accounts)]
+pub struct UpdateCpAmmCreatorFeeShare<'info> {
+    #[account(
+        constraint = authority.key() == cp_amm.creator().key()
+    )]
+    authority: Signer<'info>,
+    #[account(
+        seeds = [AmmsConfig::SEED, amms_config.id.to_le_bytes().as_ref()],
+        bump = amms_config.bump()
+    )]
+    amms_config: Box<Account<'info, AmmsConfig>>,
+    #[account(
+        mut,
+        constraint = amms_config.key() == cp_amm.amms_config().key(),
+        seeds = [CpAmm::SEED, cp_amm.lp_mint.as_ref()],
+        bump = cp_amm.bump()
+    )]
+    cp_amm: Box<Account<'info, CpAmm>>,
+}
+
+/// Sets the share of the providers fee routed to the pool creator, bounded by the `AmmsConfig`.
+pub(crate) fn handler(ctx: Context<UpdateCpAmmCreatorFeeShare>, creator_fee_share_basis_points: u16) -> Result<()> {
+    ctx.accounts.cp_amm.assert_layout_version()?;
+    let max_creator_fee_share_basis_points = ctx.accounts.amms_config.max_creator_fee_share_basis_points();
+    ctx.accounts.cp_amm.update_creator_fee_share(creator_fee_share_basis_points, max_creator_fee_share_basis_points)
+}
\ No newline at end of file
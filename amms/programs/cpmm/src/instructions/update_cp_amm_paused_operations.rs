@@ -0,0 +1,45 @@
+use anchor_lang::Accounts;
+use anchor_lang::prelude::*;
+use crate::error::ErrorCode;
+use crate::state::{AmmsConfigsManager, cp_amm::CpAmm, VersionedAccount};
+
+#[derive(Accounts)]
+pub struct UpdateCpAmmPausedOperations<'info> {
+    // The creator or an AmmsConfigsManager authority acting as the pool guardian
+    #[account(
+        constraint = (
+            authority.key() == cp_amm.creator().key() ||
+            authority.key() == amms_configs_manager.authority().key() ||
+            authority.key() == amms_configs_manager.head_authority().key()
+        )
+    )]
+    authority: Signer<'info>,
+    #[account(
+        seeds = [AmmsConfigsManager::SEED],
+        bump = amms_configs_manager.bump()
+    )]
+    amms_configs_manager: Account<'info, AmmsConfigsManager>,
+    #[account(
+        mut,
+        seeds = [CpAmm::SEED, cp_amm.lp_mint.as_ref()],
+        bump = cp_amm.bump()
+    )]
+    cp_amm: Box<Account<'info, CpAmm>>,
+}
+
+/// Suspends or resumes swaps, liquidity provisions and withdrawals of the pool.
+///
+/// The creator can only pause swaps and provisions, so liquidity providers can always exit
+/// unless an `AmmsConfigsManager` authority halts withdrawals.
+pub(crate) fn handler(ctx: Context<UpdateCpAmmPausedOperations>, swaps_paused: bool, provides_paused: bool, withdraws_paused: bool) -> Result<()> {
+    ctx.accounts.cp_amm.assert_layout_version()?;
+    let authority = ctx.accounts.authority.key();
+    let is_manager_authority = authority == ctx.accounts.amms_configs_manager.authority().key()
+        || authority == ctx.accounts.amms_configs_manager.head_authority().key();
+    require!(
+        is_manager_authority || withdraws_paused == ctx.accounts.cp_amm.withdraws_paused(),
+        ErrorCode::WithdrawsPauseUnauthorized
+    );
+    ctx.accounts.cp_amm.update_paused_operations(swaps_paused, provides_paused, withdraws_paused);
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,24 @@
+use anchor_lang::Accounts;
+use anchor_lang::prelude::*;
+use crate::state::VersionedAccount;
+use crate::state::cp_amm::{CpAmm, TradingSchedule};
+
+#[derive(Accounts)]
+pub struct UpdateCpAmmTradingSchedule<'info> {
+    #[account(
+        constraint = creator.key() == cp_amm.creator().key()
+    )]
+    creator: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [CpAmm::SEED, cp_amm.lp_mint.as_ref()],
+        bump = cp_amm.bump()
+    )]
+    cp_amm: Box<Account<'info, CpAmm>>,
+}
+
+pub(crate) fn handler(ctx: Context<UpdateCpAmmTradingSchedule>, trading_schedule: Option<TradingSchedule>) -> Result<()> {
+    ctx.accounts.cp_amm.assert_layout_version()?;
+    ctx.accounts.cp_amm.update_trading_schedule(trading_schedule);
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,17 @@
+use anchor_lang::prelude::*;
+use crate::state::cp_amm::Snapshot;
+
+#[derive(Accounts)]
+pub struct VerifyCpAmmLpSnapshotLeaf<'info> {
+    #[account(
+        seeds = [Snapshot::SEED, snapshot.cp_amm().as_ref(), snapshot.slot().to_le_bytes().as_ref()],
+        bump = snapshot.bump()
+    )]
+    pub snapshot: Box<Account<'info, Snapshot>>,
+}
+
+/// Verifies that a holder's LP tokens balance is part of a recorded snapshot, failing otherwise,
+/// so airdrop and governance programs may check LP positions via CPI.
+pub(crate) fn handler(ctx: Context<VerifyCpAmmLpSnapshotLeaf>, holder: Pubkey, lp_tokens: u64, proof: Vec<[u8; 32]>) -> Result<()> {
+    ctx.accounts.snapshot.verify_leaf(&holder, lp_tokens, &proof)
+}
\ No newline at end of file
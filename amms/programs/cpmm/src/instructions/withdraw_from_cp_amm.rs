@@ -3,9 +3,35 @@ use anchor_spl::associated_token::AssociatedToken;
 use anchor_spl::token;
 use anchor_spl::token::Token;
 use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
-use crate::state::{AmmsConfig, cp_amm::CpAmm};
+use crate::liquidity_hook::{LiquidityDeltaKind, LiquiditySnapshot};
+use crate::screening_hook::{LiquidityOperation, LiquidityOperationKind};
+use crate::state::{AmmsConfig, cp_amm::{CpAmm, EventRecordKind, EventRing, LiquidityPosition}, VersionedAccount};
+use crate::utils::{record_liquidity_change, LiquidityChange};
+use crate::utils::liquidity_hook_instructions::notify_liquidity_subscriber;
+use crate::utils::screening_hook_instructions::screen_liquidity_operation;
 use crate::utils::token_instructions::{BurnTokensInstructions, TransferTokensInstruction};
 
+/// Accounts of `withdraw_from_cp_amm` and `withdraw_from_cp_amm_exact`, expected in the field order:
+///
+/// 0. `signer` - writable signer owning the LP token account and paying for the withdrawn token accounts.
+/// 1. `base_mint`, 2. `quote_mint` - the tradable mints of the pool.
+/// 3. `lp_mint` - writable LP mint of the pool.
+/// 4. `signer_base_account`, 5. `signer_quote_account` - writable associated token accounts of the signer
+///    receiving the liquidity, created if missing.
+/// 6. `signer_lp_account` - writable token account the LP tokens are burned from.
+/// 7. `amms_config` - the `AmmsConfig` of the pool.
+/// 8. `cp_amm` - writable pool.
+/// 9. `cp_amm_base_vault`, 10. `cp_amm_quote_vault` - writable vaults of the pool.
+/// 11. `associated_token_program`, 12. `lp_token_program`, 13. `base_token_program`, 14. `quote_token_program`, 15. `system_program`.
+/// 16. `liquidity_position` - optional writable `LiquidityPosition` of the signer, the program id if absent.
+/// 17. `event_ring` - optional writable `EventRing` of the pool recording the withdrawal, the program id if absent.
+///
+/// The screening program of the `AmmsConfig`, if any, comes first in the remaining accounts, followed by
+/// the liquidity subscriber program and its forwarded accounts if the pool has one.
+///
+/// The `signer` is only required to sign, so it may be a PDA of another program signing via CPI signer seeds.
+/// Creating the withdrawn token accounts transfers lamports from the `signer`, so a PDA signer must then be
+/// a system-owned account without data holding enough lamports, otherwise the accounts must exist beforehand.
 #[derive(Accounts)]
 pub struct WithdrawFromCpAmm<'info>{
     #[account(mut)]
@@ -76,58 +102,172 @@ pub struct WithdrawFromCpAmm<'info>{
     pub base_token_program: Interface<'info, TokenInterface>,
     pub quote_token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
+
+    #[account(
+        mut,
+        seeds = [LiquidityPosition::SEED, cp_amm.key().as_ref(), signer.key().as_ref()],
+        bump = liquidity_position.bump()
+    )]
+    pub liquidity_position: Option<Box<Account<'info, LiquidityPosition>>>,
+
+    #[account(
+        mut,
+        constraint = event_ring.load()?.cp_amm() == &cp_amm.key()
+    )]
+    pub event_ring: Option<AccountLoader<'info, EventRing>>,
 }
 
-pub(crate) fn handler(ctx: Context<WithdrawFromCpAmm>, lp_tokens: u64) -> Result<()> {
-    let liquidity_burn_instruction = Box::new(ctx.accounts.get_liquidity_burn_instruction(lp_tokens)?);
-    
-    let withdraw_payload = ctx.accounts.cp_amm.get_withdraw_payload(lp_tokens)?;
+/// Withdraws liquidity from the pool.
+///
+/// With `is_burn_exact` set, only the LP tokens covering the rounded withdrawn amounts are burned,
+/// so the rounding remainder stays on the signer's LP account instead of being paid to the pool.
+pub(crate) fn handler<'info>(ctx: Context<'_, '_, '_, 'info, WithdrawFromCpAmm<'info>>, lp_tokens: u64, is_burn_exact: bool) -> Result<()> {
+    WithdrawCore {
+        signer: ctx.accounts.signer.key(),
+        owner: ctx.accounts.signer.to_account_info(),
+        burn_authority: ctx.accounts.signer.to_account_info(),
+        base_mint: &ctx.accounts.base_mint,
+        quote_mint: &ctx.accounts.quote_mint,
+        lp_mint: &ctx.accounts.lp_mint,
+        owner_base_account: &ctx.accounts.signer_base_account,
+        owner_quote_account: &ctx.accounts.signer_quote_account,
+        owner_lp_account: &ctx.accounts.signer_lp_account,
+        amms_config: &ctx.accounts.amms_config,
+        cp_amm: &mut ctx.accounts.cp_amm,
+        cp_amm_base_vault: &ctx.accounts.cp_amm_base_vault,
+        cp_amm_quote_vault: &ctx.accounts.cp_amm_quote_vault,
+        lp_token_program: &ctx.accounts.lp_token_program,
+        base_token_program: &ctx.accounts.base_token_program,
+        quote_token_program: &ctx.accounts.quote_token_program,
+        liquidity_position: ctx.accounts.liquidity_position.as_deref_mut(),
+        event_ring: ctx.accounts.event_ring.as_ref(),
+        remaining_accounts: ctx.remaining_accounts,
+    }.execute(lp_tokens, is_burn_exact)
+}
+
+/// The accounts of a withdrawal from a `CpAmm`, shared by `withdraw_from_cp_amm` and
+/// `withdraw_from_cp_amm_as_delegate` so both run the same withdrawal.
+///
+/// The `owner` owns the LP token account and the token accounts receiving the liquidity and is recorded as
+/// the provider, while the `burn_authority` burns the LP tokens from the owner's LP token account, e.g. the
+/// owner itself or a manager approved as their delegate. The `signer` is the key signing the instruction,
+/// passed to the screening program.
+pub(crate) struct WithdrawCore<'a, 'info> {
+    pub signer: Pubkey,
+    pub owner: AccountInfo<'info>,
+    pub burn_authority: AccountInfo<'info>,
+    pub base_mint: &'a InterfaceAccount<'info, Mint>,
+    pub quote_mint: &'a InterfaceAccount<'info, Mint>,
+    pub lp_mint: &'a Account<'info, token::Mint>,
+    pub owner_base_account: &'a InterfaceAccount<'info, TokenAccount>,
+    pub owner_quote_account: &'a InterfaceAccount<'info, TokenAccount>,
+    pub owner_lp_account: &'a Account<'info, token::TokenAccount>,
+    pub amms_config: &'a AmmsConfig,
+    pub cp_amm: &'a mut Account<'info, CpAmm>,
+    pub cp_amm_base_vault: &'a InterfaceAccount<'info, TokenAccount>,
+    pub cp_amm_quote_vault: &'a InterfaceAccount<'info, TokenAccount>,
+    pub lp_token_program: &'a Program<'info, Token>,
+    pub base_token_program: &'a Interface<'info, TokenInterface>,
+    pub quote_token_program: &'a Interface<'info, TokenInterface>,
+    pub liquidity_position: Option<&'a mut Account<'info, LiquidityPosition>>,
+    pub event_ring: Option<&'a AccountLoader<'info, EventRing>>,
+    pub remaining_accounts: &'a [AccountInfo<'info>],
+}
 
-    let withdraw_base_liquidity_instruction = Box::new(ctx.accounts.get_withdraw_base_liquidity_transfer_instruction(withdraw_payload.base_withdraw_amount())?);
-    let withdraw_quote_liquidity_instruction = Box::new(ctx.accounts.get_withdraw_quote_liquidity_transfer_instruction(withdraw_payload.quote_withdraw_amount())?);
-    
-    liquidity_burn_instruction.execute(None)?;
+impl<'a, 'info> WithdrawCore<'a, 'info>{
+    /// Executes the withdrawal, see `withdraw_from_cp_amm` for its behaviour.
+    #[inline(never)]
+    pub(crate) fn execute(mut self, lp_tokens: u64, is_burn_exact: bool) -> Result<()> {
+        self.cp_amm.assert_layout_version()?;
+        let liquidity_snapshot = LiquiditySnapshot::of(self.cp_amm);
+        self.cp_amm.validate_lp_tokens_supply(self.lp_mint.supply)?;
 
-    let cp_amm_seeds = ctx.accounts.cp_amm.seeds();
-    let withdraw_instruction_seeds: &[&[&[u8]]] = &[&cp_amm_seeds];
+        let withdraw_payload = self.cp_amm.get_withdraw_payload(
+            lp_tokens,
+            Clock::get()?.slot,
+            self.amms_config.incident_withdraw_limit_basis_points(),
+            self.amms_config.min_withdraw_lp_tokens(),
+            self.owner_lp_account.amount,
+            is_burn_exact,
+            self.amms_config.adjust_liquidity_ratio_tolerance()
+        )?;
 
-    withdraw_base_liquidity_instruction.execute(Some(withdraw_instruction_seeds))?;
-    withdraw_quote_liquidity_instruction.execute(Some(withdraw_instruction_seeds))?;
+        let remaining_accounts = screen_liquidity_operation(
+            self.amms_config,
+            self.cp_amm,
+            self.owner.clone(),
+            self.remaining_accounts,
+            &LiquidityOperation {
+                cp_amm: self.cp_amm.key(),
+                kind: LiquidityOperationKind::Withdraw,
+                signer: self.signer,
+                owner: self.owner.key(),
+                base_amount: withdraw_payload.base_withdraw_amount(),
+                quote_amount: withdraw_payload.quote_withdraw_amount(),
+                lp_tokens: withdraw_payload.lp_tokens_to_burn(),
+            }
+        )?;
 
+        let liquidity_burn_instruction = Box::new(self.get_liquidity_burn_instruction(withdraw_payload.lp_tokens_to_burn())?);
 
-    ctx.accounts.cp_amm.withdraw(withdraw_payload);
+        let withdraw_base_liquidity_instruction = Box::new(self.get_withdraw_base_liquidity_transfer_instruction(withdraw_payload.base_withdraw_amount())?);
+        let withdraw_quote_liquidity_instruction = Box::new(self.get_withdraw_quote_liquidity_transfer_instruction(withdraw_payload.quote_withdraw_amount())?);
 
-    Ok(())
-}
+        liquidity_burn_instruction.execute(None)?;
 
-impl<'info> WithdrawFromCpAmm<'info>{
+        let cp_amm_seeds = self.cp_amm.seeds();
+        let withdraw_instruction_seeds: &[&[&[u8]]] = &[&cp_amm_seeds];
+
+        withdraw_base_liquidity_instruction.execute(Some(withdraw_instruction_seeds))?;
+        withdraw_quote_liquidity_instruction.execute(Some(withdraw_instruction_seeds))?;
+
+        if let Some(liquidity_position) = self.liquidity_position.as_mut() {
+            liquidity_position.record_withdraw(withdraw_payload.lp_tokens_to_burn(), Clock::get()?.unix_timestamp);
+        }
+        self.cp_amm.record_slot_start_price(Clock::get()?.slot);
+        let liquidity_change = LiquidityChange {
+            kind: EventRecordKind::Withdraw,
+            signer: self.owner.key(),
+            base_amount: withdraw_payload.base_withdraw_amount(),
+            quote_amount: withdraw_payload.quote_withdraw_amount(),
+            lp_tokens: withdraw_payload.lp_tokens_to_burn(),
+        };
+        self.cp_amm.withdraw(withdraw_payload);
+        record_liquidity_change(self.cp_amm, self.amms_config, self.event_ring, liquidity_change, Clock::get()?.slot)?;
+        notify_liquidity_subscriber(self.cp_amm, remaining_accounts, LiquidityDeltaKind::Withdraw, liquidity_snapshot)?;
+
+        Ok(())
+    }
+    #[inline(never)]
     fn get_withdraw_base_liquidity_transfer_instruction(&self, base_liquidity: u64) -> Result<TransferTokensInstruction<'_, '_, '_, 'info>>{
         TransferTokensInstruction::try_new(
             base_liquidity,
-            &self.base_mint,
-            &self.cp_amm_base_vault,
+            self.base_mint,
+            self.cp_amm_base_vault,
             self.cp_amm.to_account_info(),
-            &self.signer_base_account,
-            &self.base_token_program
+            self.owner_base_account,
+            self.base_token_program
         )
     }
+    #[inline(never)]
     fn get_withdraw_quote_liquidity_transfer_instruction(&self, quote_liquidity: u64) -> Result<TransferTokensInstruction<'_, '_, '_, 'info>>{
         TransferTokensInstruction::try_new(
             quote_liquidity,
-            &self.quote_mint,
-            &self.cp_amm_quote_vault,
+            self.quote_mint,
+            self.cp_amm_quote_vault,
             self.cp_amm.to_account_info(),
-            &self.signer_quote_account,
-            &self.quote_token_program
+            self.owner_quote_account,
+            self.quote_token_program
         )
     }
+    #[inline(never)]
     fn get_liquidity_burn_instruction(&self, liquidity: u64) -> Result<BurnTokensInstructions<'_, '_, '_, 'info>>{
         BurnTokensInstructions::try_new(
             liquidity,
-            &self.lp_mint,
-            &self.signer_lp_account,
-            self.signer.to_account_info(),
-            &self.lp_token_program
+            self.lp_mint,
+            self.owner_lp_account,
+            self.burn_authority.clone(),
+            self.lp_token_program
         )
     }
 }
\ No newline at end of file
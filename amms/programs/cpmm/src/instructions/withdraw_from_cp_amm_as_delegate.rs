@@ -0,0 +1,128 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program_option::COption;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token;
+use anchor_spl::token::Token;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::error::ErrorCode;
+use crate::instructions::WithdrawCore;
+use crate::state::{AmmsConfig, cp_amm::{CpAmm, EventRing, LiquidityPosition}};
+
+#[derive(Accounts)]
+pub struct WithdrawFromCpAmmAsDelegate<'info>{
+    #[account(mut)]
+    pub manager: Signer<'info>,
+    /// CHECK: Owner of the delegated LP token account, validated via token account constraints
+    pub owner: AccountInfo<'info>,
+    pub base_mint: Box<InterfaceAccount<'info, Mint>>,
+    pub quote_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(mut)]
+    pub lp_mint: Box<Account<'info, token::Mint>>,
+
+    #[account(
+        init_if_needed,
+        payer = manager,
+        associated_token::mint = base_mint,
+        associated_token::authority = owner,
+        associated_token::token_program = base_token_program
+    )]
+    pub owner_base_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = manager,
+        associated_token::mint = quote_mint,
+        associated_token::authority = owner,
+        associated_token::token_program = quote_token_program
+    )]
+    pub owner_quote_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = owner_lp_account.owner == owner.key() @ ErrorCode::InvalidDelegatedTokenAccount,
+        constraint = owner_lp_account.delegate == COption::Some(manager.key()) @ ErrorCode::InvalidDelegatedTokenAccount,
+    )]
+    // Token program will check mint and delegated amount via token_instructions instruction
+    pub owner_lp_account: Box<Account<'info, token::TokenAccount>>,
+
+    #[account(
+        seeds = [AmmsConfig::SEED, amms_config.id.to_le_bytes().as_ref()],
+        bump = amms_config.bump()
+    )]
+    pub amms_config: Box<Account<'info, AmmsConfig>>,
+
+    #[account(
+        mut,
+        constraint = cp_amm.is_launched(),
+        constraint = amms_config.key() == cp_amm.amms_config().key(),
+        constraint = lp_mint.key() == cp_amm.lp_mint,
+        constraint = base_mint.key() == cp_amm.base_mint().key(),
+        constraint = quote_mint.key() == cp_amm.quote_mint().key(),
+        constraint = cp_amm_base_vault.key() == cp_amm.base_vault().key(),
+        constraint = cp_amm_quote_vault.key() == cp_amm.quote_vault().key(),
+        seeds = [CpAmm::SEED, cp_amm.lp_mint.as_ref()],
+        bump = cp_amm.bump()
+    )]
+    pub cp_amm: Box<Account<'info, CpAmm>>,
+
+    #[account(
+        mut,
+        seeds = [CpAmm::VAULT_SEED, cp_amm.key().as_ref(), cp_amm.base_mint().as_ref()],
+        bump = cp_amm.base_vault_bump()
+    )]
+    pub cp_amm_base_vault:Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [CpAmm::VAULT_SEED, cp_amm.key().as_ref(), cp_amm.quote_mint().as_ref()],
+        bump = cp_amm.quote_vault_bump()
+    )]
+    pub cp_amm_quote_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub lp_token_program: Program<'info, Token>,
+    pub base_token_program: Interface<'info, TokenInterface>,
+    pub quote_token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+
+    #[account(
+        mut,
+        seeds = [LiquidityPosition::SEED, cp_amm.key().as_ref(), owner.key().as_ref()],
+        bump = liquidity_position.bump()
+    )]
+    pub liquidity_position: Option<Box<Account<'info, LiquidityPosition>>>,
+
+    #[account(
+        mut,
+        constraint = event_ring.load()?.cp_amm() == &cp_amm.key()
+    )]
+    pub event_ring: Option<AccountLoader<'info, EventRing>>,
+}
+
+/// Withdraws the owner's liquidity from the pool, signed by a manager approved as the delegate of the
+/// owner's LP token account. The withdrawn tokens are sent to the owner's token accounts.
+///
+/// The withdrawal itself runs through the same `WithdrawCore` as `withdraw_from_cp_amm`, with the owner as the provider.
+pub(crate) fn handler<'info>(ctx: Context<'_, '_, '_, 'info, WithdrawFromCpAmmAsDelegate<'info>>, lp_tokens: u64) -> Result<()> {
+    WithdrawCore {
+        signer: ctx.accounts.manager.key(),
+        owner: ctx.accounts.owner.to_account_info(),
+        burn_authority: ctx.accounts.manager.to_account_info(),
+        base_mint: &ctx.accounts.base_mint,
+        quote_mint: &ctx.accounts.quote_mint,
+        lp_mint: &ctx.accounts.lp_mint,
+        owner_base_account: &ctx.accounts.owner_base_account,
+        owner_quote_account: &ctx.accounts.owner_quote_account,
+        owner_lp_account: &ctx.accounts.owner_lp_account,
+        amms_config: &ctx.accounts.amms_config,
+        cp_amm: &mut ctx.accounts.cp_amm,
+        cp_amm_base_vault: &ctx.accounts.cp_amm_base_vault,
+        cp_amm_quote_vault: &ctx.accounts.cp_amm_quote_vault,
+        lp_token_program: &ctx.accounts.lp_token_program,
+        base_token_program: &ctx.accounts.base_token_program,
+        quote_token_program: &ctx.accounts.quote_token_program,
+        liquidity_position: ctx.accounts.liquidity_position.as_deref_mut(),
+        event_ring: ctx.accounts.event_ring.as_ref(),
+        remaining_accounts: ctx.remaining_accounts,
+    }.execute(lp_tokens, false)
+}
\ No newline at end of file
@@ -0,0 +1,75 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::error::ErrorCode;
+use crate::state::{AmmsConfig, wp_amm::WpAmm};
+use crate::utils::token_instructions::TransferTokensInstruction;
+
+#[derive(Accounts)]
+pub struct CollectFeesFromWpAmm<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    /// CHECK: Amms config's fee authority can be arbitrary type. It never signs here, so it may be a PDA
+    /// of another program (e.g. a DAO treasury): its ATA below is derived on-chain and paid for by the signer
+    pub fee_authority: AccountInfo<'info>,
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        init_if_needed,
+        payer = signer,
+        associated_token::mint = mint,
+        associated_token::authority = fee_authority,
+        associated_token::token_program = token_program
+    )]
+    pub fee_authority_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        constraint = amms_config.fee_authority().key() == fee_authority.key(),
+        seeds = [AmmsConfig::SEED, amms_config.id.to_le_bytes().as_ref()],
+        bump = amms_config.bump()
+    )]
+    pub amms_config: Box<Account<'info, AmmsConfig>>,
+
+    #[account(
+        mut,
+        constraint = wp_amm.is_launched(),
+        constraint = amms_config.key() == wp_amm.amms_config().key(),
+        constraint = wp_amm.vault_for_mint(&mint.key()) == Some(&wp_amm_vault.key()) @ ErrorCode::WpAmmInvalidSwapMints,
+        seeds = [WpAmm::SEED, wp_amm.lp_mint.as_ref()],
+        bump = wp_amm.bump()
+    )]
+    pub wp_amm: Box<Account<'info, WpAmm>>,
+
+    #[account(mut)]
+    pub wp_amm_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Collects the protocol fees of one token of a `WpAmm` into the fee authority account.
+pub(crate) fn handler(ctx: Context<CollectFeesFromWpAmm>) -> Result<()> {
+    let index = ctx.accounts.wp_amm.mint_index(&ctx.accounts.mint.key()).ok_or(ErrorCode::WpAmmInvalidSwapMints)?;
+    let protocol_fees = ctx.accounts.wp_amm.collect_protocol_fees(index)?;
+
+    let wp_amm_seeds = ctx.accounts.wp_amm.seeds();
+    let collect_fees_instruction_seeds: &[&[&[u8]]] = &[&wp_amm_seeds];
+    ctx.accounts.get_collect_fees_transfer_instruction(protocol_fees)?.execute(Some(collect_fees_instruction_seeds))?;
+
+    Ok(())
+}
+
+impl<'info> CollectFeesFromWpAmm<'info> {
+    #[inline(never)]
+    fn get_collect_fees_transfer_instruction(&self, amount: u64) -> Result<TransferTokensInstruction<'_, '_, '_, 'info>> {
+        TransferTokensInstruction::try_new(
+            amount,
+            &self.mint,
+            &self.wp_amm_vault,
+            self.wp_amm.to_account_info(),
+            &self.fee_authority_account,
+            &self.token_program
+        )
+    }
+}
\ No newline at end of file
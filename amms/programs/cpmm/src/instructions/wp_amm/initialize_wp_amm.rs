@@ -0,0 +1,184 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{token::{Mint, Token}, token_interface};
+use anchor_spl::token_interface::TokenInterface;
+use crate::constants::WP_AMM_INITIALIZE_PRICE_IN_LAMPORTS;
+use crate::state::{AmmsConfig, wp_amm::{
+    WpAmm,
+    WpAmmCalculate,
+    WP_AMM_TOKENS_COUNT
+}};
+use crate::utils::system_instructions::TransferLamportsInstruction;
+use crate::utils::token_accounts_instructions::CreatePdaTokenAccountInstruction;
+use crate::utils::token_metadata::log_invalid_mint;
+use crate::utils::validate_tradable_mint;
+
+#[derive(Accounts)]
+pub struct InitializeWpAmm<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(mut)]
+    /// CHECK: Amms config's fee authority can be arbitrary type
+    pub fee_authority: AccountInfo<'info>,
+    pub mint_a: Box<InterfaceAccount<'info, token_interface::Mint>>,
+    #[account(
+        constraint = mint_a.key() != mint_b.key()
+    )]
+    pub mint_b: Box<InterfaceAccount<'info, token_interface::Mint>>,
+    #[account(
+        constraint = mint_a.key() != mint_c.key(),
+        constraint = mint_b.key() != mint_c.key()
+    )]
+    pub mint_c: Box<InterfaceAccount<'info, token_interface::Mint>>,
+
+    #[account(
+        init,
+        payer = signer,
+        mint::decimals = WpAmm::LP_MINT_INITIAL_DECIMALS,
+        mint::authority = wp_amm,
+        mint::token_program = lp_token_program
+    )]
+    pub lp_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        constraint = amms_config.fee_authority().key() == fee_authority.key(),
+        seeds = [AmmsConfig::SEED, amms_config.id.to_le_bytes().as_ref()],
+        bump = amms_config.bump()
+    )]
+    pub amms_config: Box<Account<'info, AmmsConfig>>,
+
+    #[account(
+        init,
+        payer = signer,
+        space = 8 + WpAmm::INIT_SPACE,
+        seeds = [WpAmm::SEED, lp_mint.key().as_ref()],
+        bump
+    )]
+    pub wp_amm: Box<Account<'info, WpAmm>>,
+
+    #[account(
+        mut,
+        seeds = [WpAmm::VAULT_SEED, wp_amm.key().as_ref(), mint_a.key().as_ref()],
+        bump
+    )]
+    /// CHECK: Vault is created and initialized by the instruction
+    pub wp_amm_vault_a: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [WpAmm::VAULT_SEED, wp_amm.key().as_ref(), mint_b.key().as_ref()],
+        bump
+    )]
+    /// CHECK: Vault is created and initialized by the instruction
+    pub wp_amm_vault_b: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [WpAmm::VAULT_SEED, wp_amm.key().as_ref(), mint_c.key().as_ref()],
+        bump
+    )]
+    /// CHECK: Vault is created and initialized by the instruction
+    pub wp_amm_vault_c: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [WpAmm::VAULT_SEED, wp_amm.key().as_ref(), lp_mint.key().as_ref()],
+        bump
+    )]
+    /// CHECK: Vault is created and initialized by the instruction
+    pub wp_amm_locked_lp_vault: AccountInfo<'info>,
+
+    pub rent: Sysvar<'info, Rent>,
+    pub system_program: Program<'info, System>,
+    pub lp_token_program: Program<'info, Token>,
+    pub token_program_a: Interface<'info, TokenInterface>,
+    pub token_program_b: Interface<'info, TokenInterface>,
+    pub token_program_c: Interface<'info, TokenInterface>,
+}
+
+pub(crate) fn handler(ctx: Context<InitializeWpAmm>, weights_basis_points: [u16; WP_AMM_TOKENS_COUNT]) -> Result<()> {
+    for mint in [&ctx.accounts.mint_a, &ctx.accounts.mint_b, &ctx.accounts.mint_c] {
+        ctx.accounts.amms_config.validate_mint_decimals(mint.decimals)
+            .and_then(|_| validate_tradable_mint(mint))
+            .map_err(|error| log_invalid_mint(&mint.to_account_info(), None, error))?;
+    }
+
+    let wp_amm_key = ctx.accounts.wp_amm.key();
+    let vaults_bumps = [ctx.bumps.wp_amm_vault_a, ctx.bumps.wp_amm_vault_b, ctx.bumps.wp_amm_vault_c];
+    for (index, vault_bump) in vaults_bumps.into_iter().enumerate() {
+        let mint_key = ctx.accounts.mint(index).key();
+        let create_wp_amm_vault = Box::new(ctx.accounts.get_create_wp_amm_vault_instruction(index)?);
+        let wp_amm_vault_seeds = [WpAmm::VAULT_SEED, wp_amm_key.as_ref(), mint_key.as_ref(), &[vault_bump]];
+        create_wp_amm_vault.execute(&[&wp_amm_vault_seeds])?;
+    }
+    {
+        let lp_mint_key = ctx.accounts.lp_mint.key();
+        let create_wp_amm_locked_lp_vault = Box::new(ctx.accounts.get_create_wp_amm_locked_lp_vault_instruction()?);
+        let wp_amm_locked_lp_vault_seeds = [WpAmm::VAULT_SEED, wp_amm_key.as_ref(), lp_mint_key.as_ref(), &[ctx.bumps.wp_amm_locked_lp_vault]];
+        create_wp_amm_locked_lp_vault.execute(&[&wp_amm_locked_lp_vault_seeds])?;
+    }
+    let accounts = ctx.accounts;
+
+    let pay_initial_lamports_instruction = Box::new(accounts.get_pay_initial_lamports_instruction(WP_AMM_INITIALIZE_PRICE_IN_LAMPORTS)?);
+    pay_initial_lamports_instruction.execute()?;
+
+    let mints = [accounts.mint_a.key(), accounts.mint_b.key(), accounts.mint_c.key()];
+    let vaults = [accounts.wp_amm_vault_a.key(), accounts.wp_amm_vault_b.key(), accounts.wp_amm_vault_c.key()];
+    accounts.wp_amm.initialize(
+        mints,
+        weights_basis_points,
+        accounts.lp_mint.key(),
+        vaults,
+        accounts.wp_amm_locked_lp_vault.key(),
+        accounts.amms_config.key(),
+        accounts.signer.key(),
+        ctx.bumps.wp_amm,
+        vaults_bumps,
+        ctx.bumps.wp_amm_locked_lp_vault
+    )
+}
+
+impl<'info> InitializeWpAmm<'info>{
+    fn mint(&self, index: usize) -> &InterfaceAccount<'info, token_interface::Mint> {
+        match index {
+            0 => &self.mint_a,
+            1 => &self.mint_b,
+            _ => &self.mint_c,
+        }
+    }
+    #[inline(never)]
+    fn get_pay_initial_lamports_instruction(&self, lamports: u64) -> Result<TransferLamportsInstruction<'_, '_, '_, 'info>>{
+        TransferLamportsInstruction::new(
+            lamports,
+            self.signer.to_account_info(),
+            self.fee_authority.to_account_info(),
+            &self.system_program
+        )
+    }
+    #[inline(never)]
+    fn get_create_wp_amm_vault_instruction(&self, index: usize) -> Result<CreatePdaTokenAccountInstruction<'_, '_, '_, 'info>>{
+        let (vault, token_program) = match index {
+            0 => (&self.wp_amm_vault_a, &self.token_program_a),
+            1 => (&self.wp_amm_vault_b, &self.token_program_b),
+            _ => (&self.wp_amm_vault_c, &self.token_program_c),
+        };
+        CreatePdaTokenAccountInstruction::try_new(
+            self.signer.to_account_info(),
+            vault.to_account_info(),
+            self.wp_amm.to_account_info(),
+            self.mint(index).to_account_info(),
+            token_program.to_account_info(),
+            self.system_program.to_account_info()
+        )
+    }
+    #[inline(never)]
+    fn get_create_wp_amm_locked_lp_vault_instruction(&self) -> Result<CreatePdaTokenAccountInstruction<'_, '_, '_, 'info>>{
+        CreatePdaTokenAccountInstruction::try_new(
+            self.signer.to_account_info(),
+            self.wp_amm_locked_lp_vault.to_account_info(),
+            self.wp_amm.to_account_info(),
+            self.lp_mint.to_account_info(),
+            self.lp_token_program.to_account_info(),
+            self.system_program.to_account_info()
+        )
+    }
+}
\ No newline at end of file
@@ -0,0 +1,14 @@
+#![allow(ambiguous_glob_reexports)]
+pub mod initialize_wp_amm;
+pub mod launch_wp_amm;
+pub mod provide_to_wp_amm;
+pub mod withdraw_from_wp_amm;
+pub mod swap_in_wp_amm;
+pub mod collect_fees_from_wp_amm;
+
+pub use initialize_wp_amm::*;
+pub use launch_wp_amm::*;
+pub use provide_to_wp_amm::*;
+pub use withdraw_from_wp_amm::*;
+pub use swap_in_wp_amm::*;
+pub use collect_fees_from_wp_amm::*;
\ No newline at end of file
@@ -0,0 +1,114 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions as sysvar_instructions;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::error::ErrorCode;
+use crate::state::{AmmsConfig, wp_amm::WpAmm};
+use crate::utils::token_instructions::{TransferTokensInstruction};
+use crate::utils::validate_swap_origin;
+
+#[derive(Accounts)]
+pub struct SwapInWpAmm<'info>{
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(constraint = mint_in.key() != mint_out.key() @ ErrorCode::WpAmmInvalidSwapMints)]
+    pub mint_in: Box<InterfaceAccount<'info, Mint>>,
+    pub mint_out: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut)]
+    // Token program will check mint and authority via token_instructions instruction
+    pub signer_in_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = signer,
+        associated_token::mint = mint_out,
+        associated_token::authority = signer,
+        associated_token::token_program = token_program_out
+    )]
+    pub signer_out_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        seeds = [AmmsConfig::SEED, amms_config.id.to_le_bytes().as_ref()],
+        bump = amms_config.bump()
+    )]
+    pub amms_config: Box<Account<'info, AmmsConfig>>,
+
+    #[account(
+        mut,
+        constraint = wp_amm.is_launched(),
+        constraint = amms_config.key() == wp_amm.amms_config().key(),
+        constraint = wp_amm.vault_for_mint(&mint_in.key()) == Some(&wp_amm_vault_in.key()) @ ErrorCode::WpAmmInvalidSwapMints,
+        constraint = wp_amm.vault_for_mint(&mint_out.key()) == Some(&wp_amm_vault_out.key()) @ ErrorCode::WpAmmInvalidSwapMints,
+        seeds = [WpAmm::SEED, wp_amm.lp_mint.as_ref()],
+        bump = wp_amm.bump()
+    )]
+    pub wp_amm: Box<Account<'info, WpAmm>>,
+
+    #[account(mut)]
+    pub wp_amm_vault_in: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub wp_amm_vault_out: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program_in: Interface<'info, TokenInterface>,
+    pub token_program_out: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+
+    #[account(address = sysvar_instructions::ID)]
+    /// CHECK: The instructions sysvar, identifying the origin of the swap while a swap origin policy is in force
+    pub instructions_sysvar: Option<UncheckedAccount<'info>>,
+}
+
+pub(crate) fn handler(ctx: Context<SwapInWpAmm>, swap_amount: u64, estimated_result: u64, allowed_slippage: u64) -> Result<()> {
+    validate_swap_origin(&ctx.accounts.amms_config, ctx.accounts.instructions_sysvar.as_deref(), Clock::get()?.slot)?;
+    let in_index = ctx.accounts.wp_amm.mint_index(&ctx.accounts.mint_in.key()).ok_or(ErrorCode::WpAmmInvalidSwapMints)?;
+    let out_index = ctx.accounts.wp_amm.mint_index(&ctx.accounts.mint_out.key()).ok_or(ErrorCode::WpAmmInvalidSwapMints)?;
+
+    let in_transfer_instruction = Box::new(ctx.accounts.get_in_transfer_instruction(swap_amount)?);
+    let swap_payload = ctx.accounts.wp_amm.get_swap_payload(
+        in_index,
+        out_index,
+        in_transfer_instruction.get_amount_after_fee(),
+        estimated_result,
+        allowed_slippage,
+        ctx.accounts.amms_config.providers_fee_rate_basis_points_at(Clock::get()?.slot),
+        ctx.accounts.amms_config.protocol_fee_rate_basis_points()
+    )?;
+
+    let out_transfer_instruction = Box::new(ctx.accounts.get_out_transfer_instruction(swap_payload.amount_to_withdraw())?);
+    in_transfer_instruction.execute(None)?;
+    let wp_amm_seeds = ctx.accounts.wp_amm.seeds();
+    let out_instruction_seeds: &[&[&[u8]]] = &[&wp_amm_seeds];
+    out_transfer_instruction.execute(Some(out_instruction_seeds))?;
+
+    ctx.accounts.wp_amm.swap(swap_payload);
+
+    Ok(())
+}
+
+impl<'info> SwapInWpAmm<'info>{
+    #[inline(never)]
+    fn get_in_transfer_instruction(&self, in_amount: u64) -> Result<TransferTokensInstruction<'_, '_, '_, 'info>>{
+        TransferTokensInstruction::try_new(
+            in_amount,
+            &self.mint_in,
+            &self.signer_in_account,
+            self.signer.to_account_info(),
+            &self.wp_amm_vault_in,
+            &self.token_program_in
+        )
+    }
+    #[inline(never)]
+    fn get_out_transfer_instruction(&self, out_amount: u64) -> Result<TransferTokensInstruction<'_, '_, '_, 'info>>{
+        TransferTokensInstruction::try_new(
+            out_amount,
+            &self.mint_out,
+            &self.wp_amm_vault_out,
+            self.wp_amm.to_account_info(),
+            &self.signer_out_account,
+            &self.token_program_out
+        )
+    }
+}
\ No newline at end of file
@@ -0,0 +1,136 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token;
+use anchor_spl::token::Token;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::{AmmsConfig, wp_amm::{WpAmm, WP_AMM_TOKENS_COUNT}};
+use crate::utils::token_instructions::{BurnTokensInstructions, TransferTokensInstruction};
+
+#[derive(Accounts)]
+pub struct WithdrawFromWpAmm<'info>{
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    pub mint_a: Box<InterfaceAccount<'info, Mint>>,
+    pub mint_b: Box<InterfaceAccount<'info, Mint>>,
+    pub mint_c: Box<InterfaceAccount<'info, Mint>>,
+    #[account(mut)]
+    pub lp_mint: Box<Account<'info, token::Mint>>,
+
+    #[account(
+        init_if_needed,
+        payer = signer,
+        associated_token::mint = mint_a,
+        associated_token::authority = signer,
+        associated_token::token_program = token_program_a
+    )]
+    pub signer_account_a: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = signer,
+        associated_token::mint = mint_b,
+        associated_token::authority = signer,
+        associated_token::token_program = token_program_b
+    )]
+    pub signer_account_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = signer,
+        associated_token::mint = mint_c,
+        associated_token::authority = signer,
+        associated_token::token_program = token_program_c
+    )]
+    pub signer_account_c: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    // Token program will check mint and authority via token_instructions instruction
+    pub signer_lp_account: Box<Account<'info, token::TokenAccount>>,
+
+    #[account(
+        seeds = [AmmsConfig::SEED, amms_config.id.to_le_bytes().as_ref()],
+        bump = amms_config.bump()
+    )]
+    pub amms_config: Box<Account<'info, AmmsConfig>>,
+
+    #[account(
+        mut,
+        constraint = wp_amm.is_launched(),
+        constraint = amms_config.key() == wp_amm.amms_config().key(),
+        constraint = lp_mint.key() == wp_amm.lp_mint,
+        constraint = [mint_a.key(), mint_b.key(), mint_c.key()] == *wp_amm.mints(),
+        constraint = [wp_amm_vault_a.key(), wp_amm_vault_b.key(), wp_amm_vault_c.key()] == *wp_amm.vaults(),
+        seeds = [WpAmm::SEED, wp_amm.lp_mint.as_ref()],
+        bump = wp_amm.bump()
+    )]
+    pub wp_amm: Box<Account<'info, WpAmm>>,
+
+    #[account(mut)]
+    pub wp_amm_vault_a: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub wp_amm_vault_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub wp_amm_vault_c: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub lp_token_program: Program<'info, Token>,
+    pub token_program_a: Interface<'info, TokenInterface>,
+    pub token_program_b: Interface<'info, TokenInterface>,
+    pub token_program_c: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+pub(crate) fn handler(ctx: Context<WithdrawFromWpAmm>, lp_tokens: u64) -> Result<()> {
+    let liquidity_burn_instruction = Box::new(ctx.accounts.get_liquidity_burn_instruction(lp_tokens)?);
+
+    let withdraw_payload = ctx.accounts.wp_amm.get_withdraw_payload(lp_tokens)?;
+
+    let mut withdraw_liquidity_instructions = Vec::with_capacity(WP_AMM_TOKENS_COUNT);
+    for (index, token_liquidity) in withdraw_payload.withdraw_liquidity().into_iter().enumerate() {
+        withdraw_liquidity_instructions.push(Box::new(ctx.accounts.get_withdraw_liquidity_transfer_instruction(index, token_liquidity)?));
+    }
+
+    liquidity_burn_instruction.execute(None)?;
+
+    let wp_amm_seeds = ctx.accounts.wp_amm.seeds();
+    let withdraw_instruction_seeds: &[&[&[u8]]] = &[&wp_amm_seeds];
+
+    for withdraw_liquidity_instruction in withdraw_liquidity_instructions {
+        withdraw_liquidity_instruction.execute(Some(withdraw_instruction_seeds))?;
+    }
+
+    ctx.accounts.wp_amm.withdraw(withdraw_payload);
+
+    Ok(())
+}
+
+impl<'info> WithdrawFromWpAmm<'info>{
+    #[inline(never)]
+    fn get_withdraw_liquidity_transfer_instruction(&self, index: usize, liquidity: u64) -> Result<TransferTokensInstruction<'_, '_, '_, 'info>>{
+        let (mint, wp_amm_vault, signer_account, token_program) = match index {
+            0 => (&self.mint_a, &self.wp_amm_vault_a, &self.signer_account_a, &self.token_program_a),
+            1 => (&self.mint_b, &self.wp_amm_vault_b, &self.signer_account_b, &self.token_program_b),
+            _ => (&self.mint_c, &self.wp_amm_vault_c, &self.signer_account_c, &self.token_program_c),
+        };
+        TransferTokensInstruction::try_new(
+            liquidity,
+            mint,
+            wp_amm_vault,
+            self.wp_amm.to_account_info(),
+            signer_account,
+            token_program
+        )
+    }
+    #[inline(never)]
+    fn get_liquidity_burn_instruction(&self, liquidity: u64) -> Result<BurnTokensInstructions<'_, '_, '_, 'info>>{
+        BurnTokensInstructions::try_new(
+            liquidity,
+            &self.lp_mint,
+            &self.signer_lp_account,
+            self.signer.to_account_info(),
+            &self.lp_token_program
+        )
+    }
+}
\ No newline at end of file
@@ -4,9 +4,16 @@ declare_id!("EFta43Um9GJB1ABsMh18srSEzsq1EbE3k9uU9aL3dsR3");
 
 pub mod constants;
 pub mod error;
+pub mod events;
 pub mod instructions;
+pub mod liquidity_hook;
+pub mod screening_hook;
 pub mod state;
 pub mod utils;
+#[cfg(feature = "sim")]
+pub mod sim;
+#[cfg(test)]
+mod fuzz;
 
 pub use instructions::*;
 
@@ -26,6 +33,22 @@ pub mod cpmm {
         update_amms_configs_manager_head_authority::handler(ctx)
     }
 
+    pub fn update_amms_configs_manager_pool_creation_limit(ctx: Context<UpdateAmmsConfigsManagerPoolCreationLimit>, pool_creation_window_slots: u64, max_pools_per_window: u16) -> Result<()>{
+        update_amms_configs_manager_pool_creation_limit::handler(ctx, pool_creation_window_slots, max_pools_per_window)
+    }
+
+    pub fn update_amms_configs_manager_swap_origin_policy<'info>(ctx: Context<'_, '_, 'info, 'info, UpdateAmmsConfigsManagerSwapOriginPolicy<'info>>, swap_origin_policy: state::SwapOriginPolicy, policy_slots: u64) -> Result<()>{
+        update_amms_configs_manager_swap_origin_policy::handler(ctx, swap_origin_policy, policy_slots)
+    }
+
+    pub fn realloc_amms_configs_manager(ctx: Context<ReallocAmmsConfigsManager>) -> Result<()>{
+        realloc_amms_configs_manager::handler(ctx)
+    }
+
+    pub fn realloc_amms_config(ctx: Context<ReallocAmmsConfig>) -> Result<()>{
+        realloc_amms_config::handler(ctx)
+    }
+
 
     pub fn initialize_amms_config(ctx: Context<InitializeAmmsConfig>, protocol_fee_rate_basis_points: u16, providers_fee_rate_basis_points: u16) -> Result<()>{
         initialize_amms_config::handler(ctx, protocol_fee_rate_basis_points, providers_fee_rate_basis_points)
@@ -39,27 +62,315 @@ pub mod cpmm {
         update_amms_config_providers_fee_rate::handler(ctx, new_providers_fee_rate_basis_points)
     }
 
+    pub fn schedule_amms_config_providers_fee_ramp(ctx: Context<ScheduleAmmsConfigProvidersFeeRamp>, target_providers_fee_rate_basis_points: u16, duration_slots: u64) -> Result<()>{
+        schedule_amms_config_providers_fee_ramp::handler(ctx, target_providers_fee_rate_basis_points, duration_slots)
+    }
+
     pub fn update_amms_config_protocol_fee_rate(ctx: Context<UpdateAmmsConfigProtocolFeeRate>, new_protocol_fee_rate_basis_points: u16) -> Result<()>{
         update_amms_config_protocol_fee_rate::handler(ctx, new_protocol_fee_rate_basis_points)
     }
 
+    pub fn update_amms_config_incident_withdraw_limit(ctx: Context<UpdateAmmsConfigIncidentWithdrawLimit>, new_incident_withdraw_limit_basis_points: u16) -> Result<()>{
+        update_amms_config_incident_withdraw_limit::handler(ctx, new_incident_withdraw_limit_basis_points)
+    }
+    pub fn update_amms_config_keeper_bounty(ctx: Context<UpdateAmmsConfigKeeperBounty>, new_keeper_bounty_basis_points: u16) -> Result<()>{
+        update_amms_config_keeper_bounty::handler(ctx, new_keeper_bounty_basis_points)
+    }
+    pub fn update_amms_config_protocol_fees_cap(ctx: Context<UpdateAmmsConfigProtocolFeesCap>, new_protocol_fees_cap_basis_points: u16) -> Result<()>{
+        update_amms_config_protocol_fees_cap::handler(ctx, new_protocol_fees_cap_basis_points)
+    }
+    pub fn update_amms_config_lp_tokens_limits(ctx: Context<UpdateAmmsConfigLpTokensLimits>, new_min_provide_lp_tokens: u64, new_min_withdraw_lp_tokens: u64) -> Result<()>{
+        update_amms_config_lp_tokens_limits::handler(ctx, new_min_provide_lp_tokens, new_min_withdraw_lp_tokens)
+    }
+    pub fn update_amms_config_mint_decimals_range(ctx: Context<UpdateAmmsConfigMintDecimalsRange>, new_min_mint_decimals: u8, new_max_mint_decimals: u8) -> Result<()>{
+        update_amms_config_mint_decimals_range::handler(ctx, new_min_mint_decimals, new_max_mint_decimals)
+    }
+    pub fn update_amms_config_protocol_rebalance(ctx: Context<UpdateAmmsConfigProtocolRebalance>, is_protocol_rebalance_enabled: bool) -> Result<()>{
+        update_amms_config_protocol_rebalance::handler(ctx, is_protocol_rebalance_enabled)
+    }
+    pub fn update_amms_config_fee_bounds(ctx: Context<UpdateAmmsConfigFeeBounds>, fee_bounds: Option<state::FeeBounds>) -> Result<()>{
+        update_amms_config_fee_bounds::handler(ctx, fee_bounds)
+    }
+    pub fn update_amms_config_lending(ctx: Context<UpdateAmmsConfigLending>, lending_adapter: Option<Pubkey>, max_lend_out_basis_points: u16, lend_out_interest_rate_basis_points: u16) -> Result<()>{
+        update_amms_config_lending::handler(ctx, lending_adapter, max_lend_out_basis_points, lend_out_interest_rate_basis_points)
+    }
+    pub fn update_amms_config_loyalty_boost(ctx: Context<UpdateAmmsConfigLoyaltyBoost>, loyalty_boost: Option<state::LoyaltyBoost>) -> Result<()>{
+        update_amms_config_loyalty_boost::handler(ctx, loyalty_boost)
+    }
+    pub fn update_amms_config_pool_creation_pricing(ctx: Context<UpdateAmmsConfigPoolCreationPricing>, pool_creation_pricing: Option<state::PoolCreationPricing>) -> Result<()>{
+        update_amms_config_pool_creation_pricing::handler(ctx, pool_creation_pricing)
+    }
+    pub fn update_amms_config_launch_price_bound(ctx: Context<UpdateAmmsConfigLaunchPriceBound>, max_launch_price_orders: u8) -> Result<()>{
+        update_amms_config_launch_price_bound::handler(ctx, max_launch_price_orders)
+    }
+    pub fn update_amms_config_tolerances(ctx: Context<UpdateAmmsConfigTolerances>, swap_constant_product_tolerance_ppb: u32, adjust_liquidity_ratio_tolerance_ppb: u32) -> Result<()>{
+        update_amms_config_tolerances::handler(ctx, swap_constant_product_tolerance_ppb, adjust_liquidity_ratio_tolerance_ppb)
+    }
+    pub fn update_amms_config_fee_settlement(ctx: Context<UpdateAmmsConfigFeeSettlement>, fee_settlement: Option<state::FeeSettlement>) -> Result<()>{
+        update_amms_config_fee_settlement::handler(ctx, fee_settlement)
+    }
+    pub fn update_amms_config_creator_fee_share_limit(ctx: Context<UpdateAmmsConfigCreatorFeeShareLimit>, new_max_creator_fee_share_basis_points: u16) -> Result<()>{
+        update_amms_config_creator_fee_share_limit::handler(ctx, new_max_creator_fee_share_basis_points)
+    }
+    pub fn update_amms_config_stale_pool_close(ctx: Context<UpdateAmmsConfigStalePoolClose>, stale_pool_close_epochs: u16, stale_pool_closer_share_basis_points: u16) -> Result<()>{
+        update_amms_config_stale_pool_close::handler(ctx, stale_pool_close_epochs, stale_pool_closer_share_basis_points)
+    }
+
+    pub fn update_amms_config_screening_program(ctx: Context<UpdateAmmsConfigScreeningProgram>) -> Result<()>{
+        update_amms_config_screening_program::handler(ctx)
+    }
+    pub fn update_amms_config_mint_equivalence(ctx: Context<UpdateAmmsConfigMintEquivalence>, mint: Pubkey, group: Option<u8>) -> Result<()>{
+        update_amms_config_mint_equivalence::handler(ctx, mint, group)
+    }
+    pub fn update_amms_config_trader_fee_tiers(ctx: Context<UpdateAmmsConfigTraderFeeTiers>, tiers: Vec<state::TraderFeeTier>) -> Result<()>{
+        update_amms_config_trader_fee_tiers::handler(ctx, tiers)
+    }
+    pub fn update_amms_config_pool_creation_allow_list(ctx: Context<UpdateAmmsConfigPoolCreationAllowList>, is_pool_creation_allow_listed: bool) -> Result<()>{
+        update_amms_config_pool_creation_allow_list::handler(ctx, is_pool_creation_allow_listed)
+    }
+    pub fn update_amms_config_lp_concentration(ctx: Context<UpdateAmmsConfigLpConcentration>, lp_concentration_share_basis_points: u16, lp_concentration_guard_epochs: u16) -> Result<()>{
+        update_amms_config_lp_concentration::handler(ctx, lp_concentration_share_basis_points, lp_concentration_guard_epochs)
+    }
+    pub fn issue_pool_creator_permit(ctx: Context<IssuePoolCreatorPermit>) -> Result<()>{
+        issue_pool_creator_permit::handler(ctx)
+    }
+    pub fn revoke_pool_creator_permit(ctx: Context<RevokePoolCreatorPermit>) -> Result<()>{
+        revoke_pool_creator_permit::handler(ctx)
+    }
+    pub fn initialize_router_registry(ctx: Context<InitializeRouterRegistry>) -> Result<()>{
+        initialize_router_registry::handler(ctx)
+    }
+    pub fn update_router_registry(ctx: Context<UpdateRouterRegistry>, router: Pubkey, is_approved: bool) -> Result<()>{
+        update_router_registry::handler(ctx, router, is_approved)
+    }
+
+    pub fn update_cp_amm_incident_mode(ctx: Context<UpdateCpAmmIncidentMode>, is_incident_limited: bool) -> Result<()>{
+        update_cp_amm_incident_mode::handler(ctx, is_incident_limited)
+    }
+    pub fn update_cp_amm_protected_mode(ctx: Context<UpdateCpAmmProtectedMode>, protected_slots: u64) -> Result<()>{
+        update_cp_amm_protected_mode::handler(ctx, protected_slots)
+    }
+    pub fn update_cp_amm_usd_oracle(ctx: Context<UpdateCpAmmUsdOracle>) -> Result<()>{
+        update_cp_amm_usd_oracle::handler(ctx)
+    }
+    pub fn update_cp_amm_liquidity_subscriber(ctx: Context<UpdateCpAmmLiquiditySubscriber>) -> Result<()>{
+        update_cp_amm_liquidity_subscriber::handler(ctx)
+    }
+    pub fn update_cp_amm_lend_out(ctx: Context<UpdateCpAmmLendOut>, is_lend_out_enabled: bool) -> Result<()>{
+        update_cp_amm_lend_out::handler(ctx, is_lend_out_enabled)
+    }
+    pub fn update_cp_amm_protocol_fee_override(ctx: Context<UpdateCpAmmProtocolFeeOverride>, protocol_fee_override_basis_points: Option<u16>) -> Result<()>{
+        update_cp_amm_protocol_fee_override::handler(ctx, protocol_fee_override_basis_points)
+    }
+    pub fn recall_cp_amm_lend_out_loan(ctx: Context<RecallCpAmmLendOutLoan>) -> Result<()>{
+        recall_cp_amm_lend_out_loan::handler(ctx)
+    }
+    pub fn resync_cp_amm_lp_tokens_supply(ctx: Context<ResyncCpAmmLpTokensSupply>) -> Result<()>{
+        resync_cp_amm_lp_tokens_supply::handler(ctx)
+    }
+    pub fn record_cp_amm_lp_snapshot(ctx: Context<RecordCpAmmLpSnapshot>, slot: u64, merkle_root: [u8; 32], holders_count: u32, total_lp_tokens: u64) -> Result<()>{
+        record_cp_amm_lp_snapshot::handler(ctx, slot, merkle_root, holders_count, total_lp_tokens)
+    }
+    pub fn recover_stranded_tokens(ctx: Context<RecoverStrandedTokens>) -> Result<()>{
+        recover_stranded_tokens::handler(ctx)
+    }
+    pub fn realloc_pool_account(ctx: Context<ReallocPoolAccount>) -> Result<()>{
+        realloc_pool_account::handler(ctx)
+    }
+
     
     pub fn initialize_cp_amm(ctx: Context<InitializeCpAmm>) -> Result<()>{
         initialize_cp_amm::handler(ctx)
     }
-    pub fn launch_cp_amm(ctx: Context<LaunchCpAmm>, base_liquidity: u64, quote_liquidity: u64) -> Result<()>{
-        launch_cp_amm::handler(ctx, base_liquidity, quote_liquidity)
+    pub fn initialize_cp_amm_with_lp_mint(ctx: Context<InitializeCpAmmWithLpMint>) -> Result<()>{
+        initialize_cp_amm_with_lp_mint::handler(ctx)
+    }
+    pub fn launch_cp_amm<'info>(ctx: Context<'_, '_, '_, 'info, LaunchCpAmm<'info>>, base_liquidity: u64, quote_liquidity: u64) -> Result<()>{
+        launch_cp_amm::handler(ctx, base_liquidity, quote_liquidity, None, 0)
+    }
+    pub fn launch_cp_amm_with_expected_ratio<'info>(ctx: Context<'_, '_, '_, 'info, LaunchCpAmm<'info>>, base_liquidity: u64, quote_liquidity: u64, expected_base_quote_ratio_sqrt: utils::math::Q64_128, tolerance_basis_points: u16) -> Result<()>{
+        launch_cp_amm::handler(ctx, base_liquidity, quote_liquidity, Some((expected_base_quote_ratio_sqrt, tolerance_basis_points)), 0)
+    }
+    pub fn launch_cp_amm_with_warm_up<'info>(ctx: Context<'_, '_, '_, 'info, LaunchCpAmm<'info>>, base_liquidity: u64, quote_liquidity: u64, warm_up_slots: u64) -> Result<()>{
+        launch_cp_amm::handler(ctx, base_liquidity, quote_liquidity, None, warm_up_slots)
+    }
+    pub fn close_stale_cp_amm(ctx: Context<CloseStaleCpAmm>) -> Result<()>{
+        close_stale_cp_amm::handler(ctx)
+    }
+    pub fn ensure_rent_exempt(ctx: Context<EnsureRentExempt>, max_top_up: u64) -> Result<RentShortfall>{
+        ensure_rent_exempt::handler(ctx, max_top_up)
     }
-    pub fn provide_to_cp_amm(ctx: Context<ProvideToCpAmm>, base_liquidity: u64, quote_liquidity: u64) -> Result<()>{
+    pub fn provide_to_cp_amm<'info>(ctx: Context<'_, '_, '_, 'info, ProvideToCpAmm<'info>>, base_liquidity: u64, quote_liquidity: u64) -> Result<()>{
         provide_to_cp_amm::handler(ctx, base_liquidity, quote_liquidity)
     }
-    pub fn withdraw_from_cp_amm(ctx: Context<WithdrawFromCpAmm>, lp_tokens: u64) -> Result<()>{
-        withdraw_from_cp_amm::handler(ctx, lp_tokens)
+    pub fn provide_split<'info>(ctx: Context<'_, '_, 'info, 'info, ProvideSplit<'info>>, base_liquidity: u64, quote_liquidity: u64, weights: Vec<u16>) -> Result<()>{
+        provide_split::handler(ctx, base_liquidity, quote_liquidity, weights)
+    }
+    pub fn withdraw_from_cp_amm<'info>(ctx: Context<'_, '_, '_, 'info, WithdrawFromCpAmm<'info>>, lp_tokens: u64) -> Result<()>{
+        withdraw_from_cp_amm::handler(ctx, lp_tokens, false)
+    }
+    pub fn withdraw_from_cp_amm_exact<'info>(ctx: Context<'_, '_, '_, 'info, WithdrawFromCpAmm<'info>>, lp_tokens: u64) -> Result<()>{
+        withdraw_from_cp_amm::handler(ctx, lp_tokens, true)
+    }
+    pub fn provide_to_cp_amm_as_delegate<'info>(ctx: Context<'_, '_, '_, 'info, ProvideToCpAmmAsDelegate<'info>>, base_liquidity: u64, quote_liquidity: u64) -> Result<()>{
+        provide_to_cp_amm_as_delegate::handler(ctx, base_liquidity, quote_liquidity)
+    }
+    pub fn withdraw_from_cp_amm_as_delegate<'info>(ctx: Context<'_, '_, '_, 'info, WithdrawFromCpAmmAsDelegate<'info>>, lp_tokens: u64) -> Result<()>{
+        withdraw_from_cp_amm_as_delegate::handler(ctx, lp_tokens)
+    }
+    pub fn swap_in_cp_amm<'info>(ctx: Context<'_, '_, '_, 'info, SwapInCpAmm<'info>>, swap_amount: u64, estimated_result: u64, allowed_slippage: u64, is_in_out: bool) -> Result<SwapResult>{
+        utils::assert_legacy_ix_enabled()?;
+        swap_in_cp_amm::handler(ctx, swap_amount, estimated_result, allowed_slippage, is_in_out, None)
+    }
+    pub fn swap_in_cp_amm_strict<'info>(ctx: Context<'_, '_, '_, 'info, SwapInCpAmm<'info>>, swap_amount: u64, estimated_result: u64, allowed_slippage: u64, is_in_out: bool, min_received: u64) -> Result<SwapResult>{
+        utils::assert_legacy_ix_enabled()?;
+        swap_in_cp_amm::handler(ctx, swap_amount, estimated_result, allowed_slippage, is_in_out, Some(min_received))
+    }
+    pub fn swap_in_cp_amm_v2<'info>(ctx: Context<'_, '_, '_, 'info, SwapInCpAmm<'info>>, swap_amount: u64, direction: SwapDirection, estimated_result: u64, slippage_basis_points: u16, deadline: i64, min_received: Option<u64>) -> Result<SwapResult>{
+        swap_in_cp_amm_v2::handler(ctx, swap_amount, direction, estimated_result, slippage_basis_points, deadline, min_received)
+    }
+    pub fn swap_in_cp_amm_compact<'info>(ctx: Context<'_, '_, '_, 'info, SwapInCpAmmCompact<'info>>, swap_amount: u64, estimated_result: u64, allowed_slippage: u64, is_in_out: bool) -> Result<SwapResult>{
+        swap_in_cp_amm_compact::handler(ctx, swap_amount, estimated_result, allowed_slippage, is_in_out)
+    }
+    pub fn swap_in_cp_amm_with_session<'info>(ctx: Context<'_, '_, '_, 'info, SwapInCpAmmWithSession<'info>>, swap_amount: u64, estimated_result: u64, allowed_slippage: u64, is_in_out: bool) -> Result<SwapResult>{
+        swap_in_cp_amm_with_session::handler(ctx, swap_amount, estimated_result, allowed_slippage, is_in_out, None)
+    }
+    pub fn swap_in_cp_amm_with_session_strict<'info>(ctx: Context<'_, '_, '_, 'info, SwapInCpAmmWithSession<'info>>, swap_amount: u64, estimated_result: u64, allowed_slippage: u64, is_in_out: bool, min_received: u64) -> Result<SwapResult>{
+        swap_in_cp_amm_with_session::handler(ctx, swap_amount, estimated_result, allowed_slippage, is_in_out, Some(min_received))
+    }
+    pub fn open_trading_session(ctx: Context<OpenTradingSession>, session_key: Pubkey, max_swap_quote_amount: u64, max_total_quote_volume: u64, expires_at_slot: u64) -> Result<()>{
+        open_trading_session::handler(ctx, session_key, max_swap_quote_amount, max_total_quote_volume, expires_at_slot)
+    }
+    pub fn close_trading_session(ctx: Context<CloseTradingSession>) -> Result<()>{
+        close_trading_session::handler(ctx)
+    }
+    pub fn rebalance_in_cp_amm<'info>(ctx: Context<'_, '_, '_, 'info, RebalanceInCpAmm<'info>>, swap_amount: u64, estimated_result: u64, allowed_slippage: u64, is_in_out: bool) -> Result<SwapResult>{
+        rebalance_in_cp_amm::handler(ctx, swap_amount, estimated_result, allowed_slippage, is_in_out)
+    }
+    pub fn arb_two_pools<'info>(ctx: Context<'_, '_, '_, 'info, ArbTwoPools<'info>>, swap_amount: u64, min_profit: u64) -> Result<ArbResult>{
+        arb_two_pools::handler(ctx, swap_amount, min_profit)
+    }
+    pub fn report_vault_drift(ctx: Context<ReportVaultDrift>) -> Result<()>{
+        report_vault_drift::handler(ctx)
+    }
+    pub fn collect_fees_from_cp_amm<'info>(ctx: Context<'_, '_, '_, 'info, CollectFeesFromCpAmm<'info>>) -> Result<()>{
+        collect_fees_from_cp_amm::handler(ctx, false, None)
+    }
+    pub fn collect_fees_from_cp_amm_idempotent<'info>(ctx: Context<'_, '_, '_, 'info, CollectFeesFromCpAmm<'info>>) -> Result<()>{
+        collect_fees_from_cp_amm::handler(ctx, true, None)
+    }
+    pub fn collect_fees_from_cp_amm_partial<'info>(ctx: Context<'_, '_, '_, 'info, CollectFeesFromCpAmm<'info>>, base_fees_amount: u64, quote_fees_amount: u64) -> Result<()>{
+        collect_fees_from_cp_amm::handler(ctx, false, Some((base_fees_amount, quote_fees_amount)))
+    }
+    pub fn collect_creator_fees(ctx: Context<CollectCreatorFees>) -> Result<()>{
+        collect_creator_fees::handler(ctx)
+    }
+    pub fn update_cp_amm_trading_schedule(ctx: Context<UpdateCpAmmTradingSchedule>, trading_schedule: Option<state::cp_amm::TradingSchedule>) -> Result<()>{
+        update_cp_amm_trading_schedule::handler(ctx, trading_schedule)
+    }
+    pub fn update_cp_amm_paused_operations(ctx: Context<UpdateCpAmmPausedOperations>, swaps_paused: bool, provides_paused: bool, withdraws_paused: bool) -> Result<()>{
+        update_cp_amm_paused_operations::handler(ctx, swaps_paused, provides_paused, withdraws_paused)
+    }
+    pub fn update_cp_amm_creator_fee_share(ctx: Context<UpdateCpAmmCreatorFeeShare>, creator_fee_share_basis_points: u16) -> Result<()>{
+        update_cp_amm_creator_fee_share::handler(ctx, creator_fee_share_basis_points)
+    }
+    pub fn lock_fee_parameters(ctx: Context<LockFeeParameters>) -> Result<()>{
+        lock_fee_parameters::handler(ctx)
+    }
+    pub fn enable_cp_amm_decimals_normalization(ctx: Context<EnableCpAmmDecimalsNormalization>) -> Result<()>{
+        enable_cp_amm_decimals_normalization::handler(ctx)
+    }
+    pub fn get_lp_value(ctx: Context<GetLpValue>, lp_tokens: u64) -> Result<LpValue>{
+        get_lp_value::handler(ctx, lp_tokens)
+    }
+    pub fn initialize_cp_amm_fee_checkpoints(ctx: Context<InitializeCpAmmFeeCheckpoints>) -> Result<()>{
+        initialize_cp_amm_fee_checkpoints::handler(ctx)
+    }
+    pub fn record_cp_amm_fee_checkpoint(ctx: Context<RecordCpAmmFeeCheckpoint>) -> Result<()>{
+        record_cp_amm_fee_checkpoint::handler(ctx)
+    }
+    pub fn get_trailing_apr(ctx: Context<GetTrailingApr>) -> Result<TrailingApr>{
+        get_trailing_apr::handler(ctx)
+    }
+    pub fn get_virtual_price(ctx: Context<GetVirtualPrice>) -> Result<VirtualPrice>{
+        get_virtual_price::handler(ctx)
+    }
+    pub fn get_safe_spot_price(ctx: Context<GetSafeSpotPrice>) -> Result<SafeSpotPrice>{
+        get_safe_spot_price::handler(ctx)
+    }
+    pub fn initialize_cp_amm_virtual_price_checkpoints(ctx: Context<InitializeCpAmmVirtualPriceCheckpoints>) -> Result<()>{
+        initialize_cp_amm_virtual_price_checkpoints::handler(ctx)
+    }
+    pub fn record_cp_amm_virtual_price_checkpoint(ctx: Context<RecordCpAmmVirtualPriceCheckpoint>) -> Result<()>{
+        record_cp_amm_virtual_price_checkpoint::handler(ctx)
+    }
+    pub fn refresh_cp_amm_display_data(ctx: Context<RefreshCpAmmDisplayData>) -> Result<()>{
+        refresh_cp_amm_display_data::handler(ctx)
+    }
+    pub fn initialize_cp_amm_pool_stats_shard(ctx: Context<InitializeCpAmmPoolStatsShard>, shard_index: u8) -> Result<()>{
+        initialize_cp_amm_pool_stats_shard::handler(ctx, shard_index)
+    }
+    pub fn initialize_cp_amm_event_ring(ctx: Context<InitializeCpAmmEventRing>) -> Result<()>{
+        initialize_cp_amm_event_ring::handler(ctx)
+    }
+    pub fn register_cp_amm_lookup_table(ctx: Context<RegisterCpAmmLookupTable>) -> Result<()>{
+        register_cp_amm_lookup_table::handler(ctx)
+    }
+    pub fn open_cp_amm_liquidity_position(ctx: Context<OpenCpAmmLiquidityPosition>) -> Result<()>{
+        open_cp_amm_liquidity_position::handler(ctx)
+    }
+    pub fn open_trader_volume(ctx: Context<OpenTraderVolume>) -> Result<()>{
+        open_trader_volume::handler(ctx)
+    }
+    pub fn get_loyalty_multiplier(ctx: Context<GetLoyaltyMultiplier>) -> Result<LoyaltyMultiplier>{
+        get_loyalty_multiplier::handler(ctx)
+    }
+    pub fn transfer_lp_position(ctx: Context<TransferLpPosition>, lp_tokens: u64) -> Result<()>{
+        transfer_lp_position::handler(ctx, lp_tokens)
+    }
+    pub fn quote_gross_swap_input(ctx: Context<QuoteGrossSwapInput>, net_amount: u64, is_in_out: bool) -> Result<GrossSwapInput>{
+        quote_gross_swap_input::handler(ctx, net_amount, is_in_out)
+    }
+    pub fn quote_many(ctx: Context<QuoteMany>, requests: Vec<SwapQuoteRequest>) -> Result<Vec<Option<SwapQuote>>>{
+        quote_many::handler(ctx, requests)
+    }
+    pub fn verify_cp_amm_lp_snapshot_leaf(ctx: Context<VerifyCpAmmLpSnapshotLeaf>, holder: Pubkey, lp_tokens: u64, proof: Vec<[u8; 32]>) -> Result<()>{
+        verify_cp_amm_lp_snapshot_leaf::handler(ctx, holder, lp_tokens, proof)
+    }
+
+    pub fn borrow_from_cp_amm(ctx: Context<BorrowFromCpAmm>, amount: u64) -> Result<()>{
+        borrow_from_cp_amm::handler(ctx, amount)
+    }
+
+    pub fn repay_to_cp_amm<'info>(ctx: Context<'_, '_, '_, 'info, RepayToCpAmm<'info>>, amount: u64) -> Result<()>{
+        repay_to_cp_amm::handler(ctx, amount)
+    }
+    pub fn create_test_pool(ctx: Context<CreateTestPool>, base_decimals: u8, quote_decimals: u8, base_amount: u64, quote_amount: u64, base_liquidity: u64, quote_liquidity: u64) -> Result<()>{
+        create_test_pool::handler(ctx, base_decimals, quote_decimals, base_amount, quote_amount, base_liquidity, quote_liquidity)
+    }
+
+    pub fn initialize_wp_amm(ctx: Context<InitializeWpAmm>, weights_basis_points: [u16; 3]) -> Result<()>{
+        utils::assert_wp_amm_build()?;
+        initialize_wp_amm::handler(ctx, weights_basis_points)
+    }
+    pub fn launch_wp_amm(ctx: Context<LaunchWpAmm>, liquidity: [u64; 3]) -> Result<()>{
+        utils::assert_wp_amm_build()?;
+        launch_wp_amm::handler(ctx, liquidity)
+    }
+    pub fn provide_to_wp_amm(ctx: Context<ProvideToWpAmm>, liquidity: [u64; 3], min_lp_tokens: u64) -> Result<()>{
+        utils::assert_wp_amm_build()?;
+        provide_to_wp_amm::handler(ctx, liquidity, min_lp_tokens)
+    }
+    pub fn withdraw_from_wp_amm(ctx: Context<WithdrawFromWpAmm>, lp_tokens: u64) -> Result<()>{
+        utils::assert_wp_amm_build()?;
+        withdraw_from_wp_amm::handler(ctx, lp_tokens)
     }
-    pub fn swap_in_cp_amm(ctx: Context<SwapInCpAmm>, swap_amount: u64, estimated_result: u64, allowed_slippage: u64, is_in_out: bool) -> Result<()>{
-        swap_in_cp_amm::handler(ctx, swap_amount, estimated_result, allowed_slippage, is_in_out)
+    pub fn swap_in_wp_amm(ctx: Context<SwapInWpAmm>, swap_amount: u64, estimated_result: u64, allowed_slippage: u64) -> Result<()>{
+        utils::assert_wp_amm_build()?;
+        swap_in_wp_amm::handler(ctx, swap_amount, estimated_result, allowed_slippage)
     }
-    pub fn collect_fees_from_cp_amm(ctx: Context<CollectFeesFromCpAmm>) -> Result<()>{
-        collect_fees_from_cp_amm::handler(ctx)
+    pub fn collect_fees_from_wp_amm(ctx: Context<CollectFeesFromWpAmm>) -> Result<()>{
+        utils::assert_wp_amm_build()?;
+        collect_fees_from_wp_amm::handler(ctx)
     }
 }
\ No newline at end of file
@@ -0,0 +1,144 @@
+//! Reference interface for programs subscribing to liquidity changes of a `CpAmm`.
+//!
+//! After every instruction changing the pool liquidity, the AMM invokes its liquidity subscriber
+//! with the instruction data `LIQUIDITY_DELTA_HOOK_DISCRIMINATOR ++ borsh(LiquidityDelta)`, which
+//! matches an Anchor instruction declared as `on_liquidity_delta(ctx, delta: LiquidityDelta)`.
+//!
+//! The subscriber receives the following accounts:
+//! 0. `[]` The `CpAmm` account, already holding the updated state.
+//! 1. `..` Accounts passed to the AMM instruction after the subscriber program account, with
+//!    their writable flags preserved and signer privileges dropped.
+//!
+//! The `CpAmm` is passed read-only and may not be forwarded again, so the runtime rejects any
+//! attempt of the subscriber to reenter the AMM with an instruction modifying the same pool.
+use anchor_lang::prelude::*;
+use crate::state::cp_amm::{CpAmm, CpAmmCore};
+
+/// Instruction discriminator of the subscriber hook, the Anchor sighash of `on_liquidity_delta`.
+pub const LIQUIDITY_DELTA_HOOK_DISCRIMINATOR: [u8; 8] = [1, 4, 93, 218, 6, 219, 218, 198];
+
+/// Kind of the instruction that changed the liquidity of a `CpAmm`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LiquidityDeltaKind {
+    Launch,
+    Provide,
+    Withdraw,
+    Swap,
+    Rebalance,
+    Repay,
+    FeeSettlement,
+}
+
+/// Compact description of a liquidity change sent to the liquidity subscriber of a `CpAmm`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LiquidityDelta {
+    /// The changed `CpAmm` account.
+    pub cp_amm: Pubkey,
+
+    /// The kind of the instruction that changed the liquidity.
+    pub kind: LiquidityDeltaKind,
+
+    /// The slot of the change.
+    pub slot: u64,
+
+    /// The signed change of the base liquidity.
+    pub base_liquidity_delta: i128,
+
+    /// The signed change of the quote liquidity.
+    pub quote_liquidity_delta: i128,
+
+    /// The signed change of the LP tokens supply.
+    pub lp_tokens_supply_delta: i128,
+
+    /// The base liquidity after the change.
+    pub base_liquidity: u64,
+
+    /// The quote liquidity after the change.
+    pub quote_liquidity: u64,
+
+    /// The LP tokens supply after the change.
+    pub lp_tokens_supply: u64,
+}
+
+/// Liquidity of a `CpAmm` captured before an instruction changes it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct LiquiditySnapshot {
+    base_liquidity: u64,
+    quote_liquidity: u64,
+    lp_tokens_supply: u64,
+}
+
+impl LiquiditySnapshot {
+    /// Captures the current liquidity of the `CpAmm`.
+    pub fn of(cp_amm: &CpAmm) -> Self {
+        Self {
+            base_liquidity: cp_amm.base_liquidity(),
+            quote_liquidity: cp_amm.quote_liquidity(),
+            lp_tokens_supply: cp_amm.lp_tokens_supply(),
+        }
+    }
+
+    /// Builds the delta between the snapshot and the current liquidity of the `CpAmm`.
+    ///
+    /// # Parameters
+    /// - `cp_amm_key`: The address of the `CpAmm` account.
+    /// - `cp_amm`: The `CpAmm` with the liquidity change applied.
+    /// - `kind`: The kind of the instruction that changed the liquidity.
+    /// - `slot`: The current slot.
+    pub fn get_delta(&self, cp_amm_key: Pubkey, cp_amm: &CpAmm, kind: LiquidityDeltaKind, slot: u64) -> LiquidityDelta {
+        LiquidityDelta {
+            cp_amm: cp_amm_key,
+            kind,
+            slot,
+            base_liquidity_delta: cp_amm.base_liquidity() as i128 - self.base_liquidity as i128,
+            quote_liquidity_delta: cp_amm.quote_liquidity() as i128 - self.quote_liquidity as i128,
+            lp_tokens_supply_delta: cp_amm.lp_tokens_supply() as i128 - self.lp_tokens_supply as i128,
+            base_liquidity: cp_amm.base_liquidity(),
+            quote_liquidity: cp_amm.quote_liquidity(),
+            lp_tokens_supply: cp_amm.lp_tokens_supply(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod liquidity_hook_tests {
+    use anchor_lang::solana_program::hash::hash;
+    use super::*;
+
+    /// Tests that the hook discriminator matches the Anchor sighash of `on_liquidity_delta`.
+    #[test]
+    fn test_liquidity_delta_hook_discriminator() {
+        assert_eq!(LIQUIDITY_DELTA_HOOK_DISCRIMINATOR, hash(b"global:on_liquidity_delta").to_bytes()[..8]);
+    }
+
+    /// Tests the Borsh layout of `LiquidityDelta`.
+    #[test]
+    fn test_liquidity_delta_layout() {
+        let cp_amm = Pubkey::new_unique();
+        let delta = LiquidityDelta {
+            cp_amm,
+            kind: LiquidityDeltaKind::Withdraw,
+            slot: 77,
+            base_liquidity_delta: -5,
+            quote_liquidity_delta: -10,
+            lp_tokens_supply_delta: -7,
+            base_liquidity: 95,
+            quote_liquidity: 190,
+            lp_tokens_supply: 133,
+        };
+
+        let mut expected = Vec::new();
+        expected.extend_from_slice(cp_amm.as_ref());
+        expected.push(2);
+        expected.extend_from_slice(&77u64.to_le_bytes());
+        expected.extend_from_slice(&(-5i128).to_le_bytes());
+        expected.extend_from_slice(&(-10i128).to_le_bytes());
+        expected.extend_from_slice(&(-7i128).to_le_bytes());
+        expected.extend_from_slice(&95u64.to_le_bytes());
+        expected.extend_from_slice(&190u64.to_le_bytes());
+        expected.extend_from_slice(&133u64.to_le_bytes());
+
+        assert_eq!(delta.try_to_vec().unwrap(), expected);
+        assert_eq!(LiquidityDelta::try_from_slice(&expected).unwrap(), delta);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,93 @@
+//! Reference interface for programs screening liquidity operations of the pools of an `AmmsConfig`.
+//!
+//! Before every provide and withdraw in a pool whose `AmmsConfig` has a screening program, the AMM
+//! invokes the program with the instruction data `SCREEN_LIQUIDITY_OPERATION_DISCRIMINATOR ++ borsh(LiquidityOperation)`,
+//! which matches an Anchor instruction declared as `screen_liquidity_operation(ctx, operation: LiquidityOperation)`.
+//! Returning an error rejects the operation, and a program approving every operation is a no-op.
+//!
+//! The screening program receives the following accounts:
+//! 0. `[]` The `CpAmm` account, holding the state before the operation.
+//! 1. `[]` The owner of the provided or withdrawn liquidity.
+//! 2. `..` Accounts passed to the AMM instruction after the screening program account, with
+//!    their writable flags preserved and signer privileges dropped.
+//!
+//! The screening program account is expected first in the remaining accounts of the AMM instruction.
+//! The liquidity subscriber program of the pool, if any, and its forwarded accounts follow it,
+//! so they are forwarded to the screening program as well.
+use anchor_lang::prelude::*;
+
+/// Instruction discriminator of the screening hook, the Anchor sighash of `screen_liquidity_operation`.
+pub const SCREEN_LIQUIDITY_OPERATION_DISCRIMINATOR: [u8; 8] = [58, 223, 159, 175, 183, 56, 213, 183];
+
+/// Kind of the screened liquidity operation.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LiquidityOperationKind {
+    Provide,
+    Withdraw,
+}
+
+/// Description of a liquidity operation sent to the screening program of an `AmmsConfig`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LiquidityOperation {
+    /// The `CpAmm` account of the operation.
+    pub cp_amm: Pubkey,
+
+    /// The kind of the operation.
+    pub kind: LiquidityOperationKind,
+
+    /// The signer of the AMM instruction.
+    pub signer: Pubkey,
+
+    /// The owner of the provided or withdrawn liquidity, differs from the signer for delegated operations.
+    pub owner: Pubkey,
+
+    /// The base amount received by the pool on provide or paid out on withdraw.
+    pub base_amount: u64,
+
+    /// The quote amount received by the pool on provide or paid out on withdraw.
+    pub quote_amount: u64,
+
+    /// The LP tokens minted on provide or burned on withdraw.
+    pub lp_tokens: u64,
+}
+
+#[cfg(test)]
+mod screening_hook_tests {
+    use anchor_lang::solana_program::hash::hash;
+    use super::*;
+
+    /// Tests that the hook discriminator matches the Anchor sighash of `screen_liquidity_operation`.
+    #[test]
+    fn test_screen_liquidity_operation_discriminator() {
+        assert_eq!(SCREEN_LIQUIDITY_OPERATION_DISCRIMINATOR, hash(b"global:screen_liquidity_operation").to_bytes()[..8]);
+    }
+
+    /// Tests the Borsh layout of `LiquidityOperation`.
+    #[test]
+    fn test_liquidity_operation_layout() {
+        let cp_amm = Pubkey::new_unique();
+        let signer = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        let operation = LiquidityOperation {
+            cp_amm,
+            kind: LiquidityOperationKind::Withdraw,
+            signer,
+            owner,
+            base_amount: 95,
+            quote_amount: 190,
+            lp_tokens: 133,
+        };
+
+        let mut expected = Vec::new();
+        expected.extend_from_slice(cp_amm.as_ref());
+        expected.push(1);
+        expected.extend_from_slice(signer.as_ref());
+        expected.extend_from_slice(owner.as_ref());
+        expected.extend_from_slice(&95u64.to_le_bytes());
+        expected.extend_from_slice(&190u64.to_le_bytes());
+        expected.extend_from_slice(&133u64.to_le_bytes());
+
+        assert_eq!(operation.try_to_vec().unwrap(), expected);
+        assert_eq!(LiquidityOperation::try_from_slice(&expected).unwrap(), operation);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,176 @@
+//! Deterministic off-chain simulations of `CpAmm` pools.
+//!
+//! Simulations run random traders and liquidity providers against a pool driven by the
+//! on-chain `CpAmmCalculate` math and report the pool state after every step as CSV.
+//! Randomness comes from a seeded `SplitMix64`, so the same `SimConfig` always
+//! produces the same output.
+mod rng;
+mod sim_pool;
+
+pub use rng::*;
+pub use sim_pool::*;
+
+use std::io::{self, Write};
+use crate::state::cp_amm::CpAmmCore;
+
+/// Parameters of a simulation run.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SimConfig {
+    /// Seed of the random generator.
+    pub seed: u64,
+    /// Number of simulated steps.
+    pub steps: u64,
+    /// Base liquidity the pool is launched with.
+    pub initial_base_liquidity: u64,
+    /// Quote liquidity the pool is launched with.
+    pub initial_quote_liquidity: u64,
+    /// Providers fee rate in basis points.
+    pub providers_fee_rate_basis_points: u16,
+    /// Protocol fee rate in basis points.
+    pub protocol_fee_rate_basis_points: u16,
+    /// Maximal swap amount as a share of the input side liquidity, in basis points.
+    pub max_swap_basis_points: u16,
+    /// Maximal provided or withdrawn liquidity as a share of the pool, in basis points.
+    pub max_liquidity_change_basis_points: u16,
+    /// Probability of a step being a liquidity operation instead of a swap, in basis points.
+    pub liquidity_operation_basis_points: u16,
+}
+
+impl Default for SimConfig {
+    fn default() -> Self {
+        Self {
+            seed: 0,
+            steps: 10_000,
+            initial_base_liquidity: 1_000_000_000_000,
+            initial_quote_liquidity: 250_000_000_000,
+            providers_fee_rate_basis_points: 25,
+            protocol_fee_rate_basis_points: 5,
+            max_swap_basis_points: 200,
+            max_liquidity_change_basis_points: 500,
+            liquidity_operation_basis_points: 1000,
+        }
+    }
+}
+
+/// Outcome of a single simulation step.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SimAction {
+    Launch,
+    SwapBaseToQuote,
+    SwapQuoteToBase,
+    Provide,
+    Withdraw,
+}
+
+impl SimAction {
+    /// Returns the CSV name of the action.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SimAction::Launch => "launch",
+            SimAction::SwapBaseToQuote => "swap_base_to_quote",
+            SimAction::SwapQuoteToBase => "swap_quote_to_base",
+            SimAction::Provide => "provide",
+            SimAction::Withdraw => "withdraw",
+        }
+    }
+}
+
+/// The CSV header matching `write_csv_row`.
+pub const CSV_HEADER: &str = "step,action,accepted,base_liquidity,quote_liquidity,lp_tokens_supply,protocol_base_fees,protocol_quote_fees,providers_base_fees,providers_quote_fees,lp_token_value_in_quote";
+
+/// Runs a simulation and writes one CSV row per step, including the launch as step `0`.
+///
+/// # Parameters
+/// - `config`: The simulation parameters.
+/// - `output`: The CSV destination.
+///
+/// # Errors
+/// - Returns an `io::Error` if the pool can't be launched or writing fails.
+pub fn run(config: &SimConfig, output: &mut impl Write) -> io::Result<()> {
+    let mut rng = SplitMix64::new(config.seed);
+    let mut pool = SimPool::launch(config.initial_base_liquidity, config.initial_quote_liquidity)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "initial liquidity is too small to launch the pool"))?;
+
+    writeln!(output, "{}", CSV_HEADER)?;
+    write_csv_row(output, 0, SimAction::Launch, true, &pool)?;
+
+    for step in 1..=config.steps {
+        let (action, accepted) = if rng.chance(config.liquidity_operation_basis_points) {
+            if rng.chance(5000) {
+                let base_amount = rng.share_of(pool.base_liquidity(), config.max_liquidity_change_basis_points);
+                (SimAction::Provide, pool.provide(base_amount))
+            } else {
+                let lp_tokens = rng.share_of(pool.lp_tokens_supply(), config.max_liquidity_change_basis_points);
+                (SimAction::Withdraw, pool.withdraw(lp_tokens))
+            }
+        } else {
+            let is_in_out = rng.chance(5000);
+            let input_liquidity = if is_in_out { pool.base_liquidity() } else { pool.quote_liquidity() };
+            let swap_amount = rng.share_of(input_liquidity, config.max_swap_basis_points);
+            let action = if is_in_out { SimAction::SwapBaseToQuote } else { SimAction::SwapQuoteToBase };
+            (action, pool.swap(swap_amount, is_in_out, config.providers_fee_rate_basis_points, config.protocol_fee_rate_basis_points))
+        };
+        write_csv_row(output, step, action, accepted, &pool)?;
+    }
+    Ok(())
+}
+
+/// Writes the pool state after a step as a CSV row.
+fn write_csv_row(output: &mut impl Write, step: u64, action: SimAction, accepted: bool, pool: &SimPool) -> io::Result<()> {
+    writeln!(
+        output,
+        "{},{},{},{},{},{},{},{},{},{},{:.9}",
+        step,
+        action.as_str(),
+        accepted,
+        pool.base_liquidity(),
+        pool.quote_liquidity(),
+        pool.lp_tokens_supply(),
+        pool.protocol_base_fees(),
+        pool.protocol_quote_fees(),
+        pool.providers_base_fees(),
+        pool.providers_quote_fees(),
+        pool.lp_token_value_in_quote(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Runs a short simulation into a string.
+    fn run_to_string(config: &SimConfig) -> String {
+        let mut output = Vec::new();
+        run(config, &mut output).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    /// Tests that the same config always produces the same output.
+    #[test]
+    fn test_run_is_deterministic() {
+        let config = SimConfig { steps: 500, ..SimConfig::default() };
+        let other_seed_config = SimConfig { seed: 1, ..config };
+
+        let output = run_to_string(&config);
+
+        assert_eq!(output, run_to_string(&config));
+        assert_ne!(output, run_to_string(&other_seed_config));
+        assert_eq!(output.lines().count(), 502);
+        assert_eq!(output.lines().next(), Some(CSV_HEADER));
+    }
+
+    /// Tests that swap fees grow the LP token value over a long run.
+    #[test]
+    fn test_run_accumulates_fees() {
+        let config = SimConfig { steps: 2000, liquidity_operation_basis_points: 0, ..SimConfig::default() };
+        let mut output = Vec::new();
+        run(&config, &mut output).unwrap();
+        let output = String::from_utf8(output).unwrap();
+
+        let fees_of = |line: &str| -> Vec<u64> {
+            line.split(',').skip(6).take(4).map(|value| value.parse().unwrap()).collect()
+        };
+        let last_line = output.lines().last().unwrap();
+        assert!(fees_of(last_line).iter().all(|fees| *fees > 0));
+    }
+}
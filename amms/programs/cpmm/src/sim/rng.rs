@@ -0,0 +1,67 @@
+/// A small deterministic pseudo-random generator.
+///
+/// Implements SplitMix64, which needs no entropy source and produces the same
+/// sequence on every platform for the same seed.
+#[derive(Debug, Clone)]
+pub struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    /// Creates a generator from a seed.
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// Returns the next pseudo-random `u64`.
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a value in `[0, bound)`, or `0` if `bound` is `0`.
+    pub fn below(&mut self, bound: u64) -> u64 {
+        if bound == 0 {
+            return 0;
+        }
+        self.next_u64() % bound
+    }
+
+    /// Returns `true` with the given probability in basis points.
+    pub fn chance(&mut self, basis_points: u16) -> bool {
+        self.below(10000) < basis_points as u64
+    }
+
+    /// Returns a value in `[1, value * basis_points / 10000]`.
+    pub fn share_of(&mut self, value: u64, basis_points: u16) -> u64 {
+        let max_share = (value as u128 * basis_points as u128 / 10000) as u64;
+        self.below(max_share.max(1)) + 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tests the generator against reference SplitMix64 outputs.
+    #[test]
+    fn test_split_mix_64_reference_values() {
+        let mut rng = SplitMix64::new(1234567);
+        assert_eq!(rng.next_u64(), 6457827717110365317);
+        assert_eq!(rng.next_u64(), 3203168211198807973);
+    }
+
+    /// Tests the bounds of `share_of`.
+    #[test]
+    fn test_share_of_bounds() {
+        let mut rng = SplitMix64::new(7);
+        for _ in 0..1000 {
+            let share = rng.share_of(10_000, 100);
+            assert!((1..=100).contains(&share));
+        }
+        assert_eq!(rng.share_of(0, 100), 1);
+    }
+}
@@ -0,0 +1,191 @@
+use cpmm_math::cp_amm::{ADJUST_LIQUIDITY_RATIO_SQRT_TOLERANCE, MIN_LIQUIDITY, SWAP_CONSTANT_PRODUCT_SQRT_TOLERANCE};
+use crate::state::cp_amm::{CpAmmCalculate, CpAmmCore};
+use crate::utils::math::Q64_128;
+
+/// An in-memory pool updated with the same math as `CpAmm`.
+///
+/// Operations mirror the validations of the `CpAmm` payload methods with the default `AmmsConfig`
+/// tolerances and leave the pool unchanged when a validation fails, so rejected operations can be
+/// counted by the caller.
+#[derive(Debug, Clone)]
+pub struct SimPool {
+    base_liquidity: u64,
+    quote_liquidity: u64,
+    constant_product_sqrt: Q64_128,
+    base_quote_ratio_sqrt: Q64_128,
+    lp_tokens_supply: u64,
+    initial_locked_liquidity: u64,
+    protocol_base_fees: u64,
+    protocol_quote_fees: u64,
+    providers_base_fees: u64,
+    providers_quote_fees: u64,
+}
+
+impl CpAmmCore for SimPool {
+    fn constant_product_sqrt(&self) -> Q64_128 {
+        self.constant_product_sqrt
+    }
+
+    fn base_quote_ratio_sqrt(&self) -> Q64_128 {
+        self.base_quote_ratio_sqrt
+    }
+
+    fn base_liquidity(&self) -> u64 {
+        self.base_liquidity
+    }
+
+    fn quote_liquidity(&self) -> u64 {
+        self.quote_liquidity
+    }
+
+    fn lp_tokens_supply(&self) -> u64 {
+        self.lp_tokens_supply
+    }
+}
+
+impl SimPool {
+    /// Launches a pool with the initial liquidity.
+    ///
+    /// # Returns
+    /// - `Some(SimPool)` if the liquidity is enough to launch a `CpAmm`.
+    /// - `None` otherwise.
+    pub fn launch(base_liquidity: u64, quote_liquidity: u64) -> Option<Self> {
+        let constant_product_sqrt = Self::calculate_constant_product_sqrt(base_liquidity, quote_liquidity)?;
+        let (lp_tokens_supply, initial_locked_liquidity) = Self::calculate_launch_lp_tokens(constant_product_sqrt, Self::INITIAL_LOCKED_LP_TOKENS).ok()?;
+        let base_quote_ratio_sqrt = Self::calculate_base_quote_ratio_sqrt(base_liquidity, quote_liquidity)?;
+        Some(Self {
+            base_liquidity,
+            quote_liquidity,
+            constant_product_sqrt,
+            base_quote_ratio_sqrt,
+            lp_tokens_supply,
+            initial_locked_liquidity,
+            protocol_base_fees: 0,
+            protocol_quote_fees: 0,
+            providers_base_fees: 0,
+            providers_quote_fees: 0,
+        })
+    }
+
+    /// Swaps `swap_amount` of input tokens, returns `false` if the swap is rejected.
+    pub fn swap(&mut self, swap_amount: u64, is_in_out: bool, providers_fee_rate_basis_points: u16, protocol_fee_rate_basis_points: u16) -> bool {
+        let providers_fee_amount = Self::calculate_swap_fee_amount(swap_amount, providers_fee_rate_basis_points);
+        let protocol_fee_amount = Self::calculate_swap_fee_amount(swap_amount, protocol_fee_rate_basis_points);
+        if (providers_fee_amount == 0 && providers_fee_rate_basis_points > 0) || (protocol_fee_amount == 0 && protocol_fee_rate_basis_points > 0) {
+            return false;
+        }
+        let Some(amount_after_fees) = swap_amount.checked_sub(providers_fee_amount + protocol_fee_amount) else {
+            return false;
+        };
+        let Some((new_base_liquidity, new_quote_liquidity)) = self.calculate_afterswap_liquidity(amount_after_fees, is_in_out) else {
+            return false;
+        };
+        let swap_result = if is_in_out {
+            self.quote_liquidity.saturating_sub(new_quote_liquidity)
+        } else {
+            self.base_liquidity.saturating_sub(new_base_liquidity)
+        };
+        if swap_result == 0 || self.validate_swap_constant_product(new_base_liquidity, new_quote_liquidity, SWAP_CONSTANT_PRODUCT_SQRT_TOLERANCE).is_err() {
+            return false;
+        }
+
+        if is_in_out {
+            self.base_liquidity = new_base_liquidity + providers_fee_amount;
+            self.quote_liquidity = new_quote_liquidity;
+            self.protocol_base_fees += protocol_fee_amount;
+            self.providers_base_fees += providers_fee_amount;
+        } else {
+            self.base_liquidity = new_base_liquidity;
+            self.quote_liquidity = new_quote_liquidity + providers_fee_amount;
+            self.protocol_quote_fees += protocol_fee_amount;
+            self.providers_quote_fees += providers_fee_amount;
+        }
+        self.update_sqrt_values();
+        true
+    }
+
+    /// Provides `base_amount` and the matching quote amount, returns `false` if rejected.
+    pub fn provide(&mut self, base_amount: u64) -> bool {
+        let quote_amount = (base_amount as u128 * self.quote_liquidity as u128 / self.base_liquidity as u128) as u64;
+        if quote_amount == 0 {
+            return false;
+        }
+        let new_base_liquidity = self.base_liquidity + base_amount;
+        let new_quote_liquidity = self.quote_liquidity + quote_amount;
+        let Ok(base_quote_ratio_sqrt) = self.validate_and_calculate_liquidity_ratio(new_base_liquidity, new_quote_liquidity, ADJUST_LIQUIDITY_RATIO_SQRT_TOLERANCE) else {
+            return false;
+        };
+        let Some(constant_product_sqrt) = Self::calculate_constant_product_sqrt(new_base_liquidity, new_quote_liquidity) else {
+            return false;
+        };
+        let Some(lp_tokens_to_mint) = self.calculate_lp_mint_for_provided_liquidity(constant_product_sqrt) else {
+            return false;
+        };
+
+        self.base_liquidity = new_base_liquidity;
+        self.quote_liquidity = new_quote_liquidity;
+        self.base_quote_ratio_sqrt = base_quote_ratio_sqrt;
+        self.constant_product_sqrt = constant_product_sqrt;
+        self.lp_tokens_supply += lp_tokens_to_mint;
+        true
+    }
+
+    /// Withdraws liquidity for `lp_tokens`, returns `false` if rejected.
+    pub fn withdraw(&mut self, lp_tokens: u64) -> bool {
+        let Some(lp_tokens_left_supply) = self.lp_tokens_supply.checked_sub(lp_tokens) else {
+            return false;
+        };
+        let Some((base_withdraw, quote_withdraw)) = self.calculate_liquidity_from_share(lp_tokens) else {
+            return false;
+        };
+        let (Some(new_base_liquidity), Some(new_quote_liquidity)) = (self.base_liquidity.checked_sub(base_withdraw), self.quote_liquidity.checked_sub(quote_withdraw)) else {
+            return false;
+        };
+        let drops_below_minimum = |liquidity: u64, new_liquidity: u64| liquidity >= MIN_LIQUIDITY && new_liquidity < MIN_LIQUIDITY;
+        if (drops_below_minimum(self.base_liquidity, new_base_liquidity) || drops_below_minimum(self.quote_liquidity, new_quote_liquidity)) &&
+            lp_tokens_left_supply > self.initial_locked_liquidity {
+            return false;
+        }
+        let Ok(base_quote_ratio_sqrt) = self.validate_and_calculate_liquidity_ratio(new_base_liquidity, new_quote_liquidity, ADJUST_LIQUIDITY_RATIO_SQRT_TOLERANCE) else {
+            return false;
+        };
+
+        self.base_liquidity = new_base_liquidity;
+        self.quote_liquidity = new_quote_liquidity;
+        self.base_quote_ratio_sqrt = base_quote_ratio_sqrt;
+        self.constant_product_sqrt = Self::calculate_constant_product_sqrt(new_base_liquidity, new_quote_liquidity).unwrap();
+        self.lp_tokens_supply = lp_tokens_left_supply;
+        true
+    }
+
+    /// Returns the protocol fees collected in base tokens.
+    pub fn protocol_base_fees(&self) -> u64 {
+        self.protocol_base_fees
+    }
+
+    /// Returns the protocol fees collected in quote tokens.
+    pub fn protocol_quote_fees(&self) -> u64 {
+        self.protocol_quote_fees
+    }
+
+    /// Returns the providers fees added to base liquidity.
+    pub fn providers_base_fees(&self) -> u64 {
+        self.providers_base_fees
+    }
+
+    /// Returns the providers fees added to quote liquidity.
+    pub fn providers_quote_fees(&self) -> u64 {
+        self.providers_quote_fees
+    }
+
+    /// Returns the value of a single LP token in quote tokens at the current pool price.
+    pub fn lp_token_value_in_quote(&self) -> f64 {
+        2.0 * self.quote_liquidity as f64 / self.lp_tokens_supply as f64
+    }
+
+    /// Recalculates the square roots after a swap, like `CpAmm::swap` deriving the constant product from the ratio.
+    fn update_sqrt_values(&mut self) {
+        self.base_quote_ratio_sqrt = Self::calculate_base_quote_ratio_sqrt(self.base_liquidity, self.quote_liquidity).unwrap();
+        self.constant_product_sqrt = self.calculate_normalized_constant_product_sqrt_from_ratio_sqrt(self.base_quote_ratio_sqrt, self.quote_liquidity).unwrap();
+    }
+}
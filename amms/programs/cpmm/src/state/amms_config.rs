@@ -1,6 +1,21 @@
-use anchor_lang::{account, InitSpace};
+use anchor_lang::{account, Discriminator, InitSpace};
 use anchor_lang::prelude::*;
 use crate::error::ErrorCode;
+use crate::utils::math::Q64_128;
+use crate::constants::CP_AMM_INITIALIZE_PRICE_IN_LAMPORTS;
+use super::{validate_bounded_fee_rates, validate_fee_rates, FeeBounds, FeeRamp, FeeSettlement, LoyaltyBoost, PoolCreationPricing, PoolIndex, VersionedAccount};
+
+/// Origin of the swaps accepted in the pools of a config while a swap origin policy is in force.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq, InitSpace)]
+pub enum SwapOriginPolicy {
+    /// Swaps are accepted from any origin.
+    #[default]
+    Unrestricted,
+    /// Only swaps invoked directly by a top-level instruction of the transaction are accepted.
+    DirectOnly,
+    /// Only swaps invoked via CPI by another program are accepted.
+    CpiOnly,
+}
 
 /// Represents a configuration object for managing fees and authorities in AMMs.
 ///
@@ -23,12 +38,152 @@ pub struct AmmsConfig {
 
     /// The protocol's fee rate, measured in basis points (1 basis point = 0.01%).
     protocol_fee_rate_basis_points: u16, // 2 bytes
+
+    /// The share of a pool's LP tokens supply that may be withdrawn per slot while the pool
+    /// is flagged as incident-limited, measured in basis points. Zero disables the limit.
+    incident_withdraw_limit_basis_points: u16, // 2 bytes
+
+    /// The share of collected protocol fees paid to whoever triggers the collection,
+    /// measured in basis points. Zero disables the bounty.
+    keeper_bounty_basis_points: u16, // 2 bytes
+
+    /// The minimal amount of LP tokens a single provide must mint. Zero disables the check.
+    min_provide_lp_tokens: u64, // 8 bytes
+
+    /// The minimal amount of LP tokens a single withdraw must redeem, unless it redeems the whole balance of
+    /// the LP account. Zero disables the check.
+    min_withdraw_lp_tokens: u64, // 8 bytes
+
+    /// The minimal decimals a tradable mint must have to be used in a pool.
+    min_mint_decimals: u8, // 1 byte
+
+    /// The maximal decimals a tradable mint may have to be used in a pool.
+    max_mint_decimals: u8, // 1 byte
+
+    /// Whether the fee authority may rebalance protocol-owned liquidity through pools
+    /// without paying protocol fees.
+    is_protocol_rebalance_enabled: bool, // 1 byte
+
+    /// Optional bounds on the fee rates the config may be updated to.
+    fee_bounds: Option<FeeBounds>, // 7 bytes
+
+    /// Optional ramp from an earlier providers fee rate to `providers_fee_rate_basis_points`.
+    providers_fee_ramp: Option<FeeRamp>, // 19 bytes
+
+    /// Optional authority of the lending adapter allowed to borrow idle liquidity from pools
+    /// with lend-out enabled. It is usually a PDA the adapter program signs with via CPI.
+    lending_adapter: Option<Pubkey>, // 33 bytes
+
+    /// The share of each side of a pool's liquidity that may be lent out, measured in basis points.
+    max_lend_out_basis_points: u16, // 2 bytes
+
+    /// The annual interest rate charged on lent out liquidity, measured in basis points.
+    lend_out_interest_rate_basis_points: u16, // 2 bytes
+
+    /// The number of pools created under this configuration, also the position
+    /// of the next pool in the config's `PoolIndex` chain.
+    pools_count: u64, // 8 bytes
+
+    /// Optional loyalty multiplier for liquidity held in the config's pools beyond configured durations.
+    loyalty_boost: Option<LoyaltyBoost>, // 11 bytes
+
+    /// The number of decimal orders of magnitude the launch price of a pool, quote per base
+    /// in whole tokens, may deviate from 1 in either direction. Zero disables the bound.
+    max_launch_price_orders: u8, // 1 byte
+
+    /// The tolerance for changes in the constant product square root of the config's pools during swaps,
+    /// measured in parts per billion (1000 = 0.0001%).
+    swap_constant_product_tolerance_ppb: u32, // 4 bytes
+
+    /// The tolerance for changes in the liquidity ratio square root of the config's pools after provides
+    /// and withdrawals, measured in parts per billion (100 = 0.00001%).
+    adjust_liquidity_ratio_tolerance_ppb: u32, // 4 bytes
+
+    /// Optional settlement of the protocol fees collected from the config's pools in a single mint.
+    fee_settlement: Option<FeeSettlement>, // 36 bytes
+
+    /// The maximal share of the providers fee the creators of the config's pools may route
+    /// to themselves, measured in basis points. Zero disables the creator fee share.
+    max_creator_fee_share_basis_points: u16, // 2 bytes
+
+    /// The number of epochs after which a never launched pool of the config may be closed by anyone.
+    /// Zero disables the closing.
+    stale_pool_close_epochs: u16, // 2 bytes
+
+    /// The share of a closed stale pool's rent paid to whoever closes it, measured in basis points.
+    /// The rest is refunded to the pool creator.
+    stale_pool_closer_share_basis_points: u16, // 2 bytes
+
+    /// Optional program screening provides and withdrawals in the config's pools via CPI,
+    /// e.g. for compliance checks. It may reject an operation by failing.
+    screening_program: Option<Pubkey>, // 33 bytes
+
+    /// Whether the config has `MintEquivalences`, which must then be checked on every pool creation.
+    has_mint_equivalences: bool, // 1 byte
+
+    /// Optional pricing of `CpAmm` creation in the config, used instead of the deployment's
+    /// `CP_AMM_INITIALIZE_PRICE_IN_LAMPORTS`.
+    pool_creation_pricing: Option<PoolCreationPricing>, // 29 bytes
+
+    /// The share of each side of a pool's liquidity its accrued protocol fees may reach, measured in
+    /// basis points, before provides require the fees to be collected first. Zero disables the cap.
+    protocol_fees_cap_basis_points: u16, // 2 bytes
+
+    /// Whether pool creation in the config is restricted to creators holding a `PoolCreatorPermit`
+    /// issued by the configs authority. Configs without the allow-list are permissionless.
+    is_pool_creation_allow_listed: bool, // 1 byte
+
+    /// The share of a pool's LP tokens, measured in basis points, a single provide may mint before
+    /// the pool raises its concentration flag. Zero disables the flag and the guard.
+    lp_concentration_share_basis_points: u16, // 2 bytes
+
+    /// The number of epochs since a pool's initialization during which provides exceeding the LP
+    /// concentration share are rejected. Zero only raises the flag.
+    lp_concentration_guard_epochs: u16, // 2 bytes
+
+    /// The origin of the swaps accepted in the config's pools until `swap_origin_policy_until_slot`, an
+    /// incident mitigation between pausing the pools and normal operation.
+    swap_origin_policy: SwapOriginPolicy, // 1 byte
+
+    /// The slot at which the swap origin policy expires.
+    swap_origin_policy_until_slot: u64, // 8 bytes
+
+    /// The version of the account data layout, see `VersionedAccount`.
+    layout_version: u8, // 1 byte
 }
 
 impl AmmsConfig {
     /// The seed used to derive the account's PDA.
     pub const SEED: &'static [u8] = b"amms_config";
 
+    /// The maximum keeper bounty, measured in basis points (1000 = 10%).
+    pub const MAX_KEEPER_BOUNTY_BASIS_POINTS: u16 = 1000;
+
+    /// The maximum share of a pool's liquidity that may be lent out, measured in basis points (5000 = 50%).
+    pub const MAX_LEND_OUT_BASIS_POINTS: u16 = 5000;
+
+    /// The maximum share of the providers fee routed to pool creators, measured in basis points (5000 = 50%).
+    pub const MAX_CREATOR_FEE_SHARE_BASIS_POINTS: u16 = 5000;
+
+    /// The default swap constant product tolerance, matching `cpmm_math::cp_amm::SWAP_CONSTANT_PRODUCT_SQRT_TOLERANCE`.
+    pub const DEFAULT_SWAP_CONSTANT_PRODUCT_TOLERANCE_PPB: u32 = 1_000;
+
+    /// The bounds of the swap constant product tolerance, measured in parts per billion.
+    pub const SWAP_CONSTANT_PRODUCT_TOLERANCE_PPB_BOUNDS: (u32, u32) = (100, 100_000);
+
+    /// The default liquidity ratio tolerance, matching `cpmm_math::cp_amm::ADJUST_LIQUIDITY_RATIO_SQRT_TOLERANCE`.
+    pub const DEFAULT_ADJUST_LIQUIDITY_RATIO_TOLERANCE_PPB: u32 = 100;
+
+    /// The bounds of the liquidity ratio tolerance, measured in parts per billion.
+    pub const ADJUST_LIQUIDITY_RATIO_TOLERANCE_PPB_BOUNDS: (u32, u32) = (10, 10_000);
+
+    /// The maximal duration of a swap origin policy, about a day of slots.
+    pub const MAX_SWAP_ORIGIN_POLICY_SLOTS: u64 = 216_000;
+
+    /// Size of accounts written before the fee rates were followed by further settings, including the
+    /// Anchor discriminator.
+    pub const INITIAL_SPACE: usize = 8 + 45;
+
     /// Initializes the `AmmsConfig` with the provided parameters.
     ///
     /// # Parameters
@@ -42,17 +197,68 @@ impl AmmsConfig {
     /// - Returns `ErrorCode::ConfigFeeRateExceeded` if the sum of `protocol_fee_rate_basis_points`
     ///   and `providers_fee_rate_basis_points` exceeds 10,000 (100%).
     pub(crate) fn initialize(&mut self, fee_authority: Pubkey, protocol_fee_rate_basis_points: u16, providers_fee_rate_basis_points: u16, id: u64, bump: u8) -> Result<()> {
-        require!(providers_fee_rate_basis_points + protocol_fee_rate_basis_points <= 10000, ErrorCode::ConfigFeeRateExceeded);
-        
+        validate_fee_rates(providers_fee_rate_basis_points, protocol_fee_rate_basis_points)?;
+
         self.bump = bump;
         self.id = id;
         self.protocol_fee_rate_basis_points = protocol_fee_rate_basis_points;
         self.providers_fee_rate_basis_points = providers_fee_rate_basis_points;
         self.fee_authority = fee_authority;
+        self.min_mint_decimals = 0;
+        self.max_mint_decimals = u8::MAX;
+        self.swap_constant_product_tolerance_ppb = Self::DEFAULT_SWAP_CONSTANT_PRODUCT_TOLERANCE_PPB;
+        self.adjust_liquidity_ratio_tolerance_ppb = Self::DEFAULT_ADJUST_LIQUIDITY_RATIO_TOLERANCE_PPB;
+        self.layout_version = Self::LAYOUT_VERSION;
         
         Ok(())
     }
 
+    /// Validates that an `AmmsConfig` account written by an older program build can be upgraded to the current layout.
+    ///
+    /// New fields are appended to the end of the layout, so an account is upgraded by growing it to the
+    /// current size with zero-initialized bytes and completing it with `upgrade_layout`.
+    ///
+    /// # Parameters
+    /// - `data`: The raw account data, including the Anchor discriminator.
+    ///
+    /// # Returns
+    /// - The size of the account in the current layout, including the Anchor discriminator.
+    ///
+    /// # Errors
+    /// - `AccountDiscriminatorMismatch` if the data isn't an `AmmsConfig` account.
+    /// - `AccountVersionMismatch` if the account was written by a newer program build or its size matches no layout.
+    /// - `ConfigLayoutUpToDate` if the account already has the current size and layout version.
+    pub fn validate_layout_upgrade(data: &[u8]) -> Result<usize> {
+        let space = 8 + Self::INIT_SPACE;
+        require!(data.len() > 8 && data[..8] == Self::DISCRIMINATOR, anchor_lang::error::ErrorCode::AccountDiscriminatorMismatch);
+        if data.len() == Self::INITIAL_SPACE {
+            return Ok(space);
+        }
+        require!(data.len() == space, ErrorCode::AccountVersionMismatch);
+        // Options are serialized compactly, so the version has no fixed offset
+        let layout_version = Self::try_deserialize(&mut &data[..])?.layout_version;
+        require!(layout_version <= Self::LAYOUT_VERSION, ErrorCode::AccountVersionMismatch);
+        require!(layout_version < Self::LAYOUT_VERSION, ErrorCode::ConfigLayoutUpToDate);
+        Ok(space)
+    }
+
+    /// Completes an `AmmsConfig` account grown to the current size by `validate_layout_upgrade`.
+    ///
+    /// Stores the current layout version and, for accounts of the initial layout, the defaults set by
+    /// `initialize` for the fields whose zero value would reject every pool: the mint decimals range
+    /// and the invariant tolerances.
+    ///
+    /// # Parameters
+    /// - `original_len`: The size of the account before it was grown.
+    pub(crate) fn upgrade_layout(&mut self, original_len: usize) {
+        if original_len == Self::INITIAL_SPACE {
+            self.max_mint_decimals = u8::MAX;
+            self.swap_constant_product_tolerance_ppb = Self::DEFAULT_SWAP_CONSTANT_PRODUCT_TOLERANCE_PPB;
+            self.adjust_liquidity_ratio_tolerance_ppb = Self::DEFAULT_ADJUST_LIQUIDITY_RATIO_TOLERANCE_PPB;
+        }
+        self.layout_version = Self::LAYOUT_VERSION;
+    }
+
     /// Updates the `fee_authority` field with a new authority public key.
     ///
     /// # Parameters
@@ -62,10 +268,11 @@ impl AmmsConfig {
     }
 
 
-    /// Updates the fee rate for liquidity providers.
+    /// Updates the fee rate for liquidity providers immediately, cancelling any scheduled ramp.
     ///
     /// Ensures that the sum of the updated `providers_fee_rate_basis_points` and
-    /// the existing `protocol_fee_rate_basis_points` does not exceed 10,000 basis points (100%).
+    /// the existing `protocol_fee_rate_basis_points` does not exceed 10,000 basis points (100%)
+    /// and that the rates fit into the fee bounds, if set.
     ///
     /// # Parameters
     /// - `new_providers_fee_rate_basis_points`: The updated fee rate for liquidity providers,
@@ -73,31 +280,378 @@ impl AmmsConfig {
     ///
     /// # Errors
     /// - Returns `ErrorCode::ConfigFeeRateExceeded` if the total fee rate exceeds 100%.
+    /// - Returns `ErrorCode::ConfigFeeRateOutOfBounds` if the rate is outside of the fee bounds.
     pub(crate) fn update_providers_fee_rate(&mut self, new_providers_fee_rate_basis_points: u16) -> Result<()> {
-        require!(
-            new_providers_fee_rate_basis_points + self.protocol_fee_rate_basis_points <= 10000,
-            ErrorCode::ConfigFeeRateExceeded
-        );
+        validate_bounded_fee_rates(self.fee_bounds.as_ref(), new_providers_fee_rate_basis_points, self.protocol_fee_rate_basis_points)?;
         self.providers_fee_rate_basis_points = new_providers_fee_rate_basis_points;
+        self.providers_fee_ramp = None;
+        Ok(())
+    }
+
+    /// Schedules a linear ramp of the providers fee rate from its current effective value to a target.
+    ///
+    /// The target is stored as `providers_fee_rate_basis_points` right away, while swaps apply
+    /// the interpolated rate until the ramp ends. Scheduling over an unfinished ramp starts
+    /// the new one from the rate effective at `current_slot`.
+    ///
+    /// # Parameters
+    /// - `current_slot`: The slot at which the ramp starts.
+    /// - `target_providers_fee_rate_basis_points`: The providers fee rate reached at the end of the ramp.
+    /// - `duration_slots`: The number of slots it takes to reach the target rate.
+    ///
+    /// # Errors
+    /// - Returns `ErrorCode::ConfigFeeRateExceeded` if the total target fee rate exceeds 100%.
+    /// - Returns `ErrorCode::ConfigFeeRateOutOfBounds` if the target rate is outside of the fee bounds.
+    /// - Returns `ErrorCode::ConfigFeeRampDurationIsZero` if `duration_slots` is zero.
+    pub(crate) fn schedule_providers_fee_ramp(&mut self, current_slot: u64, target_providers_fee_rate_basis_points: u16, duration_slots: u64) -> Result<()> {
+        validate_bounded_fee_rates(self.fee_bounds.as_ref(), target_providers_fee_rate_basis_points, self.protocol_fee_rate_basis_points)?;
+        let start_fee_rate_basis_points = self.providers_fee_rate_basis_points_at(current_slot);
+        self.providers_fee_ramp = Some(FeeRamp::try_new(current_slot, duration_slots, start_fee_rate_basis_points)?);
+        self.providers_fee_rate_basis_points = target_providers_fee_rate_basis_points;
         Ok(())
     }
 
     /// Updates the protocol fee rate.
     ///
     /// Ensures that the sum of the updated `protocol_fee_rate_basis_points` and
-    /// the existing `providers_fee_rate_basis_points` does not exceed 10,000 basis points (100%).
+    /// the existing `providers_fee_rate_basis_points` does not exceed 10,000 basis points (100%)
+    /// and that the rates fit into the fee bounds, if set.
     ///
     /// # Parameters
     /// - `new_protocol_fee_rate_basis_points`: The updated protocol fee rate, measured in basis points.
     ///
     /// # Errors
     /// - Returns `ErrorCode::ConfigFeeRateExceeded` if the total fee rate exceeds 100%.
+    /// - Returns `ErrorCode::ConfigFeeRateOutOfBounds` if the rate is outside of the fee bounds.
     pub(crate) fn update_protocol_fee_rate(&mut self, new_protocol_fee_rate_basis_points: u16) -> Result<()> {
+        validate_bounded_fee_rates(self.fee_bounds.as_ref(), self.providers_fee_rate_basis_points, new_protocol_fee_rate_basis_points)?;
+        // A ramp passes through every rate up to its start, so the total must hold there as well
+        if let Some(providers_fee_ramp) = self.providers_fee_ramp.as_ref() {
+            validate_fee_rates(providers_fee_ramp.start_fee_rate_basis_points(), new_protocol_fee_rate_basis_points)?;
+        }
+        self.protocol_fee_rate_basis_points = new_protocol_fee_rate_basis_points;
+        Ok(())
+    }
+
+    /// Sets or removes the bounds on the fee rates the config may be updated to.
+    ///
+    /// # Parameters
+    /// - `fee_bounds`: The new fee bounds, `None` removes the bounds.
+    ///
+    /// # Errors
+    /// - Returns `ErrorCode::ConfigFeeRateOutOfBounds` if the current fee rates are outside of the new bounds.
+    pub(crate) fn update_fee_bounds(&mut self, fee_bounds: Option<FeeBounds>) -> Result<()> {
+        validate_bounded_fee_rates(fee_bounds.as_ref(), self.providers_fee_rate_basis_points, self.protocol_fee_rate_basis_points)?;
+        self.fee_bounds = fee_bounds;
+        Ok(())
+    }
+
+    /// Updates the per-slot withdraw limit applied to incident-limited pools.
+    ///
+    /// # Parameters
+    /// - `new_incident_withdraw_limit_basis_points`: The share of a pool's LP tokens supply
+    ///   that may be withdrawn per slot, measured in basis points. Zero disables the limit.
+    ///
+    /// # Errors
+    /// - Returns `ErrorCode::ConfigIncidentWithdrawLimitExceeded` if the limit exceeds 100%.
+    pub(crate) fn update_incident_withdraw_limit(&mut self, new_incident_withdraw_limit_basis_points: u16) -> Result<()> {
         require!(
-            new_protocol_fee_rate_basis_points + self.providers_fee_rate_basis_points <= 10000,
-            ErrorCode::ConfigFeeRateExceeded
+            new_incident_withdraw_limit_basis_points <= 10000,
+            ErrorCode::ConfigIncidentWithdrawLimitExceeded
+        );
+        self.incident_withdraw_limit_basis_points = new_incident_withdraw_limit_basis_points;
+        Ok(())
+    }
+
+    /// Updates the bounty paid for triggering protocol fees collection.
+    ///
+    /// # Parameters
+    /// - `new_keeper_bounty_basis_points`: The share of collected protocol fees paid to the
+    ///   collection caller, measured in basis points. Zero disables the bounty.
+    ///
+    /// # Errors
+    /// - Returns `ErrorCode::ConfigKeeperBountyExceeded` if the bounty exceeds `MAX_KEEPER_BOUNTY_BASIS_POINTS`.
+    pub(crate) fn update_keeper_bounty(&mut self, new_keeper_bounty_basis_points: u16) -> Result<()> {
+        require!(
+            new_keeper_bounty_basis_points <= Self::MAX_KEEPER_BOUNTY_BASIS_POINTS,
+            ErrorCode::ConfigKeeperBountyExceeded
+        );
+        self.keeper_bounty_basis_points = new_keeper_bounty_basis_points;
+        Ok(())
+    }
+
+    /// Updates the cap on the protocol fees a pool may accrue before provides require their collection.
+    ///
+    /// # Parameters
+    /// - `new_protocol_fees_cap_basis_points`: The share of each side of a pool's liquidity its protocol
+    ///   fees may reach, measured in basis points. Zero disables the cap.
+    ///
+    /// # Errors
+    /// - Returns `ErrorCode::ConfigProtocolFeesCapExceeded` if the cap exceeds 10000 basis points (100%).
+    pub(crate) fn update_protocol_fees_cap(&mut self, new_protocol_fees_cap_basis_points: u16) -> Result<()> {
+        require!(
+            new_protocol_fees_cap_basis_points <= 10000,
+            ErrorCode::ConfigProtocolFeesCapExceeded
+        );
+        self.protocol_fees_cap_basis_points = new_protocol_fees_cap_basis_points;
+        Ok(())
+    }
+
+    /// Puts a swap origin policy in force for `policy_slots` from the current slot.
+    ///
+    /// # Parameters
+    /// - `swap_origin_policy`: The origin of the accepted swaps, `Unrestricted` lifts the policy.
+    /// - `policy_slots`: The number of slots the policy stays in force, zero lifts the policy.
+    /// - `current_slot`: The current slot.
+    ///
+    /// # Errors
+    /// - Returns `ErrorCode::SwapOriginPolicySlotsExceeded` if the policy exceeds `MAX_SWAP_ORIGIN_POLICY_SLOTS`.
+    pub(crate) fn update_swap_origin_policy(&mut self, swap_origin_policy: SwapOriginPolicy, policy_slots: u64, current_slot: u64) -> Result<()> {
+        require!(policy_slots <= Self::MAX_SWAP_ORIGIN_POLICY_SLOTS, ErrorCode::SwapOriginPolicySlotsExceeded);
+        if swap_origin_policy == SwapOriginPolicy::Unrestricted || policy_slots == 0 {
+            self.swap_origin_policy = SwapOriginPolicy::Unrestricted;
+            self.swap_origin_policy_until_slot = 0;
+        } else {
+            self.swap_origin_policy = swap_origin_policy;
+            self.swap_origin_policy_until_slot = current_slot.saturating_add(policy_slots);
+        }
+        Ok(())
+    }
+
+    /// Updates the minimal LP tokens amounts accepted per provide and per withdraw.
+    ///
+    /// # Parameters
+    /// - `new_min_provide_lp_tokens`: The minimal amount of LP tokens a provide must mint.
+    /// - `new_min_withdraw_lp_tokens`: The minimal amount of LP tokens a withdraw must redeem.
+    pub(crate) fn update_lp_tokens_limits(&mut self, new_min_provide_lp_tokens: u64, new_min_withdraw_lp_tokens: u64) {
+        self.min_provide_lp_tokens = new_min_provide_lp_tokens;
+        self.min_withdraw_lp_tokens = new_min_withdraw_lp_tokens;
+    }
+
+    /// Updates the range of decimals accepted for tradable mints.
+    ///
+    /// # Parameters
+    /// - `new_min_mint_decimals`: The minimal decimals a tradable mint must have.
+    /// - `new_max_mint_decimals`: The maximal decimals a tradable mint may have.
+    ///
+    /// # Errors
+    /// - Returns `ErrorCode::ConfigMintDecimalsRangeInvalid` if the minimum exceeds the maximum.
+    pub(crate) fn update_mint_decimals_range(&mut self, new_min_mint_decimals: u8, new_max_mint_decimals: u8) -> Result<()> {
+        require!(
+            new_min_mint_decimals <= new_max_mint_decimals,
+            ErrorCode::ConfigMintDecimalsRangeInvalid
+        );
+        self.min_mint_decimals = new_min_mint_decimals;
+        self.max_mint_decimals = new_max_mint_decimals;
+        Ok(())
+    }
+
+    /// Updates the lending adapter and the terms it borrows pools liquidity on.
+    ///
+    /// # Parameters
+    /// - `lending_adapter`: The authority of the lending adapter, `None` disables borrowing.
+    /// - `max_lend_out_basis_points`: The share of each side of a pool's liquidity that may be lent out.
+    /// - `lend_out_interest_rate_basis_points`: The annual interest rate charged on lent out liquidity.
+    ///
+    /// # Errors
+    /// - Returns `ErrorCode::ConfigLendOutLimitExceeded` if the limit exceeds `MAX_LEND_OUT_BASIS_POINTS`.
+    /// - Returns `ErrorCode::ConfigLendOutInterestRateExceeded` if the interest rate exceeds 100%.
+    pub(crate) fn update_lending(&mut self, lending_adapter: Option<Pubkey>, max_lend_out_basis_points: u16, lend_out_interest_rate_basis_points: u16) -> Result<()> {
+        require!(
+            max_lend_out_basis_points <= Self::MAX_LEND_OUT_BASIS_POINTS,
+            ErrorCode::ConfigLendOutLimitExceeded
+        );
+        require!(
+            lend_out_interest_rate_basis_points <= 10000,
+            ErrorCode::ConfigLendOutInterestRateExceeded
+        );
+        self.lending_adapter = lending_adapter;
+        self.max_lend_out_basis_points = max_lend_out_basis_points;
+        self.lend_out_interest_rate_basis_points = lend_out_interest_rate_basis_points;
+        Ok(())
+    }
+
+    /// Sets or removes the loyalty multiplier for long-term liquidity providers.
+    ///
+    /// # Parameters
+    /// - `loyalty_boost`: The new loyalty boost, `None` disables the multiplier.
+    pub(crate) fn update_loyalty_boost(&mut self, loyalty_boost: Option<LoyaltyBoost>) {
+        self.loyalty_boost = loyalty_boost;
+    }
+
+    /// Sets or removes the pricing of `CpAmm` creation in the config.
+    ///
+    /// # Parameters
+    /// - `pool_creation_pricing`: The new pricing, `None` charges the deployment's `CP_AMM_INITIALIZE_PRICE_IN_LAMPORTS`.
+    pub(crate) fn update_pool_creation_pricing(&mut self, pool_creation_pricing: Option<PoolCreationPricing>) {
+        self.pool_creation_pricing = pool_creation_pricing;
+    }
+
+    /// Updates the bound on the launch price of the config's pools.
+    ///
+    /// # Parameters
+    /// - `max_launch_price_orders`: The number of decimal orders of magnitude the launch price
+    ///   may deviate from 1 in either direction. Zero disables the bound.
+    pub(crate) fn update_max_launch_price_orders(&mut self, max_launch_price_orders: u8) {
+        self.max_launch_price_orders = max_launch_price_orders;
+    }
+
+    /// Updates the tolerances the invariants of the config's pools are validated with.
+    ///
+    /// Looser tolerances let pools with little liquidity absorb more rounding, stricter ones
+    /// bound the value that may leak through it.
+    ///
+    /// # Parameters
+    /// - `swap_constant_product_tolerance_ppb`: The swap constant product tolerance, in parts per billion.
+    /// - `adjust_liquidity_ratio_tolerance_ppb`: The liquidity ratio tolerance, in parts per billion.
+    ///
+    /// # Errors
+    /// - Returns `ErrorCode::ConfigTolerancesOutOfBounds` if a tolerance is outside of its bounds.
+    pub(crate) fn update_tolerances(&mut self, swap_constant_product_tolerance_ppb: u32, adjust_liquidity_ratio_tolerance_ppb: u32) -> Result<()> {
+        let (min_swap, max_swap) = Self::SWAP_CONSTANT_PRODUCT_TOLERANCE_PPB_BOUNDS;
+        let (min_adjust, max_adjust) = Self::ADJUST_LIQUIDITY_RATIO_TOLERANCE_PPB_BOUNDS;
+        require!(
+            (min_swap..=max_swap).contains(&swap_constant_product_tolerance_ppb)
+                && (min_adjust..=max_adjust).contains(&adjust_liquidity_ratio_tolerance_ppb),
+            ErrorCode::ConfigTolerancesOutOfBounds
+        );
+        self.swap_constant_product_tolerance_ppb = swap_constant_product_tolerance_ppb;
+        self.adjust_liquidity_ratio_tolerance_ppb = adjust_liquidity_ratio_tolerance_ppb;
+        Ok(())
+    }
+
+    /// Updates the settlement of the protocol fees collected from the config's pools.
+    ///
+    /// # Parameters
+    /// - `fee_settlement`: The settlement of the protocol fees, or `None` to collect them in the input tokens.
+    pub(crate) fn update_fee_settlement(&mut self, fee_settlement: Option<FeeSettlement>) {
+        self.fee_settlement = fee_settlement;
+    }
+
+    /// Updates the maximal share of the providers fee the creators of the config's pools may claim.
+    ///
+    /// Pools already sharing more keep their share until their creators update it.
+    ///
+    /// # Parameters
+    /// - `max_creator_fee_share_basis_points`: The maximal creator fee share, in basis points.
+    ///
+    /// # Errors
+    /// - Returns `ErrorCode::ConfigCreatorFeeShareExceeded` if the share exceeds `MAX_CREATOR_FEE_SHARE_BASIS_POINTS`.
+    pub(crate) fn update_max_creator_fee_share(&mut self, max_creator_fee_share_basis_points: u16) -> Result<()> {
+        require!(
+            max_creator_fee_share_basis_points <= Self::MAX_CREATOR_FEE_SHARE_BASIS_POINTS,
+            ErrorCode::ConfigCreatorFeeShareExceeded
+        );
+        self.max_creator_fee_share_basis_points = max_creator_fee_share_basis_points;
+        Ok(())
+    }
+
+    /// Updates the terms on which never launched pools of the config may be closed by anyone.
+    ///
+    /// # Parameters
+    /// - `stale_pool_close_epochs`: The number of epochs since initialization after which a pool is stale, `0` disables the closing.
+    /// - `stale_pool_closer_share_basis_points`: The share of the pool's rent paid to the closer.
+    ///
+    /// # Errors
+    /// - Returns `ErrorCode::ConfigStalePoolCloserShareExceeded` if the closer share exceeds 100%.
+    pub(crate) fn update_stale_pool_close(&mut self, stale_pool_close_epochs: u16, stale_pool_closer_share_basis_points: u16) -> Result<()> {
+        require!(stale_pool_closer_share_basis_points <= 10000, ErrorCode::ConfigStalePoolCloserShareExceeded);
+        self.stale_pool_close_epochs = stale_pool_close_epochs;
+        self.stale_pool_closer_share_basis_points = stale_pool_closer_share_basis_points;
+        Ok(())
+    }
+
+    /// Updates the LP concentration alert and guard of the config's pools.
+    ///
+    /// # Parameters
+    /// - `lp_concentration_share_basis_points`: The share of a pool's LP tokens a single provide may mint, `0` disables the alert.
+    /// - `lp_concentration_guard_epochs`: The number of epochs since initialization during which larger provides are rejected.
+    ///
+    /// # Errors
+    /// - Returns `ErrorCode::ConfigLpConcentrationShareExceeded` if the share exceeds 100%.
+    pub(crate) fn update_lp_concentration(&mut self, lp_concentration_share_basis_points: u16, lp_concentration_guard_epochs: u16) -> Result<()> {
+        require!(lp_concentration_share_basis_points <= 10000, ErrorCode::ConfigLpConcentrationShareExceeded);
+        self.lp_concentration_share_basis_points = lp_concentration_share_basis_points;
+        self.lp_concentration_guard_epochs = lp_concentration_guard_epochs;
+        Ok(())
+    }
+
+    /// Updates the program screening provides and withdrawals in the config's pools.
+    ///
+    /// # Parameters
+    /// - `screening_program`: The screening program, or `None` to stop screening.
+    pub(crate) fn update_screening_program(&mut self, screening_program: Option<Pubkey>) {
+        self.screening_program = screening_program;
+    }
+
+    /// Marks the config as having `MintEquivalences` checked on pool creation.
+    pub(crate) fn enable_mint_equivalences(&mut self) {
+        self.has_mint_equivalences = true;
+    }
+
+    /// Increments the `pools_count` field by 1, moving to the next `PoolIndex` slot.
+    ///
+    /// # Behavior
+    /// - Panics if the increment operation fails (e.g., due to an overflow).
+    pub(crate) fn increment_pools_count(&mut self) {
+        self.pools_count = self.pools_count.checked_add(1).unwrap()
+    }
+
+    /// Enables or disables fee-free protocol rebalancing swaps.
+    ///
+    /// # Parameters
+    /// - `is_protocol_rebalance_enabled`: Whether the fee authority may swap without protocol fees.
+    pub(crate) fn update_protocol_rebalance(&mut self, is_protocol_rebalance_enabled: bool) {
+        self.is_protocol_rebalance_enabled = is_protocol_rebalance_enabled;
+    }
+
+    /// Enables or disables the pool creation allow-list.
+    ///
+    /// # Parameters
+    /// - `is_pool_creation_allow_listed`: Whether only creators holding a `PoolCreatorPermit` may initialize pools.
+    pub(crate) fn update_pool_creation_allow_list(&mut self, is_pool_creation_allow_listed: bool) {
+        self.is_pool_creation_allow_listed = is_pool_creation_allow_listed;
+    }
+
+    /// Validates that the decimals of a tradable mint fit into the configured range.
+    ///
+    /// # Parameters
+    /// - `decimals`: The decimals of the tradable mint.
+    ///
+    /// # Errors
+    /// - Returns `ErrorCode::MintDecimalsOutOfRange` if the decimals are outside of the range.
+    pub fn validate_mint_decimals(&self, decimals: u8) -> Result<()> {
+        require!(
+            decimals >= self.min_mint_decimals && decimals <= self.max_mint_decimals,
+            ErrorCode::MintDecimalsOutOfRange
+        );
+        Ok(())
+    }
+
+    /// Validates that the launch price of a pool fits into the configured bound.
+    ///
+    /// The price is compared in whole tokens, so it doesn't depend on the mints decimals.
+    ///
+    /// # Parameters
+    /// - `base_liquidity`: The base liquidity provided at launch.
+    /// - `quote_liquidity`: The quote liquidity provided at launch.
+    /// - `base_decimals`: The decimals of the base mint.
+    /// - `quote_decimals`: The decimals of the quote mint.
+    ///
+    /// # Errors
+    /// - Returns `ErrorCode::LaunchPriceOutOfBounds` if the price deviates from 1 by more than
+    ///   `max_launch_price_orders` orders of magnitude.
+    pub fn validate_launch_price(&self, base_liquidity: u64, quote_liquidity: u64, base_decimals: u8, quote_decimals: u8) -> Result<()> {
+        if self.max_launch_price_orders == 0 {
+            return Ok(());
+        }
+        let max_orders = self.max_launch_price_orders as u32;
+        // price = (quote / 10^quote_decimals) / (base / 10^base_decimals), bounded by 10^-max_orders..=10^max_orders
+        require!(
+            is_scaled_le(quote_liquidity, base_decimals as u32, base_liquidity, quote_decimals as u32 + max_orders)
+                && is_scaled_le(base_liquidity, quote_decimals as u32, quote_liquidity, base_decimals as u32 + max_orders),
+            ErrorCode::LaunchPriceOutOfBounds
         );
-        self.protocol_fee_rate_basis_points = new_protocol_fee_rate_basis_points;
         Ok(())
     }
 
@@ -128,6 +682,21 @@ impl AmmsConfig {
         self.providers_fee_rate_basis_points
     }
 
+    /// Retrieves the providers fee rate effective at the given slot, following the scheduled ramp, if any.
+    ///
+    /// # Parameters
+    /// - `slot`: The current slot.
+    ///
+    /// # Returns
+    /// - The `u16` fee rate for providers, measured in basis points.
+    #[inline]
+    pub fn providers_fee_rate_basis_points_at(&self, slot: u64) -> u16 {
+        match self.providers_fee_ramp.as_ref() {
+            Some(providers_fee_ramp) => providers_fee_ramp.fee_rate_at(slot, self.providers_fee_rate_basis_points),
+            None => self.providers_fee_rate_basis_points,
+        }
+    }
+
     /// Retrieves the protocol fee rate.
     ///
     /// # Returns
@@ -136,30 +705,405 @@ impl AmmsConfig {
     pub fn protocol_fee_rate_basis_points(&self) -> u16 {
         self.protocol_fee_rate_basis_points
     }
-}
 
-#[cfg(test)]
-mod amms_config_tests {
-    use anchor_lang::Discriminator;
-    use crate::constants::ANCHOR_DISCRIMINATOR;
-    use super::*;
+    /// Retrieves the per-slot withdraw limit applied to incident-limited pools.
+    ///
+    /// # Returns
+    /// - The `u16` withdraw limit, measured in basis points of the LP tokens supply.
+    #[inline]
+    pub fn incident_withdraw_limit_basis_points(&self) -> u16 {
+        self.incident_withdraw_limit_basis_points
+    }
 
-    /// Tests the correct initialization of the `AmmsConfig` struct.
-    #[test]
-    fn test_amms_config_initialize() {
-        let mut amms_config = AmmsConfig {
-            bump: 0,
-            id: 0,
-            fee_authority: Pubkey::default(),
-            providers_fee_rate_basis_points: 0,
-            protocol_fee_rate_basis_points: 0,
-        };
+    /// Retrieves the bounty paid for triggering protocol fees collection.
+    ///
+    /// # Returns
+    /// - The `u16` keeper bounty, measured in basis points of the collected fees.
+    #[inline]
+    pub fn keeper_bounty_basis_points(&self) -> u16 {
+        self.keeper_bounty_basis_points
+    }
 
-        let fee_authority = Pubkey::new_unique();
-        let protocol_fee_rate_basis_points = 200;
-        let providers_fee_rate_basis_points = 300;
-        let id = 42u64;
-        let bump = 42u8;
+    /// Retrieves the cap on the protocol fees a pool may accrue before provides require their collection.
+    ///
+    /// # Returns
+    /// - The `u16` cap, measured in basis points of each side of a pool's liquidity, zero if disabled.
+    #[inline]
+    pub fn protocol_fees_cap_basis_points(&self) -> u16 {
+        self.protocol_fees_cap_basis_points
+    }
+
+    /// Checks if pool creation in the config is restricted to creators holding a `PoolCreatorPermit`.
+    #[inline]
+    pub fn is_pool_creation_allow_listed(&self) -> bool {
+        self.is_pool_creation_allow_listed
+    }
+
+    /// Retrieves the share of a pool's LP tokens a single provide may mint before the pool raises its concentration flag.
+    ///
+    /// # Returns
+    /// - The `u16` share, measured in basis points, `0` if the alert is disabled.
+    #[inline]
+    pub fn lp_concentration_share_basis_points(&self) -> u16 {
+        self.lp_concentration_share_basis_points
+    }
+
+    /// Retrieves the number of epochs since a pool's initialization during which concentrating provides are rejected.
+    ///
+    /// # Returns
+    /// - The `u16` number of epochs, `0` if the guard is disabled.
+    #[inline]
+    pub fn lp_concentration_guard_epochs(&self) -> u16 {
+        self.lp_concentration_guard_epochs
+    }
+
+    /// Retrieves the minimal amount of LP tokens a single provide must mint.
+    ///
+    /// # Returns
+    /// - The `u64` minimal LP tokens amount, zero if the check is disabled.
+    #[inline]
+    pub fn min_provide_lp_tokens(&self) -> u64 {
+        self.min_provide_lp_tokens
+    }
+
+    /// Retrieves the minimal amount of LP tokens a single withdraw must redeem.
+    ///
+    /// # Returns
+    /// - The `u64` minimal LP tokens amount, zero if the check is disabled.
+    #[inline]
+    pub fn min_withdraw_lp_tokens(&self) -> u64 {
+        self.min_withdraw_lp_tokens
+    }
+
+    /// Retrieves the minimal decimals a tradable mint must have.
+    ///
+    /// # Returns
+    /// - The `u8` minimal mint decimals.
+    #[inline]
+    pub fn min_mint_decimals(&self) -> u8 {
+        self.min_mint_decimals
+    }
+
+    /// Retrieves the maximal decimals a tradable mint may have.
+    ///
+    /// # Returns
+    /// - The `u8` maximal mint decimals.
+    #[inline]
+    pub fn max_mint_decimals(&self) -> u8 {
+        self.max_mint_decimals
+    }
+
+    /// Checks if the fee authority may rebalance protocol-owned liquidity without protocol fees.
+    ///
+    /// # Returns
+    /// - `true` if fee-free protocol rebalancing is enabled.
+    #[inline]
+    pub fn is_protocol_rebalance_enabled(&self) -> bool {
+        self.is_protocol_rebalance_enabled
+    }
+
+    /// Retrieves the bounds on the fee rates the config may be updated to.
+    ///
+    /// # Returns
+    /// - `Some(&FeeBounds)` if the fee rates are bounded, `None` otherwise.
+    #[inline]
+    pub fn fee_bounds(&self) -> Option<&FeeBounds> {
+        self.fee_bounds.as_ref()
+    }
+
+    /// Retrieves the maximal protocol fee rate new pools commit to.
+    ///
+    /// # Returns
+    /// - The `u16` maximal protocol fee rate of the fee bounds if set, otherwise the current
+    ///   protocol fee rate, measured in basis points.
+    #[inline]
+    pub fn max_protocol_fee_commitment_basis_points(&self) -> u16 {
+        self.fee_bounds.as_ref()
+            .map_or(self.protocol_fee_rate_basis_points, FeeBounds::max_protocol_fee_rate_basis_points)
+    }
+
+    /// Retrieves the scheduled providers fee rate ramp.
+    ///
+    /// # Returns
+    /// - `Some(&FeeRamp)` if a ramp has been scheduled, `None` otherwise.
+    #[inline]
+    pub fn providers_fee_ramp(&self) -> Option<&FeeRamp> {
+        self.providers_fee_ramp.as_ref()
+    }
+
+    /// Retrieves the authority of the lending adapter allowed to borrow from pools.
+    ///
+    /// # Returns
+    /// - `Some(&Pubkey)` if a lending adapter is configured, `None` otherwise.
+    #[inline]
+    pub fn lending_adapter(&self) -> Option<&Pubkey> {
+        self.lending_adapter.as_ref()
+    }
+
+    /// Retrieves the share of each side of a pool's liquidity that may be lent out.
+    ///
+    /// # Returns
+    /// - The `u16` lend-out limit, measured in basis points of the pool liquidity.
+    #[inline]
+    pub fn max_lend_out_basis_points(&self) -> u16 {
+        self.max_lend_out_basis_points
+    }
+
+    /// Retrieves the annual interest rate charged on lent out liquidity.
+    ///
+    /// # Returns
+    /// - The `u16` interest rate, measured in basis points.
+    #[inline]
+    pub fn lend_out_interest_rate_basis_points(&self) -> u16 {
+        self.lend_out_interest_rate_basis_points
+    }
+
+    /// Retrieves the number of pools created under this configuration.
+    ///
+    /// # Returns
+    /// - The `u64` number of created pools.
+    #[inline]
+    pub fn pools_count(&self) -> u64 {
+        self.pools_count
+    }
+
+    /// Retrieves the loyalty multiplier for long-term liquidity providers.
+    ///
+    /// # Returns
+    /// - `Some(&LoyaltyBoost)` if the multiplier is enabled, `None` otherwise.
+    #[inline]
+    pub fn loyalty_boost(&self) -> Option<&LoyaltyBoost> {
+        self.loyalty_boost.as_ref()
+    }
+
+    /// Retrieves the bound on the launch price of the config's pools.
+    ///
+    /// # Returns
+    /// - The `u8` number of decimal orders of magnitude the launch price may deviate from 1, zero if the bound is disabled.
+    #[inline]
+    pub fn max_launch_price_orders(&self) -> u8 {
+        self.max_launch_price_orders
+    }
+
+    /// Retrieves the swap constant product tolerance.
+    ///
+    /// # Returns
+    /// - The `u32` tolerance, measured in parts per billion.
+    #[inline]
+    pub fn swap_constant_product_tolerance_ppb(&self) -> u32 {
+        self.swap_constant_product_tolerance_ppb
+    }
+
+    /// Retrieves the liquidity ratio tolerance.
+    ///
+    /// # Returns
+    /// - The `u32` tolerance, measured in parts per billion.
+    #[inline]
+    pub fn adjust_liquidity_ratio_tolerance_ppb(&self) -> u32 {
+        self.adjust_liquidity_ratio_tolerance_ppb
+    }
+
+    /// Retrieves the swap constant product tolerance as passed to the pool calculations.
+    ///
+    /// # Returns
+    /// - The `Q64_128` tolerance of the constant product square root.
+    #[inline]
+    pub fn swap_constant_product_tolerance(&self) -> Q64_128 {
+        tolerance_from_ppb(self.swap_constant_product_tolerance_ppb)
+    }
+
+    /// Retrieves the liquidity ratio tolerance as passed to the pool calculations.
+    ///
+    /// # Returns
+    /// - The `Q64_128` tolerance of the base-to-quote ratio square root.
+    #[inline]
+    pub fn adjust_liquidity_ratio_tolerance(&self) -> Q64_128 {
+        tolerance_from_ppb(self.adjust_liquidity_ratio_tolerance_ppb)
+    }
+
+    /// Retrieves the settlement of the protocol fees collected from the config's pools.
+    ///
+    /// # Returns
+    /// - `Some(&FeeSettlement)` if the fees are settled in a single mint, `None` otherwise.
+    #[inline]
+    pub fn fee_settlement(&self) -> Option<&FeeSettlement> {
+        self.fee_settlement.as_ref()
+    }
+
+    /// Retrieves the maximal share of the providers fee routed to pool creators.
+    ///
+    /// # Returns
+    /// - The `u16` share, measured in basis points.
+    #[inline]
+    pub fn max_creator_fee_share_basis_points(&self) -> u16 {
+        self.max_creator_fee_share_basis_points
+    }
+
+    /// Retrieves the number of epochs after which a never launched pool may be closed by anyone.
+    ///
+    /// # Returns
+    /// - The `u16` number of epochs, `0` if the closing is disabled.
+    #[inline]
+    pub fn stale_pool_close_epochs(&self) -> u16 {
+        self.stale_pool_close_epochs
+    }
+
+    /// Retrieves the share of a closed stale pool's rent paid to the closer.
+    ///
+    /// # Returns
+    /// - The `u16` share, measured in basis points.
+    #[inline]
+    pub fn stale_pool_closer_share_basis_points(&self) -> u16 {
+        self.stale_pool_closer_share_basis_points
+    }
+
+    /// Retrieves the program screening provides and withdrawals in the config's pools.
+    ///
+    /// # Returns
+    /// - `Some(&Pubkey)` if a screening program is configured, `None` otherwise.
+    #[inline]
+    pub fn screening_program(&self) -> Option<&Pubkey> {
+        self.screening_program.as_ref()
+    }
+
+    /// Checks if the config has `MintEquivalences` checked on pool creation.
+    #[inline]
+    pub fn has_mint_equivalences(&self) -> bool {
+        self.has_mint_equivalences
+    }
+
+    /// Retrieves the pricing of `CpAmm` creation in the config.
+    ///
+    /// # Returns
+    /// - `Some(&PoolCreationPricing)` if the config prices pool creation, `None` otherwise.
+    #[inline]
+    pub fn pool_creation_pricing(&self) -> Option<&PoolCreationPricing> {
+        self.pool_creation_pricing.as_ref()
+    }
+
+    /// Calculates the price of creating the next `CpAmm` under the config.
+    ///
+    /// # Returns
+    /// - The `u64` price in lamports, from the config's pricing at the current `pools_count` if set,
+    ///   the deployment's `CP_AMM_INITIALIZE_PRICE_IN_LAMPORTS` otherwise.
+    pub fn cp_amm_initialize_price_in_lamports(&self) -> u64 {
+        self.pool_creation_pricing
+            .map_or(CP_AMM_INITIALIZE_PRICE_IN_LAMPORTS, |pricing| pricing.price_in_lamports(self.pools_count))
+    }
+
+    /// Retrieves the swap origin policy in force at the given slot.
+    ///
+    /// # Returns
+    /// - The stored `SwapOriginPolicy`, `Unrestricted` once it expired.
+    #[inline]
+    pub fn swap_origin_policy_at(&self, slot: u64) -> SwapOriginPolicy {
+        if slot < self.swap_origin_policy_until_slot {
+            self.swap_origin_policy
+        } else {
+            SwapOriginPolicy::Unrestricted
+        }
+    }
+
+    /// Retrieves the slot at which the swap origin policy expires.
+    ///
+    /// # Returns
+    /// - A `u64` slot, `0` if no policy was put in force.
+    #[inline]
+    pub fn swap_origin_policy_until_slot(&self) -> u64 {
+        self.swap_origin_policy_until_slot
+    }
+
+    /// Retrieves the position of the `PoolIndex` bucket the next created pool is appended to.
+    ///
+    /// # Returns
+    /// - The `u64` bucket position in the config's `PoolIndex` chain.
+    #[inline]
+    pub fn pool_index_bucket(&self) -> u64 {
+        self.pools_count / PoolIndex::CAPACITY as u64
+    }
+}
+
+/// Implements the `VersionedAccount` trait for the `AmmsConfig` struct.
+impl VersionedAccount for AmmsConfig {
+    const LAYOUT_VERSION: u8 = 1;
+
+    #[inline]
+    fn layout_version(&self) -> u8 {
+        self.layout_version
+    }
+}
+
+/// Converts a tolerance in parts per billion to its truncated `Q64_128` representation.
+fn tolerance_from_ppb(tolerance_ppb: u32) -> Q64_128 {
+    Q64_128::from_u64(tolerance_ppb as u64).checked_div(Q64_128::from_u64(1_000_000_000)).unwrap()
+}
+
+/// Checks whether `lhs * 10^lhs_exponent <= rhs * 10^rhs_exponent` without overflowing.
+fn is_scaled_le(lhs: u64, lhs_exponent: u32, rhs: u64, rhs_exponent: u32) -> bool {
+    let common_exponent = lhs_exponent.min(rhs_exponent);
+    let scale = |value: u64, exponent: u32| 10u128.checked_pow(exponent - common_exponent)
+        .and_then(|multiplier| (value as u128).checked_mul(multiplier));
+    // Both values fit into u64, so a side overflowing u128 after scaling is the larger one
+    match (scale(lhs, lhs_exponent), scale(rhs, rhs_exponent)) {
+        (Some(lhs), Some(rhs)) => lhs <= rhs,
+        (None, _) => lhs == 0,
+        (Some(_), None) => true,
+    }
+}
+
+#[cfg(test)]
+mod amms_config_tests {
+    use anchor_lang::Discriminator;
+    use crate::constants::ANCHOR_DISCRIMINATOR;
+    use super::*;
+
+    /// Tests the correct initialization of the `AmmsConfig` struct.
+    #[test]
+    fn test_amms_config_initialize() {
+        let mut amms_config = AmmsConfig {
+            bump: 0,
+            id: 0,
+            fee_authority: Pubkey::default(),
+            providers_fee_rate_basis_points: 0,
+            protocol_fee_rate_basis_points: 0,
+            incident_withdraw_limit_basis_points: 0,
+            keeper_bounty_basis_points: 0,
+            min_provide_lp_tokens: 0,
+            min_withdraw_lp_tokens: 0,
+            min_mint_decimals: 0,
+            max_mint_decimals: 0,
+            is_protocol_rebalance_enabled: false,
+            fee_bounds: None,
+            providers_fee_ramp: None,
+            lending_adapter: None,
+            max_lend_out_basis_points: 0,
+            lend_out_interest_rate_basis_points: 0,
+            pools_count: 0,
+            loyalty_boost: None,
+            max_launch_price_orders: 0,
+            swap_constant_product_tolerance_ppb: 0,
+            adjust_liquidity_ratio_tolerance_ppb: 0,
+            fee_settlement: None,
+            max_creator_fee_share_basis_points: 0,
+            stale_pool_close_epochs: 0,
+            stale_pool_closer_share_basis_points: 0,
+            screening_program: None,
+            has_mint_equivalences: false,
+            pool_creation_pricing: None,
+            protocol_fees_cap_basis_points: 0,
+            is_pool_creation_allow_listed: false,
+            lp_concentration_share_basis_points: 0,
+            lp_concentration_guard_epochs: 0,
+            swap_origin_policy: SwapOriginPolicy::Unrestricted,
+            swap_origin_policy_until_slot: 0,
+            layout_version: 0,
+        };
+
+        let fee_authority = Pubkey::new_unique();
+        let protocol_fee_rate_basis_points = 200;
+        let providers_fee_rate_basis_points = 300;
+        let id = 42u64;
+        let bump = 42u8;
 
         amms_config.initialize(fee_authority, protocol_fee_rate_basis_points, providers_fee_rate_basis_points, id, bump).unwrap();
 
@@ -173,7 +1117,58 @@ mod amms_config_tests {
         assert_eq!(amms_config.fee_authority().key(), fee_authority);
         assert_eq!(amms_config.protocol_fee_rate_basis_points(), protocol_fee_rate_basis_points);
         assert_eq!(amms_config.providers_fee_rate_basis_points(), providers_fee_rate_basis_points);
+        assert_eq!(amms_config.min_mint_decimals(), 0);
+        assert_eq!(amms_config.max_mint_decimals(), u8::MAX);
+        assert_eq!(amms_config.swap_constant_product_tolerance(), cpmm_math::cp_amm::SWAP_CONSTANT_PRODUCT_SQRT_TOLERANCE);
+        assert_eq!(amms_config.adjust_liquidity_ratio_tolerance(), cpmm_math::cp_amm::ADJUST_LIQUIDITY_RATIO_SQRT_TOLERANCE);
+        assert_eq!(amms_config.layout_version(), AmmsConfig::LAYOUT_VERSION);
+
+    }
+
+    /// Tests overflowing fee rates sum, which is rejected as an exceeded total fee rate.
+    #[test]
+    fn test_amms_config_initialize_fee_rates_overflow() {
+        let mut amms_config = AmmsConfig {
+            bump: 0,
+            id: 0,
+            fee_authority: Pubkey::default(),
+            providers_fee_rate_basis_points: 0,
+            protocol_fee_rate_basis_points: 0,
+            incident_withdraw_limit_basis_points: 0,
+            keeper_bounty_basis_points: 0,
+            min_provide_lp_tokens: 0,
+            min_withdraw_lp_tokens: 0,
+            min_mint_decimals: 0,
+            max_mint_decimals: 0,
+            is_protocol_rebalance_enabled: false,
+            fee_bounds: None,
+            providers_fee_ramp: None,
+            lending_adapter: None,
+            max_lend_out_basis_points: 0,
+            lend_out_interest_rate_basis_points: 0,
+            pools_count: 0,
+            loyalty_boost: None,
+            max_launch_price_orders: 0,
+            swap_constant_product_tolerance_ppb: 0,
+            adjust_liquidity_ratio_tolerance_ppb: 0,
+            fee_settlement: None,
+            max_creator_fee_share_basis_points: 0,
+            stale_pool_close_epochs: 0,
+            stale_pool_closer_share_basis_points: 0,
+            screening_program: None,
+            has_mint_equivalences: false,
+            pool_creation_pricing: None,
+            protocol_fees_cap_basis_points: 0,
+            is_pool_creation_allow_listed: false,
+            lp_concentration_share_basis_points: 0,
+            lp_concentration_guard_epochs: 0,
+            swap_origin_policy: SwapOriginPolicy::Unrestricted,
+            swap_origin_policy_until_slot: 0,
+            layout_version: AmmsConfig::LAYOUT_VERSION,
+        };
 
+        let result = amms_config.initialize(Pubkey::new_unique(), u16::MAX, 1, 0, 0);
+        assert_eq!(result.err(), Some(ErrorCode::ConfigFeeRateExceeded.into()));
     }
 
 
@@ -186,6 +1181,37 @@ mod amms_config_tests {
             fee_authority: Pubkey::default(),
             providers_fee_rate_basis_points: 300,
             protocol_fee_rate_basis_points: 200,
+            incident_withdraw_limit_basis_points: 0,
+            keeper_bounty_basis_points: 0,
+            min_provide_lp_tokens: 0,
+            min_withdraw_lp_tokens: 0,
+            min_mint_decimals: 0,
+            max_mint_decimals: 0,
+            is_protocol_rebalance_enabled: false,
+            fee_bounds: None,
+            providers_fee_ramp: None,
+            lending_adapter: None,
+            max_lend_out_basis_points: 0,
+            lend_out_interest_rate_basis_points: 0,
+            pools_count: 0,
+            loyalty_boost: None,
+            max_launch_price_orders: 0,
+            swap_constant_product_tolerance_ppb: 0,
+            adjust_liquidity_ratio_tolerance_ppb: 0,
+            fee_settlement: None,
+            max_creator_fee_share_basis_points: 0,
+            stale_pool_close_epochs: 0,
+            stale_pool_closer_share_basis_points: 0,
+            screening_program: None,
+            has_mint_equivalences: false,
+            pool_creation_pricing: None,
+            protocol_fees_cap_basis_points: 0,
+            is_pool_creation_allow_listed: false,
+            lp_concentration_share_basis_points: 0,
+            lp_concentration_guard_epochs: 0,
+            swap_origin_policy: SwapOriginPolicy::Unrestricted,
+            swap_origin_policy_until_slot: 0,
+            layout_version: AmmsConfig::LAYOUT_VERSION,
         };
 
         let new_fee_authority = Pubkey::new_unique();
@@ -202,6 +1228,37 @@ mod amms_config_tests {
             fee_authority: Pubkey::default(),
             providers_fee_rate_basis_points: 300,
             protocol_fee_rate_basis_points: 200,
+            incident_withdraw_limit_basis_points: 0,
+            keeper_bounty_basis_points: 0,
+            min_provide_lp_tokens: 0,
+            min_withdraw_lp_tokens: 0,
+            min_mint_decimals: 0,
+            max_mint_decimals: 0,
+            is_protocol_rebalance_enabled: false,
+            fee_bounds: None,
+            providers_fee_ramp: None,
+            lending_adapter: None,
+            max_lend_out_basis_points: 0,
+            lend_out_interest_rate_basis_points: 0,
+            pools_count: 0,
+            loyalty_boost: None,
+            max_launch_price_orders: 0,
+            swap_constant_product_tolerance_ppb: 0,
+            adjust_liquidity_ratio_tolerance_ppb: 0,
+            fee_settlement: None,
+            max_creator_fee_share_basis_points: 0,
+            stale_pool_close_epochs: 0,
+            stale_pool_closer_share_basis_points: 0,
+            screening_program: None,
+            has_mint_equivalences: false,
+            pool_creation_pricing: None,
+            protocol_fees_cap_basis_points: 0,
+            is_pool_creation_allow_listed: false,
+            lp_concentration_share_basis_points: 0,
+            lp_concentration_guard_epochs: 0,
+            swap_origin_policy: SwapOriginPolicy::Unrestricted,
+            swap_origin_policy_until_slot: 0,
+            layout_version: AmmsConfig::LAYOUT_VERSION,
         };
 
         let new_providers_fee_rate = 234;
@@ -219,6 +1276,37 @@ mod amms_config_tests {
             fee_authority: Pubkey::default(),
             providers_fee_rate_basis_points: 300,
             protocol_fee_rate_basis_points: 200,
+            incident_withdraw_limit_basis_points: 0,
+            keeper_bounty_basis_points: 0,
+            min_provide_lp_tokens: 0,
+            min_withdraw_lp_tokens: 0,
+            min_mint_decimals: 0,
+            max_mint_decimals: 0,
+            is_protocol_rebalance_enabled: false,
+            fee_bounds: None,
+            providers_fee_ramp: None,
+            lending_adapter: None,
+            max_lend_out_basis_points: 0,
+            lend_out_interest_rate_basis_points: 0,
+            pools_count: 0,
+            loyalty_boost: None,
+            max_launch_price_orders: 0,
+            swap_constant_product_tolerance_ppb: 0,
+            adjust_liquidity_ratio_tolerance_ppb: 0,
+            fee_settlement: None,
+            max_creator_fee_share_basis_points: 0,
+            stale_pool_close_epochs: 0,
+            stale_pool_closer_share_basis_points: 0,
+            screening_program: None,
+            has_mint_equivalences: false,
+            pool_creation_pricing: None,
+            protocol_fees_cap_basis_points: 0,
+            is_pool_creation_allow_listed: false,
+            lp_concentration_share_basis_points: 0,
+            lp_concentration_guard_epochs: 0,
+            swap_origin_policy: SwapOriginPolicy::Unrestricted,
+            swap_origin_policy_until_slot: 0,
+            layout_version: AmmsConfig::LAYOUT_VERSION,
         };
 
         let new_protocol_fee_rate = 234;
@@ -227,6 +1315,1118 @@ mod amms_config_tests {
         assert_eq!(amms_config.update_protocol_fee_rate(9701).ok(), None);
     }
 
+    /// Tests the `update_incident_withdraw_limit` method of the `AmmsConfig` struct.
+    #[test]
+    fn test_amms_config_update_incident_withdraw_limit() {
+        let mut amms_config = AmmsConfig {
+            bump: 42,
+            id: 42,
+            fee_authority: Pubkey::default(),
+            providers_fee_rate_basis_points: 300,
+            protocol_fee_rate_basis_points: 200,
+            incident_withdraw_limit_basis_points: 0,
+            keeper_bounty_basis_points: 0,
+            min_provide_lp_tokens: 0,
+            min_withdraw_lp_tokens: 0,
+            min_mint_decimals: 0,
+            max_mint_decimals: 0,
+            is_protocol_rebalance_enabled: false,
+            fee_bounds: None,
+            providers_fee_ramp: None,
+            lending_adapter: None,
+            max_lend_out_basis_points: 0,
+            lend_out_interest_rate_basis_points: 0,
+            pools_count: 0,
+            loyalty_boost: None,
+            max_launch_price_orders: 0,
+            swap_constant_product_tolerance_ppb: 0,
+            adjust_liquidity_ratio_tolerance_ppb: 0,
+            fee_settlement: None,
+            max_creator_fee_share_basis_points: 0,
+            stale_pool_close_epochs: 0,
+            stale_pool_closer_share_basis_points: 0,
+            screening_program: None,
+            has_mint_equivalences: false,
+            pool_creation_pricing: None,
+            protocol_fees_cap_basis_points: 0,
+            is_pool_creation_allow_listed: false,
+            lp_concentration_share_basis_points: 0,
+            lp_concentration_guard_epochs: 0,
+            swap_origin_policy: SwapOriginPolicy::Unrestricted,
+            swap_origin_policy_until_slot: 0,
+            layout_version: AmmsConfig::LAYOUT_VERSION,
+        };
+
+        let new_incident_withdraw_limit = 500;
+        amms_config.update_incident_withdraw_limit(new_incident_withdraw_limit).unwrap();
+        assert_eq!(amms_config.incident_withdraw_limit_basis_points, new_incident_withdraw_limit);
+        assert_eq!(amms_config.incident_withdraw_limit_basis_points(), new_incident_withdraw_limit);
+        assert_eq!(amms_config.update_incident_withdraw_limit(10001).ok(), None);
+    }
+
+    /// Tests the `update_keeper_bounty` method of the `AmmsConfig` struct.
+    #[test]
+    fn test_amms_config_update_keeper_bounty() {
+        let mut amms_config = AmmsConfig {
+            bump: 42,
+            id: 42,
+            fee_authority: Pubkey::default(),
+            providers_fee_rate_basis_points: 300,
+            protocol_fee_rate_basis_points: 200,
+            incident_withdraw_limit_basis_points: 0,
+            keeper_bounty_basis_points: 0,
+            min_provide_lp_tokens: 0,
+            min_withdraw_lp_tokens: 0,
+            min_mint_decimals: 0,
+            max_mint_decimals: 0,
+            is_protocol_rebalance_enabled: false,
+            fee_bounds: None,
+            providers_fee_ramp: None,
+            lending_adapter: None,
+            max_lend_out_basis_points: 0,
+            lend_out_interest_rate_basis_points: 0,
+            pools_count: 0,
+            loyalty_boost: None,
+            max_launch_price_orders: 0,
+            swap_constant_product_tolerance_ppb: 0,
+            adjust_liquidity_ratio_tolerance_ppb: 0,
+            fee_settlement: None,
+            max_creator_fee_share_basis_points: 0,
+            stale_pool_close_epochs: 0,
+            stale_pool_closer_share_basis_points: 0,
+            screening_program: None,
+            has_mint_equivalences: false,
+            pool_creation_pricing: None,
+            protocol_fees_cap_basis_points: 0,
+            is_pool_creation_allow_listed: false,
+            lp_concentration_share_basis_points: 0,
+            lp_concentration_guard_epochs: 0,
+            swap_origin_policy: SwapOriginPolicy::Unrestricted,
+            swap_origin_policy_until_slot: 0,
+            layout_version: AmmsConfig::LAYOUT_VERSION,
+        };
+
+        let new_keeper_bounty = 50;
+        amms_config.update_keeper_bounty(new_keeper_bounty).unwrap();
+        assert_eq!(amms_config.keeper_bounty_basis_points, new_keeper_bounty);
+        assert_eq!(amms_config.keeper_bounty_basis_points(), new_keeper_bounty);
+        amms_config.update_keeper_bounty(AmmsConfig::MAX_KEEPER_BOUNTY_BASIS_POINTS).unwrap();
+        assert_eq!(amms_config.update_keeper_bounty(AmmsConfig::MAX_KEEPER_BOUNTY_BASIS_POINTS + 1).ok(), None);
+    }
+
+    /// Tests the `update_protocol_fees_cap` method of the `AmmsConfig` struct.
+    #[test]
+    fn test_amms_config_update_protocol_fees_cap() {
+        let mut amms_config = AmmsConfig {
+            bump: 42,
+            id: 42,
+            fee_authority: Pubkey::default(),
+            providers_fee_rate_basis_points: 300,
+            protocol_fee_rate_basis_points: 200,
+            incident_withdraw_limit_basis_points: 0,
+            keeper_bounty_basis_points: 0,
+            min_provide_lp_tokens: 0,
+            min_withdraw_lp_tokens: 0,
+            min_mint_decimals: 0,
+            max_mint_decimals: 0,
+            is_protocol_rebalance_enabled: false,
+            fee_bounds: None,
+            providers_fee_ramp: None,
+            lending_adapter: None,
+            max_lend_out_basis_points: 0,
+            lend_out_interest_rate_basis_points: 0,
+            pools_count: 0,
+            loyalty_boost: None,
+            max_launch_price_orders: 0,
+            swap_constant_product_tolerance_ppb: 0,
+            adjust_liquidity_ratio_tolerance_ppb: 0,
+            fee_settlement: None,
+            max_creator_fee_share_basis_points: 0,
+            stale_pool_close_epochs: 0,
+            stale_pool_closer_share_basis_points: 0,
+            screening_program: None,
+            has_mint_equivalences: false,
+            pool_creation_pricing: None,
+            protocol_fees_cap_basis_points: 0,
+            is_pool_creation_allow_listed: false,
+            lp_concentration_share_basis_points: 0,
+            lp_concentration_guard_epochs: 0,
+            swap_origin_policy: SwapOriginPolicy::Unrestricted,
+            swap_origin_policy_until_slot: 0,
+            layout_version: AmmsConfig::LAYOUT_VERSION,
+        };
+
+        assert_eq!(amms_config.protocol_fees_cap_basis_points(), 0);
+        amms_config.update_protocol_fees_cap(2_000).unwrap();
+        assert_eq!(amms_config.protocol_fees_cap_basis_points(), 2_000);
+        amms_config.update_protocol_fees_cap(10000).unwrap();
+        assert_eq!(amms_config.update_protocol_fees_cap(10001).err(), Some(ErrorCode::ConfigProtocolFeesCapExceeded.into()));
+        assert_eq!(amms_config.protocol_fees_cap_basis_points(), 10000);
+        amms_config.update_protocol_fees_cap(0).unwrap();
+        assert_eq!(amms_config.protocol_fees_cap_basis_points(), 0);
+    }
+
+    /// Tests the `update_lp_tokens_limits` method of the `AmmsConfig` struct.
+    #[test]
+    fn test_amms_config_update_lp_tokens_limits() {
+        let mut amms_config = AmmsConfig {
+            bump: 42,
+            id: 42,
+            fee_authority: Pubkey::default(),
+            providers_fee_rate_basis_points: 300,
+            protocol_fee_rate_basis_points: 200,
+            incident_withdraw_limit_basis_points: 0,
+            keeper_bounty_basis_points: 0,
+            min_provide_lp_tokens: 0,
+            min_withdraw_lp_tokens: 0,
+            min_mint_decimals: 0,
+            max_mint_decimals: 0,
+            is_protocol_rebalance_enabled: false,
+            fee_bounds: None,
+            providers_fee_ramp: None,
+            lending_adapter: None,
+            max_lend_out_basis_points: 0,
+            lend_out_interest_rate_basis_points: 0,
+            pools_count: 0,
+            loyalty_boost: None,
+            max_launch_price_orders: 0,
+            swap_constant_product_tolerance_ppb: 0,
+            adjust_liquidity_ratio_tolerance_ppb: 0,
+            fee_settlement: None,
+            max_creator_fee_share_basis_points: 0,
+            stale_pool_close_epochs: 0,
+            stale_pool_closer_share_basis_points: 0,
+            screening_program: None,
+            has_mint_equivalences: false,
+            pool_creation_pricing: None,
+            protocol_fees_cap_basis_points: 0,
+            is_pool_creation_allow_listed: false,
+            lp_concentration_share_basis_points: 0,
+            lp_concentration_guard_epochs: 0,
+            swap_origin_policy: SwapOriginPolicy::Unrestricted,
+            swap_origin_policy_until_slot: 0,
+            layout_version: AmmsConfig::LAYOUT_VERSION,
+        };
+
+        amms_config.update_lp_tokens_limits(1000, 500);
+        assert_eq!(amms_config.min_provide_lp_tokens, 1000);
+        assert_eq!(amms_config.min_withdraw_lp_tokens, 500);
+        assert_eq!(amms_config.min_provide_lp_tokens(), 1000);
+        assert_eq!(amms_config.min_withdraw_lp_tokens(), 500);
+    }
+
+    /// Tests the `update_mint_decimals_range` and `validate_mint_decimals` methods of the `AmmsConfig` struct.
+    #[test]
+    fn test_amms_config_update_mint_decimals_range() {
+        let mut amms_config = AmmsConfig {
+            bump: 42,
+            id: 42,
+            fee_authority: Pubkey::default(),
+            providers_fee_rate_basis_points: 300,
+            protocol_fee_rate_basis_points: 200,
+            incident_withdraw_limit_basis_points: 0,
+            keeper_bounty_basis_points: 0,
+            min_provide_lp_tokens: 0,
+            min_withdraw_lp_tokens: 0,
+            min_mint_decimals: 0,
+            max_mint_decimals: u8::MAX,
+            is_protocol_rebalance_enabled: false,
+            fee_bounds: None,
+            providers_fee_ramp: None,
+            lending_adapter: None,
+            max_lend_out_basis_points: 0,
+            lend_out_interest_rate_basis_points: 0,
+            pools_count: 0,
+            loyalty_boost: None,
+            max_launch_price_orders: 0,
+            swap_constant_product_tolerance_ppb: 0,
+            adjust_liquidity_ratio_tolerance_ppb: 0,
+            fee_settlement: None,
+            max_creator_fee_share_basis_points: 0,
+            stale_pool_close_epochs: 0,
+            stale_pool_closer_share_basis_points: 0,
+            screening_program: None,
+            has_mint_equivalences: false,
+            pool_creation_pricing: None,
+            protocol_fees_cap_basis_points: 0,
+            is_pool_creation_allow_listed: false,
+            lp_concentration_share_basis_points: 0,
+            lp_concentration_guard_epochs: 0,
+            swap_origin_policy: SwapOriginPolicy::Unrestricted,
+            swap_origin_policy_until_slot: 0,
+            layout_version: AmmsConfig::LAYOUT_VERSION,
+        };
+
+        for decimals in [0, 6, 9, 18, u8::MAX] {
+            amms_config.validate_mint_decimals(decimals).unwrap();
+        }
+
+        amms_config.update_mint_decimals_range(6, 9).unwrap();
+        assert_eq!(amms_config.min_mint_decimals(), 6);
+        assert_eq!(amms_config.max_mint_decimals(), 9);
+        assert!(amms_config.validate_mint_decimals(0).is_err());
+        assert!(amms_config.validate_mint_decimals(5).is_err());
+        amms_config.validate_mint_decimals(6).unwrap();
+        amms_config.validate_mint_decimals(9).unwrap();
+        assert!(amms_config.validate_mint_decimals(10).is_err());
+        assert!(amms_config.validate_mint_decimals(18).is_err());
+
+        amms_config.update_mint_decimals_range(18, 18).unwrap();
+        amms_config.validate_mint_decimals(18).unwrap();
+        assert!(amms_config.update_mint_decimals_range(10, 9).is_err());
+        assert_eq!(amms_config.min_mint_decimals(), 18);
+        assert_eq!(amms_config.max_mint_decimals(), 18);
+    }
+
+    /// Tests the `update_protocol_rebalance` method of the `AmmsConfig` struct.
+    #[test]
+    fn test_amms_config_update_protocol_rebalance() {
+        let mut amms_config = AmmsConfig {
+            bump: 42,
+            id: 42,
+            fee_authority: Pubkey::default(),
+            providers_fee_rate_basis_points: 300,
+            protocol_fee_rate_basis_points: 200,
+            incident_withdraw_limit_basis_points: 0,
+            keeper_bounty_basis_points: 0,
+            min_provide_lp_tokens: 0,
+            min_withdraw_lp_tokens: 0,
+            min_mint_decimals: 0,
+            max_mint_decimals: u8::MAX,
+            is_protocol_rebalance_enabled: false,
+            fee_bounds: None,
+            providers_fee_ramp: None,
+            lending_adapter: None,
+            max_lend_out_basis_points: 0,
+            lend_out_interest_rate_basis_points: 0,
+            pools_count: 0,
+            loyalty_boost: None,
+            max_launch_price_orders: 0,
+            swap_constant_product_tolerance_ppb: 0,
+            adjust_liquidity_ratio_tolerance_ppb: 0,
+            fee_settlement: None,
+            max_creator_fee_share_basis_points: 0,
+            stale_pool_close_epochs: 0,
+            stale_pool_closer_share_basis_points: 0,
+            screening_program: None,
+            has_mint_equivalences: false,
+            pool_creation_pricing: None,
+            protocol_fees_cap_basis_points: 0,
+            is_pool_creation_allow_listed: false,
+            lp_concentration_share_basis_points: 0,
+            lp_concentration_guard_epochs: 0,
+            swap_origin_policy: SwapOriginPolicy::Unrestricted,
+            swap_origin_policy_until_slot: 0,
+            layout_version: AmmsConfig::LAYOUT_VERSION,
+        };
+
+        amms_config.update_protocol_rebalance(true);
+        assert!(amms_config.is_protocol_rebalance_enabled);
+        assert!(amms_config.is_protocol_rebalance_enabled());
+        amms_config.update_protocol_rebalance(false);
+        assert!(!amms_config.is_protocol_rebalance_enabled());
+    }
+
+    /// Tests the `update_pool_creation_allow_list` method of the `AmmsConfig` struct.
+    #[test]
+    fn test_amms_config_update_pool_creation_allow_list() {
+        let mut amms_config = AmmsConfig {
+            bump: 42,
+            id: 42,
+            fee_authority: Pubkey::default(),
+            providers_fee_rate_basis_points: 300,
+            protocol_fee_rate_basis_points: 200,
+            incident_withdraw_limit_basis_points: 0,
+            keeper_bounty_basis_points: 0,
+            min_provide_lp_tokens: 0,
+            min_withdraw_lp_tokens: 0,
+            min_mint_decimals: 0,
+            max_mint_decimals: u8::MAX,
+            is_protocol_rebalance_enabled: false,
+            fee_bounds: None,
+            providers_fee_ramp: None,
+            lending_adapter: None,
+            max_lend_out_basis_points: 0,
+            lend_out_interest_rate_basis_points: 0,
+            pools_count: 0,
+            loyalty_boost: None,
+            max_launch_price_orders: 0,
+            swap_constant_product_tolerance_ppb: 0,
+            adjust_liquidity_ratio_tolerance_ppb: 0,
+            fee_settlement: None,
+            max_creator_fee_share_basis_points: 0,
+            stale_pool_close_epochs: 0,
+            stale_pool_closer_share_basis_points: 0,
+            screening_program: None,
+            has_mint_equivalences: false,
+            pool_creation_pricing: None,
+            protocol_fees_cap_basis_points: 0,
+            is_pool_creation_allow_listed: false,
+            lp_concentration_share_basis_points: 0,
+            lp_concentration_guard_epochs: 0,
+            swap_origin_policy: SwapOriginPolicy::Unrestricted,
+            swap_origin_policy_until_slot: 0,
+            layout_version: AmmsConfig::LAYOUT_VERSION,
+        };
+
+        assert!(!amms_config.is_pool_creation_allow_listed());
+        amms_config.update_pool_creation_allow_list(true);
+        assert!(amms_config.is_pool_creation_allow_listed());
+        amms_config.update_pool_creation_allow_list(false);
+        assert!(!amms_config.is_pool_creation_allow_listed());
+    }
+
+    /// Tests the `update_lp_concentration` method of the `AmmsConfig` struct.
+    #[test]
+    fn test_amms_config_update_lp_concentration() {
+        let mut amms_config = AmmsConfig {
+            bump: 42,
+            id: 42,
+            fee_authority: Pubkey::default(),
+            providers_fee_rate_basis_points: 300,
+            protocol_fee_rate_basis_points: 200,
+            incident_withdraw_limit_basis_points: 0,
+            keeper_bounty_basis_points: 0,
+            min_provide_lp_tokens: 0,
+            min_withdraw_lp_tokens: 0,
+            min_mint_decimals: 0,
+            max_mint_decimals: u8::MAX,
+            is_protocol_rebalance_enabled: false,
+            fee_bounds: None,
+            providers_fee_ramp: None,
+            lending_adapter: None,
+            max_lend_out_basis_points: 0,
+            lend_out_interest_rate_basis_points: 0,
+            pools_count: 0,
+            loyalty_boost: None,
+            max_launch_price_orders: 0,
+            swap_constant_product_tolerance_ppb: 0,
+            adjust_liquidity_ratio_tolerance_ppb: 0,
+            fee_settlement: None,
+            max_creator_fee_share_basis_points: 0,
+            stale_pool_close_epochs: 0,
+            stale_pool_closer_share_basis_points: 0,
+            screening_program: None,
+            has_mint_equivalences: false,
+            pool_creation_pricing: None,
+            protocol_fees_cap_basis_points: 0,
+            is_pool_creation_allow_listed: false,
+            lp_concentration_share_basis_points: 0,
+            lp_concentration_guard_epochs: 0,
+            swap_origin_policy: SwapOriginPolicy::Unrestricted,
+            swap_origin_policy_until_slot: 0,
+            layout_version: AmmsConfig::LAYOUT_VERSION,
+        };
+
+        amms_config.update_lp_concentration(2_000, 7).unwrap();
+        assert_eq!(amms_config.lp_concentration_share_basis_points(), 2_000);
+        assert_eq!(amms_config.lp_concentration_guard_epochs(), 7);
+
+        assert_eq!(amms_config.update_lp_concentration(10001, 7).err(), Some(ErrorCode::ConfigLpConcentrationShareExceeded.into()));
+        assert_eq!(amms_config.lp_concentration_share_basis_points(), 2_000);
+
+        amms_config.update_lp_concentration(10000, 0).unwrap();
+        assert_eq!(amms_config.lp_concentration_share_basis_points(), 10000);
+        assert_eq!(amms_config.lp_concentration_guard_epochs(), 0);
+    }
+
+    /// Tests the `update_screening_program` method of the `AmmsConfig` struct.
+    #[test]
+    fn test_amms_config_update_screening_program() {
+        let mut amms_config = AmmsConfig {
+            bump: 42,
+            id: 42,
+            fee_authority: Pubkey::default(),
+            providers_fee_rate_basis_points: 300,
+            protocol_fee_rate_basis_points: 200,
+            incident_withdraw_limit_basis_points: 0,
+            keeper_bounty_basis_points: 0,
+            min_provide_lp_tokens: 0,
+            min_withdraw_lp_tokens: 0,
+            min_mint_decimals: 0,
+            max_mint_decimals: u8::MAX,
+            is_protocol_rebalance_enabled: false,
+            fee_bounds: None,
+            providers_fee_ramp: None,
+            lending_adapter: None,
+            max_lend_out_basis_points: 0,
+            lend_out_interest_rate_basis_points: 0,
+            pools_count: 0,
+            loyalty_boost: None,
+            max_launch_price_orders: 0,
+            swap_constant_product_tolerance_ppb: 0,
+            adjust_liquidity_ratio_tolerance_ppb: 0,
+            fee_settlement: None,
+            max_creator_fee_share_basis_points: 0,
+            stale_pool_close_epochs: 0,
+            stale_pool_closer_share_basis_points: 0,
+            screening_program: None,
+            has_mint_equivalences: false,
+            pool_creation_pricing: None,
+            protocol_fees_cap_basis_points: 0,
+            is_pool_creation_allow_listed: false,
+            lp_concentration_share_basis_points: 0,
+            lp_concentration_guard_epochs: 0,
+            swap_origin_policy: SwapOriginPolicy::Unrestricted,
+            swap_origin_policy_until_slot: 0,
+            layout_version: AmmsConfig::LAYOUT_VERSION,
+        };
+        let screening_program = Pubkey::new_unique();
+
+        amms_config.update_screening_program(Some(screening_program));
+        assert_eq!(amms_config.screening_program, Some(screening_program));
+        assert_eq!(amms_config.screening_program(), Some(&screening_program));
+
+        amms_config.update_screening_program(None);
+        assert_eq!(amms_config.screening_program(), None);
+    }
+
+    /// Tests the `enable_mint_equivalences` method of the `AmmsConfig` struct.
+    #[test]
+    fn test_amms_config_enable_mint_equivalences() {
+        let mut amms_config = AmmsConfig {
+            bump: 42,
+            id: 42,
+            fee_authority: Pubkey::default(),
+            providers_fee_rate_basis_points: 300,
+            protocol_fee_rate_basis_points: 200,
+            incident_withdraw_limit_basis_points: 0,
+            keeper_bounty_basis_points: 0,
+            min_provide_lp_tokens: 0,
+            min_withdraw_lp_tokens: 0,
+            min_mint_decimals: 0,
+            max_mint_decimals: u8::MAX,
+            is_protocol_rebalance_enabled: false,
+            fee_bounds: None,
+            providers_fee_ramp: None,
+            lending_adapter: None,
+            max_lend_out_basis_points: 0,
+            lend_out_interest_rate_basis_points: 0,
+            pools_count: 0,
+            loyalty_boost: None,
+            max_launch_price_orders: 0,
+            swap_constant_product_tolerance_ppb: 0,
+            adjust_liquidity_ratio_tolerance_ppb: 0,
+            fee_settlement: None,
+            max_creator_fee_share_basis_points: 0,
+            stale_pool_close_epochs: 0,
+            stale_pool_closer_share_basis_points: 0,
+            screening_program: None,
+            has_mint_equivalences: false,
+            pool_creation_pricing: None,
+            protocol_fees_cap_basis_points: 0,
+            is_pool_creation_allow_listed: false,
+            lp_concentration_share_basis_points: 0,
+            lp_concentration_guard_epochs: 0,
+            swap_origin_policy: SwapOriginPolicy::Unrestricted,
+            swap_origin_policy_until_slot: 0,
+            layout_version: AmmsConfig::LAYOUT_VERSION,
+        };
+        assert!(!amms_config.has_mint_equivalences());
+
+        amms_config.enable_mint_equivalences();
+        assert!(amms_config.has_mint_equivalences());
+    }
+
+    /// Tests the `update_fee_bounds` method and bounded fee rates updates of the `AmmsConfig` struct.
+    #[test]
+    fn test_amms_config_update_fee_bounds() {
+        let mut amms_config = AmmsConfig {
+            bump: 42,
+            id: 42,
+            fee_authority: Pubkey::default(),
+            providers_fee_rate_basis_points: 300,
+            protocol_fee_rate_basis_points: 200,
+            incident_withdraw_limit_basis_points: 0,
+            keeper_bounty_basis_points: 0,
+            min_provide_lp_tokens: 0,
+            min_withdraw_lp_tokens: 0,
+            min_mint_decimals: 0,
+            max_mint_decimals: u8::MAX,
+            is_protocol_rebalance_enabled: false,
+            fee_bounds: None,
+            providers_fee_ramp: None,
+            lending_adapter: None,
+            max_lend_out_basis_points: 0,
+            lend_out_interest_rate_basis_points: 0,
+            pools_count: 0,
+            loyalty_boost: None,
+            max_launch_price_orders: 0,
+            swap_constant_product_tolerance_ppb: 0,
+            adjust_liquidity_ratio_tolerance_ppb: 0,
+            fee_settlement: None,
+            max_creator_fee_share_basis_points: 0,
+            stale_pool_close_epochs: 0,
+            stale_pool_closer_share_basis_points: 0,
+            screening_program: None,
+            has_mint_equivalences: false,
+            pool_creation_pricing: None,
+            protocol_fees_cap_basis_points: 0,
+            is_pool_creation_allow_listed: false,
+            lp_concentration_share_basis_points: 0,
+            lp_concentration_guard_epochs: 0,
+            swap_origin_policy: SwapOriginPolicy::Unrestricted,
+            swap_origin_policy_until_slot: 0,
+            layout_version: AmmsConfig::LAYOUT_VERSION,
+        };
+
+        // Current rates must fit into the new bounds
+        assert_eq!(amms_config.update_fee_bounds(Some(FeeBounds::try_new(0, 299, 200).unwrap())).err(), Some(ErrorCode::ConfigFeeRateOutOfBounds.into()));
+        assert_eq!(amms_config.update_fee_bounds(Some(FeeBounds::try_new(0, 300, 199).unwrap())).err(), Some(ErrorCode::ConfigFeeRateOutOfBounds.into()));
+        assert_eq!(amms_config.fee_bounds(), None);
+        assert_eq!(amms_config.max_protocol_fee_commitment_basis_points(), 200);
+
+        let fee_bounds = FeeBounds::try_new(100, 500, 250).unwrap();
+        amms_config.update_fee_bounds(Some(fee_bounds)).unwrap();
+        assert_eq!(amms_config.fee_bounds(), Some(&fee_bounds));
+        assert_eq!(amms_config.max_protocol_fee_commitment_basis_points(), 250);
+
+        assert_eq!(amms_config.update_providers_fee_rate(99).err(), Some(ErrorCode::ConfigFeeRateOutOfBounds.into()));
+        assert_eq!(amms_config.update_providers_fee_rate(501).err(), Some(ErrorCode::ConfigFeeRateOutOfBounds.into()));
+        amms_config.update_providers_fee_rate(500).unwrap();
+        assert_eq!(amms_config.update_protocol_fee_rate(251).err(), Some(ErrorCode::ConfigFeeRateOutOfBounds.into()));
+        amms_config.update_protocol_fee_rate(250).unwrap();
+        assert_eq!(amms_config.providers_fee_rate_basis_points(), 500);
+        assert_eq!(amms_config.protocol_fee_rate_basis_points(), 250);
+
+        amms_config.update_fee_bounds(None).unwrap();
+        amms_config.update_protocol_fee_rate(1000).unwrap();
+        assert_eq!(amms_config.protocol_fee_rate_basis_points(), 1000);
+    }
+
+    /// Tests the launch price bound in whole tokens.
+    #[test]
+    fn test_amms_config_validate_launch_price() {
+        let mut amms_config = AmmsConfig {
+            bump: 42,
+            id: 42,
+            fee_authority: Pubkey::default(),
+            providers_fee_rate_basis_points: 300,
+            protocol_fee_rate_basis_points: 200,
+            incident_withdraw_limit_basis_points: 0,
+            keeper_bounty_basis_points: 0,
+            min_provide_lp_tokens: 0,
+            min_withdraw_lp_tokens: 0,
+            min_mint_decimals: 0,
+            max_mint_decimals: u8::MAX,
+            is_protocol_rebalance_enabled: false,
+            fee_bounds: None,
+            providers_fee_ramp: None,
+            lending_adapter: None,
+            max_lend_out_basis_points: 0,
+            lend_out_interest_rate_basis_points: 0,
+            pools_count: 0,
+            loyalty_boost: None,
+            max_launch_price_orders: 0,
+            swap_constant_product_tolerance_ppb: 0,
+            adjust_liquidity_ratio_tolerance_ppb: 0,
+            fee_settlement: None,
+            max_creator_fee_share_basis_points: 0,
+            stale_pool_close_epochs: 0,
+            stale_pool_closer_share_basis_points: 0,
+            screening_program: None,
+            has_mint_equivalences: false,
+            pool_creation_pricing: None,
+            protocol_fees_cap_basis_points: 0,
+            is_pool_creation_allow_listed: false,
+            lp_concentration_share_basis_points: 0,
+            lp_concentration_guard_epochs: 0,
+            swap_origin_policy: SwapOriginPolicy::Unrestricted,
+            swap_origin_policy_until_slot: 0,
+            layout_version: AmmsConfig::LAYOUT_VERSION,
+        };
+        assert!(amms_config.validate_launch_price(1, u64::MAX, 0, 0).is_ok());
+
+        amms_config.update_max_launch_price_orders(3);
+        assert_eq!(amms_config.max_launch_price_orders(), 3);
+
+        // 1 base token of 6 decimals for 1000 quote tokens of 9 decimals
+        assert!(amms_config.validate_launch_price(1_000_000, 1_000_000_000_000, 6, 9).is_ok());
+        assert_eq!(amms_config.validate_launch_price(1_000_000, 1_000_000_000_001, 6, 9).err(), Some(ErrorCode::LaunchPriceOutOfBounds.into()));
+        // 1000 base tokens for 1 quote token
+        assert!(amms_config.validate_launch_price(1_000_000_000, 1_000_000_000, 6, 9).is_ok());
+        assert_eq!(amms_config.validate_launch_price(1_000_000_001, 1_000_000_000, 6, 9).err(), Some(ErrorCode::LaunchPriceOutOfBounds.into()));
+        // Scaling beyond u128 doesn't overflow
+        assert!(amms_config.validate_launch_price(u64::MAX, u64::MAX, 250, 250).is_ok());
+        assert_eq!(amms_config.validate_launch_price(1, u64::MAX, 0, 250).err(), Some(ErrorCode::LaunchPriceOutOfBounds.into()));
+        assert_eq!(amms_config.validate_launch_price(1, 1, 0, 250).err(), Some(ErrorCode::LaunchPriceOutOfBounds.into()));
+    }
+
+    /// Tests updating the invariant tolerances within their bounds.
+    #[test]
+    fn test_amms_config_update_tolerances() {
+        let mut amms_config = AmmsConfig {
+            bump: 42,
+            id: 42,
+            fee_authority: Pubkey::default(),
+            providers_fee_rate_basis_points: 300,
+            protocol_fee_rate_basis_points: 200,
+            incident_withdraw_limit_basis_points: 0,
+            keeper_bounty_basis_points: 0,
+            min_provide_lp_tokens: 0,
+            min_withdraw_lp_tokens: 0,
+            min_mint_decimals: 0,
+            max_mint_decimals: u8::MAX,
+            is_protocol_rebalance_enabled: false,
+            fee_bounds: None,
+            providers_fee_ramp: None,
+            lending_adapter: None,
+            max_lend_out_basis_points: 0,
+            lend_out_interest_rate_basis_points: 0,
+            pools_count: 0,
+            loyalty_boost: None,
+            max_launch_price_orders: 0,
+            swap_constant_product_tolerance_ppb: AmmsConfig::DEFAULT_SWAP_CONSTANT_PRODUCT_TOLERANCE_PPB,
+            adjust_liquidity_ratio_tolerance_ppb: AmmsConfig::DEFAULT_ADJUST_LIQUIDITY_RATIO_TOLERANCE_PPB,
+            fee_settlement: None,
+            max_creator_fee_share_basis_points: 0,
+            stale_pool_close_epochs: 0,
+            stale_pool_closer_share_basis_points: 0,
+            screening_program: None,
+            has_mint_equivalences: false,
+            pool_creation_pricing: None,
+            protocol_fees_cap_basis_points: 0,
+            is_pool_creation_allow_listed: false,
+            lp_concentration_share_basis_points: 0,
+            lp_concentration_guard_epochs: 0,
+            swap_origin_policy: SwapOriginPolicy::Unrestricted,
+            swap_origin_policy_until_slot: 0,
+            layout_version: AmmsConfig::LAYOUT_VERSION,
+        };
+
+        amms_config.update_tolerances(100_000, 10).unwrap();
+        assert_eq!(amms_config.swap_constant_product_tolerance_ppb(), 100_000);
+        assert_eq!(amms_config.adjust_liquidity_ratio_tolerance_ppb(), 10);
+        assert_eq!(amms_config.swap_constant_product_tolerance(), Q64_128::ONE.checked_div(Q64_128::from_u64(10_000)).unwrap());
+        assert_eq!(amms_config.adjust_liquidity_ratio_tolerance(), Q64_128::ONE.checked_div(Q64_128::from_u64(100_000_000)).unwrap());
+
+        for (swap_tolerance_ppb, adjust_tolerance_ppb) in [(99, 100), (100_001, 100), (1_000, 9), (1_000, 10_001), (0, 0)] {
+            assert_eq!(
+                amms_config.update_tolerances(swap_tolerance_ppb, adjust_tolerance_ppb).err(),
+                Some(ErrorCode::ConfigTolerancesOutOfBounds.into())
+            );
+        }
+        assert_eq!(amms_config.swap_constant_product_tolerance_ppb(), 100_000);
+        assert_eq!(amms_config.adjust_liquidity_ratio_tolerance_ppb(), 10);
+    }
+
+    /// Tests updating the lending adapter and its limits.
+    #[test]
+    fn test_amms_config_update_lending() {
+        let mut amms_config = AmmsConfig {
+            bump: 42,
+            id: 42,
+            fee_authority: Pubkey::default(),
+            providers_fee_rate_basis_points: 300,
+            protocol_fee_rate_basis_points: 200,
+            incident_withdraw_limit_basis_points: 0,
+            keeper_bounty_basis_points: 0,
+            min_provide_lp_tokens: 0,
+            min_withdraw_lp_tokens: 0,
+            min_mint_decimals: 0,
+            max_mint_decimals: u8::MAX,
+            is_protocol_rebalance_enabled: false,
+            fee_bounds: None,
+            providers_fee_ramp: None,
+            lending_adapter: None,
+            max_lend_out_basis_points: 0,
+            lend_out_interest_rate_basis_points: 0,
+            pools_count: 0,
+            loyalty_boost: None,
+            max_launch_price_orders: 0,
+            swap_constant_product_tolerance_ppb: 0,
+            adjust_liquidity_ratio_tolerance_ppb: 0,
+            fee_settlement: None,
+            max_creator_fee_share_basis_points: 0,
+            stale_pool_close_epochs: 0,
+            stale_pool_closer_share_basis_points: 0,
+            screening_program: None,
+            has_mint_equivalences: false,
+            pool_creation_pricing: None,
+            protocol_fees_cap_basis_points: 0,
+            is_pool_creation_allow_listed: false,
+            lp_concentration_share_basis_points: 0,
+            lp_concentration_guard_epochs: 0,
+            swap_origin_policy: SwapOriginPolicy::Unrestricted,
+            swap_origin_policy_until_slot: 0,
+            layout_version: AmmsConfig::LAYOUT_VERSION,
+        };
+        let lending_adapter = Pubkey::new_unique();
+
+        amms_config.update_lending(Some(lending_adapter), AmmsConfig::MAX_LEND_OUT_BASIS_POINTS, 10000).unwrap();
+        assert_eq!(amms_config.lending_adapter(), Some(&lending_adapter));
+        assert_eq!(amms_config.max_lend_out_basis_points(), AmmsConfig::MAX_LEND_OUT_BASIS_POINTS);
+        assert_eq!(amms_config.lend_out_interest_rate_basis_points(), 10000);
+
+        assert_eq!(
+            amms_config.update_lending(None, AmmsConfig::MAX_LEND_OUT_BASIS_POINTS + 1, 500).err(),
+            Some(ErrorCode::ConfigLendOutLimitExceeded.into())
+        );
+        assert_eq!(
+            amms_config.update_lending(None, 1000, 10001).err(),
+            Some(ErrorCode::ConfigLendOutInterestRateExceeded.into())
+        );
+        assert_eq!(amms_config.lending_adapter(), Some(&lending_adapter));
+
+        amms_config.update_lending(None, 0, 0).unwrap();
+        assert_eq!(amms_config.lending_adapter(), None);
+    }
+
+    /// Tests updating the maximal creator fee share.
+    #[test]
+    fn test_amms_config_update_max_creator_fee_share() {
+        let mut amms_config = AmmsConfig {
+            bump: 42,
+            id: 42,
+            fee_authority: Pubkey::default(),
+            providers_fee_rate_basis_points: 300,
+            protocol_fee_rate_basis_points: 200,
+            incident_withdraw_limit_basis_points: 0,
+            keeper_bounty_basis_points: 0,
+            min_provide_lp_tokens: 0,
+            min_withdraw_lp_tokens: 0,
+            min_mint_decimals: 0,
+            max_mint_decimals: u8::MAX,
+            is_protocol_rebalance_enabled: false,
+            fee_bounds: None,
+            providers_fee_ramp: None,
+            lending_adapter: None,
+            max_lend_out_basis_points: 0,
+            lend_out_interest_rate_basis_points: 0,
+            pools_count: 0,
+            loyalty_boost: None,
+            max_launch_price_orders: 0,
+            swap_constant_product_tolerance_ppb: 0,
+            adjust_liquidity_ratio_tolerance_ppb: 0,
+            fee_settlement: None,
+            max_creator_fee_share_basis_points: 0,
+            stale_pool_close_epochs: 0,
+            stale_pool_closer_share_basis_points: 0,
+            screening_program: None,
+            has_mint_equivalences: false,
+            pool_creation_pricing: None,
+            protocol_fees_cap_basis_points: 0,
+            is_pool_creation_allow_listed: false,
+            lp_concentration_share_basis_points: 0,
+            lp_concentration_guard_epochs: 0,
+            swap_origin_policy: SwapOriginPolicy::Unrestricted,
+            swap_origin_policy_until_slot: 0,
+            layout_version: AmmsConfig::LAYOUT_VERSION,
+        };
+
+        amms_config.update_max_creator_fee_share(AmmsConfig::MAX_CREATOR_FEE_SHARE_BASIS_POINTS).unwrap();
+        assert_eq!(amms_config.max_creator_fee_share_basis_points(), AmmsConfig::MAX_CREATOR_FEE_SHARE_BASIS_POINTS);
+
+        assert_eq!(
+            amms_config.update_max_creator_fee_share(AmmsConfig::MAX_CREATOR_FEE_SHARE_BASIS_POINTS + 1).err(),
+            Some(ErrorCode::ConfigCreatorFeeShareExceeded.into())
+        );
+        assert_eq!(amms_config.max_creator_fee_share_basis_points(), AmmsConfig::MAX_CREATOR_FEE_SHARE_BASIS_POINTS);
+    }
+
+    /// Tests updating the stale pool closing terms.
+    #[test]
+    fn test_amms_config_update_stale_pool_close() {
+        let mut amms_config = AmmsConfig {
+            bump: 42,
+            id: 42,
+            fee_authority: Pubkey::default(),
+            providers_fee_rate_basis_points: 300,
+            protocol_fee_rate_basis_points: 200,
+            incident_withdraw_limit_basis_points: 0,
+            keeper_bounty_basis_points: 0,
+            min_provide_lp_tokens: 0,
+            min_withdraw_lp_tokens: 0,
+            min_mint_decimals: 0,
+            max_mint_decimals: u8::MAX,
+            is_protocol_rebalance_enabled: false,
+            fee_bounds: None,
+            providers_fee_ramp: None,
+            lending_adapter: None,
+            max_lend_out_basis_points: 0,
+            lend_out_interest_rate_basis_points: 0,
+            pools_count: 0,
+            loyalty_boost: None,
+            max_launch_price_orders: 0,
+            swap_constant_product_tolerance_ppb: 0,
+            adjust_liquidity_ratio_tolerance_ppb: 0,
+            fee_settlement: None,
+            max_creator_fee_share_basis_points: 0,
+            stale_pool_close_epochs: 0,
+            stale_pool_closer_share_basis_points: 0,
+            screening_program: None,
+            has_mint_equivalences: false,
+            pool_creation_pricing: None,
+            protocol_fees_cap_basis_points: 0,
+            is_pool_creation_allow_listed: false,
+            lp_concentration_share_basis_points: 0,
+            lp_concentration_guard_epochs: 0,
+            swap_origin_policy: SwapOriginPolicy::Unrestricted,
+            swap_origin_policy_until_slot: 0,
+            layout_version: AmmsConfig::LAYOUT_VERSION,
+        };
+
+        amms_config.update_stale_pool_close(30, 10000).unwrap();
+        assert_eq!(amms_config.stale_pool_close_epochs(), 30);
+        assert_eq!(amms_config.stale_pool_closer_share_basis_points(), 10000);
+
+        assert_eq!(
+            amms_config.update_stale_pool_close(10, 10001).err(),
+            Some(ErrorCode::ConfigStalePoolCloserShareExceeded.into())
+        );
+        assert_eq!(amms_config.stale_pool_close_epochs(), 30);
+
+        amms_config.update_stale_pool_close(0, 0).unwrap();
+        assert_eq!(amms_config.stale_pool_close_epochs(), 0);
+    }
+
+    /// Tests scheduling providers fee rate ramps and the effective rate along them.
+    #[test]
+    fn test_amms_config_schedule_providers_fee_ramp() {
+        let mut amms_config = AmmsConfig {
+            bump: 42,
+            id: 42,
+            fee_authority: Pubkey::default(),
+            providers_fee_rate_basis_points: 100,
+            protocol_fee_rate_basis_points: 200,
+            incident_withdraw_limit_basis_points: 0,
+            keeper_bounty_basis_points: 0,
+            min_provide_lp_tokens: 0,
+            min_withdraw_lp_tokens: 0,
+            min_mint_decimals: 0,
+            max_mint_decimals: u8::MAX,
+            is_protocol_rebalance_enabled: false,
+            fee_bounds: Some(FeeBounds::try_new(0, 500, 10000).unwrap()),
+            providers_fee_ramp: None,
+            lending_adapter: None,
+            max_lend_out_basis_points: 0,
+            lend_out_interest_rate_basis_points: 0,
+            pools_count: 0,
+            loyalty_boost: None,
+            max_launch_price_orders: 0,
+            swap_constant_product_tolerance_ppb: 0,
+            adjust_liquidity_ratio_tolerance_ppb: 0,
+            fee_settlement: None,
+            max_creator_fee_share_basis_points: 0,
+            stale_pool_close_epochs: 0,
+            stale_pool_closer_share_basis_points: 0,
+            screening_program: None,
+            has_mint_equivalences: false,
+            pool_creation_pricing: None,
+            protocol_fees_cap_basis_points: 0,
+            is_pool_creation_allow_listed: false,
+            lp_concentration_share_basis_points: 0,
+            lp_concentration_guard_epochs: 0,
+            swap_origin_policy: SwapOriginPolicy::Unrestricted,
+            swap_origin_policy_until_slot: 0,
+            layout_version: AmmsConfig::LAYOUT_VERSION,
+        };
+        assert_eq!(amms_config.providers_fee_rate_basis_points_at(0), 100);
+
+        assert_eq!(amms_config.schedule_providers_fee_ramp(1000, 501, 100).err(), Some(ErrorCode::ConfigFeeRateOutOfBounds.into()));
+        assert_eq!(amms_config.schedule_providers_fee_ramp(1000, 300, 0).err(), Some(ErrorCode::ConfigFeeRampDurationIsZero.into()));
+        assert_eq!(amms_config.providers_fee_ramp(), None);
+
+        amms_config.schedule_providers_fee_ramp(1000, 300, 100).unwrap();
+        assert_eq!(amms_config.providers_fee_rate_basis_points(), 300);
+        assert_eq!(amms_config.providers_fee_rate_basis_points_at(1000), 100);
+        assert_eq!(amms_config.providers_fee_rate_basis_points_at(1050), 200);
+        assert_eq!(amms_config.providers_fee_rate_basis_points_at(1100), 300);
+
+        // Rescheduling mid-ramp continues from the effective rate
+        amms_config.schedule_providers_fee_ramp(1050, 0, 200).unwrap();
+        assert_eq!(amms_config.providers_fee_ramp(), Some(&FeeRamp::try_new(1050, 200, 200).unwrap()));
+        assert_eq!(amms_config.providers_fee_rate_basis_points_at(1150), 100);
+        assert_eq!(amms_config.providers_fee_rate_basis_points_at(1250), 0);
+
+        // The protocol fee rate must fit with the ramp start rate as well
+        assert_eq!(amms_config.update_protocol_fee_rate(9801).err(), Some(ErrorCode::ConfigFeeRateExceeded.into()));
+        amms_config.update_protocol_fee_rate(9000).unwrap();
+
+        // An immediate update cancels the ramp
+        amms_config.update_providers_fee_rate(50).unwrap();
+        assert_eq!(amms_config.providers_fee_ramp(), None);
+        assert_eq!(amms_config.providers_fee_rate_basis_points_at(1100), 50);
+    }
+
+    /// Tests pools counting and the resulting `PoolIndex` bucket.
+    #[test]
+    fn test_amms_config_increment_pools_count() {
+        let mut amms_config = AmmsConfig {
+            bump: 42,
+            id: 42,
+            fee_authority: Pubkey::default(),
+            providers_fee_rate_basis_points: 100,
+            protocol_fee_rate_basis_points: 200,
+            incident_withdraw_limit_basis_points: 0,
+            keeper_bounty_basis_points: 0,
+            min_provide_lp_tokens: 0,
+            min_withdraw_lp_tokens: 0,
+            min_mint_decimals: 0,
+            max_mint_decimals: u8::MAX,
+            is_protocol_rebalance_enabled: false,
+            fee_bounds: None,
+            providers_fee_ramp: None,
+            lending_adapter: None,
+            max_lend_out_basis_points: 0,
+            lend_out_interest_rate_basis_points: 0,
+            pools_count: 0,
+            loyalty_boost: None,
+            max_launch_price_orders: 0,
+            swap_constant_product_tolerance_ppb: 0,
+            adjust_liquidity_ratio_tolerance_ppb: 0,
+            fee_settlement: None,
+            max_creator_fee_share_basis_points: 0,
+            stale_pool_close_epochs: 0,
+            stale_pool_closer_share_basis_points: 0,
+            screening_program: None,
+            has_mint_equivalences: false,
+            pool_creation_pricing: None,
+            protocol_fees_cap_basis_points: 0,
+            is_pool_creation_allow_listed: false,
+            lp_concentration_share_basis_points: 0,
+            lp_concentration_guard_epochs: 0,
+            swap_origin_policy: SwapOriginPolicy::Unrestricted,
+            swap_origin_policy_until_slot: 0,
+            layout_version: AmmsConfig::LAYOUT_VERSION,
+        };
+        assert_eq!(amms_config.pools_count(), 0);
+        assert_eq!(amms_config.pool_index_bucket(), 0);
+
+        for _ in 0..PoolIndex::CAPACITY - 1 {
+            amms_config.increment_pools_count();
+        }
+        assert_eq!(amms_config.pools_count(), PoolIndex::CAPACITY as u64 - 1);
+        assert_eq!(amms_config.pool_index_bucket(), 0);
+
+        amms_config.increment_pools_count();
+        assert_eq!(amms_config.pools_count(), PoolIndex::CAPACITY as u64);
+        assert_eq!(amms_config.pool_index_bucket(), 1);
+    }
+
+    /// Tests the `CpAmm` creation price with and without a config pricing.
+    #[test]
+    fn test_amms_config_update_pool_creation_pricing() {
+        let mut amms_config = AmmsConfig {
+            bump: 42,
+            id: 42,
+            fee_authority: Pubkey::default(),
+            providers_fee_rate_basis_points: 100,
+            protocol_fee_rate_basis_points: 200,
+            incident_withdraw_limit_basis_points: 0,
+            keeper_bounty_basis_points: 0,
+            min_provide_lp_tokens: 0,
+            min_withdraw_lp_tokens: 0,
+            min_mint_decimals: 0,
+            max_mint_decimals: u8::MAX,
+            is_protocol_rebalance_enabled: false,
+            fee_bounds: None,
+            providers_fee_ramp: None,
+            lending_adapter: None,
+            max_lend_out_basis_points: 0,
+            lend_out_interest_rate_basis_points: 0,
+            pools_count: 0,
+            loyalty_boost: None,
+            max_launch_price_orders: 0,
+            swap_constant_product_tolerance_ppb: 0,
+            adjust_liquidity_ratio_tolerance_ppb: 0,
+            fee_settlement: None,
+            max_creator_fee_share_basis_points: 0,
+            stale_pool_close_epochs: 0,
+            stale_pool_closer_share_basis_points: 0,
+            screening_program: None,
+            has_mint_equivalences: false,
+            pool_creation_pricing: None,
+            protocol_fees_cap_basis_points: 0,
+            is_pool_creation_allow_listed: false,
+            lp_concentration_share_basis_points: 0,
+            lp_concentration_guard_epochs: 0,
+            swap_origin_policy: SwapOriginPolicy::Unrestricted,
+            swap_origin_policy_until_slot: 0,
+            layout_version: AmmsConfig::LAYOUT_VERSION,
+        };
+        assert_eq!(amms_config.pool_creation_pricing(), None);
+        assert_eq!(amms_config.cp_amm_initialize_price_in_lamports(), CP_AMM_INITIALIZE_PRICE_IN_LAMPORTS);
+
+        let pool_creation_pricing = PoolCreationPricing::try_new(1_000, 2, 500, 2_000).unwrap();
+        amms_config.update_pool_creation_pricing(Some(pool_creation_pricing));
+        assert_eq!(amms_config.pool_creation_pricing(), Some(&pool_creation_pricing));
+        assert_eq!(amms_config.cp_amm_initialize_price_in_lamports(), 1_000);
+
+        amms_config.increment_pools_count();
+        amms_config.increment_pools_count();
+        assert_eq!(amms_config.cp_amm_initialize_price_in_lamports(), 1_500);
+
+        amms_config.update_pool_creation_pricing(None);
+        assert_eq!(amms_config.cp_amm_initialize_price_in_lamports(), CP_AMM_INITIALIZE_PRICE_IN_LAMPORTS);
+    }
+
+    /// Tests the `update_swap_origin_policy` method of the `AmmsConfig` struct.
+    #[test]
+    fn test_amms_config_update_swap_origin_policy() {
+        let mut amms_config = AmmsConfig {
+            bump: 42,
+            id: 42,
+            fee_authority: Pubkey::default(),
+            providers_fee_rate_basis_points: 300,
+            protocol_fee_rate_basis_points: 200,
+            incident_withdraw_limit_basis_points: 0,
+            keeper_bounty_basis_points: 0,
+            min_provide_lp_tokens: 0,
+            min_withdraw_lp_tokens: 0,
+            min_mint_decimals: 0,
+            max_mint_decimals: 0,
+            is_protocol_rebalance_enabled: false,
+            fee_bounds: None,
+            providers_fee_ramp: None,
+            lending_adapter: None,
+            max_lend_out_basis_points: 0,
+            lend_out_interest_rate_basis_points: 0,
+            pools_count: 0,
+            loyalty_boost: None,
+            max_launch_price_orders: 0,
+            swap_constant_product_tolerance_ppb: 0,
+            adjust_liquidity_ratio_tolerance_ppb: 0,
+            fee_settlement: None,
+            max_creator_fee_share_basis_points: 0,
+            stale_pool_close_epochs: 0,
+            stale_pool_closer_share_basis_points: 0,
+            screening_program: None,
+            has_mint_equivalences: false,
+            pool_creation_pricing: None,
+            protocol_fees_cap_basis_points: 0,
+            is_pool_creation_allow_listed: false,
+            lp_concentration_share_basis_points: 0,
+            lp_concentration_guard_epochs: 0,
+            swap_origin_policy: SwapOriginPolicy::Unrestricted,
+            swap_origin_policy_until_slot: 0,
+            layout_version: AmmsConfig::LAYOUT_VERSION,
+        };
+
+        amms_config.update_swap_origin_policy(SwapOriginPolicy::DirectOnly, 1_000, 5_000).unwrap();
+        assert_eq!(amms_config.swap_origin_policy_until_slot(), 6_000);
+        assert_eq!(amms_config.swap_origin_policy_at(5_000), SwapOriginPolicy::DirectOnly);
+        assert_eq!(amms_config.swap_origin_policy_at(5_999), SwapOriginPolicy::DirectOnly);
+        assert_eq!(amms_config.swap_origin_policy_at(6_000), SwapOriginPolicy::Unrestricted);
+
+        amms_config.update_swap_origin_policy(SwapOriginPolicy::CpiOnly, AmmsConfig::MAX_SWAP_ORIGIN_POLICY_SLOTS, 5_500).unwrap();
+        assert_eq!(amms_config.swap_origin_policy_at(5_500), SwapOriginPolicy::CpiOnly);
+        assert_eq!(
+            amms_config.update_swap_origin_policy(SwapOriginPolicy::DirectOnly, AmmsConfig::MAX_SWAP_ORIGIN_POLICY_SLOTS + 1, 5_500).unwrap_err(),
+            ErrorCode::SwapOriginPolicySlotsExceeded.into()
+        );
+        assert_eq!(amms_config.swap_origin_policy_at(5_500), SwapOriginPolicy::CpiOnly);
+
+        // Either an unrestricted policy or zero slots lift the policy
+        amms_config.update_swap_origin_policy(SwapOriginPolicy::Unrestricted, 1_000, 6_000).unwrap();
+        assert_eq!(amms_config.swap_origin_policy_at(6_000), SwapOriginPolicy::Unrestricted);
+        assert_eq!(amms_config.swap_origin_policy_until_slot(), 0);
+        amms_config.update_swap_origin_policy(SwapOriginPolicy::DirectOnly, 1_000, 6_000).unwrap();
+        amms_config.update_swap_origin_policy(SwapOriginPolicy::DirectOnly, 0, 6_000).unwrap();
+        assert_eq!(amms_config.swap_origin_policy_at(6_000), SwapOriginPolicy::Unrestricted);
+    }
+    
+
     /// Tests `AmmsConfig` account data layout.
     #[test]
     fn test_amms_config_data_layout() {
@@ -235,8 +2435,39 @@ mod amms_config_tests {
         let id = 42u64;
         let providers_fee_rate_basis_points: u16 = 200;
         let protocol_fee_rate_basis_points: u16 = 300;
+        let incident_withdraw_limit_basis_points: u16 = 500;
+        let keeper_bounty_basis_points: u16 = 50;
+        let min_provide_lp_tokens: u64 = 1000;
+        let min_withdraw_lp_tokens: u64 = 500;
+        let min_mint_decimals: u8 = 6;
+        let max_mint_decimals: u8 = 18;
+        let is_protocol_rebalance_enabled = true;
+        let fee_bounds = FeeBounds::try_new(10, 500, 100).unwrap();
+        let providers_fee_ramp = FeeRamp::try_new(1000, 500, 150).unwrap();
+        let lending_adapter = Pubkey::new_unique();
+        let max_lend_out_basis_points: u16 = 2000;
+        let lend_out_interest_rate_basis_points: u16 = 800;
+        let pools_count: u64 = 77;
+        let loyalty_boost = LoyaltyBoost::try_new(3600, 7200, 15000).unwrap();
+        let max_launch_price_orders: u8 = 9;
+        let swap_constant_product_tolerance_ppb: u32 = 2_000;
+        let adjust_liquidity_ratio_tolerance_ppb: u32 = 300;
+        let settlement_mint = Pubkey::new_unique();
+        let fee_settlement = FeeSettlement::try_new(Some(settlement_mint), 100).unwrap();
+        let max_creator_fee_share_basis_points: u16 = 2_500;
+        let stale_pool_close_epochs: u16 = 30;
+        let stale_pool_closer_share_basis_points: u16 = 1_000;
+        let screening_program = Pubkey::new_unique();
+        let pool_creation_pricing = PoolCreationPricing::try_new(100_000_000, 50, 10_000_000, 500_000_000).unwrap();
+        let protocol_fees_cap_basis_points: u16 = 1_500;
+        let is_pool_creation_allow_listed = true;
+        let lp_concentration_share_basis_points: u16 = 2_000;
+        let lp_concentration_guard_epochs: u16 = 7;
+        let swap_origin_policy = SwapOriginPolicy::CpiOnly;
+        let swap_origin_policy_until_slot = 12_000u64;
+        let layout_version = AmmsConfig::LAYOUT_VERSION;
 
-        let mut data = [0u8; ANCHOR_DISCRIMINATOR + 45];
+        let mut data = [0u8; ANCHOR_DISCRIMINATOR + 281];
         let mut offset = 0;
 
         data[offset..offset + ANCHOR_DISCRIMINATOR].copy_from_slice(&AmmsConfig::discriminator()); offset += ANCHOR_DISCRIMINATOR;
@@ -245,6 +2476,55 @@ mod amms_config_tests {
         data[offset..offset + 32].copy_from_slice(fee_authority.as_ref()); offset += 32;
         data[offset..offset + 2].copy_from_slice(&providers_fee_rate_basis_points.to_le_bytes()); offset += 2;
         data[offset..offset + 2].copy_from_slice(&protocol_fee_rate_basis_points.to_le_bytes()); offset += 2;
+        data[offset..offset + 2].copy_from_slice(&incident_withdraw_limit_basis_points.to_le_bytes()); offset += 2;
+        data[offset..offset + 2].copy_from_slice(&keeper_bounty_basis_points.to_le_bytes()); offset += 2;
+        data[offset..offset + 8].copy_from_slice(&min_provide_lp_tokens.to_le_bytes()); offset += 8;
+        data[offset..offset + 8].copy_from_slice(&min_withdraw_lp_tokens.to_le_bytes()); offset += 8;
+        data[offset..offset + 1].copy_from_slice(&min_mint_decimals.to_le_bytes()); offset += 1;
+        data[offset..offset + 1].copy_from_slice(&max_mint_decimals.to_le_bytes()); offset += 1;
+        data[offset] = is_protocol_rebalance_enabled as u8; offset += 1;
+        data[offset] = 1; offset += 1;
+        data[offset..offset + 2].copy_from_slice(&fee_bounds.min_providers_fee_rate_basis_points().to_le_bytes()); offset += 2;
+        data[offset..offset + 2].copy_from_slice(&fee_bounds.max_providers_fee_rate_basis_points().to_le_bytes()); offset += 2;
+        data[offset..offset + 2].copy_from_slice(&fee_bounds.max_protocol_fee_rate_basis_points().to_le_bytes()); offset += 2;
+        data[offset] = 1; offset += 1;
+        data[offset..offset + 8].copy_from_slice(&providers_fee_ramp.start_slot().to_le_bytes()); offset += 8;
+        data[offset..offset + 8].copy_from_slice(&providers_fee_ramp.end_slot().to_le_bytes()); offset += 8;
+        data[offset..offset + 2].copy_from_slice(&providers_fee_ramp.start_fee_rate_basis_points().to_le_bytes()); offset += 2;
+        data[offset] = 1; offset += 1;
+        data[offset..offset + 32].copy_from_slice(lending_adapter.as_ref()); offset += 32;
+        data[offset..offset + 2].copy_from_slice(&max_lend_out_basis_points.to_le_bytes()); offset += 2;
+        data[offset..offset + 2].copy_from_slice(&lend_out_interest_rate_basis_points.to_le_bytes()); offset += 2;
+        data[offset..offset + 8].copy_from_slice(&pools_count.to_le_bytes()); offset += 8;
+        data[offset] = 1; offset += 1;
+        data[offset..offset + 4].copy_from_slice(&loyalty_boost.min_hold_seconds().to_le_bytes()); offset += 4;
+        data[offset..offset + 4].copy_from_slice(&loyalty_boost.full_boost_hold_seconds().to_le_bytes()); offset += 4;
+        data[offset..offset + 2].copy_from_slice(&loyalty_boost.max_multiplier_basis_points().to_le_bytes()); offset += 2;
+        data[offset] = max_launch_price_orders; offset += 1;
+        data[offset..offset + 4].copy_from_slice(&swap_constant_product_tolerance_ppb.to_le_bytes()); offset += 4;
+        data[offset..offset + 4].copy_from_slice(&adjust_liquidity_ratio_tolerance_ppb.to_le_bytes()); offset += 4;
+        data[offset] = 1; offset += 1;
+        data[offset] = 1; offset += 1;
+        data[offset..offset + 32].copy_from_slice(settlement_mint.as_ref()); offset += 32;
+        data[offset..offset + 2].copy_from_slice(&fee_settlement.max_impact_basis_points().to_le_bytes()); offset += 2;
+        data[offset..offset + 2].copy_from_slice(&max_creator_fee_share_basis_points.to_le_bytes()); offset += 2;
+        data[offset..offset + 2].copy_from_slice(&stale_pool_close_epochs.to_le_bytes()); offset += 2;
+        data[offset..offset + 2].copy_from_slice(&stale_pool_closer_share_basis_points.to_le_bytes()); offset += 2;
+        data[offset] = 1; offset += 1;
+        data[offset..offset + 32].copy_from_slice(screening_program.as_ref()); offset += 32;
+        data[offset] = 1; offset += 1;
+        data[offset] = 1; offset += 1;
+        data[offset..offset + 8].copy_from_slice(&pool_creation_pricing.base_price_in_lamports().to_le_bytes()); offset += 8;
+        data[offset..offset + 4].copy_from_slice(&pool_creation_pricing.pools_per_step().to_le_bytes()); offset += 4;
+        data[offset..offset + 8].copy_from_slice(&pool_creation_pricing.step_price_in_lamports().to_le_bytes()); offset += 8;
+        data[offset..offset + 8].copy_from_slice(&pool_creation_pricing.max_price_in_lamports().to_le_bytes()); offset += 8;
+        data[offset..offset + 2].copy_from_slice(&protocol_fees_cap_basis_points.to_le_bytes()); offset += 2;
+        data[offset] = is_pool_creation_allow_listed as u8; offset += 1;
+        data[offset..offset + 2].copy_from_slice(&lp_concentration_share_basis_points.to_le_bytes()); offset += 2;
+        data[offset..offset + 2].copy_from_slice(&lp_concentration_guard_epochs.to_le_bytes()); offset += 2;
+        data[offset] = swap_origin_policy as u8; offset += 1;
+        data[offset..offset + 8].copy_from_slice(&swap_origin_policy_until_slot.to_le_bytes()); offset += 8;
+        data[offset] = layout_version; offset += 1;
 
         assert_eq!(ANCHOR_DISCRIMINATOR + AmmsConfig::INIT_SPACE, offset);
         
@@ -255,9 +2535,97 @@ mod amms_config_tests {
         assert_eq!(deserialized_amms_config.fee_authority, fee_authority);
         assert_eq!(deserialized_amms_config.providers_fee_rate_basis_points, providers_fee_rate_basis_points);
         assert_eq!(deserialized_amms_config.protocol_fee_rate_basis_points, protocol_fee_rate_basis_points);
+        assert_eq!(deserialized_amms_config.incident_withdraw_limit_basis_points, incident_withdraw_limit_basis_points);
+        assert_eq!(deserialized_amms_config.keeper_bounty_basis_points, keeper_bounty_basis_points);
+        assert_eq!(deserialized_amms_config.min_provide_lp_tokens, min_provide_lp_tokens);
+        assert_eq!(deserialized_amms_config.min_withdraw_lp_tokens, min_withdraw_lp_tokens);
+        assert_eq!(deserialized_amms_config.min_mint_decimals, min_mint_decimals);
+        assert_eq!(deserialized_amms_config.max_mint_decimals, max_mint_decimals);
+        assert_eq!(deserialized_amms_config.is_protocol_rebalance_enabled, is_protocol_rebalance_enabled);
+        assert_eq!(deserialized_amms_config.fee_bounds, Some(fee_bounds));
+        assert_eq!(deserialized_amms_config.providers_fee_ramp, Some(providers_fee_ramp));
+        assert_eq!(deserialized_amms_config.lending_adapter, Some(lending_adapter));
+        assert_eq!(deserialized_amms_config.max_lend_out_basis_points, max_lend_out_basis_points);
+        assert_eq!(deserialized_amms_config.lend_out_interest_rate_basis_points, lend_out_interest_rate_basis_points);
+        assert_eq!(deserialized_amms_config.pools_count, pools_count);
+        assert_eq!(deserialized_amms_config.loyalty_boost, Some(loyalty_boost));
+        assert_eq!(deserialized_amms_config.max_launch_price_orders, max_launch_price_orders);
+        assert_eq!(deserialized_amms_config.swap_constant_product_tolerance_ppb, swap_constant_product_tolerance_ppb);
+        assert_eq!(deserialized_amms_config.adjust_liquidity_ratio_tolerance_ppb, adjust_liquidity_ratio_tolerance_ppb);
+        assert_eq!(deserialized_amms_config.fee_settlement, Some(fee_settlement));
+        assert_eq!(deserialized_amms_config.max_creator_fee_share_basis_points, max_creator_fee_share_basis_points);
+        assert_eq!(deserialized_amms_config.stale_pool_close_epochs, stale_pool_close_epochs);
+        assert_eq!(deserialized_amms_config.stale_pool_closer_share_basis_points, stale_pool_closer_share_basis_points);
+        assert_eq!(deserialized_amms_config.screening_program, Some(screening_program));
+        assert!(deserialized_amms_config.has_mint_equivalences);
+        assert_eq!(deserialized_amms_config.pool_creation_pricing, Some(pool_creation_pricing));
+        assert_eq!(deserialized_amms_config.protocol_fees_cap_basis_points, protocol_fees_cap_basis_points);
+        assert_eq!(deserialized_amms_config.is_pool_creation_allow_listed, is_pool_creation_allow_listed);
+        assert_eq!(deserialized_amms_config.lp_concentration_share_basis_points, lp_concentration_share_basis_points);
+        assert_eq!(deserialized_amms_config.lp_concentration_guard_epochs, lp_concentration_guard_epochs);
+        assert_eq!(deserialized_amms_config.swap_origin_policy, swap_origin_policy);
+        assert_eq!(deserialized_amms_config.swap_origin_policy_until_slot, swap_origin_policy_until_slot);
+        assert_eq!(deserialized_amms_config.layout_version, layout_version);
 
         let mut serialized_amms_config = Vec::new();
         deserialized_amms_config.try_serialize(&mut serialized_amms_config).unwrap();
         assert_eq!(serialized_amms_config.as_slice(), data.as_ref());
     }
+
+    /// Tests the upgrade of `AmmsConfig` accounts written with the initial layout, which ends after the fee rates.
+    #[test]
+    fn test_amms_config_upgrade_initial_layout() {
+        let space = ANCHOR_DISCRIMINATOR + AmmsConfig::INIT_SPACE;
+        let fee_authority = Pubkey::new_unique();
+        let id = 42u64;
+
+        let mut initial_data = vec![0u8; AmmsConfig::INITIAL_SPACE];
+        let mut offset = 0;
+        initial_data[offset..offset + ANCHOR_DISCRIMINATOR].copy_from_slice(&AmmsConfig::discriminator()); offset += ANCHOR_DISCRIMINATOR;
+        initial_data[offset] = 254; offset += 1;
+        initial_data[offset..offset + 8].copy_from_slice(&id.to_le_bytes()); offset += 8;
+        initial_data[offset..offset + 32].copy_from_slice(fee_authority.as_ref()); offset += 32;
+        initial_data[offset..offset + 2].copy_from_slice(&200u16.to_le_bytes()); offset += 2;
+        initial_data[offset..offset + 2].copy_from_slice(&300u16.to_le_bytes()); offset += 2;
+        assert_eq!(offset, AmmsConfig::INITIAL_SPACE);
+
+        assert_eq!(AmmsConfig::validate_layout_upgrade(&initial_data).unwrap(), space);
+        initial_data.resize(space, 0);
+        let mut amms_config = AmmsConfig::try_deserialize(&mut initial_data.as_slice()).unwrap();
+        assert!(amms_config.assert_layout_version().is_err());
+        amms_config.upgrade_layout(AmmsConfig::INITIAL_SPACE);
+        let mut upgraded_data = Vec::new();
+        amms_config.try_serialize(&mut upgraded_data).unwrap();
+        upgraded_data.resize(space, 0);
+
+        let upgraded_amms_config = AmmsConfig::try_deserialize(&mut upgraded_data.as_slice()).unwrap();
+        assert!(upgraded_amms_config.assert_layout_version().is_ok());
+        assert_eq!(upgraded_amms_config.bump(), 254);
+        assert_eq!(upgraded_amms_config.id, id);
+        assert_eq!(upgraded_amms_config.fee_authority(), &fee_authority);
+        assert_eq!(upgraded_amms_config.providers_fee_rate_basis_points(), 200);
+        assert_eq!(upgraded_amms_config.protocol_fee_rate_basis_points(), 300);
+        assert_eq!(upgraded_amms_config.min_mint_decimals(), 0);
+        assert_eq!(upgraded_amms_config.max_mint_decimals(), u8::MAX);
+        assert_eq!(upgraded_amms_config.swap_constant_product_tolerance(), cpmm_math::cp_amm::SWAP_CONSTANT_PRODUCT_SQRT_TOLERANCE);
+        assert_eq!(upgraded_amms_config.adjust_liquidity_ratio_tolerance(), cpmm_math::cp_amm::ADJUST_LIQUIDITY_RATIO_SQRT_TOLERANCE);
+        assert_eq!(upgraded_amms_config.fee_bounds(), None);
+        assert!(!upgraded_amms_config.is_pool_creation_allow_listed());
+        assert_eq!(upgraded_amms_config.swap_origin_policy_at(0), SwapOriginPolicy::Unrestricted);
+        assert_eq!(
+            AmmsConfig::validate_layout_upgrade(&upgraded_data).err(),
+            Some(ErrorCode::ConfigLayoutUpToDate.into())
+        );
+
+        assert_eq!(
+            AmmsConfig::validate_layout_upgrade(&upgraded_data[..space - 1]).err(),
+            Some(ErrorCode::AccountVersionMismatch.into())
+        );
+        let mut foreign_data = upgraded_data.clone();
+        foreign_data[0] ^= 1;
+        assert_eq!(
+            AmmsConfig::validate_layout_upgrade(&foreign_data).err(),
+            Some(anchor_lang::error::ErrorCode::AccountDiscriminatorMismatch.into())
+        );
+    }
 }
\ No newline at end of file
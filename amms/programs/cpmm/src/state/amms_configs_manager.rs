@@ -1,5 +1,7 @@
-use anchor_lang::{account, InitSpace};
+use anchor_lang::{account, Discriminator, InitSpace};
 use anchor_lang::prelude::*;
+use crate::error::ErrorCode;
+use super::VersionedAccount;
 
 /// Represents the manager for AMM configurations.
 ///
@@ -20,12 +22,28 @@ pub struct AmmsConfigsManager {
 
     /// The canonical bump seed used for the account's PDA.
     bump: u8,   // 1 byte
+
+    /// The length of a per-creator pool creation window in slots, `0` disables the limit.
+    pool_creation_window_slots: u64, // 8 bytes
+
+    /// The number of pools a creator is allowed to create within a window.
+    max_pools_per_window: u16, // 2 bytes
+
+    /// The version of the account data layout, see `VersionedAccount`. Fields added later are appended
+    /// after it, so its offset stays stable.
+    layout_version: u8, // 1 byte
 }
 
 impl AmmsConfigsManager {
     /// The seed used to derive the account's PDA.
     pub const SEED: &'static [u8] = b"amms_configs_manager";
 
+    /// Size of accounts written before the pool creation limit was added, including the Anchor discriminator.
+    pub const INITIAL_SPACE: usize = 8 + 73;
+
+    /// Offset of `layout_version` in the account data, including the discriminator.
+    pub const LAYOUT_VERSION_OFFSET: usize = 8 + 83;
+
     /// Initializes the `AmmsConfigsManager` with the provided parameters.
     ///
     /// # Parameters
@@ -35,10 +53,15 @@ impl AmmsConfigsManager {
     ///
     /// # Behavior
     /// - Sets the initial `configs_count` to 0.
+    /// - Leaves the pool creation limit disabled.
     /// - Updates the authority and head authority fields with the provided values.
+    /// - Stores the current layout version.
     pub(crate) fn initialize(&mut self, authority: Pubkey, head_authority: Pubkey, bump: u8) {
+        self.layout_version = Self::LAYOUT_VERSION;
         self.bump = bump;
         self.configs_count = 0;
+        self.pool_creation_window_slots = 0;
+        self.max_pools_per_window = 0;
         self.update_authority(authority);
         self.update_head_authority(head_authority);
     }
@@ -60,6 +83,66 @@ impl AmmsConfigsManager {
         self.head_authority = head_authority;
     }
 
+    /// Updates the per-creator pool creation limit.
+    ///
+    /// # Parameters
+    /// - `pool_creation_window_slots`: The length of a creation window in slots, `0` disables the limit.
+    /// - `max_pools_per_window`: The number of pools a creator is allowed to create within a window.
+    ///
+    /// # Errors
+    /// - Returns `ErrorCode::ManagerPoolCreationLimitInvalid` if the window is set but no pools are allowed.
+    pub(crate) fn update_pool_creation_limit(&mut self, pool_creation_window_slots: u64, max_pools_per_window: u16) -> Result<()> {
+        require!(pool_creation_window_slots == 0 || max_pools_per_window > 0, ErrorCode::ManagerPoolCreationLimitInvalid);
+        self.pool_creation_window_slots = pool_creation_window_slots;
+        self.max_pools_per_window = max_pools_per_window;
+        Ok(())
+    }
+
+    /// Validates that an `AmmsConfigsManager` account written by an older program build can be upgraded
+    /// to the current layout by `authority`.
+    ///
+    /// New fields are appended to the end of the layout and their zero values leave the pool creation limit
+    /// disabled, so an account is upgraded by growing it to the current size
+    /// with zero-initialized bytes and storing the current layout version.
+    ///
+    /// # Parameters
+    /// - `data`: The raw account data, including the Anchor discriminator.
+    /// - `authority`: The signer requesting the upgrade, either the authority or the head authority.
+    ///
+    /// # Returns
+    /// - The size of the account in the current layout, including the Anchor discriminator.
+    ///
+    /// # Errors
+    /// - `AccountDiscriminatorMismatch` if the data isn't an `AmmsConfigsManager` account.
+    /// - `AccountVersionMismatch` if the account was written by a newer program build or its size matches
+    ///   no layout.
+    /// - `ManagerLayoutUpToDate` if the account already has the current size and layout version.
+    /// - `ConstraintRaw` if `authority` is neither the authority nor the head authority.
+    pub fn validate_layout_upgrade(data: &[u8], authority: &Pubkey) -> Result<usize> {
+        let space = 8 + Self::INIT_SPACE;
+        require!(data.len() > 8 && data[..8] == Self::DISCRIMINATOR, anchor_lang::error::ErrorCode::AccountDiscriminatorMismatch);
+        require!(data.len() >= Self::INITIAL_SPACE && data.len() <= space, ErrorCode::AccountVersionMismatch);
+        if data.len() == space {
+            let layout_version = data[Self::LAYOUT_VERSION_OFFSET];
+            require!(layout_version <= Self::LAYOUT_VERSION, ErrorCode::AccountVersionMismatch);
+            require!(layout_version < Self::LAYOUT_VERSION, ErrorCode::ManagerLayoutUpToDate);
+        }
+        require!(
+            data[8..40] == authority.to_bytes() || data[40..72] == authority.to_bytes(),
+            anchor_lang::error::ErrorCode::ConstraintRaw
+        );
+        Ok(space)
+    }
+
+    /// Stores the current layout version in an `AmmsConfigsManager` account grown to the current size
+    /// by `validate_layout_upgrade`.
+    ///
+    /// # Parameters
+    /// - `data`: The raw account data, including the Anchor discriminator.
+    pub fn upgrade_layout(data: &mut [u8]) {
+        data[Self::LAYOUT_VERSION_OFFSET] = Self::LAYOUT_VERSION;
+    }
+
     /// Increments the `configs_count` field by 1.
     ///
     /// # Behavior
@@ -104,6 +187,36 @@ impl AmmsConfigsManager {
     pub fn bump(&self) -> u8 {
         self.bump
     }
+
+    /// Retrieves the length of a per-creator pool creation window in slots.
+    ///
+    /// # Returns
+    /// - A `u64` value, `0` if the pool creation limit is disabled.
+    #[inline]
+    pub fn pool_creation_window_slots(&self) -> u64 {
+        self.pool_creation_window_slots
+    }
+
+    /// Retrieves the number of pools a creator is allowed to create within a window.
+    ///
+    /// # Returns
+    /// - A `u16` value representing the pools limit.
+    #[inline]
+    pub fn max_pools_per_window(&self) -> u16 {
+        self.max_pools_per_window
+    }
+
+
+}
+
+/// Implements the `VersionedAccount` trait for the `AmmsConfigsManager` struct.
+impl VersionedAccount for AmmsConfigsManager {
+    const LAYOUT_VERSION: u8 = 1;
+
+    #[inline]
+    fn layout_version(&self) -> u8 {
+        self.layout_version
+    }
 }
 
 #[cfg(test)]
@@ -120,6 +233,9 @@ mod amms_configs_manager_tests {
             head_authority: Pubkey::default(),
             configs_count: 0,
             bump: 0,
+            pool_creation_window_slots: 100,
+            max_pools_per_window: 5,
+            layout_version: 0,
         };
 
         let authority = Pubkey::new_unique();
@@ -132,11 +248,16 @@ mod amms_configs_manager_tests {
         assert_eq!(manager.head_authority, head_authority);
         assert_eq!(manager.configs_count, 0);
         assert_eq!(manager.bump, bump);
+        assert_eq!(manager.pool_creation_window_slots, 0);
+        assert_eq!(manager.max_pools_per_window, 0);
 
         assert_eq!(manager.authority().key(), authority);
         assert_eq!(manager.head_authority().key(), head_authority);
         assert_eq!(manager.configs_count(), 0);
         assert_eq!(manager.bump(), bump);
+        assert_eq!(manager.pool_creation_window_slots(), 0);
+        assert_eq!(manager.max_pools_per_window(), 0);
+        assert_eq!(manager.layout_version(), AmmsConfigsManager::LAYOUT_VERSION);
     }
     
     /// Tests the `update_authority` method of the `AmmsConfigsManager` struct.
@@ -147,6 +268,9 @@ mod amms_configs_manager_tests {
             head_authority: Pubkey::new_unique(),
             configs_count: 10,
             bump: 42,
+            pool_creation_window_slots: 0,
+            max_pools_per_window: 0,
+            layout_version: AmmsConfigsManager::LAYOUT_VERSION,
         };
 
         let new_authority = Pubkey::new_unique();
@@ -163,6 +287,9 @@ mod amms_configs_manager_tests {
             head_authority: Pubkey::default(),
             configs_count: 10,
             bump: 42,
+            pool_creation_window_slots: 0,
+            max_pools_per_window: 0,
+            layout_version: AmmsConfigsManager::LAYOUT_VERSION,
         };
 
         let new_head_authority = Pubkey::new_unique();
@@ -179,13 +306,103 @@ mod amms_configs_manager_tests {
             head_authority: Pubkey::new_unique(),
             configs_count: 5,
             bump: 42,
+            pool_creation_window_slots: 0,
+            max_pools_per_window: 0,
+            layout_version: AmmsConfigsManager::LAYOUT_VERSION,
         };
 
         manager.increment_configs_count();
 
         assert_eq!(manager.configs_count, 6);
     }
-    
+
+    /// Tests the upgrade of `AmmsConfigsManager` accounts written before the pool creation limit was added.
+    #[test]
+    fn test_amms_configs_manager_validate_layout_upgrade() {
+        let space = ANCHOR_DISCRIMINATOR + AmmsConfigsManager::INIT_SPACE;
+        let authority = Pubkey::new_unique();
+        let head_authority = Pubkey::new_unique();
+        let manager = AmmsConfigsManager {
+            authority,
+            head_authority,
+            configs_count: 7,
+            bump: 42,
+            pool_creation_window_slots: 100,
+            max_pools_per_window: 5,
+            layout_version: AmmsConfigsManager::LAYOUT_VERSION,
+        };
+        let mut data = Vec::new();
+        manager.try_serialize(&mut data).unwrap();
+        assert_eq!(data.len(), space);
+        assert_eq!(
+            AmmsConfigsManager::validate_layout_upgrade(&data, &authority).err(),
+            Some(ErrorCode::ManagerLayoutUpToDate.into())
+        );
+
+        let mut initial_data = data[..AmmsConfigsManager::INITIAL_SPACE].to_vec();
+        assert_eq!(AmmsConfigsManager::validate_layout_upgrade(&initial_data, &authority).unwrap(), space);
+        assert_eq!(AmmsConfigsManager::validate_layout_upgrade(&initial_data, &head_authority).unwrap(), space);
+        assert_eq!(
+            AmmsConfigsManager::validate_layout_upgrade(&initial_data, &Pubkey::new_unique()).err(),
+            Some(anchor_lang::error::ErrorCode::ConstraintRaw.into())
+        );
+        initial_data.resize(space, 0);
+        AmmsConfigsManager::upgrade_layout(&mut initial_data);
+        let upgraded_manager = AmmsConfigsManager::try_deserialize(&mut initial_data.as_slice()).unwrap();
+        assert_eq!(upgraded_manager.authority(), &authority);
+        assert_eq!(upgraded_manager.head_authority(), &head_authority);
+        assert_eq!(upgraded_manager.configs_count(), 7);
+        assert_eq!(upgraded_manager.bump(), 42);
+        assert_eq!(upgraded_manager.pool_creation_window_slots(), 0);
+        assert_eq!(upgraded_manager.max_pools_per_window(), 0);
+        assert!(upgraded_manager.assert_layout_version().is_ok());
+
+        let mut outdated_data = data.clone();
+        outdated_data[AmmsConfigsManager::LAYOUT_VERSION_OFFSET] = AmmsConfigsManager::LAYOUT_VERSION - 1;
+        assert_eq!(AmmsConfigsManager::validate_layout_upgrade(&outdated_data, &authority).unwrap(), space);
+        let mut future_data = data.clone();
+        future_data[AmmsConfigsManager::LAYOUT_VERSION_OFFSET] = AmmsConfigsManager::LAYOUT_VERSION + 1;
+        assert_eq!(
+            AmmsConfigsManager::validate_layout_upgrade(&future_data, &authority).err(),
+            Some(ErrorCode::AccountVersionMismatch.into())
+        );
+
+        assert_eq!(
+            AmmsConfigsManager::validate_layout_upgrade(&data[..AmmsConfigsManager::INITIAL_SPACE - 1], &authority).err(),
+            Some(ErrorCode::AccountVersionMismatch.into())
+        );
+        let mut foreign_data = data.clone();
+        foreign_data[0] ^= 1;
+        assert_eq!(
+            AmmsConfigsManager::validate_layout_upgrade(&foreign_data, &authority).err(),
+            Some(anchor_lang::error::ErrorCode::AccountDiscriminatorMismatch.into())
+        );
+    }
+
+    /// Tests the `update_pool_creation_limit` method of the `AmmsConfigsManager` struct.
+    #[test]
+    fn test_amms_configs_manager_update_pool_creation_limit(){
+        let mut manager = AmmsConfigsManager {
+            authority: Pubkey::new_unique(),
+            head_authority: Pubkey::new_unique(),
+            configs_count: 5,
+            bump: 42,
+            pool_creation_window_slots: 0,
+            max_pools_per_window: 0,
+            layout_version: AmmsConfigsManager::LAYOUT_VERSION,
+        };
+
+        manager.update_pool_creation_limit(9000, 3).unwrap();
+        assert_eq!(manager.pool_creation_window_slots(), 9000);
+        assert_eq!(manager.max_pools_per_window(), 3);
+
+        assert!(manager.update_pool_creation_limit(9000, 0).is_err());
+        assert_eq!(manager.max_pools_per_window(), 3);
+
+        manager.update_pool_creation_limit(0, 0).unwrap();
+        assert_eq!(manager.pool_creation_window_slots(), 0);
+    }
+
     /// Tests the `update_head_authority` method of the `AmmsConfigsManager` struct.
     #[test]
     fn test_amms_configs_manager_data_layout() {
@@ -193,8 +410,11 @@ mod amms_configs_manager_tests {
         let head_authority = Pubkey::new_unique();
         let configs_count = 42u64;
         let bump = 42u8;
+        let pool_creation_window_slots = 9000u64;
+        let max_pools_per_window = 3u16;
+        let layout_version = AmmsConfigsManager::LAYOUT_VERSION;
 
-        let mut data = [0u8; ANCHOR_DISCRIMINATOR + 73];
+        let mut data = [0u8; ANCHOR_DISCRIMINATOR + 84];
         let mut offset = 0;
 
         data[offset..offset + ANCHOR_DISCRIMINATOR].copy_from_slice(&AmmsConfigsManager::discriminator()); offset += ANCHOR_DISCRIMINATOR;
@@ -202,8 +422,13 @@ mod amms_configs_manager_tests {
         data[offset..offset + 32].copy_from_slice(head_authority.as_ref()); offset += 32;
         data[offset..offset + 8].copy_from_slice(&configs_count.to_le_bytes()); offset += 8;
         data[offset] = bump; offset += 1;
+        data[offset..offset + 8].copy_from_slice(&pool_creation_window_slots.to_le_bytes()); offset += 8;
+        data[offset..offset + 2].copy_from_slice(&max_pools_per_window.to_le_bytes()); offset += 2;
+        assert_eq!(offset, AmmsConfigsManager::LAYOUT_VERSION_OFFSET);
+        data[offset] = layout_version; offset += 1;
 
-        assert_eq!(offset, ANCHOR_DISCRIMINATOR + 73);
+        assert_eq!(offset, ANCHOR_DISCRIMINATOR + 84);
+        assert_eq!(offset, ANCHOR_DISCRIMINATOR + AmmsConfigsManager::INIT_SPACE);
         
         let deserialized_manager = AmmsConfigsManager::try_deserialize(&mut data.as_ref()).unwrap();
 
@@ -211,6 +436,9 @@ mod amms_configs_manager_tests {
         assert_eq!(deserialized_manager.head_authority, head_authority);
         assert_eq!(deserialized_manager.configs_count, configs_count);
         assert_eq!(deserialized_manager.bump, bump);
+        assert_eq!(deserialized_manager.pool_creation_window_slots, pool_creation_window_slots);
+        assert_eq!(deserialized_manager.max_pools_per_window, max_pools_per_window);
+        assert_eq!(deserialized_manager.layout_version, layout_version);
 
         let mut serialized_data = Vec::new();
         deserialized_manager.try_serialize(&mut serialized_data).unwrap();
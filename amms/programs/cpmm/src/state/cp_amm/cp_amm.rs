@@ -1,11 +1,13 @@
-use anchor_lang::{account, InitSpace};
+use anchor_lang::{account, Discriminator, InitSpace};
 use anchor_lang::prelude::*;
 use anchor_spl::token::{Mint};
 use anchor_spl::token_interface;
-use crate::utils::math::Q64_128;
+use cpmm_math::cp_amm::MIN_LIQUIDITY;
+use crate::utils::math::{strict_add, strict_sub, Q64_128};
 use crate::error::ErrorCode;
-use crate::state::AmmsConfig;
-use super::{CpAmmCalculate, CpAmmCore};
+use crate::state::{validate_fee_rates, AmmsConfig, FeeSettlement, VersionedAccount};
+use crate::utils::oracle::UsdPrice;
+use super::{CpAmmCalculate, CpAmmCore, Curve, CurveCalculator, CurveKind, DisplayData, DisplayEntryType, PoolStats, PoolStatus, TradingSchedule, WithdrawRateLimiter};
 
 /// Represents a Constant Product Automated Market Maker (AMM) pool.
 ///
@@ -18,11 +20,11 @@ use super::{CpAmmCalculate, CpAmmCore};
 #[account]
 #[derive(InitSpace)]
 pub struct CpAmm {
-    /// Whether the AMM has been initialized.
-    is_initialized: bool, // 1 byte
+    /// Version of the account data layout, kept first so it stays readable across layout changes.
+    layout_version: u8, // 1 byte
 
-    /// Whether the AMM has been launched and is active.
-    is_launched: bool, // 1 byte
+    /// Lifecycle status of the AMM.
+    status: PoolStatus, // 1 byte
 
     /// Canonical bump seed for the account's PDA.
     bump: [u8; 1], // 1 byte
@@ -84,7 +86,102 @@ pub struct CpAmm {
     amms_config: Pubkey, // 32 bytes
 
     /// Public key of the CpAmm creator account.
-    creator: Pubkey // 32 bytes
+    creator: Pubkey, // 32 bytes
+
+    /// Whether withdrawals are rate-limited per slot due to an ongoing incident.
+    is_incident_limited: bool, // 1 byte
+
+    /// Per-slot withdrawals accounting used while the pool is incident-limited.
+    withdraw_rate_limiter: WithdrawRateLimiter, // 24 bytes
+
+    /// Optional oracle account providing the USD price of the quote token.
+    usd_oracle: Option<Pubkey>, // 33 bytes
+
+    /// Cumulative trading statistics of the AMM not recorded on its `PoolStatsShard` accounts.
+    stats: PoolStats, // 112 bytes
+
+    /// Optional weekly trading hours outside of which swaps are rejected.
+    trading_schedule: Option<TradingSchedule>, // 22 bytes
+
+    /// Whether the lending adapter configured in the `AmmsConfig` may borrow the pool liquidity.
+    is_lend_out_enabled: bool, // 1 byte
+
+    /// Amount of base liquidity currently lent out and missing from the base vault.
+    base_lent_out: u64, // 8 bytes
+
+    /// Amount of quote liquidity currently lent out and missing from the quote vault.
+    quote_lent_out: u64, // 8 bytes
+
+    /// Optional program notified via CPI after every liquidity change of the AMM.
+    liquidity_subscriber: Option<Pubkey>, // 33 bytes
+
+    /// Optional protocol fee rate negotiated for the AMM, used instead of the `AmmsConfig` rate.
+    protocol_fee_override_basis_points: Option<u16>, // 3 bytes
+
+    /// Whether swaps are suspended, independently of the lifecycle status.
+    swaps_paused: bool, // 1 byte
+
+    /// Whether liquidity provisions are suspended, independently of the lifecycle status.
+    provides_paused: bool, // 1 byte
+
+    /// Whether liquidity withdrawals are suspended, independently of the lifecycle status.
+    withdraws_paused: bool, // 1 byte
+
+    /// The share of the providers fee routed to the pool creator, measured in basis points.
+    creator_fee_share_basis_points: u16, // 2 bytes
+
+    /// Accumulated base token fees that can be redeemed by the pool creator.
+    creator_base_fees_to_redeem: u64, // 8 bytes
+
+    /// Accumulated quote token fees that can be redeemed by the pool creator.
+    creator_quote_fees_to_redeem: u64, // 8 bytes
+
+    /// The epoch in which the AMM was initialized.
+    initialized_epoch: u64, // 8 bytes
+
+    /// Whether the per-pool fee parameters are permanently frozen by the creator.
+    is_fee_parameters_locked: bool, // 1 byte
+
+    /// Power of ten scaling the base liquidity to the pool's virtual decimal basis in the constant product math.
+    base_normalization_exponent: u8, // 1 byte
+
+    /// Power of ten scaling the quote liquidity to the pool's virtual decimal basis in the constant product math.
+    quote_normalization_exponent: u8, // 1 byte
+
+    /// The slot from which swaps are allowed, ending the provide-only warm-up after the launch.
+    /// Zero for pools launched without a warm-up.
+    trading_open_slot: u64, // 8 bytes
+
+    /// The curve pricing the liquidity of the AMM.
+    curve_kind: CurveKind, // 1 byte
+
+    /// The maximal protocol fee rate the `AmmsConfig` committed to when the AMM was initialized,
+    /// capping the protocol fee rate of swaps. `None` for AMMs initialized before the cap was recorded.
+    max_protocol_fee_basis_points_at_creation: Option<u16>, // 3 bytes
+
+    /// The slot until which the AMM is in protected mode and only accepts swaps routed through
+    /// a router approved in the `RouterRegistry`. Zero for AMMs never protected.
+    protected_until_slot: u64, // 8 bytes
+
+    /// Self-describing summary of the AMM for generic wallet UIs, refreshed by the AMM operations.
+    /// Empty for AMMs upgraded from an older layout until their next refresh.
+    display_data: DisplayData, // 160 bytes
+
+    /// The largest share of the LP tokens supply held by a provider right after a provide, measured in basis points.
+    /// The launch liquidity is not counted. Zero for AMMs upgraded from an older layout until their next provide.
+    largest_provide_share_basis_points: u16, // 2 bytes
+
+    /// Whether a provider held more than the LP concentration share of the `AmmsConfig` after a provide,
+    /// alerting traders that one LP may withdraw most of the liquidity.
+    concentration_flag: bool, // 1 byte
+
+    /// The spot price before the first liquidity change in `slot_start_price_slot`, which is the price the
+    /// previous slot with a liquidity change ended at, scaled by `VIRTUAL_PRICE_SCALE`.
+    slot_start_price: u64, // 8 bytes
+
+    /// The slot `slot_start_price` was recorded in. Zero for AMMs without a liquidity change since
+    /// the price started to be recorded.
+    slot_start_price_slot: u64, // 8 bytes
 }
 
 impl CpAmm {
@@ -94,6 +191,48 @@ impl CpAmm {
     /// Seed used for generating the vaults PDAs.
     pub const VAULT_SEED: &'static [u8] = b"vault";
 
+    /// Scale of the virtual price, which is expressed with 9 decimals.
+    pub const VIRTUAL_PRICE_SCALE: u64 = 1_000_000_000;
+
+    /// The maximal provide-only warm-up after the launch, about a day of slots.
+    pub const MAX_WARM_UP_SLOTS: u64 = 216_000;
+
+    /// The maximal protected mode window, about a week of slots.
+    pub const MAX_PROTECTED_SLOTS: u64 = 1_512_000;
+
+    /// Offset of `layout_version` in the account data, including the discriminator.
+    ///
+    /// The offsets locate the fields matched by `memcmp` filters of `getProgramAccounts`, e.g. all pools
+    /// of a config. All preceding fields have a fixed size and new fields are only appended, so the
+    /// offsets are stable across layout versions.
+    pub const LAYOUT_VERSION_OFFSET: usize = 8;
+
+    /// Offset of `base_mint` in the account data, see `LAYOUT_VERSION_OFFSET`.
+    pub const BASE_MINT_OFFSET: usize = 110;
+
+    /// Offset of `quote_mint` in the account data, see `LAYOUT_VERSION_OFFSET`.
+    pub const QUOTE_MINT_OFFSET: usize = 142;
+
+    /// Offset of `lp_mint` in the account data, see `LAYOUT_VERSION_OFFSET`.
+    pub const LP_MINT_OFFSET: usize = 174;
+
+    /// Offset of `amms_config` in the account data, see `LAYOUT_VERSION_OFFSET`.
+    pub const AMMS_CONFIG_OFFSET: usize = 302;
+
+    /// Offset of `creator` in the account data, see `LAYOUT_VERSION_OFFSET`.
+    pub const CREATOR_OFFSET: usize = 334;
+
+    /// Size of accounts written before the layout was versioned, including the Anchor discriminator.
+    ///
+    /// These accounts store the `is_initialized` and `is_launched` flags where the current layout stores
+    /// the layout version and the status, so they read as version 1 with a misplaced status.
+    pub const UNVERSIONED_SPACE: usize = 8 + 358;
+
+    /// Size of the first accounts storing `initialized_epoch`, including the Anchor discriminator.
+    ///
+    /// Smaller accounts were written before the epoch was recorded, see `upgrade_layout`.
+    pub const INITIALIZED_EPOCH_SPACE: usize = 8 + 632;
+
     /// Returns the seeds for generating the PDA.
     ///
     /// The PDA is derived using the `SEED`, the `lp_mint`, and the `bump` value.
@@ -101,16 +240,36 @@ impl CpAmm {
         [Self::SEED, self.lp_mint.as_ref(), self.bump.as_ref()]
     }
 
+    /// Returns the lifecycle status of the AMM.
+    #[inline]
+    pub fn status(&self) -> PoolStatus {
+        self.status
+    }
+
     /// Checks if the AMM has been initialized.
     #[inline]
     pub fn is_initialized(&self) -> bool {
-        self.is_initialized
+        self.status.is_initialized()
     }
 
     /// Checks if the AMM has been launched and is active.
     #[inline]
     pub fn is_launched(&self) -> bool {
-        self.is_launched
+        self.status.is_launched()
+    }
+
+    /// Checks if the launched AMM is still in its provide-only warm-up at the given slot,
+    /// in which liquidity may be provided and withdrawn, but swaps are not open yet.
+    #[inline]
+    pub fn is_warming_up(&self, slot: u64) -> bool {
+        self.status.is_launched() && slot < self.trading_open_slot
+    }
+
+    /// Checks if the AMM is in protected mode at the given slot, in which swaps must be routed
+    /// through a router approved in the `RouterRegistry`.
+    #[inline]
+    pub fn is_protected(&self, slot: u64) -> bool {
+        slot < self.protected_until_slot
     }
 
     /// Returns the canonical bump value for the PDA.
@@ -176,6 +335,365 @@ impl CpAmm {
     pub fn locked_lp_vault_bump(&self) -> u8 {
         self.locked_lp_vault_bump[0]
     }
+
+    /// Checks if withdrawals are rate-limited per slot due to an ongoing incident.
+    #[inline]
+    pub fn is_incident_limited(&self) -> bool {
+        self.is_incident_limited
+    }
+
+    /// Returns the per-slot withdrawals accounting of the AMM.
+    #[inline]
+    pub fn withdraw_rate_limiter(&self) -> &WithdrawRateLimiter {
+        &self.withdraw_rate_limiter
+    }
+
+    /// Returns the oracle account providing the USD price of the quote token, if any.
+    #[inline]
+    pub fn usd_oracle(&self) -> Option<&Pubkey> {
+        self.usd_oracle.as_ref()
+    }
+
+    /// Returns the cumulative trading statistics of the AMM not recorded on its `PoolStatsShard` accounts.
+    #[inline]
+    pub fn stats(&self) -> &PoolStats {
+        &self.stats
+    }
+
+    /// Returns the weekly trading hours of the AMM, if any.
+    #[inline]
+    pub fn trading_schedule(&self) -> Option<&TradingSchedule> {
+        self.trading_schedule.as_ref()
+    }
+
+    /// Checks if the lending adapter may borrow the pool liquidity.
+    #[inline]
+    pub fn is_lend_out_enabled(&self) -> bool {
+        self.is_lend_out_enabled
+    }
+
+    /// Returns the amount of base liquidity currently lent out.
+    #[inline]
+    pub fn base_lent_out(&self) -> u64 {
+        self.base_lent_out
+    }
+
+    /// Returns the amount of quote liquidity currently lent out.
+    #[inline]
+    pub fn quote_lent_out(&self) -> u64 {
+        self.quote_lent_out
+    }
+
+    /// Returns the program notified about liquidity changes of the AMM, if any.
+    #[inline]
+    pub fn liquidity_subscriber(&self) -> Option<&Pubkey> {
+        self.liquidity_subscriber.as_ref()
+    }
+
+    /// Returns the protocol fee rate negotiated for the AMM, if any.
+    #[inline]
+    pub fn protocol_fee_override_basis_points(&self) -> Option<u16> {
+        self.protocol_fee_override_basis_points
+    }
+
+    /// Checks if swaps are suspended.
+    #[inline]
+    pub fn swaps_paused(&self) -> bool {
+        self.swaps_paused
+    }
+
+    /// Checks if liquidity provisions are suspended.
+    #[inline]
+    pub fn provides_paused(&self) -> bool {
+        self.provides_paused
+    }
+
+    /// Checks if liquidity withdrawals are suspended.
+    #[inline]
+    pub fn withdraws_paused(&self) -> bool {
+        self.withdraws_paused
+    }
+
+    /// Returns the accumulated base token fees redeemable by the `AmmsConfig` fee authority.
+    #[inline]
+    pub fn protocol_base_fees_to_redeem(&self) -> u64 {
+        self.protocol_base_fees_to_redeem
+    }
+
+    /// Returns the accumulated quote token fees redeemable by the `AmmsConfig` fee authority.
+    #[inline]
+    pub fn protocol_quote_fees_to_redeem(&self) -> u64 {
+        self.protocol_quote_fees_to_redeem
+    }
+
+    /// Returns the share of the providers fee routed to the pool creator, in basis points.
+    #[inline]
+    pub fn creator_fee_share_basis_points(&self) -> u16 {
+        self.creator_fee_share_basis_points
+    }
+
+    /// Returns the accumulated base token fees redeemable by the pool creator.
+    #[inline]
+    pub fn creator_base_fees_to_redeem(&self) -> u64 {
+        self.creator_base_fees_to_redeem
+    }
+
+    /// Returns the accumulated quote token fees redeemable by the pool creator.
+    #[inline]
+    pub fn creator_quote_fees_to_redeem(&self) -> u64 {
+        self.creator_quote_fees_to_redeem
+    }
+
+    /// Returns the epoch in which the AMM was initialized.
+    #[inline]
+    pub fn initialized_epoch(&self) -> u64 {
+        self.initialized_epoch
+    }
+
+    /// Checks if the per-pool fee parameters are permanently frozen.
+    #[inline]
+    pub fn is_fee_parameters_locked(&self) -> bool {
+        self.is_fee_parameters_locked
+    }
+
+    /// Returns the power of ten scaling the base liquidity to the pool's virtual decimal basis.
+    #[inline]
+    pub fn base_normalization_exponent(&self) -> u8 {
+        self.base_normalization_exponent
+    }
+
+    /// Returns the power of ten scaling the quote liquidity to the pool's virtual decimal basis.
+    #[inline]
+    pub fn quote_normalization_exponent(&self) -> u8 {
+        self.quote_normalization_exponent
+    }
+
+    /// Returns the slot from which swaps are allowed, `0` if the AMM was launched without a warm-up.
+    #[inline]
+    pub fn trading_open_slot(&self) -> u64 {
+        self.trading_open_slot
+    }
+
+    /// Returns the curve pricing the liquidity of the AMM.
+    #[inline]
+    pub fn curve_kind(&self) -> CurveKind {
+        self.curve_kind
+    }
+
+    /// Returns the maximal protocol fee rate recorded when the AMM was initialized, if any.
+    #[inline]
+    pub fn max_protocol_fee_basis_points_at_creation(&self) -> Option<u16> {
+        self.max_protocol_fee_basis_points_at_creation
+    }
+
+    /// Returns the slot until which the AMM is in protected mode, zero if it was never protected.
+    #[inline]
+    pub fn protected_until_slot(&self) -> u64 {
+        self.protected_until_slot
+    }
+
+    /// Returns the self-describing summary of the AMM for generic wallet UIs.
+    #[inline]
+    pub fn display_data(&self) -> &DisplayData {
+        &self.display_data
+    }
+
+    /// Returns the largest share of the LP tokens supply held by a provider after a provide, measured in basis points.
+    #[inline]
+    pub fn largest_provide_share_basis_points(&self) -> u16 {
+        self.largest_provide_share_basis_points
+    }
+
+    /// Checks if a provider held more than the LP concentration share of the `AmmsConfig` after a provide.
+    #[inline]
+    pub fn concentration_flag(&self) -> bool {
+        self.concentration_flag
+    }
+
+    /// Returns the spot price before the first liquidity change in `slot_start_price_slot`.
+    #[inline]
+    pub fn slot_start_price(&self) -> u64 {
+        self.slot_start_price
+    }
+
+    /// Returns the slot the slot start price was recorded in.
+    #[inline]
+    pub fn slot_start_price_slot(&self) -> u64 {
+        self.slot_start_price_slot
+    }
+
+    /// Returns the spot price of the AMM, the quote liquidity per base liquidity in raw token units.
+    ///
+    /// # Returns
+    /// - The price scaled by `VIRTUAL_PRICE_SCALE` and saturated at `u64::MAX`, or zero for an AMM without base liquidity.
+    pub fn spot_price(&self) -> u64 {
+        (self.quote_liquidity as u128 * Self::VIRTUAL_PRICE_SCALE as u128)
+            .checked_div(self.base_liquidity as u128)
+            .map_or(0, |price| u64::try_from(price).unwrap_or(u64::MAX))
+    }
+
+    /// Returns the spot price the last slot before the current one ended at.
+    ///
+    /// Liquidity changes in the current slot can't move this price, so consumers requiring it to agree
+    /// with `spot_price` reject prices manipulated within a single slot without a full TWAP.
+    ///
+    /// # Parameters
+    /// - `current_slot`: The current slot.
+    ///
+    /// # Returns
+    /// - The price scaled by `VIRTUAL_PRICE_SCALE`, the spot price if the liquidity didn't change in the current slot yet.
+    pub fn last_slot_price(&self, current_slot: u64) -> u64 {
+        if current_slot == self.slot_start_price_slot {
+            self.slot_start_price
+        } else {
+            self.spot_price()
+        }
+    }
+
+    /// Returns the calculator of the AMM curve, which the pool math dispatches to.
+    #[inline]
+    pub(crate) fn curve(&self) -> Curve<'_, Self> {
+        self.curve_kind.calculator(self)
+    }
+
+    /// Returns the protocol fee rate applied to swaps in the AMM.
+    ///
+    /// # Parameters
+    /// - `config_protocol_fee_rate_basis_points`: The protocol fee rate of the associated `AmmsConfig`.
+    ///
+    /// # Returns
+    /// - The protocol fee override if set, otherwise the `AmmsConfig` rate, capped by the maximal
+    ///   protocol fee rate recorded when the AMM was initialized.
+    #[inline]
+    pub fn protocol_fee_rate_basis_points(&self, config_protocol_fee_rate_basis_points: u16) -> u16 {
+        let protocol_fee_rate_basis_points = self.protocol_fee_override_basis_points.unwrap_or(config_protocol_fee_rate_basis_points);
+        match self.max_protocol_fee_basis_points_at_creation {
+            Some(max_protocol_fee_basis_points) => protocol_fee_rate_basis_points.min(max_protocol_fee_basis_points),
+            None => protocol_fee_rate_basis_points,
+        }
+    }
+
+    /// Checks if the AMM holds protocol fees in either token that are available to collect.
+    #[inline]
+    pub fn has_protocol_fees_to_redeem(&self) -> bool {
+        self.protocol_base_fees_to_redeem > 0 || self.protocol_quote_fees_to_redeem > 0
+    }
+
+    /// Checks if swaps are allowed at the given time.
+    ///
+    /// # Parameters
+    /// - `unix_timestamp`: The current unix timestamp in seconds.
+    ///
+    /// # Returns
+    /// - `true` if the AMM has no trading schedule or the schedule is open.
+    pub fn is_market_open(&self, unix_timestamp: i64) -> bool {
+        match self.trading_schedule {
+            Some(trading_schedule) => trading_schedule.is_open(unix_timestamp),
+            None => true,
+        }
+    }
+
+    /// Validates that the AMM may be closed by anyone as a stale pool.
+    ///
+    /// A pool is stale once it stayed unlaunched for `stale_pool_close_epochs` epochs since its initialization.
+    ///
+    /// # Parameters
+    /// - `current_epoch`: The current epoch.
+    /// - `stale_pool_close_epochs`: The number of epochs set by the `AmmsConfig`, `0` disables the closing.
+    ///
+    /// # Errors
+    /// - Returns `ErrorCode::StaleCpAmmCloseDisabled` if the `AmmsConfig` disables the closing.
+    /// - Returns `ErrorCode::CpAmmAlreadyLaunched` if the pool has been launched.
+    /// - Returns `ErrorCode::CpAmmNotStale` if the pool was initialized too recently.
+    pub fn validate_stale_close(&self, current_epoch: u64, stale_pool_close_epochs: u16) -> Result<()> {
+        require!(stale_pool_close_epochs > 0, ErrorCode::StaleCpAmmCloseDisabled);
+        require!(self.status == PoolStatus::Initialized, ErrorCode::CpAmmAlreadyLaunched);
+        require!(
+            current_epoch >= self.initialized_epoch.saturating_add(stale_pool_close_epochs as u64),
+            ErrorCode::CpAmmNotStale
+        );
+        Ok(())
+    }
+
+    /// Calculates the slot from which swaps are allowed after a launch with a provide-only warm-up.
+    ///
+    /// # Parameters
+    /// - `launch_slot`: The slot of the launch.
+    /// - `warm_up_slots`: The number of slots after the launch in which only liquidity adjustments are allowed.
+    ///
+    /// # Errors
+    /// - Returns `ErrorCode::WarmUpSlotsExceeded` if the warm-up exceeds `MAX_WARM_UP_SLOTS`.
+    pub fn calculate_trading_open_slot(launch_slot: u64, warm_up_slots: u64) -> Result<u64> {
+        require!(warm_up_slots <= Self::MAX_WARM_UP_SLOTS, ErrorCode::WarmUpSlotsExceeded);
+        Ok(launch_slot.saturating_add(warm_up_slots))
+    }
+
+    /// Validates that a `CpAmm` account written by an older program build can be upgraded to the current layout.
+    ///
+    /// New fields are appended to the end of the layout, so an account is upgraded by growing it to the
+    /// current size with zero-initialized bytes and storing the current layout version.
+    ///
+    /// # Parameters
+    /// - `data`: The raw account data, including the Anchor discriminator.
+    ///
+    /// # Returns
+    /// - The size of the account in the current layout, including the Anchor discriminator.
+    ///
+    /// # Errors
+    /// - `AccountDiscriminatorMismatch` if the data isn't a `CpAmm` account.
+    /// - `AccountVersionMismatch` if the account was written by a newer program build, or is an unversioned
+    ///   account with an invalid `is_launched` flag.
+    /// - `CpAmmLayoutUpToDate` if the account already has the current size and layout version.
+    pub fn validate_layout_upgrade(data: &[u8]) -> Result<usize> {
+        let space = 8 + Self::INIT_SPACE;
+        require!(data.len() > 8 && data[..8] == Self::DISCRIMINATOR, anchor_lang::error::ErrorCode::AccountDiscriminatorMismatch);
+        let layout_version = data[8];
+        require!(layout_version <= Self::LAYOUT_VERSION && data.len() <= space, ErrorCode::AccountVersionMismatch);
+        require!(layout_version < Self::LAYOUT_VERSION || data.len() < space, ErrorCode::CpAmmLayoutUpToDate);
+        if data.len() == Self::UNVERSIONED_SPACE {
+            require!(data[9] <= 1, ErrorCode::AccountVersionMismatch);
+        }
+        Ok(space)
+    }
+
+    /// Upgrades the header of a `CpAmm` account grown to the current size by `validate_layout_upgrade`.
+    ///
+    /// Stores the current layout version and, for accounts written before the layout was versioned,
+    /// translates the `is_launched` flag into the status: `0` becomes `Initialized` and `1` becomes `Launched`.
+    ///
+    /// Never launched pools written before `initialized_epoch` was recorded get the current epoch, so
+    /// `validate_stale_close` counts the stale period from the upgrade instead of reading the zero-filled
+    /// epoch as long past.
+    ///
+    /// # Parameters
+    /// - `data`: The raw account data, including the Anchor discriminator.
+    /// - `original_len`: The size of the account before it was grown.
+    /// - `current_epoch`: The current epoch.
+    pub fn upgrade_layout(data: &mut [u8], original_len: usize, current_epoch: u64) -> Result<()> {
+        if original_len == Self::UNVERSIONED_SPACE {
+            let status = if data[9] == 1 { PoolStatus::Launched } else { PoolStatus::Initialized };
+            data[9] = status as u8;
+        }
+        data[8] = Self::LAYOUT_VERSION;
+        if original_len < Self::INITIALIZED_EPOCH_SPACE {
+            let mut cp_amm = Self::try_deserialize(&mut &data[..])?;
+            if cp_amm.status == PoolStatus::Initialized {
+                cp_amm.initialized_epoch = current_epoch;
+                cp_amm.try_serialize(&mut &mut data[..])?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Implements the `VersionedAccount` trait for the `CpAmm` struct.
+impl VersionedAccount for CpAmm {
+    const LAYOUT_VERSION: u8 = 3;
+
+    #[inline]
+    fn layout_version(&self) -> u8 {
+        self.layout_version
+    }
 }
 
 /// Implements the `CpAmmCore` trait for the `CpAmm` struct.
@@ -224,6 +742,22 @@ impl CpAmmCore for CpAmm {
     fn lp_tokens_supply(&self) -> u64 {
         self.lp_tokens_supply
     }
+
+    /// Retrieves the factor scaling the base liquidity to the pool's virtual decimal basis.
+    ///
+    /// # Returns
+    /// - A `u64` value of `10^base_normalization_exponent`.
+    fn base_liquidity_scale(&self) -> u64 {
+        10_u64.pow(self.base_normalization_exponent as u32)
+    }
+
+    /// Retrieves the factor scaling the quote liquidity to the pool's virtual decimal basis.
+    ///
+    /// # Returns
+    /// - A `u64` value of `10^quote_normalization_exponent`.
+    fn quote_liquidity_scale(&self) -> u64 {
+        10_u64.pow(self.quote_normalization_exponent as u32)
+    }
 }
 
 impl CpAmm {
@@ -239,34 +773,202 @@ impl CpAmm {
     /// - `Err(ErrorCode)` if any of the checks fail.
     #[inline]
     fn check_state(&self) -> Result<()> {
-        require!(self.is_launched, ErrorCode::CpAmmNotLaunched);
+        require!(self.status.is_launched(), ErrorCode::CpAmmNotLaunched);
         require!(self.quote_liquidity > 0, ErrorCode::BaseLiquidityIsZero);
         require!(self.base_liquidity > 0, ErrorCode::QuoteLiquidityIsZero);
         require!(self.lp_tokens_supply > 0, ErrorCode::LpTokensSupplyIsZero);
         Ok(())
     }
     
+    /// Validates that the vaults of the AMM hold at least its recorded liquidity and accrued fees.
+    ///
+    /// Complements `check_state`, which has no access to the vault balances. Lent out liquidity is
+    /// missing from the vaults by design, while donations and rounding only raise the balances, so a
+    /// balance below the recorded amounts means tokens left a vault without the AMM accounting for it,
+    /// e.g. through an exploited token program.
+    ///
+    /// # Parameters
+    /// - `base_vault_balance`: The current balance of the base vault.
+    /// - `quote_vault_balance`: The current balance of the quote vault.
+    ///
+    /// # Returns
+    /// - `Ok(())` if both vaults cover the recorded amounts.
+    /// - `Err(ErrorCode::VaultBalanceDrift)` otherwise.
+    #[inline]
+    pub fn check_vault_balances(&self, base_vault_balance: u64, quote_vault_balance: u64) -> Result<()> {
+        let (recorded_base_balance, recorded_quote_balance) = self.recorded_vault_balances();
+        require!(
+            base_vault_balance as u128 >= recorded_base_balance && quote_vault_balance as u128 >= recorded_quote_balance,
+            ErrorCode::VaultBalanceDrift
+        );
+        Ok(())
+    }
+
+    /// Calculates the balances the vaults must hold: the liquidity not lent out and the protocol
+    /// and creator fees to redeem.
+    ///
+    /// # Returns
+    /// - A tuple of the `u128` base and quote balances.
+    pub fn recorded_vault_balances(&self) -> (u128, u128) {
+        let recorded_balance = |liquidity: u64, lent_out: u64, protocol_fees: u64, creator_fees: u64| {
+            (liquidity as u128).saturating_sub(lent_out as u128) + protocol_fees as u128 + creator_fees as u128
+        };
+        (
+            recorded_balance(self.base_liquidity, self.base_lent_out, self.protocol_base_fees_to_redeem, self.creator_base_fees_to_redeem),
+            recorded_balance(self.quote_liquidity, self.quote_lent_out, self.protocol_quote_fees_to_redeem, self.creator_quote_fees_to_redeem),
+        )
+    }
+
+    /// Validates that the actual LP mint supply is covered by the tracked LP tokens supply.
+    ///
+    /// The launch mints both the launch and the initially locked liquidity, and only the AMM can mint
+    /// LP tokens, so the LP mint supply can never exceed `lp_tokens_supply` unless the accounting is broken,
+    /// in which case withdrawals would mis-split funds. The LP mint supply may be lower, because holders
+    /// can burn their LP tokens directly through the token program, which only donates their share to the pool.
+    ///
+    /// # Parameters
+    /// - `lp_mint_supply`: The current supply of the LP mint.
+    ///
+    /// # Returns
+    /// - `Ok(())` if the LP mint supply doesn't exceed the tracked supply.
+    /// - `Err(ErrorCode::LpTokensSupplyDesync)` otherwise.
+    #[inline]
+    pub fn validate_lp_tokens_supply(&self, lp_mint_supply: u64) -> Result<()> {
+        require!(lp_mint_supply <= self.lp_tokens_supply, ErrorCode::LpTokensSupplyDesync);
+        Ok(())
+    }
+
+    /// Validates that the protocol fees accrued by the AMM don't exceed the `AmmsConfig` cap.
+    ///
+    /// Uncollected protocol fees sit in the vaults next to the liquidity, so vault balances diverge from
+    /// the tradable liquidity while they grow, e.g. if the fee authority is lost. Once a side exceeds its
+    /// share of the liquidity, provides are rejected until anyone collects the fees to the fee authority,
+    /// which is rewarded by the keeper bounty. Swaps and withdrawals keep working.
+    ///
+    /// # Parameters
+    /// - `protocol_fees_cap_basis_points`: The cap of the `AmmsConfig`, zero disables the check.
+    ///
+    /// # Returns
+    /// - `Ok(())` if the cap is disabled or both sides of the protocol fees are within it.
+    /// - `Err(ErrorCode::ProtocolFeesCapExceeded)` otherwise.
+    #[inline]
+    pub fn check_protocol_fees_cap(&self, protocol_fees_cap_basis_points: u16) -> Result<()> {
+        if protocol_fees_cap_basis_points == 0 {
+            return Ok(());
+        }
+        let is_within_cap = |protocol_fees: u64, liquidity: u64| {
+            protocol_fees as u128 * 10000 <= liquidity as u128 * protocol_fees_cap_basis_points as u128
+        };
+        require!(
+            is_within_cap(self.protocol_base_fees_to_redeem, self.base_liquidity) &&
+                is_within_cap(self.protocol_quote_fees_to_redeem, self.quote_liquidity),
+            ErrorCode::ProtocolFeesCapExceeded
+        );
+        Ok(())
+    }
+
+    /// Validates that a provide doesn't concentrate the LP tokens of a young AMM in a single LP.
+    ///
+    /// During the first epochs after the initialization a provide may not leave the receiving LP token
+    /// account with more than the `AmmsConfig` share of the LP tokens supply, so one LP can't dominate, and
+    /// later drain, a pool traders have just started to rely on. Afterwards such provides only raise the
+    /// concentration flag.
+    ///
+    /// The LP tokens already held by the receiving account are counted, so splitting a deposit into several
+    /// provides doesn't bypass the guard. LP tokens spread over several accounts can't be attributed to one
+    /// LP on-chain and are counted separately.
+    ///
+    /// # Parameters
+    /// - `provide_payload`: The payload of the provide.
+    /// - `holder_lp_tokens`: The LP tokens held by the account receiving the minted ones, before the provide.
+    /// - `lp_concentration_share_basis_points`: The share of the `AmmsConfig`, zero disables the check.
+    /// - `lp_concentration_guard_epochs`: The number of epochs since the initialization the check applies for.
+    /// - `current_epoch`: The current epoch.
+    ///
+    /// # Returns
+    /// - `Ok(())` if the check is disabled or doesn't apply anymore, or the provide is within the share.
+    /// - `Err(ErrorCode::LpConcentrationExceeded)` otherwise.
+    #[inline]
+    pub fn check_lp_concentration(
+        &self,
+        provide_payload: &ProvidePayload,
+        holder_lp_tokens: u64,
+        lp_concentration_share_basis_points: u16,
+        lp_concentration_guard_epochs: u16,
+        current_epoch: u64
+    ) -> Result<()> {
+        if lp_concentration_share_basis_points == 0 || current_epoch >= self.initialized_epoch.saturating_add(lp_concentration_guard_epochs as u64) {
+            return Ok(());
+        }
+        require!(
+            provide_payload.holder_share_basis_points(holder_lp_tokens) <= lp_concentration_share_basis_points,
+            ErrorCode::LpConcentrationExceeded
+        );
+        Ok(())
+    }
+
+    /// Validates that the LP mint holds no tokens the launch wouldn't account for.
+    ///
+    /// LP tokens minted before the launch would claim a share of the launch liquidity, and a foreign
+    /// freeze authority could lock the holders' LP tokens, so both are rejected at the launch,
+    /// independently of the checks of the LP mints accepted at the initialization.
+    ///
+    /// # Parameters
+    /// - `cp_amm`: The public key of the AMM.
+    /// - `lp_mint_supply`: The current supply of the LP mint.
+    /// - `lp_mint_freeze_authority`: The freeze authority of the LP mint.
+    ///
+    /// # Returns
+    /// - `Ok(())` if the LP mint is empty and can only be frozen by the AMM.
+    /// - `Err(ErrorCode::LaunchLpMintSupplyNotZero)` if LP tokens were minted before the launch.
+    /// - `Err(ErrorCode::LaunchLpMintFreezeAuthorityInvalid)` if the freeze authority isn't unset or the AMM.
+    #[inline]
+    pub fn validate_launch_lp_mint(cp_amm: &Pubkey, lp_mint_supply: u64, lp_mint_freeze_authority: Option<Pubkey>) -> Result<()> {
+        require!(lp_mint_supply == 0, ErrorCode::LaunchLpMintSupplyNotZero);
+        require!(
+            lp_mint_freeze_authority.filter(|freeze_authority| freeze_authority != cp_amm).is_none(),
+            ErrorCode::LaunchLpMintFreezeAuthorityInvalid
+        );
+        Ok(())
+    }
+
     /// Prepares the payload for launching the AMM with the provided base and quote liquidity.
     ///
     /// It calculates the initial constant product, liquidity ratios, and the total supply of LP tokens to mint.
+    /// With decimals normalization enabled, they are calculated on the liquidity scaled to the pool's virtual decimal basis.
     ///
     /// # Parameters
     /// - `base_liquidity`: The amount of base liquidity to add during the launch.
     /// - `quote_liquidity`: The amount of quote liquidity to add during the launch.
+    /// - `base_decimals`: The decimals of the base mint.
+    /// - `quote_decimals`: The decimals of the quote mint.
+    /// - `lp_mint_decimals`: The decimals of the LP mint.
+    /// - `expected_base_quote_ratio_sqrt`: The base-to-quote ratio square root expected by the creator
+    ///   and its tolerance in basis points, if the creator asked for the check.
     ///
     /// # Returns
     /// - `Ok(LaunchPayload)` containing the calculated launch details.
+    /// - `Err(ErrorCode::LaunchRatioToleranceExceeded)` if the ratio deviates from the expected one beyond the tolerance.
     /// - `Err(ErrorCode)` if any preconditions fail or calculations encounter errors.
     #[inline(never)]
-    pub fn get_launch_payload(&self, base_liquidity: u64, quote_liquidity: u64) -> Result<LaunchPayload> {
-        require!(!self.is_launched, ErrorCode::CpAmmAlreadyLaunched);
-        require!(self.is_initialized, ErrorCode::CpAmmNotInitialized);
+    pub fn get_launch_payload(&self, base_liquidity: u64, quote_liquidity: u64, base_decimals: u8, quote_decimals: u8, lp_mint_decimals: u8, expected_base_quote_ratio_sqrt: Option<(Q64_128, u16)>) -> Result<LaunchPayload> {
+        self.status.launch()?;
         require!(base_liquidity > 0, ErrorCode::ProvidedBaseLiquidityIsZero);
         require!(quote_liquidity > 0, ErrorCode::ProvidedQuoteLiquidityIsZero);
 
-        let constant_product_sqrt = Self::calculate_constant_product_sqrt(base_liquidity, quote_liquidity).unwrap();
-        let (lp_tokens_supply, initial_locked_liquidity) = Self::calculate_launch_lp_tokens(constant_product_sqrt)?;
-        let base_quote_ratio_sqrt = Self::calculate_base_quote_ratio_sqrt(base_liquidity, quote_liquidity).unwrap();
+        let constant_product_sqrt = self.curve().calculate_invariant_sqrt(base_liquidity, quote_liquidity).ok_or(ErrorCode::ConstantProductCalculationFailed)?;
+        let initial_locked_liquidity = Self::calculate_initial_locked_lp_tokens(
+            base_decimals.saturating_add(self.base_normalization_exponent),
+            quote_decimals.saturating_add(self.quote_normalization_exponent),
+            lp_mint_decimals
+        );
+        let (lp_tokens_supply, initial_locked_liquidity) = Self::calculate_launch_lp_tokens(constant_product_sqrt, initial_locked_liquidity)?;
+        let base_quote_ratio_sqrt = self.calculate_normalized_base_quote_ratio_sqrt(base_liquidity, quote_liquidity).ok_or(ErrorCode::BaseQuoteRatioCalculationFailed)?;
+        if let Some((expected_base_quote_ratio_sqrt, tolerance_basis_points)) = expected_base_quote_ratio_sqrt {
+            // The creator expects the ratio of the liquidity amounts, not of the normalized liquidity
+            let raw_base_quote_ratio_sqrt = Self::calculate_base_quote_ratio_sqrt(base_liquidity, quote_liquidity).unwrap();
+            Self::validate_expected_base_quote_ratio_sqrt(raw_base_quote_ratio_sqrt, expected_base_quote_ratio_sqrt, tolerance_basis_points)?;
+        }
         
         Ok(LaunchPayload {
             initial_locked_liquidity,
@@ -285,23 +987,29 @@ impl CpAmm {
     /// # Parameters
     /// - `base_liquidity`: The amount of base liquidity to provide.
     /// - `quote_liquidity`: The amount of quote liquidity to provide.
+    /// - `min_lp_tokens`: The minimal amount of LP tokens the provide must mint.
+    /// - `liquidity_ratio_tolerance`: The allowed relative deviation of the liquidity ratio square root.
     ///
     /// # Returns
     /// - `Ok(ProvidePayload)` containing the updated pool state and LP tokens to mint.
+    /// - `Err(ErrorCode::CpAmmProvidesPaused)` if liquidity provisions are suspended.
+    /// - `Err(ErrorCode::ProvideLpTokensBelowMinimum)` if fewer than `min_lp_tokens` LP tokens would be minted.
     /// - `Err(ErrorCode)` if any checks fail or calculations encounter errors.
     #[inline(never)]
-    pub fn get_provide_payload(&self, base_liquidity: u64, quote_liquidity: u64) -> Result<ProvidePayload> {
+    pub fn get_provide_payload(&self, base_liquidity: u64, quote_liquidity: u64, min_lp_tokens: u64, liquidity_ratio_tolerance: Q64_128) -> Result<ProvidePayload> {
         self.check_state()?;
+        require!(!self.provides_paused, ErrorCode::CpAmmProvidesPaused);
         require!(base_liquidity > 0, ErrorCode::ProvidedBaseLiquidityIsZero);
         require!(quote_liquidity > 0, ErrorCode::ProvidedQuoteLiquidityIsZero);
 
         let new_base_liquidity = self.base_liquidity.checked_add(base_liquidity).ok_or(ErrorCode::ProvideOverflowError)?;
         let new_quote_liquidity = self.quote_liquidity.checked_add(quote_liquidity).ok_or(ErrorCode::ProvideOverflowError)?;
-        let new_base_quote_ratio_sqrt =  self.validate_and_calculate_liquidity_ratio(new_base_liquidity, new_quote_liquidity)?;
+        let new_base_quote_ratio_sqrt =  self.validate_and_calculate_liquidity_ratio(new_base_liquidity, new_quote_liquidity, liquidity_ratio_tolerance)?;
 
-        let new_constant_product_sqrt = Self::calculate_constant_product_sqrt(new_base_liquidity, new_quote_liquidity).unwrap();
+        let new_constant_product_sqrt = self.curve().calculate_invariant_sqrt(new_base_liquidity, new_quote_liquidity).unwrap();
         
-        let lp_tokens_to_mint = self.calculate_lp_mint_for_provided_liquidity(new_constant_product_sqrt).ok_or(ErrorCode::LpTokensCalculationFailed)?;
+        let lp_tokens_to_mint = self.curve().calculate_lp_mint_for_provided_liquidity(new_constant_product_sqrt).ok_or(ErrorCode::LpTokensCalculationFailed)?;
+        require!(lp_tokens_to_mint >= min_lp_tokens, ErrorCode::ProvideLpTokensBelowMinimum);
 
         let new_lp_tokens_supply = self.lp_tokens_supply.checked_add(lp_tokens_to_mint).ok_or(ErrorCode::ProvideOverflowError)?;
         Ok(ProvidePayload {
@@ -317,38 +1025,117 @@ impl CpAmm {
     /// Prepares the payload for withdrawing liquidity from the AMM.
     ///
     /// It calculates the amounts of base and quote liquidity to withdraw, ensuring the pool remains valid.
+    /// If the AMM is incident-limited and `incident_withdraw_limit_basis_points` is non-zero,
+    /// the withdrawal is also checked against the per-slot limit.
+    ///
+    /// The withdrawn amounts are rounded, so redeeming all `lp_tokens` may pay out slightly less than
+    /// their share of the pool. With `is_burn_exact`, only the LP tokens the payout is worth are burned
+    /// and the remainder stays with the user.
     ///
     /// # Parameters
     /// - `lp_tokens`: The number of LP tokens to redeem for liquidity withdrawal.
+    /// - `current_slot`: The slot in which the withdrawal happens.
+    /// - `incident_withdraw_limit_basis_points`: The share of LP tokens supply allowed to be withdrawn per slot.
+    /// - `min_lp_tokens`: The minimal amount of LP tokens the withdraw must redeem.
+    /// - `holder_lp_tokens`: The LP tokens held by the redeeming account, which may always be withdrawn as a whole.
+    /// - `is_burn_exact`: Whether to burn only the LP tokens the rounded payout is worth.
+    /// - `liquidity_ratio_tolerance`: The allowed relative deviation of the liquidity ratio square root.
     ///
     /// # Returns
     /// - `Ok(WithdrawPayload)` containing the updated pool state and withdrawn liquidity amounts.
+    /// - `Err(ErrorCode::CpAmmWithdrawsPaused)` if liquidity withdrawals are suspended.
+    /// - `Err(ErrorCode::WithdrawLpTokensBelowMinimum)` if `lp_tokens` is less than `min_lp_tokens` and not the whole `holder_lp_tokens`.
+    /// - `Err(ErrorCode::WithdrawLiquidityBelowMinimum)` if the base or quote liquidity would drop from at least `MIN_LIQUIDITY`
+    ///   below it while LP tokens other than the locked ones remain in supply. Reserves already below the minimum, e.g.
+    ///   after a small launch or swaps, don't lock the LPs out.
     /// - `Err(ErrorCode)` if any checks fail or calculations encounter errors.
     #[inline(never)]
-    pub fn get_withdraw_payload(&self, lp_tokens: u64) -> Result<WithdrawPayload> {
+    #[allow(clippy::too_many_arguments)]
+    pub fn get_withdraw_payload(&self, lp_tokens: u64, current_slot: u64, incident_withdraw_limit_basis_points: u16, min_lp_tokens: u64, holder_lp_tokens: u64, is_burn_exact: bool, liquidity_ratio_tolerance: Q64_128) -> Result<WithdrawPayload> {
         self.check_state()?;
+        require!(!self.withdraws_paused, ErrorCode::CpAmmWithdrawsPaused);
         require!(lp_tokens > 0, ErrorCode::ProvidedLpTokensIsZero);
-
-        let lp_tokens_left_supply = self.lp_tokens_supply.checked_sub(lp_tokens).ok_or(ErrorCode::WithdrawOverflowError)?;
-
-        let (base_withdraw, quote_withdraw) = self.calculate_liquidity_from_share(lp_tokens).ok_or(ErrorCode::WithdrawLiquidityCalculationFailed)?;
+        // Holders below the minimum can still exit with their whole balance
+        require!(lp_tokens >= min_lp_tokens || lp_tokens == holder_lp_tokens, ErrorCode::WithdrawLpTokensBelowMinimum);
+
+        let withdraw_rate_limiter = if self.is_incident_limited && incident_withdraw_limit_basis_points > 0 {
+            self.withdraw_rate_limiter.register_withdraw(current_slot, self.lp_tokens_supply, lp_tokens, incident_withdraw_limit_basis_points)?
+        } else {
+            self.withdraw_rate_limiter
+        };
+
+        require!(lp_tokens <= self.lp_tokens_supply, ErrorCode::WithdrawOverflowError);
+        let (base_withdraw, quote_withdraw) = self.curve().calculate_liquidity_from_share(lp_tokens).ok_or(ErrorCode::WithdrawLiquidityCalculationFailed)?;
+
+        let (lp_tokens_to_burn, base_withdraw, quote_withdraw) = if is_burn_exact {
+            self.calculate_burn_exact_withdraw(lp_tokens, base_withdraw, quote_withdraw).ok_or(ErrorCode::WithdrawLiquidityCalculationFailed)?
+        } else {
+            (lp_tokens, base_withdraw, quote_withdraw)
+        };
+        let lp_tokens_left_supply = self.lp_tokens_supply.checked_sub(lp_tokens_to_burn).ok_or(ErrorCode::WithdrawOverflowError)?;
         
         let new_base_liquidity = self.base_liquidity.checked_sub(base_withdraw).ok_or(ErrorCode::WithdrawOverflowError)?;
         let new_quote_liquidity = self.quote_liquidity.checked_sub(quote_withdraw).ok_or(ErrorCode::WithdrawOverflowError)?;
+        require!(
+            new_base_liquidity >= self.base_lent_out && new_quote_liquidity >= self.quote_lent_out,
+            ErrorCode::LentOutLiquidityUnavailable
+        );
+        // Only the locked LP tokens may back reserves the withdrawal takes below the minimum
+        let drops_below_minimum = |liquidity: u64, new_liquidity: u64| liquidity >= MIN_LIQUIDITY && new_liquidity < MIN_LIQUIDITY;
+        require!(
+            !(drops_below_minimum(self.base_liquidity, new_base_liquidity) || drops_below_minimum(self.quote_liquidity, new_quote_liquidity)) ||
+                lp_tokens_left_supply <= self.initial_locked_liquidity,
+            ErrorCode::WithdrawLiquidityBelowMinimum
+        );
 
         // Checks that new base and quote liquidity don't equal zero and amm won't be drained
-        let new_base_quote_ratio_sqrt = self.validate_and_calculate_liquidity_ratio(new_base_liquidity, new_quote_liquidity)?;
+        let new_base_quote_ratio_sqrt = self.validate_and_calculate_liquidity_ratio(new_base_liquidity, new_quote_liquidity, liquidity_ratio_tolerance)?;
 
         Ok(WithdrawPayload{
             base_quote_ratio_sqrt: new_base_quote_ratio_sqrt,
             base_liquidity: new_base_liquidity,
             quote_liquidity: new_quote_liquidity,
             lp_tokens_supply: lp_tokens_left_supply,
+            lp_tokens_to_burn,
             base_withdraw_amount: base_withdraw,
             quote_withdraw_amount: quote_withdraw,
+            withdraw_rate_limiter,
         })
     }
 
+    /// Calculates the current redeemable base and quote amounts for an amount of LP tokens.
+    ///
+    /// # Parameters
+    /// - `lp_tokens`: The number of LP tokens to value.
+    ///
+    /// # Returns
+    /// - `Ok((u64, u64))` with the base and quote amounts the LP tokens can be redeemed for.
+    /// - `Err(ErrorCode::LpTokensExceedSupply)` if `lp_tokens` exceeds the LP tokens supply.
+    /// - `Err(ErrorCode)` if any checks fail or calculations encounter errors.
+    pub fn get_lp_value(&self, lp_tokens: u64) -> Result<(u64, u64)> {
+        self.check_state()?;
+        require!(lp_tokens > 0, ErrorCode::ProvidedLpTokensIsZero);
+        require!(lp_tokens <= self.lp_tokens_supply, ErrorCode::LpTokensExceedSupply);
+
+        let lp_value = self.curve().calculate_liquidity_from_share(lp_tokens).ok_or(ErrorCode::WithdrawLiquidityCalculationFailed)?;
+        Ok(lp_value)
+    }
+
+    /// Calculates the virtual price of the AMM, the value of one LP token in quote tokens scaled by `VIRTUAL_PRICE_SCALE`.
+    ///
+    /// The pool reserves are valued at the pool price, i.e. at twice the quote liquidity, so the virtual price
+    /// only grows with the collected providers fees while the pool price stays unchanged.
+    ///
+    /// # Returns
+    /// - `Ok(u64)` with the virtual price.
+    /// - `Err(ErrorCode::VirtualPriceCalculationFailed)` if the virtual price overflows.
+    /// - `Err(ErrorCode)` if the AMM state is invalid.
+    pub fn get_virtual_price(&self) -> Result<u64> {
+        self.check_state()?;
+        let virtual_price = 2 * self.quote_liquidity as u128 * Self::VIRTUAL_PRICE_SCALE as u128 / self.lp_tokens_supply as u128;
+        u64::try_from(virtual_price).map_err(|_| ErrorCode::VirtualPriceCalculationFailed.into())
+    }
+
     /// Computes the swap payload for exchanging tokens within the AMM.
     ///
     /// This function handles both **base-to-quote** and **quote-to-base** swaps.
@@ -362,70 +1149,327 @@ impl CpAmm {
     /// - `providers_fee_rate_basis_points`: The liquidity provider's fee rate in basis points.
     /// - `protocol_fee_rate_basis_points`: The protocol fee rate in basis points.
     /// - `is_in_out`: `true` if swapping **base → quote**, `false` if swapping **quote → base**.
+    /// - `quote_usd_price`: The USD price of the quote token used for volume statistics, if available.
+    /// - `constant_product_tolerance`: The allowed relative deviation of the constant product square root.
+    /// - `current_slot`: The current slot, checked against the end of the provide-only warm-up.
     ///
     /// # Returns
     /// - `Ok(SwapPayload)`: Contains the updated liquidity state and fees.
+    /// - `Err(ErrorCode::CpAmmSwapsPaused)`: If swaps are suspended.
+    /// - `Err(ErrorCode::TradingNotOpenYet)`: If the AMM is still in its provide-only warm-up.
     /// - `Err(ErrorCode)`: If any validation fails (e.g., insufficient liquidity, overflow, or slippage exceeded).
+    #[allow(clippy::too_many_arguments)]
     #[inline(never)]
-    pub fn get_swap_payload(&self, swap_amount: u64, estimated_result: u64, allowed_slippage: u64, providers_fee_rate_basis_points: u16, protocol_fee_rate_basis_points: u16, is_in_out: bool) -> Result<SwapPayload> {
+    pub fn get_swap_payload(&self, swap_amount: u64, estimated_result: u64, allowed_slippage: u64, providers_fee_rate_basis_points: u16, protocol_fee_rate_basis_points: u16, is_in_out: bool, quote_usd_price: Option<UsdPrice>, constant_product_tolerance: Q64_128, current_slot: u64) -> Result<SwapPayload> {
         self.check_state()?;
+        require!(!self.swaps_paused, ErrorCode::CpAmmSwapsPaused);
+        require!(current_slot >= self.trading_open_slot, ErrorCode::TradingNotOpenYet);
         require!(swap_amount > 0, ErrorCode::SwapAmountIsZero);
         require!(estimated_result > 0, ErrorCode::EstimatedResultIsZero);
-        require!(providers_fee_rate_basis_points + protocol_fee_rate_basis_points <= 10000, ErrorCode::ConfigFeeRateExceeded);
+        validate_fee_rates(providers_fee_rate_basis_points, protocol_fee_rate_basis_points)?;
 
-        let providers_fee_amount = Self::calculate_fee_amount(swap_amount, providers_fee_rate_basis_points);
+        let providers_fee_amount = Self::calculate_swap_fee_amount(swap_amount, providers_fee_rate_basis_points);
         require!(providers_fee_amount > 0 || providers_fee_rate_basis_points == 0, ErrorCode::SwapFeesAreZero);
 
-        let protocol_fee_amount = Self::calculate_fee_amount(swap_amount, protocol_fee_rate_basis_points);
+        let protocol_fee_amount = Self::calculate_swap_fee_amount(swap_amount, protocol_fee_rate_basis_points);
         require!(protocol_fee_amount > 0 || protocol_fee_rate_basis_points == 0, ErrorCode::SwapFeesAreZero);
+
+        // The creator's slice is carved out of the providers fee, so the swap input is unaffected
+        let creator_fee_amount = Self::calculate_fee_amount(providers_fee_amount, self.creator_fee_share_basis_points);
         
-        let (new_base_liquidity, new_quote_liquidity, amount_to_withdraw, protocol_fees_to_redeem);
+        let (new_base_liquidity, new_quote_liquidity, amount_to_withdraw, protocol_fees_to_redeem, creator_fees_to_redeem);
         
         if is_in_out {
             protocol_fees_to_redeem = self.protocol_base_fees_to_redeem.checked_add(protocol_fee_amount).ok_or(ErrorCode::SwapOverflowError)?;
+            creator_fees_to_redeem = self.creator_base_fees_to_redeem.checked_add(creator_fee_amount).ok_or(ErrorCode::SwapOverflowError)?;
             let base_amount_after_fees = swap_amount.checked_sub(providers_fee_amount).unwrap().checked_sub(protocol_fee_amount).ok_or(ErrorCode::SwapOverflowError)?;
-            (new_base_liquidity, new_quote_liquidity) = self.calculate_afterswap_liquidity(base_amount_after_fees, true).ok_or(ErrorCode::AfterswapCalculationFailed)?;
+            (new_base_liquidity, new_quote_liquidity) = self.curve().calculate_afterswap_liquidity(base_amount_after_fees, true).ok_or(ErrorCode::AfterswapCalculationFailed)?;
             amount_to_withdraw = self.quote_liquidity.checked_sub(new_quote_liquidity).ok_or(ErrorCode::SwapOverflowError)?;
         }
         else{
             protocol_fees_to_redeem = self.protocol_quote_fees_to_redeem.checked_add(protocol_fee_amount).ok_or(ErrorCode::SwapOverflowError)?;
+            creator_fees_to_redeem = self.creator_quote_fees_to_redeem.checked_add(creator_fee_amount).ok_or(ErrorCode::SwapOverflowError)?;
             let quote_amount_after_fees = swap_amount.checked_sub(providers_fee_amount).unwrap().checked_sub(protocol_fee_amount).ok_or(ErrorCode::SwapOverflowError)?;
-            (new_base_liquidity, new_quote_liquidity) = self.calculate_afterswap_liquidity(quote_amount_after_fees, false).ok_or(ErrorCode::AfterswapCalculationFailed)?;
+            (new_base_liquidity, new_quote_liquidity) = self.curve().calculate_afterswap_liquidity(quote_amount_after_fees, false).ok_or(ErrorCode::AfterswapCalculationFailed)?;
             amount_to_withdraw = self.base_liquidity.checked_sub(new_base_liquidity).ok_or(ErrorCode::SwapOverflowError)?;
         }
         
+        // Lent out liquidity is missing from the vaults, so it can't be swapped out
+        require!(
+            new_base_liquidity >= self.base_lent_out && new_quote_liquidity >= self.quote_lent_out,
+            ErrorCode::LentOutLiquidityUnavailable
+        );
+
         // Check constant product change is in acceptable range
-        self.validate_swap_constant_product(new_base_liquidity, new_quote_liquidity)?;
+        self.curve().validate_swap_invariant(new_base_liquidity, new_quote_liquidity, constant_product_tolerance)?;
         Self::check_swap_result(amount_to_withdraw, estimated_result, allowed_slippage)?;
+
+        // Providers fee net of the creator's slice is added to the input side liquidity in `swap`
+        let providers_fee_amount = strict_sub!(providers_fee_amount, creator_fee_amount);
+        let (final_base_liquidity, final_quote_liquidity) = if is_in_out {
+            (strict_add!(new_base_liquidity, providers_fee_amount), new_quote_liquidity)
+        } else {
+            (new_base_liquidity, strict_add!(new_quote_liquidity, providers_fee_amount))
+        };
+        // The new ratio is stored by `swap`, which derives the constant product from it
+        let new_base_quote_ratio_sqrt = self.calculate_normalized_base_quote_ratio_sqrt(final_base_liquidity, final_quote_liquidity)
+            .ok_or(ErrorCode::BaseQuoteRatioCalculationFailed)?;
+        let price_impact_basis_points = self.calculate_price_impact_from_ratio_sqrt(new_base_quote_ratio_sqrt)
+            .ok_or(ErrorCode::BaseQuoteRatioCalculationFailed)?;
+
+        let (base_volume, quote_volume) = if is_in_out {
+            (swap_amount, amount_to_withdraw)
+        } else {
+            (amount_to_withdraw, swap_amount)
+        };
+        let usd_volume = quote_usd_price.and_then(|price| price.usd_value(quote_volume));
         
         Ok(SwapPayload::new(
             new_base_liquidity,
             new_quote_liquidity,
             protocol_fees_to_redeem,
+            creator_fees_to_redeem,
             providers_fee_amount,
+            protocol_fee_amount,
+            creator_fee_amount,
             amount_to_withdraw,
             is_in_out,
+            price_impact_basis_points,
+            new_base_quote_ratio_sqrt,
+            PoolStats::default()
+                .record_swap(base_volume, quote_volume, usd_volume)
+                .record_fees(providers_fee_amount, protocol_fee_amount, is_in_out),
         ))
     }
 
     /// Prepares the payload for collecting protocol fees from the AMM.
     ///
     /// This method checks if there are any protocol fees available for redemption and creates
-    /// a `CollectFeesPayload` containing the amounts of base and quote token fees.
+    /// a `CollectFeesPayload` containing the amounts of base and quote token fees. The requested
+    /// amounts are taken out of the accrued fees, which keep the rest for a later collection.
+    /// A `keeper_bounty_basis_points` share of the requested fees is split off for the collection caller.
+    ///
+    /// With a `fee_settlement`, the protocol fees in the token other than the settlement mint are swapped
+    /// in-pool into it, free of fees, so the fee authority receives a single asset. While the conversion
+    /// would exceed the maximal price impact, or the spot price moved away from the price the last slot
+    /// ended at by more than that impact, those fees and their bounty are held back in the AMM. As anyone
+    /// can collect, the latter keeps a caller from settling the fees at a price skewed in the same slot.
+    ///
+    /// # Parameters
+    /// - `requested_base_fees`: The protocol fees in base tokens to collect, including the keeper bounty.
+    /// - `requested_quote_fees`: The protocol fees in quote tokens to collect, including the keeper bounty.
+    /// - `keeper_bounty_basis_points`: The share of the collected fees paid to the caller, in basis points.
+    /// - `protocol_fee_rate_basis_points`: The protocol fee rate currently applied to the AMM swaps, in basis points.
+    /// - `fee_settlement`: The settlement of the protocol fees of the `AmmsConfig`, if any.
+    /// - `constant_product_tolerance`: The allowed relative deviation of the constant product square root.
+    /// - `current_slot`: The current slot, to compare the spot price with `last_slot_price`.
     ///
     /// # Returns
     /// - `Ok(CollectFeesPayload)`: Contains the protocol fees available for redemption for both base and quote tokens.
-    /// - `Err(ErrorCode::ProvidersFeesIsZero)`: If both `protocol_base_fees_to_redeem` and `protocol_quote_fees_to_redeem` are zero, meaning no fees are available to collect.
+    /// - `Err(ErrorCode::ProtocolFeeDisabled)`: If no fees are available to collect because the protocol fee rate is zero.
+    /// - `Err(ErrorCode::ProvidersFeesIsZero)`: If both `protocol_base_fees_to_redeem` and `protocol_quote_fees_to_redeem` are zero, meaning no fees are available to collect,
+    ///   or both requested amounts are zero.
+    /// - `Err(ErrorCode::CollectFeesAmountExceeded)`: If a requested amount exceeds the accrued fees.
+    /// - `Err(ErrorCode::FeeSettlementImpactExceeded)`: If all the fees are held back by the settlement.
     #[inline(never)]
-    pub fn get_collect_fees_payload(&self) -> Result<CollectFeesPayload>{
-        require!(self.protocol_base_fees_to_redeem > 0 || self.protocol_quote_fees_to_redeem > 0, ErrorCode::ProvidersFeesIsZero);
+    #[allow(clippy::too_many_arguments)]
+    pub fn get_collect_fees_payload(
+        &self,
+        requested_base_fees: u64,
+        requested_quote_fees: u64,
+        keeper_bounty_basis_points: u16,
+        protocol_fee_rate_basis_points: u16,
+        fee_settlement: Option<&FeeSettlement>,
+        constant_product_tolerance: Q64_128,
+        current_slot: u64
+    ) -> Result<CollectFeesPayload>{
+        if !self.has_protocol_fees_to_redeem() {
+            // Fees accrued before the rate was zeroed are still collectable, so only empty pools are rejected
+            require!(protocol_fee_rate_basis_points > 0, ErrorCode::ProtocolFeeDisabled);
+            return err!(ErrorCode::ProvidersFeesIsZero);
+        }
+        require!(
+            requested_base_fees <= self.protocol_base_fees_to_redeem && requested_quote_fees <= self.protocol_quote_fees_to_redeem,
+            ErrorCode::CollectFeesAmountExceeded
+        );
+        require!(requested_base_fees > 0 || requested_quote_fees > 0, ErrorCode::ProvidersFeesIsZero);
+        let mut base_keeper_bounty = Self::calculate_fee_amount(requested_base_fees, keeper_bounty_basis_points);
+        let mut quote_keeper_bounty = Self::calculate_fee_amount(requested_quote_fees, keeper_bounty_basis_points);
+        let mut protocol_base_fees_to_redeem = strict_sub!(requested_base_fees, base_keeper_bounty);
+        let mut protocol_quote_fees_to_redeem = strict_sub!(requested_quote_fees, quote_keeper_bounty);
+        let mut new_protocol_base_fees_to_redeem = strict_sub!(self.protocol_base_fees_to_redeem, requested_base_fees);
+        let mut new_protocol_quote_fees_to_redeem = strict_sub!(self.protocol_quote_fees_to_redeem, requested_quote_fees);
+        let (mut base_liquidity, mut quote_liquidity) = (self.base_liquidity, self.quote_liquidity);
+
+        let settlement = fee_settlement.and_then(|fee_settlement| fee_settlement
+            .is_in_out(&self.base_mint, &self.quote_mint)
+            .map(|is_in_out| (is_in_out, fee_settlement.max_impact_basis_points()))
+        );
+        if let Some((is_in_out, max_impact_basis_points)) = settlement {
+            let fees_to_convert = if is_in_out { protocol_base_fees_to_redeem } else { protocol_quote_fees_to_redeem };
+            if fees_to_convert > 0 {
+                match self.calculate_fee_settlement_liquidity(fees_to_convert, is_in_out, max_impact_basis_points, constant_product_tolerance, current_slot) {
+                    Some((new_base_liquidity, new_quote_liquidity)) if is_in_out => {
+                        let settled_amount = strict_sub!(self.quote_liquidity, new_quote_liquidity);
+                        (protocol_base_fees_to_redeem, protocol_quote_fees_to_redeem) = (0, strict_add!(protocol_quote_fees_to_redeem, settled_amount));
+                        (base_liquidity, quote_liquidity) = (new_base_liquidity, new_quote_liquidity);
+                    }
+                    Some((new_base_liquidity, new_quote_liquidity)) => {
+                        let settled_amount = strict_sub!(self.base_liquidity, new_base_liquidity);
+                        (protocol_base_fees_to_redeem, protocol_quote_fees_to_redeem) = (strict_add!(protocol_base_fees_to_redeem, settled_amount), 0);
+                        (base_liquidity, quote_liquidity) = (new_base_liquidity, new_quote_liquidity);
+                    }
+                    None if is_in_out => {
+                        (protocol_base_fees_to_redeem, base_keeper_bounty) = (0, 0);
+                        new_protocol_base_fees_to_redeem = self.protocol_base_fees_to_redeem;
+                    }
+                    None => {
+                        (protocol_quote_fees_to_redeem, quote_keeper_bounty) = (0, 0);
+                        new_protocol_quote_fees_to_redeem = self.protocol_quote_fees_to_redeem;
+                    }
+                }
+            }
+        }
+        require!(
+            new_protocol_base_fees_to_redeem < self.protocol_base_fees_to_redeem || new_protocol_quote_fees_to_redeem < self.protocol_quote_fees_to_redeem,
+            ErrorCode::FeeSettlementImpactExceeded
+        );
+
         Ok(CollectFeesPayload::new(
-            self.protocol_base_fees_to_redeem,
-            self.protocol_quote_fees_to_redeem,
-            0,
-            0
+            protocol_base_fees_to_redeem,
+            protocol_quote_fees_to_redeem,
+            base_keeper_bounty,
+            quote_keeper_bounty,
+            new_protocol_base_fees_to_redeem,
+            new_protocol_quote_fees_to_redeem,
+            base_liquidity,
+            quote_liquidity
         ))
     }
+
+    /// Calculates the liquidity after swapping protocol fees in-pool into the settlement mint, free of fees.
+    ///
+    /// The fees already sit in the vaults, so the conversion only moves them into the liquidity
+    /// and the swapped out amount out of it.
+    ///
+    /// # Parameters
+    /// - `fees_to_convert`: The amount of protocol fees to swap.
+    /// - `is_in_out`: `true` if base fees are swapped into quote, `false` if quote fees are swapped into base.
+    /// - `max_impact_basis_points`: The maximal price impact of the swap, in basis points.
+    /// - `constant_product_tolerance`: The allowed relative deviation of the constant product square root.
+    /// - `current_slot`: The current slot.
+    ///
+    /// # Returns
+    /// - `Some((u64, u64))` with the new base and quote liquidity.
+    /// - `None` if swaps are paused, the spot price deviates from `last_slot_price` by more than the maximal
+    ///   price impact, or the swap yields nothing, takes lent out liquidity, breaks the constant product
+    ///   or exceeds the maximal price impact.
+    fn calculate_fee_settlement_liquidity(
+        &self,
+        fees_to_convert: u64,
+        is_in_out: bool,
+        max_impact_basis_points: u16,
+        constant_product_tolerance: Q64_128,
+        current_slot: u64
+    ) -> Option<(u64, u64)> {
+        let last_slot_price = self.last_slot_price(current_slot) as u128;
+        let price_deviation = (self.spot_price() as u128).abs_diff(last_slot_price);
+        if self.swaps_paused || price_deviation * 10000 > last_slot_price * max_impact_basis_points as u128 {
+            return None;
+        }
+        let (new_base_liquidity, new_quote_liquidity) = self.curve().calculate_afterswap_liquidity(fees_to_convert, is_in_out)?;
+        let settled_amount = if is_in_out {
+            self.quote_liquidity.checked_sub(new_quote_liquidity)?
+        } else {
+            self.base_liquidity.checked_sub(new_base_liquidity)?
+        };
+        let price_impact_basis_points = self.calculate_price_impact_basis_points(new_base_liquidity, new_quote_liquidity)?;
+        let is_settleable = settled_amount > 0
+            && price_impact_basis_points <= max_impact_basis_points as u64
+            && new_base_liquidity >= self.base_lent_out
+            && new_quote_liquidity >= self.quote_lent_out
+            && self.curve().validate_swap_invariant(new_base_liquidity, new_quote_liquidity, constant_product_tolerance).is_ok();
+        is_settleable.then_some((new_base_liquidity, new_quote_liquidity))
+    }
+
+    /// Prepares the payload for lending out pool liquidity to the lending adapter.
+    ///
+    /// Lent out liquidity still belongs to the pool, but can't be swapped out or withdrawn until repaid.
+    ///
+    /// # Parameters
+    /// - `amount`: The amount of tokens to lend out.
+    /// - `is_base`: Whether the base or the quote liquidity is lent out.
+    /// - `max_lend_out_basis_points`: The share of the liquidity that may be lent out, in basis points.
+    ///
+    /// # Returns
+    /// - `Ok(LendOutPayload)` containing the updated lent out amounts.
+    /// - `Err(ErrorCode::CpAmmLendOutDisabled)` if lending out is disabled for the AMM.
+    /// - `Err(ErrorCode::LendOutLimitExceeded)` if the lent out liquidity would exceed the limit.
+    pub fn get_borrow_payload(&self, amount: u64, is_base: bool, max_lend_out_basis_points: u16) -> Result<LendOutPayload> {
+        self.check_state()?;
+        require!(self.is_lend_out_enabled, ErrorCode::CpAmmLendOutDisabled);
+        require!(amount > 0, ErrorCode::LendOutAmountIsZero);
+
+        let (liquidity, lent_out) = if is_base {
+            (self.base_liquidity, self.base_lent_out)
+        } else {
+            (self.quote_liquidity, self.quote_lent_out)
+        };
+        let new_lent_out = lent_out.checked_add(amount).ok_or(ErrorCode::LendOutLimitExceeded)?;
+        let lend_out_limit = (liquidity as u128 * max_lend_out_basis_points as u128 / 10000) as u64;
+        require!(new_lent_out <= lend_out_limit, ErrorCode::LendOutLimitExceeded);
+
+        let (base_lent_out, quote_lent_out) = if is_base {
+            (new_lent_out, self.quote_lent_out)
+        } else {
+            (self.base_lent_out, new_lent_out)
+        };
+        Ok(LendOutPayload {
+            base_liquidity: self.base_liquidity,
+            quote_liquidity: self.quote_liquidity,
+            base_lent_out,
+            quote_lent_out,
+            stats: self.stats,
+        })
+    }
+
+    /// Prepares the payload for a lend-out loan repayment.
+    ///
+    /// The repaid principal becomes available again, while the interest is added to the liquidity
+    /// the same way as providers fees.
+    ///
+    /// # Parameters
+    /// - `principal`: The repaid principal.
+    /// - `interest`: The repaid interest.
+    /// - `is_base`: Whether the base or the quote liquidity is repaid.
+    ///
+    /// # Returns
+    /// - `Ok(LendOutPayload)` containing the updated liquidity and lent out amounts.
+    /// - `Err(ErrorCode::LendOutRepayExceedsDebt)` if the principal exceeds the lent out liquidity.
+    pub fn get_repay_payload(&self, principal: u64, interest: u64, is_base: bool) -> Result<LendOutPayload> {
+        require!(principal > 0 || interest > 0, ErrorCode::LendOutAmountIsZero);
+
+        let (liquidity, lent_out) = if is_base {
+            (self.base_liquidity, self.base_lent_out)
+        } else {
+            (self.quote_liquidity, self.quote_lent_out)
+        };
+        let new_lent_out = lent_out.checked_sub(principal).ok_or(ErrorCode::LendOutRepayExceedsDebt)?;
+        let new_liquidity = liquidity.checked_add(interest).ok_or(ErrorCode::ProvideOverflowError)?;
+
+        let (base_liquidity, quote_liquidity, base_lent_out, quote_lent_out) = if is_base {
+            (new_liquidity, self.quote_liquidity, new_lent_out, self.quote_lent_out)
+        } else {
+            (self.base_liquidity, new_liquidity, self.base_lent_out, new_lent_out)
+        };
+        Ok(LendOutPayload {
+            base_liquidity,
+            quote_liquidity,
+            base_lent_out,
+            quote_lent_out,
+            stats: self.stats.record_fees(interest, 0, is_base),
+        })
+    }
 }
 
 impl CpAmm {
@@ -448,6 +1492,7 @@ impl CpAmm {
     /// - `base_vault_bump`: The canonical bump seed for the AMM's PDA.
     /// - `quote_vault_bump`: The canonical bump seed for the AMM's PDA.
     /// - `locked_lp_vault_bump`: The canonical bump seed for the AMM's PDA.
+    /// - `current_epoch`: The epoch in which the AMM is initialized.
     ///
     /// # Returns
     /// - `Ok(())` if the initialization is successful.
@@ -466,12 +1511,11 @@ impl CpAmm {
         bump: u8,
         base_vault_bump: u8,
         quote_vault_bump: u8,
-        locked_lp_vault_bump: u8
+        locked_lp_vault_bump: u8,
+        current_epoch: u64
     ) -> Result<()>{
-        require!(!self.is_initialized, ErrorCode::CpAmmAlreadyInitialized);
-
-        self.is_initialized = true;
-        self.is_launched = false;
+        self.status = self.status.initialize()?;
+        self.layout_version = Self::LAYOUT_VERSION;
 
         self.base_mint = base_mint.key();
         self.quote_mint = quote_mint.key();
@@ -486,6 +1530,9 @@ impl CpAmm {
         self.base_vault_bump = [base_vault_bump];
         self.quote_vault_bump = [quote_vault_bump];
         self.locked_lp_vault_bump = [locked_lp_vault_bump];
+        self.initialized_epoch = current_epoch;
+        self.curve_kind = CurveKind::ConstantProduct;
+        self.max_protocol_fee_basis_points_at_creation = Some(amms_config.max_protocol_fee_commitment_basis_points());
 
         Ok(())
     }
@@ -497,12 +1544,14 @@ impl CpAmm {
     ///
     /// # Parameters
     /// - `launch_payload`: Contains the initial liquidity, LP token supply, and ratios.
+    /// - `trading_open_slot`: The slot from which swaps are allowed, see `calculate_trading_open_slot`.
     ///
     /// # Returns
     /// - No return value. Modifies the internal state of the AMM.
     #[inline(never)]
-    pub(crate) fn launch(&mut self, launch_payload: LaunchPayload) -> (){
-        self.is_launched = true;
+    pub(crate) fn launch(&mut self, launch_payload: LaunchPayload, trading_open_slot: u64) -> (){
+        self.status = PoolStatus::Launched;
+        self.trading_open_slot = trading_open_slot;
         self.base_liquidity = launch_payload.base_liquidity;
         self.quote_liquidity = launch_payload.quote_liquidity;
         self.initial_locked_liquidity = launch_payload.initial_locked_liquidity;
@@ -536,61 +1585,336 @@ impl CpAmm {
     /// constant product, and liquidity ratio after liquidity is redeemed from the pool.
     ///
     /// # Parameters
-    /// - `withdraw_payload`: Contains the new liquidity amounts and LP tokens to burn.
+    /// - `withdraw_payload`: Contains the new liquidity amounts and LP tokens to burn.
+    ///
+    /// # Returns
+    /// - No return value. Modifies the internal state of the AMM.
+    #[inline(never)]
+    pub(crate) fn withdraw(&mut self, withdraw_payload: WithdrawPayload) -> (){
+        self.base_liquidity = withdraw_payload.base_liquidity;
+        self.quote_liquidity = withdraw_payload.quote_liquidity;
+        self.lp_tokens_supply = withdraw_payload.lp_tokens_supply;
+        self.constant_product_sqrt = self.curve().calculate_invariant_sqrt(self.base_liquidity, self.quote_liquidity).unwrap();
+        self.base_quote_ratio_sqrt = withdraw_payload.base_quote_ratio_sqrt;
+        self.withdraw_rate_limiter = withdraw_payload.withdraw_rate_limiter;
+    }
+
+    /// Updates the AMM state after a token swap operation.
+    ///
+    /// This method adjusts the base and quote liquidity, protocol fees, providers fees, constant product,
+    /// and liquidity ratio after a swap. It ensures the AMM remains consistent with the
+    /// constant product invariant. Trading statistics left in the payload are added to the pool statistics.
+    ///
+    /// The liquidity ratio is taken from the payload, which calculated it for the price impact, and the
+    /// pool curve derives the invariant from it, so the constant product curve doesn't compute any square root.
+    ///
+    /// # Parameters
+    /// - `swap_payload`: Contains the updated liquidity values, fees, and swap details.
+    ///
+    /// # Returns
+    /// - `Ok(())` after modifying the internal state of the AMM.
+    /// - `Err(ErrorCode::StrictMathOverflow)` with `strict-math` if adding the providers fee overflows the liquidity.
+    #[inline(never)]
+    pub(crate) fn swap(&mut self, swap_payload: SwapPayload) -> Result<()> {
+        if swap_payload.is_in_out{
+            self.protocol_base_fees_to_redeem = swap_payload.protocol_fees_to_redeem;
+            self.creator_base_fees_to_redeem = swap_payload.creator_fees_to_redeem;
+            self.base_liquidity = strict_add!(swap_payload.base_liquidity, swap_payload.providers_fee_amount);
+            self.quote_liquidity = swap_payload.quote_liquidity;
+        }
+        else{
+            self.protocol_quote_fees_to_redeem = swap_payload.protocol_fees_to_redeem;
+            self.creator_quote_fees_to_redeem = swap_payload.creator_fees_to_redeem;
+            self.base_liquidity = swap_payload.base_liquidity;
+            self.quote_liquidity = strict_add!(swap_payload.quote_liquidity, swap_payload.providers_fee_amount);
+        }
+        self.stats = self.stats.merge(&swap_payload.stats);
+        self.base_quote_ratio_sqrt = swap_payload.base_quote_ratio_sqrt;
+        self.constant_product_sqrt = self.curve().calculate_invariant_sqrt_from_ratio_sqrt(self.base_quote_ratio_sqrt, self.base_liquidity, self.quote_liquidity).unwrap();
+        Ok(())
+    }
+
+    /// Updates the protocol fees for the AMM based on the provided payload.
+    ///
+    /// This method sets the protocol fees available for redemption to the updated values
+    /// specified in the `CollectFeesPayload`.
+    ///
+    /// # Parameters
+    /// - `collect_fees_payload`: A `CollectFeesPayload` containing the updated protocol fees
+    ///   for both base and quote tokens.
+    ///
+    /// # Returns
+    /// - Whether the settlement of the fees changed the liquidity of the AMM.
+    #[inline(never)]
+    pub(crate) fn collect_fees(&mut self, collect_fees_payload: CollectFeesPayload) -> bool {
+        self.protocol_base_fees_to_redeem = collect_fees_payload.new_protocol_base_fees_to_redeem;
+        self.protocol_quote_fees_to_redeem = collect_fees_payload.new_protocol_quote_fees_to_redeem;
+        if self.base_liquidity == collect_fees_payload.base_liquidity && self.quote_liquidity == collect_fees_payload.quote_liquidity {
+            return false;
+        }
+        self.base_liquidity = collect_fees_payload.base_liquidity;
+        self.quote_liquidity = collect_fees_payload.quote_liquidity;
+        self.constant_product_sqrt = self.curve().calculate_invariant_sqrt(self.base_liquidity, self.quote_liquidity).unwrap();
+        self.base_quote_ratio_sqrt = self.calculate_normalized_base_quote_ratio_sqrt(self.base_liquidity, self.quote_liquidity).unwrap();
+        true
+    }
+
+    /// Updates the AMM state after lending out liquidity or a loan repayment.
+    ///
+    /// # Parameters
+    /// - `lend_out_payload`: Contains the updated liquidity and lent out amounts.
+    #[inline(never)]
+    pub(crate) fn lend_out(&mut self, lend_out_payload: LendOutPayload) {
+        self.base_lent_out = lend_out_payload.base_lent_out;
+        self.quote_lent_out = lend_out_payload.quote_lent_out;
+        self.stats = lend_out_payload.stats;
+        if self.base_liquidity != lend_out_payload.base_liquidity || self.quote_liquidity != lend_out_payload.quote_liquidity {
+            self.base_liquidity = lend_out_payload.base_liquidity;
+            self.quote_liquidity = lend_out_payload.quote_liquidity;
+            self.constant_product_sqrt = self.curve().calculate_invariant_sqrt(self.base_liquidity, self.quote_liquidity).unwrap();
+            self.base_quote_ratio_sqrt = self.calculate_normalized_base_quote_ratio_sqrt(self.base_liquidity, self.quote_liquidity).unwrap();
+        }
+    }
+
+    /// Enables or disables lending out the AMM liquidity to the lending adapter.
+    ///
+    /// # Parameters
+    /// - `is_lend_out_enabled`: Whether the lending adapter may borrow the AMM liquidity.
+    pub(crate) fn update_lend_out(&mut self, is_lend_out_enabled: bool) {
+        self.is_lend_out_enabled = is_lend_out_enabled;
+    }
+
+    /// Enables or disables the per-slot withdrawals rate limit of the AMM.
+    ///
+    /// Enabling the limit resets the withdrawals accounting, so the first limited slot
+    /// starts from the current LP tokens supply.
+    ///
+    /// # Parameters
+    /// - `is_incident_limited`: Whether the AMM should be incident-limited.
+    pub(crate) fn update_incident_mode(&mut self, is_incident_limited: bool) {
+        if is_incident_limited && !self.is_incident_limited {
+            self.withdraw_rate_limiter = WithdrawRateLimiter::default();
+        }
+        self.is_incident_limited = is_incident_limited;
+    }
+
+    /// Starts, extends or ends the protected mode of the AMM.
+    ///
+    /// # Parameters
+    /// - `protected_slots`: The number of slots from the current one the AMM stays protected, zero ends the protection.
+    /// - `current_slot`: The current slot.
+    ///
+    /// # Errors
+    /// - Returns `ErrorCode::ProtectedSlotsExceeded` if the window exceeds `MAX_PROTECTED_SLOTS`.
+    pub(crate) fn update_protected_mode(&mut self, protected_slots: u64, current_slot: u64) -> Result<()> {
+        require!(protected_slots <= Self::MAX_PROTECTED_SLOTS, ErrorCode::ProtectedSlotsExceeded);
+        self.protected_until_slot = if protected_slots == 0 { 0 } else { current_slot.saturating_add(protected_slots) };
+        Ok(())
+    }
+
+    /// Records the share of the LP tokens supply held by a provider after a provide and updates the concentration flag.
+    ///
+    /// The flag is re-evaluated against the current share of the `AmmsConfig` on every provide, so
+    /// disabling the share clears it while the largest share is kept.
+    ///
+    /// # Parameters
+    /// - `provide_payload`: The payload of the provide.
+    /// - `holder_lp_tokens`: The LP tokens held by the account receiving the minted ones, before the provide.
+    /// - `lp_concentration_share_basis_points`: The share of the `AmmsConfig`, zero disables the flag.
+    pub(crate) fn record_lp_concentration(&mut self, provide_payload: &ProvidePayload, holder_lp_tokens: u64, lp_concentration_share_basis_points: u16) {
+        self.largest_provide_share_basis_points = self.largest_provide_share_basis_points.max(provide_payload.holder_share_basis_points(holder_lp_tokens));
+        self.concentration_flag = lp_concentration_share_basis_points != 0 &&
+            self.largest_provide_share_basis_points > lp_concentration_share_basis_points;
+    }
+
+    /// Records the spot price at the first liquidity change of a slot, before the change is applied.
+    ///
+    /// # Parameters
+    /// - `current_slot`: The current slot.
+    pub(crate) fn record_slot_start_price(&mut self, current_slot: u64) {
+        if current_slot != self.slot_start_price_slot {
+            self.slot_start_price = self.spot_price();
+            self.slot_start_price_slot = current_slot;
+        }
+    }
+
+    /// Rewrites the `DisplayData` entries from the current state of the AMM.
+    ///
+    /// The price is the `spot_price` of the AMM.
+    ///
+    /// # Parameters
+    /// - `providers_fee_rate_basis_points`: The providers fee rate of the associated `AmmsConfig` at the current slot.
+    /// - `config_protocol_fee_rate_basis_points`: The protocol fee rate of the associated `AmmsConfig`.
+    /// - `current_slot`: The current slot, recorded as the `LastUpdateSlot` entry.
+    pub(crate) fn refresh_display_data(&mut self, providers_fee_rate_basis_points: u16, config_protocol_fee_rate_basis_points: u16, current_slot: u64) {
+        let price = self.spot_price();
+        let protocol_fee_rate_basis_points = self.protocol_fee_rate_basis_points(config_protocol_fee_rate_basis_points);
+
+        let mints = [self.base_mint.to_bytes(), self.quote_mint.to_bytes()].concat();
+        let reserves = [self.base_liquidity.to_le_bytes(), self.quote_liquidity.to_le_bytes()].concat();
+        let fee_rates = [providers_fee_rate_basis_points.to_le_bytes(), protocol_fee_rate_basis_points.to_le_bytes()].concat();
+        self.display_data.write(&[
+            (DisplayEntryType::Mints, &mints),
+            (DisplayEntryType::Reserves, &reserves),
+            (DisplayEntryType::Price, &price.to_le_bytes()),
+            (DisplayEntryType::FeeRates, &fee_rates),
+            (DisplayEntryType::LpTokensSupply, &self.lp_tokens_supply.to_le_bytes()),
+            (DisplayEntryType::LastUpdateSlot, &current_slot.to_le_bytes()),
+        ]);
+    }
+
+    /// Sets or clears the oracle account providing the USD price of the quote token.
+    ///
+    /// # Parameters
+    /// - `usd_oracle`: The oracle account, or `None` to stop USD volume accounting.
+    pub(crate) fn update_usd_oracle(&mut self, usd_oracle: Option<Pubkey>) {
+        self.usd_oracle = usd_oracle;
+    }
+
+    /// Sets or clears the program notified about liquidity changes of the AMM.
+    ///
+    /// # Parameters
+    /// - `liquidity_subscriber`: The subscriber program, or `None` to stop notifications.
+    pub(crate) fn update_liquidity_subscriber(&mut self, liquidity_subscriber: Option<Pubkey>) {
+        self.liquidity_subscriber = liquidity_subscriber;
+    }
+
+    /// Sets or clears the protocol fee rate negotiated for the AMM.
+    ///
+    /// # Parameters
+    /// - `protocol_fee_override_basis_points`: The protocol fee rate, or `None` to use the `AmmsConfig` rate.
+    /// - `providers_fee_rate_basis_points`: The providers fee rate of the associated `AmmsConfig`.
+    ///
+    /// # Errors
+    /// - Returns `ErrorCode::ParametersLocked` if the fee parameters are locked.
+    /// - Returns `ErrorCode::ConfigFeeRateExceeded` if the total fee rate exceeds 100%.
+    pub(crate) fn update_protocol_fee_override(&mut self, protocol_fee_override_basis_points: Option<u16>, providers_fee_rate_basis_points: u16) -> Result<()> {
+        require!(!self.is_fee_parameters_locked, ErrorCode::ParametersLocked);
+        if let Some(protocol_fee_override_basis_points) = protocol_fee_override_basis_points {
+            validate_fee_rates(providers_fee_rate_basis_points, protocol_fee_override_basis_points)?;
+        }
+        self.protocol_fee_override_basis_points = protocol_fee_override_basis_points;
+        Ok(())
+    }
+
+    /// Resyncs the tracked LP tokens supply with the actual LP mint supply.
+    ///
+    /// # Parameters
+    /// - `lp_mint_supply`: The current supply of the LP mint.
+    ///
+    /// # Errors
+    /// - Returns `ErrorCode::CpAmmNotLaunched` if the AMM is not launched.
+    /// - Returns `ErrorCode::LpTokensSupplyResyncInvalid` if the LP mint supply is below the locked liquidity.
+    pub(crate) fn resync_lp_tokens_supply(&mut self, lp_mint_supply: u64) -> Result<()> {
+        require!(self.status.is_launched(), ErrorCode::CpAmmNotLaunched);
+        require!(
+            lp_mint_supply > 0 && lp_mint_supply >= self.initial_locked_liquidity,
+            ErrorCode::LpTokensSupplyResyncInvalid
+        );
+        self.lp_tokens_supply = lp_mint_supply;
+        Ok(())
+    }
+
+    /// Sets or clears the weekly trading hours of the AMM.
+    ///
+    /// # Parameters
+    /// - `trading_schedule`: The trading hours, or `None` to allow swaps at any time.
+    pub(crate) fn update_trading_schedule(&mut self, trading_schedule: Option<TradingSchedule>) {
+        self.trading_schedule = trading_schedule;
+    }
+
+    /// Suspends or resumes individual operations of the AMM.
+    ///
+    /// Unlike pausing the pool status, this allows e.g. halting swaps during an oracle incident
+    /// while liquidity providers can still withdraw.
+    ///
+    /// # Parameters
+    /// - `swaps_paused`: Whether swaps should be suspended.
+    /// - `provides_paused`: Whether liquidity provisions should be suspended.
+    /// - `withdraws_paused`: Whether liquidity withdrawals should be suspended.
+    pub(crate) fn update_paused_operations(&mut self, swaps_paused: bool, provides_paused: bool, withdraws_paused: bool) {
+        self.swaps_paused = swaps_paused;
+        self.provides_paused = provides_paused;
+        self.withdraws_paused = withdraws_paused;
+    }
+
+    /// Restricts the AMM after a vault balance drift, suspending swaps and provides.
+    ///
+    /// Withdrawals stay available, so liquidity providers can exit with what is left in the vaults.
+    /// The authority lifts the restriction with `update_paused_operations` once the incident is resolved.
+    pub(crate) fn freeze_on_vault_drift(&mut self) {
+        self.swaps_paused = true;
+        self.provides_paused = true;
+    }
+
+    /// Updates the share of the providers fee routed to the pool creator.
+    ///
+    /// # Parameters
+    /// - `creator_fee_share_basis_points`: The share of the providers fee, in basis points.
+    /// - `max_creator_fee_share_basis_points`: The maximal share allowed by the `AmmsConfig`.
     ///
-    /// # Returns
-    /// - No return value. Modifies the internal state of the AMM.
-    #[inline(never)]
-    pub(crate) fn withdraw(&mut self, withdraw_payload: WithdrawPayload) -> (){
-        self.base_liquidity = withdraw_payload.base_liquidity;
-        self.quote_liquidity = withdraw_payload.quote_liquidity;
-        self.lp_tokens_supply = withdraw_payload.lp_tokens_supply;
-        self.constant_product_sqrt = Self::calculate_constant_product_sqrt(self.base_liquidity, self.quote_liquidity).unwrap();
-        self.base_quote_ratio_sqrt = withdraw_payload.base_quote_ratio_sqrt;
+    /// # Errors
+    /// - Returns `ErrorCode::ParametersLocked` if the fee parameters are locked.
+    /// - Returns `ErrorCode::CreatorFeeShareExceeded` if the share exceeds the `AmmsConfig` maximum.
+    pub(crate) fn update_creator_fee_share(&mut self, creator_fee_share_basis_points: u16, max_creator_fee_share_basis_points: u16) -> Result<()> {
+        require!(!self.is_fee_parameters_locked, ErrorCode::ParametersLocked);
+        require!(creator_fee_share_basis_points <= max_creator_fee_share_basis_points, ErrorCode::CreatorFeeShareExceeded);
+        self.creator_fee_share_basis_points = creator_fee_share_basis_points;
+        Ok(())
     }
 
-    /// Updates the AMM state after a token swap operation.
+    /// Permanently freezes the per-pool fee parameters, i.e. the creator fee share and the protocol fee override.
     ///
-    /// This method adjusts the base and quote liquidity, protocol fees, providers fees, constant product,
-    /// and liquidity ratio after a swap. It ensures the AMM remains consistent with the
-    /// constant product invariant.
+    /// # Errors
+    /// - Returns `ErrorCode::ParametersLocked` if the fee parameters are already locked.
+    pub(crate) fn lock_fee_parameters(&mut self) -> Result<()> {
+        require!(!self.is_fee_parameters_locked, ErrorCode::ParametersLocked);
+        self.is_fee_parameters_locked = true;
+        Ok(())
+    }
+
+    /// Enables the decimals normalization of the constant product math for the mints decimals.
+    ///
+    /// The liquidity of the mint with fewer decimals is scaled to the decimals of the other mint,
+    /// which keeps the LP tokens minted and the liquidity withdrawn precise for pairs of extreme decimals.
+    /// The scaled liquidity must fit into `u64`, so the normalization limits the liquidity the pool can hold.
     ///
     /// # Parameters
-    /// - `swap_payload`: Contains the updated liquidity values, fees, and swap details.
+    /// - `base_decimals`: The decimals of the base mint.
+    /// - `quote_decimals`: The decimals of the quote mint.
     ///
-    /// # Returns
-    /// - No return value. Modifies the internal state of the AMM.
-    #[inline(never)]
-    pub(crate) fn swap(&mut self, swap_payload: SwapPayload) {
-        self.base_liquidity = swap_payload.base_liquidity;
-        self.quote_liquidity = swap_payload.quote_liquidity;
-        if swap_payload.is_in_out{
-            self.protocol_base_fees_to_redeem = swap_payload.protocol_fees_to_redeem;
-            self.base_liquidity += swap_payload.providers_fee_amount
-        }
-        else{
-            self.protocol_quote_fees_to_redeem = swap_payload.protocol_fees_to_redeem;
-            self.quote_liquidity += swap_payload.providers_fee_amount
-        }
-        self.constant_product_sqrt = Self::calculate_constant_product_sqrt(self.base_liquidity, self.quote_liquidity).unwrap();
-        self.base_quote_ratio_sqrt = Self::calculate_base_quote_ratio_sqrt(self.base_liquidity, self.quote_liquidity).unwrap();
+    /// # Errors
+    /// - Returns `ErrorCode::CpAmmNotInitialized` if the pool is `Uninitialized`.
+    /// - Returns `ErrorCode::CpAmmAlreadyLaunched` if the pool has already been launched.
+    pub(crate) fn enable_decimals_normalization(&mut self, base_decimals: u8, quote_decimals: u8) -> Result<()> {
+        // Only pools awaiting their launch liquidity can change their virtual decimal basis
+        self.status.launch()?;
+        (self.base_normalization_exponent, self.quote_normalization_exponent) = Self::calculate_normalization_exponents(base_decimals, quote_decimals);
+        Ok(())
     }
 
-    /// Updates the protocol fees for the AMM based on the provided payload.
+    /// Prepares the payload for collecting the pool creator's fees.
     ///
-    /// This method sets the protocol fees available for redemption to the updated values
-    /// specified in the `CollectFeesPayload`.
+    /// # Returns
+    /// - `Ok(CollectCreatorFeesPayload)` with the creator fees available for redemption for both tokens.
+    /// - `Err(ErrorCode::CreatorFeesIsZero)` if there are no creator fees to collect.
+    pub fn get_collect_creator_fees_payload(&self) -> Result<CollectCreatorFeesPayload> {
+        require!(self.creator_base_fees_to_redeem > 0 || self.creator_quote_fees_to_redeem > 0, ErrorCode::CreatorFeesIsZero);
+        Ok(CollectCreatorFeesPayload {
+            creator_base_fees_to_redeem: self.creator_base_fees_to_redeem,
+            creator_quote_fees_to_redeem: self.creator_quote_fees_to_redeem,
+            new_creator_base_fees_to_redeem: 0,
+            new_creator_quote_fees_to_redeem: 0,
+        })
+    }
+
+    /// Clears the pool creator's fees once they are transferred out of the vaults.
     ///
     /// # Parameters
-    /// - `collect_fees_payload`: A `CollectFeesPayload` containing the updated protocol fees
-    ///   for both base and quote tokens.
-    ///
-    /// # Returns
-    /// - None. This method directly modifies the internal state of the AMM.
-    #[inline(never)]
-    pub(crate) fn collect_fees(&mut self, collect_fees_payload: CollectFeesPayload) {
-        self.protocol_base_fees_to_redeem = collect_fees_payload.new_protocol_base_fees_to_redeem;
-        self.protocol_quote_fees_to_redeem = collect_fees_payload.new_protocol_quote_fees_to_redeem;
+    /// - `collect_creator_fees_payload`: The creator fees that were redeemed.
+    pub(crate) fn collect_creator_fees(&mut self, collect_creator_fees_payload: CollectCreatorFeesPayload) {
+        self.creator_base_fees_to_redeem = collect_creator_fees_payload.new_creator_base_fees_to_redeem;
+        self.creator_quote_fees_to_redeem = collect_creator_fees_payload.new_creator_quote_fees_to_redeem;
     }
 
 }
@@ -598,13 +1922,14 @@ impl CpAmm {
 #[cfg(test)]
 mod cp_amm_tests {
     use anchor_lang::Discriminator;
+    use cpmm_math::cp_amm::{ADJUST_LIQUIDITY_RATIO_SQRT_TOLERANCE, SWAP_CONSTANT_PRODUCT_SQRT_TOLERANCE};
     use crate::constants::ANCHOR_DISCRIMINATOR;
     use super::*;
 
     #[derive(Default)]
     struct CpAmmBuilder {
-        is_initialized: bool,
-        is_launched: bool,
+        layout_version: u8,
+        status: PoolStatus,
         initial_locked_liquidity: u64,
         constant_product_sqrt: Q64_128,
         base_quote_ratio_sqrt: Q64_128,
@@ -624,7 +1949,36 @@ mod cp_amm_tests {
         bump: [u8; 1],
         base_vault_bump: [u8; 1],
         quote_vault_bump: [u8; 1],
-        locked_lp_vault_bump: [u8; 1]
+        locked_lp_vault_bump: [u8; 1],
+        is_incident_limited: bool,
+        withdraw_rate_limiter: WithdrawRateLimiter,
+        usd_oracle: Option<Pubkey>,
+        stats: PoolStats,
+        trading_schedule: Option<TradingSchedule>,
+        is_lend_out_enabled: bool,
+        base_lent_out: u64,
+        quote_lent_out: u64,
+        liquidity_subscriber: Option<Pubkey>,
+        protocol_fee_override_basis_points: Option<u16>,
+        swaps_paused: bool,
+        provides_paused: bool,
+        withdraws_paused: bool,
+        creator_fee_share_basis_points: u16,
+        creator_base_fees_to_redeem: u64,
+        creator_quote_fees_to_redeem: u64,
+        initialized_epoch: u64,
+        is_fee_parameters_locked: bool,
+        base_normalization_exponent: u8,
+        quote_normalization_exponent: u8,
+        trading_open_slot: u64,
+        curve_kind: CurveKind,
+        max_protocol_fee_basis_points_at_creation: Option<u16>,
+        protected_until_slot: u64,
+        display_data: DisplayData,
+        largest_provide_share_basis_points: u16,
+        concentration_flag: bool,
+        slot_start_price: u64,
+        slot_start_price_slot: u64,
     }
 
     impl CpAmmBuilder {
@@ -634,13 +1988,13 @@ mod cp_amm_tests {
             }
         }
 
-        fn is_initialized(mut self, value: bool) -> Self {
-            self.is_initialized = value;
+        fn layout_version(mut self, value: u8) -> Self {
+            self.layout_version = value;
             self
         }
 
-        fn is_launched(mut self, value: bool) -> Self {
-            self.is_launched = value;
+        fn status(mut self, value: PoolStatus) -> Self {
+            self.status = value;
             self
         }
 
@@ -743,10 +2097,115 @@ mod cp_amm_tests {
             self
         }
 
+        fn incident_limited(mut self, value: bool) -> Self {
+            self.is_incident_limited = value;
+            self
+        }
+
+        fn trading_schedule(mut self, value: Option<TradingSchedule>) -> Self {
+            self.trading_schedule = value;
+            self
+        }
+
+        fn lend_out_enabled(mut self, value: bool) -> Self {
+            self.is_lend_out_enabled = value;
+            self
+        }
+
+        fn base_lent_out(mut self, value: u64) -> Self {
+            self.base_lent_out = value;
+            self
+        }
+
+        fn quote_lent_out(mut self, value: u64) -> Self {
+            self.quote_lent_out = value;
+            self
+        }
+
+        fn liquidity_subscriber(mut self, value: Option<Pubkey>) -> Self {
+            self.liquidity_subscriber = value;
+            self
+        }
+
+        fn protocol_fee_override_basis_points(mut self, value: Option<u16>) -> Self {
+            self.protocol_fee_override_basis_points = value;
+            self
+        }
+
+        fn swaps_paused(mut self, value: bool) -> Self {
+            self.swaps_paused = value;
+            self
+        }
+
+        fn provides_paused(mut self, value: bool) -> Self {
+            self.provides_paused = value;
+            self
+        }
+
+        fn withdraws_paused(mut self, value: bool) -> Self {
+            self.withdraws_paused = value;
+            self
+        }
+
+        fn creator_fee_share_basis_points(mut self, value: u16) -> Self {
+            self.creator_fee_share_basis_points = value;
+            self
+        }
+
+        fn creator_base_fees_to_redeem(mut self, value: u64) -> Self {
+            self.creator_base_fees_to_redeem = value;
+            self
+        }
+
+        fn creator_quote_fees_to_redeem(mut self, value: u64) -> Self {
+            self.creator_quote_fees_to_redeem = value;
+            self
+        }
+
+        fn initialized_epoch(mut self, value: u64) -> Self {
+            self.initialized_epoch = value;
+            self
+        }
+
+        fn fee_parameters_locked(mut self, value: bool) -> Self {
+            self.is_fee_parameters_locked = value;
+            self
+        }
+
+        fn base_normalization_exponent(mut self, value: u8) -> Self {
+            self.base_normalization_exponent = value;
+            self
+        }
+
+        fn quote_normalization_exponent(mut self, value: u8) -> Self {
+            self.quote_normalization_exponent = value;
+            self
+        }
+
+        fn trading_open_slot(mut self, value: u64) -> Self {
+            self.trading_open_slot = value;
+            self
+        }
+
+        fn max_protocol_fee_basis_points_at_creation(mut self, value: Option<u16>) -> Self {
+            self.max_protocol_fee_basis_points_at_creation = value;
+            self
+        }
+
+        fn protected_until_slot(mut self, value: u64) -> Self {
+            self.protected_until_slot = value;
+            self
+        }
+
+        fn largest_provide_share_basis_points(mut self, value: u16) -> Self {
+            self.largest_provide_share_basis_points = value;
+            self
+        }
+
         fn build(self) -> CpAmm {
             CpAmm {
-                is_initialized: self.is_initialized,
-                is_launched: self.is_launched,
+                layout_version: self.layout_version,
+                status: self.status,
                 initial_locked_liquidity: self.initial_locked_liquidity,
                 constant_product_sqrt: self.constant_product_sqrt,
                 base_quote_ratio_sqrt: self.base_quote_ratio_sqrt,
@@ -766,7 +2225,36 @@ mod cp_amm_tests {
                 bump: self.bump,
                 base_vault_bump: self.base_vault_bump,
                 quote_vault_bump: self.quote_vault_bump,
-                locked_lp_vault_bump: self.locked_lp_vault_bump
+                locked_lp_vault_bump: self.locked_lp_vault_bump,
+                is_incident_limited: self.is_incident_limited,
+                withdraw_rate_limiter: self.withdraw_rate_limiter,
+                usd_oracle: self.usd_oracle,
+                stats: self.stats,
+                trading_schedule: self.trading_schedule,
+                is_lend_out_enabled: self.is_lend_out_enabled,
+                base_lent_out: self.base_lent_out,
+                quote_lent_out: self.quote_lent_out,
+                liquidity_subscriber: self.liquidity_subscriber,
+                protocol_fee_override_basis_points: self.protocol_fee_override_basis_points,
+                swaps_paused: self.swaps_paused,
+                provides_paused: self.provides_paused,
+                withdraws_paused: self.withdraws_paused,
+                creator_fee_share_basis_points: self.creator_fee_share_basis_points,
+                creator_base_fees_to_redeem: self.creator_base_fees_to_redeem,
+                creator_quote_fees_to_redeem: self.creator_quote_fees_to_redeem,
+                initialized_epoch: self.initialized_epoch,
+                is_fee_parameters_locked: self.is_fee_parameters_locked,
+                base_normalization_exponent: self.base_normalization_exponent,
+                quote_normalization_exponent: self.quote_normalization_exponent,
+                trading_open_slot: self.trading_open_slot,
+                curve_kind: self.curve_kind,
+                max_protocol_fee_basis_points_at_creation: self.max_protocol_fee_basis_points_at_creation,
+                protected_until_slot: self.protected_until_slot,
+                display_data: self.display_data,
+                largest_provide_share_basis_points: self.largest_provide_share_basis_points,
+                concentration_flag: self.concentration_flag,
+                slot_start_price: self.slot_start_price,
+                slot_start_price_slot: self.slot_start_price_slot,
             }
         }
     }
@@ -774,8 +2262,8 @@ mod cp_amm_tests {
     /// Tests `CpAmm` account data layout.
     #[test]
     fn test_cp_amm_data_layout(){
-        let is_initialized = true;
-        let is_launched = true;
+        let layout_version = CpAmm::LAYOUT_VERSION;
+        let status = PoolStatus::Launched;
         let initial_locked_liquidity = 1_000_000u64;
         let constant_product_sqrt = Q64_128::from_u64(2_000_000);
         let base_quote_ratio_sqrt = Q64_128::from_u64(1_000_000);
@@ -793,17 +2281,58 @@ mod cp_amm_tests {
         let amms_config = Pubkey::new_unique();
         let creator = Pubkey::new_unique();
         let bump = [42u8];
+        let base_vault_bump = [43u8];
+        let quote_vault_bump = [44u8];
+        let locked_lp_vault_bump = [45u8];
+        let is_incident_limited = true;
+        let withdraw_rate_limiter_slot = 1_234u64;
+        let withdraw_rate_limiter_supply = 100_000u64;
+        let withdraw_rate_limiter_withdrawn = 3_000u64;
+        let usd_oracle = Pubkey::new_unique();
+        let cumulative_base_volume = 7_000_000u128;
+        let cumulative_quote_volume = 3_500_000u128;
+        let cumulative_usd_volume = 10_500_000u128;
+        let cumulative_base_providers_fees = 21_000u128;
+        let cumulative_quote_providers_fees = 10_500u128;
+        let cumulative_base_protocol_fees = 7_000u128;
+        let cumulative_quote_protocol_fees = 3_500u128;
+        let trading_open_hours = [0xA5u8; 21];
+        let is_lend_out_enabled = true;
+        let base_lent_out = 40_000u64;
+        let quote_lent_out = 20_000u64;
+        let liquidity_subscriber = Pubkey::new_unique();
+        let protocol_fee_override_basis_points = 7u16;
+        let swaps_paused = true;
+        let provides_paused = false;
+        let withdraws_paused = true;
+        let creator_fee_share_basis_points = 2_500u16;
+        let creator_base_fees_to_redeem = 1_200u64;
+        let creator_quote_fees_to_redeem = 600u64;
+        let initialized_epoch = 512u64;
+        let is_fee_parameters_locked = true;
+        let base_normalization_exponent = 9u8;
+        let quote_normalization_exponent = 0u8;
+        let trading_open_slot = 4_096u64;
+        let curve_kind = CurveKind::ConstantProduct;
+        let max_protocol_fee_basis_points_at_creation = 20u16;
+        let protected_until_slot = 8_192u64;
+        let display_data_entry = [6u8, 0, 8, 0, 42, 0, 0, 0, 0, 0, 0, 0];
+        let largest_provide_share_basis_points = 3_000u16;
+        let concentration_flag = true;
+        let slot_start_price = 250_000_000u64;
+        let slot_start_price_slot = 8_000u64;
         
-        let mut data = [0u8; ANCHOR_DISCRIMINATOR + 358];
+        let mut data = [0u8; ANCHOR_DISCRIMINATOR + 834];
         let mut offset = 0;
 
         data[offset..offset + ANCHOR_DISCRIMINATOR].copy_from_slice(&CpAmm::discriminator()); offset += ANCHOR_DISCRIMINATOR;
-        data[offset] = is_initialized as u8; offset += 1;
-        data[offset] = is_launched as u8; offset += 1;
-        data[offset] = bump[0]; offset += 1;
-        data[offset] = bump[0]; offset += 1;
-        data[offset] = bump[0]; offset += 1;
+        assert_eq!(offset, CpAmm::LAYOUT_VERSION_OFFSET);
+        data[offset] = layout_version; offset += 1;
+        data[offset] = status as u8; offset += 1;
         data[offset] = bump[0]; offset += 1;
+        data[offset] = base_vault_bump[0]; offset += 1;
+        data[offset] = quote_vault_bump[0]; offset += 1;
+        data[offset] = locked_lp_vault_bump[0]; offset += 1;
         data[offset..offset + 8].copy_from_slice(&initial_locked_liquidity.to_le_bytes()); offset += 8;
         data[offset..offset + 16].copy_from_slice(&constant_product_sqrt.get_fractional_bits().to_le_bytes()); offset += 16;
         data[offset..offset + 8].copy_from_slice(&constant_product_sqrt.get_integer_bits().to_le_bytes()); offset += 8;
@@ -814,21 +2343,69 @@ mod cp_amm_tests {
         data[offset..offset + 8].copy_from_slice(&lp_tokens_supply.to_le_bytes()); offset += 8;
         data[offset..offset + 8].copy_from_slice(&protocol_base_fees_to_redeem.to_le_bytes()); offset += 8;
         data[offset..offset + 8].copy_from_slice(&protocol_quote_fees_to_redeem.to_le_bytes()); offset += 8;
+        assert_eq!(offset, CpAmm::BASE_MINT_OFFSET);
         data[offset..offset + 32].copy_from_slice(base_mint.as_ref()); offset += 32;
+        assert_eq!(offset, CpAmm::QUOTE_MINT_OFFSET);
         data[offset..offset + 32].copy_from_slice(quote_mint.as_ref()); offset += 32;
+        assert_eq!(offset, CpAmm::LP_MINT_OFFSET);
         data[offset..offset + 32].copy_from_slice(lp_mint.as_ref()); offset += 32;
         data[offset..offset + 32].copy_from_slice(base_vault.as_ref()); offset += 32;
         data[offset..offset + 32].copy_from_slice(quote_vault.as_ref()); offset += 32;
         data[offset..offset + 32].copy_from_slice(locked_lp_vault.as_ref()); offset += 32;
+        assert_eq!(offset, CpAmm::AMMS_CONFIG_OFFSET);
         data[offset..offset + 32].copy_from_slice(amms_config.as_ref()); offset += 32;
+        assert_eq!(offset, CpAmm::CREATOR_OFFSET);
         data[offset..offset + 32].copy_from_slice(creator.as_ref()); offset += 32;
+        data[offset] = is_incident_limited as u8; offset += 1;
+        data[offset..offset + 8].copy_from_slice(&withdraw_rate_limiter_slot.to_le_bytes()); offset += 8;
+        data[offset..offset + 8].copy_from_slice(&withdraw_rate_limiter_supply.to_le_bytes()); offset += 8;
+        data[offset..offset + 8].copy_from_slice(&withdraw_rate_limiter_withdrawn.to_le_bytes()); offset += 8;
+        data[offset] = 1; offset += 1;
+        data[offset..offset + 32].copy_from_slice(usd_oracle.as_ref()); offset += 32;
+        data[offset..offset + 16].copy_from_slice(&cumulative_base_volume.to_le_bytes()); offset += 16;
+        data[offset..offset + 16].copy_from_slice(&cumulative_quote_volume.to_le_bytes()); offset += 16;
+        data[offset..offset + 16].copy_from_slice(&cumulative_usd_volume.to_le_bytes()); offset += 16;
+        data[offset..offset + 16].copy_from_slice(&cumulative_base_providers_fees.to_le_bytes()); offset += 16;
+        data[offset..offset + 16].copy_from_slice(&cumulative_quote_providers_fees.to_le_bytes()); offset += 16;
+        data[offset..offset + 16].copy_from_slice(&cumulative_base_protocol_fees.to_le_bytes()); offset += 16;
+        data[offset..offset + 16].copy_from_slice(&cumulative_quote_protocol_fees.to_le_bytes()); offset += 16;
+        data[offset] = 1; offset += 1;
+        data[offset..offset + 21].copy_from_slice(&trading_open_hours); offset += 21;
+        data[offset] = is_lend_out_enabled as u8; offset += 1;
+        data[offset..offset + 8].copy_from_slice(&base_lent_out.to_le_bytes()); offset += 8;
+        data[offset..offset + 8].copy_from_slice(&quote_lent_out.to_le_bytes()); offset += 8;
+        data[offset] = 1; offset += 1;
+        data[offset..offset + 32].copy_from_slice(liquidity_subscriber.as_ref()); offset += 32;
+        data[offset] = 1; offset += 1;
+        data[offset..offset + 2].copy_from_slice(&protocol_fee_override_basis_points.to_le_bytes()); offset += 2;
+        data[offset] = swaps_paused as u8; offset += 1;
+        data[offset] = provides_paused as u8; offset += 1;
+        data[offset] = withdraws_paused as u8; offset += 1;
+        data[offset..offset + 2].copy_from_slice(&creator_fee_share_basis_points.to_le_bytes()); offset += 2;
+        data[offset..offset + 8].copy_from_slice(&creator_base_fees_to_redeem.to_le_bytes()); offset += 8;
+        data[offset..offset + 8].copy_from_slice(&creator_quote_fees_to_redeem.to_le_bytes()); offset += 8;
+        data[offset..offset + 8].copy_from_slice(&initialized_epoch.to_le_bytes()); offset += 8;
+        assert_eq!(offset, CpAmm::INITIALIZED_EPOCH_SPACE);
+        data[offset] = is_fee_parameters_locked as u8; offset += 1;
+        data[offset] = base_normalization_exponent; offset += 1;
+        data[offset] = quote_normalization_exponent; offset += 1;
+        data[offset..offset + 8].copy_from_slice(&trading_open_slot.to_le_bytes()); offset += 8;
+        data[offset] = curve_kind as u8; offset += 1;
+        data[offset] = 1; offset += 1;
+        data[offset..offset + 2].copy_from_slice(&max_protocol_fee_basis_points_at_creation.to_le_bytes()); offset += 2;
+        data[offset..offset + 8].copy_from_slice(&protected_until_slot.to_le_bytes()); offset += 8;
+        data[offset..offset + 12].copy_from_slice(&display_data_entry); offset += 160;
+        data[offset..offset + 2].copy_from_slice(&largest_provide_share_basis_points.to_le_bytes()); offset += 2;
+        data[offset] = concentration_flag as u8; offset += 1;
+        data[offset..offset + 8].copy_from_slice(&slot_start_price.to_le_bytes()); offset += 8;
+        data[offset..offset + 8].copy_from_slice(&slot_start_price_slot.to_le_bytes()); offset += 8;
         
         assert_eq!(ANCHOR_DISCRIMINATOR + CpAmm::INIT_SPACE, offset);
 
         let deserialized_cp_amm = CpAmm::try_deserialize(&mut data.as_ref()).unwrap();
 
-        assert_eq!(deserialized_cp_amm.is_initialized, is_initialized);
-        assert_eq!(deserialized_cp_amm.is_launched, is_launched);
+        assert_eq!(deserialized_cp_amm.layout_version, layout_version);
+        assert_eq!(deserialized_cp_amm.status, status);
         assert_eq!(deserialized_cp_amm.initial_locked_liquidity, initial_locked_liquidity);
         assert_eq!(deserialized_cp_amm.constant_product_sqrt, constant_product_sqrt);
         assert_eq!(deserialized_cp_amm.base_quote_ratio_sqrt, base_quote_ratio_sqrt);
@@ -845,23 +2422,217 @@ mod cp_amm_tests {
         assert_eq!(deserialized_cp_amm.locked_lp_vault, locked_lp_vault);
         assert_eq!(deserialized_cp_amm.amms_config, amms_config);
         assert_eq!(deserialized_cp_amm.bump, bump);
-        assert_eq!(deserialized_cp_amm.base_vault_bump, bump);
-        assert_eq!(deserialized_cp_amm.quote_vault_bump, bump);
-        assert_eq!(deserialized_cp_amm.locked_lp_vault_bump, bump);
+        assert_eq!(deserialized_cp_amm.base_vault_bump, base_vault_bump);
+        assert_eq!(deserialized_cp_amm.quote_vault_bump, quote_vault_bump);
+        assert_eq!(deserialized_cp_amm.locked_lp_vault_bump, locked_lp_vault_bump);
+        assert_eq!(deserialized_cp_amm.is_incident_limited, is_incident_limited);
+        assert_eq!(deserialized_cp_amm.withdraw_rate_limiter.slot(), withdraw_rate_limiter_slot);
+        assert_eq!(deserialized_cp_amm.withdraw_rate_limiter.slot_lp_tokens_supply(), withdraw_rate_limiter_supply);
+        assert_eq!(deserialized_cp_amm.withdraw_rate_limiter.withdrawn_lp_tokens(), withdraw_rate_limiter_withdrawn);
+        assert_eq!(deserialized_cp_amm.usd_oracle, Some(usd_oracle));
+        assert_eq!(deserialized_cp_amm.stats.cumulative_base_volume(), cumulative_base_volume);
+        assert_eq!(deserialized_cp_amm.stats.cumulative_quote_volume(), cumulative_quote_volume);
+        assert_eq!(deserialized_cp_amm.stats.cumulative_usd_volume(), cumulative_usd_volume);
+        assert_eq!(deserialized_cp_amm.stats.cumulative_base_providers_fees(), cumulative_base_providers_fees);
+        assert_eq!(deserialized_cp_amm.stats.cumulative_quote_providers_fees(), cumulative_quote_providers_fees);
+        assert_eq!(deserialized_cp_amm.stats.cumulative_base_protocol_fees(), cumulative_base_protocol_fees);
+        assert_eq!(deserialized_cp_amm.stats.cumulative_quote_protocol_fees(), cumulative_quote_protocol_fees);
+        assert_eq!(deserialized_cp_amm.trading_schedule, Some(TradingSchedule::new(trading_open_hours)));
+        assert_eq!(deserialized_cp_amm.is_lend_out_enabled, is_lend_out_enabled);
+        assert_eq!(deserialized_cp_amm.base_lent_out, base_lent_out);
+        assert_eq!(deserialized_cp_amm.quote_lent_out, quote_lent_out);
+        assert_eq!(deserialized_cp_amm.liquidity_subscriber, Some(liquidity_subscriber));
+        assert_eq!(deserialized_cp_amm.protocol_fee_override_basis_points, Some(protocol_fee_override_basis_points));
+        assert_eq!(deserialized_cp_amm.swaps_paused, swaps_paused);
+        assert_eq!(deserialized_cp_amm.provides_paused, provides_paused);
+        assert_eq!(deserialized_cp_amm.withdraws_paused, withdraws_paused);
+        assert_eq!(deserialized_cp_amm.creator_fee_share_basis_points, creator_fee_share_basis_points);
+        assert_eq!(deserialized_cp_amm.creator_base_fees_to_redeem, creator_base_fees_to_redeem);
+        assert_eq!(deserialized_cp_amm.creator_quote_fees_to_redeem, creator_quote_fees_to_redeem);
+        assert_eq!(deserialized_cp_amm.initialized_epoch, initialized_epoch);
+        assert_eq!(deserialized_cp_amm.is_fee_parameters_locked, is_fee_parameters_locked);
+        assert_eq!(deserialized_cp_amm.base_normalization_exponent, base_normalization_exponent);
+        assert_eq!(deserialized_cp_amm.quote_normalization_exponent, quote_normalization_exponent);
+        assert_eq!(deserialized_cp_amm.trading_open_slot, trading_open_slot);
+        assert_eq!(deserialized_cp_amm.curve_kind, curve_kind);
+        assert_eq!(deserialized_cp_amm.max_protocol_fee_basis_points_at_creation, Some(max_protocol_fee_basis_points_at_creation));
+        assert_eq!(deserialized_cp_amm.protected_until_slot, protected_until_slot);
+        assert_eq!(deserialized_cp_amm.display_data.entry(DisplayEntryType::LastUpdateSlot), Some(42u64.to_le_bytes().as_ref()));
+        assert_eq!(deserialized_cp_amm.largest_provide_share_basis_points, largest_provide_share_basis_points);
+        assert_eq!(deserialized_cp_amm.concentration_flag, concentration_flag);
+        assert_eq!(deserialized_cp_amm.slot_start_price, slot_start_price);
+        assert_eq!(deserialized_cp_amm.slot_start_price_slot, slot_start_price_slot);
 
         let mut serialized_cp_amm = Vec::new();
         deserialized_cp_amm.try_serialize(&mut serialized_cp_amm).unwrap();
         assert_eq!(serialized_cp_amm.as_slice(), data.as_ref());
     }
     
+    /// Tests the layout version assertion of the `CpAmm` struct.
+    #[test]
+    fn test_cp_amm_assert_layout_version() {
+        let amm = CpAmmBuilder::new().layout_version(CpAmm::LAYOUT_VERSION).build();
+        assert_eq!(amm.layout_version(), CpAmm::LAYOUT_VERSION);
+        assert!(amm.assert_layout_version().is_ok());
+
+        let outdated_amm = CpAmmBuilder::new().layout_version(CpAmm::LAYOUT_VERSION - 1).build();
+        assert!(outdated_amm.assert_layout_version().is_err());
+        let future_amm = CpAmmBuilder::new().layout_version(CpAmm::LAYOUT_VERSION + 1).build();
+        assert!(future_amm.assert_layout_version().is_err());
+    }
+
+    /// Tests the validation of `CpAmm` layout upgrades of accounts written by older program builds.
+    #[test]
+    fn test_cp_amm_validate_layout_upgrade() {
+        let space = 8 + CpAmm::INIT_SPACE;
+        let amm = CpAmmBuilder::new()
+            .layout_version(CpAmm::LAYOUT_VERSION)
+            .status(PoolStatus::Launched)
+            .base_liquidity(4000)
+            .quote_liquidity(5000)
+            .build();
+        let mut data = Vec::new();
+        amm.try_serialize(&mut data).unwrap();
+        // Accounts are allocated with the maximum size of the layout
+        data.resize(space, 0);
+        assert_eq!(CpAmm::validate_layout_upgrade(&data).err(), Some(ErrorCode::CpAmmLayoutUpToDate.into()));
+
+        // Outdated version of the current size
+        let mut outdated_data = data.clone();
+        outdated_data[8] = CpAmm::LAYOUT_VERSION - 1;
+        assert_eq!(CpAmm::validate_layout_upgrade(&outdated_data).unwrap(), space);
+
+        // Account written before the trailing fields were appended
+        let mut short_data = data[..space - 199].to_vec();
+        assert_eq!(CpAmm::validate_layout_upgrade(&short_data).unwrap(), space);
+        short_data.resize(space, 0);
+        let upgraded_amm = CpAmm::try_deserialize(&mut short_data.as_slice()).unwrap();
+        assert_eq!(upgraded_amm.status(), PoolStatus::Launched);
+        assert_eq!(upgraded_amm.base_liquidity(), 4000);
+        assert_eq!(upgraded_amm.quote_liquidity(), 5000);
+        assert_eq!(upgraded_amm.trading_open_slot(), 0);
+        assert_eq!(upgraded_amm.curve_kind(), CurveKind::ConstantProduct);
+        assert_eq!(upgraded_amm.max_protocol_fee_basis_points_at_creation(), None);
+        assert_eq!(upgraded_amm.protected_until_slot(), 0);
+        assert_eq!(upgraded_amm.display_data().entries().count(), 0);
+        assert_eq!(upgraded_amm.largest_provide_share_basis_points(), 0);
+        assert!(!upgraded_amm.concentration_flag());
+        assert_eq!(upgraded_amm.slot_start_price_slot(), 0);
+
+        let mut future_data = data.clone();
+        future_data[8] = CpAmm::LAYOUT_VERSION + 1;
+        assert_eq!(CpAmm::validate_layout_upgrade(&future_data).err(), Some(ErrorCode::AccountVersionMismatch.into()));
+        let mut oversized_data = data.clone();
+        oversized_data.push(0);
+        assert_eq!(CpAmm::validate_layout_upgrade(&oversized_data).err(), Some(ErrorCode::AccountVersionMismatch.into()));
+
+        let mut foreign_data = data.clone();
+        foreign_data[0] ^= 1;
+        assert_eq!(
+            CpAmm::validate_layout_upgrade(&foreign_data).err(),
+            Some(anchor_lang::error::ErrorCode::AccountDiscriminatorMismatch.into())
+        );
+        assert!(CpAmm::validate_layout_upgrade(&data[..8]).is_err());
+    }
+
+    /// Tests the upgrade of `CpAmm` accounts written before the layout was versioned, which store
+    /// the `is_initialized` and `is_launched` flags in place of the layout version and the status.
+    #[test]
+    fn test_cp_amm_upgrade_unversioned_layout() {
+        let space = 8 + CpAmm::INIT_SPACE;
+        let base_liquidity = 4000u64;
+        let quote_liquidity = 5000u64;
+        let lp_mint = Pubkey::new_unique();
+        let creator = Pubkey::new_unique();
+
+        let unversioned_data = |is_launched: bool| {
+            let mut data = vec![0u8; CpAmm::UNVERSIONED_SPACE];
+            let mut offset = 0;
+            data[offset..offset + ANCHOR_DISCRIMINATOR].copy_from_slice(&CpAmm::discriminator()); offset += ANCHOR_DISCRIMINATOR;
+            data[offset] = 1; offset += 1;
+            data[offset] = is_launched as u8; offset += 1;
+            offset += 4 + 8 + 24 + 24;
+            data[offset..offset + 8].copy_from_slice(&base_liquidity.to_le_bytes()); offset += 8;
+            data[offset..offset + 8].copy_from_slice(&quote_liquidity.to_le_bytes());
+            data[CpAmm::LP_MINT_OFFSET..CpAmm::LP_MINT_OFFSET + 32].copy_from_slice(lp_mint.as_ref());
+            data[CpAmm::CREATOR_OFFSET..CpAmm::CREATOR_OFFSET + 32].copy_from_slice(creator.as_ref());
+            data
+        };
+
+        for (is_launched, status) in [(true, PoolStatus::Launched), (false, PoolStatus::Initialized)] {
+            let mut data = unversioned_data(is_launched);
+            assert_eq!(CpAmm::validate_layout_upgrade(&data).unwrap(), space);
+            data.resize(space, 0);
+            CpAmm::upgrade_layout(&mut data, CpAmm::UNVERSIONED_SPACE, 0).unwrap();
+
+            let upgraded_amm = CpAmm::try_deserialize(&mut data.as_slice()).unwrap();
+            assert_eq!(upgraded_amm.layout_version(), CpAmm::LAYOUT_VERSION);
+            assert_eq!(upgraded_amm.status(), status);
+            assert_eq!(upgraded_amm.base_liquidity(), base_liquidity);
+            assert_eq!(upgraded_amm.quote_liquidity(), quote_liquidity);
+            assert_eq!(upgraded_amm.lp_mint, lp_mint);
+            assert_eq!(upgraded_amm.creator(), &creator);
+            // A launched pool can't be launched again over its liquidity
+            assert_eq!(upgraded_amm.status().launch().is_ok(), !is_launched);
+        }
+
+        let mut invalid_data = unversioned_data(true);
+        invalid_data[9] = 2;
+        assert_eq!(CpAmm::validate_layout_upgrade(&invalid_data).err(), Some(ErrorCode::AccountVersionMismatch.into()));
+
+        // Versioned accounts keep their status
+        let amm = CpAmmBuilder::new().layout_version(CpAmm::LAYOUT_VERSION - 1).status(PoolStatus::Paused).build();
+        let mut data = Vec::new();
+        amm.try_serialize(&mut data).unwrap();
+        data.resize(space, 0);
+        CpAmm::upgrade_layout(&mut data, space, 0).unwrap();
+        let upgraded_amm = CpAmm::try_deserialize(&mut data.as_slice()).unwrap();
+        assert_eq!(upgraded_amm.layout_version(), CpAmm::LAYOUT_VERSION);
+        assert_eq!(upgraded_amm.status(), PoolStatus::Paused);
+    }
+
+    /// Tests that a never launched pool written before `initialized_epoch` was recorded can't be closed as
+    /// stale right after its upgrade, while a launched pool keeps its zero epoch.
+    #[test]
+    fn test_cp_amm_upgrade_layout_initialized_epoch() {
+        let space = 8 + CpAmm::INIT_SPACE;
+        let unversioned_data = |is_launched: bool| {
+            let mut data = vec![0u8; CpAmm::UNVERSIONED_SPACE];
+            data[..ANCHOR_DISCRIMINATOR].copy_from_slice(&CpAmm::discriminator());
+            data[8] = 1;
+            data[9] = is_launched as u8;
+            data
+        };
+
+        let mut data = unversioned_data(false);
+        data.resize(space, 0);
+        CpAmm::upgrade_layout(&mut data, CpAmm::UNVERSIONED_SPACE, 500).unwrap();
+        let upgraded_amm = CpAmm::try_deserialize(&mut data.as_slice()).unwrap();
+        assert_eq!(upgraded_amm.initialized_epoch(), 500);
+        assert_eq!(upgraded_amm.validate_stale_close(505, 10).err(), Some(ErrorCode::CpAmmNotStale.into()));
+        upgraded_amm.validate_stale_close(510, 10).unwrap();
+
+        let mut data = unversioned_data(true);
+        data.resize(space, 0);
+        CpAmm::upgrade_layout(&mut data, CpAmm::UNVERSIONED_SPACE, 500).unwrap();
+        assert_eq!(CpAmm::try_deserialize(&mut data.as_slice()).unwrap().initialized_epoch(), 0);
+
+        // Accounts already recording the epoch keep it
+        let amm = CpAmmBuilder::new().layout_version(CpAmm::LAYOUT_VERSION - 1).status(PoolStatus::Initialized).initialized_epoch(100).build();
+        let mut data = Vec::new();
+        amm.try_serialize(&mut data).unwrap();
+        data.resize(space, 0);
+        CpAmm::upgrade_layout(&mut data, CpAmm::INITIALIZED_EPOCH_SPACE, 500).unwrap();
+        assert_eq!(CpAmm::try_deserialize(&mut data.as_slice()).unwrap().initialized_epoch(), 100);
+    }
+
     /// Tests getter methods of the `CpAmm` struct.
     #[test]
     fn test_cp_amm_getters() {
         let unique_pubkey = Pubkey::new_unique();
 
         let amm = CpAmmBuilder::new()
-            .is_initialized(true)
-            .is_launched(false)
+            .status(PoolStatus::Initialized)
             .initial_locked_liquidity(1000)
             .constant_product_sqrt(Q64_128::from_u64(2000))
             .base_quote_ratio_sqrt(Q64_128::from_u64(3000))
@@ -880,14 +2651,18 @@ mod cp_amm_tests {
             .base_vault_bump([245])
             .quote_vault_bump([212])
             .locked_lp_vault_bump([123])
+            .incident_limited(true)
             .build();
 
+        assert_eq!(amm.status(), PoolStatus::Initialized);
         assert!(amm.is_initialized());
         assert!(!amm.is_launched());
         assert_eq!(amm.bump(), 253);
         assert_eq!(amm.base_vault_bump(), 245);
         assert_eq!(amm.quote_vault_bump(), 212);
         assert_eq!(amm.locked_lp_vault_bump(), 123);
+        assert!(amm.is_incident_limited());
+        assert_eq!(amm.withdraw_rate_limiter(), &WithdrawRateLimiter::default());
         assert_eq!(amm.base_mint(), &unique_pubkey);
         assert_eq!(amm.quote_mint(), &unique_pubkey);
         assert_eq!(amm.lp_mint, unique_pubkey);
@@ -920,9 +2695,10 @@ mod cp_amm_tests {
                 400000,
             );
             
-            amm.launch(launch_payload);
+            amm.launch(launch_payload, 1_000);
 
-            assert!(amm.is_launched);
+            assert_eq!(amm.status, PoolStatus::Launched);
+            assert_eq!(amm.trading_open_slot, 1_000);
             assert_eq!(amm.base_liquidity, 400000);
             assert_eq!(amm.quote_liquidity, 400000);
             assert_eq!(amm.lp_tokens_supply, 400000);
@@ -965,7 +2741,9 @@ mod cp_amm_tests {
                 1000,
                 5000,
                 400,
+                400,
                 100,
+                WithdrawRateLimiter::default().register_withdraw(10, 5400, 400, 10000).unwrap(),
             );
 
             amm.withdraw(withdraw_payload);
@@ -975,6 +2753,338 @@ mod cp_amm_tests {
             assert_eq!(amm.lp_tokens_supply, 5000);
             assert_eq!(amm.base_quote_ratio_sqrt, Q64_128::from_u64(2));
             assert_eq!(amm.constant_product_sqrt, Q64_128::from_u64(2000));
+            assert_eq!(amm.withdraw_rate_limiter.slot(), 10);
+            assert_eq!(amm.withdraw_rate_limiter.withdrawn_lp_tokens(), 400);
+        }
+
+        /// Tests the `update_incident_mode` method of `CpAmm`.
+        #[test]
+        fn test_update_incident_mode() {
+            let mut amm = CpAmmBuilder::new().build();
+            amm.withdraw_rate_limiter = WithdrawRateLimiter::default().register_withdraw(10, 5000, 400, 10000).unwrap();
+
+            amm.update_incident_mode(true);
+            assert!(amm.is_incident_limited);
+            assert_eq!(amm.withdraw_rate_limiter, WithdrawRateLimiter::default());
+
+            amm.update_incident_mode(false);
+            assert!(!amm.is_incident_limited);
+        }
+
+        /// Tests the `update_usd_oracle` method of `CpAmm`.
+        #[test]
+        fn test_update_usd_oracle() {
+            let mut amm = CpAmmBuilder::new().build();
+            let usd_oracle = Pubkey::new_unique();
+
+            amm.update_usd_oracle(Some(usd_oracle));
+            assert_eq!(amm.usd_oracle(), Some(&usd_oracle));
+
+            amm.update_usd_oracle(None);
+            assert_eq!(amm.usd_oracle(), None);
+        }
+
+        /// Tests the `update_liquidity_subscriber` method of `CpAmm`.
+        #[test]
+        fn test_update_liquidity_subscriber() {
+            let liquidity_subscriber = Pubkey::new_unique();
+            let mut amm = CpAmmBuilder::new().liquidity_subscriber(Some(liquidity_subscriber)).build();
+            assert_eq!(amm.liquidity_subscriber(), Some(&liquidity_subscriber));
+
+            amm.update_liquidity_subscriber(None);
+            assert_eq!(amm.liquidity_subscriber(), None);
+
+            let new_liquidity_subscriber = Pubkey::new_unique();
+            amm.update_liquidity_subscriber(Some(new_liquidity_subscriber));
+            assert_eq!(amm.liquidity_subscriber(), Some(&new_liquidity_subscriber));
+        }
+
+        /// Tests the `update_protocol_fee_override` method of `CpAmm`.
+        #[test]
+        fn test_update_protocol_fee_override() {
+            let mut amm = CpAmmBuilder::new().protocol_fee_override_basis_points(Some(5)).build();
+            assert_eq!(amm.protocol_fee_override_basis_points(), Some(5));
+            assert_eq!(amm.protocol_fee_rate_basis_points(30), 5);
+
+            // A zero override waives the protocol fee
+            amm.update_protocol_fee_override(Some(0), 100).unwrap();
+            assert_eq!(amm.protocol_fee_rate_basis_points(30), 0);
+
+            assert_eq!(
+                amm.update_protocol_fee_override(Some(9_901), 100).err(),
+                Some(ErrorCode::ConfigFeeRateExceeded.into())
+            );
+            assert_eq!(amm.protocol_fee_override_basis_points(), Some(0));
+
+            amm.update_protocol_fee_override(None, 100).unwrap();
+            assert_eq!(amm.protocol_fee_override_basis_points(), None);
+            assert_eq!(amm.protocol_fee_rate_basis_points(30), 30);
+        }
+
+        /// Tests that the protocol fee rate is capped by the rate recorded at the AMM initialization.
+        #[test]
+        fn test_protocol_fee_rate_capped_at_creation() {
+            let mut amm = CpAmmBuilder::new().max_protocol_fee_basis_points_at_creation(Some(20)).build();
+            assert_eq!(amm.protocol_fee_rate_basis_points(10), 10);
+            assert_eq!(amm.protocol_fee_rate_basis_points(20), 20);
+            // Config raises past the recorded cap are not applied to the AMM
+            assert_eq!(amm.protocol_fee_rate_basis_points(50), 20);
+
+            amm.update_protocol_fee_override(Some(40), 100).unwrap();
+            assert_eq!(amm.protocol_fee_rate_basis_points(10), 20);
+            amm.update_protocol_fee_override(Some(5), 100).unwrap();
+            assert_eq!(amm.protocol_fee_rate_basis_points(50), 5);
+
+            let legacy_amm = CpAmmBuilder::new().build();
+            assert_eq!(legacy_amm.max_protocol_fee_basis_points_at_creation(), None);
+            assert_eq!(legacy_amm.protocol_fee_rate_basis_points(50), 50);
+        }
+
+        /// Tests the liquidity delta between a `LiquiditySnapshot` and the updated `CpAmm`.
+        #[test]
+        fn test_liquidity_snapshot_get_delta() {
+            use crate::liquidity_hook::{LiquidityDelta, LiquidityDeltaKind, LiquiditySnapshot};
+
+            let cp_amm_key = Pubkey::new_unique();
+            let amm_before = CpAmmBuilder::new().base_liquidity(3000).quote_liquidity(2000).lp_tokens_supply(2400).build();
+            let amm_after = CpAmmBuilder::new().base_liquidity(3500).quote_liquidity(1800).lp_tokens_supply(2300).build();
+
+            let liquidity_snapshot = LiquiditySnapshot::of(&amm_before);
+            assert_eq!(liquidity_snapshot.get_delta(cp_amm_key, &amm_after, LiquidityDeltaKind::Swap, 42), LiquidityDelta {
+                cp_amm: cp_amm_key,
+                kind: LiquidityDeltaKind::Swap,
+                slot: 42,
+                base_liquidity_delta: 500,
+                quote_liquidity_delta: -200,
+                lp_tokens_supply_delta: -100,
+                base_liquidity: 3500,
+                quote_liquidity: 1800,
+                lp_tokens_supply: 2300,
+            });
+            assert_eq!(liquidity_snapshot.get_delta(cp_amm_key, &amm_before, LiquidityDeltaKind::Repay, 43).base_liquidity_delta, 0);
+        }
+
+        /// Tests that a fee settlement converting protocol fees through the reserves is reported to the
+        /// liquidity subscriber as a `FeeSettlement` delta, like `collect_fees_from_cp_amm` notifies it.
+        #[test]
+        fn test_fee_settlement_liquidity_delta() {
+            use crate::liquidity_hook::{LiquidityDeltaKind, LiquiditySnapshot};
+
+            let cp_amm_key = Pubkey::new_unique();
+            let mut amm = CpAmmBuilder::new()
+                .status(PoolStatus::Launched)
+                .base_liquidity(6_000_000)
+                .quote_liquidity(1_500_000)
+                .constant_product_sqrt(Q64_128::from_u64(3_000_000))
+                .base_quote_ratio_sqrt(Q64_128::from_u64(2))
+                .lp_tokens_supply(3_000_000)
+                .protocol_base_fees_to_redeem(60_000)
+                .build();
+            let liquidity_snapshot = LiquiditySnapshot::of(&amm);
+            let payload = amm.get_collect_fees_payload(60_000, 0, 0, 30, Some(&FeeSettlement::try_new(None, 500).unwrap()), SWAP_CONSTANT_PRODUCT_SQRT_TOLERANCE, 1).unwrap();
+            assert!(amm.collect_fees(payload));
+
+            let delta = liquidity_snapshot.get_delta(cp_amm_key, &amm, LiquidityDeltaKind::FeeSettlement, 1);
+            assert_eq!(delta.kind, LiquidityDeltaKind::FeeSettlement);
+            assert_eq!(delta.base_liquidity_delta, 60_000);
+            assert!(delta.quote_liquidity_delta < 0);
+            assert_eq!(delta.lp_tokens_supply_delta, 0);
+            assert_eq!((delta.base_liquidity, delta.quote_liquidity), (amm.base_liquidity, amm.quote_liquidity));
+
+            // Collecting without a settlement leaves the liquidity and the subscriber untouched
+            let mut amm = CpAmmBuilder::new().protocol_base_fees_to_redeem(60_000).build();
+            let payload = amm.get_collect_fees_payload(60_000, 0, 0, 30, None, SWAP_CONSTANT_PRODUCT_SQRT_TOLERANCE, 1).unwrap();
+            assert!(!amm.collect_fees(payload));
+        }
+
+        /// Tests the validation of the LP mint at the launch of a `CpAmm`.
+        #[test]
+        fn test_validate_launch_lp_mint() {
+            let cp_amm = Pubkey::new_unique();
+            CpAmm::validate_launch_lp_mint(&cp_amm, 0, None).unwrap();
+            CpAmm::validate_launch_lp_mint(&cp_amm, 0, Some(cp_amm)).unwrap();
+            assert_eq!(CpAmm::validate_launch_lp_mint(&cp_amm, 1, None).unwrap_err(), ErrorCode::LaunchLpMintSupplyNotZero.into());
+            assert_eq!(
+                CpAmm::validate_launch_lp_mint(&cp_amm, 0, Some(Pubkey::new_unique())).unwrap_err(),
+                ErrorCode::LaunchLpMintFreezeAuthorityInvalid.into()
+            );
+        }
+
+        /// Tests the `validate_lp_tokens_supply` and `resync_lp_tokens_supply` methods of `CpAmm`.
+        #[test]
+        fn test_lp_tokens_supply_sync() {
+            let mut amm = CpAmmBuilder::new()
+                .status(PoolStatus::Launched)
+                .initial_locked_liquidity(1_000)
+                .lp_tokens_supply(3_000_000)
+                .build();
+
+            amm.validate_lp_tokens_supply(3_000_000).unwrap();
+            // LP tokens burned directly through the token program
+            amm.validate_lp_tokens_supply(2_999_999).unwrap();
+            assert_eq!(amm.validate_lp_tokens_supply(3_000_001).unwrap_err(), ErrorCode::LpTokensSupplyDesync.into());
+
+            amm.resync_lp_tokens_supply(2_999_999).unwrap();
+            assert_eq!(amm.lp_tokens_supply, 2_999_999);
+            assert_eq!(amm.validate_lp_tokens_supply(3_000_000).unwrap_err(), ErrorCode::LpTokensSupplyDesync.into());
+
+            assert_eq!(amm.resync_lp_tokens_supply(999).unwrap_err(), ErrorCode::LpTokensSupplyResyncInvalid.into());
+            assert_eq!(amm.lp_tokens_supply, 2_999_999);
+
+            let mut amm = CpAmmBuilder::new().build();
+            assert_eq!(amm.resync_lp_tokens_supply(1_000).unwrap_err(), ErrorCode::CpAmmNotLaunched.into());
+            assert_eq!(amm.resync_lp_tokens_supply(0).unwrap_err(), ErrorCode::CpAmmNotLaunched.into());
+        }
+
+        /// Tests the `update_trading_schedule` and `is_market_open` methods of `CpAmm`.
+        #[test]
+        fn test_update_trading_schedule() {
+            // Thursday, 1970-01-01 00:00:00 UTC is the 72nd hour of the week
+            let mut open_hours = [0u8; 21];
+            open_hours[9] = 1;
+            let schedule = TradingSchedule::new(open_hours);
+
+            let mut amm = CpAmmBuilder::new().trading_schedule(Some(schedule)).build();
+            assert_eq!(amm.trading_schedule(), Some(&schedule));
+            assert!(amm.is_market_open(0));
+            assert!(amm.is_market_open(3599));
+            assert!(!amm.is_market_open(3600));
+
+            amm.update_trading_schedule(None);
+            assert_eq!(amm.trading_schedule(), None);
+            assert!(amm.is_market_open(3600));
+
+            amm.update_trading_schedule(Some(TradingSchedule::default()));
+            assert!(!amm.is_market_open(0));
+        }
+
+        /// Tests the `update_paused_operations` method of `CpAmm`.
+        #[test]
+        fn test_update_paused_operations() {
+            let mut amm = CpAmmBuilder::new().build();
+            assert!(!amm.swaps_paused() && !amm.provides_paused() && !amm.withdraws_paused());
+
+            amm.update_paused_operations(true, false, false);
+            assert!(amm.swaps_paused());
+            assert!(!amm.provides_paused());
+            assert!(!amm.withdraws_paused());
+
+            amm.update_paused_operations(false, true, true);
+            assert!(!amm.swaps_paused());
+            assert!(amm.provides_paused());
+            assert!(amm.withdraws_paused());
+        }
+
+        /// Tests the `check_vault_balances` and `freeze_on_vault_drift` methods of `CpAmm`.
+        #[test]
+        fn test_vault_drift() {
+            let mut amm = CpAmmBuilder::new()
+                .status(PoolStatus::Launched)
+                .base_liquidity(1_000_000)
+                .quote_liquidity(4_000_000)
+                .lp_tokens_supply(2_000_000)
+                .protocol_base_fees_to_redeem(300)
+                .creator_base_fees_to_redeem(200)
+                .protocol_quote_fees_to_redeem(1_000)
+                .base_lent_out(100_000)
+                .build();
+            assert_eq!(amm.recorded_vault_balances(), (900_500, 4_001_000));
+
+            amm.check_vault_balances(900_500, 4_001_000).unwrap();
+            // Donations raise the balances above the recorded amounts
+            amm.check_vault_balances(1_000_000, 5_000_000).unwrap();
+            assert_eq!(amm.check_vault_balances(900_499, 4_001_000).unwrap_err(), ErrorCode::VaultBalanceDrift.into());
+            assert_eq!(amm.check_vault_balances(900_500, 4_000_999).unwrap_err(), ErrorCode::VaultBalanceDrift.into());
+
+            let amm_max = CpAmmBuilder::new()
+                .base_liquidity(u64::MAX)
+                .protocol_base_fees_to_redeem(u64::MAX)
+                .creator_base_fees_to_redeem(u64::MAX)
+                .build();
+            assert_eq!(amm_max.check_vault_balances(u64::MAX, 0).unwrap_err(), ErrorCode::VaultBalanceDrift.into());
+
+            amm.freeze_on_vault_drift();
+            assert!(amm.swaps_paused() && amm.provides_paused() && !amm.withdraws_paused());
+            assert_eq!(amm.get_swap_payload(1_000, 0, u64::MAX, 0, 0, true, None, SWAP_CONSTANT_PRODUCT_SQRT_TOLERANCE, 0).unwrap_err(), ErrorCode::CpAmmSwapsPaused.into());
+            assert_eq!(amm.get_provide_payload(1_000, 4_000, 0, ADJUST_LIQUIDITY_RATIO_SQRT_TOLERANCE).unwrap_err(), ErrorCode::CpAmmProvidesPaused.into());
+        }
+
+        /// Tests the `check_protocol_fees_cap` method of `CpAmm`.
+        #[test]
+        fn test_check_protocol_fees_cap() {
+            let amm = CpAmmBuilder::new()
+                .base_liquidity(1_000_000)
+                .quote_liquidity(4_000_000)
+                .protocol_base_fees_to_redeem(10_000)
+                .protocol_quote_fees_to_redeem(20_000)
+                .build();
+            amm.check_protocol_fees_cap(0).unwrap();
+            amm.check_protocol_fees_cap(100).unwrap();
+            assert_eq!(amm.check_protocol_fees_cap(99).unwrap_err(), ErrorCode::ProtocolFeesCapExceeded.into());
+
+            // The cap applies to the fees of each side relative to the liquidity of that side
+            let amm = CpAmmBuilder::new()
+                .base_liquidity(1_000_000)
+                .quote_liquidity(4_000_000)
+                .protocol_quote_fees_to_redeem(40_001)
+                .build();
+            assert_eq!(amm.check_protocol_fees_cap(100).unwrap_err(), ErrorCode::ProtocolFeesCapExceeded.into());
+            amm.check_protocol_fees_cap(101).unwrap();
+
+            let amm_max = CpAmmBuilder::new()
+                .base_liquidity(u64::MAX)
+                .protocol_base_fees_to_redeem(u64::MAX)
+                .build();
+            amm_max.check_protocol_fees_cap(10000).unwrap();
+            assert_eq!(amm_max.check_protocol_fees_cap(9999).unwrap_err(), ErrorCode::ProtocolFeesCapExceeded.into());
+        }
+
+        /// Tests the `update_creator_fee_share` method of `CpAmm`.
+        #[test]
+        fn test_update_creator_fee_share() {
+            let mut amm = CpAmmBuilder::new().build();
+            assert_eq!(amm.creator_fee_share_basis_points(), 0);
+
+            amm.update_creator_fee_share(2_000, 2_500).unwrap();
+            assert_eq!(amm.creator_fee_share_basis_points(), 2_000);
+
+            amm.update_creator_fee_share(2_500, 2_500).unwrap();
+            assert_eq!(amm.creator_fee_share_basis_points(), 2_500);
+
+            assert_eq!(amm.update_creator_fee_share(2_501, 2_500).err(), Some(ErrorCode::CreatorFeeShareExceeded.into()));
+            assert_eq!(amm.creator_fee_share_basis_points(), 2_500);
+        }
+
+        /// Tests the `lock_fee_parameters` method of `CpAmm`.
+        #[test]
+        fn test_lock_fee_parameters() {
+            let mut amm = CpAmmBuilder::new().creator_fee_share_basis_points(1_000).build();
+            assert!(!amm.is_fee_parameters_locked());
+
+            amm.lock_fee_parameters().unwrap();
+            assert!(amm.is_fee_parameters_locked());
+            assert_eq!(amm.lock_fee_parameters().err(), Some(ErrorCode::ParametersLocked.into()));
+
+            assert_eq!(amm.update_creator_fee_share(2_000, 2_500).err(), Some(ErrorCode::ParametersLocked.into()));
+            assert_eq!(amm.creator_fee_share_basis_points(), 1_000);
+            assert_eq!(amm.update_protocol_fee_override(Some(5), 100).err(), Some(ErrorCode::ParametersLocked.into()));
+            assert_eq!(amm.protocol_fee_override_basis_points(), None);
+
+            let mut locked_amm = CpAmmBuilder::new().fee_parameters_locked(true).build();
+            assert_eq!(locked_amm.lock_fee_parameters().err(), Some(ErrorCode::ParametersLocked.into()));
+        }
+
+        /// Tests the `update_lend_out` method of `CpAmm`.
+        #[test]
+        fn test_update_lend_out() {
+            let mut amm = CpAmmBuilder::new().build();
+            assert!(!amm.is_lend_out_enabled());
+
+            amm.update_lend_out(true);
+            assert!(amm.is_lend_out_enabled());
+
+            amm.update_lend_out(false);
+            assert!(!amm.is_lend_out_enabled());
         }
 
         /// Tests the `swap` method of `CpAmm`.
@@ -982,23 +3092,33 @@ mod cp_amm_tests {
         fn test_swap() {
             let mut amm = CpAmmBuilder::new().build();
 
-            let swap_payload_in = SwapPayload::new(3980, 1000, 1, 20, 100, true);
-            let swap_payload_out = SwapPayload::new(1000, 985, 15, 15, 100, false);
+            let swap_payload_in = SwapPayload::new(3980, 1000, 1, 0, 20, 1, 0, 100, true, 7600, Q64_128::from_u64(2), PoolStats::default().record_swap(2000, 100, None));
+            let swap_payload_out = SwapPayload::new(1000, 985, 15, 0, 15, 15, 0, 100, false, 7500, Q64_128::ONE, PoolStats::default().record_swap(2100, 1100, Some(500)));
 
-            amm.swap(swap_payload_in);
+            amm.swap(swap_payload_in).unwrap();
             assert_eq!(amm.base_liquidity, 4000);
             assert_eq!(amm.quote_liquidity, 1000);
             assert_eq!(amm.protocol_base_fees_to_redeem, 1);
             assert_eq!(amm.constant_product_sqrt, Q64_128::from_u64(2000));
             assert_eq!(amm.base_quote_ratio_sqrt, Q64_128::from_u64(2));
 
-            amm.swap(swap_payload_out);
+            amm.swap(swap_payload_out).unwrap();
             assert_eq!(amm.base_liquidity, 1000);
             assert_eq!(amm.quote_liquidity, 1000);
             assert_eq!(amm.protocol_base_fees_to_redeem, 1);
             assert_eq!(amm.protocol_quote_fees_to_redeem, 15);
             assert_eq!(amm.constant_product_sqrt, Q64_128::from_u64(1000));
             assert_eq!(amm.base_quote_ratio_sqrt, Q64_128::from_u64(1));
+            assert_eq!(amm.stats.cumulative_base_volume(), 4100);
+            assert_eq!(amm.stats.cumulative_quote_volume(), 1200);
+            assert_eq!(amm.stats.cumulative_usd_volume(), 500);
+
+            // Statistics taken out of the payload for a `PoolStatsShard` are not recorded on the pool
+            let mut swap_payload_sharded = SwapPayload::new(1000, 1000, 1, 0, 0, 1, 0, 100, true, 0, Q64_128::ONE, PoolStats::default().record_swap(100, 100, None));
+            assert_eq!(swap_payload_sharded.take_stats(), PoolStats::default().record_swap(100, 100, None));
+            amm.swap(swap_payload_sharded).unwrap();
+            assert_eq!(amm.stats.cumulative_base_volume(), 4100);
+            assert_eq!(amm.stats.cumulative_quote_volume(), 1200);
         }
 
         /// Tests the `collect_fees` method of `CpAmm`.
@@ -1006,29 +3126,68 @@ mod cp_amm_tests {
         fn test_collect_fees() {
             let mut amm = CpAmmBuilder::new().protocol_base_fees_to_redeem(123213).protocol_quote_fees_to_redeem(213442).build();
 
-            let collect_fees_payload = CollectFeesPayload::new(123213, 213442, 0,0);
+            let collect_fees_payload = CollectFeesPayload::new(123213, 213442, 0, 0, 0, 0, amm.base_liquidity, amm.quote_liquidity);
 
-            amm.collect_fees(collect_fees_payload);
+            assert!(!amm.collect_fees(collect_fees_payload));
             assert_eq!(amm.protocol_base_fees_to_redeem, 0);
             assert_eq!(amm.protocol_quote_fees_to_redeem, 0);
+
+            // Settled fees move into the liquidity
+            let mut amm = CpAmmBuilder::new().base_liquidity(6_000_000).quote_liquidity(1_500_000).protocol_base_fees_to_redeem(60_000).build();
+            assert!(amm.collect_fees(CollectFeesPayload::new(0, 14_851, 0, 0, 0, 0, 6_060_000, 1_485_149)));
+            assert_eq!(amm.protocol_base_fees_to_redeem, 0);
+            assert_eq!((amm.base_liquidity, amm.quote_liquidity), (6_060_000, 1_485_149));
+            assert_eq!(amm.constant_product_sqrt, CpAmm::calculate_constant_product_sqrt(6_060_000, 1_485_149).unwrap());
+            assert_eq!(amm.base_quote_ratio_sqrt, CpAmm::calculate_base_quote_ratio_sqrt(6_060_000, 1_485_149).unwrap());
+        }
+
+        /// Tests the `swap` and `collect_creator_fees` methods of `CpAmm` with a creator fee share.
+        #[test]
+        fn test_collect_creator_fees() {
+            let mut amm = CpAmmBuilder::new().creator_base_fees_to_redeem(10).creator_quote_fees_to_redeem(2).build();
+
+            amm.swap(SwapPayload::new(3980, 1000, 1, 15, 20, 1, 5, 100, true, 7600, Q64_128::from_u64(2), PoolStats::default())).unwrap();
+            amm.swap(SwapPayload::new(4000, 985, 15, 7, 15, 15, 5, 100, false, 7500, Q64_128::from_u64(2), PoolStats::default())).unwrap();
+            assert_eq!(amm.creator_base_fees_to_redeem(), 15);
+            assert_eq!(amm.creator_quote_fees_to_redeem(), 7);
+
+            let payload = amm.get_collect_creator_fees_payload().unwrap();
+            assert_eq!(payload.creator_base_fees_to_redeem(), 15);
+            assert_eq!(payload.creator_quote_fees_to_redeem(), 7);
+
+            amm.collect_creator_fees(payload);
+            assert_eq!(amm.creator_base_fees_to_redeem(), 0);
+            assert_eq!(amm.creator_quote_fees_to_redeem(), 0);
+            assert_eq!(amm.get_collect_creator_fees_payload().err(), Some(ErrorCode::CreatorFeesIsZero.into()));
+        }
+
+        /// Tests an overflowing providers fee in `swap`, which panics by default and errors with `strict-math`.
+        #[test]
+        #[cfg_attr(not(feature = "strict-math"), should_panic)]
+        fn test_swap_providers_fee_overflow() {
+            let mut amm = CpAmmBuilder::new().build();
+            let result = amm.swap(SwapPayload::new(u64::MAX, 1000, 0, 0, 1, 0, 0, 100, true, 0, Q64_128::from_u64(2), PoolStats::default()));
+            assert_eq!(result.err(), Some(ErrorCode::StrictMathOverflow.into()));
         }
     }
     
     mod operations_calculations_tests {
+        use cpmm_math::golden::{self, SwapVector};
+        use cpmm_math::quote::SwapQuote;
         use super::*;
 
         /// Tests the `check_state` method of `CpAmm`.
         #[test]
         fn test_check_state() {
             let amm1 = CpAmmBuilder::new()
-                .is_launched(false)
+                .status(PoolStatus::Initialized)
                 .base_liquidity(1000)
                 .quote_liquidity(1000)
                 .lp_tokens_supply(0)
                 .build();
             
             let amm2 = CpAmmBuilder::new()
-                .is_launched(true)
+                .status(PoolStatus::Launched)
                 .base_liquidity(1000)
                 .quote_liquidity(1000)
                 .lp_tokens_supply(5000)
@@ -1037,18 +3196,30 @@ mod cp_amm_tests {
             assert!(amm2.check_state().is_ok());
         }
 
+        /// Tests the `validate_stale_close` method of `CpAmm`.
+        #[test]
+        fn test_validate_stale_close() {
+            let amm = CpAmmBuilder::new().status(PoolStatus::Initialized).initialized_epoch(100).build();
+            assert!(amm.validate_stale_close(110, 10).is_ok());
+            assert!(amm.validate_stale_close(200, 10).is_ok());
+            assert_eq!(amm.validate_stale_close(109, 10).err(), Some(ErrorCode::CpAmmNotStale.into()));
+            assert_eq!(amm.validate_stale_close(200, 0).err(), Some(ErrorCode::StaleCpAmmCloseDisabled.into()));
+
+            let amm = CpAmmBuilder::new().status(PoolStatus::Launched).initialized_epoch(100).build();
+            assert_eq!(amm.validate_stale_close(200, 10).err(), Some(ErrorCode::CpAmmAlreadyLaunched.into()));
+        }
+
         /// Tests the `get_launch_payload` method of `CpAmm`.
         #[test]
         fn test_get_launch_payload() {
             let amm = CpAmmBuilder::new()
-                .is_initialized(true)
-                .is_launched(false)
+                .status(PoolStatus::Initialized)
                 .build();
 
             let base_liquidity = 400000;
             let quote_liquidity = 400000;
 
-            let payload = amm.get_launch_payload(base_liquidity, quote_liquidity).unwrap();
+            let payload = amm.get_launch_payload(base_liquidity, quote_liquidity, 6, 9, 7, None).unwrap();
 
             assert_eq!(payload.base_liquidity, 400000);
             assert_eq!(payload.quote_liquidity, 400000);
@@ -1057,7 +3228,91 @@ mod cp_amm_tests {
             assert_eq!(payload.lp_tokens_supply, payload.constant_product_sqrt.as_u64());
             assert_eq!(payload.initial_locked_liquidity, CpAmm::INITIAL_LOCKED_LP_TOKENS);
             
-            assert!(amm.get_launch_payload(5500, 1000).is_err());
+            assert!(amm.get_launch_payload(5500, 1000, 6, 9, 7, None).is_err());
+
+            // Base-to-quote ratio 1:4 gives a ratio square root of 0.5
+            let half = Q64_128::from_bits(0, 1u128 << 127);
+            let payload = amm.get_launch_payload(1_000_000, 4_000_000, 6, 9, 7, Some((half, 0))).unwrap();
+            assert_eq!(payload.base_quote_ratio_sqrt, half);
+            assert!(amm.get_launch_payload(1_000_000, 4_040_000, 6, 9, 7, Some((half, 100))).is_ok());
+            assert_eq!(
+                amm.get_launch_payload(1_000_000, 4_000_000, 6, 9, 7, Some((Q64_128::from_u64(1), 100))).err(),
+                Some(ErrorCode::LaunchRatioToleranceExceeded.into())
+            );
+            assert_eq!(
+                amm.get_launch_payload(1_000_000, 5_000_000, 6, 9, 7, Some((half, 100))).err(),
+                Some(ErrorCode::LaunchRatioToleranceExceeded.into())
+            );
+        }
+
+        /// Tests the `get_launch_payload` method of `CpAmm` across the mints decimals spectrum.
+        #[test]
+        fn test_get_launch_payload_decimals() {
+            let amm = CpAmmBuilder::new()
+                .status(PoolStatus::Initialized)
+                .build();
+
+            // 10_000 whole tokens of each mint lock `MIN_LIQUIDITY` instead of a single LP token
+            let payload = amm.get_launch_payload(10_000, 10_000, 0, 0, 0, None).unwrap();
+            assert_eq!(payload.initial_locked_liquidity, MIN_LIQUIDITY);
+            assert_eq!(payload.launch_liquidity(), 9_000);
+
+            let payload = amm.get_launch_payload(100, 100_000_000_000, 0, 9, 4, None).unwrap();
+            assert_eq!(payload.initial_locked_liquidity, 10_000);
+
+            let payload = amm.get_launch_payload(100_000_000, 100_000_000_000, 6, 9, 7, None).unwrap();
+            assert_eq!(payload.initial_locked_liquidity, CpAmm::INITIAL_LOCKED_LP_TOKENS);
+
+            // 10 whole tokens of each mint, close to the `u64` limit for 18 decimals
+            let payload = amm.get_launch_payload(10_000_000_000_000_000_000, 10_000_000, 18, 6, 9, None).unwrap();
+            assert_eq!(payload.initial_locked_liquidity, CpAmm::INITIAL_LOCKED_LP_TOKENS);
+
+            let payload = amm.get_launch_payload(10_000_000_000_000_000_000, 10_000_000_000_000_000_000, 18, 18, 9, None).unwrap();
+            assert_eq!(payload.lp_tokens_supply, 10_000_000_000_000_000_000);
+            assert_eq!(payload.initial_locked_liquidity, CpAmm::INITIAL_LOCKED_LP_TOKENS);
+
+            // Launch liquidity of 0 decimals mints is still required to exceed the locked liquidity
+            assert!(amm.get_launch_payload(3_999, 3_999, 0, 0, 0, None).is_err());
+            assert!(amm.get_launch_payload(4_000, 4_000, 0, 0, 0, None).is_ok());
+
+            // Pools initialized before the derivation keep the initial LP decimals
+            let payload = amm.get_launch_payload(10_000, 10_000, 0, 0, CpAmm::LP_MINT_INITIAL_DECIMALS, None).unwrap();
+            assert_eq!(payload.initial_locked_liquidity, MIN_LIQUIDITY);
+        }
+
+        /// Tests the decimals normalization of `CpAmm` for a 0 vs 9 decimals pair.
+        #[test]
+        fn test_enable_decimals_normalization() {
+            let mut amm = CpAmmBuilder::new()
+                .status(PoolStatus::Initialized)
+                .build();
+            amm.enable_decimals_normalization(0, 9).unwrap();
+            assert_eq!(amm.base_normalization_exponent(), 9);
+            assert_eq!(amm.quote_normalization_exponent(), 0);
+
+            // The creator's expected ratio is the ratio of the liquidity amounts
+            let expected_base_quote_ratio_sqrt = CpAmm::calculate_base_quote_ratio_sqrt(1_000, 3_000_000_000_000).unwrap();
+            let payload = amm.get_launch_payload(1_000, 3_000_000_000_000, 0, 9, 4, Some((expected_base_quote_ratio_sqrt, 1))).unwrap();
+            assert_eq!(payload.lp_tokens_supply, 1_732_050_807_568);
+            assert_eq!(payload.initial_locked_liquidity, 10_000);
+            amm.launch(payload, 0);
+
+            let provide_payload = amm.get_provide_payload(1, 3_000_000_000, 0, ADJUST_LIQUIDITY_RATIO_SQRT_TOLERANCE).unwrap();
+            assert_eq!(provide_payload.lp_tokens_to_mint(), 1_732_050_807);
+            amm.provide(provide_payload);
+
+            let withdraw_payload = amm.get_withdraw_payload(1_732_050_807, 0, 0, 0, u64::MAX, false, ADJUST_LIQUIDITY_RATIO_SQRT_TOLERANCE).unwrap();
+            assert_eq!(withdraw_payload.base_withdraw_amount(), 1);
+            assert!(3_000_000_000 - withdraw_payload.quote_withdraw_amount() <= 2);
+            amm.withdraw(withdraw_payload);
+            assert_eq!((amm.base_liquidity, amm.lp_tokens_supply), (1_000, 1_732_050_807_568));
+
+            assert_eq!(amm.enable_decimals_normalization(0, 9).err(), Some(ErrorCode::CpAmmAlreadyLaunched.into()));
+            let mut uninitialized_amm = CpAmmBuilder::new().build();
+            assert_eq!(uninitialized_amm.enable_decimals_normalization(0, 9).err(), Some(ErrorCode::CpAmmNotInitialized.into()));
+
+            let normalized_amm = CpAmmBuilder::new().base_normalization_exponent(0).quote_normalization_exponent(3).build();
+            assert_eq!((normalized_amm.base_liquidity_scale(), normalized_amm.quote_liquidity_scale()), (1, 1_000));
         }
 
         /// Tests the `get_provide_payload` method of `CpAmm`.
@@ -1070,7 +3325,7 @@ mod cp_amm_tests {
             let initial_lp_tokens_supply = 2_000_000;
 
             let amm = CpAmmBuilder::new()
-                .is_launched(true)
+                .status(PoolStatus::Launched)
                 .base_liquidity(initial_base_liquidity)
                 .quote_liquidity(initial_quote_liquidity)
                 .constant_product_sqrt(initial_constant_product_sqrt)
@@ -1081,7 +3336,7 @@ mod cp_amm_tests {
             let provided_base_liquidity = 2_000_000;
             let provided_quote_liquidity = 500_000;
 
-            let payload = amm.get_provide_payload(provided_base_liquidity, provided_quote_liquidity).unwrap();
+            let payload = amm.get_provide_payload(provided_base_liquidity, provided_quote_liquidity, 0, ADJUST_LIQUIDITY_RATIO_SQRT_TOLERANCE).unwrap();
 
             let expected_base_liquidity = initial_base_liquidity + provided_base_liquidity;
             let expected_quote_liquidity = initial_quote_liquidity + provided_quote_liquidity;
@@ -1095,6 +3350,83 @@ mod cp_amm_tests {
             assert_eq!(payload.constant_product, expected_constant_product_sqrt);
             assert_eq!(payload.lp_tokens_to_mint, expected_lp_tokens_to_mint);
             assert_eq!(payload.lp_tokens_supply, expected_lp_tokens_supply);
+
+            assert!(amm.get_provide_payload(provided_base_liquidity, provided_quote_liquidity, expected_lp_tokens_to_mint, ADJUST_LIQUIDITY_RATIO_SQRT_TOLERANCE).is_ok());
+            assert!(amm.get_provide_payload(provided_base_liquidity, provided_quote_liquidity, expected_lp_tokens_to_mint + 1, ADJUST_LIQUIDITY_RATIO_SQRT_TOLERANCE).is_err());
+        }
+
+        /// Tests the `get_borrow_payload` and `get_repay_payload` methods of `CpAmm`.
+        #[test]
+        fn test_get_lend_out_payloads() {
+            let amm = CpAmmBuilder::new()
+                .status(PoolStatus::Launched)
+                .base_liquidity(6_000_000)
+                .quote_liquidity(1_500_000)
+                .constant_product_sqrt(Q64_128::from_u64(3_000_000))
+                .base_quote_ratio_sqrt(Q64_128::from_u64(2))
+                .lp_tokens_supply(3_000_000)
+                .build();
+            assert_eq!(amm.get_borrow_payload(1000, true, 1000).err(), Some(ErrorCode::CpAmmLendOutDisabled.into()));
+
+            let mut amm = CpAmmBuilder::new()
+                .lend_out_enabled(true)
+                .status(PoolStatus::Launched)
+                .base_liquidity(6_000_000)
+                .quote_liquidity(1_500_000)
+                .constant_product_sqrt(Q64_128::from_u64(3_000_000))
+                .base_quote_ratio_sqrt(Q64_128::from_u64(2))
+                .lp_tokens_supply(3_000_000)
+                .quote_lent_out(100_000)
+                .build();
+            assert_eq!(amm.get_borrow_payload(0, true, 1000).err(), Some(ErrorCode::LendOutAmountIsZero.into()));
+            assert_eq!(amm.get_borrow_payload(600_001, true, 1000).err(), Some(ErrorCode::LendOutLimitExceeded.into()));
+            assert_eq!(amm.get_borrow_payload(50_001, false, 1000).err(), Some(ErrorCode::LendOutLimitExceeded.into()));
+
+            let payload = amm.get_borrow_payload(600_000, true, 1000).unwrap();
+            assert_eq!(payload.base_lent_out(), 600_000);
+            assert_eq!(payload.quote_lent_out(), 100_000);
+            amm.lend_out(payload);
+            assert_eq!(amm.base_lent_out(), 600_000);
+            assert_eq!(amm.base_liquidity(), 6_000_000);
+            assert_eq!(amm.constant_product_sqrt(), Q64_128::from_u64(3_000_000));
+
+            assert_eq!(amm.get_repay_payload(100_001, 0, false).err(), Some(ErrorCode::LendOutRepayExceedsDebt.into()));
+            assert_eq!(amm.get_repay_payload(0, 0, false).err(), Some(ErrorCode::LendOutAmountIsZero.into()));
+
+            // Interest is added to the liquidity as providers fees
+            let payload = amm.get_repay_payload(100_000, 2_000, false).unwrap();
+            assert_eq!(payload.quote_lent_out(), 0);
+            amm.lend_out(payload);
+            assert_eq!(amm.quote_lent_out(), 0);
+            assert_eq!(amm.quote_liquidity(), 1_502_000);
+            assert_eq!(amm.constant_product_sqrt(), CpAmm::calculate_constant_product_sqrt(6_000_000, 1_502_000).unwrap());
+            assert_eq!(amm.stats().cumulative_quote_providers_fees(), 2_000);
+            assert_eq!(amm.stats().cumulative_quote_protocol_fees(), 0);
+        }
+
+        /// Tests that lent out liquidity can't be swapped out or withdrawn.
+        #[test]
+        fn test_lent_out_liquidity_unavailable() {
+            let amm = CpAmmBuilder::new()
+                .status(PoolStatus::Launched)
+                .base_liquidity(6_000_000)
+                .quote_liquidity(1_500_000)
+                .constant_product_sqrt(Q64_128::from_u64(3_000_000))
+                .base_quote_ratio_sqrt(Q64_128::from_u64(2))
+                .lp_tokens_supply(3_000_000)
+                .base_lent_out(4_000_001)
+                .quote_lent_out(1_000_000)
+                .build();
+
+            assert_eq!(amm.get_withdraw_payload(1_000_000, 0, 0, 0, u64::MAX, false, ADJUST_LIQUIDITY_RATIO_SQRT_TOLERANCE).err(), Some(ErrorCode::LentOutLiquidityUnavailable.into()));
+            assert!(amm.get_withdraw_payload(500_000, 0, 0, 0, u64::MAX, false, ADJUST_LIQUIDITY_RATIO_SQRT_TOLERANCE).is_ok());
+
+            // Swapping quote in takes out base tokens beyond the available ones
+            assert_eq!(
+                amm.get_swap_payload(1_000_000, 1, u64::MAX, 0, 0, false, None, SWAP_CONSTANT_PRODUCT_SQRT_TOLERANCE, 0).err(),
+                Some(ErrorCode::LentOutLiquidityUnavailable.into())
+            );
+            assert!(amm.get_swap_payload(1_000_000, 1, u64::MAX, 0, 0, true, None, SWAP_CONSTANT_PRODUCT_SQRT_TOLERANCE, 0).is_ok());
         }
 
         /// Tests the `get_withdraw_payload` method of `CpAmm`.
@@ -1107,7 +3439,7 @@ mod cp_amm_tests {
             let initial_lp_tokens_supply = 3_000_000;
 
             let amm = CpAmmBuilder::new()
-                .is_launched(true)
+                .status(PoolStatus::Launched)
                 .base_liquidity(initial_base_liquidity)
                 .quote_liquidity(initial_quote_liquidity)
                 .constant_product_sqrt(initial_constant_product_sqrt)
@@ -1117,7 +3449,7 @@ mod cp_amm_tests {
 
             let lp_tokens_withdraw = 1000000;
 
-            let payload = amm.get_withdraw_payload(lp_tokens_withdraw).unwrap();
+            let payload = amm.get_withdraw_payload(lp_tokens_withdraw, 0, 0, 0, u64::MAX, false, ADJUST_LIQUIDITY_RATIO_SQRT_TOLERANCE).unwrap();
 
             let expected_base_withdraw_amount = 2_000_000;
             let expected_quote_withdraw_amount = 500_000;
@@ -1131,6 +3463,395 @@ mod cp_amm_tests {
             assert_eq!(payload.base_withdraw_amount, expected_base_withdraw_amount);
             assert_eq!(payload.quote_withdraw_amount, expected_quote_withdraw_amount);
             assert_eq!(payload.lp_tokens_supply, expected_lp_tokens_supply);
+            assert_eq!(payload.withdraw_rate_limiter, WithdrawRateLimiter::default());
+
+            assert!(amm.get_withdraw_payload(lp_tokens_withdraw, 0, 0, lp_tokens_withdraw, u64::MAX, false, ADJUST_LIQUIDITY_RATIO_SQRT_TOLERANCE).is_ok());
+            assert!(amm.get_withdraw_payload(lp_tokens_withdraw, 0, 0, lp_tokens_withdraw + 1, u64::MAX, false, ADJUST_LIQUIDITY_RATIO_SQRT_TOLERANCE).is_err());
+            // The whole balance of a holder below the minimum is withdrawable
+            assert!(amm.get_withdraw_payload(lp_tokens_withdraw, 0, 0, lp_tokens_withdraw + 1, lp_tokens_withdraw, false, ADJUST_LIQUIDITY_RATIO_SQRT_TOLERANCE).is_ok());
+            assert!(amm.get_withdraw_payload(lp_tokens_withdraw, 0, 0, lp_tokens_withdraw + 1, lp_tokens_withdraw + 1, false, ADJUST_LIQUIDITY_RATIO_SQRT_TOLERANCE).is_err());
+        }
+
+        /// Tests the `get_withdraw_payload` method of `CpAmm` draining a pool to dust.
+        #[test]
+        fn test_get_withdraw_payload_min_liquidity() {
+            // 2_500 base tokens against 25_000_000 quote tokens, a tenth of the LP tokens is locked
+            let amm = CpAmmBuilder::new()
+                .status(PoolStatus::Launched)
+                .base_liquidity(2_500)
+                .quote_liquidity(25_000_000)
+                .constant_product_sqrt(Q64_128::from_u64(250_000))
+                .base_quote_ratio_sqrt(Q64_128::from_u64(1).checked_div(Q64_128::from_u64(100)).unwrap())
+                .lp_tokens_supply(250_000)
+                .initial_locked_liquidity(25_000)
+                .build();
+
+            let payload = amm.get_withdraw_payload(150_000, 0, 0, 0, u64::MAX, false, ADJUST_LIQUIDITY_RATIO_SQRT_TOLERANCE).unwrap();
+            assert_eq!(payload.base_liquidity, MIN_LIQUIDITY);
+
+            // Leaving dust while other LP tokens than the locked ones remain is rejected
+            assert_eq!(
+                amm.get_withdraw_payload(200_000, 0, 0, 0, u64::MAX, false, ADJUST_LIQUIDITY_RATIO_SQRT_TOLERANCE).err(),
+                Some(ErrorCode::WithdrawLiquidityBelowMinimum.into())
+            );
+            assert_eq!(
+                amm.get_withdraw_payload(224_000, 0, 0, 0, u64::MAX, false, ADJUST_LIQUIDITY_RATIO_SQRT_TOLERANCE).err(),
+                Some(ErrorCode::WithdrawLiquidityBelowMinimum.into())
+            );
+
+            // The locked liquidity backs the reserves left by the last unlocked LP tokens
+            let payload = amm.get_withdraw_payload(225_000, 0, 0, 0, u64::MAX, false, ADJUST_LIQUIDITY_RATIO_SQRT_TOLERANCE).unwrap();
+            assert_eq!(payload.lp_tokens_supply, 25_000);
+            assert_eq!((payload.base_liquidity, payload.quote_liquidity), (250, 2_500_000));
+
+            // Quote liquidity is held to the minimum as well
+            let amm = CpAmmBuilder::new()
+                .status(PoolStatus::Launched)
+                .base_liquidity(25_000_000)
+                .quote_liquidity(2_500)
+                .constant_product_sqrt(Q64_128::from_u64(250_000))
+                .base_quote_ratio_sqrt(Q64_128::from_u64(100))
+                .lp_tokens_supply(250_000)
+                .initial_locked_liquidity(25_000)
+                .build();
+            assert!(amm.get_withdraw_payload(150_000, 0, 0, 0, u64::MAX, false, ADJUST_LIQUIDITY_RATIO_SQRT_TOLERANCE).is_ok());
+            assert_eq!(
+                amm.get_withdraw_payload(200_000, 0, 0, 0, u64::MAX, false, ADJUST_LIQUIDITY_RATIO_SQRT_TOLERANCE).err(),
+                Some(ErrorCode::WithdrawLiquidityBelowMinimum.into())
+            );
+
+            // Reserves already below the minimum, e.g. after swaps, don't lock the LPs out
+            let amm = CpAmmBuilder::new()
+                .status(PoolStatus::Launched)
+                .base_liquidity(250)
+                .quote_liquidity(2_500_000)
+                .constant_product_sqrt(Q64_128::from_u64(25_000))
+                .base_quote_ratio_sqrt(Q64_128::from_u64(1).checked_div(Q64_128::from_u64(100)).unwrap())
+                .lp_tokens_supply(25_000)
+                .initial_locked_liquidity(2_500)
+                .build();
+            let payload = amm.get_withdraw_payload(10_000, 0, 0, 0, u64::MAX, false, ADJUST_LIQUIDITY_RATIO_SQRT_TOLERANCE).unwrap();
+            assert_eq!((payload.base_liquidity, payload.quote_liquidity), (150, 1_500_000));
+            assert!(amm.get_withdraw_payload(22_500, 0, 0, 0, u64::MAX, false, ADJUST_LIQUIDITY_RATIO_SQRT_TOLERANCE).is_ok());
+        }
+
+        /// Tests the `get_withdraw_payload` method of `CpAmm` burning only the LP tokens covering the withdrawn amounts.
+        #[test]
+        fn test_get_withdraw_payload_burn_exact() {
+            // 1 base token is worth 1000 LP tokens
+            let amm = CpAmmBuilder::new()
+                .status(PoolStatus::Launched)
+                .base_liquidity(10_000_000)
+                .quote_liquidity(10_000_000_000_000)
+                .constant_product_sqrt(Q64_128::from_u64(10_000_000_000))
+                .base_quote_ratio_sqrt(Q64_128::from_u64(1000).checked_div(Q64_128::from_u64(1_000_000)).unwrap())
+                .lp_tokens_supply(10_000_000_000)
+                .build();
+
+            let payload = amm.get_withdraw_payload(1_000_200, 0, 0, 0, u64::MAX, false, ADJUST_LIQUIDITY_RATIO_SQRT_TOLERANCE).unwrap();
+            assert_eq!(payload.lp_tokens_to_burn, 1_000_200);
+            assert_eq!(payload.base_withdraw_amount, 1_000);
+            assert_eq!(payload.quote_withdraw_amount, 1_000_200_000);
+
+            let exact_payload = amm.get_withdraw_payload(1_000_200, 0, 0, 0, u64::MAX, true, ADJUST_LIQUIDITY_RATIO_SQRT_TOLERANCE).unwrap();
+            assert_eq!(exact_payload.lp_tokens_to_burn, 1_000_000);
+            assert_eq!(exact_payload.base_withdraw_amount, 1_000);
+            assert_eq!(exact_payload.quote_withdraw_amount, 1_000_000_000);
+            assert_eq!(exact_payload.lp_tokens_supply, 10_000_000_000 - 1_000_000);
+            assert_eq!(exact_payload.base_liquidity, 10_000_000 - 1_000);
+            assert_eq!(exact_payload.quote_liquidity, 10_000_000_000_000 - 1_000_000_000);
+        }
+
+        /// Tests the `get_lp_value` method of `CpAmm`.
+        #[test]
+        fn test_get_lp_value() {
+            let amm = CpAmmBuilder::new()
+                .status(PoolStatus::Launched)
+                .base_liquidity(6_000_000)
+                .quote_liquidity(1_500_000)
+                .constant_product_sqrt(Q64_128::from_u64(3_000_000))
+                .base_quote_ratio_sqrt(Q64_128::from_u64(2))
+                .lp_tokens_supply(3_000_000)
+                .build();
+
+            assert_eq!(amm.get_lp_value(1_000_000).unwrap(), (2_000_000, 500_000));
+            assert_eq!(amm.get_lp_value(3_000_000).unwrap(), (6_000_000, 1_500_000));
+            assert_eq!(
+                amm.get_lp_value(1_000_000).unwrap(),
+                {
+                    let payload = amm.get_withdraw_payload(1_000_000, 0, 0, 0, u64::MAX, false, ADJUST_LIQUIDITY_RATIO_SQRT_TOLERANCE).unwrap();
+                    (payload.base_withdraw_amount(), payload.quote_withdraw_amount())
+                }
+            );
+
+            assert_eq!(amm.get_lp_value(0).unwrap_err(), ErrorCode::ProvidedLpTokensIsZero.into());
+            assert_eq!(amm.get_lp_value(3_000_001).unwrap_err(), ErrorCode::LpTokensExceedSupply.into());
+            assert!(amm.get_lp_value(1).is_err());
+            assert!(CpAmmBuilder::new().build().get_lp_value(1_000_000).is_err());
+        }
+
+        /// Tests that the constant product curve of a `CpAmm` dispatches to the constant product math.
+        #[test]
+        fn test_constant_product_curve() {
+            let amm = CpAmmBuilder::new()
+                .status(PoolStatus::Launched)
+                .base_liquidity(6_000_000)
+                .quote_liquidity(1_500_000)
+                .constant_product_sqrt(Q64_128::from_u64(3_000_000))
+                .base_quote_ratio_sqrt(Q64_128::from_u64(2))
+                .lp_tokens_supply(3_000_000)
+                .build();
+            assert_eq!(amm.curve_kind(), CurveKind::ConstantProduct);
+            let curve = amm.curve();
+
+            assert_eq!(curve.calculate_invariant_sqrt(6_000_000, 1_500_000), Some(Q64_128::from_u64(3_000_000)));
+            assert_eq!(curve.calculate_invariant_sqrt_from_ratio_sqrt(Q64_128::from_u64(2), 6_000_000, 1_500_000), Some(Q64_128::from_u64(3_000_000)));
+            assert_eq!(curve.calculate_afterswap_liquidity(2_000_000, true), amm.calculate_afterswap_liquidity(2_000_000, true));
+            assert_eq!(curve.calculate_lp_mint_for_provided_liquidity(Q64_128::from_u64(4_000_000)), amm.calculate_lp_mint_for_provided_liquidity(Q64_128::from_u64(4_000_000)));
+            assert_eq!(curve.calculate_liquidity_from_share(1_000_000), Some((2_000_000, 500_000)));
+            assert!(curve.validate_swap_invariant(8_000_000, 1_125_000, SWAP_CONSTANT_PRODUCT_SQRT_TOLERANCE).is_ok());
+            assert_eq!(
+                curve.validate_swap_invariant(8_000_000, 1_000_000, SWAP_CONSTANT_PRODUCT_SQRT_TOLERANCE).err(),
+                Some(ErrorCode::ConstantProductToleranceExceeded.into())
+            );
+        }
+
+        /// Tests the `get_virtual_price` method of `CpAmm`.
+        #[test]
+        fn test_get_virtual_price() {
+            let amm = CpAmmBuilder::new()
+                .status(PoolStatus::Launched)
+                .base_liquidity(6_000_000)
+                .quote_liquidity(1_500_000)
+                .lp_tokens_supply(3_000_000)
+                .build();
+            // 3_000_000 quote of reserves value over 3_000_000 LP tokens
+            assert_eq!(amm.get_virtual_price().unwrap(), CpAmm::VIRTUAL_PRICE_SCALE);
+
+            let amm = CpAmmBuilder::new()
+                .status(PoolStatus::Launched)
+                .base_liquidity(1)
+                .quote_liquidity(u64::MAX)
+                .lp_tokens_supply(1)
+                .build();
+            assert_eq!(amm.get_virtual_price().unwrap_err(), ErrorCode::VirtualPriceCalculationFailed.into());
+            assert_eq!(CpAmmBuilder::new().build().get_virtual_price().unwrap_err(), ErrorCode::CpAmmNotLaunched.into());
+        }
+
+        /// Tests that swaps are rejected during the provide-only warm-up while liquidity adjustments stay available.
+        #[test]
+        fn test_warm_up_payloads() {
+            assert_eq!(CpAmm::calculate_trading_open_slot(500, 0).unwrap(), 500);
+            assert_eq!(CpAmm::calculate_trading_open_slot(500, CpAmm::MAX_WARM_UP_SLOTS).unwrap(), 500 + CpAmm::MAX_WARM_UP_SLOTS);
+            assert_eq!(CpAmm::calculate_trading_open_slot(500, CpAmm::MAX_WARM_UP_SLOTS + 1).unwrap_err(), ErrorCode::WarmUpSlotsExceeded.into());
+
+            let amm = CpAmmBuilder::new()
+                .status(PoolStatus::Launched)
+                .base_liquidity(6_000_000)
+                .quote_liquidity(1_500_000)
+                .constant_product_sqrt(Q64_128::from_u64(3_000_000))
+                .base_quote_ratio_sqrt(Q64_128::from_u64(2))
+                .lp_tokens_supply(3_000_000)
+                .trading_open_slot(1_000)
+                .build();
+
+            assert!(amm.is_warming_up(999));
+            assert_eq!(amm.get_swap_payload(10_000, 2_468, 10, 100, 10, true, None, SWAP_CONSTANT_PRODUCT_SQRT_TOLERANCE, 999).unwrap_err(), ErrorCode::TradingNotOpenYet.into());
+            assert!(amm.get_provide_payload(60_000, 15_000, 0, ADJUST_LIQUIDITY_RATIO_SQRT_TOLERANCE).is_ok());
+            assert!(amm.get_withdraw_payload(1_000_000, 999, 0, 0, u64::MAX, false, ADJUST_LIQUIDITY_RATIO_SQRT_TOLERANCE).is_ok());
+
+            assert!(!amm.is_warming_up(1_000));
+            assert!(amm.get_swap_payload(10_000, 2_468, 10, 100, 10, true, None, SWAP_CONSTANT_PRODUCT_SQRT_TOLERANCE, 1_000).is_ok());
+
+            // Pools launched without a warm-up are never warming up, and neither are unlaunched ones
+            assert!(!CpAmmBuilder::new().status(PoolStatus::Launched).build().is_warming_up(0));
+            assert!(!CpAmmBuilder::new().status(PoolStatus::Initialized).trading_open_slot(1_000).build().is_warming_up(0));
+        }
+
+        /// Tests starting, extending and ending the protected mode of `CpAmm`.
+        #[test]
+        fn test_update_protected_mode() {
+            let mut amm = CpAmmBuilder::new().status(PoolStatus::Launched).protected_until_slot(100).build();
+            assert!(amm.is_protected(99));
+            assert!(!amm.is_protected(100));
+
+            amm.update_protected_mode(1_000, 5_000).unwrap();
+            assert_eq!(amm.protected_until_slot(), 6_000);
+            assert!(amm.is_protected(5_000));
+            assert!(amm.is_protected(5_999));
+            assert!(!amm.is_protected(6_000));
+
+            amm.update_protected_mode(CpAmm::MAX_PROTECTED_SLOTS, 5_500).unwrap();
+            assert_eq!(amm.protected_until_slot(), 5_500 + CpAmm::MAX_PROTECTED_SLOTS);
+            assert_eq!(amm.update_protected_mode(CpAmm::MAX_PROTECTED_SLOTS + 1, 5_500).unwrap_err(), ErrorCode::ProtectedSlotsExceeded.into());
+            assert_eq!(amm.protected_until_slot(), 5_500 + CpAmm::MAX_PROTECTED_SLOTS);
+
+            amm.update_protected_mode(0, 6_000).unwrap();
+            assert_eq!(amm.protected_until_slot(), 0);
+            assert!(!amm.is_protected(6_000));
+        }
+
+        /// Tests the LP concentration guard and flag of `CpAmm` provides.
+        #[test]
+        fn test_lp_concentration() {
+            let mut amm = CpAmmBuilder::new().status(PoolStatus::Launched).initialized_epoch(100).largest_provide_share_basis_points(500).build();
+            let large_provide = ProvidePayload::new(Q64_128::from_u64(1), Q64_128::from_u64(1), 0, 0, 10_000, 2_500);
+            let small_provide = ProvidePayload::new(Q64_128::from_u64(1), Q64_128::from_u64(1), 0, 0, 10_000, 1_000);
+            assert_eq!(large_provide.holder_share_basis_points(0), 2_500);
+            assert_eq!(small_provide.holder_share_basis_points(0), 1_000);
+            assert_eq!(small_provide.holder_share_basis_points(1_000), 2_000);
+            assert_eq!(small_provide.holder_share_basis_points(u64::MAX), 10_000);
+
+            // The guard applies during the configured epochs since the initialization only
+            assert_eq!(amm.check_lp_concentration(&large_provide, 0, 2_000, 7, 106).err(), Some(ErrorCode::LpConcentrationExceeded.into()));
+            assert!(amm.check_lp_concentration(&small_provide, 0, 2_000, 7, 106).is_ok());
+            assert!(amm.check_lp_concentration(&large_provide, 0, 2_000, 7, 107).is_ok());
+            assert!(amm.check_lp_concentration(&large_provide, 0, 2_000, 0, 100).is_ok());
+            assert!(amm.check_lp_concentration(&large_provide, 0, 0, 7, 100).is_ok());
+            assert!(amm.check_lp_concentration(&large_provide, 0, 2_500, 7, 100).is_ok());
+
+            // Splitting a deposit doesn't bypass the guard, the LP tokens already held are counted
+            assert!(amm.check_lp_concentration(&small_provide, 1_000, 2_000, 7, 106).is_ok());
+            assert_eq!(amm.check_lp_concentration(&small_provide, 1_001, 2_000, 7, 106).err(), Some(ErrorCode::LpConcentrationExceeded.into()));
+
+            amm.record_lp_concentration(&small_provide, 0, 2_000);
+            assert_eq!(amm.largest_provide_share_basis_points(), 1_000);
+            assert!(!amm.concentration_flag());
+
+            amm.record_lp_concentration(&large_provide, 0, 2_000);
+            assert_eq!(amm.largest_provide_share_basis_points(), 2_500);
+            assert!(amm.concentration_flag());
+
+            // The largest share is kept, the flag follows the configured share
+            amm.record_lp_concentration(&small_provide, 0, 3_000);
+            assert_eq!(amm.largest_provide_share_basis_points(), 2_500);
+            assert!(!amm.concentration_flag());
+            amm.record_lp_concentration(&small_provide, 0, 2_000);
+            assert!(amm.concentration_flag());
+            amm.record_lp_concentration(&small_provide, 0, 0);
+            assert!(!amm.concentration_flag());
+
+            amm.record_lp_concentration(&small_provide, 2_000, 2_000);
+            assert_eq!(amm.largest_provide_share_basis_points(), 3_000);
+        }
+
+        /// Tests the spot price the last slot ended at, recorded at the first liquidity change of a slot.
+        #[test]
+        fn test_last_slot_price() {
+            let mut amm = CpAmmBuilder::new()
+                .status(PoolStatus::Launched)
+                .base_liquidity(4_000_000)
+                .quote_liquidity(1_000_000)
+                .build();
+            assert_eq!(amm.spot_price(), 250_000_000);
+            assert_eq!(amm.last_slot_price(10), 250_000_000);
+
+            // The first change of a slot records the price the previous slot ended at
+            amm.record_slot_start_price(10);
+            amm.quote_liquidity = 2_000_000;
+            assert_eq!(amm.spot_price(), 500_000_000);
+            assert_eq!(amm.last_slot_price(10), 250_000_000);
+
+            // Later changes within the slot keep it
+            amm.record_slot_start_price(10);
+            amm.quote_liquidity = 3_000_000;
+            assert_eq!(amm.last_slot_price(10), 250_000_000);
+            assert_eq!(amm.slot_start_price(), 250_000_000);
+            assert_eq!(amm.slot_start_price_slot(), 10);
+
+            // Without a change in a later slot yet, the last slot ended at the spot price
+            assert_eq!(amm.last_slot_price(11), 750_000_000);
+            amm.record_slot_start_price(12);
+            amm.quote_liquidity = 1_000_000;
+            assert_eq!(amm.last_slot_price(12), 750_000_000);
+            assert_eq!(amm.spot_price(), 250_000_000);
+
+            assert_eq!(CpAmmBuilder::new().build().spot_price(), 0);
+        }
+
+        /// Tests the `DisplayData` entries written by `refresh_display_data`.
+        #[test]
+        fn test_refresh_display_data() {
+            let (base_mint, quote_mint) = (Pubkey::new_unique(), Pubkey::new_unique());
+            let mut amm = CpAmmBuilder::new()
+                .status(PoolStatus::Launched)
+                .base_mint(base_mint)
+                .quote_mint(quote_mint)
+                .base_liquidity(4_000_000)
+                .quote_liquidity(1_000_000)
+                .lp_tokens_supply(2_000_000)
+                .max_protocol_fee_basis_points_at_creation(Some(20))
+                .build();
+            assert_eq!(amm.display_data().entries().count(), 0);
+
+            amm.refresh_display_data(25, 30, 777);
+            let display_data = amm.display_data();
+            assert_eq!(display_data.entry(DisplayEntryType::Mints), Some([base_mint.to_bytes(), quote_mint.to_bytes()].concat().as_slice()));
+            assert_eq!(display_data.entry(DisplayEntryType::Reserves), Some([4_000_000u64.to_le_bytes(), 1_000_000u64.to_le_bytes()].concat().as_slice()));
+            assert_eq!(display_data.entry(DisplayEntryType::Price), Some(250_000_000u64.to_le_bytes().as_ref()));
+            // The protocol fee rate is capped by the rate recorded at creation
+            assert_eq!(display_data.entry(DisplayEntryType::FeeRates), Some([25, 0, 20, 0].as_ref()));
+            assert_eq!(display_data.entry(DisplayEntryType::LpTokensSupply), Some(2_000_000u64.to_le_bytes().as_ref()));
+            assert_eq!(display_data.entry(DisplayEntryType::LastUpdateSlot), Some(777u64.to_le_bytes().as_ref()));
+            assert_eq!(display_data.entries().count(), 6);
+
+            let mut amm = CpAmmBuilder::new().base_liquidity(1).quote_liquidity(u64::MAX).build();
+            amm.refresh_display_data(0, 0, 0);
+            assert_eq!(amm.display_data().entry(DisplayEntryType::Price), Some(u64::MAX.to_le_bytes().as_ref()));
+            let mut amm = CpAmmBuilder::new().quote_liquidity(1_000).build();
+            amm.refresh_display_data(0, 0, 0);
+            assert_eq!(amm.display_data().entry(DisplayEntryType::Price), Some(0u64.to_le_bytes().as_ref()));
+        }
+
+        /// Tests that paused operations are rejected while the other operations stay available.
+        #[test]
+        fn test_paused_operations_payloads() {
+            let amm_builder = || CpAmmBuilder::new()
+                .status(PoolStatus::Launched)
+                .base_liquidity(6_000_000)
+                .quote_liquidity(1_500_000)
+                .constant_product_sqrt(Q64_128::from_u64(3_000_000))
+                .base_quote_ratio_sqrt(Q64_128::from_u64(2))
+                .lp_tokens_supply(3_000_000);
+
+            let amm = amm_builder().swaps_paused(true).build();
+            assert_eq!(amm.get_swap_payload(10_000, 2_468, 10, 100, 10, true, None, SWAP_CONSTANT_PRODUCT_SQRT_TOLERANCE, 0).unwrap_err(), ErrorCode::CpAmmSwapsPaused.into());
+            assert!(amm.get_provide_payload(60_000, 15_000, 0, ADJUST_LIQUIDITY_RATIO_SQRT_TOLERANCE).is_ok());
+            assert!(amm.get_withdraw_payload(1_000_000, 0, 0, 0, u64::MAX, false, ADJUST_LIQUIDITY_RATIO_SQRT_TOLERANCE).is_ok());
+
+            let amm = amm_builder().provides_paused(true).build();
+            assert_eq!(amm.get_provide_payload(60_000, 15_000, 0, ADJUST_LIQUIDITY_RATIO_SQRT_TOLERANCE).unwrap_err(), ErrorCode::CpAmmProvidesPaused.into());
+            assert!(amm.get_swap_payload(10_000, 2_468, 10, 100, 10, true, None, SWAP_CONSTANT_PRODUCT_SQRT_TOLERANCE, 0).is_ok());
+            assert!(amm.get_withdraw_payload(1_000_000, 0, 0, 0, u64::MAX, false, ADJUST_LIQUIDITY_RATIO_SQRT_TOLERANCE).is_ok());
+
+            let amm = amm_builder().withdraws_paused(true).build();
+            assert_eq!(amm.get_withdraw_payload(1_000_000, 0, 0, 0, u64::MAX, false, ADJUST_LIQUIDITY_RATIO_SQRT_TOLERANCE).unwrap_err(), ErrorCode::CpAmmWithdrawsPaused.into());
+            assert!(amm.get_swap_payload(10_000, 2_468, 10, 100, 10, true, None, SWAP_CONSTANT_PRODUCT_SQRT_TOLERANCE, 0).is_ok());
+            assert!(amm.get_provide_payload(60_000, 15_000, 0, ADJUST_LIQUIDITY_RATIO_SQRT_TOLERANCE).is_ok());
+        }
+
+        /// Tests the `get_withdraw_payload` method of `CpAmm` for an incident-limited pool.
+        #[test]
+        fn test_get_incident_limited_withdraw_payload() {
+            let amm = CpAmmBuilder::new()
+                .status(PoolStatus::Launched)
+                .incident_limited(true)
+                .base_liquidity(6_000_000)
+                .quote_liquidity(1_500_000)
+                .constant_product_sqrt(Q64_128::from_u64(3_000_000))
+                .base_quote_ratio_sqrt(Q64_128::from_u64(2))
+                .lp_tokens_supply(3_000_000)
+                .build();
+
+            // 5% of 3_000_000 LP tokens per slot
+            let payload = amm.get_withdraw_payload(150_000, 7, 500, 0, u64::MAX, false, ADJUST_LIQUIDITY_RATIO_SQRT_TOLERANCE).unwrap();
+            assert_eq!(payload.withdraw_rate_limiter.slot(), 7);
+            assert_eq!(payload.withdraw_rate_limiter.slot_lp_tokens_supply(), 3_000_000);
+            assert_eq!(payload.withdraw_rate_limiter.withdrawn_lp_tokens(), 150_000);
+
+            assert!(amm.get_withdraw_payload(150_001, 7, 500, 0, u64::MAX, false, ADJUST_LIQUIDITY_RATIO_SQRT_TOLERANCE).is_err());
+            // Zero limit disables the rate limiter
+            assert!(amm.get_withdraw_payload(300_000, 7, 0, 0, u64::MAX, false, ADJUST_LIQUIDITY_RATIO_SQRT_TOLERANCE).is_ok());
         }
 
         /// Tests the `get_swap_payload` method of `CpAmm` for in->out swap.
@@ -1145,7 +3866,7 @@ mod cp_amm_tests {
             let initial_lp_tokens_supply = 3_000_000;
                 
             let amm = CpAmmBuilder::new()
-                .is_launched(true)
+                .status(PoolStatus::Launched)
                 .base_liquidity(initial_base_liquidity)
                 .quote_liquidity(initial_quote_liquidity)
                 .constant_product_sqrt(initial_constant_product_sqrt)
@@ -1153,14 +3874,14 @@ mod cp_amm_tests {
                 .lp_tokens_supply(initial_lp_tokens_supply)
                 .build();
         
-            let base_amount: u64 = 3_061_224;
-            let protocol_fee = base_amount * protocol_fee_basis_points as u64 / 10000;
-            let providers_fee = base_amount * providers_fee_basis_points as u64 / 10000;
+            let base_amount: u64 = 3_061_226;
+            let protocol_fee = (base_amount * protocol_fee_basis_points as u64).div_ceil(10000);
+            let providers_fee = (base_amount * providers_fee_basis_points as u64).div_ceil(10000);
             let estimated_result = 500_000;
             let allowed_slippage = 0;
 
             
-            let payload = amm.get_swap_payload(base_amount, estimated_result, allowed_slippage, providers_fee_basis_points, protocol_fee_basis_points, true).unwrap();
+            let payload = amm.get_swap_payload(base_amount, estimated_result, allowed_slippage, providers_fee_basis_points, protocol_fee_basis_points, true, None, SWAP_CONSTANT_PRODUCT_SQRT_TOLERANCE, 0).unwrap();
         
             assert_eq!(payload.base_liquidity, initial_base_liquidity + base_amount - protocol_fee - providers_fee);
             assert_eq!(payload.quote_liquidity, initial_quote_liquidity - estimated_result);
@@ -1168,6 +3889,9 @@ mod cp_amm_tests {
             assert_eq!(payload.providers_fee_amount, providers_fee);
             assert_eq!(payload.amount_to_withdraw, estimated_result);
             assert!(payload.is_in_out);
+            // Ratio 4.0 -> 9.030612
+            assert_eq!(payload.price_impact_basis_points, 12577);
+            assert_eq!(payload.stats, PoolStats::default().record_swap(base_amount, estimated_result, None).record_fees(providers_fee, protocol_fee, true));
         }
 
         /// Tests the `get_swap_payload` method of `CpAmm` for out->in swap.
@@ -1182,7 +3906,7 @@ mod cp_amm_tests {
             let initial_lp_tokens_supply = 3_000_000;
                 
             let amm = CpAmmBuilder::new()
-                .is_launched(true)
+                .status(PoolStatus::Launched)
                 .base_liquidity(initial_base_liquidity)
                 .quote_liquidity(initial_quote_liquidity)
                 .constant_product_sqrt(initial_constant_product_sqrt)
@@ -1190,13 +3914,15 @@ mod cp_amm_tests {
                 .lp_tokens_supply(initial_lp_tokens_supply)
                 .build();
 
-            let quote_amount: u64 = 510_204;
-            let protocol_fee = quote_amount * protocol_fee_basis_points as u64 / 10000;
-            let providers_fee = quote_amount * providers_fee_basis_points as u64 / 10000;
+            let quote_amount: u64 = 510_206;
+            let protocol_fee = (quote_amount * protocol_fee_basis_points as u64).div_ceil(10000);
+            let providers_fee = (quote_amount * providers_fee_basis_points as u64).div_ceil(10000);
             let estimated_result = 1_500_000;
             let allowed_slippage = 0;
 
-            let payload = amm.get_swap_payload(quote_amount, estimated_result, allowed_slippage, providers_fee_basis_points, protocol_fee_basis_points, false).unwrap();
+            // 2 USD per quote token with 6 decimals
+            let quote_usd_price = UsdPrice::new(200, -2, 6);
+            let payload = amm.get_swap_payload(quote_amount, estimated_result, allowed_slippage, providers_fee_basis_points, protocol_fee_basis_points, false, Some(quote_usd_price), SWAP_CONSTANT_PRODUCT_SQRT_TOLERANCE, 0).unwrap();
 
             assert_eq!(payload.base_liquidity, initial_base_liquidity - estimated_result);
             assert_eq!(payload.quote_liquidity, initial_quote_liquidity + quote_amount - protocol_fee - providers_fee);
@@ -1204,6 +3930,159 @@ mod cp_amm_tests {
             assert_eq!(payload.providers_fee_amount, providers_fee);
             assert_eq!(payload.amount_to_withdraw, estimated_result);
             assert!(!payload.is_in_out);
+            // Ratio 4.0 -> 2.244289
+            assert_eq!(payload.price_impact_basis_points, 4389);
+            assert_eq!(payload.stats, PoolStats::default().record_swap(estimated_result, quote_amount, Some(2 * quote_amount)).record_fees(providers_fee, protocol_fee, false));
+        }
+
+        /// Tests the `get_swap_payload` method of `CpAmm` with waived protocol fees.
+        #[test]
+        fn test_get_swap_payload_without_protocol_fee() {
+            let initial_base_liquidity = 6_000_000;
+            let providers_fee_basis_points = 100;
+
+            let amm = CpAmmBuilder::new()
+                .status(PoolStatus::Launched)
+                .base_liquidity(initial_base_liquidity)
+                .quote_liquidity(1_500_000)
+                .constant_product_sqrt(Q64_128::from_u64(3_000_000))
+                .base_quote_ratio_sqrt(Q64_128::from_u64(2))
+                .lp_tokens_supply(3_000_000)
+                .protocol_base_fees_to_redeem(777)
+                .build();
+
+            let base_amount: u64 = 3_030_304;
+            let providers_fee = (base_amount * providers_fee_basis_points as u64).div_ceil(10000);
+
+            let payload = amm.get_swap_payload(base_amount, 500_000, 0, providers_fee_basis_points, 0, true, None, SWAP_CONSTANT_PRODUCT_SQRT_TOLERANCE, 0).unwrap();
+
+            assert_eq!(payload.base_liquidity, initial_base_liquidity + base_amount - providers_fee);
+            assert_eq!(payload.protocol_fees_to_redeem, 777);
+            assert_eq!(payload.providers_fee_amount, providers_fee);
+            assert_eq!(payload.amount_to_withdraw, 500_000);
+        }
+
+        /// Tests the `get_swap_payload` method of `CpAmm` with a creator share of the providers fee.
+        #[test]
+        fn test_get_swap_payload_with_creator_fee_share() {
+            let initial_base_liquidity = 6_000_000;
+            let providers_fee_basis_points = 100;
+
+            let amm = CpAmmBuilder::new()
+                .status(PoolStatus::Launched)
+                .base_liquidity(initial_base_liquidity)
+                .quote_liquidity(1_500_000)
+                .constant_product_sqrt(Q64_128::from_u64(3_000_000))
+                .base_quote_ratio_sqrt(Q64_128::from_u64(2))
+                .lp_tokens_supply(3_000_000)
+                .creator_fee_share_basis_points(2_500)
+                .creator_base_fees_to_redeem(100)
+                .build();
+
+            let base_amount: u64 = 3_030_304;
+            let providers_fee = (base_amount * providers_fee_basis_points as u64).div_ceil(10000);
+            let creator_fee = providers_fee * 2_500 / 10000;
+
+            let payload = amm.get_swap_payload(base_amount, 500_000, 0, providers_fee_basis_points, 0, true, None, SWAP_CONSTANT_PRODUCT_SQRT_TOLERANCE, 0).unwrap();
+
+            // The swapped liquidity is the same as without the share, only the providers part shrinks
+            assert_eq!(payload.base_liquidity, initial_base_liquidity + base_amount - providers_fee);
+            assert_eq!(payload.creator_fees_to_redeem, 100 + creator_fee);
+            assert_eq!(payload.providers_fee_amount, providers_fee - creator_fee);
+            assert_eq!(payload.creator_fee_amount, creator_fee);
+            assert_eq!(payload.protocol_fee_amount, 0);
+            assert_eq!(payload.amount_to_withdraw, 500_000);
+            assert_eq!(payload.stats, PoolStats::default().record_swap(base_amount, 500_000, None).record_fees(providers_fee - creator_fee, 0, true));
+        }
+
+        /// Tests that swap fees are rounded up in favor of the pool and the swapped tokens are fully accounted for.
+        #[test]
+        fn test_get_swap_payload_fees_round_up() {
+            let (providers_fee_basis_points, protocol_fee_basis_points) = (25, 5);
+            for swap_amount in [1u64, 399, 1_001, 12_345, 99_999] {
+                let build_amm = || CpAmmBuilder::new()
+                    .status(PoolStatus::Launched)
+                    .base_liquidity(6_000_000)
+                    .quote_liquidity(1_500_000)
+                    .constant_product_sqrt(Q64_128::from_u64(3_000_000))
+                    .base_quote_ratio_sqrt(Q64_128::from_u64(2))
+                    .lp_tokens_supply(3_000_000)
+                    .creator_fee_share_basis_points(2_500)
+                    .build();
+                let amm = build_amm();
+                let Ok(payload) = amm.get_swap_payload(swap_amount, 1, u64::MAX, providers_fee_basis_points, protocol_fee_basis_points, true, None, SWAP_CONSTANT_PRODUCT_SQRT_TOLERANCE, 0) else {
+                    // The rounded up fees leave nothing to swap
+                    assert!(swap_amount < 10);
+                    continue;
+                };
+
+                // Fees are never undercounted
+                let providers_fee = payload.providers_fee_amount + payload.creator_fee_amount;
+                assert!(providers_fee as u128 * 10_000 >= swap_amount as u128 * providers_fee_basis_points as u128);
+                assert!(payload.protocol_fee_amount as u128 * 10_000 >= swap_amount as u128 * protocol_fee_basis_points as u128);
+                assert_eq!(providers_fee, (swap_amount * providers_fee_basis_points as u64).div_ceil(10_000));
+                assert_eq!(payload.protocol_fee_amount, (swap_amount * protocol_fee_basis_points as u64).div_ceil(10_000));
+
+                // The swapped in tokens end up in the liquidity or the redeemable fees
+                let mut swapped_amm = build_amm();
+                swapped_amm.swap(payload).unwrap();
+                assert_eq!(
+                    swapped_amm.base_liquidity + swapped_amm.protocol_base_fees_to_redeem + swapped_amm.creator_base_fees_to_redeem,
+                    amm.base_liquidity + swap_amount
+                );
+            }
+        }
+
+        /// Tests overflowing swap fee rates sum, which is rejected as an exceeded total fee rate.
+        #[test]
+        fn test_get_swap_payload_fee_rates_overflow() {
+            let amm = CpAmmBuilder::new()
+                .status(PoolStatus::Launched)
+                .base_liquidity(6_000_000)
+                .quote_liquidity(1_500_000)
+                .constant_product_sqrt(Q64_128::from_u64(3_000_000))
+                .base_quote_ratio_sqrt(Q64_128::from_u64(2))
+                .lp_tokens_supply(3_000_000)
+                .build();
+
+            let result = amm.get_swap_payload(1_000_000, 1, 10000, u16::MAX, 1, true, None, SWAP_CONSTANT_PRODUCT_SQRT_TOLERANCE, 0);
+            assert_eq!(result.err(), Some(ErrorCode::ConfigFeeRateExceeded.into()));
+        }
+
+        /// Tests that `cpmm_math` quotes match the swap, provide and withdraw payloads.
+        #[test]
+        fn test_payloads_match_cpmm_math_quotes() {
+            let amm = CpAmmBuilder::new()
+                .status(PoolStatus::Launched)
+                .base_liquidity(6_000_000)
+                .quote_liquidity(1_500_000)
+                .constant_product_sqrt(Q64_128::from_u64(3_000_000))
+                .base_quote_ratio_sqrt(Q64_128::from_u64(2))
+                .lp_tokens_supply(3_000_000)
+                .build();
+            let state = cpmm_math::quote::CpAmmState::new(
+                amm.base_liquidity, amm.quote_liquidity, amm.lp_tokens_supply, amm.constant_product_sqrt, amm.base_quote_ratio_sqrt
+            ).unwrap();
+
+            for is_in_out in [true, false] {
+                let swap_quote = state.quote_swap(300_000, 30, 5, is_in_out).unwrap();
+                let payload = amm.get_swap_payload(300_000, swap_quote.amount_out, 0, 30, 5, is_in_out, None, SWAP_CONSTANT_PRODUCT_SQRT_TOLERANCE, 0).unwrap();
+                assert_eq!(payload.amount_to_withdraw, swap_quote.amount_out);
+                assert_eq!(payload.providers_fee_amount, swap_quote.providers_fee_amount);
+                assert_eq!(payload.price_impact_basis_points, swap_quote.price_impact_basis_points);
+            }
+
+            let provide_quote = state.quote_provide(600_000, 150_000).unwrap();
+            let provide_payload = amm.get_provide_payload(600_000, 150_000, 0, ADJUST_LIQUIDITY_RATIO_SQRT_TOLERANCE).unwrap();
+            assert_eq!(provide_payload.lp_tokens_to_mint, provide_quote.lp_tokens_to_mint);
+
+            for is_burn_exact in [true, false] {
+                let withdraw_quote = state.quote_withdraw(1_000_000, is_burn_exact).unwrap();
+                let withdraw_payload = amm.get_withdraw_payload(1_000_000, 0, 0, 0, u64::MAX, is_burn_exact, ADJUST_LIQUIDITY_RATIO_SQRT_TOLERANCE).unwrap();
+                assert_eq!(withdraw_payload.lp_tokens_to_burn, withdraw_quote.lp_tokens_to_burn);
+                assert_eq!(withdraw_payload.base_withdraw_amount, withdraw_quote.base_withdraw_amount);
+                assert_eq!(withdraw_payload.quote_withdraw_amount, withdraw_quote.quote_withdraw_amount);
+            }
         }
 
         /// Tests the `get_collect_fees_payload` method of `CpAmm`.
@@ -1216,12 +4095,373 @@ mod cp_amm_tests {
                 .protocol_quote_fees_to_redeem(protocol_quote_fees_to_redeem)
                 .build();
 
-            let payload = amm.get_collect_fees_payload().unwrap();
+            let payload = amm.get_collect_fees_payload(protocol_base_fees_to_redeem, protocol_quote_fees_to_redeem, 0, 30, None, SWAP_CONSTANT_PRODUCT_SQRT_TOLERANCE, 1).unwrap();
 
             assert_eq!(payload.protocol_base_fees_to_redeem, protocol_base_fees_to_redeem);
             assert_eq!(payload.protocol_quote_fees_to_redeem, protocol_quote_fees_to_redeem);
+            assert_eq!(payload.base_keeper_bounty, 0);
+            assert_eq!(payload.quote_keeper_bounty, 0);
+            assert_eq!(payload.new_protocol_base_fees_to_redeem, 0);
+            assert_eq!(payload.new_protocol_quote_fees_to_redeem, 0);
+
+            let payload = amm.get_collect_fees_payload(protocol_base_fees_to_redeem, protocol_quote_fees_to_redeem, 100, 30, None, SWAP_CONSTANT_PRODUCT_SQRT_TOLERANCE, 1).unwrap();
+
+            assert_eq!(payload.base_keeper_bounty, 12343);
+            assert_eq!(payload.quote_keeper_bounty, 675745);
+            assert_eq!(payload.protocol_base_fees_to_redeem, protocol_base_fees_to_redeem - 12343);
+            assert_eq!(payload.protocol_quote_fees_to_redeem, protocol_quote_fees_to_redeem - 675745);
+            assert_eq!(payload.new_protocol_base_fees_to_redeem, 0);
+            assert_eq!(payload.new_protocol_quote_fees_to_redeem, 0);
+
+            // Fees accrued before the protocol fee was disabled are still collectable
+            assert!(amm.get_collect_fees_payload(amm.protocol_base_fees_to_redeem, amm.protocol_quote_fees_to_redeem, 0, 0, None, SWAP_CONSTANT_PRODUCT_SQRT_TOLERANCE, 1).is_ok());
+            assert!(amm.has_protocol_fees_to_redeem());
+
+            let empty_amm = CpAmmBuilder::new().build();
+            assert!(!empty_amm.has_protocol_fees_to_redeem());
+            assert_eq!(empty_amm.get_collect_fees_payload(empty_amm.protocol_base_fees_to_redeem, empty_amm.protocol_quote_fees_to_redeem, 0, 30, None, SWAP_CONSTANT_PRODUCT_SQRT_TOLERANCE, 1).err(), Some(ErrorCode::ProvidersFeesIsZero.into()));
+            assert_eq!(empty_amm.get_collect_fees_payload(empty_amm.protocol_base_fees_to_redeem, empty_amm.protocol_quote_fees_to_redeem, 0, 0, None, SWAP_CONSTANT_PRODUCT_SQRT_TOLERANCE, 1).err(), Some(ErrorCode::ProtocolFeeDisabled.into()));
+        }
+
+        /// Tests partial collections of the protocol fees by `get_collect_fees_payload`.
+        #[test]
+        fn test_get_collect_fees_payload_partial() {
+            let mut amm = CpAmmBuilder::new()
+                .protocol_base_fees_to_redeem(1_000_000)
+                .protocol_quote_fees_to_redeem(500_000)
+                .build();
+
+            let payload = amm.get_collect_fees_payload(400_000, 0, 100, 30, None, SWAP_CONSTANT_PRODUCT_SQRT_TOLERANCE, 1).unwrap();
+            assert_eq!((payload.protocol_base_fees_to_redeem, payload.base_keeper_bounty), (396_000, 4_000));
+            assert_eq!((payload.protocol_quote_fees_to_redeem, payload.quote_keeper_bounty), (0, 0));
+            assert_eq!((payload.new_protocol_base_fees_to_redeem, payload.new_protocol_quote_fees_to_redeem), (600_000, 500_000));
+
+            amm.collect_fees(payload);
+            assert_eq!((amm.protocol_base_fees_to_redeem(), amm.protocol_quote_fees_to_redeem()), (600_000, 500_000));
+
+            let payload = amm.get_collect_fees_payload(600_000, 250_000, 0, 30, None, SWAP_CONSTANT_PRODUCT_SQRT_TOLERANCE, 1).unwrap();
+            assert_eq!((payload.protocol_base_fees_to_redeem, payload.protocol_quote_fees_to_redeem), (600_000, 250_000));
+            assert_eq!((payload.new_protocol_base_fees_to_redeem, payload.new_protocol_quote_fees_to_redeem), (0, 250_000));
+
+            assert_eq!(amm.get_collect_fees_payload(600_001, 0, 0, 30, None, SWAP_CONSTANT_PRODUCT_SQRT_TOLERANCE, 1).err(), Some(ErrorCode::CollectFeesAmountExceeded.into()));
+            assert_eq!(amm.get_collect_fees_payload(0, 500_001, 0, 30, None, SWAP_CONSTANT_PRODUCT_SQRT_TOLERANCE, 1).err(), Some(ErrorCode::CollectFeesAmountExceeded.into()));
+            assert_eq!(amm.get_collect_fees_payload(0, 0, 0, 30, None, SWAP_CONSTANT_PRODUCT_SQRT_TOLERANCE, 1).err(), Some(ErrorCode::ProvidersFeesIsZero.into()));
+        }
+
+        /// Tests the settlement of the protocol fees in a single mint by `get_collect_fees_payload`.
+        #[test]
+        fn test_get_collect_fees_payload_with_settlement() {
+            let (base_mint, quote_mint) = (Pubkey::new_unique(), Pubkey::new_unique());
+            let amm_builder = || CpAmmBuilder::new()
+                .status(PoolStatus::Launched)
+                .base_mint(base_mint)
+                .quote_mint(quote_mint)
+                .base_liquidity(6_000_000)
+                .quote_liquidity(1_500_000)
+                .constant_product_sqrt(Q64_128::from_u64(3_000_000))
+                .base_quote_ratio_sqrt(Q64_128::from_u64(2))
+                .lp_tokens_supply(3_000_000)
+                .protocol_base_fees_to_redeem(60_000)
+                .protocol_quote_fees_to_redeem(10_000);
+            let amm = amm_builder().build();
+            let in_quote = FeeSettlement::try_new(None, 500).unwrap();
+
+            // Base fees are swapped into quote after the keeper bounty is split off
+            let payload = amm.get_collect_fees_payload(amm.protocol_base_fees_to_redeem, amm.protocol_quote_fees_to_redeem, 100, 30, Some(&in_quote), SWAP_CONSTANT_PRODUCT_SQRT_TOLERANCE, 1).unwrap();
+            assert_eq!(payload.base_keeper_bounty, 600);
+            assert_eq!(payload.quote_keeper_bounty, 100);
+            assert_eq!(payload.protocol_base_fees_to_redeem, 0);
+            assert_eq!(payload.base_liquidity, 6_059_400);
+            assert!(payload.quote_liquidity < 1_500_000);
+            assert_eq!(payload.protocol_quote_fees_to_redeem, 9_900 + 1_500_000 - payload.quote_liquidity);
             assert_eq!(payload.new_protocol_base_fees_to_redeem, 0);
             assert_eq!(payload.new_protocol_quote_fees_to_redeem, 0);
+            assert_eq!(
+                amm.get_collect_fees_payload(amm.protocol_base_fees_to_redeem, amm.protocol_quote_fees_to_redeem, 100, 30, Some(&FeeSettlement::try_new(Some(quote_mint), 500).unwrap()), SWAP_CONSTANT_PRODUCT_SQRT_TOLERANCE, 1).unwrap().protocol_quote_fees_to_redeem,
+                payload.protocol_quote_fees_to_redeem
+            );
+
+            // Quote fees are swapped into base when base is the settlement mint
+            let payload = amm.get_collect_fees_payload(amm.protocol_base_fees_to_redeem, amm.protocol_quote_fees_to_redeem, 0, 30, Some(&FeeSettlement::try_new(Some(base_mint), 500).unwrap()), SWAP_CONSTANT_PRODUCT_SQRT_TOLERANCE, 1).unwrap();
+            assert_eq!(payload.protocol_quote_fees_to_redeem, 0);
+            assert_eq!(payload.quote_liquidity, 1_510_000);
+            assert_eq!(payload.protocol_base_fees_to_redeem, 60_000 + 6_000_000 - payload.base_liquidity);
+
+            // Pools not trading the settlement mint are collected as is
+            let payload = amm.get_collect_fees_payload(amm.protocol_base_fees_to_redeem, amm.protocol_quote_fees_to_redeem, 0, 30, Some(&FeeSettlement::try_new(Some(Pubkey::new_unique()), 500).unwrap()), SWAP_CONSTANT_PRODUCT_SQRT_TOLERANCE, 1).unwrap();
+            assert_eq!((payload.protocol_base_fees_to_redeem, payload.protocol_quote_fees_to_redeem), (60_000, 10_000));
+            assert_eq!((payload.base_liquidity, payload.quote_liquidity), (6_000_000, 1_500_000));
+
+            // Fees exceeding the maximal price impact are held back with their bounty
+            let payload = amm.get_collect_fees_payload(amm.protocol_base_fees_to_redeem, amm.protocol_quote_fees_to_redeem, 100, 30, Some(&FeeSettlement::try_new(None, 100).unwrap()), SWAP_CONSTANT_PRODUCT_SQRT_TOLERANCE, 1).unwrap();
+            assert_eq!((payload.protocol_base_fees_to_redeem, payload.base_keeper_bounty), (0, 0));
+            assert_eq!((payload.protocol_quote_fees_to_redeem, payload.quote_keeper_bounty), (9_900, 100));
+            assert_eq!((payload.new_protocol_base_fees_to_redeem, payload.new_protocol_quote_fees_to_redeem), (60_000, 0));
+            assert_eq!((payload.base_liquidity, payload.quote_liquidity), (6_000_000, 1_500_000));
+
+            // Only the requested base fees are settled, the rest stays accrued
+            let payload = amm.get_collect_fees_payload(20_000, 10_000, 0, 30, Some(&in_quote), SWAP_CONSTANT_PRODUCT_SQRT_TOLERANCE, 1).unwrap();
+            assert_eq!(payload.protocol_base_fees_to_redeem, 0);
+            assert_eq!(payload.base_liquidity, 6_020_000);
+            assert_eq!(payload.protocol_quote_fees_to_redeem, 10_000 + 1_500_000 - payload.quote_liquidity);
+            assert_eq!((payload.new_protocol_base_fees_to_redeem, payload.new_protocol_quote_fees_to_redeem), (40_000, 0));
+
+            // Fees aren't settled at a spot price moved away from the price the last slot ended at
+            let mut skewed_amm = amm_builder().quote_liquidity(1_600_000).build();
+            skewed_amm.record_slot_start_price(5);
+            skewed_amm.quote_liquidity = 1_500_000;
+            let payload = skewed_amm.get_collect_fees_payload(skewed_amm.protocol_base_fees_to_redeem, skewed_amm.protocol_quote_fees_to_redeem, 0, 30, Some(&in_quote), SWAP_CONSTANT_PRODUCT_SQRT_TOLERANCE, 5).unwrap();
+            assert_eq!((payload.protocol_base_fees_to_redeem, payload.protocol_quote_fees_to_redeem), (0, 10_000));
+            assert_eq!(payload.new_protocol_base_fees_to_redeem, 60_000);
+            let payload = skewed_amm.get_collect_fees_payload(skewed_amm.protocol_base_fees_to_redeem, skewed_amm.protocol_quote_fees_to_redeem, 0, 30, Some(&FeeSettlement::try_new(None, 700).unwrap()), SWAP_CONSTANT_PRODUCT_SQRT_TOLERANCE, 5).unwrap();
+            assert_eq!(payload.new_protocol_base_fees_to_redeem, 0);
+            let payload = skewed_amm.get_collect_fees_payload(skewed_amm.protocol_base_fees_to_redeem, skewed_amm.protocol_quote_fees_to_redeem, 0, 30, Some(&in_quote), SWAP_CONSTANT_PRODUCT_SQRT_TOLERANCE, 6).unwrap();
+            assert_eq!(payload.new_protocol_base_fees_to_redeem, 0);
+
+            let paused_amm = amm_builder().swaps_paused(true).build();
+            assert_eq!(paused_amm.get_collect_fees_payload(paused_amm.protocol_base_fees_to_redeem, paused_amm.protocol_quote_fees_to_redeem, 0, 30, Some(&in_quote), SWAP_CONSTANT_PRODUCT_SQRT_TOLERANCE, 1).unwrap().new_protocol_base_fees_to_redeem, 60_000);
+
+            let base_fees_amm = amm_builder().protocol_quote_fees_to_redeem(0).build();
+            assert_eq!(
+                base_fees_amm.get_collect_fees_payload(base_fees_amm.protocol_base_fees_to_redeem, base_fees_amm.protocol_quote_fees_to_redeem, 0, 30, Some(&FeeSettlement::try_new(None, 100).unwrap()), SWAP_CONSTANT_PRODUCT_SQRT_TOLERANCE, 1).err(),
+                Some(ErrorCode::FeeSettlementImpactExceeded.into())
+            );
+        }
+        /// Tests `get_swap_payload` against the golden swap vectors shared with the SDKs.
+        ///
+        /// With `CPMM_BLESS_GOLDEN` set, the outcomes of the table are rewritten from the program instead.
+        #[test]
+        fn test_get_swap_payload_golden_vectors() {
+            let outcome = |vector: &SwapVector| {
+                let mut amm = CpAmmBuilder::new()
+                    .status(PoolStatus::Initialized)
+                    .build();
+                let launch_payload = amm.get_launch_payload(vector.base_liquidity, vector.quote_liquidity, 6, 6, 6, None).unwrap();
+                amm.launch(launch_payload, 0);
+                amm.get_swap_payload(
+                    vector.swap_amount, 1, u64::MAX, vector.providers_fee_rate_basis_points, vector.protocol_fee_rate_basis_points,
+                    vector.is_in_out, None, SWAP_CONSTANT_PRODUCT_SQRT_TOLERANCE, 0
+                ).ok().map(|payload| SwapQuote {
+                    amount_out: payload.amount_to_withdraw(),
+                    providers_fee_amount: payload.providers_fee_amount(),
+                    protocol_fee_amount: payload.protocol_fee_amount(),
+                    price_impact_basis_points: payload.price_impact_basis_points(),
+                })
+            };
+
+            if std::env::var_os("CPMM_BLESS_GOLDEN").is_some() {
+                let path = concat!(env!("CARGO_MANIFEST_DIR"), "/../../crates/cpmm-math/golden/swap_vectors.csv");
+                let mut lines: Vec<String> = golden::SWAP_VECTORS_CSV.lines()
+                    .take_while(|line| line.starts_with('#'))
+                    .map(str::to_string)
+                    .collect();
+                lines.push(golden::SWAP_VECTORS_HEADER.to_string());
+                lines.extend(golden::swap_vectors().into_iter().map(|vector| SwapVector { expected: outcome(&vector), ..vector }.to_string()));
+                std::fs::write(path, lines.join("\n")).unwrap();
+                return;
+            }
+
+            for vector in golden::swap_vectors() {
+                assert_eq!(outcome(&vector), vector.expected, "Swap vector {}", vector);
+            }
+        }
+    }
+
+    mod fee_reconciliation_tests {
+        use crate::events::{CollectCreatorFeesEvent, CollectFeesEvent, SwapEvent};
+        use super::*;
+
+        /// The number of slots in a day, at 400ms per slot.
+        const SLOTS_PER_DAY: u64 = 216_000;
+
+        /// The number of slots between two replayed swaps.
+        const SWAP_INTERVAL_SLOTS: u64 = 50;
+
+        /// The number of slots between two protocol fees collections.
+        const COLLECT_FEES_INTERVAL_SLOTS: u64 = 9_000;
+
+        /// The number of slots between two creator fees collections.
+        const COLLECT_CREATOR_FEES_INTERVAL_SLOTS: u64 = 54_000;
+
+        const PROVIDERS_FEE_RATE_BASIS_POINTS: u16 = 25;
+        const PROTOCOL_FEE_RATE_BASIS_POINTS: u16 = 5;
+        const CREATOR_FEE_SHARE_BASIS_POINTS: u16 = 2_000;
+        const KEEPER_BOUNTY_BASIS_POINTS: u16 = 1_000;
+
+        /// An event emitted by a replayed instruction.
+        enum FeeEvent {
+            Swap(SwapEvent),
+            CollectFees(CollectFeesEvent),
+            CollectCreatorFees(CollectCreatorFeesEvent),
+        }
+
+        /// Accounting rebuilt from the event stream alone, indexed by `[base, quote]`.
+        #[derive(Default)]
+        struct EventLedger {
+            vault_balances: [u128; 2],
+            emitted_providers_fees: [u128; 2],
+            emitted_protocol_fees: [u128; 2],
+            emitted_creator_fees: [u128; 2],
+            collected_protocol_fees: [u128; 2],
+            collected_creator_fees: [u128; 2],
+        }
+
+        impl EventLedger {
+            fn apply(&mut self, event: &FeeEvent) {
+                match event {
+                    FeeEvent::Swap(event) => {
+                        let (input, output) = if event.is_in_out { (0, 1) } else { (1, 0) };
+                        self.vault_balances[input] += event.swap_amount as u128;
+                        self.vault_balances[output] -= event.swap_result as u128;
+                        self.emitted_providers_fees[input] += event.providers_fee_amount as u128;
+                        self.emitted_protocol_fees[input] += event.protocol_fee_amount as u128;
+                        self.emitted_creator_fees[input] += event.creator_fee_amount as u128;
+                    }
+                    FeeEvent::CollectFees(event) => {
+                        let collected = [
+                            (event.base_fees_amount + event.base_keeper_bounty) as u128,
+                            (event.quote_fees_amount + event.quote_keeper_bounty) as u128,
+                        ];
+                        for (side, amount) in collected.into_iter().enumerate() {
+                            self.vault_balances[side] -= amount;
+                            self.collected_protocol_fees[side] += amount;
+                        }
+                    }
+                    FeeEvent::CollectCreatorFees(event) => {
+                        let collected = [event.base_fees_amount as u128, event.quote_fees_amount as u128];
+                        for (side, amount) in collected.into_iter().enumerate() {
+                            self.vault_balances[side] -= amount;
+                            self.collected_creator_fees[side] += amount;
+                        }
+                    }
+                }
+            }
+
+            /// Asserts that the fees accrued in the pool match the emitted minus the collected fees.
+            fn assert_accrued_fees(&self, amm: &CpAmm) {
+                assert_eq!(self.emitted_protocol_fees[0] - self.collected_protocol_fees[0], amm.protocol_base_fees_to_redeem as u128);
+                assert_eq!(self.emitted_protocol_fees[1] - self.collected_protocol_fees[1], amm.protocol_quote_fees_to_redeem as u128);
+                assert_eq!(self.emitted_creator_fees[0] - self.collected_creator_fees[0], amm.creator_base_fees_to_redeem as u128);
+                assert_eq!(self.emitted_creator_fees[1] - self.collected_creator_fees[1], amm.creator_quote_fees_to_redeem as u128);
+            }
+        }
+
+        /// Swaps a pseudo-random amount of either token, building the event like the swap instructions.
+        fn swap(amm: &mut CpAmm, rng_state: &mut u64) -> FeeEvent {
+            // xorshift64
+            *rng_state ^= *rng_state << 13;
+            *rng_state ^= *rng_state >> 7;
+            *rng_state ^= *rng_state << 17;
+            let is_in_out = *rng_state & 1 == 1;
+            let input_liquidity = if is_in_out { amm.base_liquidity } else { amm.quote_liquidity };
+            let swap_amount = 1_000_000 + (*rng_state >> 1) % (input_liquidity / 200);
+
+            let swap_payload = amm.get_swap_payload(
+                swap_amount,
+                1,
+                u64::MAX,
+                PROVIDERS_FEE_RATE_BASIS_POINTS,
+                PROTOCOL_FEE_RATE_BASIS_POINTS,
+                is_in_out,
+                None,
+                SWAP_CONSTANT_PRODUCT_SQRT_TOLERANCE,
+                0
+            ).unwrap();
+            let event = SwapEvent {
+                cp_amm: Pubkey::default(),
+                signer: Pubkey::default(),
+                is_in_out,
+                swap_amount,
+                swap_result: swap_payload.amount_to_withdraw(),
+                price_impact_basis_points: swap_payload.price_impact_basis_points(),
+                providers_fee_rate_basis_points: PROVIDERS_FEE_RATE_BASIS_POINTS,
+                protocol_fee_rate_basis_points: PROTOCOL_FEE_RATE_BASIS_POINTS,
+                providers_fee_amount: swap_payload.providers_fee_amount(),
+                protocol_fee_amount: swap_payload.protocol_fee_amount(),
+                creator_fee_amount: swap_payload.creator_fee_amount(),
+            };
+            amm.swap(swap_payload).unwrap();
+            FeeEvent::Swap(event)
+        }
+
+        /// Collects the protocol fees, building the event like `collect_fees_from_cp_amm`.
+        fn collect_fees(amm: &mut CpAmm) -> FeeEvent {
+            let collect_fees_payload = amm.get_collect_fees_payload(amm.protocol_base_fees_to_redeem, amm.protocol_quote_fees_to_redeem, KEEPER_BOUNTY_BASIS_POINTS, PROTOCOL_FEE_RATE_BASIS_POINTS, None, SWAP_CONSTANT_PRODUCT_SQRT_TOLERANCE, 1).unwrap();
+            let event = CollectFeesEvent {
+                cp_amm: Pubkey::default(),
+                fee_authority: Pubkey::default(),
+                signer: Pubkey::default(),
+                base_fees_amount: collect_fees_payload.protocol_base_fees_to_redeem(),
+                quote_fees_amount: collect_fees_payload.protocol_quote_fees_to_redeem(),
+                base_keeper_bounty: collect_fees_payload.base_keeper_bounty(),
+                quote_keeper_bounty: collect_fees_payload.quote_keeper_bounty(),
+            };
+            amm.collect_fees(collect_fees_payload);
+            FeeEvent::CollectFees(event)
+        }
+
+        /// Collects the creator fees, building the event like `collect_creator_fees`.
+        fn collect_creator_fees(amm: &mut CpAmm) -> FeeEvent {
+            let collect_creator_fees_payload = amm.get_collect_creator_fees_payload().unwrap();
+            let event = CollectCreatorFeesEvent {
+                cp_amm: Pubkey::default(),
+                creator: Pubkey::default(),
+                base_fees_amount: collect_creator_fees_payload.creator_base_fees_to_redeem(),
+                quote_fees_amount: collect_creator_fees_payload.creator_quote_fees_to_redeem(),
+            };
+            amm.collect_creator_fees(collect_creator_fees_payload);
+            FeeEvent::CollectCreatorFees(event)
+        }
+
+        /// Replays a day of swaps with periodic fee collections and verifies after every event
+        /// that the event stream alone accounts for the accrued fees, the collected fees and the vaults.
+        #[test]
+        fn test_events_reconcile_fees_over_a_day() {
+            for seed in [0x2545_f491_4f6c_dd1d_u64, 0x9e37_79b9_7f4a_7c15, 0xbf58_476d_1ce4_e5b9] {
+                let (base_liquidity, quote_liquidity) = (1_000_000_000_000, 250_000_000_000);
+                let mut amm = CpAmmBuilder::new()
+                    .status(PoolStatus::Launched)
+                    .base_liquidity(base_liquidity)
+                    .quote_liquidity(quote_liquidity)
+                    .constant_product_sqrt(CpAmm::calculate_constant_product_sqrt(base_liquidity, quote_liquidity).unwrap())
+                    .base_quote_ratio_sqrt(CpAmm::calculate_base_quote_ratio_sqrt(base_liquidity, quote_liquidity).unwrap())
+                    .lp_tokens_supply(500_000_000_000)
+                    .creator_fee_share_basis_points(CREATOR_FEE_SHARE_BASIS_POINTS)
+                    .build();
+                let mut ledger = EventLedger {
+                    vault_balances: [base_liquidity as u128, quote_liquidity as u128],
+                    ..EventLedger::default()
+                };
+                let mut rng_state = seed;
+
+                for slot in (SWAP_INTERVAL_SLOTS..=SLOTS_PER_DAY).step_by(SWAP_INTERVAL_SLOTS as usize) {
+                    let mut events = vec![swap(&mut amm, &mut rng_state)];
+                    if slot % COLLECT_FEES_INTERVAL_SLOTS == 0 {
+                        events.push(collect_fees(&mut amm));
+                    }
+                    if slot % COLLECT_CREATOR_FEES_INTERVAL_SLOTS == 0 {
+                        events.push(collect_creator_fees(&mut amm));
+                    }
+                    for event in events.iter() {
+                        ledger.apply(event);
+                    }
+                    ledger.assert_accrued_fees(&amm);
+                }
+
+                // The day ends with a collection, so everything emitted has been collected exactly
+                assert_eq!((amm.protocol_base_fees_to_redeem, amm.protocol_quote_fees_to_redeem), (0, 0));
+                assert_eq!((amm.creator_base_fees_to_redeem, amm.creator_quote_fees_to_redeem), (0, 0));
+                assert_eq!(ledger.collected_protocol_fees, ledger.emitted_protocol_fees);
+                assert_eq!(ledger.collected_creator_fees, ledger.emitted_creator_fees);
+
+                let stats = amm.stats();
+                assert_eq!(ledger.emitted_providers_fees, [stats.cumulative_base_providers_fees(), stats.cumulative_quote_providers_fees()]);
+                assert_eq!(ledger.emitted_protocol_fees, [stats.cumulative_base_protocol_fees(), stats.cumulative_quote_protocol_fees()]);
+                assert!(ledger.emitted_creator_fees.iter().all(|fees| *fees > 0));
+
+                // With no fees left to redeem the vaults hold exactly the liquidity
+                assert_eq!(ledger.vault_balances, [amm.base_liquidity as u128, amm.quote_liquidity as u128]);
+            }
         }
     }
 }
@@ -1339,6 +4579,16 @@ impl ProvidePayload {
     pub fn lp_tokens_to_mint(&self) -> u64{
         self.lp_tokens_to_mint
     }
+
+    /// Returns the share of the updated LP token supply held by the liquidity provider after the provide, measured in basis points.
+    ///
+    /// # Parameters
+    /// - `holder_lp_tokens`: The LP tokens held by the provider before the provide.
+    pub fn holder_share_basis_points(&self, holder_lp_tokens: u64) -> u16 {
+        ((holder_lp_tokens as u128 + self.lp_tokens_to_mint as u128) * 10000)
+            .checked_div(self.lp_tokens_supply as u128)
+            .map_or(0, |share| share.min(10000) as u16)
+    }
 }
 
 /// Represents the data required to withdraw liquidity from the AMM.
@@ -1353,14 +4603,17 @@ impl ProvidePayload {
 /// - `lp_tokens_supply`: The updated total supply of LP tokens.
 /// - `base_withdraw_amount`: The amount of base tokens withdrawn.
 /// - `quote_withdraw_amount`: The amount of quote tokens withdrawn.
+/// - `withdraw_rate_limiter`: The updated per-slot withdrawals accounting.
 #[derive(Debug)]
 pub struct WithdrawPayload{
     base_quote_ratio_sqrt: Q64_128,
     base_liquidity: u64,
     quote_liquidity: u64,
     lp_tokens_supply: u64,
+    lp_tokens_to_burn: u64,
     base_withdraw_amount: u64,
-    quote_withdraw_amount: u64
+    quote_withdraw_amount: u64,
+    withdraw_rate_limiter: WithdrawRateLimiter,
 }
 impl WithdrawPayload {
     /// Creates a new `WithdrawPayload` instance with the specified parameters.
@@ -1370,26 +4623,38 @@ impl WithdrawPayload {
     /// - `base_liquidity`: The updated base liquidity amount.
     /// - `quote_liquidity`: The updated quote liquidity amount.
     /// - `lp_tokens_supply`: The updated LP token supply.
+    /// - `lp_tokens_to_burn`: The LP tokens burned from the user.
     /// - `base_withdraw_amount`: The base tokens withdrawn.
     /// - `quote_withdraw_amount`: The quote tokens withdrawn.
+    /// - `withdraw_rate_limiter`: The updated per-slot withdrawals accounting.
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         base_quote_ratio_sqrt: Q64_128,
         base_liquidity: u64,
         quote_liquidity: u64,
         lp_tokens_supply: u64,
+        lp_tokens_to_burn: u64,
         base_withdraw_amount: u64,
         quote_withdraw_amount: u64,
+        withdraw_rate_limiter: WithdrawRateLimiter,
     ) -> Self {
         Self {
             base_quote_ratio_sqrt,
             base_liquidity,
             quote_liquidity,
             lp_tokens_supply,
+            lp_tokens_to_burn,
             base_withdraw_amount,
             quote_withdraw_amount,
+            withdraw_rate_limiter,
         }
     }
 
+    /// Returns the amount of LP tokens burned from the user.
+    pub fn lp_tokens_to_burn(&self) -> u64{
+        self.lp_tokens_to_burn
+    }
+
     /// Returns the amount of base tokens withdrawn.
     pub fn base_withdraw_amount(&self) -> u64{
         self.base_withdraw_amount
@@ -1411,17 +4676,27 @@ impl WithdrawPayload {
 /// - `base_liquidity`: The updated base token liquidity in the pool.
 /// - `quote_liquidity`: The updated quote token liquidity in the pool.
 /// - `protocol_fees_to_redeem`: The protocol fees to redeem updated with fees collected from the swap.
-/// - `providers_fee_amount`: The providers fees collected from the swap.
+/// - `creator_fees_to_redeem`: The creator fees to redeem updated with the creator's share of the providers fees.
+/// - `providers_fee_amount`: The providers fees collected from the swap, net of the creator's share.
+/// - `protocol_fee_amount`: The protocol fees collected from the swap.
+/// - `creator_fee_amount`: The creator's share of the providers fees collected from the swap.
 /// - `amount_to_withdraw`: The amount of tokens to withdraw after the swap.
 /// - `is_in_out`: Indicates whether the swap is "in-to-out" (true) or "out-to-in" (false).
+/// - `stats`: The trading statistics recorded by the swap.
 #[derive(Debug)]
 pub struct SwapPayload {
     base_liquidity: u64,
     quote_liquidity: u64,
     protocol_fees_to_redeem: u64,
+    creator_fees_to_redeem: u64,
     providers_fee_amount: u64,
+    protocol_fee_amount: u64,
+    creator_fee_amount: u64,
     amount_to_withdraw: u64,
     is_in_out: bool,
+    price_impact_basis_points: u64,
+    base_quote_ratio_sqrt: Q64_128,
+    stats: PoolStats,
 }
 
 impl SwapPayload {
@@ -1431,17 +4706,30 @@ impl SwapPayload {
     /// - `base_liquidity`: The updated base token liquidity.
     /// - `quote_liquidity`: The updated quote token liquidity.
     /// - `protocol_fees_to_redeem`: The protocol fees to redeem updated with fees collected from the swap.
-    /// - `providers_fee_amount`: The providers fees collected from the swap.
+    /// - `creator_fees_to_redeem`: The creator fees to redeem updated with the creator's share of the providers fees.
+    /// - `providers_fee_amount`: The providers fees collected from the swap, net of the creator's share.
+    /// - `protocol_fee_amount`: The protocol fees collected from the swap.
+    /// - `creator_fee_amount`: The creator's share of the providers fees collected from the swap.
     /// - `amount_to_withdraw`: The amount of tokens withdrawn.
     /// - `is_in_out`: Indicates the direction of the swap.
-    fn new(base_liquidity: u64, quote_liquidity: u64, protocol_fees_to_redeem: u64, providers_fee_amount: u64, amount_to_withdraw: u64, is_in_out: bool) -> Self {
+    /// - `price_impact_basis_points`: The change of the base-to-quote ratio caused by the swap.
+    /// - `base_quote_ratio_sqrt`: The normalized base-to-quote ratio square root after the swap, fees included.
+    /// - `stats`: The trading statistics recorded by the swap.
+    #[allow(clippy::too_many_arguments)]
+    fn new(base_liquidity: u64, quote_liquidity: u64, protocol_fees_to_redeem: u64, creator_fees_to_redeem: u64, providers_fee_amount: u64, protocol_fee_amount: u64, creator_fee_amount: u64, amount_to_withdraw: u64, is_in_out: bool, price_impact_basis_points: u64, base_quote_ratio_sqrt: Q64_128, stats: PoolStats) -> Self {
         Self{
             base_liquidity,
             quote_liquidity,
             protocol_fees_to_redeem,
+            creator_fees_to_redeem,
             providers_fee_amount,
+            protocol_fee_amount,
+            creator_fee_amount,
             amount_to_withdraw,
             is_in_out,
+            price_impact_basis_points,
+            base_quote_ratio_sqrt,
+            stats,
         }
     }
 
@@ -1449,6 +4737,32 @@ impl SwapPayload {
     pub fn amount_to_withdraw(&self) -> u64{
         self.amount_to_withdraw
     }
+
+    /// Returns the price impact of the swap in basis points.
+    pub fn price_impact_basis_points(&self) -> u64{
+        self.price_impact_basis_points
+    }
+
+    /// Returns the providers fees collected from the swap, net of the creator's share.
+    pub fn providers_fee_amount(&self) -> u64{
+        self.providers_fee_amount
+    }
+
+    /// Returns the protocol fees collected from the swap.
+    pub fn protocol_fee_amount(&self) -> u64{
+        self.protocol_fee_amount
+    }
+
+    /// Returns the creator's share of the providers fees collected from the swap.
+    pub fn creator_fee_amount(&self) -> u64{
+        self.creator_fee_amount
+    }
+
+    /// Takes the trading statistics recorded by the swap out of the payload,
+    /// so they are recorded on a `PoolStatsShard` instead of the `CpAmm`.
+    pub(crate) fn take_stats(&mut self) -> PoolStats{
+        std::mem::take(&mut self.stats)
+    }
 }
 
 /// Represents the data required for collecting protocol fees in the AMM.
@@ -1467,12 +4781,24 @@ pub struct CollectFeesPayload {
 
     /// The amount of protocol fees in quote tokens that will be redeemed.
     protocol_quote_fees_to_redeem: u64,
+
+    /// The amount of base tokens paid to the collection caller out of the protocol fees.
+    base_keeper_bounty: u64,
+
+    /// The amount of quote tokens paid to the collection caller out of the protocol fees.
+    quote_keeper_bounty: u64,
     
     /// Left amount of protocol fees in base tokens that can be redeemed.
     new_protocol_base_fees_to_redeem: u64,
 
     /// Left amount of protocol fees in quote tokens that can be redeemed.
     new_protocol_quote_fees_to_redeem: u64,
+
+    /// The updated base token liquidity, changed by the settlement of the fees.
+    base_liquidity: u64,
+
+    /// The updated quote token liquidity, changed by the settlement of the fees.
+    quote_liquidity: u64,
 }
 
 impl CollectFeesPayload {
@@ -1481,22 +4807,35 @@ impl CollectFeesPayload {
     /// # Parameters
     /// - `protocol_base_fees_to_redeem`: The amount of protocol fees in base tokens for redemption.
     /// - `protocol_quote_fees_to_redeem`: The amount of protocol fees in quote tokens for redemption.
+    /// - `base_keeper_bounty`: The amount of base tokens paid to the collection caller.
+    /// - `quote_keeper_bounty`: The amount of quote tokens paid to the collection caller.
     /// - `new_protocol_base_fees_to_redeem`: Left amount of protocol fees in base tokens available for redemption.
     /// - `new_protocol_quote_fees_to_redeem`: Left amount of protocol fees in quote tokens available for redemption.
+    /// - `base_liquidity`: The updated base token liquidity.
+    /// - `quote_liquidity`: The updated quote token liquidity.
     ///
     /// # Returns
     /// - A new instance of `CollectFeesPayload`.
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         protocol_base_fees_to_redeem: u64,
         protocol_quote_fees_to_redeem: u64,
+        base_keeper_bounty: u64,
+        quote_keeper_bounty: u64,
         new_protocol_base_fees_to_redeem: u64,
         new_protocol_quote_fees_to_redeem: u64,
+        base_liquidity: u64,
+        quote_liquidity: u64,
     ) -> Self {
         Self {
             protocol_base_fees_to_redeem,
             protocol_quote_fees_to_redeem,
+            base_keeper_bounty,
+            quote_keeper_bounty,
             new_protocol_base_fees_to_redeem,
-            new_protocol_quote_fees_to_redeem
+            new_protocol_quote_fees_to_redeem,
+            base_liquidity,
+            quote_liquidity
         }
     }
 
@@ -1509,7 +4848,74 @@ impl CollectFeesPayload {
     pub fn protocol_quote_fees_to_redeem(&self) -> u64 {
         self.protocol_quote_fees_to_redeem
     }
+
+    /// Returns the amount of base tokens paid to the collection caller.
+    pub fn base_keeper_bounty(&self) -> u64 {
+        self.base_keeper_bounty
+    }
+
+    /// Returns the amount of quote tokens paid to the collection caller.
+    pub fn quote_keeper_bounty(&self) -> u64 {
+        self.quote_keeper_bounty
+    }
+}
+
+/// Represents the pool creator's fees to collect from the AMM.
+///
+/// # Fields
+/// - `creator_base_fees_to_redeem`: The amount of creator fees in base tokens for redemption.
+/// - `creator_quote_fees_to_redeem`: The amount of creator fees in quote tokens for redemption.
+/// - `new_creator_base_fees_to_redeem`: Left amount of creator fees in base tokens available for redemption.
+/// - `new_creator_quote_fees_to_redeem`: Left amount of creator fees in quote tokens available for redemption.
+#[derive(Debug)]
+pub struct CollectCreatorFeesPayload {
+    creator_base_fees_to_redeem: u64,
+    creator_quote_fees_to_redeem: u64,
+    new_creator_base_fees_to_redeem: u64,
+    new_creator_quote_fees_to_redeem: u64,
+}
+
+impl CollectCreatorFeesPayload {
+    /// Returns the amount of creator fees in base tokens for redemption.
+    pub fn creator_base_fees_to_redeem(&self) -> u64 {
+        self.creator_base_fees_to_redeem
+    }
+
+    /// Returns the amount of creator fees in quote tokens for redemption.
+    pub fn creator_quote_fees_to_redeem(&self) -> u64 {
+        self.creator_quote_fees_to_redeem
+    }
+}
+
+/// Represents the AMM state after lending out liquidity or a loan repayment.
+///
+/// # Fields
+/// - `base_liquidity`: The updated base token liquidity, including repaid interest.
+/// - `quote_liquidity`: The updated quote token liquidity, including repaid interest.
+/// - `base_lent_out`: The updated amount of lent out base liquidity.
+/// - `quote_lent_out`: The updated amount of lent out quote liquidity.
+/// - `stats`: The updated trading statistics of the pool.
+#[derive(Debug)]
+pub struct LendOutPayload {
+    base_liquidity: u64,
+    quote_liquidity: u64,
+    base_lent_out: u64,
+    quote_lent_out: u64,
+    stats: PoolStats,
+}
+
+impl LendOutPayload {
+    /// Returns the updated amount of lent out base liquidity.
+    pub fn base_lent_out(&self) -> u64 {
+        self.base_lent_out
+    }
+
+    /// Returns the updated amount of lent out quote liquidity.
+    pub fn quote_lent_out(&self) -> u64 {
+        self.quote_lent_out
+    }
 }
+
 #[cfg(test)]
 mod payloads_tests {
     use super::*;
@@ -1567,17 +4973,22 @@ mod payloads_tests {
             4000,
             5000,
             6000,
+            1500,
             1000,
             2000,
+            WithdrawRateLimiter::default(),
         );
 
         assert_eq!(payload.base_quote_ratio_sqrt, Q64_128::from_u64(2000));
         assert_eq!(payload.base_liquidity, 4000);
         assert_eq!(payload.quote_liquidity, 5000);
         assert_eq!(payload.lp_tokens_supply, 6000);
+        assert_eq!(payload.lp_tokens_to_burn, 1500);
         assert_eq!(payload.base_withdraw_amount, 1000);
         assert_eq!(payload.quote_withdraw_amount, 2000);
+        assert_eq!(payload.withdraw_rate_limiter, WithdrawRateLimiter::default());
 
+        assert_eq!(payload.lp_tokens_to_burn(), 1500);
         assert_eq!(payload.base_withdraw_amount(), 1000);
         assert_eq!(payload.quote_withdraw_amount(), 2000);
     }
@@ -1585,22 +4996,32 @@ mod payloads_tests {
     /// Tests the `SwapPayload` struct's creation and getters.
     #[test]
     fn test_swap_payload() {
-        let payload = SwapPayload::new(4000, 5000, 6000, 6500,7000, true);
+        let payload = SwapPayload::new(4000, 5000, 6000, 6250, 6500, 600, 25, 7000, true, 150, Q64_128::from_u64(3), PoolStats::default());
 
         assert_eq!(payload.base_liquidity, 4000);
         assert_eq!(payload.quote_liquidity, 5000);
         assert_eq!(payload.protocol_fees_to_redeem, 6000);
+        assert_eq!(payload.creator_fees_to_redeem, 6250);
         assert_eq!(payload.providers_fee_amount, 6500);
+        assert_eq!(payload.protocol_fee_amount, 600);
+        assert_eq!(payload.creator_fee_amount, 25);
         assert_eq!(payload.amount_to_withdraw, 7000);
         assert!(payload.is_in_out);
+        assert_eq!(payload.price_impact_basis_points, 150);
+        assert_eq!(payload.base_quote_ratio_sqrt, Q64_128::from_u64(3));
+        assert_eq!(payload.stats, PoolStats::default());
 
         assert_eq!(payload.amount_to_withdraw(), 7000);
+        assert_eq!(payload.price_impact_basis_points(), 150);
+        assert_eq!(payload.providers_fee_amount(), 6500);
+        assert_eq!(payload.protocol_fee_amount(), 600);
+        assert_eq!(payload.creator_fee_amount(), 25);
     }
     
     /// Tests the `CollectFeesPayload` struct's creation and getters.
     #[test]
     fn test_collect_fees_payload() {
-        let payload = CollectFeesPayload::new(112314, 536454000, 0, 0);
+        let payload = CollectFeesPayload::new(112314, 536454000, 1123, 5364540, 0, 0, 6_000_000, 1_500_000);
 
         assert_eq!(payload.protocol_base_fees_to_redeem, 112314);
         assert_eq!(payload.protocol_quote_fees_to_redeem, 536454000);
@@ -1608,5 +5029,9 @@ mod payloads_tests {
         assert_eq!(payload.new_protocol_quote_fees_to_redeem, 0);
         assert_eq!(payload.protocol_base_fees_to_redeem(), 112314);
         assert_eq!(payload.protocol_quote_fees_to_redeem(), 536454000);
+        assert_eq!(payload.base_keeper_bounty(), 1123);
+        assert_eq!(payload.quote_keeper_bounty(), 5364540);
+        assert_eq!(payload.base_liquidity, 6_000_000);
+        assert_eq!(payload.quote_liquidity, 1_500_000);
     }
 }
\ No newline at end of file
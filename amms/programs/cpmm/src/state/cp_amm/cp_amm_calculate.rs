@@ -1,5 +1,6 @@
 use anchor_lang::prelude::*;
-use crate::utils::math::Q64_128;
+use cpmm_math::cp_amm;
+use crate::utils::math::{strict_mul, Q64_128, SignedQ64_128};
 use crate::error::ErrorCode;
 use crate::state::cp_amm::CpAmmCore;
 
@@ -7,16 +8,20 @@ use crate::state::cp_amm::CpAmmCore;
 ///
 /// This trait defines methods and associated constants required for the operation of an AMM,
 /// such as calculating square roots of the constant product, liquidity ratios, and handling fees.
-/// The constants provide essential parameters like tolerance levels, initial liquidity, and fee structures.
+/// The pure calculations are implemented in `cpmm_math::cp_amm`, which off-chain quoting shares.
+/// The constants provide essential parameters like initial liquidity, while the tolerance levels
+/// are configured per `AmmsConfig` and passed to the validations. The pool math reaches the
+/// constant product specific calculations through `CurveCalculator`, so other curves can replace them.
+///
+/// Pools with decimals normalization scale their liquidity to a common virtual decimal basis before
+/// the constant product math, so their constant product and liquidity ratio are stored normalized.
 pub(crate) trait CpAmmCalculate: CpAmmCore {
-    /// The number of decimals for the LP token.
+    /// The number of decimals the initially locked LP tokens are capped at, also the LP mint
+    /// decimals of pools initialized before they were derived from the tradable mints.
     const LP_MINT_INITIAL_DECIMALS: u8 = 5;
 
-    /// The maximum allowable fee rate, expressed in basis points.
-    ///
-    /// - 1 basis point = 0.01%.
-    /// - `FEE_MAX_BASIS_POINTS = 10000` corresponds to a maximum fee rate of 100%.
-    const FEE_MAX_BASIS_POINTS: u128 = 10000;
+    /// The maximal number of decimals of the LP mint.
+    const MAX_LP_MINT_DECIMALS: u8 = 9;
 
     /// The initial amount of locked LP tokens in the pool.
     ///
@@ -24,35 +29,171 @@ pub(crate) trait CpAmmCalculate: CpAmmCore {
     /// - Example: If `LP_MINT_INITIAL_DECIMALS = 5`, then `INITIAL_LOCKED_LP_TOKENS = 100000`.
     const INITIAL_LOCKED_LP_TOKENS: u64 = 10_u64.pow(Self::LP_MINT_INITIAL_DECIMALS as u32);
 
-    /// The tolerance for changes in the constant product square root during swaps.
+    /// The maximal power of ten the liquidity of one side is scaled by to normalize the mints decimals.
+    ///
+    /// The scaled liquidity has to fit into `u64`, so larger exponents would leave too little room for the liquidity itself.
+    const MAX_NORMALIZATION_EXPONENT: u8 = 9;
+
+    /// Calculates the powers of ten scaling the base and quote liquidity to a common virtual decimal basis.
+    ///
+    /// The liquidity of the mint with fewer decimals is scaled up to the decimals of the other mint,
+    /// by at most `MAX_NORMALIZATION_EXPONENT`, so the constant product, the liquidity ratio and
+    /// the LP tokens supply keep the precision of the more precise mint.
+    ///
+    /// # Parameters
+    /// - `base_decimals`: The decimals of the base mint.
+    /// - `quote_decimals`: The decimals of the quote mint.
+    ///
+    /// # Returns
+    /// - The `(u8, u8)` base and quote normalization exponents, at least one of them zero.
+    fn calculate_normalization_exponents(base_decimals: u8, quote_decimals: u8) -> (u8, u8) {
+        let exponent = base_decimals.abs_diff(quote_decimals).min(Self::MAX_NORMALIZATION_EXPONENT);
+        if base_decimals < quote_decimals {
+            (exponent, 0)
+        } else {
+            (0, exponent)
+        }
+    }
+
+    /// Scales the base and quote liquidity to the pool's virtual decimal basis.
+    ///
+    /// # Parameters
+    /// - `base_liquidity`: The base liquidity.
+    /// - `quote_liquidity`: The quote liquidity.
+    ///
+    /// # Returns
+    /// - `Some((u64, u64))` with the normalized base and quote liquidity.
+    /// - `None` if the normalized liquidity overflows.
+    #[inline]
+    fn normalize_liquidity(&self, base_liquidity: u64, quote_liquidity: u64) -> Option<(u64, u64)> {
+        Some((base_liquidity.checked_mul(self.base_liquidity_scale())?, quote_liquidity.checked_mul(self.quote_liquidity_scale())?))
+    }
+
+    /// Scales normalized liquidity back to the mint decimals, rounding to the nearest unit.
+    ///
+    /// # Parameters
+    /// - `normalized_liquidity`: The liquidity in the pool's virtual decimal basis.
+    /// - `scale`: The factor the liquidity was normalized with.
+    ///
+    /// # Returns
+    /// - The `u64` liquidity in the mint decimals.
+    #[inline]
+    fn denormalize_rounded(normalized_liquidity: u64, scale: u64) -> u64 {
+        ((normalized_liquidity as u128 + scale as u128 / 2) / scale as u128) as u64
+    }
+
+    /// Calculates the square root of the constant product of the liquidity normalized to the pool's virtual decimal basis.
+    ///
+    /// # Parameters
+    /// - `base_liquidity`: The base liquidity.
+    /// - `quote_liquidity`: The quote liquidity.
+    ///
+    /// # Returns
+    /// - `Some(Q64_128)` with the square root of the normalized constant product.
+    /// - `None` if the normalized liquidity overflows or the product is zero.
+    #[inline]
+    fn calculate_normalized_constant_product_sqrt(&self, base_liquidity: u64, quote_liquidity: u64) -> Option<Q64_128> {
+        let (base_liquidity, quote_liquidity) = self.normalize_liquidity(base_liquidity, quote_liquidity)?;
+        Self::calculate_constant_product_sqrt(base_liquidity, quote_liquidity)
+    }
+
+    /// Calculates the base-to-quote ratio square root of the liquidity normalized to the pool's virtual decimal basis.
+    ///
+    /// # Parameters
+    /// - `base_liquidity`: The base liquidity.
+    /// - `quote_liquidity`: The quote liquidity.
+    ///
+    /// # Returns
+    /// - `Some(Q64_128)` with the normalized ratio square root.
+    /// - `None` if the normalized liquidity overflows or the ratio is zero.
+    #[inline]
+    fn calculate_normalized_base_quote_ratio_sqrt(&self, base_liquidity: u64, quote_liquidity: u64) -> Option<Q64_128> {
+        let (base_liquidity, quote_liquidity) = self.normalize_liquidity(base_liquidity, quote_liquidity)?;
+        Self::calculate_base_quote_ratio_sqrt(base_liquidity, quote_liquidity)
+    }
+
+    /// Calculates the decimals of the LP mint for the given mints decimals.
+    ///
+    /// The LP tokens supply is the geometric mean of the provided liquidity, so one whole token
+    /// of each mint corresponds to `10^((base_decimals + quote_decimals) / 2)` LP tokens.
+    /// Using that exponent as the LP decimals makes one whole LP token worth about one whole token
+    /// of each mint, it is capped by `MAX_LP_MINT_DECIMALS`.
+    ///
+    /// # Parameters
+    /// - `base_decimals`: The decimals of the base mint.
+    /// - `quote_decimals`: The decimals of the quote mint.
+    ///
+    /// # Returns
+    /// - The `u8` decimals of the LP mint.
+    fn calculate_lp_mint_decimals(base_decimals: u8, quote_decimals: u8) -> u8 {
+        let decimals = (base_decimals as u16 + quote_decimals as u16) / 2;
+        decimals.min(Self::MAX_LP_MINT_DECIMALS as u16) as u8
+    }
+
+    /// Splits the liquidity between several pools proportionally to their weights.
+    ///
+    /// Each share is rounded down and the rounding remainder goes to the last pool,
+    /// so the shares always sum up to `liquidity`.
+    ///
+    /// # Parameters
+    /// - `liquidity`: The amount of liquidity to split.
+    /// - `weights`: The weights of the pools.
     ///
-    /// - This value is used to ensure the AMM adheres to the constant product rule with minimal deviation.
-    /// - Defined as a `Q64_128` value representing a tolerance of `0.0001%`.
-    const SWAP_CONSTANT_PRODUCT_SQRT_TOLERANCE: Q64_128 = Q64_128::from_bits(0, 340282366920938463463374607431768);
+    /// # Returns
+    /// - `Some(Vec<u64>)` with the liquidity share of each pool.
+    /// - `None` if there are no weights or they sum up to zero.
+    fn calculate_split_liquidity(liquidity: u64, weights: &[u16]) -> Option<Vec<u64>> {
+        let total_weight = weights.iter().map(|weight| *weight as u128).sum::<u128>();
+        if total_weight == 0 {
+            return None;
+        }
+        let mut shares: Vec<u64> = weights.iter()
+            .map(|weight| (liquidity as u128 * *weight as u128 / total_weight) as u64)
+            .collect();
+        let remainder = liquidity - shares.iter().sum::<u64>();
+        *shares.last_mut()? += remainder;
+        Some(shares)
+    }
 
-    /// The tolerance for liquidity ratio sqrt after adjusting.
+    /// Calculates the amount of LP tokens locked during pool launch for the given mints decimals.
+    ///
+    /// One whole token of each mint corresponds to `10^((base_decimals + quote_decimals) / 2)` LP tokens.
+    /// The locked amount is that value capped by one whole LP token and by `INITIAL_LOCKED_LP_TOKENS`,
+    /// which keeps common pairs unchanged while letting low-decimals pairs launch with reasonable liquidity.
+    /// It never falls below `MIN_LIQUIDITY`, so the locked share of low-decimals pools still guards
+    /// against the rounding of the LP tokens value.
     ///
-    /// - Similar to `SWAP_CONSTANT_PRODUCT_SQRT_TOLERANCE`, this constant defines the allowable deviation
-    ///   when recalculating the liquidity ratio of the pool.
-    /// - Defined as a `Q64_128` value representing a tolerance of `0.00001%`.
-    const ADJUST_LIQUIDITY_RATIO_SQRT_TOLERANCE: Q64_128 = Q64_128::from_bits(0, 34028236692093846346337460743176);
+    /// # Parameters
+    /// - `base_decimals`: The decimals of the base mint.
+    /// - `quote_decimals`: The decimals of the quote mint.
+    /// - `lp_mint_decimals`: The decimals of the LP mint.
+    ///
+    /// # Returns
+    /// - The `u64` amount of LP tokens to lock.
+    fn calculate_initial_locked_lp_tokens(base_decimals: u8, quote_decimals: u8, lp_mint_decimals: u8) -> u64 {
+        let decimals = ((base_decimals as u32 + quote_decimals as u32) / 2).min(lp_mint_decimals as u32);
+        if decimals >= Self::LP_MINT_INITIAL_DECIMALS as u32 {
+            return Self::INITIAL_LOCKED_LP_TOKENS;
+        }
+        10_u64.pow(decimals).max(cp_amm::MIN_LIQUIDITY)
+    }
 
     /// Calculates the initial LP token supply and locked liquidity during pool launch.
     ///
     /// # Parameters
     /// - `constant_product_sqrt`: The square root of the constant product for the pool.
+    /// - `initial_locked_liquidity`: The amount of LP tokens to lock.
     ///
     /// # Returns
     /// - `Ok((u64, u64))` with the initial LP token supply and locked liquidity.
     /// - `Err(ErrorCode)` if the supply is too small.
-    fn calculate_launch_lp_tokens(constant_product_sqrt: Q64_128) -> Result<(u64, u64)> {
+    fn calculate_launch_lp_tokens(constant_product_sqrt: Q64_128, initial_locked_liquidity: u64) -> Result<(u64, u64)> {
         let lp_tokens_supply = constant_product_sqrt.as_u64();
         require!(lp_tokens_supply > 0, ErrorCode::LpTokensCalculationFailed);
-        let initial_locked_liquidity = Self::INITIAL_LOCKED_LP_TOKENS;
         let difference = lp_tokens_supply
             .checked_sub(initial_locked_liquidity)
             .ok_or(ErrorCode::LaunchLiquidityTooSmall)?;
-        require!(difference >= initial_locked_liquidity * 3, ErrorCode::LaunchLiquidityTooSmall);
+        require!(difference >= strict_mul!(initial_locked_liquidity, 3), ErrorCode::LaunchLiquidityTooSmall);
         Ok((lp_tokens_supply, initial_locked_liquidity))
     }
     
@@ -65,18 +206,13 @@ pub(crate) trait CpAmmCalculate: CpAmmCore {
     /// - `Some(u64)` with the amount of LP tokens to mint if the calculation is valid.
     /// - `None` if the calculation fails (e.g., due to underflow or zero tokens).
     fn calculate_lp_mint_for_provided_liquidity(&self, new_constant_product_sqrt: Q64_128) -> Option<u64> {
-        let provided_liquidity = new_constant_product_sqrt.checked_sub(self.constant_product_sqrt())?;
-
-        let share_from_current_liquidity = provided_liquidity.checked_div(self.constant_product_sqrt())?;
-        let tokens_to_mint = share_from_current_liquidity.checked_mul(Q64_128::from_u64(self.lp_tokens_supply()))?.as_u64();
-        if tokens_to_mint == 0{
-            return None;
-        }
-        Some(tokens_to_mint)
+        cp_amm::calculate_lp_mint_for_provided_liquidity(self.constant_product_sqrt(), self.lp_tokens_supply(), new_constant_product_sqrt)
     }
     
     /// Calculates the amount of base and quote liquidity to withdraw for a given share of LP tokens.
     ///
+    /// The share is calculated in the pool's virtual decimal basis and rounded down to the mints decimals.
+    ///
     /// # Parameters
     /// - `lp_tokens`: The number of LP tokens being redeemed.
     ///
@@ -84,17 +220,28 @@ pub(crate) trait CpAmmCalculate: CpAmmCore {
     /// - `Some((u64, u64))` with the base and quote liquidity amounts.
     /// - `None` if the calculation fails (e.g., due to zero tokens).
     fn calculate_liquidity_from_share(&self, lp_tokens: u64) -> Option<(u64, u64)>{
-        let liquidity_share = Q64_128::from_u64(lp_tokens).checked_div(Q64_128::from_u64(self.lp_tokens_supply()))?;
-        let constant_product_sqrt_share = self.constant_product_sqrt().checked_mul(liquidity_share)?;
-        let base_withdraw = constant_product_sqrt_share.saturating_mul(self.base_quote_ratio_sqrt()).as_u64_round();
-        let quote_withdraw = constant_product_sqrt_share.saturating_checked_div(self.base_quote_ratio_sqrt())?.as_u64_round();
-        
-        if base_withdraw == 0 || quote_withdraw == 0{
+        let (base_withdraw, quote_withdraw) = cp_amm::calculate_liquidity_from_share(self.constant_product_sqrt(), self.base_quote_ratio_sqrt(), self.lp_tokens_supply(), lp_tokens)?;
+        let (base_withdraw, quote_withdraw) = (base_withdraw / self.base_liquidity_scale(), quote_withdraw / self.quote_liquidity_scale());
+        if base_withdraw == 0 || quote_withdraw == 0 {
             return None;
         }
         Some((base_withdraw, quote_withdraw))
     }
 
+    /// Calculates a withdrawal burning only the LP tokens its payout is worth.
+    ///
+    /// # Parameters
+    /// - `lp_tokens`: The LP tokens requested to be redeemed.
+    /// - `base_amount`: The base liquidity paid out for `lp_tokens`.
+    /// - `quote_amount`: The quote liquidity paid out for `lp_tokens`.
+    ///
+    /// # Returns
+    /// - `Some((u64, u64, u64))` with the LP tokens to burn and the base and quote liquidity to pay out.
+    /// - `None` if the calculation fails (e.g., due to zero tokens).
+    fn calculate_burn_exact_withdraw(&self, lp_tokens: u64, base_amount: u64, quote_amount: u64) -> Option<(u64, u64, u64)> {
+        cp_amm::calculate_burn_exact_withdraw(self.base_liquidity(), self.quote_liquidity(), self.lp_tokens_supply(), lp_tokens, base_amount, quote_amount)
+    }
+
     /// Calculates the new base and quote liquidity after a swap.
     ///
     /// # Parameters
@@ -105,17 +252,12 @@ pub(crate) trait CpAmmCalculate: CpAmmCore {
     /// - `Some((u64, u64))` with the new base and quote liquidity values.
     /// - `None` if the calculation fails.
     fn calculate_afterswap_liquidity(&self, swap_amount: u64, is_in_out: bool) -> Option<(u64, u64)>{
-        let new_base_liquidity;
-        let new_quote_liquidity;
-        if is_in_out {
-            new_base_liquidity = self.base_liquidity().checked_add(swap_amount)?;
-            new_quote_liquidity = self.calculate_opposite_liquidity(new_base_liquidity)?;
-        }
-        else{
-            new_quote_liquidity = self.quote_liquidity().checked_add(swap_amount)?;
-            new_base_liquidity = self.calculate_opposite_liquidity(new_quote_liquidity)?;
-        }
-        Some((new_base_liquidity, new_quote_liquidity))
+        let (base_scale, quote_scale) = (self.base_liquidity_scale(), self.quote_liquidity_scale());
+        let (base_liquidity, quote_liquidity) = self.normalize_liquidity(self.base_liquidity(), self.quote_liquidity())?;
+        let swap_amount = swap_amount.checked_mul(if is_in_out { base_scale } else { quote_scale })?;
+        let (new_base_liquidity, new_quote_liquidity) = cp_amm::calculate_afterswap_liquidity(self.constant_product_sqrt(), base_liquidity, quote_liquidity, swap_amount, is_in_out)?;
+        // The swapped in side stays a multiple of its scale, the opposite side is rounded to the nearest unit as without normalization
+        Some((Self::denormalize_rounded(new_base_liquidity, base_scale), Self::denormalize_rounded(new_quote_liquidity, quote_scale)))
     }
 
     /// Validates and calculates the new liquidity ratio after liquidity change.
@@ -123,15 +265,24 @@ pub(crate) trait CpAmmCalculate: CpAmmCore {
     /// # Parameters
     /// - `new_base_liquidity`: The new base liquidity.
     /// - `new_quote_liquidity`: The new quote liquidity.
+    /// - `tolerance`: The allowed relative deviation of the ratio square root.
     ///
     /// # Returns
     /// - `Ok(Q64_128)` with the new base-to-quote ratio square root.
-    /// - `Err(ErrorCode)` if the new ratio exceeds the allowed tolerance.
-    fn validate_and_calculate_liquidity_ratio(&self, new_base_liquidity: u64, new_quote_liquidity: u64) -> Result<Q64_128>{
-        let new_base_quote_ratio_sqrt = Self::calculate_base_quote_ratio_sqrt(new_base_liquidity, new_quote_liquidity).ok_or(ErrorCode::BaseQuoteRatioCalculationFailed)?;
-        let difference = self.base_quote_ratio_sqrt().abs_diff(new_base_quote_ratio_sqrt);
-        let allowed_difference = self.base_quote_ratio_sqrt() * Self::ADJUST_LIQUIDITY_RATIO_SQRT_TOLERANCE;
-        require!(difference <= allowed_difference, ErrorCode::LiquidityRatioToleranceExceeded);
+    /// - `Err(ErrorCode::LiquidityRatioAboveTolerance)` if the new ratio exceeds the allowed tolerance with too much base liquidity.
+    /// - `Err(ErrorCode::LiquidityRatioBelowTolerance)` if the new ratio exceeds the allowed tolerance with too much quote liquidity.
+    fn validate_and_calculate_liquidity_ratio(&self, new_base_liquidity: u64, new_quote_liquidity: u64, tolerance: Q64_128) -> Result<Q64_128>{
+        let new_base_quote_ratio_sqrt = self.calculate_normalized_base_quote_ratio_sqrt(new_base_liquidity, new_quote_liquidity).ok_or(ErrorCode::BaseQuoteRatioCalculationFailed)?;
+        let ratio_sqrt_delta = SignedQ64_128::delta(self.base_quote_ratio_sqrt(), new_base_quote_ratio_sqrt);
+        let allowed_difference = strict_mul!(self.base_quote_ratio_sqrt(), tolerance);
+        if !ratio_sqrt_delta.is_within(allowed_difference) {
+            Self::log_tolerance_breach("Liquidity ratio sqrt", ratio_sqrt_delta, allowed_difference, self.base_quote_ratio_sqrt());
+            return if ratio_sqrt_delta.is_negative() {
+                err!(ErrorCode::LiquidityRatioBelowTolerance)
+            } else {
+                err!(ErrorCode::LiquidityRatioAboveTolerance)
+            };
+        }
         Ok(new_base_quote_ratio_sqrt)
     }
 
@@ -140,15 +291,66 @@ pub(crate) trait CpAmmCalculate: CpAmmCore {
     /// # Parameters
     /// - `new_base_liquidity`: The new base liquidity.
     /// - `new_quote_liquidity`: The new quote liquidity.
+    /// - `tolerance`: The allowed relative deviation of the constant product square root.
     ///
     /// # Returns
     /// - `Ok(())` if the constant product remains within tolerance.
     /// - `Err(ErrorCode)` if the product exceeds the allowed tolerance.
-    fn validate_swap_constant_product(&self, new_base_liquidity: u64, new_quote_liquidity: u64) -> Result<()>{
-        let new_constant_product_sqrt = Self::calculate_constant_product_sqrt(new_base_liquidity, new_quote_liquidity).ok_or(ErrorCode::ConstantProductCalculationFailed)?;
-        let difference = self.constant_product_sqrt().abs_diff(new_constant_product_sqrt);
-        let allowed_difference = self.constant_product_sqrt() * Self::SWAP_CONSTANT_PRODUCT_SQRT_TOLERANCE;
-        require!(difference <= allowed_difference, ErrorCode::ConstantProductToleranceExceeded);
+    fn validate_swap_constant_product(&self, new_base_liquidity: u64, new_quote_liquidity: u64, tolerance: Q64_128) -> Result<()>{
+        let new_constant_product_sqrt = self.calculate_normalized_constant_product_sqrt(new_base_liquidity, new_quote_liquidity).ok_or(ErrorCode::ConstantProductCalculationFailed)?;
+        let constant_product_sqrt_delta = SignedQ64_128::delta(self.constant_product_sqrt(), new_constant_product_sqrt);
+        let allowed_difference = strict_mul!(self.constant_product_sqrt(), tolerance);
+        if !constant_product_sqrt_delta.is_within(allowed_difference) {
+            Self::log_tolerance_breach("Constant product sqrt", constant_product_sqrt_delta, allowed_difference, self.constant_product_sqrt());
+            return err!(ErrorCode::ConstantProductToleranceExceeded);
+        }
+        Ok(())
+    }
+
+    /// Logs the computed and the allowed difference of a breached tolerance, so integrators can tell
+    /// how far off their amounts were.
+    ///
+    /// The differences are logged as hexadecimal Q64.128 values and approximately in basis points
+    /// of the reference value.
+    ///
+    /// # Parameters
+    /// - `name`: The name of the validated value.
+    /// - `delta`: The computed change of the value.
+    /// - `allowed_difference`: The maximal allowed absolute change of the value.
+    /// - `reference`: The value before the change.
+    #[cold]
+    fn log_tolerance_breach(name: &str, delta: SignedQ64_128, allowed_difference: Q64_128, reference: Q64_128) {
+        let to_ppb = |difference: Q64_128| difference.checked_div(reference)
+            .and_then(|share| share.checked_mul(Q64_128::from_u64(1_000_000_000)))
+            .map_or(u64::MAX, |ppb| ppb.as_u64_round());
+        let (delta_ppb, allowed_ppb) = (to_ppb(delta.magnitude()), to_ppb(allowed_difference));
+        let sign = if delta.is_negative() { "-" } else { "+" };
+        msg!(
+            "{} tolerance exceeded: delta {}{:#x}.{:032x} (~{}{}.{:05} bps), allowed {:#x}.{:032x} (~{}.{:05} bps)",
+            name,
+            sign, delta.magnitude().get_integer_bits(), delta.magnitude().get_fractional_bits(),
+            sign, delta_ppb / 100_000, delta_ppb % 100_000,
+            allowed_difference.get_integer_bits(), allowed_difference.get_fractional_bits(),
+            allowed_ppb / 100_000, allowed_ppb % 100_000
+        );
+    }
+
+    /// Validates a base-to-quote ratio square root against the one expected by the caller.
+    ///
+    /// # Parameters
+    /// - `base_quote_ratio_sqrt`: The calculated base-to-quote ratio square root.
+    /// - `expected_base_quote_ratio_sqrt`: The base-to-quote ratio square root expected by the caller.
+    /// - `tolerance_basis_points`: The allowed relative deviation of the ratio square root, in basis points.
+    ///
+    /// # Returns
+    /// - `Ok(())` if the ratio is within the tolerance.
+    /// - `Err(ErrorCode::LaunchRatioToleranceExceeded)` otherwise.
+    #[inline]
+    fn validate_expected_base_quote_ratio_sqrt(base_quote_ratio_sqrt: Q64_128, expected_base_quote_ratio_sqrt: Q64_128, tolerance_basis_points: u16) -> Result<()> {
+        let tolerance = Q64_128::from_u64(tolerance_basis_points as u64).checked_div(Q64_128::from_u64(10000)).unwrap();
+        let is_within_tolerance = cp_amm::is_within_tolerance(expected_base_quote_ratio_sqrt, base_quote_ratio_sqrt, tolerance)
+            .ok_or(ErrorCode::BaseQuoteRatioCalculationFailed)?;
+        require!(is_within_tolerance, ErrorCode::LaunchRatioToleranceExceeded);
         Ok(())
     }
 
@@ -177,14 +379,9 @@ pub(crate) trait CpAmmCalculate: CpAmmCore {
     /// # Returns
     /// - `Some(u64)` with the opposite liquidity value.
     /// - `None` if the result is zero.
-    #[inline]
+    #[cfg(test)]
     fn calculate_opposite_liquidity(&self, x_liquidity: u64) -> Option<u64> {
-        let constant_product = self.constant_product_sqrt().square_as_u128();
-        let opposite_liquidity = (Q64_128::from_u128(constant_product) / Q64_128::from_u128(x_liquidity as u128)).as_u64_round();
-        if opposite_liquidity == 0 {
-            return None;
-        }
-        Some(opposite_liquidity)
+        cp_amm::calculate_opposite_liquidity(self.constant_product_sqrt(), x_liquidity)
     }
 
     /// Calculates the fee for a given swap amount based on the provided fee rate.
@@ -197,7 +394,21 @@ pub(crate) trait CpAmmCalculate: CpAmmCore {
     /// - A `u64` representing the **calculated fee amount**.
     #[inline]
     fn calculate_fee_amount(swap_amount: u64, fee_basis_points: u16) -> u64 {
-        ((swap_amount as u128) * (fee_basis_points as u128) / Self::FEE_MAX_BASIS_POINTS) as u64
+        cp_amm::calculate_fee_amount(swap_amount, fee_basis_points)
+    }
+
+    /// Calculates the swap fee for a given swap amount based on the provided fee rate, rounded up
+    /// in favor of the pool.
+    ///
+    /// # Parameters
+    /// - `swap_amount`: The amount of tokens being swapped.
+    /// - `fee_basis_points`: The fee rate expressed in **basis points** (1 basis point = 0.01%).
+    ///
+    /// # Returns
+    /// - A `u64` representing the **calculated fee amount**.
+    #[inline]
+    fn calculate_swap_fee_amount(swap_amount: u64, fee_basis_points: u16) -> u64 {
+        cp_amm::calculate_swap_fee_amount(swap_amount, fee_basis_points)
     }
     
     /// Calculates the base-to-quote liquidity ratio square root.
@@ -211,11 +422,52 @@ pub(crate) trait CpAmmCalculate: CpAmmCore {
     /// - `None` if the ratio is zero.
     #[inline]
     fn calculate_base_quote_ratio_sqrt(base_liquidity: u64, quote_liquidity: u64) -> Option<Q64_128> {
-        let ratio = Q64_128::checked_div_sqrt(Q64_128::from_u64(base_liquidity), Q64_128::from_u64(quote_liquidity))?;
-        if ratio.is_zero() {
-            return None;
-        }
-        Some(ratio)
+        cp_amm::calculate_base_quote_ratio_sqrt(base_liquidity, quote_liquidity)
+    }
+
+    /// Calculates the price impact of moving the pool to the new liquidity.
+    ///
+    /// The impact is the relative change of the base-to-quote liquidity ratio.
+    ///
+    /// # Parameters
+    /// - `new_base_liquidity`: The base liquidity after the operation.
+    /// - `new_quote_liquidity`: The quote liquidity after the operation.
+    ///
+    /// # Returns
+    /// - `Some(u64)` with the price impact in basis points.
+    /// - `None` if the new ratio can't be calculated.
+    #[inline]
+    fn calculate_price_impact_basis_points(&self, new_base_liquidity: u64, new_quote_liquidity: u64) -> Option<u64> {
+        let (new_base_liquidity, new_quote_liquidity) = self.normalize_liquidity(new_base_liquidity, new_quote_liquidity)?;
+        cp_amm::calculate_price_impact_basis_points(self.base_quote_ratio_sqrt(), new_base_liquidity, new_quote_liquidity)
+    }
+
+    /// Calculates the price impact of moving the pool to an already calculated normalized liquidity ratio.
+    ///
+    /// # Parameters
+    /// - `new_base_quote_ratio_sqrt`: The normalized base-to-quote ratio square root after the operation.
+    ///
+    /// # Returns
+    /// - `Some(u64)` with the price impact in basis points.
+    /// - `None` if the current ratio is zero.
+    #[inline]
+    fn calculate_price_impact_from_ratio_sqrt(&self, new_base_quote_ratio_sqrt: Q64_128) -> Option<u64> {
+        cp_amm::calculate_price_impact_from_ratio_sqrt(self.base_quote_ratio_sqrt(), new_base_quote_ratio_sqrt)
+    }
+
+    /// Derives the normalized constant product square root from the normalized liquidity ratio square root,
+    /// sparing the square root of `calculate_normalized_constant_product_sqrt`.
+    ///
+    /// # Parameters
+    /// - `base_quote_ratio_sqrt`: The normalized base-to-quote ratio square root.
+    /// - `quote_liquidity`: The quote liquidity.
+    ///
+    /// # Returns
+    /// - `Some(Q64_128)` with the normalized constant product square root.
+    /// - `None` if the normalized liquidity overflows or the product is zero.
+    #[inline]
+    fn calculate_normalized_constant_product_sqrt_from_ratio_sqrt(&self, base_quote_ratio_sqrt: Q64_128, quote_liquidity: u64) -> Option<Q64_128> {
+        cp_amm::calculate_constant_product_sqrt_from_ratio_sqrt(base_quote_ratio_sqrt, quote_liquidity.checked_mul(self.quote_liquidity_scale())?)
     }
 
     /// Calculates the square root of the constant product.
@@ -229,11 +481,7 @@ pub(crate) trait CpAmmCalculate: CpAmmCore {
     /// - `None` if the product is zero.
     #[inline]
     fn calculate_constant_product_sqrt(base_liquidity: u64, quote_liquidity: u64) -> Option<Q64_128> {
-        let constant_product_sqrt = Q64_128::sqrt_from_u128(base_liquidity as u128 * quote_liquidity as u128);
-        if constant_product_sqrt.is_zero() {
-            return None;
-        }
-        Some(constant_product_sqrt)
+        cp_amm::calculate_constant_product_sqrt(base_liquidity, quote_liquidity)
     }
 }
 
@@ -241,6 +489,8 @@ impl<T: CpAmmCore> CpAmmCalculate for T{}
 
 #[cfg(test)]
 mod tests {
+    use cpmm_math::cp_amm;
+    use crate::error::ErrorCode;
     use crate::state::cp_amm::{CpAmmCore, CpAmmCalculate};
     use crate::utils::math::Q64_128;
 
@@ -251,6 +501,8 @@ mod tests {
         constant_product_sqrt: Q64_128,
         base_quote_ratio_sqrt: Q64_128,
         lp_tokens_supply: u64,
+        base_liquidity_scale: u64,
+        quote_liquidity_scale: u64,
     }
     
     impl TestCpAmm{
@@ -259,7 +511,7 @@ mod tests {
         /// Returns `None` if any calculation fails.
         fn try_new(base_liquidity: u64, quote_liquidity: u64) -> Option<Self>{
             let constant_product_sqrt = TestCpAmm::calculate_constant_product_sqrt(base_liquidity, quote_liquidity)?;
-            let lp_tokens_supply = TestCpAmm::calculate_launch_lp_tokens(constant_product_sqrt).ok()?;
+            let lp_tokens_supply = TestCpAmm::calculate_launch_lp_tokens(constant_product_sqrt, TestCpAmm::INITIAL_LOCKED_LP_TOKENS).ok()?;
             let base_quote_ratio = TestCpAmm::calculate_base_quote_ratio_sqrt(base_liquidity, quote_liquidity)?;
             
             Some(
@@ -268,10 +520,26 @@ mod tests {
                     quote_liquidity,
                     constant_product_sqrt,
                     base_quote_ratio_sqrt: base_quote_ratio,
-                    lp_tokens_supply: lp_tokens_supply.0
+                    lp_tokens_supply: lp_tokens_supply.0,
+                    base_liquidity_scale: 1,
+                    quote_liquidity_scale: 1,
                 }
             )
         }
+
+        /// Creates a new instance of `TestCpAmm` with decimals normalization for the given mints decimals.
+        ///
+        /// Returns `None` if any calculation fails.
+        fn try_new_normalized(base_liquidity: u64, quote_liquidity: u64, base_decimals: u8, quote_decimals: u8) -> Option<Self>{
+            let (base_exponent, quote_exponent) = TestCpAmm::calculate_normalization_exponents(base_decimals, quote_decimals);
+            let mut amm = TestCpAmm::try_new(base_liquidity, quote_liquidity)?;
+            amm.base_liquidity_scale = 10_u64.pow(base_exponent as u32);
+            amm.quote_liquidity_scale = 10_u64.pow(quote_exponent as u32);
+            amm.constant_product_sqrt = amm.calculate_normalized_constant_product_sqrt(base_liquidity, quote_liquidity)?;
+            amm.base_quote_ratio_sqrt = amm.calculate_normalized_base_quote_ratio_sqrt(base_liquidity, quote_liquidity)?;
+            amm.lp_tokens_supply = TestCpAmm::calculate_launch_lp_tokens(amm.constant_product_sqrt, TestCpAmm::INITIAL_LOCKED_LP_TOKENS).ok()?.0;
+            Some(amm)
+        }
     }
     impl CpAmmCore for TestCpAmm {
         fn constant_product_sqrt(&self) -> Q64_128 {
@@ -293,11 +561,35 @@ mod tests {
         fn lp_tokens_supply(&self) -> u64 {
             self.lp_tokens_supply
         }
+
+        fn base_liquidity_scale(&self) -> u64 {
+            self.base_liquidity_scale
+        }
+
+        fn quote_liquidity_scale(&self) -> u64 {
+            self.quote_liquidity_scale
+        }
     }
     
     /// Unit tests for the `TestCpAmm` implementation.
     mod unit_tests {
         use super::*;
+
+        /// Tests that the tolerance constants are the truncated Q64.128 representations
+        /// of their documented rational values: `0.0001% = 1/10^6` and `0.00001% = 1/10^7`.
+        #[test]
+        fn test_tolerance_constants_specification() {
+            // floor(2^128 / d) == floor((2^128 - 1) / d) for any `d` that is not a power of two
+            let tolerances = [
+                (cp_amm::SWAP_CONSTANT_PRODUCT_SQRT_TOLERANCE, 1_000_000u128),
+                (cp_amm::ADJUST_LIQUIDITY_RATIO_SQRT_TOLERANCE, 10_000_000u128),
+            ];
+            for (tolerance, denominator) in tolerances {
+                assert_eq!(tolerance.get_integer_bits(), 0);
+                assert_eq!(tolerance.get_fractional_bits(), u128::MAX / denominator);
+                assert_eq!(tolerance, Q64_128::ONE.checked_div(Q64_128::from_u64(denominator as u64)).unwrap());
+            }
+        }
         
         /// Tests `calculate_base_quote_ratio_sqrt` with extreme values.
         #[test]
@@ -363,6 +655,19 @@ mod tests {
             );
         }
         
+        /// Tests `calculate_price_impact_basis_points` for both swap directions.
+        #[test]
+        fn test_calculate_price_impact_basis_points() {
+            let amm = TestCpAmm::try_new(4_000_000, 1_000_000).unwrap();
+
+            assert_eq!(amm.calculate_price_impact_basis_points(4_000_000, 1_000_000), Some(0));
+            // Ratio 4.0 -> 5.0
+            assert_eq!(amm.calculate_price_impact_basis_points(5_000_000, 1_000_000), Some(2500));
+            // Ratio 4.0 -> 3.2
+            assert_eq!(amm.calculate_price_impact_basis_points(4_000_000, 1_250_000), Some(2000));
+            assert_eq!(amm.calculate_price_impact_basis_points(0, 1_000_000), None);
+        }
+
         /// Tests `calculate_constant_product_sqrt` with extreme values.
         #[test]
         fn test_calculate_constant_product_sqrt_extreme() {
@@ -416,13 +721,77 @@ mod tests {
             );
         }
 
+        /// Tests `calculate_lp_mint_decimals` across the mints decimals spectrum.
+        #[test]
+        fn test_calculate_lp_mint_decimals() {
+            let cases: [(u8, u8, u8); 7] = [
+                (0, 0, 0),
+                (0, 1, 0),
+                (6, 3, 4),
+                (6, 9, 7),
+                (9, 9, 9),
+                (18, 6, 9),
+                (u8::MAX, u8::MAX, TestCpAmm::MAX_LP_MINT_DECIMALS),
+            ];
+            for (base_decimals, quote_decimals, expected_lp_mint_decimals) in cases {
+                assert_eq!(
+                    TestCpAmm::calculate_lp_mint_decimals(base_decimals, quote_decimals),
+                    expected_lp_mint_decimals,
+                    "LP mint decimals mismatch for decimals {} and {}",
+                    base_decimals,
+                    quote_decimals
+                );
+            }
+        }
+
+        /// Tests `calculate_initial_locked_lp_tokens` across the mints decimals spectrum.
+        #[test]
+        fn test_calculate_initial_locked_lp_tokens() {
+            let cases: [(u8, u8, u64); 8] = [
+                (0, 0, cp_amm::MIN_LIQUIDITY),
+                (0, 1, cp_amm::MIN_LIQUIDITY),
+                (1, 1, cp_amm::MIN_LIQUIDITY),
+                (0, 6, cp_amm::MIN_LIQUIDITY),
+                (0, 9, 10_000),
+                (6, 6, TestCpAmm::INITIAL_LOCKED_LP_TOKENS),
+                (9, 18, TestCpAmm::INITIAL_LOCKED_LP_TOKENS),
+                (u8::MAX, u8::MAX, TestCpAmm::INITIAL_LOCKED_LP_TOKENS),
+            ];
+            for (base_decimals, quote_decimals, expected_initial_locked_liquidity) in cases {
+                let lp_mint_decimals = TestCpAmm::calculate_lp_mint_decimals(base_decimals, quote_decimals);
+                assert_eq!(
+                    TestCpAmm::calculate_initial_locked_lp_tokens(base_decimals, quote_decimals, lp_mint_decimals),
+                    expected_initial_locked_liquidity,
+                    "Initial locked liquidity mismatch for decimals {} and {}",
+                    base_decimals,
+                    quote_decimals
+                );
+            }
+
+            // Pools initialized before the derivation keep the initial LP decimals
+            assert_eq!(TestCpAmm::calculate_initial_locked_lp_tokens(0, 0, TestCpAmm::LP_MINT_INITIAL_DECIMALS), cp_amm::MIN_LIQUIDITY);
+            assert_eq!(TestCpAmm::calculate_initial_locked_lp_tokens(9, 9, TestCpAmm::LP_MINT_INITIAL_DECIMALS), TestCpAmm::INITIAL_LOCKED_LP_TOKENS);
+            // The locked amount never exceeds one whole LP token above the `cp_amm::MIN_LIQUIDITY` floor
+            assert_eq!(TestCpAmm::calculate_initial_locked_lp_tokens(6, 6, 4), 10_000);
+            assert_eq!(TestCpAmm::calculate_initial_locked_lp_tokens(6, 6, 0), cp_amm::MIN_LIQUIDITY);
+
+            // A 0/0-decimals pool locks `cp_amm::MIN_LIQUIDITY` instead of a single LP token
+            let (lp_tokens_supply, initial_locked_liquidity) = TestCpAmm::calculate_launch_lp_tokens(
+                Q64_128::from_u64(4 * cp_amm::MIN_LIQUIDITY),
+                TestCpAmm::calculate_initial_locked_lp_tokens(0, 0, 0)
+            ).unwrap();
+            assert_eq!(lp_tokens_supply, 4 * cp_amm::MIN_LIQUIDITY);
+            assert_eq!(initial_locked_liquidity, cp_amm::MIN_LIQUIDITY);
+            assert!(TestCpAmm::calculate_launch_lp_tokens(Q64_128::from_u64(4 * cp_amm::MIN_LIQUIDITY - 1), cp_amm::MIN_LIQUIDITY).is_err());
+        }
+
         /// Tests `calculate_launch_lp_tokens` for expected behavior.
         #[test]
         fn test_calculate_launch_lp_tokens() {
             let constant_product_sqrt = Q64_128::from_u64(543654623489);
 
             let (lp_tokens_supply, initial_locked_liquidity) =
-                TestCpAmm::calculate_launch_lp_tokens(constant_product_sqrt).unwrap();
+                TestCpAmm::calculate_launch_lp_tokens(constant_product_sqrt, TestCpAmm::INITIAL_LOCKED_LP_TOKENS).unwrap();
 
             let expected_lp_tokens_supply = constant_product_sqrt.as_u64();
             let expected_initial_locked_liquidity = 10_u64.pow(TestCpAmm::LP_MINT_INITIAL_DECIMALS as u32);
@@ -503,6 +872,105 @@ mod tests {
             );
         }
 
+        /// Tests `calculate_normalization_exponents` across the mints decimals spectrum.
+        #[test]
+        fn test_calculate_normalization_exponents() {
+            assert_eq!(TestCpAmm::calculate_normalization_exponents(0, 9), (9, 0));
+            assert_eq!(TestCpAmm::calculate_normalization_exponents(9, 0), (0, 9));
+            assert_eq!(TestCpAmm::calculate_normalization_exponents(6, 9), (3, 0));
+            assert_eq!(TestCpAmm::calculate_normalization_exponents(6, 6), (0, 0));
+            // The scale is capped to leave room for the liquidity itself
+            assert_eq!(TestCpAmm::calculate_normalization_exponents(0, 18), (9, 0));
+            assert_eq!(TestCpAmm::calculate_normalization_exponents(18, 2), (0, 9));
+        }
+
+        /// Tests that decimals normalization improves the precision of the LP mint for a 0 vs 9 decimals pair.
+        #[test]
+        fn test_normalized_lp_mint_precision_at_decimal_extremes() {
+            // 1000 tokens with 0 decimals against 3000 tokens with 9 decimals
+            let (base_liquidity, quote_liquidity) = (1_000, 3_000_000_000_000);
+            let raw_amm = TestCpAmm::try_new(base_liquidity, quote_liquidity).unwrap();
+            let normalized_amm = TestCpAmm::try_new_normalized(base_liquidity, quote_liquidity, 0, 9).unwrap();
+            assert_eq!(raw_amm.lp_tokens_supply, 54_772_255);
+            assert_eq!(normalized_amm.lp_tokens_supply, 1_732_050_807_568);
+
+            // Providing 0.1% of the pool should mint 0.1% of the LP tokens supply
+            let (new_base_liquidity, new_quote_liquidity) = (base_liquidity + 1, quote_liquidity + 3_000_000_000);
+            let relative_error = |amm: &TestCpAmm| {
+                let new_constant_product_sqrt = amm.calculate_normalized_constant_product_sqrt(new_base_liquidity, new_quote_liquidity).unwrap();
+                let minted_tokens = amm.calculate_lp_mint_for_provided_liquidity(new_constant_product_sqrt).unwrap();
+                let expected_minted = amm.lp_tokens_supply as f64 / 1_000.0;
+                (expected_minted - minted_tokens as f64).abs() / expected_minted
+            };
+            let raw_error = relative_error(&raw_amm);
+            let normalized_error = relative_error(&normalized_amm);
+
+            assert!(raw_error > 1e-6, "Raw LP mint error is expected at the decimal extremes, got: {}", raw_error);
+            assert!(normalized_error < 1e-9, "Normalized LP mint error is too large: {}", normalized_error);
+        }
+
+        /// Tests that decimals normalization improves the precision of withdrawals for a 0 vs 9 decimals pair.
+        #[test]
+        fn test_normalized_withdraw_precision_at_decimal_extremes() {
+            let (base_liquidity, quote_liquidity) = (1_000, 3_000_000_000_000);
+            let raw_amm = TestCpAmm::try_new(base_liquidity, quote_liquidity).unwrap();
+            let normalized_amm = TestCpAmm::try_new_normalized(base_liquidity, quote_liquidity, 0, 9).unwrap();
+
+            // Withdrawing the LP tokens worth 0.1% of the pool should return 1 base and 3 quote tokens
+            let quote_loss = |amm: &TestCpAmm| {
+                let (base_withdraw, quote_withdraw) = amm.calculate_liquidity_from_share(amm.lp_tokens_supply / 1_000).unwrap();
+                assert_eq!(base_withdraw, 1);
+                3_000_000_000 - quote_withdraw
+            };
+            let raw_loss = quote_loss(&raw_amm);
+            let normalized_loss = quote_loss(&normalized_amm);
+
+            assert!(raw_loss > 10_000, "Raw withdraw loss is expected at the decimal extremes, got: {}", raw_loss);
+            assert!(normalized_loss <= 2, "Normalized withdraw loss is too large: {}", normalized_loss);
+
+            // Normalized withdrawals never pay out more than the share of the pool
+            let (base_withdraw, quote_withdraw) = normalized_amm.calculate_liquidity_from_share(normalized_amm.lp_tokens_supply / 3).unwrap();
+            assert_eq!(base_withdraw, 333);
+            assert!(quote_withdraw <= quote_liquidity / 3);
+        }
+
+        /// Tests that swaps of a normalized pool match the swaps of a pool without normalization.
+        #[test]
+        fn test_normalized_afterswap_liquidity() {
+            let (base_liquidity, quote_liquidity) = (1_000, 3_000_000_000_000);
+            let raw_amm = TestCpAmm::try_new(base_liquidity, quote_liquidity).unwrap();
+            let normalized_amm = TestCpAmm::try_new_normalized(base_liquidity, quote_liquidity, 0, 9).unwrap();
+
+            for (swap_amount, is_in_out) in [(10, true), (3_000_000_000, false), (29_999_999_999, false)] {
+                let (raw_base, raw_quote) = raw_amm.calculate_afterswap_liquidity(swap_amount, is_in_out).unwrap();
+                let (normalized_base, normalized_quote) = normalized_amm.calculate_afterswap_liquidity(swap_amount, is_in_out).unwrap();
+                assert_eq!((raw_base, raw_quote), (normalized_base, normalized_quote));
+                assert_eq!(
+                    raw_amm.validate_swap_constant_product(raw_base, raw_quote, cp_amm::SWAP_CONSTANT_PRODUCT_SQRT_TOLERANCE).is_ok(),
+                    normalized_amm.validate_swap_constant_product(normalized_base, normalized_quote, cp_amm::SWAP_CONSTANT_PRODUCT_SQRT_TOLERANCE).is_ok()
+                );
+            }
+
+            // The normalized liquidity must fit into `u64`
+            let large_amm = TestCpAmm::try_new_normalized(10_000_000_000, 3_000_000_000_000, 0, 9).unwrap();
+            assert!(large_amm.calculate_afterswap_liquidity(10_000_000_000, true).is_none());
+        }
+
+        /// Tests `calculate_split_liquidity` for proportional shares summing up to the liquidity.
+        #[test]
+        fn test_calculate_split_liquidity() {
+            assert_eq!(TestCpAmm::calculate_split_liquidity(1_000, &[1, 1]), Some(vec![500, 500]));
+            assert_eq!(TestCpAmm::calculate_split_liquidity(1_000, &[1, 3]), Some(vec![250, 750]));
+            assert_eq!(TestCpAmm::calculate_split_liquidity(1_000, &[1, 1, 1]), Some(vec![333, 333, 334]));
+            assert_eq!(TestCpAmm::calculate_split_liquidity(7, &[5_000]), Some(vec![7]));
+            assert_eq!(TestCpAmm::calculate_split_liquidity(1, &[1, 1]), Some(vec![0, 1]));
+            assert_eq!(TestCpAmm::calculate_split_liquidity(1_000, &[]), None);
+            assert_eq!(TestCpAmm::calculate_split_liquidity(1_000, &[0, 0]), None);
+
+            let shares = TestCpAmm::calculate_split_liquidity(u64::MAX, &[u16::MAX, u16::MAX, 1]).unwrap();
+            assert_eq!(shares.iter().map(|share| *share as u128).sum::<u128>(), u64::MAX as u128);
+        }
+
         /// Tests `calculate_fee_amount` for correctness.
         #[test]
         fn test_calculate_fee_amount() {
@@ -518,6 +986,7 @@ mod tests {
                 expected_fee,
                 fee
             );
+            assert_eq!(TestCpAmm::calculate_fee_amount(u64::MAX, 0), 0);
         }
 
         /// Tests `calculate_opposite_liquidity` for correctness.
@@ -595,8 +1064,8 @@ mod tests {
             let new_quote_liquidity = 40_000_000;
             let invalid_base_liquidity = 1_999_999;
 
-            let ratio = amm.validate_and_calculate_liquidity_ratio(new_base_liquidity, new_quote_liquidity).unwrap();
-            let invalid_ratio = amm.validate_and_calculate_liquidity_ratio(invalid_base_liquidity, new_quote_liquidity);
+            let ratio = amm.validate_and_calculate_liquidity_ratio(new_base_liquidity, new_quote_liquidity, cp_amm::ADJUST_LIQUIDITY_RATIO_SQRT_TOLERANCE).unwrap();
+            let invalid_ratio = amm.validate_and_calculate_liquidity_ratio(invalid_base_liquidity, new_quote_liquidity, cp_amm::ADJUST_LIQUIDITY_RATIO_SQRT_TOLERANCE);
 
             assert!(
                 invalid_ratio.is_err(),
@@ -608,6 +1077,11 @@ mod tests {
                 "Calculated liquidity ratio mismatch. Expected: {:?}, Got: {:?}",
                 amm.base_quote_ratio_sqrt, ratio
             );
+
+            // The direction of the breach is reported with distinct errors
+            let above_ratio = amm.validate_and_calculate_liquidity_ratio(2_000_001, new_quote_liquidity, cp_amm::ADJUST_LIQUIDITY_RATIO_SQRT_TOLERANCE);
+            assert_eq!(invalid_ratio.err(), Some(ErrorCode::LiquidityRatioBelowTolerance.into()));
+            assert_eq!(above_ratio.err(), Some(ErrorCode::LiquidityRatioAboveTolerance.into()));
         }
 
         /// Tests `validate_swap_constant_product` for correct validation of constant product after a swap.
@@ -621,8 +1095,8 @@ mod tests {
             let new_quote_liquidity = 4_000_000;
             let invalid_base_liquidity = 4_999_990;
 
-            let result = amm.validate_swap_constant_product(new_base_liquidity, new_quote_liquidity);
-            let invalid_result = amm.validate_swap_constant_product(invalid_base_liquidity, new_quote_liquidity);
+            let result = amm.validate_swap_constant_product(new_base_liquidity, new_quote_liquidity, cp_amm::SWAP_CONSTANT_PRODUCT_SQRT_TOLERANCE);
+            let invalid_result = amm.validate_swap_constant_product(invalid_base_liquidity, new_quote_liquidity, cp_amm::SWAP_CONSTANT_PRODUCT_SQRT_TOLERANCE);
 
             assert!(
                 result.is_ok(),
@@ -734,7 +1208,7 @@ mod tests {
                     let lp_tokens = constant_product_sqrt.as_u64();
             
                     if lp_tokens >> 3 >= TestCpAmm::INITIAL_LOCKED_LP_TOKENS {
-                        let (launch_liquidity, initial_locked) = TestCpAmm::calculate_launch_lp_tokens(constant_product_sqrt).unwrap();
+                        let (launch_liquidity, initial_locked) = TestCpAmm::calculate_launch_lp_tokens(constant_product_sqrt, TestCpAmm::INITIAL_LOCKED_LP_TOKENS).unwrap();
             
                         prop_assert_eq!(
                             launch_liquidity,
@@ -755,5 +1229,72 @@ mod tests {
                 }
             }
         }
+
+        proptest! {
+            #![proptest_config(ProptestConfig::with_cases(100000))]
+
+            /// Fuzz-test for `calculate_normalized_constant_product_sqrt_from_ratio_sqrt`, which `CpAmm::swap` uses instead of a square root.
+            /// Ensures the derived constant product stays within a negligible relative deviation of the square root
+            /// and, together with the ratio it is derived from, restores the normalized liquidity exactly.
+            #[test]
+            fn test_fuzz_constant_product_sqrt_from_ratio_sqrt(base_liquidity in arbitrary_u64(), quote_liquidity in arbitrary_u64(), base_decimals in 0..=18u8, quote_decimals in 0..=18u8) {
+                let (base_exponent, quote_exponent) = TestCpAmm::calculate_normalization_exponents(base_decimals, quote_decimals);
+                let amm = TestCpAmm {
+                    base_liquidity,
+                    quote_liquidity,
+                    constant_product_sqrt: Q64_128::ONE,
+                    base_quote_ratio_sqrt: Q64_128::ONE,
+                    lp_tokens_supply: 1,
+                    base_liquidity_scale: 10_u64.pow(base_exponent as u32),
+                    quote_liquidity_scale: 10_u64.pow(quote_exponent as u32),
+                };
+                let (Some((normalized_base, normalized_quote)), Some(base_quote_ratio_sqrt)) = (
+                    amm.normalize_liquidity(base_liquidity, quote_liquidity),
+                    amm.calculate_normalized_base_quote_ratio_sqrt(base_liquidity, quote_liquidity),
+                ) else {
+                    return Ok(());
+                };
+                let constant_product_sqrt = amm.calculate_normalized_constant_product_sqrt(base_liquidity, quote_liquidity).unwrap();
+                let derived_constant_product_sqrt = amm.calculate_normalized_constant_product_sqrt_from_ratio_sqrt(base_quote_ratio_sqrt, quote_liquidity).unwrap();
+
+                // 2^-50, well below the 10^-6 swap tolerance
+                let parity_tolerance = Q64_128::from_bits(0, 1 << 78);
+                prop_assert!(
+                    cp_amm::is_within_tolerance(constant_product_sqrt, derived_constant_product_sqrt, parity_tolerance).unwrap(),
+                    "Derived constant product sqrt drifted. Expected: {:?}, Got: {:?}",
+                    constant_product_sqrt,
+                    derived_constant_product_sqrt
+                );
+                prop_assert_eq!(derived_constant_product_sqrt.saturating_mul(base_quote_ratio_sqrt).as_u64_round(), normalized_base);
+                prop_assert_eq!(derived_constant_product_sqrt.saturating_checked_div(base_quote_ratio_sqrt).unwrap().as_u64_round(), normalized_quote);
+            }
+        }
+
+        proptest! {
+            #![proptest_config(ProptestConfig::with_cases(10000))]
+
+            /// Property test for the tolerance constants.
+            /// Ensures that scaling a value by a tolerance matches dividing it by the tolerance denominator,
+            /// up to the truncation error of the constant and of the division.
+            #[test]
+            fn test_fuzz_tolerance_constants(value in arbitrary_u64()) {
+                let tolerances = [
+                    (cp_amm::SWAP_CONSTANT_PRODUCT_SQRT_TOLERANCE, 1_000_000u64),
+                    (cp_amm::ADJUST_LIQUIDITY_RATIO_SQRT_TOLERANCE, 10_000_000u64),
+                ];
+                for (tolerance, denominator) in tolerances {
+                    let scaled = Q64_128::from_u64(value).checked_mul(tolerance).unwrap();
+                    let expected = Q64_128::from_u64(value).checked_div(Q64_128::from_u64(denominator)).unwrap();
+                    prop_assert!(scaled <= expected, "Tolerance {:?} overestimates 1/{}", tolerance, denominator);
+                    prop_assert!(
+                        expected.abs_diff(scaled) <= Q64_128::from_bits(0, value as u128 + 1),
+                        "Tolerance {:?} drifted from 1/{} for value {}",
+                        tolerance,
+                        denominator,
+                        value
+                    );
+                }
+            }
+        }
     }
 }
\ No newline at end of file
@@ -31,4 +31,20 @@ pub trait CpAmmCore {
     /// # Returns
     /// - A `u64` value representing the total supply of LP tokens.
     fn lp_tokens_supply(&self) -> u64;
+
+    /// Retrieves the factor scaling the base liquidity to the pool's virtual decimal basis.
+    ///
+    /// # Returns
+    /// - A `u64` power of ten, `1` for pools without decimals normalization.
+    fn base_liquidity_scale(&self) -> u64 {
+        1
+    }
+
+    /// Retrieves the factor scaling the quote liquidity to the pool's virtual decimal basis.
+    ///
+    /// # Returns
+    /// - A `u64` power of ten, `1` for pools without decimals normalization.
+    fn quote_liquidity_scale(&self) -> u64 {
+        1
+    }
 }
\ No newline at end of file
@@ -0,0 +1,181 @@
+use anchor_lang::prelude::*;
+use crate::utils::math::Q64_128;
+use super::{CpAmmCalculate, CpAmmCore};
+
+/// The curve pricing the liquidity of a pool.
+///
+/// The pool math dispatches on the curve of the pool, so new curves are added as variants
+/// with their own `CurveCalculator` implementation, without changes to the instruction handlers.
+/// Pools created before the curve was stored read the default constant product curve.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, AnchorSerialize, AnchorDeserialize, InitSpace)]
+pub enum CurveKind {
+    /// The constant product curve, keeping `base_liquidity * quote_liquidity` across swaps.
+    #[default]
+    ConstantProduct,
+}
+
+impl CurveKind {
+    /// Returns the calculator of the curve over the liquidity of a pool.
+    ///
+    /// # Parameters
+    /// - `pool`: The pool the curve prices.
+    pub(crate) fn calculator<T: CpAmmCore>(self, pool: &T) -> Curve<'_, T> {
+        match self {
+            Self::ConstantProduct => Curve::ConstantProduct(ConstantProductCurve(pool)),
+        }
+    }
+}
+
+/// A trait implementing the curve-specific math of a pool: swaps, LP tokens minting and redemption,
+/// and the validation of the curve invariant.
+///
+/// The square root of the invariant is stored in the pool's `constant_product_sqrt` and measures
+/// the pool liquidity backing the LP tokens. The fees, the liquidity ratio and the decimals
+/// normalization are shared by all curves and stay in `CpAmmCalculate`.
+pub(crate) trait CurveCalculator {
+    /// Calculates the invariant square root of the liquidity.
+    ///
+    /// # Parameters
+    /// - `base_liquidity`: The base liquidity.
+    /// - `quote_liquidity`: The quote liquidity.
+    ///
+    /// # Returns
+    /// - `Some(Q64_128)` with the invariant square root.
+    /// - `None` if the calculation fails.
+    fn calculate_invariant_sqrt(&self, base_liquidity: u64, quote_liquidity: u64) -> Option<Q64_128>;
+
+    /// Calculates the invariant square root of the liquidity from an already calculated liquidity ratio,
+    /// letting the curve spare the square roots it can derive from the ratio.
+    ///
+    /// # Parameters
+    /// - `base_quote_ratio_sqrt`: The normalized base-to-quote ratio square root of the liquidity.
+    /// - `base_liquidity`: The base liquidity.
+    /// - `quote_liquidity`: The quote liquidity.
+    ///
+    /// # Returns
+    /// - `Some(Q64_128)` with the invariant square root.
+    /// - `None` if the calculation fails.
+    fn calculate_invariant_sqrt_from_ratio_sqrt(&self, base_quote_ratio_sqrt: Q64_128, base_liquidity: u64, quote_liquidity: u64) -> Option<Q64_128>;
+
+    /// Calculates the new base and quote liquidity after a swap.
+    ///
+    /// # Parameters
+    /// - `swap_amount`: The amount being swapped in.
+    /// - `is_in_out`: Whether the base (true) or the quote (false) is swapped in.
+    ///
+    /// # Returns
+    /// - `Some((u64, u64))` with the new base and quote liquidity.
+    /// - `None` if the calculation fails.
+    fn calculate_afterswap_liquidity(&self, swap_amount: u64, is_in_out: bool) -> Option<(u64, u64)>;
+
+    /// Calculates the LP tokens to mint for the liquidity provided.
+    ///
+    /// # Parameters
+    /// - `new_invariant_sqrt`: The invariant square root after the provide.
+    ///
+    /// # Returns
+    /// - `Some(u64)` with the LP tokens to mint.
+    /// - `None` if the calculation fails.
+    fn calculate_lp_mint_for_provided_liquidity(&self, new_invariant_sqrt: Q64_128) -> Option<u64>;
+
+    /// Calculates the liquidity redeemed for LP tokens.
+    ///
+    /// # Parameters
+    /// - `lp_tokens`: The LP tokens to redeem.
+    ///
+    /// # Returns
+    /// - `Some((u64, u64))` with the base and quote liquidity.
+    /// - `None` if the calculation fails.
+    fn calculate_liquidity_from_share(&self, lp_tokens: u64) -> Option<(u64, u64)>;
+
+    /// Validates the invariant after a swap.
+    ///
+    /// # Parameters
+    /// - `new_base_liquidity`: The new base liquidity.
+    /// - `new_quote_liquidity`: The new quote liquidity.
+    /// - `tolerance`: The allowed relative deviation of the invariant square root.
+    ///
+    /// # Returns
+    /// - `Ok(())` if the invariant remains within tolerance.
+    /// - `Err(ErrorCode)` if the invariant exceeds the allowed tolerance.
+    fn validate_swap_invariant(&self, new_base_liquidity: u64, new_quote_liquidity: u64, tolerance: Q64_128) -> Result<()>;
+}
+
+/// The constant product curve of a pool, implemented by `CpAmmCalculate`.
+pub(crate) struct ConstantProductCurve<'a, T: CpAmmCore>(&'a T);
+
+impl<T: CpAmmCore> CurveCalculator for ConstantProductCurve<'_, T> {
+    #[inline]
+    fn calculate_invariant_sqrt(&self, base_liquidity: u64, quote_liquidity: u64) -> Option<Q64_128> {
+        self.0.calculate_normalized_constant_product_sqrt(base_liquidity, quote_liquidity)
+    }
+
+    #[inline]
+    fn calculate_invariant_sqrt_from_ratio_sqrt(&self, base_quote_ratio_sqrt: Q64_128, _base_liquidity: u64, quote_liquidity: u64) -> Option<Q64_128> {
+        self.0.calculate_normalized_constant_product_sqrt_from_ratio_sqrt(base_quote_ratio_sqrt, quote_liquidity)
+    }
+
+    #[inline]
+    fn calculate_afterswap_liquidity(&self, swap_amount: u64, is_in_out: bool) -> Option<(u64, u64)> {
+        self.0.calculate_afterswap_liquidity(swap_amount, is_in_out)
+    }
+
+    #[inline]
+    fn calculate_lp_mint_for_provided_liquidity(&self, new_invariant_sqrt: Q64_128) -> Option<u64> {
+        self.0.calculate_lp_mint_for_provided_liquidity(new_invariant_sqrt)
+    }
+
+    #[inline]
+    fn calculate_liquidity_from_share(&self, lp_tokens: u64) -> Option<(u64, u64)> {
+        self.0.calculate_liquidity_from_share(lp_tokens)
+    }
+
+    #[inline]
+    fn validate_swap_invariant(&self, new_base_liquidity: u64, new_quote_liquidity: u64, tolerance: Q64_128) -> Result<()> {
+        self.0.validate_swap_constant_product(new_base_liquidity, new_quote_liquidity, tolerance)
+    }
+}
+
+/// The calculator of a pool curve, dispatching to the implementation of the pool's `CurveKind`.
+pub(crate) enum Curve<'a, T: CpAmmCore> {
+    /// The constant product curve.
+    ConstantProduct(ConstantProductCurve<'a, T>),
+}
+
+impl<T: CpAmmCore> CurveCalculator for Curve<'_, T> {
+    fn calculate_invariant_sqrt(&self, base_liquidity: u64, quote_liquidity: u64) -> Option<Q64_128> {
+        match self {
+            Self::ConstantProduct(curve) => curve.calculate_invariant_sqrt(base_liquidity, quote_liquidity),
+        }
+    }
+
+    fn calculate_invariant_sqrt_from_ratio_sqrt(&self, base_quote_ratio_sqrt: Q64_128, base_liquidity: u64, quote_liquidity: u64) -> Option<Q64_128> {
+        match self {
+            Self::ConstantProduct(curve) => curve.calculate_invariant_sqrt_from_ratio_sqrt(base_quote_ratio_sqrt, base_liquidity, quote_liquidity),
+        }
+    }
+
+    fn calculate_afterswap_liquidity(&self, swap_amount: u64, is_in_out: bool) -> Option<(u64, u64)> {
+        match self {
+            Self::ConstantProduct(curve) => curve.calculate_afterswap_liquidity(swap_amount, is_in_out),
+        }
+    }
+
+    fn calculate_lp_mint_for_provided_liquidity(&self, new_invariant_sqrt: Q64_128) -> Option<u64> {
+        match self {
+            Self::ConstantProduct(curve) => curve.calculate_lp_mint_for_provided_liquidity(new_invariant_sqrt),
+        }
+    }
+
+    fn calculate_liquidity_from_share(&self, lp_tokens: u64) -> Option<(u64, u64)> {
+        match self {
+            Self::ConstantProduct(curve) => curve.calculate_liquidity_from_share(lp_tokens),
+        }
+    }
+
+    fn validate_swap_invariant(&self, new_base_liquidity: u64, new_quote_liquidity: u64, tolerance: Q64_128) -> Result<()> {
+        match self {
+            Self::ConstantProduct(curve) => curve.validate_swap_invariant(new_base_liquidity, new_quote_liquidity, tolerance),
+        }
+    }
+}
\ No newline at end of file
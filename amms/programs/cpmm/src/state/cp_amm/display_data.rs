@@ -0,0 +1,143 @@
+use anchor_lang::prelude::*;
+
+/// Type of an entry of the `DisplayData` of a `CpAmm`, stored as a little-endian `u16`.
+#[repr(u16)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DisplayEntryType {
+    /// Terminates the entries, zeroed bytes of the region hold no entries.
+    Uninitialized = 0,
+    /// The base and quote mints, 32 bytes each.
+    Mints = 1,
+    /// The base and quote liquidity in raw token units, `u64` each, a hint of the pool's TVL.
+    Reserves = 2,
+    /// The price of a raw base unit in raw quote units, a `u64` with 9 decimals.
+    Price = 3,
+    /// The providers and protocol fee rates of swaps in basis points, `u16` each.
+    FeeRates = 4,
+    /// The supply of LP tokens, a `u64`.
+    LpTokensSupply = 5,
+    /// The slot of the last refresh of the entries, a `u64`.
+    LastUpdateSlot = 6,
+}
+
+/// Self-describing summary of a `CpAmm` stored at the end of its account, so generic wallet UIs
+/// can render pools without the program IDL.
+///
+/// The region holds type-length-value entries in the style of SPL Token-2022 extensions: a `u16`
+/// `DisplayEntryType`, a `u16` length and the value, all little-endian. The entries end with an
+/// `Uninitialized` type or the end of the region, so readers skip unknown types by their length.
+/// The entries are snapshots refreshed by the AMM operations, see `CpAmm::refresh_display_data`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, AnchorSerialize, AnchorDeserialize, InitSpace)]
+pub struct DisplayData {
+    /// The encoded entries, zero-padded.
+    data: [u8; 160], // 160 bytes
+}
+
+impl Default for DisplayData {
+    fn default() -> Self {
+        Self { data: [0; 160] }
+    }
+}
+
+impl DisplayData {
+    /// The size of the region in bytes.
+    pub const LEN: usize = 160;
+
+    /// The size of the type and length header of an entry in bytes.
+    pub const ENTRY_HEADER_LEN: usize = 4;
+
+    /// Replaces the entries of the region.
+    ///
+    /// # Parameters
+    /// - `entries`: The types and values of the entries, in order.
+    ///
+    /// # Panics
+    /// - Panics if the entries don't fit into the region, which the fixed set of `CpAmm` entries always does.
+    pub(crate) fn write(&mut self, entries: &[(DisplayEntryType, &[u8])]) {
+        self.data = [0; 160];
+        let mut offset = 0;
+        for (entry_type, value) in entries {
+            self.data[offset..offset + 2].copy_from_slice(&(*entry_type as u16).to_le_bytes());
+            self.data[offset + 2..offset + 4].copy_from_slice(&(value.len() as u16).to_le_bytes());
+            offset += Self::ENTRY_HEADER_LEN;
+            self.data[offset..offset + value.len()].copy_from_slice(value);
+            offset += value.len();
+        }
+    }
+
+    /// Returns the raw bytes of the region.
+    #[inline]
+    pub fn data(&self) -> &[u8; 160] {
+        &self.data
+    }
+
+    /// Returns the entries of the region as their raw types and values.
+    ///
+    /// A truncated entry ends the iteration, like an `Uninitialized` type.
+    pub fn entries(&self) -> impl Iterator<Item = (u16, &[u8])> {
+        let mut offset = 0;
+        std::iter::from_fn(move || {
+            let header = self.data.get(offset..offset + Self::ENTRY_HEADER_LEN)?;
+            let entry_type = u16::from_le_bytes([header[0], header[1]]);
+            let length = u16::from_le_bytes([header[2], header[3]]) as usize;
+            if entry_type == DisplayEntryType::Uninitialized as u16 {
+                return None;
+            }
+            let value = self.data.get(offset + Self::ENTRY_HEADER_LEN..offset + Self::ENTRY_HEADER_LEN + length)?;
+            offset += Self::ENTRY_HEADER_LEN + length;
+            Some((entry_type, value))
+        })
+    }
+
+    /// Returns the value of the first entry of a type.
+    pub fn entry(&self, entry_type: DisplayEntryType) -> Option<&[u8]> {
+        self.entries()
+            .find(|(raw_type, _)| *raw_type == entry_type as u16)
+            .map(|(_, value)| value)
+    }
+}
+
+#[cfg(test)]
+mod display_data_tests {
+    use super::*;
+
+    /// Tests writing and reading the entries of `DisplayData`.
+    #[test]
+    fn test_display_data_entries() {
+        let mut display_data = DisplayData::default();
+        assert_eq!(display_data.entries().count(), 0);
+
+        display_data.write(&[
+            (DisplayEntryType::FeeRates, &[25, 0, 5, 0]),
+            (DisplayEntryType::LastUpdateSlot, &42u64.to_le_bytes()),
+        ]);
+        assert_eq!(&display_data.data()[..8], &[4, 0, 4, 0, 25, 0, 5, 0]);
+        assert_eq!(
+            display_data.entries().collect::<Vec<_>>(),
+            vec![(4, [25, 0, 5, 0].as_ref()), (6, 42u64.to_le_bytes().as_ref())]
+        );
+        assert_eq!(display_data.entry(DisplayEntryType::LastUpdateSlot), Some(42u64.to_le_bytes().as_ref()));
+        assert_eq!(display_data.entry(DisplayEntryType::Price), None);
+
+        // Rewriting clears the previous entries
+        display_data.write(&[(DisplayEntryType::Price, &7u64.to_le_bytes())]);
+        assert_eq!(display_data.entries().count(), 1);
+        assert_eq!(display_data.entry(DisplayEntryType::FeeRates), None);
+    }
+
+    /// Tests that unknown types are skipped and truncated entries end the entries.
+    #[test]
+    fn test_display_data_entries_malformed() {
+        let mut data = [0u8; 160];
+        data[..6].copy_from_slice(&[99, 0, 2, 0, 1, 2]);
+        data[6..12].copy_from_slice(&[3, 0, 2, 0, 3, 4]);
+        let display_data = DisplayData { data };
+        assert_eq!(display_data.entries().collect::<Vec<_>>(), vec![(99, [1, 2].as_ref()), (3, [3, 4].as_ref())]);
+        assert_eq!(display_data.entry(DisplayEntryType::Price), Some([3, 4].as_ref()));
+
+        let mut data = [0u8; 160];
+        data[..4].copy_from_slice(&[2, 0, 157, 0]);
+        let display_data = DisplayData { data };
+        assert_eq!(display_data.entries().count(), 0);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,316 @@
+use anchor_lang::prelude::*;
+
+/// The kind of an operation recorded in an `EventRing`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum EventRecordKind {
+    /// A swap of base tokens in for quote tokens out.
+    SwapBaseToQuote = 1,
+    /// A swap of quote tokens in for base tokens out.
+    SwapQuoteToBase = 2,
+    /// A liquidity provide.
+    Provide = 3,
+    /// A liquidity withdrawal.
+    Withdraw = 4,
+    /// A conversion of protocol fees into the settlement mint through the pool's reserves.
+    FeeSettlement = 5,
+    /// The launch of the pool with its initial liquidity.
+    Launch = 6,
+    /// A repayment of lent out liquidity with its interest.
+    Repay = 7,
+}
+
+impl EventRecordKind {
+    /// Returns the kind of a swap in the given direction.
+    #[inline]
+    pub fn swap(is_in_out: bool) -> Self {
+        if is_in_out { Self::SwapBaseToQuote } else { Self::SwapQuoteToBase }
+    }
+
+    /// Returns the kind stored as `value`, or `None` for an empty or unknown record.
+    pub fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            1 => Some(Self::SwapBaseToQuote),
+            2 => Some(Self::SwapQuoteToBase),
+            3 => Some(Self::Provide),
+            4 => Some(Self::Withdraw),
+            5 => Some(Self::FeeSettlement),
+            6 => Some(Self::Launch),
+            7 => Some(Self::Repay),
+            _ => None,
+        }
+    }
+}
+
+/// A compact record of an operation in a pool.
+#[zero_copy]
+#[derive(Debug, Default, PartialEq, Eq, InitSpace)]
+pub struct EventRecord {
+    /// The sequence number of the record among all records written to the ring.
+    sequence: u64, // 8 bytes
+
+    /// The slot the operation was executed in.
+    slot: u64, // 8 bytes
+
+    /// The account performing the operation.
+    signer: Pubkey, // 32 bytes
+
+    /// The base tokens moved in or out of the pool.
+    base_amount: u64, // 8 bytes
+
+    /// The quote tokens moved in or out of the pool.
+    quote_amount: u64, // 8 bytes
+
+    /// The LP tokens minted or burned, zero for swaps.
+    lp_tokens: u64, // 8 bytes
+
+    /// The `EventRecordKind` of the operation, zero for an empty record.
+    kind: u8, // 1 byte
+
+    /// Padding to the 8 bytes alignment of the record.
+    _padding: [u8; 7], // 7 bytes
+}
+
+impl EventRecord {
+    /// Returns the sequence number of the record among all records written to the ring.
+    #[inline]
+    pub fn sequence(&self) -> u64 {
+        self.sequence
+    }
+
+    /// Returns the slot the operation was executed in.
+    #[inline]
+    pub fn slot(&self) -> u64 {
+        self.slot
+    }
+
+    /// Returns the account performing the operation.
+    #[inline]
+    pub fn signer(&self) -> &Pubkey {
+        &self.signer
+    }
+
+    /// Returns the base tokens moved in or out of the pool.
+    #[inline]
+    pub fn base_amount(&self) -> u64 {
+        self.base_amount
+    }
+
+    /// Returns the quote tokens moved in or out of the pool.
+    #[inline]
+    pub fn quote_amount(&self) -> u64 {
+        self.quote_amount
+    }
+
+    /// Returns the LP tokens minted or burned, zero for swaps.
+    #[inline]
+    pub fn lp_tokens(&self) -> u64 {
+        self.lp_tokens
+    }
+
+    /// Returns the kind of the operation, or `None` for an empty record.
+    #[inline]
+    pub fn kind(&self) -> Option<EventRecordKind> {
+        EventRecordKind::from_u8(self.kind)
+    }
+}
+
+/// Zero-copy log of the latest operations of a `CpAmm`, an alternative to program logs for indexers.
+///
+/// Program logs may be truncated, while the ring keeps the last `CAPACITY` records in account data.
+/// Handlers passing the optional ring append a record per operation, overwriting the oldest one.
+/// Records carry a sequence number incremented by every write, so an indexer polling the ring
+/// detects the records it missed from a gap between the sequence numbers it has seen.
+#[account(zero_copy)]
+#[derive(InitSpace)]
+pub struct EventRing {
+    /// The public key of the `CpAmm` the records belong to.
+    cp_amm: Pubkey, // 32 bytes
+
+    /// The sequence number of the next record, equal to the number of records ever written.
+    sequence: u64, // 8 bytes
+
+    /// The canonical bump seed used for the account's PDA.
+    bump: u8, // 1 byte
+
+    /// Padding to the 8 bytes alignment of the records.
+    _padding: [u8; 7], // 7 bytes
+
+    /// The records, the record of sequence number `s` is stored at index `s % CAPACITY`.
+    records: [EventRecord; 64], // 5120 bytes
+}
+
+impl EventRing {
+    /// The seed used to derive the account's PDA.
+    pub const SEED: &'static [u8] = b"event_ring";
+
+    /// The number of records kept in the ring.
+    pub const CAPACITY: usize = 64;
+
+    /// Initializes the `EventRing` of a pool.
+    ///
+    /// # Parameters
+    /// - `cp_amm`: The public key of the `CpAmm`.
+    /// - `bump`: The bump seed for the account's PDA.
+    pub(crate) fn initialize(&mut self, cp_amm: Pubkey, bump: u8) {
+        self.cp_amm = cp_amm;
+        self.sequence = 0;
+        self.bump = bump;
+    }
+
+    /// Appends a record of an operation, overwriting the oldest record once the ring is full.
+    ///
+    /// # Parameters
+    /// - `kind`: The kind of the operation.
+    /// - `signer`: The account performing the operation.
+    /// - `base_amount`: The base tokens moved in or out of the pool.
+    /// - `quote_amount`: The quote tokens moved in or out of the pool.
+    /// - `lp_tokens`: The LP tokens minted or burned.
+    /// - `slot`: The current slot.
+    pub(crate) fn push(&mut self, kind: EventRecordKind, signer: Pubkey, base_amount: u64, quote_amount: u64, lp_tokens: u64, slot: u64) {
+        self.records[(self.sequence % Self::CAPACITY as u64) as usize] = EventRecord {
+            sequence: self.sequence,
+            slot,
+            signer,
+            base_amount,
+            quote_amount,
+            lp_tokens,
+            kind: kind as u8,
+            _padding: [0; 7],
+        };
+        self.sequence += 1;
+    }
+
+    /// Returns the record of a sequence number, or `None` if it isn't written yet or was overwritten.
+    pub fn record(&self, sequence: u64) -> Option<&EventRecord> {
+        if sequence >= self.sequence || self.sequence - sequence > Self::CAPACITY as u64 {
+            return None;
+        }
+        Some(&self.records[(sequence % Self::CAPACITY as u64) as usize])
+    }
+
+    /// Returns the public key of the `CpAmm` the records belong to.
+    #[inline]
+    pub fn cp_amm(&self) -> &Pubkey {
+        &self.cp_amm
+    }
+
+    /// Returns the sequence number of the next record, equal to the number of records ever written.
+    #[inline]
+    pub fn sequence(&self) -> u64 {
+        self.sequence
+    }
+
+    /// Returns the bump seed used for the account's PDA.
+    #[inline]
+    pub fn bump(&self) -> u8 {
+        self.bump
+    }
+}
+
+#[cfg(test)]
+mod event_ring_tests {
+    use anchor_lang::{Discriminator, ZeroCopy};
+    use super::*;
+
+    fn empty_event_ring() -> EventRing {
+        EventRing {
+            cp_amm: Pubkey::default(),
+            sequence: 0,
+            bump: 0,
+            _padding: [0; 7],
+            records: [EventRecord::default(); EventRing::CAPACITY],
+        }
+    }
+
+    /// Tests appending records to the `EventRing` and their lookup by sequence number.
+    #[test]
+    fn test_event_ring_push() {
+        let mut event_ring = empty_event_ring();
+        let cp_amm = Pubkey::new_unique();
+        event_ring.initialize(cp_amm, 42);
+        assert_eq!(event_ring.cp_amm(), &cp_amm);
+        assert_eq!(event_ring.bump(), 42);
+        assert_eq!(event_ring.record(0), None);
+
+        let signer = Pubkey::new_unique();
+        event_ring.push(EventRecordKind::swap(true), signer, 1_000, 250, 0, 100);
+        event_ring.push(EventRecordKind::Provide, signer, 2_000, 500, 1_000, 101);
+        assert_eq!(event_ring.sequence(), 2);
+
+        let record = event_ring.record(0).unwrap();
+        assert_eq!(record.kind(), Some(EventRecordKind::SwapBaseToQuote));
+        assert_eq!((record.sequence(), record.slot(), record.signer()), (0, 100, &signer));
+        assert_eq!((record.base_amount(), record.quote_amount(), record.lp_tokens()), (1_000, 250, 0));
+        assert_eq!(event_ring.record(1).unwrap().kind(), Some(EventRecordKind::Provide));
+        assert_eq!(event_ring.record(2), None);
+        assert_eq!(EventRecord::default().kind(), None);
+    }
+
+    /// Tests that every `EventRecordKind` is read back from its stored value.
+    #[test]
+    fn test_event_record_kind_from_u8() {
+        let kinds = [
+            EventRecordKind::SwapBaseToQuote,
+            EventRecordKind::SwapQuoteToBase,
+            EventRecordKind::Provide,
+            EventRecordKind::Withdraw,
+            EventRecordKind::FeeSettlement,
+            EventRecordKind::Launch,
+            EventRecordKind::Repay,
+        ];
+        for kind in kinds {
+            assert_eq!(EventRecordKind::from_u8(kind as u8), Some(kind));
+        }
+        assert_eq!(EventRecordKind::from_u8(0), None);
+        assert_eq!(EventRecordKind::from_u8(8), None);
+    }
+
+    /// Tests that the `EventRing` overwrites its oldest records once full.
+    #[test]
+    fn test_event_ring_wrap_around() {
+        let mut event_ring = empty_event_ring();
+        let signer = Pubkey::new_unique();
+        for slot in 0..EventRing::CAPACITY as u64 + 10 {
+            event_ring.push(EventRecordKind::swap(false), signer, slot, slot, 0, slot);
+        }
+
+        assert_eq!(event_ring.sequence(), 74);
+        assert_eq!(event_ring.record(9), None);
+        assert_eq!(event_ring.record(10).unwrap().slot(), 10);
+        assert_eq!(event_ring.record(73).unwrap().slot(), 73);
+        assert_eq!(event_ring.records[0].sequence(), 64);
+    }
+
+    /// Tests the data layout of the `EventRing` struct.
+    #[test]
+    fn test_event_ring_data_layout() {
+        assert_eq!(EventRecord::INIT_SPACE, std::mem::size_of::<EventRecord>());
+        assert_eq!(EventRing::INIT_SPACE, std::mem::size_of::<EventRing>());
+
+        let cp_amm = Pubkey::new_unique();
+        let signer = Pubkey::new_unique();
+        let mut event_ring = empty_event_ring();
+        event_ring.initialize(cp_amm, 42);
+        event_ring.push(EventRecordKind::Withdraw, signer, 1_000, 2_000, 3_000, 4_000);
+
+        let mut data = vec![0u8; EventRing::INIT_SPACE];
+        let mut offset = 0;
+        data[offset..offset + 32].copy_from_slice(cp_amm.as_ref()); offset += 32;
+        data[offset..offset + 8].copy_from_slice(&1u64.to_le_bytes()); offset += 8;
+        data[offset] = 42; offset += 8;
+        data[offset..offset + 8].copy_from_slice(&0u64.to_le_bytes()); offset += 8;
+        data[offset..offset + 8].copy_from_slice(&4_000u64.to_le_bytes()); offset += 8;
+        data[offset..offset + 32].copy_from_slice(signer.as_ref()); offset += 32;
+        data[offset..offset + 8].copy_from_slice(&1_000u64.to_le_bytes()); offset += 8;
+        data[offset..offset + 8].copy_from_slice(&2_000u64.to_le_bytes()); offset += 8;
+        data[offset..offset + 8].copy_from_slice(&3_000u64.to_le_bytes()); offset += 8;
+        data[offset] = EventRecordKind::Withdraw as u8;
+
+        assert_eq!(bytemuck::bytes_of(&event_ring), data.as_slice());
+        assert_eq!(bytemuck::from_bytes::<EventRing>(&data).record(0), event_ring.record(0));
+        assert_eq!(EventRing::discriminator(), <EventRing as Discriminator>::DISCRIMINATOR);
+        fn assert_zero_copy<T: ZeroCopy>() {}
+        assert_zero_copy::<EventRing>();
+    }
+}
\ No newline at end of file
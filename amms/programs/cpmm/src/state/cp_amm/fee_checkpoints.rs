@@ -0,0 +1,306 @@
+use anchor_lang::{account, InitSpace};
+use anchor_lang::prelude::*;
+use crate::error::ErrorCode;
+use super::PoolStats;
+
+/// Snapshot of the cumulative providers fees of a `CpAmm` at a point in time.
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub struct FeeCheckpoint {
+    /// The unix timestamp of the checkpoint, in seconds.
+    timestamp: i64, // 8 bytes
+
+    /// The cumulative providers fees collected in base tokens.
+    cumulative_base_providers_fees: u128, // 16 bytes
+
+    /// The cumulative providers fees collected in quote tokens.
+    cumulative_quote_providers_fees: u128, // 16 bytes
+}
+
+impl FeeCheckpoint {
+    /// Returns the unix timestamp of the checkpoint.
+    #[inline]
+    pub fn timestamp(&self) -> i64 {
+        self.timestamp
+    }
+
+    /// Returns the cumulative providers fees collected in base tokens.
+    #[inline]
+    pub fn cumulative_base_providers_fees(&self) -> u128 {
+        self.cumulative_base_providers_fees
+    }
+
+    /// Returns the cumulative providers fees collected in quote tokens.
+    #[inline]
+    pub fn cumulative_quote_providers_fees(&self) -> u128 {
+        self.cumulative_quote_providers_fees
+    }
+}
+
+/// Ring buffer of periodic providers fee checkpoints of a `CpAmm`.
+///
+/// Checkpoints are recorded permissionlessly at most once per `CHECKPOINT_INTERVAL_SECONDS`,
+/// so the buffer covers a bit more than 7 days of history when cranked regularly.
+#[account]
+#[derive(InitSpace)]
+pub struct FeeCheckpoints {
+    /// The canonical bump seed used for the account's PDA.
+    bump: u8, // 1 byte
+
+    /// The public key of the tracked `CpAmm`.
+    cp_amm: Pubkey, // 32 bytes
+
+    /// The index the next checkpoint is written to.
+    head: u16, // 2 bytes
+
+    /// The number of recorded checkpoints, up to `CAPACITY`.
+    count: u16, // 2 bytes
+
+    /// The checkpoints, the oldest one is overwritten once the buffer is full.
+    checkpoints: [FeeCheckpoint; 64], // 2560 bytes
+}
+
+impl FeeCheckpoints {
+    /// The seed used to derive the account's PDA.
+    pub const SEED: &'static [u8] = b"fee_checkpoints";
+
+    /// The number of checkpoints kept in the buffer.
+    pub const CAPACITY: usize = 64;
+
+    /// The minimal time between two consecutive checkpoints, in seconds.
+    pub const CHECKPOINT_INTERVAL_SECONDS: i64 = 3 * 60 * 60;
+
+    /// The number of seconds per year used to annualize the fee rate.
+    pub const SECONDS_PER_YEAR: u64 = 365 * 24 * 60 * 60;
+
+    /// Initializes the `FeeCheckpoints` with the first checkpoint of the pool.
+    ///
+    /// # Parameters
+    /// - `cp_amm`: The public key of the tracked `CpAmm`.
+    /// - `bump`: The bump seed for the account's PDA.
+    /// - `current_timestamp`: The current unix timestamp.
+    /// - `stats`: The current trading statistics of the `CpAmm`.
+    pub(crate) fn initialize(&mut self, cp_amm: Pubkey, bump: u8, current_timestamp: i64, stats: &PoolStats) {
+        self.bump = bump;
+        self.cp_amm = cp_amm;
+        self.push(current_timestamp, stats);
+    }
+
+    /// Records a new checkpoint of the pool providers fees.
+    ///
+    /// # Parameters
+    /// - `current_timestamp`: The current unix timestamp.
+    /// - `stats`: The current trading statistics of the `CpAmm`.
+    ///
+    /// # Errors
+    /// - `FeeCheckpointTooEarly` if less than `CHECKPOINT_INTERVAL_SECONDS` passed since the latest checkpoint.
+    pub(crate) fn record(&mut self, current_timestamp: i64, stats: &PoolStats) -> Result<()> {
+        let next_timestamp = self.latest().map_or(i64::MIN, |latest| latest.timestamp.saturating_add(Self::CHECKPOINT_INTERVAL_SECONDS));
+        require!(current_timestamp >= next_timestamp, ErrorCode::FeeCheckpointTooEarly);
+        self.push(current_timestamp, stats);
+        Ok(())
+    }
+
+    fn push(&mut self, timestamp: i64, stats: &PoolStats) {
+        self.checkpoints[self.head as usize] = FeeCheckpoint {
+            timestamp,
+            cumulative_base_providers_fees: stats.cumulative_base_providers_fees(),
+            cumulative_quote_providers_fees: stats.cumulative_quote_providers_fees(),
+        };
+        self.head = ((self.head as usize + 1) % Self::CAPACITY) as u16;
+        self.count = (self.count as usize + 1).min(Self::CAPACITY) as u16;
+    }
+
+    /// Iterates over the recorded checkpoints from the newest to the oldest.
+    fn iter_newest_first(&self) -> impl Iterator<Item = &FeeCheckpoint> {
+        (1..=self.count as usize).map(move |offset| &self.checkpoints[(self.head as usize + Self::CAPACITY - offset) % Self::CAPACITY])
+    }
+
+    /// Returns the newest checkpoint, if any.
+    pub fn latest(&self) -> Option<&FeeCheckpoint> {
+        self.iter_newest_first().next()
+    }
+
+    /// Returns the newest checkpoint recorded at or before the timestamp, falling back
+    /// to the oldest checkpoint if the buffer does not reach that far back.
+    ///
+    /// # Parameters
+    /// - `timestamp`: The unix timestamp to look back to.
+    pub fn find_at_or_before(&self, timestamp: i64) -> Option<&FeeCheckpoint> {
+        self.iter_newest_first()
+            .find(|checkpoint| checkpoint.timestamp <= timestamp)
+            .or_else(|| self.iter_newest_first().last())
+    }
+
+    /// Calculates the annualized providers fee rate of the pool over a trailing window.
+    ///
+    /// Fees collected since the checkpoint are valued in quote tokens at the current pool price
+    /// and related to the current pool value of twice its quote liquidity.
+    ///
+    /// # Parameters
+    /// - `current_timestamp`: The current unix timestamp.
+    /// - `window_seconds`: The length of the trailing window, in seconds.
+    /// - `stats`: The current trading statistics of the `CpAmm`.
+    /// - `base_liquidity`: The current base liquidity of the `CpAmm`.
+    /// - `quote_liquidity`: The current quote liquidity of the `CpAmm`.
+    ///
+    /// # Returns
+    /// The APR in basis points, saturated at `u64::MAX`, and the actually covered
+    /// number of seconds, which is shorter than the window if the history is shorter.
+    pub fn get_trailing_apr(
+        &self,
+        current_timestamp: i64,
+        window_seconds: i64,
+        stats: &PoolStats,
+        base_liquidity: u64,
+        quote_liquidity: u64
+    ) -> (u64, u64) {
+        let Some(checkpoint) = self.find_at_or_before(current_timestamp.saturating_sub(window_seconds)) else {
+            return (0, 0);
+        };
+        let covered_seconds = current_timestamp.saturating_sub(checkpoint.timestamp).max(0) as u64;
+        if covered_seconds == 0 || base_liquidity == 0 || quote_liquidity == 0 {
+            return (0, covered_seconds);
+        }
+
+        let base_fees = stats.cumulative_base_providers_fees().saturating_sub(checkpoint.cumulative_base_providers_fees);
+        let quote_fees = stats.cumulative_quote_providers_fees().saturating_sub(checkpoint.cumulative_quote_providers_fees);
+        let apr_basis_points = base_fees.checked_mul(quote_liquidity as u128)
+            .map(|base_fees_in_quote| base_fees_in_quote / base_liquidity as u128)
+            .and_then(|base_fees_in_quote| base_fees_in_quote.checked_add(quote_fees))
+            .and_then(|fees_in_quote| fees_in_quote.checked_mul(10000 * Self::SECONDS_PER_YEAR as u128))
+            .map(|annualized_fees| annualized_fees / (covered_seconds as u128 * 2 * quote_liquidity as u128))
+            .map_or(u64::MAX, |apr_basis_points| u64::try_from(apr_basis_points).unwrap_or(u64::MAX));
+        (apr_basis_points, covered_seconds)
+    }
+
+    /// Returns the bump seed used for the account's PDA.
+    #[inline]
+    pub fn bump(&self) -> u8 {
+        self.bump
+    }
+
+    /// Returns the public key of the tracked `CpAmm`.
+    #[inline]
+    pub fn cp_amm(&self) -> &Pubkey {
+        &self.cp_amm
+    }
+
+    /// Returns the number of recorded checkpoints.
+    #[inline]
+    pub fn count(&self) -> u16 {
+        self.count
+    }
+}
+
+#[cfg(test)]
+mod fee_checkpoints_tests {
+    use anchor_lang::Discriminator;
+    use crate::constants::ANCHOR_DISCRIMINATOR;
+    use super::*;
+
+    const DAY: i64 = 24 * 60 * 60;
+
+    fn empty_checkpoints() -> FeeCheckpoints {
+        FeeCheckpoints {
+            bump: 0,
+            cp_amm: Pubkey::default(),
+            head: 0,
+            count: 0,
+            checkpoints: [FeeCheckpoint::default(); FeeCheckpoints::CAPACITY],
+        }
+    }
+
+    /// Tests checkpoint recording intervals and overwriting of the oldest checkpoints.
+    #[test]
+    fn test_record() {
+        let mut checkpoints = empty_checkpoints();
+        assert_eq!(checkpoints.latest(), None);
+        assert_eq!(checkpoints.find_at_or_before(0), None);
+
+        checkpoints.initialize(Pubkey::new_unique(), 250, 1_000, &PoolStats::default());
+        assert_eq!(checkpoints.count(), 1);
+        assert_eq!(checkpoints.latest().unwrap().timestamp(), 1_000);
+
+        let interval = FeeCheckpoints::CHECKPOINT_INTERVAL_SECONDS;
+        assert_eq!(checkpoints.record(1_000 + interval - 1, &PoolStats::default()).err(), Some(ErrorCode::FeeCheckpointTooEarly.into()));
+
+        let stats = PoolStats::default().record_fees(30, 0, true).record_fees(50, 0, false);
+        checkpoints.record(1_000 + interval, &stats).unwrap();
+        assert_eq!(checkpoints.count(), 2);
+        assert_eq!(checkpoints.latest().unwrap().cumulative_base_providers_fees(), 30);
+        assert_eq!(checkpoints.latest().unwrap().cumulative_quote_providers_fees(), 50);
+
+        for i in 2..=FeeCheckpoints::CAPACITY as i64 {
+            checkpoints.record(1_000 + i * interval, &stats).unwrap();
+        }
+        assert_eq!(checkpoints.count() as usize, FeeCheckpoints::CAPACITY);
+        assert_eq!(checkpoints.latest().unwrap().timestamp(), 1_000 + FeeCheckpoints::CAPACITY as i64 * interval);
+        // The first checkpoint was overwritten, so the oldest one is the second
+        assert_eq!(checkpoints.find_at_or_before(0).unwrap().timestamp(), 1_000 + interval);
+        assert_eq!(checkpoints.find_at_or_before(1_000 + 5 * interval + 1).unwrap().timestamp(), 1_000 + 5 * interval);
+    }
+
+    /// Tests the trailing APR calculation.
+    #[test]
+    fn test_get_trailing_apr() {
+        let mut checkpoints = empty_checkpoints();
+        let stats = PoolStats::default().record_fees(100, 0, true).record_fees(200, 0, false);
+        checkpoints.initialize(Pubkey::new_unique(), 250, 0, &stats);
+        checkpoints.record(6 * DAY, &stats.record_fees(10, 0, true).record_fees(20, 0, false)).unwrap();
+
+        // 1 base fee valued at 2 quote and 2 quote fees per day over a pool value of 4000 quote
+        let stats = stats.record_fees(11, 0, true).record_fees(22, 0, false);
+        assert_eq!(checkpoints.get_trailing_apr(7 * DAY, DAY, &stats, 1000, 2000), (3650, DAY as u64));
+        assert_eq!(checkpoints.get_trailing_apr(7 * DAY, 7 * DAY, &stats, 1000, 2000), (5735, 7 * DAY as u64));
+        // The history is shorter than the window
+        assert_eq!(checkpoints.get_trailing_apr(7 * DAY, 30 * DAY, &stats, 1000, 2000), (5735, 7 * DAY as u64));
+        // Empty pool or no elapsed time
+        assert_eq!(checkpoints.get_trailing_apr(7 * DAY, DAY, &stats, 0, 2000), (0, DAY as u64));
+        assert_eq!(checkpoints.get_trailing_apr(0, DAY, &stats, 1000, 2000), (0, 0));
+        // Saturated on overflow
+        let stats = stats.record_fees(u64::MAX, 0, true).record_fees(u64::MAX, 0, true);
+        assert_eq!(checkpoints.get_trailing_apr(7 * DAY, DAY, &stats, 1, u64::MAX).0, u64::MAX);
+    }
+
+    /// Tests `FeeCheckpoints` account data layout.
+    #[test]
+    fn test_fee_checkpoints_data_layout() {
+        let bump = 250u8;
+        let cp_amm = Pubkey::new_unique();
+        let head = 2u16;
+        let count = 2u16;
+
+        let mut data = [0u8; ANCHOR_DISCRIMINATOR + 2597];
+        let mut offset = 0;
+
+        data[offset..offset + ANCHOR_DISCRIMINATOR].copy_from_slice(&FeeCheckpoints::discriminator()); offset += ANCHOR_DISCRIMINATOR;
+        data[offset] = bump; offset += 1;
+        data[offset..offset + 32].copy_from_slice(cp_amm.as_ref()); offset += 32;
+        data[offset..offset + 2].copy_from_slice(&head.to_le_bytes()); offset += 2;
+        data[offset..offset + 2].copy_from_slice(&count.to_le_bytes()); offset += 2;
+        for i in 0..FeeCheckpoints::CAPACITY {
+            data[offset..offset + 8].copy_from_slice(&(i as i64 * 100).to_le_bytes()); offset += 8;
+            data[offset..offset + 16].copy_from_slice(&(i as u128).to_le_bytes()); offset += 16;
+            data[offset..offset + 16].copy_from_slice(&(2 * i as u128).to_le_bytes()); offset += 16;
+        }
+
+        assert_eq!(ANCHOR_DISCRIMINATOR + FeeCheckpoints::INIT_SPACE, offset);
+
+        let deserialized_checkpoints = FeeCheckpoints::try_deserialize(&mut data.as_ref()).unwrap();
+
+        assert_eq!(deserialized_checkpoints.bump, bump);
+        assert_eq!(deserialized_checkpoints.cp_amm, cp_amm);
+        assert_eq!(deserialized_checkpoints.head, head);
+        assert_eq!(deserialized_checkpoints.count, count);
+        assert_eq!(deserialized_checkpoints.checkpoints[63], FeeCheckpoint {
+            timestamp: 6300,
+            cumulative_base_providers_fees: 63,
+            cumulative_quote_providers_fees: 126,
+        });
+        assert_eq!(deserialized_checkpoints.latest().unwrap().timestamp(), 100);
+
+        let mut serialized_checkpoints = Vec::new();
+        deserialized_checkpoints.try_serialize(&mut serialized_checkpoints).unwrap();
+        assert_eq!(serialized_checkpoints.as_slice(), data.as_ref());
+    }
+}
\ No newline at end of file
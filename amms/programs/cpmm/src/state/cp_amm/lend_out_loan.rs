@@ -0,0 +1,277 @@
+use anchor_lang::{account, InitSpace};
+use anchor_lang::prelude::*;
+use crate::error::ErrorCode;
+
+/// IOU of the lending adapter for the liquidity it borrowed from one side of a `CpAmm`.
+///
+/// Interest accrues linearly on the outstanding principal at the `AmmsConfig` lend-out rate.
+/// Repayments settle the accrued interest first, which is added to the pool liquidity
+/// as providers fees, and the principal afterwards.
+#[account]
+#[derive(InitSpace)]
+pub struct LendOutLoan {
+    /// The canonical bump seed used for the account's PDA.
+    bump: u8, // 1 byte
+
+    /// The public key of the lending `CpAmm`.
+    cp_amm: Pubkey, // 32 bytes
+
+    /// The public key of the lent out mint.
+    mint: Pubkey, // 32 bytes
+
+    /// The outstanding amount of borrowed tokens.
+    principal: u64, // 8 bytes
+
+    /// The interest accrued and not repaid yet.
+    accrued_interest: u64, // 8 bytes
+
+    /// The slot up to which the interest has been accrued.
+    last_accrual_slot: u64, // 8 bytes
+
+    /// Whether the loan has been recalled and must be repaid before borrowing again.
+    is_recalled: bool, // 1 byte
+}
+
+impl LendOutLoan {
+    /// The seed used to derive the account's PDA.
+    pub const SEED: &'static [u8] = b"lend_out_loan";
+
+    /// The approximate number of slots per year used to accrue the annual interest rate.
+    pub const SLOTS_PER_YEAR: u64 = 78_840_000;
+
+    /// Initializes the `LendOutLoan` for one side of a pool.
+    ///
+    /// # Parameters
+    /// - `cp_amm`: The public key of the lending `CpAmm`.
+    /// - `mint`: The public key of the lent out mint.
+    /// - `bump`: The bump seed for the account's PDA.
+    pub(crate) fn initialize(&mut self, cp_amm: Pubkey, mint: Pubkey, bump: u8) {
+        self.bump = bump;
+        self.cp_amm = cp_amm;
+        self.mint = mint;
+    }
+
+    /// Accrues the interest on the outstanding principal up to the current slot.
+    ///
+    /// The interest is rounded up, so a non-zero principal never borrows for free.
+    ///
+    /// # Parameters
+    /// - `current_slot`: The current slot.
+    /// - `interest_rate_basis_points`: The annual interest rate, measured in basis points.
+    pub(crate) fn accrue_interest(&mut self, current_slot: u64, interest_rate_basis_points: u16) {
+        let elapsed_slots = current_slot.saturating_sub(self.last_accrual_slot);
+        let interest = (self.principal as u128 * interest_rate_basis_points as u128 * elapsed_slots as u128)
+            .div_ceil(10000 * Self::SLOTS_PER_YEAR as u128);
+        self.accrued_interest = self.accrued_interest.saturating_add(u64::try_from(interest).unwrap_or(u64::MAX));
+        self.last_accrual_slot = self.last_accrual_slot.max(current_slot);
+    }
+
+    /// Registers newly borrowed tokens.
+    ///
+    /// # Errors
+    /// - Returns `ErrorCode::LendOutLoanRecalled` if the loan has been recalled.
+    pub(crate) fn borrow(&mut self, amount: u64) -> Result<()> {
+        require!(!self.is_recalled, ErrorCode::LendOutLoanRecalled);
+        self.principal = self.principal.checked_add(amount).ok_or(ErrorCode::LendOutLimitExceeded)?;
+        Ok(())
+    }
+
+    /// Splits a repayment into the interest and principal parts.
+    ///
+    /// # Parameters
+    /// - `amount`: The amount of repaid tokens.
+    ///
+    /// # Returns
+    /// - `Ok((u64, u64))` with the repaid interest and principal.
+    /// - `Err(ErrorCode::LendOutRepayExceedsDebt)` if the amount exceeds the whole debt.
+    pub fn get_repayment(&self, amount: u64) -> Result<(u64, u64)> {
+        let interest = amount.min(self.accrued_interest);
+        let principal = amount - interest;
+        require!(principal <= self.principal, ErrorCode::LendOutRepayExceedsDebt);
+        Ok((interest, principal))
+    }
+
+    /// Registers a repayment, lifting the recall once the whole debt is repaid.
+    ///
+    /// # Parameters
+    /// - `interest`: The repaid interest.
+    /// - `principal`: The repaid principal.
+    pub(crate) fn repay(&mut self, interest: u64, principal: u64) {
+        self.accrued_interest -= interest;
+        self.principal -= principal;
+        if self.accrued_interest == 0 && self.principal == 0 {
+            self.is_recalled = false;
+        }
+    }
+
+    /// Recalls the loan, blocking further borrowing until it is repaid in full.
+    pub(crate) fn recall(&mut self) {
+        self.is_recalled = true;
+    }
+
+    /// Checks if the loan was initialized.
+    #[inline]
+    pub fn is_initialized(&self) -> bool {
+        self.cp_amm != Pubkey::default()
+    }
+
+    /// Returns the bump seed used for the account's PDA.
+    #[inline]
+    pub fn bump(&self) -> u8 {
+        self.bump
+    }
+
+    /// Returns the public key of the lending `CpAmm`.
+    #[inline]
+    pub fn cp_amm(&self) -> &Pubkey {
+        &self.cp_amm
+    }
+
+    /// Returns the public key of the lent out mint.
+    #[inline]
+    pub fn mint(&self) -> &Pubkey {
+        &self.mint
+    }
+
+    /// Returns the outstanding amount of borrowed tokens.
+    #[inline]
+    pub fn principal(&self) -> u64 {
+        self.principal
+    }
+
+    /// Returns the interest accrued and not repaid yet.
+    #[inline]
+    pub fn accrued_interest(&self) -> u64 {
+        self.accrued_interest
+    }
+
+    /// Returns the slot up to which the interest has been accrued.
+    #[inline]
+    pub fn last_accrual_slot(&self) -> u64 {
+        self.last_accrual_slot
+    }
+
+    /// Checks if the loan has been recalled.
+    #[inline]
+    pub fn is_recalled(&self) -> bool {
+        self.is_recalled
+    }
+}
+
+#[cfg(test)]
+mod lend_out_loan_tests {
+    use anchor_lang::Discriminator;
+    use crate::constants::ANCHOR_DISCRIMINATOR;
+    use super::*;
+
+    fn initialized_loan() -> LendOutLoan {
+        let mut loan = LendOutLoan {
+            bump: 0,
+            cp_amm: Pubkey::default(),
+            mint: Pubkey::default(),
+            principal: 0,
+            accrued_interest: 0,
+            last_accrual_slot: 0,
+            is_recalled: false,
+        };
+        assert!(!loan.is_initialized());
+        loan.initialize(Pubkey::new_unique(), Pubkey::new_unique(), 250);
+        assert!(loan.is_initialized());
+        loan
+    }
+
+    /// Tests interest accrual on the outstanding principal.
+    #[test]
+    fn test_accrue_interest() {
+        let mut loan = initialized_loan();
+        loan.accrue_interest(100, 1000);
+        assert_eq!(loan.accrued_interest(), 0);
+        assert_eq!(loan.last_accrual_slot(), 100);
+
+        loan.borrow(1_000_000).unwrap();
+        loan.accrue_interest(100 + LendOutLoan::SLOTS_PER_YEAR, 1000);
+        assert_eq!(loan.accrued_interest(), 100_000);
+
+        // Rounded up for any elapsed slot
+        loan.accrue_interest(101 + LendOutLoan::SLOTS_PER_YEAR, 1000);
+        assert_eq!(loan.accrued_interest(), 100_001);
+
+        // Older slots accrue nothing and don't move the accrual slot back
+        loan.accrue_interest(0, 1000);
+        assert_eq!(loan.accrued_interest(), 100_001);
+        assert_eq!(loan.last_accrual_slot(), 101 + LendOutLoan::SLOTS_PER_YEAR);
+    }
+
+    /// Tests that repayments settle the interest before the principal.
+    #[test]
+    fn test_repayment() {
+        let mut loan = initialized_loan();
+        loan.borrow(1000).unwrap();
+        loan.accrued_interest = 50;
+
+        assert_eq!(loan.get_repayment(30).unwrap(), (30, 0));
+        assert_eq!(loan.get_repayment(450).unwrap(), (50, 400));
+        assert_eq!(loan.get_repayment(1050).unwrap(), (50, 1000));
+        assert_eq!(loan.get_repayment(1051).err(), Some(ErrorCode::LendOutRepayExceedsDebt.into()));
+
+        loan.repay(50, 400);
+        assert_eq!(loan.accrued_interest(), 0);
+        assert_eq!(loan.principal(), 600);
+    }
+
+    /// Tests that a recall blocks borrowing until the whole debt is repaid.
+    #[test]
+    fn test_recall() {
+        let mut loan = initialized_loan();
+        loan.borrow(1000).unwrap();
+        loan.recall();
+        assert!(loan.is_recalled());
+        assert_eq!(loan.borrow(1).err(), Some(ErrorCode::LendOutLoanRecalled.into()));
+
+        loan.repay(0, 999);
+        assert!(loan.is_recalled());
+        loan.repay(0, 1);
+        assert!(!loan.is_recalled());
+        loan.borrow(1).unwrap();
+    }
+
+    /// Tests `LendOutLoan` account data layout.
+    #[test]
+    fn test_lend_out_loan_data_layout() {
+        let bump = 250u8;
+        let cp_amm = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let principal = 1_000u64;
+        let accrued_interest = 20u64;
+        let last_accrual_slot = 300u64;
+        let is_recalled = true;
+
+        let mut data = [0u8; ANCHOR_DISCRIMINATOR + 90];
+        let mut offset = 0;
+
+        data[offset..offset + ANCHOR_DISCRIMINATOR].copy_from_slice(&LendOutLoan::discriminator()); offset += ANCHOR_DISCRIMINATOR;
+        data[offset] = bump; offset += 1;
+        data[offset..offset + 32].copy_from_slice(cp_amm.as_ref()); offset += 32;
+        data[offset..offset + 32].copy_from_slice(mint.as_ref()); offset += 32;
+        data[offset..offset + 8].copy_from_slice(&principal.to_le_bytes()); offset += 8;
+        data[offset..offset + 8].copy_from_slice(&accrued_interest.to_le_bytes()); offset += 8;
+        data[offset..offset + 8].copy_from_slice(&last_accrual_slot.to_le_bytes()); offset += 8;
+        data[offset] = is_recalled as u8; offset += 1;
+
+        assert_eq!(ANCHOR_DISCRIMINATOR + LendOutLoan::INIT_SPACE, offset);
+
+        let deserialized_loan = LendOutLoan::try_deserialize(&mut data.as_slice()).unwrap();
+
+        assert_eq!(deserialized_loan.bump(), bump);
+        assert_eq!(deserialized_loan.cp_amm(), &cp_amm);
+        assert_eq!(deserialized_loan.mint(), &mint);
+        assert_eq!(deserialized_loan.principal(), principal);
+        assert_eq!(deserialized_loan.accrued_interest(), accrued_interest);
+        assert_eq!(deserialized_loan.last_accrual_slot(), last_accrual_slot);
+        assert_eq!(deserialized_loan.is_recalled(), is_recalled);
+
+        let mut serialized_loan = Vec::new();
+        deserialized_loan.try_serialize(&mut serialized_loan).unwrap();
+        assert_eq!(serialized_loan.as_slice(), data.as_ref());
+    }
+}
\ No newline at end of file
@@ -0,0 +1,311 @@
+use anchor_lang::{account, InitSpace};
+use anchor_lang::prelude::*;
+use crate::state::LoyaltyBoost;
+
+/// Tracks how long an owner has held liquidity provided to a `CpAmm`.
+///
+/// The position is updated by provides and withdrawals passing it. Provides move the hold start
+/// towards the current time proportionally to the added LP tokens, while partial withdrawals
+/// decay the held duration proportionally to the withdrawn share of the position.
+#[account]
+#[derive(InitSpace)]
+pub struct LiquidityPosition {
+    /// The canonical bump seed used for the account's PDA.
+    bump: u8, // 1 byte
+
+    /// The public key of the `CpAmm` the liquidity is provided to.
+    cp_amm: Pubkey, // 32 bytes
+
+    /// The public key of the LP tokens owner.
+    owner: Pubkey, // 32 bytes
+
+    /// The LP tokens tracked by the position.
+    lp_tokens: u64, // 8 bytes
+
+    /// The LP-weighted timestamp since which the tracked LP tokens are held.
+    hold_start_timestamp: i64, // 8 bytes
+}
+
+impl LiquidityPosition {
+    /// The seed used to derive the account's PDA.
+    pub const SEED: &'static [u8] = b"liquidity_position";
+
+    /// Initializes the `LiquidityPosition` of an owner in a pool.
+    ///
+    /// # Parameters
+    /// - `cp_amm`: The public key of the `CpAmm`.
+    /// - `owner`: The public key of the LP tokens owner.
+    /// - `bump`: The bump seed for the account's PDA.
+    pub(crate) fn initialize(&mut self, cp_amm: Pubkey, owner: Pubkey, bump: u8) {
+        self.bump = bump;
+        self.cp_amm = cp_amm;
+        self.owner = owner;
+        self.lp_tokens = 0;
+        self.hold_start_timestamp = 0;
+    }
+
+    /// Registers provided LP tokens, moving the hold start towards the current time
+    /// proportionally to their share in the position.
+    ///
+    /// # Parameters
+    /// - `lp_tokens`: The minted LP tokens.
+    /// - `current_timestamp`: The current unix timestamp.
+    pub(crate) fn record_provide(&mut self, lp_tokens: u64, current_timestamp: i64) {
+        let total_lp_tokens = self.lp_tokens.saturating_add(lp_tokens);
+        if total_lp_tokens == 0 {
+            return;
+        }
+        let weighted_timestamps = self.hold_start_timestamp as i128 * self.lp_tokens as i128
+            + current_timestamp as i128 * lp_tokens as i128;
+        self.hold_start_timestamp = (weighted_timestamps / total_lp_tokens as i128) as i64;
+        self.lp_tokens = total_lp_tokens;
+    }
+
+    /// Registers withdrawn LP tokens, decaying the held duration proportionally
+    /// to the withdrawn share of the position.
+    ///
+    /// # Parameters
+    /// - `lp_tokens`: The burned LP tokens.
+    /// - `current_timestamp`: The current unix timestamp.
+    pub(crate) fn record_withdraw(&mut self, lp_tokens: u64, current_timestamp: i64) {
+        if self.lp_tokens == 0 {
+            return;
+        }
+        let remaining_lp_tokens = self.lp_tokens.saturating_sub(lp_tokens);
+        let remaining_hold_seconds = self.hold_seconds(current_timestamp) as u128 * remaining_lp_tokens as u128 / self.lp_tokens as u128;
+        self.hold_start_timestamp = current_timestamp.saturating_sub(remaining_hold_seconds as i64);
+        self.lp_tokens = remaining_lp_tokens;
+    }
+
+    /// Moves tracked LP tokens to another position of the pool without resetting their attribution.
+    ///
+    /// The moved LP tokens keep the hold start of this position, merged into the hold start of the
+    /// `destination` proportionally to their share in it, while the hold start of this position is kept.
+    /// LP tokens beyond the tracked ones are moved without attribution.
+    ///
+    /// # Parameters
+    /// - `destination`: The position receiving the LP tokens.
+    /// - `lp_tokens`: The transferred LP tokens.
+    ///
+    /// # Returns
+    /// - The `u64` tracked LP tokens moved to the `destination`.
+    pub(crate) fn transfer_to(&mut self, destination: &mut LiquidityPosition, lp_tokens: u64) -> u64 {
+        let moved_lp_tokens = lp_tokens.min(self.lp_tokens);
+        destination.record_provide(moved_lp_tokens, self.hold_start_timestamp);
+        self.lp_tokens -= moved_lp_tokens;
+        moved_lp_tokens
+    }
+
+    /// Calculates the duration the tracked LP tokens have been held for.
+    ///
+    /// # Parameters
+    /// - `current_timestamp`: The current unix timestamp.
+    ///
+    /// # Returns
+    /// - The `u64` held duration in seconds, zero for an empty position.
+    pub fn hold_seconds(&self, current_timestamp: i64) -> u64 {
+        if self.lp_tokens == 0 {
+            return 0;
+        }
+        current_timestamp.saturating_sub(self.hold_start_timestamp).max(0) as u64
+    }
+
+    /// Calculates the loyalty multiplier of the position.
+    ///
+    /// # Parameters
+    /// - `current_timestamp`: The current unix timestamp.
+    /// - `loyalty_boost`: The loyalty boost of the pool's `AmmsConfig`, if set.
+    ///
+    /// # Returns
+    /// - The `u16` multiplier, measured in basis points (10000 = 1x).
+    pub fn loyalty_multiplier_basis_points(&self, current_timestamp: i64, loyalty_boost: Option<&LoyaltyBoost>) -> u16 {
+        loyalty_boost.map_or(
+            LoyaltyBoost::BASE_MULTIPLIER_BASIS_POINTS,
+            |loyalty_boost| loyalty_boost.multiplier_basis_points(self.hold_seconds(current_timestamp))
+        )
+    }
+
+    /// Returns the bump seed used for the account's PDA.
+    #[inline]
+    pub fn bump(&self) -> u8 {
+        self.bump
+    }
+
+    /// Returns the public key of the `CpAmm` the liquidity is provided to.
+    #[inline]
+    pub fn cp_amm(&self) -> &Pubkey {
+        &self.cp_amm
+    }
+
+    /// Returns the public key of the LP tokens owner.
+    #[inline]
+    pub fn owner(&self) -> &Pubkey {
+        &self.owner
+    }
+
+    /// Returns the LP tokens tracked by the position.
+    #[inline]
+    pub fn lp_tokens(&self) -> u64 {
+        self.lp_tokens
+    }
+
+    /// Returns the LP-weighted timestamp since which the tracked LP tokens are held.
+    #[inline]
+    pub fn hold_start_timestamp(&self) -> i64 {
+        self.hold_start_timestamp
+    }
+}
+
+#[cfg(test)]
+mod liquidity_position_tests {
+    use anchor_lang::Discriminator;
+    use crate::constants::ANCHOR_DISCRIMINATOR;
+    use super::*;
+
+    fn empty_position() -> LiquidityPosition {
+        LiquidityPosition {
+            bump: 0,
+            cp_amm: Pubkey::new_unique(),
+            owner: Pubkey::new_unique(),
+            lp_tokens: 0,
+            hold_start_timestamp: 0,
+        }
+    }
+
+    /// Tests the correct initialization of the `LiquidityPosition` struct.
+    #[test]
+    fn test_liquidity_position_initialize() {
+        let mut position = LiquidityPosition {
+            bump: 0,
+            cp_amm: Pubkey::default(),
+            owner: Pubkey::default(),
+            lp_tokens: 5,
+            hold_start_timestamp: 10,
+        };
+        let cp_amm = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        position.initialize(cp_amm, owner, 42);
+
+        assert_eq!(position.bump(), 42);
+        assert_eq!(position.cp_amm().key(), cp_amm);
+        assert_eq!(position.owner().key(), owner);
+        assert_eq!(position.lp_tokens(), 0);
+        assert_eq!(position.hold_start_timestamp(), 0);
+    }
+
+    /// Tests that provides move the hold start proportionally to the added LP tokens.
+    #[test]
+    fn test_liquidity_position_record_provide() {
+        let mut position = empty_position();
+
+        position.record_provide(1000, 10_000);
+        assert_eq!(position.lp_tokens(), 1000);
+        assert_eq!(position.hold_start_timestamp(), 10_000);
+        assert_eq!(position.hold_seconds(12_000), 2000);
+
+        position.record_provide(3000, 14_000);
+        assert_eq!(position.lp_tokens(), 4000);
+        assert_eq!(position.hold_start_timestamp(), 13_000);
+
+        position.record_provide(0, 20_000);
+        assert_eq!(position.hold_start_timestamp(), 13_000);
+    }
+
+    /// Tests that partial withdrawals decay the held duration by the withdrawn share.
+    #[test]
+    fn test_liquidity_position_record_withdraw() {
+        let mut position = empty_position();
+        position.record_provide(1000, 10_000);
+
+        position.record_withdraw(250, 14_000);
+        assert_eq!(position.lp_tokens(), 750);
+        assert_eq!(position.hold_seconds(14_000), 3000);
+        assert_eq!(position.hold_start_timestamp(), 11_000);
+
+        // Withdrawing more than tracked empties the position
+        position.record_withdraw(1000, 15_000);
+        assert_eq!(position.lp_tokens(), 0);
+        assert_eq!(position.hold_seconds(20_000), 0);
+
+        position.record_provide(100, 30_000);
+        assert_eq!(position.hold_start_timestamp(), 30_000);
+    }
+
+    /// Tests that transfers move tracked LP tokens along with their hold start.
+    #[test]
+    fn test_liquidity_position_transfer_to() {
+        let mut source = empty_position();
+        let mut destination = empty_position();
+        source.record_provide(1000, 10_000);
+        destination.record_provide(1000, 20_000);
+
+        assert_eq!(source.transfer_to(&mut destination, 250), 250);
+        assert_eq!(source.lp_tokens(), 750);
+        assert_eq!(source.hold_start_timestamp(), 10_000);
+        assert_eq!(destination.lp_tokens(), 1250);
+        assert_eq!(destination.hold_start_timestamp(), 18_000);
+
+        // Untracked LP tokens are not attributed
+        assert_eq!(source.transfer_to(&mut destination, 1000), 750);
+        assert_eq!(source.lp_tokens(), 0);
+        assert_eq!(source.hold_seconds(30_000), 0);
+        assert_eq!(destination.lp_tokens(), 2000);
+        assert_eq!(destination.hold_start_timestamp(), 15_000);
+
+        // An empty destination inherits the hold start
+        let mut empty_destination = empty_position();
+        assert_eq!(destination.transfer_to(&mut empty_destination, 500), 500);
+        assert_eq!(empty_destination.hold_start_timestamp(), 15_000);
+        assert_eq!(empty_destination.hold_seconds(16_000), 1000);
+    }
+
+    /// Tests the loyalty multiplier of a position.
+    #[test]
+    fn test_liquidity_position_loyalty_multiplier_basis_points() {
+        let loyalty_boost = LoyaltyBoost::try_new(1000, 3000, 20000).unwrap();
+        let mut position = empty_position();
+        position.record_provide(1000, 10_000);
+
+        assert_eq!(position.loyalty_multiplier_basis_points(12_000, None), 10000);
+        assert_eq!(position.loyalty_multiplier_basis_points(12_000, Some(&loyalty_boost)), 15000);
+        assert_eq!(position.loyalty_multiplier_basis_points(13_000, Some(&loyalty_boost)), 20000);
+
+        // Withdrawing half of the position halves the held duration
+        position.record_withdraw(500, 13_000);
+        assert_eq!(position.loyalty_multiplier_basis_points(13_000, Some(&loyalty_boost)), 12500);
+    }
+
+    /// Tests the data layout of the `LiquidityPosition` struct.
+    #[test]
+    fn test_liquidity_position_data_layout() {
+        let bump = 42u8;
+        let cp_amm = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        let lp_tokens = 1234u64;
+        let hold_start_timestamp = -5i64;
+
+        let mut data = [0u8; ANCHOR_DISCRIMINATOR + 81];
+        let mut offset = 0;
+
+        data[offset..offset + ANCHOR_DISCRIMINATOR].copy_from_slice(&LiquidityPosition::discriminator()); offset += ANCHOR_DISCRIMINATOR;
+        data[offset] = bump; offset += 1;
+        data[offset..offset + 32].copy_from_slice(cp_amm.as_ref()); offset += 32;
+        data[offset..offset + 32].copy_from_slice(owner.as_ref()); offset += 32;
+        data[offset..offset + 8].copy_from_slice(&lp_tokens.to_le_bytes()); offset += 8;
+        data[offset..offset + 8].copy_from_slice(&hold_start_timestamp.to_le_bytes()); offset += 8;
+
+        assert_eq!(offset, ANCHOR_DISCRIMINATOR + LiquidityPosition::INIT_SPACE);
+
+        let deserialized_position = LiquidityPosition::try_deserialize(&mut data.as_ref()).unwrap();
+
+        assert_eq!(deserialized_position.bump, bump);
+        assert_eq!(deserialized_position.cp_amm, cp_amm);
+        assert_eq!(deserialized_position.owner, owner);
+        assert_eq!(deserialized_position.lp_tokens, lp_tokens);
+        assert_eq!(deserialized_position.hold_start_timestamp, hold_start_timestamp);
+
+        let mut serialized_data = Vec::new();
+        deserialized_position.try_serialize(&mut serialized_data).unwrap();
+        assert_eq!(serialized_data.as_slice(), data.as_ref());
+    }
+}
\ No newline at end of file
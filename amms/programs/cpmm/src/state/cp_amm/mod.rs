@@ -1,8 +1,37 @@
-
 mod cp_amm;
 mod cp_amm_calculate;
 mod cp_amm_core;
+mod curve;
+mod withdraw_rate_limiter;
+mod pool_stats;
+mod pool_stats_shard;
+mod pool_lookup_table;
+mod trading_schedule;
+mod pool_status;
+mod lend_out_loan;
+mod fee_checkpoints;
+mod virtual_price_checkpoints;
+mod liquidity_position;
+mod snapshot;
+mod trading_session;
+mod event_ring;
+mod display_data;
 
 pub use cp_amm::*;
 pub use cp_amm_core::*;
+pub use curve::*;
+pub use withdraw_rate_limiter::*;
+pub use pool_stats::*;
+pub use pool_stats_shard::*;
+pub use pool_lookup_table::*;
+pub use trading_schedule::*;
+pub use pool_status::*;
+pub use lend_out_loan::*;
+pub use fee_checkpoints::*;
+pub use virtual_price_checkpoints::*;
+pub use liquidity_position::*;
+pub use snapshot::*;
+pub use trading_session::*;
+pub use event_ring::*;
+pub use display_data::*;
 pub(crate) use cp_amm_calculate::*;
\ No newline at end of file
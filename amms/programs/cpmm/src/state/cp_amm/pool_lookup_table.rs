@@ -0,0 +1,161 @@
+use anchor_lang::prelude::*;
+use crate::error::ErrorCode;
+use super::CpAmm;
+
+/// Registry entry of the Address Lookup Table holding the account set of a `CpAmm`.
+///
+/// The table is created and extended off-chain, usually in the launch transaction, and registered
+/// by the pool creator, so integrators can find it and build small v0 transactions referencing
+/// the pool's vaults, mints and config through it. The program never reads the table on swaps.
+#[account]
+#[derive(InitSpace)]
+pub struct PoolLookupTable {
+    /// The canonical bump seed used for the account's PDA.
+    bump: u8, // 1 byte
+
+    /// The public key of the `CpAmm` the lookup table belongs to.
+    cp_amm: Pubkey, // 32 bytes
+
+    /// The public key of the Address Lookup Table account.
+    lookup_table: Pubkey, // 32 bytes
+}
+
+impl PoolLookupTable {
+    /// The seed used to derive the account's PDA.
+    pub const SEED: &'static [u8] = b"pool_lookup_table";
+
+    /// Returns the accounts of a pool a registered lookup table must hold.
+    ///
+    /// # Parameters
+    /// - `cp_amm_key`: The public key of the `CpAmm`.
+    /// - `cp_amm`: The `CpAmm` account.
+    pub fn required_addresses(cp_amm_key: &Pubkey, cp_amm: &CpAmm) -> [Pubkey; 8] {
+        [
+            *cp_amm_key,
+            *cp_amm.amms_config(),
+            *cp_amm.base_mint(),
+            *cp_amm.quote_mint(),
+            cp_amm.lp_mint,
+            *cp_amm.base_vault(),
+            *cp_amm.quote_vault(),
+            *cp_amm.locked_lp_vault(),
+        ]
+    }
+
+    /// Validates that a lookup table holds all the required accounts of the pool.
+    ///
+    /// # Parameters
+    /// - `addresses`: The addresses stored in the lookup table.
+    /// - `required_addresses`: The accounts of the pool, see `required_addresses`.
+    ///
+    /// # Errors
+    /// - Returns `ErrorCode::LookupTableInvalid` if any required account is missing.
+    pub fn validate_addresses(addresses: &[Pubkey], required_addresses: &[Pubkey]) -> Result<()> {
+        require!(
+            required_addresses.iter().all(|address| addresses.contains(address)),
+            ErrorCode::LookupTableInvalid
+        );
+        Ok(())
+    }
+
+    /// Registers the lookup table of a pool, replacing a previously registered one.
+    ///
+    /// # Parameters
+    /// - `cp_amm`: The public key of the `CpAmm`.
+    /// - `lookup_table`: The public key of the Address Lookup Table account.
+    /// - `bump`: The bump seed for the account's PDA.
+    pub(crate) fn register(&mut self, cp_amm: Pubkey, lookup_table: Pubkey, bump: u8) {
+        self.bump = bump;
+        self.cp_amm = cp_amm;
+        self.lookup_table = lookup_table;
+    }
+
+    /// Returns the bump seed used for the account's PDA.
+    #[inline]
+    pub fn bump(&self) -> u8 {
+        self.bump
+    }
+
+    /// Returns the public key of the `CpAmm` the lookup table belongs to.
+    #[inline]
+    pub fn cp_amm(&self) -> &Pubkey {
+        &self.cp_amm
+    }
+
+    /// Returns the public key of the Address Lookup Table account.
+    #[inline]
+    pub fn lookup_table(&self) -> &Pubkey {
+        &self.lookup_table
+    }
+}
+
+#[cfg(test)]
+mod pool_lookup_table_tests {
+    use anchor_lang::Discriminator;
+    use crate::constants::ANCHOR_DISCRIMINATOR;
+    use super::*;
+
+    /// Tests the registration of a lookup table.
+    #[test]
+    fn test_pool_lookup_table_register() {
+        let mut pool_lookup_table = PoolLookupTable {
+            bump: 0,
+            cp_amm: Pubkey::default(),
+            lookup_table: Pubkey::new_unique(),
+        };
+        let (cp_amm, lookup_table) = (Pubkey::new_unique(), Pubkey::new_unique());
+        pool_lookup_table.register(cp_amm, lookup_table, 42);
+
+        assert_eq!(pool_lookup_table.bump(), 42);
+        assert_eq!(*pool_lookup_table.cp_amm(), cp_amm);
+        assert_eq!(*pool_lookup_table.lookup_table(), lookup_table);
+    }
+
+    /// Tests the validation of the addresses held by a lookup table.
+    #[test]
+    fn test_pool_lookup_table_validate_addresses() {
+        let required_addresses: Vec<Pubkey> = (0..8).map(|_| Pubkey::new_unique()).collect();
+        let mut addresses = vec![Pubkey::new_unique()];
+        addresses.extend(required_addresses.iter().rev());
+        addresses.push(Pubkey::new_unique());
+
+        assert!(PoolLookupTable::validate_addresses(&addresses, &required_addresses).is_ok());
+        assert!(PoolLookupTable::validate_addresses(&required_addresses, &required_addresses).is_ok());
+        assert_eq!(
+            PoolLookupTable::validate_addresses(&addresses[..8], &required_addresses).err(),
+            Some(ErrorCode::LookupTableInvalid.into())
+        );
+        assert_eq!(
+            PoolLookupTable::validate_addresses(&[], &required_addresses).err(),
+            Some(ErrorCode::LookupTableInvalid.into())
+        );
+    }
+
+    /// Tests the data layout of the `PoolLookupTable` struct.
+    #[test]
+    fn test_pool_lookup_table_data_layout() {
+        let bump = 42u8;
+        let cp_amm = Pubkey::new_unique();
+        let lookup_table = Pubkey::new_unique();
+
+        let mut data = [0u8; ANCHOR_DISCRIMINATOR + 65];
+        let mut offset = 0;
+
+        data[offset..offset + ANCHOR_DISCRIMINATOR].copy_from_slice(&PoolLookupTable::discriminator()); offset += ANCHOR_DISCRIMINATOR;
+        data[offset] = bump; offset += 1;
+        data[offset..offset + 32].copy_from_slice(cp_amm.as_ref()); offset += 32;
+        data[offset..offset + 32].copy_from_slice(lookup_table.as_ref()); offset += 32;
+
+        assert_eq!(offset, ANCHOR_DISCRIMINATOR + PoolLookupTable::INIT_SPACE);
+
+        let deserialized_pool_lookup_table = PoolLookupTable::try_deserialize(&mut data.as_ref()).unwrap();
+
+        assert_eq!(deserialized_pool_lookup_table.bump, bump);
+        assert_eq!(deserialized_pool_lookup_table.cp_amm, cp_amm);
+        assert_eq!(deserialized_pool_lookup_table.lookup_table, lookup_table);
+
+        let mut serialized_data = Vec::new();
+        deserialized_pool_lookup_table.try_serialize(&mut serialized_data).unwrap();
+        assert_eq!(serialized_data.as_slice(), data.as_ref());
+    }
+}
\ No newline at end of file
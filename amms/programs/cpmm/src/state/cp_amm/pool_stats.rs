@@ -0,0 +1,192 @@
+use anchor_lang::prelude::*;
+
+/// Cumulative trading statistics of a `CpAmm`.
+///
+/// Volumes are counted on the swapped amounts including fees. USD volume is recorded
+/// only for swaps executed while the pool has a USD oracle and its price is valid.
+/// Fees are accumulated in the input token of each swap at the rates applied to it,
+/// so fee totals stay accurate across `AmmsConfig` fee rate updates.
+#[derive(Debug, Clone, Copy, PartialEq, Default, AnchorSerialize, AnchorDeserialize, InitSpace)]
+pub struct PoolStats {
+    /// Cumulative swapped volume in base tokens.
+    cumulative_base_volume: u128, // 16 bytes
+
+    /// Cumulative swapped volume in quote tokens.
+    cumulative_quote_volume: u128, // 16 bytes
+
+    /// Cumulative swapped volume in USD with `UsdPrice::USD_VALUE_DECIMALS` decimals.
+    cumulative_usd_volume: u128, // 16 bytes
+
+    /// Cumulative providers fees charged in base tokens.
+    cumulative_base_providers_fees: u128, // 16 bytes
+
+    /// Cumulative providers fees charged in quote tokens.
+    cumulative_quote_providers_fees: u128, // 16 bytes
+
+    /// Cumulative protocol fees charged in base tokens.
+    cumulative_base_protocol_fees: u128, // 16 bytes
+
+    /// Cumulative protocol fees charged in quote tokens.
+    cumulative_quote_protocol_fees: u128, // 16 bytes
+}
+
+impl PoolStats {
+    /// Returns the cumulative swapped volume in base tokens.
+    #[inline]
+    pub fn cumulative_base_volume(&self) -> u128 {
+        self.cumulative_base_volume
+    }
+
+    /// Returns the cumulative swapped volume in quote tokens.
+    #[inline]
+    pub fn cumulative_quote_volume(&self) -> u128 {
+        self.cumulative_quote_volume
+    }
+
+    /// Returns the cumulative swapped volume in USD.
+    #[inline]
+    pub fn cumulative_usd_volume(&self) -> u128 {
+        self.cumulative_usd_volume
+    }
+
+    /// Returns the cumulative providers fees charged in base tokens.
+    #[inline]
+    pub fn cumulative_base_providers_fees(&self) -> u128 {
+        self.cumulative_base_providers_fees
+    }
+
+    /// Returns the cumulative providers fees charged in quote tokens.
+    #[inline]
+    pub fn cumulative_quote_providers_fees(&self) -> u128 {
+        self.cumulative_quote_providers_fees
+    }
+
+    /// Returns the cumulative protocol fees charged in base tokens.
+    #[inline]
+    pub fn cumulative_base_protocol_fees(&self) -> u128 {
+        self.cumulative_base_protocol_fees
+    }
+
+    /// Returns the cumulative protocol fees charged in quote tokens.
+    #[inline]
+    pub fn cumulative_quote_protocol_fees(&self) -> u128 {
+        self.cumulative_quote_protocol_fees
+    }
+
+    /// Calculates the statistics after a swap.
+    ///
+    /// Counters saturate instead of overflowing, so statistics never block swaps.
+    ///
+    /// # Parameters
+    /// - `base_volume`: The base tokens amount of the swap.
+    /// - `quote_volume`: The quote tokens amount of the swap.
+    /// - `usd_volume`: The USD value of the swap, if known.
+    pub(crate) fn record_swap(&self, base_volume: u64, quote_volume: u64, usd_volume: Option<u64>) -> Self {
+        Self {
+            cumulative_base_volume: self.cumulative_base_volume.saturating_add(base_volume as u128),
+            cumulative_quote_volume: self.cumulative_quote_volume.saturating_add(quote_volume as u128),
+            cumulative_usd_volume: self.cumulative_usd_volume.saturating_add(usd_volume.unwrap_or(0) as u128),
+            ..*self
+        }
+    }
+
+    /// Calculates the statistics after charging swap fees.
+    ///
+    /// Counters saturate instead of overflowing, so statistics never block swaps.
+    ///
+    /// # Parameters
+    /// - `providers_fee`: The providers fee amount charged by the swap.
+    /// - `protocol_fee`: The protocol fee amount charged by the swap.
+    /// - `is_base`: `true` if fees were charged in base tokens, `false` for quote tokens.
+    pub(crate) fn record_fees(&self, providers_fee: u64, protocol_fee: u64, is_base: bool) -> Self {
+        if is_base {
+            Self {
+                cumulative_base_providers_fees: self.cumulative_base_providers_fees.saturating_add(providers_fee as u128),
+                cumulative_base_protocol_fees: self.cumulative_base_protocol_fees.saturating_add(protocol_fee as u128),
+                ..*self
+            }
+        } else {
+            Self {
+                cumulative_quote_providers_fees: self.cumulative_quote_providers_fees.saturating_add(providers_fee as u128),
+                cumulative_quote_protocol_fees: self.cumulative_quote_protocol_fees.saturating_add(protocol_fee as u128),
+                ..*self
+            }
+        }
+    }
+
+    /// Calculates the statistics combined with another set of statistics of the same pool.
+    ///
+    /// Used to aggregate the statistics recorded on the `CpAmm` and its `PoolStatsShard` accounts.
+    /// Counters saturate instead of overflowing, so statistics never block swaps.
+    ///
+    /// # Parameters
+    /// - `other`: The statistics to add.
+    pub(crate) fn merge(&self, other: &PoolStats) -> Self {
+        Self {
+            cumulative_base_volume: self.cumulative_base_volume.saturating_add(other.cumulative_base_volume),
+            cumulative_quote_volume: self.cumulative_quote_volume.saturating_add(other.cumulative_quote_volume),
+            cumulative_usd_volume: self.cumulative_usd_volume.saturating_add(other.cumulative_usd_volume),
+            cumulative_base_providers_fees: self.cumulative_base_providers_fees.saturating_add(other.cumulative_base_providers_fees),
+            cumulative_quote_providers_fees: self.cumulative_quote_providers_fees.saturating_add(other.cumulative_quote_providers_fees),
+            cumulative_base_protocol_fees: self.cumulative_base_protocol_fees.saturating_add(other.cumulative_base_protocol_fees),
+            cumulative_quote_protocol_fees: self.cumulative_quote_protocol_fees.saturating_add(other.cumulative_quote_protocol_fees),
+        }
+    }
+}
+
+#[cfg(test)]
+mod pool_stats_tests {
+    use super::*;
+
+    /// Tests volumes accumulation with and without a USD value.
+    #[test]
+    fn test_record_swap() {
+        let stats = PoolStats::default()
+            .record_swap(1_000, 2_000, Some(3_000))
+            .record_swap(500, 700, None);
+
+        assert_eq!(stats.cumulative_base_volume(), 1_500);
+        assert_eq!(stats.cumulative_quote_volume(), 2_700);
+        assert_eq!(stats.cumulative_usd_volume(), 3_000);
+        assert_eq!(stats.cumulative_base_providers_fees(), 0);
+        assert_eq!(stats.cumulative_quote_protocol_fees(), 0);
+    }
+
+    /// Tests fees accumulation on both sides.
+    #[test]
+    fn test_record_fees() {
+        let stats = PoolStats::default()
+            .record_swap(1_000, 2_000, None)
+            .record_fees(30, 10, true)
+            .record_fees(50, 0, false)
+            .record_fees(5, 2, true);
+
+        assert_eq!(stats.cumulative_base_providers_fees(), 35);
+        assert_eq!(stats.cumulative_base_protocol_fees(), 12);
+        assert_eq!(stats.cumulative_quote_providers_fees(), 50);
+        assert_eq!(stats.cumulative_quote_protocol_fees(), 0);
+        assert_eq!(stats.cumulative_base_volume(), 1_000);
+
+        let stats = stats.record_fees(u64::MAX, u64::MAX, false).record_fees(u64::MAX, u64::MAX, false);
+        assert_eq!(stats.cumulative_quote_providers_fees(), 50 + 2 * u64::MAX as u128);
+    }
+    /// Tests merging statistics of the pool and its shards.
+    #[test]
+    fn test_merge() {
+        let pool_stats = PoolStats::default().record_swap(1_000, 2_000, Some(3_000)).record_fees(30, 10, true);
+        let shard_stats = PoolStats::default().record_swap(500, 700, None).record_fees(50, 5, false);
+        let stats = pool_stats.merge(&shard_stats);
+
+        assert_eq!(stats.cumulative_base_volume(), 1_500);
+        assert_eq!(stats.cumulative_quote_volume(), 2_700);
+        assert_eq!(stats.cumulative_usd_volume(), 3_000);
+        assert_eq!(stats.cumulative_base_providers_fees(), 30);
+        assert_eq!(stats.cumulative_base_protocol_fees(), 10);
+        assert_eq!(stats.cumulative_quote_providers_fees(), 50);
+        assert_eq!(stats.cumulative_quote_protocol_fees(), 5);
+        assert_eq!(stats.merge(&PoolStats::default()), stats);
+
+        let saturated = PoolStats { cumulative_base_volume: u128::MAX, ..PoolStats::default() };
+        assert_eq!(saturated.merge(&stats).cumulative_base_volume(), u128::MAX);
+    }
+}
\ No newline at end of file
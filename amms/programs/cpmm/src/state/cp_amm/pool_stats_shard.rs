@@ -0,0 +1,185 @@
+use anchor_lang::{account, InitSpace};
+use anchor_lang::prelude::*;
+use anchor_lang::error::ErrorCode;
+use super::PoolStats;
+
+/// Side account holding a share of the trading statistics of a `CpAmm`.
+///
+/// Swaps passing a shard record their statistics on it instead of the `CpAmm`, so ancillary data
+/// doesn't grow the hot pool account and concurrent swaps may spread their statistics writes
+/// over `SHARDS_COUNT` accounts. Clients pick the shard by slot parity, see `shard_index_at`.
+/// The pool statistics are the sum of the `CpAmm` statistics and the statistics of all its shards.
+#[account]
+#[derive(InitSpace)]
+pub struct PoolStatsShard {
+    /// The canonical bump seed used for the account's PDA.
+    bump: u8, // 1 byte
+
+    /// The public key of the `CpAmm` the statistics belong to.
+    cp_amm: Pubkey, // 32 bytes
+
+    /// The index of the shard, lower than `SHARDS_COUNT`.
+    shard_index: u8, // 1 byte
+
+    /// The trading statistics recorded on the shard.
+    stats: PoolStats, // 112 bytes
+}
+
+impl PoolStatsShard {
+    /// The seed used to derive the account's PDA.
+    pub const SEED: &'static [u8] = b"pool_stats_shard";
+
+    /// The number of shards of a `CpAmm`.
+    pub const SHARDS_COUNT: u8 = 2;
+
+    /// Returns the index of the shard to use for a swap in the given slot.
+    ///
+    /// Any initialized shard is accepted by swaps, so a transaction landing in
+    /// another slot than expected still succeeds.
+    ///
+    /// # Parameters
+    /// - `slot`: The slot the swap is expected to land in.
+    pub fn shard_index_at(slot: u64) -> u8 {
+        (slot % Self::SHARDS_COUNT as u64) as u8
+    }
+
+    /// Initializes the `PoolStatsShard` of a pool.
+    ///
+    /// # Parameters
+    /// - `cp_amm`: The public key of the `CpAmm`.
+    /// - `shard_index`: The index of the shard.
+    /// - `bump`: The bump seed for the account's PDA.
+    pub(crate) fn initialize(&mut self, cp_amm: Pubkey, shard_index: u8, bump: u8) {
+        self.bump = bump;
+        self.cp_amm = cp_amm;
+        self.shard_index = shard_index;
+        self.stats = PoolStats::default();
+    }
+
+    /// Adds the statistics recorded by a swap to the shard.
+    ///
+    /// # Parameters
+    /// - `stats`: The statistics recorded by the swap.
+    pub(crate) fn record(&mut self, stats: &PoolStats) {
+        self.stats = self.stats.merge(stats);
+    }
+
+    /// Calculates the total trading statistics of a pool.
+    ///
+    /// Shard accounts that are not initialized yet are skipped, so the accounts must be validated
+    /// to be the shard PDAs of the pool by the caller.
+    ///
+    /// # Parameters
+    /// - `cp_amm_stats`: The statistics recorded on the `CpAmm`.
+    /// - `shard_accounts`: The `PoolStatsShard` accounts of the pool.
+    pub(crate) fn aggregate_stats(cp_amm_stats: &PoolStats, shard_accounts: &[&AccountInfo]) -> Result<PoolStats> {
+        let mut stats = *cp_amm_stats;
+        for shard_account in shard_accounts {
+            if shard_account.data_is_empty() {
+                continue;
+            }
+            require_keys_eq!(*shard_account.owner, crate::ID, ErrorCode::AccountOwnedByWrongProgram);
+            let shard = PoolStatsShard::try_deserialize(&mut shard_account.try_borrow_data()?.as_ref())?;
+            stats = stats.merge(shard.stats());
+        }
+        Ok(stats)
+    }
+
+    /// Returns the bump seed used for the account's PDA.
+    #[inline]
+    pub fn bump(&self) -> u8 {
+        self.bump
+    }
+
+    /// Returns the public key of the `CpAmm` the statistics belong to.
+    #[inline]
+    pub fn cp_amm(&self) -> &Pubkey {
+        &self.cp_amm
+    }
+
+    /// Returns the index of the shard.
+    #[inline]
+    pub fn shard_index(&self) -> u8 {
+        self.shard_index
+    }
+
+    /// Returns the trading statistics recorded on the shard.
+    #[inline]
+    pub fn stats(&self) -> &PoolStats {
+        &self.stats
+    }
+}
+
+#[cfg(test)]
+mod pool_stats_shard_tests {
+    use anchor_lang::Discriminator;
+    use crate::constants::ANCHOR_DISCRIMINATOR;
+    use super::*;
+
+    /// Tests the initialization of the `PoolStatsShard` struct and recording swaps on it.
+    #[test]
+    fn test_pool_stats_shard_initialize_and_record() {
+        let mut shard = PoolStatsShard {
+            bump: 0,
+            cp_amm: Pubkey::default(),
+            shard_index: 0,
+            stats: PoolStats::default().record_swap(1, 1, None),
+        };
+        let cp_amm = Pubkey::new_unique();
+        shard.initialize(cp_amm, 1, 42);
+
+        assert_eq!(shard.bump(), 42);
+        assert_eq!(shard.cp_amm().key(), cp_amm);
+        assert_eq!(shard.shard_index(), 1);
+        assert_eq!(*shard.stats(), PoolStats::default());
+
+        let swap_stats = PoolStats::default().record_swap(1_000, 2_000, None).record_fees(3, 1, true);
+        shard.record(&swap_stats);
+        shard.record(&swap_stats);
+        assert_eq!(*shard.stats(), swap_stats.merge(&swap_stats));
+    }
+
+    /// Tests the shard selection by slot parity.
+    #[test]
+    fn test_pool_stats_shard_index_at() {
+        assert_eq!(PoolStatsShard::shard_index_at(0), 0);
+        assert_eq!(PoolStatsShard::shard_index_at(1), 1);
+        assert_eq!(PoolStatsShard::shard_index_at(u64::MAX), 1);
+        assert!((0..100).all(|slot| PoolStatsShard::shard_index_at(slot) < PoolStatsShard::SHARDS_COUNT));
+    }
+
+    /// Tests the data layout of the `PoolStatsShard` struct.
+    #[test]
+    fn test_pool_stats_shard_data_layout() {
+        let bump = 42u8;
+        let cp_amm = Pubkey::new_unique();
+        let shard_index = 1u8;
+        let cumulative_base_volume = 1234u128;
+        let cumulative_quote_protocol_fees = 5678u128;
+
+        let mut data = [0u8; ANCHOR_DISCRIMINATOR + 146];
+        let mut offset = 0;
+
+        data[offset..offset + ANCHOR_DISCRIMINATOR].copy_from_slice(&PoolStatsShard::discriminator()); offset += ANCHOR_DISCRIMINATOR;
+        data[offset] = bump; offset += 1;
+        data[offset..offset + 32].copy_from_slice(cp_amm.as_ref()); offset += 32;
+        data[offset] = shard_index; offset += 1;
+        data[offset..offset + 16].copy_from_slice(&cumulative_base_volume.to_le_bytes()); offset += 16;
+        offset += 16 * 5;
+        data[offset..offset + 16].copy_from_slice(&cumulative_quote_protocol_fees.to_le_bytes()); offset += 16;
+
+        assert_eq!(offset, ANCHOR_DISCRIMINATOR + PoolStatsShard::INIT_SPACE);
+
+        let deserialized_shard = PoolStatsShard::try_deserialize(&mut data.as_ref()).unwrap();
+
+        assert_eq!(deserialized_shard.bump, bump);
+        assert_eq!(deserialized_shard.cp_amm, cp_amm);
+        assert_eq!(deserialized_shard.shard_index, shard_index);
+        assert_eq!(deserialized_shard.stats.cumulative_base_volume(), cumulative_base_volume);
+        assert_eq!(deserialized_shard.stats.cumulative_quote_protocol_fees(), cumulative_quote_protocol_fees);
+
+        let mut serialized_data = Vec::new();
+        deserialized_shard.try_serialize(&mut serialized_data).unwrap();
+        assert_eq!(serialized_data.as_slice(), data.as_ref());
+    }
+}
\ No newline at end of file
@@ -0,0 +1,186 @@
+use anchor_lang::prelude::*;
+use crate::error::ErrorCode;
+
+/// Lifecycle status of a `CpAmm`.
+///
+/// A pool goes through `Uninitialized -> Initialized -> Launched`. A launched pool can be
+/// paused and resumed, and both launched and paused pools can be retired, which is final.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, AnchorSerialize, AnchorDeserialize, InitSpace)]
+pub enum PoolStatus {
+    /// The account has been allocated, but the pool hasn't been set up yet.
+    #[default]
+    Uninitialized,
+    /// The pool has been set up and awaits its launch liquidity.
+    Initialized,
+    /// The pool has been launched and is active.
+    Launched,
+    /// The pool has been launched, but operations are suspended.
+    Paused,
+    /// The pool has been permanently shut down.
+    Retired,
+}
+
+impl PoolStatus {
+    /// Checks if the pool has been initialized.
+    #[inline]
+    pub fn is_initialized(&self) -> bool {
+        *self != Self::Uninitialized
+    }
+
+    /// Checks if the pool has been launched and is active.
+    #[inline]
+    pub fn is_launched(&self) -> bool {
+        *self == Self::Launched
+    }
+
+    /// Returns the status after the pool initialization.
+    ///
+    /// # Errors
+    /// - Returns `ErrorCode::CpAmmAlreadyInitialized` if the pool isn't `Uninitialized`.
+    pub fn initialize(self) -> Result<Self> {
+        require!(self == Self::Uninitialized, ErrorCode::CpAmmAlreadyInitialized);
+        Ok(Self::Initialized)
+    }
+
+    /// Returns the status after the pool launch.
+    ///
+    /// # Errors
+    /// - Returns `ErrorCode::CpAmmNotInitialized` if the pool is `Uninitialized`.
+    /// - Returns `ErrorCode::CpAmmAlreadyLaunched` if the pool has already been launched.
+    pub fn launch(self) -> Result<Self> {
+        match self {
+            Self::Initialized => Ok(Self::Launched),
+            Self::Uninitialized => err!(ErrorCode::CpAmmNotInitialized),
+            Self::Launched | Self::Paused | Self::Retired => err!(ErrorCode::CpAmmAlreadyLaunched),
+        }
+    }
+
+    /// Returns the status after pausing the pool.
+    ///
+    /// # Errors
+    /// - Returns `ErrorCode::CpAmmStatusTransitionInvalid` if the pool isn't `Launched`.
+    pub fn pause(self) -> Result<Self> {
+        require!(self == Self::Launched, ErrorCode::CpAmmStatusTransitionInvalid);
+        Ok(Self::Paused)
+    }
+
+    /// Returns the status after resuming a paused pool.
+    ///
+    /// # Errors
+    /// - Returns `ErrorCode::CpAmmStatusTransitionInvalid` if the pool isn't `Paused`.
+    pub fn resume(self) -> Result<Self> {
+        require!(self == Self::Paused, ErrorCode::CpAmmStatusTransitionInvalid);
+        Ok(Self::Launched)
+    }
+
+    /// Returns the status after retiring the pool.
+    ///
+    /// # Errors
+    /// - Returns `ErrorCode::CpAmmStatusTransitionInvalid` if the pool isn't `Launched` or `Paused`.
+    pub fn retire(self) -> Result<Self> {
+        require!(matches!(self, Self::Launched | Self::Paused), ErrorCode::CpAmmStatusTransitionInvalid);
+        Ok(Self::Retired)
+    }
+}
+
+#[cfg(test)]
+mod pool_status_tests {
+    use super::*;
+
+    const ALL: [PoolStatus; 5] = [
+        PoolStatus::Uninitialized,
+        PoolStatus::Initialized,
+        PoolStatus::Launched,
+        PoolStatus::Paused,
+        PoolStatus::Retired,
+    ];
+
+    /// Asserts the outcome of a transition from every status.
+    fn assert_transitions(transition: fn(PoolStatus) -> Result<PoolStatus>, expected: [std::result::Result<PoolStatus, ErrorCode>; 5]) {
+        for (status, expected) in ALL.into_iter().zip(expected) {
+            match expected {
+                Ok(next) => assert_eq!(transition(status).unwrap(), next, "from {:?}", status),
+                Err(error) => assert_eq!(transition(status).unwrap_err(), error.into(), "from {:?}", status),
+            }
+        }
+    }
+
+    #[test]
+    fn test_pool_status_default() {
+        assert_eq!(PoolStatus::default(), PoolStatus::Uninitialized);
+    }
+
+    #[test]
+    fn test_pool_status_flags() {
+        let initialized = ALL.map(|status| status.is_initialized());
+        assert_eq!(initialized, [false, true, true, true, true]);
+
+        let launched = ALL.map(|status| status.is_launched());
+        assert_eq!(launched, [false, false, true, false, false]);
+    }
+
+    #[test]
+    fn test_pool_status_initialize() {
+        assert_transitions(PoolStatus::initialize, [
+            Ok(PoolStatus::Initialized),
+            Err(ErrorCode::CpAmmAlreadyInitialized),
+            Err(ErrorCode::CpAmmAlreadyInitialized),
+            Err(ErrorCode::CpAmmAlreadyInitialized),
+            Err(ErrorCode::CpAmmAlreadyInitialized),
+        ]);
+    }
+
+    #[test]
+    fn test_pool_status_launch() {
+        assert_transitions(PoolStatus::launch, [
+            Err(ErrorCode::CpAmmNotInitialized),
+            Ok(PoolStatus::Launched),
+            Err(ErrorCode::CpAmmAlreadyLaunched),
+            Err(ErrorCode::CpAmmAlreadyLaunched),
+            Err(ErrorCode::CpAmmAlreadyLaunched),
+        ]);
+    }
+
+    #[test]
+    fn test_pool_status_pause() {
+        assert_transitions(PoolStatus::pause, [
+            Err(ErrorCode::CpAmmStatusTransitionInvalid),
+            Err(ErrorCode::CpAmmStatusTransitionInvalid),
+            Ok(PoolStatus::Paused),
+            Err(ErrorCode::CpAmmStatusTransitionInvalid),
+            Err(ErrorCode::CpAmmStatusTransitionInvalid),
+        ]);
+    }
+
+    #[test]
+    fn test_pool_status_resume() {
+        assert_transitions(PoolStatus::resume, [
+            Err(ErrorCode::CpAmmStatusTransitionInvalid),
+            Err(ErrorCode::CpAmmStatusTransitionInvalid),
+            Err(ErrorCode::CpAmmStatusTransitionInvalid),
+            Ok(PoolStatus::Launched),
+            Err(ErrorCode::CpAmmStatusTransitionInvalid),
+        ]);
+    }
+
+    #[test]
+    fn test_pool_status_retire() {
+        assert_transitions(PoolStatus::retire, [
+            Err(ErrorCode::CpAmmStatusTransitionInvalid),
+            Err(ErrorCode::CpAmmStatusTransitionInvalid),
+            Ok(PoolStatus::Retired),
+            Ok(PoolStatus::Retired),
+            Err(ErrorCode::CpAmmStatusTransitionInvalid),
+        ]);
+    }
+
+    #[test]
+    fn test_pool_status_serialization() {
+        for (index, status) in ALL.into_iter().enumerate() {
+            let data = status.try_to_vec().unwrap();
+            assert_eq!(data, vec![index as u8]);
+            assert_eq!(PoolStatus::try_from_slice(&data).unwrap(), status);
+        }
+        assert_eq!(PoolStatus::INIT_SPACE, 1);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,257 @@
+use anchor_lang::{account, InitSpace};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+use crate::error::ErrorCode;
+
+/// On-chain anchor of the LP holder balances of a `CpAmm` at a given slot.
+///
+/// The merkle tree of the balances is computed off-chain and only its root is recorded, so airdrops
+/// and governance votes based on LP positions can verify a holder's balance against the snapshot
+/// with `verify_leaf` without trusting the distributor.
+///
+/// Leaves are `hash(0x00 || holder || lp_tokens_le)` and nodes are `hash(0x01 || min(a, b) || max(a, b))`,
+/// the prefixes keeping a node from being passed off as a leaf and the sorted pairs making proofs
+/// position-independent.
+#[account]
+#[derive(InitSpace)]
+pub struct Snapshot {
+    /// The canonical bump seed used for the account's PDA.
+    bump: u8, // 1 byte
+
+    /// The public key of the `CpAmm` the LP tokens belong to.
+    cp_amm: Pubkey, // 32 bytes
+
+    /// The slot the LP holder balances were taken at.
+    slot: u64, // 8 bytes
+
+    /// The root of the merkle tree of the LP holder balances.
+    merkle_root: [u8; 32], // 32 bytes
+
+    /// The number of leaves of the merkle tree.
+    holders_count: u32, // 4 bytes
+
+    /// The total amount of LP tokens held by the snapshotted holders.
+    total_lp_tokens: u64, // 8 bytes
+}
+
+impl Snapshot {
+    /// The seed used to derive the account's PDA.
+    pub const SEED: &'static [u8] = b"snapshot";
+
+    const LEAF_PREFIX: u8 = 0;
+    const NODE_PREFIX: u8 = 1;
+
+    /// Initializes the `Snapshot` of a pool.
+    ///
+    /// # Parameters
+    /// - `cp_amm`: The public key of the `CpAmm`.
+    /// - `slot`: The slot the balances were taken at, not later than `current_slot`.
+    /// - `merkle_root`: The non-zero root of the merkle tree of the balances.
+    /// - `holders_count`: The non-zero number of leaves of the merkle tree.
+    /// - `total_lp_tokens`: The non-zero sum of the balances, not exceeding `lp_tokens_supply`.
+    /// - `current_slot`: The current slot.
+    /// - `lp_tokens_supply`: The LP tokens supply of the `CpAmm`.
+    /// - `bump`: The bump seed for the account's PDA.
+    ///
+    /// # Errors
+    /// - Returns `SnapshotInvalid` if any of the above bounds is violated.
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn initialize(
+        &mut self,
+        cp_amm: Pubkey,
+        slot: u64,
+        merkle_root: [u8; 32],
+        holders_count: u32,
+        total_lp_tokens: u64,
+        current_slot: u64,
+        lp_tokens_supply: u64,
+        bump: u8,
+    ) -> Result<()> {
+        require!(merkle_root != [0u8; 32], ErrorCode::SnapshotInvalid);
+        require!(holders_count > 0, ErrorCode::SnapshotInvalid);
+        require!(total_lp_tokens > 0 && total_lp_tokens <= lp_tokens_supply, ErrorCode::SnapshotInvalid);
+        require!(slot <= current_slot, ErrorCode::SnapshotInvalid);
+
+        self.bump = bump;
+        self.cp_amm = cp_amm;
+        self.slot = slot;
+        self.merkle_root = merkle_root;
+        self.holders_count = holders_count;
+        self.total_lp_tokens = total_lp_tokens;
+        Ok(())
+    }
+
+    /// Calculates the merkle leaf of a holder's balance.
+    ///
+    /// # Parameters
+    /// - `holder`: The public key of the LP tokens holder.
+    /// - `lp_tokens`: The amount of LP tokens held.
+    pub fn leaf_hash(holder: &Pubkey, lp_tokens: u64) -> [u8; 32] {
+        hashv(&[&[Self::LEAF_PREFIX], holder.as_ref(), &lp_tokens.to_le_bytes()]).to_bytes()
+    }
+
+    /// Calculates the merkle node of two children, in any order.
+    pub fn node_hash(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+        let (left, right) = if a <= b { (a, b) } else { (b, a) };
+        hashv(&[&[Self::NODE_PREFIX], left, right]).to_bytes()
+    }
+
+    /// Returns the maximum proof length of the tree, its depth.
+    pub fn max_proof_len(&self) -> usize {
+        (u32::BITS - self.holders_count.saturating_sub(1).leading_zeros()) as usize
+    }
+
+    /// Verifies that a holder's balance is a leaf of the snapshot.
+    ///
+    /// # Parameters
+    /// - `holder`: The public key of the LP tokens holder.
+    /// - `lp_tokens`: The amount of LP tokens held.
+    /// - `proof`: The sibling hashes from the leaf up to the root.
+    ///
+    /// # Errors
+    /// - Returns `SnapshotProofInvalid` if the proof is longer than the tree depth, the balance exceeds
+    ///   the snapshotted total or the proof doesn't lead to the merkle root.
+    pub fn verify_leaf(&self, holder: &Pubkey, lp_tokens: u64, proof: &[[u8; 32]]) -> Result<()> {
+        require!(proof.len() <= self.max_proof_len(), ErrorCode::SnapshotProofInvalid);
+        require!(lp_tokens <= self.total_lp_tokens, ErrorCode::SnapshotProofInvalid);
+        let root = proof.iter().fold(Self::leaf_hash(holder, lp_tokens), |node, sibling| Self::node_hash(&node, sibling));
+        require!(root == self.merkle_root, ErrorCode::SnapshotProofInvalid);
+        Ok(())
+    }
+
+    /// Returns the bump seed used for the account's PDA.
+    #[inline]
+    pub fn bump(&self) -> u8 {
+        self.bump
+    }
+
+    /// Returns the public key of the `CpAmm` the LP tokens belong to.
+    #[inline]
+    pub fn cp_amm(&self) -> &Pubkey {
+        &self.cp_amm
+    }
+
+    /// Returns the slot the LP holder balances were taken at.
+    #[inline]
+    pub fn slot(&self) -> u64 {
+        self.slot
+    }
+
+    /// Returns the root of the merkle tree of the LP holder balances.
+    #[inline]
+    pub fn merkle_root(&self) -> &[u8; 32] {
+        &self.merkle_root
+    }
+
+    /// Returns the number of leaves of the merkle tree.
+    #[inline]
+    pub fn holders_count(&self) -> u32 {
+        self.holders_count
+    }
+
+    /// Returns the total amount of LP tokens held by the snapshotted holders.
+    #[inline]
+    pub fn total_lp_tokens(&self) -> u64 {
+        self.total_lp_tokens
+    }
+}
+
+#[cfg(test)]
+mod snapshot_tests {
+    use anchor_lang::Discriminator;
+    use crate::constants::ANCHOR_DISCRIMINATOR;
+    use super::*;
+
+    fn empty_snapshot() -> Snapshot {
+        Snapshot {
+            bump: 0,
+            cp_amm: Pubkey::default(),
+            slot: 0,
+            merkle_root: [0u8; 32],
+            holders_count: 0,
+            total_lp_tokens: 0,
+        }
+    }
+
+    /// Tests the initialization of the `Snapshot` struct and its bounds.
+    #[test]
+    fn test_snapshot_initialize() {
+        let cp_amm = Pubkey::new_unique();
+        let root = [7u8; 32];
+
+        let mut snapshot = empty_snapshot();
+        assert!(snapshot.initialize(cp_amm, 10, [0u8; 32], 1, 1, 10, 1, 42).is_err());
+        assert!(snapshot.initialize(cp_amm, 10, root, 0, 1, 10, 1, 42).is_err());
+        assert!(snapshot.initialize(cp_amm, 10, root, 1, 0, 10, 1, 42).is_err());
+        assert!(snapshot.initialize(cp_amm, 10, root, 1, 2, 10, 1, 42).is_err());
+        assert!(snapshot.initialize(cp_amm, 11, root, 1, 1, 10, 1, 42).is_err());
+
+        snapshot.initialize(cp_amm, 10, root, 3, 1_000, 10, 1_000, 42).unwrap();
+        assert_eq!(snapshot.bump(), 42);
+        assert_eq!(snapshot.cp_amm().key(), cp_amm);
+        assert_eq!(snapshot.slot(), 10);
+        assert_eq!(*snapshot.merkle_root(), root);
+        assert_eq!(snapshot.holders_count(), 3);
+        assert_eq!(snapshot.total_lp_tokens(), 1_000);
+        assert_eq!(snapshot.max_proof_len(), 2);
+    }
+
+    /// Tests the verification of the leaves of a three holders tree.
+    #[test]
+    fn test_snapshot_verify_leaf() {
+        let holders = [(Pubkey::new_unique(), 100u64), (Pubkey::new_unique(), 200), (Pubkey::new_unique(), 300)];
+        let leaves: Vec<[u8; 32]> = holders.iter().map(|(holder, lp_tokens)| Snapshot::leaf_hash(holder, *lp_tokens)).collect();
+        let node_01 = Snapshot::node_hash(&leaves[0], &leaves[1]);
+        let root = Snapshot::node_hash(&node_01, &leaves[2]);
+
+        let mut snapshot = empty_snapshot();
+        snapshot.initialize(Pubkey::new_unique(), 1, root, 3, 600, 1, 1_000, 0).unwrap();
+
+        assert!(snapshot.verify_leaf(&holders[0].0, 100, &[leaves[1], leaves[2]]).is_ok());
+        assert!(snapshot.verify_leaf(&holders[1].0, 200, &[leaves[0], leaves[2]]).is_ok());
+        assert!(snapshot.verify_leaf(&holders[2].0, 300, &[node_01]).is_ok());
+
+        assert!(snapshot.verify_leaf(&holders[0].0, 101, &[leaves[1], leaves[2]]).is_err());
+        assert!(snapshot.verify_leaf(&holders[1].0, 100, &[leaves[1], leaves[2]]).is_err());
+        assert!(snapshot.verify_leaf(&holders[2].0, 300, &[]).is_err());
+        assert!(snapshot.verify_leaf(&holders[0].0, 100, &[leaves[1], leaves[2], leaves[0]]).is_err());
+        assert!(snapshot.verify_leaf(&holders[0].0, u64::MAX, &[leaves[1], leaves[2]]).is_err());
+    }
+
+    /// Tests the data layout of the `Snapshot` struct.
+    #[test]
+    fn test_snapshot_data_layout() {
+        let bump = 42u8;
+        let cp_amm = Pubkey::new_unique();
+        let slot = 1234u64;
+        let merkle_root = [9u8; 32];
+        let holders_count = 56u32;
+        let total_lp_tokens = 7890u64;
+
+        let mut data = [0u8; ANCHOR_DISCRIMINATOR + 85];
+        let mut offset = 0;
+
+        data[offset..offset + ANCHOR_DISCRIMINATOR].copy_from_slice(&Snapshot::discriminator()); offset += ANCHOR_DISCRIMINATOR;
+        data[offset] = bump; offset += 1;
+        data[offset..offset + 32].copy_from_slice(cp_amm.as_ref()); offset += 32;
+        data[offset..offset + 8].copy_from_slice(&slot.to_le_bytes()); offset += 8;
+        data[offset..offset + 32].copy_from_slice(&merkle_root); offset += 32;
+        data[offset..offset + 4].copy_from_slice(&holders_count.to_le_bytes()); offset += 4;
+        data[offset..offset + 8].copy_from_slice(&total_lp_tokens.to_le_bytes()); offset += 8;
+
+        assert_eq!(offset, ANCHOR_DISCRIMINATOR + Snapshot::INIT_SPACE);
+
+        let deserialized_snapshot = Snapshot::try_deserialize(&mut data.as_ref()).unwrap();
+
+        assert_eq!(deserialized_snapshot.bump, bump);
+        assert_eq!(deserialized_snapshot.cp_amm, cp_amm);
+        assert_eq!(deserialized_snapshot.slot, slot);
+        assert_eq!(deserialized_snapshot.merkle_root, merkle_root);
+        assert_eq!(deserialized_snapshot.holders_count, holders_count);
+        assert_eq!(deserialized_snapshot.total_lp_tokens, total_lp_tokens);
+
+        let mut serialized_data = Vec::new();
+        deserialized_snapshot.try_serialize(&mut serialized_data).unwrap();
+        assert_eq!(serialized_data.as_slice(), data.as_ref());
+    }
+}
\ No newline at end of file
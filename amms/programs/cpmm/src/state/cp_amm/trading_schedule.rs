@@ -0,0 +1,95 @@
+use anchor_lang::prelude::*;
+
+/// Weekly trading hours of a `CpAmm`.
+///
+/// The schedule is a bitmap of the 168 hours of a week in UTC, starting on Monday 00:00.
+/// Bit `i % 8` of byte `i / 8` is set when swaps are allowed during hour `i` of the week.
+#[derive(Debug, Clone, Copy, PartialEq, Default, AnchorSerialize, AnchorDeserialize, InitSpace)]
+pub struct TradingSchedule {
+    /// Bitmap of the open hours of the week.
+    open_hours: [u8; 21], // 21 bytes
+}
+
+impl TradingSchedule {
+    /// The number of hours in a week covered by the schedule.
+    pub const HOURS_PER_WEEK: i64 = 168;
+
+    /// Creates a schedule from the open hours bitmap.
+    pub fn new(open_hours: [u8; 21]) -> Self {
+        Self {
+            open_hours
+        }
+    }
+
+    /// Returns the bitmap of the open hours of the week.
+    #[inline]
+    pub fn open_hours(&self) -> &[u8; 21] {
+        &self.open_hours
+    }
+
+    /// Checks if trading is open at the given time.
+    ///
+    /// # Parameters
+    /// - `unix_timestamp`: The current unix timestamp in seconds.
+    ///
+    /// # Returns
+    /// - `true` if the hour of the week containing `unix_timestamp` is open.
+    pub fn is_open(&self, unix_timestamp: i64) -> bool {
+        // 1970-01-01 was a Thursday, so the epoch is 3 days into the week
+        let hour_of_week = (unix_timestamp.div_euclid(3600) + 3 * 24).rem_euclid(Self::HOURS_PER_WEEK) as usize;
+        self.open_hours[hour_of_week / 8] & (1 << (hour_of_week % 8)) != 0
+    }
+}
+
+#[cfg(test)]
+mod trading_schedule_tests {
+    use super::*;
+
+    /// Monday, 2024-01-01 00:00:00 UTC.
+    const MONDAY: i64 = 1_704_067_200;
+
+    /// Builds a schedule open on weekdays from 9:00 to 17:00 UTC.
+    fn weekdays_schedule() -> TradingSchedule {
+        let mut open_hours = [0u8; 21];
+        for day in 0..5 {
+            for hour in 9..17 {
+                let hour_of_week = day * 24 + hour;
+                open_hours[hour_of_week / 8] |= 1 << (hour_of_week % 8);
+            }
+        }
+        TradingSchedule::new(open_hours)
+    }
+
+    /// Tests `is_open` at the schedule boundaries.
+    #[test]
+    fn test_is_open() {
+        let schedule = weekdays_schedule();
+
+        assert!(!schedule.is_open(MONDAY));
+        assert!(!schedule.is_open(MONDAY + 9 * 3600 - 1));
+        assert!(schedule.is_open(MONDAY + 9 * 3600));
+        assert!(schedule.is_open(MONDAY + 17 * 3600 - 1));
+        assert!(!schedule.is_open(MONDAY + 17 * 3600));
+
+        // Friday and Saturday
+        assert!(schedule.is_open(MONDAY + 4 * 86400 + 12 * 3600));
+        assert!(!schedule.is_open(MONDAY + 5 * 86400 + 12 * 3600));
+
+        // Next week and timestamps before the epoch
+        assert!(schedule.is_open(MONDAY + 7 * 86400 + 12 * 3600));
+        assert!(schedule.is_open(MONDAY - 7 * 86400 + 12 * 3600));
+        assert!(!schedule.is_open(-1));
+        assert!(schedule.is_open(12 * 3600));
+    }
+
+    /// Tests fully open and fully closed schedules.
+    #[test]
+    fn test_is_open_full_week() {
+        let open = TradingSchedule::new([u8::MAX; 21]);
+        let closed = TradingSchedule::default();
+        for hour in 0..TradingSchedule::HOURS_PER_WEEK {
+            assert!(open.is_open(MONDAY + hour * 3600));
+            assert!(!closed.is_open(MONDAY + hour * 3600));
+        }
+    }
+}
\ No newline at end of file
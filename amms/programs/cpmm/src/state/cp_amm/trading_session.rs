@@ -0,0 +1,275 @@
+use anchor_lang::{account, InitSpace};
+use anchor_lang::prelude::*;
+use crate::error::ErrorCode;
+
+/// Authorizes a hot session key to swap in a `CpAmm` on behalf of an owner.
+///
+/// The owner approves the session as the delegate of its token accounts, so the session key never
+/// holds the owner's tokens and may only swap them back and forth between the owner's accounts.
+/// Each swap and the total volume of the session are bounded in quote tokens, and the session
+/// expires at a slot, after which it may only be closed.
+#[account]
+#[derive(InitSpace)]
+pub struct TradingSession {
+    /// The canonical bump seed used for the account's PDA.
+    bump: [u8; 1], // 1 byte
+
+    /// The public key of the `CpAmm` the session swaps in.
+    cp_amm: Pubkey, // 32 bytes
+
+    /// The public key of the wallet owning the swapped tokens.
+    owner: Pubkey, // 32 bytes
+
+    /// The public key of the session key allowed to sign swaps.
+    session_key: Pubkey, // 32 bytes
+
+    /// The maximal quote tokens amount of a single swap.
+    max_swap_quote_amount: u64, // 8 bytes
+
+    /// The maximal quote tokens volume of all swaps of the session.
+    max_total_quote_volume: u64, // 8 bytes
+
+    /// The quote tokens volume swapped in the session.
+    total_quote_volume: u64, // 8 bytes
+
+    /// The slot at which the session expires.
+    expires_at_slot: u64, // 8 bytes
+}
+
+impl TradingSession {
+    /// The seed used to derive the account's PDA.
+    pub const SEED: &'static [u8] = b"trading_session";
+
+    /// Initializes the `TradingSession` of an owner in a pool.
+    ///
+    /// # Parameters
+    /// - `cp_amm`: The public key of the `CpAmm`.
+    /// - `owner`: The public key of the wallet owning the swapped tokens.
+    /// - `session_key`: The public key of the session key.
+    /// - `max_swap_quote_amount`: The maximal quote tokens amount of a single swap.
+    /// - `max_total_quote_volume`: The maximal quote tokens volume of the session.
+    /// - `expires_at_slot`: The slot at which the session expires.
+    /// - `current_slot`: The current slot.
+    /// - `bump`: The bump seed for the account's PDA.
+    ///
+    /// # Errors
+    /// - Returns `ErrorCode::TradingSessionInvalid` if the session is already expired, its swap limit is zero
+    ///   or exceeds its total volume limit.
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn initialize(
+        &mut self,
+        cp_amm: Pubkey,
+        owner: Pubkey,
+        session_key: Pubkey,
+        max_swap_quote_amount: u64,
+        max_total_quote_volume: u64,
+        expires_at_slot: u64,
+        current_slot: u64,
+        bump: u8
+    ) -> Result<()> {
+        require!(
+            expires_at_slot > current_slot && max_swap_quote_amount > 0 && max_swap_quote_amount <= max_total_quote_volume,
+            ErrorCode::TradingSessionInvalid
+        );
+        self.bump = [bump];
+        self.cp_amm = cp_amm;
+        self.owner = owner;
+        self.session_key = session_key;
+        self.max_swap_quote_amount = max_swap_quote_amount;
+        self.max_total_quote_volume = max_total_quote_volume;
+        self.total_quote_volume = 0;
+        self.expires_at_slot = expires_at_slot;
+        Ok(())
+    }
+
+    /// Validates a swap against the limits of the session and adds it to the session volume.
+    ///
+    /// # Parameters
+    /// - `quote_amount`: The quote tokens swapped in or out of the pool.
+    /// - `current_slot`: The current slot.
+    ///
+    /// # Errors
+    /// - Returns `ErrorCode::TradingSessionExpired` if the session has expired.
+    /// - Returns `ErrorCode::TradingSessionSwapLimitExceeded` if the swap exceeds the swap limit.
+    /// - Returns `ErrorCode::TradingSessionVolumeLimitExceeded` if the swap exceeds the remaining volume.
+    pub(crate) fn record_swap(&mut self, quote_amount: u64, current_slot: u64) -> Result<()> {
+        require!(!self.is_expired(current_slot), ErrorCode::TradingSessionExpired);
+        require!(quote_amount <= self.max_swap_quote_amount, ErrorCode::TradingSessionSwapLimitExceeded);
+        require!(quote_amount <= self.remaining_quote_volume(), ErrorCode::TradingSessionVolumeLimitExceeded);
+        self.total_quote_volume += quote_amount;
+        Ok(())
+    }
+
+    /// Returns whether the session has expired at the given slot.
+    #[inline]
+    pub fn is_expired(&self, current_slot: u64) -> bool {
+        current_slot >= self.expires_at_slot
+    }
+
+    /// Returns the quote tokens volume the session may still swap.
+    #[inline]
+    pub fn remaining_quote_volume(&self) -> u64 {
+        self.max_total_quote_volume.saturating_sub(self.total_quote_volume)
+    }
+
+    /// Returns the seeds used to sign the transfers of the owner's tokens delegated to the session.
+    #[inline]
+    pub fn seeds(&self) -> [&[u8]; 5] {
+        [Self::SEED, self.cp_amm.as_ref(), self.owner.as_ref(), self.session_key.as_ref(), self.bump.as_ref()]
+    }
+
+    /// Returns the bump seed used for the account's PDA.
+    #[inline]
+    pub fn bump(&self) -> u8 {
+        self.bump[0]
+    }
+
+    /// Returns the public key of the `CpAmm` the session swaps in.
+    #[inline]
+    pub fn cp_amm(&self) -> &Pubkey {
+        &self.cp_amm
+    }
+
+    /// Returns the public key of the wallet owning the swapped tokens.
+    #[inline]
+    pub fn owner(&self) -> &Pubkey {
+        &self.owner
+    }
+
+    /// Returns the public key of the session key allowed to sign swaps.
+    #[inline]
+    pub fn session_key(&self) -> &Pubkey {
+        &self.session_key
+    }
+
+    /// Returns the maximal quote tokens amount of a single swap.
+    #[inline]
+    pub fn max_swap_quote_amount(&self) -> u64 {
+        self.max_swap_quote_amount
+    }
+
+    /// Returns the maximal quote tokens volume of all swaps of the session.
+    #[inline]
+    pub fn max_total_quote_volume(&self) -> u64 {
+        self.max_total_quote_volume
+    }
+
+    /// Returns the quote tokens volume swapped in the session.
+    #[inline]
+    pub fn total_quote_volume(&self) -> u64 {
+        self.total_quote_volume
+    }
+
+    /// Returns the slot at which the session expires.
+    #[inline]
+    pub fn expires_at_slot(&self) -> u64 {
+        self.expires_at_slot
+    }
+}
+
+#[cfg(test)]
+mod trading_session_tests {
+    use anchor_lang::Discriminator;
+    use crate::constants::ANCHOR_DISCRIMINATOR;
+    use super::*;
+
+    fn empty_session() -> TradingSession {
+        TradingSession {
+            bump: [0],
+            cp_amm: Pubkey::default(),
+            owner: Pubkey::default(),
+            session_key: Pubkey::default(),
+            max_swap_quote_amount: 0,
+            max_total_quote_volume: 0,
+            total_quote_volume: 0,
+            expires_at_slot: 0,
+        }
+    }
+
+    /// Tests the initialization of the `TradingSession` struct and the validation of its limits.
+    #[test]
+    fn test_trading_session_initialize() {
+        let (cp_amm, owner, session_key) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let mut session = empty_session();
+
+        assert_eq!(session.initialize(cp_amm, owner, session_key, 100, 1000, 500, 500, 42).err(), Some(ErrorCode::TradingSessionInvalid.into()));
+        assert_eq!(session.initialize(cp_amm, owner, session_key, 0, 1000, 600, 500, 42).err(), Some(ErrorCode::TradingSessionInvalid.into()));
+        assert_eq!(session.initialize(cp_amm, owner, session_key, 1001, 1000, 600, 500, 42).err(), Some(ErrorCode::TradingSessionInvalid.into()));
+
+        session.initialize(cp_amm, owner, session_key, 1000, 1000, 600, 500, 42).unwrap();
+        assert_eq!(session.bump(), 42);
+        assert_eq!(session.cp_amm(), &cp_amm);
+        assert_eq!(session.owner(), &owner);
+        assert_eq!(session.session_key(), &session_key);
+        assert_eq!(session.max_swap_quote_amount(), 1000);
+        assert_eq!(session.max_total_quote_volume(), 1000);
+        assert_eq!(session.total_quote_volume(), 0);
+        assert_eq!(session.expires_at_slot(), 600);
+        assert_eq!(session.seeds(), [TradingSession::SEED, cp_amm.as_ref(), owner.as_ref(), session_key.as_ref(), &[42]]);
+    }
+
+    /// Tests that swaps are bounded by the swap limit, the total volume and the expiration of the session.
+    #[test]
+    fn test_trading_session_record_swap() {
+        let mut session = empty_session();
+        session.initialize(Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique(), 400, 1000, 600, 500, 42).unwrap();
+
+        session.record_swap(400, 500).unwrap();
+        session.record_swap(350, 550).unwrap();
+        assert_eq!(session.total_quote_volume(), 750);
+        assert_eq!(session.remaining_quote_volume(), 250);
+
+        assert_eq!(session.record_swap(401, 550).err(), Some(ErrorCode::TradingSessionSwapLimitExceeded.into()));
+        assert_eq!(session.record_swap(251, 550).err(), Some(ErrorCode::TradingSessionVolumeLimitExceeded.into()));
+        assert_eq!(session.total_quote_volume(), 750);
+
+        session.record_swap(250, 599).unwrap();
+        assert_eq!(session.remaining_quote_volume(), 0);
+        assert_eq!(session.record_swap(0, 600).err(), Some(ErrorCode::TradingSessionExpired.into()));
+        assert!(session.is_expired(600));
+        assert!(!session.is_expired(599));
+    }
+
+    /// Tests the data layout of the `TradingSession` struct.
+    #[test]
+    fn test_trading_session_data_layout() {
+        let bump = 42u8;
+        let cp_amm = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        let session_key = Pubkey::new_unique();
+        let max_swap_quote_amount = 1_000u64;
+        let max_total_quote_volume = 10_000u64;
+        let total_quote_volume = 2_500u64;
+        let expires_at_slot = 123_456u64;
+
+        let mut data = [0u8; ANCHOR_DISCRIMINATOR + 129];
+        let mut offset = 0;
+
+        data[offset..offset + ANCHOR_DISCRIMINATOR].copy_from_slice(&TradingSession::discriminator()); offset += ANCHOR_DISCRIMINATOR;
+        data[offset] = bump; offset += 1;
+        data[offset..offset + 32].copy_from_slice(cp_amm.as_ref()); offset += 32;
+        data[offset..offset + 32].copy_from_slice(owner.as_ref()); offset += 32;
+        data[offset..offset + 32].copy_from_slice(session_key.as_ref()); offset += 32;
+        data[offset..offset + 8].copy_from_slice(&max_swap_quote_amount.to_le_bytes()); offset += 8;
+        data[offset..offset + 8].copy_from_slice(&max_total_quote_volume.to_le_bytes()); offset += 8;
+        data[offset..offset + 8].copy_from_slice(&total_quote_volume.to_le_bytes()); offset += 8;
+        data[offset..offset + 8].copy_from_slice(&expires_at_slot.to_le_bytes()); offset += 8;
+
+        assert_eq!(offset, ANCHOR_DISCRIMINATOR + TradingSession::INIT_SPACE);
+
+        let deserialized_session = TradingSession::try_deserialize(&mut data.as_ref()).unwrap();
+
+        assert_eq!(deserialized_session.bump(), bump);
+        assert_eq!(deserialized_session.cp_amm(), &cp_amm);
+        assert_eq!(deserialized_session.owner(), &owner);
+        assert_eq!(deserialized_session.session_key(), &session_key);
+        assert_eq!(deserialized_session.max_swap_quote_amount(), max_swap_quote_amount);
+        assert_eq!(deserialized_session.max_total_quote_volume(), max_total_quote_volume);
+        assert_eq!(deserialized_session.total_quote_volume(), total_quote_volume);
+        assert_eq!(deserialized_session.expires_at_slot(), expires_at_slot);
+
+        let mut serialized_data = Vec::new();
+        deserialized_session.try_serialize(&mut serialized_data).unwrap();
+        assert_eq!(serialized_data.as_slice(), data.as_ref());
+    }
+}
\ No newline at end of file
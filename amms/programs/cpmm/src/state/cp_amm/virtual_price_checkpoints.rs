@@ -0,0 +1,222 @@
+use anchor_lang::{account, InitSpace};
+use anchor_lang::prelude::*;
+use crate::error::ErrorCode;
+
+/// Snapshot of the virtual price of a `CpAmm` at a point in time.
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub struct VirtualPriceCheckpoint {
+    /// The unix timestamp of the checkpoint, in seconds.
+    timestamp: i64, // 8 bytes
+
+    /// The value of one LP token in quote tokens, scaled by `CpAmm::VIRTUAL_PRICE_SCALE`.
+    virtual_price: u64, // 8 bytes
+}
+
+impl VirtualPriceCheckpoint {
+    /// Returns the unix timestamp of the checkpoint.
+    #[inline]
+    pub fn timestamp(&self) -> i64 {
+        self.timestamp
+    }
+
+    /// Returns the virtual price at the checkpoint.
+    #[inline]
+    pub fn virtual_price(&self) -> u64 {
+        self.virtual_price
+    }
+}
+
+/// Ring buffer of daily virtual price checkpoints of a `CpAmm`.
+///
+/// Checkpoints are recorded permissionlessly at most once per `CHECKPOINT_INTERVAL_SECONDS`,
+/// giving lending protocols a feed of the LP token value that can't be moved within a single transaction.
+#[account]
+#[derive(InitSpace)]
+pub struct VirtualPriceCheckpoints {
+    /// The canonical bump seed used for the account's PDA.
+    bump: u8, // 1 byte
+
+    /// The public key of the tracked `CpAmm`.
+    cp_amm: Pubkey, // 32 bytes
+
+    /// The index the next checkpoint is written to.
+    head: u16, // 2 bytes
+
+    /// The number of recorded checkpoints, up to `CAPACITY`.
+    count: u16, // 2 bytes
+
+    /// The checkpoints, the oldest one is overwritten once the buffer is full.
+    checkpoints: [VirtualPriceCheckpoint; 30], // 480 bytes
+}
+
+impl VirtualPriceCheckpoints {
+    /// The seed used to derive the account's PDA.
+    pub const SEED: &'static [u8] = b"virtual_price_checkpoints";
+
+    /// The number of checkpoints kept in the buffer.
+    pub const CAPACITY: usize = 30;
+
+    /// The minimal time between two consecutive checkpoints, in seconds.
+    pub const CHECKPOINT_INTERVAL_SECONDS: i64 = 24 * 60 * 60;
+
+    /// Initializes the `VirtualPriceCheckpoints` with the first checkpoint of the pool.
+    ///
+    /// # Parameters
+    /// - `cp_amm`: The public key of the tracked `CpAmm`.
+    /// - `bump`: The bump seed for the account's PDA.
+    /// - `current_timestamp`: The current unix timestamp.
+    /// - `virtual_price`: The current virtual price of the `CpAmm`.
+    pub(crate) fn initialize(&mut self, cp_amm: Pubkey, bump: u8, current_timestamp: i64, virtual_price: u64) {
+        self.bump = bump;
+        self.cp_amm = cp_amm;
+        self.push(current_timestamp, virtual_price);
+    }
+
+    /// Records a new checkpoint of the pool virtual price.
+    ///
+    /// # Parameters
+    /// - `current_timestamp`: The current unix timestamp.
+    /// - `virtual_price`: The current virtual price of the `CpAmm`.
+    ///
+    /// # Errors
+    /// - `VirtualPriceCheckpointTooEarly` if less than `CHECKPOINT_INTERVAL_SECONDS` passed since the latest checkpoint.
+    pub(crate) fn record(&mut self, current_timestamp: i64, virtual_price: u64) -> Result<()> {
+        let next_timestamp = self.latest().map_or(i64::MIN, |latest| latest.timestamp.saturating_add(Self::CHECKPOINT_INTERVAL_SECONDS));
+        require!(current_timestamp >= next_timestamp, ErrorCode::VirtualPriceCheckpointTooEarly);
+        self.push(current_timestamp, virtual_price);
+        Ok(())
+    }
+
+    fn push(&mut self, timestamp: i64, virtual_price: u64) {
+        self.checkpoints[self.head as usize] = VirtualPriceCheckpoint {
+            timestamp,
+            virtual_price,
+        };
+        self.head = ((self.head as usize + 1) % Self::CAPACITY) as u16;
+        self.count = (self.count as usize + 1).min(Self::CAPACITY) as u16;
+    }
+
+    /// Iterates over the recorded checkpoints from the newest to the oldest.
+    fn iter_newest_first(&self) -> impl Iterator<Item = &VirtualPriceCheckpoint> {
+        (1..=self.count as usize).map(move |offset| &self.checkpoints[(self.head as usize + Self::CAPACITY - offset) % Self::CAPACITY])
+    }
+
+    /// Returns the newest checkpoint, if any.
+    pub fn latest(&self) -> Option<&VirtualPriceCheckpoint> {
+        self.iter_newest_first().next()
+    }
+
+    /// Returns the newest checkpoint recorded at or before the timestamp, falling back
+    /// to the oldest checkpoint if the buffer does not reach that far back.
+    ///
+    /// # Parameters
+    /// - `timestamp`: The unix timestamp to look back to.
+    pub fn find_at_or_before(&self, timestamp: i64) -> Option<&VirtualPriceCheckpoint> {
+        self.iter_newest_first()
+            .find(|checkpoint| checkpoint.timestamp <= timestamp)
+            .or_else(|| self.iter_newest_first().last())
+    }
+
+    /// Returns the bump seed used for the account's PDA.
+    #[inline]
+    pub fn bump(&self) -> u8 {
+        self.bump
+    }
+
+    /// Returns the public key of the tracked `CpAmm`.
+    #[inline]
+    pub fn cp_amm(&self) -> &Pubkey {
+        &self.cp_amm
+    }
+
+    /// Returns the number of recorded checkpoints.
+    #[inline]
+    pub fn count(&self) -> u16 {
+        self.count
+    }
+}
+
+#[cfg(test)]
+mod virtual_price_checkpoints_tests {
+    use anchor_lang::Discriminator;
+    use crate::constants::ANCHOR_DISCRIMINATOR;
+    use super::*;
+
+    fn empty_checkpoints() -> VirtualPriceCheckpoints {
+        VirtualPriceCheckpoints {
+            bump: 0,
+            cp_amm: Pubkey::default(),
+            head: 0,
+            count: 0,
+            checkpoints: [VirtualPriceCheckpoint::default(); VirtualPriceCheckpoints::CAPACITY],
+        }
+    }
+
+    /// Tests checkpoint recording intervals and overwriting of the oldest checkpoints.
+    #[test]
+    fn test_record() {
+        let mut checkpoints = empty_checkpoints();
+        assert_eq!(checkpoints.latest(), None);
+        assert_eq!(checkpoints.find_at_or_before(0), None);
+
+        checkpoints.initialize(Pubkey::new_unique(), 250, 1_000, 1_000_000_000);
+        assert_eq!(checkpoints.count(), 1);
+        assert_eq!(checkpoints.latest().unwrap().timestamp(), 1_000);
+
+        let interval = VirtualPriceCheckpoints::CHECKPOINT_INTERVAL_SECONDS;
+        assert_eq!(checkpoints.record(1_000 + interval - 1, 1_000_000_001).err(), Some(ErrorCode::VirtualPriceCheckpointTooEarly.into()));
+
+        checkpoints.record(1_000 + interval, 1_000_000_001).unwrap();
+        assert_eq!(checkpoints.count(), 2);
+        assert_eq!(checkpoints.latest().unwrap().virtual_price(), 1_000_000_001);
+
+        for i in 2..=VirtualPriceCheckpoints::CAPACITY as i64 {
+            checkpoints.record(1_000 + i * interval, 1_000_000_000 + i as u64).unwrap();
+        }
+        assert_eq!(checkpoints.count() as usize, VirtualPriceCheckpoints::CAPACITY);
+        assert_eq!(checkpoints.latest().unwrap().timestamp(), 1_000 + VirtualPriceCheckpoints::CAPACITY as i64 * interval);
+        // The first checkpoint was overwritten, so the oldest one is the second
+        assert_eq!(checkpoints.find_at_or_before(0).unwrap().timestamp(), 1_000 + interval);
+        assert_eq!(checkpoints.find_at_or_before(1_000 + 5 * interval + 1).unwrap().virtual_price(), 1_000_000_005);
+    }
+
+    /// Tests `VirtualPriceCheckpoints` account data layout.
+    #[test]
+    fn test_virtual_price_checkpoints_data_layout() {
+        let bump = 250u8;
+        let cp_amm = Pubkey::new_unique();
+        let head = 2u16;
+        let count = 2u16;
+
+        let mut data = [0u8; ANCHOR_DISCRIMINATOR + 517];
+        let mut offset = 0;
+
+        data[offset..offset + ANCHOR_DISCRIMINATOR].copy_from_slice(&VirtualPriceCheckpoints::discriminator()); offset += ANCHOR_DISCRIMINATOR;
+        data[offset] = bump; offset += 1;
+        data[offset..offset + 32].copy_from_slice(cp_amm.as_ref()); offset += 32;
+        data[offset..offset + 2].copy_from_slice(&head.to_le_bytes()); offset += 2;
+        data[offset..offset + 2].copy_from_slice(&count.to_le_bytes()); offset += 2;
+        for i in 0..VirtualPriceCheckpoints::CAPACITY {
+            data[offset..offset + 8].copy_from_slice(&(i as i64 * 100).to_le_bytes()); offset += 8;
+            data[offset..offset + 8].copy_from_slice(&(1_000 + i as u64).to_le_bytes()); offset += 8;
+        }
+
+        assert_eq!(ANCHOR_DISCRIMINATOR + VirtualPriceCheckpoints::INIT_SPACE, offset);
+
+        let deserialized_checkpoints = VirtualPriceCheckpoints::try_deserialize(&mut data.as_ref()).unwrap();
+
+        assert_eq!(deserialized_checkpoints.bump, bump);
+        assert_eq!(deserialized_checkpoints.cp_amm, cp_amm);
+        assert_eq!(deserialized_checkpoints.head, head);
+        assert_eq!(deserialized_checkpoints.count, count);
+        assert_eq!(deserialized_checkpoints.checkpoints[29], VirtualPriceCheckpoint {
+            timestamp: 2900,
+            virtual_price: 1_029,
+        });
+        assert_eq!(deserialized_checkpoints.latest().unwrap().timestamp(), 100);
+
+        let mut serialized_checkpoints = Vec::new();
+        deserialized_checkpoints.try_serialize(&mut serialized_checkpoints).unwrap();
+        assert_eq!(serialized_checkpoints.as_slice(), data.as_ref());
+    }
+}
\ No newline at end of file
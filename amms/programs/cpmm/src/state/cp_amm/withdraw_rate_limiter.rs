@@ -0,0 +1,98 @@
+use anchor_lang::prelude::*;
+use crate::error::ErrorCode;
+
+/// Tracks LP tokens withdrawn from a `CpAmm` within a single slot.
+///
+/// While the pool is incident-limited, withdrawals in one slot are capped at a share of the
+/// LP tokens supply observed at the first withdrawal of that slot. The counter is reset
+/// as soon as a withdrawal happens in a newer slot.
+#[derive(Debug, Clone, Copy, PartialEq, Default, AnchorSerialize, AnchorDeserialize, InitSpace)]
+pub struct WithdrawRateLimiter {
+    /// Slot of the latest tracked withdrawal.
+    slot: u64, // 8 bytes
+
+    /// LP tokens supply at the first tracked withdrawal of `slot`.
+    slot_lp_tokens_supply: u64, // 8 bytes
+
+    /// LP tokens withdrawn during `slot`.
+    withdrawn_lp_tokens: u64, // 8 bytes
+}
+
+impl WithdrawRateLimiter {
+    /// Returns the slot of the latest tracked withdrawal.
+    #[inline]
+    pub fn slot(&self) -> u64 {
+        self.slot
+    }
+
+    /// Returns the LP tokens supply at the first tracked withdrawal of the current slot.
+    #[inline]
+    pub fn slot_lp_tokens_supply(&self) -> u64 {
+        self.slot_lp_tokens_supply
+    }
+
+    /// Returns the LP tokens withdrawn during the tracked slot.
+    #[inline]
+    pub fn withdrawn_lp_tokens(&self) -> u64 {
+        self.withdrawn_lp_tokens
+    }
+
+    /// Calculates the limiter state after a withdrawal of `lp_tokens`.
+    ///
+    /// # Parameters
+    /// - `current_slot`: The slot in which the withdrawal happens.
+    /// - `lp_tokens_supply`: The LP tokens supply before the withdrawal.
+    /// - `lp_tokens`: The amount of LP tokens being withdrawn.
+    /// - `limit_basis_points`: The share of the slot's LP tokens supply allowed to be withdrawn.
+    ///
+    /// # Returns
+    /// - `Ok(WithdrawRateLimiter)` with the updated slot accounting.
+    /// - `Err(ErrorCode::WithdrawRateLimitExceeded)` if the withdrawal exceeds the slot limit.
+    pub(crate) fn register_withdraw(&self, current_slot: u64, lp_tokens_supply: u64, lp_tokens: u64, limit_basis_points: u16) -> Result<Self> {
+        let (slot_lp_tokens_supply, withdrawn_lp_tokens) = if current_slot == self.slot {
+            (self.slot_lp_tokens_supply, self.withdrawn_lp_tokens)
+        } else {
+            (lp_tokens_supply, 0)
+        };
+
+        let withdrawn_lp_tokens = withdrawn_lp_tokens.checked_add(lp_tokens).ok_or(ErrorCode::WithdrawOverflowError)?;
+        require!(
+            withdrawn_lp_tokens as u128 * 10000 <= slot_lp_tokens_supply as u128 * limit_basis_points as u128,
+            ErrorCode::WithdrawRateLimitExceeded
+        );
+
+        Ok(Self {
+            slot: current_slot,
+            slot_lp_tokens_supply,
+            withdrawn_lp_tokens,
+        })
+    }
+}
+
+#[cfg(test)]
+mod withdraw_rate_limiter_tests {
+    use super::*;
+
+    /// Tests withdrawals accumulation within one slot and reset on a newer slot.
+    #[test]
+    fn test_register_withdraw() {
+        let limiter = WithdrawRateLimiter::default();
+
+        let limiter = limiter.register_withdraw(10, 1_000_000, 30_000, 500).unwrap();
+        assert_eq!(limiter.slot(), 10);
+        assert_eq!(limiter.slot_lp_tokens_supply(), 1_000_000);
+        assert_eq!(limiter.withdrawn_lp_tokens(), 30_000);
+
+        // Supply at the slot start is kept even if the pool supply already decreased
+        let limiter = limiter.register_withdraw(10, 970_000, 20_000, 500).unwrap();
+        assert_eq!(limiter.slot_lp_tokens_supply(), 1_000_000);
+        assert_eq!(limiter.withdrawn_lp_tokens(), 50_000);
+
+        assert!(limiter.register_withdraw(10, 950_000, 1, 500).is_err());
+
+        let limiter = limiter.register_withdraw(11, 950_000, 47_500, 500).unwrap();
+        assert_eq!(limiter.slot(), 11);
+        assert_eq!(limiter.slot_lp_tokens_supply(), 950_000);
+        assert_eq!(limiter.withdrawn_lp_tokens(), 47_500);
+    }
+}
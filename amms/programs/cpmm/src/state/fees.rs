@@ -0,0 +1,390 @@
+use anchor_lang::prelude::*;
+use crate::error::ErrorCode;
+
+/// The maximal fee rate, measured in basis points (10000 = 100%).
+pub const MAX_FEE_RATE_BASIS_POINTS: u16 = 10000;
+
+/// Validates that the total of the fee rate components does not exceed 100%.
+///
+/// The total is accumulated with checked arithmetic, so a sum overflowing `u16` is rejected
+/// instead of panicking. Additional fee components, e.g. per-pool or referral rates,
+/// are validated by passing them along with the providers and protocol rates.
+///
+/// # Parameters
+/// - `fee_rates_basis_points`: The fee rate components, measured in basis points.
+///
+/// # Errors
+/// - Returns `ErrorCode::ConfigFeeRateExceeded` if the total fee rate exceeds 100%.
+pub fn validate_total_fee_rate(fee_rates_basis_points: &[u16]) -> Result<()> {
+    let total_fee_rate_basis_points = fee_rates_basis_points
+        .iter()
+        .try_fold(0u16, |total, fee_rate_basis_points| total.checked_add(*fee_rate_basis_points));
+    require!(
+        total_fee_rate_basis_points.is_some_and(|total| total <= MAX_FEE_RATE_BASIS_POINTS),
+        ErrorCode::ConfigFeeRateExceeded
+    );
+    Ok(())
+}
+
+/// Validates that the total of the providers and protocol fee rates does not exceed 100%.
+///
+/// All fee rate pairs, whether stored in an `AmmsConfig` or applied by a swap, are validated here.
+///
+/// # Parameters
+/// - `providers_fee_rate_basis_points`: The providers fee rate, measured in basis points.
+/// - `protocol_fee_rate_basis_points`: The protocol fee rate, measured in basis points.
+///
+/// # Errors
+/// - Returns `ErrorCode::ConfigFeeRateExceeded` if the total fee rate exceeds 100%.
+pub fn validate_fee_rates(providers_fee_rate_basis_points: u16, protocol_fee_rate_basis_points: u16) -> Result<()> {
+    validate_total_fee_rate(&[providers_fee_rate_basis_points, protocol_fee_rate_basis_points])
+}
+
+/// Bounds on the fee rates an `AmmsConfig` may be updated to.
+#[derive(Debug, Clone, Copy, PartialEq, AnchorSerialize, AnchorDeserialize, InitSpace)]
+pub struct FeeBounds {
+    /// The minimal providers fee rate, measured in basis points.
+    min_providers_fee_rate_basis_points: u16, // 2 bytes
+
+    /// The maximal providers fee rate, measured in basis points.
+    max_providers_fee_rate_basis_points: u16, // 2 bytes
+
+    /// The maximal protocol fee rate, measured in basis points.
+    max_protocol_fee_rate_basis_points: u16, // 2 bytes
+}
+
+impl FeeBounds {
+    /// Creates new `FeeBounds`.
+    ///
+    /// # Parameters
+    /// - `min_providers_fee_rate_basis_points`: The minimal providers fee rate.
+    /// - `max_providers_fee_rate_basis_points`: The maximal providers fee rate.
+    /// - `max_protocol_fee_rate_basis_points`: The maximal protocol fee rate.
+    ///
+    /// # Errors
+    /// - Returns `ErrorCode::ConfigFeeBoundsInvalid` if the providers minimum exceeds the maximum
+    ///   or any maximum exceeds 100%.
+    pub fn try_new(min_providers_fee_rate_basis_points: u16, max_providers_fee_rate_basis_points: u16, max_protocol_fee_rate_basis_points: u16) -> Result<Self> {
+        require!(
+            min_providers_fee_rate_basis_points <= max_providers_fee_rate_basis_points &&
+            max_providers_fee_rate_basis_points <= MAX_FEE_RATE_BASIS_POINTS &&
+            max_protocol_fee_rate_basis_points <= MAX_FEE_RATE_BASIS_POINTS,
+            ErrorCode::ConfigFeeBoundsInvalid
+        );
+        Ok(Self {
+            min_providers_fee_rate_basis_points,
+            max_providers_fee_rate_basis_points,
+            max_protocol_fee_rate_basis_points,
+        })
+    }
+
+    /// Returns the minimal providers fee rate, measured in basis points.
+    #[inline]
+    pub fn min_providers_fee_rate_basis_points(&self) -> u16 {
+        self.min_providers_fee_rate_basis_points
+    }
+
+    /// Returns the maximal providers fee rate, measured in basis points.
+    #[inline]
+    pub fn max_providers_fee_rate_basis_points(&self) -> u16 {
+        self.max_providers_fee_rate_basis_points
+    }
+
+    /// Returns the maximal protocol fee rate, measured in basis points.
+    #[inline]
+    pub fn max_protocol_fee_rate_basis_points(&self) -> u16 {
+        self.max_protocol_fee_rate_basis_points
+    }
+
+    /// Validates that the fee rates fit into the bounds.
+    ///
+    /// # Errors
+    /// - Returns `ErrorCode::ConfigFeeRateOutOfBounds` if any fee rate is out of the bounds.
+    pub fn validate_fee_rates(&self, providers_fee_rate_basis_points: u16, protocol_fee_rate_basis_points: u16) -> Result<()> {
+        require!(
+            providers_fee_rate_basis_points >= self.min_providers_fee_rate_basis_points &&
+            providers_fee_rate_basis_points <= self.max_providers_fee_rate_basis_points &&
+            protocol_fee_rate_basis_points <= self.max_protocol_fee_rate_basis_points,
+            ErrorCode::ConfigFeeRateOutOfBounds
+        );
+        Ok(())
+    }
+}
+
+/// Linear ramp of a fee rate from a start rate to the rate stored in an `AmmsConfig`.
+///
+/// The ramp only stores where it starts, the target is the config's rate, so an elapsed ramp
+/// resolves to the stored rate without any cleanup.
+#[derive(Debug, Clone, Copy, PartialEq, AnchorSerialize, AnchorDeserialize, InitSpace)]
+pub struct FeeRamp {
+    /// The slot at which the ramp starts.
+    start_slot: u64, // 8 bytes
+
+    /// The slot at which the ramp reaches the target rate.
+    end_slot: u64, // 8 bytes
+
+    /// The fee rate at `start_slot`, measured in basis points.
+    start_fee_rate_basis_points: u16, // 2 bytes
+}
+
+impl FeeRamp {
+    /// Creates a new `FeeRamp`.
+    ///
+    /// # Parameters
+    /// - `start_slot`: The slot at which the ramp starts.
+    /// - `duration_slots`: The number of slots it takes to reach the target rate.
+    /// - `start_fee_rate_basis_points`: The fee rate at `start_slot`.
+    ///
+    /// # Errors
+    /// - Returns `ErrorCode::ConfigFeeRampDurationIsZero` if `duration_slots` is zero.
+    pub fn try_new(start_slot: u64, duration_slots: u64, start_fee_rate_basis_points: u16) -> Result<Self> {
+        require!(duration_slots > 0, ErrorCode::ConfigFeeRampDurationIsZero);
+        Ok(Self {
+            start_slot,
+            end_slot: start_slot.saturating_add(duration_slots),
+            start_fee_rate_basis_points,
+        })
+    }
+
+    /// Returns the slot at which the ramp starts.
+    #[inline]
+    pub fn start_slot(&self) -> u64 {
+        self.start_slot
+    }
+
+    /// Returns the slot at which the ramp reaches the target rate.
+    #[inline]
+    pub fn end_slot(&self) -> u64 {
+        self.end_slot
+    }
+
+    /// Returns the fee rate at the start of the ramp, measured in basis points.
+    #[inline]
+    pub fn start_fee_rate_basis_points(&self) -> u16 {
+        self.start_fee_rate_basis_points
+    }
+
+    /// Interpolates the fee rate at the given slot.
+    ///
+    /// # Parameters
+    /// - `slot`: The current slot.
+    /// - `target_fee_rate_basis_points`: The fee rate reached at the end of the ramp.
+    ///
+    /// # Returns
+    /// - The fee rate, rounded towards the start rate, measured in basis points.
+    pub fn fee_rate_at(&self, slot: u64, target_fee_rate_basis_points: u16) -> u16 {
+        if slot >= self.end_slot {
+            return target_fee_rate_basis_points;
+        }
+        if slot <= self.start_slot {
+            return self.start_fee_rate_basis_points;
+        }
+        let elapsed = (slot - self.start_slot) as i128;
+        let duration = (self.end_slot - self.start_slot) as i128;
+        let start = self.start_fee_rate_basis_points as i128;
+        let delta = target_fee_rate_basis_points as i128 - start;
+        // Stays between the start and the target rates, so it always fits into u16
+        (start + delta * elapsed / duration) as u16
+    }
+}
+
+/// Settlement of the protocol fees collected from the pools of an `AmmsConfig` in a single asset.
+///
+/// On collection, the protocol fees accrued in the other token of a pool are first swapped in-pool
+/// into the settlement mint, so the fee authority receives one asset instead of whichever token was the input.
+#[derive(Debug, Clone, Copy, PartialEq, AnchorSerialize, AnchorDeserialize, InitSpace)]
+pub struct FeeSettlement {
+    /// The mint the fees are settled in, or `None` to settle in the quote mint of each pool.
+    settlement_mint: Option<Pubkey>, // 33 bytes
+
+    /// The maximal price impact of the conversion swap, measured in basis points.
+    max_impact_basis_points: u16, // 2 bytes
+}
+
+impl FeeSettlement {
+    /// Creates a new `FeeSettlement`.
+    ///
+    /// # Parameters
+    /// - `settlement_mint`: The mint the fees are settled in, or `None` for the quote mint of each pool.
+    /// - `max_impact_basis_points`: The maximal price impact of the conversion swap.
+    ///
+    /// # Errors
+    /// - Returns `ErrorCode::ConfigFeeSettlementInvalid` if the maximal price impact is zero or exceeds 100%.
+    pub fn try_new(settlement_mint: Option<Pubkey>, max_impact_basis_points: u16) -> Result<Self> {
+        require!(
+            max_impact_basis_points > 0 && max_impact_basis_points <= MAX_FEE_RATE_BASIS_POINTS,
+            ErrorCode::ConfigFeeSettlementInvalid
+        );
+        Ok(Self {
+            settlement_mint,
+            max_impact_basis_points,
+        })
+    }
+
+    /// Returns the mint the fees are settled in, or `None` for the quote mint of each pool.
+    #[inline]
+    pub fn settlement_mint(&self) -> Option<&Pubkey> {
+        self.settlement_mint.as_ref()
+    }
+
+    /// Returns the maximal price impact of the conversion swap, measured in basis points.
+    #[inline]
+    pub fn max_impact_basis_points(&self) -> u16 {
+        self.max_impact_basis_points
+    }
+
+    /// Resolves the direction of the conversion swap for a pool.
+    ///
+    /// # Parameters
+    /// - `base_mint`: The base mint of the pool.
+    /// - `quote_mint`: The quote mint of the pool.
+    ///
+    /// # Returns
+    /// - `Some(true)` if the base fees are converted into the quote mint, `Some(false)` if the quote
+    ///   fees are converted into the base mint, `None` if the pool doesn't trade the settlement mint.
+    pub fn is_in_out(&self, base_mint: &Pubkey, quote_mint: &Pubkey) -> Option<bool> {
+        match self.settlement_mint {
+            None => Some(true),
+            Some(settlement_mint) if settlement_mint == *quote_mint => Some(true),
+            Some(settlement_mint) if settlement_mint == *base_mint => Some(false),
+            Some(_) => None,
+        }
+    }
+}
+
+/// Validates fee rates stored in an `AmmsConfig`: their total and, if set, the config's fee bounds.
+///
+/// # Parameters
+/// - `fee_bounds`: The optional fee bounds of the config.
+/// - `providers_fee_rate_basis_points`: The providers fee rate, measured in basis points.
+/// - `protocol_fee_rate_basis_points`: The protocol fee rate, measured in basis points.
+pub fn validate_bounded_fee_rates(fee_bounds: Option<&FeeBounds>, providers_fee_rate_basis_points: u16, protocol_fee_rate_basis_points: u16) -> Result<()> {
+    validate_fee_rates(providers_fee_rate_basis_points, protocol_fee_rate_basis_points)?;
+    if let Some(fee_bounds) = fee_bounds {
+        fee_bounds.validate_fee_rates(providers_fee_rate_basis_points, protocol_fee_rate_basis_points)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod fees_tests {
+    use super::*;
+
+    /// Tests the total fee rate boundary.
+    #[test]
+    fn test_validate_fee_rates() {
+        assert!(validate_fee_rates(0, 0).is_ok());
+        assert!(validate_fee_rates(MAX_FEE_RATE_BASIS_POINTS, 0).is_ok());
+        assert!(validate_fee_rates(0, MAX_FEE_RATE_BASIS_POINTS).is_ok());
+        assert!(validate_fee_rates(5000, 5000).is_ok());
+        assert_eq!(validate_fee_rates(5000, 5001).err(), Some(ErrorCode::ConfigFeeRateExceeded.into()));
+        assert_eq!(validate_fee_rates(MAX_FEE_RATE_BASIS_POINTS + 1, 0).err(), Some(ErrorCode::ConfigFeeRateExceeded.into()));
+        assert_eq!(validate_fee_rates(0, MAX_FEE_RATE_BASIS_POINTS + 1).err(), Some(ErrorCode::ConfigFeeRateExceeded.into()));
+        assert_eq!(validate_fee_rates(u16::MAX, 1).err(), Some(ErrorCode::ConfigFeeRateExceeded.into()));
+        assert_eq!(validate_fee_rates(u16::MAX, u16::MAX).err(), Some(ErrorCode::ConfigFeeRateExceeded.into()));
+    }
+
+    /// Tests the total fee rate boundary across any number of fee components.
+    #[test]
+    fn test_validate_total_fee_rate() {
+        assert!(validate_total_fee_rate(&[]).is_ok());
+        assert!(validate_total_fee_rate(&[MAX_FEE_RATE_BASIS_POINTS]).is_ok());
+        assert!(validate_total_fee_rate(&[5000, 3000, 2000]).is_ok());
+        assert_eq!(validate_total_fee_rate(&[5000, 3000, 2001]).err(), Some(ErrorCode::ConfigFeeRateExceeded.into()));
+        assert_eq!(validate_total_fee_rate(&[u16::MAX, 1, 0]).err(), Some(ErrorCode::ConfigFeeRateExceeded.into()));
+    }
+
+    /// Tests `FeeBounds` creation boundaries.
+    #[test]
+    fn test_fee_bounds_try_new() {
+        let fee_bounds = FeeBounds::try_new(10, 100, 50).unwrap();
+        assert_eq!(fee_bounds.min_providers_fee_rate_basis_points(), 10);
+        assert_eq!(fee_bounds.max_providers_fee_rate_basis_points(), 100);
+        assert_eq!(fee_bounds.max_protocol_fee_rate_basis_points(), 50);
+
+        assert!(FeeBounds::try_new(100, 100, 0).is_ok());
+        assert!(FeeBounds::try_new(0, MAX_FEE_RATE_BASIS_POINTS, MAX_FEE_RATE_BASIS_POINTS).is_ok());
+        assert_eq!(FeeBounds::try_new(101, 100, 0).err(), Some(ErrorCode::ConfigFeeBoundsInvalid.into()));
+        assert_eq!(FeeBounds::try_new(0, MAX_FEE_RATE_BASIS_POINTS + 1, 0).err(), Some(ErrorCode::ConfigFeeBoundsInvalid.into()));
+        assert_eq!(FeeBounds::try_new(0, 0, MAX_FEE_RATE_BASIS_POINTS + 1).err(), Some(ErrorCode::ConfigFeeBoundsInvalid.into()));
+    }
+
+    /// Tests fee rates validation against every bound.
+    #[test]
+    fn test_fee_bounds_validate_fee_rates() {
+        let fee_bounds = FeeBounds::try_new(10, 100, 50).unwrap();
+
+        assert!(fee_bounds.validate_fee_rates(10, 0).is_ok());
+        assert!(fee_bounds.validate_fee_rates(100, 50).is_ok());
+        assert_eq!(fee_bounds.validate_fee_rates(9, 0).err(), Some(ErrorCode::ConfigFeeRateOutOfBounds.into()));
+        assert_eq!(fee_bounds.validate_fee_rates(101, 0).err(), Some(ErrorCode::ConfigFeeRateOutOfBounds.into()));
+        assert_eq!(fee_bounds.validate_fee_rates(50, 51).err(), Some(ErrorCode::ConfigFeeRateOutOfBounds.into()));
+    }
+
+    /// Tests combined validation with and without bounds.
+    #[test]
+    fn test_validate_bounded_fee_rates() {
+        let fee_bounds = FeeBounds::try_new(0, MAX_FEE_RATE_BASIS_POINTS, 100).unwrap();
+
+        assert!(validate_bounded_fee_rates(None, 9000, 1000).is_ok());
+        assert_eq!(validate_bounded_fee_rates(Some(&fee_bounds), 9000, 1000).err(), Some(ErrorCode::ConfigFeeRateOutOfBounds.into()));
+        assert!(validate_bounded_fee_rates(Some(&fee_bounds), 9900, 100).is_ok());
+        assert_eq!(validate_bounded_fee_rates(Some(&fee_bounds), 9901, 100).err(), Some(ErrorCode::ConfigFeeRateExceeded.into()));
+        assert_eq!(validate_bounded_fee_rates(None, 9001, 1000).err(), Some(ErrorCode::ConfigFeeRateExceeded.into()));
+    }
+
+    /// Tests `FeeRamp` creation.
+    #[test]
+    fn test_fee_ramp_try_new() {
+        let fee_ramp = FeeRamp::try_new(100, 50, 30).unwrap();
+        assert_eq!(fee_ramp.start_slot(), 100);
+        assert_eq!(fee_ramp.end_slot(), 150);
+        assert_eq!(fee_ramp.start_fee_rate_basis_points(), 30);
+
+        assert_eq!(FeeRamp::try_new(u64::MAX - 1, 10, 0).unwrap().end_slot(), u64::MAX);
+        assert_eq!(FeeRamp::try_new(100, 0, 30).err(), Some(ErrorCode::ConfigFeeRampDurationIsZero.into()));
+    }
+
+    /// Tests the fee rate interpolation of upward and downward ramps.
+    #[test]
+    fn test_fee_ramp_fee_rate_at() {
+        let fee_ramp = FeeRamp::try_new(1000, 100, 30).unwrap();
+
+        assert_eq!(fee_ramp.fee_rate_at(0, 130), 30);
+        assert_eq!(fee_ramp.fee_rate_at(1000, 130), 30);
+        assert_eq!(fee_ramp.fee_rate_at(1001, 130), 31);
+        assert_eq!(fee_ramp.fee_rate_at(1050, 130), 80);
+        assert_eq!(fee_ramp.fee_rate_at(1099, 130), 129);
+        assert_eq!(fee_ramp.fee_rate_at(1100, 130), 130);
+        assert_eq!(fee_ramp.fee_rate_at(u64::MAX, 130), 130);
+
+        assert_eq!(fee_ramp.fee_rate_at(1001, 0), 30);
+        assert_eq!(fee_ramp.fee_rate_at(1050, 0), 15);
+        assert_eq!(fee_ramp.fee_rate_at(1100, 0), 0);
+
+        let fee_ramp = FeeRamp::try_new(0, u64::MAX, MAX_FEE_RATE_BASIS_POINTS).unwrap();
+        assert_eq!(fee_ramp.fee_rate_at(u64::MAX / 2, 0), MAX_FEE_RATE_BASIS_POINTS / 2 + 1);
+    }
+
+    /// Tests `FeeSettlement` creation and the conversion direction resolution.
+    #[test]
+    fn test_fee_settlement() {
+        let (base_mint, quote_mint) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+        assert_eq!(FeeSettlement::try_new(None, 0).err(), Some(ErrorCode::ConfigFeeSettlementInvalid.into()));
+        assert_eq!(FeeSettlement::try_new(None, MAX_FEE_RATE_BASIS_POINTS + 1).err(), Some(ErrorCode::ConfigFeeSettlementInvalid.into()));
+
+        let fee_settlement = FeeSettlement::try_new(None, 50).unwrap();
+        assert_eq!(fee_settlement.settlement_mint(), None);
+        assert_eq!(fee_settlement.max_impact_basis_points(), 50);
+        assert_eq!(fee_settlement.is_in_out(&base_mint, &quote_mint), Some(true));
+
+        let fee_settlement = FeeSettlement::try_new(Some(quote_mint), MAX_FEE_RATE_BASIS_POINTS).unwrap();
+        assert_eq!(fee_settlement.is_in_out(&base_mint, &quote_mint), Some(true));
+
+        let fee_settlement = FeeSettlement::try_new(Some(base_mint), 50).unwrap();
+        assert_eq!(fee_settlement.settlement_mint(), Some(&base_mint));
+        assert_eq!(fee_settlement.is_in_out(&base_mint, &quote_mint), Some(false));
+        assert_eq!(fee_settlement.is_in_out(&quote_mint, &Pubkey::new_unique()), None);
+    }
+}
\ No newline at end of file
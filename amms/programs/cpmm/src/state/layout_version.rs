@@ -0,0 +1,24 @@
+use anchor_lang::prelude::*;
+use crate::error::ErrorCode;
+
+/// Account storing the version of its data layout.
+///
+/// The version is bumped whenever the layout of an already deployed account changes, so
+/// instructions reject accounts written by another program build instead of silently
+/// misinterpreting their bytes.
+pub trait VersionedAccount {
+    /// The layout version expected by this program build.
+    const LAYOUT_VERSION: u8;
+
+    /// Returns the layout version stored in the account.
+    fn layout_version(&self) -> u8;
+
+    /// Checks that the stored layout version matches the one expected by this program build.
+    ///
+    /// # Errors
+    /// - `AccountVersionMismatch` if the versions differ.
+    fn assert_layout_version(&self) -> Result<()> {
+        require_eq!(self.layout_version(), Self::LAYOUT_VERSION, ErrorCode::AccountVersionMismatch);
+        Ok(())
+    }
+}
\ No newline at end of file
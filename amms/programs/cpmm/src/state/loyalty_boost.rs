@@ -0,0 +1,120 @@
+use anchor_lang::prelude::*;
+use crate::error::ErrorCode;
+
+/// Loyalty multiplier applied to liquidity held in a `CpAmm` beyond configured durations.
+///
+/// The multiplier stays at 1x until the liquidity is held for `min_hold_seconds`, then grows
+/// linearly and reaches `max_multiplier_basis_points` after `full_boost_hold_seconds`.
+#[derive(Debug, Clone, Copy, PartialEq, AnchorSerialize, AnchorDeserialize, InitSpace)]
+pub struct LoyaltyBoost {
+    /// The holding duration after which the multiplier starts growing, in seconds.
+    min_hold_seconds: u32, // 4 bytes
+
+    /// The holding duration after which the multiplier reaches its maximum, in seconds.
+    full_boost_hold_seconds: u32, // 4 bytes
+
+    /// The maximal multiplier, measured in basis points (10000 = 1x).
+    max_multiplier_basis_points: u16, // 2 bytes
+}
+
+impl LoyaltyBoost {
+    /// The multiplier of liquidity that is not boosted, measured in basis points (10000 = 1x).
+    pub const BASE_MULTIPLIER_BASIS_POINTS: u16 = 10000;
+
+    /// The maximal allowed multiplier, measured in basis points (30000 = 3x).
+    pub const MAX_MULTIPLIER_BASIS_POINTS: u16 = 30000;
+
+    /// Creates a new `LoyaltyBoost`.
+    ///
+    /// # Parameters
+    /// - `min_hold_seconds`: The holding duration after which the multiplier starts growing.
+    /// - `full_boost_hold_seconds`: The holding duration after which the multiplier is maximal.
+    /// - `max_multiplier_basis_points`: The maximal multiplier, measured in basis points.
+    ///
+    /// # Errors
+    /// - Returns `ErrorCode::ConfigLoyaltyBoostInvalid` if the full boost duration does not exceed
+    ///   the minimal one or the maximal multiplier is outside of the 1x..=3x range.
+    pub fn try_new(min_hold_seconds: u32, full_boost_hold_seconds: u32, max_multiplier_basis_points: u16) -> Result<Self> {
+        require!(
+            min_hold_seconds < full_boost_hold_seconds &&
+            (Self::BASE_MULTIPLIER_BASIS_POINTS..=Self::MAX_MULTIPLIER_BASIS_POINTS).contains(&max_multiplier_basis_points),
+            ErrorCode::ConfigLoyaltyBoostInvalid
+        );
+        Ok(Self {
+            min_hold_seconds,
+            full_boost_hold_seconds,
+            max_multiplier_basis_points,
+        })
+    }
+
+    /// Calculates the multiplier of liquidity held for the given duration.
+    ///
+    /// # Parameters
+    /// - `hold_seconds`: The duration the liquidity has been held for, in seconds.
+    ///
+    /// # Returns
+    /// - The `u16` multiplier, measured in basis points (10000 = 1x).
+    pub fn multiplier_basis_points(&self, hold_seconds: u64) -> u16 {
+        if hold_seconds <= self.min_hold_seconds as u64 {
+            return Self::BASE_MULTIPLIER_BASIS_POINTS;
+        }
+        if hold_seconds >= self.full_boost_hold_seconds as u64 {
+            return self.max_multiplier_basis_points;
+        }
+        let boost = (self.max_multiplier_basis_points - Self::BASE_MULTIPLIER_BASIS_POINTS) as u64
+            * (hold_seconds - self.min_hold_seconds as u64)
+            / (self.full_boost_hold_seconds - self.min_hold_seconds) as u64;
+        Self::BASE_MULTIPLIER_BASIS_POINTS + boost as u16
+    }
+
+    /// Returns the holding duration after which the multiplier starts growing, in seconds.
+    #[inline]
+    pub fn min_hold_seconds(&self) -> u32 {
+        self.min_hold_seconds
+    }
+
+    /// Returns the holding duration after which the multiplier reaches its maximum, in seconds.
+    #[inline]
+    pub fn full_boost_hold_seconds(&self) -> u32 {
+        self.full_boost_hold_seconds
+    }
+
+    /// Returns the maximal multiplier, measured in basis points.
+    #[inline]
+    pub fn max_multiplier_basis_points(&self) -> u16 {
+        self.max_multiplier_basis_points
+    }
+}
+
+#[cfg(test)]
+mod loyalty_boost_tests {
+    use super::*;
+
+    /// Tests validation of the `LoyaltyBoost` parameters.
+    #[test]
+    fn test_loyalty_boost_try_new() {
+        let loyalty_boost = LoyaltyBoost::try_new(100, 200, 15000).unwrap();
+        assert_eq!(loyalty_boost.min_hold_seconds(), 100);
+        assert_eq!(loyalty_boost.full_boost_hold_seconds(), 200);
+        assert_eq!(loyalty_boost.max_multiplier_basis_points(), 15000);
+
+        assert!(LoyaltyBoost::try_new(0, 1, LoyaltyBoost::BASE_MULTIPLIER_BASIS_POINTS).is_ok());
+        assert!(LoyaltyBoost::try_new(0, 1, LoyaltyBoost::MAX_MULTIPLIER_BASIS_POINTS).is_ok());
+        assert_eq!(LoyaltyBoost::try_new(200, 200, 15000).err(), Some(ErrorCode::ConfigLoyaltyBoostInvalid.into()));
+        assert_eq!(LoyaltyBoost::try_new(0, 200, 9999).err(), Some(ErrorCode::ConfigLoyaltyBoostInvalid.into()));
+        assert_eq!(LoyaltyBoost::try_new(0, 200, 30001).err(), Some(ErrorCode::ConfigLoyaltyBoostInvalid.into()));
+    }
+
+    /// Tests the multiplier growth between the minimal and the full boost durations.
+    #[test]
+    fn test_loyalty_boost_multiplier_basis_points() {
+        let loyalty_boost = LoyaltyBoost::try_new(1000, 3000, 20000).unwrap();
+        assert_eq!(loyalty_boost.multiplier_basis_points(0), 10000);
+        assert_eq!(loyalty_boost.multiplier_basis_points(1000), 10000);
+        assert_eq!(loyalty_boost.multiplier_basis_points(1001), 10005);
+        assert_eq!(loyalty_boost.multiplier_basis_points(2000), 15000);
+        assert_eq!(loyalty_boost.multiplier_basis_points(2999), 19995);
+        assert_eq!(loyalty_boost.multiplier_basis_points(3000), 20000);
+        assert_eq!(loyalty_boost.multiplier_basis_points(u64::MAX), 20000);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,228 @@
+use anchor_lang::{account, InitSpace};
+use anchor_lang::prelude::*;
+use crate::error::ErrorCode;
+
+/// A mint assigned to a group of mints representing the same underlying asset.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq, InitSpace)]
+pub struct MintEquivalence {
+    /// The public key of the mint.
+    pub mint: Pubkey,
+
+    /// The group of the mint, mints of the same group wrap the same underlying asset.
+    pub group: u8,
+}
+
+/// Sets of equivalent mints of an `AmmsConfig`, e.g. different wrappers of the same stablecoin.
+///
+/// Pools of two mints from the same group would trade an asset against itself and mislead
+/// traders, so their creation is rejected in the config.
+#[account]
+#[derive(InitSpace)]
+pub struct MintEquivalences {
+    /// The canonical bump seed used for the account's PDA.
+    bump: u8, // 1 byte
+
+    /// The `AmmsConfig` the equivalences apply to.
+    amms_config: Pubkey, // 32 bytes
+
+    /// The number of mints assigned to groups.
+    mints_count: u8, // 1 byte
+
+    /// The assigned mints, only the first `mints_count` entries are set.
+    mints: [MintEquivalence; 32], // 1056 bytes
+}
+
+impl MintEquivalences {
+    /// The seed used to derive the account's PDA.
+    pub const SEED: &'static [u8] = b"mint_equivalences";
+
+    /// The number of mints that can be assigned to groups.
+    pub const CAPACITY: u8 = 32;
+
+    /// Initializes the `MintEquivalences` of a config.
+    ///
+    /// # Parameters
+    /// - `amms_config`: The public key of the `AmmsConfig` the equivalences apply to.
+    /// - `bump`: The bump seed for the account's PDA.
+    pub(crate) fn initialize(&mut self, amms_config: Pubkey, bump: u8) {
+        self.bump = bump;
+        self.amms_config = amms_config;
+        self.mints_count = 0;
+        self.mints = [MintEquivalence::default(); 32];
+    }
+
+    /// Assigns a mint to a group, moves it to another group or removes it from its group.
+    ///
+    /// # Parameters
+    /// - `mint`: The public key of the mint.
+    /// - `group`: The group of the mint, or `None` to remove the mint.
+    ///
+    /// # Errors
+    /// - Returns `ErrorCode::MintEquivalencesFull` if a new mint is assigned while `CAPACITY` mints are assigned.
+    pub(crate) fn update(&mut self, mint: Pubkey, group: Option<u8>) -> Result<()> {
+        let position = self.mints().iter().position(|equivalence| equivalence.mint == mint);
+        match (position, group) {
+            (Some(position), Some(group)) => self.mints[position].group = group,
+            (Some(position), None) => {
+                let last = self.mints_count as usize - 1;
+                self.mints[position] = self.mints[last];
+                self.mints[last] = MintEquivalence::default();
+                self.mints_count -= 1;
+            }
+            (None, Some(group)) => {
+                require!(self.mints_count < Self::CAPACITY, ErrorCode::MintEquivalencesFull);
+                self.mints[self.mints_count as usize] = MintEquivalence { mint, group };
+                self.mints_count += 1;
+            }
+            (None, None) => {}
+        }
+        Ok(())
+    }
+
+    /// Validates that a pool of two mints doesn't trade an asset against itself.
+    ///
+    /// # Errors
+    /// - Returns `ErrorCode::EquivalentMintsPair` if both mints belong to the same group.
+    pub fn validate_pair(&self, base_mint: &Pubkey, quote_mint: &Pubkey) -> Result<()> {
+        let base_group = self.group(base_mint);
+        require!(base_group.is_none() || base_group != self.group(quote_mint), ErrorCode::EquivalentMintsPair);
+        Ok(())
+    }
+
+    /// Returns the group of a mint, or `None` if the mint isn't assigned to a group.
+    pub fn group(&self, mint: &Pubkey) -> Option<u8> {
+        self.mints().iter().find(|equivalence| equivalence.mint == *mint).map(|equivalence| equivalence.group)
+    }
+
+    /// Returns the bump seed used for the account's PDA.
+    #[inline]
+    pub fn bump(&self) -> u8 {
+        self.bump
+    }
+
+    /// Returns the public key of the `AmmsConfig` the equivalences apply to.
+    #[inline]
+    pub fn amms_config(&self) -> &Pubkey {
+        &self.amms_config
+    }
+
+    /// Returns the mints assigned to groups.
+    #[inline]
+    pub fn mints(&self) -> &[MintEquivalence] {
+        &self.mints[..self.mints_count as usize]
+    }
+}
+
+#[cfg(test)]
+mod mint_equivalences_tests {
+    use anchor_lang::Discriminator;
+    use crate::constants::ANCHOR_DISCRIMINATOR;
+    use super::*;
+
+    fn empty_mint_equivalences() -> MintEquivalences {
+        MintEquivalences {
+            bump: 0,
+            amms_config: Pubkey::default(),
+            mints_count: 0,
+            mints: [MintEquivalence::default(); 32],
+        }
+    }
+
+    /// Tests assigning, moving and removing mints of `MintEquivalences`.
+    #[test]
+    fn test_mint_equivalences_update() {
+        let mut mint_equivalences = empty_mint_equivalences();
+        let amms_config = Pubkey::new_unique();
+        mint_equivalences.initialize(amms_config, 42);
+        assert_eq!(mint_equivalences.bump(), 42);
+        assert_eq!(mint_equivalences.amms_config(), &amms_config);
+        assert!(mint_equivalences.mints().is_empty());
+
+        let (first_usdc, second_usdc, sol) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        mint_equivalences.update(first_usdc, Some(1)).unwrap();
+        mint_equivalences.update(second_usdc, Some(1)).unwrap();
+        mint_equivalences.update(sol, Some(2)).unwrap();
+        assert_eq!(mint_equivalences.mints().len(), 3);
+        assert_eq!(mint_equivalences.group(&second_usdc), Some(1));
+
+        mint_equivalences.update(second_usdc, Some(2)).unwrap();
+        assert_eq!(mint_equivalences.mints().len(), 3);
+        assert_eq!(mint_equivalences.group(&second_usdc), Some(2));
+
+        mint_equivalences.update(first_usdc, None).unwrap();
+        assert_eq!(mint_equivalences.mints(), &[
+            MintEquivalence { mint: sol, group: 2 },
+            MintEquivalence { mint: second_usdc, group: 2 },
+        ]);
+        assert_eq!(mint_equivalences.mints[2], MintEquivalence::default());
+        assert_eq!(mint_equivalences.group(&first_usdc), None);
+
+        // Removing an unassigned mint is a no-op
+        mint_equivalences.update(first_usdc, None).unwrap();
+        assert_eq!(mint_equivalences.mints().len(), 2);
+    }
+
+    /// Tests the capacity of `MintEquivalences`.
+    #[test]
+    fn test_mint_equivalences_capacity() {
+        let mut mint_equivalences = empty_mint_equivalences();
+        for _ in 0..MintEquivalences::CAPACITY {
+            mint_equivalences.update(Pubkey::new_unique(), Some(0)).unwrap();
+        }
+        assert_eq!(mint_equivalences.update(Pubkey::new_unique(), Some(0)).err(), Some(ErrorCode::MintEquivalencesFull.into()));
+
+        // Assigned mints can still be moved and removed
+        let assigned_mint = mint_equivalences.mints()[0].mint;
+        mint_equivalences.update(assigned_mint, Some(1)).unwrap();
+        mint_equivalences.update(assigned_mint, None).unwrap();
+        mint_equivalences.update(Pubkey::new_unique(), Some(0)).unwrap();
+    }
+
+    /// Tests the validation of pool mint pairs against `MintEquivalences`.
+    #[test]
+    fn test_mint_equivalences_validate_pair() {
+        let mut mint_equivalences = empty_mint_equivalences();
+        let (first_usdc, second_usdc, sol, other) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        mint_equivalences.update(first_usdc, Some(1)).unwrap();
+        mint_equivalences.update(second_usdc, Some(1)).unwrap();
+        mint_equivalences.update(sol, Some(2)).unwrap();
+
+        assert_eq!(mint_equivalences.validate_pair(&first_usdc, &second_usdc).err(), Some(ErrorCode::EquivalentMintsPair.into()));
+        assert_eq!(mint_equivalences.validate_pair(&second_usdc, &first_usdc).err(), Some(ErrorCode::EquivalentMintsPair.into()));
+        assert!(mint_equivalences.validate_pair(&first_usdc, &sol).is_ok());
+        assert!(mint_equivalences.validate_pair(&sol, &other).is_ok());
+        assert!(mint_equivalences.validate_pair(&other, &Pubkey::new_unique()).is_ok());
+    }
+
+    /// Tests the data layout of the `MintEquivalences` struct.
+    #[test]
+    fn test_mint_equivalences_data_layout() {
+        let bump = 42u8;
+        let amms_config = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let group = 7u8;
+
+        let mut data = [0u8; ANCHOR_DISCRIMINATOR + 1090];
+        let mut offset = 0;
+
+        data[offset..offset + ANCHOR_DISCRIMINATOR].copy_from_slice(&MintEquivalences::discriminator()); offset += ANCHOR_DISCRIMINATOR;
+        data[offset] = bump; offset += 1;
+        data[offset..offset + 32].copy_from_slice(amms_config.as_ref()); offset += 32;
+        data[offset] = 1; offset += 1;
+        data[offset..offset + 32].copy_from_slice(mint.as_ref()); offset += 32;
+        data[offset] = group; offset += 1;
+        offset += 31 * 33;
+
+        assert_eq!(offset, ANCHOR_DISCRIMINATOR + MintEquivalences::INIT_SPACE);
+
+        let deserialized_mint_equivalences = MintEquivalences::try_deserialize(&mut data.as_ref()).unwrap();
+
+        assert_eq!(deserialized_mint_equivalences.bump, bump);
+        assert_eq!(deserialized_mint_equivalences.amms_config, amms_config);
+        assert_eq!(deserialized_mint_equivalences.mints(), &[MintEquivalence { mint, group }]);
+
+        let mut serialized_data = Vec::new();
+        deserialized_mint_equivalences.try_serialize(&mut serialized_data).unwrap();
+        assert_eq!(serialized_data.as_slice(), data.as_ref());
+    }
+}
\ No newline at end of file
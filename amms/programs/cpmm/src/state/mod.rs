@@ -1,6 +1,29 @@
 mod amms_config;
 mod amms_configs_manager;
+mod fees;
+mod layout_version;
+mod loyalty_boost;
+mod mint_equivalences;
+mod pool_creation_limiter;
+mod pool_creation_pricing;
+mod pool_creator_permit;
+mod pool_index;
+mod router_registry;
+mod trader_fee_tiers;
+mod trader_volume;
 pub mod cp_amm;
+pub mod wp_amm;
 
 pub use amms_configs_manager::*;
-pub use amms_config::*;
\ No newline at end of file
+pub use amms_config::*;
+pub use fees::*;
+pub use layout_version::*;
+pub use loyalty_boost::*;
+pub use mint_equivalences::*;
+pub use pool_creation_limiter::*;
+pub use pool_creation_pricing::*;
+pub use pool_creator_permit::*;
+pub use pool_index::*;
+pub use router_registry::*;
+pub use trader_fee_tiers::*;
+pub use trader_volume::*;
\ No newline at end of file
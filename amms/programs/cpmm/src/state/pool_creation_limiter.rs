@@ -0,0 +1,182 @@
+use anchor_lang::{account, InitSpace};
+use anchor_lang::prelude::*;
+use crate::error::ErrorCode;
+
+/// Tracks pools created by a single creator within the current creation window.
+///
+/// The limits are configured on `AmmsConfigsManager`. A window starts at the first
+/// pool creation after the previous window expired, and the counter is reset with it.
+#[account]
+#[derive(InitSpace)]
+pub struct PoolCreationLimiter {
+    /// The canonical bump seed used for the account's PDA.
+    bump: u8, // 1 byte
+
+    /// The creator whose pool creations are tracked.
+    creator: Pubkey, // 32 bytes
+
+    /// Slot at which the current creation window started.
+    window_start_slot: u64, // 8 bytes
+
+    /// Pools created during the current window.
+    pools_created: u16, // 2 bytes
+}
+
+impl PoolCreationLimiter {
+    /// The seed used to derive the account's PDA.
+    pub const SEED: &'static [u8] = b"pool_creation_limiter";
+
+    /// Initializes the `PoolCreationLimiter` for a creator.
+    ///
+    /// # Parameters
+    /// - `creator`: The public key of the tracked creator.
+    /// - `bump`: The bump seed for the account's PDA.
+    pub(crate) fn initialize(&mut self, creator: Pubkey, bump: u8) {
+        self.bump = bump;
+        self.creator = creator;
+        self.window_start_slot = 0;
+        self.pools_created = 0;
+    }
+
+    /// Registers a pool creation in the current window.
+    ///
+    /// # Parameters
+    /// - `current_slot`: The slot in which the pool is created.
+    /// - `window_slots`: The length of a creation window in slots, `0` disables the limit.
+    /// - `max_pools_per_window`: The number of pools allowed to be created within a window.
+    ///
+    /// # Errors
+    /// - Returns `ErrorCode::PoolCreationRateLimitExceeded` if the creator reached the window limit.
+    pub(crate) fn register_pool_creation(&mut self, current_slot: u64, window_slots: u64, max_pools_per_window: u16) -> Result<()> {
+        if window_slots == 0 {
+            return Ok(());
+        }
+        if self.pools_created == 0 || current_slot >= self.window_start_slot.saturating_add(window_slots) {
+            self.window_start_slot = current_slot;
+            self.pools_created = 0;
+        }
+        require!(self.pools_created < max_pools_per_window, ErrorCode::PoolCreationRateLimitExceeded);
+        self.pools_created += 1;
+        Ok(())
+    }
+
+    /// Checks if the limiter was initialized.
+    #[inline]
+    pub fn is_initialized(&self) -> bool {
+        self.creator != Pubkey::default()
+    }
+
+    /// Returns the bump seed used for the account's PDA.
+    #[inline]
+    pub fn bump(&self) -> u8 {
+        self.bump
+    }
+
+    /// Returns the public key of the tracked creator.
+    #[inline]
+    pub fn creator(&self) -> &Pubkey {
+        &self.creator
+    }
+
+    /// Returns the slot at which the current creation window started.
+    #[inline]
+    pub fn window_start_slot(&self) -> u64 {
+        self.window_start_slot
+    }
+
+    /// Returns the number of pools created during the current window.
+    #[inline]
+    pub fn pools_created(&self) -> u16 {
+        self.pools_created
+    }
+}
+
+#[cfg(test)]
+mod pool_creation_limiter_tests {
+    use anchor_lang::Discriminator;
+    use crate::constants::ANCHOR_DISCRIMINATOR;
+    use super::*;
+
+    /// Tests the correct initialization of the `PoolCreationLimiter` struct.
+    #[test]
+    fn test_pool_creation_limiter_initialize() {
+        let mut limiter = PoolCreationLimiter {
+            bump: 0,
+            creator: Pubkey::default(),
+            window_start_slot: 10,
+            pools_created: 3,
+        };
+        assert!(!limiter.is_initialized());
+
+        let creator = Pubkey::new_unique();
+        limiter.initialize(creator, 42);
+
+        assert!(limiter.is_initialized());
+        assert_eq!(limiter.bump(), 42);
+        assert_eq!(limiter.creator().key(), creator);
+        assert_eq!(limiter.window_start_slot(), 0);
+        assert_eq!(limiter.pools_created(), 0);
+    }
+
+    /// Tests pool creations accumulation within a window and reset after it expires.
+    #[test]
+    fn test_pool_creation_limiter_register_pool_creation() {
+        let mut limiter = PoolCreationLimiter {
+            bump: 0,
+            creator: Pubkey::new_unique(),
+            window_start_slot: 0,
+            pools_created: 0,
+        };
+
+        limiter.register_pool_creation(100, 50, 2).unwrap();
+        assert_eq!(limiter.window_start_slot(), 100);
+        assert_eq!(limiter.pools_created(), 1);
+
+        limiter.register_pool_creation(149, 50, 2).unwrap();
+        assert_eq!(limiter.window_start_slot(), 100);
+        assert_eq!(limiter.pools_created(), 2);
+
+        assert!(limiter.register_pool_creation(149, 50, 2).is_err());
+        assert_eq!(limiter.pools_created(), 2);
+
+        limiter.register_pool_creation(150, 50, 2).unwrap();
+        assert_eq!(limiter.window_start_slot(), 150);
+        assert_eq!(limiter.pools_created(), 1);
+
+        // Zero window disables the limit and keeps the accounting untouched
+        limiter.register_pool_creation(151, 0, 0).unwrap();
+        assert_eq!(limiter.window_start_slot(), 150);
+        assert_eq!(limiter.pools_created(), 1);
+    }
+
+    /// Tests the data layout of the `PoolCreationLimiter` struct.
+    #[test]
+    fn test_pool_creation_limiter_data_layout() {
+        let bump = 42u8;
+        let creator = Pubkey::new_unique();
+        let window_start_slot = 1234u64;
+        let pools_created = 7u16;
+
+        let mut data = [0u8; ANCHOR_DISCRIMINATOR + 43];
+        let mut offset = 0;
+
+        data[offset..offset + ANCHOR_DISCRIMINATOR].copy_from_slice(&PoolCreationLimiter::discriminator()); offset += ANCHOR_DISCRIMINATOR;
+        data[offset] = bump; offset += 1;
+        data[offset..offset + 32].copy_from_slice(creator.as_ref()); offset += 32;
+        data[offset..offset + 8].copy_from_slice(&window_start_slot.to_le_bytes()); offset += 8;
+        data[offset..offset + 2].copy_from_slice(&pools_created.to_le_bytes()); offset += 2;
+
+        assert_eq!(offset, ANCHOR_DISCRIMINATOR + PoolCreationLimiter::INIT_SPACE);
+
+        let deserialized_limiter = PoolCreationLimiter::try_deserialize(&mut data.as_ref()).unwrap();
+
+        assert_eq!(deserialized_limiter.bump, bump);
+        assert_eq!(deserialized_limiter.creator, creator);
+        assert_eq!(deserialized_limiter.window_start_slot, window_start_slot);
+        assert_eq!(deserialized_limiter.pools_created, pools_created);
+
+        let mut serialized_data = Vec::new();
+        deserialized_limiter.try_serialize(&mut serialized_data).unwrap();
+        assert_eq!(serialized_data.as_slice(), data.as_ref());
+    }
+}
\ No newline at end of file
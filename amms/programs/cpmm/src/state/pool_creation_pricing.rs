@@ -0,0 +1,136 @@
+use anchor_lang::prelude::*;
+use crate::error::ErrorCode;
+
+/// Price of pool creation in an `AmmsConfig`, flat or rising in steps with the number of pools created.
+///
+/// The price starts at `base_price_in_lamports` and rises by `step_price_in_lamports` after every
+/// `pools_per_step` pools created under the config, up to `max_price_in_lamports`. A pricing without
+/// steps charges the base price for every pool.
+#[derive(Debug, Clone, Copy, PartialEq, AnchorSerialize, AnchorDeserialize, InitSpace)]
+pub struct PoolCreationPricing {
+    /// The price of the first pools, in lamports.
+    base_price_in_lamports: u64, // 8 bytes
+
+    /// The number of pools created per price step, zero for a flat price.
+    pools_per_step: u32, // 4 bytes
+
+    /// The price increase of every step, in lamports, zero for a flat price.
+    step_price_in_lamports: u64, // 8 bytes
+
+    /// The price the steps are capped at, in lamports.
+    max_price_in_lamports: u64, // 8 bytes
+}
+
+impl PoolCreationPricing {
+    /// The maximal price of pool creation, in lamports (100 SOL).
+    pub const MAX_PRICE_IN_LAMPORTS: u64 = 100_000_000_000;
+
+    /// Creates a new `PoolCreationPricing`.
+    ///
+    /// # Parameters
+    /// - `base_price_in_lamports`: The price of the first pools.
+    /// - `pools_per_step`: The number of pools created per price step, zero for a flat price.
+    /// - `step_price_in_lamports`: The price increase of every step, zero for a flat price.
+    /// - `max_price_in_lamports`: The price the steps are capped at.
+    ///
+    /// # Errors
+    /// - Returns `ErrorCode::ConfigPoolCreationPricingInvalid` if the maximal price exceeds `MAX_PRICE_IN_LAMPORTS`
+    ///   or is below the base price, or only one of `pools_per_step` and `step_price_in_lamports` is zero.
+    pub fn try_new(base_price_in_lamports: u64, pools_per_step: u32, step_price_in_lamports: u64, max_price_in_lamports: u64) -> Result<Self> {
+        require!(
+            base_price_in_lamports <= max_price_in_lamports &&
+            max_price_in_lamports <= Self::MAX_PRICE_IN_LAMPORTS &&
+            (pools_per_step == 0) == (step_price_in_lamports == 0),
+            ErrorCode::ConfigPoolCreationPricingInvalid
+        );
+        Ok(Self {
+            base_price_in_lamports,
+            pools_per_step,
+            step_price_in_lamports,
+            max_price_in_lamports,
+        })
+    }
+
+    /// Calculates the price of the next pool.
+    ///
+    /// # Parameters
+    /// - `pools_count`: The number of pools already created under the config.
+    ///
+    /// # Returns
+    /// - The `u64` price, in lamports.
+    pub fn price_in_lamports(&self, pools_count: u64) -> u64 {
+        if self.pools_per_step == 0 {
+            return self.base_price_in_lamports;
+        }
+        let steps = pools_count / self.pools_per_step as u64;
+        self.step_price_in_lamports.saturating_mul(steps)
+            .saturating_add(self.base_price_in_lamports)
+            .min(self.max_price_in_lamports)
+    }
+
+    /// Returns the price of the first pools, in lamports.
+    #[inline]
+    pub fn base_price_in_lamports(&self) -> u64 {
+        self.base_price_in_lamports
+    }
+
+    /// Returns the number of pools created per price step.
+    #[inline]
+    pub fn pools_per_step(&self) -> u32 {
+        self.pools_per_step
+    }
+
+    /// Returns the price increase of every step, in lamports.
+    #[inline]
+    pub fn step_price_in_lamports(&self) -> u64 {
+        self.step_price_in_lamports
+    }
+
+    /// Returns the price the steps are capped at, in lamports.
+    #[inline]
+    pub fn max_price_in_lamports(&self) -> u64 {
+        self.max_price_in_lamports
+    }
+}
+
+#[cfg(test)]
+mod pool_creation_pricing_tests {
+    use super::*;
+
+    /// Tests validation of the `PoolCreationPricing` parameters.
+    #[test]
+    fn test_pool_creation_pricing_try_new() {
+        let pricing = PoolCreationPricing::try_new(100, 10, 50, 300).unwrap();
+        assert_eq!(pricing.base_price_in_lamports(), 100);
+        assert_eq!(pricing.pools_per_step(), 10);
+        assert_eq!(pricing.step_price_in_lamports(), 50);
+        assert_eq!(pricing.max_price_in_lamports(), 300);
+
+        assert!(PoolCreationPricing::try_new(0, 0, 0, 0).is_ok());
+        assert!(PoolCreationPricing::try_new(PoolCreationPricing::MAX_PRICE_IN_LAMPORTS, 0, 0, PoolCreationPricing::MAX_PRICE_IN_LAMPORTS).is_ok());
+        assert_eq!(PoolCreationPricing::try_new(0, 0, 0, PoolCreationPricing::MAX_PRICE_IN_LAMPORTS + 1).err(), Some(ErrorCode::ConfigPoolCreationPricingInvalid.into()));
+        assert_eq!(PoolCreationPricing::try_new(301, 10, 50, 300).err(), Some(ErrorCode::ConfigPoolCreationPricingInvalid.into()));
+        assert_eq!(PoolCreationPricing::try_new(100, 0, 50, 300).err(), Some(ErrorCode::ConfigPoolCreationPricingInvalid.into()));
+        assert_eq!(PoolCreationPricing::try_new(100, 10, 0, 300).err(), Some(ErrorCode::ConfigPoolCreationPricingInvalid.into()));
+    }
+
+    /// Tests flat and stepped prices by the number of created pools.
+    #[test]
+    fn test_pool_creation_pricing_price_in_lamports() {
+        let flat = PoolCreationPricing::try_new(100, 0, 0, 300).unwrap();
+        assert_eq!(flat.price_in_lamports(0), 100);
+        assert_eq!(flat.price_in_lamports(u64::MAX), 100);
+
+        let stepped = PoolCreationPricing::try_new(100, 10, 50, 300).unwrap();
+        assert_eq!(stepped.price_in_lamports(0), 100);
+        assert_eq!(stepped.price_in_lamports(9), 100);
+        assert_eq!(stepped.price_in_lamports(10), 150);
+        assert_eq!(stepped.price_in_lamports(39), 250);
+        assert_eq!(stepped.price_in_lamports(40), 300);
+        assert_eq!(stepped.price_in_lamports(u64::MAX), 300);
+
+        let steep = PoolCreationPricing::try_new(0, 1, u64::MAX, PoolCreationPricing::MAX_PRICE_IN_LAMPORTS).unwrap();
+        assert_eq!(steep.price_in_lamports(0), 0);
+        assert_eq!(steep.price_in_lamports(2), PoolCreationPricing::MAX_PRICE_IN_LAMPORTS);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,104 @@
+use anchor_lang::{account, InitSpace};
+use anchor_lang::prelude::*;
+
+/// Permission of a creator to initialize pools in an `AmmsConfig` with the pool creation allow-list enabled.
+///
+/// Permits are issued and revoked by the configs authority, see `AmmsConfig::is_pool_creation_allow_listed`.
+/// Configs without the allow-list stay permissionless and ignore the permits.
+#[account]
+#[derive(InitSpace)]
+pub struct PoolCreatorPermit {
+    /// The canonical bump seed used for the account's PDA.
+    bump: u8, // 1 byte
+
+    /// The `AmmsConfig` the permit applies to.
+    amms_config: Pubkey, // 32 bytes
+
+    /// The creator allowed to initialize pools.
+    creator: Pubkey, // 32 bytes
+}
+
+impl PoolCreatorPermit {
+    /// The seed used to derive the account's PDA.
+    pub const SEED: &'static [u8] = b"pool_creator_permit";
+
+    /// Initializes the `PoolCreatorPermit` of a creator.
+    ///
+    /// # Parameters
+    /// - `amms_config`: The public key of the `AmmsConfig` the permit applies to.
+    /// - `creator`: The public key of the permitted creator.
+    /// - `bump`: The bump seed for the account's PDA.
+    pub(crate) fn initialize(&mut self, amms_config: Pubkey, creator: Pubkey, bump: u8) {
+        self.bump = bump;
+        self.amms_config = amms_config;
+        self.creator = creator;
+    }
+
+    /// Returns the bump seed used for the account's PDA.
+    #[inline]
+    pub fn bump(&self) -> u8 {
+        self.bump
+    }
+
+    /// Returns the public key of the `AmmsConfig` the permit applies to.
+    #[inline]
+    pub fn amms_config(&self) -> &Pubkey {
+        &self.amms_config
+    }
+
+    /// Returns the public key of the permitted creator.
+    #[inline]
+    pub fn creator(&self) -> &Pubkey {
+        &self.creator
+    }
+}
+
+#[cfg(test)]
+mod pool_creator_permit_tests {
+    use anchor_lang::Discriminator;
+    use crate::constants::ANCHOR_DISCRIMINATOR;
+    use super::*;
+
+    /// Tests the initialization of the `PoolCreatorPermit`.
+    #[test]
+    fn test_pool_creator_permit_initialize() {
+        let mut pool_creator_permit = PoolCreatorPermit {
+            bump: 0,
+            amms_config: Pubkey::default(),
+            creator: Pubkey::default(),
+        };
+        let (amms_config, creator) = (Pubkey::new_unique(), Pubkey::new_unique());
+        pool_creator_permit.initialize(amms_config, creator, 251);
+        assert_eq!(pool_creator_permit.bump(), 251);
+        assert_eq!(pool_creator_permit.amms_config(), &amms_config);
+        assert_eq!(pool_creator_permit.creator(), &creator);
+    }
+
+    /// Tests the data layout of the `PoolCreatorPermit` struct.
+    #[test]
+    fn test_pool_creator_permit_data_layout() {
+        let bump = 42u8;
+        let amms_config = Pubkey::new_unique();
+        let creator = Pubkey::new_unique();
+
+        let mut data = [0u8; ANCHOR_DISCRIMINATOR + 65];
+        let mut offset = 0;
+
+        data[offset..offset + ANCHOR_DISCRIMINATOR].copy_from_slice(&PoolCreatorPermit::discriminator()); offset += ANCHOR_DISCRIMINATOR;
+        data[offset] = bump; offset += 1;
+        data[offset..offset + 32].copy_from_slice(amms_config.as_ref()); offset += 32;
+        data[offset..offset + 32].copy_from_slice(creator.as_ref()); offset += 32;
+
+        assert_eq!(offset, ANCHOR_DISCRIMINATOR + PoolCreatorPermit::INIT_SPACE);
+
+        let deserialized_pool_creator_permit = PoolCreatorPermit::try_deserialize(&mut data.as_ref()).unwrap();
+
+        assert_eq!(deserialized_pool_creator_permit.bump, bump);
+        assert_eq!(deserialized_pool_creator_permit.amms_config, amms_config);
+        assert_eq!(deserialized_pool_creator_permit.creator, creator);
+
+        let mut serialized_pool_creator_permit = Vec::new();
+        deserialized_pool_creator_permit.try_serialize(&mut serialized_pool_creator_permit).unwrap();
+        assert_eq!(serialized_pool_creator_permit.as_slice(), data.as_ref());
+    }
+}
\ No newline at end of file
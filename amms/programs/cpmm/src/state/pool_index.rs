@@ -0,0 +1,189 @@
+use anchor_lang::{account, InitSpace};
+use anchor_lang::prelude::*;
+use crate::error::ErrorCode;
+
+/// A bucket of the append-only index of pools created under an `AmmsConfig`.
+///
+/// Pools are appended to buckets in creation order. Bucket `n` is the PDA derived from
+/// the config and `n`, and holds the pools `n * CAPACITY..(n + 1) * CAPACITY` of the config,
+/// so all pools of a config can be enumerated by walking buckets `0..=pools_count / CAPACITY`.
+#[account]
+#[derive(InitSpace)]
+pub struct PoolIndex {
+    /// The canonical bump seed used for the account's PDA.
+    bump: u8, // 1 byte
+
+    /// The `AmmsConfig` the indexed pools belong to.
+    amms_config: Pubkey, // 32 bytes
+
+    /// The position of this bucket in the chain of the config's buckets.
+    bucket: u64, // 8 bytes
+
+    /// The number of pools stored in this bucket.
+    pools_count: u16, // 2 bytes
+
+    /// The indexed pools, only the first `pools_count` entries are set.
+    pools: [Pubkey; 32], // 1024 bytes
+}
+
+impl PoolIndex {
+    /// The seed used to derive the account's PDA.
+    pub const SEED: &'static [u8] = b"pool_index";
+
+    /// The number of pools stored in a single bucket.
+    pub const CAPACITY: u16 = 32;
+
+    /// Initializes the `PoolIndex` bucket of a config.
+    ///
+    /// # Parameters
+    /// - `amms_config`: The public key of the `AmmsConfig` the bucket indexes pools of.
+    /// - `bucket`: The position of the bucket in the chain.
+    /// - `bump`: The bump seed for the account's PDA.
+    pub(crate) fn initialize(&mut self, amms_config: Pubkey, bucket: u64, bump: u8) {
+        self.bump = bump;
+        self.amms_config = amms_config;
+        self.bucket = bucket;
+        self.pools_count = 0;
+        self.pools = [Pubkey::default(); 32];
+    }
+
+    /// Appends a pool to the bucket.
+    ///
+    /// # Parameters
+    /// - `pool`: The public key of the created pool.
+    ///
+    /// # Errors
+    /// - Returns `ErrorCode::PoolIndexBucketFull` if the bucket already holds `CAPACITY` pools.
+    pub(crate) fn push(&mut self, pool: Pubkey) -> Result<()> {
+        require!(!self.is_full(), ErrorCode::PoolIndexBucketFull);
+        self.pools[self.pools_count as usize] = pool;
+        self.pools_count += 1;
+        Ok(())
+    }
+
+    /// Checks if the bucket was initialized.
+    #[inline]
+    pub fn is_initialized(&self) -> bool {
+        self.amms_config != Pubkey::default()
+    }
+
+    /// Checks if the bucket holds `CAPACITY` pools.
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.pools_count >= Self::CAPACITY
+    }
+
+    /// Returns the bump seed used for the account's PDA.
+    #[inline]
+    pub fn bump(&self) -> u8 {
+        self.bump
+    }
+
+    /// Returns the public key of the `AmmsConfig` the bucket indexes pools of.
+    #[inline]
+    pub fn amms_config(&self) -> &Pubkey {
+        &self.amms_config
+    }
+
+    /// Returns the position of the bucket in the chain.
+    #[inline]
+    pub fn bucket(&self) -> u64 {
+        self.bucket
+    }
+
+    /// Returns the pools stored in the bucket in creation order.
+    #[inline]
+    pub fn pools(&self) -> &[Pubkey] {
+        &self.pools[..self.pools_count as usize]
+    }
+}
+
+#[cfg(test)]
+mod pool_index_tests {
+    use anchor_lang::Discriminator;
+    use crate::constants::ANCHOR_DISCRIMINATOR;
+    use super::*;
+
+    /// Tests the correct initialization of the `PoolIndex` struct.
+    #[test]
+    fn test_pool_index_initialize() {
+        let mut pool_index = PoolIndex {
+            bump: 0,
+            amms_config: Pubkey::default(),
+            bucket: 0,
+            pools_count: 3,
+            pools: [Pubkey::new_unique(); 32],
+        };
+        assert!(!pool_index.is_initialized());
+
+        let amms_config = Pubkey::new_unique();
+        pool_index.initialize(amms_config, 5, 42);
+
+        assert!(pool_index.is_initialized());
+        assert_eq!(pool_index.bump(), 42);
+        assert_eq!(pool_index.amms_config().key(), amms_config);
+        assert_eq!(pool_index.bucket(), 5);
+        assert!(pool_index.pools().is_empty());
+        assert!(pool_index.pools.iter().all(|pool| *pool == Pubkey::default()));
+    }
+
+    /// Tests appending pools until the bucket is full.
+    #[test]
+    fn test_pool_index_push() {
+        let mut pool_index = PoolIndex {
+            bump: 0,
+            amms_config: Pubkey::new_unique(),
+            bucket: 0,
+            pools_count: 0,
+            pools: [Pubkey::default(); 32],
+        };
+
+        let pools: Vec<Pubkey> = (0..PoolIndex::CAPACITY).map(|_| Pubkey::new_unique()).collect();
+        for (i, pool) in pools.iter().enumerate() {
+            assert!(!pool_index.is_full());
+            pool_index.push(*pool).unwrap();
+            assert_eq!(pool_index.pools(), &pools[..=i]);
+        }
+        assert!(pool_index.is_full());
+
+        assert!(pool_index.push(Pubkey::new_unique()).is_err());
+        assert_eq!(pool_index.pools(), pools.as_slice());
+    }
+
+    /// Tests the data layout of the `PoolIndex` struct.
+    #[test]
+    fn test_pool_index_data_layout() {
+        let bump = 42u8;
+        let amms_config = Pubkey::new_unique();
+        let bucket = 7u64;
+        let pools_count = 2u16;
+        let first_pool = Pubkey::new_unique();
+        let second_pool = Pubkey::new_unique();
+
+        let mut data = [0u8; ANCHOR_DISCRIMINATOR + 1067];
+        let mut offset = 0;
+
+        data[offset..offset + ANCHOR_DISCRIMINATOR].copy_from_slice(&PoolIndex::discriminator()); offset += ANCHOR_DISCRIMINATOR;
+        data[offset] = bump; offset += 1;
+        data[offset..offset + 32].copy_from_slice(amms_config.as_ref()); offset += 32;
+        data[offset..offset + 8].copy_from_slice(&bucket.to_le_bytes()); offset += 8;
+        data[offset..offset + 2].copy_from_slice(&pools_count.to_le_bytes()); offset += 2;
+        data[offset..offset + 32].copy_from_slice(first_pool.as_ref()); offset += 32;
+        data[offset..offset + 32].copy_from_slice(second_pool.as_ref()); offset += 32;
+        offset += 30 * 32;
+
+        assert_eq!(offset, ANCHOR_DISCRIMINATOR + PoolIndex::INIT_SPACE);
+
+        let deserialized_pool_index = PoolIndex::try_deserialize(&mut data.as_ref()).unwrap();
+
+        assert_eq!(deserialized_pool_index.bump, bump);
+        assert_eq!(deserialized_pool_index.amms_config, amms_config);
+        assert_eq!(deserialized_pool_index.bucket, bucket);
+        assert_eq!(deserialized_pool_index.pools_count, pools_count);
+        assert_eq!(deserialized_pool_index.pools(), &[first_pool, second_pool]);
+
+        let mut serialized_data = Vec::new();
+        deserialized_pool_index.try_serialize(&mut serialized_data).unwrap();
+        assert_eq!(serialized_data.as_slice(), data.as_ref());
+    }
+}
\ No newline at end of file
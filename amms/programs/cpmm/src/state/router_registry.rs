@@ -0,0 +1,169 @@
+use anchor_lang::{account, InitSpace};
+use anchor_lang::prelude::*;
+use crate::error::ErrorCode;
+
+/// Router programs approved by the head authority, e.g. trusted aggregators.
+///
+/// Pools in protected mode only accept swaps invoked via CPI from an approved router, which
+/// keeps toxic order flow away from launches for a limited window, see `CpAmm::is_protected`.
+#[account]
+#[derive(InitSpace)]
+pub struct RouterRegistry {
+    /// The canonical bump seed used for the account's PDA.
+    bump: u8, // 1 byte
+
+    /// The number of approved routers.
+    routers_count: u8, // 1 byte
+
+    /// The approved router program ids, only the first `routers_count` entries are set.
+    routers: [Pubkey; 16], // 512 bytes
+}
+
+impl RouterRegistry {
+    /// The seed used to derive the account's PDA.
+    pub const SEED: &'static [u8] = b"router_registry";
+
+    /// The number of routers that can be approved.
+    pub const CAPACITY: u8 = 16;
+
+    /// Initializes the `RouterRegistry` without any approved router.
+    ///
+    /// # Parameters
+    /// - `bump`: The bump seed for the account's PDA.
+    pub(crate) fn initialize(&mut self, bump: u8) {
+        self.bump = bump;
+        self.routers_count = 0;
+        self.routers = [Pubkey::default(); 16];
+    }
+
+    /// Approves a router program or revokes its approval.
+    ///
+    /// # Parameters
+    /// - `router`: The program id of the router.
+    /// - `is_approved`: Whether the router is approved.
+    ///
+    /// # Errors
+    /// - Returns `ErrorCode::RouterRegistryFull` if a new router is approved while `CAPACITY` routers are approved.
+    pub(crate) fn update(&mut self, router: Pubkey, is_approved: bool) -> Result<()> {
+        let position = self.routers().iter().position(|approved_router| approved_router == &router);
+        match (position, is_approved) {
+            (Some(position), false) => {
+                let last = self.routers_count as usize - 1;
+                self.routers[position] = self.routers[last];
+                self.routers[last] = Pubkey::default();
+                self.routers_count -= 1;
+            }
+            (None, true) => {
+                require!(self.routers_count < Self::CAPACITY, ErrorCode::RouterRegistryFull);
+                self.routers[self.routers_count as usize] = router;
+                self.routers_count += 1;
+            }
+            (Some(_), true) | (None, false) => {}
+        }
+        Ok(())
+    }
+
+    /// Checks if a router program is approved.
+    #[inline]
+    pub fn is_approved(&self, router: &Pubkey) -> bool {
+        self.routers().contains(router)
+    }
+
+    /// Returns the bump seed used for the account's PDA.
+    #[inline]
+    pub fn bump(&self) -> u8 {
+        self.bump
+    }
+
+    /// Returns the approved router program ids.
+    #[inline]
+    pub fn routers(&self) -> &[Pubkey] {
+        &self.routers[..self.routers_count as usize]
+    }
+}
+
+#[cfg(test)]
+mod router_registry_tests {
+    use anchor_lang::Discriminator;
+    use crate::constants::ANCHOR_DISCRIMINATOR;
+    use super::*;
+
+    fn router_registry() -> RouterRegistry {
+        RouterRegistry {
+            bump: 0,
+            routers_count: 0,
+            routers: [Pubkey::default(); 16],
+        }
+    }
+
+    /// Tests approving and revoking routers of the `RouterRegistry`.
+    #[test]
+    fn test_router_registry_update() {
+        let mut router_registry = router_registry();
+        router_registry.initialize(253);
+        assert_eq!(router_registry.bump(), 253);
+        assert!(router_registry.routers().is_empty());
+
+        let (first_router, second_router, other) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        router_registry.update(first_router, true).unwrap();
+        router_registry.update(second_router, true).unwrap();
+        // Approving an approved router is a no-op
+        router_registry.update(first_router, true).unwrap();
+        assert_eq!(router_registry.routers(), &[first_router, second_router]);
+        assert!(router_registry.is_approved(&first_router));
+        assert!(!router_registry.is_approved(&other));
+
+        router_registry.update(first_router, false).unwrap();
+        assert_eq!(router_registry.routers(), &[second_router]);
+        assert_eq!(router_registry.routers[1], Pubkey::default());
+        assert!(!router_registry.is_approved(&first_router));
+
+        // Revoking an unapproved router is a no-op
+        router_registry.update(other, false).unwrap();
+        assert_eq!(router_registry.routers(), &[second_router]);
+        // The unset entries never approve the default key
+        assert!(!router_registry.is_approved(&Pubkey::default()));
+    }
+
+    /// Tests the capacity of the `RouterRegistry`.
+    #[test]
+    fn test_router_registry_capacity() {
+        let mut router_registry = router_registry();
+        for _ in 0..RouterRegistry::CAPACITY {
+            router_registry.update(Pubkey::new_unique(), true).unwrap();
+        }
+        assert_eq!(router_registry.update(Pubkey::new_unique(), true).err(), Some(ErrorCode::RouterRegistryFull.into()));
+
+        let approved_router = router_registry.routers()[0];
+        router_registry.update(approved_router, true).unwrap();
+        router_registry.update(approved_router, false).unwrap();
+        router_registry.update(Pubkey::new_unique(), true).unwrap();
+    }
+
+    /// Tests the data layout of the `RouterRegistry` struct.
+    #[test]
+    fn test_router_registry_data_layout() {
+        let bump = 42u8;
+        let router = Pubkey::new_unique();
+
+        let mut data = [0u8; ANCHOR_DISCRIMINATOR + 514];
+        let mut offset = 0;
+
+        data[offset..offset + ANCHOR_DISCRIMINATOR].copy_from_slice(&RouterRegistry::discriminator()); offset += ANCHOR_DISCRIMINATOR;
+        data[offset] = bump; offset += 1;
+        data[offset] = 1; offset += 1;
+        data[offset..offset + 32].copy_from_slice(router.as_ref()); offset += 32;
+        offset += 15 * 32;
+
+        assert_eq!(offset, ANCHOR_DISCRIMINATOR + RouterRegistry::INIT_SPACE);
+
+        let deserialized_router_registry = RouterRegistry::try_deserialize(&mut data.as_ref()).unwrap();
+
+        assert_eq!(deserialized_router_registry.bump, bump);
+        assert_eq!(deserialized_router_registry.routers(), &[router]);
+
+        let mut serialized_router_registry = Vec::new();
+        deserialized_router_registry.try_serialize(&mut serialized_router_registry).unwrap();
+        assert_eq!(serialized_router_registry.as_slice(), data.as_ref());
+    }
+}
\ No newline at end of file
@@ -0,0 +1,186 @@
+use anchor_lang::{account, InitSpace};
+use anchor_lang::prelude::*;
+use crate::error::ErrorCode;
+
+/// A providers fee discount granted to traders whose decayed volume reaches a threshold.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq, InitSpace)]
+pub struct TraderFeeTier {
+    /// The minimal decayed volume of a trader, in USD with `UsdPrice::USD_VALUE_DECIMALS` decimals.
+    pub min_volume_usd: u64,
+
+    /// The discount of the providers fee rate, measured in basis points of the rate.
+    pub discount_basis_points: u16,
+}
+
+/// Tiered providers fee discounts for high-volume traders in the pools of an `AmmsConfig`.
+///
+/// Traders passing their `TraderVolume` to swaps get the discount of the highest tier their decayed
+/// volume reaches. Only the providers fee is discounted, the protocol fee is charged in full.
+#[account]
+#[derive(InitSpace)]
+pub struct TraderFeeTiers {
+    /// The canonical bump seed used for the account's PDA.
+    bump: u8, // 1 byte
+
+    /// The `AmmsConfig` the tiers apply to.
+    amms_config: Pubkey, // 32 bytes
+
+    /// The number of configured tiers.
+    tiers_count: u8, // 1 byte
+
+    /// The configured tiers by ascending volume, only the first `tiers_count` entries are set.
+    tiers: [TraderFeeTier; 8], // 80 bytes
+}
+
+impl TraderFeeTiers {
+    /// The seed used to derive the account's PDA.
+    pub const SEED: &'static [u8] = b"trader_fee_tiers";
+
+    /// The number of tiers that can be configured.
+    pub const CAPACITY: u8 = 8;
+
+    /// The maximal discount of a tier, measured in basis points of the providers fee rate (5000 = 50%).
+    pub const MAX_DISCOUNT_BASIS_POINTS: u16 = 5000;
+
+    /// Initializes the `TraderFeeTiers` of a config without any tier.
+    ///
+    /// # Parameters
+    /// - `amms_config`: The public key of the `AmmsConfig` the tiers apply to.
+    /// - `bump`: The bump seed for the account's PDA.
+    pub(crate) fn initialize(&mut self, amms_config: Pubkey, bump: u8) {
+        self.bump = bump;
+        self.amms_config = amms_config;
+        self.tiers_count = 0;
+        self.tiers = [TraderFeeTier::default(); 8];
+    }
+
+    /// Replaces the configured tiers, an empty table disables the discounts.
+    ///
+    /// # Parameters
+    /// - `tiers`: The new tiers by strictly ascending volume and discount.
+    ///
+    /// # Errors
+    /// - Returns `ErrorCode::TraderFeeTiersInvalid` if there are more than `CAPACITY` tiers, a discount
+    ///   is zero or exceeds `MAX_DISCOUNT_BASIS_POINTS`, or the tiers are not strictly ascending.
+    pub(crate) fn update(&mut self, tiers: &[TraderFeeTier]) -> Result<()> {
+        require!(
+            tiers.len() <= Self::CAPACITY as usize &&
+                tiers.iter().all(|tier| tier.discount_basis_points > 0 && tier.discount_basis_points <= Self::MAX_DISCOUNT_BASIS_POINTS) &&
+                tiers.windows(2).all(|pair| pair[0].min_volume_usd < pair[1].min_volume_usd && pair[0].discount_basis_points < pair[1].discount_basis_points),
+            ErrorCode::TraderFeeTiersInvalid
+        );
+        self.tiers = [TraderFeeTier::default(); 8];
+        self.tiers[..tiers.len()].copy_from_slice(tiers);
+        self.tiers_count = tiers.len() as u8;
+        Ok(())
+    }
+
+    /// Returns the highest tier reached by a volume.
+    ///
+    /// # Parameters
+    /// - `volume_usd`: The decayed volume of the trader, in USD.
+    ///
+    /// # Returns
+    /// - `Some((u8, &TraderFeeTier))` with the index and the reached tier.
+    /// - `None` if the volume is below every tier.
+    pub fn tier(&self, volume_usd: u64) -> Option<(u8, &TraderFeeTier)> {
+        self.tiers().iter()
+            .enumerate()
+            .rev()
+            .find(|(_, tier)| volume_usd >= tier.min_volume_usd)
+            .map(|(index, tier)| (index as u8, tier))
+    }
+
+    /// Applies the discount of a tier to a providers fee rate.
+    ///
+    /// The discount is rounded down, so the discounted rate never falls below the exact one.
+    ///
+    /// # Parameters
+    /// - `providers_fee_rate_basis_points`: The providers fee rate of the swap, in basis points.
+    /// - `tier`: The tier reached by the trader.
+    ///
+    /// # Returns
+    /// - The `u16` discounted providers fee rate, measured in basis points.
+    pub fn discounted_providers_fee_rate(providers_fee_rate_basis_points: u16, tier: &TraderFeeTier) -> u16 {
+        let discount = providers_fee_rate_basis_points as u32 * tier.discount_basis_points as u32 / 10000;
+        providers_fee_rate_basis_points - discount as u16
+    }
+
+    /// Returns the bump seed used for the account's PDA.
+    #[inline]
+    pub fn bump(&self) -> u8 {
+        self.bump
+    }
+
+    /// Returns the public key of the `AmmsConfig` the tiers apply to.
+    #[inline]
+    pub fn amms_config(&self) -> &Pubkey {
+        &self.amms_config
+    }
+
+    /// Returns the configured tiers by ascending volume.
+    #[inline]
+    pub fn tiers(&self) -> &[TraderFeeTier] {
+        &self.tiers[..self.tiers_count as usize]
+    }
+}
+
+#[cfg(test)]
+mod trader_fee_tiers_tests {
+    use super::*;
+
+    fn tier(min_volume_usd: u64, discount_basis_points: u16) -> TraderFeeTier {
+        TraderFeeTier { min_volume_usd, discount_basis_points }
+    }
+
+    fn trader_fee_tiers() -> TraderFeeTiers {
+        TraderFeeTiers {
+            bump: 0,
+            amms_config: Pubkey::default(),
+            tiers_count: 0,
+            tiers: [TraderFeeTier::default(); 8],
+        }
+    }
+
+    /// Tests validation of the tier tables.
+    #[test]
+    fn test_trader_fee_tiers_update() {
+        let mut trader_fee_tiers = trader_fee_tiers();
+        let amms_config = Pubkey::new_unique();
+        trader_fee_tiers.initialize(amms_config, 254);
+        assert_eq!(trader_fee_tiers.bump(), 254);
+        assert_eq!(trader_fee_tiers.amms_config(), &amms_config);
+        assert!(trader_fee_tiers.tiers().is_empty());
+
+        let tiers = [tier(1_000, 500), tier(10_000, 1000), tier(100_000, TraderFeeTiers::MAX_DISCOUNT_BASIS_POINTS)];
+        trader_fee_tiers.update(&tiers).unwrap();
+        assert_eq!(trader_fee_tiers.tiers(), &tiers);
+
+        assert_eq!(trader_fee_tiers.update(&[tier(1_000, 0)]).err(), Some(ErrorCode::TraderFeeTiersInvalid.into()));
+        assert_eq!(trader_fee_tiers.update(&[tier(1_000, 5001)]).err(), Some(ErrorCode::TraderFeeTiersInvalid.into()));
+        assert_eq!(trader_fee_tiers.update(&[tier(1_000, 500), tier(1_000, 1000)]).err(), Some(ErrorCode::TraderFeeTiersInvalid.into()));
+        assert_eq!(trader_fee_tiers.update(&[tier(1_000, 500), tier(2_000, 500)]).err(), Some(ErrorCode::TraderFeeTiersInvalid.into()));
+        assert_eq!(trader_fee_tiers.update(&(1..=9).map(|index| tier(index, index as u16)).collect::<Vec<_>>()).err(), Some(ErrorCode::TraderFeeTiersInvalid.into()));
+        assert_eq!(trader_fee_tiers.tiers(), &tiers);
+
+        trader_fee_tiers.update(&[]).unwrap();
+        assert!(trader_fee_tiers.tiers().is_empty());
+    }
+
+    /// Tests the tier reached by volumes and the discounted providers fee rates.
+    #[test]
+    fn test_trader_fee_tiers_tier() {
+        let mut trader_fee_tiers = trader_fee_tiers();
+        trader_fee_tiers.update(&[tier(1_000, 500), tier(10_000, 2500)]).unwrap();
+        assert_eq!(trader_fee_tiers.tier(0), None);
+        assert_eq!(trader_fee_tiers.tier(999), None);
+        assert_eq!(trader_fee_tiers.tier(1_000), Some((0, &tier(1_000, 500))));
+        assert_eq!(trader_fee_tiers.tier(9_999), Some((0, &tier(1_000, 500))));
+        assert_eq!(trader_fee_tiers.tier(u64::MAX), Some((1, &tier(10_000, 2500))));
+
+        assert_eq!(TraderFeeTiers::discounted_providers_fee_rate(30, &tier(1_000, 2500)), 23);
+        assert_eq!(TraderFeeTiers::discounted_providers_fee_rate(1, &tier(1_000, 5000)), 1);
+        assert_eq!(TraderFeeTiers::discounted_providers_fee_rate(0, &tier(1_000, 5000)), 0);
+        assert_eq!(TraderFeeTiers::discounted_providers_fee_rate(10000, &tier(1_000, 5000)), 5000);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,162 @@
+use anchor_lang::{account, InitSpace};
+use anchor_lang::prelude::*;
+
+/// Time-decayed swap volume of a trader in the pools of an `AmmsConfig`, granting `TraderFeeTiers` discounts.
+///
+/// The volume approximates a rolling 30-day window without storing per-swap history: on every update,
+/// the recorded volume decays linearly with the time elapsed since the previous one and vanishes after
+/// `DECAY_WINDOW_SECONDS` without swaps, then the volume of the new swap is added. Only swaps in pools
+/// with a USD oracle add volume, as pools of different quote mints are only comparable in USD.
+#[account]
+#[derive(InitSpace)]
+pub struct TraderVolume {
+    /// The canonical bump seed used for the account's PDA.
+    bump: u8, // 1 byte
+
+    /// The public key of the `AmmsConfig` whose pools the volume is recorded in.
+    amms_config: Pubkey, // 32 bytes
+
+    /// The public key of the trader.
+    trader: Pubkey, // 32 bytes
+
+    /// The volume at `last_update_timestamp`, in USD with `UsdPrice::USD_VALUE_DECIMALS` decimals.
+    volume_usd: u64, // 8 bytes
+
+    /// The unix timestamp of the last update of the volume.
+    last_update_timestamp: i64, // 8 bytes
+}
+
+impl TraderVolume {
+    /// The seed used to derive the account's PDA.
+    pub const SEED: &'static [u8] = b"trader_volume";
+
+    /// The duration after which recorded volume has fully decayed, in seconds (30 days).
+    pub const DECAY_WINDOW_SECONDS: u64 = 30 * 24 * 60 * 60;
+
+    /// Initializes the `TraderVolume` of a trader in a config.
+    ///
+    /// # Parameters
+    /// - `amms_config`: The public key of the `AmmsConfig`.
+    /// - `trader`: The public key of the trader.
+    /// - `bump`: The bump seed for the account's PDA.
+    /// - `current_timestamp`: The current unix timestamp.
+    pub(crate) fn initialize(&mut self, amms_config: Pubkey, trader: Pubkey, bump: u8, current_timestamp: i64) {
+        self.bump = bump;
+        self.amms_config = amms_config;
+        self.trader = trader;
+        self.volume_usd = 0;
+        self.last_update_timestamp = current_timestamp;
+    }
+
+    /// Decays the recorded volume to the current time and adds the volume of a swap.
+    ///
+    /// # Parameters
+    /// - `volume_usd`: The USD volume of the swap.
+    /// - `current_timestamp`: The current unix timestamp.
+    pub(crate) fn record_swap(&mut self, volume_usd: u64, current_timestamp: i64) {
+        self.volume_usd = self.decayed_volume_usd(current_timestamp).saturating_add(volume_usd);
+        self.last_update_timestamp = self.last_update_timestamp.max(current_timestamp);
+    }
+
+    /// Calculates the volume decayed to the given time.
+    ///
+    /// # Parameters
+    /// - `current_timestamp`: The current unix timestamp.
+    ///
+    /// # Returns
+    /// - The `u64` decayed volume, in USD.
+    pub fn decayed_volume_usd(&self, current_timestamp: i64) -> u64 {
+        let elapsed_seconds = current_timestamp.saturating_sub(self.last_update_timestamp).max(0) as u64;
+        if elapsed_seconds >= Self::DECAY_WINDOW_SECONDS {
+            return 0;
+        }
+        let remaining_seconds = Self::DECAY_WINDOW_SECONDS - elapsed_seconds;
+        (self.volume_usd as u128 * remaining_seconds as u128 / Self::DECAY_WINDOW_SECONDS as u128) as u64
+    }
+
+    /// Returns the bump seed used for the account's PDA.
+    #[inline]
+    pub fn bump(&self) -> u8 {
+        self.bump
+    }
+
+    /// Returns the public key of the `AmmsConfig` whose pools the volume is recorded in.
+    #[inline]
+    pub fn amms_config(&self) -> &Pubkey {
+        &self.amms_config
+    }
+
+    /// Returns the public key of the trader.
+    #[inline]
+    pub fn trader(&self) -> &Pubkey {
+        &self.trader
+    }
+
+    /// Returns the volume recorded at the last update, in USD.
+    #[inline]
+    pub fn volume_usd(&self) -> u64 {
+        self.volume_usd
+    }
+
+    /// Returns the unix timestamp of the last update of the volume.
+    #[inline]
+    pub fn last_update_timestamp(&self) -> i64 {
+        self.last_update_timestamp
+    }
+}
+
+#[cfg(test)]
+mod trader_volume_tests {
+    use super::*;
+
+    fn trader_volume(current_timestamp: i64) -> TraderVolume {
+        let mut trader_volume = TraderVolume {
+            bump: 0,
+            amms_config: Pubkey::default(),
+            trader: Pubkey::default(),
+            volume_usd: u64::MAX,
+            last_update_timestamp: 0,
+        };
+        trader_volume.initialize(Pubkey::new_unique(), Pubkey::new_unique(), 255, current_timestamp);
+        trader_volume
+    }
+
+    /// Tests the linear decay of the recorded volume.
+    #[test]
+    fn test_trader_volume_decay() {
+        let window = TraderVolume::DECAY_WINDOW_SECONDS as i64;
+        let mut trader_volume = trader_volume(1_000);
+        assert_eq!(trader_volume.volume_usd(), 0);
+        assert_eq!(trader_volume.bump(), 255);
+
+        trader_volume.record_swap(3_000_000, 1_000);
+        assert_eq!(trader_volume.decayed_volume_usd(1_000), 3_000_000);
+        assert_eq!(trader_volume.decayed_volume_usd(0), 3_000_000);
+        assert_eq!(trader_volume.decayed_volume_usd(1_000 + window / 3), 2_000_000);
+        assert_eq!(trader_volume.decayed_volume_usd(1_000 + window - 1), 1);
+        assert_eq!(trader_volume.decayed_volume_usd(1_000 + window), 0);
+        assert_eq!(trader_volume.decayed_volume_usd(i64::MAX), 0);
+    }
+
+    /// Tests recording swaps on a decaying volume.
+    #[test]
+    fn test_trader_volume_record_swap() {
+        let window = TraderVolume::DECAY_WINDOW_SECONDS as i64;
+        let mut trader_volume = trader_volume(0);
+        trader_volume.record_swap(3_000_000, 0);
+        trader_volume.record_swap(1_000_000, window / 3);
+        assert_eq!(trader_volume.volume_usd(), 3_000_000);
+        assert_eq!(trader_volume.last_update_timestamp(), window / 3);
+
+        // A swap in an earlier timestamp than the last update doesn't rewind the decay
+        trader_volume.record_swap(500_000, 0);
+        assert_eq!(trader_volume.volume_usd(), 3_500_000);
+        assert_eq!(trader_volume.last_update_timestamp(), window / 3);
+
+        trader_volume.record_swap(u64::MAX, window / 3);
+        assert_eq!(trader_volume.volume_usd(), u64::MAX);
+
+        trader_volume.record_swap(1, window * 2);
+        assert_eq!(trader_volume.volume_usd(), 1);
+    }
+}
\ No newline at end of file
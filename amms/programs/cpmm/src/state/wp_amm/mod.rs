@@ -0,0 +1,8 @@
+#[allow(clippy::module_inception)]
+mod wp_amm;
+mod wp_amm_calculate;
+mod wp_amm_core;
+
+pub use wp_amm::*;
+pub use wp_amm_core::*;
+pub(crate) use wp_amm_calculate::*;
\ No newline at end of file
@@ -0,0 +1,779 @@
+use anchor_lang::{account, InitSpace};
+use anchor_lang::prelude::*;
+use crate::error::ErrorCode;
+use crate::state::validate_fee_rates;
+use super::{WpAmmCalculate, WpAmmCore, WP_AMM_TOKENS_COUNT};
+
+/// Represents a Weighted Product Automated Market Maker (AMM) pool of three tokens.
+///
+/// The `WpAmm` struct is a research prototype of a Balancer-style pool, where each token
+/// has its own weight and swaps keep the weighted geometric mean of the pool's liquidity constant.
+///
+/// Swaps charge the providers and protocol fees of the `AmmsConfig`. The protocol fees are kept
+/// in the vaults outside of the liquidity until they are collected by the fee authority.
+///
+/// The instructions of weighted pools are only enabled in builds with the `wp-amm` feature.
+#[account]
+#[derive(InitSpace)]
+pub struct WpAmm {
+    /// Whether the AMM has been initialized.
+    is_initialized: bool, // 1 byte
+
+    /// Whether the AMM has been launched and is active.
+    is_launched: bool, // 1 byte
+
+    /// Canonical bump seed for the account's PDA.
+    bump: [u8; 1], // 1 byte
+
+    /// Canonical bump seeds for the token vaults PDAs.
+    vaults_bumps: [u8; WP_AMM_TOKENS_COUNT], // 3 bytes
+
+    /// Canonical bump seed for the locked LP vault PDA.
+    locked_lp_vault_bump: [u8; 1], // 1 byte
+
+    /// Weights of the tokens, measured in basis points and summing up to 10000.
+    weights_basis_points: [u16; WP_AMM_TOKENS_COUNT], // 6 bytes
+
+    /// Initial liquidity that is permanently locked after the pool launch.
+    initial_locked_liquidity: u64, // 8 bytes
+
+    /// Amount of each token currently in the pool's vaults.
+    liquidity: [u64; WP_AMM_TOKENS_COUNT], // 24 bytes
+
+    /// Total supply of LP tokens minted to liquidity providers.
+    lp_tokens_supply: u64, // 8 bytes
+
+    /// Public keys of the tokens' mints.
+    mints: [Pubkey; WP_AMM_TOKENS_COUNT], // 96 bytes
+
+    /// Public key of the LP token's mint.
+    pub lp_mint: Pubkey, // 32 bytes
+
+    /// Public keys of the vaults holding the tokens.
+    vaults: [Pubkey; WP_AMM_TOKENS_COUNT], // 96 bytes
+
+    /// Public key of the vault holding locked LP tokens.
+    locked_lp_vault: Pubkey, // 32 bytes
+
+    /// Public key of the associated `AmmsConfig` account.
+    amms_config: Pubkey, // 32 bytes
+
+    /// Public key of the WpAmm creator account.
+    creator: Pubkey, // 32 bytes
+
+    /// Protocol fees of each token accumulated and available for redemption.
+    protocol_fees_to_redeem: [u64; WP_AMM_TOKENS_COUNT], // 24 bytes
+}
+
+impl WpAmm {
+    /// Seed used for generating the PDA.
+    pub const SEED: &'static [u8] = b"wp_amm";
+
+    /// Seed used for generating the vaults PDAs.
+    pub const VAULT_SEED: &'static [u8] = b"vault";
+
+    /// Offset of the first of `mints` in the account data, including the discriminator.
+    ///
+    /// The offsets locate the fields matched by `memcmp` filters of `getProgramAccounts`, like the
+    /// offsets of `CpAmm`. The mint `i` starts at `MINTS_OFFSET + 32 * i`.
+    pub const MINTS_OFFSET: usize = 61;
+
+    /// Offset of `lp_mint` in the account data, see `MINTS_OFFSET`.
+    pub const LP_MINT_OFFSET: usize = 157;
+
+    /// Offset of `amms_config` in the account data, see `MINTS_OFFSET`.
+    pub const AMMS_CONFIG_OFFSET: usize = 317;
+
+    /// Offset of `creator` in the account data, see `MINTS_OFFSET`.
+    pub const CREATOR_OFFSET: usize = 349;
+
+    /// Returns the seeds for generating the PDA.
+    ///
+    /// The PDA is derived using the `SEED`, the `lp_mint`, and the `bump` value.
+    pub fn seeds(&self) -> [&[u8]; 3] {
+        [Self::SEED, self.lp_mint.as_ref(), self.bump.as_ref()]
+    }
+
+    /// Checks if the AMM has been initialized.
+    #[inline]
+    pub fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+
+    /// Checks if the AMM has been launched and is active.
+    #[inline]
+    pub fn is_launched(&self) -> bool {
+        self.is_launched
+    }
+
+    /// Returns the canonical bump value for the PDA.
+    #[inline]
+    pub fn bump(&self) -> u8 {
+        self.bump[0]
+    }
+
+    /// Returns the canonical bump value for the vault PDA of the token at `index`.
+    #[inline]
+    pub fn vault_bump(&self, index: usize) -> u8 {
+        self.vaults_bumps[index]
+    }
+
+    /// Returns the canonical bump value for the locked LP vault PDA.
+    #[inline]
+    pub fn locked_lp_vault_bump(&self) -> u8 {
+        self.locked_lp_vault_bump[0]
+    }
+
+    /// Returns the public keys of the tokens' mints.
+    #[inline]
+    pub fn mints(&self) -> &[Pubkey; WP_AMM_TOKENS_COUNT] {
+        &self.mints
+    }
+
+    /// Returns the index of the token with the given mint, if it belongs to the AMM.
+    #[inline]
+    pub fn mint_index(&self, mint: &Pubkey) -> Option<usize> {
+        self.mints.iter().position(|pool_mint| pool_mint == mint)
+    }
+
+    /// Returns the public keys of the vaults holding the tokens.
+    #[inline]
+    pub fn vaults(&self) -> &[Pubkey; WP_AMM_TOKENS_COUNT] {
+        &self.vaults
+    }
+
+    /// Returns the public key of the vault holding the token with the given mint, if it belongs to the AMM.
+    #[inline]
+    pub fn vault_for_mint(&self, mint: &Pubkey) -> Option<&Pubkey> {
+        self.mint_index(mint).map(|index| &self.vaults[index])
+    }
+
+    /// Returns the public key of the vault holding locked LP tokens.
+    #[inline]
+    pub fn locked_lp_vault(&self) -> &Pubkey {
+        &self.locked_lp_vault
+    }
+
+    /// Returns the public key of the associated `AmmsConfig` account.
+    #[inline]
+    pub fn amms_config(&self) -> &Pubkey {
+        &self.amms_config
+    }
+
+    /// Returns the public key of the WpAmm creator account.
+    #[inline]
+    pub fn creator(&self) -> &Pubkey {
+        &self.creator
+    }
+
+    /// Returns the protocol fees of each token available for redemption.
+    #[inline]
+    pub fn protocol_fees_to_redeem(&self) -> [u64; WP_AMM_TOKENS_COUNT] {
+        self.protocol_fees_to_redeem
+    }
+}
+
+/// Implements the `WpAmmCore` trait for the `WpAmm` struct.
+///
+/// This implementation auto implements 'WpAmmCalculate' trait that defines core logic and calculations for the weighted product AMM.
+impl WpAmmCore for WpAmm {
+    fn weights_basis_points(&self) -> [u16; WP_AMM_TOKENS_COUNT] {
+        self.weights_basis_points
+    }
+
+    fn liquidity(&self) -> [u64; WP_AMM_TOKENS_COUNT] {
+        self.liquidity
+    }
+
+    fn lp_tokens_supply(&self) -> u64 {
+        self.lp_tokens_supply
+    }
+}
+
+impl WpAmm {
+    /// Validates the current state of the AMM to ensure it is ready for operations.
+    ///
+    /// # Returns
+    /// - `Ok(())` if the AMM is launched, holds every token and has a positive supply of LP tokens.
+    /// - `Err(ErrorCode)` if any of the checks fail.
+    #[inline]
+    fn check_state(&self) -> Result<()> {
+        require!(self.is_launched, ErrorCode::WpAmmNotLaunched);
+        require!(self.liquidity.iter().all(|liquidity| *liquidity > 0), ErrorCode::BaseLiquidityIsZero);
+        require!(self.lp_tokens_supply > 0, ErrorCode::LpTokensSupplyIsZero);
+        Ok(())
+    }
+
+    /// Prepares the payload for launching the AMM with the provided liquidity.
+    ///
+    /// # Parameters
+    /// - `liquidity`: The amount of each token to add during the launch.
+    ///
+    /// # Returns
+    /// - `Ok(LaunchPayload)` containing the calculated launch details.
+    /// - `Err(ErrorCode)` if any preconditions fail or calculations encounter errors.
+    #[inline(never)]
+    pub fn get_launch_payload(&self, liquidity: [u64; WP_AMM_TOKENS_COUNT]) -> Result<LaunchPayload> {
+        require!(!self.is_launched, ErrorCode::WpAmmAlreadyLaunched);
+        require!(self.is_initialized, ErrorCode::WpAmmNotInitialized);
+        require!(liquidity.iter().all(|liquidity| *liquidity > 0), ErrorCode::ProvidedBaseLiquidityIsZero);
+
+        let invariant = Self::calculate_invariant(liquidity, self.weights_basis_points).ok_or(ErrorCode::WpAmmInvariantCalculationFailed)?;
+        let (lp_tokens_supply, initial_locked_liquidity) = Self::calculate_launch_lp_tokens(invariant)?;
+
+        Ok(LaunchPayload::new(initial_locked_liquidity, liquidity, lp_tokens_supply))
+    }
+
+    /// Prepares the payload for adding liquidity to the AMM.
+    ///
+    /// The LP tokens are minted for the smallest share of the pool among the provided tokens, so
+    /// `min_lp_tokens` protects the provider from donating liquidity after the pool proportions moved.
+    ///
+    /// # Parameters
+    /// - `liquidity`: The provided amount of each token.
+    /// - `min_lp_tokens`: The minimal amount of LP tokens the provide must mint.
+    ///
+    /// # Returns
+    /// - `Ok(ProvidePayload)` containing the updated pool state and LP tokens to mint.
+    /// - `Err(ErrorCode::ProvideLpTokensBelowMinimum)` if fewer than `min_lp_tokens` LP tokens would be minted.
+    /// - `Err(ErrorCode)` if any checks fail or calculations encounter errors.
+    #[inline(never)]
+    pub fn get_provide_payload(&self, liquidity: [u64; WP_AMM_TOKENS_COUNT], min_lp_tokens: u64) -> Result<ProvidePayload> {
+        self.check_state()?;
+        require!(liquidity.iter().all(|liquidity| *liquidity > 0), ErrorCode::ProvidedBaseLiquidityIsZero);
+
+        let lp_tokens_to_mint = self.calculate_lp_mint_for_provided_liquidity(liquidity).ok_or(ErrorCode::LpTokensCalculationFailed)?;
+        require!(lp_tokens_to_mint >= min_lp_tokens, ErrorCode::ProvideLpTokensBelowMinimum);
+        let lp_tokens_supply = self.lp_tokens_supply.checked_add(lp_tokens_to_mint).ok_or(ErrorCode::ProvideOverflowError)?;
+
+        let mut new_liquidity = self.liquidity;
+        for (new_token_liquidity, provided) in new_liquidity.iter_mut().zip(liquidity) {
+            *new_token_liquidity = new_token_liquidity.checked_add(provided).ok_or(ErrorCode::ProvideOverflowError)?;
+        }
+
+        Ok(ProvidePayload::new(new_liquidity, lp_tokens_supply, lp_tokens_to_mint))
+    }
+
+    /// Prepares the payload for withdrawing liquidity from the AMM.
+    ///
+    /// # Parameters
+    /// - `lp_tokens`: The number of LP tokens to redeem for liquidity withdrawal.
+    ///
+    /// # Returns
+    /// - `Ok(WithdrawPayload)` containing the updated pool state and withdrawn liquidity amounts.
+    /// - `Err(ErrorCode)` if any checks fail or calculations encounter errors.
+    #[inline(never)]
+    pub fn get_withdraw_payload(&self, lp_tokens: u64) -> Result<WithdrawPayload> {
+        self.check_state()?;
+        require!(lp_tokens > 0, ErrorCode::ProvidedLpTokensIsZero);
+
+        let lp_tokens_supply = self.lp_tokens_supply.checked_sub(lp_tokens).ok_or(ErrorCode::WithdrawOverflowError)?;
+        let withdraw_liquidity = self.calculate_liquidity_from_share(lp_tokens).ok_or(ErrorCode::WithdrawLiquidityCalculationFailed)?;
+
+        let mut new_liquidity = self.liquidity;
+        for (new_token_liquidity, withdrawn) in new_liquidity.iter_mut().zip(withdraw_liquidity) {
+            *new_token_liquidity = new_token_liquidity.checked_sub(withdrawn).ok_or(ErrorCode::WithdrawOverflowError)?;
+            require!(*new_token_liquidity > 0, ErrorCode::WithdrawLiquidityCalculationFailed);
+        }
+
+        Ok(WithdrawPayload::new(new_liquidity, lp_tokens_supply, withdraw_liquidity))
+    }
+
+    /// Computes the swap payload for exchanging one token of the AMM for another.
+    ///
+    /// # Parameters
+    /// - `in_index`: The index of the token being swapped in.
+    /// - `out_index`: The index of the token being swapped out.
+    /// - `swap_amount`: The amount of the input token.
+    /// - `estimated_result`: Expected amount of the output token.
+    /// - `allowed_slippage`: Maximum permissible deviation from `estimated_result`.
+    /// - `providers_fee_rate_basis_points`: The liquidity provider's fee rate in basis points.
+    /// - `protocol_fee_rate_basis_points`: The protocol fee rate in basis points.
+    ///
+    /// # Returns
+    /// - `Ok(SwapPayload)`: Contains the updated liquidity, protocol fees and the output amount.
+    /// - `Err(ErrorCode)`: If any validation fails (e.g., invalid tokens, overflow, or slippage exceeded).
+    #[allow(clippy::too_many_arguments)]
+    #[inline(never)]
+    pub fn get_swap_payload(&self, in_index: usize, out_index: usize, swap_amount: u64, estimated_result: u64, allowed_slippage: u64, providers_fee_rate_basis_points: u16, protocol_fee_rate_basis_points: u16) -> Result<SwapPayload> {
+        self.check_state()?;
+        require!(in_index != out_index && in_index < WP_AMM_TOKENS_COUNT && out_index < WP_AMM_TOKENS_COUNT, ErrorCode::WpAmmInvalidSwapMints);
+        require!(swap_amount > 0, ErrorCode::SwapAmountIsZero);
+        require!(estimated_result > 0, ErrorCode::EstimatedResultIsZero);
+        validate_fee_rates(providers_fee_rate_basis_points, protocol_fee_rate_basis_points)?;
+
+        let providers_fee_amount = Self::calculate_fee_amount(swap_amount, providers_fee_rate_basis_points);
+        require!(providers_fee_amount > 0 || providers_fee_rate_basis_points == 0, ErrorCode::SwapFeesAreZero);
+        let protocol_fee_amount = Self::calculate_fee_amount(swap_amount, protocol_fee_rate_basis_points);
+        require!(protocol_fee_amount > 0 || protocol_fee_rate_basis_points == 0, ErrorCode::SwapFeesAreZero);
+        let amount_after_fees = swap_amount
+            .checked_sub(providers_fee_amount)
+            .and_then(|amount| amount.checked_sub(protocol_fee_amount))
+            .ok_or(ErrorCode::SwapOverflowError)?;
+
+        let amount_to_withdraw = self.calculate_swap_result(in_index, out_index, amount_after_fees).ok_or(ErrorCode::AfterswapCalculationFailed)?;
+
+        let mut new_liquidity = self.liquidity;
+        new_liquidity[in_index] = new_liquidity[in_index].checked_add(amount_after_fees).ok_or(ErrorCode::SwapOverflowError)?;
+        new_liquidity[out_index] = new_liquidity[out_index].checked_sub(amount_to_withdraw).ok_or(ErrorCode::SwapOverflowError)?;
+
+        self.validate_swap_invariant(in_index, out_index, new_liquidity)?;
+        Self::check_swap_result(amount_to_withdraw, estimated_result, allowed_slippage)?;
+
+        // Providers fee stays in the pool and increases the invariant
+        new_liquidity[in_index] = new_liquidity[in_index].checked_add(providers_fee_amount).ok_or(ErrorCode::SwapOverflowError)?;
+        // Protocol fee stays in the vault outside of the liquidity
+        let mut protocol_fees_to_redeem = self.protocol_fees_to_redeem;
+        protocol_fees_to_redeem[in_index] = protocol_fees_to_redeem[in_index].checked_add(protocol_fee_amount).ok_or(ErrorCode::SwapOverflowError)?;
+
+        Ok(SwapPayload::new(new_liquidity, protocol_fees_to_redeem, amount_to_withdraw))
+    }
+}
+
+impl WpAmm {
+    /// Initializes the AMM with the provided token mints, weights and configuration.
+    ///
+    /// # Parameters
+    /// - `mints`: The mints of the pool's tokens.
+    /// - `weights_basis_points`: The weights of the pool's tokens, measured in basis points.
+    /// - `lp_mint`: The mint of the LP token.
+    /// - `vaults`: The vaults holding the pool's tokens.
+    /// - `locked_lp_vault`: The vault holding locked LP tokens.
+    /// - `amms_config`: The configuration account for the AMM.
+    /// - `creator`: The creator of the AMM.
+    /// - `bump`: The canonical bump seed for the AMM's PDA.
+    /// - `vaults_bumps`: The canonical bump seeds for the vaults PDAs.
+    /// - `locked_lp_vault_bump`: The canonical bump seed for the locked LP vault PDA.
+    ///
+    /// # Returns
+    /// - `Ok(())` if the initialization is successful.
+    /// - `Err(ErrorCode)` if the AMM is already initialized or the weights are invalid.
+    #[allow(clippy::too_many_arguments)]
+    #[inline(never)]
+    pub(crate) fn initialize(
+        &mut self,
+        mints: [Pubkey; WP_AMM_TOKENS_COUNT],
+        weights_basis_points: [u16; WP_AMM_TOKENS_COUNT],
+        lp_mint: Pubkey,
+        vaults: [Pubkey; WP_AMM_TOKENS_COUNT],
+        locked_lp_vault: Pubkey,
+        amms_config: Pubkey,
+        creator: Pubkey,
+        bump: u8,
+        vaults_bumps: [u8; WP_AMM_TOKENS_COUNT],
+        locked_lp_vault_bump: u8,
+    ) -> Result<()> {
+        require!(!self.is_initialized, ErrorCode::WpAmmAlreadyInitialized);
+        Self::validate_weights(weights_basis_points)?;
+
+        self.is_initialized = true;
+        self.is_launched = false;
+
+        self.mints = mints;
+        self.weights_basis_points = weights_basis_points;
+        self.lp_mint = lp_mint;
+        self.vaults = vaults;
+        self.locked_lp_vault = locked_lp_vault;
+        self.amms_config = amms_config;
+        self.creator = creator;
+
+        self.bump = [bump];
+        self.vaults_bumps = vaults_bumps;
+        self.locked_lp_vault_bump = [locked_lp_vault_bump];
+
+        Ok(())
+    }
+
+    /// Launches the AMM with the launch liquidity.
+    ///
+    /// # Parameters
+    /// - `launch_payload`: Contains the initial liquidity and LP token supply.
+    #[inline(never)]
+    pub(crate) fn launch(&mut self, launch_payload: LaunchPayload) {
+        self.is_launched = true;
+        self.liquidity = launch_payload.liquidity;
+        self.initial_locked_liquidity = launch_payload.initial_locked_liquidity;
+        self.lp_tokens_supply = launch_payload.lp_tokens_supply;
+    }
+
+    /// Updates the AMM state after liquidity is provided.
+    ///
+    /// # Parameters
+    /// - `provide_payload`: Contains the new liquidity and LP tokens supply.
+    #[inline(never)]
+    pub(crate) fn provide(&mut self, provide_payload: ProvidePayload) {
+        self.liquidity = provide_payload.liquidity;
+        self.lp_tokens_supply = provide_payload.lp_tokens_supply;
+    }
+
+    /// Updates the AMM state after liquidity is withdrawn.
+    ///
+    /// # Parameters
+    /// - `withdraw_payload`: Contains the new liquidity and LP tokens supply.
+    #[inline(never)]
+    pub(crate) fn withdraw(&mut self, withdraw_payload: WithdrawPayload) {
+        self.liquidity = withdraw_payload.liquidity;
+        self.lp_tokens_supply = withdraw_payload.lp_tokens_supply;
+    }
+
+    /// Updates the AMM state after a token swap operation.
+    ///
+    /// # Parameters
+    /// - `swap_payload`: Contains the updated liquidity and protocol fees.
+    #[inline(never)]
+    pub(crate) fn swap(&mut self, swap_payload: SwapPayload) {
+        self.liquidity = swap_payload.liquidity;
+        self.protocol_fees_to_redeem = swap_payload.protocol_fees_to_redeem;
+    }
+
+    /// Takes the protocol fees of the token at `index` for their collection.
+    ///
+    /// # Parameters
+    /// - `index`: The index of the token.
+    ///
+    /// # Returns
+    /// - `Ok(u64)` with the collected protocol fees, which are no longer available for redemption.
+    /// - `Err(ErrorCode::WpAmmInvalidSwapMints)` if the index doesn't belong to a token of the AMM.
+    /// - `Err(ErrorCode::ProvidersFeesIsZero)` if there are no fees to collect.
+    #[inline(never)]
+    pub(crate) fn collect_protocol_fees(&mut self, index: usize) -> Result<u64> {
+        let protocol_fees = self.protocol_fees_to_redeem.get_mut(index).ok_or(ErrorCode::WpAmmInvalidSwapMints)?;
+        require!(*protocol_fees > 0, ErrorCode::ProvidersFeesIsZero);
+        Ok(std::mem::take(protocol_fees))
+    }
+}
+
+#[cfg(test)]
+mod wp_amm_tests {
+    use anchor_lang::Discriminator;
+    use crate::constants::ANCHOR_DISCRIMINATOR;
+    use super::*;
+
+    /// Creates a `WpAmm` with the given liquidity, weights and LP tokens supply.
+    fn test_wp_amm(is_launched: bool, liquidity: [u64; WP_AMM_TOKENS_COUNT], weights_basis_points: [u16; WP_AMM_TOKENS_COUNT], lp_tokens_supply: u64) -> WpAmm {
+        WpAmm {
+            is_initialized: true,
+            is_launched,
+            bump: [0],
+            vaults_bumps: [0; WP_AMM_TOKENS_COUNT],
+            locked_lp_vault_bump: [0],
+            weights_basis_points,
+            initial_locked_liquidity: 0,
+            liquidity,
+            lp_tokens_supply,
+            mints: [Pubkey::default(); WP_AMM_TOKENS_COUNT],
+            lp_mint: Pubkey::default(),
+            vaults: [Pubkey::default(); WP_AMM_TOKENS_COUNT],
+            locked_lp_vault: Pubkey::default(),
+            amms_config: Pubkey::default(),
+            creator: Pubkey::default(),
+            protocol_fees_to_redeem: [0; WP_AMM_TOKENS_COUNT],
+        }
+    }
+
+    /// Tests `WpAmm` account data layout.
+    #[test]
+    fn test_wp_amm_data_layout() {
+        let weights_basis_points = [5000u16, 3000, 2000];
+        let liquidity = [1_000_000u64, 2_000_000, 3_000_000];
+        let lp_tokens_supply = 4_000_000u64;
+        let initial_locked_liquidity = 100_000u64;
+        let mints = [Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique()];
+        let lp_mint = Pubkey::new_unique();
+        let vaults = [Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique()];
+        let locked_lp_vault = Pubkey::new_unique();
+        let amms_config = Pubkey::new_unique();
+        let creator = Pubkey::new_unique();
+
+        let protocol_fees_to_redeem = [10u64, 20, 30];
+
+        let mut data = [0u8; ANCHOR_DISCRIMINATOR + 397];
+        let mut offset = 0;
+
+        data[offset..offset + ANCHOR_DISCRIMINATOR].copy_from_slice(&WpAmm::discriminator()); offset += ANCHOR_DISCRIMINATOR;
+        data[offset] = 1; offset += 1;
+        data[offset] = 1; offset += 1;
+        data[offset] = 255; offset += 1;
+        data[offset..offset + 3].copy_from_slice(&[254, 253, 252]); offset += 3;
+        data[offset] = 251; offset += 1;
+        for weight in weights_basis_points {
+            data[offset..offset + 2].copy_from_slice(&weight.to_le_bytes()); offset += 2;
+        }
+        data[offset..offset + 8].copy_from_slice(&initial_locked_liquidity.to_le_bytes()); offset += 8;
+        for token_liquidity in liquidity {
+            data[offset..offset + 8].copy_from_slice(&token_liquidity.to_le_bytes()); offset += 8;
+        }
+        data[offset..offset + 8].copy_from_slice(&lp_tokens_supply.to_le_bytes()); offset += 8;
+        assert_eq!(offset, WpAmm::MINTS_OFFSET);
+        for mint in mints {
+            data[offset..offset + 32].copy_from_slice(mint.as_ref()); offset += 32;
+        }
+        assert_eq!(offset, WpAmm::LP_MINT_OFFSET);
+        data[offset..offset + 32].copy_from_slice(lp_mint.as_ref()); offset += 32;
+        for vault in vaults {
+            data[offset..offset + 32].copy_from_slice(vault.as_ref()); offset += 32;
+        }
+        data[offset..offset + 32].copy_from_slice(locked_lp_vault.as_ref()); offset += 32;
+        assert_eq!(offset, WpAmm::AMMS_CONFIG_OFFSET);
+        data[offset..offset + 32].copy_from_slice(amms_config.as_ref()); offset += 32;
+        assert_eq!(offset, WpAmm::CREATOR_OFFSET);
+        data[offset..offset + 32].copy_from_slice(creator.as_ref()); offset += 32;
+        for protocol_fees in protocol_fees_to_redeem {
+            data[offset..offset + 8].copy_from_slice(&protocol_fees.to_le_bytes()); offset += 8;
+        }
+
+        assert_eq!(ANCHOR_DISCRIMINATOR + WpAmm::INIT_SPACE, offset);
+
+        let deserialized_wp_amm = WpAmm::try_deserialize(&mut data.as_ref()).unwrap();
+
+        assert!(deserialized_wp_amm.is_initialized());
+        assert!(deserialized_wp_amm.is_launched());
+        assert_eq!(deserialized_wp_amm.bump(), 255);
+        assert_eq!(deserialized_wp_amm.vault_bump(0), 254);
+        assert_eq!(deserialized_wp_amm.vault_bump(2), 252);
+        assert_eq!(deserialized_wp_amm.locked_lp_vault_bump(), 251);
+        assert_eq!(deserialized_wp_amm.weights_basis_points(), weights_basis_points);
+        assert_eq!(deserialized_wp_amm.initial_locked_liquidity, initial_locked_liquidity);
+        assert_eq!(deserialized_wp_amm.liquidity(), liquidity);
+        assert_eq!(deserialized_wp_amm.lp_tokens_supply(), lp_tokens_supply);
+        assert_eq!(deserialized_wp_amm.mints(), &mints);
+        assert_eq!(deserialized_wp_amm.mint_index(&mints[1]), Some(1));
+        assert_eq!(deserialized_wp_amm.mint_index(&lp_mint), None);
+        assert_eq!(deserialized_wp_amm.lp_mint, lp_mint);
+        assert_eq!(deserialized_wp_amm.vaults(), &vaults);
+        assert_eq!(deserialized_wp_amm.vault_for_mint(&mints[2]), Some(&vaults[2]));
+        assert_eq!(deserialized_wp_amm.locked_lp_vault(), &locked_lp_vault);
+        assert_eq!(deserialized_wp_amm.amms_config(), &amms_config);
+        assert_eq!(deserialized_wp_amm.creator(), &creator);
+        assert_eq!(deserialized_wp_amm.protocol_fees_to_redeem(), protocol_fees_to_redeem);
+
+        let mut serialized_wp_amm = Vec::new();
+        deserialized_wp_amm.try_serialize(&mut serialized_wp_amm).unwrap();
+        assert_eq!(serialized_wp_amm.as_slice(), data.as_ref());
+    }
+
+    /// Tests the `initialize` method of `WpAmm`.
+    #[test]
+    fn test_initialize() {
+        let mut amm = test_wp_amm(false, [0; WP_AMM_TOKENS_COUNT], [0; WP_AMM_TOKENS_COUNT], 0);
+        amm.is_initialized = false;
+        let mints = [Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique()];
+        let vaults = [Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique()];
+
+        assert!(amm.initialize(mints, [5000, 5000, 0], Pubkey::new_unique(), vaults, Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique(), 1, [2, 3, 4], 5).is_err());
+
+        amm.initialize(mints, [5000, 3000, 2000], Pubkey::new_unique(), vaults, Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique(), 1, [2, 3, 4], 5).unwrap();
+        assert!(amm.is_initialized());
+        assert!(!amm.is_launched());
+        assert_eq!(amm.mints(), &mints);
+        assert_eq!(amm.vaults(), &vaults);
+        assert_eq!(amm.weights_basis_points(), [5000, 3000, 2000]);
+        assert_eq!(amm.bump(), 1);
+        assert_eq!(amm.vault_bump(1), 3);
+        assert_eq!(amm.locked_lp_vault_bump(), 5);
+
+        assert!(amm.initialize(mints, [5000, 3000, 2000], Pubkey::new_unique(), vaults, Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique(), 1, [2, 3, 4], 5).is_err());
+    }
+
+    /// Tests the `get_launch_payload` and `launch` methods of `WpAmm`.
+    #[test]
+    fn test_launch() {
+        let mut amm = test_wp_amm(false, [0; WP_AMM_TOKENS_COUNT], [5000, 3000, 2000], 0);
+
+        assert!(amm.get_launch_payload([1_000_000, 0, 1_000_000]).is_err());
+        assert!(amm.get_launch_payload([1_000, 1_000, 1_000]).is_err());
+
+        let payload = amm.get_launch_payload([1_000_000, 1_000_000, 1_000_000]).unwrap();
+        assert_eq!(payload.initial_locked_liquidity(), WpAmm::INITIAL_LOCKED_LP_TOKENS);
+        assert_eq!(payload.launch_liquidity(), payload.lp_tokens_supply - WpAmm::INITIAL_LOCKED_LP_TOKENS);
+
+        let lp_tokens_supply = payload.lp_tokens_supply;
+        amm.launch(payload);
+        assert!(amm.is_launched());
+        assert_eq!(amm.liquidity(), [1_000_000, 1_000_000, 1_000_000]);
+        assert_eq!(amm.lp_tokens_supply(), lp_tokens_supply);
+        assert!(amm.get_launch_payload([1_000_000, 1_000_000, 1_000_000]).is_err());
+    }
+
+    /// Tests the `get_provide_payload` and `provide` methods of `WpAmm`.
+    #[test]
+    fn test_provide() {
+        let mut amm = test_wp_amm(true, [4_000_000, 2_000_000, 1_000_000], [5000, 3000, 2000], 2_000_000);
+
+        let payload = amm.get_provide_payload([400_000, 200_000, 100_000], 200_000).unwrap();
+        assert_eq!(payload.lp_tokens_to_mint(), 200_000);
+
+        amm.provide(payload);
+        assert_eq!(amm.liquidity(), [4_400_000, 2_200_000, 1_100_000]);
+        assert_eq!(amm.lp_tokens_supply(), 2_200_000);
+
+        assert!(amm.get_provide_payload([400_000, 200_000, 0], 0).is_err());
+        // The excess of the first token is donated, so the provide mints less than expected
+        assert_eq!(
+            amm.get_provide_payload([880_000, 220_000, 55_000], 200_000).err(),
+            Some(ErrorCode::ProvideLpTokensBelowMinimum.into())
+        );
+    }
+
+    /// Tests the `get_withdraw_payload` and `withdraw` methods of `WpAmm`.
+    #[test]
+    fn test_withdraw() {
+        let mut amm = test_wp_amm(true, [4_000_000, 2_000_000, 1_000_000], [5000, 3000, 2000], 2_000_000);
+
+        let payload = amm.get_withdraw_payload(500_000).unwrap();
+        assert_eq!(payload.withdraw_liquidity(), [1_000_000, 500_000, 250_000]);
+
+        amm.withdraw(payload);
+        assert_eq!(amm.liquidity(), [3_000_000, 1_500_000, 750_000]);
+        assert_eq!(amm.lp_tokens_supply(), 1_500_000);
+
+        assert!(amm.get_withdraw_payload(0).is_err());
+        assert!(amm.get_withdraw_payload(1_500_000).is_err());
+    }
+
+    /// Tests the `get_swap_payload` and `swap` methods of `WpAmm`.
+    #[test]
+    fn test_swap() {
+        let mut amm = test_wp_amm(true, [4_000_000, 2_000_000, 1_000_000], [5000, 3000, 2000], 2_000_000);
+        let swap_amount = 101_010;
+        let providers_fee = swap_amount * 100 / 10000;
+        let protocol_fee = swap_amount * 20 / 10000;
+        let expected_result = amm.calculate_swap_result(0, 2, swap_amount - providers_fee - protocol_fee).unwrap();
+
+        assert!(amm.get_swap_payload(0, 0, swap_amount, expected_result, 0, 100, 20).is_err());
+        assert!(amm.get_swap_payload(0, 2, swap_amount, expected_result + 10, 5, 100, 20).is_err());
+
+        let payload = amm.get_swap_payload(0, 2, swap_amount, expected_result, 0, 100, 20).unwrap();
+        assert_eq!(payload.amount_to_withdraw(), expected_result);
+
+        amm.swap(payload);
+        assert_eq!(amm.liquidity(), [4_000_000 + swap_amount - protocol_fee, 2_000_000, 1_000_000 - expected_result]);
+        assert_eq!(amm.protocol_fees_to_redeem(), [protocol_fee, 0, 0]);
+
+        assert_eq!(amm.collect_protocol_fees(0).unwrap(), protocol_fee);
+        assert_eq!(amm.protocol_fees_to_redeem(), [0, 0, 0]);
+        assert_eq!(amm.collect_protocol_fees(0).err(), Some(ErrorCode::ProvidersFeesIsZero.into()));
+        assert!(amm.collect_protocol_fees(WP_AMM_TOKENS_COUNT).is_err());
+    }
+}
+
+/// Represents the data required to launch the AMM.
+///
+/// # Fields
+/// - `initial_locked_liquidity`: The amount of liquidity that will be locked in the pool upon launch.
+/// - `liquidity`: The initial liquidity of each token in the pool.
+/// - `lp_tokens_supply`: The total supply of LP tokens minted upon launch.
+#[derive(Debug)]
+pub struct LaunchPayload {
+    initial_locked_liquidity: u64,
+    liquidity: [u64; WP_AMM_TOKENS_COUNT],
+    lp_tokens_supply: u64,
+}
+
+impl LaunchPayload {
+    /// Creates a new `LaunchPayload` instance with the specified parameters.
+    pub fn new(initial_locked_liquidity: u64, liquidity: [u64; WP_AMM_TOKENS_COUNT], lp_tokens_supply: u64) -> Self {
+        Self {
+            initial_locked_liquidity,
+            liquidity,
+            lp_tokens_supply,
+        }
+    }
+
+    /// Returns the amount of initially locked liquidity.
+    pub fn initial_locked_liquidity(&self) -> u64 {
+        self.initial_locked_liquidity
+    }
+
+    /// Returns the amount of liquidity minted to the creator upon launch.
+    pub fn launch_liquidity(&self) -> u64 {
+        self.lp_tokens_supply - self.initial_locked_liquidity
+    }
+}
+
+/// Represents the data required to provide liquidity to the AMM.
+///
+/// # Fields
+/// - `liquidity`: The updated liquidity of each token in the pool.
+/// - `lp_tokens_supply`: The updated total supply of LP tokens.
+/// - `lp_tokens_to_mint`: The number of LP tokens to mint for the provider.
+#[derive(Debug)]
+pub struct ProvidePayload {
+    liquidity: [u64; WP_AMM_TOKENS_COUNT],
+    lp_tokens_supply: u64,
+    lp_tokens_to_mint: u64,
+}
+
+impl ProvidePayload {
+    /// Creates a new `ProvidePayload` instance with the specified parameters.
+    pub fn new(liquidity: [u64; WP_AMM_TOKENS_COUNT], lp_tokens_supply: u64, lp_tokens_to_mint: u64) -> Self {
+        Self {
+            liquidity,
+            lp_tokens_supply,
+            lp_tokens_to_mint,
+        }
+    }
+
+    /// Returns the number of LP tokens to mint.
+    pub fn lp_tokens_to_mint(&self) -> u64 {
+        self.lp_tokens_to_mint
+    }
+}
+
+/// Represents the data required to withdraw liquidity from the AMM.
+///
+/// # Fields
+/// - `liquidity`: The updated liquidity of each token in the pool.
+/// - `lp_tokens_supply`: The updated total supply of LP tokens.
+/// - `withdraw_liquidity`: The amount of each token withdrawn.
+#[derive(Debug)]
+pub struct WithdrawPayload {
+    liquidity: [u64; WP_AMM_TOKENS_COUNT],
+    lp_tokens_supply: u64,
+    withdraw_liquidity: [u64; WP_AMM_TOKENS_COUNT],
+}
+
+impl WithdrawPayload {
+    /// Creates a new `WithdrawPayload` instance with the specified parameters.
+    pub fn new(liquidity: [u64; WP_AMM_TOKENS_COUNT], lp_tokens_supply: u64, withdraw_liquidity: [u64; WP_AMM_TOKENS_COUNT]) -> Self {
+        Self {
+            liquidity,
+            lp_tokens_supply,
+            withdraw_liquidity,
+        }
+    }
+
+    /// Returns the amount of each token withdrawn.
+    pub fn withdraw_liquidity(&self) -> [u64; WP_AMM_TOKENS_COUNT] {
+        self.withdraw_liquidity
+    }
+}
+
+/// Represents the data required for a token swap operation in the AMM.
+///
+/// # Fields
+/// - `liquidity`: The updated liquidity of each token in the pool.
+/// - `protocol_fees_to_redeem`: The updated protocol fees of each token available for redemption.
+/// - `amount_to_withdraw`: The amount of the output token sent to the user.
+#[derive(Debug)]
+pub struct SwapPayload {
+    liquidity: [u64; WP_AMM_TOKENS_COUNT],
+    protocol_fees_to_redeem: [u64; WP_AMM_TOKENS_COUNT],
+    amount_to_withdraw: u64,
+}
+
+impl SwapPayload {
+    /// Creates a new `SwapPayload` instance with the specified parameters.
+    fn new(liquidity: [u64; WP_AMM_TOKENS_COUNT], protocol_fees_to_redeem: [u64; WP_AMM_TOKENS_COUNT], amount_to_withdraw: u64) -> Self {
+        Self {
+            liquidity,
+            protocol_fees_to_redeem,
+            amount_to_withdraw,
+        }
+    }
+
+    /// Returns the amount of the output token sent to the user.
+    pub fn amount_to_withdraw(&self) -> u64 {
+        self.amount_to_withdraw
+    }
+}
@@ -0,0 +1,486 @@
+use anchor_lang::prelude::*;
+use crate::utils::math::{strict_mul, Q64_128};
+use crate::error::ErrorCode;
+use crate::state::wp_amm::{WpAmmCore, WP_AMM_TOKENS_COUNT};
+
+/// A trait for implementing core calculations and constants for a Weighted Product Automated Market Maker.
+///
+/// The pool keeps the weighted geometric mean of its liquidity `Π Bᵢ^wᵢ` (the invariant) constant during swaps,
+/// which generalizes the constant product formula to tokens with arbitrary weights.
+/// Fractional powers are evaluated in `Q64_128` through repeated square roots.
+pub(crate) trait WpAmmCalculate: WpAmmCore {
+    /// The number of decimals for the LP token.
+    const LP_MINT_INITIAL_DECIMALS: u8 = 5;
+
+    /// The maximum allowable fee rate, expressed in basis points.
+    const FEE_MAX_BASIS_POINTS: u128 = 10000;
+
+    /// The sum of all token weights, expressed in basis points.
+    const WEIGHTS_TOTAL_BASIS_POINTS: u16 = 10000;
+
+    /// The minimal weight of a single token, expressed in basis points.
+    ///
+    /// - Limits the swap exponent `wᵢₙ / wₒᵤₜ` to at most `19`.
+    const MIN_WEIGHT_BASIS_POINTS: u16 = 500;
+
+    /// The initial amount of locked LP tokens in the pool.
+    const INITIAL_LOCKED_LP_TOKENS: u64 = 10_u64.pow(Self::LP_MINT_INITIAL_DECIMALS as u32);
+
+    /// The tolerance for decrease of the invariant during swaps.
+    ///
+    /// - Defined as a `Q64_128` value representing a tolerance of `0.0001%`.
+    const SWAP_INVARIANT_TOLERANCE: Q64_128 = Q64_128::from_bits(0, 340282366920938463463374607431768);
+
+    /// The number of binary digits of a fractional exponent taken into account by `calculate_pow`.
+    const POW_FRACTION_PRECISION_BITS: u32 = 32;
+
+    /// Validates the token weights of the pool.
+    ///
+    /// # Parameters
+    /// - `weights_basis_points`: The token weights, measured in basis points.
+    ///
+    /// # Returns
+    /// - `Ok(())` if every weight is at least `MIN_WEIGHT_BASIS_POINTS` and the weights sum up to `WEIGHTS_TOTAL_BASIS_POINTS`.
+    /// - `Err(ErrorCode::WpAmmInvalidWeights)` otherwise.
+    fn validate_weights(weights_basis_points: [u16; WP_AMM_TOKENS_COUNT]) -> Result<()> {
+        require!(
+            weights_basis_points.iter().all(|weight| *weight >= Self::MIN_WEIGHT_BASIS_POINTS),
+            ErrorCode::WpAmmInvalidWeights
+        );
+        require!(
+            weights_basis_points.iter().map(|weight| *weight as u32).sum::<u32>() == Self::WEIGHTS_TOTAL_BASIS_POINTS as u32,
+            ErrorCode::WpAmmInvalidWeights
+        );
+        Ok(())
+    }
+
+    /// Raises `base` to the rational power `exponent_numerator / exponent_denominator`.
+    ///
+    /// The integer part of the exponent is applied by multiplication, the fractional part
+    /// by multiplying the consecutive square roots of `base` that match its binary digits.
+    ///
+    /// # Parameters
+    /// - `base`: The value to raise.
+    /// - `exponent_numerator`: The numerator of the exponent.
+    /// - `exponent_denominator`: The denominator of the exponent.
+    ///
+    /// # Returns
+    /// - `Some(Q64_128)` with the result rounded down.
+    /// - `None` if the denominator is zero or the calculation overflows.
+    fn calculate_pow(base: Q64_128, exponent_numerator: u16, exponent_denominator: u16) -> Option<Q64_128> {
+        if exponent_denominator == 0 {
+            return None;
+        }
+        let exponent_denominator = exponent_denominator as u32;
+        let mut result = Q64_128::ONE;
+        for _ in 0..(exponent_numerator as u32 / exponent_denominator) {
+            result = result.checked_mul(base)?;
+        }
+
+        let mut remainder = exponent_numerator as u32 % exponent_denominator;
+        let mut root = base;
+        for _ in 0..Self::POW_FRACTION_PRECISION_BITS {
+            if remainder == 0 {
+                break;
+            }
+            root = root.sqrt();
+            remainder <<= 1;
+            if remainder >= exponent_denominator {
+                remainder -= exponent_denominator;
+                result = result.checked_mul(root)?;
+            }
+        }
+        Some(result)
+    }
+
+    /// Calculates the invariant of the pool as the weighted geometric mean of its liquidity.
+    ///
+    /// # Parameters
+    /// - `liquidity`: The liquidity of each token.
+    /// - `weights_basis_points`: The token weights, measured in basis points.
+    ///
+    /// # Returns
+    /// - `Some(Q64_128)` with the invariant.
+    /// - `None` if any liquidity is zero or the calculation fails.
+    fn calculate_invariant(liquidity: [u64; WP_AMM_TOKENS_COUNT], weights_basis_points: [u16; WP_AMM_TOKENS_COUNT]) -> Option<Q64_128> {
+        let mut invariant = Q64_128::ONE;
+        for (token_liquidity, weight) in liquidity.into_iter().zip(weights_basis_points) {
+            if token_liquidity == 0 {
+                return None;
+            }
+            let weighted_liquidity = Self::calculate_pow(Q64_128::from_u64(token_liquidity), weight, Self::WEIGHTS_TOTAL_BASIS_POINTS)?;
+            invariant = invariant.checked_mul(weighted_liquidity)?;
+        }
+        if invariant.is_zero() {
+            return None;
+        }
+        Some(invariant)
+    }
+
+    /// Calculates the initial LP token supply and locked liquidity during pool launch.
+    ///
+    /// # Parameters
+    /// - `invariant`: The invariant of the launch liquidity.
+    ///
+    /// # Returns
+    /// - `Ok((u64, u64))` with the initial LP token supply and locked liquidity.
+    /// - `Err(ErrorCode)` if the supply is too small.
+    fn calculate_launch_lp_tokens(invariant: Q64_128) -> Result<(u64, u64)> {
+        let lp_tokens_supply = invariant.as_u64();
+        require!(lp_tokens_supply > 0, ErrorCode::LpTokensCalculationFailed);
+        let initial_locked_liquidity = Self::INITIAL_LOCKED_LP_TOKENS;
+        let difference = lp_tokens_supply
+            .checked_sub(initial_locked_liquidity)
+            .ok_or(ErrorCode::LaunchLiquidityTooSmall)?;
+        require!(difference >= strict_mul!(initial_locked_liquidity, 3), ErrorCode::LaunchLiquidityTooSmall);
+        Ok((lp_tokens_supply, initial_locked_liquidity))
+    }
+
+    /// Calculates the amount of LP tokens to mint for the provided liquidity.
+    ///
+    /// The amount is determined by the smallest share of the pool among the provided tokens,
+    /// so any liquidity exceeding the pool proportions is donated to the pool.
+    ///
+    /// # Parameters
+    /// - `provided_liquidity`: The provided amount of each token.
+    ///
+    /// # Returns
+    /// - `Some(u64)` with the amount of LP tokens to mint.
+    /// - `None` if the calculation fails or results in zero tokens.
+    fn calculate_lp_mint_for_provided_liquidity(&self, provided_liquidity: [u64; WP_AMM_TOKENS_COUNT]) -> Option<u64> {
+        let mut tokens_to_mint = u64::MAX;
+        for (provided, token_liquidity) in provided_liquidity.into_iter().zip(self.liquidity()) {
+            if token_liquidity == 0 {
+                return None;
+            }
+            let share_tokens = (provided as u128 * self.lp_tokens_supply() as u128 / token_liquidity as u128).min(u64::MAX as u128) as u64;
+            tokens_to_mint = tokens_to_mint.min(share_tokens);
+        }
+        if tokens_to_mint == 0 {
+            return None;
+        }
+        Some(tokens_to_mint)
+    }
+
+    /// Calculates the amount of each token to withdraw for a given share of LP tokens.
+    ///
+    /// # Parameters
+    /// - `lp_tokens`: The number of LP tokens being redeemed.
+    ///
+    /// # Returns
+    /// - `Some([u64; 3])` with the liquidity amounts rounded down.
+    /// - `None` if the calculation fails or any amount is zero.
+    fn calculate_liquidity_from_share(&self, lp_tokens: u64) -> Option<[u64; WP_AMM_TOKENS_COUNT]> {
+        if self.lp_tokens_supply() == 0 {
+            return None;
+        }
+        let mut withdraw_liquidity = [0u64; WP_AMM_TOKENS_COUNT];
+        for (withdraw, token_liquidity) in withdraw_liquidity.iter_mut().zip(self.liquidity()) {
+            *withdraw = (token_liquidity as u128 * lp_tokens as u128 / self.lp_tokens_supply() as u128) as u64;
+            if *withdraw == 0 {
+                return None;
+            }
+        }
+        Some(withdraw_liquidity)
+    }
+
+    /// Calculates the amount of the output token received for the swapped input token.
+    ///
+    /// Uses `out = Bₒᵤₜ * (1 - (Bᵢₙ / (Bᵢₙ + amount))^(wᵢₙ / wₒᵤₜ))`.
+    /// One unit of the output token is kept in the pool to cover fixed-point rounding.
+    ///
+    /// # Parameters
+    /// - `in_index`: The index of the input token.
+    /// - `out_index`: The index of the output token.
+    /// - `swap_amount`: The amount of the input token after fees.
+    ///
+    /// # Returns
+    /// - `Some(u64)` with the output amount.
+    /// - `None` if the calculation fails or the result is zero.
+    fn calculate_swap_result(&self, in_index: usize, out_index: usize, swap_amount: u64) -> Option<u64> {
+        let liquidity = self.liquidity();
+        let weights_basis_points = self.weights_basis_points();
+        let in_liquidity = *liquidity.get(in_index)?;
+        let out_liquidity = *liquidity.get(out_index)?;
+
+        let new_in_liquidity = in_liquidity.checked_add(swap_amount)?;
+        let in_liquidity_ratio = Q64_128::from_u64(in_liquidity).checked_div(Q64_128::from_u64(new_in_liquidity))?;
+        let out_liquidity_ratio = Self::calculate_pow(in_liquidity_ratio, weights_basis_points[in_index], weights_basis_points[out_index])?;
+        let out_share = Q64_128::ONE.checked_sub(out_liquidity_ratio)?;
+
+        let swap_result = Q64_128::from_u64(out_liquidity).checked_mul(out_share)?.as_u64().checked_sub(1)?;
+        if swap_result == 0 {
+            return None;
+        }
+        Some(swap_result)
+    }
+
+    /// Validates the invariant of the pool after a swap.
+    ///
+    /// Only the liquidity of the swapped tokens changes, so the invariant doesn't decrease as long as
+    /// `(newₒᵤₜ / Bₒᵤₜ)^(wₒᵤₜ / wᵢₙ) * (newᵢₙ / Bᵢₙ) >= 1`. The output ratio is raised in the opposite direction
+    /// of `calculate_swap_result`, which keeps the check independent with a single `calculate_pow`
+    /// instead of the fractional powers of both full invariants.
+    ///
+    /// # Parameters
+    /// - `in_index`: The index of the input token.
+    /// - `out_index`: The index of the output token.
+    /// - `new_liquidity`: The liquidity of each token after the swap.
+    ///
+    /// # Returns
+    /// - `Ok(())` if the invariant didn't decrease beyond the tolerance.
+    /// - `Err(ErrorCode)` otherwise.
+    fn validate_swap_invariant(&self, in_index: usize, out_index: usize, new_liquidity: [u64; WP_AMM_TOKENS_COUNT]) -> Result<()> {
+        let liquidity = self.liquidity();
+        let weights_basis_points = self.weights_basis_points();
+        let out_liquidity_ratio = Q64_128::from_u64(new_liquidity[out_index])
+            .checked_div(Q64_128::from_u64(liquidity[out_index]))
+            .ok_or(ErrorCode::WpAmmInvariantCalculationFailed)?;
+        let invariant_ratio = Self::calculate_pow(out_liquidity_ratio, weights_basis_points[out_index], weights_basis_points[in_index])
+            .and_then(|weighted_out_ratio| weighted_out_ratio.checked_mul(Q64_128::from_u64(new_liquidity[in_index])))
+            .and_then(|invariant_ratio| invariant_ratio.checked_div(Q64_128::from_u64(liquidity[in_index])))
+            .ok_or(ErrorCode::WpAmmInvariantCalculationFailed)?;
+        let min_invariant_ratio = Q64_128::ONE.checked_sub(Self::SWAP_INVARIANT_TOLERANCE).ok_or(ErrorCode::WpAmmInvariantCalculationFailed)?;
+        require!(invariant_ratio >= min_invariant_ratio, ErrorCode::WpAmmInvariantToleranceExceeded);
+        Ok(())
+    }
+
+    /// Validates the result of a swap against the estimated result and allowed slippage.
+    ///
+    /// # Parameters
+    /// - `swap_result`: The actual result of the swap.
+    /// - `estimated_swap_result`: The estimated result of the swap.
+    /// - `allowed_slippage`: The allowed slippage tolerance.
+    ///
+    /// # Returns
+    /// - `Ok(())` if the swap result is within the allowed slippage.
+    /// - `Err(ErrorCode)` if the result exceeds the slippage tolerance.
+    #[inline]
+    fn check_swap_result(swap_result: u64, estimated_swap_result: u64, allowed_slippage: u64) -> Result<()> {
+        require!(swap_result > 0, ErrorCode::SwapResultIsZero);
+        require!(swap_result.abs_diff(estimated_swap_result) <= allowed_slippage, ErrorCode::SwapSlippageExceeded);
+        Ok(())
+    }
+
+    /// Calculates the fee for a given swap amount based on the provided fee rate.
+    ///
+    /// # Parameters
+    /// - `swap_amount`: The amount of tokens being swapped.
+    /// - `fee_basis_points`: The fee rate expressed in basis points.
+    ///
+    /// # Returns
+    /// - A `u64` representing the calculated fee amount, zero without any math for a zero fee rate.
+    #[inline]
+    fn calculate_fee_amount(swap_amount: u64, fee_basis_points: u16) -> u64 {
+        if fee_basis_points == 0 {
+            return 0;
+        }
+        ((swap_amount as u128) * (fee_basis_points as u128) / Self::FEE_MAX_BASIS_POINTS) as u64
+    }
+}
+
+impl<T: WpAmmCore> WpAmmCalculate for T{}
+
+#[cfg(test)]
+mod tests {
+    use crate::state::wp_amm::{WpAmmCore, WpAmmCalculate, WP_AMM_TOKENS_COUNT};
+    use crate::utils::math::Q64_128;
+
+    /// A helper struct for testing the `WpAmmCalculate` trait.
+    struct TestWpAmm {
+        weights_basis_points: [u16; WP_AMM_TOKENS_COUNT],
+        liquidity: [u64; WP_AMM_TOKENS_COUNT],
+        lp_tokens_supply: u64,
+    }
+
+    impl TestWpAmm {
+        /// Creates a new instance of `TestWpAmm` with the launch LP tokens supply.
+        ///
+        /// Returns `None` if any calculation fails.
+        fn try_new(liquidity: [u64; WP_AMM_TOKENS_COUNT], weights_basis_points: [u16; WP_AMM_TOKENS_COUNT]) -> Option<Self> {
+            let invariant = TestWpAmm::calculate_invariant(liquidity, weights_basis_points)?;
+            let (lp_tokens_supply, _) = TestWpAmm::calculate_launch_lp_tokens(invariant).ok()?;
+            Some(Self {
+                weights_basis_points,
+                liquidity,
+                lp_tokens_supply,
+            })
+        }
+    }
+
+    impl WpAmmCore for TestWpAmm {
+        fn weights_basis_points(&self) -> [u16; WP_AMM_TOKENS_COUNT] {
+            self.weights_basis_points
+        }
+
+        fn liquidity(&self) -> [u64; WP_AMM_TOKENS_COUNT] {
+            self.liquidity
+        }
+
+        fn lp_tokens_supply(&self) -> u64 {
+            self.lp_tokens_supply
+        }
+    }
+
+    /// Unit tests for the `TestWpAmm` implementation.
+    mod unit_tests {
+        use super::*;
+
+        /// Tests that `SWAP_INVARIANT_TOLERANCE` is the truncated Q64.128 representation of `0.0001% = 1/10^6`.
+        #[test]
+        fn test_swap_invariant_tolerance_specification() {
+            assert_eq!(TestWpAmm::SWAP_INVARIANT_TOLERANCE.get_integer_bits(), 0);
+            assert_eq!(TestWpAmm::SWAP_INVARIANT_TOLERANCE.get_fractional_bits(), u128::MAX / 1_000_000);
+        }
+
+        /// Tests `validate_weights` for valid and invalid weights.
+        #[test]
+        fn test_validate_weights() {
+            assert!(TestWpAmm::validate_weights([5000, 3000, 2000]).is_ok());
+            assert!(TestWpAmm::validate_weights([3333, 3333, 3334]).is_ok());
+            assert!(TestWpAmm::validate_weights([5000, 3000, 1000]).is_err());
+            assert!(TestWpAmm::validate_weights([9000, 600, 400]).is_err());
+        }
+
+        /// Tests `calculate_pow` for integer and fractional exponents.
+        #[test]
+        fn test_calculate_pow() {
+            assert_eq!(TestWpAmm::calculate_pow(Q64_128::from_u64(3), 2, 1).unwrap(), Q64_128::from_u64(9));
+            assert_eq!(TestWpAmm::calculate_pow(Q64_128::from_u64(16), 1, 4).unwrap(), Q64_128::from_u64(2));
+            assert!(TestWpAmm::calculate_pow(Q64_128::from_u64(16), 1, 0).is_none());
+
+            let result: f64 = TestWpAmm::calculate_pow(Q64_128::from_f64(0.8).unwrap(), 5000, 3000).unwrap().into();
+            let expected = 0.8f64.powf(5000.0 / 3000.0);
+            assert!(
+                (result - expected).abs() < 1e-9,
+                "Power mismatch. Expected: {}, Got: {}",
+                expected,
+                result
+            );
+        }
+
+        /// Tests `calculate_invariant` for balanced and unbalanced liquidity.
+        #[test]
+        fn test_calculate_invariant() {
+            let invariant: f64 = TestWpAmm::calculate_invariant([1_000_000, 1_000_000, 1_000_000], [5000, 3000, 2000]).unwrap().into();
+            assert!((invariant - 1_000_000.0).abs() < 1e-2, "Invariant mismatch. Expected: 1000000, Got: {}", invariant);
+
+            let invariant: f64 = TestWpAmm::calculate_invariant([4_000_000, 1_000_000, 250_000], [5000, 2500, 2500]).unwrap().into();
+            let expected = 4_000_000f64.powf(0.5) * 1_000_000f64.powf(0.25) * 250_000f64.powf(0.25);
+            assert!((invariant - expected).abs() < 1e-2, "Invariant mismatch. Expected: {}, Got: {}", expected, invariant);
+
+            assert!(TestWpAmm::calculate_invariant([0, 1_000_000, 1_000_000], [5000, 3000, 2000]).is_none());
+        }
+
+        /// Tests `calculate_lp_mint_for_provided_liquidity` for proportional and excess liquidity.
+        #[test]
+        fn test_calculate_lp_mint_for_provided_liquidity() {
+            let amm = TestWpAmm::try_new([4_000_000, 2_000_000, 1_000_000], [5000, 3000, 2000]).unwrap();
+            let lp_tokens_supply = amm.lp_tokens_supply;
+
+            let minted = amm.calculate_lp_mint_for_provided_liquidity([400_000, 200_000, 100_000]).unwrap();
+            assert_eq!(minted, lp_tokens_supply / 10);
+
+            let minted = amm.calculate_lp_mint_for_provided_liquidity([800_000, 200_000, 100_000]).unwrap();
+            assert_eq!(minted, lp_tokens_supply / 10);
+
+            assert!(amm.calculate_lp_mint_for_provided_liquidity([400_000, 200_000, 0]).is_none());
+        }
+
+        /// Tests `calculate_liquidity_from_share` for expected behavior.
+        #[test]
+        fn test_calculate_liquidity_from_share() {
+            let amm = TestWpAmm::try_new([4_000_000, 2_000_000, 1_000_000], [5000, 3000, 2000]).unwrap();
+            let lp_tokens = amm.lp_tokens_supply / 4;
+
+            let withdraw = amm.calculate_liquidity_from_share(lp_tokens).unwrap();
+            for (withdrawn, token_liquidity) in withdraw.into_iter().zip(amm.liquidity) {
+                let expected = (token_liquidity as u128 * lp_tokens as u128 / amm.lp_tokens_supply as u128) as u64;
+                assert_eq!(withdrawn, expected);
+            }
+            assert!(amm.calculate_liquidity_from_share(0).is_none());
+        }
+
+        /// Tests `calculate_swap_result` against the floating point formula.
+        #[test]
+        fn test_calculate_swap_result() {
+            let amm = TestWpAmm::try_new([4_000_000, 2_000_000, 1_000_000], [5000, 3000, 2000]).unwrap();
+            let swap_amount = 100_000;
+
+            let swap_result = amm.calculate_swap_result(0, 2, swap_amount).unwrap();
+            let expected = 1_000_000f64 * (1.0 - (4_000_000f64 / 4_100_000f64).powf(5000.0 / 2000.0));
+            assert!(
+                (swap_result as f64) <= expected && expected - (swap_result as f64) < 2.0,
+                "Swap result mismatch. Expected: {}, Got: {}",
+                expected,
+                swap_result
+            );
+
+            let mut new_liquidity = amm.liquidity;
+            new_liquidity[0] += swap_amount;
+            new_liquidity[2] -= swap_result;
+            assert!(amm.validate_swap_invariant(0, 2, new_liquidity).is_ok());
+
+            new_liquidity[2] -= 1_000;
+            assert!(amm.validate_swap_invariant(0, 2, new_liquidity).is_err());
+
+            assert!(amm.calculate_swap_result(0, 3, swap_amount).is_none());
+        }
+
+        /// Tests `calculate_fee_amount` for correctness.
+        #[test]
+        fn test_calculate_fee_amount() {
+            assert_eq!(TestWpAmm::calculate_fee_amount(10_000, 100), 100);
+            assert_eq!(TestWpAmm::calculate_fee_amount(99, 100), 0);
+            assert_eq!(TestWpAmm::calculate_fee_amount(u64::MAX, 0), 0);
+        }
+
+        /// Tests `check_swap_result` for valid and invalid slippage.
+        #[test]
+        fn test_check_swap_result() {
+            assert!(TestWpAmm::check_swap_result(1000, 1005, 10).is_ok());
+            assert!(TestWpAmm::check_swap_result(1000, 1020, 10).is_err());
+            assert!(TestWpAmm::check_swap_result(0, 0, 10).is_err());
+        }
+    }
+
+    mod fuzz_tests {
+        use super::*;
+        use proptest::prelude::*;
+
+        proptest! {
+            /// Fuzz-test for `calculate_swap_result`.
+            /// Ensures swaps never decrease the invariant of the pool.
+            #[test]
+            fn test_fuzz_swap_keeps_invariant(
+                liquidity in prop::array::uniform3(1_000_000u64..=u64::MAX >> 8),
+                first_weight in 500u16..=9000,
+                swap_amount in 1u64..=1_000_000_000_000,
+                in_index in 0usize..3,
+                out_offset in 1usize..3,
+            ) {
+                let second_weight = (10000 - first_weight) / 2;
+                let weights_basis_points = [first_weight, second_weight, 10000 - first_weight - second_weight];
+                prop_assume!(TestWpAmm::validate_weights(weights_basis_points).is_ok());
+                let amm = TestWpAmm { weights_basis_points, liquidity, lp_tokens_supply: 1 };
+                let out_index = (in_index + out_offset) % 3;
+
+                if let Some(swap_result) = amm.calculate_swap_result(in_index, out_index, swap_amount) {
+                    let mut new_liquidity = liquidity;
+                    new_liquidity[in_index] += swap_amount;
+                    new_liquidity[out_index] -= swap_result;
+
+                    let invariant = TestWpAmm::calculate_invariant(liquidity, weights_basis_points).unwrap();
+                    let new_invariant = TestWpAmm::calculate_invariant(new_liquidity, weights_basis_points).unwrap();
+                    prop_assert!(
+                        new_invariant >= invariant.checked_sub(invariant.checked_mul(TestWpAmm::SWAP_INVARIANT_TOLERANCE).unwrap()).unwrap(),
+                        "Invariant decreased after swap. Before: {:?}, After: {:?}",
+                        invariant,
+                        new_invariant
+                    );
+                    prop_assert!(amm.validate_swap_invariant(in_index, out_index, new_liquidity).is_ok());
+                }
+            }
+        }
+    }
+}
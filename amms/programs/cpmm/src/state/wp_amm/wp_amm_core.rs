@@ -0,0 +1,23 @@
+/// The number of tradable tokens in a Weighted Product Automated Market Maker pool.
+pub const WP_AMM_TOKENS_COUNT: usize = 3;
+
+/// A trait defining the core parameters of a Weighted Product Automated Market Maker.
+pub trait WpAmmCore {
+    /// Retrieves the weights of the pool's tokens.
+    ///
+    /// # Returns
+    /// - An array of `u16` weights measured in basis points, summing up to 10000.
+    fn weights_basis_points(&self) -> [u16; WP_AMM_TOKENS_COUNT];
+
+    /// Retrieves the liquidity of the pool's tokens.
+    ///
+    /// # Returns
+    /// - An array of `u64` values representing the amount of each token in the pool.
+    fn liquidity(&self) -> [u64; WP_AMM_TOKENS_COUNT];
+
+    /// Retrieves the total supply of LP tokens in the pool.
+    ///
+    /// # Returns
+    /// - A `u64` value representing the total supply of LP tokens.
+    fn lp_tokens_supply(&self) -> u64;
+}
\ No newline at end of file
@@ -1,5 +1,8 @@
 use anchor_lang::prelude::*;
 use crate::error::ErrorCode;
+use crate::state::{AmmsConfig, cp_amm::{CpAmm, EventRecordKind, EventRing}, RouterRegistry, SwapOriginPolicy};
+use anchor_lang::solana_program::instruction::{get_stack_height, TRANSACTION_LEVEL_STACK_HEIGHT};
+use anchor_lang::solana_program::sysvar::instructions::{load_current_index_checked, load_instruction_at_checked};
 use anchor_spl::{
     token::{ID as TOKEN_PROGRAM_ID},
     token_2022::{ID as TOKEN_2022_PROGRAM_ID},
@@ -55,4 +58,143 @@ pub(crate) fn validate_tradable_mint(tradable_mint: &InterfaceAccount<Mint>) ->
         TOKEN_PROGRAM_ID => Ok(()),
         _ => Err(ErrorCode::UnsupportedMint.into()),
     }
+}
+
+/// Validates an account proposed as the `AmmsConfig` fee authority.
+///
+/// The fee authority never signs protocol fee collection, so it may be a wallet or a PDA of
+/// another program (e.g. a DAO treasury). Its associated token accounts are derived on-chain
+/// and created by the collecting signer when missing.
+///
+/// # Validation Steps
+/// 1. Reject the default public key.
+/// 2. Reject executable accounts.
+/// 3. Reject token program accounts, since a mint or token account can't use its own ATAs.
+pub(crate) fn validate_fee_authority(fee_authority: &AccountInfo) -> Result<()>{
+    require_keys_neq!(fee_authority.key(), Pubkey::default(), ErrorCode::InvalidFeeAuthority);
+    require!(!fee_authority.executable, ErrorCode::InvalidFeeAuthority);
+    require!(
+        fee_authority.owner.key() != TOKEN_PROGRAM_ID && fee_authority.owner.key() != TOKEN_2022_PROGRAM_ID,
+        ErrorCode::InvalidFeeAuthority
+    );
+    Ok(())
+}
+
+/// Guards the entrypoints of superseded instruction versions.
+///
+/// New behavior lands in versioned instructions (e.g. `swap_in_cp_amm_v2`) while the previous versions
+/// stay as thin shims over the same handlers, so existing integrations keep working. Fresh deployments
+/// may be built with the `no-legacy-ix` feature to disable the shims. Anchor dispatches every function
+/// of the `#[program]` module regardless of `cfg` attributes, so the entrypoints stay in the IDL
+/// and their bodies are compiled out in favour of `LegacyInstructionDisabled`.
+#[inline]
+pub(crate) fn assert_legacy_ix_enabled() -> Result<()>{
+    #[cfg(feature = "no-legacy-ix")]
+    return err!(ErrorCode::LegacyInstructionDisabled);
+    #[cfg(not(feature = "no-legacy-ix"))]
+    Ok(())
+}
+
+/// Guards the entrypoints of test instructions, e.g. `create_test_pool`.
+///
+/// Like `assert_legacy_ix_enabled`, the entrypoints stay in every build, and their bodies are compiled
+/// out in favour of `DevnetOnlyInstruction` unless the program is built with the `devnet` feature.
+#[inline]
+pub(crate) fn assert_devnet_build() -> Result<()>{
+    #[cfg(not(feature = "devnet"))]
+    return err!(ErrorCode::DevnetOnlyInstruction);
+    #[cfg(feature = "devnet")]
+    Ok(())
+}
+
+/// Guards the entrypoints of the weighted pools instructions, e.g. `swap_in_wp_amm`.
+///
+/// Weighted pools are a research prototype and stay out of the deployed program, so like
+/// `assert_legacy_ix_enabled` their entrypoints are compiled out in favour of `WpAmmBuildOnlyInstruction`
+/// unless the program is built with the `wp-amm` feature.
+#[inline]
+pub(crate) fn assert_wp_amm_build() -> Result<()>{
+    #[cfg(not(feature = "wp-amm"))]
+    return err!(ErrorCode::WpAmmBuildOnlyInstruction);
+    #[cfg(feature = "wp-amm")]
+    Ok(())
+}
+
+/// Validates that a swap in a `CpAmm` in protected mode is routed through an approved router.
+///
+/// The swap must be invoked via CPI, and the program of the transaction's top-level instruction
+/// that invoked it, read from the instructions sysvar, must be approved in the `RouterRegistry`.
+///
+/// # Errors
+/// - Returns `ErrorCode::ProtectedModeRouterRequired` if the registry or the instructions sysvar is missing,
+///   the swap is invoked directly by the transaction or the invoking program isn't approved.
+pub(crate) fn validate_routed_swap(router_registry: Option<&RouterRegistry>, instructions_sysvar: Option<&AccountInfo>) -> Result<()>{
+    let (Some(router_registry), Some(instructions_sysvar)) = (router_registry, instructions_sysvar) else {
+        return err!(ErrorCode::ProtectedModeRouterRequired);
+    };
+    require!(get_stack_height() > TRANSACTION_LEVEL_STACK_HEIGHT, ErrorCode::ProtectedModeRouterRequired);
+    let current_index = load_current_index_checked(instructions_sysvar)?;
+    let router = load_instruction_at_checked(current_index as usize, instructions_sysvar)?.program_id;
+    require!(router_registry.is_approved(&router), ErrorCode::ProtectedModeRouterRequired);
+    Ok(())
+}
+
+/// Validates the origin of a swap against the swap origin policy in force in the pool's config, see
+/// `AmmsConfig::update_swap_origin_policy`.
+///
+/// A swap is direct if the transaction's top-level instruction being executed, read from the
+/// instructions sysvar, targets this program, and invoked via CPI otherwise.
+///
+/// # Errors
+/// - Returns `ErrorCode::SwapOriginRestricted` if a policy is in force and the instructions sysvar
+///   is missing or the swap's origin is blocked by the policy.
+pub(crate) fn validate_swap_origin(amms_config: &AmmsConfig, instructions_sysvar: Option<&AccountInfo>, slot: u64) -> Result<()>{
+    let swap_origin_policy = amms_config.swap_origin_policy_at(slot);
+    if swap_origin_policy == SwapOriginPolicy::Unrestricted {
+        return Ok(());
+    }
+    let Some(instructions_sysvar) = instructions_sysvar else {
+        return err!(ErrorCode::SwapOriginRestricted);
+    };
+    let current_index = load_current_index_checked(instructions_sysvar)?;
+    let is_direct = load_instruction_at_checked(current_index as usize, instructions_sysvar)?.program_id == crate::ID;
+    require!(is_direct == (swap_origin_policy == SwapOriginPolicy::DirectOnly), ErrorCode::SwapOriginRestricted);
+    Ok(())
+}
+
+/// A liquidity change applied to a `CpAmm` by a swap, provide or withdrawal, see `record_liquidity_change`.
+pub(crate) struct LiquidityChange {
+    pub kind: EventRecordKind,
+    pub signer: Pubkey,
+    pub base_amount: u64,
+    pub quote_amount: u64,
+    pub lp_tokens: u64,
+}
+
+impl LiquidityChange {
+    /// Returns the change applied by a swap of `in_amount` tokens in for `out_amount` tokens out.
+    pub(crate) fn swap(is_in_out: bool, signer: Pubkey, in_amount: u64, out_amount: u64) -> Self {
+        let (base_amount, quote_amount) = if is_in_out { (in_amount, out_amount) } else { (out_amount, in_amount) };
+        Self {
+            kind: EventRecordKind::swap(is_in_out),
+            signer,
+            base_amount,
+            quote_amount,
+            lp_tokens: 0,
+        }
+    }
+}
+
+/// Records a liquidity change once it is applied to the `CpAmm`: refreshes the display data of the pool
+/// with its new state, see `CpAmm::refresh_display_data`, and appends the change to the pool's `EventRing`
+/// if passed.
+///
+/// Every instruction changing the reserves of a pool calls this, so the ring holds all of its operations
+/// and the display data never lags behind the reserves.
+pub(crate) fn record_liquidity_change(cp_amm: &mut CpAmm, amms_config: &AmmsConfig, event_ring: Option<&AccountLoader<EventRing>>, change: LiquidityChange, slot: u64) -> Result<()>{
+    cp_amm.refresh_display_data(amms_config.providers_fee_rate_basis_points_at(slot), amms_config.protocol_fee_rate_basis_points(), slot);
+    if let Some(event_ring) = event_ring {
+        event_ring.load_mut()?.push(change.kind, change.signer, change.base_amount, change.quote_amount, change.lp_tokens, slot);
+    }
+    Ok(())
 }
\ No newline at end of file
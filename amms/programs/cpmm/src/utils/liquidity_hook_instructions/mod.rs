@@ -0,0 +1,2 @@
+mod notify_liquidity_subscriber;
+pub(crate) use notify_liquidity_subscriber::*;
\ No newline at end of file
@@ -0,0 +1,99 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::program::invoke;
+use crate::error::ErrorCode;
+use crate::liquidity_hook::{LiquidityDelta, LiquidityDeltaKind, LiquiditySnapshot, LIQUIDITY_DELTA_HOOK_DISCRIMINATOR};
+use crate::state::cp_amm::CpAmm;
+
+/// Represents a CPI notifying the liquidity subscriber of a `CpAmm` about a liquidity change.
+///
+/// # Fields
+/// - `instruction`: The `on_liquidity_delta` instruction of the subscriber program.
+/// - `account_infos`: The subscriber program, the `CpAmm` and the forwarded accounts.
+pub(crate) struct NotifyLiquiditySubscriberInstruction<'info> {
+    instruction: Instruction,
+    account_infos: Vec<AccountInfo<'info>>,
+}
+
+impl<'info> NotifyLiquiditySubscriberInstruction<'info> {
+    /// Creates a new instance of `NotifyLiquiditySubscriberInstruction`.
+    ///
+    /// - `subscriber`: The liquidity subscriber configured in the `CpAmm`.
+    /// - `cp_amm`: The `CpAmm` account, passed to the subscriber as read-only.
+    /// - `remaining_accounts`: The subscriber program account followed by the accounts to forward.
+    /// - `delta`: The liquidity change to notify about.
+    ///
+    /// # Errors
+    /// - `LiquiditySubscriberMissing` if the subscriber program account is not provided.
+    /// - `InvalidLiquiditySubscriber` if the provided program is not the configured executable subscriber.
+    /// - `LiquiditySubscriberReentrancy` if the forwarded accounts include the `CpAmm`.
+    pub fn try_new(
+        subscriber: &Pubkey,
+        cp_amm: AccountInfo<'info>,
+        remaining_accounts: &[AccountInfo<'info>],
+        delta: &LiquidityDelta
+    ) -> Result<Self> {
+        let (subscriber_program, forwarded_accounts) = remaining_accounts.split_first().ok_or(ErrorCode::LiquiditySubscriberMissing)?;
+        require!(
+            subscriber_program.key() == *subscriber && subscriber_program.executable,
+            ErrorCode::InvalidLiquiditySubscriber
+        );
+
+        let mut accounts = Vec::with_capacity(forwarded_accounts.len() + 1);
+        accounts.push(AccountMeta::new_readonly(cp_amm.key(), false));
+        for account in forwarded_accounts {
+            require!(account.key() != cp_amm.key(), ErrorCode::LiquiditySubscriberReentrancy);
+            accounts.push(if account.is_writable {
+                AccountMeta::new(account.key(), false)
+            } else {
+                AccountMeta::new_readonly(account.key(), false)
+            });
+        }
+
+        let mut data = LIQUIDITY_DELTA_HOOK_DISCRIMINATOR.to_vec();
+        delta.serialize(&mut data)?;
+
+        let mut account_infos = Vec::with_capacity(remaining_accounts.len() + 1);
+        account_infos.push(subscriber_program.clone());
+        account_infos.push(cp_amm);
+        account_infos.extend_from_slice(forwarded_accounts);
+
+        Ok(Self {
+            instruction: Instruction {
+                program_id: *subscriber,
+                accounts,
+                data,
+            },
+            account_infos,
+        })
+    }
+
+    pub fn execute(self) -> Result<()> {
+        invoke(&self.instruction, &self.account_infos).map_err(Into::into)
+    }
+}
+
+/// Notifies the liquidity subscriber of the `CpAmm`, if any, about a liquidity change.
+///
+/// The updated `CpAmm` state is written to the account data before the CPI, so the subscriber
+/// observes the pool after the change.
+///
+/// # Parameters
+/// - `cp_amm`: The `CpAmm` account with the liquidity change applied.
+/// - `remaining_accounts`: The remaining accounts of the AMM instruction.
+/// - `kind`: The kind of the instruction that changed the liquidity.
+/// - `liquidity_snapshot`: The liquidity of the `CpAmm` before the change.
+pub(crate) fn notify_liquidity_subscriber<'info>(
+    cp_amm: &Account<'info, CpAmm>,
+    remaining_accounts: &[AccountInfo<'info>],
+    kind: LiquidityDeltaKind,
+    liquidity_snapshot: LiquiditySnapshot
+) -> Result<()> {
+    let Some(subscriber) = cp_amm.liquidity_subscriber() else {
+        return Ok(());
+    };
+    let delta = liquidity_snapshot.get_delta(cp_amm.key(), cp_amm, kind, Clock::get()?.slot);
+    cp_amm.exit(&crate::ID)?;
+
+    NotifyLiquiditySubscriberInstruction::try_new(subscriber, cp_amm.to_account_info(), remaining_accounts, &delta)?.execute()
+}
\ No newline at end of file
@@ -1,4 +1,3 @@
-mod uint_types;
-mod q64_128;
-pub use q64_128::*;
-use uint_types::*;
\ No newline at end of file
+mod strict;
+pub use cpmm_math::{Q64_128, SignedQ64_128};
+pub(crate) use strict::*;
\ No newline at end of file
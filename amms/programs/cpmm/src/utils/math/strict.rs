@@ -0,0 +1,45 @@
+//! Arithmetic helpers switched by the `strict-math` feature.
+//!
+//! Without the feature the macros expand to plain operators, which panic on overflow
+//! with `overflow-checks` enabled. With the feature they expand to checked operations
+//! returning `ErrorCode::StrictMathOverflow` from the enclosing function instead.
+
+#[cfg(feature = "strict-math")]
+macro_rules! strict_op {
+    ($checked:ident, $op:tt, $lhs:expr, $rhs:expr) => {
+        $lhs.$checked($rhs).ok_or($crate::error::ErrorCode::StrictMathOverflow)?
+    };
+}
+
+#[cfg(not(feature = "strict-math"))]
+macro_rules! strict_op {
+    ($checked:ident, $op:tt, $lhs:expr, $rhs:expr) => {
+        ($lhs $op $rhs)
+    };
+}
+
+/// Adds two values, see the module documentation for overflow behavior.
+macro_rules! strict_add {
+    ($lhs:expr, $rhs:expr) => {
+        $crate::utils::math::strict_op!(checked_add, +, $lhs, $rhs)
+    };
+}
+
+/// Subtracts two values, see the module documentation for overflow behavior.
+macro_rules! strict_sub {
+    ($lhs:expr, $rhs:expr) => {
+        $crate::utils::math::strict_op!(checked_sub, -, $lhs, $rhs)
+    };
+}
+
+/// Multiplies two values, see the module documentation for overflow behavior.
+macro_rules! strict_mul {
+    ($lhs:expr, $rhs:expr) => {
+        $crate::utils::math::strict_op!(checked_mul, *, $lhs, $rhs)
+    };
+}
+
+pub(crate) use strict_op;
+pub(crate) use strict_add;
+pub(crate) use strict_sub;
+pub(crate) use strict_mul;
\ No newline at end of file
@@ -1,7 +1,11 @@
 mod helpers;
 pub mod math;
+pub mod oracle;
+pub mod token_metadata;
 pub mod token_instructions;
 pub mod system_instructions;
 pub mod token_accounts_instructions;
+pub mod liquidity_hook_instructions;
+pub mod screening_hook_instructions;
 
 pub(crate) use helpers::*;
\ No newline at end of file
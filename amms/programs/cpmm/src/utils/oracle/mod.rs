@@ -0,0 +1,5 @@
+mod usd_price;
+mod pyth_price_update;
+
+pub use usd_price::*;
+pub(crate) use pyth_price_update::*;
\ No newline at end of file
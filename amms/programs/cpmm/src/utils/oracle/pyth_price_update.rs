@@ -0,0 +1,147 @@
+use anchor_lang::prelude::*;
+use crate::error::ErrorCode;
+use super::UsdPrice;
+
+/// The Pyth Solana receiver program owning `PriceUpdateV2` accounts.
+pub mod pyth_receiver {
+    use anchor_lang::declare_id;
+    declare_id!("rec5EKMGg6MxZYaMdyBfgwp4d5rB9T1VQH5pJv5LtFJ");
+}
+
+/// The Pyth Solana receiver program id.
+pub const PYTH_RECEIVER_PROGRAM_ID: Pubkey = pyth_receiver::ID;
+
+/// The maximum age of a price update accepted for USD accounting, in seconds.
+pub const MAX_USD_PRICE_AGE_SECONDS: i64 = 60;
+
+/// Anchor discriminator of the Pyth `PriceUpdateV2` account.
+const PRICE_UPDATE_V2_DISCRIMINATOR: [u8; 8] = [34, 241, 35, 99, 157, 126, 244, 205];
+
+/// Mirror of the Pyth `VerificationLevel` enum.
+#[derive(AnchorDeserialize)]
+enum VerificationLevel {
+    Partial {
+        #[allow(dead_code)]
+        num_signatures: u8
+    },
+    Full,
+}
+
+/// Mirror of the Pyth `PriceFeedMessage` struct.
+#[derive(AnchorDeserialize)]
+struct PriceFeedMessage {
+    _feed_id: [u8; 32],
+    price: i64,
+    _conf: u64,
+    exponent: i32,
+    publish_time: i64,
+    _prev_publish_time: i64,
+    _ema_price: i64,
+    _ema_conf: u64,
+}
+
+/// Mirror of the Pyth `PriceUpdateV2` account data following the discriminator.
+#[derive(AnchorDeserialize)]
+struct PriceUpdateV2 {
+    _write_authority: Pubkey,
+    verification_level: VerificationLevel,
+    price_message: PriceFeedMessage,
+    _posted_slot: u64,
+}
+
+/// Reads a USD price from a Pyth `PriceUpdateV2` account.
+///
+/// Only fully verified, positive prices published within `MAX_USD_PRICE_AGE_SECONDS` are accepted.
+/// Any other account yields `None`, so USD accounting never blocks pool operations.
+///
+/// # Parameters
+/// - `price_update`: The Pyth price update account.
+/// - `token_decimals`: The decimals of the priced token.
+/// - `current_timestamp`: The current unix timestamp.
+///
+/// # Returns
+/// - `Some(UsdPrice)` if the account holds a valid and fresh price.
+/// - `None` otherwise.
+pub(crate) fn read_pyth_usd_price(price_update: &AccountInfo, token_decimals: u8, current_timestamp: i64) -> Option<UsdPrice> {
+    if *price_update.owner != PYTH_RECEIVER_PROGRAM_ID {
+        return None;
+    }
+    let data = price_update.try_borrow_data().ok()?;
+    parse_price_update(&data, token_decimals, current_timestamp)
+}
+
+/// Reads a token USD price from the oracle configured on a pool.
+///
+/// # Parameters
+/// - `configured_oracle`: The oracle account configured on the pool, if any.
+/// - `price_update`: The oracle account passed to the instruction, if any.
+/// - `token_decimals`: The decimals of the priced token.
+///
+/// # Returns
+/// - `Ok(None)` if the pool has no oracle or the oracle price is not usable.
+/// - `Ok(Some(UsdPrice))` if the oracle holds a valid and fresh price.
+/// - `Err(ErrorCode::InvalidCpAmmUsdOracle)` if the pool has an oracle but the account is missing.
+pub(crate) fn read_configured_usd_price(configured_oracle: Option<&Pubkey>, price_update: Option<&AccountInfo>, token_decimals: u8) -> Result<Option<UsdPrice>> {
+    if configured_oracle.is_none() {
+        return Ok(None);
+    }
+    let price_update = price_update.ok_or(ErrorCode::InvalidCpAmmUsdOracle)?;
+    Ok(read_pyth_usd_price(price_update, token_decimals, Clock::get()?.unix_timestamp))
+}
+
+/// Parses raw `PriceUpdateV2` account data into a `UsdPrice`.
+fn parse_price_update(data: &[u8], token_decimals: u8, current_timestamp: i64) -> Option<UsdPrice> {
+    if data.len() < 8 || data[..8] != PRICE_UPDATE_V2_DISCRIMINATOR {
+        return None;
+    }
+    let price_update = PriceUpdateV2::deserialize(&mut &data[8..]).ok()?;
+    if !matches!(price_update.verification_level, VerificationLevel::Full) {
+        return None;
+    }
+    let message = price_update.price_message;
+    if message.price <= 0 || current_timestamp.saturating_sub(message.publish_time) > MAX_USD_PRICE_AGE_SECONDS {
+        return None;
+    }
+    Some(UsdPrice::new(message.price as u64, message.exponent, token_decimals))
+}
+
+#[cfg(test)]
+mod pyth_price_update_tests {
+    use super::*;
+
+    fn price_update_data(is_fully_verified: bool, price: i64, exponent: i32, publish_time: i64) -> Vec<u8> {
+        let mut data = PRICE_UPDATE_V2_DISCRIMINATOR.to_vec();
+        data.extend_from_slice(Pubkey::new_unique().as_ref());
+        if is_fully_verified {
+            data.push(1);
+        } else {
+            data.extend_from_slice(&[0, 3]);
+        }
+        data.extend_from_slice(&[7u8; 32]);
+        data.extend_from_slice(&price.to_le_bytes());
+        data.extend_from_slice(&10u64.to_le_bytes());
+        data.extend_from_slice(&exponent.to_le_bytes());
+        data.extend_from_slice(&publish_time.to_le_bytes());
+        data.extend_from_slice(&(publish_time - 1).to_le_bytes());
+        data.extend_from_slice(&price.to_le_bytes());
+        data.extend_from_slice(&10u64.to_le_bytes());
+        data.extend_from_slice(&100u64.to_le_bytes());
+        data
+    }
+
+    /// Tests parsing of valid, unverified, stale and malformed price updates.
+    #[test]
+    fn test_parse_price_update() {
+        let data = price_update_data(true, 100_020_000, -8, 1_000);
+        assert_eq!(parse_price_update(&data, 6, 1_030), Some(UsdPrice::new(100_020_000, -8, 6)));
+        assert_eq!(parse_price_update(&data, 6, 1_000 + MAX_USD_PRICE_AGE_SECONDS + 1), None);
+
+        assert_eq!(parse_price_update(&price_update_data(false, 100_020_000, -8, 1_000), 6, 1_000), None);
+        assert_eq!(parse_price_update(&price_update_data(true, -1, -8, 1_000), 6, 1_000), None);
+        assert_eq!(parse_price_update(&data[..40], 6, 1_000), None);
+
+        let mut data = data;
+        data[0] = 0;
+        assert_eq!(parse_price_update(&data, 6, 1_000), None);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,79 @@
+/// A USD price of a token read from an oracle.
+///
+/// The price of one whole token equals `price * 10^exponent` USD, so token amounts
+/// are converted using the token's decimals.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct UsdPrice {
+    /// The oracle price mantissa.
+    price: u64,
+
+    /// The oracle price exponent.
+    exponent: i32,
+
+    /// The decimals of the priced token.
+    token_decimals: u8,
+}
+
+impl UsdPrice {
+    /// The number of decimals of USD values produced by `usd_value`.
+    pub const USD_VALUE_DECIMALS: u8 = 6;
+
+    /// Creates a new `UsdPrice`.
+    ///
+    /// # Parameters
+    /// - `price`: The oracle price mantissa.
+    /// - `exponent`: The oracle price exponent.
+    /// - `token_decimals`: The decimals of the priced token.
+    pub fn new(price: u64, exponent: i32, token_decimals: u8) -> Self {
+        Self {
+            price,
+            exponent,
+            token_decimals,
+        }
+    }
+
+    /// Converts a token amount into USD with `USD_VALUE_DECIMALS` decimals.
+    ///
+    /// # Parameters
+    /// - `amount`: The token amount in the token's smallest units.
+    ///
+    /// # Returns
+    /// - `Some(u64)` with the USD value, rounded down.
+    /// - `None` if the value does not fit into `u64`.
+    pub fn usd_value(&self, amount: u64) -> Option<u64> {
+        let value = amount as u128 * self.price as u128;
+        let scale = Self::USD_VALUE_DECIMALS as i32 + self.exponent - self.token_decimals as i32;
+        let value = if scale >= 0 {
+            value.checked_mul(10u128.checked_pow(scale as u32)?)?
+        } else {
+            10u128.checked_pow(scale.unsigned_abs()).map_or(0, |divisor| value / divisor)
+        };
+        u64::try_from(value).ok()
+    }
+}
+
+#[cfg(test)]
+mod usd_price_tests {
+    use super::*;
+
+    /// Tests `usd_value` for positive and negative decimal scales.
+    #[test]
+    fn test_usd_value() {
+        // 1.5 USD per token with 6 decimals
+        let price = UsdPrice::new(150_000_000, -8, 6);
+        assert_eq!(price.usd_value(2_000_000), Some(3_000_000));
+        assert_eq!(price.usd_value(1), Some(1));
+
+        // 0.99995 USD per token with 9 decimals
+        let price = UsdPrice::new(99_995, -5, 9);
+        assert_eq!(price.usd_value(1_000_000_000), Some(999_950));
+        assert_eq!(price.usd_value(999), Some(0));
+
+        // 20 USD per token with 0 decimals
+        let price = UsdPrice::new(2, 1, 0);
+        assert_eq!(price.usd_value(3), Some(60_000_000));
+
+        assert_eq!(UsdPrice::new(u64::MAX, 0, 0).usd_value(u64::MAX), None);
+        assert_eq!(UsdPrice::new(1, -100, 0).usd_value(u64::MAX), Some(0));
+    }
+}
\ No newline at end of file
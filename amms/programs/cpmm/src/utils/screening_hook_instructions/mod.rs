@@ -0,0 +1,2 @@
+mod screen_liquidity_operation;
+pub(crate) use screen_liquidity_operation::*;
\ No newline at end of file
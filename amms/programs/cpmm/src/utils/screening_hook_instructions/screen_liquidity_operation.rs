@@ -0,0 +1,107 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::program::invoke;
+use crate::error::ErrorCode;
+use crate::screening_hook::{LiquidityOperation, SCREEN_LIQUIDITY_OPERATION_DISCRIMINATOR};
+use crate::state::{AmmsConfig, cp_amm::CpAmm};
+
+/// Represents a CPI asking the screening program of an `AmmsConfig` to approve a liquidity operation.
+///
+/// # Fields
+/// - `instruction`: The `screen_liquidity_operation` instruction of the screening program.
+/// - `account_infos`: The screening program, the `CpAmm`, the liquidity owner and the forwarded accounts.
+pub(crate) struct ScreenLiquidityOperationInstruction<'info> {
+    instruction: Instruction,
+    account_infos: Vec<AccountInfo<'info>>,
+}
+
+impl<'info> ScreenLiquidityOperationInstruction<'info> {
+    /// Creates a new instance of `ScreenLiquidityOperationInstruction`.
+    ///
+    /// - `screening_program`: The screening program configured in the `AmmsConfig`.
+    /// - `cp_amm`: The `CpAmm` account, passed to the screening program as read-only.
+    /// - `owner`: The owner of the provided or withdrawn liquidity, passed as read-only.
+    /// - `remaining_accounts`: The screening program account followed by the accounts to forward.
+    /// - `operation`: The liquidity operation to screen.
+    ///
+    /// # Errors
+    /// - `ScreeningProgramMissing` if the screening program account is not provided.
+    /// - `InvalidScreeningProgram` if the provided program is not the configured executable screening program.
+    /// - `ScreeningProgramReentrancy` if the forwarded accounts include the `CpAmm`.
+    pub fn try_new(
+        screening_program: &Pubkey,
+        cp_amm: AccountInfo<'info>,
+        owner: AccountInfo<'info>,
+        remaining_accounts: &[AccountInfo<'info>],
+        operation: &LiquidityOperation
+    ) -> Result<Self> {
+        let (screening_program_account, forwarded_accounts) = remaining_accounts.split_first().ok_or(ErrorCode::ScreeningProgramMissing)?;
+        require!(
+            screening_program_account.key() == *screening_program && screening_program_account.executable,
+            ErrorCode::InvalidScreeningProgram
+        );
+
+        let mut accounts = Vec::with_capacity(forwarded_accounts.len() + 2);
+        accounts.push(AccountMeta::new_readonly(cp_amm.key(), false));
+        accounts.push(AccountMeta::new_readonly(owner.key(), false));
+        for account in forwarded_accounts {
+            require!(account.key() != cp_amm.key(), ErrorCode::ScreeningProgramReentrancy);
+            accounts.push(if account.is_writable {
+                AccountMeta::new(account.key(), false)
+            } else {
+                AccountMeta::new_readonly(account.key(), false)
+            });
+        }
+
+        let mut data = SCREEN_LIQUIDITY_OPERATION_DISCRIMINATOR.to_vec();
+        operation.serialize(&mut data)?;
+
+        let mut account_infos = Vec::with_capacity(remaining_accounts.len() + 2);
+        account_infos.push(screening_program_account.clone());
+        account_infos.push(cp_amm);
+        account_infos.push(owner);
+        account_infos.extend_from_slice(forwarded_accounts);
+
+        Ok(Self {
+            instruction: Instruction {
+                program_id: *screening_program,
+                accounts,
+                data,
+            },
+            account_infos,
+        })
+    }
+
+    pub fn execute(self) -> Result<()> {
+        invoke(&self.instruction, &self.account_infos).map_err(Into::into)
+    }
+}
+
+/// Asks the screening program of the `AmmsConfig`, if any, to approve a liquidity operation.
+///
+/// The screening program rejects the operation by failing, which fails the whole instruction.
+///
+/// # Parameters
+/// - `amms_config`: The `AmmsConfig` of the pool.
+/// - `cp_amm`: The `CpAmm` account of the operation.
+/// - `owner`: The owner of the provided or withdrawn liquidity.
+/// - `remaining_accounts`: The remaining accounts of the AMM instruction.
+/// - `operation`: The liquidity operation to screen.
+///
+/// # Returns
+/// - The remaining accounts following the screening program account, or all of them if the
+///   `AmmsConfig` has no screening program.
+pub(crate) fn screen_liquidity_operation<'a, 'info>(
+    amms_config: &AmmsConfig,
+    cp_amm: &Account<'info, CpAmm>,
+    owner: AccountInfo<'info>,
+    remaining_accounts: &'a [AccountInfo<'info>],
+    operation: &LiquidityOperation
+) -> Result<&'a [AccountInfo<'info>]> {
+    let Some(screening_program) = amms_config.screening_program() else {
+        return Ok(remaining_accounts);
+    };
+
+    ScreenLiquidityOperationInstruction::try_new(screening_program, cp_amm.to_account_info(), owner, remaining_accounts, operation)?.execute()?;
+    Ok(&remaining_accounts[1..])
+}
\ No newline at end of file
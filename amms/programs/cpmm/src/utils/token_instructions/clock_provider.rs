@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+
+/// Provides the current epoch to the transfer fee calculation.
+///
+/// On-chain the epoch is read from the `Clock` sysvar via `SysvarClockProvider`,
+/// while tests inject a fixed epoch to exercise fee-on-transfer paths without a validator.
+pub(crate) trait ClockProvider {
+    /// Returns the current epoch.
+    fn epoch(&self) -> Result<u64>;
+}
+
+/// Reads the epoch from the `Clock` sysvar.
+pub(crate) struct SysvarClockProvider;
+
+impl ClockProvider for SysvarClockProvider {
+    #[inline]
+    fn epoch(&self) -> Result<u64> {
+        Ok(Clock::get()?.epoch)
+    }
+}
+
+/// Returns a fixed epoch, for deterministic tests.
+#[cfg(test)]
+pub(crate) struct FixedClockProvider(pub u64);
+
+#[cfg(test)]
+impl ClockProvider for FixedClockProvider {
+    fn epoch(&self) -> Result<u64> {
+        Ok(self.0)
+    }
+}
\ No newline at end of file
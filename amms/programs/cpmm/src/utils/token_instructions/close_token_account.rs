@@ -0,0 +1,54 @@
+use anchor_lang::context::CpiContext;
+use anchor_lang::prelude::*;
+use anchor_lang::ToAccountInfo;
+use anchor_spl::token_interface::{close_account, CloseAccount, TokenAccount, TokenInterface};
+use crate::error::ErrorCode;
+
+/// Represents an instruction to close an empty token account and reclaim its rent.
+///
+/// This struct prepares and executes the account closing by encapsulating
+/// the CPI (Cross-Program Invocation) context.
+///
+/// - `cpi_context`: The CPI context required to close the account.
+pub(crate) struct CloseTokenAccountInstruction<'at, 'bt, 'ct, 'info> {
+    cpi_context: CpiContext<'at, 'bt, 'ct, 'info, CloseAccount<'info>>,
+}
+
+impl<'at, 'bt, 'ct, 'info> CloseTokenAccountInstruction<'at, 'bt, 'ct, 'info> {
+    /// Creates a new `CloseTokenAccountInstruction` instance for closing a token account.
+    ///
+    /// - `account`: The empty token account to close.
+    /// - `authority`: The owner of the token account.
+    /// - `destination`: The account receiving the reclaimed rent.
+    /// - `token_program`: The token program owning the account.
+    pub fn try_new(account: &InterfaceAccount<'info, TokenAccount>, authority: AccountInfo<'info>, destination: AccountInfo<'info>, token_program: &Interface<'info, TokenInterface>) -> Result<Self>{
+        require!(account.amount == 0, ErrorCode::TokenAccountNotEmpty);
+        let cpi_context = CpiContext::new(
+            token_program.to_account_info(),
+            CloseAccount{
+                account: account.to_account_info(),
+                destination,
+                authority,
+            }
+        );
+        Ok(CloseTokenAccountInstruction{
+            cpi_context,
+        })
+    }
+
+    /// Executes the account closing.
+    ///
+    /// If signer seeds are provided, they are added to the CPI context to support PDA-based signing.
+    ///
+    /// - `optional_signers_seeds`: Optional signer seeds for PDA accounts.
+    ///
+    /// Returns:
+    /// - `Ok(())` if the account is closed.
+    /// - `Err(ProgramError)` if the closing fails.
+    pub fn execute(mut self, optional_signers_seeds: Option<&'at[&'bt[&'ct[u8]]]>) -> Result<()>{
+        if let Some(signer_seeds) = optional_signers_seeds {
+            self.cpi_context = self.cpi_context.with_signer(signer_seeds);
+        }
+        close_account(self.cpi_context)
+    }
+}
\ No newline at end of file
@@ -1,12 +1,18 @@
+mod clock_provider;
 mod transfer_tokens;
 mod transfer_context_regular;
 mod transfer_context_with_fee;
 mod mint_spl_tokens;
 mod burn_spl_tokens;
+mod set_spl_mint_authority;
+mod close_token_account;
 
+pub(crate) use clock_provider::*;
 pub(crate) use transfer_tokens::*;
 pub(crate) use mint_spl_tokens::*;
 pub(crate) use burn_spl_tokens::*;
+pub(crate) use set_spl_mint_authority::*;
+pub(crate) use close_token_account::*;
 
 use transfer_context_regular::*;
 use transfer_context_with_fee::*;
\ No newline at end of file
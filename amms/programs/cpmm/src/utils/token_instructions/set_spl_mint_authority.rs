@@ -0,0 +1,53 @@
+use anchor_lang::context::CpiContext;
+use anchor_lang::prelude::*;
+use anchor_lang::ToAccountInfo;
+use anchor_spl::token::{set_authority, Mint, SetAuthority, Token};
+use anchor_spl::token::spl_token::instruction::AuthorityType;
+
+
+/// Represents an instruction to hand over an authority of a mint to a new account.
+///
+/// This struct prepares and executes the authority change by encapsulating
+/// the CPI (Cross-Program Invocation) context and the necessary parameters.
+///
+/// - `authority_type`: The mint authority being changed, either `MintTokens` or `FreezeAccount`.
+/// - `new_authority`: The public key of the new authority.
+/// - `cpi_context`: The CPI context required to perform the authority change.
+pub(crate) struct SetMintAuthorityInstruction<'at, 'bt, 'ct, 'info> {
+    authority_type: AuthorityType,
+    new_authority: Pubkey,
+    cpi_context: CpiContext<'at, 'bt, 'ct, 'info, SetAuthority<'info>>,
+}
+
+impl<'at, 'bt, 'ct, 'info> SetMintAuthorityInstruction<'at, 'bt, 'ct, 'info> {
+    /// Creates a new `SetMintAuthorityInstruction` instance for changing a mint authority.
+    ///
+    /// - `authority_type`: The mint authority being changed.
+    /// - `mint`: The mint account of the token.
+    /// - `current_authority`: The current holder of the authority, signing the change.
+    /// - `new_authority`: The public key of the new authority.
+    /// - `token_program`: The SPL token program owning the mint.
+    pub fn new(authority_type: AuthorityType, mint: &Account<'info, Mint>, current_authority: AccountInfo<'info>, new_authority: Pubkey, token_program: &Program<'info, Token>) -> Self{
+        let cpi_context = CpiContext::new(
+            token_program.to_account_info(),
+            SetAuthority{
+                current_authority,
+                account_or_mint: mint.to_account_info(),
+            }
+        );
+        SetMintAuthorityInstruction{
+            authority_type,
+            new_authority,
+            cpi_context,
+        }
+    }
+
+    /// Executes the authority change.
+    ///
+    /// Returns:
+    /// - `Ok(())` if the authority change is successful.
+    /// - `Err(ProgramError)` if the authority change fails.
+    pub fn execute(self) -> Result<()>{
+        set_authority(self.cpi_context, self.authority_type, Some(self.new_authority))
+    }
+}
\ No newline at end of file
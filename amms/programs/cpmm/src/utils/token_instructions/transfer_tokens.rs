@@ -3,8 +3,8 @@ use anchor_spl::{
     token::{ID as TOKEN_PROGRAM_ID}
 };
 use anchor_spl::token_interface::{get_mint_extension_data, transfer_checked, transfer_checked_with_fee, Mint, TokenInterface, TokenAccount};
-use anchor_spl::token_interface::spl_token_2022::extension::transfer_fee::TransferFeeConfig;
-use crate::utils::token_instructions::{TransferContextRegular, TransferContextWithFee};
+use anchor_spl::token_interface::spl_token_2022::extension::transfer_fee::{TransferFee, TransferFeeConfig};
+use crate::utils::token_instructions::{ClockProvider, SysvarClockProvider, TransferContextRegular, TransferContextWithFee};
 use crate::error::ErrorCode;
 
 /// Represents an instruction to transfer tokens between accounts.
@@ -41,6 +41,23 @@ impl<'at, 'bt, 'ct, 'info>  TransferTokensInstruction<'at, 'bt, 'ct, 'info>  {
         from_authority: AccountInfo<'info>,
         to: &'_ InterfaceAccount<'info, TokenAccount>, 
         token_program: &'_ Interface<'info, TokenInterface>
+    ) -> Result<Self> {
+        Self::try_new_with_clock(amount, mint, from, from_authority, to, token_program, &SysvarClockProvider)
+    }
+
+    /// Creates a new instance of `TransferTokensInstruction`, reading the transfer fee epoch from `clock`.
+    ///
+    /// - `clock`: Provider of the epoch used to select the mint's transfer fee.
+    ///
+    /// See `try_new` for the remaining parameters.
+    pub fn try_new_with_clock(
+        amount: u64,
+        mint: &'_ InterfaceAccount<'info, Mint>,
+        from: &'_ InterfaceAccount<'info, TokenAccount>,
+        from_authority: AccountInfo<'info>,
+        to: &'_ InterfaceAccount<'info, TokenAccount>,
+        token_program: &'_ Interface<'info, TokenInterface>,
+        clock: &impl ClockProvider
     ) -> Result<Self> {
         require!(from.amount >= amount, ErrorCode::InsufficientBalanceForTransfer);
         require!(mint.to_account_info().owner.key() == token_program.key(), ErrorCode::MintAndTokenProgramMismatch);
@@ -54,8 +71,8 @@ impl<'at, 'bt, 'ct, 'info>  TransferTokensInstruction<'at, 'bt, 'ct, 'info>  {
                     mint, from_account_info, from_authority, to_account_info, token_program
                 )
             )
-        }else if let Ok(transfer_fee_config) = get_mint_extension_data::<TransferFeeConfig>(&mint.to_account_info()){
-            let fee = transfer_fee_config.calculate_epoch_fee(Clock::get()?.epoch, amount).ok_or(ErrorCode::MintTransferFeeCalculationFailed)?;
+        }else if let Some(transfer_fee_config) = get_transfer_fee_config(mint){
+            let fee = transfer_fee_config.calculate_epoch_fee(clock.epoch()?, amount).ok_or(ErrorCode::MintTransferFeeCalculationFailed)?;
             TransferContextType::WithFee(
                 TransferContextWithFee::new_for_token_2022(
                     fee, mint, from_account_info, from_authority, to_account_info, token_program
@@ -142,6 +159,42 @@ impl<'at, 'bt, 'ct, 'info>  TransferTokensInstruction<'at, 'bt, 'ct, 'info>  {
     }
 }
 
+/// Reads the `TransferFeeConfig` extension of a mint.
+///
+/// Returns:
+/// - `Some(TransferFeeConfig)` for Token 2022 mints with the extension, `None` otherwise.
+fn get_transfer_fee_config(mint: &InterfaceAccount<'_, Mint>) -> Option<TransferFeeConfig> {
+    get_mint_extension_data::<TransferFeeConfig>(&mint.to_account_info()).ok()
+}
+
+/// Calculates the gross amount to transfer so that exactly `net_amount` tokens are received
+/// after the mint's transfer fee for the given epoch.
+///
+/// - `mint`: The mint account of the token.
+/// - `net_amount`: The amount of tokens the recipient must receive.
+/// - `epoch`: The epoch the transfer happens in.
+///
+/// Returns:
+/// - `Ok((gross_amount, fee))` with the amount to transfer and the transfer fee charged on it.
+/// - `Err(ErrorCode::MintTransferFeeCalculationFailed)` if no gross amount yields `net_amount`.
+pub(crate) fn calculate_gross_transfer_amount(mint: &InterfaceAccount<'_, Mint>, net_amount: u64, epoch: u64) -> Result<(u64, u64)> {
+    let Some(transfer_fee_config) = get_transfer_fee_config(mint) else {
+        return Ok((net_amount, 0));
+    };
+    let gross_amount = calculate_pre_fee_amount(transfer_fee_config.get_epoch_fee(epoch), net_amount)
+        .ok_or(ErrorCode::MintTransferFeeCalculationFailed)?;
+    Ok((gross_amount, gross_amount - net_amount))
+}
+
+/// Calculates the amount which yields exactly `net_amount` after the `transfer_fee`.
+///
+/// Returns:
+/// - `None` if no such amount exists, e.g. for a 100% fee rate or on overflow.
+fn calculate_pre_fee_amount(transfer_fee: &TransferFee, net_amount: u64) -> Option<u64> {
+    let gross_amount = transfer_fee.calculate_pre_fee_amount(net_amount)?;
+    (transfer_fee.calculate_post_fee_amount(gross_amount)? == net_amount).then_some(gross_amount)
+}
+
 /// Represents the context of the transfer operation, which can be either:
 /// - `Regular`: For transfers without fees.
 /// - `WithFee`: For transfers that include a transfer fee.
@@ -168,4 +221,121 @@ impl<'at, 'bt, 'ct> TransferContextType<'at, 'bt, 'ct, '_>{
             }
         }
     }
+}
+
+#[cfg(test)]
+mod transfer_tokens_tests {
+    use super::*;
+    use anchor_spl::token_2022::{spl_token_2022, ID as TOKEN_2022_PROGRAM_ID};
+    use anchor_spl::token_2022::spl_token_2022::extension::{BaseStateWithExtensionsMut, ExtensionType, StateWithExtensionsMut};
+    use anchor_spl::token_2022::spl_token_2022::solana_program::program_pack::Pack;
+    use crate::utils::token_instructions::FixedClockProvider;
+
+    fn transfer_fee(transfer_fee_basis_points: u16, maximum_fee: u64) -> TransferFee {
+        TransferFee {
+            epoch: 0.into(),
+            maximum_fee: maximum_fee.into(),
+            transfer_fee_basis_points: transfer_fee_basis_points.into(),
+        }
+    }
+
+    /// Tests that the gross amount delivers exactly the net amount after the transfer fee.
+    #[test]
+    fn test_calculate_pre_fee_amount() {
+        assert_eq!(calculate_pre_fee_amount(&transfer_fee(0, 0), 1_000), Some(1_000));
+
+        // 1% fee: 1_000_000 / 0.99 rounded up
+        let fee = transfer_fee(100, u64::MAX);
+        let gross_amount = calculate_pre_fee_amount(&fee, 1_000_000).unwrap();
+        assert_eq!(gross_amount, 1_010_102);
+        assert_eq!(fee.calculate_post_fee_amount(gross_amount), Some(1_000_000));
+
+        // Fee capped by the maximum fee
+        let fee = transfer_fee(100, 500);
+        assert_eq!(calculate_pre_fee_amount(&fee, 1_000_000), Some(1_000_500));
+
+        // No gross amount delivers a positive net amount with a 100% fee
+        assert_eq!(calculate_pre_fee_amount(&transfer_fee(10_000, u64::MAX), 1_000), None);
+        assert_eq!(calculate_pre_fee_amount(&transfer_fee(100, u64::MAX), u64::MAX), None);
+    }
+
+    /// Packs a Token 2022 mint whose transfer fee changes from `older_fee` to `newer_fee` at the epoch of `newer_fee`.
+    fn token_2022_mint_data(older_fee: TransferFee, newer_fee: TransferFee) -> Vec<u8> {
+        let len = ExtensionType::try_calculate_account_len::<spl_token_2022::state::Mint>(&[ExtensionType::TransferFeeConfig]).unwrap();
+        let mut data = vec![0u8; len];
+        let mut state = StateWithExtensionsMut::<spl_token_2022::state::Mint>::unpack_uninitialized(&mut data).unwrap();
+        let transfer_fee_config = state.init_extension::<TransferFeeConfig>(true).unwrap();
+        transfer_fee_config.older_transfer_fee = older_fee;
+        transfer_fee_config.newer_transfer_fee = newer_fee;
+        state.base = spl_token_2022::state::Mint {
+            decimals: 6,
+            is_initialized: true,
+            ..Default::default()
+        };
+        state.pack_base();
+        state.init_account_type().unwrap();
+        data
+    }
+
+    fn token_2022_account_data(mint: Pubkey, amount: u64) -> Vec<u8> {
+        let mut data = vec![0u8; spl_token_2022::state::Account::LEN];
+        spl_token_2022::state::Account {
+            mint,
+            owner: Pubkey::new_unique(),
+            amount,
+            state: spl_token_2022::state::AccountState::Initialized,
+            ..Default::default()
+        }.pack_into_slice(&mut data);
+        data
+    }
+
+    /// Tests that the transfer fee is taken from the epoch of the injected clock.
+    #[test]
+    fn test_try_new_with_clock_transfer_fee_epoch() {
+        let older_fee = transfer_fee(100, u64::MAX);
+        let newer_fee = TransferFee { epoch: 10.into(), ..transfer_fee(200, 15_000) };
+
+        let (mint_key, from_key, to_key, authority_key) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let (mut mint_lamports, mut from_lamports, mut to_lamports, mut authority_lamports, mut program_lamports) = (0, 0, 0, 0, 0);
+        let mut mint_data = token_2022_mint_data(older_fee, newer_fee);
+        let mut from_data = token_2022_account_data(mint_key, 1_000_000);
+        let mut to_data = token_2022_account_data(mint_key, 0);
+        let (mut authority_data, mut program_data) = (vec![], vec![]);
+        let system_program_id = Pubkey::default();
+
+        let mint_info = AccountInfo::new(&mint_key, false, false, &mut mint_lamports, &mut mint_data, &TOKEN_2022_PROGRAM_ID, false, 0);
+        let from_info = AccountInfo::new(&from_key, false, true, &mut from_lamports, &mut from_data, &TOKEN_2022_PROGRAM_ID, false, 0);
+        let to_info = AccountInfo::new(&to_key, false, true, &mut to_lamports, &mut to_data, &TOKEN_2022_PROGRAM_ID, false, 0);
+        let authority_info = AccountInfo::new(&authority_key, true, false, &mut authority_lamports, &mut authority_data, &system_program_id, false, 0);
+        let program_info = AccountInfo::new(&TOKEN_2022_PROGRAM_ID, false, false, &mut program_lamports, &mut program_data, &system_program_id, true, 0);
+
+        let mint = InterfaceAccount::<Mint>::try_from(&mint_info).unwrap();
+        let from = InterfaceAccount::<TokenAccount>::try_from(&from_info).unwrap();
+        let to = InterfaceAccount::<TokenAccount>::try_from(&to_info).unwrap();
+        let token_program = Interface::<TokenInterface>::try_from(&program_info).unwrap();
+
+        // Older fee before the newer fee epoch
+        let instruction = TransferTokensInstruction::try_new_with_clock(
+            500_000, &mint, &from, authority_info.clone(), &to, &token_program, &FixedClockProvider(9)
+        ).unwrap();
+        assert_eq!(instruction.get_fee(), 5_000);
+        assert_eq!(instruction.get_amount_after_fee(), 495_000);
+        assert_eq!(instruction.get_raw_amount(), 500_000);
+
+        // Newer fee, capped by its maximum fee, from the newer fee epoch on
+        let instruction = TransferTokensInstruction::try_new_with_clock(
+            500_000, &mint, &from, authority_info.clone(), &to, &token_program, &FixedClockProvider(10)
+        ).unwrap();
+        assert_eq!(instruction.get_fee(), 10_000);
+        let instruction = TransferTokensInstruction::try_new_with_clock(
+            1_000_000, &mint, &from, authority_info.clone(), &to, &token_program, &FixedClockProvider(11)
+        ).unwrap();
+        assert_eq!(instruction.get_fee(), 15_000);
+        assert_eq!(instruction.get_amount_after_fee(), 985_000);
+
+        // Insufficient balance is rejected before the fee is calculated
+        assert!(TransferTokensInstruction::try_new_with_clock(
+            1_000_001, &mint, &from, authority_info, &to, &token_program, &FixedClockProvider(11)
+        ).is_err());
+    }
 }
\ No newline at end of file
@@ -0,0 +1,155 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::{ID as TOKEN_2022_PROGRAM_ID, spl_token_2022};
+use anchor_spl::token_2022::spl_token_2022::extension::{BaseStateWithExtensions, StateWithExtensions};
+use anchor_spl::token_2022_extensions::spl_token_metadata_interface::state::TokenMetadata;
+
+/// The Metaplex Token Metadata program owning `Metadata` accounts.
+pub mod metaplex_token_metadata {
+    use anchor_lang::declare_id;
+    declare_id!("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+}
+
+/// The Metaplex Token Metadata program id.
+pub const METAPLEX_TOKEN_METADATA_PROGRAM_ID: Pubkey = metaplex_token_metadata::ID;
+
+/// The `Key` of Metaplex `Metadata` accounts.
+const METAPLEX_METADATA_V1_KEY: u8 = 4;
+
+/// The maximal length of a symbol included in logs.
+const MAX_SYMBOL_LENGTH: usize = 10;
+
+/// Reads the symbol of a mint, for human-readable logs only.
+///
+/// The symbol is read from the Token-2022 `TokenMetadata` extension of the mint, or else from its
+/// Metaplex `Metadata` account if one is passed. Symbols are chosen by the mint creators, so they
+/// must never be used for validation.
+///
+/// # Parameters
+/// - `mint`: The mint account.
+/// - `metaplex_metadata`: The Metaplex `Metadata` account of the mint, if passed.
+///
+/// # Returns
+/// - `Some(String)` with the symbol.
+/// - `None` if the mint has no readable metadata.
+pub(crate) fn read_mint_symbol(mint: &AccountInfo, metaplex_metadata: Option<&AccountInfo>) -> Option<String> {
+    read_token_2022_symbol(mint).or_else(|| {
+        let metadata = metaplex_metadata?;
+        if metadata.owner != &METAPLEX_TOKEN_METADATA_PROGRAM_ID {
+            return None;
+        }
+        parse_metaplex_symbol(&metadata.try_borrow_data().ok()?, mint.key)
+    })
+}
+
+/// Returns the label of a mint in logs: its symbol followed by its address, or only its address.
+///
+/// # Parameters
+/// - `mint`: The mint account.
+/// - `metaplex_metadata`: The Metaplex `Metadata` account of the mint, if passed.
+pub(crate) fn mint_label(mint: &AccountInfo, metaplex_metadata: Option<&AccountInfo>) -> String {
+    match read_mint_symbol(mint, metaplex_metadata) {
+        Some(symbol) => format!("{} ({})", symbol, mint.key),
+        None => mint.key.to_string(),
+    }
+}
+
+/// Logs a failed validation of a mint with its label.
+///
+/// # Parameters
+/// - `mint`: The mint account.
+/// - `metaplex_metadata`: The Metaplex `Metadata` account of the mint, if passed.
+/// - `error`: The validation error, passed through.
+pub(crate) fn log_invalid_mint(mint: &AccountInfo, metaplex_metadata: Option<&AccountInfo>, error: Error) -> Error {
+    msg!("Mint validation failed: {}", mint_label(mint, metaplex_metadata));
+    error
+}
+
+/// Reads the symbol of the `TokenMetadata` extension of a Token-2022 mint.
+fn read_token_2022_symbol(mint: &AccountInfo) -> Option<String> {
+    if mint.owner != &TOKEN_2022_PROGRAM_ID {
+        return None;
+    }
+    let data = mint.try_borrow_data().ok()?;
+    let metadata = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&data).ok()?
+        .get_variable_len_extension::<TokenMetadata>().ok()?;
+    sanitize_symbol(metadata.symbol.as_bytes())
+}
+
+/// Parses the symbol of a Metaplex `Metadata` account of a mint.
+///
+/// The account starts with its `Key`, the update authority, the mint and the name and symbol as
+/// Borsh strings padded with null bytes.
+///
+/// # Parameters
+/// - `data`: The account data.
+/// - `mint`: The public key of the mint the metadata must describe.
+fn parse_metaplex_symbol(data: &[u8], mint: &Pubkey) -> Option<String> {
+    let read_string = |offset: usize| -> Option<(&[u8], usize)> {
+        let length = u32::from_le_bytes(data.get(offset..offset + 4)?.try_into().ok()?) as usize;
+        Some((data.get(offset + 4..offset + 4 + length)?, offset + 4 + length))
+    };
+    if data.first() != Some(&METAPLEX_METADATA_V1_KEY) || data.get(33..65)? != mint.as_ref() {
+        return None;
+    }
+    let (_, symbol_offset) = read_string(65)?;
+    let (symbol, _) = read_string(symbol_offset)?;
+    sanitize_symbol(symbol)
+}
+
+/// Strips the null padding of a symbol and rejects empty, overlong or non-printable symbols.
+fn sanitize_symbol(symbol: &[u8]) -> Option<String> {
+    let symbol = std::str::from_utf8(symbol).ok()?.trim_end_matches('\0').trim();
+    if symbol.is_empty() || symbol.chars().count() > MAX_SYMBOL_LENGTH || symbol.chars().any(char::is_control) {
+        return None;
+    }
+    Some(symbol.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn metaplex_metadata(mint: &Pubkey, name: &[u8], symbol: &[u8]) -> Vec<u8> {
+        let mut data = vec![METAPLEX_METADATA_V1_KEY];
+        data.extend_from_slice(Pubkey::new_unique().as_ref());
+        data.extend_from_slice(mint.as_ref());
+        for field in [name, symbol] {
+            data.extend_from_slice(&(field.len() as u32).to_le_bytes());
+            data.extend_from_slice(field);
+        }
+        data.extend_from_slice(&[0; 200]);
+        data
+    }
+
+    /// Tests parsing the symbol of Metaplex `Metadata` accounts.
+    #[test]
+    fn test_parse_metaplex_symbol() {
+        let mint = Pubkey::new_unique();
+        let data = metaplex_metadata(&mint, b"USD Coin\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0", b"USDC\0\0\0\0\0\0");
+        assert_eq!(parse_metaplex_symbol(&data, &mint), Some("USDC".to_string()));
+        assert_eq!(parse_metaplex_symbol(&data, &Pubkey::new_unique()), None);
+        assert_eq!(parse_metaplex_symbol(&data[..70], &mint), None);
+
+        let mut wrong_key = data.clone();
+        wrong_key[0] = 6;
+        assert_eq!(parse_metaplex_symbol(&wrong_key, &mint), None);
+        assert_eq!(parse_metaplex_symbol(&metaplex_metadata(&mint, b"Coin", b"\0\0\0\0"), &mint), None);
+        assert_eq!(parse_metaplex_symbol(&metaplex_metadata(&mint, b"Coin", b"A\nB"), &mint), None);
+        assert_eq!(parse_metaplex_symbol(&metaplex_metadata(&mint, b"Coin", b"LONGSYMBOL1"), &mint), None);
+    }
+
+    /// Tests the labels of mints with and without Metaplex metadata.
+    #[test]
+    fn test_mint_label() {
+        let (key, owner) = (Pubkey::new_unique(), anchor_spl::token::ID);
+        let (mut lamports, mut data) = (0u64, vec![0u8; 82]);
+        let mint = AccountInfo::new(&key, false, false, &mut lamports, &mut data, &owner, false, 0);
+        assert_eq!(read_mint_symbol(&mint, None), None);
+        assert_eq!(mint_label(&mint, None), key.to_string());
+
+        let metadata_key = Pubkey::new_unique();
+        let (mut metadata_lamports, mut metadata_data) = (0u64, metaplex_metadata(&key, b"Coin", b"COIN"));
+        let metadata = AccountInfo::new(&metadata_key, false, false, &mut metadata_lamports, &mut metadata_data, &METAPLEX_TOKEN_METADATA_PROGRAM_ID, false, 0);
+        assert_eq!(mint_label(&mint, Some(&metadata)), format!("COIN ({})", key));
+    }
+}
\ No newline at end of file
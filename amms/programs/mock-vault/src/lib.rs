@@ -0,0 +1,123 @@
+#![allow(unexpected_cfgs)]
+use anchor_lang::prelude::*;
+use cpmm::cpi::accounts::{ProvideToCpAmm, WithdrawFromCpAmm};
+use cpmm::program::Cpmm;
+
+declare_id!("9P74TAryPGg1LE5kFgP4PER2jTqULjUir9ophqyxRZmd");
+
+/// Seed of the vault authority PDA owning the vault token accounts.
+pub const VAULT_AUTHORITY_SEED: &[u8] = b"vault_authority";
+
+/// Mock of a vault program managing liquidity in the cpmm program on behalf of its PDA.
+///
+/// The vault authority is a system-owned PDA without data, funded with lamports,
+/// so it can also pay for the LP and withdrawn token accounts created by the cpmm program.
+#[program]
+pub mod mock_vault {
+    use super::*;
+
+    pub fn provide<'info>(ctx: Context<'_, '_, '_, 'info, VaultLiquidity<'info>>, base_liquidity: u64, quote_liquidity: u64) -> Result<()> {
+        let bump = [ctx.bumps.vault_authority];
+        let signer_seeds: &[&[&[u8]]] = &[&[VAULT_AUTHORITY_SEED, &bump]];
+        let accounts = ProvideToCpAmm {
+            signer: ctx.accounts.vault_authority.to_account_info(),
+            base_mint: ctx.accounts.base_mint.to_account_info(),
+            quote_mint: ctx.accounts.quote_mint.to_account_info(),
+            lp_mint: ctx.accounts.lp_mint.to_account_info(),
+            signer_base_account: ctx.accounts.vault_base_account.to_account_info(),
+            signer_quote_account: ctx.accounts.vault_quote_account.to_account_info(),
+            signer_lp_account: ctx.accounts.vault_lp_account.to_account_info(),
+            amms_config: ctx.accounts.amms_config.to_account_info(),
+            cp_amm: ctx.accounts.cp_amm.to_account_info(),
+            cp_amm_base_vault: ctx.accounts.cp_amm_base_vault.to_account_info(),
+            cp_amm_quote_vault: ctx.accounts.cp_amm_quote_vault.to_account_info(),
+            associated_token_program: ctx.accounts.associated_token_program.to_account_info(),
+            lp_token_program: ctx.accounts.lp_token_program.to_account_info(),
+            base_token_program: ctx.accounts.base_token_program.to_account_info(),
+            quote_token_program: ctx.accounts.quote_token_program.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            liquidity_position: None,
+            event_ring: None,
+        };
+        let cpi_context = CpiContext::new_with_signer(ctx.accounts.cpmm_program.to_account_info(), accounts, signer_seeds)
+            .with_remaining_accounts(ctx.remaining_accounts.to_vec());
+        cpmm::cpi::provide_to_cp_amm(cpi_context, base_liquidity, quote_liquidity)
+    }
+
+    pub fn withdraw<'info>(ctx: Context<'_, '_, '_, 'info, VaultLiquidity<'info>>, lp_tokens: u64) -> Result<()> {
+        let bump = [ctx.bumps.vault_authority];
+        let signer_seeds: &[&[&[u8]]] = &[&[VAULT_AUTHORITY_SEED, &bump]];
+        let accounts = WithdrawFromCpAmm {
+            signer: ctx.accounts.vault_authority.to_account_info(),
+            base_mint: ctx.accounts.base_mint.to_account_info(),
+            quote_mint: ctx.accounts.quote_mint.to_account_info(),
+            lp_mint: ctx.accounts.lp_mint.to_account_info(),
+            signer_base_account: ctx.accounts.vault_base_account.to_account_info(),
+            signer_quote_account: ctx.accounts.vault_quote_account.to_account_info(),
+            signer_lp_account: ctx.accounts.vault_lp_account.to_account_info(),
+            amms_config: ctx.accounts.amms_config.to_account_info(),
+            cp_amm: ctx.accounts.cp_amm.to_account_info(),
+            cp_amm_base_vault: ctx.accounts.cp_amm_base_vault.to_account_info(),
+            cp_amm_quote_vault: ctx.accounts.cp_amm_quote_vault.to_account_info(),
+            associated_token_program: ctx.accounts.associated_token_program.to_account_info(),
+            lp_token_program: ctx.accounts.lp_token_program.to_account_info(),
+            base_token_program: ctx.accounts.base_token_program.to_account_info(),
+            quote_token_program: ctx.accounts.quote_token_program.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            liquidity_position: None,
+            event_ring: None,
+        };
+        let cpi_context = CpiContext::new_with_signer(ctx.accounts.cpmm_program.to_account_info(), accounts, signer_seeds)
+            .with_remaining_accounts(ctx.remaining_accounts.to_vec());
+        cpmm::cpi::withdraw_from_cp_amm(cpi_context, lp_tokens)
+    }
+}
+
+/// Accounts forwarded to `provide_to_cp_amm` and `withdraw_from_cp_amm`, validated by the cpmm program.
+#[derive(Accounts)]
+pub struct VaultLiquidity<'info> {
+    #[account(
+        mut,
+        seeds = [VAULT_AUTHORITY_SEED],
+        bump
+    )]
+    /// CHECK: System-owned PDA signing for the vault, validated by its seeds
+    pub vault_authority: UncheckedAccount<'info>,
+    /// CHECK: Validated by the cpmm program
+    pub base_mint: UncheckedAccount<'info>,
+    /// CHECK: Validated by the cpmm program
+    pub quote_mint: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: Validated by the cpmm program
+    pub lp_mint: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: Validated by the cpmm program
+    pub vault_base_account: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: Validated by the cpmm program
+    pub vault_quote_account: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: Validated by the cpmm program
+    pub vault_lp_account: UncheckedAccount<'info>,
+    /// CHECK: Validated by the cpmm program
+    pub amms_config: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: Validated by the cpmm program
+    pub cp_amm: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: Validated by the cpmm program
+    pub cp_amm_base_vault: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: Validated by the cpmm program
+    pub cp_amm_quote_vault: UncheckedAccount<'info>,
+    /// CHECK: Validated by the cpmm program
+    pub associated_token_program: UncheckedAccount<'info>,
+    /// CHECK: Validated by the cpmm program
+    pub lp_token_program: UncheckedAccount<'info>,
+    /// CHECK: Validated by the cpmm program
+    pub base_token_program: UncheckedAccount<'info>,
+    /// CHECK: Validated by the cpmm program
+    pub quote_token_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+    pub cpmm_program: Program<'info, Cpmm>,
+}
\ No newline at end of file
@@ -0,0 +1,29 @@
+#![allow(unexpected_cfgs)]
+use anchor_lang::prelude::*;
+use cpmm::screening_hook::LiquidityOperation;
+
+declare_id!("HoJN4b2CEiE88KBvRzt7DGnvpKUgRrdePsdnnboyDsnD");
+
+/// Reference implementation of a screening program for the cpmm program, approving every operation.
+///
+/// A compliance screening program keeps the same instruction and accounts, and rejects
+/// an operation by returning an error, e.g. when the owner is missing from its allowlist
+/// passed in the remaining accounts.
+#[program]
+pub mod noop_screening {
+    use super::*;
+
+    pub fn screen_liquidity_operation(_ctx: Context<ScreenLiquidityOperation>, operation: LiquidityOperation) -> Result<()> {
+        msg!("Approved {:?} of {} LP tokens by {}", operation.kind, operation.lp_tokens, operation.owner);
+        Ok(())
+    }
+}
+
+/// Accounts passed by the cpmm program to `screen_liquidity_operation`.
+#[derive(Accounts)]
+pub struct ScreenLiquidityOperation<'info> {
+    /// CHECK: The screened pool, passed read-only by the cpmm program
+    pub cp_amm: UncheckedAccount<'info>,
+    /// CHECK: The owner of the provided or withdrawn liquidity
+    pub owner: UncheckedAccount<'info>,
+}
\ No newline at end of file
@@ -1,5 +1,6 @@
 import {
     Endian,
+    getAddressEncoder,
     getProgramDerivedAddress,
     getU64Encoder,
     KeyPairSigner,
@@ -16,13 +17,21 @@ import {
 } from "./helpers";
 import {
     getInitializeAmmsConfigInstruction,
+    getUpdateAmmsConfigCreatorFeeShareLimitInstruction,
     getUpdateAmmsConfigFeeAuthorityInstruction,
+    getUpdateAmmsConfigFeeSettlementInstruction,
+    getUpdateAmmsConfigMintEquivalenceInstruction,
     getUpdateAmmsConfigProtocolFeeRateInstruction,
     getUpdateAmmsConfigProvidersFeeRateInstruction,
+    getUpdateAmmsConfigScreeningProgramInstruction,
     InitializeAmmsConfigInput,
+    UpdateAmmsConfigCreatorFeeShareLimitInput,
     UpdateAmmsConfigFeeAuthorityInput,
+    UpdateAmmsConfigFeeSettlementInput,
+    UpdateAmmsConfigMintEquivalenceInput,
     UpdateAmmsConfigProtocolFeeRateInput,
-    UpdateAmmsConfigProvidersFeeRateInput
+    UpdateAmmsConfigProvidersFeeRateInput,
+    UpdateAmmsConfigScreeningProgramInput
 } from "../clients/js/src/generated";
 import {SYSTEM_PROGRAM_ADDRESS} from "@solana-program/system";
 import {assert} from "chai";
@@ -278,6 +287,28 @@ export const ammsConfigTests = (
             ));
         })
 
+        it("Update of AmmsConfig fee authority to default address should fail", async () => {
+            const input: UpdateAmmsConfigFeeAuthorityInput = {
+                authority: headAuthority,
+                ammsConfigsManager: ammsConfigsManagerAddress[0],
+                ammsConfig: ammsConfigAddress[0],
+                newFeeAuthority: SYSTEM_PROGRAM_ADDRESS
+            };
+
+            const ix = getUpdateAmmsConfigFeeAuthorityInstruction(input);
+
+            await (pipe(
+                await createTransaction(rpcClient, owner, [ix]),
+                (tx) => signAndSendTransaction(rpcClient, tx)
+            ).then(
+                async (signature) => {
+                    console.log(await getTransactionLogs(rpcClient, signature));
+                    assert.fail("Expected failure of update of AmmsConfig fee authority to default address");
+                },
+                (_error) => {}
+            ));
+        })
+
         it("Update AmmsConfig fee authority by head authority", async () => {
             const ammsConfigAccountBefore = await program.fetchAmmsConfig(rpcClient.rpc, ammsConfigAddress[0]);
             assert.ok(ammsConfigAccountBefore, "AmmsConfig doesn't exist");
@@ -583,5 +614,127 @@ export const ammsConfigTests = (
                 (_error) => {}
             ));
         })
+
+        /// Fee settlement update
+
+        it("Update AmmsConfig fee settlement with zero max impact should fail", async () => {
+            const input: UpdateAmmsConfigFeeSettlementInput = {
+                authority: headAuthority,
+                ammsConfigsManager: ammsConfigsManagerAddress[0],
+                ammsConfig: ammsConfigAddress[0],
+                feeSettlement: {settlementMint: null, maxImpactBasisPoints: 0}
+            };
+
+            const ix = getUpdateAmmsConfigFeeSettlementInstruction(input);
+
+            await (pipe(
+                await createTransaction(rpcClient, owner, [ix]),
+                (tx) => signAndSendTransaction(rpcClient, tx)
+            ).then(
+                async (signature) => {
+                    console.log(await getTransactionLogs(rpcClient, signature));
+                    assert.fail("Expected failure of update of AmmsConfig fee settlement with zero max impact");
+                },
+                (_error) => {}
+            ));
+        })
+
+        it("Update AmmsConfig creator fee share limit above maximum should fail", async () => {
+            const input: UpdateAmmsConfigCreatorFeeShareLimitInput = {
+                authority: headAuthority,
+                ammsConfigsManager: ammsConfigsManagerAddress[0],
+                ammsConfig: ammsConfigAddress[0],
+                newMaxCreatorFeeShareBasisPoints: 5001
+            };
+
+            const ix = getUpdateAmmsConfigCreatorFeeShareLimitInstruction(input);
+
+            await (pipe(
+                await createTransaction(rpcClient, owner, [ix]),
+                (tx) => signAndSendTransaction(rpcClient, tx)
+            ).then(
+                async (signature) => {
+                    console.log(await getTransactionLogs(rpcClient, signature));
+                    assert.fail("Expected failure of update of AmmsConfig creator fee share limit above maximum");
+                },
+                (_error) => {}
+            ));
+        })
+
+        /// Screening program update
+
+        it("Update of AmmsConfig screening program by authority should fail", async () => {
+            const input: UpdateAmmsConfigScreeningProgramInput = {
+                headAuthority: ammsConfigsManagerAuthority,
+                ammsConfigsManager: ammsConfigsManagerAddress[0],
+                ammsConfig: ammsConfigAddress[0],
+                screeningProgram: SYSTEM_PROGRAM_ADDRESS
+            };
+
+            const ix = getUpdateAmmsConfigScreeningProgramInstruction(input);
+
+            await (pipe(
+                await createTransaction(rpcClient, owner, [ix]),
+                (tx) => signAndSendTransaction(rpcClient, tx)
+            ).then(
+                async (signature) => {
+                    console.log(await getTransactionLogs(rpcClient, signature));
+                    assert.fail("Expected failure of update of AmmsConfig screening program by authority");
+                },
+                (_error) => {}
+            ));
+        })
+
+        it("Update of AmmsConfig screening program to a non-executable account should fail", async () => {
+            const input: UpdateAmmsConfigScreeningProgramInput = {
+                headAuthority: headAuthority,
+                ammsConfigsManager: ammsConfigsManagerAddress[0],
+                ammsConfig: ammsConfigAddress[0],
+                screeningProgram: feeAuthority.address
+            };
+
+            const ix = getUpdateAmmsConfigScreeningProgramInstruction(input);
+
+            await (pipe(
+                await createTransaction(rpcClient, owner, [ix]),
+                (tx) => signAndSendTransaction(rpcClient, tx)
+            ).then(
+                async (signature) => {
+                    console.log(await getTransactionLogs(rpcClient, signature));
+                    assert.fail("Expected failure of update of AmmsConfig screening program to a non-executable account");
+                },
+                (_error) => {}
+            ));
+        })
+
+        /// Mint equivalences update
+
+        it("Unauthorized attempt to update AmmsConfig mint equivalence should fail", async () => {
+            const mintEquivalencesAddress = await getProgramDerivedAddress({
+                programAddress: program.CPMM_PROGRAM_ADDRESS,
+                seeds: ["mint_equivalences", getAddressEncoder().encode(ammsConfigAddress[0])]
+            });
+            const input: UpdateAmmsConfigMintEquivalenceInput = {
+                authority: user,
+                ammsConfigsManager: ammsConfigsManagerAddress[0],
+                ammsConfig: ammsConfigAddress[0],
+                mintEquivalences: mintEquivalencesAddress[0],
+                mint: feeAuthority.address,
+                group: 1
+            };
+
+            const ix = getUpdateAmmsConfigMintEquivalenceInstruction(input);
+
+            await (pipe(
+                await createTransaction(rpcClient, owner, [ix]),
+                (tx) => signAndSendTransaction(rpcClient, tx)
+            ).then(
+                async (signature) => {
+                    console.log(await getTransactionLogs(rpcClient, signature));
+                    assert.fail("Expected failure of unauthorized update of AmmsConfig mint equivalence");
+                },
+                (_error) => {}
+            ));
+        })
     })
 }
\ No newline at end of file
@@ -1,10 +1,10 @@
 import {
-    Account, generateKeyPairSigner, getAddressEncoder, getProgramDerivedAddress, KeyPairSigner, none,
+    Account, AccountRole, Address, address, generateKeyPairSigner, getAddressEncoder, getProgramDerivedAddress, KeyPairSigner, none,
     pipe, ProgramDerivedAddress, Some, some
 } from "@solana/web3.js";
-import {SYSTEM_PROGRAM_ADDRESS} from "@solana-program/system";
+import {getTransferSolInstruction, SYSTEM_PROGRAM_ADDRESS} from "@solana-program/system";
 import {
-    ASSOCIATED_TOKEN_PROGRAM_ADDRESS, TOKEN_PROGRAM_ADDRESS, fetchMint,
+    ASSOCIATED_TOKEN_PROGRAM_ADDRESS, TOKEN_PROGRAM_ADDRESS, fetchMint, getCreateAssociatedTokenIdempotentInstruction, getMintToInstruction,
     Mint as TokenMint, Token as TokenAccount, fetchToken as fetchTokenAccount
 } from "@solana-program/token";
 import {
@@ -13,24 +13,39 @@ import {
 import {assert} from "chai";
 import {before, describe} from "mocha";
 import {
+    ArbTwoPoolsInput,
+    CloseStaleCpAmmInput,
     CollectFeesFromCpAmmInput,
     fetchAmmsConfig,
-    fetchCpAmm, getCollectFeesFromCpAmmInstruction,
+    fetchCpAmm, getArbTwoPoolsInstruction, getCloseStaleCpAmmInstruction, getCollectFeesFromCpAmmInstruction,
     getInitializeCpAmmInstruction,
+    getInitializeCpAmmWithLpMintInstruction,
     getLaunchCpAmmInstruction,
+    getProvideSplitInstruction,
     getProvideToCpAmmInstruction,
+    getSwapInCpAmmCompactInstruction,
     getSwapInCpAmmInstruction,
+    getSwapInCpAmmStrictInstruction,
+    getSwapInCpAmmV2Instruction,
+    getUpdateAmmsConfigFeeAuthorityInstruction,
     getWithdrawFromCpAmmInstruction,
     InitializeCpAmmInput,
+    InitializeCpAmmWithLpMintInput,
     LaunchCpAmmInput,
+    PoolStatus,
+    ProvideSplitInput,
     ProvideToCpAmmInput,
+    SwapInCpAmmCompactInput,
     SwapInCpAmmInput,
+    SwapDirection,
+    SwapInCpAmmStrictInput,
+    SwapInCpAmmV2Input,
     WithdrawFromCpAmmInput
 } from "../clients/js/src/generated";
 import {
     CpmmTestingEnvironment, createTestUser, createTransaction,
-    getCpAmmPDA, getCpAmmVaultPDA, getTransactionLogs,
-    signAndSendTransaction
+    getAmmsConfigsManagerPDA, getCpAmmPDA, getCpAmmVaultPDA, getMockVaultAuthorityPDA, getMockVaultLiquidityInstruction, getPoolCreationLimiterPDA,
+    getPoolIndexPDA, getTransactionLogs, MockVaultLiquidityAccounts, signAndSendTransaction
 } from "./helpers";
 import {
     getToken22PDA, getTokenPDA,
@@ -258,6 +273,9 @@ export const cpAmmTests = (cpmmTestingEnvironment: CpmmTestingEnvironment, ammsC
             const input: InitializeCpAmmInput = {
                 signer: unfundedUser,
                 ammsConfig: ammsConfigAddress[0],
+                ammsConfigsManager: (await getAmmsConfigsManagerPDA())[0],
+                poolCreationLimiter: (await getPoolCreationLimiterPDA(unfundedUser.address))[0],
+                poolIndex: (await getPoolIndexPDA(ammsConfigAddress[0], BigInt(0)))[0],
                 baseMint: TEST_MINTS.validTokenMint1.address,
                 cpAmm: TEST_CP_AMMS.cpAmm1[0],
                 feeAuthority: headAuthority.address,
@@ -292,6 +310,9 @@ export const cpAmmTests = (cpmmTestingEnvironment: CpmmTestingEnvironment, ammsC
             const input: InitializeCpAmmInput = {
                 signer: user,
                 ammsConfig: ammsConfigAddress[0],
+                ammsConfigsManager: (await getAmmsConfigsManagerPDA())[0],
+                poolCreationLimiter: (await getPoolCreationLimiterPDA(user.address))[0],
+                poolIndex: (await getPoolIndexPDA(ammsConfigAddress[0], BigInt(0)))[0],
                 baseMint: TEST_MINTS.validTokenMint1.address,
                 cpAmm: TEST_CP_AMMS.cpAmm1[0],
                 feeAuthority: headAuthority.address,
@@ -325,6 +346,9 @@ export const cpAmmTests = (cpmmTestingEnvironment: CpmmTestingEnvironment, ammsC
 
             const input: InitializeCpAmmInput = {
                 ammsConfig: ammsConfigAddress[0],
+                ammsConfigsManager: (await getAmmsConfigsManagerPDA())[0],
+                poolCreationLimiter: (await getPoolCreationLimiterPDA(user.address))[0],
+                poolIndex: (await getPoolIndexPDA(ammsConfigAddress[0], BigInt(0)))[0],
                 baseMint: TEST_MINTS.validTokenMint1.address,
                 cpAmm: TEST_CP_AMMS.cpAmm1[0],
                 feeAuthority: user.address,
@@ -359,6 +383,9 @@ export const cpAmmTests = (cpmmTestingEnvironment: CpmmTestingEnvironment, ammsC
             const malwareAmmsConfigAddress = TEST_MINTS.validTokenMint2.address;
             const input: InitializeCpAmmInput = {
                 ammsConfig: malwareAmmsConfigAddress,
+                ammsConfigsManager: (await getAmmsConfigsManagerPDA())[0],
+                poolCreationLimiter: (await getPoolCreationLimiterPDA(user.address))[0],
+                poolIndex: (await getPoolIndexPDA(malwareAmmsConfigAddress, BigInt(0)))[0],
                 baseMint: TEST_MINTS.validTokenMint1.address,
                 cpAmm: TEST_CP_AMMS.cpAmm1[0],
                 feeAuthority: headAuthority.address,
@@ -394,6 +421,9 @@ export const cpAmmTests = (cpmmTestingEnvironment: CpmmTestingEnvironment, ammsC
 
             const input: InitializeCpAmmInput = {
                 ammsConfig: ammsConfigAddress[0],
+                ammsConfigsManager: (await getAmmsConfigsManagerPDA())[0],
+                poolCreationLimiter: (await getPoolCreationLimiterPDA(user.address))[0],
+                poolIndex: (await getPoolIndexPDA(ammsConfigAddress[0], BigInt(0)))[0],
                 baseMint: TEST_MINTS.validTokenMint1.address,
                 cpAmm: TEST_CP_AMMS.cpAmm1[0],
                 feeAuthority: headAuthority.address,
@@ -424,6 +454,45 @@ export const cpAmmTests = (cpmmTestingEnvironment: CpmmTestingEnvironment, ammsC
             ));
         })
 
+        it("Initialization CpAmm with pre-existing LP mint of third-party authority should fail", async () => {
+            const lpMint = await createTokenMint(rpcClient, owner, 5);
+            const [cpAmm] = await getCpAmmPDA(lpMint.address);
+
+            const input: InitializeCpAmmWithLpMintInput = {
+                ammsConfig: ammsConfigAddress[0],
+                ammsConfigsManager: (await getAmmsConfigsManagerPDA())[0],
+                poolCreationLimiter: (await getPoolCreationLimiterPDA(user.address))[0],
+                poolIndex: (await getPoolIndexPDA(ammsConfigAddress[0], BigInt(0)))[0],
+                baseMint: TEST_MINTS.validTokenMint1.address,
+                cpAmm,
+                feeAuthority: headAuthority.address,
+                lpMint: lpMint.address,
+                quoteMint: TEST_MINTS.validTokenMint2.address,
+                cpAmmBaseVault: (await getCpAmmVaultPDA(cpAmm, TEST_MINTS.validTokenMint1.address))[0],
+                cpAmmLockedLpVault: (await getCpAmmVaultPDA(cpAmm, lpMint.address))[0],
+                cpAmmQuoteVault: (await getCpAmmVaultPDA(cpAmm, TEST_MINTS.validTokenMint2.address))[0],
+                rent,
+                signer: user,
+                systemProgram: SYSTEM_PROGRAM_ADDRESS,
+                lpTokenProgram: TOKEN_PROGRAM_ADDRESS,
+                baseTokenProgram: TOKEN_PROGRAM_ADDRESS,
+                quoteTokenProgram: TOKEN_PROGRAM_ADDRESS
+            }
+
+            const ix = getInitializeCpAmmWithLpMintInstruction(input);
+
+            await (pipe(
+                await createTransaction(rpcClient, user, [ix]),
+                (tx) => signAndSendTransaction(rpcClient, tx)
+            ).then(
+                async (signature) => {
+                    console.log(await getTransactionLogs(rpcClient, signature));
+                    assert.fail("Expected failure of CpAmm initialization with pre-existing LP mint of third-party authority");
+                },
+                (_error) => {}
+            ));
+        })
+
         it("Initialization CpAmm with invalid token account as vault should fail", async () => {
             const invalidPdaTokenAccount = await getProgramDerivedAddress({
                 programAddress: program.CPMM_PROGRAM_ADDRESS,
@@ -432,6 +501,9 @@ export const cpAmmTests = (cpmmTestingEnvironment: CpmmTestingEnvironment, ammsC
 
             const input: InitializeCpAmmInput = {
                 ammsConfig: ammsConfigAddress[0],
+                ammsConfigsManager: (await getAmmsConfigsManagerPDA())[0],
+                poolCreationLimiter: (await getPoolCreationLimiterPDA(user.address))[0],
+                poolIndex: (await getPoolIndexPDA(ammsConfigAddress[0], BigInt(0)))[0],
                 baseMint: TEST_MINTS.validTokenMint1.address,
                 cpAmm: TEST_CP_AMMS.cpAmm1[0],
                 feeAuthority: headAuthority.address,
@@ -466,6 +538,9 @@ export const cpAmmTests = (cpmmTestingEnvironment: CpmmTestingEnvironment, ammsC
             const [baseVault] = await getCpAmmVaultPDA(TEST_CP_AMMS.cpAmm1[0], TEST_MINTS.freezeAuthorityTokenMint.address);
             const input: InitializeCpAmmInput = {
                 ammsConfig: ammsConfigAddress[0],
+                ammsConfigsManager: (await getAmmsConfigsManagerPDA())[0],
+                poolCreationLimiter: (await getPoolCreationLimiterPDA(user.address))[0],
+                poolIndex: (await getPoolIndexPDA(ammsConfigAddress[0], BigInt(0)))[0],
                 baseMint: TEST_MINTS.freezeAuthorityTokenMint.address,
                 cpAmm: TEST_CP_AMMS.cpAmm1[0],
                 feeAuthority: headAuthority.address,
@@ -500,6 +575,9 @@ export const cpAmmTests = (cpmmTestingEnvironment: CpmmTestingEnvironment, ammsC
             const [baseVault] = await getCpAmmVaultPDA(TEST_CP_AMMS.cpAmm1[0], TEST_MINTS.permanentDelegateToken2022Mint.address);
             const input: InitializeCpAmmInput = {
                 ammsConfig: ammsConfigAddress[0],
+                ammsConfigsManager: (await getAmmsConfigsManagerPDA())[0],
+                poolCreationLimiter: (await getPoolCreationLimiterPDA(user.address))[0],
+                poolIndex: (await getPoolIndexPDA(ammsConfigAddress[0], BigInt(0)))[0],
                 baseMint: TEST_MINTS.permanentDelegateToken2022Mint.address,
                 cpAmm: TEST_CP_AMMS.cpAmm1[0],
                 feeAuthority: headAuthority.address,
@@ -536,6 +614,9 @@ export const cpAmmTests = (cpmmTestingEnvironment: CpmmTestingEnvironment, ammsC
             const input: InitializeCpAmmInput = {
                 signer: user,
                 ammsConfig: ammsConfigAddress[0],
+                ammsConfigsManager: (await getAmmsConfigsManagerPDA())[0],
+                poolCreationLimiter: (await getPoolCreationLimiterPDA(user.address))[0],
+                poolIndex: (await getPoolIndexPDA(ammsConfigAddress[0], BigInt(0)))[0],
                 baseMint: TEST_MINTS.validTokenMint1.address,
                 cpAmm: TEST_CP_AMMS.cpAmm1[0],
                 feeAuthority: headAuthority.address,
@@ -577,6 +658,7 @@ export const cpAmmTests = (cpmmTestingEnvironment: CpmmTestingEnvironment, ammsC
 
             assert.deepStrictEqual(lpMintAccount.data.mintAuthority, some(cpAmmAccount.address), "LP mint authority is incorrect");
             assert.deepStrictEqual(lpMintAccount.data.freezeAuthority, none(), "LP mint freeze authority should be none");
+            assert.strictEqual(lpMintAccount.data.decimals, 4, "LP mint decimals should be the floored average of the mints decimals");
 
             assert.strictEqual(cpAmmAccount.data.creator, user.address,  "Creator address mismatch");
             assert.strictEqual(cpAmmAccount.data.ammsConfig, ammsConfigAddress[0],  "AMMs config address mismatch");
@@ -588,8 +670,7 @@ export const cpAmmTests = (cpmmTestingEnvironment: CpmmTestingEnvironment, ammsC
             assert.strictEqual(cpAmmAccount.data.quoteVault, TEST_CP_AMMS.quoteVault1[0], "Quote vault address mismatch");
             assert.strictEqual(cpAmmAccount.data.lockedLpVault, TEST_CP_AMMS.lpVault1[0], "LP vault address mismatch");
 
-            assert.strictEqual(cpAmmAccount.data.isInitialized, true,  "CpAmm should be initialized");
-            assert.strictEqual(cpAmmAccount.data.isLaunched, false,  "CpAmm shouldn't be launched");
+            assert.strictEqual(cpAmmAccount.data.status, PoolStatus.Initialized, "CpAmm should be initialized");
 
             assert.strictEqual(cpAmmAccount.data.initialLockedLiquidity, BigInt(0), "Initial locked liquidity should be 0");
             assert.strictEqual(cpAmmAccount.data.lpTokensSupply, BigInt(0), "LP token supply should be 0");
@@ -611,6 +692,9 @@ export const cpAmmTests = (cpmmTestingEnvironment: CpmmTestingEnvironment, ammsC
             const input: InitializeCpAmmInput = {
                 signer: user,
                 ammsConfig: ammsConfigAddress[0],
+                ammsConfigsManager: (await getAmmsConfigsManagerPDA())[0],
+                poolCreationLimiter: (await getPoolCreationLimiterPDA(user.address))[0],
+                poolIndex: (await getPoolIndexPDA(ammsConfigAddress[0], BigInt(0)))[0],
                 baseMint: TEST_MINTS.validTokenMint1.address,
                 cpAmm: TEST_CP_AMMS.cpAmm1[0],
                 feeAuthority: headAuthority.address,
@@ -640,12 +724,45 @@ export const cpAmmTests = (cpmmTestingEnvironment: CpmmTestingEnvironment, ammsC
             ));
         })
 
+        it("Close of freshly initialized CpAmm should fail", async () => {
+            const input: CloseStaleCpAmmInput = {
+                closer: owner,
+                creator: user.address,
+                ammsConfig: ammsConfigAddress[0],
+                cpAmm: TEST_CP_AMMS.cpAmm1[0],
+                cpAmmBaseVault: TEST_CP_AMMS.baseVault1[0],
+                cpAmmQuoteVault: TEST_CP_AMMS.quoteVault1[0],
+                cpAmmLockedLpVault: TEST_CP_AMMS.lpVault1[0],
+                baseTokenProgram: TOKEN_PROGRAM_ADDRESS,
+                quoteTokenProgram: TOKEN_2022_PROGRAM_ADDRESS,
+                lpTokenProgram: TOKEN_PROGRAM_ADDRESS,
+                baseMint: TEST_MINTS.validTokenMint1.address,
+                quoteMint: TEST_MINTS.validToken22Mint1.address
+            }
+
+            const ix = getCloseStaleCpAmmInstruction(input);
+
+            await (pipe(
+                await createTransaction(rpcClient, owner, [ix]),
+                (tx) => signAndSendTransaction(rpcClient, tx)
+            ).then(
+                async (signature) => {
+                    console.log(await getTransactionLogs(rpcClient, signature));
+                    assert.fail("Expected failure of closing a freshly initialized CpAmm");
+                },
+                (_error) => {}
+            ));
+        })
+
         it("Initialization CpAmm with two token mints", async () => {
             const feeAuthorityBalanceBefore = await rpcClient.rpc.getBalance(headAuthority.address).send();
 
             const input: InitializeCpAmmInput = {
                 signer: user,
                 ammsConfig: ammsConfigAddress[0],
+                ammsConfigsManager: (await getAmmsConfigsManagerPDA())[0],
+                poolCreationLimiter: (await getPoolCreationLimiterPDA(user.address))[0],
+                poolIndex: (await getPoolIndexPDA(ammsConfigAddress[0], BigInt(0)))[0],
                 baseMint: TEST_MINTS.validTokenMint2.address,
                 cpAmm: TEST_CP_AMMS.cpAmm2[0],
                 feeAuthority: headAuthority.address,
@@ -698,8 +815,7 @@ export const cpAmmTests = (cpmmTestingEnvironment: CpmmTestingEnvironment, ammsC
             assert.strictEqual(cpAmmAccount.data.quoteVault, TEST_CP_AMMS.quoteVault2[0], "Quote vault address mismatch");
             assert.strictEqual(cpAmmAccount.data.lockedLpVault, TEST_CP_AMMS.lpVault2[0], "LP vault address mismatch");
 
-            assert.strictEqual(cpAmmAccount.data.isInitialized, true,  "CpAmm should be initialized");
-            assert.strictEqual(cpAmmAccount.data.isLaunched, false,  "CpAmm shouldn't be launched");
+            assert.strictEqual(cpAmmAccount.data.status, PoolStatus.Initialized, "CpAmm should be initialized");
 
             assert.strictEqual(cpAmmAccount.data.initialLockedLiquidity, BigInt(0), "Initial locked liquidity should be 0");
             assert.strictEqual(cpAmmAccount.data.lpTokensSupply, BigInt(0), "LP token supply should be 0");
@@ -723,6 +839,9 @@ export const cpAmmTests = (cpmmTestingEnvironment: CpmmTestingEnvironment, ammsC
             const input: InitializeCpAmmInput = {
                 signer: user,
                 ammsConfig: ammsConfigAddress[0],
+                ammsConfigsManager: (await getAmmsConfigsManagerPDA())[0],
+                poolCreationLimiter: (await getPoolCreationLimiterPDA(user.address))[0],
+                poolIndex: (await getPoolIndexPDA(ammsConfigAddress[0], BigInt(0)))[0],
                 baseMint: TEST_MINTS.validTokenMint2.address,
                 cpAmm: TEST_CP_AMMS.cpAmm3[0],
                 feeAuthority: headAuthority.address,
@@ -774,8 +893,7 @@ export const cpAmmTests = (cpmmTestingEnvironment: CpmmTestingEnvironment, ammsC
             assert.strictEqual(cpAmmAccount.data.quoteVault, TEST_CP_AMMS.quoteVault3[0], "Quote vault address mismatch");
             assert.strictEqual(cpAmmAccount.data.lockedLpVault, TEST_CP_AMMS.lpVault3[0], "LP vault address mismatch");
 
-            assert.strictEqual(cpAmmAccount.data.isInitialized, true,  "CpAmm should be initialized");
-            assert.strictEqual(cpAmmAccount.data.isLaunched, false,  "CpAmm shouldn't be launched");
+            assert.strictEqual(cpAmmAccount.data.status, PoolStatus.Initialized, "CpAmm should be initialized");
 
             assert.strictEqual(cpAmmAccount.data.initialLockedLiquidity, BigInt(0), "Initial locked liquidity should be 0");
             assert.strictEqual(cpAmmAccount.data.lpTokensSupply, BigInt(0), "LP token supply should be 0");
@@ -1021,8 +1139,7 @@ export const cpAmmTests = (cpmmTestingEnvironment: CpmmTestingEnvironment, ammsC
             assert.strictEqual(cpAmmAccountBefore.data.quoteVaultBump[0], cpAmmAccountAfter.data.quoteVaultBump[0], "Quote vault bump value should remain unchanged");
             assert.strictEqual(cpAmmAccountBefore.data.lockedLpVaultBump[0], cpAmmAccountAfter.data.lockedLpVaultBump[0], "Locked LP vault bump value should remain unchanged");
 
-            assert.strictEqual(cpAmmAccountAfter.data.isInitialized, true,  "CpAmm should be initialized");
-            assert.strictEqual(cpAmmAccountAfter.data.isLaunched, true,  "CpAmm should be launched");
+            assert.strictEqual(cpAmmAccountAfter.data.status, PoolStatus.Launched, "CpAmm should be launched");
 
             assert.strictEqual(cpAmmAccountAfter.data.initialLockedLiquidity, initialLockedLiquidity, `Initial locked liquidity does not match expected value`);
             assert.strictEqual(cpAmmAccountAfter.data.lpTokensSupply, totalLiquidity, `LP token supply does not match expected value`);
@@ -1215,8 +1332,7 @@ export const cpAmmTests = (cpmmTestingEnvironment: CpmmTestingEnvironment, ammsC
             assert.strictEqual(cpAmmAccountBefore.data.quoteVaultBump[0], cpAmmAccountAfter.data.quoteVaultBump[0], "Quote vault bump value should remain unchanged");
             assert.strictEqual(cpAmmAccountBefore.data.lockedLpVaultBump[0], cpAmmAccountAfter.data.lockedLpVaultBump[0], "Locked LP vault bump value should remain unchanged");
 
-            assert.strictEqual(cpAmmAccountAfter.data.isInitialized, true,  "CpAmm should be initialized");
-            assert.strictEqual(cpAmmAccountAfter.data.isLaunched, true,  "CpAmm should be launched");
+            assert.strictEqual(cpAmmAccountAfter.data.status, PoolStatus.Launched, "CpAmm should be launched");
 
             assert.strictEqual(cpAmmAccountAfter.data.initialLockedLiquidity, initialLockedLiquidity, `Initial locked liquidity does not match expected value`);
             assert.strictEqual(cpAmmAccountAfter.data.lpTokensSupply, totalLiquidity, `LP token supply does not match expected value`);
@@ -1321,8 +1437,7 @@ export const cpAmmTests = (cpmmTestingEnvironment: CpmmTestingEnvironment, ammsC
             assert.strictEqual(cpAmmAccountBefore.data.quoteVaultBump[0], cpAmmAccountAfter.data.quoteVaultBump[0], "Quote vault bump value should remain unchanged");
             assert.strictEqual(cpAmmAccountBefore.data.lockedLpVaultBump[0], cpAmmAccountAfter.data.lockedLpVaultBump[0], "Locked LP vault bump value should remain unchanged");
 
-            assert.strictEqual(cpAmmAccountAfter.data.isInitialized, true,  "CpAmm should be initialized");
-            assert.strictEqual(cpAmmAccountAfter.data.isLaunched, true,  "CpAmm should be launched");
+            assert.strictEqual(cpAmmAccountAfter.data.status, PoolStatus.Launched, "CpAmm should be launched");
 
             assert.strictEqual(cpAmmAccountAfter.data.initialLockedLiquidity, initialLockedLiquidity, `Initial locked liquidity does not match expected value`);
             assert.strictEqual(cpAmmAccountAfter.data.lpTokensSupply, totalLiquidity, `LP token supply does not match expected value`);
@@ -1468,8 +1583,7 @@ export const cpAmmTests = (cpmmTestingEnvironment: CpmmTestingEnvironment, ammsC
             assert.strictEqual(cpAmmAccountBefore.data.quoteVaultBump[0], cpAmmAccountAfter.data.quoteVaultBump[0], "Quote vault bump value should remain unchanged");
             assert.strictEqual(cpAmmAccountBefore.data.lockedLpVaultBump[0], cpAmmAccountAfter.data.lockedLpVaultBump[0], "Locked LP vault bump value should remain unchanged");
 
-            assert.strictEqual(cpAmmAccountAfter.data.isInitialized, true,  "CpAmm should be initialized");
-            assert.strictEqual(cpAmmAccountAfter.data.isLaunched, true,  "CpAmm should be launched");
+            assert.strictEqual(cpAmmAccountAfter.data.status, PoolStatus.Launched, "CpAmm should be launched");
 
             assert.strictEqual(cpAmmAccountBefore.data.initialLockedLiquidity, cpAmmAccountAfter.data.initialLockedLiquidity, `Initial locked liquidity should remain unchanged`);
             assert.strictEqual(cpAmmAccountAfter.data.lpTokensSupply - cpAmmAccountBefore.data.lpTokensSupply, providedLiquidity, `LP token supply does not match expected value`);
@@ -1575,8 +1689,7 @@ export const cpAmmTests = (cpmmTestingEnvironment: CpmmTestingEnvironment, ammsC
             assert.strictEqual(cpAmmAccountBefore.data.quoteVaultBump[0], cpAmmAccountAfter.data.quoteVaultBump[0], "Quote vault bump value should remain unchanged");
             assert.strictEqual(cpAmmAccountBefore.data.lockedLpVaultBump[0], cpAmmAccountAfter.data.lockedLpVaultBump[0], "Locked LP vault bump value should remain unchanged");
 
-            assert.strictEqual(cpAmmAccountAfter.data.isInitialized, true,  "CpAmm should be initialized");
-            assert.strictEqual(cpAmmAccountAfter.data.isLaunched, true,  "CpAmm should be launched");
+            assert.strictEqual(cpAmmAccountAfter.data.status, PoolStatus.Launched, "CpAmm should be launched");
 
             assert.strictEqual(cpAmmAccountBefore.data.initialLockedLiquidity, cpAmmAccountAfter.data.initialLockedLiquidity, `Initial locked liquidity should remain unchanged`);
             assert.strictEqual(cpAmmAccountAfter.data.lpTokensSupply - cpAmmAccountBefore.data.lpTokensSupply, providedLiquidity, `LP token supply does not match expected value`);
@@ -1588,6 +1701,114 @@ export const cpAmmTests = (cpmmTestingEnvironment: CpmmTestingEnvironment, ammsC
 
         })
 
+        it("Split provide to the same CpAmm twice should fail", async () => {
+            const cpAmmAccount = await fetchCpAmm(rpcClient.rpc, TEST_CP_AMMS.cpAmm2[0]);
+            const [baseMint, quoteMint] = await Promise.all([
+                fetchMint(rpcClient.rpc, cpAmmAccount.data.baseMint),
+                fetchMint(rpcClient.rpc, cpAmmAccount.data.quoteMint)
+            ]);
+
+            const input: ProvideSplitInput = {
+                signer: generalUser,
+                baseMint: cpAmmAccount.data.baseMint,
+                quoteMint: cpAmmAccount.data.quoteMint,
+                signerBaseAccount: GENERAL_USER_TOKEN_ACCOUNTS.validToken2.address,
+                signerQuoteAccount: GENERAL_USER_TOKEN_ACCOUNTS.validToken3.address,
+                lpTokenProgram: TOKEN_PROGRAM_ADDRESS,
+                baseTokenProgram: baseMint.programAddress,
+                quoteTokenProgram: quoteMint.programAddress,
+                baseLiquidity: BigInt(480000),
+                quoteLiquidity: BigInt(3_000_000),
+                weights: [1, 1]
+            };
+            const poolAccounts = [
+                {address: cpAmmAccount.data.ammsConfig, role: AccountRole.READONLY},
+                {address: cpAmmAccount.address, role: AccountRole.WRITABLE},
+                {address: cpAmmAccount.data.lpMint, role: AccountRole.WRITABLE},
+                {address: cpAmmAccount.data.baseVault, role: AccountRole.WRITABLE},
+                {address: cpAmmAccount.data.quoteVault, role: AccountRole.WRITABLE},
+                {address: GENERAL_USER_TOKEN_ACCOUNTS.lpToken2[0], role: AccountRole.WRITABLE},
+            ];
+
+            const ix = getProvideSplitInstruction(input);
+            const splitIx = {...ix, accounts: [...ix.accounts, ...poolAccounts, ...poolAccounts]};
+
+            await (pipe(
+                await createTransaction(rpcClient, owner, [splitIx]),
+                (tx) => signAndSendTransaction(rpcClient, tx)
+            ).then(
+                async (signature) => {
+                    console.log(await getTransactionLogs(rpcClient, signature));
+                    assert.fail("Expected failure of split provide to the same CpAmm twice");
+                },
+                (_error) => {}
+            ));
+        })
+
+        it("Provide and withdraw liquidity through a vault program PDA", async () => {
+            const cpAmmAccount = await fetchCpAmm(rpcClient.rpc, TEST_CP_AMMS.cpAmm2[0]);
+            const [vaultAuthority] = await getMockVaultAuthorityPDA();
+            const [[vaultBaseAccount], [vaultQuoteAccount], [vaultLpAccount]] = await Promise.all([
+                getTokenPDA(cpAmmAccount.data.baseMint, vaultAuthority),
+                getTokenPDA(cpAmmAccount.data.quoteMint, vaultAuthority),
+                getTokenPDA(cpAmmAccount.data.lpMint, vaultAuthority)
+            ]);
+
+            // The vault authority pays for its LP token account, so it must hold lamports
+            await pipe(
+                await createTransaction(rpcClient, user, [
+                    getTransferSolInstruction({source: user, destination: vaultAuthority, amount: BigInt(100_000_000)}),
+                    getCreateAssociatedTokenIdempotentInstruction({ata: vaultBaseAccount, mint: cpAmmAccount.data.baseMint, owner: vaultAuthority, payer: user}),
+                    getCreateAssociatedTokenIdempotentInstruction({ata: vaultQuoteAccount, mint: cpAmmAccount.data.quoteMint, owner: vaultAuthority, payer: user}),
+                    getMintToInstruction({mint: cpAmmAccount.data.baseMint, token: vaultBaseAccount, amount: BigInt(480000), mintAuthority: user}),
+                    getMintToInstruction({mint: cpAmmAccount.data.quoteMint, token: vaultQuoteAccount, amount: BigInt(3_000_000), mintAuthority: user})
+                ]),
+                (tx) => signAndSendTransaction(rpcClient, tx)
+            );
+
+            const accounts: MockVaultLiquidityAccounts = {
+                vaultAuthority,
+                baseMint: cpAmmAccount.data.baseMint,
+                quoteMint: cpAmmAccount.data.quoteMint,
+                lpMint: cpAmmAccount.data.lpMint,
+                vaultBaseAccount,
+                vaultQuoteAccount,
+                vaultLpAccount,
+                ammsConfig: cpAmmAccount.data.ammsConfig,
+                cpAmm: cpAmmAccount.address,
+                cpAmmBaseVault: cpAmmAccount.data.baseVault,
+                cpAmmQuoteVault: cpAmmAccount.data.quoteVault,
+                associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ADDRESS,
+                lpTokenProgram: TOKEN_PROGRAM_ADDRESS,
+                baseTokenProgram: TOKEN_PROGRAM_ADDRESS,
+                quoteTokenProgram: TOKEN_PROGRAM_ADDRESS,
+                systemProgram: SYSTEM_PROGRAM_ADDRESS
+            };
+
+            await pipe(
+                await createTransaction(rpcClient, generalUser, [getMockVaultLiquidityInstruction(accounts, "provide", [BigInt(480000), BigInt(3_000_000)])]),
+                (tx) => signAndSendTransaction(rpcClient, tx)
+            );
+
+            const vaultLpAccountAfterProvide = await fetchTokenAccount(rpcClient.rpc, vaultLpAccount);
+            assert.ok(vaultLpAccountAfterProvide.data.amount > 0, "Vault should receive LP tokens");
+            assert.strictEqual(vaultLpAccountAfterProvide.data.owner, vaultAuthority, "Vault LP account owner mismatch");
+
+            await pipe(
+                await createTransaction(rpcClient, generalUser, [getMockVaultLiquidityInstruction(accounts, "withdraw", [vaultLpAccountAfterProvide.data.amount])]),
+                (tx) => signAndSendTransaction(rpcClient, tx)
+            );
+
+            const [vaultLpAccountAfterWithdraw, vaultBaseAccountAfterWithdraw, vaultQuoteAccountAfterWithdraw] = await Promise.all([
+                fetchTokenAccount(rpcClient.rpc, vaultLpAccount),
+                fetchTokenAccount(rpcClient.rpc, vaultBaseAccount),
+                fetchTokenAccount(rpcClient.rpc, vaultQuoteAccount)
+            ]);
+            assert.strictEqual(vaultLpAccountAfterWithdraw.data.amount, BigInt(0), "Vault LP tokens should be burned");
+            assert.ok(vaultBaseAccountAfterWithdraw.data.amount > 0, "Vault should receive base liquidity back");
+            assert.ok(vaultQuoteAccountAfterWithdraw.data.amount > 0, "Vault should receive quote liquidity back");
+        })
+
         // Swap in CpAmm
 
         it("Swap base to quote in CpAmm with exceeding slippage should fail", async() => {
@@ -1599,7 +1820,7 @@ export const cpAmmTests = (cpmmTestingEnvironment: CpmmTestingEnvironment, ammsC
                 fetchMint(rpcClient.rpc, cpAmmAccountBefore.data.quoteMint),
             ]);
 
-            const swapBaseAmount = BigInt(1_242_344);
+            const swapBaseAmount = BigInt(1_242_346);
             const isInOut = true;
             // Invalid result to check slippage
             const estimatedResult = BigInt(2593581);
@@ -1641,6 +1862,146 @@ export const cpAmmTests = (cpmmTestingEnvironment: CpmmTestingEnvironment, ammsC
             ));
         })
 
+        it("Compact swap base to quote in CpAmm with swapped vaults should fail", async() => {
+            const [cpAmmAccountBefore] = await Promise.all([
+                fetchCpAmm(rpcClient.rpc, TEST_CP_AMMS.cpAmm2[0])
+            ]);
+            const [baseMint, quoteMint] = await Promise.all([
+                fetchMint(rpcClient.rpc, cpAmmAccountBefore.data.baseMint),
+                fetchMint(rpcClient.rpc, cpAmmAccountBefore.data.quoteMint),
+            ]);
+
+            // Vaults are validated against the keys stored in the CpAmm
+            const input: SwapInCpAmmCompactInput = {
+                baseMint: cpAmmAccountBefore.data.baseMint,
+                quoteMint: cpAmmAccountBefore.data.quoteMint,
+                ammsConfig: cpAmmAccountBefore.data.ammsConfig,
+                cpAmm: cpAmmAccountBefore.address,
+                cpAmmBaseVault: cpAmmAccountBefore.data.quoteVault,
+                cpAmmQuoteVault: cpAmmAccountBefore.data.baseVault,
+                signer: generalUser,
+                signerBaseAccount: GENERAL_USER_TOKEN_ACCOUNTS.validToken2.address,
+                signerQuoteAccount: GENERAL_USER_TOKEN_ACCOUNTS.validToken3.address,
+                baseTokenProgram: baseMint.programAddress,
+                quoteTokenProgram: quoteMint.programAddress,
+                swapAmount: BigInt(1_242_344),
+                isInOut: true,
+                estimatedResult: BigInt(2593583),
+                allowedSlippage: BigInt(0)
+            };
+
+            const ix = getSwapInCpAmmCompactInstruction(input);
+
+            await (pipe(
+                await createTransaction(rpcClient, owner, [ix]),
+                (tx) => signAndSendTransaction(rpcClient, tx)
+            ).then(
+                async (signature) => {
+                    console.log(await getTransactionLogs(rpcClient, signature));
+                    assert.fail("Expected failure of compact swap base to quote in CpAmm with swapped vaults");
+                },
+                (_error) => {}
+            ));
+        })
+
+        it("Swap v2 base to quote in CpAmm after the deadline should fail", async() => {
+            const [cpAmmAccountBefore] = await Promise.all([
+                fetchCpAmm(rpcClient.rpc, TEST_CP_AMMS.cpAmm2[0])
+            ]);
+            const [baseMint, quoteMint] = await Promise.all([
+                fetchMint(rpcClient.rpc, cpAmmAccountBefore.data.baseMint),
+                fetchMint(rpcClient.rpc, cpAmmAccountBefore.data.quoteMint),
+            ]);
+
+            const input: SwapInCpAmmV2Input = {
+                baseMint: cpAmmAccountBefore.data.baseMint,
+                quoteMint: cpAmmAccountBefore.data.quoteMint,
+                ammsConfig: cpAmmAccountBefore.data.ammsConfig,
+                cpAmm: cpAmmAccountBefore.address,
+                cpAmmBaseVault: cpAmmAccountBefore.data.baseVault,
+                cpAmmQuoteVault: cpAmmAccountBefore.data.quoteVault,
+                signer: generalUser,
+                signerBaseAccount: GENERAL_USER_TOKEN_ACCOUNTS.validToken2.address,
+                signerQuoteAccount: GENERAL_USER_TOKEN_ACCOUNTS.validToken3.address,
+                associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ADDRESS,
+                systemProgram: SYSTEM_PROGRAM_ADDRESS,
+                baseTokenProgram: baseMint.programAddress,
+                quoteTokenProgram: quoteMint.programAddress,
+                swapAmount: BigInt(1_242_344),
+                direction: SwapDirection.BaseToQuote,
+                estimatedResult: BigInt(2593583),
+                slippageBasisPoints: 100,
+                // Deadline in the past
+                deadline: BigInt(Math.floor(Date.now() / 1000) - 60),
+                minReceived: null
+            };
+
+            const ix = getSwapInCpAmmV2Instruction(input);
+
+            await (pipe(
+                await createTransaction(rpcClient, owner, [ix]),
+                (tx) => signAndSendTransaction(rpcClient, tx)
+            ).then(
+                async (signature) => {
+                    console.log(await getTransactionLogs(rpcClient, signature));
+                    assert.fail("Expected failure of swap v2 base to quote in CpAmm after the deadline");
+                },
+                (_error) => {}
+            ));
+        })
+
+        it("Strict swap base to quote in CpAmm receiving less than min received should fail", async() => {
+            const [cpAmmAccountBefore] = await Promise.all([
+                fetchCpAmm(rpcClient.rpc, TEST_CP_AMMS.cpAmm2[0])
+            ]);
+            const [baseMint, quoteMint] = await Promise.all([
+                fetchMint(rpcClient.rpc, cpAmmAccountBefore.data.baseMint),
+                fetchMint(rpcClient.rpc, cpAmmAccountBefore.data.quoteMint),
+            ]);
+
+            const swapBaseAmount = BigInt(1_242_346);
+            const isInOut = true;
+            // Wide slippage so only the received amount check can fail
+            const estimatedResult = BigInt(2593581);
+            const allowedSlippage = estimatedResult;
+            // More than the pool can deliver
+            const minReceived = BigInt(2) * estimatedResult;
+
+            const input: SwapInCpAmmStrictInput = {
+                baseMint: cpAmmAccountBefore.data.baseMint,
+                quoteMint: cpAmmAccountBefore.data.quoteMint,
+                ammsConfig: cpAmmAccountBefore.data.ammsConfig,
+                cpAmm: cpAmmAccountBefore.address,
+                cpAmmBaseVault: cpAmmAccountBefore.data.baseVault,
+                cpAmmQuoteVault: cpAmmAccountBefore.data.quoteVault,
+                signer: generalUser,
+                signerBaseAccount: GENERAL_USER_TOKEN_ACCOUNTS.validToken2.address,
+                signerQuoteAccount: GENERAL_USER_TOKEN_ACCOUNTS.validToken3.address,
+                associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ADDRESS,
+                systemProgram: SYSTEM_PROGRAM_ADDRESS,
+                baseTokenProgram: baseMint.programAddress,
+                quoteTokenProgram: quoteMint.programAddress,
+                swapAmount: swapBaseAmount,
+                isInOut,
+                estimatedResult,
+                allowedSlippage,
+                minReceived
+            };
+
+            const ix = getSwapInCpAmmStrictInstruction(input);
+
+            await (pipe(
+                await createTransaction(rpcClient, owner, [ix]),
+                (tx) => signAndSendTransaction(rpcClient, tx)
+            ).then(
+                async (signature) => {
+                    console.log(await getTransactionLogs(rpcClient, signature));
+                    assert.fail("Expected failure of strict swap base to quote in CpAmm receiving less than min received");
+                },
+                (_error) => {}
+            ));
+        })
+
         it("Swap base to quote in CpAmm that drains base liquidity should fail", async() => {
             const [cpAmmAccountBefore] = await Promise.all([
                 fetchCpAmm(rpcClient.rpc, TEST_CP_AMMS.cpAmm2[0])
@@ -1704,9 +2065,9 @@ export const cpAmmTests = (cpmmTestingEnvironment: CpmmTestingEnvironment, ammsC
                 rpcClient.rpc.getTokenAccountBalance(cpAmmAccountBefore.data.quoteVault).send()
             ]);
 
-            const swapBaseAmount = BigInt(1_242_344);
-            const protocolFee = swapBaseAmount * BigInt(ammsConfig.data.protocolFeeRateBasisPoints) / BigInt(10_000);
-            const providersFee = swapBaseAmount * BigInt(ammsConfig.data.providersFeeRateBasisPoints) / BigInt(10_000);
+            const swapBaseAmount = BigInt(1_242_346);
+            const protocolFee = (swapBaseAmount * BigInt(ammsConfig.data.protocolFeeRateBasisPoints) + BigInt(9_999)) / BigInt(10_000);
+            const providersFee = (swapBaseAmount * BigInt(ammsConfig.data.providersFeeRateBasisPoints) + BigInt(9_999)) / BigInt(10_000);
 
             //1_180_228
             const swapBaseAmountAfterFees = swapBaseAmount - providersFee - protocolFee;
@@ -1773,8 +2134,7 @@ export const cpAmmTests = (cpmmTestingEnvironment: CpmmTestingEnvironment, ammsC
             assert.strictEqual(cpAmmAccountBefore.data.quoteVaultBump[0], cpAmmAccountAfter.data.quoteVaultBump[0], "Quote vault bump value should remain unchanged");
             assert.strictEqual(cpAmmAccountBefore.data.lockedLpVaultBump[0], cpAmmAccountAfter.data.lockedLpVaultBump[0], "Locked LP vault bump value should remain unchanged");
 
-            assert.strictEqual(cpAmmAccountAfter.data.isInitialized, true,  "CpAmm should be initialized");
-            assert.strictEqual(cpAmmAccountAfter.data.isLaunched, true,  "CpAmm should be launched");
+            assert.strictEqual(cpAmmAccountAfter.data.status, PoolStatus.Launched, "CpAmm should be launched");
 
             assert.strictEqual(cpAmmAccountAfter.data.initialLockedLiquidity, cpAmmAccountBefore.data.initialLockedLiquidity, `Initial locked liquidity should remain unchanged`);
             assert.strictEqual(cpAmmAccountAfter.data.lpTokensSupply, cpAmmAccountBefore.data.lpTokensSupply, `LP token supply should remain unchanged`);
@@ -1802,8 +2162,8 @@ export const cpAmmTests = (cpmmTestingEnvironment: CpmmTestingEnvironment, ammsC
             ]);
 
             const swapQuoteAmount = BigInt(10_000);
-            const protocolFee = swapQuoteAmount * BigInt(ammsConfig.data.protocolFeeRateBasisPoints) / BigInt(10_000);
-            const providersFee = swapQuoteAmount * BigInt(ammsConfig.data.providersFeeRateBasisPoints) / BigInt(10_000);
+            const protocolFee = (swapQuoteAmount * BigInt(ammsConfig.data.protocolFeeRateBasisPoints) + BigInt(9_999)) / BigInt(10_000);
+            const providersFee = (swapQuoteAmount * BigInt(ammsConfig.data.providersFeeRateBasisPoints) + BigInt(9_999)) / BigInt(10_000);
 
             //9_500
             const swapQuoteAmountAfterFees = swapQuoteAmount - providersFee - protocolFee;
@@ -1870,8 +2230,7 @@ export const cpAmmTests = (cpmmTestingEnvironment: CpmmTestingEnvironment, ammsC
             assert.strictEqual(cpAmmAccountBefore.data.quoteVaultBump[0], cpAmmAccountAfter.data.quoteVaultBump[0], "Quote vault bump value should remain unchanged");
             assert.strictEqual(cpAmmAccountBefore.data.lockedLpVaultBump[0], cpAmmAccountAfter.data.lockedLpVaultBump[0], "Locked LP vault bump value should remain unchanged");
 
-            assert.strictEqual(cpAmmAccountAfter.data.isInitialized, true,  "CpAmm should be initialized");
-            assert.strictEqual(cpAmmAccountAfter.data.isLaunched, true,  "CpAmm should be launched");
+            assert.strictEqual(cpAmmAccountAfter.data.status, PoolStatus.Launched, "CpAmm should be launched");
 
             assert.strictEqual(cpAmmAccountAfter.data.initialLockedLiquidity, cpAmmAccountBefore.data.initialLockedLiquidity, `Initial locked liquidity should remain unchanged`);
             assert.strictEqual(cpAmmAccountAfter.data.lpTokensSupply, cpAmmAccountBefore.data.lpTokensSupply, `LP token supply should remain unchanged`);
@@ -1898,7 +2257,7 @@ export const cpAmmTests = (cpmmTestingEnvironment: CpmmTestingEnvironment, ammsC
                 rpcClient.rpc.getTokenAccountBalance(cpAmmAccountBefore.data.quoteVault).send()
             ]);
 
-            const quoteAmountBeforeTransfer = BigInt(1_522_710_696);
+            const quoteAmountBeforeTransfer = BigInt(1_522_710_698);
 
             const transferFee = (quoteMint.data.extensions as Some<Extension[]>).value.find((extension) => extension.__kind == "TransferFeeConfig").olderTransferFee;
             const tokenSwapFee = (quoteAmountBeforeTransfer * BigInt(transferFee.transferFeeBasisPoints) / BigInt(10_000)) < BigInt(transferFee.maximumFee)
@@ -1907,8 +2266,8 @@ export const cpAmmTests = (cpmmTestingEnvironment: CpmmTestingEnvironment, ammsC
 
             const swapQuoteAmount = quoteAmountBeforeTransfer - tokenSwapFee;
 
-            const protocolFee = swapQuoteAmount * BigInt(ammsConfig.data.protocolFeeRateBasisPoints) / BigInt(10_000);
-            const providersFee = swapQuoteAmount  * BigInt(ammsConfig.data.providersFeeRateBasisPoints) / BigInt(10_000);
+            const protocolFee = (swapQuoteAmount * BigInt(ammsConfig.data.protocolFeeRateBasisPoints) + BigInt(9_999)) / BigInt(10_000);
+            const providersFee = (swapQuoteAmount * BigInt(ammsConfig.data.providersFeeRateBasisPoints) + BigInt(9_999)) / BigInt(10_000);
 
             //1_446_565_663
             const swapQuoteAmountAfterFees = swapQuoteAmount - providersFee - protocolFee;
@@ -1975,8 +2334,7 @@ export const cpAmmTests = (cpmmTestingEnvironment: CpmmTestingEnvironment, ammsC
             assert.strictEqual(cpAmmAccountBefore.data.quoteVaultBump[0], cpAmmAccountAfter.data.quoteVaultBump[0], "Quote vault bump value should remain unchanged");
             assert.strictEqual(cpAmmAccountBefore.data.lockedLpVaultBump[0], cpAmmAccountAfter.data.lockedLpVaultBump[0], "Locked LP vault bump value should remain unchanged");
 
-            assert.strictEqual(cpAmmAccountAfter.data.isInitialized, true,  "CpAmm should be initialized");
-            assert.strictEqual(cpAmmAccountAfter.data.isLaunched, true,  "CpAmm should be launched");
+            assert.strictEqual(cpAmmAccountAfter.data.status, PoolStatus.Launched, "CpAmm should be launched");
 
             assert.strictEqual(cpAmmAccountAfter.data.initialLockedLiquidity, cpAmmAccountBefore.data.initialLockedLiquidity, `Initial locked liquidity should remain unchanged`);
             assert.strictEqual(cpAmmAccountAfter.data.lpTokensSupply, cpAmmAccountBefore.data.lpTokensSupply, `LP token supply should remain unchanged`);
@@ -1990,6 +2348,51 @@ export const cpAmmTests = (cpmmTestingEnvironment: CpmmTestingEnvironment, ammsC
 
         // Withdraw from CpAmm
 
+        // Arbitrage between CpAmms
+
+        it("Arbitrage between the same CpAmm should fail", async () => {
+            const cpAmmAccount = await fetchCpAmm(rpcClient.rpc, TEST_CP_AMMS.cpAmm2[0]);
+            const [baseMint, quoteMint] = await Promise.all([
+                fetchMint(rpcClient.rpc, cpAmmAccount.data.baseMint),
+                fetchMint(rpcClient.rpc, cpAmmAccount.data.quoteMint)
+            ]);
+
+            const input: ArbTwoPoolsInput = {
+                signer: generalUser,
+                mintA: cpAmmAccount.data.baseMint,
+                mintB: cpAmmAccount.data.quoteMint,
+                signerAAccount: GENERAL_USER_TOKEN_ACCOUNTS.validToken2.address,
+                signerBAccount: GENERAL_USER_TOKEN_ACCOUNTS.validToken3.address,
+                firstAmmsConfig: cpAmmAccount.data.ammsConfig,
+                firstCpAmm: cpAmmAccount.address,
+                firstCpAmmBaseVault: cpAmmAccount.data.baseVault,
+                firstCpAmmQuoteVault: cpAmmAccount.data.quoteVault,
+                secondAmmsConfig: cpAmmAccount.data.ammsConfig,
+                secondCpAmm: cpAmmAccount.address,
+                secondCpAmmBaseVault: cpAmmAccount.data.baseVault,
+                secondCpAmmQuoteVault: cpAmmAccount.data.quoteVault,
+                associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ADDRESS,
+                aTokenProgram: baseMint.programAddress,
+                bTokenProgram: quoteMint.programAddress,
+                systemProgram: SYSTEM_PROGRAM_ADDRESS,
+                swapAmount: BigInt(1_000_000),
+                minProfit: BigInt(0)
+            };
+
+            const ix = getArbTwoPoolsInstruction(input);
+
+            await (pipe(
+                await createTransaction(rpcClient, owner, [ix]),
+                (tx) => signAndSendTransaction(rpcClient, tx)
+            ).then(
+                async (signature) => {
+                    console.log(await getTransactionLogs(rpcClient, signature));
+                    assert.fail("Expected failure of arbitrage between the same CpAmm");
+                },
+                (_error) => {}
+            ));
+        })
+
         it("Withdraw liquidity from CpAmm with insufficient balance of lp tokens on signer's account should fail", async () => {
             const cpAmmAccountBefore = await fetchCpAmm(rpcClient.rpc, TEST_CP_AMMS.cpAmm1[0]);
             const [baseMint, quoteMint, signerLpBalance] = await Promise.all([
@@ -2114,8 +2517,7 @@ export const cpAmmTests = (cpmmTestingEnvironment: CpmmTestingEnvironment, ammsC
             assert.strictEqual(cpAmmAccountBefore.data.quoteVaultBump[0], cpAmmAccountAfter.data.quoteVaultBump[0], "Quote vault bump value should remain unchanged");
             assert.strictEqual(cpAmmAccountBefore.data.lockedLpVaultBump[0], cpAmmAccountAfter.data.lockedLpVaultBump[0], "Locked LP vault bump value should remain unchanged");
 
-            assert.strictEqual(cpAmmAccountAfter.data.isInitialized, true,  "CpAmm should be initialized");
-            assert.strictEqual(cpAmmAccountAfter.data.isLaunched, true,  "CpAmm should be launched");
+            assert.strictEqual(cpAmmAccountAfter.data.status, PoolStatus.Launched, "CpAmm should be launched");
 
             assert.strictEqual(cpAmmAccountBefore.data.initialLockedLiquidity, cpAmmAccountAfter.data.initialLockedLiquidity, `Initial locked liquidity should remain unchanged`);
             assert.strictEqual(cpAmmAccountBefore.data.lpTokensSupply - cpAmmAccountAfter.data.lpTokensSupply, withdrawLiquidity, `LP token supply does not match expected value`);
@@ -2152,6 +2554,8 @@ export const cpAmmTests = (cpmmTestingEnvironment: CpmmTestingEnvironment, ammsC
                 feeAuthority: invalidFeeAuthority.address,
                 feeAuthorityBaseAccount: invalidFeeAuthorityBaseAccount[0],
                 feeAuthorityQuoteAccount: invalidFeeAuthorityQuoteAccount[0],
+                signerBaseAccount: (await getTokenPDA(cpAmmAccountBefore.data.baseMint, user.address))[0],
+                signerQuoteAccount: (await getToken22PDA(cpAmmAccountBefore.data.quoteMint, user.address))[0],
                 associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ADDRESS,
                 systemProgram: SYSTEM_PROGRAM_ADDRESS,
                 baseTokenProgram: baseMint.programAddress,
@@ -2202,6 +2606,8 @@ export const cpAmmTests = (cpmmTestingEnvironment: CpmmTestingEnvironment, ammsC
                 feeAuthority: ammsConfig.data.feeAuthority,
                 feeAuthorityBaseAccount: FEE_AUTHORITY_TOKEN_ACCOUNTS.validToken2[0],
                 feeAuthorityQuoteAccount: FEE_AUTHORITY_TOKEN_ACCOUNTS.transferFeeToken22[0],
+                signerBaseAccount: (await getTokenPDA(cpAmmAccountBefore.data.baseMint, user.address))[0],
+                signerQuoteAccount: (await getToken22PDA(cpAmmAccountBefore.data.quoteMint, user.address))[0],
                 associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ADDRESS,
                 systemProgram: SYSTEM_PROGRAM_ADDRESS,
                 baseTokenProgram: baseMint.programAddress,
@@ -2246,8 +2652,7 @@ export const cpAmmTests = (cpmmTestingEnvironment: CpmmTestingEnvironment, ammsC
             assert.strictEqual(cpAmmAccountBefore.data.quoteVaultBump[0], cpAmmAccountAfter.data.quoteVaultBump[0], "Quote vault bump value should remain unchanged");
             assert.strictEqual(cpAmmAccountBefore.data.lockedLpVaultBump[0], cpAmmAccountAfter.data.lockedLpVaultBump[0], "Locked LP vault bump value should remain unchanged");
 
-            assert.strictEqual(cpAmmAccountAfter.data.isInitialized, true,  "CpAmm should be initialized");
-            assert.strictEqual(cpAmmAccountAfter.data.isLaunched, true,  "CpAmm should be launched");
+            assert.strictEqual(cpAmmAccountAfter.data.status, PoolStatus.Launched, "CpAmm should be launched");
 
             assert.strictEqual(cpAmmAccountAfter.data.initialLockedLiquidity, cpAmmAccountBefore.data.initialLockedLiquidity, `Initial locked liquidity should remain unchanged`);
             assert.strictEqual(cpAmmAccountAfter.data.lpTokensSupply, cpAmmAccountBefore.data.lpTokensSupply, `LP token supply should remain unchanged`);
@@ -2280,6 +2685,8 @@ export const cpAmmTests = (cpmmTestingEnvironment: CpmmTestingEnvironment, ammsC
                 feeAuthority: ammsConfig.data.feeAuthority,
                 feeAuthorityBaseAccount: FEE_AUTHORITY_TOKEN_ACCOUNTS.validToken2[0],
                 feeAuthorityQuoteAccount: FEE_AUTHORITY_TOKEN_ACCOUNTS.transferFeeToken22[0],
+                signerBaseAccount: (await getTokenPDA(cpAmmAccountBefore.data.baseMint, user.address))[0],
+                signerQuoteAccount: (await getToken22PDA(cpAmmAccountBefore.data.quoteMint, user.address))[0],
                 associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ADDRESS,
                 systemProgram: SYSTEM_PROGRAM_ADDRESS,
                 baseTokenProgram: baseMint.programAddress,
@@ -2299,5 +2706,129 @@ export const cpAmmTests = (cpmmTestingEnvironment: CpmmTestingEnvironment, ammsC
                 (_error) => {}
             ));
         })
+
+        it("Collect fees from CpAmm with PDA of another program as fee authority", async () => {
+            const [cpAmmAccountBefore, [feeAuthorityPDA]] = await Promise.all([
+                fetchCpAmm(rpcClient.rpc, TEST_CP_AMMS.cpAmm2[0]),
+                getProgramDerivedAddress({
+                    programAddress: address("GovER5Lthms3bLBqWub97yVrMmEogzX7xNjdXpPPCVZw"),
+                    seeds: ["native-treasury", getAddressEncoder().encode(cpmmTestingEnvironment.owner.address)]
+                })
+            ]);
+            const [ammsConfigBefore, baseMint, quoteMint, feeAuthorityBaseAccount, feeAuthorityQuoteAccount] = await Promise.all([
+                fetchAmmsConfig(rpcClient.rpc, cpAmmAccountBefore.data.ammsConfig),
+                fetchMint(rpcClient.rpc, cpAmmAccountBefore.data.baseMint),
+                fetchMint(rpcClient.rpc, cpAmmAccountBefore.data.quoteMint),
+                getTokenPDA(cpAmmAccountBefore.data.baseMint, feeAuthorityPDA),
+                getTokenPDA(cpAmmAccountBefore.data.quoteMint, feeAuthorityPDA)
+            ]);
+
+            const setFeeAuthority = async (feeAuthority: Address) => pipe(
+                await createTransaction(rpcClient, owner, [getUpdateAmmsConfigFeeAuthorityInstruction({
+                    authority: headAuthority,
+                    ammsConfigsManager: (await getAmmsConfigsManagerPDA())[0],
+                    ammsConfig: cpAmmAccountBefore.data.ammsConfig,
+                    newFeeAuthority: feeAuthority
+                })]),
+                (tx) => signAndSendTransaction(rpcClient, tx)
+            );
+
+            await setFeeAuthority(feeAuthorityPDA);
+
+            // The PDA never signs, its ATAs are derived and created on-chain at the signer's expense
+            const input: CollectFeesFromCpAmmInput = {
+                baseMint: cpAmmAccountBefore.data.baseMint,
+                quoteMint: cpAmmAccountBefore.data.quoteMint,
+                ammsConfig: cpAmmAccountBefore.data.ammsConfig,
+                cpAmm: cpAmmAccountBefore.address,
+                cpAmmBaseVault: cpAmmAccountBefore.data.baseVault,
+                cpAmmQuoteVault: cpAmmAccountBefore.data.quoteVault,
+                signer: user,
+                feeAuthority: feeAuthorityPDA,
+                feeAuthorityBaseAccount: feeAuthorityBaseAccount[0],
+                feeAuthorityQuoteAccount: feeAuthorityQuoteAccount[0],
+                signerBaseAccount: (await getTokenPDA(cpAmmAccountBefore.data.baseMint, user.address))[0],
+                signerQuoteAccount: (await getTokenPDA(cpAmmAccountBefore.data.quoteMint, user.address))[0],
+                associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ADDRESS,
+                systemProgram: SYSTEM_PROGRAM_ADDRESS,
+                baseTokenProgram: baseMint.programAddress,
+                quoteTokenProgram: quoteMint.programAddress
+            };
+
+            const ix = getCollectFeesFromCpAmmInstruction(input);
+
+            try {
+                await pipe(
+                    await createTransaction(rpcClient, owner, [ix]),
+                    (tx) => signAndSendTransaction(rpcClient, tx)
+                );
+            } finally {
+                await setFeeAuthority(ammsConfigBefore.data.feeAuthority);
+            }
+
+            const [cpAmmAccountAfter, feeAuthorityBaseAccountAfter, feeAuthorityQuoteAccountAfter] = await Promise.all([
+                fetchCpAmm(rpcClient.rpc, cpAmmAccountBefore.address),
+                fetchTokenAccount(rpcClient.rpc, feeAuthorityBaseAccount[0]),
+                fetchTokenAccount(rpcClient.rpc, feeAuthorityQuoteAccount[0])
+            ]);
+
+            assert.strictEqual(feeAuthorityBaseAccountAfter.data.owner, feeAuthorityPDA, "Fee authority base account owner does not match expected value");
+            assert.strictEqual(feeAuthorityQuoteAccountAfter.data.owner, feeAuthorityPDA, "Fee authority quote account owner does not match expected value");
+            assert.strictEqual(feeAuthorityBaseAccountAfter.data.amount, cpAmmAccountBefore.data.protocolBaseFeesToRedeem, "Fee authority base balance does not match expected value");
+            assert.strictEqual(feeAuthorityQuoteAccountAfter.data.amount, cpAmmAccountBefore.data.protocolQuoteFeesToRedeem, "Fee authority quote balance does not match expected value");
+
+            assert.strictEqual(cpAmmAccountAfter.data.protocolBaseFeesToRedeem, BigInt(0), "Protocol base fees do not match expected value");
+            assert.strictEqual(cpAmmAccountAfter.data.protocolQuoteFeesToRedeem, BigInt(0), "Protocol quote fees do not match expected value");
+        })
+
+        it("Create test pool in a non-devnet build should fail", async () => {
+            const lpMint = await generateKeyPairSigner();
+            const getTestMintPDA = async (index: number) => getProgramDerivedAddress({
+                programAddress: program.CPMM_PROGRAM_ADDRESS,
+                seeds: ["test_mint", getAddressEncoder().encode(lpMint.address), new Uint8Array([index])]
+            });
+            const [baseMint, quoteMint, cpAmm] = await Promise.all([
+                getTestMintPDA(0),
+                getTestMintPDA(1),
+                getCpAmmPDA(lpMint.address)
+            ]);
+
+            const ix = program.getCreateTestPoolInstruction({
+                signer: owner,
+                lpMint,
+                baseMint: baseMint[0],
+                quoteMint: quoteMint[0],
+                signerBaseAccount: (await getTokenPDA(baseMint[0], owner.address))[0],
+                signerQuoteAccount: (await getTokenPDA(quoteMint[0], owner.address))[0],
+                signerLpAccount: (await getTokenPDA(lpMint.address, owner.address))[0],
+                ammsConfig: ammsConfigAddress[0],
+                poolIndex: (await getPoolIndexPDA(ammsConfigAddress[0], BigInt(0)))[0],
+                cpAmm: cpAmm[0],
+                cpAmmBaseVault: (await getCpAmmVaultPDA(cpAmm[0], baseMint[0]))[0],
+                cpAmmQuoteVault: (await getCpAmmVaultPDA(cpAmm[0], quoteMint[0]))[0],
+                cpAmmLockedLpVault: (await getCpAmmVaultPDA(cpAmm[0], lpMint.address))[0],
+                systemProgram: SYSTEM_PROGRAM_ADDRESS,
+                lpTokenProgram: TOKEN_PROGRAM_ADDRESS,
+                tokenProgram: TOKEN_PROGRAM_ADDRESS,
+                associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ADDRESS,
+                baseDecimals: 6,
+                quoteDecimals: 9,
+                baseAmount: BigInt(1_000_000_000),
+                quoteAmount: BigInt(1_000_000_000_000),
+                baseLiquidity: BigInt(100_000_000),
+                quoteLiquidity: BigInt(100_000_000_000)
+            });
+
+            await (pipe(
+                await createTransaction(rpcClient, owner, [ix]),
+                (tx) => signAndSendTransaction(rpcClient, tx)
+            ).then(
+                async (signature) => {
+                    console.log(await getTransactionLogs(rpcClient, signature));
+                    assert.fail("Expected failure of test pool creation outside of devnet builds");
+                },
+                (_error) => {}
+            ));
+        })
     })
 }
\ No newline at end of file
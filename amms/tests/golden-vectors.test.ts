@@ -0,0 +1,40 @@
+import {readFileSync} from "fs";
+import {join} from "path";
+import {assert} from "chai";
+import {describe} from "mocha";
+import {CpAmmQuoter} from "../crates/cpmm-math/pkg";
+import {findSwapVectorMismatches, parseSwapVectors} from "../clients/js/src/golden-vectors";
+
+/**
+ * Checks the client quotes against the golden swap vectors produced by the program.
+ * The `wasm` bindings of `cpmm-math` are built by `anchor run build-wasm`.
+ */
+describe("\nGolden vectors tests", () => {
+    const vectors = parseSwapVectors(readFileSync(join(__dirname, "../crates/cpmm-math/golden/swap_vectors.csv"), "utf8"));
+
+    it("Golden vectors should include rejected swaps", async () => {
+        assert.isAbove(vectors.length, 0);
+        assert.isTrue(vectors.some((vector) => vector.expected == null));
+    });
+
+    it("Swap quotes should match every golden vector", async () => {
+        const mismatches = findSwapVectorMismatches(vectors, (vector) => {
+            const quoter = CpAmmQuoter.launched(vector.baseLiquidity, vector.quoteLiquidity);
+            try {
+                const quote = quoter.quoteSwap(vector.swapAmount, vector.providersFeeRateBasisPoints, vector.protocolFeeRateBasisPoints, vector.isInOut);
+                return {
+                    amountOut: quote.amount_out,
+                    providersFeeAmount: quote.providers_fee_amount,
+                    protocolFeeAmount: quote.protocol_fee_amount,
+                    priceImpactBasisPoints: quote.price_impact_basis_points,
+                };
+            } catch {
+                // The quoter throws for swaps the program rejects
+                return null;
+            } finally {
+                quoter.free();
+            }
+        });
+        assert.deepEqual(mismatches, []);
+    });
+});
\ No newline at end of file
@@ -1,4 +1,5 @@
 import {
+    AccountRole,
     address,
     Address,
     airdropFactory,
@@ -224,4 +225,93 @@ export const getCpAmmVaultPDA = async (cpAmm: Address, mint: Address): Promise<P
         programAddress: program.CPMM_PROGRAM_ADDRESS,
         seeds: ["vault", getAddressEncoder().encode(cpAmm), getAddressEncoder().encode(mint)]
     });
+};
+
+/**
+ * Retrieves the PDA tracking pool creations of a creator.
+ * @param {Address} creator - The address of the pools creator.
+ * @returns {Promise<ProgramDerivedAddress>} - The derived address for the pool creation limiter.
+ */
+export const getPoolCreationLimiterPDA = async (creator: Address): Promise<ProgramDerivedAddress> => {
+    return await getProgramDerivedAddress({
+        programAddress: program.CPMM_PROGRAM_ADDRESS,
+        seeds: ["pool_creation_limiter", getAddressEncoder().encode(creator)]
+    });
+};
+
+/**
+ * Retrieves the PDA of a bucket in the pool index of an AMMs config.
+ * @param {Address} ammsConfig - The address of the AMMs config.
+ * @param {bigint} bucket - The position of the bucket in the config's pool index.
+ * @returns {Promise<ProgramDerivedAddress>} - The derived address for the pool index bucket.
+ */
+export const getPoolIndexPDA = async (ammsConfig: Address, bucket: bigint): Promise<ProgramDerivedAddress> => {
+    return await getProgramDerivedAddress({
+        programAddress: program.CPMM_PROGRAM_ADDRESS,
+        seeds: ["pool_index", getAddressEncoder().encode(ammsConfig), getU64Encoder({ endian: Endian.Little }).encode(bucket)]
+    });
+};
+
+/**
+ * Address of the mock vault program providing liquidity through its PDA authority.
+ */
+export const MOCK_VAULT_PROGRAM_ADDRESS = address("9P74TAryPGg1LE5kFgP4PER2jTqULjUir9ophqyxRZmd");
+
+/**
+ * Retrieves the PDA of the mock vault authority owning the vault token accounts.
+ * @returns {Promise<ProgramDerivedAddress>} - The derived address of the mock vault authority.
+ */
+export const getMockVaultAuthorityPDA = async (): Promise<ProgramDerivedAddress> => {
+    return await getProgramDerivedAddress({
+        programAddress: MOCK_VAULT_PROGRAM_ADDRESS,
+        seeds: ["vault_authority"]
+    });
+};
+
+/**
+ * Defines the accounts of the mock vault liquidity instructions, forwarded to the CPMM program.
+ */
+export type MockVaultLiquidityAccounts = {
+    vaultAuthority: Address,
+    baseMint: Address,
+    quoteMint: Address,
+    lpMint: Address,
+    vaultBaseAccount: Address,
+    vaultQuoteAccount: Address,
+    vaultLpAccount: Address,
+    ammsConfig: Address,
+    cpAmm: Address,
+    cpAmmBaseVault: Address,
+    cpAmmQuoteVault: Address,
+    associatedTokenProgram: Address,
+    lpTokenProgram: Address,
+    baseTokenProgram: Address,
+    quoteTokenProgram: Address,
+    systemProgram: Address
+};
+
+/**
+ * Creates a mock vault instruction providing liquidity to or withdrawing liquidity from a constant product AMM.
+ * @param {MockVaultLiquidityAccounts} accounts - The accounts of the instruction, in the CPMM program order.
+ * @param {"provide" | "withdraw"} kind - The vault instruction to create.
+ * @param {bigint[]} amounts - The base and quote liquidity to provide, or the LP tokens to withdraw.
+ * @returns {IInstruction} - The mock vault instruction.
+ */
+export const getMockVaultLiquidityInstruction = (accounts: MockVaultLiquidityAccounts, kind: "provide" | "withdraw", amounts: bigint[]): IInstruction => {
+    const discriminator = kind === "provide" ? [221, 83, 15, 115, 142, 64, 245, 37] : [183, 18, 70, 156, 148, 109, 161, 34];
+    const writable = ["vaultAuthority", "lpMint", "vaultBaseAccount", "vaultQuoteAccount", "vaultLpAccount", "cpAmm", "cpAmmBaseVault", "cpAmmQuoteVault"];
+    return {
+        programAddress: MOCK_VAULT_PROGRAM_ADDRESS,
+        accounts: [
+            ...Object.entries(accounts).map(([name, accountAddress]) => ({
+                address: accountAddress,
+                role: writable.includes(name) ? AccountRole.WRITABLE : AccountRole.READONLY
+            })),
+            {address: program.CPMM_PROGRAM_ADDRESS, role: AccountRole.READONLY}
+        ],
+        data: new Uint8Array([
+            ...discriminator,
+            ...amounts.flatMap((amount) => [...getU64Encoder({ endian: Endian.Little }).encode(amount)])
+        ])
+    };
 };
\ No newline at end of file